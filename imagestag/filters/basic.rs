@@ -4,26 +4,24 @@
 //! - Threshold
 //! - Invert
 //! - Alpha premultiplication
+//! - Channel split, merge, swizzle and replace (any element type)
+//! - Alpha masks: from luminance, threshold, color to alpha
+//! - Image arithmetic: add, subtract, multiply, divide, difference, min, max
 
-use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3};
+use std::fmt;
+
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3, Axis};
+
+use super::grayscale::LumaStandard;
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-/// Apply binary threshold to grayscale image.
-///
-/// # Arguments
-/// * `image` - 2D grayscale image (u8)
-/// * `threshold` - Threshold value (0-255)
-///
-/// # Returns
-/// Binary image where pixels >= threshold become 255, others become 0
-#[pyfunction]
-pub fn threshold_gray<'py>(
-    py: Python<'py>,
-    image: PyReadonlyArray2<'py, u8>,
-    threshold: u8,
-) -> Bound<'py, PyArray2<u8>> {
-    let input = image.as_array();
+/// Binary threshold of a grayscale image: pixels >= `threshold` become 255, others 0.
+pub fn threshold_gray_u8(input: ArrayView2<u8>, threshold: u8) -> Array2<u8> {
     let (height, width) = (input.shape()[0], input.shape()[1]);
 
     let mut result = Array2::<u8>::zeros((height, width));
@@ -34,18 +32,11 @@ pub fn threshold_gray<'py>(
         }
     }
 
-    result.into_pyarray(py)
+    result
 }
 
-/// Invert colors in RGBA image.
-///
-/// Inverts RGB channels, preserves alpha.
-#[pyfunction]
-pub fn invert_rgba<'py>(
-    py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
+/// Invert the RGB channels of an RGBA image, preserving alpha.
+pub fn invert_rgba_u8(input: ArrayView3<u8>) -> Array3<u8> {
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
 
     let mut result = Array3::<u8>::zeros((height, width, channels));
@@ -59,21 +50,11 @@ pub fn invert_rgba<'py>(
         }
     }
 
-    result.into_pyarray(py)
+    result
 }
 
-/// Premultiply alpha in RGBA image.
-///
-/// Converts from straight alpha to premultiplied alpha:
-/// RGB_out = RGB_in * A / 255
-///
-/// This is useful for compositing operations.
-#[pyfunction]
-pub fn premultiply_alpha<'py>(
-    py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
+/// Convert an RGBA image from straight to premultiplied alpha (RGB * A / 255).
+pub fn premultiply_alpha_u8(input: ArrayView3<u8>) -> Array3<u8> {
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
 
     let mut result = Array3::<u8>::zeros((height, width, channels));
@@ -103,21 +84,11 @@ pub fn premultiply_alpha<'py>(
         }
     }
 
-    result.into_pyarray(py)
+    result
 }
 
-/// Unpremultiply alpha in RGBA image.
-///
-/// Converts from premultiplied alpha back to straight alpha:
-/// RGB_out = RGB_in * 255 / A
-///
-/// This is needed before saving to formats that expect straight alpha.
-#[pyfunction]
-pub fn unpremultiply_alpha<'py>(
-    py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
+/// Convert an RGBA image from premultiplied back to straight alpha (RGB * 255 / A).
+pub fn unpremultiply_alpha_u8(input: ArrayView3<u8>) -> Array3<u8> {
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
 
     let mut result = Array3::<u8>::zeros((height, width, channels));
@@ -147,5 +118,746 @@ pub fn unpremultiply_alpha<'py>(
         }
     }
 
-    result.into_pyarray(py)
+    result
+}
+
+// ============================================================================
+// Channel Operations
+// ============================================================================
+
+/// Error returned by the channel operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelError {
+    /// Planes don't all have the same (height, width) as the image
+    ShapeMismatch,
+    /// Result would have no channels or more than 4
+    ChannelCount(usize),
+    /// Channel index or order entry outside the image
+    InvalidChannel(usize),
+    /// Order string contains something other than r, g, b, a
+    InvalidOrder(String),
+    /// Second operand has this many channels (second) where the first has 1 or the first count
+    ChannelMismatch(usize, usize),
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChannelError::ShapeMismatch => write!(f, "channel planes must match the image size"),
+            ChannelError::ChannelCount(n) => write!(f, "images have 1 to 4 channels, got {}", n),
+            ChannelError::InvalidChannel(c) => write!(f, "channel {} does not exist", c),
+            ChannelError::InvalidOrder(order) => write!(f, "invalid channel order '{}' (expected letters r, g, b, a)", order),
+            ChannelError::ChannelMismatch(expected, got) => {
+                write!(f, "second image has {} channels, expected 1 or {}", got, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+/// Split an image into one (height, width) plane per channel.
+pub fn split_channels<T: Copy>(input: ArrayView3<T>) -> Vec<Array2<T>> {
+    input.axis_iter(Axis(2)).map(|plane| plane.to_owned()).collect()
+}
+
+/// Interleave 1-4 planes of equal size into an image, e.g. `[r, g, b, a]`.
+pub fn merge_channels<T: Copy>(planes: &[ArrayView2<T>]) -> Result<Array3<T>, ChannelError> {
+    if planes.is_empty() || planes.len() > 4 {
+        return Err(ChannelError::ChannelCount(planes.len()));
+    }
+    let (height, width) = planes[0].dim();
+    if planes.iter().any(|plane| plane.dim() != (height, width)) {
+        return Err(ChannelError::ShapeMismatch);
+    }
+    Ok(Array3::from_shape_fn((height, width, planes.len()), |(y, x, c)| planes[c][[y, x]]))
+}
+
+/// Parse a channel order such as "bgra" or "rgb" into source indices.
+///
+/// r, g, b, a stand for channels 0-3; a single-channel image may also use
+/// "l" for its only channel.
+pub fn parse_channel_order(order: &str) -> Result<Vec<usize>, ChannelError> {
+    order
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'r' | 'l' => Ok(0),
+            'g' => Ok(1),
+            'b' => Ok(2),
+            'a' => Ok(3),
+            _ => Err(ChannelError::InvalidOrder(order.to_string())),
+        })
+        .collect()
+}
+
+/// Reorder, duplicate or drop channels.
+///
+/// Output channel `i` is input channel `order[i]`, so `[2, 1, 0, 3]` swaps
+/// RGBA to BGRA, `[0, 1, 2]` drops alpha and `[3, 3, 3]` shows alpha as gray.
+pub fn swap_channels<T: Copy>(input: ArrayView3<T>, order: &[usize]) -> Result<Array3<T>, ChannelError> {
+    let (height, width, channels) = input.dim();
+    if order.is_empty() || order.len() > 4 {
+        return Err(ChannelError::ChannelCount(order.len()));
+    }
+    if let Some(&bad) = order.iter().find(|&&c| c >= channels) {
+        return Err(ChannelError::InvalidChannel(bad));
+    }
+    Ok(Array3::from_shape_fn((height, width, order.len()), |(y, x, c)| input[[y, x, order[c]]]))
+}
+
+/// Replace channel `index` with `plane`.
+///
+/// `index` equal to the channel count appends the plane as a new channel,
+/// e.g. to add an alpha mask to an RGB image.
+pub fn set_channel<T: Copy>(input: ArrayView3<T>, index: usize, plane: ArrayView2<T>) -> Result<Array3<T>, ChannelError> {
+    let (height, width, channels) = input.dim();
+    if plane.dim() != (height, width) {
+        return Err(ChannelError::ShapeMismatch);
+    }
+    if index > channels {
+        return Err(ChannelError::InvalidChannel(index));
+    }
+    let out_channels = channels.max(index + 1);
+    if out_channels > 4 {
+        return Err(ChannelError::ChannelCount(out_channels));
+    }
+    Ok(Array3::from_shape_fn((height, width, out_channels), |(y, x, c)| {
+        if c == index {
+            plane[[y, x]]
+        } else {
+            input[[y, x, c]]
+        }
+    }))
+}
+
+// ============================================================================
+// Alpha Utilities
+// ============================================================================
+//
+// Results are always RGBA: grayscale input is replicated into R, G and B,
+// missing alpha counts as opaque.
+
+/// Pixel (y, x) as RGBA 0.0-1.0, from 1, 3 or 4 channels.
+#[inline]
+fn rgba_at<T: Copy>(input: &ArrayView3<T>, y: usize, x: usize, to_unit: impl Fn(T) -> f32) -> [f32; 4] {
+    let channels = input.dim().2;
+    let v = |c: usize| to_unit(input[[y, x, c]]);
+    match channels {
+        1 | 2 => [v(0), v(0), v(0), if channels == 2 { v(1) } else { 1.0 }],
+        3 => [v(0), v(1), v(2), 1.0],
+        _ => [v(0), v(1), v(2), v(3)],
+    }
+}
+
+/// Map every pixel through `op` on RGBA 0.0-1.0 values.
+fn map_rgba<T: Copy>(
+    input: ArrayView3<T>,
+    to_unit: impl Fn(T) -> f32,
+    from_unit: impl Fn(f32) -> T,
+    op: impl Fn([f32; 4]) -> [f32; 4],
+) -> Array3<T> {
+    let (height, width, _) = input.dim();
+    let mut output = Array3::from_elem((height, width, 4), from_unit(0.0));
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = op(rgba_at(&input, y, x, &to_unit));
+            for (c, value) in pixel.into_iter().enumerate() {
+                output[[y, x, c]] = from_unit(value);
+            }
+        }
+    }
+    output
+}
+
+fn u8_to_unit(v: u8) -> f32 {
+    v as f32 / 255.0
+}
+
+fn unit_to_u8(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn alpha_from_luminance(pixel: [f32; 4], invert: bool) -> [f32; 4] {
+    let [r, g, b, _] = pixel;
+    let luma = LumaStandard::default().luma(r, g, b).clamp(0.0, 1.0);
+    [r, g, b, if invert { 1.0 - luma } else { luma }]
+}
+
+/// GIMP-style color to alpha: the lowest alpha for which the pixel is the
+/// un-premultiplied blend of some color over `color`.
+///
+/// Alpha up to `tolerance` becomes fully transparent; the rest is stretched
+/// to 0.0-1.0. The original alpha is multiplied in.
+fn color_to_alpha(pixel: [f32; 4], color: [f32; 3], tolerance: f32) -> [f32; 4] {
+    let mut alpha = 0.0f32;
+    for c in 0..3 {
+        let (p, k) = (pixel[c], color[c]);
+        let a = if p > k {
+            (p - k) / (1.0 - k).max(f32::EPSILON)
+        } else if p < k {
+            (k - p) / k.max(f32::EPSILON)
+        } else {
+            0.0
+        };
+        alpha = alpha.max(a);
+    }
+    let tolerance = tolerance.clamp(0.0, 1.0);
+    let alpha = if tolerance >= 1.0 { 0.0 } else { ((alpha - tolerance) / (1.0 - tolerance)).clamp(0.0, 1.0) };
+    if alpha <= 0.0 {
+        return [color[0], color[1], color[2], 0.0];
+    }
+    let unblend = |c: usize| ((pixel[c] - color[c]) / alpha + color[c]).clamp(0.0, 1.0);
+    [unblend(0), unblend(1), unblend(2), alpha * pixel[3]]
+}
+
+/// Use the luminance (BT.709) as alpha - u8 version.
+///
+/// Bright areas become opaque, dark areas transparent; `invert` swaps that.
+/// Color is kept, any existing alpha is replaced. Returns RGBA.
+pub fn alpha_from_luminance_u8(input: ArrayView3<u8>, invert: bool) -> Array3<u8> {
+    map_rgba(input, u8_to_unit, unit_to_u8, |pixel| alpha_from_luminance(pixel, invert))
+}
+
+/// Use the luminance (BT.709) as alpha - f32 version.
+pub fn alpha_from_luminance_f32(input: ArrayView3<f32>, invert: bool) -> Array3<f32> {
+    map_rgba(input, |v| v, |v| v, |pixel| alpha_from_luminance(pixel, invert))
+}
+
+/// Binarize alpha - u8 version: alpha >= `cutoff` becomes 255, the rest 0.
+///
+/// Returns RGBA; input without alpha is fully opaque.
+pub fn threshold_alpha_u8(input: ArrayView3<u8>, cutoff: u8) -> Array3<u8> {
+    let cutoff = u8_to_unit(cutoff);
+    map_rgba(input, u8_to_unit, unit_to_u8, |[r, g, b, a]| [r, g, b, if a >= cutoff { 1.0 } else { 0.0 }])
+}
+
+/// Binarize alpha - f32 version: alpha >= `cutoff` becomes 1.0, the rest 0.0.
+pub fn threshold_alpha_f32(input: ArrayView3<f32>, cutoff: f32) -> Array3<f32> {
+    map_rgba(input, |v| v, |v| v, |[r, g, b, a]| [r, g, b, if a >= cutoff { 1.0 } else { 0.0 }])
+}
+
+/// Knock out `color` (GIMP "Color to Alpha") - u8 version.
+///
+/// Pixels of exactly `color` become transparent, others partially so, with
+/// their color un-blended from `color` so they composite back to the
+/// original over it. `tolerance` (0-255) makes near matches fully
+/// transparent too. Returns RGBA.
+pub fn color_to_alpha_u8(input: ArrayView3<u8>, color: [u8; 3], tolerance: u8) -> Array3<u8> {
+    let color = color.map(u8_to_unit);
+    let tolerance = u8_to_unit(tolerance);
+    map_rgba(input, u8_to_unit, unit_to_u8, |pixel| color_to_alpha(pixel, color, tolerance))
+}
+
+/// Knock out `color` (GIMP "Color to Alpha") - f32 version.
+///
+/// `color` and `tolerance` are 0.0-1.0.
+pub fn color_to_alpha_f32(input: ArrayView3<f32>, color: [f32; 3], tolerance: f32) -> Array3<f32> {
+    map_rgba(input, |v| v, |v| v, |pixel| color_to_alpha(pixel, color, tolerance))
+}
+
+// ============================================================================
+// Image Arithmetic
+// ============================================================================
+
+/// Per-pixel operation of [`image_arithmetic_f32`] (Photoshop Apply Image /
+/// Calculations blending modes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArithmeticOp {
+    /// a + b
+    #[default]
+    Add,
+    /// a - b
+    Subtract,
+    /// a * b (normalized, white is neutral)
+    Multiply,
+    /// a / b (normalized, dividing by black gives white)
+    Divide,
+    /// |a - b|
+    Difference,
+    /// min(a, b) (darker)
+    Min,
+    /// max(a, b) (lighter)
+    Max,
+}
+
+impl ArithmeticOp {
+    /// Parse an operation name ("add", "subtract", "multiply", "divide", "difference", "min", "max").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "add" => Some(ArithmeticOp::Add),
+            "subtract" | "sub" => Some(ArithmeticOp::Subtract),
+            "multiply" | "mul" => Some(ArithmeticOp::Multiply),
+            "divide" | "div" => Some(ArithmeticOp::Divide),
+            "difference" | "diff" => Some(ArithmeticOp::Difference),
+            "min" | "darker" | "darken" => Some(ArithmeticOp::Min),
+            "max" | "lighter" | "lighten" => Some(ArithmeticOp::Max),
+            _ => None,
+        }
+    }
+
+    /// Combine two values where `full` is white (255.0 or 1.0).
+    #[inline]
+    fn apply(self, a: f32, b: f32, full: f32) -> f32 {
+        match self {
+            ArithmeticOp::Add => a + b,
+            ArithmeticOp::Subtract => a - b,
+            ArithmeticOp::Multiply => a * b / full,
+            ArithmeticOp::Divide if b > 0.0 => a * full / b,
+            ArithmeticOp::Divide => if a > 0.0 { full } else { 0.0 },
+            ArithmeticOp::Difference => (a - b).abs(),
+            ArithmeticOp::Min => a.min(b),
+            ArithmeticOp::Max => a.max(b),
+        }
+    }
+}
+
+/// Apply `op` pixel by pixel, then `/ scale + offset`, then `finish`.
+///
+/// `b` may be a single-channel mask, which is used for every channel. The
+/// alpha of an RGBA `a` is kept.
+fn arithmetic<T: Copy>(
+    a: ArrayView3<T>,
+    b: ArrayView3<T>,
+    op: ArithmeticOp,
+    (scale, offset, full): (f32, f32, f32),
+    to_f32: impl Fn(T) -> f32,
+    finish: impl Fn(f32) -> T,
+) -> Result<Array3<T>, ChannelError> {
+    let (height, width, channels) = a.dim();
+    let (b_height, b_width, b_channels) = b.dim();
+    if (b_height, b_width) != (height, width) {
+        return Err(ChannelError::ShapeMismatch);
+    }
+    if b_channels != 1 && b_channels != channels {
+        return Err(ChannelError::ChannelMismatch(channels, b_channels));
+    }
+    let scale = if scale.abs() > f32::EPSILON { scale } else { 1.0 };
+    Ok(Array3::from_shape_fn((height, width, channels), |(y, x, c)| {
+        if channels == 4 && c == 3 {
+            return a[[y, x, 3]];
+        }
+        let bv = b[[y, x, if b_channels == 1 { 0 } else { c }]];
+        finish(op.apply(to_f32(a[[y, x, c]]), to_f32(bv), full) / scale + offset)
+    }))
+}
+
+/// Combine two images pixel by pixel - u8 version.
+///
+/// # Arguments
+/// * `a` - First image with 1, 3, or 4 channels
+/// * `b` - Second image of the same size, with as many channels or a single-channel mask
+/// * `op` - Operation
+/// * `scale` - Divisor applied to the result (1.0 = none, 2.0 averages an add)
+/// * `offset` - Added after scaling, in 0-255 units
+/// * `clamp` - Saturate to 0-255; otherwise results wrap around modulo 256
+///
+/// # Returns
+/// Image with the shape of `a`; alpha of an RGBA `a` is kept
+pub fn image_arithmetic_u8(
+    a: ArrayView3<u8>,
+    b: ArrayView3<u8>,
+    op: ArithmeticOp,
+    scale: f32,
+    offset: f32,
+    clamp: bool,
+) -> Result<Array3<u8>, ChannelError> {
+    let finish = |v: f32| {
+        let v = v.round();
+        if clamp { v.clamp(0.0, 255.0) as u8 } else { (v as i64).rem_euclid(256) as u8 }
+    };
+    arithmetic(a, b, op, (scale, offset, 255.0), |v| v as f32, finish)
+}
+
+/// Combine two images pixel by pixel - f32 version.
+///
+/// # Arguments
+/// * `offset` - Added after scaling, in 0.0-1.0 units
+/// * `clamp` - Clamp to 0.0-1.0; otherwise out-of-range values are kept
+pub fn image_arithmetic_f32(
+    a: ArrayView3<f32>,
+    b: ArrayView3<f32>,
+    op: ArithmeticOp,
+    scale: f32,
+    offset: f32,
+    clamp: bool,
+) -> Result<Array3<f32>, ChannelError> {
+    arithmetic(a, b, op, (scale, offset, 1.0), |v| v, |v| if clamp { v.clamp(0.0, 1.0) } else { v })
+}
+
+/// Apply binary threshold to grayscale image.
+///
+/// # Arguments
+/// * `image` - 2D grayscale image (u8)
+/// * `threshold` - Threshold value (0-255)
+///
+/// # Returns
+/// Binary image where pixels >= threshold become 255, others become 0
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn threshold_gray<'py>(
+    py: Python<'py>,
+    image: PyHostArray2<'py, u8>,
+    threshold: u8,
+) -> Bound<'py, PyArray2<u8>> {
+    threshold_gray_u8(image.as_array(), threshold).into_pyarray(py)
+}
+
+/// Invert colors in RGBA image.
+///
+/// Inverts RGB channels, preserves alpha.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn invert_rgba<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+) -> Bound<'py, PyArray3<u8>> {
+    invert_rgba_u8(image.as_array()).into_pyarray(py)
+}
+
+/// Premultiply alpha in RGBA image.
+///
+/// Converts from straight alpha to premultiplied alpha:
+/// RGB_out = RGB_in * A / 255
+///
+/// This is useful for compositing operations.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn premultiply_alpha<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+) -> Bound<'py, PyArray3<u8>> {
+    premultiply_alpha_u8(image.as_array()).into_pyarray(py)
+}
+
+/// Unpremultiply alpha in RGBA image.
+///
+/// Converts from premultiplied alpha back to straight alpha:
+/// RGB_out = RGB_in * 255 / A
+///
+/// This is needed before saving to formats that expect straight alpha.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn unpremultiply_alpha<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+) -> Bound<'py, PyArray3<u8>> {
+    unpremultiply_alpha_u8(image.as_array()).into_pyarray(py)
+}
+
+#[cfg(feature = "python")]
+fn channel_error(e: ChannelError) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(e.to_string())
+}
+
+/// Channel order from a string ("bgra") or a list of channel indices.
+#[cfg(feature = "python")]
+fn extract_channel_order(order: &Bound<'_, PyAny>) -> PyResult<Vec<usize>> {
+    match order.extract::<String>() {
+        Ok(text) => parse_channel_order(&text).map_err(channel_error),
+        Err(_) => order.extract::<Vec<usize>>(),
+    }
+}
+
+#[cfg(feature = "python")]
+fn split_channels_impl<'py, T: numpy::Element + Copy>(image: PyHostArray3<'py, T>) -> Vec<Bound<'py, PyArray2<T>>> {
+    let py = image.py();
+    split_channels(image.as_array()).into_iter().map(|plane| plane.into_pyarray(py)).collect()
+}
+
+#[cfg(feature = "python")]
+fn merge_channels_impl<'py, T: numpy::Element + Copy>(
+    planes: &[Option<PyHostArray2<'py, T>>],
+) -> PyResult<Bound<'py, PyArray3<T>>> {
+    let planes: Vec<_> = planes.iter().flatten().collect();
+    let views: Vec<_> = planes.iter().map(|plane| plane.as_array()).collect();
+    let result = merge_channels(&views).map_err(channel_error)?;
+    Ok(result.into_pyarray(planes[0].py()))
+}
+
+/// Split an image (H, W, C) into a list of C planes (H, W).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "split_channels")]
+pub fn split_channels_py<'py>(image: PyHostArray3<'py, u8>) -> Vec<Bound<'py, PyArray2<u8>>> {
+    split_channels_impl(image)
+}
+
+/// Split an f32 image (H, W, C) into a list of C planes (H, W).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "split_channels_f32")]
+pub fn split_channels_f32_py<'py>(image: PyHostArray3<'py, f32>) -> Vec<Bound<'py, PyArray2<f32>>> {
+    split_channels_impl(image)
+}
+
+/// Merge planes (H, W) into an RGB image, or RGBA when `a` is given.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "merge_channels", signature = (r, g, b, a=None))]
+pub fn merge_channels_py<'py>(
+    r: PyHostArray2<'py, u8>,
+    g: PyHostArray2<'py, u8>,
+    b: PyHostArray2<'py, u8>,
+    a: Option<PyHostArray2<'py, u8>>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    merge_channels_impl(&[Some(r), Some(g), Some(b), a])
+}
+
+/// Merge f32 planes (H, W) into an RGB image, or RGBA when `a` is given.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "merge_channels_f32", signature = (r, g, b, a=None))]
+pub fn merge_channels_f32_py<'py>(
+    r: PyHostArray2<'py, f32>,
+    g: PyHostArray2<'py, f32>,
+    b: PyHostArray2<'py, f32>,
+    a: Option<PyHostArray2<'py, f32>>,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    merge_channels_impl(&[Some(r), Some(g), Some(b), a])
+}
+
+/// Reorder, duplicate or drop channels.
+///
+/// `order` is a string such as "bgra" or "rgb", or a list of source
+/// channel indices.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "swap_channels")]
+pub fn swap_channels_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    order: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let result = swap_channels(image.as_array(), &extract_channel_order(order)?).map_err(channel_error)?;
+    Ok(result.into_pyarray(image.py()))
+}
+
+/// Reorder, duplicate or drop channels of an f32 image.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "swap_channels_f32")]
+pub fn swap_channels_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    order: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = swap_channels(image.as_array(), &extract_channel_order(order)?).map_err(channel_error)?;
+    Ok(result.into_pyarray(image.py()))
+}
+
+/// Replace channel `index` with `plane` (H, W); `index` equal to the
+/// channel count appends a channel.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "set_channel")]
+pub fn set_channel_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    index: usize,
+    plane: PyHostArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let result = set_channel(image.as_array(), index, plane.as_array()).map_err(channel_error)?;
+    Ok(result.into_pyarray(image.py()))
+}
+
+/// Replace channel `index` of an f32 image with `plane` (H, W).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "set_channel_f32")]
+pub fn set_channel_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    index: usize,
+    plane: PyHostArray2<'py, f32>,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = set_channel(image.as_array(), index, plane.as_array()).map_err(channel_error)?;
+    Ok(result.into_pyarray(image.py()))
+}
+
+/// Use the luminance as alpha (u8); bright = opaque unless `invert`.
+///
+/// Returns RGBA with the color kept.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "alpha_from_luminance", signature = (image, invert=false))]
+pub fn alpha_from_luminance_py<'py>(image: PyHostArray3<'py, u8>, invert: bool) -> Bound<'py, PyArray3<u8>> {
+    alpha_from_luminance_u8(image.as_array(), invert).into_pyarray(image.py())
+}
+
+/// Use the luminance as alpha (f32); bright = opaque unless `invert`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "alpha_from_luminance_f32", signature = (image, invert=false))]
+pub fn alpha_from_luminance_f32_py<'py>(image: PyHostArray3<'py, f32>, invert: bool) -> Bound<'py, PyArray3<f32>> {
+    alpha_from_luminance_f32(image.as_array(), invert).into_pyarray(image.py())
+}
+
+/// Binarize alpha (u8): alpha >= `cutoff` becomes 255, the rest 0.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "threshold_alpha", signature = (image, cutoff=128))]
+pub fn threshold_alpha_py<'py>(image: PyHostArray3<'py, u8>, cutoff: u8) -> Bound<'py, PyArray3<u8>> {
+    threshold_alpha_u8(image.as_array(), cutoff).into_pyarray(image.py())
+}
+
+/// Binarize alpha (f32): alpha >= `cutoff` becomes 1.0, the rest 0.0.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "threshold_alpha_f32", signature = (image, cutoff=0.5))]
+pub fn threshold_alpha_f32_py<'py>(image: PyHostArray3<'py, f32>, cutoff: f32) -> Bound<'py, PyArray3<f32>> {
+    threshold_alpha_f32(image.as_array(), cutoff).into_pyarray(image.py())
+}
+
+/// Knock out a background color, GIMP "Color to Alpha" style (u8).
+///
+/// # Arguments
+/// * `image` - Image with 1, 3 or 4 channels
+/// * `color` - (r, g, b) color to make transparent, 0-255
+/// * `tolerance` - Near matches up to this distance (0-255) become fully transparent
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "color_to_alpha", signature = (image, color=(255, 255, 255), tolerance=0))]
+pub fn color_to_alpha_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    color: (u8, u8, u8),
+    tolerance: u8,
+) -> Bound<'py, PyArray3<u8>> {
+    color_to_alpha_u8(image.as_array(), [color.0, color.1, color.2], tolerance).into_pyarray(image.py())
+}
+
+/// Knock out a background color, GIMP "Color to Alpha" style (f32, 0.0-1.0).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "color_to_alpha_f32", signature = (image, color=(1.0, 1.0, 1.0), tolerance=0.0))]
+pub fn color_to_alpha_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    color: (f32, f32, f32),
+    tolerance: f32,
+) -> Bound<'py, PyArray3<f32>> {
+    color_to_alpha_f32(image.as_array(), [color.0, color.1, color.2], tolerance).into_pyarray(image.py())
+}
+
+#[cfg(feature = "python")]
+fn parse_arithmetic_op(name: &str) -> PyResult<ArithmeticOp> {
+    ArithmeticOp::from_name(name)
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown arithmetic operation '{}'", name)))
+}
+
+/// Combine two uint8 images pixel by pixel (Photoshop Apply Image / Calculations).
+///
+/// # Arguments
+/// * `a` - First image with 1, 3 or 4 channels
+/// * `b` - Second image of the same size, same channel count or a single-channel mask
+/// * `op` - "add", "subtract", "multiply", "divide", "difference", "min" or "max"
+/// * `scale` - Divisor of the result
+/// * `offset` - Added after scaling, 0-255 units
+/// * `clamp` - Saturate to 0-255 (otherwise wrap modulo 256)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "image_arithmetic", signature = (a, b, op="add", scale=1.0, offset=0.0, clamp=true))]
+pub fn image_arithmetic_py<'py>(
+    a: PyHostArray3<'py, u8>,
+    b: PyHostArray3<'py, u8>,
+    op: &str,
+    scale: f32,
+    offset: f32,
+    clamp: bool,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let result = image_arithmetic_u8(a.as_array(), b.as_array(), parse_arithmetic_op(op)?, scale, offset, clamp).map_err(channel_error)?;
+    Ok(result.into_pyarray(a.py()))
+}
+
+/// Combine two float32 images pixel by pixel; `offset` is in 0.0-1.0 units and
+/// `clamp=False` keeps out-of-range values.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "image_arithmetic_f32", signature = (a, b, op="add", scale=1.0, offset=0.0, clamp=true))]
+pub fn image_arithmetic_f32_py<'py>(
+    a: PyHostArray3<'py, f32>,
+    b: PyHostArray3<'py, f32>,
+    op: &str,
+    scale: f32,
+    offset: f32,
+    clamp: bool,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = image_arithmetic_f32(a.as_array(), b.as_array(), parse_arithmetic_op(op)?, scale, offset, clamp).map_err(channel_error)?;
+    Ok(result.into_pyarray(a.py()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_roundtrip() {
+        let img = Array3::from_shape_fn((2, 3, 4), |(y, x, c)| (y * 100 + x * 10 + c) as u8);
+        let planes = split_channels(img.view());
+        assert_eq!(planes.len(), 4);
+        assert_eq!(planes[2][[1, 2]], 122);
+
+        let views: Vec<_> = planes.iter().map(|p| p.view()).collect();
+        assert_eq!(merge_channels(&views).unwrap(), img);
+        assert_eq!(merge_channels(&views[..0]), Err(ChannelError::ChannelCount(0)));
+
+        let bgr = swap_channels(img.view(), &parse_channel_order("bgr").unwrap()).unwrap();
+        assert_eq!(bgr.dim(), (2, 3, 3));
+        assert_eq!(bgr[[1, 2, 0]], 122);
+        assert_eq!(swap_channels(bgr.view(), &[3]), Err(ChannelError::InvalidChannel(3)));
+        assert!(parse_channel_order("rgbx").is_err());
+
+        // Appending a plane to RGB gives RGBA
+        let rgba = set_channel(bgr.view(), 3, planes[0].view()).unwrap();
+        assert_eq!(rgba[[1, 2, 3]], 120);
+        assert_eq!(set_channel(bgr.view(), 5, planes[0].view()), Err(ChannelError::InvalidChannel(5)));
+        let small = Array2::<u8>::zeros((1, 1));
+        assert_eq!(set_channel(bgr.view(), 0, small.view()), Err(ChannelError::ShapeMismatch));
+    }
+
+    #[test]
+    fn test_alpha_utilities() {
+        let img = Array3::from_shape_vec((1, 3, 3), vec![255, 255, 255, 0, 0, 0, 255, 128, 128]).unwrap();
+
+        let mask = alpha_from_luminance_u8(img.view(), false);
+        assert_eq!(mask.dim(), (1, 3, 4));
+        assert_eq!((mask[[0, 0, 3]], mask[[0, 1, 3]]), (255, 0));
+        assert_eq!(alpha_from_luminance_u8(img.view(), true)[[0, 0, 3]], 0);
+
+        let hard = threshold_alpha_u8(mask.view(), 128);
+        assert!(hard.iter().skip(3).step_by(4).all(|&a| a == 0 || a == 255));
+
+        // White knocked out; pink over white un-blends to opaque-ish red
+        let knocked = color_to_alpha_u8(img.view(), [255, 255, 255], 0);
+        assert_eq!(knocked[[0, 0, 3]], 0);
+        assert_eq!(knocked[[0, 1, 3]], 255);
+        assert_eq!((knocked[[0, 2, 0]], knocked[[0, 2, 1]], knocked[[0, 2, 3]]), (255, 0, 127));
+        // Compositing back over white restores the pixel
+        let back = 255.0 + (knocked[[0, 2, 1]] as f32 - 255.0) * knocked[[0, 2, 3]] as f32 / 255.0;
+        assert!((back - 128.0).abs() <= 1.0);
+        // Tolerance removes near matches
+        let near = Array3::from_elem((1, 1, 3), 250u8);
+        assert_eq!(color_to_alpha_u8(near.view(), [255, 255, 255], 10)[[0, 0, 3]], 0);
+        assert!(color_to_alpha_u8(near.view(), [255, 255, 255], 0)[[0, 0, 3]] > 0);
+    }
+
+    #[test]
+    fn test_image_arithmetic() {
+        let a = Array3::from_shape_vec((1, 2, 4), vec![200, 100, 50, 255, 10, 0, 255, 128]).unwrap();
+        let b = Array3::from_shape_vec((1, 2, 4), vec![100, 100, 100, 0, 20, 255, 0, 0]).unwrap();
+
+        // Saturating vs wrapping add; alpha of `a` is kept
+        let sum = image_arithmetic_u8(a.view(), b.view(), ArithmeticOp::Add, 1.0, 0.0, true).unwrap();
+        assert_eq!(sum.slice(ndarray::s![0, 0, ..]).to_vec(), vec![255, 200, 150, 255]);
+        let wrapped = image_arithmetic_u8(a.view(), b.view(), ArithmeticOp::Add, 1.0, 0.0, false).unwrap();
+        assert_eq!(wrapped[[0, 0, 0]], 44);
+        // Photoshop-style subtract with offset 128 shows differences around gray
+        let sub = image_arithmetic_u8(a.view(), b.view(), ArithmeticOp::Subtract, 2.0, 128.0, true).unwrap();
+        assert_eq!((sub[[0, 0, 0]], sub[[0, 0, 1]], sub[[0, 1, 0]]), (178, 128, 123));
+        let mul = image_arithmetic_u8(a.view(), b.view(), ArithmeticOp::Multiply, 1.0, 0.0, true).unwrap();
+        assert_eq!((mul[[0, 1, 1]], mul[[0, 0, 0]]), (0, 78));
+        let div = image_arithmetic_u8(a.view(), b.view(), ArithmeticOp::from_name("divide").unwrap(), 1.0, 0.0, true).unwrap();
+        assert_eq!((div[[0, 0, 2]], div[[0, 1, 2]]), (128, 255));
+
+        // Single-channel mask applies to every channel; f32 keeps out-of-range values unclamped
+        let mask = Array3::from_shape_vec((1, 2, 1), vec![0.5f32, 2.0]).unwrap();
+        let af = a.mapv(|v| v as f32 / 255.0);
+        let scaled = image_arithmetic_f32(af.view(), mask.view(), ArithmeticOp::Multiply, 1.0, 0.0, false).unwrap();
+        assert!((scaled[[0, 1, 2]] - 2.0).abs() < 1e-6 && (scaled[[0, 0, 0]] - af[[0, 0, 0]] * 0.5).abs() < 1e-6);
+        assert_eq!(image_arithmetic_f32(af.view(), af.slice(ndarray::s![.., .., ..3]), ArithmeticOp::Min, 1.0, 0.0, true), Err(ChannelError::ChannelMismatch(4, 3)));
+    }
 }
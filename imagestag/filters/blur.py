@@ -37,11 +37,13 @@ class GaussianBlur(Filter):
     """Gaussian blur filter.
 
     radius: Blur radius in pixels
+    srgb_linearize: Blur in linear light (keeps saturated edges from darkening)
     """
 
     _native_frameworks: ClassVar[list[ImsFramework]] = [ImsFramework.RAW]
 
     radius: float = 2.0
+    srgb_linearize: bool = False
     _primary_param: ClassVar[str] = 'radius'
 
     def apply(self, image: Image, context: FilterContext | None = None) -> Image:
@@ -50,7 +52,9 @@ class GaussianBlur(Filter):
         has_alpha = image.pixel_format in (PixelFormat.RGBA, PixelFormat.BGRA)
         pf = PixelFormat.RGBA if has_alpha else PixelFormat.RGB
         pixels = image.get_pixels(pf)
-        result = imagestag_rust.gaussian_blur_rgba(pixels, float(self.radius))
+        result = imagestag_rust.gaussian_blur_rgba(
+            pixels, float(self.radius), srgb_linearize=self.srgb_linearize
+        )
         return Img(result, pixel_format=pf)
 
 
@@ -59,11 +63,13 @@ class BoxBlur(Filter):
     """Box (average) blur filter.
 
     radius: Blur radius in pixels
+    srgb_linearize: Blur in linear light (keeps saturated edges from darkening)
     """
 
     _native_frameworks: ClassVar[list[ImsFramework]] = [ImsFramework.RAW]
 
     radius: int = 2
+    srgb_linearize: bool = False
     _primary_param: ClassVar[str] = 'radius'
 
     def apply(self, image: Image, context: FilterContext | None = None) -> Image:
@@ -72,7 +78,9 @@ class BoxBlur(Filter):
         has_alpha = image.pixel_format in (PixelFormat.RGBA, PixelFormat.BGRA)
         pf = PixelFormat.RGBA if has_alpha else PixelFormat.RGB
         pixels = image.get_pixels(pf)
-        result = imagestag_rust.box_blur_rgba(pixels, self.radius)
+        result = imagestag_rust.box_blur_rgba(
+            pixels, self.radius, srgb_linearize=self.srgb_linearize
+        )
         return Img(result, pixel_format=pf)
 
 
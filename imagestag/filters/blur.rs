@@ -13,30 +13,32 @@
 //! This prevents transparent pixels (e.g., white with 0 alpha) from bleeding into
 //! the blur result.
 
-use ndarray::Array3;
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array3, ArrayView3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+#[cfg(feature = "python")]
+use super::blur_wasm::BlurQuality;
+use super::blur_wasm::{blur_premultiplied_f32, blur_premultiplied_u8};
 use super::core::gaussian_kernel_1d;
 
 /// Apply Gaussian blur to RGBA image.
 ///
-/// Uses separable 2-pass convolution for efficiency.
+/// Uses separable 2-pass convolution for efficiency, or the recursive
+/// Gaussian from [`RECURSIVE_MIN_SIGMA`] on.
 /// Uses premultiplied alpha to prevent transparent pixels from bleeding.
 ///
 /// # Arguments
-/// * `image` - RGBA image (height, width, 4) as u8
+/// * `input` - RGBA image (height, width, 4) as u8
 /// * `sigma` - Standard deviation of Gaussian kernel
 ///
 /// # Returns
 /// Blurred RGBA image with same dimensions
-#[pyfunction]
-pub fn gaussian_blur_rgba<'py>(
-    py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
-    sigma: f32,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
+pub fn gaussian_blur_rgba_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
     let has_alpha = channels == 4;
 
@@ -50,7 +52,10 @@ pub fn gaussian_blur_rgba<'py>(
                 }
             }
         }
-        return result.into_pyarray(py);
+        return result;
+    }
+    if sigma >= RECURSIVE_MIN_SIGMA {
+        return recursive_gaussian_blur_u8(input, sigma);
     }
 
     let kernel = gaussian_kernel_1d(sigma);
@@ -141,27 +146,195 @@ pub fn gaussian_blur_rgba<'py>(
     }
 
     // Convert back to u8
-    result.mapv(|v| v.clamp(0.0, 255.0) as u8).into_pyarray(py)
+    result.mapv(|v| v.clamp(0.0, 255.0) as u8)
 }
 
-/// Apply box blur to RGBA image.
+// ============================================================================
+// Recursive Gaussian (large sigma)
+// ============================================================================
+
+/// Sigma from which Gaussian blurs use the recursive filter.
 ///
-/// Faster than Gaussian blur but produces a blockier result.
+/// The kernel of the convolution spans 6 sigma, so its cost grows linearly
+/// with the radius; from here on the recursive filter is several times
+/// faster and its deviation from the true Gaussian is negligible.
+pub const RECURSIVE_MIN_SIGMA: f32 = 32.0;
+
+/// Coefficients of the third-order recursive Gaussian: `(B, [b1, b2, b3] / b0)`.
+///
+/// I. Young and L. van Vliet, "Recursive implementation of the Gaussian
+/// filter", Signal Processing 44 (1995).
+fn recursive_coefficients(sigma: f32) -> (f64, [f64; 3]) {
+    let sigma = sigma as f64;
+    let q = if sigma >= 2.5 { 0.98711 * sigma - 0.96330 } else { 3.97156 - 4.14554 * (1.0 - 0.26891 * sigma).sqrt() };
+    let (q2, q3) = (q * q, q * q * q);
+    let b0 = 1.57825 + 2.44413 * q + 1.4281 * q2 + 0.422205 * q3;
+    let b = [(2.44413 * q + 2.85619 * q2 + 1.26661 * q3) / b0, -(1.4281 * q2 + 1.26661 * q3) / b0, 0.422205 * q3 / b0];
+    (1.0 - b.iter().sum::<f64>(), b)
+}
+
+/// Recursive filter with its right-boundary matrix.
+#[derive(Clone, Copy)]
+struct Recursive {
+    gain: f64,
+    b: [f64; 3],
+    /// Anti-causal start state per deviation of the last causal outputs
+    /// from the edge value (B. Triggs and M. Sdika, "Boundary conditions for
+    /// Young-van Vliet recursive filtering", IEEE TSP 54 (2006)).
+    boundary: [[f64; 3]; 3],
+}
+
+impl Recursive {
+    fn new(sigma: f32) -> Self {
+        let (gain, b) = recursive_coefficients(sigma);
+        // The matrix is linear in the deviations: run the causal filter on
+        // past the end for each unit deviation, then the anti-causal one back
+        let len = (sigma as usize + 1) * 12;
+        let mut boundary = [[0.0; 3]; 3];
+        let mut causal = vec![0.0f64; len];
+        for j in 0..3 {
+            let mut s = [0.0; 3];
+            s[j] = 1.0;
+            for w in causal.iter_mut() {
+                *w = b[0] * s[0] + b[1] * s[1] + b[2] * s[2];
+                s = [*w, s[0], s[1]];
+            }
+            let mut s = [0.0; 3];
+            for (k, &w) in causal.iter().enumerate().rev() {
+                let y = gain * w + b[0] * s[0] + b[1] * s[1] + b[2] * s[2];
+                s = [y, s[0], s[1]];
+                if k < 3 {
+                    boundary[k][j] = y;
+                }
+            }
+        }
+        Self { gain, b, boundary }
+    }
+
+    /// Causal and anti-causal pass along `n` steps of `step` elements.
+    ///
+    /// Each step holds `lanes` contiguous values that are filtered side by
+    /// side (the channels of a pixel, or a whole row for the vertical pass).
+    /// Edge values are repeated beyond both ends. State is kept in f64: for
+    /// large sigma the poles sit close to 1.
+    fn lines(&self, data: &mut [f32], n: usize, step: usize, lanes: usize) {
+        if n == 0 || lanes == 0 {
+            return;
+        }
+        let Self { gain, b, boundary } = *self;
+        let last = (n - 1) * step;
+        let edge: Vec<f64> = data[last..last + lanes].iter().map(|&v| v as f64).collect();
+        let mut state: Vec<[f64; 3]> = data[..lanes].iter().map(|&v| [v as f64; 3]).collect();
+        for k in 0..n {
+            for (v, s) in data[k * step..k * step + lanes].iter_mut().zip(&mut state) {
+                let w = gain * *v as f64 + b[0] * s[0] + b[1] * s[1] + b[2] * s[2];
+                *s = [w, s[0], s[1]];
+                *v = w as f32;
+            }
+        }
+        // `state` now holds the last three causal outputs of each lane
+        for (s, &u) in state.iter_mut().zip(&edge) {
+            let d = [s[0] - u, s[1] - u, s[2] - u];
+            *s = boundary.map(|m| u + m[0] * d[0] + m[1] * d[1] + m[2] * d[2]);
+        }
+        for k in (0..n).rev() {
+            for (v, s) in data[k * step..k * step + lanes].iter_mut().zip(&mut state) {
+                let y = gain * *v as f64 + b[0] * s[0] + b[1] * s[1] + b[2] * s[2];
+                *s = [y, s[0], s[1]];
+                *v = y as f32;
+            }
+        }
+    }
+}
+
+/// Recursive Gaussian blur of an interleaved (height, width, channels)
+/// buffer, in place.
+///
+/// Cost is independent of sigma (a fixed number of multiply-adds per value
+/// and direction), which makes it the method of choice for very large
+/// radii. Edge pixels are repeated like with the Gaussian kernels.
+pub fn recursive_gaussian_in_place(buffer: &mut [f32], width: usize, height: usize, channels: usize, sigma: f32) {
+    if sigma <= 0.0 || width == 0 || height == 0 {
+        return;
+    }
+    let filter = Recursive::new(sigma);
+    let stride = width * channels;
+    for row in buffer.chunks_mut(stride) {
+        filter.lines(row, width, channels, channels);
+    }
+    filter.lines(buffer, height, stride, stride);
+}
+
+/// Recursive Gaussian blur - u8 version.
+///
+/// Premultiplied alpha like `gaussian_blur_rgba_u8`; used automatically
+/// from [`RECURSIVE_MIN_SIGMA`] on.
+pub fn recursive_gaussian_blur_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
+    blur_premultiplied_u8(input, |buffer, width, height, channels| {
+        recursive_gaussian_in_place(buffer, width, height, channels, sigma)
+    })
+}
+
+/// Recursive Gaussian blur - f32 version.
+pub fn recursive_gaussian_blur_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32> {
+    blur_premultiplied_f32(input, |buffer, width, height, channels| {
+        recursive_gaussian_in_place(buffer, width, height, channels, sigma)
+    })
+}
+
+/// Parse a blur quality name, rejecting unknown names.
+#[cfg(feature = "python")]
+pub(crate) fn parse_blur_quality(name: &str) -> PyResult<BlurQuality> {
+    BlurQuality::from_name(name)
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown blur quality '{}'", name)))
+}
+
+/// Apply Gaussian blur to RGBA image.
+///
+/// Uses separable 2-pass convolution for efficiency.
 /// Uses premultiplied alpha to prevent transparent pixels from bleeding.
 ///
 /// # Arguments
 /// * `image` - RGBA image (height, width, 4) as u8
-/// * `radius` - Blur radius in pixels
+/// * `sigma` - Standard deviation of Gaussian kernel
+/// * `srgb_linearize` - Blur in linear light instead of on sRGB values
+/// * `quality` - "exact" or "fast" (three box passes, for interactive previews)
 ///
 /// # Returns
 /// Blurred RGBA image with same dimensions
+#[cfg(feature = "python")]
 #[pyfunction]
-pub fn box_blur_rgba<'py>(
+#[pyo3(signature = (image, sigma, srgb_linearize=false, quality="exact"))]
+pub fn gaussian_blur_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
-    radius: usize,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
+    image: PyHostArray3<'py, u8>,
+    sigma: f32,
+    srgb_linearize: bool,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let quality = parse_blur_quality(quality)?;
+    if srgb_linearize {
+        return Ok(super::blur_wasm::gaussian_blur_linear_u8(image.as_array(), sigma, quality).into_pyarray(py));
+    }
+    let result = match quality {
+        BlurQuality::Exact => gaussian_blur_rgba_u8(image.as_array(), sigma),
+        BlurQuality::Fast => super::blur_wasm::fast_gaussian_blur_u8(image.as_array(), sigma),
+    };
+    Ok(result.into_pyarray(py))
+}
+
+/// Apply box blur to RGBA image.
+///
+/// Faster than Gaussian blur but produces a blockier result.
+/// Uses premultiplied alpha to prevent transparent pixels from bleeding.
+///
+/// # Arguments
+/// * `input` - RGBA image (height, width, 4) as u8
+/// * `radius` - Blur radius in pixels
+///
+/// # Returns
+/// Blurred RGBA image with same dimensions
+pub fn box_blur_rgba_u8(input: ArrayView3<u8>, radius: usize) -> Array3<u8> {
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
     let has_alpha = channels == 4;
 
@@ -175,7 +348,7 @@ pub fn box_blur_rgba<'py>(
                 }
             }
         }
-        return result.into_pyarray(py);
+        return result;
     }
 
     let mut result = Array3::<u8>::zeros((height, width, channels));
@@ -256,33 +429,79 @@ pub fn box_blur_rgba<'py>(
         }
     }
 
-    result.into_pyarray(py)
+    result
+}
+
+/// Apply box blur to RGBA image.
+///
+/// Faster than Gaussian blur but produces a blockier result.
+/// Uses premultiplied alpha to prevent transparent pixels from bleeding.
+///
+/// # Arguments
+/// * `image` - RGBA image (height, width, 4) as u8
+/// * `radius` - Blur radius in pixels
+/// * `srgb_linearize` - Blur in linear light instead of on sRGB values
+///
+/// # Returns
+/// Blurred RGBA image with same dimensions
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, radius, srgb_linearize=false))]
+pub fn box_blur_rgba<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    radius: usize,
+    srgb_linearize: bool,
+) -> Bound<'py, PyArray3<u8>> {
+    if srgb_linearize {
+        return super::blur_wasm::box_blur_linear_u8(image.as_array(), radius as u32).into_pyarray(py);
+    }
+    box_blur_rgba_u8(image.as_array(), radius).into_pyarray(py)
 }
 
 /// Apply Gaussian blur to image (f32).
 ///
-/// Uses premultiplied alpha for RGBA. Values 0.0-1.0.
+/// Uses premultiplied alpha for RGBA. Values 0.0-1.0. With `srgb_linearize`
+/// the values are treated as sRGB and blurred in linear light. `quality` is
+/// "exact" or "fast".
+#[cfg(feature = "python")]
 #[pyfunction]
+#[pyo3(signature = (image, sigma, srgb_linearize=false, quality="exact"))]
 pub fn gaussian_blur_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     sigma: f32,
-) -> Bound<'py, PyArray3<f32>> {
+    srgb_linearize: bool,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
     let input = image.as_array();
-    let result = super::blur_wasm::gaussian_blur_wasm_f32(input, sigma);
-    result.into_pyarray(py)
+    let quality = parse_blur_quality(quality)?;
+    let result = if srgb_linearize {
+        super::blur_wasm::gaussian_blur_linear_f32(input, sigma, quality)
+    } else {
+        super::blur_wasm::gaussian_blur_quality_f32(input, sigma, quality)
+    };
+    Ok(result.into_pyarray(py))
 }
 
 /// Apply box blur to image (f32).
 ///
-/// Uses premultiplied alpha for RGBA. Values 0.0-1.0.
+/// Uses premultiplied alpha for RGBA. Values 0.0-1.0. With `srgb_linearize`
+/// the values are treated as sRGB and blurred in linear light.
+#[cfg(feature = "python")]
 #[pyfunction]
+#[pyo3(signature = (image, radius, srgb_linearize=false))]
 pub fn box_blur_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     radius: u32,
+    srgb_linearize: bool,
 ) -> Bound<'py, PyArray3<f32>> {
     let input = image.as_array();
-    let result = super::blur_wasm::box_blur_wasm_f32(input, radius);
+    let result = if srgb_linearize {
+        super::blur_wasm::box_blur_linear_f32(input, radius)
+    } else {
+        super::blur_wasm::box_blur_wasm_f32(input, radius)
+    };
     result.into_pyarray(py)
 }
@@ -45,64 +45,86 @@ def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
 # Gaussian Blur
 # ============================================================================
 
-def gaussian_blur(image: np.ndarray, sigma: float = 1.0) -> np.ndarray:
+def gaussian_blur(
+    image: np.ndarray,
+    sigma: float = 1.0,
+    srgb_linearize: bool = False,
+    quality: str = "exact",
+) -> np.ndarray:
     """Apply Gaussian blur (u8).
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         sigma: Blur radius in standard deviations (0.1-100.0)
+        srgb_linearize: Blur in linear light instead of on sRGB values
+        quality: "exact", or "fast" for a three-box approximation (interactive
+            previews)
 
     Returns:
         Blurred uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "gaussian_blur")
-    return imagestag_rust.gaussian_blur_rgba(image, sigma)
+    return imagestag_rust.gaussian_blur_rgba(image, sigma, srgb_linearize, quality)
 
 
-def gaussian_blur_f32(image: np.ndarray, sigma: float = 1.0) -> np.ndarray:
+def gaussian_blur_f32(
+    image: np.ndarray,
+    sigma: float = 1.0,
+    srgb_linearize: bool = False,
+    quality: str = "exact",
+) -> np.ndarray:
     """Apply Gaussian blur (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         sigma: Blur radius in standard deviations (0.1-100.0)
+        srgb_linearize: Blur in linear light instead of on sRGB values
+        quality: "exact", or "fast" for a three-box approximation (interactive
+            previews)
 
     Returns:
         Blurred float32 array with same channel count
     """
     _validate_image(image, np.float32, "gaussian_blur_f32")
-    return imagestag_rust.gaussian_blur_rgba_f32(image, sigma)
+    return imagestag_rust.gaussian_blur_rgba_f32(image, sigma, srgb_linearize, quality)
 
 
 # ============================================================================
 # Box Blur
 # ============================================================================
 
-def box_blur(image: np.ndarray, radius: int = 1) -> np.ndarray:
+def box_blur(
+    image: np.ndarray, radius: int = 1, srgb_linearize: bool = False
+) -> np.ndarray:
     """Apply box blur (u8).
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         radius: Blur radius in pixels (1-100)
+        srgb_linearize: Blur in linear light instead of on sRGB values
 
     Returns:
         Blurred uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "box_blur")
-    return imagestag_rust.box_blur_rgba(image, radius)
+    return imagestag_rust.box_blur_rgba(image, radius, srgb_linearize)
 
 
-def box_blur_f32(image: np.ndarray, radius: int = 1) -> np.ndarray:
+def box_blur_f32(
+    image: np.ndarray, radius: int = 1, srgb_linearize: bool = False
+) -> np.ndarray:
     """Apply box blur (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         radius: Blur radius in pixels (1-100)
+        srgb_linearize: Blur in linear light instead of on sRGB values
 
     Returns:
         Blurred float32 array with same channel count
     """
     _validate_image(image, np.float32, "box_blur_f32")
-    return imagestag_rust.box_blur_rgba_f32(image, radius)
+    return imagestag_rust.box_blur_rgba_f32(image, radius, srgb_linearize)
 
 
 __all__ = [
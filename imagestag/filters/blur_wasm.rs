@@ -1,7 +1,9 @@
 //! WASM-compatible blur filters: Gaussian Blur, Box Blur.
 //!
-//! These are single-threaded (no rayon) versions of the blur filters,
-//! designed to work in WASM environments where threading is not available.
+//! These are the portable versions of the blur filters. Rows are processed
+//! sequentially by default; with the `wasm-threads` feature they run on the
+//! rayon pool, which the browser starts with `initThreadPool` (see
+//! `wasm_threads.rs`), so large blurs use all cores.
 //!
 //! ## Supported Formats
 //!
@@ -9,11 +11,22 @@
 //! - **Grayscale**: (height, width, 1)
 //! - **RGB**: (height, width, 3)
 //! - **RGBA**: (height, width, 4) - uses premultiplied alpha blending
+//!
+//! ## Linear Light
+//!
+//! Blurring sRGB-encoded values darkens and muddies saturated edges (a red
+//! and green boundary turns brown). The `*_linear_*` variants decode color
+//! to linear light, blur, and encode back; the u8 path uses the shared
+//! lookup tables of [`super::resample`]. Alpha is always blurred as is.
+
+use ndarray::{Array3, ArrayView3, ArrayViewMut3, Axis};
 
-use ndarray::{Array3, ArrayView3};
+use super::blur::{recursive_gaussian_blur_f32, recursive_gaussian_blur_u8, RECURSIVE_MIN_SIGMA};
+use super::resample::{linear_to_srgb, linear_to_srgb_u8, srgb_to_linear, srgb_u8_to_linear};
+use super::fmath;
 
 /// Generate a 1D Gaussian kernel.
-fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+pub fn gaussian_kernel(sigma: f32) -> Vec<f32> {
     if sigma <= 0.0 {
         return vec![1.0];
     }
@@ -23,7 +36,7 @@ fn gaussian_kernel(sigma: f32) -> Vec<f32> {
     let mut kernel: Vec<f32> = (0..kernel_size)
         .map(|i| {
             let x = i as f32 - half as f32;
-            (-x * x / (2.0 * sigma * sigma)).exp()
+            fmath::exp(-x * x / (2.0 * sigma * sigma))
         })
         .collect();
 
@@ -34,13 +47,31 @@ fn gaussian_kernel(sigma: f32) -> Vec<f32> {
     kernel
 }
 
+/// Fill `buffer` row by row with `fill(y, row)`.
+///
+/// Rows run on the rayon pool with the `wasm-threads` feature, otherwise
+/// sequentially.
+fn for_each_row<T: Send>(buffer: &mut [T], row_len: usize, fill: impl Fn(usize, &mut [T]) + Sync) {
+    if row_len == 0 {
+        return;
+    }
+    #[cfg(feature = "wasm-threads")]
+    {
+        use rayon::prelude::*;
+        buffer.par_chunks_mut(row_len).enumerate().for_each(|(y, row)| fill(y, row));
+    }
+    #[cfg(not(feature = "wasm-threads"))]
+    buffer.chunks_mut(row_len).enumerate().for_each(|(y, row)| fill(y, row));
+}
+
 // ============================================================================
 // Gaussian Blur (WASM)
 // ============================================================================
 
-/// Separable Gaussian blur - u8 version (no rayon).
+/// Separable Gaussian blur - u8 version.
 ///
-/// Uses premultiplied alpha for correct RGBA blending.
+/// Uses premultiplied alpha for correct RGBA blending. From
+/// `RECURSIVE_MIN_SIGMA` on it runs the recursive Gaussian instead.
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels
@@ -49,19 +80,54 @@ fn gaussian_kernel(sigma: f32) -> Vec<f32> {
 /// # Returns
 /// Blurred image with same channel count
 pub fn gaussian_blur_wasm_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
-    let (height, width, channels) = input.dim();
     if sigma <= 0.0 {
         return input.to_owned();
     }
-    let kernel = gaussian_kernel(sigma);
+    if sigma >= RECURSIVE_MIN_SIGMA {
+        return recursive_gaussian_blur_u8(input, sigma);
+    }
+    let mut temp = Array3::<f32>::zeros(input.dim());
+    let mut output = Array3::<u8>::zeros(input.dim());
+    gaussian_blur_into_u8(input, &gaussian_kernel(sigma), &mut temp, output.view_mut());
+    output
+}
+
+/// Separable Gaussian blur into caller-provided buffers - u8 version.
+///
+/// Allocation-free variant of `gaussian_blur_wasm_u8` for callers that
+/// filter many frames of the same size.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels
+/// * `kernel` - Kernel from `gaussian_kernel`
+/// * `temp` - Scratch buffer with the input's shape
+/// * `output` - Destination with the input's shape (fully overwritten)
+///
+/// Buffers in standard layout are filled directly (row-parallel with
+/// `wasm-threads`); strided ones go through a contiguous copy.
+pub fn gaussian_blur_into_u8(input: ArrayView3<u8>, kernel: &[f32], temp: &mut Array3<f32>, mut output: ArrayViewMut3<u8>) {
+    if !temp.is_standard_layout() {
+        let mut contiguous = Array3::<f32>::zeros(temp.dim());
+        gaussian_blur_into_u8(input, kernel, &mut contiguous, output);
+        temp.assign(&contiguous);
+        return;
+    }
+    if !output.is_standard_layout() {
+        let mut contiguous = Array3::<u8>::zeros(output.dim());
+        gaussian_blur_into_u8(input, kernel, temp, contiguous.view_mut());
+        output.assign(&contiguous);
+        return;
+    }
+    let (height, width, channels) = input.dim();
     let half = kernel.len() / 2;
     let has_alpha = channels == 4;
     let color_channels = if has_alpha { 3 } else { channels };
 
     // Pass 1: Horizontal
-    let mut temp = Array3::<f32>::zeros((height, width, channels));
-    for y in 0..height {
+    let temp_rows = temp.as_slice_mut().expect("checked standard layout");
+    for_each_row(temp_rows, width * channels, |y, row| {
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             if has_alpha {
                 let mut sum_rgb = [0.0f32; 3];
                 let mut sum_a = 0.0f32;
@@ -74,10 +140,8 @@ pub fn gaussian_blur_wasm_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
                     }
                     sum_a += a * kv;
                 }
-                for c in 0..3 {
-                    temp[[y, x, c]] = sum_rgb[c];
-                }
-                temp[[y, x, 3]] = sum_a;
+                px[..3].copy_from_slice(&sum_rgb);
+                px[3] = sum_a;
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
@@ -86,16 +150,18 @@ pub fn gaussian_blur_wasm_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
                             .clamp(0, width as isize - 1) as usize;
                         sum += input[[y, sx, c]] as f32 * kv;
                     }
-                    temp[[y, x, c]] = sum;
+                    px[c] = sum;
                 }
             }
         }
-    }
+    });
 
     // Pass 2: Vertical
-    let mut output = Array3::<u8>::zeros((height, width, channels));
-    for y in 0..height {
+    let temp = &*temp;
+    let output_rows = output.as_slice_mut().expect("checked standard layout");
+    for_each_row(output_rows, width * channels, |y, row| {
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             if has_alpha {
                 let mut sum_rgb = [0.0f32; 3];
                 let mut sum_a = 0.0f32;
@@ -108,13 +174,14 @@ pub fn gaussian_blur_wasm_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
                     sum_a += temp[[sy, x, 3]] * kv;
                 }
                 let final_alpha = sum_a;
-                if final_alpha > 0.001 {
-                    for c in 0..3 {
-                        output[[y, x, c]] = (sum_rgb[c] / final_alpha)
-                            .clamp(0.0, 255.0) as u8;
-                    }
+                for c in 0..3 {
+                    px[c] = if final_alpha > 0.001 {
+                        (sum_rgb[c] / final_alpha).clamp(0.0, 255.0) as u8
+                    } else {
+                        0
+                    };
                 }
-                output[[y, x, 3]] = (final_alpha * 255.0).clamp(0.0, 255.0) as u8;
+                px[3] = (final_alpha * 255.0).clamp(0.0, 255.0) as u8;
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
@@ -123,15 +190,16 @@ pub fn gaussian_blur_wasm_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
                             .clamp(0, height as isize - 1) as usize;
                         sum += temp[[sy, x, c]] * kv;
                     }
-                    output[[y, x, c]] = sum.clamp(0.0, 255.0) as u8;
+                    px[c] = sum.clamp(0.0, 255.0) as u8;
                 }
             }
         }
-    }
-    output
+    });
 }
 
-/// Separable Gaussian blur - f32 version (no rayon).
+/// Separable Gaussian blur - f32 version.
+///
+/// From `RECURSIVE_MIN_SIGMA` on it runs the recursive Gaussian instead.
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
@@ -144,6 +212,9 @@ pub fn gaussian_blur_wasm_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
     if sigma <= 0.0 {
         return input.to_owned();
     }
+    if sigma >= RECURSIVE_MIN_SIGMA {
+        return recursive_gaussian_blur_f32(input, sigma);
+    }
     let kernel = gaussian_kernel(sigma);
     let half = kernel.len() / 2;
     let has_alpha = channels == 4;
@@ -151,8 +222,9 @@ pub fn gaussian_blur_wasm_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
 
     // Pass 1: Horizontal
     let mut temp = Array3::<f32>::zeros((height, width, channels));
-    for y in 0..height {
+    for_each_row(temp.as_slice_mut().unwrap(), width * channels, |y, row| {
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             if has_alpha {
                 let mut sum_rgb = [0.0f32; 3];
                 let mut sum_a = 0.0f32;
@@ -165,10 +237,8 @@ pub fn gaussian_blur_wasm_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
                     }
                     sum_a += a * kv;
                 }
-                for c in 0..3 {
-                    temp[[y, x, c]] = sum_rgb[c];
-                }
-                temp[[y, x, 3]] = sum_a;
+                px[..3].copy_from_slice(&sum_rgb);
+                px[3] = sum_a;
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
@@ -177,16 +247,17 @@ pub fn gaussian_blur_wasm_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
                             .clamp(0, width as isize - 1) as usize;
                         sum += input[[y, sx, c]] * kv;
                     }
-                    temp[[y, x, c]] = sum;
+                    px[c] = sum;
                 }
             }
         }
-    }
+    });
 
     // Pass 2: Vertical
     let mut output = Array3::<f32>::zeros((height, width, channels));
-    for y in 0..height {
+    for_each_row(output.as_slice_mut().unwrap(), width * channels, |y, row| {
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             if has_alpha {
                 let mut sum_rgb = [0.0f32; 3];
                 let mut sum_a = 0.0f32;
@@ -201,10 +272,10 @@ pub fn gaussian_blur_wasm_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
                 let final_alpha = sum_a;
                 if final_alpha > 0.001 {
                     for c in 0..3 {
-                        output[[y, x, c]] = (sum_rgb[c] / final_alpha).clamp(0.0, 1.0);
+                        px[c] = (sum_rgb[c] / final_alpha).clamp(0.0, 1.0);
                     }
                 }
-                output[[y, x, 3]] = final_alpha.clamp(0.0, 1.0);
+                px[3] = final_alpha.clamp(0.0, 1.0);
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
@@ -213,19 +284,227 @@ pub fn gaussian_blur_wasm_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
                             .clamp(0, height as isize - 1) as usize;
                         sum += temp[[sy, x, c]] * kv;
                     }
-                    output[[y, x, c]] = sum.clamp(0.0, 1.0);
+                    px[c] = sum.clamp(0.0, 1.0);
+                }
+            }
+        }
+    });
+    output
+}
+
+// ============================================================================
+// Fast Gaussian (box approximation)
+// ============================================================================
+
+/// Accuracy of Gaussian blurs.
+///
+/// Interactive previews (effect sliders being dragged) can use `Fast` and
+/// re-render with `Exact` once the value is committed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlurQuality {
+    /// Gaussian kernel convolution
+    #[default]
+    Exact,
+    /// Three successive box blurs; cost independent of sigma, 3-5x faster
+    /// for typical radii and visually indistinguishable
+    Fast,
+}
+
+impl BlurQuality {
+    /// Parse a quality name ("exact", "fast"), case-insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "exact" => Some(BlurQuality::Exact),
+            "fast" => Some(BlurQuality::Fast),
+            _ => None,
+        }
+    }
+}
+
+/// Box radii whose successive application approximates a Gaussian of `sigma`.
+///
+/// Box widths are the odd sizes around the ideal width with the same total
+/// variance (W. Wells, "Efficient synthesis of Gaussian filters by cascaded
+/// uniform filters").
+pub fn box_radii_for_gaussian(sigma: f32, passes: usize) -> Vec<usize> {
+    let n = passes as f32;
+    let variance = 12.0 * sigma * sigma;
+    let mut lower = ((variance / n + 1.0).sqrt().floor() as i32).max(1);
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let wl = lower as f32;
+    let lower_count = ((variance - n * wl * wl - 4.0 * n * wl - 3.0 * n) / (-4.0 * wl - 4.0)).round().max(0.0) as usize;
+    (0..passes).map(|i| if i < lower_count { lower as usize / 2 } else { lower as usize / 2 + 1 }).collect()
+}
+
+/// Successive box blurs of an interleaved (height, width, channels) buffer,
+/// in place, with edge pixels repeated like the Gaussian kernels.
+///
+/// Each pass is a running sum, so the cost does not depend on the radius.
+pub fn box_passes_in_place(buffer: &mut [f32], width: usize, height: usize, channels: usize, radii: &[usize]) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let stride = width * channels;
+    let mut scratch = vec![0.0f32; buffer.len()];
+    for &r in radii.iter().filter(|&&r| r > 0) {
+        let norm = 1.0 / (2 * r + 1) as f32;
+
+        // Horizontal: buffer -> scratch
+        let source = &*buffer;
+        for_each_row(&mut scratch, stride, |y, row| {
+            let src = &source[y * stride..(y + 1) * stride];
+            let at = |x: usize, c: usize| src[x.min(width - 1) * channels + c];
+            for c in 0..channels {
+                let mut sum = at(0, c) * (r + 1) as f32 + (1..=r).map(|x| at(x, c)).sum::<f32>();
+                for x in 0..width {
+                    row[x * channels + c] = sum * norm;
+                    sum += at(x + r + 1, c) - at(x.saturating_sub(r), c);
                 }
             }
+        });
+
+        // Vertical: scratch -> buffer, one running sum per column
+        let row = |y: usize| &scratch[y.min(height - 1) * stride..][..stride];
+        let mut sums: Vec<f32> = row(0).iter().map(|&v| v * (r + 1) as f32).collect();
+        for y in 1..=r {
+            sums.iter_mut().zip(row(y)).for_each(|(s, &v)| *s += v);
+        }
+        for y in 0..height {
+            let (add, sub) = (row(y + r + 1), row(y.saturating_sub(r)));
+            let out = &mut buffer[y * stride..(y + 1) * stride];
+            for i in 0..stride {
+                out[i] = sums[i] * norm;
+                sums[i] += add[i] - sub[i];
+            }
         }
     }
+}
+
+/// Blur premultiplied f32 working pixels with `blur` and convert back.
+///
+/// `load` converts an input pixel to premultiplied working values, `store`
+/// converts a blurred premultiplied pixel back. `blur` receives the working
+/// buffer with its width, height and channel count.
+fn blur_working<T: Copy + Default>(
+    input: ArrayView3<T>,
+    load: impl Fn(&[T], &mut [f32]),
+    store: impl Fn(&[f32], &mut [T]),
+    blur: impl FnOnce(&mut [f32], usize, usize, usize),
+) -> Array3<T> {
+    let (height, width, channels) = input.dim();
+    let mut work = vec![0.0f32; height * width * channels];
+    for (px, src) in work.chunks_mut(channels.max(1)).zip(input.as_standard_layout().as_slice().unwrap().chunks(channels.max(1))) {
+        load(src, px);
+    }
+    blur(&mut work, width, height, channels);
+    let mut output = Array3::from_elem(input.dim(), T::default());
+    for (dst, px) in output.as_slice_mut().unwrap().chunks_mut(channels.max(1)).zip(work.chunks(channels.max(1))) {
+        store(px, dst);
+    }
     output
 }
 
+/// Blur an image with an in-place buffer blur - u8 version.
+///
+/// Same alpha handling as `gaussian_blur_wasm_u8`; shared by the box
+/// approximation and the recursive Gaussian.
+pub(crate) fn blur_premultiplied_u8(input: ArrayView3<u8>, blur: impl FnOnce(&mut [f32], usize, usize, usize)) -> Array3<u8> {
+    if input.dim().2 != 4 {
+        return blur_working(
+            input,
+            |s, d| d.iter_mut().zip(s).for_each(|(d, &s)| *d = s as f32),
+            |s, d| d.iter_mut().zip(s).for_each(|(d, &s)| *d = s.clamp(0.0, 255.0) as u8),
+            blur,
+        );
+    }
+    blur_working(
+        input,
+        |s, d| {
+            let a = s[3] as f32 / 255.0;
+            (0..3).for_each(|c| d[c] = s[c] as f32 * a);
+            d[3] = a;
+        },
+        |s, d| {
+            let a = s[3];
+            (0..3).for_each(|c| d[c] = if a > 0.001 { (s[c] / a).clamp(0.0, 255.0) as u8 } else { 0 });
+            d[3] = (a * 255.0).clamp(0.0, 255.0) as u8;
+        },
+        blur,
+    )
+}
+
+/// Blur an image with an in-place buffer blur - f32 version.
+///
+/// Same alpha handling as `gaussian_blur_wasm_f32`.
+pub(crate) fn blur_premultiplied_f32(input: ArrayView3<f32>, blur: impl FnOnce(&mut [f32], usize, usize, usize)) -> Array3<f32> {
+    if input.dim().2 != 4 {
+        return blur_working(
+            input,
+            |s, d| d.copy_from_slice(s),
+            |s, d| d.iter_mut().zip(s).for_each(|(d, &s)| *d = s.clamp(0.0, 1.0)),
+            blur,
+        );
+    }
+    blur_working(
+        input,
+        |s, d| {
+            (0..3).for_each(|c| d[c] = s[c] * s[3]);
+            d[3] = s[3];
+        },
+        |s, d| {
+            let a = s[3];
+            (0..3).for_each(|c| d[c] = if a > 0.001 { (s[c] / a).clamp(0.0, 1.0) } else { 0.0 });
+            d[3] = a.clamp(0.0, 1.0);
+        },
+        blur,
+    )
+}
+
+/// Fast Gaussian blur (three box passes) - u8 version.
+///
+/// Same alpha handling as `gaussian_blur_wasm_u8`.
+pub fn fast_gaussian_blur_u8(input: ArrayView3<u8>, sigma: f32) -> Array3<u8> {
+    if sigma <= 0.0 {
+        return input.to_owned();
+    }
+    let radii = box_radii_for_gaussian(sigma, 3);
+    blur_premultiplied_u8(input, |buffer, width, height, channels| box_passes_in_place(buffer, width, height, channels, &radii))
+}
+
+/// Fast Gaussian blur (three box passes) - f32 version.
+///
+/// Same alpha handling as `gaussian_blur_wasm_f32`.
+pub fn fast_gaussian_blur_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32> {
+    if sigma <= 0.0 {
+        return input.to_owned();
+    }
+    let radii = box_radii_for_gaussian(sigma, 3);
+    blur_premultiplied_f32(input, |buffer, width, height, channels| box_passes_in_place(buffer, width, height, channels, &radii))
+}
+
+/// Gaussian blur at the given quality - u8 version.
+pub fn gaussian_blur_quality_u8(input: ArrayView3<u8>, sigma: f32, quality: BlurQuality) -> Array3<u8> {
+    match quality {
+        BlurQuality::Exact => gaussian_blur_wasm_u8(input, sigma),
+        BlurQuality::Fast => fast_gaussian_blur_u8(input, sigma),
+    }
+}
+
+/// Gaussian blur at the given quality - f32 version.
+pub fn gaussian_blur_quality_f32(input: ArrayView3<f32>, sigma: f32, quality: BlurQuality) -> Array3<f32> {
+    match quality {
+        BlurQuality::Exact => gaussian_blur_wasm_f32(input, sigma),
+        BlurQuality::Fast => fast_gaussian_blur_f32(input, sigma),
+    }
+}
+
 // ============================================================================
 // Box Blur (WASM)
 // ============================================================================
 
-/// Box blur - u8 version (no rayon).
+/// Box blur - u8 version.
 ///
 /// Uses premultiplied alpha for correct RGBA blending.
 ///
@@ -246,8 +525,9 @@ pub fn box_blur_wasm_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
 
     // Pass 1: Horizontal
     let mut temp = Array3::<f32>::zeros((height, width, channels));
-    for y in 0..height {
+    for_each_row(temp.as_slice_mut().unwrap(), width * channels, |y, row| {
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             let x_start = (x as isize - r).max(0) as usize;
             let x_end = (x as isize + r + 1).min(width as isize) as usize;
             let count = (x_end - x_start) as f32;
@@ -263,29 +543,30 @@ pub fn box_blur_wasm_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
                     sum_a += a;
                 }
                 for c in 0..3 {
-                    temp[[y, x, c]] = sum_rgb[c] / count;
+                    px[c] = sum_rgb[c] / count;
                 }
-                temp[[y, x, 3]] = sum_a / count;
+                px[3] = sum_a / count;
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
                     for sx in x_start..x_end {
                         sum += input[[y, sx, c]] as f32;
                     }
-                    temp[[y, x, c]] = sum / count;
+                    px[c] = sum / count;
                 }
             }
         }
-    }
+    });
 
     // Pass 2: Vertical
     let mut output = Array3::<u8>::zeros((height, width, channels));
-    for y in 0..height {
+    for_each_row(output.as_slice_mut().unwrap(), width * channels, |y, row| {
         let y_start = (y as isize - r).max(0) as usize;
         let y_end = (y as isize + r + 1).min(height as isize) as usize;
         let count = (y_end - y_start) as f32;
 
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             if has_alpha {
                 let mut sum_rgb = [0.0f32; 3];
                 let mut sum_a = 0.0f32;
@@ -298,26 +579,26 @@ pub fn box_blur_wasm_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
                 let final_alpha = sum_a / count;
                 if final_alpha > 0.001 {
                     for c in 0..3 {
-                        output[[y, x, c]] = (sum_rgb[c] / count / final_alpha)
+                        px[c] = (sum_rgb[c] / count / final_alpha)
                             .clamp(0.0, 255.0) as u8;
                     }
                 }
-                output[[y, x, 3]] = (final_alpha * 255.0).clamp(0.0, 255.0) as u8;
+                px[3] = (final_alpha * 255.0).clamp(0.0, 255.0) as u8;
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
                     for sy in y_start..y_end {
                         sum += temp[[sy, x, c]];
                     }
-                    output[[y, x, c]] = (sum / count).clamp(0.0, 255.0) as u8;
+                    px[c] = (sum / count).clamp(0.0, 255.0) as u8;
                 }
             }
         }
-    }
+    });
     output
 }
 
-/// Box blur - f32 version (no rayon).
+/// Box blur - f32 version.
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
@@ -336,8 +617,9 @@ pub fn box_blur_wasm_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
 
     // Pass 1: Horizontal
     let mut temp = Array3::<f32>::zeros((height, width, channels));
-    for y in 0..height {
+    for_each_row(temp.as_slice_mut().unwrap(), width * channels, |y, row| {
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             let x_start = (x as isize - r).max(0) as usize;
             let x_end = (x as isize + r + 1).min(width as isize) as usize;
             let count = (x_end - x_start) as f32;
@@ -353,29 +635,30 @@ pub fn box_blur_wasm_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
                     sum_a += a;
                 }
                 for c in 0..3 {
-                    temp[[y, x, c]] = sum_rgb[c] / count;
+                    px[c] = sum_rgb[c] / count;
                 }
-                temp[[y, x, 3]] = sum_a / count;
+                px[3] = sum_a / count;
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
                     for sx in x_start..x_end {
                         sum += input[[y, sx, c]];
                     }
-                    temp[[y, x, c]] = sum / count;
+                    px[c] = sum / count;
                 }
             }
         }
-    }
+    });
 
     // Pass 2: Vertical
     let mut output = Array3::<f32>::zeros((height, width, channels));
-    for y in 0..height {
+    for_each_row(output.as_slice_mut().unwrap(), width * channels, |y, row| {
         let y_start = (y as isize - r).max(0) as usize;
         let y_end = (y as isize + r + 1).min(height as isize) as usize;
         let count = (y_end - y_start) as f32;
 
         for x in 0..width {
+            let px = &mut row[x * channels..(x + 1) * channels];
             if has_alpha {
                 let mut sum_rgb = [0.0f32; 3];
                 let mut sum_a = 0.0f32;
@@ -388,27 +671,151 @@ pub fn box_blur_wasm_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
                 let final_alpha = sum_a / count;
                 if final_alpha > 0.001 {
                     for c in 0..3 {
-                        output[[y, x, c]] = (sum_rgb[c] / count / final_alpha).clamp(0.0, 1.0);
+                        px[c] = (sum_rgb[c] / count / final_alpha).clamp(0.0, 1.0);
                     }
                 }
-                output[[y, x, 3]] = final_alpha.clamp(0.0, 1.0);
+                px[3] = final_alpha.clamp(0.0, 1.0);
             } else {
                 for c in 0..color_channels {
                     let mut sum = 0.0f32;
                     for sy in y_start..y_end {
                         sum += temp[[sy, x, c]];
                     }
-                    output[[y, x, c]] = (sum / count).clamp(0.0, 1.0);
+                    px[c] = (sum / count).clamp(0.0, 1.0);
                 }
             }
         }
+    });
+    output
+}
+
+// ============================================================================
+// Linear-Light Blur
+// ============================================================================
+
+/// Apply `decode` to the color channels of every pixel (alpha unchanged).
+fn map_color<T: Copy, U: Copy + Default>(input: ArrayView3<T>, color: impl Fn(T) -> U, alpha: impl Fn(T) -> U) -> Array3<U> {
+    let channels = input.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = Array3::<U>::from_elem(input.dim(), U::default());
+    for (src, mut dst) in input.lanes(Axis(2)).into_iter().zip(output.lanes_mut(Axis(2))) {
+        for c in 0..channels {
+            dst[c] = if c < color_channels { color(src[c]) } else { alpha(src[c]) };
+        }
     }
     output
 }
 
+/// Run an f32 blur on the linear-light version of a u8 image.
+fn blur_linear_u8(input: ArrayView3<u8>, blur: impl Fn(ArrayView3<f32>) -> Array3<f32>) -> Array3<u8> {
+    let linear = map_color(input, srgb_u8_to_linear, |a| a as f32 / 255.0);
+    map_color(blur(linear.view()).view(), linear_to_srgb_u8, |a| (a * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+/// Run an f32 blur on the linear-light version of an f32 image.
+fn blur_linear_f32(input: ArrayView3<f32>, blur: impl Fn(ArrayView3<f32>) -> Array3<f32>) -> Array3<f32> {
+    let linear = map_color(input, srgb_to_linear, |a| a);
+    map_color(blur(linear.view()).view(), linear_to_srgb, |a| a)
+}
+
+/// Gaussian blur in linear light - u8 version.
+///
+/// # Arguments
+/// * `input` - sRGB image with 1, 3, or 4 channels
+/// * `sigma` - Blur radius (standard deviation)
+/// * `quality` - Exact kernel or fast box approximation
+pub fn gaussian_blur_linear_u8(input: ArrayView3<u8>, sigma: f32, quality: BlurQuality) -> Array3<u8> {
+    if sigma <= 0.0 {
+        return input.to_owned();
+    }
+    blur_linear_u8(input, |linear| gaussian_blur_quality_f32(linear, sigma, quality))
+}
+
+/// Gaussian blur in linear light - f32 version (sRGB values 0.0-1.0).
+pub fn gaussian_blur_linear_f32(input: ArrayView3<f32>, sigma: f32, quality: BlurQuality) -> Array3<f32> {
+    if sigma <= 0.0 {
+        return input.to_owned();
+    }
+    blur_linear_f32(input, |linear| gaussian_blur_quality_f32(linear, sigma, quality))
+}
+
+/// Box blur in linear light - u8 version.
+///
+/// # Arguments
+/// * `input` - sRGB image with 1, 3, or 4 channels
+/// * `radius` - Box blur radius (kernel is 2*radius+1)
+pub fn box_blur_linear_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
+    if radius == 0 {
+        return input.to_owned();
+    }
+    blur_linear_u8(input, |linear| box_blur_wasm_f32(linear, radius))
+}
+
+/// Box blur in linear light - f32 version (sRGB values 0.0-1.0).
+pub fn box_blur_linear_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
+    if radius == 0 {
+        return input.to_owned();
+    }
+    blur_linear_f32(input, |linear| box_blur_wasm_f32(linear, radius))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ndarray::ShapeBuilder;
+
+    #[test]
+    fn test_fast_gaussian_approximates_exact() {
+        // The box radii reproduce the Gaussian's variance
+        for sigma in [1.0f32, 2.5, 7.0, 20.0] {
+            let variance: f32 = box_radii_for_gaussian(sigma, 3).iter().map(|&r| ((2 * r + 1).pow(2) - 1) as f32 / 12.0).sum();
+            assert!((variance.sqrt() - sigma).abs() < 0.6, "sigma {sigma}: {variance}");
+        }
+
+        let img = Array3::from_shape_fn((48, 40, 4), |(y, x, c)| {
+            let inside = (12..36).contains(&y) && (10..30).contains(&x);
+            match (inside, c) {
+                (true, 3) => 1.0,
+                (true, _) => [0.9, 0.3, 0.1][c],
+                _ => 0.0,
+            }
+        });
+        let exact = gaussian_blur_quality_f32(img.view(), 4.0, BlurQuality::Exact);
+        let fast = gaussian_blur_quality_f32(img.view(), 4.0, BlurQuality::Fast);
+        let max_alpha_diff = exact.index_axis(Axis(2), 3).iter().zip(fast.index_axis(Axis(2), 3)).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+        assert!(max_alpha_diff < 0.04, "{max_alpha_diff}");
+        // Color stays un-bled where there is coverage
+        assert!((fast[[24, 20, 0]] - 0.9).abs() < 1e-3);
+        assert_eq!(BlurQuality::from_name("fast"), Some(BlurQuality::Fast));
+        assert_eq!(BlurQuality::from_name("Exact"), Some(BlurQuality::Exact));
+        assert_eq!(BlurQuality::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_large_sigma_uses_recursive_gaussian() {
+        // Impulse response along a row matches the sampled Gaussian
+        let sigma = 40.0;
+        let mut img = Array3::<f32>::zeros((1, 401, 1));
+        img[[0, 200, 0]] = 1.0;
+        let result = gaussian_blur_wasm_f32(img.view(), sigma);
+        let kernel = gaussian_kernel(sigma);
+        let half = kernel.len() / 2;
+        let peak = kernel[half];
+        for x in 0..401usize {
+            let expected = if x.abs_diff(200) <= half { kernel[x + half - 200] } else { 0.0 };
+            assert!((result[[0, x, 0]] - expected).abs() < 0.02 * peak, "x={x}: {} vs {expected}", result[[0, x, 0]]);
+        }
+        assert!((result.sum() - 1.0).abs() < 1e-2, "sum {}", result.sum());
+
+        // Alpha masks of effects route there too; flat regions stay flat
+        let alpha = ndarray::Array2::from_shape_fn((120, 160), |(_, x)| if x < 80 { 1.0 } else { 0.0 });
+        let blurred = crate::filters::core::blur_alpha_f32(&alpha, 50.0);
+        assert!((blurred[[60, 80]] - 0.5).abs() < 0.05);
+        // Symmetric step, symmetric response at both image edges
+        assert!((blurred[[60, 0]] + blurred[[60, 159]] - 1.0).abs() < 0.01);
+        assert!(blurred[[0, 79]] > blurred[[0, 100]] && blurred[[0, 100]] > 0.0);
+        assert!((blurred[[0, 80]] - blurred[[119, 80]]).abs() < 1e-4);
+    }
 
     #[test]
     fn test_gaussian_blur_wasm_u8_uniform() {
@@ -428,6 +835,26 @@ mod tests {
         assert!((result[[2, 2, 0]] as i32 - 128).abs() <= 1);
     }
 
+    #[test]
+    fn test_gaussian_blur_into_strided_buffers() {
+        let input = Array3::from_shape_fn((9, 7, 4), |(y, x, c)| ((y * 31 + x * 17 + c * 50) % 256) as u8);
+        let kernel = gaussian_kernel(1.2);
+        let expected = gaussian_blur_wasm_u8(input.view(), 1.2);
+
+        // Transposed (column-major) scratch and destination
+        let mut temp = Array3::<f32>::zeros((9, 7, 4).f());
+        let mut output = Array3::<u8>::zeros((9, 7, 4).f());
+        gaussian_blur_into_u8(input.view(), &kernel, &mut temp, output.view_mut());
+        assert_eq!(output, expected);
+
+        // Every other column of a wider image
+        let mut wide = Array3::<u8>::zeros((9, 14, 4));
+        let mut temp = Array3::<f32>::zeros((9, 7, 4));
+        gaussian_blur_into_u8(input.view(), &kernel, &mut temp, wide.slice_mut(ndarray::s![.., ..;2, ..]));
+        assert_eq!(wide.slice(ndarray::s![.., ..;2, ..]), expected);
+        assert!(wide.slice(ndarray::s![.., 1..;2, ..]).iter().all(|&v| v == 0));
+    }
+
     #[test]
     fn test_gaussian_blur_wasm_f32_smooths() {
         let mut img = Array3::<f32>::zeros((5, 5, 3));
@@ -478,4 +905,25 @@ mod tests {
         // Zero sigma should be identity
         assert_eq!(result[[1, 1, 0]], 200);
     }
+
+    #[test]
+    fn test_linear_light_blur_keeps_edges_bright() {
+        // Red | green edge: sRGB blur gives a dark brown seam, linear light does not
+        let image = Array3::from_shape_fn((4, 8, 3), |(_, x, c)| match (x < 4, c) {
+            (true, 0) | (false, 1) => 255u8,
+            _ => 0,
+        });
+        let srgb = box_blur_wasm_u8(image.view(), 1);
+        let linear = box_blur_linear_u8(image.view(), 1);
+        // Pixel 3 averages two red and one green pixel
+        assert_eq!(srgb[[1, 3, 0]], 170);
+        assert_eq!(linear[[1, 3, 0]], 213);
+        assert!(linear[[1, 3, 1]] > srgb[[1, 3, 1]]);
+        // Flat areas and the LUT round trip are unchanged
+        assert_eq!(linear[[1, 0, 0]], 255);
+        assert!((0..=255u8).all(|v| linear_to_srgb_u8(srgb_u8_to_linear(v)) == v));
+        let f32_linear = gaussian_blur_linear_f32(image.mapv(|v| v as f32 / 255.0).view(), 1.0, BlurQuality::Exact);
+        let u8_linear = gaussian_blur_linear_u8(image.view(), 1.0, BlurQuality::Exact);
+        assert!((f32_linear[[1, 3, 0]] * 255.0 - u8_linear[[1, 3, 0]] as f32).abs() <= 1.0);
+    }
 }
@@ -0,0 +1,173 @@
+/**
+ * Canvas operations - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - canvas.rs (Rust implementation)
+ * - canvas.py (Python wrapper)
+ *
+ * Provides: content_bounds, alpha_bbox, opaque_bbox, trim, extend_canvas_fill
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Trim
+// ============================================================================
+
+/**
+ * Copy a rectangle out of interleaved image data.
+ */
+function cropRect(imageData, rect, ArrayType) {
+    const { data, width } = imageData;
+    const channels = imageData.channels || 4;
+    const [x, y, w, h] = rect;
+    const out = new ArrayType(w * h * channels);
+    for (let row = 0; row < h; row++) {
+        const start = ((y + row) * width + x) * channels;
+        out.set(data.subarray(start, start + w * channels), row * w * channels);
+    }
+    return { data: out, width: w, height: h, channels };
+}
+
+/**
+ * Bounding box of the non-background content (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {mode: 'transparent'|'top_left'|'bottom_right', tolerance: 0-255}
+ * @returns {number[]|null} - [x, y, width, height] or null if only background
+ */
+export function content_bounds(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const rect = wasm.content_bounds_wasm(
+        new Uint8Array(data.buffer), width, height, channels,
+        options.mode ?? 'transparent', options.tolerance ?? 0
+    );
+    return rect.length === 4 ? Array.from(rect) : null;
+}
+
+/**
+ * Bounding box of the non-background content (f32, tolerance 0.0-1.0).
+ */
+export function content_bounds_f32(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const rect = wasm.content_bounds_f32_wasm(
+        data, width, height, channels, options.mode ?? 'transparent', options.tolerance ?? 0
+    );
+    return rect.length === 4 ? Array.from(rect) : null;
+}
+
+/**
+ * Tight bounding box of the pixels with alpha above a threshold (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number} threshold - Largest alpha still counted as invisible (0-255)
+ * @returns {number[]|null} - [x, y, width, height] or null if nothing is above it
+ */
+export function alpha_bbox(imageData, threshold = 0) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const rect = wasm.alpha_bbox_wasm(new Uint8Array(data.buffer), width, height, channels, threshold);
+    return rect.length === 4 ? Array.from(rect) : null;
+}
+
+/**
+ * Tight bounding box of the pixels with alpha above a threshold (f32, 0.0-1.0).
+ */
+export function alpha_bbox_f32(imageData, threshold = 0) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const rect = wasm.alpha_bbox_f32_wasm(data, width, height, channels, threshold);
+    return rect.length === 4 ? Array.from(rect) : null;
+}
+
+/**
+ * Bounding box of every pixel with any opacity (u8), null if fully transparent.
+ */
+export function opaque_bbox(imageData) {
+    return alpha_bbox(imageData, 0);
+}
+
+/**
+ * Bounding box of every pixel with any opacity (f32), null if fully transparent.
+ */
+export function opaque_bbox_f32(imageData) {
+    return alpha_bbox_f32(imageData, 0);
+}
+
+/**
+ * Crop an image to its content (u8). Only-background images come back unchanged.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {mode: 'transparent'|'top_left'|'bottom_right', tolerance: 0-255}
+ * @returns {Object} - Cropped image data plus rect: [x, y, width, height]
+ */
+export function trim(imageData, options = {}) {
+    const rect = content_bounds(imageData, options) ?? [0, 0, imageData.width, imageData.height];
+    return { ...cropRect(imageData, rect, Uint8ClampedArray), rect };
+}
+
+/**
+ * Crop an image to its content (f32).
+ */
+export function trim_f32(imageData, options = {}) {
+    const rect = content_bounds_f32(imageData, options) ?? [0, 0, imageData.width, imageData.height];
+    return { ...cropRect(imageData, rect, Float32Array), rect };
+}
+
+// ============================================================================
+// Extend Canvas
+// ============================================================================
+
+function extendOptions(opts) {
+    return Object.assign(new wasm.ExtendCanvasOptions(), {
+        left: opts.left ?? 0,
+        top: opts.top ?? 0,
+        right: opts.right ?? 0,
+        bottom: opts.bottom ?? 0,
+        method: opts.method ?? 'mirror',
+    });
+}
+
+/**
+ * Grow the canvas and fill the new border (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {left, top, right, bottom: pixels, method: 'mirror'|'smear'|'patch_match'}
+ * @returns {Object} - Extended image data
+ */
+export function extend_canvas_fill(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const opts = extendOptions(options);
+    const outWidth = width + opts.left + opts.right;
+    const outHeight = height + opts.top + opts.bottom;
+    const result = wasm.extend_canvas_fill_wasm(new Uint8Array(data.buffer), width, height, channels, opts);
+    opts.free();
+
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: outWidth,
+        height: outHeight,
+        channels
+    };
+}
+
+/**
+ * Grow the canvas and fill the new border (f32).
+ */
+export function extend_canvas_fill_f32(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const opts = extendOptions(options);
+    const outWidth = width + opts.left + opts.right;
+    const outHeight = height + opts.top + opts.bottom;
+    const result = wasm.extend_canvas_fill_f32_wasm(data, width, height, channels, opts);
+    opts.free();
+
+    return {
+        data: new Float32Array(result.buffer),
+        width: outWidth,
+        height: outHeight,
+        channels
+    };
+}
@@ -0,0 +1,161 @@
+"""Canvas operations with Rust backend.
+
+- Trim: crop an image to the bounding box of its content, like the
+  "Trim..." command of image editors. Background is either transparent
+  pixels or the color of the top-left or bottom-right corner.
+- Alpha bounding boxes: tight rectangle of the pixels above an alpha
+  threshold (``alpha_bbox``) or with any opacity (``opaque_bbox``), for
+  layer bounds and dirty rectangles.
+- Extend canvas fill: "uncrop" - grow the canvas and fill the new border
+  by mirroring, smearing the edge, or PatchMatch texture synthesis.
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels. Transparent mode needs
+RGBA; without alpha every pixel counts as content.
+
+Co-located with:
+- canvas.rs (Rust implementation)
+- canvas.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.canvas import trim, extend_canvas_fill
+
+    cropped, (x, y, w, h) = trim(sprite)                          # transparent border
+    cropped, rect = trim(scan, mode='top_left', tolerance=8)      # near-white margin
+    wider = extend_canvas_fill(photo, left=200, right=200, method='patch_match')
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Trim
+# ============================================================================
+
+def trim(image: np.ndarray, mode: str = 'transparent',
+         tolerance: int = 0) -> tuple[np.ndarray, tuple[int, int, int, int]]:
+    """Crop an image to the bounding box of its content (u8).
+
+    An image that holds only background is returned unchanged with the
+    full rectangle.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        mode: 'transparent', 'top_left' or 'bottom_right' (corner whose
+            color is the background)
+        tolerance: Largest per-channel difference (or alpha) still counted
+            as background, 0-255
+
+    Returns:
+        (cropped uint8 array, (x, y, width, height) of the crop)
+    """
+    _validate_image(image, np.uint8, "trim")
+    return imagestag_rust.trim(image, mode, tolerance)
+
+
+def trim_f32(image: np.ndarray, mode: str = 'transparent',
+             tolerance: float = 0.0) -> tuple[np.ndarray, tuple[int, int, int, int]]:
+    """Crop an image to the bounding box of its content (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        mode: 'transparent', 'top_left' or 'bottom_right'
+        tolerance: Largest per-channel difference (or alpha), 0.0-1.0
+
+    Returns:
+        (cropped float32 array, (x, y, width, height) of the crop)
+    """
+    _validate_image(image, np.float32, "trim_f32")
+    return imagestag_rust.trim_f32(image, mode, tolerance)
+
+
+def alpha_bbox(image: np.ndarray, threshold: int = 0) -> tuple[int, int, int, int] | None:
+    """Tight bounding box of the pixels with alpha above a threshold (u8).
+
+    Images without alpha count as fully visible.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        threshold: Largest alpha still counted as invisible, 0-255
+
+    Returns:
+        (x, y, width, height), or None if no pixel is above the threshold
+    """
+    _validate_image(image, np.uint8, "alpha_bbox")
+    return imagestag_rust.alpha_bbox(image, threshold)
+
+
+def alpha_bbox_f32(image: np.ndarray, threshold: float = 0.0) -> tuple[int, int, int, int] | None:
+    """Tight bounding box of the pixels with alpha above a threshold (f32, 0.0-1.0)."""
+    _validate_image(image, np.float32, "alpha_bbox_f32")
+    return imagestag_rust.alpha_bbox_f32(image, threshold)
+
+
+def opaque_bbox(image: np.ndarray) -> tuple[int, int, int, int] | None:
+    """Bounding box of every pixel with any opacity (u8), None if fully transparent."""
+    _validate_image(image, np.uint8, "opaque_bbox")
+    return imagestag_rust.opaque_bbox(image)
+
+
+def opaque_bbox_f32(image: np.ndarray) -> tuple[int, int, int, int] | None:
+    """Bounding box of every pixel with any opacity (f32), None if fully transparent."""
+    _validate_image(image, np.float32, "opaque_bbox_f32")
+    return imagestag_rust.opaque_bbox_f32(image)
+
+
+# ============================================================================
+# Extend Canvas
+# ============================================================================
+
+def extend_canvas_fill(image: np.ndarray, left: int = 0, top: int = 0, right: int = 0,
+                       bottom: int = 0, method: str = 'mirror') -> np.ndarray:
+    """Grow the canvas and fill the new border (u8).
+
+    'mirror' reflects the image at its edges, 'smear' repeats the edge
+    pixels with a blur along the edge that grows with the distance, and
+    'patch_match' synthesizes texture from the image (slowest, most
+    plausible for natural images).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        left, top, right, bottom: Pixels added on each side
+        method: 'mirror', 'smear' or 'patch_match'
+
+    Returns:
+        uint8 array (H + top + bottom, W + left + right, C)
+    """
+    _validate_image(image, np.uint8, "extend_canvas_fill")
+    return imagestag_rust.extend_canvas_fill(image, left, top, right, bottom, method)
+
+
+def extend_canvas_fill_f32(image: np.ndarray, left: int = 0, top: int = 0, right: int = 0,
+                           bottom: int = 0, method: str = 'mirror') -> np.ndarray:
+    """Grow the canvas and fill the new border (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        left, top, right, bottom: Pixels added on each side
+        method: 'mirror', 'smear' or 'patch_match'
+
+    Returns:
+        float32 array (H + top + bottom, W + left + right, C)
+    """
+    _validate_image(image, np.float32, "extend_canvas_fill_f32")
+    return imagestag_rust.extend_canvas_fill_f32(image, left, top, right, bottom, method)
+
+
+__all__ = [
+    'trim', 'trim_f32',
+    'alpha_bbox', 'alpha_bbox_f32', 'opaque_bbox', 'opaque_bbox_f32',
+    'extend_canvas_fill', 'extend_canvas_fill_f32',
+]
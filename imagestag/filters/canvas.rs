@@ -0,0 +1,434 @@
+//! Canvas operations: trim to content, extend with synthesized borders.
+//!
+//! [`trim_u8`] is the editor "Trim…" command: it finds the bounding box of
+//! everything that is not transparent (or not the background color taken
+//! from a corner) and crops the image to it.
+//!
+//! [`alpha_bbox_u8`] and [`opaque_bbox_u8`] are the alpha-only bounding box
+//! queries behind trimming, layer bounds for effects and dirty rectangles.
+//! They test blocks of pixels at once so the compiler vectorizes the scan.
+//!
+//! [`extend_canvas_fill_u8`] is the opposite ("uncrop"): it grows the
+//! canvas and fills the new border by mirroring, by smearing the edge, or
+//! by PatchMatch synthesis from the image content.
+//!
+//! ## Supported Formats
+//!
+//! All functions support 1, 3, or 4 channel images in both u8 and f32 formats.
+//! Transparent mode needs RGBA; without alpha every pixel counts as content.
+
+use ndarray::{s, Array2, Array3, ArrayView3};
+use rayon::prelude::*;
+
+use crate::batch::region::Rect;
+use crate::filters::synthesis::fill_region_patchmatch_f32;
+
+// ============================================================================
+// Trim
+// ============================================================================
+
+/// What counts as background when trimming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrimMode {
+    /// Pixels with alpha at or below the tolerance
+    #[default]
+    Transparent,
+    /// Pixels matching the top-left pixel within the tolerance
+    TopLeft,
+    /// Pixels matching the bottom-right pixel within the tolerance
+    BottomRight,
+}
+
+impl TrimMode {
+    /// Parse a mode name ("transparent", "top_left", "bottom_right").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('-', "_").as_str() {
+            "transparent" | "alpha" => Some(TrimMode::Transparent),
+            "top_left" | "topleft" => Some(TrimMode::TopLeft),
+            "bottom_right" | "bottomright" => Some(TrimMode::BottomRight),
+            _ => None,
+        }
+    }
+}
+
+/// Pixels whose alpha is tested together; one block of u8 alpha fills a
+/// 128-bit vector after deinterleaving.
+const ALPHA_BLOCK: usize = 16;
+
+/// Whether any pixel of an RGBA block has alpha above `threshold`.
+///
+/// Branch-free over the whole block so it compiles to vector compares.
+#[inline]
+fn block_visible<T: Copy + PartialOrd>(block: &[T], threshold: T) -> bool {
+    let mut any = false;
+    for px in block.chunks_exact(4) {
+        any |= px[3] > threshold;
+    }
+    any
+}
+
+/// First and last pixel of an RGBA row with alpha above `threshold`.
+fn row_alpha_extent<T: Copy + PartialOrd>(row: &[T], threshold: T) -> Option<(usize, usize)> {
+    let width = row.len() / 4;
+    let visible = |x: usize| row[4 * x + 3] > threshold;
+    // Find the outermost blocks with content, then the pixels inside them
+    let first_block = row.chunks(4 * ALPHA_BLOCK).position(|b| block_visible(b, threshold))?;
+    let first = (first_block * ALPHA_BLOCK..width).find(|&x| visible(x))?;
+    let last_block = row.rchunks(4 * ALPHA_BLOCK).position(|b| block_visible(b, threshold)).unwrap_or(0);
+    let last = (first..width - last_block * ALPHA_BLOCK).rev().find(|&x| visible(x)).unwrap_or(first);
+    Some((first, last))
+}
+
+fn alpha_bbox_impl<T>(image: ArrayView3<T>, threshold: T) -> Option<Rect>
+where
+    T: Copy + PartialOrd + Send + Sync,
+{
+    let (height, width, channels) = image.dim();
+    if width == 0 || height == 0 {
+        return None;
+    }
+    if channels != 4 {
+        return Some(Rect::new(0, 0, width, height));
+    }
+    let data = image.as_standard_layout();
+    let data = data.as_slice().expect("standard layout");
+    let rows: Vec<Option<(usize, usize)>> = data.par_chunks(width * 4).map(|row| row_alpha_extent(row, threshold)).collect();
+    let top = rows.iter().position(Option::is_some)?;
+    let bottom = rows.iter().rposition(Option::is_some)?;
+    let (left, right) = rows[top..=bottom]
+        .iter()
+        .flatten()
+        .fold((usize::MAX, 0), |(lo, hi), &(first, last)| (lo.min(first), hi.max(last)));
+    Some(Rect::new(left, top, right - left + 1, bottom - top + 1))
+}
+
+/// Tight bounding box of the pixels with alpha above `threshold` - u8 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels);
+///   without alpha every pixel counts
+/// * `threshold` - Largest alpha still counted as invisible (0-255)
+///
+/// # Returns
+/// Bounding rectangle, or `None` if no pixel is above the threshold
+pub fn alpha_bbox_u8(image: ArrayView3<u8>, threshold: u8) -> Option<Rect> {
+    alpha_bbox_impl(image, threshold)
+}
+
+/// Tight bounding box of the pixels with alpha above `threshold` - f32 version (0.0-1.0).
+pub fn alpha_bbox_f32(image: ArrayView3<f32>, threshold: f32) -> Option<Rect> {
+    alpha_bbox_impl(image, threshold)
+}
+
+/// Bounding box of every pixel with any opacity (alpha above 0) - u8 version.
+pub fn opaque_bbox_u8(image: ArrayView3<u8>) -> Option<Rect> {
+    alpha_bbox_impl(image, 0)
+}
+
+/// Bounding box of every pixel with any opacity (alpha above 0) - f32 version.
+pub fn opaque_bbox_f32(image: ArrayView3<f32>) -> Option<Rect> {
+    alpha_bbox_impl(image, 0.0)
+}
+
+fn content_bounds_impl<T>(image: ArrayView3<T>, mode: TrimMode, tolerance: f32) -> Option<Rect>
+where
+    T: Copy + Into<f32> + Send + Sync,
+{
+    let (height, width, channels) = image.dim();
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let reference: Vec<f32> = match mode {
+        TrimMode::Transparent => Vec::new(),
+        TrimMode::TopLeft => image.slice(s![0, 0, ..]).iter().map(|&v| v.into()).collect(),
+        TrimMode::BottomRight => image.slice(s![height - 1, width - 1, ..]).iter().map(|&v| v.into()).collect(),
+    };
+    let is_content = |y: usize, x: usize| match mode {
+        TrimMode::Transparent => channels != 4 || image[[y, x, 3]].into() > tolerance,
+        _ => (0..channels).any(|c| (image[[y, x, c]].into() - reference[c]).abs() > tolerance),
+    };
+
+    // Leftmost and rightmost content column of every row
+    let rows: Vec<Option<(usize, usize)>> = (0..height)
+        .into_par_iter()
+        .map(|y| {
+            let first = (0..width).find(|&x| is_content(y, x))?;
+            let last = (first..width).rev().find(|&x| is_content(y, x)).unwrap_or(first);
+            Some((first, last))
+        })
+        .collect();
+    let top = rows.iter().position(Option::is_some)?;
+    let bottom = rows.iter().rposition(Option::is_some)?;
+    let (left, right) = rows[top..=bottom]
+        .iter()
+        .flatten()
+        .fold((usize::MAX, 0), |(lo, hi), &(first, last)| (lo.min(first), hi.max(last)));
+    Some(Rect::new(left, top, right - left + 1, bottom - top + 1))
+}
+
+/// Bounding box of the non-background content - u8 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `mode` - Transparent pixels or a corner color as background
+/// * `tolerance` - Largest per-channel difference (or alpha) still counted as background (0-255)
+///
+/// # Returns
+/// Content rectangle, or `None` if the image holds only background
+pub fn content_bounds_u8(image: ArrayView3<u8>, mode: TrimMode, tolerance: u8) -> Option<Rect> {
+    if mode == TrimMode::Transparent {
+        return alpha_bbox_u8(image, tolerance);
+    }
+    content_bounds_impl(image, mode, tolerance as f32)
+}
+
+/// Bounding box of the non-background content - f32 version (tolerance 0.0-1.0).
+pub fn content_bounds_f32(image: ArrayView3<f32>, mode: TrimMode, tolerance: f32) -> Option<Rect> {
+    if mode == TrimMode::Transparent {
+        return alpha_bbox_f32(image, tolerance);
+    }
+    content_bounds_impl(image, mode, tolerance)
+}
+
+fn crop<T: Clone>(image: ArrayView3<T>, rect: Option<Rect>) -> (Array3<T>, Rect) {
+    let (height, width, _) = image.dim();
+    let rect = rect.unwrap_or(Rect::new(0, 0, width, height));
+    let cropped = image.slice(s![rect.y..rect.y + rect.height, rect.x..rect.x + rect.width, ..]).to_owned();
+    (cropped, rect)
+}
+
+/// Crop an image to its non-background content - u8 version.
+///
+/// An image that holds only background is returned unchanged; use
+/// [`content_bounds_u8`] to tell that case apart.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `mode` - Transparent pixels or a corner color as background
+/// * `tolerance` - Largest per-channel difference (or alpha) still counted as background (0-255)
+///
+/// # Returns
+/// (cropped image, crop rectangle in the input)
+pub fn trim_u8(image: ArrayView3<u8>, mode: TrimMode, tolerance: u8) -> (Array3<u8>, Rect) {
+    crop(image, content_bounds_u8(image, mode, tolerance))
+}
+
+/// Crop an image to its non-background content - f32 version (tolerance 0.0-1.0).
+pub fn trim_f32(image: ArrayView3<f32>, mode: TrimMode, tolerance: f32) -> (Array3<f32>, Rect) {
+    crop(image, content_bounds_f32(image, mode, tolerance))
+}
+
+// ============================================================================
+// Extend Canvas
+// ============================================================================
+
+/// How the border added by [`extend_canvas_fill_f32`] is filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtendMethod {
+    /// Reflect the image at its edges (edge pixel not repeated)
+    #[default]
+    Mirror,
+    /// Repeat the edge pixels, blurred along the edge with growing distance
+    Smear,
+    /// Synthesize texture from the image with PatchMatch
+    PatchMatch,
+}
+
+impl ExtendMethod {
+    /// Parse a method name ("mirror", "smear", "patch_match").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('-', "_").as_str() {
+            "mirror" | "reflect" => Some(ExtendMethod::Mirror),
+            "smear" | "edge" => Some(ExtendMethod::Smear),
+            "patch_match" | "patchmatch" | "content_aware" => Some(ExtendMethod::PatchMatch),
+            _ => None,
+        }
+    }
+}
+
+/// Reflect an index into 0..n without repeating the edge pixel.
+fn reflect(i: isize, n: usize) -> usize {
+    if n == 1 {
+        return 0;
+    }
+    let period = 2 * (n as isize - 1);
+    let i = i.rem_euclid(period);
+    (if i < n as isize { i } else { period - i }) as usize
+}
+
+/// Distance of an index outside 0..n, and the index clamped into it.
+fn overshoot(i: isize, n: usize) -> (usize, usize) {
+    let clamped = i.clamp(0, n as isize - 1);
+    ((i - clamped).unsigned_abs(), clamped as usize)
+}
+
+/// Smear fill: the edge pixel averaged along the edge over a window that
+/// grows with the distance from the image, so far borders turn soft.
+fn smear(image: ArrayView3<f32>, left: usize, top: usize, out_h: usize, out_w: usize) -> Array3<f32> {
+    let (height, width, channels) = image.dim();
+    let has_alpha = channels == 4;
+    // Summed-area table of premultiplied values
+    let mut sat = Array3::<f64>::zeros((height + 1, width + 1, channels));
+    for y in 0..height {
+        for x in 0..width {
+            let alpha = if has_alpha { image[[y, x, 3]] as f64 } else { 1.0 };
+            for c in 0..channels {
+                let v = image[[y, x, c]] as f64 * if has_alpha && c < 3 { alpha } else { 1.0 };
+                sat[[y + 1, x + 1, c]] = v + sat[[y, x + 1, c]] + sat[[y + 1, x, c]] - sat[[y, x, c]];
+            }
+        }
+    }
+    let values: Vec<f32> = (0..out_h)
+        .into_par_iter()
+        .flat_map_iter(|oy| {
+            let (over_y, sy) = overshoot(oy as isize - top as isize, height);
+            let sat = &sat;
+            (0..out_w).flat_map(move |ox| {
+                let (over_x, sx) = overshoot(ox as isize - left as isize, width);
+                // Beyond the top/bottom edge the window runs along x, beyond left/right along y
+                let (x0, x1) = (sx.saturating_sub(over_y / 2), (sx + over_y / 2 + 1).min(width));
+                let (y0, y1) = (sy.saturating_sub(over_x / 2), (sy + over_x / 2 + 1).min(height));
+                let area = ((x1 - x0) * (y1 - y0)) as f64;
+                let mean = move |c: usize| (sat[[y1, x1, c]] + sat[[y0, x0, c]] - sat[[y0, x1, c]] - sat[[y1, x0, c]]) / area;
+                let alpha = if has_alpha { mean(3) } else { 1.0 };
+                (0..channels).map(move |c| {
+                    if has_alpha && c < 3 {
+                        if alpha > 0.0 { (mean(c) / alpha) as f32 } else { 0.0 }
+                    } else {
+                        mean(c) as f32
+                    }
+                })
+            })
+        })
+        .collect();
+    Array3::from_shape_vec((out_h, out_w, channels), values).expect("smear covers the canvas")
+}
+
+/// Grow the canvas and fill the new border - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `left`, `top`, `right`, `bottom` - Pixels added on each side
+/// * `method` - Mirror, smear or PatchMatch synthesis
+///
+/// # Returns
+/// Image of (height + top + bottom, width + left + right) with the input at (left, top)
+pub fn extend_canvas_fill_f32(
+    image: ArrayView3<f32>,
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+    method: ExtendMethod,
+) -> Array3<f32> {
+    let (height, width, channels) = image.dim();
+    let (out_h, out_w) = (height + top + bottom, width + left + right);
+    if width == 0 || height == 0 {
+        return Array3::zeros((out_h, out_w, channels));
+    }
+    match method {
+        ExtendMethod::Mirror => Array3::from_shape_fn((out_h, out_w, channels), |(y, x, c)| {
+            image[[reflect(y as isize - top as isize, height), reflect(x as isize - left as isize, width), c]]
+        }),
+        ExtendMethod::Smear => smear(image, left, top, out_h, out_w),
+        ExtendMethod::PatchMatch => {
+            let mut canvas = Array3::<f32>::zeros((out_h, out_w, channels));
+            canvas.slice_mut(s![top..top + height, left..left + width, ..]).assign(&image);
+            let mask = Array2::from_shape_fn((out_h, out_w), |(y, x)| {
+                if (top..top + height).contains(&y) && (left..left + width).contains(&x) { 0.0 } else { 1.0 }
+            });
+            fill_region_patchmatch_f32(canvas.view(), mask.view(), 7, 0)
+        }
+    }
+}
+
+/// Grow the canvas and fill the new border - u8 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `left`, `top`, `right`, `bottom` - Pixels added on each side
+/// * `method` - Mirror, smear or PatchMatch synthesis
+pub fn extend_canvas_fill_u8(
+    image: ArrayView3<u8>,
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+    method: ExtendMethod,
+) -> Array3<u8> {
+    if method == ExtendMethod::Mirror {
+        // Pure copy, no need for a float round trip
+        let (height, width, channels) = image.dim();
+        if width > 0 && height > 0 {
+            return Array3::from_shape_fn((height + top + bottom, width + left + right, channels), |(y, x, c)| {
+                image[[reflect(y as isize - top as isize, height), reflect(x as isize - left as isize, width), c]]
+            });
+        }
+    }
+    let image_f32 = image.mapv(|v| v as f32 / 255.0);
+    extend_canvas_fill_f32(image_f32.view(), left, top, right, bottom, method)
+        .mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_transparent_and_color_borders() {
+        // Opaque 3x2 block at (4, 2) on a transparent canvas, one faint pixel
+        let mut image = Array3::<u8>::zeros((10, 12, 4));
+        image.slice_mut(s![2..4, 4..7, ..]).fill(255);
+        image[[8, 1, 3]] = 3;
+        let (cropped, rect) = trim_u8(image.view(), TrimMode::Transparent, 5);
+        assert_eq!(rect, Rect::new(4, 2, 3, 2));
+        assert_eq!(cropped.dim(), (2, 3, 4));
+        assert_eq!(trim_u8(image.view(), TrimMode::Transparent, 0).1, Rect::new(1, 2, 6, 7));
+
+        // White border around a gray square
+        let image = Array3::from_shape_fn((8, 8, 3), |(y, x, _)| if (3..6).contains(&y) && (2..4).contains(&x) { 0.5 } else { 1.0 });
+        assert_eq!(trim_f32(image.view(), TrimMode::BottomRight, 0.01).1, Rect::new(2, 3, 2, 3));
+
+        // Only background: unchanged, no bounds
+        let blank = Array3::<f32>::ones((4, 5, 3));
+        assert_eq!(content_bounds_f32(blank.view(), TrimMode::TopLeft, 0.0), None);
+        assert_eq!(trim_f32(blank.view(), TrimMode::TopLeft, 0.0).1, Rect::new(0, 0, 5, 4));
+    }
+
+    #[test]
+    fn test_alpha_bbox_matches_per_pixel_scan() {
+        // Wide enough for several blocks per row, content in odd places
+        let mut image = Array3::<u8>::zeros((9, 53, 4));
+        image[[3, 17, 3]] = 200;
+        image[[6, 50, 3]] = 40;
+        image[[7, 33, 3]] = 10;
+        assert_eq!(alpha_bbox_u8(image.view(), 20), Some(Rect::new(17, 3, 34, 4)));
+        assert_eq!(opaque_bbox_u8(image.view()), Some(Rect::new(17, 3, 34, 5)));
+        assert_eq!(alpha_bbox_u8(image.view(), 200), None);
+
+        // Non-contiguous views and f32 alpha
+        let float = image.mapv(|v| v as f32 / 255.0);
+        let flipped = float.slice(s![.., ..;-1, ..]);
+        assert_eq!(alpha_bbox_f32(flipped, 0.1), Some(Rect::new(2, 3, 34, 4)));
+        assert_eq!(opaque_bbox_f32(Array3::<f32>::zeros((2, 2, 3)).view()), Some(Rect::new(0, 0, 2, 2)));
+        assert_eq!(opaque_bbox_f32(Array3::<f32>::zeros((0, 2, 4)).view()), None);
+    }
+
+    #[test]
+    fn test_extend_canvas_mirror_and_smear() {
+        let image = Array3::from_shape_fn((2, 3, 1), |(y, x, _)| (10 * y + x) as f32);
+        let mirrored = extend_canvas_fill_f32(image.view(), 2, 1, 0, 3, ExtendMethod::Mirror);
+        assert_eq!(mirrored.dim(), (6, 5, 1));
+        let row: Vec<f32> = mirrored.slice(s![1, .., 0]).to_vec();
+        assert_eq!(row, vec![2.0, 1.0, 0.0, 1.0, 2.0]);
+        let column: Vec<f32> = mirrored.slice(s![.., 2, 0]).to_vec();
+        assert_eq!(column, vec![10.0, 0.0, 10.0, 0.0, 10.0, 0.0]);
+
+        // Next to the edge the smear repeats it, further out it averages along it
+        let smeared = extend_canvas_fill_f32(image.view(), 0, 2, 0, 0, ExtendMethod::Smear);
+        assert_eq!(smeared[[1, 0, 0]], 0.0);
+        assert_eq!(smeared[[0, 1, 0]], 1.0);
+        assert_eq!(smeared[[0, 0, 0]], 0.5);
+        assert_eq!(smeared.slice(s![2.., .., ..]), image);
+    }
+}
@@ -123,13 +123,19 @@ class Grayscale(Filter):
 
 @register_filter
 class Invert(Filter):
-    """Invert colors (negative)."""
+    """Invert colors (negative).
+
+    channels selects what is inverted: 'rgb' (default), single channels
+    such as 'r', 'a' to invert only the alpha mask, or 'rgba'.
+    """
 
     _native_frameworks: ClassVar[list[ImsFramework]] = [ImsFramework.RAW]
 
+    channels: str = 'rgb'
+
     def apply(self, image: Image, context: FilterContext | None = None) -> Image:
         from imagestag.filters.color_adjust import invert
-        return _apply_color_rust(image, invert)
+        return _apply_color_rust(image, invert, self.channels)
 
 
 @register_filter
@@ -233,17 +239,22 @@ class Equalize(Filter):
     """Equalize the image histogram.
 
     Applies a non-linear mapping to the input image to create
-    a uniform distribution of grayscale values.
+    a uniform distribution of values. mode 'per_channel' (default)
+    equalizes R, G and B independently, 'luminance' equalizes the
+    brightness only and keeps the colors.
 
     Example:
         'equalize()'
+        'equalize(mode=luminance)'
     """
 
     _native_frameworks: ClassVar[list[ImsFramework]] = [ImsFramework.RAW]
 
+    mode: str = 'per_channel'
+
     def apply(self, image: 'Image', context: FilterContext | None = None) -> 'Image':
         from imagestag.filters.color_adjust import equalize_histogram
-        return _apply_color_rust(image, equalize_histogram)
+        return _apply_color_rust(image, equalize_histogram, self.mode)
 
 
 @register_filter
@@ -134,14 +134,22 @@ export const exposure_f32 = createF32Filter(
 /**
  * Invert image colors (u8).
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {channels: string} channels to invert: 'rgb' (default),
+ *     single letters like 'r', 'a' for alpha only (inverts a mask), or 'rgba'
  * @returns {Object} - Inverted image data
  */
-export const invert = createU8Filter(wasm.invert_wasm);
+export const invert = createU8Filter(
+    wasm.invert_wasm,
+    (opts) => [opts.channels ?? 'rgb']
+);
 
 /**
  * Invert image colors (f32).
  */
-export const invert_f32 = createF32Filter(wasm.invert_f32_wasm);
+export const invert_f32 = createF32Filter(
+    wasm.invert_f32_wasm,
+    (opts) => [opts.channels ?? 'rgb']
+);
 
 export default {
     initWasm,
@@ -18,10 +18,14 @@ All filters accept numpy arrays with 1, 3, or 4 channels:
 | RGBA8 | (H, W, 4) | uint8 | 4 channels, 0-255 |
 | RGBA float | (H, W, 4) | float32 | 4 channels, 0.0-1.0 |
 
+Except for histogram equalization, the filters also accept an (N, H, W, C)
+stack of animation frames and process all frames in one parallel call.
+
 ## Bit Depth Support
 
 - **u8 (8-bit)**: Values 0-255, standard for web/display
 - **f32 (float)**: Values 0.0-1.0, for HDR/linear workflows
+- **12-bit**: uint16 values 0-4095 (``brightness_12bit``, ``contrast_12bit``)
 
 Usage:
     from imagestag.filters.color_adjust import brightness, contrast
@@ -34,9 +38,13 @@ import numpy as np
 import imagestag_rust
 
 
-def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
-    """Validate image shape and dtype."""
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str, frames: bool = False) -> None:
+    """Validate image shape and dtype.
+
+    With ``frames``, an (N, H, W, C) stack of animation frames is accepted too.
+    """
+    ndims = (3, 4) if frames else (3,)
+    if image.ndim not in ndims or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, 1|3|4), got shape {image.shape}")
     if image.dtype != expected_dtype:
         raise ValueError(f"Expected {expected_dtype} dtype, got {image.dtype}")
@@ -56,7 +64,7 @@ def brightness(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     Returns:
         Brightness-adjusted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "brightness")
+    _validate_image(image, np.uint8, "brightness", frames=True)
     return imagestag_rust.brightness(image, amount)
 
 
@@ -70,10 +78,24 @@ def brightness_f32(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     Returns:
         Brightness-adjusted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "brightness_f32")
+    _validate_image(image, np.float32, "brightness_f32", frames=True)
     return imagestag_rust.brightness_f32(image, amount)
 
 
+def brightness_12bit(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
+    """Adjust image brightness (12-bit).
+
+    Args:
+        image: uint16 array with 1, 3, or 4 channels (H, W, C), values 0-4095
+        amount: -1.0 (black) to 1.0 (white), 0.0 = no change
+
+    Returns:
+        Brightness-adjusted uint16 array with same channel count
+    """
+    _validate_image(image, np.uint16, "brightness_12bit", frames=True)
+    return imagestag_rust.brightness_12bit(image, amount)
+
+
 # ============================================================================
 # Contrast
 # ============================================================================
@@ -88,7 +110,7 @@ def contrast(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     Returns:
         Contrast-adjusted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "contrast")
+    _validate_image(image, np.uint8, "contrast", frames=True)
     return imagestag_rust.contrast(image, amount)
 
 
@@ -102,10 +124,24 @@ def contrast_f32(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     Returns:
         Contrast-adjusted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "contrast_f32")
+    _validate_image(image, np.float32, "contrast_f32", frames=True)
     return imagestag_rust.contrast_f32(image, amount)
 
 
+def contrast_12bit(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
+    """Adjust image contrast (12-bit).
+
+    Args:
+        image: uint16 array with 1, 3, or 4 channels (H, W, C), values 0-4095
+        amount: -1.0 (gray) to 1.0 (max contrast), 0.0 = no change
+
+    Returns:
+        Contrast-adjusted uint16 array with same channel count
+    """
+    _validate_image(image, np.uint16, "contrast_12bit", frames=True)
+    return imagestag_rust.contrast_12bit(image, amount)
+
+
 # ============================================================================
 # Saturation
 # ============================================================================
@@ -120,7 +156,7 @@ def saturation(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     Returns:
         Saturation-adjusted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "saturation")
+    _validate_image(image, np.uint8, "saturation", frames=True)
     return imagestag_rust.saturation(image, amount)
 
 
@@ -134,7 +170,7 @@ def saturation_f32(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     Returns:
         Saturation-adjusted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "saturation_f32")
+    _validate_image(image, np.float32, "saturation_f32", frames=True)
     return imagestag_rust.saturation_f32(image, amount)
 
 
@@ -153,7 +189,7 @@ def gamma(image: np.ndarray, gamma_value: float = 1.0) -> np.ndarray:
     Returns:
         Gamma-corrected uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "gamma")
+    _validate_image(image, np.uint8, "gamma", frames=True)
     return imagestag_rust.gamma(image, gamma_value)
 
 
@@ -167,7 +203,7 @@ def gamma_f32(image: np.ndarray, gamma_value: float = 1.0) -> np.ndarray:
     Returns:
         Gamma-corrected float32 array with same channel count
     """
-    _validate_image(image, np.float32, "gamma_f32")
+    _validate_image(image, np.float32, "gamma_f32", frames=True)
     return imagestag_rust.gamma_f32(image, gamma_value)
 
 
@@ -190,7 +226,7 @@ def exposure(image: np.ndarray, exposure_val: float = 0.0,
     Returns:
         Exposure-adjusted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "exposure")
+    _validate_image(image, np.uint8, "exposure", frames=True)
     return imagestag_rust.exposure(image, exposure_val, offset, gamma_val)
 
 
@@ -207,7 +243,7 @@ def exposure_f32(image: np.ndarray, exposure_val: float = 0.0,
     Returns:
         Exposure-adjusted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "exposure_f32")
+    _validate_image(image, np.float32, "exposure_f32", frames=True)
     return imagestag_rust.exposure_f32(image, exposure_val, offset, gamma_val)
 
 
@@ -215,67 +251,89 @@ def exposure_f32(image: np.ndarray, exposure_val: float = 0.0,
 # Invert
 # ============================================================================
 
-def invert(image: np.ndarray) -> np.ndarray:
+def invert(image: np.ndarray, channels: str = "rgb") -> np.ndarray:
     """Invert image colors (u8).
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        channels: Channels to invert: "rgb" (default), letters such as "r" or
+            "gb", "a" to invert only alpha (e.g. a mask), or "rgba"
 
     Returns:
-        Inverted uint8 array with same channel count (alpha preserved if present)
+        uint8 array with the selected channels inverted, others unchanged
     """
-    _validate_image(image, np.uint8, "invert")
-    return imagestag_rust.invert(image)
+    _validate_image(image, np.uint8, "invert", frames=True)
+    return imagestag_rust.invert(image, channels)
 
 
-def invert_f32(image: np.ndarray) -> np.ndarray:
+def invert_f32(image: np.ndarray, channels: str = "rgb") -> np.ndarray:
     """Invert image colors (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        channels: Channels to invert (see invert)
 
     Returns:
-        Inverted float32 array with same channel count (alpha preserved if present)
+        float32 array with the selected channels inverted, others unchanged
     """
-    _validate_image(image, np.float32, "invert_f32")
-    return imagestag_rust.invert_f32(image)
+    _validate_image(image, np.float32, "invert_f32", frames=True)
+    return imagestag_rust.invert_f32(image, channels)
 
 
 # ============================================================================
 # Equalize Histogram
 # ============================================================================
 
-def equalize_histogram(image: np.ndarray) -> np.ndarray:
+def equalize_histogram(
+    image: np.ndarray,
+    mode: str = "per_channel",
+    mask: np.ndarray | None = None,
+) -> np.ndarray:
     """Equalize image histogram (u8).
 
     Spreads out intensity values to use the full range, improving contrast.
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        mode: 'per_channel' equalizes R, G and B independently (shifts
+            colors), 'luminance' equalizes brightness only and keeps colors
+        mask: Optional uint8 (H, W) or (H, W, 1) mask; only masked pixels
+            count towards the histogram and are changed (0 = ignored,
+            255 = equalized), e.g. to leave a background out
 
     Returns:
         Histogram-equalized uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "equalize_histogram")
-    return imagestag_rust.equalize_histogram(image)
+    if mask is not None:
+        mask = np.ascontiguousarray(mask.reshape(mask.shape[:2]), dtype=np.uint8)
+    return imagestag_rust.equalize_histogram(image, mode, mask)
 
 
-def equalize_histogram_f32(image: np.ndarray) -> np.ndarray:
+def equalize_histogram_f32(
+    image: np.ndarray,
+    mode: str = "per_channel",
+    mask: np.ndarray | None = None,
+) -> np.ndarray:
     """Equalize image histogram (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        mode: 'per_channel' or 'luminance'
+        mask: Optional float32 (H, W) or (H, W, 1) mask, 0.0-1.0
 
     Returns:
         Histogram-equalized float32 array with same channel count
     """
     _validate_image(image, np.float32, "equalize_histogram_f32")
-    return imagestag_rust.equalize_histogram_f32(image)
+    if mask is not None:
+        mask = np.ascontiguousarray(mask.reshape(mask.shape[:2]), dtype=np.float32)
+    return imagestag_rust.equalize_histogram_f32(image, mode, mask)
 
 
 __all__ = [
-    'brightness', 'brightness_f32',
-    'contrast', 'contrast_f32',
+    'brightness', 'brightness_f32', 'brightness_12bit',
+    'contrast', 'contrast_f32', 'contrast_12bit',
     'saturation', 'saturation_f32',
     'gamma', 'gamma_f32',
     'exposure', 'exposure_f32',
@@ -1,8 +1,9 @@
 //! Color adjustment filters: Brightness, Contrast, Saturation, Gamma, Exposure, Invert,
-//! Equalize Histogram.
+//! Equalize Histogram (per channel or luminance, optionally masked).
 //!
 //! These are pixel-wise operations that don't require spatial context.
-//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
+//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes; brightness
+//! and contrast also take 12-bit data stored in u16 (0-4095) directly.
 //!
 //! ## Supported Formats
 //!
@@ -14,7 +15,12 @@
 //! Channel count is inferred from the input array dimensions.
 //! Alpha channel (if present) is always preserved unchanged.
 
-use ndarray::{Array3, ArrayView3};
+use ndarray::{Array3, ArrayView2, ArrayView3, Axis};
+
+use super::fmath;
+
+/// Largest 12-bit value.
+const MAX_12BIT: f32 = 4095.0;
 
 // ============================================================================
 // Brightness
@@ -28,7 +34,8 @@ use ndarray::{Array3, ArrayView3};
 ///
 /// # Returns
 /// Brightness-adjusted image with same channel count
-pub fn brightness_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+pub fn brightness_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, amount: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
     let offset = amount * 255.0;
@@ -59,7 +66,8 @@ pub fn brightness_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
 ///
 /// # Returns
 /// Brightness-adjusted image with same channel count
-pub fn brightness_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+pub fn brightness_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, amount: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -78,6 +86,32 @@ pub fn brightness_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
     output
 }
 
+/// Adjust image brightness (12-bit version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0-4095
+/// * `amount` - Brightness adjustment: -1.0 (black) to 1.0 (white), 0.0 = no change
+///
+/// # Returns
+/// Brightness-adjusted image with same channel count
+pub fn brightness_12bit<'a>(input: impl Into<ArrayView3<'a, u16>>, amount: f32) -> Array3<u16> {
+    let offset = amount * MAX_12BIT;
+    map_color_12bit(input.into(), |v| v + offset)
+}
+
+/// Apply a per-value function to the color channels of a 12-bit image via
+/// a 4096-entry lookup table. Results are clamped to 0-4095, alpha is kept.
+fn map_color_12bit(input: ArrayView3<u16>, f: impl Fn(f32) -> f32) -> Array3<u16> {
+    let lut: Vec<u16> = (0..4096).map(|v| f(v as f32).round().clamp(0.0, MAX_12BIT) as u16).collect();
+    let channels = input.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = input.to_owned();
+    for c in 0..color_channels {
+        output.index_axis_mut(Axis(2), c).mapv_inplace(|v| lut[v.min(4095) as usize]);
+    }
+    output
+}
+
 // ============================================================================
 // Contrast
 // ============================================================================
@@ -90,7 +124,8 @@ pub fn brightness_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
 ///
 /// # Returns
 /// Contrast-adjusted image with same channel count
-pub fn contrast_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+pub fn contrast_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, amount: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -125,7 +160,8 @@ pub fn contrast_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
 ///
 /// # Returns
 /// Contrast-adjusted image with same channel count
-pub fn contrast_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+pub fn contrast_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, amount: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -152,6 +188,24 @@ pub fn contrast_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
     output
 }
 
+/// Adjust image contrast (12-bit version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0-4095
+/// * `amount` - Contrast adjustment: -1.0 (gray) to 1.0 (max contrast), 0.0 = no change
+///
+/// # Returns
+/// Contrast-adjusted image with same channel count
+pub fn contrast_12bit<'a>(input: impl Into<ArrayView3<'a, u16>>, amount: f32) -> Array3<u16> {
+    let factor = if amount >= 0.0 {
+        1.0 + amount * 3.0
+    } else {
+        1.0 + amount
+    };
+    let mid = MAX_12BIT / 2.0;
+    map_color_12bit(input.into(), |v| (v - mid) * factor + mid)
+}
+
 // ============================================================================
 // Saturation
 // ============================================================================
@@ -166,7 +220,8 @@ pub fn contrast_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
 ///
 /// # Returns
 /// Saturation-adjusted image with same channel count
-pub fn saturation_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+pub fn saturation_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, amount: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -216,7 +271,8 @@ pub fn saturation_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
 ///
 /// # Returns
 /// Saturation-adjusted image with same channel count
-pub fn saturation_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+pub fn saturation_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, amount: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -267,7 +323,8 @@ pub fn saturation_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
 ///
 /// # Returns
 /// Gamma-corrected image with same channel count
-pub fn gamma_u8(input: ArrayView3<u8>, gamma: f32) -> Array3<u8> {
+pub fn gamma_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, gamma: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -278,7 +335,7 @@ pub fn gamma_u8(input: ArrayView3<u8>, gamma: f32) -> Array3<u8> {
         for x in 0..width {
             for c in 0..color_channels {
                 let v = input[[y, x, c]] as f32 / 255.0;
-                let corrected = v.powf(inv_gamma);
+                let corrected = fmath::powf(v, inv_gamma);
                 output[[y, x, c]] = (corrected * 255.0).clamp(0.0, 255.0) as u8;
             }
             if channels == 4 {
@@ -297,7 +354,8 @@ pub fn gamma_u8(input: ArrayView3<u8>, gamma: f32) -> Array3<u8> {
 ///
 /// # Returns
 /// Gamma-corrected image with same channel count
-pub fn gamma_f32(input: ArrayView3<f32>, gamma: f32) -> Array3<f32> {
+pub fn gamma_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, gamma: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -308,7 +366,7 @@ pub fn gamma_f32(input: ArrayView3<f32>, gamma: f32) -> Array3<f32> {
         for x in 0..width {
             for c in 0..color_channels {
                 let v = input[[y, x, c]].clamp(0.0, 1.0);
-                output[[y, x, c]] = v.powf(inv_gamma);
+                output[[y, x, c]] = fmath::powf(v, inv_gamma);
             }
             if channels == 4 {
                 output[[y, x, 3]] = input[[y, x, 3]];
@@ -332,11 +390,12 @@ pub fn gamma_f32(input: ArrayView3<f32>, gamma: f32) -> Array3<f32> {
 ///
 /// # Returns
 /// Exposure-adjusted image with same channel count
-pub fn exposure_u8(input: ArrayView3<u8>, exposure: f32, offset: f32, gamma: f32) -> Array3<u8> {
+pub fn exposure_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, exposure: f32, offset: f32, gamma: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
-    let multiplier = 2.0_f32.powf(exposure);
+    let multiplier = fmath::powf(2.0, exposure);
     let inv_gamma = 1.0 / gamma.max(0.001);
     let color_channels = if channels == 4 { 3 } else { channels };
 
@@ -345,7 +404,7 @@ pub fn exposure_u8(input: ArrayView3<u8>, exposure: f32, offset: f32, gamma: f32
             for c in 0..color_channels {
                 let v = input[[y, x, c]] as f32 / 255.0;
                 let exposed = (v * multiplier + offset).clamp(0.0, 1.0);
-                let corrected = exposed.powf(inv_gamma);
+                let corrected = fmath::powf(exposed, inv_gamma);
                 output[[y, x, c]] = (corrected * 255.0).clamp(0.0, 255.0) as u8;
             }
             if channels == 4 {
@@ -366,11 +425,12 @@ pub fn exposure_u8(input: ArrayView3<u8>, exposure: f32, offset: f32, gamma: f32
 ///
 /// # Returns
 /// Exposure-adjusted image with same channel count
-pub fn exposure_f32(input: ArrayView3<f32>, exposure: f32, offset: f32, gamma: f32) -> Array3<f32> {
+pub fn exposure_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, exposure: f32, offset: f32, gamma: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
-    let multiplier = 2.0_f32.powf(exposure);
+    let multiplier = fmath::powf(2.0, exposure);
     let inv_gamma = 1.0 / gamma.max(0.001);
     let color_channels = if channels == 4 { 3 } else { channels };
 
@@ -379,7 +439,7 @@ pub fn exposure_f32(input: ArrayView3<f32>, exposure: f32, offset: f32, gamma: f
             for c in 0..color_channels {
                 let v = input[[y, x, c]];
                 let exposed = (v * multiplier + offset).clamp(0.0, 1.0);
-                output[[y, x, c]] = exposed.powf(inv_gamma);
+                output[[y, x, c]] = fmath::powf(exposed, inv_gamma);
             }
             if channels == 4 {
                 output[[y, x, 3]] = input[[y, x, 3]];
@@ -393,6 +453,67 @@ pub fn exposure_f32(input: ArrayView3<f32>, exposure: f32, offset: f32, gamma: f
 // Invert
 // ============================================================================
 
+/// Channels an operation is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelSelection {
+    pub red: bool,
+    pub green: bool,
+    pub blue: bool,
+    pub alpha: bool,
+}
+
+impl ChannelSelection {
+    /// Red, green and blue
+    pub const COLOR: Self = Self { red: true, green: true, blue: true, alpha: false };
+    /// Alpha only (e.g. to invert a mask)
+    pub const ALPHA: Self = Self { red: false, green: false, blue: false, alpha: true };
+
+    /// Parse a selection: the letters of the channels ("r", "gb", "rgba", "a")
+    /// or "rgb" / "color", "alpha", "all".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "color" => Some(Self::COLOR),
+            "alpha" => Some(Self::ALPHA),
+            "all" => Some(Self { alpha: true, ..Self::COLOR }),
+            "" => None,
+            letters => {
+                let mut selection = Self { red: false, green: false, blue: false, alpha: false };
+                for letter in letters.chars() {
+                    match letter {
+                        'r' => selection.red = true,
+                        'g' => selection.green = true,
+                        'b' => selection.blue = true,
+                        'a' => selection.alpha = true,
+                        _ => return None,
+                    }
+                }
+                Some(selection)
+            }
+        }
+    }
+
+    /// Whether channel `c` of an image with `channels` channels is selected.
+    ///
+    /// The gray channel counts as selected when any color channel is; alpha
+    /// only exists in 4-channel images.
+    fn includes(self, c: usize, channels: usize) -> bool {
+        match (channels, c) {
+            (1, 0) => self.red || self.green || self.blue,
+            (_, 0) => self.red,
+            (_, 1) => self.green,
+            (_, 2) => self.blue,
+            (4, 3) => self.alpha,
+            _ => false,
+        }
+    }
+}
+
+impl Default for ChannelSelection {
+    fn default() -> Self {
+        Self::COLOR
+    }
+}
+
 /// Invert image colors (u8 version).
 ///
 /// # Arguments
@@ -400,23 +521,8 @@ pub fn exposure_f32(input: ArrayView3<f32>, exposure: f32, offset: f32, gamma: f
 ///
 /// # Returns
 /// Color-inverted image (alpha preserved if present)
-pub fn invert_u8(input: ArrayView3<u8>) -> Array3<u8> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
-
-    let color_channels = if channels == 4 { 3 } else { channels };
-
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..color_channels {
-                output[[y, x, c]] = 255 - input[[y, x, c]];
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
-        }
-    }
-    output
+pub fn invert_u8<'a>(input: impl Into<ArrayView3<'a, u8>>) -> Array3<u8> {
+    invert_channels_u8(input, ChannelSelection::COLOR)
 }
 
 /// Invert image colors (f32 version).
@@ -426,21 +532,37 @@ pub fn invert_u8(input: ArrayView3<u8>) -> Array3<u8> {
 ///
 /// # Returns
 /// Color-inverted image (alpha preserved if present)
-pub fn invert_f32(input: ArrayView3<f32>) -> Array3<f32> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
+pub fn invert_f32<'a>(input: impl Into<ArrayView3<'a, f32>>) -> Array3<f32> {
+    invert_channels_f32(input, ChannelSelection::COLOR)
+}
 
-    let color_channels = if channels == 4 { 3 } else { channels };
+/// Invert selected channels (u8 version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `selection` - Channels to invert; the others are copied unchanged
+///
+/// # Returns
+/// Image with the selected channels inverted
+pub fn invert_channels_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, selection: ChannelSelection) -> Array3<u8> {
+    let input = input.into();
+    let channels = input.dim().2;
+    let mut output = input.to_owned();
+    for c in (0..channels).filter(|&c| selection.includes(c, channels)) {
+        output.index_axis_mut(Axis(2), c).mapv_inplace(|v| 255 - v);
+    }
+    output
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..color_channels {
-                output[[y, x, c]] = 1.0 - input[[y, x, c]];
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
-        }
+/// Invert selected channels (f32 version).
+///
+/// Same as invert_channels_u8 but for f32 images (values 0.0-1.0).
+pub fn invert_channels_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, selection: ChannelSelection) -> Array3<f32> {
+    let input = input.into();
+    let channels = input.dim().2;
+    let mut output = input.to_owned();
+    for c in (0..channels).filter(|&c| selection.includes(c, channels)) {
+        output.index_axis_mut(Axis(2), c).mapv_inplace(|v| 1.0 - v);
     }
     output
 }
@@ -449,6 +571,54 @@ pub fn invert_f32(input: ArrayView3<f32>) -> Array3<f32> {
 // Equalize Histogram
 // ============================================================================
 
+/// How histogram equalization treats the color channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EqualizeMode {
+    /// Equalize R, G and B independently (stretches contrast, shifts colors)
+    #[default]
+    PerChannel,
+    /// Equalize the BT.709 luminance and shift R, G and B by the same amount,
+    /// keeping the colors
+    Luminance,
+}
+
+impl EqualizeMode {
+    /// Parse "per_channel" / "channels" or "luminance" / "luma".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "per_channel" | "per-channel" | "channels" => Some(EqualizeMode::PerChannel),
+            "luminance" | "luma" => Some(EqualizeMode::Luminance),
+            _ => None,
+        }
+    }
+}
+
+/// BT.709 luminance
+#[inline]
+fn luma(r: f32, g: f32, b: f32) -> f32 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Normalized (0.0-1.0) equalization curve of a 256 bin histogram.
+///
+/// Returns None if all weight is in one bin, i.e. there is nothing to spread.
+fn equalize_lut(hist: &[f64; 256]) -> Option<[f32; 256]> {
+    let mut cdf = [0.0f64; 256];
+    let mut sum = 0.0;
+    for (i, &count) in hist.iter().enumerate() {
+        sum += count;
+        cdf[i] = sum;
+    }
+
+    // Minimum non-zero CDF value
+    let cdf_min = cdf.iter().copied().find(|&v| v > 0.0).unwrap_or(0.0) as f32;
+    let denom = sum as f32 - cdf_min;
+    if denom <= 0.0 {
+        return None;
+    }
+    Some(std::array::from_fn(|i| ((cdf[i] as f32 - cdf_min) / denom).clamp(0.0, 1.0)))
+}
+
 /// Equalize image histogram (u8 version).
 ///
 /// Performs per-channel histogram equalization using CDF mapping.
@@ -459,54 +629,98 @@ pub fn invert_f32(input: ArrayView3<f32>) -> Array3<f32> {
 ///
 /// # Returns
 /// Histogram-equalized image with same channel count
-pub fn equalize_histogram_u8(input: ArrayView3<u8>) -> Array3<u8> {
+pub fn equalize_histogram_u8<'a>(input: impl Into<ArrayView3<'a, u8>>) -> Array3<u8> {
+    equalize_histogram_masked_u8(input, EqualizeMode::PerChannel, None)
+}
+
+/// Equalize image histogram (f32 version).
+///
+/// Performs per-channel histogram equalization by quantizing to 256 bins,
+/// computing CDF, and mapping back to 0.0-1.0.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+///
+/// # Returns
+/// Histogram-equalized image with same channel count
+pub fn equalize_histogram_f32<'a>(input: impl Into<ArrayView3<'a, f32>>) -> Array3<f32> {
+    equalize_histogram_masked_f32(input, EqualizeMode::PerChannel, None)
+}
+
+/// Equalize image histogram with a channel mode and an optional mask (u8 version).
+///
+/// The histogram is built from the masked pixels only (weighted by the mask),
+/// so a large background does not dominate the curve, and only masked pixels
+/// are changed. Channels whose values all fall into one bin stay unchanged.
+/// Grayscale images are equalized the same way in both modes.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `mode` - Per-channel or luminance equalization
+/// * `mask` - Optional (height, width) mask, 0 = ignored, 255 = equalized
+///
+/// # Returns
+/// Histogram-equalized image with same channel count
+pub fn equalize_histogram_masked_u8<'a>(
+    input: impl Into<ArrayView3<'a, u8>>,
+    mode: EqualizeMode,
+    mask: Option<ArrayView2<u8>>,
+) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
     let color_channels = if channels == 4 { 3 } else { channels };
-    let total_pixels = (height * width) as f32;
+    let weight = |y: usize, x: usize| mask.map_or(1.0, |m| m[[y, x]] as f32 / 255.0);
+    // Alpha and unmasked pixels are preserved
+    let mut output = input.to_owned();
 
-    for c in 0..color_channels {
-        // Build histogram
-        let mut hist = [0u32; 256];
+    if mode == EqualizeMode::Luminance && color_channels == 3 {
+        let lum = |y: usize, x: usize| luma(input[[y, x, 0]] as f32, input[[y, x, 1]] as f32, input[[y, x, 2]] as f32);
+        let mut hist = [0.0f64; 256];
         for y in 0..height {
             for x in 0..width {
-                hist[input[[y, x, c]] as usize] += 1;
+                hist[(lum(y, x).round() as usize).min(255)] += weight(y, x) as f64;
             }
         }
+        let Some(lut) = equalize_lut(&hist) else { return output };
 
-        // Build CDF
-        let mut cdf = [0u32; 256];
-        cdf[0] = hist[0];
-        for i in 1..256 {
-            cdf[i] = cdf[i - 1] + hist[i];
-        }
-
-        // Find minimum non-zero CDF value
-        let cdf_min = cdf.iter().copied().find(|&v| v > 0).unwrap_or(0) as f32;
-
-        // Build lookup table
-        let mut lut = [0u8; 256];
-        let denom = total_pixels - cdf_min;
-        if denom > 0.0 {
-            for i in 0..256 {
-                lut[i] = ((cdf[i] as f32 - cdf_min) / denom * 255.0)
-                    .clamp(0.0, 255.0) as u8;
+        for y in 0..height {
+            for x in 0..width {
+                let amount = weight(y, x);
+                if amount <= 0.0 {
+                    continue;
+                }
+                let l = lum(y, x);
+                let shift = (lut[(l.round() as usize).min(255)] * 255.0 - l) * amount;
+                for c in 0..3 {
+                    output[[y, x, c]] = (input[[y, x, c]] as f32 + shift).round().clamp(0.0, 255.0) as u8;
+                }
             }
         }
+        return output;
+    }
 
-        // Apply lookup table
+    for c in 0..color_channels {
+        let mut hist = [0.0f64; 256];
         for y in 0..height {
             for x in 0..width {
-                output[[y, x, c]] = lut[input[[y, x, c]] as usize];
+                hist[input[[y, x, c]] as usize] += weight(y, x) as f64;
             }
         }
-    }
+        let Some(lut) = equalize_lut(&hist) else { continue };
 
-    // Preserve alpha
-    if channels == 4 {
         for y in 0..height {
             for x in 0..width {
-                output[[y, x, 3]] = input[[y, x, 3]];
+                let amount = weight(y, x);
+                if amount <= 0.0 {
+                    continue;
+                }
+                let v = input[[y, x, c]];
+                let target = (lut[v as usize] * 255.0) as u8;
+                output[[y, x, c]] = if amount >= 1.0 {
+                    target
+                } else {
+                    (v as f32 + (target as f32 - v as f32) * amount).round() as u8
+                };
             }
         }
     }
@@ -514,61 +728,73 @@ pub fn equalize_histogram_u8(input: ArrayView3<u8>) -> Array3<u8> {
     output
 }
 
-/// Equalize image histogram (f32 version).
+/// Equalize image histogram with a channel mode and an optional mask (f32 version).
 ///
-/// Performs per-channel histogram equalization by quantizing to 256 bins,
-/// computing CDF, and mapping back to 0.0-1.0.
+/// Same as equalize_histogram_masked_u8; values are quantized to 256 bins.
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `mode` - Per-channel or luminance equalization
+/// * `mask` - Optional (height, width) mask, 0.0 = ignored, 1.0 = equalized
 ///
 /// # Returns
 /// Histogram-equalized image with same channel count
-pub fn equalize_histogram_f32(input: ArrayView3<f32>) -> Array3<f32> {
+pub fn equalize_histogram_masked_f32<'a>(
+    input: impl Into<ArrayView3<'a, f32>>,
+    mode: EqualizeMode,
+    mask: Option<ArrayView2<f32>>,
+) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
     let color_channels = if channels == 4 { 3 } else { channels };
-    let total_pixels = (height * width) as f32;
+    let weight = |y: usize, x: usize| mask.map_or(1.0, |m| m[[y, x]].clamp(0.0, 1.0));
+    let bin = |v: f32| ((v.clamp(0.0, 1.0) * 255.0) as usize).min(255);
+    let mut output = input.to_owned();
 
-    for c in 0..color_channels {
-        // Build histogram (quantize to 256 bins)
-        let mut hist = [0u32; 256];
+    if mode == EqualizeMode::Luminance && color_channels == 3 {
+        let lum = |y: usize, x: usize| luma(input[[y, x, 0]], input[[y, x, 1]], input[[y, x, 2]]);
+        let mut hist = [0.0f64; 256];
         for y in 0..height {
             for x in 0..width {
-                let bin = (input[[y, x, c]].clamp(0.0, 1.0) * 255.0) as usize;
-                hist[bin.min(255)] += 1;
+                hist[bin(lum(y, x))] += weight(y, x) as f64;
             }
         }
+        let Some(lut) = equalize_lut(&hist) else { return output };
 
-        // Build CDF
-        let mut cdf = [0u32; 256];
-        cdf[0] = hist[0];
-        for i in 1..256 {
-            cdf[i] = cdf[i - 1] + hist[i];
-        }
-
-        let cdf_min = cdf.iter().copied().find(|&v| v > 0).unwrap_or(0) as f32;
-        let denom = total_pixels - cdf_min;
-
-        // Apply equalization
         for y in 0..height {
             for x in 0..width {
-                let bin = (input[[y, x, c]].clamp(0.0, 1.0) * 255.0) as usize;
-                let bin = bin.min(255);
-                if denom > 0.0 {
-                    output[[y, x, c]] = ((cdf[bin] as f32 - cdf_min) / denom).clamp(0.0, 1.0);
-                } else {
-                    output[[y, x, c]] = input[[y, x, c]];
+                let amount = weight(y, x);
+                if amount <= 0.0 {
+                    continue;
+                }
+                let l = lum(y, x);
+                let shift = (lut[bin(l)] - l) * amount;
+                for c in 0..3 {
+                    output[[y, x, c]] = (input[[y, x, c]] + shift).clamp(0.0, 1.0);
                 }
             }
         }
+        return output;
     }
 
-    // Preserve alpha
-    if channels == 4 {
+    for c in 0..color_channels {
+        let mut hist = [0.0f64; 256];
         for y in 0..height {
             for x in 0..width {
-                output[[y, x, 3]] = input[[y, x, 3]];
+                hist[bin(input[[y, x, c]])] += weight(y, x) as f64;
+            }
+        }
+        let Some(lut) = equalize_lut(&hist) else { continue };
+
+        for y in 0..height {
+            for x in 0..width {
+                let amount = weight(y, x);
+                if amount <= 0.0 {
+                    continue;
+                }
+                let v = input[[y, x, c]];
+                let target = lut[bin(v)];
+                output[[y, x, c]] = if amount >= 1.0 { target } else { v + (target - v) * amount };
             }
         }
     }
@@ -775,6 +1001,23 @@ mod tests {
         assert!((result[[0, 0, 2]] - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_invert_channel_selection() {
+        let img = Array3::from_shape_vec((1, 1, 4), vec![100u8, 200, 50, 255]).unwrap();
+        let red = invert_channels_u8(img.view(), ChannelSelection::from_name("r").unwrap());
+        assert_eq!(red.into_raw_vec_and_offset().0, vec![155, 200, 50, 255]);
+        // Inverting only alpha turns a mask inside out
+        let mask = invert_channels_u8(img.view(), ChannelSelection::from_name("alpha").unwrap());
+        assert_eq!(mask.into_raw_vec_and_offset().0, vec![100, 200, 50, 0]);
+        assert_eq!(invert_channels_u8(img.view(), ChannelSelection::from_name("RGBA").unwrap())[[0, 0, 3]], 0);
+
+        // Gray follows the color channels, alpha doesn't exist without 4 channels
+        let gray = Array3::from_elem((1, 1, 1), 0.25f32);
+        assert_eq!(invert_channels_f32(gray.view(), ChannelSelection::from_name("g").unwrap())[[0, 0, 0]], 0.75);
+        assert_eq!(invert_channels_f32(gray.view(), ChannelSelection::ALPHA)[[0, 0, 0]], 0.25);
+        assert_eq!(ChannelSelection::from_name("rgx"), None);
+    }
+
     // ========================================================================
     // Equalize Histogram Tests
     // ========================================================================
@@ -827,4 +1070,59 @@ mod tests {
         // Should spread values
         assert!(result[[0, 0, 0]] < result[[1, 0, 0]]);
     }
+
+    #[test]
+    fn test_equalize_histogram_mode_and_mask() {
+        // Dim reddish pixels on a large black background
+        let mut img = Array3::<u8>::zeros((4, 4, 3));
+        let mut mask = ndarray::Array2::<u8>::zeros((4, 4));
+        for x in 0..4usize {
+            img[[0, x, 0]] = 60 + 10 * x as u8;
+            img[[0, x, 1]] = 30 + 10 * x as u8;
+            img[[0, x, 2]] = 20 + 10 * x as u8;
+            mask[[0, x]] = 255;
+        }
+        assert_eq!(EqualizeMode::from_name("luma"), Some(EqualizeMode::Luminance));
+        assert_eq!(EqualizeMode::from_name("hsv"), None);
+
+        // Per channel: the brightest pixel becomes white, losing its hue
+        let per_channel = equalize_histogram_masked_u8(img.view(), EqualizeMode::PerChannel, Some(mask.view()));
+        assert_eq!((per_channel[[0, 3, 0]], per_channel[[0, 3, 1]], per_channel[[0, 3, 2]]), (255, 255, 255));
+        // Luminance: channel differences are kept (where nothing clips)
+        let luminance = equalize_histogram_masked_u8(img.view(), EqualizeMode::Luminance, Some(mask.view()));
+        for x in 1..3usize {
+            let (r, g, b) = (luminance[[0, x, 0]], luminance[[0, x, 1]], luminance[[0, x, 2]]);
+            assert!(r > g && g > b);
+            assert_eq!(r - b, 40);
+        }
+        // The masked pixels span the full range without the background
+        assert!(luminance[[0, 0, 0]] < 60 && luminance[[0, 3, 1]] > 200);
+        assert!(per_channel.slice(ndarray::s![1.., .., ..]).iter().all(|&v| v == 0));
+
+        // Without a mask the background takes most of the curve
+        let unmasked = equalize_histogram_masked_u8(img.view(), EqualizeMode::Luminance, None);
+        assert!(unmasked[[0, 0, 0]] > luminance[[0, 0, 0]]);
+        assert_eq!(equalize_histogram_u8(img.view()), equalize_histogram_masked_u8(img.view(), EqualizeMode::PerChannel, None));
+
+        let img_f32 = img.mapv(|v| v as f32 / 255.0);
+        let mask_f32 = mask.mapv(|v| v as f32 / 255.0);
+        let result = equalize_histogram_masked_f32(img_f32.view(), EqualizeMode::Luminance, Some(mask_f32.view()));
+        assert!((result[[0, 2, 0]] - result[[0, 2, 2]] - 40.0 / 255.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_brightness_contrast_12bit() {
+        let img = Array3::from_shape_fn((1, 3, 2), |(_, x, c)| if c == 1 { 4095 } else { [0u16, 2048, 4000][x] });
+        let brighter = brightness_12bit(&img, 0.1);
+        assert_eq!(brighter[[0, 0, 0]], 410);
+        assert_eq!(brighter[[0, 2, 0]], 4095);
+        // 2-channel images have no alpha, both channels are adjusted
+        assert_eq!(brightness_12bit(&img, -0.1)[[0, 0, 1]], 3686);
+
+        let flat = contrast_12bit(&img, -1.0);
+        assert!(flat.iter().all(|&v| v == 2048));
+        let punchy = contrast_12bit(&img, 0.5);
+        assert_eq!(punchy[[0, 0, 0]], 0);
+        assert_eq!(punchy[[0, 1, 0]], 2049);
+    }
 }
@@ -5,7 +5,8 @@
  * - color_science.rs (Rust implementation)
  * - color_science.py (Python wrapper)
  *
- * Provides: hue_shift, vibrance, color_balance
+ * Provides: hue_shift, hsl_adjust, white_balance_kelvin, vibrance, color_balance, match_color,
+ *           replace_color, black_white, split_tone, duotone, simulate_cvd, daltonize
  */
 
 import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
@@ -35,6 +36,55 @@ export const hue_shift_f32 = createF32Filter(
     (opts) => [opts.degrees ?? 0]
 );
 
+// ============================================================================
+// Hue / Saturation
+// ============================================================================
+
+const hslAdjustOptions = (opts) => [Object.assign(new wasm.HslAdjustOptions(), {
+    hue: opts.hue ?? 0,
+    saturation: opts.saturation ?? 0,
+    lightness: opts.lightness ?? 0,
+    colorize: opts.colorize ?? false,
+})];
+
+/**
+ * Photoshop-style Hue/Saturation (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {hue: degrees, saturation: -1.0 to 1.0, lightness: -1.0 to 1.0,
+ *     colorize: boolean} (with colorize, hue is the tint hue and saturation 0.0 to 1.0)
+ * @returns {Object} - Adjusted image data
+ */
+export const hsl_adjust = createU8Filter(wasm.hsl_adjust_wasm, hslAdjustOptions);
+
+/**
+ * Photoshop-style Hue/Saturation (f32).
+ */
+export const hsl_adjust_f32 = createF32Filter(wasm.hsl_adjust_f32_wasm, hslAdjustOptions);
+
+// ============================================================================
+// White Balance
+// ============================================================================
+
+/**
+ * White balance in Kelvin (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {kelvin: number} color temperature of the light
+ *     (2000-25000, 6504 = neutral), {tint: number} (-1.0 green to 1.0 magenta)
+ * @returns {Object} - White-balanced image data
+ */
+export const white_balance_kelvin = createU8Filter(
+    wasm.white_balance_kelvin_wasm,
+    (opts) => [opts.kelvin ?? 6504, opts.tint ?? 0]
+);
+
+/**
+ * White balance in Kelvin (f32).
+ */
+export const white_balance_kelvin_f32 = createF32Filter(
+    wasm.white_balance_kelvin_f32_wasm,
+    (opts) => [opts.kelvin ?? 6504, opts.tint ?? 0]
+);
+
 // ============================================================================
 // Vibrance
 // ============================================================================
@@ -43,12 +93,13 @@ export const hue_shift_f32 = createF32Filter(
  * Adjust image vibrance (u8).
  * Boosts less-saturated colors more than saturated ones.
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {amount: number} (-1.0 to 1.0)
+ * @param {Object} options - {amount: number} (-1.0 to 1.0), {protect_skin: number}
+ *     (0.0 to 1.0) spares skin tones from the boost
  * @returns {Object} - Vibrance-adjusted image data
  */
 export const vibrance = createU8Filter(
     wasm.vibrance_wasm,
-    (opts) => [opts.amount ?? 0]
+    (opts) => [opts.amount ?? 0, opts.protect_skin ?? 0]
 );
 
 /**
@@ -56,7 +107,7 @@ export const vibrance = createU8Filter(
  */
 export const vibrance_f32 = createF32Filter(
     wasm.vibrance_f32_wasm,
-    (opts) => [opts.amount ?? 0]
+    (opts) => [opts.amount ?? 0, opts.protect_skin ?? 0]
 );
 
 // ============================================================================
@@ -101,9 +152,160 @@ export const color_balance_f32 = createF32Filter(
     }
 );
 
+// ============================================================================
+// Match Color
+// ============================================================================
+
+/**
+ * Match image colors to a reference frame (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {reference, amount}
+ *   - reference: {data, width, height} with the same channel count as imageData
+ *   - amount: Strength of the match (0.0-1.0, default 1.0)
+ * @returns {Object} - Color-matched image data
+ */
+export const match_color = createU8Filter(
+    wasm.match_color_wasm,
+    (opts) => [new Uint8Array(opts.reference.data.buffer), opts.reference.width, opts.amount ?? 1.0]
+);
+
+// ============================================================================
+// Split Tone / Duotone
+// ============================================================================
+
+/**
+ * Photoshop-style Black & White conversion (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {reds, yellows, greens, cyans, blues, magentas, tintColor}
+ *   - hue weights: gray value of that fully saturated hue (defaults 0.4, 0.6, 0.4, 0.6, 0.2, 0.8)
+ *   - tintColor: Optional [r, g, b] (0-255)
+ * @returns {Object} - Gray (or tinted) image data
+ */
+export const black_white = createU8Filter(
+    wasm.black_white_wasm,
+    (opts) => [
+        new Float32Array([
+            opts.reds ?? 0.4, opts.yellows ?? 0.6, opts.greens ?? 0.4,
+            opts.cyans ?? 0.6, opts.blues ?? 0.2, opts.magentas ?? 0.8,
+        ]),
+        new Uint8Array(opts.tintColor ?? []),
+    ]
+);
+
+/**
+ * Replace a color throughout the image, keeping shading (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {targetColor, replacementColor, tolerance, softness, preserveLuminosity}
+ *   - targetColor / replacementColor: [r, g, b] (0-255)
+ *   - tolerance: fully replaced Lab distance, 0.0-1.0 (1.0 = Delta E 100)
+ *   - softness: width of the falloff beyond tolerance
+ *   - preserveLuminosity: change only hue and chroma
+ * @returns {Object} - Recolored image data
+ */
+export const replace_color = createU8Filter(
+    wasm.replace_color_wasm,
+    (opts) => [
+        new Uint8Array(opts.targetColor ?? [255, 0, 0]),
+        new Uint8Array(opts.replacementColor ?? [0, 0, 255]),
+        Object.assign(new wasm.ReplaceColorOptions(), {
+            tolerance: opts.tolerance ?? 0.2,
+            softness: opts.softness ?? 0.1,
+            preserve_luminosity: opts.preserveLuminosity ?? false,
+        }),
+    ]
+);
+
+/**
+ * Tint shadows and highlights with different colors (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {shadowColor, highlightColor, balance}
+ *   - shadowColor / highlightColor: [r, g, b] (0-255)
+ *   - balance: -1.0 (mostly shadow tint) to 1.0 (mostly highlight tint)
+ * @returns {Object} - Toned image data
+ */
+export const split_tone = createU8Filter(
+    wasm.split_tone_wasm,
+    (opts) => [
+        new Uint8Array(opts.shadowColor ?? [40, 80, 160]),
+        new Uint8Array(opts.highlightColor ?? [230, 180, 90]),
+        opts.balance ?? 0,
+    ]
+);
+
+/**
+ * Print the image with 1-4 inks - duotone, tritone or quadtone (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {inks, curves}
+ *   - inks: Array<[r, g, b]> (0-255), darkest first
+ *   - curves: Optional Array<Array<[tone, coverage]>> per ink (0.0-1.0), empty = linear
+ * @returns {Object} - Toned image data
+ */
+export const duotone = createU8Filter(
+    wasm.duotone_wasm,
+    (opts) => {
+        const inks = opts.inks ?? [[0, 0, 0], [230, 140, 40]];
+        const curves = opts.curves ?? [];
+        return [
+            new Uint8Array(inks.flat()),
+            new Float32Array(curves.flat(2)),
+            new Uint32Array(curves.map(c => c.length)),
+        ];
+    }
+);
+
+// ============================================================================
+// Color Vision Deficiency
+// ============================================================================
+
+/**
+ * Simulate how an image looks with a color vision deficiency (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {deficiency: 'protan'|'deutan'|'tritan', severity: 0.0-1.0}
+ * @returns {Object} - Simulated image data
+ */
+export const simulate_cvd = createU8Filter(
+    wasm.simulate_cvd_wasm,
+    (opts) => [opts.deficiency ?? 'deutan', opts.severity ?? 1.0]
+);
+
+/**
+ * Simulate how an image looks with a color vision deficiency (f32).
+ */
+export const simulate_cvd_f32 = createF32Filter(
+    wasm.simulate_cvd_f32_wasm,
+    (opts) => [opts.deficiency ?? 'deutan', opts.severity ?? 1.0]
+);
+
+/**
+ * Recolor an image so colors stay distinguishable with a deficiency (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {deficiency: 'protan'|'deutan'|'tritan', amount: 0.0-1.0}
+ * @returns {Object} - Corrected image data
+ */
+export const daltonize = createU8Filter(
+    wasm.daltonize_wasm,
+    (opts) => [opts.deficiency ?? 'deutan', opts.amount ?? 1.0]
+);
+
+/**
+ * Recolor an image so colors stay distinguishable with a deficiency (f32).
+ */
+export const daltonize_f32 = createF32Filter(
+    wasm.daltonize_f32_wasm,
+    (opts) => [opts.deficiency ?? 'deutan', opts.amount ?? 1.0]
+);
+
 export default {
     initWasm,
     hue_shift, hue_shift_f32,
+    hsl_adjust, hsl_adjust_f32,
+    white_balance_kelvin, white_balance_kelvin_f32,
     vibrance, vibrance_f32,
-    color_balance, color_balance_f32
+    color_balance, color_balance_f32,
+    match_color,
+    replace_color,
+    black_white,
+    split_tone, duotone,
+    simulate_cvd, simulate_cvd_f32,
+    daltonize, daltonize_f32
 };
@@ -34,9 +34,13 @@ import numpy as np
 import imagestag_rust
 
 
-def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
-    """Validate image shape and dtype."""
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str, frames: bool = False) -> None:
+    """Validate image shape and dtype.
+
+    With ``frames``, an (N, H, W, C) stack of animation frames is accepted too.
+    """
+    ndims = (3, 4) if frames else (3,)
+    if image.ndim not in ndims or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, 1|3|4), got shape {image.shape}")
     if image.dtype != expected_dtype:
         raise ValueError(f"Expected {expected_dtype} dtype, got {image.dtype}")
@@ -56,7 +60,7 @@ def hue_shift(image: np.ndarray, degrees: float = 0.0) -> np.ndarray:
     Returns:
         Hue-shifted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "hue_shift")
+    _validate_image(image, np.uint8, "hue_shift", frames=True)
     return imagestag_rust.hue_shift(image, degrees)
 
 
@@ -70,43 +74,101 @@ def hue_shift_f32(image: np.ndarray, degrees: float = 0.0) -> np.ndarray:
     Returns:
         Hue-shifted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "hue_shift_f32")
+    _validate_image(image, np.float32, "hue_shift_f32", frames=True)
     return imagestag_rust.hue_shift_f32(image, degrees)
 
 
+# ============================================================================
+# Hue / Saturation
+# ============================================================================
+
+def hsl_adjust(
+    image: np.ndarray,
+    hue: float = 0.0,
+    saturation: float = 0.0,
+    lightness: float = 0.0,
+    colorize: bool = False,
+) -> np.ndarray:
+    """Photoshop-style Hue/Saturation adjustment (u8).
+
+    Changes hue, saturation and lightness in one pass in the HSL model,
+    matching Photoshop's Hue/Saturation dialog.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        hue: Hue rotation in degrees (-180 to 180); with colorize the
+            absolute tint hue (0 to 360)
+        saturation: -1.0 (gray) to 1.0 (fully saturated); with colorize
+            the tint saturation 0.0 to 1.0 (Photoshop uses 0.25)
+        lightness: -1.0 (black) to 1.0 (white)
+        colorize: Tint the image with one hue instead of shifting hues
+
+    Returns:
+        Adjusted uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "hsl_adjust", frames=True)
+    return imagestag_rust.hsl_adjust(image, hue, saturation, lightness, colorize)
+
+
+def hsl_adjust_f32(
+    image: np.ndarray,
+    hue: float = 0.0,
+    saturation: float = 0.0,
+    lightness: float = 0.0,
+    colorize: bool = False,
+) -> np.ndarray:
+    """Photoshop-style Hue/Saturation adjustment (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        hue: Hue rotation in degrees, or the tint hue with colorize
+        saturation: -1.0 to 1.0, or 0.0 to 1.0 with colorize
+        lightness: -1.0 (black) to 1.0 (white)
+        colorize: Tint the image with one hue instead of shifting hues
+
+    Returns:
+        Adjusted float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "hsl_adjust_f32", frames=True)
+    return imagestag_rust.hsl_adjust_f32(image, hue, saturation, lightness, colorize)
+
+
 # ============================================================================
 # Vibrance
 # ============================================================================
 
-def vibrance(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
+def vibrance(image: np.ndarray, amount: float = 0.0, protect_skin: float = 0.0) -> np.ndarray:
     """Adjust vibrance - smart saturation (u8).
 
     Unlike saturation, vibrance boosts less-saturated colors more,
-    preserving already-vibrant colors (especially skin tones).
+    preserving already-vibrant colors. Muted skin tones still get a strong
+    boost at high amounts; protect_skin reduces it as Lightroom does.
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         amount: -1.0 (desaturate) to 1.0 (boost), 0.0 = no change
+        protect_skin: 0.0 (off) to 1.0 (skin-tone hues are not boosted)
 
     Returns:
         Vibrance-adjusted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "vibrance")
-    return imagestag_rust.vibrance(image, amount)
+    _validate_image(image, np.uint8, "vibrance", frames=True)
+    return imagestag_rust.vibrance(image, amount, protect_skin)
 
 
-def vibrance_f32(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
+def vibrance_f32(image: np.ndarray, amount: float = 0.0, protect_skin: float = 0.0) -> np.ndarray:
     """Adjust vibrance - smart saturation (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         amount: -1.0 (desaturate) to 1.0 (boost), 0.0 = no change
+        protect_skin: 0.0 (off) to 1.0 (skin-tone hues are not boosted)
 
     Returns:
         Vibrance-adjusted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "vibrance_f32")
-    return imagestag_rust.vibrance_f32(image, amount)
+    _validate_image(image, np.float32, "vibrance_f32", frames=True)
+    return imagestag_rust.vibrance_f32(image, amount, protect_skin)
 
 
 # ============================================================================
@@ -128,7 +190,7 @@ def color_balance(image: np.ndarray,
     Returns:
         Color-balanced uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "color_balance")
+    _validate_image(image, np.uint8, "color_balance", frames=True)
     return imagestag_rust.color_balance(
         image,
         list(shadows),
@@ -152,7 +214,7 @@ def color_balance_f32(image: np.ndarray,
     Returns:
         Color-balanced float32 array with same channel count
     """
-    _validate_image(image, np.float32, "color_balance_f32")
+    _validate_image(image, np.float32, "color_balance_f32", frames=True)
     return imagestag_rust.color_balance_f32(
         image,
         list(shadows),
@@ -225,6 +287,41 @@ def temperature_f32(image: np.ndarray, amount: float = 0.0) -> np.ndarray:
     return imagestag_rust.temperature_f32(image, amount)
 
 
+def white_balance_kelvin(image: np.ndarray, kelvin: float = 6504.0, tint: float = 0.0) -> np.ndarray:
+    """White balance in Kelvin, like a raw converter's temperature slider (u8).
+
+    kelvin is the color temperature of the light the image was taken in;
+    that light is mapped to neutral white using channel multipliers from the
+    Planckian locus, applied in linear light. 6504 K with tint 0 is neutral;
+    values below the actual light cool the image, values above warm it.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        kelvin: Light color temperature (2000 to 25000 K)
+        tint: -1.0 (green) to 1.0 (magenta)
+
+    Returns:
+        White-balanced uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "white_balance_kelvin", frames=True)
+    return imagestag_rust.white_balance_kelvin(image, kelvin, tint)
+
+
+def white_balance_kelvin_f32(image: np.ndarray, kelvin: float = 6504.0, tint: float = 0.0) -> np.ndarray:
+    """White balance in Kelvin (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        kelvin: Light color temperature (2000 to 25000 K)
+        tint: -1.0 (green) to 1.0 (magenta)
+
+    Returns:
+        White-balanced float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "white_balance_kelvin_f32", frames=True)
+    return imagestag_rust.white_balance_kelvin_f32(image, kelvin, tint)
+
+
 # ============================================================================
 # Channel Mixer
 # ============================================================================
@@ -261,11 +358,337 @@ def channel_mixer_f32(image: np.ndarray, r_src: int = 0, g_src: int = 1, b_src:
     return imagestag_rust.channel_mixer_f32(image, r_src, g_src, b_src)
 
 
+# ============================================================================
+# Match Color
+# ============================================================================
+
+def match_color(source: np.ndarray, reference: np.ndarray, amount: float = 1.0) -> np.ndarray:
+    """Match the colors of an image to a reference frame (u8).
+
+    Transfers the mean and spread of each Lab channel from the reference,
+    so lightness, contrast and color cast follow it. Fully transparent
+    pixels are ignored. Use it to harmonize layers shot under different
+    lighting.
+
+    Args:
+        source: uint8 array with 1, 3, or 4 channels (H, W, C)
+        reference: uint8 array with 3 or 4 channels, any size
+        amount: Strength of the match (0.0 = unchanged, 1.0 = full)
+
+    Returns:
+        Color-matched uint8 array with same channel count as source
+    """
+    _validate_image(source, np.uint8, "match_color")
+    _validate_image(reference, np.uint8, "match_color")
+    return imagestag_rust.match_color(source, reference, amount)
+
+
+def match_color_f32(source: np.ndarray, reference: np.ndarray, amount: float = 1.0) -> np.ndarray:
+    """Match the colors of an image to a reference frame (f32).
+
+    Args:
+        source: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        reference: float32 array with 3 or 4 channels, any size
+        amount: Strength of the match (0.0 = unchanged, 1.0 = full)
+
+    Returns:
+        Color-matched float32 array with same channel count as source
+    """
+    _validate_image(source, np.float32, "match_color_f32")
+    _validate_image(reference, np.float32, "match_color_f32")
+    return imagestag_rust.match_color_f32(source, reference, amount)
+
+
+# ============================================================================
+# Black & White
+# ============================================================================
+
+def black_white(
+    image: np.ndarray,
+    reds: float = 0.4,
+    yellows: float = 0.6,
+    greens: float = 0.4,
+    cyans: float = 0.6,
+    blues: float = 0.2,
+    magentas: float = 0.8,
+    tint_color: tuple | None = None,
+) -> np.ndarray:
+    """Photoshop-style Black & White conversion (u8).
+
+    Each weight sets how bright a fully saturated color of that hue becomes
+    (0.0-1.0, -2.0 to 3.0 allowed); hues in between are interpolated and
+    neutral pixels keep their value. Unlike grayscale_weighted this can e.g.
+    darken a blue sky without touching skin tones. The defaults are
+    Photoshop's.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        reds, yellows, greens, cyans, blues, magentas: Hue weights
+        tint_color: Optional (R, G, B) tint, 0-255, whose hue and
+            saturation color the result
+
+    Returns:
+        Gray (or tinted) uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "black_white", frames=True)
+    weights = (reds, yellows, greens, cyans, blues, magentas)
+    return imagestag_rust.black_white(image, weights, tint_color)
+
+
+def black_white_f32(
+    image: np.ndarray,
+    reds: float = 0.4,
+    yellows: float = 0.6,
+    greens: float = 0.4,
+    cyans: float = 0.6,
+    blues: float = 0.2,
+    magentas: float = 0.8,
+    tint_color: tuple | None = None,
+) -> np.ndarray:
+    """Photoshop-style Black & White conversion (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        reds, yellows, greens, cyans, blues, magentas: Hue weights
+        tint_color: Optional (R, G, B) tint, 0.0-1.0
+
+    Returns:
+        Gray (or tinted) float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "black_white_f32", frames=True)
+    weights = (reds, yellows, greens, cyans, blues, magentas)
+    return imagestag_rust.black_white_f32(image, weights, tint_color)
+
+
+# ============================================================================
+# Replace Color
+# ============================================================================
+
+def replace_color(
+    image: np.ndarray,
+    target_color: tuple,
+    replacement_color: tuple,
+    tolerance: float = 0.2,
+    softness: float = 0.1,
+    preserve_luminosity: bool = False,
+) -> np.ndarray:
+    """Replace a color throughout the image (u8).
+
+    Pixels are selected by their Lab distance to ``target_color`` and
+    shifted towards ``replacement_color``, keeping their shading. Unlike a
+    flood fill, matching pixels anywhere in the image change.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        target_color: Color to replace (R, G, B), 0-255
+        replacement_color: New color (R, G, B), 0-255
+        tolerance: Fully replaced distance, 0.0-1.0 (1.0 = Delta E 100)
+        softness: Width of the falloff beyond tolerance
+        preserve_luminosity: Change only hue and chroma
+
+    Returns:
+        Recolored uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "replace_color")
+    return imagestag_rust.replace_color(
+        image, target_color, replacement_color, tolerance, softness, preserve_luminosity
+    )
+
+
+def replace_color_f32(
+    image: np.ndarray,
+    target_color: tuple,
+    replacement_color: tuple,
+    tolerance: float = 0.2,
+    softness: float = 0.1,
+    preserve_luminosity: bool = False,
+) -> np.ndarray:
+    """Replace a color throughout the image (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        target_color: Color to replace (R, G, B), 0.0-1.0
+        replacement_color: New color (R, G, B), 0.0-1.0
+        tolerance: Fully replaced distance, 0.0-1.0 (1.0 = Delta E 100)
+        softness: Width of the falloff beyond tolerance
+        preserve_luminosity: Change only hue and chroma
+
+    Returns:
+        Recolored float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "replace_color_f32")
+    return imagestag_rust.replace_color_f32(
+        image, target_color, replacement_color, tolerance, softness, preserve_luminosity
+    )
+
+
+# ============================================================================
+# Split Tone / Duotone
+# ============================================================================
+
+def split_tone(
+    image: np.ndarray,
+    shadow_color: tuple = (40, 80, 160),
+    highlight_color: tuple = (230, 180, 90),
+    balance: float = 0.0,
+) -> np.ndarray:
+    """Tint shadows and highlights with different colors (u8).
+
+    Luminance is preserved; a gray tint color has no effect and more
+    saturated colors tint more strongly.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        shadow_color: Shadow tint (R, G, B), 0-255
+        highlight_color: Highlight tint (R, G, B), 0-255
+        balance: -1.0 (mostly shadow tint) to 1.0 (mostly highlight tint)
+
+    Returns:
+        Toned uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "split_tone")
+    return imagestag_rust.split_tone(image, shadow_color, highlight_color, balance)
+
+
+def split_tone_f32(
+    image: np.ndarray,
+    shadow_color: tuple = (0.16, 0.31, 0.63),
+    highlight_color: tuple = (0.9, 0.7, 0.35),
+    balance: float = 0.0,
+) -> np.ndarray:
+    """Tint shadows and highlights with different colors (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        shadow_color: Shadow tint (R, G, B), 0.0-1.0
+        highlight_color: Highlight tint (R, G, B), 0.0-1.0
+        balance: -1.0 (mostly shadow tint) to 1.0 (mostly highlight tint)
+
+    Returns:
+        Toned float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "split_tone_f32")
+    return imagestag_rust.split_tone_f32(image, shadow_color, highlight_color, balance)
+
+
+def duotone(image: np.ndarray, ink_colors: list, curves: list | None = None) -> np.ndarray:
+    """Print the image with 1-4 inks - duotone, tritone or quadtone (u8).
+
+    The luminance becomes ink coverage (white paper to full ink); each
+    ink's curve remaps it, and the inks are layered multiplicatively.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        ink_colors: List of (R, G, B) ink colors, 0-255, darkest first
+        curves: Optional list with one (tone, coverage) point list per
+                ink, values 0.0-1.0. Missing or empty curves are linear.
+
+    Returns:
+        Toned uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "duotone")
+    return imagestag_rust.duotone(image, ink_colors, curves)
+
+
+def duotone_f32(image: np.ndarray, ink_colors: list, curves: list | None = None) -> np.ndarray:
+    """Print the image with 1-4 inks (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        ink_colors: List of (R, G, B) ink colors, 0.0-1.0, darkest first
+        curves: Optional per-ink (tone, coverage) point lists
+
+    Returns:
+        Toned float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "duotone_f32")
+    return imagestag_rust.duotone_f32(image, ink_colors, curves)
+
+
+# ============================================================================
+# Color Vision Deficiency
+# ============================================================================
+
+def simulate_cvd(image: np.ndarray, deficiency: str = "deutan", severity: float = 1.0) -> np.ndarray:
+    """Simulate how an image looks with a color vision deficiency (u8).
+
+    Uses the Machado et al. (2009) matrices in linear RGB; severities below
+    1.0 approximate the milder anomalous forms.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        deficiency: "protan" (red), "deutan" (green) or "tritan" (blue)
+        severity: 0.0 (normal vision) to 1.0 (dichromacy)
+
+    Returns:
+        Simulated uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "simulate_cvd")
+    return imagestag_rust.simulate_cvd(image, deficiency, severity)
+
+
+def simulate_cvd_f32(image: np.ndarray, deficiency: str = "deutan", severity: float = 1.0) -> np.ndarray:
+    """Simulate how an image looks with a color vision deficiency (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        deficiency: "protan", "deutan" or "tritan"
+        severity: 0.0 (normal vision) to 1.0 (dichromacy)
+
+    Returns:
+        Simulated float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "simulate_cvd_f32")
+    return imagestag_rust.simulate_cvd_f32(image, deficiency, severity)
+
+
+def daltonize(image: np.ndarray, deficiency: str = "deutan", amount: float = 1.0) -> np.ndarray:
+    """Recolor an image so colors stay distinguishable with a deficiency (u8).
+
+    The color difference lost in the simulation is shifted into channels
+    the viewer still distinguishes; well-seen colors barely change.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        deficiency: "protan", "deutan" or "tritan"
+        amount: Strength of the correction (0.0 = unchanged, 1.0 = full)
+
+    Returns:
+        Corrected uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "daltonize")
+    return imagestag_rust.daltonize(image, deficiency, amount)
+
+
+def daltonize_f32(image: np.ndarray, deficiency: str = "deutan", amount: float = 1.0) -> np.ndarray:
+    """Recolor an image so colors stay distinguishable with a deficiency (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        deficiency: "protan", "deutan" or "tritan"
+        amount: Strength of the correction (0.0 = unchanged, 1.0 = full)
+
+    Returns:
+        Corrected float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "daltonize_f32")
+    return imagestag_rust.daltonize_f32(image, deficiency, amount)
+
+
 __all__ = [
     'hue_shift', 'hue_shift_f32',
+    'hsl_adjust', 'hsl_adjust_f32',
     'vibrance', 'vibrance_f32',
     'color_balance', 'color_balance_f32',
     'sepia', 'sepia_f32',
     'temperature', 'temperature_f32',
+    'white_balance_kelvin', 'white_balance_kelvin_f32',
+    'black_white', 'black_white_f32',
     'channel_mixer', 'channel_mixer_f32',
+    'match_color', 'match_color_f32',
+    'replace_color', 'replace_color_f32',
+    'split_tone', 'split_tone_f32',
+    'duotone', 'duotone_f32',
+    'simulate_cvd', 'simulate_cvd_f32',
+    'daltonize', 'daltonize_f32',
 ]
@@ -1,6 +1,8 @@
-//! Color science filters: Hue Shift, Vibrance, Color Balance, Sepia, Temperature, Channel Mixer.
+//! Color science filters: Hue Shift, Vibrance, Color Balance, Sepia, Temperature, Channel Mixer,
+//! Match Color, Replace Color, Split Tone, Duotone, Color Vision Deficiency simulation and
+//! Daltonization.
 //!
-//! These filters require color space conversions (RGB <-> HSL) or color matrix operations.
+//! These filters require color space conversions (RGB <-> HSL / Lab) or color matrix operations.
 //! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
 //!
 //! ## Supported Formats
@@ -9,7 +11,12 @@
 //! - **RGB (3 channels)**: Full color processing
 //! - **RGBA (4 channels)**: RGB processed, alpha preserved
 
-use ndarray::{Array3, ArrayView3};
+use ndarray::{Array2, Array3, ArrayView3};
+
+use crate::filters::grayscale::{LUMA_B, LUMA_G, LUMA_R};
+use crate::filters::fmath;
+use crate::filters::levels_curves::pchip_interpolate;
+use crate::filters::resample::{linear_to_srgb, linear_to_srgb_u8, srgb_to_linear, srgb_u8_to_linear};
 
 // ============================================================================
 // Color Space Conversion Utilities
@@ -119,7 +126,7 @@ fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
 /// Output: (r, g, b) in 0.0-1.0
 /// Matches skimage.color.hsv2rgb behavior.
 #[inline]
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
+pub(crate) fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
     if s.abs() < 1e-10 {
         return (v, v, v);
     }
@@ -141,6 +148,42 @@ fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
     }
 }
 
+/// D65 reference white in XYZ.
+const WHITE_D65: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Convert sRGB to CIE Lab (D65).
+/// Input: r, g, b in 0.0-1.0
+/// Output: (L, a, b) with L in 0.0-100.0
+#[inline]
+pub(crate) fn rgb_to_lab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let [r, g, b] = [r, g, b].map(srgb_to_linear);
+    let xyz = [
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+        0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+    ];
+    let f = |t: f32| if t > 0.008_856 { fmath::cbrt(t) } else { 7.787 * t + 16.0 / 116.0 };
+    let [fx, fy, fz] = [0, 1, 2].map(|i| f(xyz[i] / WHITE_D65[i]));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Convert CIE Lab (D65) to sRGB.
+/// Output: (r, g, b), clamped to 0.0-1.0
+#[inline]
+fn lab_to_rgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let fy = (l + 16.0) / 116.0;
+    let f_inv = |t: f32| if t > 0.206_893 { t * t * t } else { (t - 16.0 / 116.0) / 7.787 };
+    let [x, y, z] = [fy + a / 500.0, fy, fy - b / 200.0].map(f_inv);
+    let [x, y, z] = [x * WHITE_D65[0], y * WHITE_D65[1], z * WHITE_D65[2]];
+    let rgb = [
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    ]
+    .map(|v| linear_to_srgb(v.clamp(0.0, 1.0)));
+    (rgb[0], rgb[1], rgb[2])
+}
+
 // ============================================================================
 // Hue Shift
 // ============================================================================
@@ -234,10 +277,141 @@ pub fn hue_shift_f32(input: ArrayView3<f32>, degrees: f32) -> Array3<f32> {
     output
 }
 
+// ============================================================================
+// Hue / Saturation
+// ============================================================================
+
+/// Parameters of the Photoshop-style Hue/Saturation adjustment.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HslAdjustParams {
+    /// Hue rotation in degrees (-180 to 180); the absolute hue (0-360) when colorizing
+    pub hue: f32,
+    /// -1.0 (gray) to 1.0 (fully saturated); 0.0 to 1.0 when colorizing
+    /// (Photoshop's default there is 0.25)
+    pub saturation: f32,
+    /// -1.0 (black) to 1.0 (white)
+    pub lightness: f32,
+    /// Replace hue and saturation instead of shifting them (tint)
+    pub colorize: bool,
+}
+
+impl HslAdjustParams {
+    /// Adjust one RGB pixel (0.0-1.0).
+    fn apply(&self, r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        let (mut r, mut g, mut b) = if self.colorize {
+            let (_, _, l) = rgb_to_hsl(r, g, b);
+            hsl_to_rgb(self.hue.rem_euclid(360.0), self.saturation.clamp(0.0, 1.0), l)
+        } else {
+            let (r, g, b) = if self.hue != 0.0 {
+                let (h, s, l) = rgb_to_hsl(r, g, b);
+                hsl_to_rgb((h + self.hue).rem_euclid(360.0), s, l)
+            } else {
+                (r, g, b)
+            };
+            // Scale the distance from the HSL lightness; a positive amount
+            // reaches full saturation at 1.0 like Photoshop
+            let sat = self.saturation.clamp(-1.0, 1.0);
+            let factor = if sat > 0.0 { 1.0 / (1.0 - sat).max(1e-4) } else { 1.0 + sat };
+            let l = (r.max(g).max(b) + r.min(g).min(b)) / 2.0;
+            (l + (r - l) * factor, l + (g - l) * factor, l + (b - l) * factor)
+        };
+
+        // Lightness blends towards black or white
+        let lightness = self.lightness.clamp(-1.0, 1.0);
+        for v in [&mut r, &mut g, &mut b] {
+            *v = v.clamp(0.0, 1.0);
+            *v = if lightness < 0.0 { *v * (1.0 + lightness) } else { *v + (1.0 - *v) * lightness };
+        }
+        (r, g, b)
+    }
+}
+
+/// Photoshop-style Hue/Saturation adjustment (u8 version).
+///
+/// Hue, saturation and lightness are changed in one pass in the HSL model,
+/// matching Photoshop's Hue/Saturation dialog (master channel), including
+/// its Colorize mode. For grayscale input only lightness applies.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `params` - Hue, saturation, lightness and colorize
+///
+/// # Returns
+/// Adjusted image with same channel count
+pub fn hsl_adjust_u8(input: ArrayView3<u8>, params: &HslAdjustParams) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let result = hsl_adjust_f32(input_f32.view(), params);
+    result.mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Photoshop-style Hue/Saturation adjustment (f32 version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `params` - Hue, saturation, lightness and colorize
+///
+/// # Returns
+/// Adjusted image with same channel count
+pub fn hsl_adjust_f32(input: ArrayView3<f32>, params: &HslAdjustParams) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+
+    for y in 0..height {
+        for x in 0..width {
+            let (r, g, b) = if channels >= 3 {
+                (input[[y, x, 0]], input[[y, x, 1]], input[[y, x, 2]])
+            } else {
+                let v = input[[y, x, 0]];
+                (v, v, v)
+            };
+            let (nr, ng, nb) = if channels >= 3 {
+                params.apply(r, g, b)
+            } else {
+                // Gray has no hue; colorizing it would add channels
+                HslAdjustParams { hue: 0.0, saturation: 0.0, colorize: false, ..*params }.apply(r, g, b)
+            };
+
+            output[[y, x, 0]] = nr;
+            if channels >= 3 {
+                output[[y, x, 1]] = ng;
+                output[[y, x, 2]] = nb;
+            }
+        }
+    }
+    output
+}
+
 // ============================================================================
 // Vibrance
 // ============================================================================
 
+/// Weight (0.0-1.0) of a hue (0.0-1.0) in the skin-tone range.
+///
+/// Skin of all ethnicities falls into a narrow orange band around 25 degrees;
+/// the weight is 1.0 there and falls off smoothly to 0.0 at 0 and 50 degrees.
+#[inline]
+fn skin_tone_weight(h: f32) -> f32 {
+    const CENTER: f32 = 25.0 / 360.0;
+    const HALF_WIDTH: f32 = 25.0 / 360.0;
+    let d = (h - CENTER).abs();
+    let d = d.min(1.0 - d);
+    let t = (1.0 - d / HALF_WIDTH).max(0.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// New HSV saturation for a vibrance change.
+#[inline]
+fn vibrance_saturation(h: f32, s: f32, amount: f32, protect_skin: f32) -> f32 {
+    // Vibrance: boost saturation more for less-saturated pixels
+    // adjustment = amount * (1 - sat)
+    // new_sat = sat * (1 + adjustment)
+    let mut adjustment = amount * (1.0 - s);
+    if amount > 0.0 && protect_skin > 0.0 {
+        adjustment *= 1.0 - protect_skin.clamp(0.0, 1.0) * skin_tone_weight(h);
+    }
+    (s * (1.0 + adjustment)).clamp(0.0, 1.0)
+}
+
 /// Adjust image vibrance (u8 version).
 ///
 /// Vibrance boosts less-saturated colors more than already saturated colors.
@@ -251,6 +425,37 @@ pub fn hue_shift_f32(input: ArrayView3<f32>, degrees: f32) -> Array3<f32> {
 /// # Returns
 /// Vibrance-adjusted image with same channel count
 pub fn vibrance_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+    vibrance_protected_u8(input, amount, 0.0)
+}
+
+/// Adjust image vibrance (f32 version).
+///
+/// Uses HSV color space to match skimage behavior exactly.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amount` - Vibrance adjustment: -1.0 to 1.0, 0.0 = no change
+///
+/// # Returns
+/// Vibrance-adjusted image with same channel count
+pub fn vibrance_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+    vibrance_protected_f32(input, amount, 0.0)
+}
+
+/// Adjust image vibrance with skin-tone protection (u8 version).
+///
+/// Like Lightroom's vibrance, a positive amount boosts orange skin-tone hues
+/// less so faces don't turn orange at high amounts. Desaturation is not
+/// affected.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `amount` - Vibrance adjustment: -1.0 to 1.0, 0.0 = no change
+/// * `protect_skin` - 0.0 = no protection, 1.0 = skin tones are not boosted
+///
+/// # Returns
+/// Vibrance-adjusted image with same channel count
+pub fn vibrance_protected_u8(input: ArrayView3<u8>, amount: f32, protect_skin: f32) -> Array3<u8> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -271,12 +476,7 @@ pub fn vibrance_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
 
             // Convert to HSV (matching skimage.color.rgb2hsv)
             let (h, s, v) = rgb_to_hsv(r, g, b);
-
-            // Vibrance: boost saturation more for less-saturated pixels
-            // adjustment = amount * (1 - sat)
-            // new_sat = sat * (1 + adjustment)
-            let adjustment = amount * (1.0 - s);
-            let new_s = (s * (1.0 + adjustment)).clamp(0.0, 1.0);
+            let new_s = vibrance_saturation(h, s, amount, protect_skin);
 
             // Convert back to RGB
             let (nr, ng, nb) = hsv_to_rgb(h, new_s, v);
@@ -293,17 +493,16 @@ pub fn vibrance_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
     output
 }
 
-/// Adjust image vibrance (f32 version).
-///
-/// Uses HSV color space to match skimage behavior exactly.
+/// Adjust image vibrance with skin-tone protection (f32 version).
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
 /// * `amount` - Vibrance adjustment: -1.0 to 1.0, 0.0 = no change
+/// * `protect_skin` - 0.0 = no protection, 1.0 = skin tones are not boosted
 ///
 /// # Returns
 /// Vibrance-adjusted image with same channel count
-pub fn vibrance_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+pub fn vibrance_protected_f32(input: ArrayView3<f32>, amount: f32, protect_skin: f32) -> Array3<f32> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -324,10 +523,7 @@ pub fn vibrance_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
 
             // Convert to HSV
             let (h, s, v) = rgb_to_hsv(r, g, b);
-
-            // Vibrance: boost saturation more for less-saturated pixels
-            let adjustment = amount * (1.0 - s);
-            let new_s = (s * (1.0 + adjustment)).clamp(0.0, 1.0);
+            let new_s = vibrance_saturation(h, s, amount, protect_skin);
 
             // Convert back to RGB
             let (nr, ng, nb) = hsv_to_rgb(h, new_s, v);
@@ -670,6 +866,143 @@ pub fn temperature_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
     output
 }
 
+// ============================================================================
+// White Balance (Kelvin)
+// ============================================================================
+
+/// Color temperature treated as neutral by white_balance_kelvin (D65's CCT).
+pub const NEUTRAL_KELVIN: f32 = 6504.0;
+
+/// Distance from the Planckian locus (CIE 1960 Duv) at tint 1.0.
+const TINT_DUV: f64 = 0.02;
+
+/// CIE 1960 (u, v) of the Planckian locus, cubic spline approximation by
+/// Kim et al. (2002), valid from 1667 K to 25000 K.
+fn planckian_uv(kelvin: f64) -> (f64, f64) {
+    let t = kelvin.clamp(1667.0, 25000.0);
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 4000.0 {
+        -0.266_123_9e9 / t3 - 0.234_358_9e6 / t2 + 0.877_695_6e3 / t + 0.179_910
+    } else {
+        -3.025_846_9e9 / t3 + 2.107_037_9e6 / t2 + 0.222_634_7e3 / t + 0.240_390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.106_381_4 * x3 - 1.348_110_20 * x2 + 2.185_558_32 * x - 0.202_196_83
+    } else if t <= 4000.0 {
+        -0.954_947_6 * x3 - 1.374_185_93 * x2 + 2.091_370_15 * x - 0.167_488_67
+    } else {
+        3.081_758_0 * x3 - 5.873_386_70 * x2 + 3.751_129_97 * x - 0.370_014_83
+    };
+    let d = -2.0 * x + 12.0 * y + 3.0;
+    (4.0 * x / d, 6.0 * y / d)
+}
+
+/// Linear sRGB (Y = 1) of an illuminant at `kelvin`, `duv` above (green)
+/// or below (magenta) the Planckian locus.
+fn illuminant_rgb(kelvin: f64, duv: f64) -> [f64; 3] {
+    let (u, v) = planckian_uv(kelvin);
+    // Unit normal of the locus, pointing towards green (+v)
+    let (u1, v1) = planckian_uv(kelvin + 1.0);
+    let (du, dv) = (u1 - u, v1 - v);
+    let len = (du * du + dv * dv).sqrt().max(1e-12);
+    let (nu, nv) = if du > 0.0 { (-dv / len, du / len) } else { (dv / len, -du / len) };
+    let (u, v) = (u + nu * duv, v + nv * duv);
+
+    let d = 2.0 * u - 8.0 * v + 4.0;
+    let (x, y) = (3.0 * u / d, 2.0 * v / d);
+    let (cx, cz) = (x / y, (1.0 - x - y) / y);
+    [
+        3.240_454_2 * cx - 1.537_138_5 - 0.498_531_4 * cz,
+        -0.969_266_0 * cx + 1.876_010_8 + 0.041_556_0 * cz,
+        0.055_643_4 * cx - 0.204_025_9 + 1.057_225_2 * cz,
+    ]
+}
+
+/// Linear-light RGB multipliers that neutralize a light source.
+///
+/// Normalized so a gray pixel keeps its luminance. `kelvin` = NEUTRAL_KELVIN
+/// and `tint` = 0.0 give exactly 1.0 for every channel.
+///
+/// # Arguments
+/// * `kelvin` - Color temperature of the light in the scene (2000-25000 K)
+/// * `tint` - -1.0 (green) to 1.0 (magenta) correction
+pub fn white_balance_multipliers(kelvin: f32, tint: f32) -> [f32; 3] {
+    let reference = illuminant_rgb(NEUTRAL_KELVIN as f64, 0.0);
+    // Positive tint adds magenta, i.e. compensates a greenish light
+    let light = illuminant_rgb(kelvin.clamp(2000.0, 25000.0) as f64, tint.clamp(-1.0, 1.0) as f64 * TINT_DUV);
+    let m: [f64; 3] = std::array::from_fn(|c| reference[c] / light[c].max(1e-3));
+    let luma = LUMA_R as f64 * m[0] + LUMA_G as f64 * m[1] + LUMA_B as f64 * m[2];
+    if (kelvin == NEUTRAL_KELVIN && tint == 0.0) || luma <= 0.0 {
+        return [1.0; 3];
+    }
+    m.map(|v| (v / luma) as f32)
+}
+
+/// White balance in correlated color temperature units (f32 version).
+///
+/// Like the temperature and tint sliders of a raw converter: `kelvin` is
+/// the color of the light the image was lit by, which is mapped to neutral
+/// white. Lower values than the actual light make the image cooler, higher
+/// values warmer. The channel multipliers come from the Planckian locus and
+/// are applied in linear light. For grayscale input, returns a copy (no-op).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `kelvin` - Color temperature of the light (2000-25000 K, 6504 = neutral)
+/// * `tint` - -1.0 (green) to 1.0 (magenta), 0.0 = on the locus
+///
+/// # Returns
+/// White-balanced image with same channel count
+pub fn white_balance_kelvin_f32(input: ArrayView3<f32>, kelvin: f32, tint: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+
+    let m = white_balance_multipliers(kelvin, tint);
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..3 {
+                let linear = srgb_to_linear(input[[y, x, c]].clamp(0.0, 1.0)) * m[c];
+                output[[y, x, c]] = linear_to_srgb(linear.min(1.0)).clamp(0.0, 1.0);
+            }
+        }
+    }
+    output
+}
+
+/// White balance in correlated color temperature units (u8 version).
+///
+/// Same as white_balance_kelvin_f32, using lookup tables for the sRGB
+/// transfer function.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `kelvin` - Color temperature of the light (2000-25000 K, 6504 = neutral)
+/// * `tint` - -1.0 (green) to 1.0 (magenta), 0.0 = on the locus
+///
+/// # Returns
+/// White-balanced image with same channel count
+pub fn white_balance_kelvin_u8(input: ArrayView3<u8>, kelvin: f32, tint: f32) -> Array3<u8> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+
+    let m = white_balance_multipliers(kelvin, tint);
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..3 {
+                output[[y, x, c]] = linear_to_srgb_u8(srgb_u8_to_linear(input[[y, x, c]]) * m[c]);
+            }
+        }
+    }
+    output
+}
+
 // ============================================================================
 // Channel Mixer
 // ============================================================================
@@ -769,6 +1102,567 @@ pub fn channel_mixer_f32(
     output
 }
 
+// ============================================================================
+// Match Color
+// ============================================================================
+
+/// Mean and standard deviation of the L, a, b channels of the visible pixels.
+fn lab_statistics(image: ArrayView3<f32>) -> Option<([f32; 3], [f32; 3])> {
+    let (height, width, channels) = image.dim();
+    let mut sum = [0.0f64; 3];
+    let mut sum_sq = [0.0f64; 3];
+    let mut count = 0usize;
+    for y in 0..height {
+        for x in 0..width {
+            if channels == 4 && image[[y, x, 3]] <= 0.0 {
+                continue;
+            }
+            let (l, a, b) = rgb_to_lab(image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]);
+            for (k, v) in [l, a, b].into_iter().enumerate() {
+                sum[k] += v as f64;
+                sum_sq[k] += (v as f64) * (v as f64);
+            }
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    let n = count as f64;
+    let mean = sum.map(|s| s / n);
+    let std = [0, 1, 2].map(|k| (sum_sq[k] / n - mean[k] * mean[k]).max(0.0).sqrt() as f32);
+    Some((mean.map(|m| m as f32), std))
+}
+
+/// Match the colors of an image to a reference (f32 version).
+///
+/// Transfers the mean and standard deviation of every Lab channel from the
+/// reference to the source (Reinhard color transfer), so lightness, contrast
+/// and color cast follow the reference frame. Fully transparent pixels are
+/// ignored in both statistics. For grayscale input, returns a copy (no-op).
+///
+/// # Arguments
+/// * `source` - Image to adjust with 1, 3, or 4 channels, values 0.0-1.0
+/// * `reference` - Image to take the color statistics from (any size, 3 or 4 channels)
+/// * `amount` - Strength of the adjustment (0.0 = unchanged, 1.0 = full match)
+///
+/// # Returns
+/// Color-matched image with same channel count as `source`
+pub fn match_color_f32(source: ArrayView3<f32>, reference: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+    let (height, width, channels) = source.dim();
+    let mut output = source.to_owned();
+    if channels < 3 || reference.dim().2 < 3 {
+        return output;
+    }
+    let (Some((src_mean, src_std)), Some((ref_mean, ref_std))) = (lab_statistics(source), lab_statistics(reference)) else {
+        return output;
+    };
+
+    let amount = amount.clamp(0.0, 1.0);
+    let gain = [0, 1, 2].map(|k| if src_std[k] > 1e-4 { ref_std[k] / src_std[k] } else { 1.0 });
+    for y in 0..height {
+        for x in 0..width {
+            let (l, a, b) = rgb_to_lab(source[[y, x, 0]], source[[y, x, 1]], source[[y, x, 2]]);
+            let lab = [l, a, b];
+            let [l, a, b] = [0, 1, 2].map(|k| {
+                let target = (lab[k] - src_mean[k]) * gain[k] + ref_mean[k];
+                lab[k] + (target - lab[k]) * amount
+            });
+            let (r, g, b) = lab_to_rgb(l.clamp(0.0, 100.0), a, b);
+            output[[y, x, 0]] = r;
+            output[[y, x, 1]] = g;
+            output[[y, x, 2]] = b;
+        }
+    }
+    output
+}
+
+/// Match the colors of an image to a reference (u8 version).
+pub fn match_color_u8(source: ArrayView3<u8>, reference: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+    let source_f32 = source.mapv(|v| v as f32 / 255.0);
+    let reference_f32 = reference.mapv(|v| v as f32 / 255.0);
+    match_color_f32(source_f32.view(), reference_f32.view(), amount).mapv(|v| (v * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Replace Color
+// ============================================================================
+
+/// Soft mask of the pixels close to a color (f32 version).
+///
+/// Closeness is the CIE Lab color difference (Delta E 1976) divided by 100.
+/// Pixels within `tolerance` are fully selected; beyond it the mask fades
+/// linearly to 0 over `softness`. Grayscale input compares its gray value.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `target` - Color to select (R, G, B), 0.0-1.0
+/// * `tolerance` - Fully selected distance (0.0-1.0, 1.0 = Delta E 100)
+/// * `softness` - Width of the falloff beyond `tolerance` (same units)
+///
+/// # Returns
+/// Mask (height, width), 1.0 = selected
+pub fn color_range_mask_f32(input: ArrayView3<f32>, target: [f32; 3], tolerance: f32, softness: f32) -> Array2<f32> {
+    let (height, width, channels) = input.dim();
+    let (tl, ta, tb) = rgb_to_lab(target[0], target[1], target[2]);
+    let (tolerance, softness) = (tolerance.max(0.0), softness.max(0.0));
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let rgb = if channels >= 3 { [0, 1, 2].map(|c| input[[y, x, c]]) } else { [input[[y, x, 0]]; 3] };
+        let (l, a, b) = rgb_to_lab(rgb[0], rgb[1], rgb[2]);
+        let distance = ((l - tl).powi(2) + (a - ta).powi(2) + (b - tb).powi(2)).sqrt() / 100.0;
+        if distance <= tolerance {
+            1.0
+        } else if softness > 0.0 {
+            (1.0 - (distance - tolerance) / softness).max(0.0)
+        } else {
+            0.0
+        }
+    })
+}
+
+/// Soft mask of the pixels close to a color (u8 version).
+///
+/// # Returns
+/// Mask (height, width), 255 = selected
+pub fn color_range_mask_u8(input: ArrayView3<u8>, target: [u8; 3], tolerance: f32, softness: f32) -> Array2<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let target = target.map(|v| v as f32 / 255.0);
+    color_range_mask_f32(input_f32.view(), target, tolerance, softness).mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Replace a color throughout the image (f32 version).
+///
+/// Like Photoshop's Replace Color: every pixel is weighted by the color
+/// range mask of `target` ([`color_range_mask_f32`]) and shifted in Lab by
+/// the difference from `target` to `replacement`, so shading and texture
+/// of the recolored areas survive. Unlike a flood fill, matching pixels
+/// anywhere in the image change. For grayscale input, returns a copy.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `target` - Color to replace (R, G, B), 0.0-1.0
+/// * `replacement` - New color (R, G, B), 0.0-1.0
+/// * `tolerance` - Fully replaced distance (0.0-1.0, 1.0 = Delta E 100)
+/// * `softness` - Width of the falloff beyond `tolerance` (same units)
+/// * `preserve_luminosity` - Keep the lightness of every pixel, changing
+///   only hue and chroma
+///
+/// # Returns
+/// Recolored image with same channel count
+pub fn replace_color_f32(
+    input: ArrayView3<f32>,
+    target: [f32; 3],
+    replacement: [f32; 3],
+    tolerance: f32,
+    softness: f32,
+    preserve_luminosity: bool,
+) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+    let mask = color_range_mask_f32(input, target, tolerance, softness);
+    let (tl, ta, tb) = rgb_to_lab(target[0], target[1], target[2]);
+    let (rl, ra, rb) = rgb_to_lab(replacement[0], replacement[1], replacement[2]);
+    let shift = [if preserve_luminosity { 0.0 } else { rl - tl }, ra - ta, rb - tb];
+
+    for y in 0..height {
+        for x in 0..width {
+            let weight = mask[[y, x]];
+            if weight <= 0.0 {
+                continue;
+            }
+            let rgb = [0, 1, 2].map(|c| input[[y, x, c]]);
+            let (l, a, b) = rgb_to_lab(rgb[0], rgb[1], rgb[2]);
+            let (r, g, b) = lab_to_rgb((l + shift[0]).clamp(0.0, 100.0), a + shift[1], b + shift[2]);
+            for (c, replaced) in [r, g, b].into_iter().enumerate() {
+                output[[y, x, c]] = rgb[c] + (replaced - rgb[c]) * weight;
+            }
+        }
+    }
+    output
+}
+
+/// Replace a color throughout the image (u8 version).
+pub fn replace_color_u8(
+    input: ArrayView3<u8>,
+    target: [u8; 3],
+    replacement: [u8; 3],
+    tolerance: f32,
+    softness: f32,
+    preserve_luminosity: bool,
+) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let [target, replacement] = [target, replacement].map(|c| c.map(|v| v as f32 / 255.0));
+    replace_color_f32(input_f32.view(), target, replacement, tolerance, softness, preserve_luminosity)
+        .mapv(|v| (v * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Split Tone / Duotone
+// ============================================================================
+
+/// BT.709 luminance of an RGB triple.
+#[inline]
+fn luma(rgb: [f32; 3]) -> f32 {
+    LUMA_R * rgb[0] + LUMA_G * rgb[1] + LUMA_B * rgb[2]
+}
+
+/// Tint shadows and highlights with different colors (f32 version).
+///
+/// Each tint color only contributes its chroma (the color minus its own
+/// luminance), so the image luminance is kept and a gray tint does nothing;
+/// saturated colors tint more strongly. For grayscale input, returns a copy.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `shadow_color` - Tint for the shadows (R, G, B), 0.0-1.0
+/// * `highlight_color` - Tint for the highlights (R, G, B), 0.0-1.0
+/// * `balance` - Split point (-1.0 = mostly shadow tint, 0.0 = even, 1.0 = mostly highlight tint)
+///
+/// # Returns
+/// Toned image with same channel count
+pub fn split_tone_f32(input: ArrayView3<f32>, shadow_color: [f32; 3], highlight_color: [f32; 3], balance: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+
+    let chroma = |color: [f32; 3]| {
+        let l = luma(color);
+        color.map(|c| c - l)
+    };
+    let (shadow_chroma, highlight_chroma) = (chroma(shadow_color), chroma(highlight_color));
+    let shift = balance.clamp(-1.0, 1.0) * 0.5;
+
+    for y in 0..height {
+        for x in 0..width {
+            let rgb = [0, 1, 2].map(|c| input[[y, x, c]]);
+            let t = (luma(rgb) + shift).clamp(0.0, 1.0);
+            let highlight_weight = t * t * (3.0 - 2.0 * t);
+            for c in 0..3 {
+                let tint = shadow_chroma[c] * (1.0 - highlight_weight) + highlight_chroma[c] * highlight_weight;
+                output[[y, x, c]] = (rgb[c] + tint).clamp(0.0, 1.0);
+            }
+        }
+    }
+    output
+}
+
+/// Tint shadows and highlights with different colors (u8 version).
+pub fn split_tone_u8(input: ArrayView3<u8>, shadow_color: [u8; 3], highlight_color: [u8; 3], balance: f32) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let to_f32 = |color: [u8; 3]| color.map(|v| v as f32 / 255.0);
+    split_tone_f32(input_f32.view(), to_f32(shadow_color), to_f32(highlight_color), balance)
+        .mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Print the image with 1-4 inks like a duotone / tritone / quadtone (f32 version).
+///
+/// The image luminance becomes a tone value (0.0 = paper white, 1.0 =
+/// black). Each ink's curve maps that tone to ink coverage, and the inks
+/// are layered multiplicatively on white paper. For grayscale input,
+/// returns a copy.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `inks` - Ink colors (R, G, B), 0.0-1.0, typically the darkest first
+/// * `curves` - Per-ink (tone, coverage) control points, 0.0-1.0. Missing or
+///   empty curves are linear.
+///
+/// # Returns
+/// Toned image with same channel count
+pub fn duotone_f32(input: ArrayView3<f32>, inks: &[[f32; 3]], curves: &[Vec<(f32, f32)>]) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 || inks.is_empty() {
+        return output;
+    }
+
+    // Coverage lookup per ink, indexed by tone
+    const STEPS: usize = 1024;
+    let coverage: Vec<Vec<f32>> = (0..inks.len())
+        .map(|i| {
+            let points = curves.get(i).map(Vec::as_slice).unwrap_or(&[]);
+            (0..=STEPS)
+                .map(|step| {
+                    let tone = step as f32 / STEPS as f32;
+                    if points.is_empty() { tone } else { pchip_interpolate(points, tone).clamp(0.0, 1.0) }
+                })
+                .collect()
+        })
+        .collect();
+
+    for y in 0..height {
+        for x in 0..width {
+            let tone = 1.0 - luma([0, 1, 2].map(|c| input[[y, x, c]])).clamp(0.0, 1.0);
+            let index = (tone * STEPS as f32).round() as usize;
+            let mut paper = [1.0f32; 3];
+            for (ink, table) in inks.iter().zip(&coverage) {
+                let amount = table[index];
+                for c in 0..3 {
+                    paper[c] *= 1.0 - amount * (1.0 - ink[c]);
+                }
+            }
+            for c in 0..3 {
+                output[[y, x, c]] = paper[c];
+            }
+        }
+    }
+    output
+}
+
+/// Print the image with 1-4 inks (u8 version).
+pub fn duotone_u8(input: ArrayView3<u8>, inks: &[[u8; 3]], curves: &[Vec<(f32, f32)>]) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let inks: Vec<[f32; 3]> = inks.iter().map(|ink| ink.map(|v| v as f32 / 255.0)).collect();
+    duotone_f32(input_f32.view(), &inks, curves).mapv(|v| (v * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Black & White
+// ============================================================================
+
+/// Parameters of the Photoshop-style Black & White conversion.
+///
+/// Each weight is the gray value (0.0-1.0, -2.0 to 3.0 allowed) a fully
+/// saturated color of that hue turns into. The defaults are Photoshop's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackWhiteParams {
+    pub reds: f32,
+    pub yellows: f32,
+    pub greens: f32,
+    pub cyans: f32,
+    pub blues: f32,
+    pub magentas: f32,
+    /// Optional tint color (R, G, B), 0.0-1.0; its hue and saturation are
+    /// applied to the gray result
+    pub tint: Option<[f32; 3]>,
+}
+
+impl Default for BlackWhiteParams {
+    fn default() -> Self {
+        Self { reds: 0.4, yellows: 0.6, greens: 0.4, cyans: 0.6, blues: 0.2, magentas: 0.8, tint: None }
+    }
+}
+
+impl BlackWhiteParams {
+    /// Gray value of an RGB pixel.
+    ///
+    /// The pixel's hue lies between a primary (its largest channel) and a
+    /// secondary (the largest two); gray = min + (max - mid) * primary weight
+    /// + (mid - min) * secondary weight. Neutral pixels are unchanged.
+    fn gray(&self, [r, g, b]: [f32; 3]) -> f32 {
+        let (max, mid, min, primary, secondary) = if r >= g && r >= b {
+            if g >= b { (r, g, b, self.reds, self.yellows) } else { (r, b, g, self.reds, self.magentas) }
+        } else if g >= b {
+            if r >= b { (g, r, b, self.greens, self.yellows) } else { (g, b, r, self.greens, self.cyans) }
+        } else if r >= g {
+            (b, r, g, self.blues, self.magentas)
+        } else {
+            (b, g, r, self.blues, self.cyans)
+        };
+        min + (max - mid) * primary + (mid - min) * secondary
+    }
+}
+
+/// Photoshop-style Black & White conversion (f32 version).
+///
+/// Unlike a weighted channel sum (grayscale_weighted) each of the six hue
+/// ranges gets its own brightness, e.g. darkening a blue sky without
+/// touching skin tones. With a tint the result is colorized with the tint's
+/// hue and saturation. For grayscale input, returns a copy.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `params` - Hue weights and optional tint
+///
+/// # Returns
+/// Gray (or tinted) image with same channel count
+pub fn black_white_f32(input: ArrayView3<f32>, params: &BlackWhiteParams) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+
+    let tint = params.tint.map(|[r, g, b]| {
+        let (h, s, _) = rgb_to_hsl(r, g, b);
+        (h, s)
+    });
+    for y in 0..height {
+        for x in 0..width {
+            let gray = params.gray([0, 1, 2].map(|c| input[[y, x, c]].clamp(0.0, 1.0))).clamp(0.0, 1.0);
+            let (r, g, b) = match tint {
+                Some((h, s)) => hsl_to_rgb(h, s, gray),
+                None => (gray, gray, gray),
+            };
+            output[[y, x, 0]] = r.clamp(0.0, 1.0);
+            output[[y, x, 1]] = g.clamp(0.0, 1.0);
+            output[[y, x, 2]] = b.clamp(0.0, 1.0);
+        }
+    }
+    output
+}
+
+/// Photoshop-style Black & White conversion (u8 version).
+pub fn black_white_u8(input: ArrayView3<u8>, params: &BlackWhiteParams) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    black_white_f32(input_f32.view(), params).mapv(|v| (v * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Color Vision Deficiency
+// ============================================================================
+
+/// Kind of color vision deficiency (missing or shifted cone type).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorVisionDeficiency {
+    /// Long-wavelength (red) cones: protanopia / protanomaly
+    Protan,
+    /// Medium-wavelength (green) cones: deuteranopia / deuteranomaly, the most common
+    #[default]
+    Deutan,
+    /// Short-wavelength (blue) cones: tritanopia / tritanomaly
+    Tritan,
+}
+
+impl ColorVisionDeficiency {
+    /// Parse a deficiency name ("protan", "deutan", "tritan" or the -opia / -omaly forms).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "protan" | "protanopia" | "protanomaly" => Some(ColorVisionDeficiency::Protan),
+            "deutan" | "deuteranopia" | "deuteranomaly" => Some(ColorVisionDeficiency::Deutan),
+            "tritan" | "tritanopia" | "tritanomaly" => Some(ColorVisionDeficiency::Tritan),
+            _ => None,
+        }
+    }
+
+    /// Machado et al. (2009) dichromat matrix for linear RGB (severity 1.0).
+    fn dichromat_matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protan => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVisionDeficiency::Deutan => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVisionDeficiency::Tritan => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// Simulation matrix blended from identity by severity (0.0-1.0).
+    fn matrix(self, severity: f32) -> [[f32; 3]; 3] {
+        let s = severity.clamp(0.0, 1.0);
+        let full = self.dichromat_matrix();
+        std::array::from_fn(|r| {
+            std::array::from_fn(|c| {
+                let identity = if r == c { 1.0 - s } else { 0.0 };
+                identity + full[r][c] * s
+            })
+        })
+    }
+
+    /// Daltonization: where the lost color difference is redistributed.
+    fn error_shift(self) -> [[f32; 3]; 3] {
+        match self {
+            // Red-green error goes to lightness (green) and blue
+            ColorVisionDeficiency::Protan | ColorVisionDeficiency::Deutan => [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]],
+            // Blue-yellow error goes to red and green
+            ColorVisionDeficiency::Tritan => [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]],
+        }
+    }
+}
+
+#[inline]
+fn apply_matrix(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    std::array::from_fn(|r| m[r][0] * v[0] + m[r][1] * v[1] + m[r][2] * v[2])
+}
+
+/// Map every RGB pixel through `f` in linear light; alpha and grayscale are untouched.
+fn map_linear_rgb(input: ArrayView3<f32>, f: impl Fn([f32; 3]) -> [f32; 3]) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+    for y in 0..height {
+        for x in 0..width {
+            let rgb = f([0, 1, 2].map(|c| srgb_to_linear(input[[y, x, c]].clamp(0.0, 1.0))));
+            for c in 0..3 {
+                output[[y, x, c]] = linear_to_srgb(rgb[c].clamp(0.0, 1.0));
+            }
+        }
+    }
+    output
+}
+
+/// Simulate how an image appears with a color vision deficiency (f32 version).
+///
+/// Uses the Machado et al. (2009) dichromat matrices in linear RGB;
+/// severities below 1.0 blend them with the identity to approximate the
+/// anomalous trichromacies. For grayscale input, returns a copy.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `deficiency` - Protan, deutan or tritan
+/// * `severity` - 0.0 (normal vision) to 1.0 (dichromacy)
+///
+/// # Returns
+/// Simulated image with same channel count
+pub fn simulate_cvd_f32(input: ArrayView3<f32>, deficiency: ColorVisionDeficiency, severity: f32) -> Array3<f32> {
+    let m = deficiency.matrix(severity);
+    map_linear_rgb(input, |rgb| apply_matrix(&m, rgb))
+}
+
+/// Simulate a color vision deficiency (u8 version).
+pub fn simulate_cvd_u8(input: ArrayView3<u8>, deficiency: ColorVisionDeficiency, severity: f32) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    simulate_cvd_f32(input_f32.view(), deficiency, severity).mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Recolor an image so people with a color vision deficiency can tell colors apart (f32 version).
+///
+/// Daltonization (Fidaner et al.): the difference between the image and its
+/// dichromat simulation is the information that gets lost; it is shifted
+/// into channels the viewer still distinguishes and added back. Colors that
+/// are already seen correctly barely change. For grayscale input, returns
+/// a copy.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `deficiency` - Protan, deutan or tritan
+/// * `amount` - Strength of the correction (0.0 = unchanged, 1.0 = full)
+///
+/// # Returns
+/// Corrected image with same channel count
+pub fn daltonize_f32(input: ArrayView3<f32>, deficiency: ColorVisionDeficiency, amount: f32) -> Array3<f32> {
+    let simulate = deficiency.dichromat_matrix();
+    let shift = deficiency.error_shift();
+    let amount = amount.max(0.0);
+    map_linear_rgb(input, |rgb| {
+        let seen = apply_matrix(&simulate, rgb);
+        let correction = apply_matrix(&shift, [0, 1, 2].map(|c| rgb[c] - seen[c]));
+        [0, 1, 2].map(|c| rgb[c] + correction[c] * amount)
+    })
+}
+
+/// Recolor an image for viewers with a color vision deficiency (u8 version).
+pub fn daltonize_u8(input: ArrayView3<u8>, deficiency: ColorVisionDeficiency, amount: f32) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    daltonize_f32(input_f32.view(), deficiency, amount).mapv(|v| (v * 255.0).round() as u8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -837,6 +1731,66 @@ mod tests {
         assert!((result[[0, 0, 2]] - 0.5).abs() < 0.001);
     }
 
+    #[test]
+    fn test_hsl_adjust() {
+        let mut img = Array3::<f32>::zeros((1, 3, 4));
+        for (x, px) in [[0.8, 0.4, 0.2, 1.0], [0.5, 0.5, 0.5, 0.5], [0.1, 0.2, 0.6, 1.0]].iter().enumerate() {
+            for c in 0..4 {
+                img[[0, x, c]] = px[c];
+            }
+        }
+
+        // Neutral parameters change nothing
+        let same = hsl_adjust_f32(img.view(), &HslAdjustParams::default());
+        assert!(same.iter().zip(img.iter()).all(|(a, b)| (a - b).abs() < 1e-6));
+
+        // Hue matches hue_shift, -100% saturation is gray, alpha is kept
+        let shifted = hsl_adjust_f32(img.view(), &HslAdjustParams { hue: 120.0, ..Default::default() });
+        assert_eq!(shifted, hue_shift_f32(img.view(), 120.0));
+        let gray = hsl_adjust_f32(img.view(), &HslAdjustParams { saturation: -1.0, ..Default::default() });
+        assert!((gray[[0, 0, 0]] - 0.5).abs() < 1e-6 && (gray[[0, 0, 2]] - 0.5).abs() < 1e-6);
+        assert_eq!(gray[[0, 1, 3]], 0.5);
+
+        // +100% lightness is white
+        let white = hsl_adjust_f32(img.view(), &HslAdjustParams { lightness: 1.0, ..Default::default() });
+        assert!(white.iter().all(|&v| v == 1.0 || v == 0.5));
+
+        // Colorize tints everything with one hue, keeping lightness
+        let params = HslAdjustParams { hue: 240.0, saturation: 0.5, lightness: 0.0, colorize: true };
+        let tinted = hsl_adjust_f32(img.view(), &params);
+        for x in 0..3 {
+            let (h, s, _) = rgb_to_hsl(tinted[[0, x, 0]], tinted[[0, x, 1]], tinted[[0, x, 2]]);
+            assert!((h - 240.0).abs() < 0.5 && (s - 0.5).abs() < 1e-3);
+        }
+        assert!((tinted[[0, 1, 0]] + tinted[[0, 1, 2]] - 1.0).abs() < 1e-5);
+
+        let img_u8 = img.mapv(|v| (v * 255.0).round() as u8);
+        assert_eq!(hsl_adjust_u8(img_u8.view(), &HslAdjustParams::default()), img_u8);
+    }
+
+    #[test]
+    fn test_vibrance_protect_skin() {
+        // A muted skin tone and a muted blue
+        let mut img = Array3::<f32>::zeros((1, 2, 3));
+        for (c, v) in [0.8, 0.6, 0.5].into_iter().enumerate() {
+            img[[0, 0, c]] = v;
+        }
+        for (c, v) in [0.5, 0.6, 0.8].into_iter().enumerate() {
+            img[[0, 1, c]] = v;
+        }
+        let spread = |r: &Array3<f32>, x: usize| r[[0, x, 0]].max(r[[0, x, 2]]) - r[[0, x, 0]].min(r[[0, x, 2]]);
+
+        let plain = vibrance_f32(img.view(), 1.0);
+        let protected = vibrance_protected_f32(img.view(), 1.0, 1.0);
+        assert!(spread(&plain, 0) > 0.35);
+        // The skin tone is barely boosted, the blue as much as before
+        assert!(spread(&protected, 0) < 0.32);
+        assert!((protected[[0, 1, 0]] - plain[[0, 1, 0]]).abs() < 1e-6);
+        assert_eq!(vibrance_protected_f32(img.view(), 1.0, 0.0), plain);
+        // Desaturation ignores the protection
+        assert_eq!(vibrance_protected_f32(img.view(), -0.5, 1.0), vibrance_f32(img.view(), -0.5));
+    }
+
     #[test]
     fn test_vibrance_grayscale_noop() {
         let mut img = Array3::<f32>::zeros((1, 1, 1));
@@ -949,6 +1903,66 @@ mod tests {
         assert!(result[[0, 0, 2]] > 0.5);
     }
 
+    #[test]
+    fn test_white_balance_kelvin() {
+        assert_eq!(white_balance_multipliers(NEUTRAL_KELVIN, 0.0), [1.0; 3]);
+        // The locus runs from orange to blue
+        let (u_warm, _) = planckian_uv(2000.0);
+        let (u_cool, _) = planckian_uv(20000.0);
+        assert!(u_warm > u_cool);
+
+        let img = Array3::<u8>::from_shape_fn((1, 2, 4), |(_, x, c)| if c == 3 { 200 } else { [128u8, 230][x] });
+        assert_eq!(white_balance_kelvin_u8(img.view(), NEUTRAL_KELVIN, 0.0), img);
+
+        // Compensating tungsten light cools, compensating shade warms
+        let tungsten = white_balance_kelvin_u8(img.view(), 3000.0, 0.0);
+        assert!(tungsten[[0, 0, 2]] > 128 + 30 && tungsten[[0, 0, 0]] < 128 - 20);
+        let shade = white_balance_kelvin_u8(img.view(), 9000.0, 0.0);
+        assert!(shade[[0, 0, 0]] > shade[[0, 0, 2]]);
+        assert_eq!(tungsten[[0, 1, 3]], 200);
+
+        // Positive tint adds magenta
+        let magenta = white_balance_kelvin_u8(img.view(), NEUTRAL_KELVIN, 1.0);
+        assert!(magenta[[0, 0, 1]] < magenta[[0, 0, 0]] && magenta[[0, 0, 1]] < magenta[[0, 0, 2]]);
+
+        // A gray keeps its luminance (in linear light)
+        let img_f32 = img.mapv(|v| v as f32 / 255.0);
+        let result = white_balance_kelvin_f32(img_f32.view(), 4000.0, 0.3);
+        let luma = |px: [f32; 3]| {
+            let [r, g, b] = px.map(srgb_to_linear);
+            LUMA_R * r + LUMA_G * g + LUMA_B * b
+        };
+        let before = luma([img_f32[[0, 0, 0]]; 3]);
+        let after = luma([result[[0, 0, 0]], result[[0, 0, 1]], result[[0, 0, 2]]]);
+        assert!((before - after).abs() < 1e-3, "{} vs {}", before, after);
+    }
+
+    #[test]
+    fn test_black_white() {
+        let colors = [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0], [0.0, 1.0, 1.0], [0.0, 0.0, 1.0], [1.0, 0.0, 1.0], [0.3, 0.3, 0.3]];
+        let img = Array3::from_shape_fn((1, colors.len(), 4), |(_, x, c)| if c == 3 { 1.0 } else { colors[x][c] });
+
+        // Photoshop's defaults: primaries and secondaries map to their weights
+        let result = black_white_f32(img.view(), &BlackWhiteParams::default());
+        for (x, expected) in [0.4, 0.6, 0.4, 0.6, 0.2, 0.8, 0.3].into_iter().enumerate() {
+            assert!((result[[0, x, 0]] - expected).abs() < 1e-6, "{}: {}", x, result[[0, x, 0]]);
+            assert_eq!(result[[0, x, 0]], result[[0, x, 2]]);
+        }
+
+        // Orange sits between reds and yellows
+        let params = BlackWhiteParams { blues: 0.0, ..Default::default() };
+        assert!((params.gray([1.0, 0.5, 0.0]) - 0.5).abs() < 1e-6);
+        let dark_sky = black_white_f32(img.view(), &params);
+        assert_eq!(dark_sky[[0, 4, 0]], 0.0);
+
+        // A tint colors the grays but keeps black and white
+        let tinted = black_white_f32(img.view(), &BlackWhiteParams { tint: Some([0.8, 0.6, 0.4]), ..Default::default() });
+        assert!(tinted[[0, 6, 0]] > tinted[[0, 6, 2]]);
+        let edges = Array3::from_shape_fn((1, 2, 3), |(_, x, _)| x as u8 * 255);
+        let params = BlackWhiteParams { tint: Some([0.8, 0.6, 0.4]), ..Default::default() };
+        assert_eq!(black_white_u8(edges.view(), &params), edges);
+    }
+
     #[test]
     fn test_temperature_grayscale_noop() {
         let mut img = Array3::<u8>::zeros((1, 1, 1));
@@ -1000,4 +2014,102 @@ mod tests {
         let result = channel_mixer_u8(img.view(), 2, 0, 1);
         assert_eq!(result[[0, 0, 0]], 128);
     }
+
+    #[test]
+    fn test_lab_roundtrip() {
+        for (r, g, b) in [(0.2, 0.5, 0.8), (1.0, 0.0, 0.0), (0.5, 0.5, 0.5)] {
+            let (l, a, bb) = rgb_to_lab(r, g, b);
+            let (r2, g2, b2) = lab_to_rgb(l, a, bb);
+            assert!((r - r2).abs() < 1e-3 && (g - g2).abs() < 1e-3 && (b - b2).abs() < 1e-3);
+        }
+        let (l, a, b) = rgb_to_lab(1.0, 1.0, 1.0);
+        assert!((l - 100.0).abs() < 0.01 && a.abs() < 0.01 && b.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_match_color_takes_reference_cast() {
+        // Neutral ramp matched to a warm, darker reference
+        let source = Array3::from_shape_fn((4, 4, 3), |(y, x, _)| 0.3 + 0.04 * (x + y * 4) as f32);
+        let reference = Array3::from_shape_fn((2, 2, 3), |(y, x, c)| [0.5, 0.35, 0.2][c] + 0.05 * (x + y) as f32);
+        let result = match_color_f32(source.view(), reference.view(), 1.0);
+
+        let (src_mean, _) = lab_statistics(result.view()).unwrap();
+        let (ref_mean, _) = lab_statistics(reference.view()).unwrap();
+        for k in 0..3 {
+            assert!((src_mean[k] - ref_mean[k]).abs() < 1.0, "channel {k}: {} vs {}", src_mean[k], ref_mean[k]);
+        }
+        assert_eq!(match_color_f32(source.view(), reference.view(), 0.0).mapv(|v| (v * 1000.0).round()), source.mapv(|v| (v * 1000.0).round()));
+    }
+
+    #[test]
+    fn test_replace_color_recolors_matching_pixels() {
+        // Two shades of red and a green pixel
+        let img = Array3::from_shape_fn((1, 3, 3), |(_, x, c)| [[0.8, 0.1, 0.1], [0.6, 0.08, 0.08], [0.1, 0.7, 0.2]][x][c]);
+        let result = replace_color_f32(img.view(), [0.8, 0.1, 0.1], [0.1, 0.2, 0.8], 0.15, 0.1, false);
+
+        assert!((0..3).all(|c| (result[[0, 0, c]] - [0.1, 0.2, 0.8][c]).abs() < 0.01));
+        assert!(result[[0, 1, 2]] > result[[0, 1, 0]], "darker red shade turns blue too");
+        assert!((0..3).all(|c| (result[[0, 2, c]] - img[[0, 2, c]]).abs() < 1e-6));
+
+        let mask = color_range_mask_f32(img.view(), [0.8, 0.1, 0.1], 0.15, 0.1);
+        assert_eq!((mask[[0, 0]], mask[[0, 2]]), (1.0, 0.0));
+
+        // Preserving luminosity keeps the Lab lightness of the target
+        let kept = replace_color_f32(img.view(), [0.8, 0.1, 0.1], [0.1, 0.2, 0.8], 0.15, 0.1, true);
+        let before = rgb_to_lab(0.8, 0.1, 0.1).0;
+        let after = rgb_to_lab(kept[[0, 0, 0]], kept[[0, 0, 1]], kept[[0, 0, 2]]).0;
+        assert!((before - after).abs() < 1.0, "L {before} vs {after}");
+    }
+
+    #[test]
+    fn test_split_tone_keeps_luminance() {
+        let img = Array3::from_shape_fn((1, 3, 3), |(_, x, _)| [0.15, 0.5, 0.85][x]);
+        let result = split_tone_f32(img.view(), [0.2, 0.4, 0.8], [0.9, 0.7, 0.3], 0.0);
+
+        // Blue shadows, warm highlights, luminance unchanged
+        assert!(result[[0, 0, 2]] > result[[0, 0, 0]]);
+        assert!(result[[0, 2, 0]] > result[[0, 2, 2]]);
+        for x in 0..3 {
+            let rgb = [0, 1, 2].map(|c| result[[0, x, c]]);
+            assert!((luma(rgb) - img[[0, x, 0]]).abs() < 0.01);
+        }
+        let gray = split_tone_f32(img.view(), [0.5; 3], [0.5; 3], 0.3);
+        assert!(gray.iter().zip(img.iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_duotone_layers_inks() {
+        let img = Array3::from_shape_fn((1, 3, 3), |(_, x, _)| [0.0, 0.5, 1.0][x]);
+
+        // A single black ink reproduces the grayscale image
+        let mono = duotone_f32(img.view(), &[[0.0, 0.0, 0.0]], &[]);
+        assert!((mono[[0, 1, 0]] - 0.5).abs() < 0.01);
+
+        // Black only in the shadows plus orange everywhere
+        let curves = vec![vec![(0.0, 0.0), (0.5, 0.0), (1.0, 1.0)], vec![]];
+        let result = duotone_f32(img.view(), &[[0.0, 0.0, 0.0], [1.0, 0.5, 0.0]], &curves);
+        assert!(result[[0, 2, 0]] > 0.99 && result[[0, 2, 2]] > 0.99);
+        assert!((result[[0, 1, 0]] - 1.0).abs() < 0.01 && (result[[0, 1, 2]] - 0.5).abs() < 0.01);
+        assert!(result[[0, 0, 0]] < 0.01);
+    }
+
+    #[test]
+    fn test_cvd_simulation_and_daltonize() {
+        // Red and green that deutans confuse
+        let img = Array3::from_shape_fn((1, 2, 4), |(_, x, c)| [[0.8, 0.3, 0.2, 1.0], [0.4, 0.55, 0.2, 1.0]][x][c]);
+        let diff = |a: &Array3<f32>| (0..3).map(|c| (a[[0, 0, c]] - a[[0, 1, c]]).abs()).sum::<f32>();
+
+        let deutan = ColorVisionDeficiency::from_name("deuteranopia").unwrap();
+        let seen = simulate_cvd_f32(img.view(), deutan, 1.0);
+        assert!(diff(&seen) < diff(&img) * 0.5);
+        assert_eq!(seen[[0, 0, 3]], 1.0);
+        // Grays look the same to everyone; severity 0 is normal vision
+        let gray = Array3::from_elem((1, 1, 3), 0.5f32);
+        assert!((simulate_cvd_f32(gray.view(), ColorVisionDeficiency::Tritan, 1.0)[[0, 0, 2]] - 0.5).abs() < 0.01);
+        assert!((simulate_cvd_f32(img.view(), deutan, 0.0)[[0, 0, 0]] - 0.8).abs() < 1e-4);
+
+        // Daltonized colors stay further apart for the same viewer
+        let corrected = daltonize_f32(img.view(), deutan, 1.0);
+        assert!(diff(&simulate_cvd_f32(corrected.view(), deutan, 1.0)) > diff(&seen) * 1.5);
+    }
 }
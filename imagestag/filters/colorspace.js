@@ -0,0 +1,57 @@
+/**
+ * Transfer functions - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - colorspace.rs (Rust implementation)
+ * - colorspace.py (Python wrapper)
+ *
+ * Provides: decode_transfer, encode_transfer
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+/**
+ * Run a f32 transfer conversion and wrap the result.
+ */
+function convertTransfer(wasmFn, imageData, transfer) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasmFn(data, width, height, channels, transfer ?? 'srgb');
+    return {
+        data: new Float32Array(result.buffer),
+        width,
+        height,
+        channels
+    };
+}
+
+// ============================================================================
+// Transfer Functions
+// ============================================================================
+
+/**
+ * Decode an encoded image to linear light (f32 only).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}, signal values 0-1
+ * @param {string} transfer - 'srgb'|'pq'|'hlg'|'slog3'|'vlog'|'logc3'
+ * @returns {Object} - Linear image data (1.0 = reference white, HDR curves exceed 1.0)
+ */
+export function decode_transfer(imageData, transfer = 'srgb') {
+    return convertTransfer(wasm.decode_transfer_wasm, imageData, transfer);
+}
+
+/**
+ * Encode a linear image with a transfer curve (f32 only).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}, 1.0 = reference white
+ * @param {string} transfer - 'srgb'|'pq'|'hlg'|'slog3'|'vlog'|'logc3'
+ * @returns {Object} - Encoded image data, signal values 0-1
+ */
+export function encode_transfer(imageData, transfer = 'srgb') {
+    return convertTransfer(wasm.encode_transfer_wasm, imageData, transfer);
+}
+
+export default {
+    initWasm,
+    decode_transfer, encode_transfer
+};
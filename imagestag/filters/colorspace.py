@@ -0,0 +1,79 @@
+"""Transfer functions with Rust backend.
+
+This module converts between encoded signals and linear light, so video
+frames and camera footage can be linearized before filtering and encoded
+again afterwards:
+- sRGB
+- PQ (SMPTE ST 2084, HDR10)
+- HLG (ITU-R BT.2100, scene light)
+- S-Log3 (Sony), V-Log (Panasonic), LogC3 (ARRI, EI 800)
+
+Linear values use one scale for all curves: 1.0 is reference white and
+0.18 mid gray. PQ and HLG highlights decode above 1.0; use
+`imagestag.filters.tonemap` to bring them back to display range.
+
+## Supported Formats
+
+float32 arrays with 1, 3, or 4 channels. Color channels are converted
+independently, alpha is preserved.
+
+Co-located with:
+- colorspace.rs (Rust implementation)
+- colorspace.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.colorspace import decode_transfer, encode_transfer
+
+    linear = decode_transfer(frame, "slog3")
+    frame = encode_transfer(processed, "slog3")
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Transfer Functions
+# ============================================================================
+
+def decode_transfer(image: np.ndarray, transfer: str = "srgb") -> np.ndarray:
+    """Decode an encoded image to linear light (f32 only).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), signal
+            values 0.0-1.0
+        transfer: "srgb", "pq", "hlg", "slog3", "vlog" or "logc3"
+
+    Returns:
+        Linear float32 array (1.0 = reference white, HDR curves exceed 1.0)
+    """
+    _validate_image(image, np.float32, "decode_transfer")
+    return imagestag_rust.decode_transfer(image, transfer)
+
+
+def encode_transfer(image: np.ndarray, transfer: str = "srgb") -> np.ndarray:
+    """Encode a linear image with a transfer curve (f32 only).
+
+    Args:
+        image: Linear float32 array with 1, 3, or 4 channels (H, W, C),
+            1.0 = reference white
+        transfer: "srgb", "pq", "hlg", "slog3", "vlog" or "logc3"
+
+    Returns:
+        Encoded float32 array, signal values 0.0-1.0
+    """
+    _validate_image(image, np.float32, "encode_transfer")
+    return imagestag_rust.encode_transfer(image, transfer)
+
+
+__all__ = [
+    'decode_transfer', 'encode_transfer',
+]
@@ -0,0 +1,263 @@
+//! Transfer functions: sRGB, PQ, HLG, S-Log3, V-Log, LogC3.
+//!
+//! Encoded video and camera footage store light through a non-linear
+//! transfer curve. Blurs, resampling and blending behave physically only on
+//! linear light, so frames are decoded to linear before filtering and
+//! encoded again afterwards.
+//!
+//! Linear values share one scale across all curves: 1.0 is reference
+//! (diffuse) white and 0.18 mid gray. HDR curves therefore decode to values
+//! above 1.0 for highlights - PQ reaches 10000 / 203 ≈ 49.3, HLG about 3.8 -
+//! which [`tonemap`](super::tonemap) brings back to display range. sRGB and
+//! the log curves decode their full signal range as usual.
+//!
+//! The curves only have an f32 version: 8 bits don't resolve linear light
+//! without visible banding.
+//!
+//! ## Supported Formats
+//!
+//! All functions accept images with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - converts the single channel
+//! - **RGB**: (height, width, 3) - converts each channel on its own
+//! - **RGBA**: (height, width, 4) - converts RGB, preserves alpha
+
+use ndarray::{Array3, ArrayView3};
+
+use super::resample::{linear_to_srgb, srgb_to_linear};
+
+/// Luminance of reference white in PQ content (ITU-R BT.2408), in cd/m².
+const PQ_REFERENCE_WHITE: f32 = 203.0;
+/// Peak luminance of the PQ signal range, in cd/m².
+const PQ_PEAK: f32 = 10000.0;
+
+// SMPTE ST 2084 constants
+const PQ_M1: f32 = 2610.0 / 16384.0;
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+// ITU-R BT.2100 HLG constants
+const HLG_A: f32 = 0.178_832_77;
+const HLG_B: f32 = 0.284_668_92;
+const HLG_C: f32 = 0.559_910_7;
+/// Scene light of reference white (75% HLG signal), scaled to 1.0.
+const HLG_REFERENCE_WHITE: f32 = 0.264_962_56;
+
+// Sony S-Log3
+const SLOG3_CUT_LINEAR: f32 = 0.011_25;
+const SLOG3_CUT_CODE: f32 = 171.210_3;
+
+// Panasonic V-Log
+const VLOG_CUT_LINEAR: f32 = 0.01;
+const VLOG_CUT_CODE: f32 = 0.181;
+const VLOG_B: f32 = 0.008_73;
+const VLOG_C: f32 = 0.241_514;
+const VLOG_D: f32 = 0.598_206;
+
+// ARRI LogC3 at EI 800
+const LOGC3_CUT: f32 = 0.010_591;
+const LOGC3_A: f32 = 5.555_556;
+const LOGC3_B: f32 = 0.052_272;
+const LOGC3_C: f32 = 0.247_190;
+const LOGC3_D: f32 = 0.385_537;
+const LOGC3_E: f32 = 5.367_655;
+const LOGC3_F: f32 = 0.092_809;
+
+/// Non-linear transfer curve of an encoded signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferFunction {
+    /// sRGB (IEC 61966-2-1), the default for images and SDR web video
+    #[default]
+    Srgb,
+    /// Perceptual quantizer (SMPTE ST 2084), HDR10 / Dolby Vision
+    Pq,
+    /// Hybrid log-gamma (ITU-R BT.2100), broadcast HDR; scene light, no OOTF
+    Hlg,
+    /// Sony S-Log3
+    SLog3,
+    /// Panasonic V-Log
+    VLog,
+    /// ARRI LogC3 at EI 800
+    LogC3,
+}
+
+impl TransferFunction {
+    /// Parse a curve name ("srgb", "pq", "hlg", "slog3", "vlog", "logc3").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "srgb" => Some(TransferFunction::Srgb),
+            "pq" | "st2084" => Some(TransferFunction::Pq),
+            "hlg" => Some(TransferFunction::Hlg),
+            "slog3" => Some(TransferFunction::SLog3),
+            "vlog" => Some(TransferFunction::VLog),
+            "logc3" | "logc" => Some(TransferFunction::LogC3),
+            _ => None,
+        }
+    }
+
+    /// Decode one signal value (0.0-1.0) to linear light.
+    pub fn decode(self, v: f32) -> f32 {
+        match self {
+            TransferFunction::Srgb => srgb_to_linear(v),
+            TransferFunction::Pq => {
+                let p = v.clamp(0.0, 1.0).powf(1.0 / PQ_M2);
+                let y = ((p - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * p)).powf(1.0 / PQ_M1);
+                y * PQ_PEAK / PQ_REFERENCE_WHITE
+            }
+            TransferFunction::Hlg => {
+                let v = v.max(0.0);
+                let e = if v <= 0.5 { v * v / 3.0 } else { (((v - HLG_C) / HLG_A).exp() + HLG_B) / 12.0 };
+                e / HLG_REFERENCE_WHITE
+            }
+            TransferFunction::SLog3 => {
+                let code = v * 1023.0;
+                if code >= SLOG3_CUT_CODE {
+                    10f32.powf((code - 420.0) / 261.5) * 0.19 - 0.01
+                } else {
+                    (code - 95.0) * SLOG3_CUT_LINEAR / (SLOG3_CUT_CODE - 95.0)
+                }
+            }
+            TransferFunction::VLog => {
+                if v < VLOG_CUT_CODE {
+                    (v - 0.125) / 5.6
+                } else {
+                    10f32.powf((v - VLOG_D) / VLOG_C) - VLOG_B
+                }
+            }
+            TransferFunction::LogC3 => {
+                if v > LOGC3_E * LOGC3_CUT + LOGC3_F {
+                    (10f32.powf((v - LOGC3_D) / LOGC3_C) - LOGC3_B) / LOGC3_A
+                } else {
+                    (v - LOGC3_F) / LOGC3_E
+                }
+            }
+        }
+    }
+
+    /// Encode one linear light value to the signal (0.0-1.0 for the
+    /// curve's range; PQ and HLG clip above their peak).
+    pub fn encode(self, v: f32) -> f32 {
+        match self {
+            TransferFunction::Srgb => linear_to_srgb(v),
+            TransferFunction::Pq => {
+                let y = (v * PQ_REFERENCE_WHITE / PQ_PEAK).clamp(0.0, 1.0).powf(PQ_M1);
+                ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+            }
+            TransferFunction::Hlg => {
+                let e = (v * HLG_REFERENCE_WHITE).clamp(0.0, 1.0);
+                if e <= 1.0 / 12.0 { (3.0 * e).sqrt() } else { HLG_A * (12.0 * e - HLG_B).ln() + HLG_C }
+            }
+            TransferFunction::SLog3 => {
+                if v >= SLOG3_CUT_LINEAR {
+                    (420.0 + ((v + 0.01) / 0.19).log10() * 261.5) / 1023.0
+                } else {
+                    (v * (SLOG3_CUT_CODE - 95.0) / SLOG3_CUT_LINEAR + 95.0) / 1023.0
+                }
+            }
+            TransferFunction::VLog => {
+                if v < VLOG_CUT_LINEAR {
+                    5.6 * v + 0.125
+                } else {
+                    VLOG_C * (v + VLOG_B).log10() + VLOG_D
+                }
+            }
+            TransferFunction::LogC3 => {
+                if v > LOGC3_CUT {
+                    LOGC3_C * (LOGC3_A * v + LOGC3_B).log10() + LOGC3_D
+                } else {
+                    LOGC3_E * v + LOGC3_F
+                }
+            }
+        }
+    }
+}
+
+/// Apply `f` to the color channels, leave alpha untouched.
+fn map_color_channels(input: ArrayView3<f32>, f: impl Fn(f32) -> f32) -> Array3<f32> {
+    let channels = input.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = input.to_owned();
+    for mut pixel in output.lanes_mut(ndarray::Axis(2)) {
+        for c in 0..color_channels {
+            pixel[c] = f(pixel[c]);
+        }
+    }
+    output
+}
+
+/// Decode an encoded image to linear light - f32 only.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), signal values 0.0-1.0
+/// * `transfer` - Curve the image is encoded with
+///
+/// # Returns
+/// Linear image (1.0 = reference white, HDR curves exceed 1.0), alpha preserved
+pub fn decode_transfer_f32(input: ArrayView3<f32>, transfer: TransferFunction) -> Array3<f32> {
+    map_color_channels(input, |v| transfer.decode(v))
+}
+
+/// Encode a linear image with a transfer curve - f32 only.
+///
+/// # Arguments
+/// * `input` - Linear image with 1, 3, or 4 channels (height, width, channels), 1.0 = reference white
+/// * `transfer` - Curve to encode with
+///
+/// # Returns
+/// Encoded image with signal values 0.0-1.0, alpha preserved
+pub fn encode_transfer_f32(input: ArrayView3<f32>, transfer: TransferFunction) -> Array3<f32> {
+    map_color_channels(input, |v| transfer.encode(v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [TransferFunction; 6] = [
+        TransferFunction::Srgb,
+        TransferFunction::Pq,
+        TransferFunction::Hlg,
+        TransferFunction::SLog3,
+        TransferFunction::VLog,
+        TransferFunction::LogC3,
+    ];
+
+    #[test]
+    fn test_curves_round_trip_and_are_monotonic() {
+        for curve in CURVES {
+            let mut last = f32::NEG_INFINITY;
+            for i in 0..=200 {
+                let signal = i as f32 / 200.0;
+                let linear = curve.decode(signal);
+                assert!(linear >= last, "{curve:?} not monotonic at {signal}");
+                last = linear;
+                let back = curve.encode(linear);
+                assert!((back - signal).abs() < 2e-4, "{curve:?}: {signal} -> {linear} -> {back}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_reference_points() {
+        // Published code values for 18% gray
+        assert!((TransferFunction::SLog3.encode(0.18) * 1023.0 - 420.0).abs() < 0.5);
+        assert!((TransferFunction::VLog.encode(0.18) - 0.423).abs() < 1e-3);
+        assert!((TransferFunction::LogC3.encode(0.18) - 0.391).abs() < 1e-3);
+        // Reference white: PQ 58% signal (203 nits), HLG 75% signal
+        assert!((TransferFunction::Pq.encode(1.0) - 0.58).abs() < 5e-3);
+        assert!((TransferFunction::Hlg.decode(0.75) - 1.0).abs() < 1e-4);
+        assert!((TransferFunction::Pq.decode(1.0) - 10000.0 / 203.0).abs() < 1e-2);
+        assert_eq!(TransferFunction::from_name("S-Log3"), Some(TransferFunction::SLog3));
+        assert_eq!(TransferFunction::from_name("gamma"), None);
+    }
+
+    #[test]
+    fn test_image_conversion_keeps_alpha() {
+        let image = Array3::from_shape_fn((2, 2, 4), |(y, x, c)| if c == 3 { 0.3 } else { (y * 2 + x) as f32 / 4.0 });
+        let linear = decode_transfer_f32(image.view(), TransferFunction::Pq);
+        assert!(linear.lanes(ndarray::Axis(2)).into_iter().all(|p| p[3] == 0.3));
+        let encoded = encode_transfer_f32(linear.view(), TransferFunction::Pq);
+        assert!(encoded.iter().zip(image.iter()).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+}
@@ -0,0 +1,220 @@
+/**
+ * Compositing - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - compositing.rs (Rust implementation)
+ * - compositing.py (Python wrapper)
+ *
+ * Provides: blendImages, blendImagesF32, compositeOnCheckerboard,
+ * compositeOnCheckerboardF32, compositeOnColor, compositeOnColorF32,
+ * makeAnaglyph, makeAnaglyphF32, makeSbs, makeSbsF32, onionSkin
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Blend Images
+// ============================================================================
+
+function blendImagesOptions(opts) {
+    return Object.assign(new wasm.BlendImagesOptions(), {
+        opacity: opts.opacity ?? 1.0,
+        blend_mode: opts.blendMode ?? 'normal',
+    });
+}
+
+/**
+ * Blend b over a through an optional mask and opacity (u8).
+ * @param {Object} a - Base image {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} b - Image blended on top, same size and channels as a
+ * @param {Uint8Array|null} mask - width * height: 0 = keep a, 255 = full blend
+ * @param {Object} opts - {opacity: 1.0, blendMode: 'normal'}
+ * @returns {Object} - Blended image data
+ */
+export function blendImages(a, b, mask = null, opts = {}) {
+    const { width, height } = a;
+    const channels = a.channels || 4;
+    const options = blendImagesOptions(opts);
+    const result = wasm.blend_images_wasm(
+        new Uint8Array(a.data.buffer), new Uint8Array(b.data.buffer),
+        width, height, channels, mask ?? new Uint8Array(0), options
+    );
+    options.free();
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels };
+}
+
+/**
+ * Blend b over a through an optional mask and opacity (f32).
+ * @param {Object} a - Base image {data: Float32Array, width, height, channels}
+ * @param {Object} b - Image blended on top, same size and channels as a
+ * @param {Float32Array|null} mask - width * height: 0.0 = keep a, 1.0 = full blend
+ * @param {Object} opts - {opacity: 1.0, blendMode: 'normal'}
+ * @returns {Object} - Blended image data
+ */
+export function blendImagesF32(a, b, mask = null, opts = {}) {
+    const { width, height } = a;
+    const channels = a.channels || 4;
+    const options = blendImagesOptions(opts);
+    const result = wasm.blend_images_f32_wasm(
+        a.data, b.data, width, height, channels, mask ?? new Float32Array(0), options
+    );
+    options.free();
+    return { data: new Float32Array(result.buffer), width, height, channels };
+}
+
+// ============================================================================
+// Flatten onto Backgrounds
+// ============================================================================
+
+/**
+ * Flatten an image onto a transparency checkerboard (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} opts - {cellSize: 8, colorA: [255, 255, 255], colorB: [204, 204, 204]}
+ * @returns {Object} - RGB image data {data, width, height, channels: 3}
+ */
+export function compositeOnCheckerboard(imageData, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.composite_on_checkerboard_wasm(
+        new Uint8Array(data.buffer), width, height, channels, opts.cellSize ?? 8,
+        new Uint8Array(opts.colorA ?? [255, 255, 255]), new Uint8Array(opts.colorB ?? [204, 204, 204])
+    );
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 3 };
+}
+
+/**
+ * Flatten an image onto a transparency checkerboard (f32).
+ * @param {Object} opts - {cellSize: 8, colorA: [1, 1, 1], colorB: [0.8, 0.8, 0.8]}
+ */
+export function compositeOnCheckerboardF32(imageData, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.composite_on_checkerboard_f32_wasm(
+        data, width, height, channels, opts.cellSize ?? 8,
+        new Float32Array(opts.colorA ?? [1, 1, 1]), new Float32Array(opts.colorB ?? [0.8, 0.8, 0.8])
+    );
+    return { data: new Float32Array(result.buffer), width, height, channels: 3 };
+}
+
+/**
+ * Flatten an image onto a solid color (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number[]} rgb - Background color [r, g, b]
+ * @returns {Object} - RGB image data {data, width, height, channels: 3}
+ */
+export function compositeOnColor(imageData, rgb = [255, 255, 255]) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.composite_on_color_wasm(new Uint8Array(data.buffer), width, height, channels, new Uint8Array(rgb));
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 3 };
+}
+
+/**
+ * Flatten an image onto a solid color (f32).
+ * @param {number[]} rgb - Background color [r, g, b] in 0-1
+ */
+export function compositeOnColorF32(imageData, rgb = [1, 1, 1]) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.composite_on_color_f32_wasm(data, width, height, channels, new Float32Array(rgb));
+    return { data: new Float32Array(result.buffer), width, height, channels: 3 };
+}
+
+// ============================================================================
+// Stereo 3D
+// ============================================================================
+
+/**
+ * Combine a stereo pair into a red/cyan anaglyph (u8).
+ * @param {Object} left - Left-eye view {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} right - Right-eye view, same size and channels as left
+ * @param {string} mode - 'color' (red_cyan), 'gray' or 'dubois'
+ * @returns {Object} - RGB image data {data, width, height, channels: 3}
+ */
+export function makeAnaglyph(left, right, mode = 'dubois') {
+    const { width, height } = left;
+    const channels = left.channels || 4;
+    const result = wasm.make_anaglyph_wasm(
+        new Uint8Array(left.data.buffer), new Uint8Array(right.data.buffer), width, height, channels, mode
+    );
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 3 };
+}
+
+/**
+ * Combine a stereo pair into a red/cyan anaglyph (f32).
+ */
+export function makeAnaglyphF32(left, right, mode = 'dubois') {
+    const { width, height } = left;
+    const channels = left.channels || 4;
+    const result = wasm.make_anaglyph_f32_wasm(left.data, right.data, width, height, channels, mode);
+    return { data: new Float32Array(result.buffer), width, height, channels: 3 };
+}
+
+/**
+ * Place a stereo pair side by side (u8).
+ * @param {Object} left - Left-eye view {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} right - Right-eye view, same size and channels as left
+ * @returns {Object} - Image data with width * 2
+ */
+export function makeSbs(left, right) {
+    const { width, height } = left;
+    const channels = left.channels || 4;
+    const result = wasm.make_sbs_wasm(new Uint8Array(left.data.buffer), new Uint8Array(right.data.buffer), width, height, channels);
+    return { data: new Uint8ClampedArray(result.buffer), width: width * 2, height, channels };
+}
+
+/**
+ * Place a stereo pair side by side (f32).
+ */
+export function makeSbsF32(left, right) {
+    const { width, height } = left;
+    const channels = left.channels || 4;
+    const result = wasm.make_sbs_f32_wasm(left.data, right.data, width, height, channels);
+    return { data: new Float32Array(result.buffer), width: width * 2, height, channels };
+}
+
+// ============================================================================
+// Onion Skin
+// ============================================================================
+
+/**
+ * Preview an animation frame over tinted, fading ghosts of its neighbors (u8).
+ * @param {Object} current - Current frame {data: Uint8ClampedArray, width, height, channels}
+ * @param {Array<Object>} before - Preceding frames (same size), nearest first
+ * @param {Array<Object>} after - Following frames (same size), nearest first
+ * @param {Object} opts - {tintBefore: [255, 64, 64], tintAfter: [64, 192, 64], opacityFalloff: 0.5}
+ * @returns {Object} - Composited image data
+ */
+export function onionSkin(current, before = [], after = [], opts = {}) {
+    const { width, height } = current;
+    const channels = current.channels || 4;
+    const concat = (frames) => {
+        const out = new Uint8Array(width * height * channels * frames.length);
+        frames.forEach((f, i) => out.set(new Uint8Array(f.data.buffer), i * width * height * channels));
+        return out;
+    };
+    const [br, bg, bb] = opts.tintBefore ?? [255, 64, 64];
+    const [ar, ag, ab] = opts.tintAfter ?? [64, 192, 64];
+    const options = Object.assign(new wasm.OnionSkinOptions(), {
+        tint_before_r: br, tint_before_g: bg, tint_before_b: bb,
+        tint_after_r: ar, tint_after_g: ag, tint_after_b: ab,
+        opacity_falloff: opts.opacityFalloff ?? 0.5,
+    });
+    const result = wasm.onion_skin_wasm(
+        new Uint8Array(current.data.buffer), concat(before), concat(after), width, height, channels, options
+    );
+    options.free();
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels };
+}
+
+export default {
+    initWasm,
+    blendImages, blendImagesF32,
+    compositeOnCheckerboard, compositeOnCheckerboardF32,
+    compositeOnColor, compositeOnColorF32,
+    makeAnaglyph, makeAnaglyphF32,
+    makeSbs, makeSbsF32,
+    onionSkin
+};
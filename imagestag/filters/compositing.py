@@ -0,0 +1,301 @@
+"""Compositing with Rust backend.
+
+This module blends a second image over a base image per pixel:
+- Optional (H, W) mask and global opacity control the strength
+- Blend modes shared with the overlay layer effects (normal, multiply,
+  screen, overlay, ...)
+- Flattening onto a transparency checkerboard or a solid color for display
+- Stereo pairs: red/cyan anaglyphs and side-by-side frames
+- Onion skin previews of animation frames with tinted ghosts of neighbors
+
+Typical use is applying a filtered result only partially, e.g. at 40%
+through a brush mask, without full-size arithmetic in numpy.
+
+## Supported Formats
+
+Both images must have the same shape with 1, 3, or 4 channels (uint8 or
+float32). RGBA colors are mixed premultiplied, so transparent pixels don't
+bleed their color. Flattening and anaglyphs always return RGB (H, W, 3).
+
+Co-located with:
+- compositing.rs (Rust implementation)
+- compositing.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.compositing import blend_images, composite_on_checkerboard
+
+    result = blend_images(image, sharpened, mask=brush_mask, opacity=0.4)
+    display = composite_on_checkerboard(result, cell_size=8)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+def _validate_blend_inputs(a: np.ndarray, b: np.ndarray, mask, expected_dtype: type, name: str) -> None:
+    """Validate both images and the optional mask against each other."""
+    _validate_image(a, expected_dtype, name)
+    _validate_image(b, expected_dtype, name)
+    if a.shape != b.shape:
+        raise ValueError(f"{name}: images must have the same shape, got {a.shape} and {b.shape}")
+    if mask is not None:
+        if mask.shape != a.shape[:2]:
+            raise ValueError(f"{name}: expected mask of shape {a.shape[:2]}, got {mask.shape}")
+        if mask.dtype != expected_dtype:
+            raise ValueError(f"{name}: expected {expected_dtype} mask, got {mask.dtype}")
+
+
+# ============================================================================
+# Blend Images
+# ============================================================================
+
+def blend_images(a: np.ndarray, b: np.ndarray, mask: np.ndarray = None, opacity: float = 1.0,
+                 blend_mode: str = "normal") -> np.ndarray:
+    """Blend `b` over `a` through an optional mask and opacity (u8).
+
+    Args:
+        a: Base uint8 array with 1, 3, or 4 channels (H, W, C)
+        b: uint8 array blended on top, same shape as `a`
+        mask: Optional uint8 array (H, W): 0 = keep `a`, 255 = full blend
+        opacity: Global strength (0.0-1.0)
+        blend_mode: Overlay blend mode ("normal", "multiply", "screen", ...);
+            unknown modes fall back to "normal"
+
+    Returns:
+        uint8 array with the shape of `a`
+    """
+    _validate_blend_inputs(a, b, mask, np.uint8, "blend_images")
+    return imagestag_rust.blend_images(a, b, mask, opacity, blend_mode)
+
+
+def blend_images_f32(a: np.ndarray, b: np.ndarray, mask: np.ndarray = None, opacity: float = 1.0,
+                     blend_mode: str = "normal") -> np.ndarray:
+    """Blend `b` over `a` through an optional mask and opacity (f32).
+
+    Args:
+        a: Base float32 array with 1, 3, or 4 channels (H, W, C)
+        b: float32 array blended on top, same shape as `a`
+        mask: Optional float32 array (H, W): 0.0 = keep `a`, 1.0 = full blend
+
+    Returns:
+        float32 array with the shape of `a`
+    """
+    _validate_blend_inputs(a, b, mask, np.float32, "blend_images_f32")
+    return imagestag_rust.blend_images_f32(a, b, mask, opacity, blend_mode)
+
+
+# ============================================================================
+# Flatten onto Backgrounds
+# ============================================================================
+
+def composite_on_checkerboard(image: np.ndarray, cell_size: int = 8,
+                              color_a: tuple = (255, 255, 255),
+                              color_b: tuple = (204, 204, 204)) -> np.ndarray:
+    """Flatten an image onto a transparency checkerboard (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        cell_size: Edge length of a checkerboard cell in pixels
+        color_a: Color (r, g, b) of the top-left cell
+        color_b: Color (r, g, b) of the other cells
+
+    Returns:
+        RGB uint8 array (H, W, 3)
+    """
+    _validate_image(image, np.uint8, "composite_on_checkerboard")
+    a = tuple(int(v) for v in color_a)
+    b = tuple(int(v) for v in color_b)
+    return imagestag_rust.composite_on_checkerboard(image, cell_size, a, b)
+
+
+def composite_on_checkerboard_f32(image: np.ndarray, cell_size: int = 8,
+                                  color_a: tuple = (1.0, 1.0, 1.0),
+                                  color_b: tuple = (0.8, 0.8, 0.8)) -> np.ndarray:
+    """Flatten an image onto a transparency checkerboard (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        color_a: Color (r, g, b) in 0.0-1.0 of the top-left cell
+        color_b: Color (r, g, b) in 0.0-1.0 of the other cells
+
+    Returns:
+        RGB float32 array (H, W, 3)
+    """
+    _validate_image(image, np.float32, "composite_on_checkerboard_f32")
+    a = tuple(float(v) for v in color_a)
+    b = tuple(float(v) for v in color_b)
+    return imagestag_rust.composite_on_checkerboard_f32(image, cell_size, a, b)
+
+
+def composite_on_color(image: np.ndarray, rgb: tuple = (255, 255, 255)) -> np.ndarray:
+    """Flatten an image onto a solid color (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        rgb: Background color (r, g, b)
+
+    Returns:
+        RGB uint8 array (H, W, 3)
+    """
+    _validate_image(image, np.uint8, "composite_on_color")
+    return imagestag_rust.composite_on_color(image, tuple(int(v) for v in rgb))
+
+
+def composite_on_color_f32(image: np.ndarray, rgb: tuple = (1.0, 1.0, 1.0)) -> np.ndarray:
+    """Flatten an image onto a solid color (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        rgb: Background color (r, g, b) in 0.0-1.0
+
+    Returns:
+        RGB float32 array (H, W, 3)
+    """
+    _validate_image(image, np.float32, "composite_on_color_f32")
+    return imagestag_rust.composite_on_color_f32(image, tuple(float(v) for v in rgb))
+
+
+# ============================================================================
+# Stereo 3D
+# ============================================================================
+
+def _validate_stereo_pair(left: np.ndarray, right: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate both views and that their shapes match."""
+    _validate_image(left, expected_dtype, name)
+    _validate_image(right, expected_dtype, name)
+    if left.shape != right.shape:
+        raise ValueError(f"{name}: views must have the same shape, got {left.shape} and {right.shape}")
+
+
+def make_anaglyph(left: np.ndarray, right: np.ndarray, mode: str = "dubois") -> np.ndarray:
+    """Combine a stereo pair into a red/cyan anaglyph (u8).
+
+    Args:
+        left: uint8 left-eye view with 1, 3, or 4 channels (H, W, C)
+        right: uint8 right-eye view, same shape as left
+        mode: "color" (alias "red_cyan"): red from left, green/blue from right;
+            "gray": luminance only, least ghosting;
+            "dubois": Dubois' optimized matrices, keeps color with little ghosting
+
+    Returns:
+        RGB uint8 array (H, W, 3); alpha is ignored
+    """
+    _validate_stereo_pair(left, right, np.uint8, "make_anaglyph")
+    return imagestag_rust.make_anaglyph(left, right, mode)
+
+
+def make_anaglyph_f32(left: np.ndarray, right: np.ndarray, mode: str = "dubois") -> np.ndarray:
+    """Combine a stereo pair into a red/cyan anaglyph (f32).
+
+    Args:
+        left: float32 left-eye view with 1, 3, or 4 channels (H, W, C)
+        right: float32 right-eye view, same shape as left
+        mode: "color", "gray" or "dubois"
+
+    Returns:
+        RGB float32 array (H, W, 3)
+    """
+    _validate_stereo_pair(left, right, np.float32, "make_anaglyph_f32")
+    return imagestag_rust.make_anaglyph_f32(left, right, mode)
+
+
+def make_sbs(left: np.ndarray, right: np.ndarray) -> np.ndarray:
+    """Place a stereo pair side by side, left view on the left (u8).
+
+    Args:
+        left: uint8 left-eye view with 1, 3, or 4 channels (H, W, C)
+        right: uint8 right-eye view, same shape as left
+
+    Returns:
+        uint8 array (H, 2 * W, C)
+    """
+    _validate_stereo_pair(left, right, np.uint8, "make_sbs")
+    return imagestag_rust.make_sbs(left, right)
+
+
+def make_sbs_f32(left: np.ndarray, right: np.ndarray) -> np.ndarray:
+    """Place a stereo pair side by side, left view on the left (f32).
+
+    Returns:
+        float32 array (H, 2 * W, C)
+    """
+    _validate_stereo_pair(left, right, np.float32, "make_sbs_f32")
+    return imagestag_rust.make_sbs_f32(left, right)
+
+
+# ============================================================================
+# Onion Skin
+# ============================================================================
+
+def _validate_onion_frames(frames_before: list, frames_after: list, current: np.ndarray,
+                           expected_dtype: type, name: str) -> None:
+    """Validate the current frame and that all ghosts share its shape."""
+    _validate_image(current, expected_dtype, name)
+    for frame in list(frames_before) + list(frames_after):
+        if frame.shape != current.shape or frame.dtype != expected_dtype:
+            raise ValueError(f"{name}: frames must match the current frame {current.shape} {expected_dtype}, "
+                             f"got {frame.shape} {frame.dtype}")
+
+
+def onion_skin(frames_before: list, frames_after: list, current: np.ndarray,
+               tint_before: tuple = (255, 64, 64), tint_after: tuple = (64, 192, 64),
+               opacity_falloff: float = 0.5) -> np.ndarray:
+    """Preview an animation frame over tinted, fading ghosts of its neighbors (u8).
+
+    Ghosts k frames away are drawn with opacity opacity_falloff ** k. With
+    alpha they lie under the current frame; opaque frames get the ghosts
+    multiplied on top, like a light table.
+
+    Args:
+        frames_before: Preceding uint8 frames, nearest first
+        frames_after: Following uint8 frames, nearest first
+        current: uint8 current frame with 1, 3, or 4 channels (H, W, C)
+        tint_before: Color of earlier ghosts; dark content takes the tint
+        tint_after: Color of later ghosts
+        opacity_falloff: Opacity of the nearest ghosts and factor per further frame
+
+    Returns:
+        uint8 array with the shape of current
+    """
+    _validate_onion_frames(frames_before, frames_after, current, np.uint8, "onion_skin")
+    return imagestag_rust.onion_skin(list(frames_before), list(frames_after), current,
+                                     tuple(int(v) for v in tint_before), tuple(int(v) for v in tint_after),
+                                     opacity_falloff)
+
+
+def onion_skin_f32(frames_before: list, frames_after: list, current: np.ndarray,
+                   tint_before: tuple = (1.0, 0.25, 0.25), tint_after: tuple = (0.25, 0.75, 0.25),
+                   opacity_falloff: float = 0.5) -> np.ndarray:
+    """Preview an animation frame over tinted, fading ghosts of its neighbors (f32).
+
+    Returns:
+        float32 array with the shape of current
+    """
+    _validate_onion_frames(frames_before, frames_after, current, np.float32, "onion_skin_f32")
+    return imagestag_rust.onion_skin_f32(list(frames_before), list(frames_after), current,
+                                         tuple(float(v) for v in tint_before), tuple(float(v) for v in tint_after),
+                                         opacity_falloff)
+
+
+__all__ = [
+    'blend_images',
+    'blend_images_f32',
+    'composite_on_checkerboard',
+    'composite_on_checkerboard_f32',
+    'composite_on_color',
+    'composite_on_color_f32',
+    'make_anaglyph',
+    'make_anaglyph_f32',
+    'make_sbs',
+    'make_sbs_f32',
+    'onion_skin',
+    'onion_skin_f32',
+]
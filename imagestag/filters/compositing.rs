@@ -0,0 +1,575 @@
+//! Compositing: per-pixel blending of two images.
+//!
+//! [`blend_images_f32`] mixes a second image `b` over a base image `a`
+//! through an optional mask and a global opacity, using the same blend modes
+//! as the overlay layer effects. Typical use is applying a filtered copy only
+//! partially, e.g. "sharpened result at 40% through a brush mask", without
+//! full-size arithmetic in numpy or JavaScript.
+//!
+//! [`composite_on_checkerboard_u8`] and [`composite_on_color_u8`] flatten an
+//! image with alpha onto a transparency grid or a solid color, giving
+//! display-ready RGB for canvases that can't show alpha themselves.
+//!
+//! [`make_anaglyph_u8`] and [`make_sbs_u8`] combine a stereo pair into a
+//! red/cyan anaglyph or a side-by-side frame for 3D displays.
+//!
+//! [`onion_skin_u8`] previews an animation frame over tinted, fading ghosts
+//! of its neighbors, replacing per-frame blending in the timeline UI.
+//!
+//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
+//!
+//! ## Supported Formats
+//!
+//! Blended images must have the same shape with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - blends the single channel
+//! - **RGB**: (height, width, 3) - blends each channel
+//! - **RGBA**: (height, width, 4) - blends color and alpha; colors are mixed
+//!   premultiplied so transparent pixels don't bleed their color
+//!
+//! Flattening accepts the same formats and always returns RGB (H, W, 3).
+//! Anaglyphs take two images of the same shape and return RGB; alpha is
+//! ignored, so flatten transparent images first.
+
+use ndarray::{concatenate, Array3, ArrayView2, ArrayView3, Axis};
+use rayon::prelude::*;
+
+use crate::filters::colorspace::TransferFunction;
+use crate::filters::grayscale::LumaStandard;
+use crate::layer_effects::color_overlay::blend_channel;
+
+/// Blend `b` over `a` through a mask and opacity - f32 version.
+///
+/// The mix strength per pixel is `opacity * mask`. At strength 0 the pixel
+/// of `a` is kept unchanged, at 1 it becomes `blend_mode(a, b)` (for
+/// "normal" simply `b`). Unknown blend modes fall back to "normal".
+///
+/// # Arguments
+/// * `a` - Base image with 1, 3, or 4 channels (height, width, channels)
+/// * `b` - Image blended on top, same shape as `a`
+/// * `mask` - Optional (height, width) mask, 0.0 = keep `a`, 1.0 = full blend
+/// * `opacity` - Global strength (0.0-1.0)
+/// * `blend_mode` - Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+///
+/// # Returns
+/// Blended image with the shape of `a`
+pub fn blend_images_f32(
+    a: ArrayView3<f32>,
+    b: ArrayView3<f32>,
+    mask: Option<ArrayView2<f32>>,
+    opacity: f32,
+    blend_mode: &str,
+) -> Array3<f32> {
+    let (height, width, channels) = a.dim();
+    assert_eq!(b.dim(), a.dim(), "blended images must have the same shape");
+    if let Some(m) = &mask {
+        assert_eq!(m.dim(), (height, width), "mask size must match the image");
+    }
+    let opacity = opacity.clamp(0.0, 1.0);
+    let has_alpha = channels == 4;
+    let color_channels = if has_alpha { 3 } else { channels };
+
+    let mut output = a.iter().copied().collect::<Vec<f32>>();
+    if width == 0 || channels == 0 {
+        return Array3::from_shape_vec((height, width, channels), output).unwrap();
+    }
+    output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let strength = opacity * mask.as_ref().map_or(1.0, |m| m[[y, x]].clamp(0.0, 1.0));
+            if strength <= 0.0 {
+                continue;
+            }
+            let pixel = &mut row[x * channels..(x + 1) * channels];
+            let (alpha_a, alpha_b) = if has_alpha { (a[[y, x, 3]], b[[y, x, 3]]) } else { (1.0, 1.0) };
+            let alpha = alpha_a + (alpha_b - alpha_a) * strength;
+            for c in 0..color_channels {
+                let base = a[[y, x, c]];
+                let blended = blend_channel(blend_mode, base, b[[y, x, c]]);
+                let premultiplied = base * alpha_a + (blended * alpha_b - base * alpha_a) * strength;
+                pixel[c] = if alpha > 0.0 { premultiplied / alpha } else { base };
+            }
+            if has_alpha {
+                pixel[3] = alpha;
+            }
+        }
+    });
+    Array3::from_shape_vec((height, width, channels), output).unwrap()
+}
+
+/// Blend `b` over `a` through a mask and opacity - u8 version.
+///
+/// # Arguments
+/// * `a` - Base image with 1, 3, or 4 channels (height, width, channels)
+/// * `b` - Image blended on top, same shape as `a`
+/// * `mask` - Optional (height, width) mask, 0 = keep `a`, 255 = full blend
+/// * `opacity` - Global strength (0.0-1.0)
+/// * `blend_mode` - Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+///
+/// # Returns
+/// Blended image with the shape of `a`
+pub fn blend_images_u8(
+    a: ArrayView3<u8>,
+    b: ArrayView3<u8>,
+    mask: Option<ArrayView2<u8>>,
+    opacity: f32,
+    blend_mode: &str,
+) -> Array3<u8> {
+    let a_f32 = a.mapv(|v| v as f32 / 255.0);
+    let b_f32 = b.mapv(|v| v as f32 / 255.0);
+    let mask_f32 = mask.map(|m| m.mapv(|v| v as f32 / 255.0));
+    let result = blend_images_f32(a_f32.view(), b_f32.view(), mask_f32.as_ref().map(|m| m.view()), opacity, blend_mode);
+    result.mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Flatten onto Backgrounds
+// ============================================================================
+
+/// Flatten `image` onto a per-pixel background color, giving RGB.
+fn flatten_f32(image: ArrayView3<f32>, background: impl Fn(usize, usize) -> [f32; 3] + Sync) -> Array3<f32> {
+    let (height, width, channels) = image.dim();
+    let mut output = vec![0.0f32; height * width * 3];
+    if width == 0 {
+        return Array3::from_shape_vec((height, width, 3), output).unwrap();
+    }
+    output.par_chunks_mut(width * 3).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let rgb = if channels < 3 { [image[[y, x, 0]]; 3] } else { [image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]] };
+            let alpha = if channels == 4 { image[[y, x, 3]].clamp(0.0, 1.0) } else { 1.0 };
+            let bg = background(y, x);
+            for c in 0..3 {
+                row[x * 3 + c] = rgb[c] * alpha + bg[c] * (1.0 - alpha);
+            }
+        }
+    });
+    Array3::from_shape_vec((height, width, 3), output).unwrap()
+}
+
+/// Flatten `image` onto a per-pixel background color, giving RGB (integer math).
+fn flatten_u8(image: ArrayView3<u8>, background: impl Fn(usize, usize) -> [u8; 3] + Sync) -> Array3<u8> {
+    let (height, width, channels) = image.dim();
+    let mut output = vec![0u8; height * width * 3];
+    if width == 0 {
+        return Array3::from_shape_vec((height, width, 3), output).unwrap();
+    }
+    output.par_chunks_mut(width * 3).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let rgb = if channels < 3 { [image[[y, x, 0]]; 3] } else { [image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]] };
+            let alpha = if channels == 4 { image[[y, x, 3]] as u32 } else { 255 };
+            let bg = background(y, x);
+            for c in 0..3 {
+                row[x * 3 + c] = ((rgb[c] as u32 * alpha + bg[c] as u32 * (255 - alpha) + 127) / 255) as u8;
+            }
+        }
+    });
+    Array3::from_shape_vec((height, width, 3), output).unwrap()
+}
+
+/// Color of the checkerboard cell containing pixel (y, x).
+#[inline]
+fn checker_color<T: Copy>(y: usize, x: usize, cell_size: usize, color_a: [T; 3], color_b: [T; 3]) -> [T; 3] {
+    if (y / cell_size + x / cell_size).is_multiple_of(2) {
+        color_a
+    } else {
+        color_b
+    }
+}
+
+/// Flatten an image onto a transparency checkerboard - u8 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels); only RGBA has transparency
+/// * `cell_size` - Edge length of a checkerboard cell in pixels (minimum 1)
+/// * `color_a` - Color of the top-left cell
+/// * `color_b` - Color of the other cells
+///
+/// # Returns
+/// RGB image (height, width, 3)
+pub fn composite_on_checkerboard_u8(image: ArrayView3<u8>, cell_size: usize, color_a: [u8; 3], color_b: [u8; 3]) -> Array3<u8> {
+    let cell_size = cell_size.max(1);
+    flatten_u8(image, |y, x| checker_color(y, x, cell_size, color_a, color_b))
+}
+
+/// Flatten an image onto a transparency checkerboard - f32 version.
+///
+/// Same as composite_on_checkerboard_u8 with colors in 0.0-1.0.
+pub fn composite_on_checkerboard_f32(image: ArrayView3<f32>, cell_size: usize, color_a: [f32; 3], color_b: [f32; 3]) -> Array3<f32> {
+    let cell_size = cell_size.max(1);
+    flatten_f32(image, |y, x| checker_color(y, x, cell_size, color_a, color_b))
+}
+
+/// Flatten an image onto a solid color - u8 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `rgb` - Background color
+///
+/// # Returns
+/// RGB image (height, width, 3)
+pub fn composite_on_color_u8(image: ArrayView3<u8>, rgb: [u8; 3]) -> Array3<u8> {
+    flatten_u8(image, |_, _| rgb)
+}
+
+/// Flatten an image onto a solid color - f32 version.
+///
+/// Same as composite_on_color_u8 with the color in 0.0-1.0.
+pub fn composite_on_color_f32(image: ArrayView3<f32>, rgb: [f32; 3]) -> Array3<f32> {
+    flatten_f32(image, |_, _| rgb)
+}
+
+// ============================================================================
+// Stereo 3D
+// ============================================================================
+
+/// Dubois' least-squares red/cyan matrix applied to the left view (linear RGB).
+const DUBOIS_LEFT: [[f32; 3]; 3] = [
+    [0.456, 0.500, 0.176],
+    [-0.040, -0.038, -0.016],
+    [-0.015, -0.021, -0.005],
+];
+
+/// Dubois' least-squares red/cyan matrix applied to the right view (linear RGB).
+const DUBOIS_RIGHT: [[f32; 3]; 3] = [
+    [-0.043, -0.088, -0.002],
+    [0.378, 0.734, -0.018],
+    [-0.072, -0.113, 1.226],
+];
+
+/// How a stereo pair is mixed into a red/cyan anaglyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnaglyphMode {
+    /// Red from the left view, green and blue from the right: full color,
+    /// but saturated reds and cyans cause retinal rivalry
+    Color,
+    /// Luminance of each view in its channel(s): no color, least ghosting
+    Gray,
+    /// Eric Dubois' optimized matrices (in linear light): keeps most color
+    /// with far less ghosting than plain color anaglyphs
+    #[default]
+    Dubois,
+}
+
+impl AnaglyphMode {
+    /// Parse a mode name ("color" / "red_cyan", "gray", "dubois").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "color" | "redcyan" => Some(AnaglyphMode::Color),
+            "gray" | "grey" => Some(AnaglyphMode::Gray),
+            "dubois" => Some(AnaglyphMode::Dubois),
+            _ => None,
+        }
+    }
+}
+
+/// RGB of a pixel, gray for single-channel images.
+#[inline]
+fn rgb_at(image: &ArrayView3<f32>, y: usize, x: usize) -> [f32; 3] {
+    if image.dim().2 < 3 {
+        [image[[y, x, 0]]; 3]
+    } else {
+        [image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]]
+    }
+}
+
+/// Combine a stereo pair into a red/cyan anaglyph - f32 version.
+///
+/// # Arguments
+/// * `left` - Left-eye view with 1, 3, or 4 channels (height, width, channels)
+/// * `right` - Right-eye view, same shape as `left`
+/// * `mode` - Channel mixing, see [`AnaglyphMode`]
+///
+/// # Returns
+/// RGB image (height, width, 3) for red (left) / cyan (right) glasses
+pub fn make_anaglyph_f32(left: ArrayView3<f32>, right: ArrayView3<f32>, mode: AnaglyphMode) -> Array3<f32> {
+    assert_eq!(left.dim(), right.dim(), "stereo views must have the same shape");
+    let (height, width, _) = left.dim();
+    let srgb = TransferFunction::Srgb;
+    let luma = |[r, g, b]: [f32; 3]| LumaStandard::Bt709.luma(r, g, b);
+    let apply = |m: &[[f32; 3]; 3], v: [f32; 3]| -> [f32; 3] {
+        std::array::from_fn(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+    };
+
+    let mut output = vec![0.0f32; height * width * 3];
+    output.par_chunks_mut(width * 3).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let (l, r) = (rgb_at(&left, y, x), rgb_at(&right, y, x));
+            let rgb = match mode {
+                AnaglyphMode::Color => [l[0], r[1], r[2]],
+                AnaglyphMode::Gray => {
+                    let (gl, gr) = (luma(l), luma(r));
+                    [gl, gr, gr]
+                }
+                AnaglyphMode::Dubois => {
+                    let (l, r) = (l.map(|v| srgb.decode(v)), r.map(|v| srgb.decode(v)));
+                    let (ml, mr) = (apply(&DUBOIS_LEFT, l), apply(&DUBOIS_RIGHT, r));
+                    std::array::from_fn(|c| srgb.encode((ml[c] + mr[c]).clamp(0.0, 1.0)))
+                }
+            };
+            row[x * 3..x * 3 + 3].copy_from_slice(&rgb);
+        }
+    });
+    Array3::from_shape_vec((height, width, 3), output).unwrap()
+}
+
+/// Combine a stereo pair into a red/cyan anaglyph - u8 version.
+///
+/// Same as make_anaglyph_f32 on 0-255 values.
+pub fn make_anaglyph_u8(left: ArrayView3<u8>, right: ArrayView3<u8>, mode: AnaglyphMode) -> Array3<u8> {
+    let left_f32 = left.mapv(|v| v as f32 / 255.0);
+    let right_f32 = right.mapv(|v| v as f32 / 255.0);
+    make_anaglyph_f32(left_f32.view(), right_f32.view(), mode).mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+/// Place a stereo pair side by side (left view on the left) - u8 version.
+///
+/// # Arguments
+/// * `left` - Left-eye view with 1, 3, or 4 channels (height, width, channels)
+/// * `right` - Right-eye view, same shape as `left`
+///
+/// # Returns
+/// Image (height, 2 * width, channels), for parallel viewing or 3D TVs
+pub fn make_sbs_u8(left: ArrayView3<u8>, right: ArrayView3<u8>) -> Array3<u8> {
+    assert_eq!(left.dim(), right.dim(), "stereo views must have the same shape");
+    concatenate(Axis(1), &[left.view(), right.view()]).expect("views have the same shape")
+}
+
+/// Place a stereo pair side by side - f32 version.
+pub fn make_sbs_f32(left: ArrayView3<f32>, right: ArrayView3<f32>) -> Array3<f32> {
+    assert_eq!(left.dim(), right.dim(), "stereo views must have the same shape");
+    concatenate(Axis(1), &[left.view(), right.view()]).expect("views have the same shape")
+}
+
+// ============================================================================
+// Onion Skin
+// ============================================================================
+
+/// Ghost color: dark content (line art) takes the tint, light content stays light.
+#[inline]
+fn tint_ghost(rgb: [f32; 3], tint: [f32; 3]) -> [f32; 3] {
+    let luma = LumaStandard::Bt709.luma(rgb[0], rgb[1], rgb[2]).clamp(0.0, 1.0);
+    tint.map(|t| t + luma * (1.0 - t))
+}
+
+/// Preview of an animation frame with its neighbors as tinted ghosts - f32 version.
+///
+/// Ghost `k` frames away from the current one has opacity
+/// `opacity_falloff^k`; farther ghosts are drawn first. With alpha (RGBA)
+/// the ghosts lie under the current frame, as on an animator's light table
+/// with cels. Opaque frames (1 or 3 channels) would hide everything below,
+/// so there the ghosts are multiplied onto the current frame instead.
+/// Single-channel frames are not tinted.
+///
+/// # Arguments
+/// * `frames_before` - Preceding frames, nearest first
+/// * `frames_after` - Following frames, nearest first
+/// * `current` - Current frame with 1, 3, or 4 channels (height, width, channels)
+/// * `tint_before` / `tint_after` - Ghost colors (RGB 0.0-1.0)
+/// * `opacity_falloff` - Opacity of the nearest ghosts and factor per further frame (0.0-1.0)
+///
+/// # Returns
+/// Composited preview with the shape of `current`
+pub fn onion_skin_f32<'a>(
+    frames_before: &[ArrayView3<'a, f32>],
+    frames_after: &[ArrayView3<'a, f32>],
+    current: ArrayView3<f32>,
+    tint_before: [f32; 3],
+    tint_after: [f32; 3],
+    opacity_falloff: f32,
+) -> Array3<f32> {
+    let (height, width, channels) = current.dim();
+    for frame in frames_before.iter().chain(frames_after) {
+        assert_eq!(frame.dim(), current.dim(), "onion skin frames must have the shape of the current frame");
+    }
+    let falloff = opacity_falloff.clamp(0.0, 1.0);
+    let depth = frames_before.len().max(frames_after.len());
+    let mut ghosts = Vec::new();
+    for k in (0..depth).rev() {
+        let opacity = falloff.powi(k as i32 + 1);
+        if let Some(frame) = frames_before.get(k) {
+            ghosts.push((frame, tint_before, opacity));
+        }
+        if let Some(frame) = frames_after.get(k) {
+            ghosts.push((frame, tint_after, opacity));
+        }
+    }
+    let has_alpha = channels == 4;
+    let ghost_color = |frame: &ArrayView3<f32>, tint: [f32; 3], y: usize, x: usize| {
+        if channels == 1 { [frame[[y, x, 0]]; 3] } else { tint_ghost(rgb_at(frame, y, x), tint) }
+    };
+
+    let mut output = vec![0.0f32; height * width * channels];
+    if width == 0 || channels == 0 {
+        return Array3::from_shape_vec((height, width, channels), output).unwrap();
+    }
+    output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let pixel = &mut row[x * channels..(x + 1) * channels];
+            if has_alpha {
+                // Premultiplied "over", back to front
+                let (mut color, mut alpha) = ([0.0f32; 3], 0.0f32);
+                let mut over = |rgb: [f32; 3], a: f32| {
+                    for c in 0..3 {
+                        color[c] = rgb[c] * a + color[c] * (1.0 - a);
+                    }
+                    alpha = a + alpha * (1.0 - a);
+                };
+                for (frame, tint, opacity) in &ghosts {
+                    over(ghost_color(frame, *tint, y, x), frame[[y, x, 3]].clamp(0.0, 1.0) * opacity);
+                }
+                over(rgb_at(&current, y, x), current[[y, x, 3]].clamp(0.0, 1.0));
+                for c in 0..3 {
+                    pixel[c] = if alpha > 0.0 { (color[c] / alpha).clamp(0.0, 1.0) } else { 0.0 };
+                }
+                pixel[3] = alpha;
+            } else {
+                for (c, v) in pixel.iter_mut().enumerate() {
+                    *v = current[[y, x, c]];
+                }
+                for (frame, tint, opacity) in &ghosts {
+                    let rgb = ghost_color(frame, *tint, y, x);
+                    for (c, v) in pixel.iter_mut().enumerate() {
+                        *v *= 1.0 + (rgb[c] - 1.0) * opacity;
+                    }
+                }
+            }
+        }
+    });
+    Array3::from_shape_vec((height, width, channels), output).unwrap()
+}
+
+/// Preview of an animation frame with tinted ghosts - u8 version.
+///
+/// Same as onion_skin_f32 on 0-255 values and tints.
+pub fn onion_skin_u8(
+    frames_before: &[ArrayView3<u8>],
+    frames_after: &[ArrayView3<u8>],
+    current: ArrayView3<u8>,
+    tint_before: [u8; 3],
+    tint_after: [u8; 3],
+    opacity_falloff: f32,
+) -> Array3<u8> {
+    let to_f32 = |frames: &[ArrayView3<u8>]| -> Vec<Array3<f32>> {
+        frames.iter().map(|f| f.mapv(|v| v as f32 / 255.0)).collect()
+    };
+    let (before, after) = (to_f32(frames_before), to_f32(frames_after));
+    let before: Vec<ArrayView3<f32>> = before.iter().map(|f| f.view()).collect();
+    let after: Vec<ArrayView3<f32>> = after.iter().map(|f| f.view()).collect();
+    let current_f32 = current.mapv(|v| v as f32 / 255.0);
+    let result = onion_skin_f32(
+        &before,
+        &after,
+        current_f32.view(),
+        tint_before.map(|v| v as f32 / 255.0),
+        tint_after.map(|v| v as f32 / 255.0),
+        opacity_falloff,
+    );
+    result.mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array2;
+
+    #[test]
+    fn test_mask_and_opacity_mix() {
+        let a = Array3::from_elem((2, 2, 3), 0.2f32);
+        let b = Array3::from_elem((2, 2, 3), 0.8f32);
+        let mask = Array2::from_shape_vec((2, 2), vec![0.0, 1.0, 0.5, 1.0]).unwrap();
+        let result = blend_images_f32(a.view(), b.view(), Some(mask.view()), 0.4, "normal");
+        assert_eq!(result[[0, 0, 0]], 0.2);
+        assert!((result[[0, 1, 0]] - 0.44).abs() < 1e-6);
+        assert!((result[[1, 0, 1]] - 0.32).abs() < 1e-6);
+
+        // Blend modes apply before the mix: multiply at full strength
+        let multiply = blend_images_f32(a.view(), b.view(), None, 1.0, "multiply");
+        assert!(multiply.iter().all(|&v| (v - 0.16).abs() < 1e-6));
+
+        let a_u8 = Array3::from_elem((2, 2, 1), 0u8);
+        let b_u8 = Array3::from_elem((2, 2, 1), 200u8);
+        let mask_u8 = Array2::from_elem((2, 2), 255u8);
+        let result = blend_images_u8(a_u8.view(), b_u8.view(), Some(mask_u8.view()), 0.5, "normal");
+        assert!(result.iter().all(|&v| v == 100));
+    }
+
+    #[test]
+    fn test_alpha_is_mixed_premultiplied() {
+        // Opaque red base, fully transparent green on top
+        let mut a = Array3::<f32>::zeros((1, 1, 4));
+        a[[0, 0, 0]] = 1.0;
+        a[[0, 0, 3]] = 1.0;
+        let mut b = Array3::<f32>::zeros((1, 1, 4));
+        b[[0, 0, 1]] = 1.0;
+        let result = blend_images_f32(a.view(), b.view(), None, 0.5, "normal");
+        // Alpha halves, but the invisible green must not tint the color
+        assert_eq!(result[[0, 0, 3]], 0.5);
+        assert_eq!(result[[0, 0, 0]], 1.0);
+        assert_eq!(result[[0, 0, 1]], 0.0);
+    }
+
+    #[test]
+    fn test_flatten_onto_checkerboard_and_color() {
+        // Left half opaque red, right half fully transparent
+        let image = Array3::from_shape_fn((4, 4, 4), |(_, x, c)| match c {
+            0 => 255,
+            3 if x < 2 => 255,
+            _ => 0,
+        });
+        let board = composite_on_checkerboard_u8(image.view(), 1, [255, 255, 255], [204, 204, 204]);
+        assert_eq!(board.dim(), (4, 4, 3));
+        assert_eq!(board.slice(ndarray::s![0, 0, ..]).to_vec(), vec![255, 0, 0]);
+        assert_eq!(board.slice(ndarray::s![0, 2, ..]).to_vec(), vec![255, 255, 255]);
+        assert_eq!(board.slice(ndarray::s![0, 3, ..]).to_vec(), vec![204, 204, 204]);
+        assert_eq!(board.slice(ndarray::s![1, 3, ..]).to_vec(), vec![255, 255, 255]);
+
+        let half = Array3::from_shape_fn((1, 1, 4), |(_, _, c)| if c == 3 { 0.5 } else { 1.0f32 });
+        let flat = composite_on_color_f32(half.view(), [0.0, 0.0, 0.0]);
+        assert!(flat.iter().all(|&v| (v - 0.5).abs() < 1e-6));
+        let gray = Array3::from_elem((2, 2, 1), 77u8);
+        assert!(composite_on_color_u8(gray.view(), [0, 0, 0]).iter().all(|&v| v == 77));
+    }
+
+    #[test]
+    fn test_stereo_anaglyph_and_side_by_side() {
+        let left = Array3::from_shape_fn((2, 3, 3), |(_, _, c)| [200u8, 40, 40][c]);
+        let right = Array3::from_shape_fn((2, 3, 3), |(_, _, c)| [20u8, 150, 220][c]);
+
+        let color = make_anaglyph_u8(left.view(), right.view(), AnaglyphMode::Color);
+        assert_eq!(color.slice(ndarray::s![1, 2, ..]).to_vec(), vec![200, 150, 220]);
+
+        // Dubois: the red channel comes (almost) only from the left view
+        let dubois = make_anaglyph_u8(left.view(), right.view(), AnaglyphMode::Dubois);
+        let black = Array3::<u8>::zeros((2, 3, 3));
+        let left_only = make_anaglyph_u8(left.view(), black.view(), AnaglyphMode::Dubois);
+        assert!(dubois[[0, 0, 0]].abs_diff(left_only[[0, 0, 0]]) <= 8);
+        assert!(left_only[[0, 0, 1]] == 0 && left_only[[0, 0, 2]] == 0);
+
+        let sbs = make_sbs_u8(left.view(), right.view());
+        assert_eq!(sbs.dim(), (2, 6, 3));
+        assert_eq!(sbs[[0, 2, 0]], 200);
+        assert_eq!(sbs[[0, 3, 0]], 20);
+    }
+
+    #[test]
+    fn test_onion_skin_ghosts_fade_with_distance() {
+        // Cels: a black stroke at column `x` on a transparent frame
+        let cel = |stroke: usize| Array3::from_shape_fn((1, 4, 4), |(_, x, c)| if x == stroke && c == 3 { 1.0f32 } else { 0.0 });
+        let (prev2, prev1, current, next1) = (cel(0), cel(1), cel(2), cel(3));
+        let red = [1.0, 0.0, 0.0];
+        let green = [0.0, 1.0, 0.0];
+        let result = onion_skin_f32(&[prev1.view(), prev2.view()], &[next1.view()], current.view(), red, green, 0.5);
+
+        assert_eq!(result[[0, 2, 3]], 1.0);
+        assert_eq!(result.slice(ndarray::s![0, 2, ..3]).to_vec(), vec![0.0, 0.0, 0.0]);
+        assert_eq!(result.slice(ndarray::s![0, 1, ..]).to_vec(), vec![1.0, 0.0, 0.0, 0.5]);
+        assert_eq!(result.slice(ndarray::s![0, 3, ..]).to_vec(), vec![0.0, 1.0, 0.0, 0.5]);
+        assert_eq!(result[[0, 0, 3]], 0.25);
+
+        // Opaque frames: ghosts multiply onto the current frame
+        let white = Array3::from_elem((1, 2, 3), 255u8);
+        let mut dark = white.clone();
+        dark.slice_mut(ndarray::s![0, 0, ..]).fill(0);
+        let result = onion_skin_u8(&[dark.view()], &[], white.view(), [255, 0, 0], [0, 255, 0], 0.5);
+        assert_eq!(result.slice(ndarray::s![0, 0, ..]).to_vec(), vec![255, 128, 128]);
+        assert_eq!(result.slice(ndarray::s![0, 1, ..]).to_vec(), vec![255, 255, 255]);
+    }
+}
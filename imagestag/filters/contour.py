@@ -218,6 +218,8 @@ def extract_contours(
     simplify_epsilon: float = 0.0,
     fit_beziers: bool = False,
     bezier_smoothness: float = 0.25,
+    downsample: int = 1,
+    tolerance: float = 0.25,
 ) -> list[Contour]:
     """
     Extract contours from an alpha mask using Marching Squares.
@@ -234,6 +236,10 @@ def extract_contours(
         fit_beziers: Whether to fit cubic Bezier curves to the simplified polyline.
         bezier_smoothness: Smoothness factor for Bezier fitting (0.1-0.5).
             Higher values produce smoother curves.
+        downsample: Trace at 1/downsample resolution and refine the points
+            against the full mask. Speeds up huge masks; features smaller
+            than about `downsample` pixels may be lost. 1 = off.
+        tolerance: Largest refinement error in pixels when downsampling.
 
     Returns:
         List of Contour objects, each containing:
@@ -272,6 +278,8 @@ def extract_contours(
         simplify_epsilon=simplify_epsilon,
         fit_beziers=fit_beziers,
         bezier_smoothness=bezier_smoothness,
+        downsample=downsample,
+        tolerance=tolerance,
     )
 
     # Convert to Python objects
@@ -385,6 +393,154 @@ def extract_contours_to_svg(
     )
 
 
+def trace_image_to_svg(
+    image: np.ndarray,
+    max_colors: int = 8,
+    simplify_epsilon: float = 1.0,
+    fit_beziers: bool = True,
+) -> str:
+    """
+    Trace a whole image into a multi-color SVG document.
+
+    The image is reduced to max_colors with k-means, every color becomes a
+    layer traced with marching squares, and the layers are stacked from the
+    most to the least frequent color. Pixels with alpha below 128 stay
+    uncovered.
+
+    Args:
+        image: uint8 image (H, W), (H, W, 3) or (H, W, 4).
+        max_colors: Maximum number of color layers (1-255).
+        simplify_epsilon: Douglas-Peucker simplification epsilon.
+        fit_beziers: Whether to fit Bezier curves.
+
+    Returns:
+        Complete SVG document as a string.
+
+    Example:
+        >>> svg = trace_image_to_svg(logo, max_colors=4)
+    """
+    import imagestag_rust
+    if image.dtype != np.uint8:
+        raise ValueError(f"Expected uint8 dtype, got {image.dtype}")
+    if image.ndim == 2:
+        image = image[:, :, np.newaxis]
+    return imagestag_rust.trace_image_to_svg(
+        np.ascontiguousarray(image), max_colors, simplify_epsilon, fit_beziers
+    )
+
+
+def fill_contours(
+    contours: list[Contour],
+    width: int,
+    height: int,
+    fill_rule: str = "nonzero",
+) -> np.ndarray:
+    """
+    Rasterize contours (including holes) back into a mask.
+
+    Every contour is treated as closed. Pixel (x, y) is inside when the
+    point (x, y) is, the coordinate convention of extract_contours, so
+    extracted contours fill back to the mask they came from.
+
+    Args:
+        contours: Outlines and holes; Bezier segments are ignored, the
+            polyline points are used.
+        width: Mask width.
+        height: Mask height.
+        fill_rule: "nonzero" (holes need opposite winding, the SVG default)
+            or "evenodd" (nested contours alternate).
+
+    Returns:
+        uint8 mask (H, W), 255 inside.
+
+    Example:
+        >>> contours = extract_contours(mask)
+        >>> restored = fill_contours(contours, 512, 512, fill_rule="evenodd")
+    """
+    import imagestag_rust
+    points = [[p.to_tuple() for p in c.points] for c in contours]
+    flat = imagestag_rust.fill_contours(points, width, height, fill_rule)
+    return np.array(flat, dtype=np.uint8).reshape(height, width)
+
+
+def path_boolean(
+    subject: list[Contour],
+    clip: list[Contour],
+    op: str = "union",
+    fill_rule: str = "nonzero",
+    tolerance: float = 0.1,
+) -> list[Contour]:
+    """
+    Combine two sets of closed contours without rasterizing them.
+
+    Use this to merge vector selections or shape layers exactly; going
+    through masks and re-tracing would lose precision.
+
+    Args:
+        subject: Outlines and holes of the first operand. Contours with
+            Bezier segments use the curves, the others their points.
+        clip: Outlines and holes of the second operand.
+        op: "union", "intersect" or "subtract" (subject minus clip).
+        fill_rule: "nonzero" or "evenodd", applied to each operand.
+        tolerance: Largest distance in pixels between a curve and its
+            flattened polyline.
+
+    Returns:
+        Closed polyline contours; outer boundaries clockwise on screen,
+        holes counter-clockwise.
+
+    Example:
+        >>> merged = path_boolean(selection, extract_contours(mask), op="union")
+    """
+    import imagestag_rust
+
+    def to_raw(contour: Contour):
+        beziers = [b.to_tuple() for b in contour.beziers] if contour.beziers else None
+        return [p.to_tuple() for p in contour.points], beziers
+
+    raw = imagestag_rust.path_boolean(
+        [to_raw(c) for c in subject], [to_raw(c) for c in clip], op, fill_rule, tolerance
+    )
+    return [Contour(points=[Point(x=x, y=y) for x, y in points], is_closed=True) for points in raw]
+
+
+def stroke_path_outline(
+    path: Contour,
+    width: float,
+    cap: str = "butt",
+    join: str = "miter",
+    miter_limit: float = 4.0,
+) -> list[Contour]:
+    """
+    Convert a stroked path into the filled outline of its stroke.
+
+    The result can be edited and exported like any other filled shape, so
+    stroked selections export to SVG exactly instead of as a stroke style.
+
+    Args:
+        path: Polyline or Bezier path; is_closed decides between joins and
+            caps at its ends.
+        width: Stroke width in pixels.
+        cap: "butt", "round" or "square".
+        join: "miter", "round" or "bevel".
+        miter_limit: Largest ratio of miter length to stroke width before
+            a miter is beveled (as in SVG).
+
+    Returns:
+        Closed outline contours; a closed path gives an outline and a hole.
+
+    Example:
+        >>> outline = stroke_path_outline(contour, 4.0, join="round")
+        >>> svg = contours_to_svg(outline, 512, 512)
+    """
+    import imagestag_rust
+    beziers = [b.to_tuple() for b in path.beziers] if path.beziers else None
+    raw = imagestag_rust.stroke_path_outline(
+        ([p.to_tuple() for p in path.points], beziers), width, cap, join, miter_limit, path.is_closed
+    )
+    return [Contour(points=[Point(x=x, y=y) for x, y in points], is_closed=True) for points in raw]
+
+
 def _normalize_mask(mask: np.ndarray) -> np.ndarray:
     """Normalize mask to 2D uint8 array (H, W) with values 0-255."""
     if mask.ndim == 3:
@@ -493,3 +649,40 @@ def douglas_peucker_closed(
     """
     import imagestag_rust
     return imagestag_rust.douglas_peucker_closed(points, epsilon)
+
+
+# =============================================================================
+# Marching Ants
+# =============================================================================
+
+def contour_to_dash_segments(
+    contour: Contour,
+    dash_length: float = 4.0,
+    phase: float = 0.0,
+) -> list[list[tuple[float, float]]]:
+    """
+    Split a contour into the dash polylines of a marching ants outline.
+
+    The pattern alternates dash_length drawn and dash_length skipped along
+    the contour. Advancing phase moves the dashes forward, so an animation
+    only needs new segments per phase step instead of dashing every frame.
+
+    Args:
+        contour: Contour to dash; Bezier segments are ignored, the
+            polyline points are used.
+        dash_length: Length of each dash and gap in pixels (<= 0 returns
+            the whole contour as one solid polyline).
+        phase: Pattern offset in pixels; repeats every 2 * dash_length.
+
+    Returns:
+        List of dashes, each a list of (x, y) tuples.
+
+    Example:
+        >>> contours = extract_contours(mask)
+        >>> dashes = contour_to_dash_segments(contours[0], 4.0, phase=frame % 8)
+    """
+    import imagestag_rust
+    points = [p.to_tuple() for p in contour.points]
+    return imagestag_rust.contour_to_dash_segments(
+        points, dash_length, phase, contour.is_closed
+    )
@@ -3,11 +3,16 @@
 //! This module provides shared functionality used by multiple filters:
 //! - Gaussian kernel generation
 //! - Distance field computation
+//! - Integral images (summed-area tables) and box filtering
 //! - Color blending utilities
 //! - Coordinate transformation helpers
 
-use ndarray::{Array1, Array2, Array3, ArrayView3, Axis};
-use rayon::prelude::*;
+use ndarray::{Array2, Array3, ArrayView3};
+
+use super::blur::{recursive_gaussian_in_place, RECURSIVE_MIN_SIGMA};
+use super::blur_wasm::{box_passes_in_place, box_radii_for_gaussian, BlurQuality};
+use super::fmath;
+use super::pool::{self, Pooled};
 
 /// Generate a 1D Gaussian kernel.
 ///
@@ -28,7 +33,7 @@ pub fn gaussian_kernel_1d(sigma: f32) -> Vec<f32> {
     let mut kernel: Vec<f32> = (0..kernel_size)
         .map(|i| {
             let x = i as f32 - half as f32;
-            (-x * x / (2.0 * sigma * sigma)).exp()
+            fmath::exp(-x * x / (2.0 * sigma * sigma))
         })
         .collect();
 
@@ -113,13 +118,26 @@ pub fn blur_alpha_u8(alpha: &Array2<u8>, sigma: f32) -> Array2<u8> {
 }
 
 /// Apply separable 1D Gaussian blur to f32 alpha channel.
+///
+/// Large sigmas (glows and shadows with big radii) use the recursive
+/// Gaussian of [`blur`](super::blur), whose cost does not grow with sigma.
+///
+/// Scratch and result storage come from [`pool`](super::pool); callers that
+/// drop the result right away can wrap it in [`pool::Pooled`](super::pool::Pooled).
 pub fn blur_alpha_f32(alpha: &Array2<f32>, sigma: f32) -> Array2<f32> {
     let (height, width) = (alpha.shape()[0], alpha.shape()[1]);
+    if sigma >= RECURSIVE_MIN_SIGMA {
+        let mut result = pool::copy2(alpha.view());
+        let buffer = result.as_slice_mut().expect("pooled buffer in standard layout");
+        recursive_gaussian_in_place(buffer, width, height, 1, sigma);
+        result.mapv_inplace(|v| v.clamp(0.0, 1.0));
+        return result;
+    }
     let kernel = gaussian_kernel_1d(sigma);
     let half = kernel.len() / 2;
 
-    let mut temp = Array2::<f32>::zeros((height, width));
-    let mut result = Array2::<f32>::zeros((height, width));
+    let mut temp = Pooled::new(pool::take2((height, width)));
+    let mut result = pool::take2((height, width));
 
     // Horizontal pass
     for y in 0..height {
@@ -143,11 +161,27 @@ pub fn blur_alpha_f32(alpha: &Array2<f32>, sigma: f32) -> Array2<f32> {
                     .clamp(0, height as isize - 1) as usize;
                 sum += temp[[sy, x]] * kv;
             }
-            result[[y, x]] = sum;
+            result[[y, x]] = sum.clamp(0.0, 1.0);
         }
     }
 
-    result.mapv(|v| v.clamp(0.0, 1.0))
+    result
+}
+
+/// Blur an f32 alpha channel at the given quality.
+///
+/// `Fast` runs three box passes instead of the Gaussian kernel; layer effects
+/// use it for interactive previews. Storage comes from [`pool`](super::pool).
+pub fn blur_alpha_quality_f32(alpha: &Array2<f32>, sigma: f32, quality: BlurQuality) -> Array2<f32> {
+    if quality == BlurQuality::Exact || sigma <= 0.0 {
+        return blur_alpha_f32(alpha, sigma);
+    }
+    let (height, width) = alpha.dim();
+    let mut result = pool::copy2(alpha.view());
+    let buffer = result.as_slice_mut().expect("pooled buffer in standard layout");
+    box_passes_in_place(buffer, width, height, 1, &box_radii_for_gaussian(sigma, 3));
+    result.mapv_inplace(|v| v.clamp(0.0, 1.0));
+    result
 }
 
 /// Compute signed distance field from alpha channel.
@@ -392,6 +426,36 @@ pub fn alpha_f32_to_u8(alpha: &Array2<f32>) -> Array2<u8> {
     alpha.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8)
 }
 
+/// Convert a u8 image to f32 (0-255 -> 0.0-1.0).
+pub fn image_u8_to_f32(image: ArrayView3<u8>) -> Array3<f32> {
+    image.mapv(|v| v as f32 / 255.0)
+}
+
+/// Convert an f32 image to u8 (0.0-1.0 -> 0-255, truncating like [`alpha_f32_to_u8`]).
+pub fn image_f32_to_u8(image: &Array3<f32>) -> Array3<u8> {
+    image.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8)
+}
+
+/// Bilinear sample of an alpha channel at continuous coordinates.
+///
+/// Pixel centers lie at (x + 0.5, y + 0.5); samples outside the mask are 0.
+pub fn sample_alpha_bilinear(alpha: &Array2<f32>, x: f32, y: f32) -> f32 {
+    let (height, width) = alpha.dim();
+    let (fx, fy) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (fx.floor(), fy.floor());
+    let (tx, ty) = (fx - x0, fy - y0);
+    let at = |xi: f32, yi: f32| {
+        if xi < 0.0 || yi < 0.0 || xi >= width as f32 || yi >= height as f32 {
+            0.0
+        } else {
+            alpha[[yi as usize, xi as usize]]
+        }
+    };
+    let top = at(x0, y0) * (1.0 - tx) + at(x0 + 1.0, y0) * tx;
+    let bottom = at(x0, y0 + 1.0) * (1.0 - tx) + at(x0 + 1.0, y0 + 1.0) * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
 /// Expand canvas by adding padding around the image.
 ///
 /// # Arguments
@@ -418,13 +482,18 @@ pub fn expand_canvas_u8(image: &Array3<u8>, expand: usize) -> Array3<u8> {
     result
 }
 
-/// Expand canvas for f32 images.
+/// Expand canvas for f32 images (storage from [`pool`](super::pool)).
 pub fn expand_canvas_f32(image: &Array3<f32>, expand: usize) -> Array3<f32> {
-    let (h, w, c) = (image.shape()[0], image.shape()[1], image.shape()[2]);
+    expand_canvas_view_f32(image.view(), expand)
+}
+
+/// [`expand_canvas_f32`] for a borrowed image, saving the caller a copy.
+pub fn expand_canvas_view_f32(image: ArrayView3<f32>, expand: usize) -> Array3<f32> {
+    let (h, w, c) = image.dim();
     let new_h = h + expand * 2;
     let new_w = w + expand * 2;
 
-    let mut result = Array3::<f32>::zeros((new_h, new_w, c));
+    let mut result = pool::take3((new_h, new_w, c));
 
     for y in 0..h {
         for x in 0..w {
@@ -436,3 +505,448 @@ pub fn expand_canvas_f32(image: &Array3<f32>, expand: usize) -> Array3<f32> {
 
     result
 }
+
+/// Where the layer sits inside an explicit output canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanvasAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl CanvasAnchor {
+    /// Parse an anchor name ("top_left", "top", ..., "center", ...,
+    /// "bottom_right"). Unknown names map to center.
+    pub fn parse(name: &str) -> Self {
+        match name.to_lowercase().replace('-', "_").as_str() {
+            "top_left" => CanvasAnchor::TopLeft,
+            "top" => CanvasAnchor::Top,
+            "top_right" => CanvasAnchor::TopRight,
+            "left" => CanvasAnchor::Left,
+            "right" => CanvasAnchor::Right,
+            "bottom_left" => CanvasAnchor::BottomLeft,
+            "bottom" => CanvasAnchor::Bottom,
+            "bottom_right" => CanvasAnchor::BottomRight,
+            _ => CanvasAnchor::Center,
+        }
+    }
+
+    /// Horizontal and vertical alignment: 0 = start, 1 = center, 2 = end.
+    fn alignment(self) -> (usize, usize) {
+        match self {
+            CanvasAnchor::TopLeft => (0, 0),
+            CanvasAnchor::Top => (1, 0),
+            CanvasAnchor::TopRight => (2, 0),
+            CanvasAnchor::Left => (0, 1),
+            CanvasAnchor::Center => (1, 1),
+            CanvasAnchor::Right => (2, 1),
+            CanvasAnchor::BottomLeft => (0, 2),
+            CanvasAnchor::Bottom => (1, 2),
+            CanvasAnchor::BottomRight => (2, 2),
+        }
+    }
+}
+
+/// Output canvas of effects that reach beyond the layer (drop shadow, outer
+/// glow, stroke, layer styles).
+///
+/// Effects always render on a canvas grown by their expansion; the policy
+/// decides what part of it is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanvasPolicy {
+    /// Return the whole grown canvas (the layer starts at
+    /// (expansion, expansion))
+    #[default]
+    Grow,
+    /// Crop back to the input size and position; overflow is cut off
+    ClipToInput,
+    /// Canvas of `width` x `height` with the layer placed at `anchor`.
+    /// Overflow beyond the canvas is cut off, missing area is transparent.
+    Explicit { width: usize, height: usize, anchor: CanvasAnchor },
+}
+
+impl CanvasPolicy {
+    /// Parse a policy name ("grow", "clip"), or an explicit canvas when
+    /// `size` (width, height) is given. Unknown names map to grow.
+    pub fn parse(name: &str, size: Option<(usize, usize)>, anchor: &str) -> Self {
+        if let Some((width, height)) = size {
+            return CanvasPolicy::Explicit { width, height, anchor: CanvasAnchor::parse(anchor) };
+        }
+        match name {
+            "clip" | "clip_to_input" => CanvasPolicy::ClipToInput,
+            _ => CanvasPolicy::Grow,
+        }
+    }
+
+    /// Output (height, width) for a layer of `input` (height, width) grown
+    /// by `expand` on each side.
+    pub fn output_size(&self, input: (usize, usize), expand: usize) -> (usize, usize) {
+        match *self {
+            CanvasPolicy::Grow => (input.0 + 2 * expand, input.1 + 2 * expand),
+            CanvasPolicy::ClipToInput => input,
+            CanvasPolicy::Explicit { width, height, .. } => (height, width),
+        }
+    }
+
+    /// Position (x, y) of the layer's top-left corner in the output canvas.
+    /// Negative when the layer starts left of or above the canvas.
+    pub fn layer_origin(&self, input: (usize, usize), expand: usize) -> (isize, isize) {
+        match *self {
+            CanvasPolicy::Grow => (expand as isize, expand as isize),
+            CanvasPolicy::ClipToInput => (0, 0),
+            CanvasPolicy::Explicit { width, height, anchor } => {
+                let (ax, ay) = anchor.alignment();
+                let place = |canvas: usize, layer: usize, align: usize| (canvas as isize - layer as isize) * align as isize / 2;
+                (place(width, input.1, ax), place(height, input.0, ay))
+            }
+        }
+    }
+
+    /// Cut a canvas rendered with `expand` pixels on each side of a layer of
+    /// `input` (height, width) down to this policy's canvas.
+    pub fn apply<T: Copy + Default>(&self, rendered: Array3<T>, input: (usize, usize), expand: usize) -> Array3<T> {
+        if *self == CanvasPolicy::Grow {
+            return rendered;
+        }
+        let (src_h, src_w, channels) = rendered.dim();
+        let (out_h, out_w) = self.output_size(input, expand);
+        let (ox, oy) = self.layer_origin(input, expand);
+        // Rendered pixel (y, x) lands at (y + dy, x + dx)
+        let (dx, dy) = (ox - expand as isize, oy - expand as isize);
+        let mut result = Array3::<T>::from_elem((out_h, out_w, channels), T::default());
+        let range = |shift: isize, src: usize, out: usize| (shift.max(0) as usize).min(out)..((src as isize + shift).clamp(0, out as isize) as usize);
+        for y in range(dy, src_h, out_h) {
+            let sy = (y as isize - dy) as usize;
+            for x in range(dx, src_w, out_w) {
+                let sx = (x as isize - dx) as usize;
+                for c in 0..channels {
+                    result[[y, x, c]] = rendered[[sy, sx, c]];
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Squared distance value used for "no feature pixel found" in distance transforms.
+const EDT_INF: f64 = 1.0e20;
+
+/// One-dimensional squared Euclidean distance transform (Felzenszwalb & Huttenlocher).
+///
+/// `f` holds 0.0 at feature positions and `EDT_INF` elsewhere; the result is written
+/// to `out`. `v` and `z` are scratch buffers of length `n` and `n + 1`.
+fn edt_1d(f: &[f64], out: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+
+    let mut k = 0usize;
+    v[0] = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+
+    for q in 1..n {
+        let qf = q as f64;
+        let mut s;
+        loop {
+            let pf = v[k] as f64;
+            s = ((f[q] + qf * qf) - (f[v[k]] + pf * pf)) / (2.0 * qf - 2.0 * pf);
+            if s <= z[k] {
+                k -= 1;
+            } else {
+                break;
+            }
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+
+    k = 0;
+    for (q, o) in out.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let d = q as f64 - v[k] as f64;
+        *o = d * d + f[v[k]];
+    }
+}
+
+/// Exact Euclidean distance transform of a binary mask.
+///
+/// For every pixel where `mask` is true, computes the distance (in pixels) to the
+/// nearest pixel where `mask` is false. Pixels outside the mask get 0.0. If the
+/// mask contains no false pixels, distances saturate at a very large value.
+///
+/// Runs in linear time regardless of the distances involved, which makes it
+/// suitable for large bevel sizes, strokes and feathering.
+///
+/// # Arguments
+/// * `mask` - Binary mask (true = foreground)
+///
+/// # Returns
+/// Distance to the nearest background pixel as f32 array
+pub fn euclidean_distance_transform(mask: &Array2<bool>) -> Array2<f32> {
+    let (height, width) = mask.dim();
+    let mut dist = mask.mapv(|m| if m { EDT_INF } else { 0.0 });
+    let n = height.max(width);
+    let mut f = vec![0.0f64; n];
+    let mut out = vec![0.0f64; n];
+    let mut v = vec![0usize; n];
+    let mut z = vec![0.0f64; n + 1];
+
+    // Columns
+    for x in 0..width {
+        let mut column = dist.column_mut(x);
+        for (fv, &d) in f.iter_mut().zip(column.iter()) {
+            *fv = d;
+        }
+        edt_1d(&f[..height], &mut out[..height], &mut v, &mut z);
+        for (d, &o) in column.iter_mut().zip(out.iter()) {
+            *d = o;
+        }
+    }
+
+    // Rows
+    for y in 0..height {
+        let mut row = dist.row_mut(y);
+        for (fv, &d) in f.iter_mut().zip(row.iter()) {
+            *fv = d;
+        }
+        edt_1d(&f[..width], &mut out[..width], &mut v, &mut z);
+        for (d, &o) in row.iter_mut().zip(out.iter()) {
+            *d = o;
+        }
+    }
+
+    dist.mapv(|d| d.min(EDT_INF).sqrt() as f32)
+}
+
+/// Compute an anti-aliased signed distance from an alpha channel.
+///
+/// Positive values are inside the shape (alpha > 0.5), negative outside.
+/// Pixels on the edge use their fractional alpha so the zero crossing lies
+/// at sub-pixel precision instead of on the pixel grid.
+///
+/// Unlike [`compute_sdf`], this uses an exact linear-time distance transform,
+/// so there is no maximum distance.
+pub fn signed_distance_alpha(alpha: &Array2<f32>) -> Array2<f32> {
+    let inside = alpha.mapv(|a| a > 0.5);
+    let outside = inside.mapv(|m| !m);
+    let d_in = euclidean_distance_transform(&inside);
+    let d_out = euclidean_distance_transform(&outside);
+
+    let mut sdf = Array2::<f32>::zeros(alpha.dim());
+    for ((y, x), v) in sdf.indexed_iter_mut() {
+        let signed = if inside[[y, x]] {
+            d_in[[y, x]] - 0.5
+        } else {
+            0.5 - d_out[[y, x]]
+        };
+        // Edge pixels: refine with coverage
+        *v = if signed.abs() < 1.0 {
+            alpha[[y, x]] - 0.5
+        } else {
+            signed
+        };
+    }
+    sdf
+}
+
+// ============================================================================
+// Integral Images
+// ============================================================================
+
+/// Compute the integral image (summed-area table) of every channel.
+///
+/// The table has a zero row and column in front: entry `[y, x, c]` holds
+/// the sum of all pixels above and left of `(y, x)`, exclusive. The sum of
+/// any rectangle then takes four lookups (see [`integral_window_sum`]).
+/// Sums are kept in f64 so large images don't lose precision.
+///
+/// # Arguments
+/// * `image` - Image (height, width, channels), u8 or f32
+///
+/// # Returns
+/// Table (height + 1, width + 1, channels) in the value range of the input
+pub fn integral_image<T: Copy + Into<f64>>(image: ArrayView3<T>) -> Array3<f64> {
+    let (height, width, channels) = image.dim();
+    let mut table = Array3::<f64>::zeros((height + 1, width + 1, channels));
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..channels {
+                table[[y + 1, x + 1, c]] =
+                    image[[y, x, c]].into() + table[[y, x + 1, c]] + table[[y + 1, x, c]] - table[[y, x, c]];
+            }
+        }
+    }
+    table
+}
+
+/// Sum of channel `c` over rows `y0..y1` and columns `x0..x1` (exclusive ends).
+#[inline]
+pub fn integral_window_sum(table: &Array3<f64>, y0: usize, x0: usize, y1: usize, x1: usize, c: usize) -> f64 {
+    table[[y1, x1, c]] - table[[y0, x1, c]] - table[[y1, x0, c]] + table[[y0, x0, c]]
+}
+
+/// Mean of the (2 * radius + 1)^2 window around every pixel - f32 version.
+///
+/// Cost per pixel is constant regardless of the radius. The window shrinks
+/// at the borders instead of padding. All channels are averaged on their
+/// own, alpha included (no premultiplication).
+///
+/// # Arguments
+/// * `image` - Image (height, width, channels), values 0.0-1.0
+/// * `radius` - Window radius in pixels (0 returns the input)
+///
+/// # Returns
+/// Filtered image with same shape
+pub fn box_filter_via_integral_f32(image: ArrayView3<f32>, radius: usize) -> Array3<f32> {
+    let (height, width, channels) = image.dim();
+    let table = integral_image(image);
+    Array3::from_shape_fn((height, width, channels), |(y, x, c)| {
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+        let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+        (integral_window_sum(&table, y0, x0, y1, x1, c) / ((y1 - y0) * (x1 - x0)) as f64) as f32
+    })
+}
+
+/// Mean of the (2 * radius + 1)^2 window around every pixel - u8 version.
+pub fn box_filter_via_integral_u8(image: ArrayView3<u8>, radius: usize) -> Array3<u8> {
+    let (height, width, channels) = image.dim();
+    let table = integral_image(image);
+    Array3::from_shape_fn((height, width, channels), |(y, x, c)| {
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+        let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+        (integral_window_sum(&table, y0, x0, y1, x1, c) / ((y1 - y0) * (x1 - x0)) as f64).round() as u8
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force_distance(mask: &Array2<bool>) -> Array2<f32> {
+        let (h, w) = mask.dim();
+        let mut out = Array2::<f32>::zeros((h, w));
+        for y in 0..h {
+            for x in 0..w {
+                if !mask[[y, x]] {
+                    continue;
+                }
+                let mut best = f32::MAX;
+                for by in 0..h {
+                    for bx in 0..w {
+                        if !mask[[by, bx]] {
+                            let dx = x as f32 - bx as f32;
+                            let dy = y as f32 - by as f32;
+                            best = best.min((dx * dx + dy * dy).sqrt());
+                        }
+                    }
+                }
+                out[[y, x]] = best;
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_distance_transform_matches_brute_force() {
+        let mut mask = Array2::<bool>::from_elem((17, 23), true);
+        mask[[0, 0]] = false;
+        mask[[9, 14]] = false;
+        mask[[16, 3]] = false;
+        for x in 5..9 {
+            mask[[4, x]] = false;
+        }
+
+        let fast = euclidean_distance_transform(&mask);
+        let slow = brute_force_distance(&mask);
+        for (a, b) in fast.iter().zip(slow.iter()) {
+            assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_canvas_policy_crops_and_anchors() {
+        // 2x3 layer rendered with 2 pixels of overflow on each side
+        let rendered = Array3::from_shape_fn((6, 7, 1), |(y, x, _)| (y * 7 + x) as f32);
+        let input = (2, 3);
+
+        assert_eq!(CanvasPolicy::Grow.apply(rendered.clone(), input, 2), rendered);
+        let clipped = CanvasPolicy::ClipToInput.apply(rendered.clone(), input, 2);
+        assert_eq!(clipped, rendered.slice(ndarray::s![2..4, 2..5, ..]));
+
+        // Layer centered in a 5x4 canvas: origin (1, 1), one pixel of overflow kept
+        let centered = CanvasPolicy::parse("grow", Some((5, 4)), "center");
+        assert_eq!(centered.layer_origin(input, 2), (1, 1));
+        let out = centered.apply(rendered.clone(), input, 2);
+        assert_eq!(out.dim(), (4, 5, 1));
+        assert_eq!(out, rendered.slice(ndarray::s![1..5, 1..6, ..]));
+
+        // Canvas larger than the rendered area is padded with zeros
+        let corner = CanvasPolicy::Explicit { width: 10, height: 8, anchor: CanvasAnchor::BottomRight };
+        assert_eq!(corner.layer_origin(input, 2), (7, 6));
+        let out = corner.apply(rendered.clone(), input, 2);
+        assert_eq!(out[[6, 7, 0]], rendered[[2, 2, 0]]);
+        assert_eq!(out[[0, 0, 0]], 0.0);
+        assert_eq!(out[[7, 9, 0]], rendered[[3, 4, 0]]);
+    }
+
+    #[test]
+    fn test_distance_transform_background_is_zero() {
+        let mask = Array2::<bool>::from_elem((4, 4), false);
+        let dist = euclidean_distance_transform(&mask);
+        assert!(dist.iter().all(|&d| d == 0.0));
+    }
+
+    #[test]
+    fn test_integral_image_and_box_filter() {
+        let image = Array3::from_shape_fn((7, 9, 2), |(y, x, c)| ((y * 9 + x) * (c + 1) % 23) as f32 / 22.0);
+        let table = integral_image(image.view());
+        assert_eq!(table.dim(), (8, 10, 2));
+        let direct: f64 = image.slice(ndarray::s![2..5, 3..8, 1]).iter().map(|&v| v as f64).sum();
+        assert!((integral_window_sum(&table, 2, 3, 5, 8, 1) - direct).abs() < 1e-9);
+
+        // Brute-force window mean, shrunk at the borders
+        let radius = 2;
+        let filtered = box_filter_via_integral_f32(image.view(), radius);
+        for ((y, x, c), &v) in filtered.indexed_iter() {
+            let window = image.slice(ndarray::s![
+                y.saturating_sub(radius)..(y + radius + 1).min(7),
+                x.saturating_sub(radius)..(x + radius + 1).min(9),
+                c
+            ]);
+            let mean = window.iter().sum::<f32>() / window.len() as f32;
+            assert!((v - mean).abs() < 1e-5);
+        }
+
+        let image_u8 = image.mapv(|v| (v * 255.0).round() as u8);
+        assert_eq!(box_filter_via_integral_u8(image_u8.view(), 0), image_u8);
+        assert_eq!(integral_image(image_u8.view())[[7, 9, 0]], image_u8.slice(ndarray::s![.., .., 0]).iter().map(|&v| v as f64).sum::<f64>());
+    }
+
+    #[test]
+    fn test_signed_distance_alpha_sign() {
+        let mut alpha = Array2::<f32>::zeros((11, 11));
+        for y in 3..8 {
+            for x in 3..8 {
+                alpha[[y, x]] = 1.0;
+            }
+        }
+        let sdf = signed_distance_alpha(&alpha);
+        assert!(sdf[[5, 5]] > 2.0);
+        assert!(sdf[[0, 0]] < -2.0);
+        // Edge pixels sit within half a pixel of the boundary
+        assert!(sdf[[3, 5]].abs() <= 0.5);
+    }
+}
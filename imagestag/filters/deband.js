@@ -0,0 +1,41 @@
+/**
+ * Debanding - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - deband.rs (Rust implementation)
+ * - deband.py (Python wrapper)
+ *
+ * Provides: deband
+ */
+
+import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Deband
+// ============================================================================
+
+/**
+ * Smooth quantization banding in flat gradients (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {threshold: number (0-255), range: number (px), grain: number (0-255)}
+ * @returns {Object} - Filtered image data
+ */
+export const deband = createU8Filter(
+    wasm.deband_wasm,
+    (opts) => [opts.threshold ?? 4, opts.range ?? 16, opts.grain ?? 6]
+);
+
+/**
+ * Smooth quantization banding (f32). `threshold` and `grain` are given in 0-255 like the u8 version.
+ */
+export const deband_f32 = createF32Filter(
+    wasm.deband_f32_wasm,
+    (opts) => [(opts.threshold ?? 4) / 255.0, opts.range ?? 16, (opts.grain ?? 6) / 255.0]
+);
+
+export default {
+    initWasm,
+    deband, deband_f32
+};
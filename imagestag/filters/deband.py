@@ -0,0 +1,80 @@
+"""Debanding with Rust backend.
+
+This module smooths quantization banding - the visible steps in 8-bit
+skies, vignettes and other soft gradients - while keeping detail:
+- Each pixel is compared with four samples at a random distance (up to
+  ``range`` pixels) around it
+- If all samples are within ``threshold``, the pixel is replaced by their
+  average; otherwise it is detail and left alone
+- Optional grain dithers the result so it doesn't band again on output
+
+Sampling and grain are position-hashed, so results are deterministic.
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels. Color channels are
+processed independently, alpha is preserved.
+
+Co-located with:
+- deband.rs (Rust implementation)
+- deband.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.deband import deband
+
+    clean = deband(sky, threshold=4.0, range=16, grain=6.0)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Deband
+# ============================================================================
+
+def deband(image: np.ndarray, threshold: float = 4.0, range: int = 16,
+           grain: float = 6.0) -> np.ndarray:
+    """Smooth banding in flat gradients (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        threshold: Largest difference still treated as banding (0-255)
+        range: Largest sample distance in pixels; should exceed the band width
+        grain: Dither grain amplitude, peak to peak (0-255, 0 = none)
+
+    Returns:
+        Debanded uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "deband")
+    return imagestag_rust.deband(image, threshold, range, grain)
+
+
+def deband_f32(image: np.ndarray, threshold: float = 4.0 / 255.0, range: int = 16,
+               grain: float = 6.0 / 255.0) -> np.ndarray:
+    """Smooth banding in flat gradients (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        threshold: Largest difference still treated as banding (0.0-1.0)
+        range: Largest sample distance in pixels
+        grain: Dither grain amplitude, peak to peak (0.0-1.0, 0 = none)
+
+    Returns:
+        Debanded float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "deband_f32")
+    return imagestag_rust.deband_f32(image, threshold, range, grain)
+
+
+__all__ = [
+    'deband', 'deband_f32',
+]
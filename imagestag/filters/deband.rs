@@ -0,0 +1,145 @@
+//! Debanding: smooth quantization steps in gradients.
+//!
+//! 8-bit skies and vignettes often show visible bands where the gradient
+//! steps from one code value to the next. Deband replaces each pixel that
+//! sits in a flat area with the average of four pixels sampled at a random
+//! distance (up to `range`) around it; where any sample differs by more than
+//! `threshold` the pixel is treated as detail and kept. Optional grain
+//! dithers the result so the smoothed values don't snap back into bands
+//! when stored as u8.
+//!
+//! Sample offsets and grain come from [`hash_uniform`], so the output is
+//! deterministic and identical across tiles.
+//!
+//! ## Supported Formats
+//!
+//! All filters accept images with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - processes the single channel
+//! - **RGB**: (height, width, 3) - processes each channel on its own
+//! - **RGBA**: (height, width, 4) - processes RGB, preserves alpha
+
+use ndarray::{Array3, ArrayView3};
+use rayon::prelude::*;
+
+use crate::filters::noise::hash_uniform;
+
+/// Hash channel for the sample distance (color channels use 0-2 for grain).
+const DISTANCE_STREAM: u32 = 8;
+/// Hash channel for the sample angle.
+const ANGLE_STREAM: u32 = 9;
+/// Fixed seed; debanding has no user-visible randomness.
+const DEBAND_SEED: u64 = 0x6465_6261_6e64;
+
+/// Deband parameters, all in 0.0-1.0 value units except `range`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebandParams {
+    /// Largest difference between a pixel and its samples that still counts
+    /// as banding rather than detail
+    pub threshold: f32,
+    /// Largest sample distance in pixels; should exceed the band width
+    pub range: u32,
+    /// Peak-to-peak amplitude of the dither grain (0 = none)
+    pub grain: f32,
+}
+
+impl Default for DebandParams {
+    fn default() -> Self {
+        Self { threshold: 4.0 / 255.0, range: 16, grain: 6.0 / 255.0 }
+    }
+}
+
+/// Deband - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `params` - Threshold, range and grain
+///
+/// # Returns
+/// Debanded image with same channel count, alpha preserved (not clamped)
+pub fn deband_f32(input: ArrayView3<f32>, params: &DebandParams) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    if width == 0 || height == 0 || params.range == 0 {
+        return input.to_owned();
+    }
+
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let max_x = width as isize - 1;
+    let max_y = height as isize - 1;
+    let mut output = input.iter().copied().collect::<Vec<f32>>();
+    output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let (px, py) = (x as i64, y as i64);
+            // One random offset per pixel, sampled in a rotated cross
+            let distance = 1.0 + hash_uniform(px, py, DISTANCE_STREAM, DEBAND_SEED) * (params.range as f32 - 1.0);
+            let angle = hash_uniform(px, py, ANGLE_STREAM, DEBAND_SEED) * std::f32::consts::TAU;
+            let dx = (distance * angle.cos()).round() as isize;
+            let dy = (distance * angle.sin()).round() as isize;
+            let at = |ox: isize, oy: isize| {
+                let sx = (x as isize + ox).clamp(0, max_x) as usize;
+                let sy = (y as isize + oy).clamp(0, max_y) as usize;
+                (sy, sx)
+            };
+            let taps = [at(dx, dy), at(-dx, -dy), at(-dy, dx), at(dy, -dx)];
+
+            for c in 0..color_channels {
+                let center = input[[y, x, c]];
+                let mut sum = 0.0;
+                let mut flat = true;
+                for &(sy, sx) in &taps {
+                    let v = input[[sy, sx, c]];
+                    flat &= (v - center).abs() <= params.threshold;
+                    sum += v;
+                }
+                let mut value = if flat { sum * 0.25 } else { center };
+                if params.grain > 0.0 {
+                    value += (hash_uniform(px, py, c as u32, DEBAND_SEED) - 0.5) * params.grain;
+                }
+                row[x * channels + c] = value;
+            }
+        }
+    });
+    Array3::from_shape_vec((height, width, channels), output).expect("output matches input shape")
+}
+
+/// Deband - u8 version.
+///
+/// Runs [`deband_f32`] and rounds back; with `grain` > 0 the rounding is
+/// dithered, so the smoothed gradient survives the 8-bit output.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `params` - Threshold and grain in 0.0-1.0 units (e.g. 4.0 / 255.0)
+///
+/// # Returns
+/// Debanded image with same channel count, alpha preserved
+pub fn deband_u8(input: ArrayView3<u8>, params: &DebandParams) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    deband_f32(input_f32.view(), params).mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deband_smooths_steps_and_keeps_edges() {
+        // Shallow staircase (1 code value every 8 px) next to a hard edge
+        let img = Array3::<f32>::from_shape_fn((32, 64, 1), |(_, x, _)| {
+            if x < 48 { (100 + x / 8) as f32 / 255.0 } else { 1.0 }
+        });
+        let params = DebandParams { grain: 0.0, ..Default::default() };
+        let result = deband_f32(img.view(), &params);
+
+        // Pixels next to a step take in-between values
+        let smoothed = (0..32).filter(|&y| {
+            let v = result[[y, 15, 0]] * 255.0;
+            (v - v.round()).abs() > 0.01
+        });
+        assert!(smoothed.count() > 8);
+        // The edge is detail, far beyond the threshold
+        for y in 0..32 {
+            assert_eq!(result[[y, 48, 0]], 1.0);
+            assert_eq!(result[[y, 47, 0]], img[[y, 47, 0]]);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/**
+ * Distort filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - distort.rs (Rust implementation)
+ * - distort.py (Python wrapper)
+ *
+ * Provides: polar_coordinates, twirl, spherize, pinch, wave, kaleidoscope, mirror
+ */
+
+import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Polar Coordinates
+// ============================================================================
+
+/**
+ * Convert between rectangular and polar coordinates (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {direction: 'rect_to_polar'|'polar_to_rect', interpolation: 'nearest'|'bilinear'|'bicubic'}
+ * @returns {Object} - Filtered image data
+ */
+export const polar_coordinates = createU8Filter(
+    wasm.polar_coordinates_wasm,
+    (opts) => [opts.direction ?? 'rect_to_polar', opts.interpolation ?? 'bilinear']
+);
+
+/**
+ * Convert between rectangular and polar coordinates (f32).
+ */
+export const polar_coordinates_f32 = createF32Filter(
+    wasm.polar_coordinates_f32_wasm,
+    (opts) => [opts.direction ?? 'rect_to_polar', opts.interpolation ?? 'bilinear']
+);
+
+// ============================================================================
+// Twirl
+// ============================================================================
+
+const twirlOptions = (opts) => [Object.assign(new wasm.TwirlOptions(), {
+    angle: opts.angle ?? 50,
+    radius: opts.radius ?? 1.0,
+    center_x: opts.center_x ?? 0.5,
+    center_y: opts.center_y ?? 0.5,
+})];
+
+/**
+ * Twirl the image around a center (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {angle: degrees (clockwise), radius: fraction of half the shorter side, center_x, center_y: fractions}
+ * @returns {Object} - Filtered image data
+ */
+export const twirl = createU8Filter(wasm.twirl_wasm, twirlOptions);
+
+/**
+ * Twirl the image around a center (f32).
+ */
+export const twirl_f32 = createF32Filter(wasm.twirl_f32_wasm, twirlOptions);
+
+// ============================================================================
+// Spherize / Pinch
+// ============================================================================
+
+/**
+ * Wrap the image around a sphere (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amount: -1.0 (squeeze) to 1.0 (bulge)}
+ * @returns {Object} - Filtered image data
+ */
+export const spherize = createU8Filter(wasm.spherize_wasm, (opts) => [opts.amount ?? 1.0]);
+
+/**
+ * Wrap the image around a sphere (f32).
+ */
+export const spherize_f32 = createF32Filter(wasm.spherize_f32_wasm, (opts) => [opts.amount ?? 1.0]);
+
+/**
+ * Squeeze the image toward the center (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amount: -1.0 (push out) to 1.0 (pinch in)}
+ * @returns {Object} - Filtered image data
+ */
+export const pinch = createU8Filter(wasm.pinch_wasm, (opts) => [opts.amount ?? 0.5]);
+
+/**
+ * Squeeze the image toward the center (f32).
+ */
+export const pinch_f32 = createF32Filter(wasm.pinch_f32_wasm, (opts) => [opts.amount ?? 0.5]);
+
+// ============================================================================
+// Wave
+// ============================================================================
+
+/**
+ * Ripple the image with a periodic wave (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amplitude: px, wavelength: px, type: 'sine'|'triangle'|'square'}
+ * @returns {Object} - Filtered image data
+ */
+export const wave = createU8Filter(
+    wasm.wave_wasm,
+    (opts) => [opts.amplitude ?? 10, opts.wavelength ?? 120, opts.type ?? 'sine']
+);
+
+/**
+ * Ripple the image with a periodic wave (f32).
+ */
+export const wave_f32 = createF32Filter(
+    wasm.wave_f32_wasm,
+    (opts) => [opts.amplitude ?? 10, opts.wavelength ?? 120, opts.type ?? 'sine']
+);
+
+// ============================================================================
+// Kaleidoscope / Mirror
+// ============================================================================
+
+const kaleidoscopeOptions = (opts) => [Object.assign(new wasm.KaleidoscopeOptions(), {
+    segments: opts.segments ?? 6,
+    rotation: opts.rotation ?? 0,
+    center_x: opts.center_x ?? 0.5,
+    center_y: opts.center_y ?? 0.5,
+})];
+
+/**
+ * Repeat one wedge of the image around a center, mirrored alternately (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {segments, rotation: degrees, center_x, center_y: fractions}
+ * @returns {Object} - Filtered image data
+ */
+export const kaleidoscope = createU8Filter(wasm.kaleidoscope_wasm, kaleidoscopeOptions);
+
+/**
+ * Repeat one wedge of the image around a center (f32).
+ */
+export const kaleidoscope_f32 = createF32Filter(wasm.kaleidoscope_f32_wasm, kaleidoscopeOptions);
+
+/**
+ * Reflect one side of the image onto the other across a line (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {axis_angle: degrees (0 = vertical), offset: px}
+ * @returns {Object} - Filtered image data
+ */
+export const mirror = createU8Filter(wasm.mirror_wasm, (opts) => [opts.axis_angle ?? 0, opts.offset ?? 0]);
+
+/**
+ * Reflect one side of the image onto the other across a line (f32).
+ */
+export const mirror_f32 = createF32Filter(wasm.mirror_f32_wasm, (opts) => [opts.axis_angle ?? 0, opts.offset ?? 0]);
+
+export default {
+    initWasm,
+    polar_coordinates, polar_coordinates_f32,
+    twirl, twirl_f32,
+    spherize, spherize_f32,
+    pinch, pinch_f32,
+    wave, wave_f32,
+    kaleidoscope, kaleidoscope_f32,
+    mirror, mirror_f32
+};
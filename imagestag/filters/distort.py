@@ -0,0 +1,305 @@
+"""Distort filters with Rust backend.
+
+This module remaps pixel coordinates:
+- Polar coordinates: wrap an image around its center (rectangular to
+  polar) or unroll a round subject into a strip (polar to rectangular),
+  matching Photoshop's Distort > Polar Coordinates
+- Twirl, spherize, pinch: radial distortions around a center
+- Wave: periodic sine, triangle or square displacement
+- Kaleidoscope, mirror: symmetric patterns from one wedge or half
+
+Every output pixel is sampled from the source (bilinear; polar coordinates
+also offers nearest neighbor and bicubic). Positions outside the image
+clamp to the edge.
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels. RGBA is interpolated
+premultiplied so transparent pixels don't bleed.
+
+Co-located with:
+- distort.rs (Rust implementation)
+- distort.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.distort import polar_coordinates, twirl
+
+    radial = polar_coordinates(stripes)                          # rect -> polar
+    strip = polar_coordinates(clock, direction='polar_to_rect')  # polar -> rect
+    swirled = twirl(image, angle=120, radius=0.8)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Polar Coordinates
+# ============================================================================
+
+def polar_coordinates(image: np.ndarray, direction: str = 'rect_to_polar',
+                      interpolation: str = 'bilinear') -> np.ndarray:
+    """Convert between rectangular and polar coordinates (u8).
+
+    The polar image fills the ellipse inscribed in the canvas. Rectangular
+    to polar puts the top row in the center and the bottom row on the rim,
+    with the left/right seam at 12 o'clock; polar to rectangular inverts it.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        direction: 'rect_to_polar' or 'polar_to_rect'
+        interpolation: 'nearest', 'bilinear' or 'bicubic'
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "polar_coordinates")
+    return imagestag_rust.polar_coordinates(image, direction, interpolation)
+
+
+def polar_coordinates_f32(image: np.ndarray, direction: str = 'rect_to_polar',
+                          interpolation: str = 'bilinear') -> np.ndarray:
+    """Convert between rectangular and polar coordinates (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        direction: 'rect_to_polar' or 'polar_to_rect'
+        interpolation: 'nearest', 'bilinear' or 'bicubic'
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "polar_coordinates_f32")
+    return imagestag_rust.polar_coordinates_f32(image, direction, interpolation)
+
+
+# ============================================================================
+# Twirl
+# ============================================================================
+
+def twirl(image: np.ndarray, angle: float = 50.0, radius: float = 1.0,
+          center: tuple = (0.5, 0.5)) -> np.ndarray:
+    """Twirl the image around a center (u8).
+
+    The rotation is strongest at the center and fades out at ``radius``.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        angle: Rotation at the center in degrees (positive = clockwise)
+        radius: Extent as a fraction of half the shorter side
+        center: (x, y) center as fractions of width and height
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "twirl")
+    return imagestag_rust.twirl(image, angle, radius, tuple(center))
+
+
+def twirl_f32(image: np.ndarray, angle: float = 50.0, radius: float = 1.0,
+              center: tuple = (0.5, 0.5)) -> np.ndarray:
+    """Twirl the image around a center (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        angle: Rotation at the center in degrees (positive = clockwise)
+        radius: Extent as a fraction of half the shorter side
+        center: (x, y) center as fractions of width and height
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "twirl_f32")
+    return imagestag_rust.twirl_f32(image, angle, radius, tuple(center))
+
+
+# ============================================================================
+# Spherize / Pinch
+# ============================================================================
+
+def spherize(image: np.ndarray, amount: float = 1.0) -> np.ndarray:
+    """Wrap the image around a sphere inside its inscribed ellipse (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amount: -1.0 (squeeze in) to 1.0 (bulge out)
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "spherize")
+    return imagestag_rust.spherize(image, amount)
+
+
+def spherize_f32(image: np.ndarray, amount: float = 1.0) -> np.ndarray:
+    """Wrap the image around a sphere inside its inscribed ellipse (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        amount: -1.0 (squeeze in) to 1.0 (bulge out)
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "spherize_f32")
+    return imagestag_rust.spherize_f32(image, amount)
+
+
+def pinch(image: np.ndarray, amount: float = 0.5) -> np.ndarray:
+    """Squeeze the image toward its center (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amount: -1.0 (push out) to 1.0 (pinch in)
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "pinch")
+    return imagestag_rust.pinch(image, amount)
+
+
+def pinch_f32(image: np.ndarray, amount: float = 0.5) -> np.ndarray:
+    """Squeeze the image toward its center (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        amount: -1.0 (push out) to 1.0 (pinch in)
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "pinch_f32")
+    return imagestag_rust.pinch_f32(image, amount)
+
+
+# ============================================================================
+# Wave
+# ============================================================================
+
+def wave(image: np.ndarray, amplitude: float = 10.0, wavelength: float = 120.0,
+         wave_type: str = 'sine') -> np.ndarray:
+    """Ripple the image with a periodic wave in both directions (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amplitude: Largest displacement in pixels
+        wavelength: Wave period in pixels
+        wave_type: 'sine', 'triangle' or 'square'
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "wave")
+    return imagestag_rust.wave(image, amplitude, wavelength, wave_type)
+
+
+def wave_f32(image: np.ndarray, amplitude: float = 10.0, wavelength: float = 120.0,
+             wave_type: str = 'sine') -> np.ndarray:
+    """Ripple the image with a periodic wave in both directions (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        amplitude: Largest displacement in pixels
+        wavelength: Wave period in pixels
+        wave_type: 'sine', 'triangle' or 'square'
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "wave_f32")
+    return imagestag_rust.wave_f32(image, amplitude, wavelength, wave_type)
+
+
+# ============================================================================
+# Kaleidoscope / Mirror
+# ============================================================================
+
+def kaleidoscope(image: np.ndarray, segments: int = 6, rotation: float = 0.0,
+                 center: tuple = (0.5, 0.5)) -> np.ndarray:
+    """Repeat one wedge of the image around a center (u8).
+
+    The wedge spans 360 / segments degrees starting at ``rotation``; every
+    other copy is mirrored so the seams match.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        segments: Number of wedges
+        rotation: Angle of the source wedge in degrees (clockwise from +x)
+        center: (x, y) center as fractions of width and height
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "kaleidoscope")
+    return imagestag_rust.kaleidoscope(image, segments, rotation, tuple(center))
+
+
+def kaleidoscope_f32(image: np.ndarray, segments: int = 6, rotation: float = 0.0,
+                     center: tuple = (0.5, 0.5)) -> np.ndarray:
+    """Repeat one wedge of the image around a center (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        segments: Number of wedges
+        rotation: Angle of the source wedge in degrees (clockwise from +x)
+        center: (x, y) center as fractions of width and height
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "kaleidoscope_f32")
+    return imagestag_rust.kaleidoscope_f32(image, segments, rotation, tuple(center))
+
+
+def mirror(image: np.ndarray, axis_angle: float = 0.0, offset: float = 0.0) -> np.ndarray:
+    """Reflect one side of the image onto the other across a line (u8).
+
+    The line passes through the image center. At angle 0 it is vertical
+    and the left side is copied onto the right; positive angles turn it
+    clockwise.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        axis_angle: Angle of the mirror line in degrees
+        offset: Shift of the line along its normal in pixels
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "mirror")
+    return imagestag_rust.mirror(image, axis_angle, offset)
+
+
+def mirror_f32(image: np.ndarray, axis_angle: float = 0.0, offset: float = 0.0) -> np.ndarray:
+    """Reflect one side of the image onto the other across a line (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        axis_angle: Angle of the mirror line in degrees
+        offset: Shift of the line along its normal in pixels
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "mirror_f32")
+    return imagestag_rust.mirror_f32(image, axis_angle, offset)
+
+
+__all__ = [
+    'polar_coordinates', 'polar_coordinates_f32',
+    'twirl', 'twirl_f32',
+    'spherize', 'spherize_f32',
+    'pinch', 'pinch_f32',
+    'wave', 'wave_f32',
+    'kaleidoscope', 'kaleidoscope_f32',
+    'mirror', 'mirror_f32',
+]
@@ -0,0 +1,573 @@
+//! Distort filters: coordinate remapping effects.
+//!
+//! Each filter maps every output pixel back to a source position and
+//! samples the input there ("inverse mapping"), so the result has no holes.
+//! [`polar_coordinates_f32`] matches Photoshop's Distort > Polar Coordinates:
+//! rectangular to polar wraps the image around its center (top edge in the
+//! middle, bottom edge on the rim), polar to rectangular unrolls a round
+//! subject such as a clock face or a scanned record into a strip.
+//! [`twirl_f32`], [`spherize_f32`], [`pinch_f32`] and [`wave_f32`] are the
+//! parametric Photoshop-style distortions; [`kaleidoscope_f32`] and
+//! [`mirror_f32`] build symmetric patterns. These always sample bilinearly.
+//!
+//! ## Interpolation
+//!
+//! Samples are nearest neighbor, bilinear or bicubic (Catmull-Rom). RGBA is
+//! interpolated premultiplied so transparent pixels don't bleed; positions
+//! outside the image clamp to the edge.
+//!
+//! ## Supported Formats
+//!
+//! All filters accept images with 1, 3, or 4 channels in u8 and f32 formats.
+
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use ndarray::{Array3, ArrayView3};
+use rayon::prelude::*;
+
+// ============================================================================
+// Sampling
+// ============================================================================
+
+/// Resampling filter used by the distort filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Nearest source pixel (hard edges, no new colors)
+    Nearest,
+    /// Bilinear blend of the four surrounding pixels
+    #[default]
+    Bilinear,
+    /// Catmull-Rom bicubic over 4x4 pixels (sharper, may ring slightly)
+    Bicubic,
+}
+
+impl Interpolation {
+    /// Parse an interpolation name ("nearest", "bilinear", "bicubic").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "nearest" | "nearest_neighbor" => Some(Interpolation::Nearest),
+            "bilinear" | "linear" => Some(Interpolation::Bilinear),
+            "bicubic" | "cubic" => Some(Interpolation::Bicubic),
+            _ => None,
+        }
+    }
+}
+
+/// Catmull-Rom cubic weight (a = -0.5).
+#[inline]
+fn cubic(t: f32) -> f32 {
+    let t = t.abs();
+    if t < 1.0 {
+        (1.5 * t - 2.5) * t * t + 1.0
+    } else if t < 2.0 {
+        ((-0.5 * t + 2.5) * t - 4.0) * t + 2.0
+    } else {
+        0.0
+    }
+}
+
+/// Resample `input` through an inverse mapping.
+///
+/// `map(x, y)` returns the source pixel position of output pixel (x, y)
+/// (pixel centers at integers). `wrap_x` wraps columns instead of clamping
+/// them, for mappings where the image is periodic horizontally.
+pub(crate) fn remap_f32<F>(
+    input: ArrayView3<f32>,
+    out_w: usize,
+    out_h: usize,
+    interpolation: Interpolation,
+    wrap_x: bool,
+    map: F,
+) -> Array3<f32>
+where
+    F: Fn(f32, f32) -> (f32, f32) + Sync,
+{
+    let (height, width, channels) = input.dim();
+    if out_w == 0 || out_h == 0 || width == 0 || height == 0 {
+        return Array3::zeros((out_h, out_w, channels));
+    }
+    let has_alpha = channels == 4;
+    let col = |x: isize| {
+        if wrap_x {
+            x.rem_euclid(width as isize) as usize
+        } else {
+            x.clamp(0, width as isize - 1) as usize
+        }
+    };
+    let row = |y: isize| y.clamp(0, height as isize - 1) as usize;
+
+    let mut output = vec![0.0f32; out_h * out_w * channels];
+    output.par_chunks_mut(out_w * channels).enumerate().for_each(|(y, out_row)| {
+        for x in 0..out_w {
+            let (sx, sy) = map(x as f32, y as f32);
+            let pixel = &mut out_row[x * channels..(x + 1) * channels];
+            if interpolation == Interpolation::Nearest {
+                let (px, py) = (col(sx.round() as isize), row(sy.round() as isize));
+                for (c, out) in pixel.iter_mut().enumerate() {
+                    *out = input[[py, px, c]];
+                }
+                continue;
+            }
+
+            let (x0, y0) = (sx.floor(), sy.floor());
+            let (fx, fy) = (sx - x0, sy - y0);
+            let (x0, y0) = (x0 as isize, y0 as isize);
+            let (wx, wy, first) = match interpolation {
+                Interpolation::Bicubic => (
+                    [cubic(fx + 1.0), cubic(fx), cubic(1.0 - fx), cubic(2.0 - fx)],
+                    [cubic(fy + 1.0), cubic(fy), cubic(1.0 - fy), cubic(2.0 - fy)],
+                    -1,
+                ),
+                _ => ([1.0 - fx, fx, 0.0, 0.0], [1.0 - fy, fy, 0.0, 0.0], 0),
+            };
+
+            let mut sum = [0.0f32; 4];
+            for (j, &wy) in wy.iter().enumerate().filter(|(_, w)| **w != 0.0) {
+                let py = row(y0 + first + j as isize);
+                for (i, &wx) in wx.iter().enumerate().filter(|(_, w)| **w != 0.0) {
+                    let px = col(x0 + first + i as isize);
+                    let w = wx * wy;
+                    if has_alpha {
+                        let a = input[[py, px, 3]];
+                        for c in 0..3 {
+                            sum[c] += w * a * input[[py, px, c]];
+                        }
+                        sum[3] += w * a;
+                    } else {
+                        for c in 0..channels {
+                            sum[c] += w * input[[py, px, c]];
+                        }
+                    }
+                }
+            }
+            if has_alpha {
+                let alpha = sum[3].clamp(0.0, 1.0);
+                for c in 0..3 {
+                    pixel[c] = if alpha > 0.0 { (sum[c] / sum[3]).clamp(0.0, 1.0) } else { 0.0 };
+                }
+                pixel[3] = alpha;
+            } else {
+                pixel.copy_from_slice(&sum[..channels]);
+            }
+        }
+    });
+    Array3::from_shape_vec((out_h, out_w, channels), output).expect("output matches shape")
+}
+
+/// Run an f32 distort filter on u8 data.
+fn via_f32<F>(input: ArrayView3<u8>, filter: F) -> Array3<u8>
+where
+    F: FnOnce(ArrayView3<f32>) -> Array3<f32>,
+{
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    filter(input_f32.view()).mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Polar Coordinates
+// ============================================================================
+
+/// Direction of the polar coordinate conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PolarDirection {
+    /// Wrap the image around its center: x becomes the angle (clockwise
+    /// from 12 o'clock), y the distance from the center
+    #[default]
+    RectToPolar,
+    /// Unroll a round subject: the angle runs along x, the radius down y
+    PolarToRect,
+}
+
+impl PolarDirection {
+    /// Parse a direction name ("rect_to_polar", "polar_to_rect").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('-', "_").as_str() {
+            "rect_to_polar" | "rectangular_to_polar" | "to_polar" => Some(PolarDirection::RectToPolar),
+            "polar_to_rect" | "polar_to_rectangular" | "to_rect" => Some(PolarDirection::PolarToRect),
+            _ => None,
+        }
+    }
+}
+
+/// Convert between rectangular and polar coordinates - f32 version.
+///
+/// The polar image fills the ellipse inscribed in the canvas, so a square
+/// input gives a circle. Rectangular to polar: the top row lands in the
+/// center and the bottom row on the rim; the left and right edges meet at
+/// 12 o'clock, and the corners outside the ellipse repeat the bottom row.
+/// Polar to rectangular is the exact inverse.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `direction` - Rectangular to polar or polar to rectangular
+/// * `interpolation` - Resampling filter
+///
+/// # Returns
+/// Converted image with the same dimensions
+pub fn polar_coordinates_f32(input: ArrayView3<f32>, direction: PolarDirection, interpolation: Interpolation) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let (w, h) = (width as f32, height as f32);
+    let (cx, cy) = (w / 2.0, h / 2.0);
+    match direction {
+        PolarDirection::RectToPolar => remap_f32(input, width, height, interpolation, true, |x, y| {
+            // Normalized offset from the center; the rim ellipse has radius 1
+            let dx = (x + 0.5 - cx) / cx;
+            let dy = (y + 0.5 - cy) / cy;
+            let angle = dx.atan2(-dy).rem_euclid(TAU);
+            let radius = dx.hypot(dy);
+            (angle / TAU * w - 0.5, radius * h - 0.5)
+        }),
+        PolarDirection::PolarToRect => remap_f32(input, width, height, interpolation, false, |x, y| {
+            let angle = (x + 0.5) / w * TAU;
+            let radius = (y + 0.5) / h;
+            let (sin, cos) = angle.sin_cos();
+            (cx + radius * sin * cx - 0.5, cy - radius * cos * cy - 0.5)
+        }),
+    }
+}
+
+/// Convert between rectangular and polar coordinates - u8 version.
+pub fn polar_coordinates_u8(input: ArrayView3<u8>, direction: PolarDirection, interpolation: Interpolation) -> Array3<u8> {
+    via_f32(input, |view| polar_coordinates_f32(view, direction, interpolation))
+}
+
+// ============================================================================
+// Twirl
+// ============================================================================
+
+/// Rotate the image around a center, most strongly at the center - f32 version.
+///
+/// The rotation falls off quadratically to zero at `radius`; pixels
+/// further out are unchanged.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `angle` - Rotation at the center in degrees (positive = clockwise)
+/// * `radius` - Extent as a fraction of half the shorter image side (0.0-1.0+)
+/// * `center` - (x, y) center as a fraction of width and height
+///
+/// # Returns
+/// Twirled image with the same dimensions
+pub fn twirl_f32(input: ArrayView3<f32>, angle: f32, radius: f32, center: (f32, f32)) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let (cx, cy) = (center.0 * width as f32, center.1 * height as f32);
+    let max_r = radius * width.min(height) as f32 / 2.0;
+    if max_r <= 0.0 || angle == 0.0 {
+        return input.to_owned();
+    }
+    let angle = angle.to_radians();
+    remap_f32(input, width, height, Interpolation::Bilinear, false, |x, y| {
+        let (dx, dy) = (x + 0.5 - cx, y + 0.5 - cy);
+        let d = dx.hypot(dy);
+        if d >= max_r {
+            return (x, y);
+        }
+        let falloff = 1.0 - d / max_r;
+        // Inverse mapping: turn the output offset back by the local angle
+        let (sin, cos) = (-angle * falloff * falloff).sin_cos();
+        (cx + dx * cos - dy * sin - 0.5, cy + dx * sin + dy * cos - 0.5)
+    })
+}
+
+/// Rotate the image around a center - u8 version.
+pub fn twirl_u8(input: ArrayView3<u8>, angle: f32, radius: f32, center: (f32, f32)) -> Array3<u8> {
+    via_f32(input, |view| twirl_f32(view, angle, radius, center))
+}
+
+// ============================================================================
+// Spherize / Pinch
+// ============================================================================
+
+/// Remap within the ellipse inscribed in the image: the output at
+/// normalized radius r (0 center, 1 rim) samples the source at r * scale(r).
+fn radial_remap<F>(input: ArrayView3<f32>, scale: F) -> Array3<f32>
+where
+    F: Fn(f32) -> f32 + Sync,
+{
+    let (height, width, _) = input.dim();
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    remap_f32(input, width, height, Interpolation::Bilinear, false, |x, y| {
+        let (dx, dy) = ((x + 0.5 - cx) / cx, (y + 0.5 - cy) / cy);
+        let r = dx.hypot(dy);
+        if r >= 1.0 || r == 0.0 {
+            return (x, y);
+        }
+        let k = scale(r);
+        (cx + dx * k * cx - 0.5, cy + dy * k * cy - 0.5)
+    })
+}
+
+/// Wrap the image around a sphere (or the inside of one) - f32 version.
+///
+/// Works inside the ellipse inscribed in the image. At full strength the
+/// mapping is the orthographic view of a sphere, magnifying the center by
+/// π/2 and compressing the rim.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amount` - -1.0 to 1.0 (positive = bulge out, negative = squeeze in)
+///
+/// # Returns
+/// Spherized image with the same dimensions
+pub fn spherize_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+    let amount = amount.clamp(-1.0, 1.0);
+    if amount == 0.0 {
+        return input.to_owned();
+    }
+    radial_remap(input, |r| {
+        // Source radius on the sphere (bulge) or its inverse (squeeze)
+        let sphere = if amount > 0.0 { r.asin() / FRAC_PI_2 } else { (r * FRAC_PI_2).sin() };
+        1.0 + (sphere / r - 1.0) * amount.abs()
+    })
+}
+
+/// Wrap the image around a sphere - u8 version.
+pub fn spherize_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+    via_f32(input, |view| spherize_f32(view, amount))
+}
+
+/// Squeeze the image toward (or push it away from) the center - f32 version.
+///
+/// Works inside the ellipse inscribed in the image, with a smooth transition
+/// to the untouched rim (GIMP's whirl-and-pinch falloff).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amount` - -1.0 to 1.0 (positive = pinch in, negative = push out)
+///
+/// # Returns
+/// Pinched image with the same dimensions
+pub fn pinch_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+    let amount = amount.clamp(-1.0, 1.0);
+    if amount == 0.0 {
+        return input.to_owned();
+    }
+    radial_remap(input, |r| (r * FRAC_PI_2).sin().powf(-amount))
+}
+
+/// Squeeze the image toward the center - u8 version.
+pub fn pinch_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+    via_f32(input, |view| pinch_f32(view, amount))
+}
+
+// ============================================================================
+// Wave
+// ============================================================================
+
+/// Waveform of [`wave_f32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaveType {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+}
+
+impl WaveType {
+    /// Parse a wave type name ("sine", "triangle", "square").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "sine" | "sin" => Some(WaveType::Sine),
+            "triangle" => Some(WaveType::Triangle),
+            "square" => Some(WaveType::Square),
+            _ => None,
+        }
+    }
+
+    /// Wave value in -1.0 to 1.0 at `phase` periods.
+    #[inline]
+    fn eval(self, phase: f32) -> f32 {
+        let t = phase.rem_euclid(1.0);
+        match self {
+            WaveType::Sine => (t * TAU).sin(),
+            WaveType::Triangle => 1.0 - 4.0 * (t - 0.25).rem_euclid(1.0).min(1.0 - (t - 0.25).rem_euclid(1.0)),
+            WaveType::Square => if t < 0.5 { 1.0 } else { -1.0 },
+        }
+    }
+}
+
+/// Ripple the image with a periodic wave in both directions - f32 version.
+///
+/// Columns shift vertically with a wave running along x and rows shift
+/// horizontally with a wave running along y.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amplitude` - Largest displacement in pixels
+/// * `wavelength` - Wave period in pixels
+/// * `wave_type` - Sine, triangle or square
+///
+/// # Returns
+/// Distorted image with the same dimensions
+pub fn wave_f32(input: ArrayView3<f32>, amplitude: f32, wavelength: f32, wave_type: WaveType) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    if amplitude == 0.0 {
+        return input.to_owned();
+    }
+    let wavelength = wavelength.max(1.0);
+    remap_f32(input, width, height, Interpolation::Bilinear, false, |x, y| {
+        (x + amplitude * wave_type.eval(y / wavelength), y + amplitude * wave_type.eval(x / wavelength))
+    })
+}
+
+/// Ripple the image with a periodic wave - u8 version.
+pub fn wave_u8(input: ArrayView3<u8>, amplitude: f32, wavelength: f32, wave_type: WaveType) -> Array3<u8> {
+    via_f32(input, |view| wave_f32(view, amplitude, wavelength, wave_type))
+}
+
+// ============================================================================
+// Kaleidoscope / Mirror
+// ============================================================================
+
+/// Repeat one wedge of the image around a center with alternating mirroring - f32 version.
+///
+/// The wedge spans 360° / `segments` starting at `rotation` (clockwise from
+/// the +x axis); every other copy is mirrored, so the seams are seamless.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `segments` - Number of wedges (1 = unchanged)
+/// * `rotation` - Angle of the source wedge in degrees
+/// * `center` - (x, y) center as a fraction of width and height
+///
+/// # Returns
+/// Kaleidoscope image with the same dimensions
+pub fn kaleidoscope_f32(input: ArrayView3<f32>, segments: u32, rotation: f32, center: (f32, f32)) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    if segments <= 1 {
+        return input.to_owned();
+    }
+    let (cx, cy) = (center.0 * width as f32, center.1 * height as f32);
+    let wedge = TAU / segments as f32;
+    let rotation = rotation.to_radians();
+    remap_f32(input, width, height, Interpolation::Bilinear, false, |x, y| {
+        let (dx, dy) = (x + 0.5 - cx, y + 0.5 - cy);
+        let r = dx.hypot(dy);
+        // Fold the angle into [0, wedge], mirroring every other wedge
+        let a = (dy.atan2(dx) - rotation).rem_euclid(2.0 * wedge);
+        let a = if a > wedge { 2.0 * wedge - a } else { a };
+        let (sin, cos) = (a + rotation).sin_cos();
+        (cx + r * cos - 0.5, cy + r * sin - 0.5)
+    })
+}
+
+/// Repeat one wedge of the image around a center - u8 version.
+pub fn kaleidoscope_u8(input: ArrayView3<u8>, segments: u32, rotation: f32, center: (f32, f32)) -> Array3<u8> {
+    via_f32(input, |view| kaleidoscope_f32(view, segments, rotation, center))
+}
+
+/// Reflect one side of the image onto the other across a line - f32 version.
+///
+/// The mirror line passes through the image center, shifted by `offset`
+/// pixels along its normal. At `axis_angle` 0 the line is vertical and the
+/// left side is copied onto the right; positive angles turn it clockwise.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `axis_angle` - Angle of the mirror line in degrees (0 = vertical)
+/// * `offset` - Shift of the line in pixels (positive = right at angle 0)
+///
+/// # Returns
+/// Mirrored image with the same dimensions
+pub fn mirror_f32(input: ArrayView3<f32>, axis_angle: f32, offset: f32) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    let (ny, nx) = axis_angle.to_radians().sin_cos();
+    remap_f32(input, width, height, Interpolation::Bilinear, false, |x, y| {
+        let side = (x + 0.5 - cx) * nx + (y + 0.5 - cy) * ny - offset;
+        if side <= 0.0 {
+            (x, y)
+        } else {
+            (x - 2.0 * side * nx, y - 2.0 * side * ny)
+        }
+    })
+}
+
+/// Reflect one side of the image onto the other - u8 version.
+pub fn mirror_u8(input: ArrayView3<u8>, axis_angle: f32, offset: f32) -> Array3<u8> {
+    via_f32(input, |view| mirror_f32(view, axis_angle, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polar_coordinates_layout_and_round_trip() {
+        // Vertical gradient: rect to polar turns it into a radial gradient
+        let img = Array3::<f32>::from_shape_fn((64, 64, 1), |(y, _, _)| y as f32 / 63.0);
+        let polar = polar_coordinates_f32(img.view(), PolarDirection::RectToPolar, Interpolation::Bilinear);
+        assert!(polar[[32, 32, 0]] < 0.05);
+        assert!(polar[[32, 1, 0]] > 0.9);
+        assert!((polar[[16, 32, 0]] - polar[[32, 16, 0]]).abs() < 0.02);
+
+        // Smooth image survives the round trip away from center and rim
+        let img = Array3::<f32>::from_shape_fn((64, 64, 1), |(y, x, _)| {
+            0.5 + 0.4 * (x as f32 * TAU / 64.0).sin() * (y as f32 / 63.0)
+        });
+        let polar = polar_coordinates_f32(img.view(), PolarDirection::RectToPolar, Interpolation::Bicubic);
+        let back = polar_coordinates_f32(polar.view(), PolarDirection::PolarToRect, Interpolation::Bicubic);
+        for y in 16..48 {
+            for x in 0..64 {
+                assert!((back[[y, x, 0]] - img[[y, x, 0]]).abs() < 0.05, "({}, {})", x, y);
+            }
+        }
+
+        // Nearest keeps the original values
+        let nearest = polar_coordinates_f32(img.view(), PolarDirection::RectToPolar, Interpolation::Nearest);
+        assert!(nearest.iter().all(|v| img.iter().any(|o| o == v)));
+    }
+
+    #[test]
+    fn test_parametric_distortions() {
+        let img = Array3::<f32>::from_shape_fn((64, 64, 1), |(y, x, _)| ((x / 8 + y / 8) % 2) as f32);
+
+        // Outside their area of effect, the filters leave pixels alone
+        let twirled = twirl_f32(img.view(), 90.0, 0.5, (0.5, 0.5));
+        assert_eq!(twirled[[2, 2, 0]], img[[2, 2, 0]]);
+        assert_ne!(twirled, img);
+        let spherized = spherize_f32(img.view(), 1.0);
+        assert_eq!(spherized[[0, 0, 0]], img[[0, 0, 0]]);
+
+        // Horizontal gradient: bulge magnifies the center (flatter slope),
+        // pinch and squeeze shrink it (steeper slope)
+        let ramp = Array3::<f32>::from_shape_fn((64, 64, 1), |(_, x, _)| x as f32 / 63.0);
+        let slope = |img: &Array3<f32>| img[[32, 36, 0]] - img[[32, 28, 0]];
+        let base = slope(&ramp);
+        assert!(slope(&spherize_f32(ramp.view(), 1.0)) < base * 0.8);
+        assert!(slope(&spherize_f32(ramp.view(), -1.0)) > base * 1.2);
+        assert!(slope(&pinch_f32(ramp.view(), 0.5)) > base * 1.2);
+        assert!(slope(&pinch_f32(ramp.view(), -0.5)) < base * 0.8);
+
+        // Square wave shifts whole bands by exactly the amplitude
+        let waved = wave_f32(ramp.view(), 2.0, 16.0, WaveType::Square);
+        assert!((waved[[4, 20, 0]] - ramp[[4, 22, 0]]).abs() < 1e-6);
+        assert!((waved[[12, 20, 0]] - ramp[[12, 18, 0]]).abs() < 1e-6);
+        assert_eq!(WaveType::Triangle.eval(0.25), 1.0);
+        assert_eq!(WaveType::Triangle.eval(0.75), -1.0);
+    }
+
+    #[test]
+    fn test_kaleidoscope_and_mirror_symmetry() {
+        let img = Array3::<f32>::from_shape_fn((64, 64, 1), |(y, x, _)| ((x * 7 + y * 13) % 17) as f32 / 16.0);
+
+        // Vertical mirror: right half reflects the left
+        let mirrored = mirror_f32(img.view(), 0.0, 0.0);
+        for y in 0..64 {
+            for x in 0..32 {
+                assert_eq!(mirrored[[y, x, 0]], img[[y, x, 0]]);
+                assert!((mirrored[[y, 63 - x, 0]] - img[[y, x, 0]]).abs() < 1e-5);
+            }
+        }
+
+        // Four segments: symmetric under reflection across both axes
+        let k = kaleidoscope_f32(img.view(), 4, 0.0, (0.5, 0.5));
+        for y in 0..64 {
+            for x in 0..64 {
+                assert!((k[[y, x, 0]] - k[[63 - y, x, 0]]).abs() < 1e-4);
+                assert!((k[[y, x, 0]] - k[[y, 63 - x, 0]]).abs() < 1e-4);
+            }
+        }
+        assert_eq!(kaleidoscope_f32(img.view(), 1, 0.0, (0.5, 0.5)), img);
+    }
+}
@@ -16,24 +16,27 @@ export { initWasm };
 // Sobel
 // ============================================================================
 
+const sobelParams = (opts) => [opts.direction ?? 'both', opts.kernelSize ?? 3];
+const sobelLuma = createU8Filter(wasm.sobel_wasm, sobelParams);
+const sobelChannels = createU8Filter(wasm.sobel_per_channel_wasm, sobelParams);
+const sobelLumaF32 = createF32Filter(wasm.sobel_f32_wasm, sobelParams);
+const sobelChannelsF32 = createF32Filter(wasm.sobel_per_channel_f32_wasm, sobelParams);
+
 /**
  * Apply Sobel edge detection (u8).
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {direction: 'horizontal'|'vertical'|'both'}
+ * @param {Object} options - {direction: 'h'|'v'|'both'|'angle', kernelSize: 3|5|7,
+ *   perChannel: boolean (edges per color channel instead of luminance)}
  * @returns {Object} - Edge-detected image data
  */
-export const sobel = createU8Filter(
-    wasm.sobel_wasm,
-    (opts) => [opts.direction ?? 'both']
-);
+export const sobel = (imageData, options = {}) =>
+    (options.perChannel ? sobelChannels : sobelLuma)(imageData, options);
 
 /**
  * Apply Sobel edge detection (f32).
  */
-export const sobel_f32 = createF32Filter(
-    wasm.sobel_f32_wasm,
-    (opts) => [opts.direction ?? 'both']
-);
+export const sobel_f32 = (imageData, options = {}) =>
+    (options.perChannel ? sobelChannelsF32 : sobelLumaF32)(imageData, options);
 
 // ============================================================================
 // Laplacian
@@ -74,9 +77,33 @@ export const find_edges = createU8Filter(wasm.find_edges_wasm);
  */
 export const find_edges_f32 = createF32Filter(wasm.find_edges_f32_wasm);
 
+// ============================================================================
+// Difference of Gaussians
+// ============================================================================
+
+/**
+ * Difference of Gaussians edge map (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {sigma1: number, sigma2: number}
+ * @returns {Object} - Edge map image data (normalized magnitude)
+ */
+export const dog = createU8Filter(
+    wasm.dog_wasm,
+    (opts) => [opts.sigma1 ?? 1.0, opts.sigma2 ?? 1.6]
+);
+
+/**
+ * Difference of Gaussians edge map (f32).
+ */
+export const dog_f32 = createF32Filter(
+    wasm.dog_f32_wasm,
+    (opts) => [opts.sigma1 ?? 1.0, opts.sigma2 ?? 1.6]
+);
+
 export default {
     initWasm,
     sobel, sobel_f32,
     laplacian, laplacian_f32,
-    find_edges, find_edges_f32
+    find_edges, find_edges_f32,
+    dog, dog_f32
 };
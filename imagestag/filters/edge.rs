@@ -1,4 +1,4 @@
-//! Edge detection filters: Sobel, Laplacian, Find Edges.
+//! Edge detection filters: Sobel, Laplacian, Find Edges, Difference of Gaussians.
 //!
 //! These filters detect and highlight edges in images.
 //! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
@@ -11,23 +11,21 @@
 //! - **RGB**: (height, width, 3) - computes luminance from RGB
 //! - **RGBA**: (height, width, 4) - computes luminance from RGB, preserves alpha
 //!
-//! Output is always grayscale (same value for all color channels).
-
-use ndarray::{Array3, ArrayView3};
+//! Output is grayscale (same value for all color channels), except for
+//! [`sobel_per_channel_f32`] which keeps the channels apart.
+//!
+//! Luminance uses BT.709 coefficients; the `*_with_luma` variants and
+//! `find_edges_*_with_progress` take a [`LumaStandard`] instead.
 
-// Luminosity coefficients (matching skimage.color.rgb2gray exactly)
-const LUMA_R: f32 = 0.2125;
-const LUMA_G: f32 = 0.7154;
-const LUMA_B: f32 = 0.0721;
+use ndarray::{Array2, Array3, ArrayView3};
 
-// f64 versions for high-precision edge detection
-const LUMA_R_F64: f64 = 0.2125;
-const LUMA_G_F64: f64 = 0.7154;
-const LUMA_B_F64: f64 = 0.0721;
+use super::blur_wasm::gaussian_blur_wasm_f32;
+use super::grayscale::{grayscale_to_single_channel_f32, grayscale_to_single_channel_weighted_f32, LumaStandard};
+use crate::progress::{no_progress, report, Cancelled};
 
 /// Get luminance from pixel (normalized to 0-1) with reflect padding at borders
 #[inline]
-fn get_lum_u8_reflect(input: &ArrayView3<u8>, y: i32, x: i32, height: usize, width: usize, channels: usize) -> f32 {
+fn get_lum_u8_reflect(input: &ArrayView3<u8>, y: i32, x: i32, height: usize, width: usize, channels: usize, luma: LumaStandard) -> f32 {
     // Reflect mode: (d c b a | a b c d | d c b a)
     let ry = reflect_index(y, height);
     let rx = reflect_index(x, width);
@@ -38,7 +36,7 @@ fn get_lum_u8_reflect(input: &ArrayView3<u8>, y: i32, x: i32, height: usize, wid
         let r = input[[ry, rx, 0]] as f32 / 255.0;
         let g = input[[ry, rx, 1]] as f32 / 255.0;
         let b = input[[ry, rx, 2]] as f32 / 255.0;
-        LUMA_R * r + LUMA_G * g + LUMA_B * b
+        luma.luma(r, g, b)
     }
 }
 
@@ -57,37 +55,36 @@ fn reflect_index(i: i32, size: usize) -> usize {
 
 /// Get luminance from pixel (normalized to 0-1)
 #[inline]
-fn get_lum_u8(input: &ArrayView3<u8>, y: usize, x: usize, channels: usize) -> f32 {
+fn get_lum_u8(input: &ArrayView3<u8>, y: usize, x: usize, channels: usize, luma: LumaStandard) -> f32 {
     if channels == 1 {
         input[[y, x, 0]] as f32 / 255.0
     } else {
         let r = input[[y, x, 0]] as f32 / 255.0;
         let g = input[[y, x, 1]] as f32 / 255.0;
         let b = input[[y, x, 2]] as f32 / 255.0;
-        LUMA_R * r + LUMA_G * g + LUMA_B * b
+        luma.luma(r, g, b)
     }
 }
 
 #[inline]
-fn get_lum_f32(input: &ArrayView3<f32>, y: usize, x: usize, channels: usize) -> f32 {
+fn get_lum_f32(input: &ArrayView3<f32>, y: usize, x: usize, channels: usize, luma: LumaStandard) -> f32 {
     if channels == 1 {
         input[[y, x, 0]]
     } else {
-        LUMA_R * input[[y, x, 0]] + LUMA_G * input[[y, x, 1]] + LUMA_B * input[[y, x, 2]]
+        luma.luma(input[[y, x, 0]], input[[y, x, 1]], input[[y, x, 2]])
     }
 }
 
-/// Get luminance from pixel (f32) with reflect padding at borders
-#[inline]
-fn get_lum_f32_reflect(input: &ArrayView3<f32>, y: i32, x: i32, height: usize, width: usize, channels: usize) -> f32 {
-    let ry = reflect_index(y, height);
-    let rx = reflect_index(x, width);
+/// Luminance plane (height, width, 1) of an f32 image, read by the f32
+/// kernels instead of recomputing the luminance for every kernel tap.
+fn luma_plane_f32(input: ArrayView3<f32>, luma: LumaStandard) -> Array3<f32> {
+    grayscale_to_single_channel_weighted_f32(input, luma.into())
+}
 
-    if channels == 1 {
-        input[[ry, rx, 0]]
-    } else {
-        LUMA_R * input[[ry, rx, 0]] + LUMA_G * input[[ry, rx, 1]] + LUMA_B * input[[ry, rx, 2]]
-    }
+/// Sample a luminance plane with reflect padding at borders
+#[inline]
+fn get_plane_reflect(plane: &Array3<f32>, y: i32, x: i32, height: usize, width: usize) -> f32 {
+    plane[[reflect_index(y, height), reflect_index(x, width), 0]]
 }
 
 /// Get alpha from pixel (u8, normalized to 0-1) with reflect padding at borders
@@ -193,12 +190,22 @@ fn build_sobel_kernels(kernel_size: u8) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
-/// * `direction` - "h" for horizontal, "v" for vertical, "both" for magnitude
+/// * `direction` - "h" for horizontal, "v" for vertical, "both" for magnitude,
+///   "angle" for the gradient direction (-PI..PI mapped to 0..1, see [`sobel_direction_f32`])
 /// * `kernel_size` - Kernel size: 3, 5, or 7 (default 3)
 ///
 /// # Returns
 /// Edge-detected image with same channel count (grayscale values)
 pub fn sobel_u8(input: ArrayView3<u8>, direction: &str, kernel_size: u8) -> Array3<u8> {
+    sobel_u8_with_luma(input, direction, kernel_size, LumaStandard::default())
+}
+
+/// Apply Sobel edge detection with the given luma standard - u8 version.
+pub fn sobel_u8_with_luma(input: ArrayView3<u8>, direction: &str, kernel_size: u8, luma: LumaStandard) -> Array3<u8> {
+    if direction == "angle" {
+        let input_f32 = input.mapv(|v| v as f32 / 255.0);
+        return sobel_angle_image_f32(input_f32.view(), kernel_size, luma).mapv(|v| (v * 255.0).round() as u8);
+    }
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -221,7 +228,7 @@ pub fn sobel_u8(input: ArrayView3<u8>, direction: &str, kernel_size: u8) -> Arra
                 for kx in 0..ks {
                     let py = y as i32 + ky - half;
                     let px = x as i32 + kx - half;
-                    let lum = get_lum_u8_reflect(&input, py, px, height, width, channels);
+                    let lum = get_lum_u8_reflect(&input, py, px, height, width, channels, luma);
                     gh += lum * kernel_h[ky as usize][kx as usize];
                     gv += lum * kernel_v[ky as usize][kx as usize];
                 }
@@ -281,12 +288,21 @@ pub fn sobel_u8(input: ArrayView3<u8>, direction: &str, kernel_size: u8) -> Arra
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
-/// * `direction` - "h" for horizontal, "v" for vertical, "both" for magnitude
+/// * `direction` - "h" for horizontal, "v" for vertical, "both" for magnitude,
+///   "angle" for the gradient direction (-PI..PI mapped to 0..1, see [`sobel_direction_f32`])
 /// * `kernel_size` - Kernel size: 3, 5, or 7 (default 3)
 ///
 /// # Returns
 /// Edge-detected image with same channel count (grayscale values)
 pub fn sobel_f32(input: ArrayView3<f32>, direction: &str, kernel_size: u8) -> Array3<f32> {
+    sobel_f32_with_luma(input, direction, kernel_size, LumaStandard::default())
+}
+
+/// Apply Sobel edge detection with the given luma standard - f32 version.
+pub fn sobel_f32_with_luma(input: ArrayView3<f32>, direction: &str, kernel_size: u8, luma: LumaStandard) -> Array3<f32> {
+    if direction == "angle" {
+        return sobel_angle_image_f32(input, kernel_size, luma);
+    }
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -296,6 +312,7 @@ pub fn sobel_f32(input: ArrayView3<f32>, direction: &str, kernel_size: u8) -> Ar
 
     let sqrt_ndim = std::f32::consts::SQRT_2;
     let color_channels = if channels == 4 { 3 } else { channels };
+    let lum_plane = luma_plane_f32(input, luma);
 
     // Process ALL pixels using reflect padding at borders
     for y in 0..height {
@@ -307,7 +324,7 @@ pub fn sobel_f32(input: ArrayView3<f32>, direction: &str, kernel_size: u8) -> Ar
                 for kx in 0..ks {
                     let py = y as i32 + ky - half;
                     let px = x as i32 + kx - half;
-                    let lum = get_lum_f32_reflect(&input, py, px, height, width, channels);
+                    let lum = get_plane_reflect(&lum_plane, py, px, height, width);
                     gh += lum * kernel_h[ky as usize][kx as usize];
                     gv += lum * kernel_v[ky as usize][kx as usize];
                 }
@@ -357,6 +374,127 @@ pub fn sobel_f32(input: ArrayView3<f32>, direction: &str, kernel_size: u8) -> Ar
     output
 }
 
+/// Sobel responses (sobel_h, sobel_v) at (y, x) of a plane read by `sample`
+/// with reflect padding.
+fn sobel_response<F>(sample: F, y: usize, x: usize, kernels: &(Vec<Vec<f32>>, Vec<Vec<f32>>)) -> (f32, f32)
+where
+    F: Fn(i32, i32) -> f32,
+{
+    let (kernel_h, kernel_v) = kernels;
+    let ks = kernel_h.len() as i32;
+    let half = (ks - 1) / 2;
+    let (mut gh, mut gv) = (0.0f32, 0.0f32);
+    for ky in 0..ks {
+        for kx in 0..ks {
+            let v = sample(y as i32 + ky - half, x as i32 + kx - half);
+            gh += v * kernel_h[ky as usize][kx as usize];
+            gv += v * kernel_v[ky as usize][kx as usize];
+        }
+    }
+    (gh, gv)
+}
+
+/// Luminance gradient (d/dx, d/dy) per pixel using Sobel kernels.
+///
+/// Image coordinates: x grows to the right, y grows downwards. Uses reflect
+/// padding at borders like [`sobel_f32`].
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `kernel_size` - Kernel size: 3, 5, or 7
+///
+/// # Returns
+/// (gradient_x, gradient_y), each (height, width)
+pub fn sobel_gradients_f32(input: ArrayView3<f32>, kernel_size: u8) -> (Array2<f32>, Array2<f32>) {
+    luma_gradients_f32(input, kernel_size, LumaStandard::default())
+}
+
+fn luma_gradients_f32(input: ArrayView3<f32>, kernel_size: u8, luma: LumaStandard) -> (Array2<f32>, Array2<f32>) {
+    let (height, width, _) = input.dim();
+    let kernels = build_sobel_kernels(kernel_size);
+    let lum_plane = luma_plane_f32(input, luma);
+    let mut gradient_x = Array2::<f32>::zeros((height, width));
+    let mut gradient_y = Array2::<f32>::zeros((height, width));
+    for y in 0..height {
+        for x in 0..width {
+            let sample = |py, px| get_plane_reflect(&lum_plane, py, px, height, width);
+            let (gh, gv) = sobel_response(sample, y, x, &kernels);
+            // sobel_h is top minus bottom, sobel_v is left minus right
+            gradient_x[[y, x]] = -gv;
+            gradient_y[[y, x]] = -gh;
+        }
+    }
+    (gradient_x, gradient_y)
+}
+
+/// Gradient direction per pixel in radians, `atan2(d/dy, d/dx)`.
+///
+/// Points towards increasing brightness, -PI to PI with y growing
+/// downwards (PI/2 = brighter below). Flat areas get 0.
+pub fn sobel_direction_f32(input: ArrayView3<f32>, kernel_size: u8) -> Array2<f32> {
+    let (gradient_x, gradient_y) = sobel_gradients_f32(input, kernel_size);
+    ndarray::Zip::from(&gradient_x).and(&gradient_y).map_collect(|&gx, &gy| gy.atan2(gx))
+}
+
+/// Gradient direction image: the angle mapped from -PI..PI to 0.0..1.0 in
+/// all color channels, alpha preserved.
+fn sobel_angle_image_f32(input: ArrayView3<f32>, kernel_size: u8, luma: LumaStandard) -> Array3<f32> {
+    let channels = input.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let (gradient_x, gradient_y) = luma_gradients_f32(input, kernel_size, luma);
+    let direction = ndarray::Zip::from(&gradient_x).and(&gradient_y).map_collect(|&gx, &gy| gy.atan2(gx));
+    let mut output = input.to_owned();
+    for ((y, x), &angle) in direction.indexed_iter() {
+        let v = (angle + std::f32::consts::PI) / std::f32::consts::TAU;
+        for c in 0..color_channels {
+            output[[y, x, c]] = v;
+        }
+    }
+    output
+}
+
+/// Apply Sobel edge detection to every color channel separately - f32 version.
+///
+/// Unlike [`sobel_f32`], colors are not collapsed to luminance, so edges
+/// between colors of equal brightness survive.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `direction` - "h" for horizontal, "v" for vertical, "both" for magnitude
+/// * `kernel_size` - Kernel size: 3, 5, or 7
+///
+/// # Returns
+/// Per-channel edge image with same channel count, alpha preserved
+pub fn sobel_per_channel_f32(input: ArrayView3<f32>, direction: &str, kernel_size: u8) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let kernels = build_sobel_kernels(kernel_size);
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = input.to_owned();
+
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..color_channels {
+                let sample = |py, px| input[[reflect_index(py, height), reflect_index(px, width), c]];
+                let (gh, gv) = sobel_response(sample, y, x, &kernels);
+                let edge = match direction {
+                    "h" => gh.abs(),
+                    "v" => gv.abs(),
+                    _ => (gh * gh + gv * gv).sqrt() / std::f32::consts::SQRT_2,
+                };
+                output[[y, x, c]] = edge.clamp(0.0, 1.0);
+            }
+        }
+    }
+    output
+}
+
+/// Apply Sobel edge detection to every color channel separately - u8 version.
+pub fn sobel_per_channel_u8(input: ArrayView3<u8>, direction: &str, kernel_size: u8) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let result = sobel_per_channel_f32(input_f32.view(), direction, kernel_size);
+    result.mapv(|v| (v * 255.0).round() as u8)
+}
+
 // ============================================================================
 // Laplacian Edge Detection
 // ============================================================================
@@ -411,6 +549,11 @@ fn build_laplacian_kernel(kernel_size: u8) -> (Vec<Vec<f32>>, usize) {
 /// # Returns
 /// Edge-detected image with same channel count (grayscale values)
 pub fn laplacian_u8(input: ArrayView3<u8>, kernel_size: u8) -> Array3<u8> {
+    laplacian_u8_with_luma(input, kernel_size, LumaStandard::default())
+}
+
+/// Apply Laplacian edge detection with the given luma standard - u8 version.
+pub fn laplacian_u8_with_luma(input: ArrayView3<u8>, kernel_size: u8, luma: LumaStandard) -> Array3<u8> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -430,7 +573,7 @@ pub fn laplacian_u8(input: ArrayView3<u8>, kernel_size: u8) -> Array3<u8> {
                 for kx in 0..ks as i32 {
                     let py = y as i32 + ky - half;
                     let px = x as i32 + kx - half;
-                    let lum = get_lum_u8_reflect(&input, py, px, height, width, channels);
+                    let lum = get_lum_u8_reflect(&input, py, px, height, width, channels, luma);
                     let kval = kernel[ky as usize][kx as usize];
                     sum += lum * kval;
                     if channels == 4 {
@@ -486,12 +629,18 @@ pub fn laplacian_u8(input: ArrayView3<u8>, kernel_size: u8) -> Array3<u8> {
 /// # Returns
 /// Edge-detected image with same channel count (grayscale values)
 pub fn laplacian_f32(input: ArrayView3<f32>, kernel_size: u8) -> Array3<f32> {
+    laplacian_f32_with_luma(input, kernel_size, LumaStandard::default())
+}
+
+/// Apply Laplacian edge detection with the given luma standard - f32 version.
+pub fn laplacian_f32_with_luma(input: ArrayView3<f32>, kernel_size: u8, luma: LumaStandard) -> Array3<f32> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
     let color_channels = if channels == 4 { 3 } else { channels };
     let (kernel, ks) = build_laplacian_kernel(kernel_size);
     let half = (ks as i32 - 1) / 2;
+    let lum_plane = luma_plane_f32(input, luma);
 
     // First pass: compute raw Laplacian values for ALL pixels with reflect padding
     let mut raw_values = vec![vec![0.0f32; width]; height];
@@ -505,7 +654,7 @@ pub fn laplacian_f32(input: ArrayView3<f32>, kernel_size: u8) -> Array3<f32> {
                 for kx in 0..ks as i32 {
                     let py = y as i32 + ky - half;
                     let px = x as i32 + kx - half;
-                    let lum = get_lum_f32_reflect(&input, py, px, height, width, channels);
+                    let lum = get_plane_reflect(&lum_plane, py, px, height, width);
                     let kval = kernel[ky as usize][kx as usize];
                     sum += lum * kval;
                     if channels == 4 {
@@ -654,16 +803,34 @@ fn gaussian_blur_canny_f64(gray: &[Vec<f64>], sigma: f64) -> Vec<Vec<f64>> {
 /// # Returns
 /// Edge-detected image with same channel count (binary: 0 or 255)
 pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high_threshold: f64) -> Array3<u8> {
+    find_edges_u8_with_progress(input, sigma, low_threshold, high_threshold, LumaStandard::default(), &mut no_progress)
+        .expect("never cancelled")
+}
+
+/// `find_edges_u8` with a luma standard, reporting progress (0.0-1.0);
+/// returning `false` cancels.
+///
+/// Hysteresis needs a data-dependent number of passes, so its share of the
+/// progress approaches 100% without a fixed step.
+pub fn find_edges_u8_with_progress(
+    input: ArrayView3<u8>,
+    sigma: f64,
+    low_threshold: f64,
+    high_threshold: f64,
+    luma: LumaStandard,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<Array3<u8>, Cancelled> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
     if height < 3 || width < 3 {
-        return output;
+        return Ok(output);
     }
 
     let color_channels = if channels == 4 { 3 } else { channels };
 
     // Convert to grayscale (0-1 range) using f64 precision throughout
+    let [luma_r, luma_g, luma_b] = luma.coefficients().map(f64::from);
     let mut gray = vec![vec![0.0f64; width]; height];
     for y in 0..height {
         for x in 0..width {
@@ -673,7 +840,7 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
                 let r = input[[y, x, 0]] as f64 / 255.0;
                 let g = input[[y, x, 1]] as f64 / 255.0;
                 let b = input[[y, x, 2]] as f64 / 255.0;
-                gray[y][x] = LUMA_R_F64 * r + LUMA_G_F64 * g + LUMA_B_F64 * b;
+                gray[y][x] = luma_r * r + luma_g * g + luma_b * b;
             }
         }
     }
@@ -694,6 +861,7 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
     // Gaussian blur with configurable sigma (constant mode, edge normalization)
     let blurred = gaussian_blur_canny_f64(&gray, sigma);
     let blurred_alpha = gray_alpha.as_ref().map(|a| gaussian_blur_canny_f64(a, sigma));
+    report(progress, 0.2)?;
 
     // Compute gradients using Sobel kernels
     // axis=0 (isobel): [[-1, -2, -1], [0, 0, 0], [1, 2, 1]] (row gradient)
@@ -707,6 +875,7 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
 
     // Compute gradients with reflect padding (matching scipy.ndimage default)
     for y in 0..height {
+        report(progress, 0.2 + 0.4 * y as f32 / height as f32)?;
         for x in 0..width {
             let mut gi = 0.0f64;
             let mut gj = 0.0f64;
@@ -818,6 +987,8 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
         }
     }
 
+    report(progress, 0.7)?;
+
     // Apply thresholding AFTER NMS
     let mut low_mask = vec![vec![false; width]; height];
     let mut high_mask = vec![vec![false; width]; height];
@@ -842,7 +1013,10 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
 
     // Propagate edges from high to connected low pixels
     let mut changed = true;
+    let mut passes = 0;
     while changed {
+        report(progress, 1.0 - 0.3 / (passes + 1) as f32)?;
+        passes += 1;
         changed = false;
         for y in 1..height - 1 {
             for x in 1..width - 1 {
@@ -884,7 +1058,8 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
         }
     }
 
-    output
+    report(progress, 1.0)?;
+    Ok(output)
 }
 
 /// Find edges using Canny edge detection - f32 version.
@@ -900,16 +1075,34 @@ pub fn find_edges_u8(input: ArrayView3<u8>, sigma: f64, low_threshold: f64, high
 /// # Returns
 /// Edge-detected image with same channel count (binary: 0.0 or 1.0)
 pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, high_threshold: f64) -> Array3<f32> {
+    find_edges_f32_with_progress(input, sigma, low_threshold, high_threshold, LumaStandard::default(), &mut no_progress)
+        .expect("never cancelled")
+}
+
+/// `find_edges_f32` with a luma standard, reporting progress (0.0-1.0);
+/// returning `false` cancels.
+///
+/// Hysteresis needs a data-dependent number of passes, so its share of the
+/// progress approaches 100% without a fixed step.
+pub fn find_edges_f32_with_progress(
+    input: ArrayView3<f32>,
+    sigma: f64,
+    low_threshold: f64,
+    high_threshold: f64,
+    luma: LumaStandard,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<Array3<f32>, Cancelled> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
     if height < 3 || width < 3 {
-        return output;
+        return Ok(output);
     }
 
     let color_channels = if channels == 4 { 3 } else { channels };
 
     // Convert to grayscale using f64 precision throughout
+    let [luma_r, luma_g, luma_b] = luma.coefficients().map(f64::from);
     let mut gray = vec![vec![0.0f64; width]; height];
     for y in 0..height {
         for x in 0..width {
@@ -919,7 +1112,7 @@ pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, hi
                 let r = input[[y, x, 0]] as f64;
                 let g = input[[y, x, 1]] as f64;
                 let b = input[[y, x, 2]] as f64;
-                gray[y][x] = LUMA_R_F64 * r + LUMA_G_F64 * g + LUMA_B_F64 * b;
+                gray[y][x] = luma_r * r + luma_g * g + luma_b * b;
             }
         }
     }
@@ -940,6 +1133,7 @@ pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, hi
     // Gaussian blur with configurable sigma (constant mode, edge normalization)
     let blurred = gaussian_blur_canny_f64(&gray, sigma);
     let blurred_alpha = gray_alpha.as_ref().map(|a| gaussian_blur_canny_f64(a, sigma));
+    report(progress, 0.2)?;
 
     // Compute gradients using Sobel kernels
     let kernel_i: [[f64; 3]; 3] = [[-1.0, -2.0, -1.0], [0.0, 0.0, 0.0], [1.0, 2.0, 1.0]];
@@ -950,6 +1144,7 @@ pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, hi
     let mut magnitude = vec![vec![0.0f64; width]; height];
 
     for y in 0..height {
+        report(progress, 0.2 + 0.4 * y as f32 / height as f32)?;
         for x in 0..width {
             let mut gi = 0.0f64;
             let mut gj = 0.0f64;
@@ -1057,6 +1252,8 @@ pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, hi
         }
     }
 
+    report(progress, 0.7)?;
+
     // Apply thresholding AFTER NMS
     let mut low_mask = vec![vec![false; width]; height];
     let mut high_mask = vec![vec![false; width]; height];
@@ -1077,7 +1274,10 @@ pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, hi
 
     let mut edges = high_mask.clone();
     let mut changed = true;
+    let mut passes = 0;
     while changed {
+        report(progress, 1.0 - 0.3 / (passes + 1) as f32)?;
+        passes += 1;
         changed = false;
         for y in 1..height - 1 {
             for x in 1..width - 1 {
@@ -1118,7 +1318,8 @@ pub fn find_edges_f32(input: ArrayView3<f32>, sigma: f64, low_threshold: f64, hi
         }
     }
 
-    output
+    report(progress, 1.0)?;
+    Ok(output)
 }
 
 // ============================================================================
@@ -1270,7 +1471,7 @@ pub fn draw_contours_u8(
                 let r = input[[y, x, 0]] as f32;
                 let g = input[[y, x, 1]] as f32;
                 let b = input[[y, x, 2]] as f32;
-                (LUMA_R * r + LUMA_G * g + LUMA_B * b).round() as u8
+                LumaStandard::default().luma(r, g, b).round() as u8
             };
             binary[y][x] = lum >= threshold_val;
         }
@@ -1351,7 +1552,7 @@ pub fn draw_contours_f32(
     let mut binary = vec![vec![false; width]; height];
     for y in 0..height {
         for x in 0..width {
-            binary[y][x] = get_lum_f32(&input, y, x, channels) >= threshold_val;
+            binary[y][x] = get_lum_f32(&input, y, x, channels, LumaStandard::default()) >= threshold_val;
         }
     }
 
@@ -1422,6 +1623,72 @@ pub fn draw_contours_f32(
     output
 }
 
+// ============================================================================
+// Difference of Gaussians
+// ============================================================================
+
+/// Luminance plane (height, width, 1) blurred with a Gaussian of `sigma`.
+pub(crate) fn blurred_luminance_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32> {
+    gaussian_blur_wasm_f32(grayscale_to_single_channel_f32(input).view(), sigma)
+}
+
+/// Difference of Gaussians edge map - f32 version.
+///
+/// Subtracts a wide Gaussian blur (`sigma2`) of the luminance from a
+/// narrow one (`sigma1`), a band-pass that responds to edges and lines of
+/// roughly the scale between both sigmas. The magnitude is normalized so
+/// the strongest response becomes 1.0 (like GIMP's Difference of Gaussians
+/// with "normalize").
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `sigma1` - Narrow blur sigma (e.g. 1.0)
+/// * `sigma2` - Wide blur sigma, larger than `sigma1` (e.g. 1.6 * sigma1)
+///
+/// # Returns
+/// Edge map with same channel count (grayscale values, alpha preserved)
+pub fn dog_f32(input: ArrayView3<f32>, sigma1: f32, sigma2: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let narrow = blurred_luminance_f32(input, sigma1);
+    let wide = blurred_luminance_f32(input, sigma2);
+    let diff = (&narrow - &wide).mapv(f32::abs);
+    let max = diff.iter().cloned().fold(0.0f32, f32::max);
+    let scale = if max > 1e-6 { 1.0 / max } else { 0.0 };
+
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = Array3::<f32>::zeros((height, width, channels));
+    for y in 0..height {
+        for x in 0..width {
+            let value = (diff[[y, x, 0]] * scale).clamp(0.0, 1.0);
+            for c in 0..color_channels {
+                output[[y, x, c]] = value;
+            }
+            if channels == 4 {
+                output[[y, x, 3]] = input[[y, x, 3]];
+            }
+        }
+    }
+    output
+}
+
+/// Difference of Gaussians edge map - u8 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `sigma1` - Narrow blur sigma
+/// * `sigma2` - Wide blur sigma
+///
+/// # Returns
+/// Edge map with same channel count (grayscale values, alpha preserved)
+pub fn dog_u8(input: ArrayView3<u8>, sigma1: f32, sigma2: f32) -> Array3<u8> {
+    let result = dog_f32(input.mapv(|v| v as f32 / 255.0).view(), sigma1, sigma2);
+    let mut output = result.mapv(|v| (v * 255.0).round() as u8);
+    if input.dim().2 == 4 {
+        output.index_axis_mut(ndarray::Axis(2), 3).assign(&input.index_axis(ndarray::Axis(2), 3));
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1500,6 +1767,45 @@ mod tests {
         assert!(result[[5, 5, 0]] > 0, "Sobel 7x7 should detect edge");
     }
 
+    #[test]
+    fn test_sobel_direction_and_per_channel() {
+        // Brighter to the right: gradient points along +x
+        let ramp = Array3::from_shape_fn((7, 7, 1), |(_, x, _)| x as f32 / 6.0);
+        let direction = sobel_direction_f32(ramp.view(), 3);
+        assert!(direction[[3, 3]].abs() < 1e-4);
+        // Brighter below: +PI/2
+        let ramp = Array3::from_shape_fn((7, 7, 1), |(y, _, _)| y as f32 / 6.0);
+        let direction = sobel_direction_f32(ramp.view(), 3);
+        assert!((direction[[3, 3]] - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+        let angle = sobel_f32(ramp.view(), "angle", 3);
+        assert!((angle[[3, 3, 0]] - 0.75).abs() < 1e-4);
+
+        // Red/green boundary tuned to equal luminance: invisible to the
+        // luminance Sobel, found per channel
+        let [luma_r, luma_g, _] = LumaStandard::default().coefficients();
+        let green = luma_r / luma_g;
+        let img = Array3::from_shape_fn((6, 6, 3), |(_, x, c)| match (x < 3, c) {
+            (true, 0) => 1.0,
+            (false, 1) => green,
+            _ => 0.0,
+        });
+        assert!(sobel_f32(img.view(), "both", 3)[[3, 3, 0]] < 1e-3);
+        let result = sobel_per_channel_f32(img.view(), "both", 3);
+        assert!(result[[3, 3, 0]] > 0.1 && result[[3, 3, 1]] > 0.1);
+        assert_eq!(result[[3, 3, 2]], 0.0);
+    }
+
+    #[test]
+    fn test_luma_standard_weights_edges() {
+        // Green step: BT.601 weights green less than BT.709
+        let img = Array3::from_shape_fn((6, 6, 3), |(_, x, c)| if x >= 3 && c == 1 { 255u8 } else { 0 });
+        let bt709 = sobel_u8(img.view(), "v", 3)[[3, 3, 0]];
+        let bt601 = sobel_u8_with_luma(img.view(), "v", 3, LumaStandard::Bt601)[[3, 3, 0]];
+        let bt2020 = sobel_u8_with_luma(img.view(), "v", 3, LumaStandard::Bt2020)[[3, 3, 0]];
+        assert!(bt601 < bt2020 && bt2020 < bt709, "{} {} {}", bt601, bt2020, bt709);
+        assert_eq!(sobel_u8_with_luma(img.view(), "v", 3, LumaStandard::Bt709)[[3, 3, 0]], bt709);
+    }
+
     #[test]
     fn test_laplacian_u8_flat_is_zero() {
         let mut img = Array3::<u8>::zeros((5, 5, 4));
@@ -1597,6 +1903,28 @@ mod tests {
     // Alpha-aware edge detection tests
     // ========================================================================
 
+    #[test]
+    fn test_find_edges_progress_and_cancel() {
+        let img = Array3::<u8>::from_shape_fn((24, 24, 3), |(y, x, _)| if x < 12 { 40 } else { 220 - y as u8 });
+        let mut reports = Vec::new();
+        let result = find_edges_u8_with_progress(img.view(), 1.0, 0.1, 0.2, LumaStandard::default(), &mut |f| {
+            reports.push(f);
+            true
+        });
+        assert_eq!(result.unwrap(), find_edges_u8(img.view(), 1.0, 0.1, 0.2));
+        assert!(reports.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(reports.last(), Some(&1.0));
+
+        // The report that cancels is the last one delivered
+        let mut reports = Vec::new();
+        let cancelled = find_edges_u8_with_progress(img.view(), 1.0, 0.1, 0.2, LumaStandard::default(), &mut |f| {
+            reports.push(f);
+            f < 0.4
+        });
+        assert_eq!(cancelled, Err(Cancelled));
+        assert_eq!(reports.iter().position(|&f| f >= 0.4), Some(reports.len() - 1));
+    }
+
     #[test]
     fn test_sobel_u8_detects_alpha_edge() {
         // Uniform black RGB, alpha goes 0→255 — edge should be detected
@@ -1684,4 +2012,16 @@ mod tests {
         let has_edge = (1..8).any(|y| (1..8).any(|x| result[[y, x, 0]] > 0));
         assert!(has_edge, "Find edges should detect alpha boundary edge");
     }
+
+    #[test]
+    fn test_dog_responds_to_edges_only() {
+        let img = Array3::from_shape_fn((12, 12, 4), |(_, x, c)| if c == 3 || x >= 6 { 255u8 } else { 0 });
+        let result = dog_u8(img.view(), 1.0, 1.6);
+        assert_eq!(result[[6, 0, 0]], 0);
+        assert!(result[[6, 5, 0]] > 128 || result[[6, 6, 0]] > 128);
+        assert_eq!(result[[6, 5, 3]], 255);
+
+        let flat = Array3::from_elem((5, 5, 1), 0.4f32);
+        assert!(dog_f32(flat.view(), 1.0, 2.0).iter().all(|&v| v == 0.0));
+    }
 }
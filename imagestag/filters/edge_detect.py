@@ -1,7 +1,7 @@
 """Edge detection filters with Rust backend.
 
 This module provides edge detection filters:
-- Sobel (horizontal, vertical, or combined)
+- Sobel (horizontal, vertical, combined, or gradient direction; luminance or per channel)
 - Laplacian
 - Find Edges (combined edge detection)
 
@@ -51,42 +51,52 @@ def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
 # Sobel
 # ============================================================================
 
-def sobel(image: np.ndarray, direction: str = "both", kernel_size: int = 3) -> np.ndarray:
+def sobel(image: np.ndarray, direction: str = "both", kernel_size: int = 3,
+          per_channel: bool = False) -> np.ndarray:
     """Apply Sobel edge detection (u8).
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
-        direction: "h" (horizontal), "v" (vertical), or "both" (combined)
+        direction: "h" (horizontal), "v" (vertical), "both" (combined), or
+            "angle" (gradient direction, -pi..pi mapped to the full value range)
         kernel_size: 3, 5, or 7 for kernel size
+        per_channel: Detect edges in every color channel instead of the luminance
 
     Returns:
         Edge-detected uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "sobel")
-    if direction not in ("h", "v", "both"):
-        raise ValueError(f"Direction must be 'h', 'v', or 'both', got {direction}")
+    if direction not in ("h", "v", "both", "angle"):
+        raise ValueError(f"Direction must be 'h', 'v', 'both', or 'angle', got {direction}")
+    if per_channel and direction == "angle":
+        raise ValueError("Direction 'angle' is not available per channel")
     if kernel_size not in (3, 5, 7):
         raise ValueError(f"Kernel size must be 3, 5, or 7, got {kernel_size}")
-    return imagestag_rust.sobel(image, direction, kernel_size)
+    return imagestag_rust.sobel(image, direction, kernel_size, per_channel)
 
 
-def sobel_f32(image: np.ndarray, direction: str = "both", kernel_size: int = 3) -> np.ndarray:
+def sobel_f32(image: np.ndarray, direction: str = "both", kernel_size: int = 3,
+              per_channel: bool = False) -> np.ndarray:
     """Apply Sobel edge detection (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
-        direction: "h" (horizontal), "v" (vertical), or "both" (combined)
+        direction: "h" (horizontal), "v" (vertical), "both" (combined), or
+            "angle" (gradient direction, -pi..pi mapped to the full value range)
         kernel_size: 3, 5, or 7 for kernel size
+        per_channel: Detect edges in every color channel instead of the luminance
 
     Returns:
         Edge-detected float32 array with same channel count
     """
     _validate_image(image, np.float32, "sobel_f32")
-    if direction not in ("h", "v", "both"):
-        raise ValueError(f"Direction must be 'h', 'v', or 'both', got {direction}")
+    if direction not in ("h", "v", "both", "angle"):
+        raise ValueError(f"Direction must be 'h', 'v', 'both', or 'angle', got {direction}")
+    if per_channel and direction == "angle":
+        raise ValueError("Direction 'angle' is not available per channel")
     if kernel_size not in (3, 5, 7):
         raise ValueError(f"Kernel size must be 3, 5, or 7, got {kernel_size}")
-    return imagestag_rust.sobel_f32(image, direction, kernel_size)
+    return imagestag_rust.sobel_f32(image, direction, kernel_size, per_channel)
 
 
 # ============================================================================
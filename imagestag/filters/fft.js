@@ -0,0 +1,116 @@
+/**
+ * FFT and frequency-domain filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - fft.rs (Rust implementation)
+ * - fft.py (Python wrapper)
+ *
+ * Provides: fftForward, fftInverse, fft_lowpass, fft_highpass, fft_notch_filter
+ */
+
+import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Transform
+// ============================================================================
+
+/**
+ * Forward 2D FFT of luminance (or each color channel).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @param {Object} options - {perChannel: boolean}
+ * @returns {Object} - Spectrum {data: Float32Array of interleaved (re, im), width, height, channels}
+ */
+export function fftForward(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.fft_forward_wasm(data, width, height, channels, options.perChannel ?? false);
+    return {
+        data: new Float32Array(result.buffer),
+        width,
+        height,
+        channels: result.length / (width * height * 2)
+    };
+}
+
+/**
+ * Inverse 2D FFT of a spectrum from fftForward.
+ * @param {Object} spectrum - {data: Float32Array of interleaved (re, im), width, height, channels}
+ * @returns {Object} - Image data {data: Float32Array, width, height, channels}
+ */
+export function fftInverse(spectrum) {
+    const { data, width, height, channels } = spectrum;
+    const result = wasm.fft_inverse_wasm(data, width, height, channels);
+    return { data: new Float32Array(result.buffer), width, height, channels };
+}
+
+// ============================================================================
+// Low-pass / High-pass
+// ============================================================================
+
+/**
+ * Gaussian low-pass (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {cutoff: number (fraction of Nyquist, 0-1)}
+ * @returns {Object} - Filtered image data
+ */
+export const fft_lowpass = createU8Filter(
+    wasm.fft_lowpass_wasm,
+    (opts) => [opts.cutoff ?? 0.25]
+);
+
+/**
+ * Gaussian low-pass (f32).
+ */
+export const fft_lowpass_f32 = createF32Filter(
+    wasm.fft_lowpass_f32_wasm,
+    (opts) => [opts.cutoff ?? 0.25]
+);
+
+/**
+ * Gaussian high-pass (u8), centered on 128.
+ */
+export const fft_highpass = createU8Filter(
+    wasm.fft_highpass_wasm,
+    (opts) => [opts.cutoff ?? 0.1]
+);
+
+/**
+ * Gaussian high-pass (f32), centered on 0.0.
+ */
+export const fft_highpass_f32 = createF32Filter(
+    wasm.fft_highpass_f32_wasm,
+    (opts) => [opts.cutoff ?? 0.1]
+);
+
+// ============================================================================
+// Notch Filter
+// ============================================================================
+
+/**
+ * Suppress spectrum peaks to remove periodic patterns (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {notches: [[u, v, radius], ...] in cycles per image}
+ * @returns {Object} - Filtered image data
+ */
+export const fft_notch_filter = createU8Filter(
+    wasm.fft_notch_filter_wasm,
+    (opts) => [new Float32Array((opts.notches ?? []).flat())]
+);
+
+/**
+ * Suppress spectrum peaks (f32).
+ */
+export const fft_notch_filter_f32 = createF32Filter(
+    wasm.fft_notch_filter_f32_wasm,
+    (opts) => [new Float32Array((opts.notches ?? []).flat())]
+);
+
+export default {
+    initWasm,
+    fftForward, fftInverse,
+    fft_lowpass, fft_lowpass_f32,
+    fft_highpass, fft_highpass_f32,
+    fft_notch_filter, fft_notch_filter_f32
+};
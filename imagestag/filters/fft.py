@@ -0,0 +1,182 @@
+"""FFT and frequency-domain filtering with Rust backend.
+
+This module transforms images to the frequency domain and filters them there:
+- Forward/inverse 2D FFT of luminance or each color channel
+- Gaussian low-pass and high-pass
+- Notch filter: suppress isolated spectrum peaks to remove periodic
+  patterns (halftone rasters in scans, screen-door, stripes)
+
+Spectra follow ``numpy.fft.fft2`` conventions (unshifted, unnormalized), so
+``np.fft.fftshift`` gives the usual centered display. Notch positions are
+offsets from DC in that centered display, in cycles per image.
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels. Color channels are
+filtered independently, alpha is preserved. The FFT itself is float32 only.
+
+Co-located with:
+- fft.rs (Rust implementation)
+- fft.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.fft import fft_forward, fft_notch_filter
+
+    spectrum = np.fft.fftshift(np.abs(fft_forward(image_f32))[..., 0])
+    clean = fft_notch_filter(scan, [(64, 0, 3), (0, 64, 3)])
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+def _to_notches(notches) -> list:
+    """Normalize notches to a list of (u, v, radius) float tuples."""
+    result = []
+    for notch in notches:
+        if len(notch) != 3:
+            raise ValueError(f"fft_notch_filter: notch must be (u, v, radius), got {notch}")
+        result.append(tuple(float(v) for v in notch))
+    return result
+
+
+# ============================================================================
+# Transform
+# ============================================================================
+
+def fft_forward(image: np.ndarray, per_channel: bool = False) -> np.ndarray:
+    """Forward 2D FFT of an image.
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        per_channel: Transform each color channel instead of the luminance
+
+    Returns:
+        complex64 spectrum (H, W, 1) for luminance, (H, W, 1|3) per channel
+    """
+    _validate_image(image, np.float32, "fft_forward")
+    return imagestag_rust.fft_forward(image, per_channel)
+
+
+def fft_inverse(spectrum: np.ndarray) -> np.ndarray:
+    """Inverse 2D FFT of every channel of a spectrum.
+
+    Args:
+        spectrum: complex64 array (H, W, C), e.g. from fft_forward
+
+    Returns:
+        float32 array (H, W, C) (real part)
+    """
+    if spectrum.ndim != 3:
+        raise ValueError(f"fft_inverse: expected spectrum (H, W, C), got shape {spectrum.shape}")
+    return imagestag_rust.fft_inverse(spectrum.astype(np.complex64, copy=False))
+
+
+# ============================================================================
+# Low-pass / High-pass
+# ============================================================================
+
+def fft_lowpass(image: np.ndarray, cutoff: float = 0.25) -> np.ndarray:
+    """Gaussian low-pass (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        cutoff: Frequency of 0.5 gain as a fraction of Nyquist (0.0-1.0)
+
+    Returns:
+        Filtered uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "fft_lowpass")
+    return imagestag_rust.fft_lowpass(image, cutoff)
+
+
+def fft_lowpass_f32(image: np.ndarray, cutoff: float = 0.25) -> np.ndarray:
+    """Gaussian low-pass (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        cutoff: Frequency of 0.5 gain as a fraction of Nyquist (0.0-1.0)
+
+    Returns:
+        Filtered float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "fft_lowpass_f32")
+    return imagestag_rust.fft_lowpass_f32(image, cutoff)
+
+
+def fft_highpass(image: np.ndarray, cutoff: float = 0.1) -> np.ndarray:
+    """Gaussian high-pass (u8), centered on 128.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        cutoff: Frequency of 0.5 gain as a fraction of Nyquist (0.0-1.0)
+
+    Returns:
+        Detail uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "fft_highpass")
+    return imagestag_rust.fft_highpass(image, cutoff)
+
+
+def fft_highpass_f32(image: np.ndarray, cutoff: float = 0.1) -> np.ndarray:
+    """Gaussian high-pass (f32), centered on 0.0.
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        cutoff: Frequency of 0.5 gain as a fraction of Nyquist (0.0-1.0)
+
+    Returns:
+        Detail float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "fft_highpass_f32")
+    return imagestag_rust.fft_highpass_f32(image, cutoff)
+
+
+# ============================================================================
+# Notch Filter
+# ============================================================================
+
+def fft_notch_filter(image: np.ndarray, notches) -> np.ndarray:
+    """Remove periodic patterns by suppressing spectrum peaks (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        notches: (u, v, radius) tuples in cycles per image; u along the
+            width, v along the height, offset from DC. The mirrored peak
+            (-u, -v) is suppressed too.
+
+    Returns:
+        Filtered uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "fft_notch_filter")
+    return imagestag_rust.fft_notch_filter(image, _to_notches(notches))
+
+
+def fft_notch_filter_f32(image: np.ndarray, notches) -> np.ndarray:
+    """Remove periodic patterns by suppressing spectrum peaks (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        notches: (u, v, radius) tuples in cycles per image
+
+    Returns:
+        Filtered float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "fft_notch_filter_f32")
+    return imagestag_rust.fft_notch_filter_f32(image, _to_notches(notches))
+
+
+__all__ = [
+    'fft_forward', 'fft_inverse',
+    'fft_lowpass', 'fft_lowpass_f32',
+    'fft_highpass', 'fft_highpass_f32',
+    'fft_notch_filter', 'fft_notch_filter_f32',
+]
@@ -0,0 +1,500 @@
+//! 2D FFT and frequency-domain filters: low-pass, high-pass, notch.
+//!
+//! Periodic structure - halftone rasters in scans, screen-door and moiré
+//! patterns, scanner stripes - shows up as isolated bright peaks in the
+//! spectrum. Suppressing those peaks (notch filter) removes the pattern
+//! while leaving the image content almost untouched.
+//!
+//! The transform is implemented here without external dependencies:
+//! iterative radix-2 for power-of-two lengths, Bluestein's chirp-z algorithm
+//! for all other lengths, so any image size works. Rows and columns are
+//! transformed in parallel.
+//!
+//! ## Conventions
+//!
+//! - Spectra are unshifted and unnormalized like NumPy's `fft2`: DC sits at
+//!   `[0, 0]`, frequency `(v, u)` at `[v mod H, u mod W]`, and the inverse
+//!   divides by `H * W`.
+//! - Filter frequencies are normalized: `cutoff` is a fraction of Nyquist
+//!   (1.0 = 0.5 cycles per pixel); notch positions are in cycles per image
+//!   (the offset from DC in a centered spectrum display).
+//! - The filters mirror the image to twice its size before transforming, so
+//!   opposite borders don't bleed into each other.
+//!
+//! ## Supported Formats
+//!
+//! All filters accept images with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - processes the single channel
+//! - **RGB**: (height, width, 3) - processes each channel on its own
+//! - **RGBA**: (height, width, 4) - processes RGB, preserves alpha
+
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3, Axis};
+use rayon::prelude::*;
+
+use crate::filters::grayscale::LumaStandard;
+
+// ============================================================================
+// Complex Numbers
+// ============================================================================
+
+/// Single-precision complex number.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// Complex conjugate.
+    #[inline]
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Magnitude `|z|`.
+    #[inline]
+    pub fn norm(self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    /// `e^(i angle)`, evaluated in f64 so large twiddle tables stay accurate.
+    fn from_angle(angle: f64) -> Self {
+        Self::new(angle.cos() as f32, angle.sin() as f32)
+    }
+
+    #[inline]
+    fn scale(self, s: f32) -> Self {
+        Self::new(self.re * s, self.im * s)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    #[inline]
+    fn add(self, o: Self) -> Self {
+        Self::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    #[inline]
+    fn sub(self, o: Self) -> Self {
+        Self::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    #[inline]
+    fn mul(self, o: Self) -> Self {
+        Self::new(self.re * o.re - self.im * o.im, self.re * o.im + self.im * o.re)
+    }
+}
+
+// ============================================================================
+// 1D FFT
+// ============================================================================
+
+/// Precomputed tables for transforms of one length.
+#[derive(Debug, Clone)]
+pub struct FftPlan {
+    len: usize,
+    kind: PlanKind,
+}
+
+#[derive(Debug, Clone)]
+enum PlanKind {
+    /// Power-of-two length: twiddles `e^(-2πik/n)` for `k < n/2`
+    Radix2 { twiddles: Vec<Complex> },
+    /// Any other length, as a circular convolution of power-of-two length
+    Bluestein {
+        inner: Box<FftPlan>,
+        /// `e^(-iπk²/n)` for `k < n`
+        chirp: Vec<Complex>,
+        /// Forward transform of the conjugate chirp, pre-divided by the inner length
+        kernel: Vec<Complex>,
+    },
+}
+
+impl FftPlan {
+    /// Plan transforms of `len` points (`len` >= 1).
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "FFT length must be positive");
+        if len.is_power_of_two() {
+            let twiddles = (0..len / 2).map(|k| Complex::from_angle(-2.0 * PI * k as f64 / len as f64)).collect();
+            return Self { len, kind: PlanKind::Radix2 { twiddles } };
+        }
+
+        let m = (2 * len - 1).next_power_of_two();
+        let inner = FftPlan::new(m);
+        // k² mod 2n keeps the angle small and exact for large k
+        let chirp: Vec<Complex> = (0..len)
+            .map(|k| Complex::from_angle(-PI * ((k * k) % (2 * len)) as f64 / len as f64))
+            .collect();
+        let mut kernel = vec![Complex::default(); m];
+        kernel[0] = chirp[0].conj();
+        for k in 1..len {
+            kernel[k] = chirp[k].conj();
+            kernel[m - k] = chirp[k].conj();
+        }
+        inner.forward(&mut kernel);
+        let inv_m = 1.0 / m as f32;
+        kernel.iter_mut().for_each(|v| *v = v.scale(inv_m));
+        Self { len, kind: PlanKind::Bluestein { inner: Box::new(inner), chirp, kernel } }
+    }
+
+    /// Forward transform in place (unnormalized).
+    pub fn forward(&self, buf: &mut [Complex]) {
+        assert_eq!(buf.len(), self.len, "buffer length must match the plan");
+        match &self.kind {
+            PlanKind::Radix2 { twiddles } => radix2(buf, twiddles),
+            PlanKind::Bluestein { inner, chirp, kernel } => {
+                let mut work = vec![Complex::default(); inner.len];
+                for (w, (&x, &c)) in work.iter_mut().zip(buf.iter().zip(chirp)) {
+                    *w = x * c;
+                }
+                inner.forward(&mut work);
+                // Inverse via conjugation; the 1/m is folded into the kernel
+                for (w, &k) in work.iter_mut().zip(kernel) {
+                    *w = (*w * k).conj();
+                }
+                inner.forward(&mut work);
+                for (x, (&w, &c)) in buf.iter_mut().zip(work.iter().zip(chirp)) {
+                    *x = w.conj() * c;
+                }
+            }
+        }
+    }
+
+    /// Inverse transform in place (unnormalized: divide by `len` yourself).
+    pub fn inverse(&self, buf: &mut [Complex]) {
+        buf.iter_mut().for_each(|v| *v = v.conj());
+        self.forward(buf);
+        buf.iter_mut().for_each(|v| *v = v.conj());
+    }
+}
+
+/// Iterative Cooley-Tukey on a power-of-two buffer.
+fn radix2(buf: &mut [Complex], twiddles: &[Complex]) {
+    let n = buf.len();
+    if n < 2 {
+        return;
+    }
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if j > i {
+            buf.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let u = buf[start + k];
+                let v = buf[start + k + half] * twiddles[k * step];
+                buf[start + k] = u + v;
+                buf[start + k + half] = u - v;
+            }
+        }
+        len *= 2;
+    }
+}
+
+// ============================================================================
+// 2D FFT
+// ============================================================================
+
+/// Transform a row-major (height, width) buffer in place along both axes.
+fn fft2_in_place(data: &mut [Complex], height: usize, width: usize, inverse: bool) {
+    let run = |plan: &FftPlan, line: &mut [Complex]| {
+        if inverse {
+            plan.inverse(line)
+        } else {
+            plan.forward(line)
+        }
+    };
+    let row_plan = FftPlan::new(width);
+    data.par_chunks_mut(width).for_each(|row| run(&row_plan, row));
+
+    let col_plan = FftPlan::new(height);
+    let mut columns = vec![Complex::default(); width * height];
+    for y in 0..height {
+        for x in 0..width {
+            columns[x * height + y] = data[y * width + x];
+        }
+    }
+    columns.par_chunks_mut(height).for_each(|col| run(&col_plan, col));
+    for y in 0..height {
+        for x in 0..width {
+            data[y * width + x] = columns[x * height + y];
+        }
+    }
+}
+
+/// Forward 2D FFT of a real plane (unshifted, unnormalized).
+pub fn fft2(input: ArrayView2<f32>) -> Array2<Complex> {
+    let (height, width) = input.dim();
+    if height == 0 || width == 0 {
+        return Array2::default((height, width));
+    }
+    let mut data: Vec<Complex> = input.iter().map(|&v| Complex::new(v, 0.0)).collect();
+    fft2_in_place(&mut data, height, width, false);
+    Array2::from_shape_vec((height, width), data).expect("spectrum matches input shape")
+}
+
+/// Inverse 2D FFT, returning the real part divided by `height * width`.
+pub fn ifft2(spectrum: ArrayView2<Complex>) -> Array2<f32> {
+    let (height, width) = spectrum.dim();
+    if height == 0 || width == 0 {
+        return Array2::zeros((height, width));
+    }
+    let mut data: Vec<Complex> = spectrum.iter().copied().collect();
+    fft2_in_place(&mut data, height, width, true);
+    let inv = 1.0 / (height * width) as f32;
+    Array2::from_shape_vec((height, width), data.iter().map(|v| v.re * inv).collect()).expect("plane matches spectrum shape")
+}
+
+/// Forward 2D FFT of an image.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `per_channel` - Transform each color channel (alpha excluded) instead
+///   of the BT.709 luminance
+///
+/// # Returns
+/// Spectrum (height, width, 1) for luminance, (height, width, 1 or 3) per channel
+pub fn fft_forward_f32(input: ArrayView3<f32>, per_channel: bool) -> Array3<Complex> {
+    let (height, width, channels) = input.dim();
+    let planes: Vec<Array2<f32>> = if per_channel || channels == 1 {
+        let color_channels = if channels == 4 { 3 } else { channels };
+        (0..color_channels).map(|c| input.index_axis(Axis(2), c).to_owned()).collect()
+    } else {
+        let luma = LumaStandard::Bt709;
+        vec![Array2::from_shape_fn((height, width), |(y, x)| {
+            luma.luma(input[[y, x, 0]], input[[y, x, 1]], input[[y, x, 2]])
+        })]
+    };
+    let mut output = Array3::default((height, width, planes.len()));
+    for (c, plane) in planes.iter().enumerate() {
+        output.index_axis_mut(Axis(2), c).assign(&fft2(plane.view()));
+    }
+    output
+}
+
+/// Inverse 2D FFT of every channel of a spectrum from [`fft_forward_f32`].
+pub fn fft_inverse_f32(spectrum: ArrayView3<Complex>) -> Array3<f32> {
+    let (height, width, channels) = spectrum.dim();
+    let mut output = Array3::zeros((height, width, channels));
+    for c in 0..channels {
+        output.index_axis_mut(Axis(2), c).assign(&ifft2(spectrum.index_axis(Axis(2), c)));
+    }
+    output
+}
+
+// ============================================================================
+// Frequency-Domain Filters
+// ============================================================================
+
+/// One notch of [`fft_notch_filter_f32`], in cycles per image.
+///
+/// `(u, v)` is the peak's offset from DC in a centered spectrum (u along
+/// the width, v along the height). The mirrored peak `(-u, -v)` that every
+/// real image has is removed as well.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Notch {
+    pub u: f32,
+    pub v: f32,
+    /// Radius (cycles per image) at which the gain is back to 0.5
+    pub radius: f32,
+}
+
+/// Smooth step from 1 at distance 0 to 0 far away, 0.5 at `radius`.
+#[inline]
+fn gaussian_gain(distance_sq: f32, radius: f32) -> f32 {
+    (-std::f32::consts::LN_2 * distance_sq / (radius * radius).max(f32::MIN_POSITIVE)).exp()
+}
+
+/// Filter each color channel by a real gain over the frequency plane.
+///
+/// `gain(fy, fx)` receives signed frequencies in cycles per pixel
+/// (-0.5..0.5). The channel is mirrored to (2H, 2W) first, so the implied
+/// periodic image has no seams.
+fn filter_f32<G>(input: ArrayView3<f32>, gain: G) -> Array3<f32>
+where
+    G: Fn(f32, f32) -> f32 + Sync,
+{
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if height == 0 || width == 0 {
+        return output;
+    }
+    let (ph, pw) = (2 * height, 2 * width);
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mirror = |i: usize, n: usize| if i < n { i } else { 2 * n - 1 - i };
+    let freq = |i: usize, n: usize| if i < n.div_ceil(2) { i as f32 / n as f32 } else { i as f32 / n as f32 - 1.0 };
+
+    for c in 0..color_channels {
+        let mut data: Vec<Complex> = (0..ph * pw)
+            .map(|i| Complex::new(input[[mirror(i / pw, height), mirror(i % pw, width), c]], 0.0))
+            .collect();
+        fft2_in_place(&mut data, ph, pw, false);
+        data.par_chunks_mut(pw).enumerate().for_each(|(y, row)| {
+            let fy = freq(y, ph);
+            for (x, v) in row.iter_mut().enumerate() {
+                *v = v.scale(gain(fy, freq(x, pw)));
+            }
+        });
+        fft2_in_place(&mut data, ph, pw, true);
+        let inv = 1.0 / (ph * pw) as f32;
+        for y in 0..height {
+            for x in 0..width {
+                output[[y, x, c]] = data[y * pw + x].re * inv;
+            }
+        }
+    }
+    output
+}
+
+/// Gaussian low-pass (blur) in the frequency domain - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `cutoff` - Frequency where the gain falls to 0.5, as a fraction of Nyquist (0.0-1.0)
+///
+/// # Returns
+/// Filtered image with same channel count, alpha preserved
+pub fn fft_lowpass_f32(input: ArrayView3<f32>, cutoff: f32) -> Array3<f32> {
+    let radius = cutoff * 0.5;
+    filter_f32(input, |fy, fx| gaussian_gain(fy * fy + fx * fx, radius))
+}
+
+/// Gaussian high-pass (detail) in the frequency domain - f32 version.
+///
+/// The complement of [`fft_lowpass_f32`]: keeps detail above `cutoff` and
+/// removes the smooth part, so the result is centered on 0.0 (add 0.5 to
+/// view it).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `cutoff` - Frequency where the gain rises to 0.5, as a fraction of Nyquist (0.0-1.0)
+///
+/// # Returns
+/// Filtered image with same channel count, alpha preserved
+pub fn fft_highpass_f32(input: ArrayView3<f32>, cutoff: f32) -> Array3<f32> {
+    let radius = cutoff * 0.5;
+    filter_f32(input, |fy, fx| 1.0 - gaussian_gain(fy * fy + fx * fx, radius))
+}
+
+/// Remove periodic patterns by suppressing spectrum peaks - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `notches` - Peaks to remove, in cycles per image of `input`
+///
+/// # Returns
+/// Filtered image with same channel count, alpha preserved
+pub fn fft_notch_filter_f32(input: ArrayView3<f32>, notches: &[Notch]) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    filter_f32(input, |fy, fx| {
+        // Cycles per pixel -> cycles per image
+        let (u, v) = (fx * width as f32, fy * height as f32);
+        notches.iter().fold(1.0, |g, n| {
+            let near = (u - n.u).powi(2) + (v - n.v).powi(2);
+            let mirrored = (u + n.u).powi(2) + (v + n.v).powi(2);
+            g * (1.0 - gaussian_gain(near, n.radius)) * (1.0 - gaussian_gain(mirrored, n.radius))
+        })
+    })
+}
+
+/// Gaussian low-pass - u8 version (see [`fft_lowpass_f32`]).
+pub fn fft_lowpass_u8(input: ArrayView3<u8>, cutoff: f32) -> Array3<u8> {
+    to_u8(fft_lowpass_f32(to_f32(input).view(), cutoff))
+}
+
+/// Gaussian high-pass - u8 version, centered on 128 (see [`fft_highpass_f32`]).
+pub fn fft_highpass_u8(input: ArrayView3<u8>, cutoff: f32) -> Array3<u8> {
+    let mut result = fft_highpass_f32(to_f32(input).view(), cutoff);
+    let color_channels = if input.dim().2 == 4 { 3 } else { input.dim().2 };
+    result.axis_iter_mut(Axis(2)).take(color_channels).for_each(|mut c| c += 0.5);
+    to_u8(result)
+}
+
+/// Notch filter - u8 version (see [`fft_notch_filter_f32`]).
+pub fn fft_notch_filter_u8(input: ArrayView3<u8>, notches: &[Notch]) -> Array3<u8> {
+    to_u8(fft_notch_filter_f32(to_f32(input).view(), notches))
+}
+
+fn to_f32(input: ArrayView3<u8>) -> Array3<f32> {
+    input.mapv(|v| v as f32 / 255.0)
+}
+
+fn to_u8(input: Array3<f32>) -> Array3<u8> {
+    input.mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_dft(x: &[Complex]) -> Vec<Complex> {
+        let n = x.len();
+        (0..n)
+            .map(|k| {
+                x.iter().enumerate().fold(Complex::default(), |acc, (j, &v)| {
+                    acc + v * Complex::from_angle(-2.0 * PI * (j * k) as f64 / n as f64)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fft_matches_dft_for_any_length() {
+        for n in [1, 2, 5, 8, 12, 17, 64, 100] {
+            let x: Vec<Complex> = (0..n).map(|i| Complex::new((i as f32 * 0.7).sin(), (i % 3) as f32 * 0.1)).collect();
+            let expected = naive_dft(&x);
+            let plan = FftPlan::new(n);
+            let mut y = x.clone();
+            plan.forward(&mut y);
+            for (a, b) in y.iter().zip(&expected) {
+                assert!((*a - *b).norm() < 1e-3 * n as f32, "n={}: {:?} vs {:?}", n, a, b);
+            }
+            plan.inverse(&mut y);
+            for (a, b) in y.iter().zip(&x) {
+                assert!((a.scale(1.0 / n as f32) - *b).norm() < 1e-4, "n={} roundtrip", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_notch_removes_raster_and_keeps_content() {
+        // Smooth ramp plus a vertical stripe raster of 8 cycles per image
+        let (h, w) = (24, 40);
+        let ramp = Array3::from_shape_fn((h, w, 1), |(y, _, _)| 0.3 + 0.4 * y as f32 / h as f32);
+        let raster = Array3::from_shape_fn((h, w, 1), |(_, x, _)| {
+            // Half-pixel phase: symmetric about the mirror axes at -0.5 and w - 0.5
+            0.1 * (2.0 * std::f32::consts::PI * 8.0 * (x as f32 + 0.5) / w as f32).cos()
+        });
+        let image = &ramp + &raster;
+
+        let spectrum = fft_forward_f32(image.view(), false);
+        assert!(spectrum[[0, 8, 0]].norm() > 0.04 * (h * w) as f32);
+
+        let result = fft_notch_filter_f32(image.view(), &[Notch { u: 8.0, v: 0.0, radius: 1.5 }]);
+        let err = (&result - &ramp).mapv(f32::abs);
+        assert!(err.iter().cloned().fold(0.0, f32::max) < 0.02);
+
+        let roundtrip = fft_inverse_f32(fft_forward_f32(image.view(), true).view());
+        assert!(roundtrip.iter().zip(image.iter()).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+}
@@ -0,0 +1,119 @@
+//! Transcendental functions for the core filter set, bit-exact across targets.
+//!
+//! Rust compiles `+`, `-`, `*`, `/` and `sqrt` to IEEE 754 operations that
+//! round identically on every target, and it never contracts `a * b + c`
+//! into a fused multiply-add on its own. What differs between a native
+//! build and WASM are `exp`, `powf`, `sin` and friends: `std` calls the
+//! platform's C math library (glibc, the MSVC CRT, ...), while
+//! `wasm32-unknown-unknown` uses a compiled-in port of musl's. Results can
+//! differ in the last bit, which a blur kernel or a gamma table then turns
+//! into u8 values that are off by one.
+//!
+//! With the `deterministic` feature these functions use the pure Rust
+//! [`libm`](https://docs.rs/libm) crate on every target instead. Without it
+//! they forward to `std`, which is faster on native targets.
+//!
+//! ## Guarantees (`deterministic` feature)
+//!
+//! For the core filter set - every filter of the batch pipeline
+//! ([`crate::batch::FilterSpec`]): color adjustments, gamma, exposure,
+//! levels, hue/vibrance/sepia/temperature, posterize, threshold, pixelate,
+//! vignette, sharpen, high pass, Gaussian and box blur, median, denoise,
+//! morphology, auto levels and thumbnails - u8 and f32 output is
+//! bit-identical between the Python extension on x86-64 / aarch64 and the
+//! WASM module, independent of the rayon thread count:
+//! - parallel loops only split the image into rows; every output value is
+//!   accumulated by one thread in a fixed order, never by a parallel
+//!   reduction
+//! - `mul_add` and explicit SIMD are not used in these filters
+//! - transcendental functions come from this module
+//!
+//! Other filters use `std` math directly and are deterministic per target
+//! only. The GPU paths ([`crate::gpu`]) are never bit-exact.
+
+/// Whether the crate was built with the `deterministic` feature.
+pub const DETERMINISTIC: bool = cfg!(feature = "deterministic");
+
+/// e^x
+#[inline]
+pub fn exp(x: f32) -> f32 {
+    #[cfg(feature = "deterministic")]
+    return libm::expf(x);
+    #[cfg(not(feature = "deterministic"))]
+    return x.exp();
+}
+
+/// Natural logarithm
+#[inline]
+pub fn ln(x: f32) -> f32 {
+    #[cfg(feature = "deterministic")]
+    return libm::logf(x);
+    #[cfg(not(feature = "deterministic"))]
+    return x.ln();
+}
+
+/// x^y
+#[inline]
+pub fn powf(x: f32, y: f32) -> f32 {
+    #[cfg(feature = "deterministic")]
+    return libm::powf(x, y);
+    #[cfg(not(feature = "deterministic"))]
+    return x.powf(y);
+}
+
+/// Sine (radians)
+#[inline]
+pub fn sin(x: f32) -> f32 {
+    #[cfg(feature = "deterministic")]
+    return libm::sinf(x);
+    #[cfg(not(feature = "deterministic"))]
+    return x.sin();
+}
+
+/// Cosine (radians)
+#[inline]
+pub fn cos(x: f32) -> f32 {
+    #[cfg(feature = "deterministic")]
+    return libm::cosf(x);
+    #[cfg(not(feature = "deterministic"))]
+    return x.cos();
+}
+
+/// Cube root
+#[inline]
+pub fn cbrt(x: f32) -> f32 {
+    #[cfg(feature = "deterministic")]
+    return libm::cbrtf(x);
+    #[cfg(not(feature = "deterministic"))]
+    return x.cbrt();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::{apply_pipeline_f32, parse_pipeline};
+    use ndarray::Array3;
+
+    #[test]
+    fn test_math_and_thread_independence() {
+        // Both implementations are correctly rounded or within an ulp
+        for x in [0.0f32, 0.25, 1.0, 2.5, -3.0] {
+            assert!((exp(x) - x.exp()).abs() <= f32::EPSILON * x.exp());
+            assert!((sin(x) - x.sin()).abs() <= f32::EPSILON);
+            assert!((cos(x) - x.cos()).abs() <= f32::EPSILON);
+            assert!((cbrt(x) - x.cbrt()).abs() <= f32::EPSILON * 2.0);
+        }
+        assert_eq!(powf(0.5, 2.0), 0.25);
+        assert_eq!(ln(1.0), 0.0);
+
+        // The same pipeline gives the same bits on 1 and 4 threads
+        let image = Array3::from_shape_fn((19, 23, 4), |(y, x, c)| ((x * 7 + y * 13 + c * 29) % 97) as f32 / 96.0);
+        let pipeline = parse_pipeline("blur 2.5 | gamma 1.8 | exposure 0.3 | denoise 0.2 | vignette 0.5 | thumbnail 10").unwrap();
+        let run = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| apply_pipeline_f32(image.view(), &pipeline))
+        };
+        let (single, multi) = (run(1), run(4));
+        assert!(single.iter().zip(multi.iter()).all(|(a, b)| a.to_bits() == b.to_bits()));
+    }
+}
@@ -0,0 +1,112 @@
+/**
+ * Glitch / VHS filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - glitch.rs (Rust implementation)
+ * - glitch.py (Python wrapper)
+ *
+ * Provides: rgb_split, scanlines, block_shift, wave_distort
+ */
+
+import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// RGB Split
+// ============================================================================
+
+const rgbSplitParams = (opts) => [opts.shift_x ?? 4, opts.shift_y ?? 0];
+
+/**
+ * Shift the red and blue channels in opposite directions (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {shift_x, shift_y}
+ * @returns {Object} - Filtered image data
+ */
+export const rgb_split = createU8Filter(wasm.rgb_split_wasm, rgbSplitParams);
+
+/**
+ * Shift the red and blue channels in opposite directions (f32).
+ */
+export const rgb_split_f32 = createF32Filter(wasm.rgb_split_f32_wasm, rgbSplitParams);
+
+// ============================================================================
+// Scanlines
+// ============================================================================
+
+const scanlinesOptions = (opts) => [Object.assign(new wasm.ScanlinesOptions(), {
+    spacing: opts.spacing ?? 3,
+    thickness: opts.thickness ?? 1,
+    intensity: opts.intensity ?? 0.3,
+})];
+
+/**
+ * Darken periodic rows like CRT / VHS scanlines (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {spacing, thickness, intensity}
+ * @returns {Object} - Filtered image data
+ */
+export const scanlines = createU8Filter(wasm.scanlines_wasm, scanlinesOptions);
+
+/**
+ * Darken periodic rows like CRT / VHS scanlines (f32).
+ */
+export const scanlines_f32 = createF32Filter(wasm.scanlines_f32_wasm, scanlinesOptions);
+
+// ============================================================================
+// Block Shift
+// ============================================================================
+
+const blockShiftOptions = (opts) => [Object.assign(new wasm.BlockShiftOptions(), {
+    block_height: opts.block_height ?? 8,
+    max_shift: opts.max_shift ?? 20,
+    probability: opts.probability ?? 0.2,
+    seed: opts.seed ?? 0,
+})];
+
+/**
+ * Displace random horizontal bands sideways (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {block_height, max_shift, probability, seed}
+ * @returns {Object} - Filtered image data
+ */
+export const block_shift = createU8Filter(wasm.block_shift_wasm, blockShiftOptions);
+
+/**
+ * Displace random horizontal bands sideways (f32).
+ */
+export const block_shift_f32 = createF32Filter(wasm.block_shift_f32_wasm, blockShiftOptions);
+
+// ============================================================================
+// Wave Distort
+// ============================================================================
+
+const waveDistortOptions = (opts) => [Object.assign(new wasm.WaveDistortOptions(), {
+    amplitude: opts.amplitude ?? 4.0,
+    wavelength: opts.wavelength ?? 64.0,
+    phase: opts.phase ?? 0.0,
+    jitter: opts.jitter ?? 0.0,
+    seed: opts.seed ?? 0,
+})];
+
+/**
+ * Shift rows along a sine wave plus random jitter (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amplitude, wavelength, phase, jitter, seed}
+ * @returns {Object} - Filtered image data
+ */
+export const wave_distort = createU8Filter(wasm.wave_distort_wasm, waveDistortOptions);
+
+/**
+ * Shift rows along a sine wave plus random jitter (f32).
+ */
+export const wave_distort_f32 = createF32Filter(wasm.wave_distort_f32_wasm, waveDistortOptions);
+
+export default {
+    initWasm,
+    rgb_split, rgb_split_f32,
+    scanlines, scanlines_f32,
+    block_shift, block_shift_f32,
+    wave_distort, wave_distort_f32
+};
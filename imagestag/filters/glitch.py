@@ -0,0 +1,156 @@
+"""Glitch / VHS filters with Rust backend.
+
+This module provides the primitives of the "glitch" look:
+- RGB split (chromatic channel offset)
+- Scanlines (periodic darkened rows)
+- Block shift (random horizontal band displacement)
+- Wave distort (sine row wobble with random jitter)
+
+Random choices are derived from the seed by hashing, so a given set of
+parameters renders identically here and in the browser (WASM).
+
+## Supported Formats
+
+All filters accept numpy arrays with 1, 3, or 4 channels (uint8 or float32)
+and return the same shape.
+
+Co-located with:
+- glitch.rs (Rust implementation)
+- glitch.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.glitch import rgb_split, scanlines, block_shift, wave_distort
+
+    result = rgb_split(image, shift_x=6)
+    result = block_shift(result, block_height=12, max_shift=30, probability=0.3, seed=7)
+    result = scanlines(result, spacing=3, intensity=0.25)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# RGB Split
+# ============================================================================
+
+def rgb_split(image: np.ndarray, shift_x: int = 4, shift_y: int = 0) -> np.ndarray:
+    """Shift the red and blue channels in opposite directions (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        shift_x: Horizontal red shift in pixels (blue moves the other way)
+        shift_y: Vertical red shift in pixels (blue moves the other way)
+
+    Returns:
+        uint8 array with same shape
+    """
+    _validate_image(image, np.uint8, "rgb_split")
+    return imagestag_rust.rgb_split(image, shift_x, shift_y)
+
+
+def rgb_split_f32(image: np.ndarray, shift_x: int = 4, shift_y: int = 0) -> np.ndarray:
+    """Shift the red and blue channels in opposite directions (f32)."""
+    _validate_image(image, np.float32, "rgb_split_f32")
+    return imagestag_rust.rgb_split_f32(image, shift_x, shift_y)
+
+
+# ============================================================================
+# Scanlines
+# ============================================================================
+
+def scanlines(image: np.ndarray, spacing: int = 3, thickness: int = 1, intensity: float = 0.3) -> np.ndarray:
+    """Darken periodic rows like CRT / VHS scanlines (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        spacing: Scanline period in rows
+        thickness: Dark rows per period
+        intensity: Darkening of the scanline rows (0.0 = none, 1.0 = black)
+
+    Returns:
+        uint8 array with same shape
+    """
+    _validate_image(image, np.uint8, "scanlines")
+    return imagestag_rust.scanlines(image, spacing, thickness, intensity)
+
+
+def scanlines_f32(image: np.ndarray, spacing: int = 3, thickness: int = 1, intensity: float = 0.3) -> np.ndarray:
+    """Darken periodic rows like CRT / VHS scanlines (f32)."""
+    _validate_image(image, np.float32, "scanlines_f32")
+    return imagestag_rust.scanlines_f32(image, spacing, thickness, intensity)
+
+
+# ============================================================================
+# Block Shift
+# ============================================================================
+
+def block_shift(image: np.ndarray, block_height: int = 8, max_shift: int = 20,
+                probability: float = 0.2, seed: int = 0) -> np.ndarray:
+    """Displace random horizontal bands sideways (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        block_height: Band height in rows
+        max_shift: Largest horizontal displacement in pixels
+        probability: Fraction of bands that move (0.0-1.0)
+        seed: Random seed
+
+    Returns:
+        uint8 array with same shape; moved bands wrap around
+    """
+    _validate_image(image, np.uint8, "block_shift")
+    return imagestag_rust.block_shift(image, block_height, max_shift, probability, seed)
+
+
+def block_shift_f32(image: np.ndarray, block_height: int = 8, max_shift: int = 20,
+                    probability: float = 0.2, seed: int = 0) -> np.ndarray:
+    """Displace random horizontal bands sideways (f32)."""
+    _validate_image(image, np.float32, "block_shift_f32")
+    return imagestag_rust.block_shift_f32(image, block_height, max_shift, probability, seed)
+
+
+# ============================================================================
+# Wave Distort
+# ============================================================================
+
+def wave_distort(image: np.ndarray, amplitude: float = 4.0, wavelength: float = 64.0,
+                 phase: float = 0.0, jitter: float = 0.0, seed: int = 0) -> np.ndarray:
+    """Shift rows along a sine wave plus random jitter (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amplitude: Wave amplitude in pixels
+        wavelength: Wave period in rows
+        phase: Wave phase in radians
+        jitter: Extra random per-row shift in pixels
+        seed: Random seed
+
+    Returns:
+        uint8 array with same shape; rows wrap around
+    """
+    _validate_image(image, np.uint8, "wave_distort")
+    return imagestag_rust.wave_distort(image, amplitude, wavelength, phase, jitter, seed)
+
+
+def wave_distort_f32(image: np.ndarray, amplitude: float = 4.0, wavelength: float = 64.0,
+                     phase: float = 0.0, jitter: float = 0.0, seed: int = 0) -> np.ndarray:
+    """Shift rows along a sine wave plus random jitter (f32)."""
+    _validate_image(image, np.float32, "wave_distort_f32")
+    return imagestag_rust.wave_distort_f32(image, amplitude, wavelength, phase, jitter, seed)
+
+
+__all__ = [
+    'rgb_split', 'rgb_split_f32',
+    'scanlines', 'scanlines_f32',
+    'block_shift', 'block_shift_f32',
+    'wave_distort', 'wave_distort_f32',
+]
@@ -0,0 +1,249 @@
+//! Glitch / VHS filters: RGB Split, Scanlines, Block Shift, Wave Distort.
+//!
+//! Building blocks for the "glitch" look. Random choices (which blocks move,
+//! row jitter) come from a hash of the seed and the row, so the same
+//! parameters give identical output in Python and WASM.
+//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
+//!
+//! ## Supported Formats
+//!
+//! All filters accept images with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - RGB split is a no-op
+//! - **RGB**: (height, width, 3)
+//! - **RGBA**: (height, width, 4) - alpha moves with the pixels (block shift,
+//!   wave distort) or is preserved (RGB split, scanlines)
+
+use ndarray::{Array3, ArrayView3};
+
+use crate::filters::noise::hash_uniform;
+
+/// Run an f32 filter on a u8 image.
+fn via_f32<F>(input: ArrayView3<u8>, filter: F) -> Array3<u8>
+where
+    F: FnOnce(ArrayView3<f32>) -> Array3<f32>,
+{
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    filter(input_f32.view()).mapv(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Sample a row at a fractional x with linear interpolation, wrapping around.
+#[inline]
+fn sample_row_wrapped(input: &ArrayView3<f32>, y: usize, x: f32, c: usize) -> f32 {
+    let width = input.dim().1 as isize;
+    let x0 = x.floor();
+    let t = x - x0;
+    let i0 = (x0 as isize).rem_euclid(width) as usize;
+    let i1 = (x0 as isize + 1).rem_euclid(width) as usize;
+    input[[y, i0, c]] * (1.0 - t) + input[[y, i1, c]] * t
+}
+
+// ============================================================================
+// RGB Split
+// ============================================================================
+
+/// Shift the red and blue channels in opposite directions (chromatic split) - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `shift_x` - Horizontal red shift in pixels (blue moves the other way)
+/// * `shift_y` - Vertical red shift in pixels (blue moves the other way)
+///
+/// # Returns
+/// Image with same channel count; edges are clamped
+pub fn rgb_split_f32(input: ArrayView3<f32>, shift_x: i32, shift_y: i32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if channels < 3 {
+        return output;
+    }
+
+    let clamp = |v: isize, len: usize| v.clamp(0, len as isize - 1) as usize;
+    for y in 0..height {
+        for x in 0..width {
+            for (c, sign) in [(0usize, 1isize), (2, -1)] {
+                let sx = clamp(x as isize - sign * shift_x as isize, width);
+                let sy = clamp(y as isize - sign * shift_y as isize, height);
+                output[[y, x, c]] = input[[sy, sx, c]];
+            }
+        }
+    }
+
+    output
+}
+
+/// Shift the red and blue channels in opposite directions - u8 version.
+pub fn rgb_split_u8(input: ArrayView3<u8>, shift_x: i32, shift_y: i32) -> Array3<u8> {
+    via_f32(input, |img| rgb_split_f32(img, shift_x, shift_y))
+}
+
+// ============================================================================
+// Scanlines
+// ============================================================================
+
+/// Darken every `spacing`-th row band like a CRT / VHS scanline - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `spacing` - Scanline period in rows
+/// * `thickness` - Dark rows per period
+/// * `intensity` - Darkening of the scanline rows (0.0 = none, 1.0 = black)
+///
+/// # Returns
+/// Image with same channel count, alpha preserved
+pub fn scanlines_f32(input: ArrayView3<f32>, spacing: u32, thickness: u32, intensity: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let spacing = spacing.max(1) as usize;
+    let factor = 1.0 - intensity.clamp(0.0, 1.0);
+
+    for y in (0..height).filter(|y| y % spacing < thickness as usize) {
+        for x in 0..width {
+            for c in 0..color_channels {
+                output[[y, x, c]] *= factor;
+            }
+        }
+    }
+
+    output
+}
+
+/// Darken every `spacing`-th row band - u8 version.
+pub fn scanlines_u8(input: ArrayView3<u8>, spacing: u32, thickness: u32, intensity: f32) -> Array3<u8> {
+    via_f32(input, |img| scanlines_f32(img, spacing, thickness, intensity))
+}
+
+// ============================================================================
+// Block Shift
+// ============================================================================
+
+/// Displace random horizontal bands sideways (digital tearing) - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `block_height` - Band height in rows
+/// * `max_shift` - Largest horizontal displacement in pixels
+/// * `probability` - Fraction of bands that move (0.0-1.0)
+/// * `seed` - Random seed for deterministic results
+///
+/// # Returns
+/// Image with same channel count; moved bands wrap around
+pub fn block_shift_f32(input: ArrayView3<f32>, block_height: u32, max_shift: u32, probability: f32, seed: u64) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let block_height = block_height.max(1) as usize;
+
+    for band in 0..height.div_ceil(block_height) {
+        if hash_uniform(0, band as i64, 0, seed) >= probability {
+            continue;
+        }
+        let shift = ((hash_uniform(0, band as i64, 1, seed) * 2.0 - 1.0) * max_shift as f32).round() as isize;
+        for y in band * block_height..((band + 1) * block_height).min(height) {
+            for x in 0..width {
+                let sx = (x as isize - shift).rem_euclid(width as isize) as usize;
+                for c in 0..channels {
+                    output[[y, x, c]] = input[[y, sx, c]];
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Displace random horizontal bands sideways - u8 version.
+pub fn block_shift_u8(input: ArrayView3<u8>, block_height: u32, max_shift: u32, probability: f32, seed: u64) -> Array3<u8> {
+    via_f32(input, |img| block_shift_f32(img, block_height, max_shift, probability, seed))
+}
+
+// ============================================================================
+// Wave Distort
+// ============================================================================
+
+/// Shift rows along a sine wave plus random jitter (VHS tracking wobble) - f32 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amplitude` - Wave amplitude in pixels
+/// * `wavelength` - Wave period in rows
+/// * `phase` - Wave phase in radians
+/// * `jitter` - Extra random per-row shift in pixels
+/// * `seed` - Random seed for deterministic results
+///
+/// # Returns
+/// Image with same channel count; rows wrap around
+pub fn wave_distort_f32(
+    input: ArrayView3<f32>,
+    amplitude: f32,
+    wavelength: f32,
+    phase: f32,
+    jitter: f32,
+    seed: u64,
+) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    if width == 0 {
+        return output;
+    }
+    let frequency = std::f32::consts::TAU / wavelength.max(1.0);
+
+    for y in 0..height {
+        let wobble = (hash_uniform(0, y as i64, 0, seed) * 2.0 - 1.0) * jitter;
+        let shift = amplitude * (y as f32 * frequency + phase).sin() + wobble;
+        for x in 0..width {
+            for c in 0..channels {
+                output[[y, x, c]] = sample_row_wrapped(&input, y, x as f32 - shift, c);
+            }
+        }
+    }
+
+    output
+}
+
+/// Shift rows along a sine wave plus random jitter - u8 version.
+pub fn wave_distort_u8(input: ArrayView3<u8>, amplitude: f32, wavelength: f32, phase: f32, jitter: f32, seed: u64) -> Array3<u8> {
+    via_f32(input, |img| wave_distort_f32(img, amplitude, wavelength, phase, jitter, seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp() -> Array3<f32> {
+        Array3::from_shape_fn((8, 16, 4), |(y, x, c)| if c == 3 { 1.0 } else { (x + y * 16) as f32 / 128.0 })
+    }
+
+    #[test]
+    fn test_rgb_split_moves_red_and_blue_apart() {
+        let img = ramp();
+        let out = rgb_split_f32(img.view(), 2, 0);
+        assert_eq!(out[[3, 5, 0]], img[[3, 3, 0]]);
+        assert_eq!(out[[3, 5, 1]], img[[3, 5, 1]]);
+        assert_eq!(out[[3, 5, 2]], img[[3, 7, 2]]);
+        assert_eq!(out[[3, 0, 0]], img[[3, 0, 0]]);
+    }
+
+    #[test]
+    fn test_scanlines_darken_periodic_rows() {
+        let img = Array3::<f32>::from_elem((6, 2, 3), 1.0);
+        let out = scanlines_f32(img.view(), 3, 1, 0.5);
+        assert_eq!(out[[0, 0, 0]], 0.5);
+        assert_eq!(out[[1, 0, 0]], 1.0);
+        assert_eq!(out[[3, 1, 2]], 0.5);
+    }
+
+    #[test]
+    fn test_block_shift_and_wave_are_seeded() {
+        let img = ramp();
+        let a = block_shift_f32(img.view(), 2, 5, 1.0, 9);
+        assert_eq!(a, block_shift_f32(img.view(), 2, 5, 1.0, 9));
+        assert_eq!(block_shift_f32(img.view(), 2, 5, 0.0, 9), img);
+        // Rows of one band move together
+        assert!((0..16).all(|x| (a[[1, x, 0]] - a[[0, x, 0]] - 0.125).abs() < 1e-6));
+
+        let w = wave_distort_f32(img.view(), 3.0, 8.0, 0.5, 1.0, 4);
+        assert_eq!(w, wave_distort_f32(img.view(), 3.0, 8.0, 0.5, 1.0, 4));
+        assert_ne!(w, wave_distort_f32(img.view(), 3.0, 8.0, 0.5, 1.0, 5));
+        assert_eq!(wave_distort_f32(img.view(), 0.0, 8.0, 0.0, 0.0, 4), img);
+    }
+}
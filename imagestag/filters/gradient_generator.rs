@@ -4,36 +4,63 @@
 //! Reuses gradient math from `layer_effects::gradient_overlay`.
 
 use ndarray::Array3;
+#[cfg(feature = "python")]
 use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::layer_effects::gradient_overlay::{
-    GradientStop, calculate_gradient_t, interpolate_gradient,
-};
+use crate::layer_effects::gradient_overlay::{gradient_fill, GradientOverlayParams};
+#[cfg(feature = "python")]
+use crate::layer_effects::gradient_overlay::parse_stops;
 
-/// Parse flat stops array into sorted GradientStop vec.
+/// Generate a gradient image as u8 array.
 ///
-/// For u8 input: [pos, r, g, b, ...] where r,g,b are 0-255
-/// For f32 input: [pos, r, g, b, ...] where r,g,b are 0.0-1.0
-fn parse_stops(stops: &[f32], normalize: bool) -> Vec<GradientStop> {
-    let mut gradient_stops: Vec<GradientStop> = stops.chunks(4)
-        .filter(|c| c.len() == 4)
-        .map(|c| GradientStop {
-            position: c[0],
-            r: if normalize { c[1] / 255.0 } else { c[1] },
-            g: if normalize { c[2] / 255.0 } else { c[2] },
-            b: if normalize { c[3] / 255.0 } else { c[3] },
-        })
-        .collect();
+/// Only the stops and geometry fields of `params` are used; opacity and
+/// blend mode have no meaning without an underlying layer.
+///
+/// # Arguments
+/// * `channels` - Output channels: 3 (RGB) or 4 (RGBA, fully opaque)
+pub fn generate_gradient_u8(width: usize, height: usize, channels: usize, params: &GradientOverlayParams) -> Array3<u8> {
+    let gradient = gradient_fill(width, height, params);
+    let ch = if channels == 3 { 3 } else { 4 };
+
+    let mut result = Array3::<u8>::zeros((height, width, ch));
+
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..3 {
+                result[[y, x, c]] = (gradient[[y, x, c]] * 255.0).clamp(0.0, 255.0) as u8;
+            }
+            if ch == 4 {
+                result[[y, x, 3]] = 255;
+            }
+        }
+    }
+
+    result
+}
 
-    gradient_stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
+/// Generate a gradient image as f32 array (0.0-1.0).
+///
+/// See [`generate_gradient_u8`].
+pub fn generate_gradient_f32(width: usize, height: usize, channels: usize, params: &GradientOverlayParams) -> Array3<f32> {
+    let gradient = gradient_fill(width, height, params);
+    let ch = if channels == 3 { 3 } else { 4 };
+
+    let mut result = Array3::<f32>::zeros((height, width, ch));
 
-    if gradient_stops.is_empty() {
-        gradient_stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
-        gradient_stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..3 {
+                result[[y, x, c]] = gradient[[y, x, c]];
+            }
+            if ch == 4 {
+                result[[y, x, 3]] = 1.0;
+            }
+        }
     }
 
-    gradient_stops
+    result
 }
 
 /// Generate a gradient image as RGBA u8 array.
@@ -50,6 +77,7 @@ fn parse_stops(stops: &[f32], normalize: bool) -> Vec<GradientStop> {
 /// * `offset_y` - Vertical center offset (-1.0 to 1.0)
 /// * `reverse` - Whether to reverse the gradient
 /// * `channels` - Output channels: 3 (RGB) or 4 (RGBA)
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(signature = (width, height, stops, style="linear", angle=90.0, scale_x=1.0, scale_y=1.0, offset_x=0.0, offset_y=0.0, reverse=false, channels=4))]
 pub fn generate_gradient<'py>(
@@ -66,34 +94,28 @@ pub fn generate_gradient<'py>(
     reverse: bool,
     channels: usize,
 ) -> Bound<'py, PyArray3<u8>> {
-    let gradient_stops = parse_stops(&stops, true); // normalize 0-255 to 0.0-1.0
-    let ch = if channels == 3 { 3 } else { 4 };
-
-    let mut result = Array3::<u8>::zeros((height, width, ch));
-
-    for y in 0..height {
-        for x in 0..width {
-            let t = calculate_gradient_t(x, y, width, height, style, angle, scale_x, scale_y, offset_x, offset_y, reverse);
-            let (r, g, b) = interpolate_gradient(&gradient_stops, t);
-            result[[y, x, 0]] = (r * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 1]] = (g * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 2]] = (b * 255.0).clamp(0.0, 255.0) as u8;
-            if ch == 4 {
-                result[[y, x, 3]] = 255;
-            }
-        }
-    }
-
-    result.into_pyarray(py)
+    let params = GradientOverlayParams {
+        stops: parse_stops(&stops, 255.0), // normalize 0-255 to 0.0-1.0
+        style: style.to_string(),
+        angle,
+        scale_x,
+        scale_y,
+        offset_x,
+        offset_y,
+        reverse,
+        ..Default::default()
+    };
+    generate_gradient_u8(width, height, channels, &params).into_pyarray(py)
 }
 
 /// Generate a gradient image as RGBA f32 array.
 ///
 /// Same as generate_gradient but returns f32 values (0.0-1.0).
 /// Stops format: [pos, r, g, b, ...] where all values are 0.0-1.0
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (width, height, stops, style="linear", angle=90.0, scale_x=1.0, scale_y=1.0, offset_x=0.0, offset_y=0.0, reverse=false, channels=4))]
-pub fn generate_gradient_f32<'py>(
+#[pyo3(name = "generate_gradient_f32", signature = (width, height, stops, style="linear", angle=90.0, scale_x=1.0, scale_y=1.0, offset_x=0.0, offset_y=0.0, reverse=false, channels=4))]
+pub fn generate_gradient_f32_py<'py>(
     py: Python<'py>,
     width: usize,
     height: usize,
@@ -107,23 +129,16 @@ pub fn generate_gradient_f32<'py>(
     reverse: bool,
     channels: usize,
 ) -> Bound<'py, PyArray3<f32>> {
-    let gradient_stops = parse_stops(&stops, false); // already 0.0-1.0
-    let ch = if channels == 3 { 3 } else { 4 };
-
-    let mut result = Array3::<f32>::zeros((height, width, ch));
-
-    for y in 0..height {
-        for x in 0..width {
-            let t = calculate_gradient_t(x, y, width, height, style, angle, scale_x, scale_y, offset_x, offset_y, reverse);
-            let (r, g, b) = interpolate_gradient(&gradient_stops, t);
-            result[[y, x, 0]] = r;
-            result[[y, x, 1]] = g;
-            result[[y, x, 2]] = b;
-            if ch == 4 {
-                result[[y, x, 3]] = 1.0;
-            }
-        }
-    }
-
-    result.into_pyarray(py)
+    let params = GradientOverlayParams {
+        stops: parse_stops(&stops, 1.0), // already 0.0-1.0
+        style: style.to_string(),
+        angle,
+        scale_x,
+        scale_y,
+        offset_x,
+        offset_y,
+        reverse,
+        ..Default::default()
+    };
+    generate_gradient_f32(width, height, channels, &params).into_pyarray(py)
 }
@@ -3,6 +3,7 @@
 This module provides high-performance grayscale conversion:
 - Default uses ITU-R BT.709 luminosity coefficients
 - Weighted version allows custom RGB channel weights (Photoshop-style Black & White)
+- Single channel versions return an (H, W, 1) luminance mask
 
 ## Bit Depth Support
 
@@ -47,7 +48,7 @@ def grayscale(image: np.ndarray) -> np.ndarray:
     Returns:
         Grayscale uint8 array (H, W, C) with R=G=B=luminosity
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, C) with C in (1, 3, 4), got shape {image.shape}")
 
     if image.dtype != np.uint8:
@@ -74,7 +75,7 @@ def grayscale_f32(image: np.ndarray) -> np.ndarray:
     Returns:
         Grayscale float32 array (H, W, C) with R=G=B=luminosity
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, C) with C in (1, 3, 4), got shape {image.shape}")
 
     if image.dtype != np.float32:
@@ -121,7 +122,7 @@ def grayscale_weighted(
         # Equal weight (simple average)
         result = grayscale_weighted(image, r_weight=1.0, g_weight=1.0, b_weight=1.0)
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, C) with C in (1, 3, 4), got shape {image.shape}")
 
     if image.dtype != np.uint8:
@@ -147,7 +148,7 @@ def grayscale_weighted_f32(
     Returns:
         Grayscale float32 array (H, W, C) with R=G=B=weighted_luminosity
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, C) with C in (1, 3, 4), got shape {image.shape}")
 
     if image.dtype != np.float32:
@@ -156,6 +157,80 @@ def grayscale_weighted_f32(
     return imagestag_rust.grayscale_weighted_f32_py(image, r_weight, g_weight, b_weight)
 
 
+# ============================================================================
+# Single Channel Output
+# ============================================================================
+
+def _validate_single_channel_input(image: np.ndarray, expected_dtype: type) -> None:
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
+        raise ValueError(f"Expected image (H, W, C) with C in (1, 3, 4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"Expected {np.dtype(expected_dtype)} dtype, got {image.dtype}")
+
+
+def grayscale_to_single_channel(image: np.ndarray, luma: str = "bt709") -> np.ndarray:
+    """Luminance of an image as a single channel (u8).
+
+    Same values as ``grayscale`` but without duplicating the luminance into
+    R, G and B or keeping alpha, so masks take a quarter of the memory.
+
+    Args:
+        image: uint8 array (H, W, C) where C is 1, 3, or 4
+        luma: Coefficient standard: "bt601", "bt709" or "bt2020"
+
+    Returns:
+        Luminance uint8 array (H, W, 1)
+    """
+    _validate_single_channel_input(image, np.uint8)
+    return imagestag_rust.grayscale_to_single_channel(image, luma)
+
+
+def grayscale_to_single_channel_f32(image: np.ndarray, luma: str = "bt709") -> np.ndarray:
+    """Luminance of an image as a single channel (f32).
+
+    Args:
+        image: float32 array (H, W, C) with values 0.0-1.0, where C is 1, 3, or 4
+        luma: Coefficient standard: "bt601", "bt709" or "bt2020"
+
+    Returns:
+        Luminance float32 array (H, W, 1)
+    """
+    _validate_single_channel_input(image, np.float32)
+    return imagestag_rust.grayscale_to_single_channel_f32(image, luma)
+
+
+def grayscale_to_single_channel_weighted(
+    image: np.ndarray,
+    r_weight: float = LUMA_R,
+    g_weight: float = LUMA_G,
+    b_weight: float = LUMA_B,
+) -> np.ndarray:
+    """Luminance with custom RGB channel weights as a single channel (u8).
+
+    Weights are normalized automatically, as for ``grayscale_weighted``.
+
+    Returns:
+        Luminance uint8 array (H, W, 1)
+    """
+    _validate_single_channel_input(image, np.uint8)
+    return imagestag_rust.grayscale_to_single_channel_weighted(image, r_weight, g_weight, b_weight)
+
+
+def grayscale_to_single_channel_weighted_f32(
+    image: np.ndarray,
+    r_weight: float = LUMA_R,
+    g_weight: float = LUMA_G,
+    b_weight: float = LUMA_B,
+) -> np.ndarray:
+    """Luminance with custom RGB channel weights as a single channel (f32).
+
+    Returns:
+        Luminance float32 array (H, W, 1)
+    """
+    _validate_single_channel_input(image, np.float32)
+    return imagestag_rust.grayscale_to_single_channel_weighted_f32(image, r_weight, g_weight, b_weight)
+
+
 # ============================================================================
 # Conversion Utilities
 # ============================================================================
@@ -220,10 +295,46 @@ def convert_12bit_to_f32(image: np.ndarray) -> np.ndarray:
     return imagestag_rust.convert_12bit_to_f32(image)
 
 
+def pack_12bit(image: np.ndarray) -> bytes:
+    """Pack a 12-bit image into two-pixels-in-three-bytes (MIPI RAW12).
+
+    Each pair of values a, b is stored as ``a >> 4``, ``b >> 4``,
+    ``(b & 0xF) << 4 | (a & 0xF)``; an odd trailing value is paired with 0.
+
+    Args:
+        image: uint16 array (H, W, C) with values 0-4095
+
+    Returns:
+        Packed bytes, 3 bytes per 2 values
+    """
+    if image.dtype != np.uint16 or image.ndim != 3:
+        raise ValueError(f"Expected uint16 (H, W, C) array, got {image.dtype} {image.shape}")
+
+    return imagestag_rust.pack_12bit(image)
+
+
+def unpack_12bit(data: bytes, width: int, height: int, channels: int = 1) -> np.ndarray:
+    """Unpack a RAW12 buffer from :func:`pack_12bit` or capture hardware.
+
+    Args:
+        data: Packed bytes
+        width: Image width
+        height: Image height
+        channels: Values per pixel
+
+    Returns:
+        uint16 array (H, W, C) with values 0-4095
+    """
+    return imagestag_rust.unpack_12bit(data, width, height, channels)
+
+
 __all__ = [
     'grayscale', 'grayscale_f32',
     'grayscale_weighted', 'grayscale_weighted_f32',
+    'grayscale_to_single_channel', 'grayscale_to_single_channel_f32',
+    'grayscale_to_single_channel_weighted', 'grayscale_to_single_channel_weighted_f32',
     'LUMA_R', 'LUMA_G', 'LUMA_B',
     'convert_u8_to_f32', 'convert_f32_to_u8',
     'convert_f32_to_12bit', 'convert_12bit_to_f32',
+    'pack_12bit', 'unpack_12bit',
 ]
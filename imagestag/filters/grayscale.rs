@@ -1,7 +1,8 @@
 //! Grayscale conversion filter.
 //!
 //! Works with both PyO3 (numpy) and WASM (JS).
-//! Uses ITU-R BT.709 luminosity coefficients.
+//! Uses ITU-R BT.709 luminosity coefficients unless a [`LumaStandard`]
+//! is given.
 //!
 //! ## Supported Formats
 //!
@@ -14,6 +15,7 @@
 //!
 //! - **u8 (8-bit)**: Values 0-255, standard for web/display
 //! - **f32 (float)**: Values 0.0-1.0, for HDR/linear workflows
+//! - **u16 12-bit**: Values 0-4095, see [`pack_12bit`] for sensor buffers
 //!
 //! Both versions use identical algorithms. The f32 version preserves
 //! full precision for chained operations.
@@ -25,6 +27,68 @@ pub const LUMA_R: f32 = 0.2126;
 pub const LUMA_G: f32 = 0.7152;
 pub const LUMA_B: f32 = 0.0722;
 
+/// Luma coefficient standard used to turn RGB into luminance.
+///
+/// Shared by grayscale, threshold, edge detection and emboss. All filters
+/// default to BT.709; BT.601 matches SD video and older libraries, BT.2020
+/// UHD/HDR material.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LumaStandard {
+    /// ITU-R BT.601: 0.299, 0.587, 0.114
+    Bt601,
+    /// ITU-R BT.709: 0.2126, 0.7152, 0.0722
+    #[default]
+    Bt709,
+    /// ITU-R BT.2020: 0.2627, 0.6780, 0.0593
+    Bt2020,
+}
+
+impl LumaStandard {
+    /// Parse a standard name ("bt601", "bt709", "bt2020"; dots, dashes and
+    /// "rec" prefixes are accepted, e.g. "BT.709" or "rec2020").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        match name.to_ascii_lowercase().as_str() {
+            "bt601" | "rec601" | "601" => Some(LumaStandard::Bt601),
+            "bt709" | "rec709" | "709" => Some(LumaStandard::Bt709),
+            "bt2020" | "rec2020" | "2020" => Some(LumaStandard::Bt2020),
+            _ => None,
+        }
+    }
+
+    /// Canonical name, as accepted by [`LumaStandard::from_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            LumaStandard::Bt601 => "bt601",
+            LumaStandard::Bt709 => "bt709",
+            LumaStandard::Bt2020 => "bt2020",
+        }
+    }
+
+    /// R, G, B weights (sum to 1.0).
+    pub fn coefficients(self) -> [f32; 3] {
+        match self {
+            LumaStandard::Bt601 => [0.299, 0.587, 0.114],
+            LumaStandard::Bt709 => [LUMA_R, LUMA_G, LUMA_B],
+            LumaStandard::Bt2020 => [0.2627, 0.6780, 0.0593],
+        }
+    }
+
+    /// Weighted sum of `r`, `g`, `b` (any scale).
+    #[inline]
+    pub fn luma(self, r: f32, g: f32, b: f32) -> f32 {
+        let [kr, kg, kb] = self.coefficients();
+        kr * r + kg * g + kb * b
+    }
+}
+
+impl From<LumaStandard> for GrayscaleWeights {
+    fn from(standard: LumaStandard) -> Self {
+        let [r, g, b] = standard.coefficients();
+        Self { r, g, b }
+    }
+}
+
 /// RGB channel weights for grayscale conversion.
 /// Default uses BT.709 coefficients. Custom weights allow
 /// Photoshop-style Black & White adjustments.
@@ -51,6 +115,16 @@ impl GrayscaleWeights {
         Self::default()
     }
 
+    /// BT.601 luminosity
+    pub fn bt601() -> Self {
+        LumaStandard::Bt601.into()
+    }
+
+    /// BT.2020 luminosity
+    pub fn bt2020() -> Self {
+        LumaStandard::Bt2020.into()
+    }
+
     /// Simple average (R+G+B)/3
     pub fn average() -> Self {
         Self { r: 1.0/3.0, g: 1.0/3.0, b: 1.0/3.0 }
@@ -269,6 +343,73 @@ pub fn grayscale_rgba_f32(input: ArrayView3<f32>) -> Array3<f32> {
     grayscale_f32(input)
 }
 
+// ============================================================================
+// Single Channel Output
+// ============================================================================
+
+/// Luminance of an image as one channel with custom RGB weights - u8 version.
+///
+/// Same values as channel 0 of [`grayscale_weighted_u8`], but the output is
+/// (height, width, 1), so no copies of the luminance and no alpha are
+/// stored. 1- and 2-channel input passes channel 0 through.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `weights` - RGB channel weights for conversion
+///
+/// # Returns
+/// Luminance mask (height, width, 1)
+pub fn grayscale_to_single_channel_weighted_u8(input: ArrayView3<u8>, weights: GrayscaleWeights) -> Array3<u8> {
+    let (height, width, channels) = input.dim();
+    Array3::from_shape_fn((height, width, 1), |(y, x, _)| {
+        if channels < 3 {
+            return input[[y, x, 0]];
+        }
+        let r = input[[y, x, 0]] as f32;
+        let g = input[[y, x, 1]] as f32;
+        let b = input[[y, x, 2]] as f32;
+        (weights.r * r + weights.g * g + weights.b * b).clamp(0.0, 255.0) as u8
+    })
+}
+
+/// Luminance of an image as one channel (BT.709) - u8 version.
+///
+/// See [`grayscale_to_single_channel_weighted_u8`].
+pub fn grayscale_to_single_channel_u8(input: ArrayView3<u8>) -> Array3<u8> {
+    grayscale_to_single_channel_weighted_u8(input, GrayscaleWeights::default())
+}
+
+/// Luminance of an image as one channel with custom RGB weights - f32 version.
+///
+/// Same values as channel 0 of [`grayscale_weighted_f32`], output
+/// (height, width, 1).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `weights` - RGB channel weights for conversion
+///
+/// # Returns
+/// Luminance mask (height, width, 1)
+pub fn grayscale_to_single_channel_weighted_f32(input: ArrayView3<f32>, weights: GrayscaleWeights) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    Array3::from_shape_fn((height, width, 1), |(y, x, _)| {
+        if channels < 3 {
+            return input[[y, x, 0]];
+        }
+        let r = input[[y, x, 0]];
+        let g = input[[y, x, 1]];
+        let b = input[[y, x, 2]];
+        (weights.r * r + weights.g * g + weights.b * b).clamp(0.0, 1.0)
+    })
+}
+
+/// Luminance of an image as one channel (BT.709) - f32 version.
+///
+/// See [`grayscale_to_single_channel_weighted_f32`].
+pub fn grayscale_to_single_channel_f32(input: ArrayView3<f32>) -> Array3<f32> {
+    grayscale_to_single_channel_weighted_f32(input, GrayscaleWeights::default())
+}
+
 // ============================================================================
 // Conversion Utilities
 // ============================================================================
@@ -293,6 +434,40 @@ pub fn u16_12bit_to_f32(input: ArrayView3<u16>) -> Array3<f32> {
     input.mapv(|v| v as f32 / 4095.0)
 }
 
+/// Pack 12-bit values two-in-three-bytes (MIPI CSI-2 RAW12 layout).
+///
+/// Each pair of values `a`, `b` becomes `a >> 4`, `b >> 4`, then the low
+/// nibbles as `(b & 0xF) << 4 | (a & 0xF)`. An odd trailing value is paired
+/// with zero. Values above 4095 are clamped.
+pub fn pack_12bit(values: &[u16]) -> Vec<u8> {
+    let mut packed = Vec::with_capacity(values.len().div_ceil(2) * 3);
+    for pair in values.chunks(2) {
+        let a = pair[0].min(4095);
+        let b = pair.get(1).copied().unwrap_or(0).min(4095);
+        packed.extend_from_slice(&[(a >> 4) as u8, (b >> 4) as u8, (((b & 0xF) << 4) | (a & 0xF)) as u8]);
+    }
+    packed
+}
+
+/// Unpack `count` 12-bit values from a [`pack_12bit`] buffer.
+///
+/// Returns `None` if `data` holds fewer than `count` values.
+pub fn unpack_12bit(data: &[u8], count: usize) -> Option<Vec<u16>> {
+    if data.len() < count.div_ceil(2) * 3 {
+        return None;
+    }
+    let mut values: Vec<u16> = data
+        .chunks_exact(3)
+        .take(count.div_ceil(2))
+        .flat_map(|b| {
+            let (high_a, high_b, low) = (b[0] as u16, b[1] as u16, b[2] as u16);
+            [(high_a << 4) | (low & 0xF), (high_b << 4) | (low >> 4)]
+        })
+        .collect();
+    values.truncate(count);
+    Some(values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,6 +642,16 @@ mod tests {
         assert!(diff <= 1, "u8 and f32 results should match within 1, got diff={}", diff);
     }
 
+    #[test]
+    fn test_12bit_packing() {
+        let values = [0x123u16, 0xABC, 4095, 0, 7];
+        let packed = pack_12bit(&values);
+        assert_eq!(packed[..3], [0x12, 0xAB, 0xC3]);
+        assert_eq!(packed.len(), 9);
+        assert_eq!(unpack_12bit(&packed, 5), Some(values.to_vec()));
+        assert_eq!(unpack_12bit(&packed, 7), None);
+    }
+
     #[test]
     fn test_12bit_roundtrip() {
         let mut img = Array3::<f32>::zeros((1, 1, 4));
@@ -484,4 +669,36 @@ mod tests {
             assert!(diff < max_error, "12-bit roundtrip error too large: {}", diff);
         }
     }
+
+    #[test]
+    fn test_luma_standards() {
+        for standard in [LumaStandard::Bt601, LumaStandard::Bt709, LumaStandard::Bt2020] {
+            assert!((standard.coefficients().iter().sum::<f32>() - 1.0).abs() < 1e-4);
+            assert_eq!(LumaStandard::from_name(standard.name()), Some(standard));
+        }
+        assert_eq!(LumaStandard::from_name("BT.2020"), Some(LumaStandard::Bt2020));
+        assert_eq!(LumaStandard::from_name("rec-601"), Some(LumaStandard::Bt601));
+        assert_eq!(LumaStandard::from_name("srgb"), None);
+
+        let mut img = Array3::<u8>::zeros((1, 1, 3));
+        img[[0, 0, 1]] = 255;
+        let result = grayscale_weighted_u8(img.view(), LumaStandard::Bt601.into());
+        assert_eq!(result[[0, 0, 0]], 149); // 0.587 * 255
+    }
+
+    #[test]
+    fn test_single_channel_matches_grayscale() {
+        for channels in [1, 3, 4] {
+            let img = Array3::from_shape_fn((3, 5, channels), |(y, x, c)| (y * 60 + x * 37 + c * 90) as u8);
+            let single = grayscale_to_single_channel_u8(img.view());
+            assert_eq!(single.dim(), (3, 5, 1));
+            assert_eq!(single.index_axis(ndarray::Axis(2), 0), grayscale_u8(img.view()).index_axis(ndarray::Axis(2), 0));
+
+            let img = img.mapv(|v| v as f32 / 255.0);
+            let weights = GrayscaleWeights::bt601();
+            let single = grayscale_to_single_channel_weighted_f32(img.view(), weights);
+            let full = grayscale_weighted_f32(img.view(), weights);
+            assert_eq!(single.index_axis(ndarray::Axis(2), 0), full.index_axis(ndarray::Axis(2), 0));
+        }
+    }
 }
@@ -62,6 +62,12 @@ function u8filter(wasmFn, paramExtractor = () => []) {
     };
 }
 
+/** Parse a '#rrggbb' color into a Uint8Array [r, g, b]. */
+function hexRgb(hex) {
+    const c = hex.replace('#', '');
+    return new Uint8Array([0, 2, 4].map(i => parseInt(c.substring(i, i + 2), 16)));
+}
+
 // ---------------------------------------------------------------------------
 // Filter definitions — each maps a friendly name to a WASM function + params
 // ---------------------------------------------------------------------------
@@ -73,14 +79,17 @@ function u8filter(wasmFn, paramExtractor = () => []) {
 export const filters = {
     // --- Color ---
     grayscale:      u8filter(wasm.grayscale_rgba_wasm),
-    invert:         u8filter(wasm.invert_wasm),
+    invert:         u8filter(wasm.invert_wasm,        o => [o.channels ?? 'rgb']),
     brightness:     u8filter(wasm.brightness_wasm,    o => [(o.amount ?? 0) / 100]),
     contrast:       u8filter(wasm.contrast_wasm,      o => [(o.amount ?? 0) / 100]),
     saturation:     u8filter(wasm.saturation_wasm,    o => [(o.amount ?? 0) / 100]),
     gamma:          u8filter(wasm.gamma_wasm,          o => [o.gamma_value ?? 1.0]),
     exposure:       u8filter(wasm.exposure_wasm,       o => [o.exposure_val ?? 0, o.offset ?? 0, o.gamma_val ?? 1.0]),
     hue_shift:      u8filter(wasm.hue_shift_wasm,     o => [o.degrees ?? 0]),
-    vibrance:       u8filter(wasm.vibrance_wasm,       o => [(o.amount ?? 0) / 100]),
+    hsl_adjust:     u8filter(wasm.hsl_adjust_wasm,    o => [Object.assign(new wasm.HslAdjustOptions(), {
+        hue: o.hue ?? 0, saturation: (o.saturation ?? 0) / 100, lightness: (o.lightness ?? 0) / 100, colorize: o.colorize ?? false,
+    })]),
+    vibrance:       u8filter(wasm.vibrance_wasm,       o => [(o.amount ?? 0) / 100, (o.protect_skin ?? 0) / 100]),
     color_balance:  u8filter(wasm.color_balance_wasm,  o => {
         // Support both legacy triplet params and new range-based params
         if (o.shadows || o.midtones || o.highlights) {
@@ -99,38 +108,69 @@ export const filters = {
     }),
     sepia:          u8filter(wasm.sepia_wasm,          o => [(o.intensity ?? 100) / 100]),
     temperature:    u8filter(wasm.temperature_wasm,    o => [(o.amount ?? 0) / 100]),
+    white_balance:  u8filter(wasm.white_balance_kelvin_wasm, o => [o.kelvin ?? 6504, (o.tint ?? 0) / 100]),
+    black_white:    u8filter(wasm.black_white_wasm,    o => [
+        new Float32Array(['reds', 'yellows', 'greens', 'cyans', 'blues', 'magentas'].map((id, i) => (o[id] ?? [40, 60, 40, 60, 20, 80][i]) / 100)),
+        o.tint ? hexRgb(o.tint_color ?? '#e1d3b3') : new Uint8Array(0),
+    ]),
+    split_tone:     u8filter(wasm.split_tone_wasm,     o => [
+        hexRgb(o.shadow_color ?? '#2850a0'), hexRgb(o.highlight_color ?? '#e6b45a'), (o.balance ?? 0) / 100,
+    ]),
+    duotone:        u8filter(wasm.duotone_wasm,        o => [
+        new Uint8Array([...hexRgb(o.ink1 ?? '#000000'), ...hexRgb(o.ink2 ?? '#e68c28')]),
+        new Float32Array(0), new Uint32Array(0),
+    ]),
     channel_mixer:  u8filter(wasm.channel_mixer_wasm,  o => [o.r_source ?? 0, o.g_source ?? 1, o.b_source ?? 2]),
-    equalize_histogram: u8filter(wasm.equalize_histogram_wasm),
+    simulate_cvd:   u8filter(wasm.simulate_cvd_wasm,   o => [o.deficiency ?? 'deutan', (o.severity ?? 100) / 100]),
+    daltonize:      u8filter(wasm.daltonize_wasm,      o => [o.deficiency ?? 'deutan', (o.amount ?? 100) / 100]),
+    equalize_histogram: u8filter(wasm.equalize_histogram_wasm, o => [o.mode ?? 'per_channel', new Uint8Array(0)]),
 
     // --- Levels / Curves ---
     levels:         u8filter(wasm.levels_wasm, o => [
-        o.in_black ?? 0, o.in_white ?? 255, o.out_black ?? 0, o.out_white ?? 255, o.gamma ?? 1.0,
+        o.in_black ?? 0, o.in_white ?? 255, o.out_black ?? 0, o.out_white ?? 255, o.gamma ?? 1.0, o.channel ?? 'rgb',
     ]),
     curves:         u8filter(wasm.curves_wasm, o => {
         const pts = o.points || [[0, 0], [1, 1]];
-        return [new Float32Array(pts.flat())];
+        return [new Float32Array(pts.flat()), o.interpolation ?? 'pchip', o.luminosity_only ? 'luminosity' : (o.channel ?? 'rgb')];
     }),
-    auto_levels:    u8filter(wasm.auto_levels_wasm, o => [(o.clip_percent ?? 1.0) / 100]),
+    auto_levels:    u8filter(wasm.auto_levels_wasm, o => [(o.clip_percent ?? 1.0) / 100, o.channel ?? 'rgb']),
+    auto_contrast:  u8filter(wasm.auto_contrast_wasm, o => [(o.clip_percent ?? 0.1) / 100]),
+    auto_tone:      u8filter(wasm.auto_tone_wasm, o => [(o.clip_percent ?? 0.1) / 100, o.snap_neutral ?? false]),
+    auto_color:     u8filter(wasm.auto_color_wasm, o => [(o.clip_percent ?? 0.1) / 100, o.snap_neutral ?? true]),
 
     // --- Edge Detection ---
-    sobel:          u8filter(wasm.sobel_wasm,      o => {
-        const d = o.direction ?? 'both';
-        // Rust expects 'h', 'v', or 'both'
-        return [d === 'horizontal' ? 'h' : d === 'vertical' ? 'v' : d, o.kernel_size ?? 3];
-    }),
+    sobel:          (imageData, options = {}) => u8filter(
+        options.per_channel ? wasm.sobel_per_channel_wasm : wasm.sobel_wasm, o => {
+            const d = o.direction ?? 'both';
+            // Rust expects 'h', 'v', 'both', or 'angle'
+            return [d === 'horizontal' ? 'h' : d === 'vertical' ? 'v' : d, o.kernel_size ?? 3];
+        })(imageData, options),
     laplacian:      u8filter(wasm.laplacian_wasm,  o => [o.kernel_size ?? 3]),
     find_edges:     u8filter(wasm.find_edges_wasm, o => [o.sigma ?? 1.0, o.low_threshold ?? 0.1, o.high_threshold ?? 0.2]),
+    dog:            u8filter(wasm.dog_wasm,        o => [o.sigma1 ?? 1.0, o.sigma2 ?? 1.6]),
     draw_contours:  u8filter(wasm.draw_contours_wasm, o => [o.threshold ?? 128, o.line_width ?? 2, o.color_r ?? 0, o.color_g ?? 255, o.color_b ?? 0]),
 
     // --- Blur ---
-    gaussian_blur:  u8filter(wasm.gaussian_blur_wasm,  o => [o.sigma ?? 3.0]),
-    box_blur:       u8filter(wasm.box_blur_wasm,       o => [o.radius ?? 5]),
-    motion_blur:    u8filter(wasm.motion_blur_wasm,    o => [o.angle ?? 0, o.distance ?? 5]),
+    gaussian_blur:  u8filter(wasm.gaussian_blur_wasm,  o => [o.sigma ?? 3.0, o.srgb_linearize ?? false, o.quality ?? 'exact']),
+    box_blur:       u8filter(wasm.box_blur_wasm,       o => [o.radius ?? 5, o.srgb_linearize ?? false]),
+    motion_blur:    u8filter(wasm.motion_blur_wasm,    o => [o.angle ?? 0, o.distance ?? 5, o.border_mode ?? 'reflect']),
+    spin_blur:      u8filter(wasm.spin_blur_wasm, o => [Object.assign(new wasm.SpinBlurOptions(), {
+        angle: o.angle ?? 10, center_x: (o.center_x ?? 50) / 100, center_y: (o.center_y ?? 50) / 100,
+    }), o.border_mode ?? 'reflect']),
 
     // --- Sharpen ---
     sharpen:        u8filter(wasm.sharpen_wasm, o => [o.amount ?? 0.5]),
-    unsharp_mask:   u8filter(wasm.unsharp_mask_wasm, o => [o.amount ?? 1.0, o.radius ?? 1.0, o.threshold ?? 0]),
+    unsharp_mask:   u8filter(wasm.unsharp_mask_params_wasm, o => [Object.assign(new wasm.UnsharpMaskOptions(), {
+        amount: o.amount ?? 1.0, radius: o.radius ?? 1.0, threshold: (o.threshold ?? 0) / 255,
+        edge_mask: (o.edge_mask ?? 0) / 100, shadow_amount: (o.shadows ?? 100) / 100, highlight_amount: (o.highlights ?? 100) / 100,
+    })]),
     high_pass:      u8filter(wasm.high_pass_wasm, o => [o.radius ?? 3]),
+    clarity:        u8filter(wasm.clarity_wasm, o => [(o.amount ?? 50) / 100, o.radius ?? 20]),
+    texture:        u8filter(wasm.texture_wasm, o => [(o.amount ?? 50) / 100]),
+    smart_sharpen:  u8filter(wasm.smart_sharpen_wasm, o => [o.mode ?? 'gaussian', Object.assign(new wasm.SmartSharpenOptions(), {
+        amount: (o.amount ?? 100) / 100, radius: o.radius ?? 1.0, angle: o.angle ?? 0,
+    })]),
+    wavelet_sharpen: u8filter(wasm.wavelet_sharpen_wasm, o => [new Float32Array([o.fine ?? 1.8, o.medium ?? 1.4, o.coarse ?? 1.0])]),
 
     // --- Morphology ---
     dilate:         u8filter(wasm.dilate_wasm,           o => [o.radius ?? 1]),
@@ -146,15 +186,45 @@ export const filters = {
         (o.amount ?? 20) / 100, o.gaussian ?? true, o.monochrome ?? false, o.seed ?? 0,
     ]),
     median:         u8filter(wasm.median_wasm,   o => [o.radius ?? 1]),
+    remove_hot_pixels: u8filter(wasm.remove_hot_pixels_wasm, o => [o.threshold ?? 40]),
+    surface_blur:   u8filter(wasm.surface_blur_wasm, o => [o.radius ?? 5, o.threshold ?? 15]),
     denoise:        u8filter(wasm.denoise_wasm,  o => [(o.strength ?? 33) / 100]),
+    deband:         u8filter(wasm.deband_wasm, o => [o.threshold ?? 4, o.range ?? 16, o.grain ?? 6]),
+    wavelet_denoise: u8filter(wasm.wavelet_denoise_wasm, o => [new Float32Array([o.fine ?? 8, o.medium ?? 4, o.coarse ?? 2])]),
+    film_grain:     u8filter(wasm.film_grain_wasm, o => [Object.assign(new wasm.FilmGrainOptions(), {
+        intensity: (o.intensity ?? 30) / 100, size: o.size ?? 1.5, roughness: (o.roughness ?? 50) / 100,
+        chroma_amount: (o.chroma_amount ?? 0) / 100, seed: o.seed ?? 0,
+    })]),
 
     // --- Stylize ---
     posterize:      u8filter(wasm.posterize_wasm,  o => [o.levels ?? 4]),
     solarize:       u8filter(wasm.solarize_wasm,   o => [o.threshold ?? 128]),
     threshold:      u8filter(wasm.threshold_wasm,  o => [o.threshold ?? 128]),
+    adaptive_threshold: u8filter(wasm.adaptive_threshold_wasm, o => [o.block_size ?? 11, o.c ?? 5, o.method ?? 'mean']),
     emboss:         u8filter(wasm.emboss_wasm,     o => [o.angle ?? 135, o.depth ?? 1.0]),
-    pixelate:       u8filter(wasm.pixelate_wasm,   o => [o.block_size ?? 10]),
-    vignette:       u8filter(wasm.vignette_wasm,   o => [(o.amount ?? 40) * 0.02]),
+    pixelate:       u8filter(wasm.pixelate_shaped_wasm, o => [o.block_size ?? 10, o.shape ?? 'square', new Uint8Array(0)]),
+    vignette:       u8filter(wasm.vignette_params_wasm, o => {
+        const [r, g, b] = hexRgb(o.color ?? '#ffffff');
+        return [Object.assign(new wasm.VignetteOptions(), {
+            amount: (o.amount ?? 40) * 0.02, center_x: (o.center_x ?? 50) / 100, center_y: (o.center_y ?? 50) / 100,
+            midpoint: (o.midpoint ?? 0) / 100, roundness: (o.roundness ?? 100) / 100, feather: (o.feather ?? 100) / 100,
+            colored: !!o.colored, color_r: r / 255, color_g: g / 255, color_b: b / 255,
+        })];
+    }),
+
+    // --- Distort ---
+    polar_coordinates: u8filter(wasm.polar_coordinates_wasm, o => [o.direction ?? 'rect_to_polar', o.interpolation ?? 'bilinear']),
+    twirl:          u8filter(wasm.twirl_wasm, o => [Object.assign(new wasm.TwirlOptions(), {
+        angle: o.angle ?? 50, radius: (o.radius ?? 100) / 100,
+    })]),
+    spherize:       u8filter(wasm.spherize_wasm, o => [(o.amount ?? 100) / 100]),
+    pinch:          u8filter(wasm.pinch_wasm,    o => [(o.amount ?? 50) / 100]),
+    wave:           u8filter(wasm.wave_wasm,     o => [o.amplitude ?? 10, o.wavelength ?? 120, o.type ?? 'sine']),
+    kaleidoscope:   u8filter(wasm.kaleidoscope_wasm, o => [Object.assign(new wasm.KaleidoscopeOptions(), {
+        segments: o.segments ?? 6, rotation: o.rotation ?? 0,
+    })]),
+    mirror:         u8filter(wasm.mirror_wasm,   o => [o.axis_angle ?? 0, o.offset ?? 0]),
+    make_seamless:  u8filter(wasm.make_seamless_wasm, o => [o.method ?? 'offset_blend', o.blend_width ?? 0]),
 
     // --- Composite filters (chain/dispatch to individual WASM filters) ---
     brightness_contrast: (imageData, options = {}) => {
@@ -174,19 +244,17 @@ export const filters = {
         const l = options.lightness ?? 0;
         const v = options.vibrance ?? 0;
         const t = options.temperature ?? 0;
-        if (h !== 0) result = filters.hue_shift(result, { degrees: h });
-        if (s !== 0) result = filters.saturation(result, { amount: s });
-        if (l !== 0) result = filters.brightness(result, { amount: l });
-        if (v !== 0) result = filters.vibrance(result, { amount: v });
+        const colorize = options.colorize ?? false;
+        if (h !== 0 || s !== 0 || l !== 0 || colorize) {
+            result = filters.hsl_adjust(result, { hue: h, saturation: s, lightness: l, colorize });
+        }
+        if (v !== 0) result = filters.vibrance(result, { amount: v, protect_skin: options.protect_skin ?? 0 });
         if (t !== 0) result = filters.temperature(result, { amount: t });
         return result;
     },
     binary_threshold: (imageData, options = {}) => {
         return filters.threshold(imageData, { threshold: options.threshold ?? 128 });
     },
-    auto_contrast: (imageData, options = {}) => {
-        return filters.auto_levels(imageData, { clip_percent: options.clip_percent ?? 1.0 });
-    },
     median_blur: (imageData, options = {}) => {
         return filters.median(imageData, { radius: options.radius ?? 3 });
     },
@@ -227,6 +295,26 @@ export const filters = {
         return { data: out, width: edges.width, height: edges.height, channels: edges.channels || 4 };
     },
     pencil_sketch: u8filter(wasm.pencil_sketch_wasm, o => [o.sigma_s ?? 60, o.shade_factor ?? 50]),
+    xdog: u8filter(wasm.xdog_wasm, o => [Object.assign(new wasm.XdogOptions(), {
+        sigma: o.sigma ?? 1.0, k: o.k ?? 1.6, p: o.p ?? 20, epsilon: o.epsilon ?? 0.1, phi: o.phi ?? 10,
+    })]),
+
+    // --- Glitch ---
+    rgb_split:      u8filter(wasm.rgb_split_wasm, o => [o.shift_x ?? 4, o.shift_y ?? 0]),
+    scanlines:      u8filter(wasm.scanlines_wasm, o => [Object.assign(new wasm.ScanlinesOptions(), {
+        spacing: o.spacing ?? 3, thickness: o.thickness ?? 1, intensity: (o.intensity ?? 30) / 100,
+    })]),
+    block_shift:    u8filter(wasm.block_shift_wasm, o => [Object.assign(new wasm.BlockShiftOptions(), {
+        block_height: o.block_height ?? 8, max_shift: o.max_shift ?? 20, probability: (o.probability ?? 20) / 100,
+        seed: o.seed ?? 0,
+    })]),
+    wave_distort:   u8filter(wasm.wave_distort_wasm, o => [Object.assign(new wasm.WaveDistortOptions(), {
+        amplitude: o.amplitude ?? 4, wavelength: o.wavelength ?? 64, phase: o.phase ?? 0, jitter: o.jitter ?? 0,
+        seed: o.seed ?? 0,
+    })]),
+
+    // --- Material maps ---
+    height_to_normal: u8filter(wasm.height_to_normal_wasm, o => [o.strength ?? 2.0, o.invert_y ?? false]),
     edge_detect: (imageData, options = {}) => {
         const method = options.method ?? 'sobel';
         switch (method) {
@@ -278,7 +366,9 @@ export function getFilterIds() {
  */
 export const filterMetadata = {
     grayscale:     { name: 'Grayscale',        category: 'color',      params: [] },
-    invert:        { name: 'Invert',           category: 'color',      params: [] },
+    invert:        { name: 'Invert',           category: 'color',      params: [
+        { id: 'channels', name: 'Channels', type: 'select', options: ['rgb', 'r', 'g', 'b', 'a', 'rgba'], default: 'rgb' },
+    ]},
     brightness_contrast: { name: 'Brightness/Contrast', category: 'color', params: [
         { id: 'brightness', name: 'Brightness', type: 'range', min: -100, max: 100, step: 1, default: 0, suffix: '%' },
         { id: 'contrast', name: 'Contrast', type: 'range', min: -100, max: 100, step: 1, default: 0, suffix: '%' },
@@ -288,7 +378,9 @@ export const filterMetadata = {
         { id: 'hue', name: 'Hue', type: 'range', min: -180, max: 180, step: 1, default: 0, suffix: '°' },
         { id: 'saturation', name: 'Saturation', type: 'range', min: -100, max: 100, step: 1, default: 0, suffix: '%' },
         { id: 'lightness', name: 'Lightness', type: 'range', min: -100, max: 100, step: 1, default: 0, suffix: '%' },
+        { id: 'colorize', name: 'Colorize', type: 'checkbox', default: false },
         { id: 'vibrance', name: 'Vibrance', type: 'range', min: -100, max: 100, step: 1, default: 0, suffix: '%' },
+        { id: 'protect_skin', name: 'Protect Skin', type: 'range', min: 0, max: 100, step: 1, default: 0, suffix: '%' },
         { id: 'temperature', name: 'Temperature', type: 'range', min: -100, max: 100, step: 1, default: 0 },
     ]},
     exposure:      { name: 'Exposure',         category: 'color',      params: [
@@ -305,12 +397,45 @@ export const filterMetadata = {
     sepia:         { name: 'Sepia',            category: 'color',      params: [
         { id: 'intensity', name: 'Intensity', type: 'range', min: 0, max: 100, step: 1, default: 100, suffix: '%' },
     ]},
+    white_balance: { name: 'White Balance',    category: 'color',      params: [
+        { id: 'kelvin', name: 'Temperature', type: 'range', min: 2000, max: 25000, step: 50, default: 6504, suffix: 'K' },
+        { id: 'tint', name: 'Tint', type: 'range', min: -100, max: 100, step: 1, default: 0 },
+    ]},
+    black_white:   { name: 'Black & White',    category: 'color',      params: [
+        { id: 'reds', name: 'Reds', type: 'range', min: -200, max: 300, step: 1, default: 40, suffix: '%' },
+        { id: 'yellows', name: 'Yellows', type: 'range', min: -200, max: 300, step: 1, default: 60, suffix: '%' },
+        { id: 'greens', name: 'Greens', type: 'range', min: -200, max: 300, step: 1, default: 40, suffix: '%' },
+        { id: 'cyans', name: 'Cyans', type: 'range', min: -200, max: 300, step: 1, default: 60, suffix: '%' },
+        { id: 'blues', name: 'Blues', type: 'range', min: -200, max: 300, step: 1, default: 20, suffix: '%' },
+        { id: 'magentas', name: 'Magentas', type: 'range', min: -200, max: 300, step: 1, default: 80, suffix: '%' },
+        { id: 'tint', name: 'Tint', type: 'checkbox', default: false },
+        { id: 'tint_color', name: 'Tint Color', type: 'color', default: '#e1d3b3' },
+    ]},
+    split_tone:    { name: 'Split Tone',       category: 'color',      params: [
+        { id: 'shadow_color', name: 'Shadows', type: 'color', default: '#2850a0' },
+        { id: 'highlight_color', name: 'Highlights', type: 'color', default: '#e6b45a' },
+        { id: 'balance', name: 'Balance', type: 'range', min: -100, max: 100, step: 1, default: 0 },
+    ]},
+    duotone:       { name: 'Duotone',          category: 'color',      params: [
+        { id: 'ink1', name: 'Ink 1', type: 'color', default: '#000000' },
+        { id: 'ink2', name: 'Ink 2', type: 'color', default: '#e68c28' },
+    ]},
     channel_mixer: { name: 'Channel Mixer',    category: 'color',      params: [
         { id: 'r_source', name: 'Red Source', type: 'select', options: [0, 1, 2], default: 0 },
         { id: 'g_source', name: 'Green Source', type: 'select', options: [0, 1, 2], default: 1 },
         { id: 'b_source', name: 'Blue Source', type: 'select', options: [0, 1, 2], default: 2 },
     ]},
-    equalize_histogram: { name: 'Equalize Histogram', category: 'color', params: [] },
+    simulate_cvd:  { name: 'Color Blindness Simulation', category: 'color', params: [
+        { id: 'deficiency', name: 'Type', type: 'select', options: ['protan', 'deutan', 'tritan'], default: 'deutan' },
+        { id: 'severity', name: 'Severity', type: 'range', min: 0, max: 100, step: 1, default: 100, suffix: '%' },
+    ]},
+    daltonize:     { name: 'Daltonize',        category: 'color',      params: [
+        { id: 'deficiency', name: 'Type', type: 'select', options: ['protan', 'deutan', 'tritan'], default: 'deutan' },
+        { id: 'amount', name: 'Amount', type: 'range', min: 0, max: 100, step: 1, default: 100, suffix: '%' },
+    ]},
+    equalize_histogram: { name: 'Equalize Histogram', category: 'color', params: [
+        { id: 'mode', name: 'Mode', type: 'select', options: ['per_channel', 'luminance'], default: 'per_channel' },
+    ]},
 
     levels:        { name: 'Levels',           category: 'color',      params: [
         { id: 'in_black',  name: 'Input Black',  type: 'range', min: 0, max: 255, step: 1, default: 0 },
@@ -323,7 +448,15 @@ export const filterMetadata = {
         { id: 'clip_percent', name: 'Clip %', type: 'range', min: 0, max: 5, step: 0.1, default: 1.0, suffix: '%' },
     ]},
     auto_contrast: { name: 'Auto Contrast', category: 'color', params: [
-        { id: 'clip_percent', name: 'Clip Percent', type: 'range', min: 0.0, max: 5.0, step: 0.1, default: 1.0, suffix: '%' },
+        { id: 'clip_percent', name: 'Clip Percent', type: 'range', min: 0.0, max: 5.0, step: 0.1, default: 0.1, suffix: '%' },
+    ]},
+    auto_tone:     { name: 'Auto Tone',       category: 'color',      params: [
+        { id: 'clip_percent', name: 'Clip %', type: 'range', min: 0, max: 5, step: 0.1, default: 0.1, suffix: '%' },
+        { id: 'snap_neutral', name: 'Snap Neutral Midtones', type: 'checkbox', default: false },
+    ]},
+    auto_color:    { name: 'Auto Color',      category: 'color',      params: [
+        { id: 'clip_percent', name: 'Clip %', type: 'range', min: 0, max: 5, step: 0.1, default: 0.1, suffix: '%' },
+        { id: 'snap_neutral', name: 'Snap Neutral Midtones', type: 'checkbox', default: true },
     ]},
     median_blur:   { name: 'Median Blur', category: 'blur', params: [
         { id: 'radius', name: 'Radius', type: 'range', min: 1, max: 21, step: 1, default: 3 },
@@ -331,9 +464,14 @@ export const filterMetadata = {
 
     edge_detect:   { name: 'Edge Detection',   category: 'edge',       params: [
         { id: 'method', name: 'Method', type: 'select', options: ['sobel', 'laplacian'], default: 'sobel' },
-        { id: 'direction', name: 'Direction', type: 'select', options: ['both', 'horizontal', 'vertical'], default: 'both', visible_when: { method: ['sobel'] } },
+        { id: 'direction', name: 'Direction', type: 'select', options: ['both', 'horizontal', 'vertical', 'angle'], default: 'both', visible_when: { method: ['sobel'] } },
+        { id: 'per_channel', name: 'Per Channel', type: 'checkbox', default: false, visible_when: { method: ['sobel'] } },
         { id: 'kernel_size', name: 'Kernel Size', type: 'select', options: [3, 5, 7], default: 3 },
     ]},
+    dog:           { name: 'Difference of Gaussians', category: 'edge', params: [
+        { id: 'sigma1', name: 'Radius 1', type: 'range', min: 0.1, max: 20, step: 0.1, default: 1.0 },
+        { id: 'sigma2', name: 'Radius 2', type: 'range', min: 0.1, max: 40, step: 0.1, default: 1.6 },
+    ]},
     find_contours: { name: 'Find Contours', category: 'edge', params: [
         { id: 'sigma', name: 'Sigma', type: 'range', min: 0.1, max: 5.0, step: 0.1, default: 1.0 },
         { id: 'low_threshold', name: 'Low Threshold', type: 'range', min: 0.01, max: 0.5, step: 0.01, default: 0.1 },
@@ -344,13 +482,23 @@ export const filterMetadata = {
 
     gaussian_blur: { name: 'Gaussian Blur',    category: 'blur',       params: [
         { id: 'sigma', name: 'Sigma', type: 'range', min: 0.1, max: 20, step: 0.1, default: 3.0, suffix: 'px' },
+        { id: 'srgb_linearize', name: 'Linear Light', type: 'checkbox', default: false },
+        { id: 'quality', name: 'Quality', type: 'select', options: ['exact', 'fast'], default: 'exact' },
     ]},
     box_blur:      { name: 'Box Blur',         category: 'blur',       params: [
         { id: 'radius', name: 'Radius', type: 'range', min: 1, max: 50, step: 1, default: 5, suffix: 'px' },
+        { id: 'srgb_linearize', name: 'Linear Light', type: 'checkbox', default: false },
     ]},
     motion_blur:   { name: 'Motion Blur',      category: 'blur',       params: [
         { id: 'angle', name: 'Angle', type: 'range', min: 0, max: 360, step: 1, default: 0, suffix: '°' },
         { id: 'distance', name: 'Distance', type: 'range', min: 1, max: 50, step: 1, default: 5, suffix: 'px' },
+        { id: 'border_mode', name: 'Edges', type: 'select', options: ['reflect', 'mirror', 'nearest', 'wrap', 'constant'], default: 'reflect' },
+    ]},
+    spin_blur:     { name: 'Spin Blur',        category: 'blur',       params: [
+        { id: 'angle', name: 'Angle', type: 'range', min: 0, max: 360, step: 1, default: 10, suffix: '°' },
+        { id: 'center_x', name: 'Center X', type: 'range', min: 0, max: 100, step: 1, default: 50, suffix: '%' },
+        { id: 'center_y', name: 'Center Y', type: 'range', min: 0, max: 100, step: 1, default: 50, suffix: '%' },
+        { id: 'border_mode', name: 'Edges', type: 'select', options: ['reflect', 'mirror', 'nearest', 'wrap', 'constant'], default: 'reflect' },
     ]},
 
     sharpen:       { name: 'Sharpen',          category: 'sharpen',    params: [
@@ -360,10 +508,31 @@ export const filterMetadata = {
         { id: 'amount', name: 'Amount', type: 'range', min: 0, max: 5, step: 0.1, default: 1.0 },
         { id: 'radius', name: 'Radius', type: 'range', min: 0.1, max: 20, step: 0.1, default: 1.0, suffix: 'px' },
         { id: 'threshold', name: 'Threshold', type: 'range', min: 0, max: 255, step: 1, default: 0 },
+        { id: 'edge_mask', name: 'Edge Mask', type: 'range', min: 0, max: 100, step: 1, default: 0, suffix: '%' },
+        { id: 'shadows', name: 'Shadows', type: 'range', min: 0, max: 200, step: 1, default: 100, suffix: '%' },
+        { id: 'highlights', name: 'Highlights', type: 'range', min: 0, max: 200, step: 1, default: 100, suffix: '%' },
     ]},
     high_pass:     { name: 'High Pass',        category: 'sharpen',    params: [
         { id: 'radius', name: 'Radius', type: 'range', min: 1, max: 50, step: 1, default: 3, suffix: 'px' },
     ]},
+    clarity:       { name: 'Clarity',          category: 'sharpen',    params: [
+        { id: 'amount', name: 'Amount', type: 'range', min: -100, max: 100, step: 1, default: 50 },
+        { id: 'radius', name: 'Radius', type: 'range', min: 5, max: 100, step: 1, default: 20, suffix: 'px' },
+    ]},
+    texture:       { name: 'Texture',          category: 'sharpen',    params: [
+        { id: 'amount', name: 'Amount', type: 'range', min: -100, max: 100, step: 1, default: 50 },
+    ]},
+    smart_sharpen: { name: 'Smart Sharpen',    category: 'sharpen',    params: [
+        { id: 'amount', name: 'Amount', type: 'range', min: 0, max: 500, step: 1, default: 100, suffix: '%' },
+        { id: 'radius', name: 'Radius', type: 'range', min: 0.1, max: 64, step: 0.1, default: 1, suffix: 'px' },
+        { id: 'mode', name: 'Remove', type: 'select', options: ['gaussian', 'lens', 'motion'], default: 'gaussian' },
+        { id: 'angle', name: 'Angle', type: 'range', min: 0, max: 360, step: 1, default: 0, suffix: '°' },
+    ]},
+    wavelet_sharpen: { name: 'Wavelet Sharpen', category: 'sharpen',  params: [
+        { id: 'fine', name: 'Fine (1-2 px)', type: 'range', min: 0, max: 4, step: 0.05, default: 1.8 },
+        { id: 'medium', name: 'Medium (2-4 px)', type: 'range', min: 0, max: 4, step: 0.05, default: 1.4 },
+        { id: 'coarse', name: 'Coarse (4-8 px)', type: 'range', min: 0, max: 4, step: 0.05, default: 1.0 },
+    ]},
 
     morphology_op: { name: 'Morphological',      category: 'morphology', params: [
         { id: 'operation', name: 'Operation', type: 'select', options: ['dilate', 'erode', 'open', 'close', 'gradient', 'tophat', 'blackhat'], default: 'dilate' },
@@ -378,9 +547,32 @@ export const filterMetadata = {
     median:        { name: 'Median',           category: 'noise',      params: [
         { id: 'radius', name: 'Radius', type: 'range', min: 1, max: 10, step: 1, default: 1 },
     ]},
+    remove_hot_pixels: { name: 'Remove Hot Pixels', category: 'noise', params: [
+        { id: 'threshold', name: 'Threshold', type: 'range', min: 1, max: 255, step: 1, default: 40 },
+    ]},
+    surface_blur:  { name: 'Surface Blur',     category: 'noise',      params: [
+        { id: 'radius', name: 'Radius', type: 'range', min: 1, max: 100, step: 1, default: 5, suffix: 'px' },
+        { id: 'threshold', name: 'Threshold', type: 'range', min: 2, max: 255, step: 1, default: 15 },
+    ]},
     denoise:       { name: 'Denoise',          category: 'noise',      params: [
         { id: 'strength', name: 'Strength', type: 'range', min: 0, max: 100, step: 1, default: 33, suffix: '%' },
     ]},
+    deband:        { name: 'Deband',           category: 'noise',      params: [
+        { id: 'threshold', name: 'Threshold', type: 'range', min: 1, max: 32, step: 1, default: 4 },
+        { id: 'range', name: 'Range', type: 'range', min: 1, max: 64, step: 1, default: 16, suffix: 'px' },
+        { id: 'grain', name: 'Grain', type: 'range', min: 0, max: 32, step: 1, default: 6 },
+    ]},
+    wavelet_denoise: { name: 'Wavelet Denoise', category: 'noise',    params: [
+        { id: 'fine', name: 'Fine (1-2 px)', type: 'range', min: 0, max: 64, step: 1, default: 8 },
+        { id: 'medium', name: 'Medium (2-4 px)', type: 'range', min: 0, max: 64, step: 1, default: 4 },
+        { id: 'coarse', name: 'Coarse (4-8 px)', type: 'range', min: 0, max: 64, step: 1, default: 2 },
+    ]},
+    film_grain:    { name: 'Film Grain',       category: 'noise',      params: [
+        { id: 'intensity', name: 'Intensity', type: 'range', min: 0, max: 100, step: 1, default: 30, suffix: '%' },
+        { id: 'size', name: 'Size', type: 'range', min: 0.5, max: 5, step: 0.1, default: 1.5, suffix: 'px' },
+        { id: 'roughness', name: 'Roughness', type: 'range', min: 0, max: 100, step: 1, default: 50, suffix: '%' },
+        { id: 'chroma_amount', name: 'Color', type: 'range', min: 0, max: 100, step: 1, default: 0, suffix: '%' },
+    ]},
 
     posterize:     { name: 'Posterize',        category: 'artistic',   params: [
         { id: 'levels', name: 'Levels', type: 'range', min: 2, max: 32, step: 1, default: 4 },
@@ -391,11 +583,55 @@ export const filterMetadata = {
     threshold:     { name: 'Threshold',        category: 'color',      params: [
         { id: 'threshold', name: 'Threshold', type: 'range', min: 0, max: 255, step: 1, default: 128 },
     ]},
+    adaptive_threshold: { name: 'Adaptive Threshold', category: 'color', params: [
+        { id: 'block_size', name: 'Block Size', type: 'range', min: 3, max: 99, step: 2, default: 11, suffix: 'px' },
+        { id: 'c', name: 'Offset', type: 'range', min: -50, max: 50, step: 1, default: 5 },
+        { id: 'method', name: 'Method', type: 'select', options: ['mean', 'gaussian'], default: 'mean' },
+    ]},
     pixelate:      { name: 'Pixelate',         category: 'artistic',   params: [
         { id: 'block_size', name: 'Block Size', type: 'range', min: 2, max: 50, step: 1, default: 10, suffix: 'px' },
+        { id: 'shape', name: 'Shape', type: 'select', options: ['square', 'circle', 'hexagon'], default: 'square' },
     ]},
     vignette:      { name: 'Vignette',         category: 'artistic',   params: [
         { id: 'amount', name: 'Amount', type: 'range', min: 0, max: 100, step: 1, default: 40, suffix: '%' },
+        { id: 'center_x', name: 'Center X', type: 'range', min: 0, max: 100, step: 1, default: 50, suffix: '%' },
+        { id: 'center_y', name: 'Center Y', type: 'range', min: 0, max: 100, step: 1, default: 50, suffix: '%' },
+        { id: 'midpoint', name: 'Midpoint', type: 'range', min: 0, max: 100, step: 1, default: 0, suffix: '%' },
+        { id: 'roundness', name: 'Roundness', type: 'range', min: -100, max: 100, step: 1, default: 100 },
+        { id: 'feather', name: 'Feather', type: 'range', min: 1, max: 100, step: 1, default: 100, suffix: '%' },
+        { id: 'colored', name: 'Use Color', type: 'checkbox', default: false },
+        { id: 'color', name: 'Color', type: 'color', default: '#ffffff' },
+    ]},
+    polar_coordinates: { name: 'Polar Coordinates', category: 'artistic', params: [
+        { id: 'direction', name: 'Direction', type: 'select', options: ['rect_to_polar', 'polar_to_rect'], default: 'rect_to_polar' },
+        { id: 'interpolation', name: 'Interpolation', type: 'select', options: ['nearest', 'bilinear', 'bicubic'], default: 'bilinear' },
+    ]},
+    twirl:         { name: 'Twirl',            category: 'artistic',   params: [
+        { id: 'angle', name: 'Angle', type: 'range', min: -999, max: 999, step: 1, default: 50, suffix: '°' },
+        { id: 'radius', name: 'Radius', type: 'range', min: 1, max: 100, step: 1, default: 100, suffix: '%' },
+    ]},
+    spherize:      { name: 'Spherize',         category: 'artistic',   params: [
+        { id: 'amount', name: 'Amount', type: 'range', min: -100, max: 100, step: 1, default: 100, suffix: '%' },
+    ]},
+    pinch:         { name: 'Pinch',            category: 'artistic',   params: [
+        { id: 'amount', name: 'Amount', type: 'range', min: -100, max: 100, step: 1, default: 50, suffix: '%' },
+    ]},
+    wave:          { name: 'Wave',             category: 'artistic',   params: [
+        { id: 'amplitude', name: 'Amplitude', type: 'range', min: 1, max: 100, step: 1, default: 10, suffix: 'px' },
+        { id: 'wavelength', name: 'Wavelength', type: 'range', min: 2, max: 500, step: 1, default: 120, suffix: 'px' },
+        { id: 'type', name: 'Type', type: 'select', options: ['sine', 'triangle', 'square'], default: 'sine' },
+    ]},
+    kaleidoscope:  { name: 'Kaleidoscope',     category: 'artistic',   params: [
+        { id: 'segments', name: 'Segments', type: 'range', min: 2, max: 32, step: 1, default: 6 },
+        { id: 'rotation', name: 'Rotation', type: 'range', min: 0, max: 360, step: 1, default: 0, suffix: '°' },
+    ]},
+    mirror:        { name: 'Mirror',           category: 'artistic',   params: [
+        { id: 'axis_angle', name: 'Axis Angle', type: 'range', min: -180, max: 180, step: 1, default: 0, suffix: '°' },
+        { id: 'offset', name: 'Offset', type: 'range', min: -500, max: 500, step: 1, default: 0, suffix: 'px' },
+    ]},
+    make_seamless: { name: 'Make Seamless',    category: 'artistic',   params: [
+        { id: 'method', name: 'Method', type: 'select', options: ['offset_blend', 'frequency'], default: 'offset_blend' },
+        { id: 'blend_width', name: 'Blend Width', type: 'range', min: 0, max: 500, step: 1, default: 0, suffix: 'px' },
     ]},
     emboss:        { name: 'Emboss',           category: 'artistic',   params: [
         { id: 'angle', name: 'Angle', type: 'range', min: 0, max: 360, step: 1, default: 135, suffix: '°' },
@@ -406,6 +642,40 @@ export const filterMetadata = {
         { id: 'sigma_r', name: 'Edge Strength', type: 'range', min: 1, max: 100, step: 1, default: 35, suffix: '%' },
         { id: 'shade_factor', name: 'Shade Factor', type: 'range', min: 0, max: 100, step: 1, default: 50, suffix: '%' },
     ]},
+    xdog:          { name: 'XDoG Sketch', category: 'artistic', params: [
+        { id: 'sigma', name: 'Line Scale', type: 'range', min: 0.3, max: 5, step: 0.1, default: 1.0 },
+        { id: 'k', name: 'Radius Ratio', type: 'range', min: 1.1, max: 5, step: 0.1, default: 1.6 },
+        { id: 'p', name: 'Sharpening', type: 'range', min: 0, max: 100, step: 1, default: 20 },
+        { id: 'epsilon', name: 'Threshold', type: 'range', min: -1, max: 1, step: 0.01, default: 0.1 },
+        { id: 'phi', name: 'Softness', type: 'range', min: 0.5, max: 100, step: 0.5, default: 10 },
+    ]},
+
+    rgb_split:     { name: 'RGB Split',        category: 'artistic',   params: [
+        { id: 'shift_x', name: 'Shift X', type: 'range', min: -50, max: 50, step: 1, default: 4, suffix: 'px' },
+        { id: 'shift_y', name: 'Shift Y', type: 'range', min: -50, max: 50, step: 1, default: 0, suffix: 'px' },
+    ]},
+    scanlines:     { name: 'Scanlines',        category: 'artistic',   params: [
+        { id: 'spacing', name: 'Spacing', type: 'range', min: 2, max: 20, step: 1, default: 3, suffix: 'px' },
+        { id: 'thickness', name: 'Thickness', type: 'range', min: 1, max: 10, step: 1, default: 1, suffix: 'px' },
+        { id: 'intensity', name: 'Intensity', type: 'range', min: 0, max: 100, step: 1, default: 30, suffix: '%' },
+    ]},
+    block_shift:   { name: 'Block Shift',      category: 'artistic',   params: [
+        { id: 'block_height', name: 'Block Height', type: 'range', min: 1, max: 100, step: 1, default: 8, suffix: 'px' },
+        { id: 'max_shift', name: 'Max Shift', type: 'range', min: 0, max: 200, step: 1, default: 20, suffix: 'px' },
+        { id: 'probability', name: 'Amount', type: 'range', min: 0, max: 100, step: 1, default: 20, suffix: '%' },
+        { id: 'seed', name: 'Seed', type: 'range', min: 0, max: 1000, step: 1, default: 0 },
+    ]},
+    wave_distort:  { name: 'Wave Distort',     category: 'artistic',   params: [
+        { id: 'amplitude', name: 'Amplitude', type: 'range', min: 0, max: 50, step: 0.5, default: 4, suffix: 'px' },
+        { id: 'wavelength', name: 'Wavelength', type: 'range', min: 4, max: 500, step: 1, default: 64, suffix: 'px' },
+        { id: 'jitter', name: 'Jitter', type: 'range', min: 0, max: 20, step: 0.5, default: 0, suffix: 'px' },
+        { id: 'seed', name: 'Seed', type: 'range', min: 0, max: 1000, step: 1, default: 0 },
+    ]},
+
+    height_to_normal: { name: 'Height to Normal', category: 'artistic', params: [
+        { id: 'strength', name: 'Strength', type: 'range', min: 0, max: 20, step: 0.1, default: 2.0 },
+        { id: 'invert_y', name: 'Invert Y (DirectX)', type: 'checkbox', default: false },
+    ]},
 };
 
 export default { initFilters, isInitialized, filters, applyFilter, getFilterIds, filterMetadata };
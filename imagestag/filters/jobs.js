@@ -0,0 +1,145 @@
+/**
+ * Chunked (async) filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - rust/src/chunked.rs (band-by-band execution)
+ * - jobs_worker.js (worker entry point)
+ *
+ * Heavy filters run one band of rows per step and yield to the event loop
+ * in between, so the page stays responsive. Progress is reported through a
+ * callback and as 'progress' events ({detail: {fraction}}) on an optional
+ * EventTarget; an AbortSignal cancels the job.
+ *
+ * Provides: runJob, median_async, denoise_async, surface_blur_async,
+ * gaussian_blur_async, runInWorker
+ *
+ * Usage:
+ *   const events = new EventTarget();
+ *   events.addEventListener('progress', (e) => bar.value = e.detail.fraction);
+ *   const result = await denoise_async(imageData, { strength: 0.5 }, { events });
+ *
+ * The WASM module must be initialized first (initWasm from core.js).
+ */
+
+// Imported directly rather than via core.js so the module also loads in workers
+import * as wasm from '../wasm/imagestag_rust.js';
+
+/** Milliseconds of filtering between two yields to the event loop. */
+const SLICE_MS = 12;
+
+function yieldToEventLoop() {
+    if (globalThis.scheduler?.yield) return globalThis.scheduler.yield();
+    return new Promise((resolve) => setTimeout(resolve, 0));
+}
+
+/**
+ * Run a FilterJob to completion, yielding between slices.
+ * @param {FilterJob} job - Job from one of the *_job_wasm exports (freed when done)
+ * @param {Object} options - {onProgress(fraction), events: EventTarget, signal: AbortSignal, sliceMs: 12}
+ * @returns {Promise<Object>} - {data: Uint8ClampedArray, width, height, channels}
+ */
+export async function runJob(job, options = {}) {
+    const { onProgress, events, signal } = options;
+    const sliceMs = options.sliceMs ?? SLICE_MS;
+    const report = (fraction) => {
+        onProgress?.(fraction);
+        events?.dispatchEvent(new CustomEvent('progress', { detail: { fraction } }));
+    };
+    try {
+        let done = false;
+        while (!done) {
+            signal?.throwIfAborted();
+            const start = performance.now();
+            do {
+                done = job.step();
+            } while (!done && performance.now() - start < sliceMs);
+            report(job.fraction());
+            if (!done) await yieldToEventLoop();
+        }
+        const { width, height, channels } = job;
+        const result = job.take_result();
+        return { data: new Uint8ClampedArray(result.buffer), width, height, channels };
+    } finally {
+        job.free();
+    }
+}
+
+function startJob(wasmFn, imageData, params) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    return wasmFn(new Uint8Array(data.buffer), width, height, channels, ...params);
+}
+
+// ============================================================================
+// Filters
+// ============================================================================
+
+const jobFilters = {
+    median: (opts) => [wasm.median_job_wasm, [opts.radius ?? 3]],
+    denoise: (opts) => [wasm.denoise_job_wasm, [opts.strength ?? 0.5]],
+    surface_blur: (opts) => [wasm.surface_blur_job_wasm, [opts.radius ?? 5, opts.threshold ?? 15]],
+    gaussian_blur: (opts) => [wasm.gaussian_blur_job_wasm, [opts.sigma ?? 2]],
+};
+
+/**
+ * Start the named chunked filter without running it.
+ * @param {string} name - 'median', 'denoise', 'surface_blur' or 'gaussian_blur'
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - Filter parameters
+ * @returns {FilterJob}
+ */
+export function createJob(name, imageData, options = {}) {
+    const filter = jobFilters[name];
+    if (!filter) throw new Error(`unknown job filter '${name}'`);
+    const [wasmFn, params] = filter(options);
+    return startJob(wasmFn, imageData, params);
+}
+
+/** Median filter without blocking the event loop (u8). Options: {radius}. */
+export const median_async = (imageData, options = {}, run = {}) => runJob(createJob('median', imageData, options), run);
+
+/** Non-local means denoise without blocking the event loop (u8). Options: {strength}. */
+export const denoise_async = (imageData, options = {}, run = {}) => runJob(createJob('denoise', imageData, options), run);
+
+/** Surface blur without blocking the event loop (u8). Options: {radius, threshold}. */
+export const surface_blur_async = (imageData, options = {}, run = {}) =>
+    runJob(createJob('surface_blur', imageData, options), run);
+
+/** Gaussian blur without blocking the event loop (u8). Options: {sigma}. */
+export const gaussian_blur_async = (imageData, options = {}, run = {}) =>
+    runJob(createJob('gaussian_blur', imageData, options), run);
+
+// ============================================================================
+// Worker
+// ============================================================================
+
+/**
+ * Run a chunked filter inside a worker started from jobs_worker.js.
+ *
+ * The pixel buffer is transferred to the worker (imageData.data is
+ * detached afterwards) and the result is transferred back.
+ * @param {Worker} worker - Module worker running jobs_worker.js, initialized with the compiled module
+ * @param {string} name - Filter name as for createJob
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - Filter parameters
+ * @param {Object} run - {onProgress(fraction), events: EventTarget}
+ * @returns {Promise<Object>} - Filtered image data
+ */
+export function runInWorker(worker, name, imageData, options = {}, run = {}) {
+    const id = Math.random().toString(36).slice(2);
+    return new Promise((resolve, reject) => {
+        const onMessage = ({ data: msg }) => {
+            if (msg.id !== id) return;
+            if (msg.type === 'progress') {
+                run.onProgress?.(msg.fraction);
+                run.events?.dispatchEvent(new CustomEvent('progress', { detail: { fraction: msg.fraction } }));
+                return;
+            }
+            worker.removeEventListener('message', onMessage);
+            if (msg.type === 'error') reject(new Error(msg.message));
+            else resolve(msg.result);
+        };
+        worker.addEventListener('message', onMessage);
+        worker.postMessage({ id, name, imageData, options }, [imageData.data.buffer]);
+    });
+}
@@ -0,0 +1,25 @@
+/**
+ * Worker entry point for chunked filters (see runInWorker in jobs.js).
+ *
+ * The first message carries the compiled module ({module}); every later
+ * message runs one job and answers with progress messages and the result,
+ * whose pixel buffer is transferred.
+ */
+
+import { initSync } from '../wasm/imagestag_rust.js';
+import { createJob, runJob } from './jobs.js';
+
+self.addEventListener('message', ({ data }) => {
+    initSync({ module: data.module });
+    self.addEventListener('message', async ({ data: { id, name, imageData, options } }) => {
+        try {
+            const result = await runJob(createJob(name, imageData, options), {
+                onProgress: (fraction) => self.postMessage({ id, type: 'progress', fraction }),
+            });
+            self.postMessage({ id, type: 'result', result }, [result.data.buffer]);
+        } catch (e) {
+            self.postMessage({ id, type: 'error', message: String(e?.message ?? e) });
+        }
+    });
+    self.postMessage('started');
+}, { once: true });
@@ -65,6 +65,9 @@ export async function initWasm() {
  * @param {number} [options.simplifyEpsilon=0.0] - Douglas-Peucker epsilon (0 = no simplification)
  * @param {boolean} [options.fitBeziers=false] - Whether to fit Bezier curves
  * @param {number} [options.bezierSmoothness=0.25] - Bezier smoothness (0.1-0.5)
+ * @param {number} [options.downsample=1] - Trace at 1/downsample resolution and refine
+ *     against the full mask (speeds up huge masks, 1 = off)
+ * @param {number} [options.tolerance=0.25] - Refinement tolerance in pixels
  * @returns {Contour[]} Array of contours
  */
 export function extractContours(mask, width, height, options = {}) {
@@ -73,14 +76,18 @@ export function extractContours(mask, width, height, options = {}) {
         simplifyEpsilon = 0.0,
         fitBeziers = false,
         bezierSmoothness = 0.25,
+        downsample = 1,
+        tolerance = 0.25,
     } = options;
 
     // Call WASM implementation
-    const flatResult = wasm.extract_contours_precise_wasm(
+    const flatResult = wasm.extract_contours_downsampled_wasm(
         new Uint8Array(mask.buffer || mask),
         width,
         height,
         threshold,
+        downsample,
+        tolerance,
         simplifyEpsilon,
         fitBeziers,
         bezierSmoothness
@@ -268,6 +275,130 @@ export function extractContoursToSvg(mask, width, height, options = {}) {
     });
 }
 
+/**
+ * Trace a whole image into a multi-color SVG document.
+ *
+ * The image is reduced to maxColors with k-means, every color becomes a
+ * layer traced with marching squares, and the layers are stacked from the
+ * most to the least frequent color.
+ *
+ * @param {Uint8Array|Uint8ClampedArray} data - Image data (RGB or RGBA), flattened
+ * @param {number} width - Image width
+ * @param {number} height - Image height
+ * @param {number} channels - Number of channels (1, 3 or 4)
+ * @param {Object} options - Tracing options
+ * @param {number} [options.maxColors=8] - Maximum number of color layers (1-255)
+ * @param {number} [options.simplifyEpsilon=1.0] - Douglas-Peucker epsilon
+ * @param {boolean} [options.fitBeziers=true] - Whether to fit Bezier curves
+ * @returns {string} Complete SVG document
+ */
+export function traceImageToSvg(data, width, height, channels, options = {}) {
+    const {
+        maxColors = 8,
+        simplifyEpsilon = 1.0,
+        fitBeziers = true,
+    } = options;
+
+    return wasm.trace_image_to_svg_wasm(
+        new Uint8Array(data.buffer || data),
+        width,
+        height,
+        channels,
+        maxColors,
+        simplifyEpsilon,
+        fitBeziers
+    );
+}
+
+/**
+ * Rasterize contours (including holes) back into a mask.
+ *
+ * Every contour is treated as closed; extracted contours fill back to the
+ * mask they came from.
+ *
+ * @param {Contour[]} contours - Outlines and holes (the polyline points are used)
+ * @param {number} width - Mask width
+ * @param {number} height - Mask height
+ * @param {string} [fillRule='nonzero'] - 'nonzero' or 'evenodd'
+ * @returns {Uint8Array} Mask (255 = inside), flattened row-major
+ */
+export function fillContours(contours, width, height, fillRule = 'nonzero') {
+    // Format: [num_contours, len1, x1, y1, ..., len2, ...]
+    const size = 1 + contours.reduce((n, c) => n + 1 + c.points.length * 2, 0);
+    const flat = new Float32Array(size);
+    flat[0] = contours.length;
+    let idx = 1;
+    for (const contour of contours) {
+        flat[idx++] = contour.points.length;
+        for (const p of contour.points) {
+            flat[idx++] = p.x;
+            flat[idx++] = p.y;
+        }
+    }
+    return wasm.fill_contours_wasm(flat, width, height, fillRule);
+}
+
+/**
+ * Combine two sets of closed contours without rasterizing them.
+ *
+ * @param {Contour[]} subject - Outlines and holes of the first operand (curves are used when present)
+ * @param {Contour[]} clip - Outlines and holes of the second operand
+ * @param {string} [op='union'] - 'union', 'intersect' or 'subtract' (subject minus clip)
+ * @param {Object} options - Options
+ * @param {string} [options.fillRule='nonzero'] - 'nonzero' or 'evenodd'
+ * @param {number} [options.tolerance=0.1] - Bezier flattening tolerance in pixels
+ * @returns {Contour[]} Closed polylines; outer boundaries clockwise on screen, holes counter-clockwise
+ */
+export function pathBoolean(subject, clip, op = 'union', options = {}) {
+    const { fillRule = 'nonzero', tolerance = 0.1 } = options;
+    const flat = wasm.path_boolean_wasm(
+        contoursToFlat(subject), contoursToFlat(clip), op, fillRule, tolerance
+    );
+    return parseFlatContours(flat);
+}
+
+/**
+ * Convert a stroked path into the filled outline of its stroke.
+ *
+ * @param {Contour} path - Polyline or Bezier path (isClosed decides between joins and caps)
+ * @param {number} width - Stroke width in pixels
+ * @param {Object} options - Options
+ * @param {string} [options.cap='butt'] - 'butt', 'round' or 'square'
+ * @param {string} [options.join='miter'] - 'miter', 'round' or 'bevel'
+ * @param {number} [options.miterLimit=4] - Largest miter length over stroke width before beveling
+ * @returns {Contour[]} Closed outlines; a closed path gives an outline and a hole
+ */
+export function strokePathOutline(path, width, options = {}) {
+    const { cap = 'butt', join = 'miter', miterLimit = 4 } = options;
+    const flat = wasm.stroke_path_outline_wasm(contoursToFlat([path]), width, cap, join, miterLimit);
+    return parseFlatContours(flat);
+}
+
+/**
+ * Encode contours into the flat format read by parseFlatContours.
+ *
+ * @param {Contour[]} contours - Contours to encode
+ * @returns {Float32Array} Flat array
+ */
+function contoursToFlat(contours) {
+    const values = [contours.length];
+    for (const contour of contours) {
+        values.push(contour.isClosed ? 1 : 0, contour.points.length);
+        for (const p of contour.points) {
+            values.push(p.x, p.y);
+        }
+        if (contour.beziers) {
+            values.push(1, contour.beziers.length);
+            for (const b of contour.beziers) {
+                values.push(b.p0.x, b.p0.y, b.p1.x, b.p1.y, b.p2.x, b.p2.y, b.p3.x, b.p3.y);
+            }
+        } else {
+            values.push(0);
+        }
+    }
+    return new Float32Array(values);
+}
+
 /**
  * Simplify a polyline using the Douglas-Peucker algorithm.
  *
@@ -325,3 +456,39 @@ export function douglasPeuckerClosed(points, epsilon) {
     }
     return result;
 }
+
+/**
+ * Split a contour into the dash polylines of a marching ants outline.
+ *
+ * The pattern alternates dashLength drawn and dashLength skipped along the
+ * contour. Advancing phase moves the dashes forward, so the canvas only has
+ * to upload new line segments per phase step instead of dashing every frame.
+ *
+ * @param {Contour} contour - Contour to dash (the polyline points are used)
+ * @param {number} [dashLength=4] - Length of each dash and gap in pixels
+ * @param {number} [phase=0] - Pattern offset in pixels; repeats every 2 * dashLength
+ * @returns {Array<Array<{x: number, y: number}>>} Dash polylines
+ */
+export function contourToDashSegments(contour, dashLength = 4, phase = 0) {
+    const flatPoints = new Float32Array(contour.points.length * 2);
+    for (let i = 0; i < contour.points.length; i++) {
+        flatPoints[i * 2] = contour.points[i].x;
+        flatPoints[i * 2 + 1] = contour.points[i].y;
+    }
+
+    const flat = wasm.contour_to_dash_segments_wasm(flatPoints, contour.isClosed, dashLength, phase);
+
+    // Format: [num_dashes, len1, x1, y1, ..., len2, ...]
+    const dashes = [];
+    let idx = 1;
+    for (let d = 0; d < flat[0]; d++) {
+        const count = flat[idx++];
+        const dash = [];
+        for (let i = 0; i < count; i++) {
+            dash.push({ x: flat[idx], y: flat[idx + 1] });
+            idx += 2;
+        }
+        dashes.push(dash);
+    }
+    return dashes;
+}
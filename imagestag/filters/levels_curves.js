@@ -5,7 +5,7 @@
  * - levels_curves.rs (Rust implementation)
  * - levels_curves.py (Python wrapper)
  *
- * Provides: levels, curves, auto_levels
+ * Provides: levels, curves, auto_levels, auto_contrast, auto_tone, auto_color
  */
 
 import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
@@ -20,7 +20,8 @@ export { initWasm };
  * Apply levels adjustment (u8).
  * Remaps input levels to output levels with gamma correction.
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {in_black, in_white, out_black, out_white, gamma}
+ * @param {Object} options - {in_black, in_white, out_black, out_white, gamma, channel}
+ *   - channel: 'rgb' (default), 'r', 'g', 'b', 'alpha' or 'luminosity'
  * @returns {Object} - Adjusted image data
  */
 export const levels = createU8Filter(
@@ -30,13 +31,14 @@ export const levels = createU8Filter(
         opts.in_white ?? 255,
         opts.out_black ?? 0,
         opts.out_white ?? 255,
-        opts.gamma ?? 1.0
+        opts.gamma ?? 1.0,
+        opts.channel ?? 'rgb'
     ]
 );
 
 /**
  * Apply levels adjustment (f32).
- * @param {Object} options - {in_black, in_white, out_black, out_white, gamma} (0.0-1.0 range)
+ * @param {Object} options - {in_black, in_white, out_black, out_white, gamma, channel} (0.0-1.0 range)
  */
 export const levels_f32 = createF32Filter(
     wasm.levels_f32_wasm,
@@ -45,7 +47,8 @@ export const levels_f32 = createF32Filter(
         (opts.in_white ?? 255) / 255.0,
         (opts.out_black ?? 0) / 255.0,
         (opts.out_white ?? 255) / 255.0,
-        opts.gamma ?? 1.0
+        opts.gamma ?? 1.0,
+        opts.channel ?? 'rgb'
     ]
 );
 
@@ -55,9 +58,12 @@ export const levels_f32 = createF32Filter(
 
 /**
  * Apply curves adjustment (u8).
- * Uses PCHIP interpolation for smooth curve fitting.
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {points: Array<[number, number]>} - Control points as [input, output] pairs (0.0-1.0)
+ * @param {Object} options - {points, interpolation, luminosityOnly, channel}
+ *   - points: Array<[number, number]> - Control points as [input, output] pairs (0.0-1.0)
+ *   - interpolation: 'pchip' (default), 'monotone_cubic' (Photoshop-like) or 'linear'
+ *   - luminosityOnly: Apply the curve to luminance only, preserving color ratios
+ *   - channel: 'rgb' (default), 'r', 'g', 'b', 'alpha' or 'luminosity'
  * @returns {Object} - Adjusted image data
  */
 export const curves = createU8Filter(
@@ -70,7 +76,7 @@ export const curves = createU8Filter(
             flat[i * 2] = points[i][0];
             flat[i * 2 + 1] = points[i][1];
         }
-        return [flat];
+        return [flat, opts.interpolation ?? 'pchip', opts.luminosityOnly ? 'luminosity' : (opts.channel ?? 'rgb')];
     }
 );
 
@@ -86,7 +92,7 @@ export const curves_f32 = createF32Filter(
             flat[i * 2] = points[i][0];
             flat[i * 2 + 1] = points[i][1];
         }
-        return [flat];
+        return [flat, opts.interpolation ?? 'pchip', opts.luminosityOnly ? 'luminosity' : (opts.channel ?? 'rgb')];
     }
 );
 
@@ -98,12 +104,14 @@ export const curves_f32 = createF32Filter(
  * Apply auto levels (histogram stretch) - u8 version.
  * Automatically adjusts levels based on image histogram.
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {clip_percent: number} (0.0-0.5, e.g., 0.01 = 1%)
+ * @param {Object} options - {clip_percent: number, channel: string}
+ *   - clip_percent: 0.0-0.5, e.g., 0.01 = 1%
+ *   - channel: 'rgb' (default), 'r', 'g', 'b', 'alpha' or 'luminosity'
  * @returns {Object} - Auto-leveled image data
  */
 export const auto_levels = createU8Filter(
     wasm.auto_levels_wasm,
-    (opts) => [opts.clip_percent ?? 0.01]
+    (opts) => [opts.clip_percent ?? 0.01, opts.channel ?? 'rgb']
 );
 
 /**
@@ -111,12 +119,76 @@ export const auto_levels = createU8Filter(
  */
 export const auto_levels_f32 = createF32Filter(
     wasm.auto_levels_f32_wasm,
-    (opts) => [opts.clip_percent ?? 0.01]
+    (opts) => [opts.clip_percent ?? 0.01, opts.channel ?? 'rgb']
+);
+
+// ============================================================================
+// Auto Contrast / Auto Tone / Auto Color
+// ============================================================================
+
+/**
+ * Apply auto contrast (shared black / white point) - u8 version.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {clip_percent: number} (fraction, 0.001 = 0.1%)
+ * @returns {Object} - Contrast-stretched image data
+ */
+export const auto_contrast = createU8Filter(
+    wasm.auto_contrast_wasm,
+    (opts) => [opts.clip_percent ?? 0.001]
+);
+
+/**
+ * Apply auto contrast (f32).
+ */
+export const auto_contrast_f32 = createF32Filter(
+    wasm.auto_contrast_f32_wasm,
+    (opts) => [opts.clip_percent ?? 0.001]
+);
+
+/**
+ * Apply auto tone (per-channel stretch) - u8 version.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {clip_percent: number, snap_neutral: boolean}
+ * @returns {Object} - Tone-corrected image data
+ */
+export const auto_tone = createU8Filter(
+    wasm.auto_tone_wasm,
+    (opts) => [opts.clip_percent ?? 0.001, opts.snap_neutral ?? false]
+);
+
+/**
+ * Apply auto tone (f32).
+ */
+export const auto_tone_f32 = createF32Filter(
+    wasm.auto_tone_f32_wasm,
+    (opts) => [opts.clip_percent ?? 0.001, opts.snap_neutral ?? false]
+);
+
+/**
+ * Apply auto color (dark / light color endpoints) - u8 version.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {clip_percent: number, snap_neutral: boolean}
+ * @returns {Object} - Color-corrected image data
+ */
+export const auto_color = createU8Filter(
+    wasm.auto_color_wasm,
+    (opts) => [opts.clip_percent ?? 0.001, opts.snap_neutral ?? true]
+);
+
+/**
+ * Apply auto color (f32).
+ */
+export const auto_color_f32 = createF32Filter(
+    wasm.auto_color_f32_wasm,
+    (opts) => [opts.clip_percent ?? 0.001, opts.snap_neutral ?? true]
 );
 
 export default {
     initWasm,
     levels, levels_f32,
     curves, curves_f32,
-    auto_levels, auto_levels_f32
+    auto_levels, auto_levels_f32,
+    auto_contrast, auto_contrast_f32,
+    auto_tone, auto_tone_f32,
+    auto_color, auto_color_f32
 };
@@ -4,6 +4,11 @@ This module provides tonal adjustment filters:
 - Levels (input/output range mapping with gamma)
 - Curves (spline-based tonal adjustment)
 - Auto Levels (histogram stretch)
+- Auto Contrast, Auto Tone, Auto Color (Photoshop-style automatic corrections)
+
+Levels, curves and auto levels take a ``channel``: "rgb" (default, all
+color channels), "r", "g", "b", "alpha" or "luminosity". "alpha" adjusts
+only the alpha channel, which refines selections stored as alpha masks.
 
 ## Supported Formats
 
@@ -22,6 +27,8 @@ All filters accept numpy arrays with 1, 3, or 4 channels:
 
 - **u8 (8-bit)**: Values 0-255, standard for web/display
 - **f32 (float)**: Values 0.0-1.0, for HDR/linear workflows
+- **12-bit**: uint16 values 0-4095 (``levels_12bit``, ``curves_12bit``),
+  for sensor data without a round trip through 8 bits
 
 Usage:
     from imagestag.filters.levels_curves import levels, curves, auto_levels
@@ -29,15 +36,22 @@ Usage:
     result = levels(image, in_black=20, in_white=240)
     result = curves(image, points=[(0, 0), (0.25, 0.2), (0.75, 0.8), (1, 1)])
     result = auto_levels(image, clip_percent=0.5)
+
+    # Harden the edge of an alpha mask
+    result = levels(rgba_image, in_black=96, in_white=160, channel="alpha")
 """
 import numpy as np
 
 import imagestag_rust
 
 
-def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
-    """Validate image shape and dtype."""
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str, frames: bool = False) -> None:
+    """Validate image shape and dtype.
+
+    With ``frames``, an (N, H, W, C) stack of animation frames is accepted too.
+    """
+    ndims = (3, 4) if frames else (3,)
+    if image.ndim not in ndims or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, 1|3|4), got shape {image.shape}")
     if image.dtype != expected_dtype:
         raise ValueError(f"Expected {expected_dtype} dtype, got {image.dtype}")
@@ -52,7 +66,8 @@ def levels(image: np.ndarray,
            in_white: int = 255,
            out_black: int = 0,
            out_white: int = 255,
-           gamma: float = 1.0) -> np.ndarray:
+           gamma: float = 1.0,
+           channel: str = "rgb") -> np.ndarray:
     """Apply levels adjustment (u8).
 
     Maps input range [in_black, in_white] to output range [out_black, out_white]
@@ -65,12 +80,14 @@ def levels(image: np.ndarray,
         out_black: Output black point (0-255)
         out_white: Output white point (0-255)
         gamma: Gamma correction (0.1-10.0), 1.0 = linear
+        channel: Channel to adjust - "rgb", "r", "g", "b", "alpha" or
+            "luminosity"; the other channels are left unchanged
 
     Returns:
         Levels-adjusted uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "levels")
-    return imagestag_rust.levels(image, in_black, in_white, out_black, out_white, gamma)
+    _validate_image(image, np.uint8, "levels", frames=True)
+    return imagestag_rust.levels(image, in_black, in_white, out_black, out_white, gamma, channel)
 
 
 def levels_f32(image: np.ndarray,
@@ -78,7 +95,8 @@ def levels_f32(image: np.ndarray,
                in_white: float = 1.0,
                out_black: float = 0.0,
                out_white: float = 1.0,
-               gamma: float = 1.0) -> np.ndarray:
+               gamma: float = 1.0,
+               channel: str = "rgb") -> np.ndarray:
     """Apply levels adjustment (f32).
 
     Args:
@@ -88,55 +106,126 @@ def levels_f32(image: np.ndarray,
         out_black: Output black point (0.0-1.0)
         out_white: Output white point (0.0-1.0)
         gamma: Gamma correction (0.1-10.0), 1.0 = linear
+        channel: "rgb", "r", "g", "b", "alpha" or "luminosity"
 
     Returns:
         Levels-adjusted float32 array with same channel count
     """
-    _validate_image(image, np.float32, "levels_f32")
-    return imagestag_rust.levels_f32(image, in_black, in_white, out_black, out_white, gamma)
+    _validate_image(image, np.float32, "levels_f32", frames=True)
+    return imagestag_rust.levels_f32(image, in_black, in_white, out_black, out_white, gamma, channel)
+
+
+def levels_12bit(image: np.ndarray,
+                 in_black: int = 0,
+                 in_white: int = 4095,
+                 out_black: int = 0,
+                 out_white: int = 4095,
+                 gamma: float = 1.0,
+                 channel: str = "rgb") -> np.ndarray:
+    """Apply levels adjustment (12-bit).
+
+    Args:
+        image: uint16 array with 1, 3, or 4 channels (H, W, C), values 0-4095
+        in_black: Input black point (0-4095)
+        in_white: Input white point (0-4095)
+        out_black: Output black point (0-4095)
+        out_white: Output white point (0-4095)
+        gamma: Gamma correction (0.1-10.0), 1.0 = linear
+        channel: "rgb", "r", "g", "b", "alpha" or "luminosity"
+
+    Returns:
+        Levels-adjusted uint16 array with same channel count
+    """
+    _validate_image(image, np.uint16, "levels_12bit", frames=True)
+    return imagestag_rust.levels_12bit(image, in_black, in_white, out_black, out_white, gamma, channel)
 
 
 # ============================================================================
 # Curves
 # ============================================================================
 
-def curves(image: np.ndarray, points: list) -> np.ndarray:
+def curves(
+    image: np.ndarray,
+    points: list,
+    interpolation: str = "pchip",
+    luminosity_only: bool = False,
+    channel: str = "rgb",
+) -> np.ndarray:
     """Apply curves adjustment (u8).
 
-    Uses Catmull-Rom spline interpolation through control points.
-
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         points: List of (input, output) tuples, values 0.0-1.0
                 Example: [(0, 0), (0.25, 0.2), (0.75, 0.8), (1, 1)]
+        interpolation: Curve shape between the points - 'pchip' (default,
+            scipy PchipInterpolator), 'monotone_cubic' (Fritsch-Carlson,
+            Photoshop-like) or 'linear'
+        luminosity_only: Apply the curve to luminance only, preserving
+            color ratios (RGB images only); same as channel="luminosity"
+        channel: Channel to adjust - "rgb", "r", "g", "b", "alpha" or
+            "luminosity"; the other channels are left unchanged
 
     Returns:
         Curves-adjusted uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "curves")
-    return imagestag_rust.curves(image, points)
+    return imagestag_rust.curves(image, points, interpolation, luminosity_only, channel)
 
 
-def curves_f32(image: np.ndarray, points: list) -> np.ndarray:
+def curves_f32(
+    image: np.ndarray,
+    points: list,
+    interpolation: str = "pchip",
+    luminosity_only: bool = False,
+    channel: str = "rgb",
+) -> np.ndarray:
     """Apply curves adjustment (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         points: List of (input, output) tuples, values 0.0-1.0
                 Example: [(0, 0), (0.25, 0.2), (0.75, 0.8), (1, 1)]
+        interpolation: 'pchip', 'monotone_cubic' or 'linear'
+        luminosity_only: Apply the curve to luminance only, preserving
+            color ratios (RGB images only); same as channel="luminosity"
+        channel: "rgb", "r", "g", "b", "alpha" or "luminosity"
 
     Returns:
         Curves-adjusted float32 array with same channel count
     """
     _validate_image(image, np.float32, "curves_f32")
-    return imagestag_rust.curves_f32(image, points)
+    return imagestag_rust.curves_f32(image, points, interpolation, luminosity_only, channel)
+
+
+def curves_12bit(
+    image: np.ndarray,
+    points: list,
+    interpolation: str = "pchip",
+    luminosity_only: bool = False,
+    channel: str = "rgb",
+) -> np.ndarray:
+    """Apply curves adjustment (12-bit).
+
+    Args:
+        image: uint16 array with 1, 3, or 4 channels (H, W, C), values 0-4095
+        points: List of (input, output) tuples, values 0.0-1.0
+        interpolation: 'pchip', 'monotone_cubic' or 'linear'
+        luminosity_only: Apply the curve to luminance only, preserving
+            color ratios (RGB images only); same as channel="luminosity"
+        channel: "rgb", "r", "g", "b", "alpha" or "luminosity"
+
+    Returns:
+        Curves-adjusted uint16 array with same channel count
+    """
+    _validate_image(image, np.uint16, "curves_12bit")
+    return imagestag_rust.curves_12bit(image, points, interpolation, luminosity_only, channel)
 
 
 # ============================================================================
 # Auto Levels
 # ============================================================================
 
-def auto_levels(image: np.ndarray, clip_percent: float = 0.0) -> np.ndarray:
+def auto_levels(image: np.ndarray, clip_percent: float = 0.0, channel: str = "rgb") -> np.ndarray:
     """Apply auto levels / histogram stretch (u8).
 
     Automatically stretches the histogram to use the full 0-255 range.
@@ -145,30 +234,110 @@ def auto_levels(image: np.ndarray, clip_percent: float = 0.0) -> np.ndarray:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         clip_percent: Percentage to clip from each end (0.0-50.0)
                       Higher values ignore outliers for more robust results
+        channel: Channel to stretch - "rgb" (each color channel on its own),
+            "r", "g", "b", "alpha" or "luminosity"
 
     Returns:
         Auto-leveled uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "auto_levels")
-    return imagestag_rust.auto_levels(image, clip_percent)
+    return imagestag_rust.auto_levels(image, clip_percent, channel)
 
 
-def auto_levels_f32(image: np.ndarray, clip_percent: float = 0.0) -> np.ndarray:
+def auto_levels_f32(image: np.ndarray, clip_percent: float = 0.0, channel: str = "rgb") -> np.ndarray:
     """Apply auto levels / histogram stretch (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         clip_percent: Percentage to clip from each end (0.0-50.0)
+        channel: "rgb", "r", "g", "b", "alpha" or "luminosity"
 
     Returns:
         Auto-leveled float32 array with same channel count
     """
     _validate_image(image, np.float32, "auto_levels_f32")
-    return imagestag_rust.auto_levels_f32(image, clip_percent)
+    return imagestag_rust.auto_levels_f32(image, clip_percent, channel)
+
+
+# ============================================================================
+# Auto Contrast / Auto Tone / Auto Color
+# ============================================================================
+
+def auto_contrast(image: np.ndarray, clip_percent: float = 0.001) -> np.ndarray:
+    """Apply auto contrast (u8).
+
+    Stretches all color channels with one shared black / white point, so
+    contrast improves without shifting the color balance.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        clip_percent: Fraction to clip from each end (0.001 = 0.1%)
+
+    Returns:
+        Contrast-stretched uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "auto_contrast")
+    return imagestag_rust.auto_contrast(image, clip_percent)
+
+
+def auto_contrast_f32(image: np.ndarray, clip_percent: float = 0.001) -> np.ndarray:
+    """Apply auto contrast (f32)."""
+    _validate_image(image, np.float32, "auto_contrast_f32")
+    return imagestag_rust.auto_contrast_f32(image, clip_percent)
+
+
+def auto_tone(image: np.ndarray, clip_percent: float = 0.001, snap_neutral: bool = False) -> np.ndarray:
+    """Apply auto tone (u8).
+
+    Stretches every color channel on its own (like auto_levels).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        clip_percent: Fraction to clip from each end (0.001 = 0.1%)
+        snap_neutral: Also make near-gray midtones neutral (RGB only)
+
+    Returns:
+        Tone-corrected uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "auto_tone")
+    return imagestag_rust.auto_tone(image, clip_percent, snap_neutral)
+
+
+def auto_tone_f32(image: np.ndarray, clip_percent: float = 0.001, snap_neutral: bool = False) -> np.ndarray:
+    """Apply auto tone (f32)."""
+    _validate_image(image, np.float32, "auto_tone_f32")
+    return imagestag_rust.auto_tone_f32(image, clip_percent, snap_neutral)
+
+
+def auto_color(image: np.ndarray, clip_percent: float = 0.001, snap_neutral: bool = True) -> np.ndarray:
+    """Apply auto color (u8).
+
+    Averages the darkest and lightest pixels and maps them to black and
+    white per channel, which removes color casts.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        clip_percent: Fraction of darkest / lightest pixels to average (0.001 = 0.1%)
+        snap_neutral: Also make near-gray midtones neutral (RGB only)
+
+    Returns:
+        Color-corrected uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "auto_color")
+    return imagestag_rust.auto_color(image, clip_percent, snap_neutral)
+
+
+def auto_color_f32(image: np.ndarray, clip_percent: float = 0.001, snap_neutral: bool = True) -> np.ndarray:
+    """Apply auto color (f32)."""
+    _validate_image(image, np.float32, "auto_color_f32")
+    return imagestag_rust.auto_color_f32(image, clip_percent, snap_neutral)
 
 
 __all__ = [
-    'levels', 'levels_f32',
-    'curves', 'curves_f32',
+    'levels', 'levels_f32', 'levels_12bit',
+    'curves', 'curves_f32', 'curves_12bit',
     'auto_levels', 'auto_levels_f32',
+    'auto_contrast', 'auto_contrast_f32',
+    'auto_tone', 'auto_tone_f32',
+    'auto_color', 'auto_color_f32',
 ]
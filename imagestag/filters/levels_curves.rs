@@ -1,7 +1,11 @@
-//! Levels and curves filters: Levels, Curves, Auto Levels.
+//! Levels and curves filters: Levels, Curves, Auto Levels, Auto Contrast,
+//! Auto Color, Auto Tone.
 //!
 //! These filters manipulate the tonal range of images.
-//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
+//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes. Levels and
+//! curves also have `_12bit` variants for 12-bit data stored in u16
+//! (0-4095), as delivered by camera sensors, which keep the full precision
+//! instead of going through 8 bits.
 //!
 //! ## Supported Formats
 //!
@@ -9,8 +13,174 @@
 //! - **Grayscale**: (height, width, 1) - processes the single channel
 //! - **RGB**: (height, width, 3) - processes all 3 channels
 //! - **RGBA**: (height, width, 4) - processes RGB, preserves alpha
+//!
+//! Curves can run in luminosity mode: the curve is applied to the BT.709
+//! luminance and RGB are scaled by the same factor, so color ratios (hue and
+//! saturation) are kept - like Photoshop's Curves layer in Luminosity mode.
+//!
+//! The `*_channel` variants take a [`LevelsChannel`] and adjust a single
+//! channel instead - red, green, blue, alpha or luminosity - which is how
+//! selections stored as alpha masks are refined.
+
+use ndarray::{Array3, ArrayView3, Axis};
+
+use crate::filters::grayscale::{LUMA_B, LUMA_G, LUMA_R};
+use crate::filters::fmath;
+
+/// Largest 12-bit value.
+const MAX_12BIT: u16 = 4095;
+
+// ============================================================================
+// Channel Selection
+// ============================================================================
+
+/// Channel a levels, curves or auto levels adjustment is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelsChannel {
+    /// Red, green and blue with the same adjustment (composite)
+    #[default]
+    Rgb,
+    Red,
+    Green,
+    Blue,
+    /// Alpha only; images without alpha are returned unchanged
+    Alpha,
+    /// BT.709 luminance, with RGB scaled by the same factor to keep color ratios
+    Luminosity,
+}
+
+impl LevelsChannel {
+    /// Parse a channel name ("rgb", "r", "g", "b", "alpha", "luminosity").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "rgb" | "composite" => Some(LevelsChannel::Rgb),
+            "r" | "red" => Some(LevelsChannel::Red),
+            "g" | "green" => Some(LevelsChannel::Green),
+            "b" | "blue" => Some(LevelsChannel::Blue),
+            "a" | "alpha" => Some(LevelsChannel::Alpha),
+            "luminosity" | "luma" | "l" => Some(LevelsChannel::Luminosity),
+            _ => None,
+        }
+    }
+
+    /// Canonical name, accepted by [`LevelsChannel::from_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            LevelsChannel::Rgb => "rgb",
+            LevelsChannel::Red => "r",
+            LevelsChannel::Green => "g",
+            LevelsChannel::Blue => "b",
+            LevelsChannel::Alpha => "alpha",
+            LevelsChannel::Luminosity => "luminosity",
+        }
+    }
+
+    /// Whether this is a luminosity adjustment of an image with RGB channels.
+    fn is_luminosity(self, channels: usize) -> bool {
+        self == LevelsChannel::Luminosity && channels >= 3
+    }
+
+    /// Channel indices adjusted one by one for an image with `channels` channels.
+    ///
+    /// A single-channel image is its own red, green, blue and luminance.
+    fn targets(self, channels: usize) -> Vec<usize> {
+        let color_channels = if channels == 4 { 3 } else { channels };
+        match self {
+            LevelsChannel::Rgb | LevelsChannel::Luminosity => (0..color_channels).collect(),
+            LevelsChannel::Red | LevelsChannel::Green | LevelsChannel::Blue if color_channels < 3 => vec![0],
+            LevelsChannel::Red => vec![0],
+            LevelsChannel::Green => vec![1],
+            LevelsChannel::Blue => vec![2],
+            LevelsChannel::Alpha if channels == 4 => vec![3],
+            LevelsChannel::Alpha => Vec::new(),
+        }
+    }
+}
+
+/// Apply a tone table to the selected channel - u8 version.
+///
+/// `tone[v]` is the output (0-255, unclamped) for input value `v`. In
+/// luminosity mode the table is interpolated at the fractional luminance.
+fn apply_tone_u8(input: ArrayView3<u8>, channel: LevelsChannel, tone: &[f32]) -> Array3<u8> {
+    apply_tone_table(input, channel, tone, |v| v.clamp(0.0, 255.0) as u8)
+}
+
+/// Apply a tone table to the selected channel - 12-bit version.
+///
+/// `tone[v]` is the output (0-4095, unclamped) for input value `v`; inputs
+/// above 4095 use the last entry.
+fn apply_tone_12bit(input: ArrayView3<u16>, channel: LevelsChannel, tone: &[f32]) -> Array3<u16> {
+    let clamped = input.mapv(|v| v.min(MAX_12BIT));
+    apply_tone_table(clamped.view(), channel, tone, |v| v.round().clamp(0.0, MAX_12BIT as f32) as u16)
+}
+
+/// Shared implementation of the integer tone table variants.
+fn apply_tone_table<T>(input: ArrayView3<T>, channel: LevelsChannel, tone: &[f32], store: impl Fn(f32) -> T) -> Array3<T>
+where
+    T: Copy + Into<f32> + Into<usize>,
+{
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let last = tone.len() - 2;
+
+    if channel.is_luminosity(channels) {
+        for y in 0..height {
+            for x in 0..width {
+                let [r, g, b] = [0, 1, 2].map(|c| Into::<f32>::into(input[[y, x, c]]));
+                let luma = LUMA_R * r + LUMA_G * g + LUMA_B * b;
+                // Interpolate the table at the fractional luminance
+                let i = (luma as usize).min(last);
+                let target = tone[i] + (tone[i + 1] - tone[i]) * (luma - i as f32);
+                for (c, v) in [r, g, b].into_iter().enumerate() {
+                    let adjusted = luminosity_factor(luma, target).map_or(target, |f| v * f);
+                    output[[y, x, c]] = store(adjusted);
+                }
+            }
+        }
+        return output;
+    }
+
+    let lut: Vec<T> = tone.iter().map(|&v| store(v)).collect();
+    for c in channel.targets(channels) {
+        output.index_axis_mut(Axis(2), c).mapv_inplace(|v| lut[Into::<usize>::into(v)]);
+    }
+    output
+}
+
+/// Apply a tone function to the selected channel - f32 version.
+///
+/// Results are clamped to 0.0-1.0. In luminosity mode RGB is clamped to
+/// 0.0-1.0 before the luminance is computed.
+fn apply_tone_f32(input: ArrayView3<f32>, channel: LevelsChannel, tone: impl Fn(f32) -> f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
 
-use ndarray::{Array3, ArrayView3};
+    if channel.is_luminosity(channels) {
+        for y in 0..height {
+            for x in 0..width {
+                let [r, g, b] = [0, 1, 2].map(|c| input[[y, x, c]].clamp(0.0, 1.0));
+                let luma = LUMA_R * r + LUMA_G * g + LUMA_B * b;
+                let target = tone(luma);
+                for (c, v) in [r, g, b].into_iter().enumerate() {
+                    let adjusted = luminosity_factor(luma, target).map_or(target, |f| v * f);
+                    output[[y, x, c]] = adjusted.clamp(0.0, 1.0);
+                }
+            }
+        }
+        return output;
+    }
+
+    for c in channel.targets(channels) {
+        output.index_axis_mut(Axis(2), c).mapv_inplace(|v| tone(v).clamp(0.0, 1.0));
+    }
+    output
+}
+
+/// Scale factor that moves a pixel's luminance from `luma` to `target`.
+#[inline]
+fn luminosity_factor(luma: f32, target: f32) -> Option<f32> {
+    (luma > 1e-6).then(|| target / luma)
+}
 
 // ============================================================================
 // Levels
@@ -38,37 +208,34 @@ pub fn levels_u8(
     out_white: u8,
     gamma: f32,
 ) -> Array3<u8> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
+    levels_channel_u8(input, in_black, in_white, out_black, out_white, gamma, LevelsChannel::Rgb)
+}
 
+/// Apply levels adjustment to one channel - u8 version.
+///
+/// Same as [`levels_u8`], but only `channel` is adjusted; other channels
+/// are copied unchanged.
+pub fn levels_channel_u8(
+    input: ArrayView3<u8>,
+    in_black: u8,
+    in_white: u8,
+    out_black: u8,
+    out_white: u8,
+    gamma: f32,
+    channel: LevelsChannel,
+) -> Array3<u8> {
     let in_range = (in_white as f32 - in_black as f32).max(1.0);
     let out_range = out_white as f32 - out_black as f32;
     let inv_gamma = 1.0 / gamma.max(0.001);
 
-    let color_channels = if channels == 4 { 3 } else { channels };
-
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..color_channels {
-                let v = input[[y, x, c]] as f32;
-
-                // Map input range to 0-1
-                let normalized = ((v - in_black as f32) / in_range).clamp(0.0, 1.0);
-
-                // Apply gamma
-                let gamma_corrected = normalized.powf(inv_gamma);
-
-                // Map to output range
-                let result = out_black as f32 + gamma_corrected * out_range;
-
-                output[[y, x, c]] = result.clamp(0.0, 255.0) as u8;
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
-        }
-    }
-    output
+    let tone: Vec<f32> = (0..256)
+        .map(|v| {
+            // Map input range to 0-1, apply gamma, map to output range
+            let normalized = ((v as f32 - in_black as f32) / in_range).clamp(0.0, 1.0);
+            out_black as f32 + fmath::powf(normalized, inv_gamma) * out_range
+        })
+        .collect();
+    apply_tone_u8(input, channel, &tone)
 }
 
 /// Apply levels adjustment - f32 version.
@@ -91,44 +258,195 @@ pub fn levels_f32(
     out_white: f32,
     gamma: f32,
 ) -> Array3<f32> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
+    levels_channel_f32(input, in_black, in_white, out_black, out_white, gamma, LevelsChannel::Rgb)
+}
 
+/// Apply levels adjustment to one channel - f32 version.
+///
+/// Same as [`levels_f32`], but only `channel` is adjusted.
+pub fn levels_channel_f32(
+    input: ArrayView3<f32>,
+    in_black: f32,
+    in_white: f32,
+    out_black: f32,
+    out_white: f32,
+    gamma: f32,
+    channel: LevelsChannel,
+) -> Array3<f32> {
     let in_range = (in_white - in_black).max(0.001);
     let out_range = out_white - out_black;
     let inv_gamma = 1.0 / gamma.max(0.001);
 
-    let color_channels = if channels == 4 { 3 } else { channels };
+    apply_tone_f32(input, channel, |v| {
+        let normalized = ((v - in_black) / in_range).clamp(0.0, 1.0);
+        out_black + fmath::powf(normalized, inv_gamma) * out_range
+    })
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..color_channels {
-                let v = input[[y, x, c]];
+/// Apply levels adjustment - 12-bit version.
+///
+/// Same as [`levels_u8`] with all levels in the 0-4095 range.
+pub fn levels_12bit(
+    input: ArrayView3<u16>,
+    in_black: u16,
+    in_white: u16,
+    out_black: u16,
+    out_white: u16,
+    gamma: f32,
+) -> Array3<u16> {
+    levels_channel_12bit(input, in_black, in_white, out_black, out_white, gamma, LevelsChannel::Rgb)
+}
 
-                let normalized = ((v - in_black) / in_range).clamp(0.0, 1.0);
-                let gamma_corrected = normalized.powf(inv_gamma);
-                let result = out_black + gamma_corrected * out_range;
+/// Apply levels adjustment to one channel - 12-bit version.
+pub fn levels_channel_12bit(
+    input: ArrayView3<u16>,
+    in_black: u16,
+    in_white: u16,
+    out_black: u16,
+    out_white: u16,
+    gamma: f32,
+    channel: LevelsChannel,
+) -> Array3<u16> {
+    let in_range = (in_white as f32 - in_black as f32).max(1.0);
+    let out_range = out_white as f32 - out_black as f32;
+    let inv_gamma = 1.0 / gamma.max(0.001);
 
-                output[[y, x, c]] = result.clamp(0.0, 1.0);
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
-        }
-    }
-    output
+    let tone: Vec<f32> = (0..=MAX_12BIT)
+        .map(|v| {
+            let normalized = ((v as f32 - in_black as f32) / in_range).clamp(0.0, 1.0);
+            out_black as f32 + fmath::powf(normalized, inv_gamma) * out_range
+        })
+        .collect();
+    apply_tone_12bit(input, channel, &tone)
 }
 
 // ============================================================================
 // Curves
 // ============================================================================
 
+/// Interpolation between curve control points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurveInterpolation {
+    /// Straight segments between control points
+    Linear,
+    /// PCHIP, matching scipy.interpolate.PchipInterpolator (reference parity)
+    #[default]
+    Pchip,
+    /// Fritsch-Carlson monotone cubic spline - the smooth, non-overshooting
+    /// shape of Photoshop's curve editor
+    MonotoneCubic,
+}
+
+impl CurveInterpolation {
+    /// Parse an interpolation name. Unknown names map to PCHIP.
+    pub fn parse(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "linear" => CurveInterpolation::Linear,
+            "monotone_cubic" | "monotone" | "spline" => CurveInterpolation::MonotoneCubic,
+            _ => CurveInterpolation::Pchip,
+        }
+    }
+
+    /// Evaluate the curve through `points` (sorted by input) at `t`.
+    pub fn evaluate(self, points: &[(f32, f32)], t: f32) -> f32 {
+        match self {
+            CurveInterpolation::Linear => linear_interpolate(points, t),
+            CurveInterpolation::Pchip => pchip_interpolate(points, t),
+            CurveInterpolation::MonotoneCubic => monotone_cubic_interpolate(points, t),
+        }
+    }
+}
+
+/// Index of the segment containing `t`, clamped to the first / last segment.
+fn find_segment(points: &[(f32, f32)], t: f32) -> usize {
+    let n = points.len();
+    (0..n - 1).find(|&i| t <= points[i + 1].0).unwrap_or(n - 2)
+}
+
+/// Piecewise-linear interpolation, constant outside the control points.
+pub fn linear_interpolate(points: &[(f32, f32)], t: f32) -> f32 {
+    let n = points.len();
+    if n == 0 {
+        return t;
+    }
+    if n == 1 || t <= points[0].0 {
+        return points[0].1;
+    }
+    if t >= points[n - 1].0 {
+        return points[n - 1].1;
+    }
+
+    let k = find_segment(points, t);
+    let (x0, y0) = points[k];
+    let (x1, y1) = points[k + 1];
+    if (x1 - x0).abs() < 1e-10 {
+        return y1;
+    }
+    y0 + (y1 - y0) * (t - x0) / (x1 - x0)
+}
+
+/// Fritsch-Carlson monotone cubic interpolation, constant outside the control points.
+///
+/// Tangents start as the mean of the adjacent secants and are then limited
+/// so that every segment stays monotone - the curve never overshoots a
+/// control point, but flows through it without the kinks of linear segments.
+pub fn monotone_cubic_interpolate(points: &[(f32, f32)], t: f32) -> f32 {
+    let n = points.len();
+    if n < 3 {
+        return linear_interpolate(points, t);
+    }
+    if t <= points[0].0 {
+        return points[0].1;
+    }
+    if t >= points[n - 1].0 {
+        return points[n - 1].1;
+    }
+
+    let h: Vec<f32> = points.windows(2).map(|w| (w[1].0 - w[0].0).max(1e-10)).collect();
+    let delta: Vec<f32> = points.windows(2).zip(&h).map(|(w, &h)| (w[1].1 - w[0].1) / h).collect();
+
+    // Initial tangents: one-sided secants at the ends, averages inside
+    let mut m = vec![0.0f32; n];
+    m[0] = delta[0];
+    m[n - 1] = delta[n - 2];
+    for i in 1..n - 1 {
+        m[i] = if delta[i - 1] * delta[i] <= 0.0 { 0.0 } else { (delta[i - 1] + delta[i]) / 2.0 };
+    }
+
+    // Limit tangents to the monotonicity region (alpha^2 + beta^2 <= 9)
+    for k in 0..n - 1 {
+        if delta[k].abs() < 1e-10 {
+            m[k] = 0.0;
+            m[k + 1] = 0.0;
+            continue;
+        }
+        let alpha = m[k] / delta[k];
+        let beta = m[k + 1] / delta[k];
+        let radius = alpha * alpha + beta * beta;
+        if radius > 9.0 {
+            let tau = 3.0 / radius.sqrt();
+            m[k] = tau * alpha * delta[k];
+            m[k + 1] = tau * beta * delta[k];
+        }
+    }
+
+    let k = find_segment(points, t);
+    let s = (t - points[k].0) / h[k];
+    let s2 = s * s;
+    let s3 = s2 * s;
+    let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+    let h10 = s3 - 2.0 * s2 + s;
+    let h01 = -2.0 * s3 + 3.0 * s2;
+    let h11 = s3 - s2;
+    points[k].1 * h00 + h[k] * m[k] * h10 + points[k + 1].1 * h01 + h[k] * m[k + 1] * h11
+}
+
 /// PCHIP (Piecewise Cubic Hermite Interpolating Polynomial) interpolation.
 /// Matches scipy.interpolate.PchipInterpolator behavior.
 ///
 /// PCHIP preserves monotonicity and doesn't overshoot at control points,
 /// making it ideal for tone curve adjustments.
-fn pchip_interpolate(points: &[(f32, f32)], t: f32) -> f32 {
+pub fn pchip_interpolate(points: &[(f32, f32)], t: f32) -> f32 {
     let n = points.len();
 
     if n == 0 {
@@ -258,70 +576,98 @@ fn pchip_endpoint_slope(h1: f32, h2: f32, delta1: f32, delta2: f32) -> f32 {
 
 /// Apply curves adjustment - u8 version.
 ///
-/// Uses PCHIP interpolation to match scipy.interpolate.PchipInterpolator.
-///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
 /// * `points` - Control points as (input, output) pairs, values 0.0-1.0
 ///              Must include (0,0) and (1,1) endpoints if you want them anchored.
+/// * `interpolation` - Curve shape between the control points
+/// * `luminosity_only` - Apply the curve to luminance only, preserving color
+///   ratios (RGB images only)
 ///
 /// # Returns
 /// Curves-adjusted image with same channel count
-pub fn curves_u8(input: ArrayView3<u8>, points: &[(f32, f32)]) -> Array3<u8> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
+pub fn curves_u8(
+    input: ArrayView3<u8>,
+    points: &[(f32, f32)],
+    interpolation: CurveInterpolation,
+    luminosity_only: bool,
+) -> Array3<u8> {
+    let channel = if luminosity_only { LevelsChannel::Luminosity } else { LevelsChannel::Rgb };
+    curves_channel_u8(input, points, interpolation, channel)
+}
 
+/// Apply curves adjustment to one channel - u8 version.
+///
+/// Same as [`curves_u8`], but only `channel` is adjusted; other channels
+/// are copied unchanged.
+pub fn curves_channel_u8(
+    input: ArrayView3<u8>,
+    points: &[(f32, f32)],
+    interpolation: CurveInterpolation,
+    channel: LevelsChannel,
+) -> Array3<u8> {
     // Pre-compute lookup table for efficiency
-    let mut lut = [0u8; 256];
-    for i in 0..256 {
-        let t = i as f32 / 255.0;
-        let result = pchip_interpolate(points, t);
-        lut[i] = (result * 255.0).clamp(0.0, 255.0) as u8;
-    }
-
-    let color_channels = if channels == 4 { 3 } else { channels };
-
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..color_channels {
-                output[[y, x, c]] = lut[input[[y, x, c]] as usize];
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
-        }
-    }
-    output
+    let curve: Vec<f32> = (0..256).map(|i| interpolation.evaluate(points, i as f32 / 255.0) * 255.0).collect();
+    apply_tone_u8(input, channel, &curve)
 }
 
 /// Apply curves adjustment - f32 version.
 ///
-/// Uses PCHIP interpolation to match scipy.interpolate.PchipInterpolator.
-///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
 /// * `points` - Control points as (input, output) pairs, values 0.0-1.0
+/// * `interpolation` - Curve shape between the control points
+/// * `luminosity_only` - Apply the curve to luminance only, preserving color
+///   ratios (RGB images only)
 ///
 /// # Returns
 /// Curves-adjusted image with same channel count
-pub fn curves_f32(input: ArrayView3<f32>, points: &[(f32, f32)]) -> Array3<f32> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
+pub fn curves_f32(
+    input: ArrayView3<f32>,
+    points: &[(f32, f32)],
+    interpolation: CurveInterpolation,
+    luminosity_only: bool,
+) -> Array3<f32> {
+    let channel = if luminosity_only { LevelsChannel::Luminosity } else { LevelsChannel::Rgb };
+    curves_channel_f32(input, points, interpolation, channel)
+}
 
-    let color_channels = if channels == 4 { 3 } else { channels };
+/// Apply curves adjustment to one channel - f32 version.
+///
+/// Same as [`curves_f32`], but only `channel` is adjusted.
+pub fn curves_channel_f32(
+    input: ArrayView3<f32>,
+    points: &[(f32, f32)],
+    interpolation: CurveInterpolation,
+    channel: LevelsChannel,
+) -> Array3<f32> {
+    apply_tone_f32(input, channel, |v| interpolation.evaluate(points, v.clamp(0.0, 1.0)))
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..color_channels {
-                let v = input[[y, x, c]].clamp(0.0, 1.0);
-                output[[y, x, c]] = pchip_interpolate(points, v).clamp(0.0, 1.0);
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
-        }
-    }
-    output
+/// Apply curves adjustment - 12-bit version.
+///
+/// Same as [`curves_u8`] for values 0-4095; the curve is evaluated once per
+/// 12-bit level.
+pub fn curves_12bit(
+    input: ArrayView3<u16>,
+    points: &[(f32, f32)],
+    interpolation: CurveInterpolation,
+    luminosity_only: bool,
+) -> Array3<u16> {
+    let channel = if luminosity_only { LevelsChannel::Luminosity } else { LevelsChannel::Rgb };
+    curves_channel_12bit(input, points, interpolation, channel)
+}
+
+/// Apply curves adjustment to one channel - 12-bit version.
+pub fn curves_channel_12bit(
+    input: ArrayView3<u16>,
+    points: &[(f32, f32)],
+    interpolation: CurveInterpolation,
+    channel: LevelsChannel,
+) -> Array3<u16> {
+    let max = MAX_12BIT as f32;
+    let curve: Vec<f32> = (0..=MAX_12BIT).map(|i| interpolation.evaluate(points, i as f32 / max) * max).collect();
+    apply_tone_12bit(input, channel, &curve)
 }
 
 // ============================================================================
@@ -382,18 +728,33 @@ fn percentile_from_sorted_u8(sorted: &[u8], p: f32) -> f32 {
 /// # Returns
 /// Auto-leveled image with same channel count
 pub fn auto_levels_u8(input: ArrayView3<u8>, clip_percent: f32) -> Array3<u8> {
+    auto_levels_channel_u8(input, clip_percent, LevelsChannel::Rgb)
+}
+
+/// Apply auto levels to one channel - u8 version.
+///
+/// Same as [`auto_levels_u8`], but only `channel` is stretched. In
+/// luminosity mode the endpoints come from the luminance histogram.
+pub fn auto_levels_channel_u8(input: ArrayView3<u8>, clip_percent: f32, channel: LevelsChannel) -> Array3<u8> {
     let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
 
     // clip_percent is a fraction (0.01 = 1%)
     // Convert to percentile values: 0.01 -> 1% and 99%
     let p_low = clip_percent * 100.0;  // e.g., 1.0 for 1%
     let p_high = (1.0 - clip_percent) * 100.0;  // e.g., 99.0 for 99%
 
-    let color_channels = if channels == 4 { 3 } else { channels };
+    if channel.is_luminosity(channels) {
+        let sorted = collect_luma_values(input.mapv(|v| v as f32).view());
+        let low = percentile_from_sorted_f32(&sorted, p_low);
+        let range = (percentile_from_sorted_f32(&sorted, p_high) - low).max(1.0);
+        let tone: Vec<f32> = (0..256).map(|v| (v as f32 - low) * 255.0 / range).collect();
+        return apply_tone_u8(input, channel, &tone);
+    }
+
+    let mut output = input.to_owned();
 
     // Process each channel independently
-    for c in 0..color_channels {
+    for c in channel.targets(channels) {
         let sorted = collect_channel_values_u8(input, c);
 
         let low = percentile_from_sorted_u8(&sorted, p_low);
@@ -410,16 +771,21 @@ pub fn auto_levels_u8(input: ArrayView3<u8>, clip_percent: f32) -> Array3<u8> {
         }
     }
 
-    // Copy alpha
-    if channels == 4 {
-        for y in 0..height {
-            for x in 0..width {
-                output[[y, x, 3]] = input[[y, x, 3]];
-            }
+    output
+}
+
+/// Collect the BT.709 luminance of every pixel, sorted for percentile calculation.
+fn collect_luma_values(input: ArrayView3<f32>) -> Vec<f32> {
+    let (height, width, _) = input.dim();
+    let mut values = Vec::with_capacity(height * width);
+
+    for y in 0..height {
+        for x in 0..width {
+            values.push(LUMA_R * input[[y, x, 0]] + LUMA_G * input[[y, x, 1]] + LUMA_B * input[[y, x, 2]]);
         }
     }
-
-    output
+    values.sort_by(f32::total_cmp);
+    values
 }
 
 /// Collect all pixel values from a channel into a sorted array for percentile calculation.
@@ -432,7 +798,7 @@ fn collect_channel_values_f32(input: ArrayView3<f32>, channel: usize) -> Vec<f32
             values.push(input[[y, x, channel]]);
         }
     }
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    values.sort_by(f32::total_cmp);
     values
 }
 
@@ -473,16 +839,30 @@ fn percentile_from_sorted_f32(sorted: &[f32], p: f32) -> f32 {
 /// # Returns
 /// Auto-leveled image with same channel count
 pub fn auto_levels_f32(input: ArrayView3<f32>, clip_percent: f32) -> Array3<f32> {
+    auto_levels_channel_f32(input, clip_percent, LevelsChannel::Rgb)
+}
+
+/// Apply auto levels to one channel - f32 version.
+///
+/// Same as [`auto_levels_f32`], but only `channel` is stretched.
+pub fn auto_levels_channel_f32(input: ArrayView3<f32>, clip_percent: f32, channel: LevelsChannel) -> Array3<f32> {
     let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
 
     // clip_percent is a fraction (0.01 = 1%)
     let p_low = clip_percent * 100.0;
     let p_high = (1.0 - clip_percent) * 100.0;
 
-    let color_channels = if channels == 4 { 3 } else { channels };
+    if channel.is_luminosity(channels) {
+        let clamped = input.mapv(|v| v.clamp(0.0, 1.0));
+        let sorted = collect_luma_values(clamped.view());
+        let low = percentile_from_sorted_f32(&sorted, p_low);
+        let range = (percentile_from_sorted_f32(&sorted, p_high) - low).max(0.001);
+        return apply_tone_f32(input, channel, |v| (v - low) / range);
+    }
 
-    for c in 0..color_channels {
+    let mut output = input.to_owned();
+
+    for c in channel.targets(channels) {
         let sorted = collect_channel_values_f32(input, c);
 
         let low = percentile_from_sorted_f32(&sorted, p_low);
@@ -499,16 +879,176 @@ pub fn auto_levels_f32(input: ArrayView3<f32>, clip_percent: f32) -> Array3<f32>
         }
     }
 
-    // Copy alpha
-    if channels == 4 {
-        for y in 0..height {
-            for x in 0..width {
-                output[[y, x, 3]] = input[[y, x, 3]];
+    output
+}
+
+// ============================================================================
+// Auto Contrast / Auto Color / Auto Tone
+// ============================================================================
+
+/// Stretch each color channel from `low[c]..high[c]` to 0-1, then apply
+/// `gamma[c]` (as an exponent). Alpha is preserved.
+fn remap_channels_f32(input: ArrayView3<f32>, low: &[f32], high: &[f32], gamma: &[f32]) -> Array3<f32> {
+    let mut output = input.to_owned();
+    for (c, ((&lo, &hi), &g)) in low.iter().zip(high).zip(gamma).enumerate() {
+        let range = (hi - lo).max(0.001);
+        output.index_axis_mut(Axis(2), c).mapv_inplace(|v| fmath::powf(((v - lo) / range).clamp(0.0, 1.0), g));
+    }
+    output
+}
+
+/// Per-channel gammas that turn the average near-neutral midtone gray.
+///
+/// Photoshop's "Snap Neutral Midtones": pixels that are almost gray and
+/// neither dark nor bright are assumed to be neutral in the scene, so any
+/// tint they carry is a color cast.
+fn neutral_midtone_gamma(stretched: ArrayView3<f32>) -> Vec<f32> {
+    let (height, width, _) = stretched.dim();
+    let mut sum = [0.0f64; 3];
+    let mut count = 0usize;
+    for y in 0..height {
+        for x in 0..width {
+            let [r, g, b] = [0, 1, 2].map(|c| stretched[[y, x, c]]);
+            let luma = LUMA_R * r + LUMA_G * g + LUMA_B * b;
+            let chroma = r.max(g).max(b) - r.min(g).min(b);
+            if (0.2..=0.8).contains(&luma) && chroma < 0.15 {
+                for (s, v) in sum.iter_mut().zip([r, g, b]) {
+                    *s += v as f64;
+                }
+                count += 1;
             }
         }
     }
+    if count == 0 {
+        return vec![1.0; 3];
+    }
+    let mean = sum.map(|s| (s / count as f64) as f32);
+    let target = (mean[0] + mean[1] + mean[2]) / 3.0;
+    // m^gamma = target  =>  gamma = ln(target) / ln(m)
+    mean.iter().map(|&m| (fmath::ln(target) / fmath::ln(m.clamp(0.01, 0.99))).clamp(0.5, 2.0)).collect()
+}
 
-    output
+/// Stretch with the given endpoints and optionally snap neutral midtones.
+fn finish_auto_f32(input: ArrayView3<f32>, low: &[f32], high: &[f32], snap_neutral: bool) -> Array3<f32> {
+    let ones = vec![1.0; low.len()];
+    let stretched = remap_channels_f32(input, low, high, &ones);
+    if !snap_neutral || low.len() < 3 {
+        return stretched;
+    }
+    let gamma = neutral_midtone_gamma(stretched.view());
+    remap_channels_f32(stretched.view(), &[0.0; 3], &[1.0; 3], &gamma)
+}
+
+/// Apply auto contrast (monochromatic histogram stretch) - f32 version.
+///
+/// Like auto levels, but one black / white point is shared by all color
+/// channels, so contrast improves without changing the color balance
+/// (Photoshop's "Enhance Monochromatic Contrast").
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `clip_percent` - Fraction to clip from each end (0.0-0.5, e.g., 0.001 = 0.1%)
+///
+/// # Returns
+/// Contrast-stretched image with same channel count
+pub fn auto_contrast_f32(input: ArrayView3<f32>, clip_percent: f32) -> Array3<f32> {
+    let color_channels = input.dim().2.min(3);
+    let mut values: Vec<f32> = (0..color_channels).flat_map(|c| collect_channel_values_f32(input, c)).collect();
+    values.sort_by(f32::total_cmp);
+
+    let low = percentile_from_sorted_f32(&values, clip_percent * 100.0);
+    let high = percentile_from_sorted_f32(&values, (1.0 - clip_percent) * 100.0);
+    finish_auto_f32(input, &vec![low; color_channels], &vec![high; color_channels], false)
+}
+
+/// Apply auto contrast - u8 version.
+pub fn auto_contrast_u8(input: ArrayView3<u8>, clip_percent: f32) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    auto_contrast_f32(input_f32.view(), clip_percent).mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Apply auto tone (per-channel histogram stretch) - f32 version.
+///
+/// Stretches every color channel on its own, which also removes casts that
+/// only shift a channel's endpoints (Photoshop's "Enhance Per Channel
+/// Contrast"). Same stretch as [`auto_levels_f32`].
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `clip_percent` - Fraction to clip from each end (0.0-0.5, e.g., 0.001 = 0.1%)
+/// * `snap_neutral` - Also neutralize near-gray midtones (RGB images only)
+///
+/// # Returns
+/// Tone-corrected image with same channel count
+pub fn auto_tone_f32(input: ArrayView3<f32>, clip_percent: f32, snap_neutral: bool) -> Array3<f32> {
+    let color_channels = input.dim().2.min(3);
+    let (low, high): (Vec<f32>, Vec<f32>) = (0..color_channels)
+        .map(|c| {
+            let sorted = collect_channel_values_f32(input, c);
+            (
+                percentile_from_sorted_f32(&sorted, clip_percent * 100.0),
+                percentile_from_sorted_f32(&sorted, (1.0 - clip_percent) * 100.0),
+            )
+        })
+        .unzip();
+    finish_auto_f32(input, &low, &high, snap_neutral)
+}
+
+/// Apply auto tone - u8 version.
+pub fn auto_tone_u8(input: ArrayView3<u8>, clip_percent: f32, snap_neutral: bool) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    auto_tone_f32(input_f32.view(), clip_percent, snap_neutral).mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Apply auto color (cast removal) - f32 version.
+///
+/// Finds the darkest and lightest pixels by luminance and averages their
+/// colors; each channel is then stretched so those colors become black and
+/// white, aligning the channel endpoints (Photoshop's "Find Dark & Light
+/// Colors"). With `snap_neutral`, near-gray midtones are made neutral too.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `clip_percent` - Fraction of darkest / lightest pixels to average (e.g., 0.001 = 0.1%)
+/// * `snap_neutral` - Also neutralize near-gray midtones (RGB images only)
+///
+/// # Returns
+/// Color-corrected image with same channel count
+pub fn auto_color_f32(input: ArrayView3<f32>, clip_percent: f32, snap_neutral: bool) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    if channels < 3 {
+        return auto_tone_f32(input, clip_percent, false);
+    }
+
+    let luma = |y: usize, x: usize| LUMA_R * input[[y, x, 0]] + LUMA_G * input[[y, x, 1]] + LUMA_B * input[[y, x, 2]];
+    let mut sorted: Vec<f32> = (0..height).flat_map(|y| (0..width).map(move |x| (y, x))).map(|(y, x)| luma(y, x)).collect();
+    sorted.sort_by(f32::total_cmp);
+    let dark_limit = percentile_from_sorted_f32(&sorted, clip_percent * 100.0);
+    let light_limit = percentile_from_sorted_f32(&sorted, (1.0 - clip_percent) * 100.0);
+
+    let mut dark = [0.0f64; 4];
+    let mut light = [0.0f64; 4];
+    for y in 0..height {
+        for x in 0..width {
+            let l = luma(y, x);
+            for (acc, hit) in [(&mut dark, l <= dark_limit), (&mut light, l >= light_limit)] {
+                if hit {
+                    for c in 0..3 {
+                        acc[c] += input[[y, x, c]] as f64;
+                    }
+                    acc[3] += 1.0;
+                }
+            }
+        }
+    }
+    let average = |acc: [f64; 4]| -> Vec<f32> { (0..3).map(|c| (acc[c] / acc[3].max(1.0)) as f32).collect() };
+    finish_auto_f32(input, &average(dark), &average(light), snap_neutral)
+}
+
+/// Apply auto color - u8 version.
+pub fn auto_color_u8(input: ArrayView3<u8>, clip_percent: f32, snap_neutral: bool) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    auto_color_f32(input_f32.view(), clip_percent, snap_neutral).mapv(|v| (v * 255.0).round() as u8)
 }
 
 #[cfg(test)]
@@ -558,7 +1098,7 @@ mod tests {
         img[[0, 0, 3]] = 255;
 
         let points = vec![(0.0, 0.0), (1.0, 1.0)];
-        let result = curves_u8(img.view(), &points);
+        let result = curves_u8(img.view(), &points, CurveInterpolation::Pchip, false);
 
         assert!((result[[0, 0, 0]] as i32 - 128).abs() <= 1);
     }
@@ -577,12 +1117,115 @@ mod tests {
             (0.75, 0.85),
             (1.0, 1.0),
         ];
-        let result = curves_f32(img.view(), &points);
+        let result = curves_f32(img.view(), &points, CurveInterpolation::Pchip, false);
 
         // Midpoint should stay roughly at 0.5
         assert!((result[[0, 0, 0]] - 0.5).abs() < 0.1);
     }
 
+    #[test]
+    fn test_monotone_cubic_is_smooth_without_overshoot() {
+        let points = [(0.0, 0.0), (0.3, 0.1), (0.4, 0.9), (1.0, 1.0)];
+        let samples: Vec<f32> = (0..=100)
+            .map(|i| CurveInterpolation::MonotoneCubic.evaluate(&points, i as f32 / 100.0))
+            .collect();
+        assert!(samples.windows(2).all(|w| w[1] >= w[0] - 1e-6));
+        assert!(samples.iter().all(|&v| (0.0..=1.0).contains(&v)));
+        assert!((CurveInterpolation::MonotoneCubic.evaluate(&points, 0.4) - 0.9).abs() < 1e-6);
+
+        // Linear has a kink at the control point, the spline bends through it
+        let linear = CurveInterpolation::Linear.evaluate(&points, 0.7);
+        assert!((linear - 0.95).abs() < 1e-6);
+        assert!(samples[70] > linear);
+    }
+
+    #[test]
+    fn test_curves_luminosity_preserves_color_ratios() {
+        let mut img = Array3::<f32>::zeros((1, 1, 3));
+        img[[0, 0, 0]] = 0.4;
+        img[[0, 0, 1]] = 0.2;
+        img[[0, 0, 2]] = 0.1;
+
+        let points = [(0.0, 0.0), (0.5, 0.7), (1.0, 1.0)];
+        let result = curves_f32(img.view(), &points, CurveInterpolation::MonotoneCubic, true);
+
+        assert!(result[[0, 0, 0]] > 0.4);
+        assert!((result[[0, 0, 0]] / result[[0, 0, 1]] - 2.0).abs() < 1e-4);
+        assert!((result[[0, 0, 1]] / result[[0, 0, 2]] - 2.0).abs() < 1e-4);
+
+        let img_u8 = img.mapv(|v| (v * 255.0) as u8);
+        let result_u8 = curves_u8(img_u8.view(), &points, CurveInterpolation::MonotoneCubic, true);
+        assert!((result_u8[[0, 0, 0]] as f32 - result[[0, 0, 0]] * 255.0).abs() <= 1.5);
+    }
+
+    #[test]
+    fn test_levels_channel_alpha_only() {
+        let img = Array3::from_shape_fn((1, 2, 4), |(_, x, c)| if c == 3 { 64 + 64 * x as u8 } else { 100 });
+        // Threshold-like levels on the mask: 64-128 -> 0-255
+        let result = levels_channel_u8(img.view(), 64, 128, 0, 255, 1.0, LevelsChannel::Alpha);
+        assert_eq!(result[[0, 0, 3]], 0);
+        assert_eq!(result[[0, 1, 3]], 255);
+        assert!((0..3).all(|c| result[[0, 0, c]] == 100 && result[[0, 1, c]] == 100));
+
+        // No alpha to adjust
+        let rgb = Array3::<u8>::from_elem((1, 1, 3), 100);
+        assert_eq!(levels_channel_u8(rgb.view(), 64, 128, 0, 255, 1.0, LevelsChannel::Alpha), rgb);
+    }
+
+    #[test]
+    fn test_curves_channel_single_color() {
+        let img = Array3::<f32>::from_elem((1, 1, 4), 0.5);
+        let points = [(0.0, 1.0), (1.0, 0.0)];
+        let result = curves_channel_f32(img.view(), &points, CurveInterpolation::Linear, LevelsChannel::Green);
+        assert_eq!(result.iter().copied().collect::<Vec<_>>(), vec![0.5, 0.5, 0.5, 0.5]);
+
+        let img = Array3::from_shape_fn((1, 1, 3), |(_, _, c)| 0.2 * (c + 1) as f32);
+        let result = curves_channel_f32(img.view(), &points, CurveInterpolation::Linear, LevelsChannel::Blue);
+        assert!((result[[0, 0, 2]] - 0.4).abs() < 1e-6);
+        assert_eq!(result[[0, 0, 0]], img[[0, 0, 0]]);
+
+        // Red of a grayscale image is its only channel
+        let gray = Array3::<u8>::from_elem((1, 1, 1), 55);
+        let result = curves_channel_u8(gray.view(), &points, CurveInterpolation::Linear, LevelsChannel::Red);
+        assert_eq!(result[[0, 0, 0]], 200);
+    }
+
+    #[test]
+    fn test_channel_wrappers_match_composite() {
+        let img = Array3::from_shape_fn((3, 5, 4), |(y, x, c)| (y * 40 + x * 17 + c * 29) as u8);
+        let points = [(0.0, 0.1), (0.4, 0.6), (1.0, 0.9)];
+        for (channel, luminosity_only) in [(LevelsChannel::Rgb, false), (LevelsChannel::Luminosity, true)] {
+            assert_eq!(
+                curves_channel_u8(img.view(), &points, CurveInterpolation::Pchip, channel),
+                curves_u8(img.view(), &points, CurveInterpolation::Pchip, luminosity_only)
+            );
+        }
+        assert_eq!(
+            levels_channel_u8(img.view(), 20, 200, 10, 240, 1.3, LevelsChannel::Rgb),
+            levels_u8(img.view(), 20, 200, 10, 240, 1.3)
+        );
+        assert_eq!(LevelsChannel::from_name("Composite"), Some(LevelsChannel::Rgb));
+        assert_eq!(LevelsChannel::from_name(LevelsChannel::Alpha.name()), Some(LevelsChannel::Alpha));
+        assert_eq!(LevelsChannel::from_name("cyan"), None);
+    }
+
+    #[test]
+    fn test_auto_levels_channel_luminosity_keeps_ratios() {
+        let img = Array3::from_shape_fn((1, 4, 3), |(_, x, c)| (0.2 + 0.1 * x as f32) * [0.6, 0.5, 0.4][c]);
+        let result = auto_levels_channel_f32(img.view(), 0.0, LevelsChannel::Luminosity);
+        let luma = |x: usize| LUMA_R * result[[0, x, 0]] + LUMA_G * result[[0, x, 1]] + LUMA_B * result[[0, x, 2]];
+        assert!(luma(0).abs() < 1e-5);
+        for x in 1..3 {
+            assert!((luma(x) - x as f32 / 3.0).abs() < 1e-4);
+            assert!((result[[0, x, 0]] / result[[0, x, 1]] - 1.2).abs() < 1e-4);
+        }
+
+        let mask = Array3::from_shape_fn((1, 4, 4), |(_, x, c)| if c == 3 { 0.25 + 0.1 * x as f32 } else { 0.5 });
+        let result = auto_levels_channel_f32(mask.view(), 0.0, LevelsChannel::Alpha);
+        assert!(result[[0, 0, 3]].abs() < 1e-5 && (result[[0, 3, 3]] - 1.0).abs() < 1e-5);
+        assert_eq!(result[[0, 2, 0]], 0.5);
+    }
+
     #[test]
     fn test_auto_levels_u8() {
         let mut img = Array3::<u8>::zeros((2, 2, 4));
@@ -615,6 +1258,42 @@ mod tests {
         assert_eq!(max, 255);
     }
 
+    #[test]
+    fn test_auto_contrast_shares_endpoints() {
+        // Red spans 0.2-0.6, green 0.4-0.8: shared range is 0.2-0.8
+        let img = Array3::from_shape_fn((1, 2, 3), |(_, x, c)| match c {
+            0 => 0.2 + 0.4 * x as f32,
+            1 => 0.4 + 0.4 * x as f32,
+            _ => 0.5,
+        });
+        let result = auto_contrast_f32(img.view(), 0.0);
+        assert!(result[[0, 0, 0]].abs() < 1e-5);
+        assert!((result[[0, 1, 1]] - 1.0).abs() < 1e-5);
+        assert!((result[[0, 1, 0]] - 2.0 / 3.0).abs() < 1e-4);
+
+        let tone = auto_tone_f32(img.view(), 0.0, false);
+        assert!((tone[[0, 1, 0]] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_auto_color_removes_cast() {
+        // Gray ramp with a blue cast in the shadows and a yellow cast in the highlights
+        let img = Array3::from_shape_fn((1, 11, 3), |(_, x, c)| {
+            let v = 0.1 + 0.08 * x as f32;
+            match c {
+                2 => v + 0.05 - 0.01 * x as f32,
+                _ => v,
+            }
+        });
+        let result = auto_color_f32(img.view(), 0.0, true);
+        for x in [0, 5, 10] {
+            let spread = (0..3).map(|c| result[[0, x, c]]).fold(0.0f32, f32::max)
+                - (0..3).map(|c| result[[0, x, c]]).fold(1.0f32, f32::min);
+            assert!(spread < 0.03, "x={x} spread={spread}");
+        }
+        assert!(result[[0, 0, 0]] < 0.01 && result[[0, 10, 1]] > 0.99);
+    }
+
     #[test]
     fn test_auto_levels_f32_preserves_alpha() {
         let mut img = Array3::<f32>::zeros((1, 1, 4));
@@ -627,4 +1306,26 @@ mod tests {
 
         assert!((result[[0, 0, 3]] - 0.7).abs() < 0.001);
     }
+
+    #[test]
+    fn test_levels_and_curves_12bit() {
+        let img = Array3::from_shape_fn((1, 4, 4), |(_, x, c)| if c == 3 { 4095 } else { [0u16, 1000, 2048, 4095][x] });
+        // Identity keeps every 12-bit level, no 8-bit banding
+        assert_eq!(levels_12bit(img.view(), 0, 4095, 0, 4095, 1.0), img);
+        let identity = [(0.0, 0.0), (1.0, 1.0)];
+        assert_eq!(curves_12bit(img.view(), &identity, CurveInterpolation::Linear, false), img);
+
+        // Stretching 1000-3000 to the full range
+        let stretched = levels_12bit(img.view(), 1000, 3000, 0, 4095, 1.0);
+        assert_eq!(stretched[[0, 1, 0]], 0);
+        assert_eq!(stretched[[0, 2, 0]], 2146);
+        assert_eq!(stretched[[0, 3, 0]], 4095);
+        assert_eq!(stretched[[0, 0, 3]], 4095);
+
+        let inverted = curves_12bit(img.view(), &[(0.0, 1.0), (1.0, 0.0)], CurveInterpolation::Linear, false);
+        assert_eq!(inverted[[0, 1, 1]], 3095);
+        // Out-of-range input is treated as white
+        let hot = Array3::from_elem((1, 1, 1), 5000u16);
+        assert_eq!(levels_12bit(hot.view(), 0, 4095, 0, 2000, 1.0)[[0, 0, 0]], 2000);
+    }
 }
@@ -0,0 +1,64 @@
+/**
+ * Material map filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - material.rs (Rust implementation)
+ * - material.py (Python wrapper)
+ *
+ * Provides: height_to_normal
+ */
+
+import { initWasm, convertU8ToF32, convertF32To12bit, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Height to Normal
+// ============================================================================
+
+/**
+ * Convert a height map into a tangent-space normal map (u8).
+ * The result is RGB, or RGBA when the input has 4 channels.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {strength: number, invertY: boolean (DirectX convention)}
+ * @returns {Object} - Normal map image data
+ */
+export function height_to_normal(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.height_to_normal_wasm(
+        new Uint8Array(data.buffer), width, height, channels,
+        options.strength ?? 2.0, options.invertY ?? false
+    );
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width,
+        height,
+        channels: channels === 4 ? 4 : 3
+    };
+}
+
+/**
+ * Convert a height map into a tangent-space normal map (f32).
+ * Takes u8 image data and returns 12-bit output like the other f32 filters.
+ */
+export function height_to_normal_f32(imageData, options = {}) {
+    const { width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const inputF32 = convertU8ToF32(imageData);
+    const result = wasm.height_to_normal_f32_wasm(
+        new Float32Array(inputF32.data.buffer), width, height, channels,
+        options.strength ?? 2.0, options.invertY ?? false
+    );
+    return convertF32To12bit({
+        data: new Float32Array(result.buffer),
+        width,
+        height,
+        channels: channels === 4 ? 4 : 3
+    });
+}
+
+export default {
+    initWasm,
+    height_to_normal, height_to_normal_f32
+};
@@ -0,0 +1,76 @@
+"""Material map filters with Rust backend.
+
+This module turns height (bump) maps into maps used by game engines and
+3D renderers:
+- Height to normal (tangent-space normal map from Sobel slopes)
+
+## Supported Formats
+
+All filters accept numpy arrays with 1, 3, or 4 channels (uint8 or float32).
+The height is the luminance (or the single channel of grayscale input).
+Normal maps are returned as RGB, or RGBA when the input has alpha.
+
+Co-located with:
+- material.rs (Rust implementation)
+- material.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.material import height_to_normal
+
+    normal_map = height_to_normal(height_map, strength=4.0)
+    normal_map_dx = height_to_normal(height_map, strength=4.0, invert_y=True)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Height to Normal
+# ============================================================================
+
+def height_to_normal(image: np.ndarray, strength: float = 2.0, invert_y: bool = False) -> np.ndarray:
+    """Convert a height map into a tangent-space normal map (u8).
+
+    A flat area becomes (128, 128, 255). The default is the OpenGL
+    convention (green = up).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C), white = high
+        strength: Bump strength (0 = flat; 1 = a full 0-1 step per pixel tilts
+            by 45 degrees)
+        invert_y: Flip the green channel (DirectX convention)
+
+    Returns:
+        uint8 normal map (H, W, 3), or (H, W, 4) keeping the input alpha
+    """
+    _validate_image(image, np.uint8, "height_to_normal")
+    return imagestag_rust.height_to_normal(image, strength, invert_y)
+
+
+def height_to_normal_f32(image: np.ndarray, strength: float = 2.0, invert_y: bool = False) -> np.ndarray:
+    """Convert a height map into a tangent-space normal map (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        strength: Bump strength
+        invert_y: Flip the green channel (DirectX convention)
+
+    Returns:
+        float32 normal map (H, W, 3), or (H, W, 4) keeping the input alpha
+    """
+    _validate_image(image, np.float32, "height_to_normal_f32")
+    return imagestag_rust.height_to_normal_f32(image, strength, invert_y)
+
+
+__all__ = [
+    'height_to_normal', 'height_to_normal_f32',
+]
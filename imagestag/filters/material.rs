@@ -0,0 +1,99 @@
+//! Material map filters: Height to Normal.
+//!
+//! Helpers for game and 3D texture work, turning pattern or photo based
+//! height (bump) maps into the maps a renderer consumes.
+//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
+//!
+//! ## Supported Formats
+//!
+//! All filters accept images with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - the value is the height
+//! - **RGB**: (height, width, 3) - the luminance is the height
+//! - **RGBA**: (height, width, 4) - the luminance is the height, alpha preserved
+//!
+//! Normal maps are always RGB (plus alpha for RGBA input).
+
+use ndarray::{Array3, ArrayView3};
+
+use super::edge::sobel_gradients_f32;
+
+// ============================================================================
+// Height to Normal
+// ============================================================================
+
+/// Convert a height map into a tangent-space normal map - f32 version.
+///
+/// The slope comes from Sobel derivatives of the luminance. Normals are
+/// encoded as `n * 0.5 + 0.5`, so a flat area becomes (0.5, 0.5, 1.0).
+/// The default is the OpenGL convention (green = up); `invert_y` gives the
+/// DirectX convention (green = down).
+///
+/// # Arguments
+/// * `input` - Height map with 1, 3, or 4 channels, values 0.0-1.0 (white = high)
+/// * `strength` - Bump height: slope multiplier (0.0 = flat, 1.0 = a full
+///   0-1 step per pixel tilts by 45 degrees)
+/// * `invert_y` - Flip the green channel (DirectX convention)
+///
+/// # Returns
+/// Normal map with 3 channels, or 4 with the alpha of RGBA input
+pub fn height_to_normal_f32(input: ArrayView3<f32>, strength: f32, invert_y: bool) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let out_channels = if channels == 4 { 4 } else { 3 };
+    let mut output = Array3::<f32>::zeros((height, width, out_channels));
+    let (gradient_x, gradient_y) = sobel_gradients_f32(input, 3);
+    // The Sobel kernels span two pixels
+    let scale = strength * 0.5;
+    let y_sign = if invert_y { -1.0 } else { 1.0 };
+
+    for y in 0..height {
+        for x in 0..width {
+            // Image y grows downwards, tangent-space y points up
+            let nx = -gradient_x[[y, x]] * scale;
+            let ny = gradient_y[[y, x]] * scale * y_sign;
+            let length = (nx * nx + ny * ny + 1.0).sqrt();
+            output[[y, x, 0]] = nx / length * 0.5 + 0.5;
+            output[[y, x, 1]] = ny / length * 0.5 + 0.5;
+            output[[y, x, 2]] = 1.0 / length * 0.5 + 0.5;
+            if channels == 4 {
+                output[[y, x, 3]] = input[[y, x, 3]];
+            }
+        }
+    }
+    output
+}
+
+/// Convert a height map into a tangent-space normal map - u8 version.
+///
+/// # Returns
+/// Normal map with 3 channels, or 4 with the alpha of RGBA input
+pub fn height_to_normal_u8(input: ArrayView3<u8>, strength: f32, invert_y: bool) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let result = height_to_normal_f32(input_f32.view(), strength, invert_y);
+    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_height_to_normal_tilts_against_the_slope() {
+        let flat = Array3::<f32>::from_elem((5, 5, 1), 0.3);
+        let normal = height_to_normal_f32(flat.view(), 2.0, false);
+        assert_eq!(normal.dim(), (5, 5, 3));
+        assert!((normal[[2, 2, 0]] - 0.5).abs() < 1e-6 && (normal[[2, 2, 2]] - 1.0).abs() < 1e-6);
+
+        // Rising to the right: the normal leans left
+        let ramp = Array3::from_shape_fn((5, 5, 1), |(_, x, _)| x as f32 * 0.1);
+        let normal = height_to_normal_f32(ramp.view(), 10.0, false);
+        assert!((normal[[2, 2, 0]] - (0.5 - 0.5 / 2f32.sqrt())).abs() < 1e-4);
+        assert!((normal[[2, 2, 1]] - 0.5).abs() < 1e-6);
+
+        // Rising downwards: green leans up in OpenGL, down in DirectX
+        let ramp = Array3::from_shape_fn((5, 5, 4), |(y, _, c)| if c == 3 { 0.8 } else { y as f32 * 0.1 });
+        let gl = height_to_normal_f32(ramp.view(), 2.0, false);
+        let dx = height_to_normal_f32(ramp.view(), 2.0, true);
+        assert!(gl[[2, 2, 1]] > 0.5 && dx[[2, 2, 1]] < 0.5);
+        assert_eq!(gl[[2, 2, 3]], 0.8);
+    }
+}
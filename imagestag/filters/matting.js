@@ -0,0 +1,121 @@
+/**
+ * Matting filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - matting.rs (Rust implementation)
+ * - matting.py (Python wrapper)
+ *
+ * Background removal with soft alpha. Chroma key returns RGBA
+ * (4 channels) with straight alpha, alpha matting the matte (1 channel).
+ */
+
+import { initSync } from '../wasm/imagestag_rust.js';
+import * as wasm from '../wasm/imagestag_rust.js';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+let wasmInitialized = false;
+
+/**
+ * Initialize WASM module (required before using filter functions).
+ */
+export async function initWasm() {
+    if (wasmInitialized) return;
+    const __filename = fileURLToPath(import.meta.url);
+    const __dirname = path.dirname(__filename);
+    const wasmPath = path.join(__dirname, '..', 'wasm', 'imagestag_rust_bg.wasm');
+    const wasmBuffer = fs.readFileSync(wasmPath);
+    initSync(wasmBuffer);
+    wasmInitialized = true;
+}
+
+// ============================================================================
+// Chroma Key
+// ============================================================================
+
+function chromaKeyOptions(opts) {
+    const [r, g, b] = opts.keyColor ?? [0, 1, 0];
+    return Object.assign(new wasm.ChromaKeyOptions(), {
+        key_r: r,
+        key_g: g,
+        key_b: b,
+        tolerance: opts.tolerance ?? 0.15,
+        softness: opts.softness ?? 0.1,
+        spill_suppression: opts.spillSuppression ?? 1.0,
+    });
+}
+
+/**
+ * Remove a uniform backdrop such as a green or blue screen (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} opts - {keyColor: [r, g, b] in 0-1, tolerance, softness, spillSuppression}
+ * @returns {Object} - RGBA image data {data, width, height, channels: 4}
+ */
+export function chromaKey(imageData, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const options = chromaKeyOptions(opts);
+    const result = wasm.chroma_key_wasm(new Uint8Array(data.buffer), width, height, channels, options);
+    options.free();
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4 };
+}
+
+/**
+ * Remove a uniform backdrop such as a green or blue screen (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @param {Object} opts - {keyColor: [r, g, b] in 0-1, tolerance, softness, spillSuppression}
+ * @returns {Object} - RGBA image data {data, width, height, channels: 4}
+ */
+export function chromaKeyF32(imageData, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const options = chromaKeyOptions(opts);
+    const result = wasm.chroma_key_f32_wasm(data, width, height, channels, options);
+    options.free();
+    return { data: new Float32Array(result.buffer), width, height, channels: 4 };
+}
+
+// ============================================================================
+// Alpha Matting
+// ============================================================================
+
+/**
+ * Estimate fractional alpha in the unknown region of a trimap (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Uint8Array} trimap - width * height: 0 = background, 255 = foreground, else unknown
+ * @param {Object} opts - {radius: 4, epsilon: 1e-4}
+ * @returns {Object} - Alpha matte {data, width, height, channels: 1}
+ */
+export function alphaMatting(imageData, trimap, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.alpha_matting_wasm(
+        new Uint8Array(data.buffer), trimap, width, height, channels,
+        opts.radius ?? 4, opts.epsilon ?? 1e-4
+    );
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 1 };
+}
+
+/**
+ * Estimate fractional alpha in the unknown region of a trimap (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @param {Float32Array} trimap - width * height: 0.0 = background, 1.0 = foreground, else unknown
+ * @param {Object} opts - {radius: 4, epsilon: 1e-4}
+ * @returns {Object} - Alpha matte {data, width, height, channels: 1}
+ */
+export function alphaMattingF32(imageData, trimap, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.alpha_matting_f32_wasm(
+        data, trimap, width, height, channels,
+        opts.radius ?? 4, opts.epsilon ?? 1e-4
+    );
+    return { data: new Float32Array(result.buffer), width, height, channels: 1 };
+}
+
+export default {
+    initWasm,
+    chromaKey, chromaKeyF32,
+    alphaMatting, alphaMattingF32
+};
@@ -0,0 +1,118 @@
+"""Matting filters with Rust backend.
+
+This module provides background removal and soft alpha extraction:
+- Chroma key (green/blue screen) with soft edges and spill suppression
+- Alpha matting: fractional alpha for the unknown band of a trimap
+
+## Supported Formats
+
+All filters accept numpy arrays with 1, 3, or 4 channels (uint8 or float32).
+Chroma key returns RGBA (H, W, 4) with straight alpha; existing alpha of RGBA
+input is multiplied with the keyed alpha. Alpha matting returns the matte
+(H, W, 1).
+
+Usage:
+    from imagestag.filters.matting import chroma_key, alpha_matting
+
+    # Key out a green screen sampled at the top-left corner
+    cutout = chroma_key(image, key_color=tuple(image[0, 0, :3]))
+
+    # Refine a rough selection: 0 = background, 255 = foreground, 128 = unknown
+    alpha = alpha_matting(image, trimap)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Chroma Key
+# ============================================================================
+
+def chroma_key(image: np.ndarray, key_color: tuple = (0, 255, 0), tolerance: float = 0.15,
+               softness: float = 0.1, spill_suppression: float = 1.0) -> np.ndarray:
+    """Remove a uniform backdrop such as a green or blue screen (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        key_color: Backdrop color (r, g, b) in 0-255, best sampled from the image
+        tolerance: Chroma distance keyed out completely (0.0-1.0)
+        softness: Width of the soft alpha ramp beyond the tolerance (0.0-1.0)
+        spill_suppression: Backdrop color spill removal (0.0 = off, 1.0 = full)
+
+    Returns:
+        RGBA uint8 array (H, W, 4)
+    """
+    _validate_image(image, np.uint8, "chroma_key")
+    key = tuple(int(v) for v in key_color)
+    return imagestag_rust.chroma_key(image, key, tolerance, softness, spill_suppression)
+
+
+def chroma_key_f32(image: np.ndarray, key_color: tuple = (0.0, 1.0, 0.0), tolerance: float = 0.15,
+                   softness: float = 0.1, spill_suppression: float = 1.0) -> np.ndarray:
+    """Remove a uniform backdrop such as a green or blue screen (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        key_color: Backdrop color (r, g, b) in 0.0-1.0
+
+    Returns:
+        RGBA float32 array (H, W, 4)
+    """
+    _validate_image(image, np.float32, "chroma_key_f32")
+    key = tuple(float(v) for v in key_color)
+    return imagestag_rust.chroma_key_f32(image, key, tolerance, softness, spill_suppression)
+
+
+# ============================================================================
+# Alpha Matting
+# ============================================================================
+
+def _validate_trimap(image: np.ndarray, trimap: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate trimap shape and dtype against the image."""
+    if trimap.shape != image.shape[:2]:
+        raise ValueError(f"{name}: expected trimap of shape {image.shape[:2]}, got {trimap.shape}")
+    if trimap.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} trimap, got {trimap.dtype}")
+
+
+def alpha_matting(image: np.ndarray, trimap: np.ndarray, radius: int = 4,
+                  epsilon: float = 1e-4) -> np.ndarray:
+    """Estimate fractional alpha in the unknown region of a trimap (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        trimap: uint8 array (H, W): 0 = background, 255 = foreground, anything else unknown
+        radius: Guided filter window radius
+        epsilon: Guided filter regularization (larger = smoother matte)
+
+    Returns:
+        uint8 alpha matte (H, W, 1)
+    """
+    _validate_image(image, np.uint8, "alpha_matting")
+    _validate_trimap(image, trimap, np.uint8, "alpha_matting")
+    return imagestag_rust.alpha_matting(image, trimap, radius, epsilon)
+
+
+def alpha_matting_f32(image: np.ndarray, trimap: np.ndarray, radius: int = 4,
+                      epsilon: float = 1e-4) -> np.ndarray:
+    """Estimate fractional alpha in the unknown region of a trimap (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        trimap: float32 array (H, W): 0.0 = background, 1.0 = foreground, anything else unknown
+
+    Returns:
+        float32 alpha matte (H, W, 1)
+    """
+    _validate_image(image, np.float32, "alpha_matting_f32")
+    _validate_trimap(image, trimap, np.float32, "alpha_matting_f32")
+    return imagestag_rust.alpha_matting_f32(image, trimap, radius, epsilon)
@@ -0,0 +1,460 @@
+//! Matting: background removal and soft alpha extraction.
+//!
+//! - **Chroma Key**: Keys out a uniform backdrop (green/blue screen) by its
+//!   chroma distance, with a soft ramp and color spill removal
+//! - **Alpha Matting**: Fills the unknown band of a trimap with fractional
+//!   alpha (local color model + color guided filter)
+//!
+//! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
+//!
+//! ## Supported Formats
+//!
+//! - **Grayscale (1 channel)**: Treated as gray RGB
+//! - **RGB (3 channels)**: Keyed into RGBA
+//! - **RGBA (4 channels)**: Keyed alpha is multiplied with the existing alpha
+//!
+//! Chroma key results are RGBA (H, W, 4) with straight (non-premultiplied)
+//! alpha; alpha matting returns the matte (H, W, 1).
+
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3};
+use rayon::prelude::*;
+
+/// RGB of a pixel (gray repeated for single-channel images).
+#[inline]
+fn pixel_rgb(input: &ArrayView3<f32>, y: usize, x: usize) -> [f32; 3] {
+    if input.dim().2 < 3 {
+        let v = input[[y, x, 0]];
+        [v, v, v]
+    } else {
+        [input[[y, x, 0]], input[[y, x, 1]], input[[y, x, 2]]]
+    }
+}
+
+// ============================================================================
+// Chroma Key
+// ============================================================================
+
+/// Chroma (Cb, Cr) of an RGB color, BT.601.
+#[inline]
+fn chroma(rgb: [f32; 3]) -> (f32, f32) {
+    let [r, g, b] = rgb;
+    (
+        -0.168_736 * r - 0.331_264 * g + 0.5 * b,
+        0.5 * r - 0.418_688 * g - 0.081_312 * b,
+    )
+}
+
+fn chroma_key_impl(input: ArrayView3<f32>, key: [f32; 3], tolerance: f32, softness: f32, spill_suppression: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let (key_cb, key_cr) = chroma(key);
+
+    // Spill is removed from the key's dominant channel
+    let dominant = (0..3).max_by(|&a, &b| key[a].total_cmp(&key[b])).unwrap_or(1);
+    let others = [(dominant + 1) % 3, (dominant + 2) % 3];
+
+    let mut output = vec![0.0f32; height * width * 4];
+    if width == 0 {
+        return Array3::from_shape_vec((height, width, 4), output).unwrap();
+    }
+    output.par_chunks_mut(width * 4).enumerate().for_each(|(y, row)| {
+        for x in 0..width {
+            let mut rgb = pixel_rgb(&input, y, x);
+            let (cb, cr) = chroma(rgb);
+            let distance = (cb - key_cb).hypot(cr - key_cr);
+            let key_alpha = if distance <= tolerance {
+                0.0
+            } else if softness <= 0.0 || distance >= tolerance + softness {
+                1.0
+            } else {
+                let t = (distance - tolerance) / softness;
+                t * t * (3.0 - 2.0 * t)
+            };
+
+            let limit = (rgb[others[0]] + rgb[others[1]]) / 2.0;
+            let spill = (rgb[dominant] - limit).max(0.0);
+            rgb[dominant] -= spill * spill_suppression;
+
+            let alpha = if channels == 4 { input[[y, x, 3]] } else { 1.0 };
+            let out = &mut row[x * 4..x * 4 + 4];
+            out[..3].copy_from_slice(&rgb);
+            out[3] = key_alpha * alpha;
+        }
+    });
+    Array3::from_shape_vec((height, width, 4), output).unwrap()
+}
+
+/// Remove a uniform backdrop (green/blue screen) - f32 version.
+///
+/// Pixels whose chroma (CbCr) is within `tolerance` of the key become fully
+/// transparent, pixels beyond `tolerance + softness` stay opaque, with a
+/// smooth ramp in between. Because only chroma is compared, shading and
+/// wrinkles in the backdrop are keyed as well. Spill suppression pulls the
+/// key's dominant channel down to the average of the other two, removing
+/// the colored fringe the backdrop reflects onto the subject.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `key_color` - Backdrop color [r, g, b], best sampled from the image
+/// * `tolerance` - Chroma distance keyed out completely (0.0-1.0, e.g. 0.15)
+/// * `softness` - Width of the soft alpha ramp (0.0-1.0, e.g. 0.1)
+/// * `spill_suppression` - Spill removal strength (0.0 = off, 1.0 = full)
+///
+/// # Returns
+/// RGBA image (H, W, 4) with the keyed alpha
+pub fn chroma_key_f32(
+    input: ArrayView3<f32>,
+    key_color: [f32; 3],
+    tolerance: f32,
+    softness: f32,
+    spill_suppression: f32,
+) -> Array3<f32> {
+    chroma_key_impl(input, key_color, tolerance, softness, spill_suppression.clamp(0.0, 1.0))
+}
+
+/// Remove a uniform backdrop (green/blue screen) - u8 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `key_color` - Backdrop color [r, g, b] in 0-255
+/// * `tolerance` - Chroma distance keyed out completely (0.0-1.0, e.g. 0.15)
+/// * `softness` - Width of the soft alpha ramp (0.0-1.0, e.g. 0.1)
+/// * `spill_suppression` - Spill removal strength (0.0 = off, 1.0 = full)
+///
+/// # Returns
+/// RGBA image (H, W, 4) with the keyed alpha
+pub fn chroma_key_u8(
+    input: ArrayView3<u8>,
+    key_color: [u8; 3],
+    tolerance: f32,
+    softness: f32,
+    spill_suppression: f32,
+) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let key = key_color.map(|v| v as f32 / 255.0);
+    chroma_key_f32(input_f32.view(), key, tolerance, softness, spill_suppression).mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Alpha Matting
+// ============================================================================
+
+/// Box mean over a (2r+1)² window of every channel of a (H, W, C) stack,
+/// clamped at the borders. Separable running sums, O(1) per pixel.
+fn box_mean(input: &Array3<f32>, radius: usize) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut horizontal = Array3::<f32>::zeros((height, width, channels));
+    for y in 0..height {
+        for c in 0..channels {
+            let mut sum = 0.0f32;
+            let (mut lo, mut hi) = (0usize, 0usize);
+            for x in 0..width {
+                let (start, end) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+                while hi < end {
+                    sum += input[[y, hi, c]];
+                    hi += 1;
+                }
+                while lo < start {
+                    sum -= input[[y, lo, c]];
+                    lo += 1;
+                }
+                horizontal[[y, x, c]] = sum / (end - start) as f32;
+            }
+        }
+    }
+    let mut output = Array3::<f32>::zeros((height, width, channels));
+    for x in 0..width {
+        for c in 0..channels {
+            let mut sum = 0.0f32;
+            let (mut lo, mut hi) = (0usize, 0usize);
+            for y in 0..height {
+                let (start, end) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+                while hi < end {
+                    sum += horizontal[[hi, x, c]];
+                    hi += 1;
+                }
+                while lo < start {
+                    sum -= horizontal[[lo, x, c]];
+                    lo += 1;
+                }
+                output[[y, x, c]] = sum / (end - start) as f32;
+            }
+        }
+    }
+    output
+}
+
+/// Solve the symmetric 3x3 system (m + eps I) a = v by Cramer's rule.
+fn solve3(m: [f32; 6], v: [f32; 3], eps: f32) -> [f32; 3] {
+    // m = [xx, xy, xz, yy, yz, zz]
+    let (a, b, c, d, e, f) = (m[0] + eps, m[1], m[2], m[3] + eps, m[4], m[5] + eps);
+    let det = a * (d * f - e * e) - b * (b * f - e * c) + c * (b * e - d * c);
+    if det.abs() < 1e-12 {
+        return [0.0; 3];
+    }
+    let inv = [
+        [d * f - e * e, c * e - b * f, b * e - c * d],
+        [c * e - b * f, a * f - c * c, b * c - a * e],
+        [b * e - c * d, b * c - a * e, a * d - b * b],
+    ];
+    std::array::from_fn(|i| (inv[i][0] * v[0] + inv[i][1] * v[1] + inv[i][2] * v[2]) / det)
+}
+
+/// Edge-preserving smoothing of `p` guided by the colors of `guide` (He et al.).
+fn guided_filter(guide: &Array3<f32>, p: &Array2<f32>, radius: usize, eps: f32) -> Array2<f32> {
+    let (height, width, _) = guide.dim();
+    // Stack: I (3), I*I^T upper triangle (6), p (1), I*p (3)
+    let stack = Array3::from_shape_fn((height, width, 13), |(y, x, k)| {
+        let i = [guide[[y, x, 0]], guide[[y, x, 1]], guide[[y, x, 2]]];
+        let pv = p[[y, x]];
+        match k {
+            0..=2 => i[k],
+            3 => i[0] * i[0],
+            4 => i[0] * i[1],
+            5 => i[0] * i[2],
+            6 => i[1] * i[1],
+            7 => i[1] * i[2],
+            8 => i[2] * i[2],
+            9 => pv,
+            _ => i[k - 10] * pv,
+        }
+    });
+    let means = box_mean(&stack, radius);
+
+    let mut coefficients = Array3::<f32>::zeros((height, width, 4));
+    for y in 0..height {
+        for x in 0..width {
+            let m = |k: usize| means[[y, x, k]];
+            let mean_i = [m(0), m(1), m(2)];
+            let cov = [
+                m(3) - mean_i[0] * mean_i[0],
+                m(4) - mean_i[0] * mean_i[1],
+                m(5) - mean_i[0] * mean_i[2],
+                m(6) - mean_i[1] * mean_i[1],
+                m(7) - mean_i[1] * mean_i[2],
+                m(8) - mean_i[2] * mean_i[2],
+            ];
+            let mean_p = m(9);
+            let cov_ip = [m(10) - mean_i[0] * mean_p, m(11) - mean_i[1] * mean_p, m(12) - mean_i[2] * mean_p];
+            let a = solve3(cov, cov_ip, eps);
+            let b = mean_p - a[0] * mean_i[0] - a[1] * mean_i[1] - a[2] * mean_i[2];
+            coefficients[[y, x, 0]] = a[0];
+            coefficients[[y, x, 1]] = a[1];
+            coefficients[[y, x, 2]] = a[2];
+            coefficients[[y, x, 3]] = b;
+        }
+    }
+    let coefficients = box_mean(&coefficients, radius);
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let c = |k: usize| coefficients[[y, x, k]];
+        c(0) * guide[[y, x, 0]] + c(1) * guide[[y, x, 1]] + c(2) * guide[[y, x, 2]] + c(3)
+    })
+}
+
+/// Trimap regions.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Background,
+    Foreground,
+    Unknown,
+}
+
+fn alpha_matting_impl(input: ArrayView3<f32>, regions: &Array2<Region>, radius: usize, epsilon: f32) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let guide = Array3::from_shape_fn((height, width, 3), |(y, x, c)| pixel_rgb(&input, y, x)[c]);
+
+    // Local foreground/background colors: normalized convolution of the known
+    // pixels, widening the window per pixel until it reaches both regions
+    let known = Array3::from_shape_fn((height, width, 8), |(y, x, k)| {
+        let region = regions[[y, x]];
+        let (side, channel) = (k / 4, k % 4);
+        let selected = if side == 0 { Region::Foreground } else { Region::Background };
+        if region != selected {
+            0.0
+        } else if channel == 3 {
+            1.0
+        } else {
+            guide[[y, x, channel]]
+        }
+    });
+    let mut colors: Array2<Option<([f32; 3], [f32; 3])>> = Array2::from_elem((height, width), None);
+    let mut color_radius = (radius * 2).max(4);
+    loop {
+        let means = box_mean(&known, color_radius);
+        let mut pending = false;
+        for ((y, x), slot) in colors.indexed_iter_mut() {
+            if slot.is_some() || regions[[y, x]] != Region::Unknown {
+                continue;
+            }
+            let (wf, wb) = (means[[y, x, 3]], means[[y, x, 7]]);
+            if wf > 0.0 && wb > 0.0 {
+                *slot = Some((
+                    std::array::from_fn(|c| means[[y, x, c]] / wf),
+                    std::array::from_fn(|c| means[[y, x, 4 + c]] / wb),
+                ));
+            } else {
+                pending = true;
+            }
+        }
+        if !pending || color_radius >= height.max(width) {
+            break;
+        }
+        color_radius *= 2;
+    }
+
+    // Initial alpha: projection of the color onto the local F-B line
+    let initial = Array2::from_shape_fn((height, width), |(y, x)| match regions[[y, x]] {
+        Region::Foreground => 1.0,
+        Region::Background => 0.0,
+        Region::Unknown => {
+            let Some((fg, bg)) = colors[[y, x]] else {
+                // Trimap without one of the known regions
+                return 0.5;
+            };
+            let (mut num, mut den) = (0.0f32, 0.0f32);
+            for c in 0..3 {
+                let d = fg[c] - bg[c];
+                num += (guide[[y, x, c]] - bg[c]) * d;
+                den += d * d;
+            }
+            if den < 1e-6 { 0.5 } else { (num / den).clamp(0.0, 1.0) }
+        }
+    });
+
+    let refined = guided_filter(&guide, &initial, radius, epsilon);
+    Array3::from_shape_fn((height, width, 1), |(y, x, _)| match regions[[y, x]] {
+        Region::Foreground => 1.0,
+        Region::Background => 0.0,
+        Region::Unknown => refined[[y, x]].clamp(0.0, 1.0),
+    })
+}
+
+/// Estimate fractional alpha in the unknown region of a trimap - f32 version.
+///
+/// Each unknown pixel is first projected onto the line between the local
+/// mean foreground and background colors (gathered from the known regions
+/// nearby), then the estimate is refined with a color guided filter so the
+/// matte follows the image's edges (hair, fur, motion blur). Known regions
+/// keep exactly 0.0 or 1.0.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `trimap` - (H, W): 0.0 = background, 1.0 = foreground, anything else unknown
+/// * `radius` - Guided filter window radius (e.g. 4)
+/// * `epsilon` - Guided filter regularization (e.g. 1e-4; larger = smoother)
+///
+/// # Returns
+/// Alpha matte (H, W, 1)
+///
+/// # Panics
+/// If the trimap size differs from the image size.
+pub fn alpha_matting_f32(input: ArrayView3<f32>, trimap: ArrayView2<f32>, radius: usize, epsilon: f32) -> Array3<f32> {
+    assert_eq!(trimap.dim(), (input.dim().0, input.dim().1), "trimap size must match the image");
+    let regions = trimap.mapv(|v| {
+        if v <= 0.0 {
+            Region::Background
+        } else if v >= 1.0 {
+            Region::Foreground
+        } else {
+            Region::Unknown
+        }
+    });
+    alpha_matting_impl(input, &regions, radius, epsilon)
+}
+
+/// Estimate fractional alpha in the unknown region of a trimap - u8 version.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `trimap` - (H, W): 0 = background, 255 = foreground, anything else unknown
+/// * `radius` - Guided filter window radius (e.g. 4)
+/// * `epsilon` - Guided filter regularization (e.g. 1e-4)
+///
+/// # Returns
+/// Alpha matte (H, W, 1), 0-255
+pub fn alpha_matting_u8(input: ArrayView3<u8>, trimap: ArrayView2<u8>, radius: usize, epsilon: f32) -> Array3<u8> {
+    assert_eq!(trimap.dim(), (input.dim().0, input.dim().1), "trimap size must match the image");
+    let regions = trimap.mapv(|v| match v {
+        0 => Region::Background,
+        255 => Region::Foreground,
+        _ => Region::Unknown,
+    });
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    alpha_matting_impl(input_f32.view(), &regions, radius, epsilon).mapv(|v| (v * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREEN: [f32; 3] = [0.1, 0.8, 0.2];
+
+    #[test]
+    fn test_chroma_key_removes_backdrop() {
+        // Shaded green backdrop with a red subject in the middle
+        let image = Array3::from_shape_fn((10, 10, 3), |(y, x, c)| {
+            if (3..7).contains(&x) && (3..7).contains(&y) {
+                [0.8, 0.2, 0.2][c]
+            } else {
+                GREEN[c] * (0.85 + 0.03 * x as f32)
+            }
+        });
+        let result = chroma_key_f32(image.view(), GREEN, 0.15, 0.1, 1.0);
+        assert_eq!(result.dim(), (10, 10, 4));
+        assert_eq!(result[[0, 0, 3]], 0.0);
+        assert_eq!(result[[9, 9, 3]], 0.0);
+        assert_eq!(result[[5, 5, 3]], 1.0);
+        assert_eq!(result[[5, 5, 0]], 0.8);
+
+        // Existing alpha is kept for the subject
+        let mut rgba = Array3::from_elem((1, 1, 4), 0.5f32);
+        rgba[[0, 0, 0]] = 0.9;
+        assert_eq!(chroma_key_f32(rgba.view(), GREEN, 0.15, 0.1, 0.0)[[0, 0, 3]], 0.5);
+    }
+
+    #[test]
+    fn test_chroma_key_soft_edge_and_spill() {
+        // Greenish fringe pixel: partially transparent, green pulled down
+        let fringe = Array3::from_shape_vec((1, 1, 3), vec![0.45, 0.7, 0.35]).unwrap();
+        let result = chroma_key_f32(fringe.view(), GREEN, 0.15, 0.3, 1.0);
+        let alpha = result[[0, 0, 3]];
+        assert!(alpha > 0.0 && alpha < 1.0, "alpha {alpha}");
+        assert!((result[[0, 0, 1]] - 0.4).abs() < 1e-6);
+
+        let untouched = chroma_key_f32(fringe.view(), GREEN, 0.15, 0.3, 0.0);
+        assert_eq!(untouched[[0, 0, 1]], 0.7);
+
+        let u8_image = Array3::from_shape_vec((1, 2, 3), vec![25, 204, 51, 200, 40, 40]).unwrap();
+        let keyed = chroma_key_u8(u8_image.view(), [25, 204, 51], 0.15, 0.1, 1.0);
+        assert_eq!((keyed[[0, 0, 3]], keyed[[0, 1, 3]]), (0, 255));
+    }
+
+    #[test]
+    fn test_alpha_matting_recovers_blend() {
+        // Red foreground left, blue background right, a soft ramp in between
+        let (fg, bg) = ([0.9, 0.1, 0.1], [0.1, 0.2, 0.8]);
+        let alpha_at = |x: usize| ((x as f32 - 12.0) / 8.0).clamp(0.0, 1.0);
+        let image = Array3::from_shape_fn((16, 32, 3), |(_, x, c)| {
+            let a = 1.0 - alpha_at(x);
+            a * fg[c] + (1.0 - a) * bg[c]
+        });
+        let trimap = Array2::from_shape_fn((16, 32), |(_, x)| match x {
+            0..=9 => 1.0,
+            23.. => 0.0,
+            _ => 0.5,
+        });
+
+        let matte = alpha_matting_f32(image.view(), trimap.view(), 2, 1e-5);
+        assert_eq!(matte.dim(), (16, 32, 1));
+        assert_eq!((matte[[8, 0, 0]], matte[[8, 31, 0]]), (1.0, 0.0));
+        for x in 10..23 {
+            let expected = 1.0 - alpha_at(x);
+            assert!((matte[[8, x, 0]] - expected).abs() < 0.1, "x={x}: {} vs {expected}", matte[[8, x, 0]]);
+        }
+
+        let image_u8 = image.mapv(|v| (v * 255.0).round() as u8);
+        let trimap_u8 = trimap.mapv(|v| (v * 255.0) as u8);
+        let matte_u8 = alpha_matting_u8(image_u8.view(), trimap_u8.view(), 2, 1e-5);
+        assert_eq!((matte_u8[[0, 0, 0]], matte_u8[[0, 31, 0]]), (255, 0));
+        assert!((matte_u8[[4, 16, 0]] as i32 - 128).abs() < 26);
+    }
+}
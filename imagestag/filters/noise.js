@@ -5,7 +5,7 @@
  * - noise.rs (Rust implementation)
  * - noise.py (Python wrapper)
  *
- * Provides: add_noise, median, denoise
+ * Provides: add_noise, add_noise_hashed, film_grain, median, remove_hot_pixels, surface_blur, denoise
  */
 
 import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
@@ -45,6 +45,54 @@ export const add_noise_f32 = createF32Filter(
     ]
 );
 
+const hashedNoiseOptions = (opts) => [Object.assign(new wasm.HashedNoiseOptions(), {
+    amount: opts.amount ?? 0.1,
+    gaussian: opts.gaussian ?? true,
+    monochrome: opts.monochrome ?? false,
+    seed: opts.seed ?? 0,
+    origin_x: opts.origin_x ?? 0,
+    origin_y: opts.origin_y ?? 0,
+})];
+
+/**
+ * Add position-stable noise to image (u8).
+ * Tiles processed with their origin_x/origin_y match a full-image render.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amount, gaussian, monochrome, seed, origin_x, origin_y}
+ * @returns {Object} - Noisy image data
+ */
+export const add_noise_hashed = createU8Filter(wasm.add_noise_hashed_wasm, hashedNoiseOptions);
+
+/**
+ * Add position-stable noise to image (f32).
+ */
+export const add_noise_hashed_f32 = createF32Filter(wasm.add_noise_hashed_f32_wasm, hashedNoiseOptions);
+
+// ============================================================================
+// Film Grain
+// ============================================================================
+
+const filmGrainOptions = (opts) => [Object.assign(new wasm.FilmGrainOptions(), {
+    intensity: opts.intensity ?? 0.3,
+    size: opts.size ?? 1.5,
+    roughness: opts.roughness ?? 0.5,
+    chroma_amount: opts.chroma_amount ?? 0.0,
+    seed: opts.seed ?? 0,
+})];
+
+/**
+ * Simulate photographic film grain (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {intensity, size, roughness, chroma_amount, seed}
+ * @returns {Object} - Grainy image data
+ */
+export const film_grain = createU8Filter(wasm.film_grain_wasm, filmGrainOptions);
+
+/**
+ * Simulate photographic film grain (f32).
+ */
+export const film_grain_f32 = createF32Filter(wasm.film_grain_f32_wasm, filmGrainOptions);
+
 // ============================================================================
 // Median Filter
 // ============================================================================
@@ -68,6 +116,52 @@ export const median_f32 = createF32Filter(
     (opts) => [opts.radius ?? 1]
 );
 
+// ============================================================================
+// Hot Pixel Removal
+// ============================================================================
+
+/**
+ * Replace isolated hot/dead pixels with their 3x3 median (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {threshold: number (0-255)}
+ * @returns {Object} - Filtered image data
+ */
+export const remove_hot_pixels = createU8Filter(
+    wasm.remove_hot_pixels_wasm,
+    (opts) => [opts.threshold ?? 40]
+);
+
+/**
+ * Replace isolated hot/dead pixels (f32). `threshold` is given in 0-255 like the u8 version.
+ */
+export const remove_hot_pixels_f32 = createF32Filter(
+    wasm.remove_hot_pixels_f32_wasm,
+    (opts) => [(opts.threshold ?? 40) / 255.0]
+);
+
+// ============================================================================
+// Surface Blur
+// ============================================================================
+
+/**
+ * Blur only across similar values, keeping edges (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {radius: number (1-100), threshold: number (0-255)}
+ * @returns {Object} - Filtered image data
+ */
+export const surface_blur = createU8Filter(
+    wasm.surface_blur_wasm,
+    (opts) => [opts.radius ?? 5, opts.threshold ?? 15]
+);
+
+/**
+ * Blur only across similar values (f32). `threshold` is given in 0-255 like the u8 version.
+ */
+export const surface_blur_f32 = createF32Filter(
+    wasm.surface_blur_f32_wasm,
+    (opts) => [opts.radius ?? 5, (opts.threshold ?? 15) / 255.0]
+);
+
 // ============================================================================
 // Denoise
 // ============================================================================
@@ -94,6 +188,10 @@ export const denoise_f32 = createF32Filter(
 export default {
     initWasm,
     add_noise, add_noise_f32,
+    add_noise_hashed, add_noise_hashed_f32,
+    film_grain, film_grain_f32,
     median, median_f32,
+    remove_hot_pixels, remove_hot_pixels_f32,
+    surface_blur, surface_blur_f32,
     denoise, denoise_f32
 };
@@ -1,8 +1,10 @@
 """Noise filters with Rust backend.
 
 This module provides noise manipulation filters:
-- Add Noise (Gaussian or uniform)
+- Add Noise (Gaussian or uniform, optionally position-stable for tiling)
+- Film Grain (luminance-dependent photographic grain)
 - Median (noise reduction)
+- Surface Blur (edge-preserving threshold blur)
 - Denoise (non-local means)
 
 ## Supported Formats
@@ -97,6 +99,95 @@ def add_noise_f32(
     return imagestag_rust.add_noise_f32(image, amount, gaussian, monochrome, seed)
 
 
+def add_noise_hashed(
+    image: np.ndarray,
+    amount: float = 0.1,
+    gaussian: bool = True,
+    monochrome: bool = False,
+    seed: int = 0,
+    origin_x: int = 0,
+    origin_y: int = 0,
+) -> np.ndarray:
+    """Add position-stable noise to image (u8).
+
+    Each sample is a hash of the absolute pixel position and seed, so an image
+    processed in tiles gets the same noise as when processed in one piece.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amount: Noise intensity (0.0-1.0)
+        gaussian: Use Gaussian noise (True) or uniform noise (False)
+        monochrome: Apply same noise to all channels (grayscale noise)
+        seed: Random seed
+        origin_x: X position of the tile's top-left pixel in the full image
+        origin_y: Y position of the tile's top-left pixel in the full image
+
+    Returns:
+        Noisy uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "add_noise_hashed")
+    return imagestag_rust.add_noise_hashed(image, amount, gaussian, monochrome, seed, origin_x, origin_y)
+
+
+def add_noise_hashed_f32(
+    image: np.ndarray,
+    amount: float = 0.1,
+    gaussian: bool = True,
+    monochrome: bool = False,
+    seed: int = 0,
+    origin_x: int = 0,
+    origin_y: int = 0,
+) -> np.ndarray:
+    """Add position-stable noise to image (f32). See add_noise_hashed."""
+    _validate_image(image, np.float32, "add_noise_hashed_f32")
+    return imagestag_rust.add_noise_hashed_f32(image, amount, gaussian, monochrome, seed, origin_x, origin_y)
+
+
+# ============================================================================
+# Film Grain
+# ============================================================================
+
+def film_grain(
+    image: np.ndarray,
+    intensity: float = 0.3,
+    size: float = 1.5,
+    roughness: float = 0.5,
+    chroma_amount: float = 0.0,
+    seed: int = 0,
+) -> np.ndarray:
+    """Simulate photographic film grain (u8).
+
+    Unlike add_noise, the grain is spatially correlated (blurred blue noise)
+    and follows luminance: shadows are grainier than highlights.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        intensity: Grain strength (0.0-1.0)
+        size: Grain size in pixels
+        roughness: 0.0 = soft clumps, 1.0 = sharp pixel-level grain
+        chroma_amount: Color grain relative to luminance grain (0.0 = monochrome)
+        seed: Random seed
+
+    Returns:
+        Grainy uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "film_grain")
+    return imagestag_rust.film_grain(image, intensity, size, roughness, chroma_amount, seed)
+
+
+def film_grain_f32(
+    image: np.ndarray,
+    intensity: float = 0.3,
+    size: float = 1.5,
+    roughness: float = 0.5,
+    chroma_amount: float = 0.0,
+    seed: int = 0,
+) -> np.ndarray:
+    """Simulate photographic film grain (f32). See film_grain."""
+    _validate_image(image, np.float32, "film_grain_f32")
+    return imagestag_rust.film_grain_f32(image, intensity, size, roughness, chroma_amount, seed)
+
+
 # ============================================================================
 # Median Filter
 # ============================================================================
@@ -129,6 +220,81 @@ def median_f32(image: np.ndarray, radius: int = 1) -> np.ndarray:
     return imagestag_rust.median_f32(image, radius)
 
 
+# ============================================================================
+# Hot Pixel Removal
+# ============================================================================
+
+def remove_hot_pixels(image: np.ndarray, threshold: float = 40.0) -> np.ndarray:
+    """Replace isolated hot/dead pixels with their 3x3 median (u8).
+
+    A value counts as an outlier when it is more than ``threshold`` above
+    the brightest or below the darkest of its neighbors. All other pixels,
+    including edges and fine texture, are left untouched.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        threshold: Required distance from the neighbors' range (0-255)
+
+    Returns:
+        Cleaned uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "remove_hot_pixels")
+    return imagestag_rust.remove_hot_pixels(image, threshold)
+
+
+def remove_hot_pixels_f32(image: np.ndarray, threshold: float = 40.0 / 255.0) -> np.ndarray:
+    """Replace isolated hot/dead pixels with their 3x3 median (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        threshold: Required distance from the neighbors' range (0.0-1.0)
+
+    Returns:
+        Cleaned float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "remove_hot_pixels_f32")
+    return imagestag_rust.remove_hot_pixels_f32(image, threshold)
+
+
+# ============================================================================
+# Surface Blur
+# ============================================================================
+
+def surface_blur(image: np.ndarray, radius: int = 5, threshold: float = 15.0) -> np.ndarray:
+    """Blur only across similar values, keeping edges (u8).
+
+    Matches Photoshop's Surface Blur: neighbors within the window are
+    weighted by how close their value is to the center pixel, falling to
+    zero at a difference of 2.5 x threshold. Useful for skin smoothing and
+    noise cleanup.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        radius: Window radius in pixels (1-100)
+        threshold: Tonal difference below which neighbors are blended (0-255)
+
+    Returns:
+        Blurred uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "surface_blur")
+    return imagestag_rust.surface_blur(image, radius, threshold)
+
+
+def surface_blur_f32(image: np.ndarray, radius: int = 5, threshold: float = 15.0 / 255.0) -> np.ndarray:
+    """Blur only across similar values, keeping edges (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        radius: Window radius in pixels (1-100)
+        threshold: Tonal difference below which neighbors are blended (0.0-1.0)
+
+    Returns:
+        Blurred float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "surface_blur_f32")
+    return imagestag_rust.surface_blur_f32(image, radius, threshold)
+
+
 # ============================================================================
 # Denoise (Non-local Means)
 # ============================================================================
@@ -166,6 +332,10 @@ def denoise_f32(image: np.ndarray, strength: float = 0.5) -> np.ndarray:
 
 __all__ = [
     'add_noise', 'add_noise_f32',
+    'add_noise_hashed', 'add_noise_hashed_f32',
+    'film_grain', 'film_grain_f32',
     'median', 'median_f32',
+    'remove_hot_pixels', 'remove_hot_pixels_f32',
+    'surface_blur', 'surface_blur_f32',
     'denoise', 'denoise_f32',
 ]
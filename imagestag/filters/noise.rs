@@ -1,4 +1,4 @@
-//! Noise filters: Add Noise, Median, Denoise.
+//! Noise filters: Add Noise, Film Grain, Median, Hot Pixels, Surface Blur, Denoise.
 //!
 //! These filters add or remove noise from images.
 //! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
@@ -13,10 +13,30 @@
 //! ## Alpha Handling
 //!
 //! - **Add Noise**: Per-pixel operation, preserves alpha unchanged
+//! - **Film Grain**: Per-pixel operation, preserves alpha unchanged
 //! - **Median**: Processes RGB channels independently, preserves alpha
+//! - **Hot Pixels**: Processes RGB channels independently, preserves alpha
+//! - **Surface Blur**: Processes RGB channels independently, preserves alpha
 //! - **Denoise**: Uses premultiplied alpha to prevent transparent pixel bleeding
+//!
+//! ## Position-Stable Noise
+//!
+//! `add_noise_hashed_*` derives every sample from a hash of the absolute pixel
+//! coordinate, channel and seed instead of a sequential RNG. Rendering an
+//! image in tiles or stripes (passing each tile's origin) then yields exactly
+//! the same noise as rendering it in one piece - no seams.
+//!
+//! ## Progress
+//!
+//! Median and denoise have `*_with_progress` variants that report per row
+//! and can be cancelled (see [`crate::progress`]).
 
-use ndarray::{Array3, ArrayView3};
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+use rayon::prelude::*;
+
+use crate::filters::blur_wasm::gaussian_blur_wasm_f32;
+use crate::filters::fmath;
+use crate::progress::{no_progress, report, Cancelled};
 
 // ============================================================================
 // Simple RNG (deterministic for parity testing)
@@ -176,6 +196,223 @@ pub fn add_noise_f32(
     output
 }
 
+// ============================================================================
+// Hash-based Noise (position-stable)
+// ============================================================================
+
+/// PCG output permutation (RXS-M-XS) used as an integer hash.
+#[inline]
+fn pcg_hash(v: u32) -> u32 {
+    let state = v.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
+
+/// Hash an absolute pixel coordinate, channel and seed to 32 random bits.
+#[inline]
+pub fn position_hash(x: i64, y: i64, channel: u32, seed: u64) -> u32 {
+    let mut h = pcg_hash(seed as u32 ^ pcg_hash((seed >> 32) as u32));
+    h = pcg_hash(h ^ x as u32);
+    h = pcg_hash(h ^ y as u32);
+    pcg_hash(h ^ channel)
+}
+
+/// Uniform f32 in [0, 1) for a pixel coordinate (see [`position_hash`]).
+#[inline]
+pub fn hash_uniform(x: i64, y: i64, channel: u32, seed: u64) -> f32 {
+    (position_hash(x, y, channel, seed) >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// Noise sample for a pixel: Gaussian (Box-Muller over two hash streams) or
+/// uniform in [-1, 1).
+#[inline]
+fn hash_sample(x: i64, y: i64, channel: u32, seed: u64, gaussian: bool) -> f32 {
+    if gaussian {
+        let u1 = hash_uniform(x, y, channel * 2, seed).max(1e-10);
+        let u2 = hash_uniform(x, y, channel * 2 + 1, seed);
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+    } else {
+        (hash_uniform(x, y, channel, seed) - 0.5) * 2.0
+    }
+}
+
+/// Add position-stable noise to image - f32 version.
+///
+/// Same noise model as [`add_noise_f32`], but every sample depends only on
+/// the absolute pixel position, so tiles rendered separately line up.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amount` - Noise amount (0.0-1.0, where 1.0 = max noise)
+/// * `gaussian` - If true, use Gaussian noise; if false, use uniform noise
+/// * `monochrome` - If true, same noise for all color channels; if false, independent noise
+/// * `seed` - Random seed for deterministic results
+/// * `origin` - Position (x, y) of the input's top-left pixel in the full image
+///
+/// # Returns
+/// Noisy image with same channel count
+pub fn add_noise_hashed_f32(
+    input: ArrayView3<f32>,
+    amount: f32,
+    gaussian: bool,
+    monochrome: bool,
+    seed: u64,
+    origin: (i64, i64),
+) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let color_channels = if channels == 4 { 3 } else { channels };
+
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = (origin.0 + x as i64, origin.1 + y as i64);
+            for c in 0..color_channels {
+                let stream = if monochrome { 0 } else { c as u32 };
+                let noise = hash_sample(px, py, stream, seed, gaussian) * amount;
+                output[[y, x, c]] = (input[[y, x, c]] + noise).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    output
+}
+
+/// Add position-stable noise to image - u8 version.
+///
+/// See [`add_noise_hashed_f32`]; `amount` is relative to 255.
+pub fn add_noise_hashed_u8(
+    input: ArrayView3<u8>,
+    amount: f32,
+    gaussian: bool,
+    monochrome: bool,
+    seed: u64,
+    origin: (i64, i64),
+) -> Array3<u8> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let scale = amount * 255.0;
+    let color_channels = if channels == 4 { 3 } else { channels };
+
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = (origin.0 + x as i64, origin.1 + y as i64);
+            for c in 0..color_channels {
+                let stream = if monochrome { 0 } else { c as u32 };
+                let v = input[[y, x, c]] as f32 + hash_sample(px, py, stream, seed, gaussian) * scale;
+                output[[y, x, c]] = v.clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    output
+}
+
+// ============================================================================
+// Film Grain
+// ============================================================================
+
+/// Grain standard deviation at `intensity` 1.0 in the deepest shadows.
+const GRAIN_MAX_SIGMA: f32 = 0.15;
+
+/// Zero-mean, unit-variance grain field for one channel.
+///
+/// Blue noise is approximated by high-passing hashed white noise (removing
+/// the low frequencies that read as blotches), then blurred to the grain size.
+/// `roughness` mixes the blurred clumps with the raw high-frequency noise.
+fn grain_field(height: usize, width: usize, size: f32, roughness: f32, stream: u32, seed: u64) -> Array2<f32> {
+    let blur = |field: &Array2<f32>, sigma: f32| {
+        let single = field.view().insert_axis(Axis(2));
+        gaussian_blur_wasm_f32(single, sigma).index_axis_move(Axis(2), 0)
+    };
+    let white = Array2::from_shape_fn((height, width), |(y, x)| hash_uniform(x as i64, y as i64, stream, seed));
+    let blue = &white - &blur(&white, 1.0);
+    let clumped = if size > 0.5 { blur(&blue, size * 0.5) } else { blue.clone() };
+
+    let normalize = |field: &Array2<f32>| {
+        let mean = field.mean().unwrap_or(0.0);
+        let std = field.mapv(|v| (v - mean) * (v - mean)).mean().unwrap_or(0.0).sqrt().max(1e-6);
+        field.mapv(|v| (v - mean) / std)
+    };
+    let roughness = roughness.clamp(0.0, 1.0);
+    let mixed = normalize(&clumped) * (1.0 - roughness) + normalize(&blue) * roughness;
+    normalize(&mixed)
+}
+
+/// Simulate photographic film grain - f32 version.
+///
+/// Produces correlated grain whose strength follows luminance: shadows are
+/// grainier than highlights. Grain is position-hashed, so the same seed
+/// always gives the same pattern.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `intensity` - Grain strength (0.0-1.0)
+/// * `size` - Grain size in pixels (about the diameter of a grain clump)
+/// * `roughness` - 0.0 = soft clumps, 1.0 = sharp pixel-level grain
+/// * `chroma_amount` - Color grain relative to luminance grain (0.0 = monochrome)
+/// * `seed` - Random seed for deterministic results
+///
+/// # Returns
+/// Grainy image with same channel count
+pub fn film_grain_f32(
+    input: ArrayView3<f32>,
+    intensity: f32,
+    size: f32,
+    roughness: f32,
+    chroma_amount: f32,
+    seed: u64,
+) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let color_channels = if channels == 4 { 3 } else { channels };
+    if height == 0 || width == 0 || intensity <= 0.0 {
+        return output;
+    }
+
+    let luma_grain = grain_field(height, width, size, roughness, 0, seed);
+    let chroma_grain: Vec<Array2<f32>> = if color_channels == 3 && chroma_amount > 0.0 {
+        (1..=3).map(|c| grain_field(height, width, size, roughness, c, seed)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let scale = intensity.clamp(0.0, 1.0) * GRAIN_MAX_SIGMA;
+    for y in 0..height {
+        for x in 0..width {
+            let luma = if color_channels == 3 {
+                0.299 * input[[y, x, 0]] + 0.587 * input[[y, x, 1]] + 0.114 * input[[y, x, 2]]
+            } else {
+                input[[y, x, 0]]
+            };
+            // Shadows get the full grain, highlights a quarter of it
+            let weight = 1.0 - 0.75 * luma.clamp(0.0, 1.0);
+            for c in 0..color_channels {
+                let mut grain = luma_grain[[y, x]];
+                if let Some(field) = chroma_grain.get(c) {
+                    grain += field[[y, x]] * chroma_amount;
+                }
+                output[[y, x, c]] = (input[[y, x, c]] + grain * weight * scale).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    output
+}
+
+/// Simulate photographic film grain - u8 version (see [`film_grain_f32`]).
+pub fn film_grain_u8(
+    input: ArrayView3<u8>,
+    intensity: f32,
+    size: f32,
+    roughness: f32,
+    chroma_amount: f32,
+    seed: u64,
+) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    film_grain_f32(input_f32.view(), intensity, size, roughness, chroma_amount, seed)
+        .mapv(|v| (v * 255.0).round() as u8)
+}
+
 // ============================================================================
 // Median Filter
 // ============================================================================
@@ -186,17 +423,36 @@ pub fn add_noise_f32(
 /// - r=1: Median-of-9 via sorting network (19 compare-swaps per pixel)
 /// - r>=2: Huang's histogram algorithm with column histograms (O(1) per pixel)
 pub fn median_channel_u8(chan: &[u8], out: &mut [u8], width: usize, height: usize, radius: usize) {
+    median_channel_u8_with_progress(chan, out, width, height, radius, &mut no_progress).expect("never cancelled")
+}
+
+/// `median_channel_u8` reporting the fraction of rows done to `progress`.
+pub fn median_channel_u8_with_progress(
+    chan: &[u8],
+    out: &mut [u8],
+    width: usize,
+    height: usize,
+    radius: usize,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<(), Cancelled> {
     if radius == 1 {
-        median_3x3(chan, out, width, height);
+        median_3x3(chan, out, width, height, progress)
     } else {
-        median_histogram(chan, out, width, height, radius);
+        median_histogram(chan, out, width, height, radius, progress)
     }
 }
 
 /// Specialized 3x3 median using a sorting network.
 /// Only 19 compare-and-swap operations per pixel for median of 9 elements.
-fn median_3x3(chan: &[u8], out: &mut [u8], width: usize, height: usize) {
+fn median_3x3(
+    chan: &[u8],
+    out: &mut [u8],
+    width: usize,
+    height: usize,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<(), Cancelled> {
     for y in 0..height {
+        report(progress, y as f32 / height as f32)?;
         let y_top = y.saturating_sub(1);
         let y_bot = (y + 1).min(height - 1);
         let row_off = y * width;
@@ -248,11 +504,19 @@ fn median_3x3(chan: &[u8], out: &mut [u8], width: usize, height: usize) {
             unsafe { *out.get_unchecked_mut(row_off + x) = median_val; }
         }
     }
+    report(progress, 1.0)
 }
 
 /// Huang's histogram-based median for large radii (r > 7).
 /// Column histograms updated O(1) per row, window histogram via 256-bin add/remove.
-fn median_histogram(chan: &[u8], out: &mut [u8], width: usize, height: usize, radius: usize) {
+fn median_histogram(
+    chan: &[u8],
+    out: &mut [u8],
+    width: usize,
+    height: usize,
+    radius: usize,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<(), Cancelled> {
     // Column histograms: 256 bins per column, u16 (max count = 2*21+1 = 43)
     let mut col_hist: Vec<[u16; 256]> = vec![[0u16; 256]; width];
 
@@ -266,6 +530,8 @@ fn median_histogram(chan: &[u8], out: &mut [u8], width: usize, height: usize, ra
     }
 
     for y in 0..height {
+        report(progress, y as f32 / height as f32)?;
+
         // Update column histograms
         if y > 0 {
             let remove_row = y as isize - radius as isize - 1;
@@ -330,6 +596,7 @@ fn median_histogram(chan: &[u8], out: &mut [u8], width: usize, height: usize, ra
             }
         }
     }
+    report(progress, 1.0)
 }
 
 /// Find median from a 256-bin histogram.
@@ -358,10 +625,19 @@ fn hist_median(hist: &[u32; 256], count: u32) -> u8 {
 /// # Returns
 /// Median-filtered image with same channel count
 pub fn median_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
+    median_u8_with_progress(input, radius, &mut no_progress).expect("never cancelled")
+}
+
+/// `median_u8` reporting progress (0.0-1.0) per row; returning `false` cancels.
+pub fn median_u8_with_progress(
+    input: ArrayView3<u8>,
+    radius: u32,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<Array3<u8>, Cancelled> {
     let (height, width, channels) = input.dim();
     let radius = radius.min(21) as usize;
     if radius == 0 {
-        return input.to_owned();
+        return Ok(input.to_owned());
     }
 
     let mut output = Array3::<u8>::zeros((height, width, channels));
@@ -380,7 +656,9 @@ pub fn median_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
 
         // Process
         let mut out_chan = vec![0u8; npixels];
-        median_channel_u8(&chan, &mut out_chan, width, height, radius);
+        median_channel_u8_with_progress(&chan, &mut out_chan, width, height, radius, &mut |f| {
+            progress((c as f32 + f) / color_channels as f32)
+        })?;
 
         // Write back to ndarray
         for y in 0..height {
@@ -400,7 +678,7 @@ pub fn median_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
         }
     }
 
-    output
+    Ok(output)
 }
 
 /// Apply median filter - f32 version.
@@ -415,10 +693,19 @@ pub fn median_u8(input: ArrayView3<u8>, radius: u32) -> Array3<u8> {
 /// # Returns
 /// Median-filtered image with same channel count
 pub fn median_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
+    median_f32_with_progress(input, radius, &mut no_progress).expect("never cancelled")
+}
+
+/// `median_f32` reporting progress (0.0-1.0) per row; returning `false` cancels.
+pub fn median_f32_with_progress(
+    input: ArrayView3<f32>,
+    radius: u32,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<Array3<f32>, Cancelled> {
     let (height, width, channels) = input.dim();
     let radius = radius.min(21) as usize;
     if radius == 0 {
-        return input.to_owned();
+        return Ok(input.to_owned());
     }
 
     let mut output = Array3::<f32>::zeros((height, width, channels));
@@ -437,7 +724,9 @@ pub fn median_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
 
         // Process using same u8 core
         let mut out_chan = vec![0u8; npixels];
-        median_channel_u8(&chan, &mut out_chan, width, height, radius);
+        median_channel_u8_with_progress(&chan, &mut out_chan, width, height, radius, &mut |f| {
+            progress((c as f32 + f) / color_channels as f32)
+        })?;
 
         // Convert back to f32
         for y in 0..height {
@@ -456,9 +745,256 @@ pub fn median_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
         }
     }
 
+    Ok(output)
+}
+
+// ============================================================================
+// Hot Pixel Removal
+// ============================================================================
+
+/// Replace isolated outliers with the median of their 3x3 window.
+///
+/// A channel value is an outlier when it lies more than `threshold` above
+/// the brightest or below the darkest of its (up to 8) neighbors. Rows are
+/// processed in parallel; everything else is copied through unchanged.
+fn remove_hot_pixels_impl<T>(input: ArrayView3<T>, threshold: f32) -> Array3<T>
+where
+    T: Copy + PartialOrd + Into<f32> + Send + Sync,
+{
+    let (height, width, channels) = input.dim();
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = input.iter().copied().collect::<Vec<T>>();
+    if width * height > 1 {
+        output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+            let (y0, y1) = (y.saturating_sub(1), (y + 1).min(height - 1));
+            for x in 0..width {
+                let (x0, x1) = (x.saturating_sub(1), (x + 1).min(width - 1));
+                for c in 0..color_channels {
+                    let center: f32 = input[[y, x, c]].into();
+                    let (mut lo, mut hi) = (f32::INFINITY, f32::NEG_INFINITY);
+                    for sy in y0..=y1 {
+                        for sx in x0..=x1 {
+                            if (sy, sx) != (y, x) {
+                                let v: f32 = input[[sy, sx, c]].into();
+                                lo = lo.min(v);
+                                hi = hi.max(v);
+                            }
+                        }
+                    }
+                    if center - hi <= threshold && lo - center <= threshold {
+                        continue;
+                    }
+                    let mut window = [input[[y, x, c]]; 9];
+                    let mut count = 0;
+                    for sy in y0..=y1 {
+                        for sx in x0..=x1 {
+                            window[count] = input[[sy, sx, c]];
+                            count += 1;
+                        }
+                    }
+                    let window = &mut window[..count];
+                    window.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                    row[x * channels + c] = window[count / 2];
+                }
+            }
+        });
+    }
+    Array3::from_shape_vec((height, width, channels), output).expect("output matches input shape")
+}
+
+/// Remove hot and dead pixels - u8 version.
+///
+/// Only pixels that stand out from *all* their neighbors are touched, so
+/// edges, lines and texture pass through unchanged - unlike a plain
+/// [`median_u8`], which softens the whole image. Meant for stuck sensor
+/// pixels in long exposures.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `threshold` - How far (0-255) a value must exceed its neighbors' range
+///
+/// # Returns
+/// Cleaned image with same channel count, alpha preserved
+pub fn remove_hot_pixels_u8(input: ArrayView3<u8>, threshold: f32) -> Array3<u8> {
+    remove_hot_pixels_impl(input, threshold)
+}
+
+/// Remove hot and dead pixels - f32 version.
+///
+/// Same detection as [`remove_hot_pixels_u8`] on exact values (no
+/// quantization, values above 1.0 allowed).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `threshold` - How far (0.0-1.0) a value must exceed its neighbors' range
+///
+/// # Returns
+/// Cleaned image with same channel count, alpha preserved
+pub fn remove_hot_pixels_f32(input: ArrayView3<f32>, threshold: f32) -> Array3<f32> {
+    remove_hot_pixels_impl(input, threshold)
+}
+
+// ============================================================================
+// Surface Blur
+// ============================================================================
+
+/// Largest surface blur radius (Photoshop's limit).
+const SURFACE_BLUR_MAX_RADIUS: u32 = 100;
+
+/// Neighbors differ by up to `SURFACE_BLUR_REACH * threshold` before their
+/// weight falls to zero (Photoshop's falloff).
+const SURFACE_BLUR_REACH: f32 = 2.5;
+
+/// Weight of a neighbor differing by `diff` from the center.
+#[inline]
+fn surface_weight(diff: f32, threshold: f32) -> f32 {
+    (1.0 - diff / (SURFACE_BLUR_REACH * threshold)).max(0.0)
+}
+
+/// Surface blur of one flat u8 channel.
+///
+/// Keeps column histograms like [`median_histogram`], so the window costs
+/// O(1) per pixel regardless of radius; each output then averages only the
+/// histogram bins within reach of the center value.
+fn surface_blur_channel_u8(chan: &[u8], out: &mut [u8], width: usize, height: usize, radius: usize, threshold: f32) {
+    let weights: Vec<f32> = (0..256).map(|d| surface_weight(d as f32, threshold)).collect();
+    let reach = weights.iter().take_while(|&&w| w > 0.0).count() as isize;
+    let mut col_hist: Vec<[u16; 256]> = vec![[0u16; 256]; width];
+
+    for y in 0..height {
+        if y == 0 {
+            for sy in 0..=radius.min(height - 1) {
+                for x in 0..width {
+                    col_hist[x][chan[sy * width + x] as usize] += 1;
+                }
+            }
+        } else {
+            if y > radius {
+                let ry = y - radius - 1;
+                for x in 0..width {
+                    col_hist[x][chan[ry * width + x] as usize] -= 1;
+                }
+            }
+            if y + radius < height {
+                let ay = y + radius;
+                for x in 0..width {
+                    col_hist[x][chan[ay * width + x] as usize] += 1;
+                }
+            }
+        }
+
+        let mut hist = [0u32; 256];
+        for column in col_hist.iter().take(radius.min(width - 1) + 1) {
+            for (h, &v) in hist.iter_mut().zip(column.iter()) {
+                *h += v as u32;
+            }
+        }
+
+        for x in 0..width {
+            if x > radius {
+                for (h, &v) in hist.iter_mut().zip(col_hist[x - radius - 1].iter()) {
+                    *h -= v as u32;
+                }
+            }
+            if x > 0 && x + radius < width {
+                for (h, &v) in hist.iter_mut().zip(col_hist[x + radius].iter()) {
+                    *h += v as u32;
+                }
+            }
+
+            let center = chan[y * width + x] as isize;
+            let lo = (center - reach + 1).max(0) as usize;
+            let hi = (center + reach - 1).min(255) as usize;
+            let (mut sum, mut total) = (0.0f32, 0.0f32);
+            for (v, &count) in hist.iter().enumerate().take(hi + 1).skip(lo) {
+                if count > 0 {
+                    let w = weights[v.abs_diff(center as usize)] * count as f32;
+                    sum += w * v as f32;
+                    total += w;
+                }
+            }
+            out[y * width + x] = (sum / total).round() as u8;
+        }
+    }
+}
+
+/// Apply surface blur - u8 version.
+///
+/// Averages only neighbors whose value is close to the center pixel's, so
+/// flat areas (skin, sky, noise) are smoothed while edges stay sharp. A
+/// neighbor's weight falls linearly from 1 at equal value to 0 at a
+/// difference of 2.5 × `threshold`, as in Photoshop's Surface Blur.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `radius` - Window radius in pixels (1-100)
+/// * `threshold` - Tonal difference (0-255) below which neighbors are blended
+///
+/// # Returns
+/// Blurred image with same channel count, alpha preserved
+pub fn surface_blur_u8(input: ArrayView3<u8>, radius: u32, threshold: f32) -> Array3<u8> {
+    let (height, width, channels) = input.dim();
+    let radius = radius.min(SURFACE_BLUR_MAX_RADIUS) as usize;
+    if radius == 0 || threshold <= 0.0 || width == 0 || height == 0 {
+        return input.to_owned();
+    }
+
+    let mut output = input.to_owned();
+    let color_channels = if channels == 4 { 3 } else { channels };
+    for c in 0..color_channels {
+        let chan: Vec<u8> = input.index_axis(Axis(2), c).iter().copied().collect();
+        let mut out_chan = vec![0u8; height * width];
+        surface_blur_channel_u8(&chan, &mut out_chan, width, height, radius, threshold);
+        output
+            .index_axis_mut(Axis(2), c)
+            .assign(&ndarray::ArrayView2::from_shape((height, width), &out_chan).expect("channel matches image"));
+    }
     output
 }
 
+/// Apply surface blur - f32 version.
+///
+/// Same weighting as [`surface_blur_u8`] on exact values, summing the
+/// window directly (rows in parallel).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `radius` - Window radius in pixels (1-100)
+/// * `threshold` - Tonal difference (0.0-1.0) below which neighbors are blended
+///
+/// # Returns
+/// Blurred image with same channel count, alpha preserved
+pub fn surface_blur_f32(input: ArrayView3<f32>, radius: u32, threshold: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let radius = radius.min(SURFACE_BLUR_MAX_RADIUS) as usize;
+    if radius == 0 || threshold <= 0.0 || width == 0 || height == 0 {
+        return input.to_owned();
+    }
+
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = input.iter().copied().collect::<Vec<f32>>();
+    output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius).min(height - 1));
+        for x in 0..width {
+            let (x0, x1) = (x.saturating_sub(radius), (x + radius).min(width - 1));
+            for c in 0..color_channels {
+                let center = input[[y, x, c]];
+                let (mut sum, mut total) = (0.0f32, 0.0f32);
+                for sy in y0..=y1 {
+                    for sx in x0..=x1 {
+                        let v = input[[sy, sx, c]];
+                        let w = surface_weight((v - center).abs(), threshold);
+                        sum += w * v;
+                        total += w;
+                    }
+                }
+                row[x * channels + c] = sum / total;
+            }
+        }
+    });
+    Array3::from_shape_vec((height, width, channels), output).expect("output matches input shape")
+}
+
 // ============================================================================
 // Denoise (Non-Local Means)
 // ============================================================================
@@ -476,6 +1012,15 @@ pub fn median_f32(input: ArrayView3<f32>, radius: u32) -> Array3<f32> {
 /// # Returns
 /// Denoised image with same channel count
 pub fn denoise_u8(input: ArrayView3<u8>, strength: f32) -> Array3<u8> {
+    denoise_u8_with_progress(input, strength, &mut no_progress).expect("never cancelled")
+}
+
+/// `denoise_u8` reporting progress (0.0-1.0) per row; returning `false` cancels.
+pub fn denoise_u8_with_progress(
+    input: ArrayView3<u8>,
+    strength: f32,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<Array3<u8>, Cancelled> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -499,6 +1044,7 @@ pub fn denoise_u8(input: ArrayView3<u8>, strength: f32) -> Array3<u8> {
     };
 
     for y in 0..height {
+        report(progress, y as f32 / height as f32)?;
         for x in 0..width {
             // For transparent pixels in RGBA, just copy
             if has_alpha && input[[y, x, 3]] < 1 {
@@ -549,7 +1095,7 @@ pub fn denoise_u8(input: ArrayView3<u8>, strength: f32) -> Array3<u8> {
                     let normalized_ssd = ssd / (patch_pixels * color_channels) as f32;
 
                     // Weight: exp(-SSD / h²)
-                    let weight = (-normalized_ssd / h_sq).exp();
+                    let weight = fmath::exp(-normalized_ssd / h_sq);
 
                     // Accumulate weighted pixel values
                     for c in 0..color_channels {
@@ -577,7 +1123,8 @@ pub fn denoise_u8(input: ArrayView3<u8>, strength: f32) -> Array3<u8> {
         }
     }
 
-    output
+    report(progress, 1.0)?;
+    Ok(output)
 }
 
 /// Apply denoise filter - f32 version.
@@ -591,6 +1138,15 @@ pub fn denoise_u8(input: ArrayView3<u8>, strength: f32) -> Array3<u8> {
 /// # Returns
 /// Denoised image with same channel count
 pub fn denoise_f32(input: ArrayView3<f32>, strength: f32) -> Array3<f32> {
+    denoise_f32_with_progress(input, strength, &mut no_progress).expect("never cancelled")
+}
+
+/// `denoise_f32` reporting progress (0.0-1.0) per row; returning `false` cancels.
+pub fn denoise_f32_with_progress(
+    input: ArrayView3<f32>,
+    strength: f32,
+    progress: &mut dyn FnMut(f32) -> bool,
+) -> Result<Array3<f32>, Cancelled> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -612,6 +1168,7 @@ pub fn denoise_f32(input: ArrayView3<f32>, strength: f32) -> Array3<f32> {
     };
 
     for y in 0..height {
+        report(progress, y as f32 / height as f32)?;
         for x in 0..width {
             // For transparent pixels in RGBA, just copy
             if has_alpha && input[[y, x, 3]] < 0.001 {
@@ -657,7 +1214,7 @@ pub fn denoise_f32(input: ArrayView3<f32>, strength: f32) -> Array3<f32> {
                     }
 
                     let normalized_ssd = ssd / (patch_pixels * color_channels) as f32;
-                    let weight = (-normalized_ssd / h_sq).exp();
+                    let weight = fmath::exp(-normalized_ssd / h_sq);
 
                     for c in 0..color_channels {
                         sum_color[c] += input[[sy, sx, c]] * weight;
@@ -682,7 +1239,8 @@ pub fn denoise_f32(input: ArrayView3<f32>, strength: f32) -> Array3<f32> {
         }
     }
 
-    output
+    report(progress, 1.0)?;
+    Ok(output)
 }
 
 #[cfg(test)]
@@ -725,6 +1283,46 @@ mod tests {
         assert!(has_change);
     }
 
+    #[test]
+    fn test_add_noise_hashed_is_tile_invariant() {
+        let img = Array3::<f32>::from_elem((16, 20, 3), 0.5);
+        let full = add_noise_hashed_f32(img.view(), 0.2, true, false, 7, (0, 0));
+
+        // Right half rendered on its own lines up with the full render
+        let tile = img.slice(ndarray::s![4..16, 10..20, ..]);
+        let part = add_noise_hashed_f32(tile, 0.2, true, false, 7, (10, 4));
+        assert_eq!(part, full.slice(ndarray::s![4..16, 10..20, ..]));
+
+        // Channels and neighbours differ, other seeds give another field
+        assert_ne!(full[[0, 0, 0]], full[[0, 0, 1]]);
+        assert_ne!(full[[0, 0, 0]], full[[0, 1, 0]]);
+        let other = add_noise_hashed_f32(img.view(), 0.2, true, false, 8, (0, 0));
+        assert_ne!(full, other);
+    }
+
+    #[test]
+    fn test_film_grain_stronger_in_shadows() {
+        // Left half dark, right half bright
+        let img = Array3::<f32>::from_shape_fn((32, 64, 3), |(_, x, _)| if x < 32 { 0.2 } else { 0.8 });
+        let out = film_grain_f32(img.view(), 0.5, 2.0, 0.3, 0.0, 3);
+
+        let deviation = |x0: usize| {
+            let mut sum = 0.0;
+            for y in 0..32 {
+                for x in x0..x0 + 32 {
+                    sum += (out[[y, x, 0]] - img[[y, x, 0]]).powi(2);
+                }
+            }
+            (sum / 1024.0).sqrt()
+        };
+        assert!(deviation(0) > deviation(32) * 2.0);
+        // Monochrome grain: channels move together
+        assert_eq!(out[[5, 5, 0]], out[[5, 5, 2]]);
+
+        let chroma = film_grain_f32(img.view(), 0.5, 2.0, 0.3, 1.0, 3);
+        assert_ne!(chroma[[5, 5, 0]], chroma[[5, 5, 2]]);
+    }
+
     #[test]
     fn test_median_u8_removes_salt_pepper() {
         let mut img = Array3::<u8>::zeros((5, 5, 4));
@@ -743,6 +1341,28 @@ mod tests {
         assert!(result[[2, 2, 0]] < 200);
     }
 
+    #[test]
+    fn test_median_progress_and_cancel() {
+        let img = Array3::<u8>::from_shape_fn((16, 8, 3), |(y, x, c)| ((y * 13 + x * 7 + c) % 256) as u8);
+        let mut reports = Vec::new();
+        let result = median_u8_with_progress(img.view(), 2, &mut |f| {
+            reports.push(f);
+            true
+        });
+        assert_eq!(result.unwrap(), median_u8(img.view(), 2));
+        assert!(reports.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(reports.last(), Some(&1.0));
+
+        // The report that cancels is the last one delivered
+        let mut reports = Vec::new();
+        let cancelled = denoise_u8_with_progress(img.view(), 0.5, &mut |f| {
+            reports.push(f);
+            f < 0.5
+        });
+        assert_eq!(cancelled, Err(Cancelled));
+        assert_eq!(reports.iter().position(|&f| f >= 0.5), Some(reports.len() - 1));
+    }
+
     #[test]
     fn test_median_f32_preserves_edge() {
         let mut img = Array3::<f32>::zeros((5, 5, 4));
@@ -760,6 +1380,27 @@ mod tests {
         assert!(result[[2, 4, 0]] > 0.5);
     }
 
+    #[test]
+    fn test_surface_blur_smooths_noise_and_keeps_edges() {
+        // Noisy dark and light halves
+        let img = Array3::from_shape_fn((12, 16, 4), |(y, x, c)| match c {
+            3 => 200,
+            _ => (if x < 8 { 60 } else { 190 }) + if (x + y) % 2 == 0 { 6 } else { 0 },
+        });
+        let result = surface_blur_u8(img.view(), 3, 10.0);
+        // Noise is averaged out, the step between the halves survives
+        assert!(result[[6, 3, 0]].abs_diff(63) <= 1 && result[[6, 4, 0]].abs_diff(63) <= 1);
+        assert!(result[[6, 7, 0]] < 70 && result[[6, 8, 0]] > 185);
+        assert_eq!(result[[6, 6, 3]], 200);
+
+        // The f32 path applies the same weights
+        let img_f32 = img.mapv(|v| v as f32 / 255.0);
+        let result_f32 = surface_blur_f32(img_f32.view(), 3, 10.0 / 255.0);
+        assert!(result_f32.iter().zip(result.iter()).all(|(&f, &u)| (f * 255.0 - u as f32).abs() <= 1.0));
+
+        assert_eq!(surface_blur_u8(img.view(), 3, 0.0), img);
+    }
+
     #[test]
     fn test_denoise_u8_smooth_region() {
         let mut img = Array3::<u8>::zeros((5, 5, 4));
@@ -797,4 +1438,24 @@ mod tests {
         assert!((result[[1, 1, 3]] - 0.7).abs() < 0.001);
     }
 
+    #[test]
+    fn test_remove_hot_pixels_only_touches_outliers() {
+        // Vertical edge at x = 4 plus one hot pixel and one dead pixel
+        let mut img = Array3::<u8>::from_shape_fn((8, 8, 3), |(_, x, _)| if x < 4 { 60 } else { 180 });
+        img[[2, 1, 0]] = 250;
+        img[[5, 6, 2]] = 0;
+
+        let result = remove_hot_pixels_u8(img.view(), 40.0);
+        assert_eq!(result[[2, 1, 0]], 60);
+        assert_eq!(result[[5, 6, 2]], 180);
+        // Everything else, including the edge, is unchanged
+        let mut expected = img.clone();
+        expected[[2, 1, 0]] = 60;
+        expected[[5, 6, 2]] = 180;
+        assert_eq!(result, expected);
+
+        let img_f32 = img.mapv(|v| v as f32 / 255.0);
+        let result_f32 = remove_hot_pixels_f32(img_f32.view(), 40.0 / 255.0);
+        assert_eq!(result_f32, expected.mapv(|v| v as f32 / 255.0));
+    }
 }
@@ -0,0 +1,162 @@
+/**
+ * Perspective rectification - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - perspective.rs (Rust implementation)
+ * - perspective.py (Python wrapper)
+ *
+ * Detects the dominant quadrilateral of a document photo and warps it to
+ * an upright rectangle, or places a design onto a quadrilateral of a photo
+ * (mockups). Corners are [x0, y0, ..., x3, y3] ordered top-left,
+ * top-right, bottom-right, bottom-left.
+ */
+
+import { initSync } from '../wasm/imagestag_rust.js';
+import * as wasm from '../wasm/imagestag_rust.js';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+let wasmInitialized = false;
+
+/**
+ * Initialize WASM module (required before using filter functions).
+ */
+export async function initWasm() {
+    if (wasmInitialized) return;
+    const __filename = fileURLToPath(import.meta.url);
+    const __dirname = path.dirname(__filename);
+    const wasmPath = path.join(__dirname, '..', 'wasm', 'imagestag_rust_bg.wasm');
+    const wasmBuffer = fs.readFileSync(wasmPath);
+    initSync(wasmBuffer);
+    wasmInitialized = true;
+}
+
+function outputSize(corners, outWidth, outHeight) {
+    if (outWidth > 0 && outHeight > 0) return [outWidth, outHeight];
+    return wasm.quad_size_wasm(new Float32Array(corners));
+}
+
+/**
+ * Find the dominant quadrilateral (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @returns {number[]|null} - [x0, y0, ..., x3, y3] or null if none found
+ */
+export function detectDocumentQuad(imageData) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const quad = wasm.detect_document_quad_wasm(new Uint8Array(data.buffer), width, height, channels);
+    return quad.length === 8 ? Array.from(quad) : null;
+}
+
+/**
+ * Find the dominant quadrilateral (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @returns {number[]|null} - [x0, y0, ..., x3, y3] or null if none found
+ */
+export function detectDocumentQuadF32(imageData) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const quad = wasm.detect_document_quad_f32_wasm(data, width, height, channels);
+    return quad.length === 8 ? Array.from(quad) : null;
+}
+
+/**
+ * Warp a quadrilateral region to an upright rectangle (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number[]} corners - [x0, y0, ..., x3, y3]
+ * @param {number} outWidth - Output width (0 = longest horizontal side)
+ * @param {number} outHeight - Output height (0 = longest vertical side)
+ * @returns {Object} - Rectified image data {data, width, height, channels}
+ */
+export function rectifyQuad(imageData, corners, outWidth = 0, outHeight = 0) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const [w, h] = outputSize(corners, outWidth, outHeight);
+
+    const result = wasm.rectify_quad_wasm(
+        new Uint8Array(data.buffer), width, height, channels, new Float32Array(corners), w, h
+    );
+
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: w,
+        height: h,
+        channels
+    };
+}
+
+/**
+ * Warp a quadrilateral region to an upright rectangle (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @returns {Object} - Rectified image data {data, width, height, channels}
+ */
+export function rectifyQuadF32(imageData, corners, outWidth = 0, outHeight = 0) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const [w, h] = outputSize(corners, outWidth, outHeight);
+
+    const result = wasm.rectify_quad_f32_wasm(data, width, height, channels, new Float32Array(corners), w, h);
+
+    return {
+        data: new Float32Array(result.buffer),
+        width: w,
+        height: h,
+        channels
+    };
+}
+
+function mapToQuadOptions(src, options) {
+    return Object.assign(new wasm.MapToQuadOptions(), {
+        src_width: src.width,
+        src_height: src.height,
+        src_channels: src.channels || 4,
+        opacity: options.opacity ?? 1.0,
+        blend_mode: options.blendMode ?? 'normal',
+    });
+}
+
+/**
+ * Warp a design onto a quadrilateral of a photo and composite it (u8).
+ * @param {Object} src - Design {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} dst - Photo {data: Uint8ClampedArray, width, height, channels}
+ * @param {number[]} corners - Target quad in dst [x0, y0, ..., x3, y3]
+ * @param {Object} options - {blendMode: 'normal', opacity: 1.0}
+ * @returns {Object} - Composited image data with the size of dst
+ */
+export function mapToQuad(src, dst, corners, options = {}) {
+    const { data, width, height } = dst;
+    const channels = dst.channels || 4;
+    const opts = mapToQuadOptions(src, options);
+    const result = wasm.map_to_quad_wasm(
+        new Uint8Array(src.data.buffer), new Uint8Array(data.buffer), width, height, channels,
+        new Float32Array(corners), opts
+    );
+    opts.free();
+
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width,
+        height,
+        channels
+    };
+}
+
+/**
+ * Warp a design onto a quadrilateral of a photo and composite it (f32).
+ * @returns {Object} - Composited image data with the size of dst
+ */
+export function mapToQuadF32(src, dst, corners, options = {}) {
+    const { data, width, height } = dst;
+    const channels = dst.channels || 4;
+    const opts = mapToQuadOptions(src, options);
+    const result = wasm.map_to_quad_f32_wasm(src.data, data, width, height, channels, new Float32Array(corners), opts);
+    opts.free();
+
+    return {
+        data: new Float32Array(result.buffer),
+        width,
+        height,
+        channels
+    };
+}
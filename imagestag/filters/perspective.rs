@@ -0,0 +1,438 @@
+//! Perspective rectification of documents, receipts and whiteboards.
+//!
+//! `rectify_quad` warps an arbitrary quadrilateral to an upright rectangle
+//! with a projective (homography) mapping and bicubic sampling.
+//! `detect_document_quad` finds the dominant quadrilateral in a photo by
+//! tracing the contours of its edge map and approximating them with
+//! Douglas-Peucker polygons. `map_to_quad` goes the other way: it warps a
+//! flat design onto a quadrilateral of a photo and composites it there
+//! (product mockups).
+//!
+//! ## Supported Formats
+//!
+//! All functions support 1, 3, or 4 channel images in both u8 and f32 formats.
+//!
+//! ## Corner Order
+//!
+//! Corners are `[x, y]` pixel positions ordered top-left, top-right,
+//! bottom-right, bottom-left.
+
+use ndarray::{Array2, Array3, ArrayView3};
+
+use super::compositing::blend_images_f32;
+use super::edge::find_edges_f32;
+use super::grayscale::LumaStandard;
+use super::straighten::warp_bicubic;
+use crate::selection::marching_squares::{douglas_peucker, marching_squares, Point};
+
+/// Four corners: top-left, top-right, bottom-right, bottom-left.
+pub type Quad = [[f32; 2]; 4];
+
+/// Contours must enclose at least this fraction of the image to count as a document.
+const MIN_AREA_FRACTION: f32 = 0.1;
+
+/// Douglas-Peucker tolerance relative to the contour perimeter.
+const APPROX_FRACTION: f32 = 0.02;
+
+// ============================================================================
+// Rectification
+// ============================================================================
+
+/// Projective mapping from the unit square to a quad (Heckbert's
+/// square-to-quad): (0,0)->TL, (1,0)->TR, (1,1)->BR, (0,1)->BL.
+#[derive(Clone, Copy)]
+struct Homography {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+    f: f32,
+    g: f32,
+    h: f32,
+}
+
+impl Homography {
+    fn square_to_quad(quad: &Quad) -> Self {
+        let [[x0, y0], [x1, y1], [x2, y2], [x3, y3]] = *quad;
+        let (sx, sy) = (x0 - x1 + x2 - x3, y0 - y1 + y2 - y3);
+        let (dx1, dx2, dy1, dy2) = (x1 - x2, x3 - x2, y1 - y2, y3 - y2);
+        let den = dx1 * dy2 - dx2 * dy1;
+        let (g, h) = if (sx.abs() < 1e-6 && sy.abs() < 1e-6) || den.abs() < 1e-12 {
+            // Parallelogram: the mapping is affine
+            (0.0, 0.0)
+        } else {
+            ((sx * dy2 - dx2 * sy) / den, (dx1 * sy - sx * dy1) / den)
+        };
+        Homography {
+            a: x1 - x0 + g * x1,
+            b: x3 - x0 + h * x3,
+            c: x0,
+            d: y1 - y0 + g * y1,
+            e: y3 - y0 + h * y3,
+            f: y0,
+            g,
+            h,
+        }
+    }
+
+    fn map(&self, u: f32, v: f32) -> (f32, f32) {
+        let w = self.g * u + self.h * v + 1.0;
+        ((self.a * u + self.b * v + self.c) / w, (self.d * u + self.e * v + self.f) / w)
+    }
+
+    /// Inverse mapping from the quad back to the unit square (adjugate of
+    /// the 3x3 matrix; the projective scale cancels out).
+    fn unmap(&self, x: f32, y: f32) -> (f32, f32) {
+        let Homography { a, b, c, d, e, f, g, h } = *self;
+        let u = (e - f * h) * x + (c * h - b) * y + (b * f - c * e);
+        let v = (f * g - d) * x + (a - c * g) * y + (c * d - a * f);
+        let w = (d * h - e * g) * x + (b * g - a * h) * y + (a * e - b * d);
+        (u / w, v / w)
+    }
+}
+
+/// Natural output size of a quad: the longer of each pair of opposite sides.
+///
+/// # Returns
+/// (width, height), each at least 1
+pub fn quad_size(corners: &Quad) -> (usize, usize) {
+    let dist = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).hypot(a[1] - b[1]);
+    let [tl, tr, br, bl] = *corners;
+    let width = dist(tl, tr).max(dist(bl, br)).round() as usize + 1;
+    let height = dist(tl, bl).max(dist(tr, br)).round() as usize + 1;
+    (width.max(1), height.max(1))
+}
+
+fn rectify_impl(input: ArrayView3<f32>, corners: &Quad, out_w: usize, out_h: usize) -> Array3<f32> {
+    let (out_w, out_h) = if out_w == 0 || out_h == 0 { quad_size(corners) } else { (out_w, out_h) };
+    let homography = Homography::square_to_quad(corners);
+    let (sx, sy) = (1.0 / (out_w.max(2) - 1) as f32, 1.0 / (out_h.max(2) - 1) as f32);
+    warp_bicubic(input, out_w, out_h, |x, y| homography.map(x * sx, y * sy))
+}
+
+/// Warp a quadrilateral region to an upright rectangle - f32 version.
+///
+/// The corners map onto the corner pixels of the output. Areas of the quad
+/// outside the source image become 0 (transparent for RGBA).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `corners` - Quad corners (top-left, top-right, bottom-right, bottom-left)
+/// * `out_w` - Output width (0 = `quad_size`)
+/// * `out_h` - Output height (0 = `quad_size`)
+///
+/// # Returns
+/// Rectified image with the same channel count
+pub fn rectify_quad_f32(input: ArrayView3<f32>, corners: &Quad, out_w: usize, out_h: usize) -> Array3<f32> {
+    rectify_impl(input, corners, out_w, out_h)
+}
+
+/// Warp a quadrilateral region to an upright rectangle - u8 version.
+pub fn rectify_quad_u8(input: ArrayView3<u8>, corners: &Quad, out_w: usize, out_h: usize) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    rectify_impl(input_f32.view(), corners, out_w, out_h).mapv(|v| (v * 255.0 + 0.5) as u8)
+}
+
+// ============================================================================
+// Mockup Placement
+// ============================================================================
+
+/// Antialiased coverage of a convex quad at pixel center (x, y): the signed
+/// distance to the nearest edge, shifted so the edge itself is 50%.
+fn quad_coverage(corners: &Quad, x: f32, y: f32) -> f32 {
+    let orientation: f32 = (0..4)
+        .map(|i| {
+            let (p, q) = (corners[i], corners[(i + 1) % 4]);
+            p[0] * q[1] - q[0] * p[1]
+        })
+        .sum::<f32>()
+        .signum();
+    let inside = (0..4)
+        .map(|i| {
+            let (p, q) = (corners[i], corners[(i + 1) % 4]);
+            let (ex, ey) = (q[0] - p[0], q[1] - p[1]);
+            orientation * (ex * (y - p[1]) - ey * (x - p[0])) / ex.hypot(ey).max(1e-6)
+        })
+        .fold(f32::MAX, f32::min);
+    (inside + 0.5).clamp(0.0, 1.0)
+}
+
+fn map_to_quad_impl(src: ArrayView3<f32>, dst: ArrayView3<f32>, corners: &Quad, blend_mode: &str, opacity: f32) -> Array3<f32> {
+    let (src_h, src_w, src_c) = src.dim();
+    let (dst_h, dst_w, dst_c) = dst.dim();
+    let homography = Homography::square_to_quad(corners);
+    let (scale_x, scale_y) = ((src_w.max(2) - 1) as f32, (src_h.max(2) - 1) as f32);
+    let warped = warp_bicubic(src, dst_w, dst_h, |x, y| {
+        let (u, v) = homography.unmap(x, y);
+        (u * scale_x, v * scale_y)
+    });
+
+    // Layer in the destination's channel layout; coverage and source alpha go to the mask
+    let mut mask = Array2::<f32>::zeros((dst_h, dst_w));
+    let mut layer = Array3::<f32>::zeros((dst_h, dst_w, dst_c));
+    for y in 0..dst_h {
+        for x in 0..dst_w {
+            let coverage = quad_coverage(corners, x as f32, y as f32);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let alpha = if src_c == 4 { warped[[y, x, 3]] } else { 1.0 };
+            mask[[y, x]] = coverage * alpha;
+            let rgb = if src_c >= 3 {
+                [warped[[y, x, 0]], warped[[y, x, 1]], warped[[y, x, 2]]]
+            } else {
+                [warped[[y, x, 0]]; 3]
+            };
+            match dst_c {
+                1 => layer[[y, x, 0]] = LumaStandard::default().luma(rgb[0], rgb[1], rgb[2]),
+                _ => {
+                    for c in 0..3 {
+                        layer[[y, x, c]] = rgb[c];
+                    }
+                    if dst_c == 4 {
+                        layer[[y, x, 3]] = 1.0;
+                    }
+                }
+            }
+        }
+    }
+    blend_images_f32(dst, layer.view(), Some(mask.view()), opacity, blend_mode)
+}
+
+/// Warp an image onto a quadrilateral of another and composite it - f32 version.
+///
+/// The corners of `src` land on the quad corners (projective mapping,
+/// bicubic sampling), the quad edges are antialiased and the source alpha
+/// is respected. The layer is then blended like [`blend_images_f32`].
+/// `src` and `dst` may have different sizes and channel counts.
+///
+/// # Arguments
+/// * `src` - Design to place, with 1, 3, or 4 channels, values 0.0-1.0
+/// * `dst` - Photo to place it on, with 1, 3, or 4 channels
+/// * `corners` - Target quad in `dst` (top-left, top-right, bottom-right, bottom-left)
+/// * `blend_mode` - Blend mode, e.g. "normal" or "multiply" to keep the photo's shading
+/// * `opacity` - Layer opacity (0.0-1.0)
+///
+/// # Returns
+/// Composited image with the shape of `dst`
+pub fn map_to_quad_f32(src: ArrayView3<f32>, dst: ArrayView3<f32>, corners: &Quad, blend_mode: &str, opacity: f32) -> Array3<f32> {
+    map_to_quad_impl(src, dst, corners, blend_mode, opacity)
+}
+
+/// Warp an image onto a quadrilateral of another and composite it - u8 version.
+pub fn map_to_quad_u8(src: ArrayView3<u8>, dst: ArrayView3<u8>, corners: &Quad, blend_mode: &str, opacity: f32) -> Array3<u8> {
+    let src_f32 = src.mapv(|v| v as f32 / 255.0);
+    let dst_f32 = dst.mapv(|v| v as f32 / 255.0);
+    map_to_quad_impl(src_f32.view(), dst_f32.view(), corners, blend_mode, opacity).mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Document Detection
+// ============================================================================
+
+/// Canny edge map (255 = edge), thickened by one pixel to close small gaps.
+fn edge_mask(input: ArrayView3<f32>) -> Vec<u8> {
+    let edges = find_edges_f32(input, 1.5, 0.1, 0.2);
+    let (height, width, _) = edges.dim();
+    let mut mask = vec![0u8; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            if edges[[y, x, 0]] < 0.5 {
+                continue;
+            }
+            for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                    mask[ny * width + nx] = 255;
+                }
+            }
+        }
+    }
+    mask
+}
+
+/// Shoelace area of a polygon.
+fn polygon_area(points: &[Point]) -> f32 {
+    let n = points.len();
+    let twice: f32 = (0..n)
+        .map(|i| {
+            let (p, q) = (points[i], points[(i + 1) % n]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum();
+    twice.abs() / 2.0
+}
+
+fn is_convex(points: &[Point]) -> bool {
+    let n = points.len();
+    let signs: Vec<f32> = (0..n)
+        .map(|i| {
+            let (a, b, c) = (points[i], points[(i + 1) % n], points[(i + 2) % n]);
+            (b.x - a.x) * (c.y - b.y) - (b.y - a.y) * (c.x - b.x)
+        })
+        .collect();
+    signs.iter().all(|&s| s > 0.0) || signs.iter().all(|&s| s < 0.0)
+}
+
+/// Approximate a closed contour with a polygon, starting at the point
+/// farthest from its centroid (always a hull vertex, so a corner).
+fn approximate_polygon(points: &[Point]) -> Vec<Point> {
+    let n = points.len() as f32;
+    let cx = points.iter().map(|p| p.x).sum::<f32>() / n;
+    let cy = points.iter().map(|p| p.y).sum::<f32>() / n;
+    let centroid = Point::new(cx, cy);
+    let start = points
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.distance_to(&centroid).total_cmp(&b.1.distance_to(&centroid)))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+
+    let mut ring: Vec<Point> = points[start..].iter().chain(points[..start].iter()).cloned().collect();
+    ring.push(ring[0]);
+    let perimeter: f32 = ring.windows(2).map(|w| w[0].distance_to(&w[1])).sum();
+    let mut polygon = douglas_peucker(&ring, perimeter * APPROX_FRACTION);
+    polygon.pop();
+    polygon
+}
+
+/// Sort four corners into top-left, top-right, bottom-right, bottom-left.
+fn order_corners(points: &[Point]) -> Quad {
+    let cx = points.iter().map(|p| p.x).sum::<f32>() / 4.0;
+    let cy = points.iter().map(|p| p.y).sum::<f32>() / 4.0;
+    // Ascending angle is clockwise on screen (y points down)
+    let mut sorted: Vec<Point> = points.to_vec();
+    sorted.sort_by(|a, b| (a.y - cy).atan2(a.x - cx).total_cmp(&(b.y - cy).atan2(b.x - cx)));
+    let first = (0..4).min_by(|&a, &b| (sorted[a].x + sorted[a].y).total_cmp(&(sorted[b].x + sorted[b].y))).unwrap_or(0);
+    std::array::from_fn(|i| {
+        let p = sorted[(first + i) % 4];
+        [p.x, p.y]
+    })
+}
+
+fn detect_impl(input: ArrayView3<f32>) -> Option<Quad> {
+    let (height, width, _) = input.dim();
+    if width < 8 || height < 8 {
+        return None;
+    }
+    let min_area = MIN_AREA_FRACTION * (width * height) as f32;
+    let mask = edge_mask(input);
+
+    let mut candidates: Vec<(f32, Quad)> = marching_squares(&mask, width, height, 0.5)
+        .into_iter()
+        .filter(|contour| contour.is_closed && contour.points.len() >= 4)
+        .filter(|contour| {
+            // Cheap bounding box check before approximating
+            let (min_x, max_x) = contour.points.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p.x), hi.max(p.x)));
+            let (min_y, max_y) = contour.points.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p.y), hi.max(p.y)));
+            (max_x - min_x) * (max_y - min_y) >= min_area
+        })
+        .map(|contour| approximate_polygon(&contour.points))
+        .filter(|polygon| polygon.len() == 4 && is_convex(polygon))
+        .map(|polygon| (polygon_area(&polygon), polygon))
+        .filter(|(area, _)| *area >= min_area)
+        .map(|(area, polygon)| (area, order_corners(&polygon)))
+        .collect();
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let (_, outer) = *candidates.first()?;
+
+    // The edge band has an outer and an inner outline; their mean is the edge itself
+    let tolerance = 0.05 * (width as f32).hypot(height as f32);
+    let close = |quad: &Quad| (0..4).all(|i| (quad[i][0] - outer[i][0]).hypot(quad[i][1] - outer[i][1]) < tolerance);
+    let inner = candidates[1..].iter().map(|(_, quad)| quad).find(|quad| close(quad));
+    Some(match inner {
+        Some(inner) => std::array::from_fn(|i| [(outer[i][0] + inner[i][0]) / 2.0, (outer[i][1] + inner[i][1]) / 2.0]),
+        None => outer,
+    })
+}
+
+/// Find the dominant quadrilateral (document, receipt, screen) - f32 version.
+///
+/// Traces the contours of the thickened Canny edge map, approximates each
+/// with a polygon (tolerance 2% of its perimeter) and returns the largest
+/// convex four-sided one covering at least 10% of the image.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+///
+/// # Returns
+/// Corners (top-left, top-right, bottom-right, bottom-left), or `None`
+pub fn detect_document_quad_f32(input: ArrayView3<f32>) -> Option<Quad> {
+    detect_impl(input)
+}
+
+/// Find the dominant quadrilateral (document, receipt, screen) - u8 version.
+pub fn detect_document_quad_u8(input: ArrayView3<u8>) -> Option<Quad> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    detect_impl(input_f32.view())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dark background with a bright, perspective-distorted sheet.
+    fn photo_of_sheet(quad: &Quad) -> Array3<f32> {
+        let inside = |x: f32, y: f32| {
+            (0..4).all(|i| {
+                let (a, b) = (quad[i], quad[(i + 1) % 4]);
+                (b[0] - a[0]) * (y - a[1]) - (b[1] - a[1]) * (x - a[0]) >= 0.0
+            })
+        };
+        Array3::from_shape_fn((120, 160, 3), |(y, x, _)| if inside(x as f32, y as f32) { 0.9 } else { 0.1 })
+    }
+
+    #[test]
+    fn test_rectify_identity_and_square_mapping() {
+        let image = Array3::from_shape_fn((10, 12, 1), |(y, x, _)| (x * 3 + y * 7) as f32 / 120.0);
+        let corners = [[0.0, 0.0], [11.0, 0.0], [11.0, 9.0], [0.0, 9.0]];
+        let result = rectify_quad_f32(image.view(), &corners, 12, 10);
+        assert!(result.iter().zip(image.iter()).all(|(a, b)| (a - b).abs() < 1e-4));
+        assert_eq!(quad_size(&corners), (12, 10));
+
+        // A projective quad maps its corners exactly
+        let quad = [[10.0, 5.0], [50.0, 12.0], [44.0, 40.0], [3.0, 33.0]];
+        let h = Homography::square_to_quad(&quad);
+        for (uv, expected) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].iter().zip(quad) {
+            let (x, y) = h.map(uv.0, uv.1);
+            assert!((x - expected[0]).abs() < 1e-3 && (y - expected[1]).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_detect_and_rectify_document() {
+        let quad = [[30.0, 20.0], [130.0, 30.0], [120.0, 100.0], [40.0, 95.0]];
+        let image = photo_of_sheet(&quad);
+        let found = detect_document_quad_f32(image.view()).expect("document found");
+        for (corner, expected) in found.iter().zip(quad) {
+            assert!((corner[0] - expected[0]).abs() < 2.0 && (corner[1] - expected[1]).abs() < 2.0, "{found:?}");
+        }
+
+        let sheet = rectify_quad_f32(image.view(), &found, 0, 0);
+        let (h, w, _) = sheet.dim();
+        assert!(sheet[[h / 2, w / 2, 0]] > 0.85 && sheet[[3, 3, 0]] > 0.5);
+
+        assert!(detect_document_quad_f32(Array3::from_elem((40, 40, 3), 0.5).view()).is_none());
+    }
+
+    #[test]
+    fn test_map_to_quad_places_design() {
+        let design = Array3::from_shape_fn((8, 8, 4), |(_, _, c)| if c == 0 || c == 3 { 1.0 } else { 0.0 });
+        let photo = Array3::<f32>::from_elem((32, 40, 3), 0.5);
+        let quad = [[10.0, 6.0], [30.0, 8.0], [28.0, 26.0], [12.0, 24.0]];
+
+        let h = Homography::square_to_quad(&quad);
+        let (u, v) = h.unmap(h.map(0.3, 0.7).0, h.map(0.3, 0.7).1);
+        assert!((u - 0.3).abs() < 1e-4 && (v - 0.7).abs() < 1e-4);
+
+        let result = map_to_quad_f32(design.view(), photo.view(), &quad, "normal", 1.0);
+        assert_eq!(result.dim(), (32, 40, 3));
+        assert!((result[[16, 20, 0]] - 1.0).abs() < 1e-4 && result[[16, 20, 1]] < 1e-4);
+        assert_eq!(result[[2, 2, 0]], 0.5);
+        // Antialiased edge: the top edge crosses x = 20 at y = 7
+        assert!(result[[7, 20, 1]] > 0.05 && result[[7, 20, 1]] < 0.45);
+
+        let faded = map_to_quad_f32(design.view(), photo.view(), &quad, "normal", 0.5);
+        assert!((faded[[16, 20, 0]] - 0.75).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,377 @@
+//! Reusable f32 buffers for temporaries of layer effects.
+//!
+//! Every layer effect call allocates several full-size temporaries (the
+//! expanded canvas, its alpha, blur scratch, masks). While a slider is
+//! dragged the same effect runs on the same layer size dozens of times per
+//! second, so those allocations repeat with identical shapes. The pool keeps
+//! released buffers per shape and hands them out again instead of going
+//! back to the allocator.
+//!
+//! - [`take2`] / [`take3`] return a zero-filled array, reusing pooled storage
+//!   of exactly that shape when available
+//! - [`Pooled`] wraps a temporary and returns its storage on drop
+//! - [`give`] returns an array explicitly
+//! - [`trim`] / [`clear`] free this thread's buffers, [`release_all`] those
+//!   of every thread
+//!
+//! Each thread has its own pool (no locking on take/give), but all pools
+//! share one byte budget: at most [`MAX_PER_SHAPE`] buffers per shape and
+//! [`MAX_POOL_BYTES`] across the whole process, however many rayon workers
+//! run effects. Anything beyond is freed normally.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ndarray::{Array, Array2, Array3, ArrayView2, Dimension};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Buffers kept per shape.
+pub const MAX_PER_SHAPE: usize = 4;
+/// Bytes kept across the pools of all threads.
+pub const MAX_POOL_BYTES: usize = 256 << 20;
+
+/// Bytes held by all pools, checked against [`MAX_POOL_BYTES`].
+static TOTAL_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// Bumped by [`release_all`]; pools of an older generation drop their buffers.
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Counters of the current thread's pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Requests served from pooled storage
+    pub hits: usize,
+    /// Requests that had to allocate
+    pub misses: usize,
+    /// Bytes currently held
+    pub pooled_bytes: usize,
+}
+
+#[derive(Default)]
+struct Pool {
+    free: HashMap<Vec<usize>, Vec<Vec<f32>>>,
+    stats: PoolStats,
+    generation: usize,
+}
+
+impl Pool {
+    /// Free buffers, largest first, until at most `max_bytes` are held.
+    fn trim(&mut self, max_bytes: usize) {
+        while self.stats.pooled_bytes > max_bytes {
+            let largest = self.free.keys().max_by_key(|shape| shape.iter().product::<usize>()).cloned();
+            let Some(shape) = largest else { break };
+            let list = self.free.get_mut(&shape).expect("shape was just found");
+            let bytes = list.pop().map_or(0, |v| v.capacity() * size_of::<f32>());
+            if list.is_empty() {
+                self.free.remove(&shape);
+            }
+            self.stats.pooled_bytes -= bytes;
+            TOTAL_BYTES.fetch_sub(bytes, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Drop for Pool {
+    // Thread exit: hand the bytes back to the shared budget
+    fn drop(&mut self) {
+        TOTAL_BYTES.fetch_sub(self.stats.pooled_bytes, Ordering::Relaxed);
+    }
+}
+
+thread_local! {
+    static POOL: RefCell<Pool> = RefCell::new(Pool::default());
+}
+
+/// Run `f` on this thread's pool, first dropping buffers a [`release_all`]
+/// asked for.
+fn with_pool<R>(f: impl FnOnce(&mut Pool) -> R) -> R {
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let generation = GENERATION.load(Ordering::Relaxed);
+        if pool.generation != generation {
+            pool.trim(0);
+            pool.generation = generation;
+        }
+        f(&mut pool)
+    })
+}
+
+fn take_vec(shape: &[usize]) -> Vec<f32> {
+    let len = shape.iter().product();
+    let reused = with_pool(|pool| {
+        let vec = pool.free.get_mut(shape).and_then(Vec::pop);
+        match &vec {
+            Some(v) => {
+                let bytes = v.capacity() * size_of::<f32>();
+                pool.stats.hits += 1;
+                pool.stats.pooled_bytes -= bytes;
+                TOTAL_BYTES.fetch_sub(bytes, Ordering::Relaxed);
+            }
+            None => pool.stats.misses += 1,
+        }
+        vec
+    });
+    match reused {
+        Some(mut vec) => {
+            vec.fill(0.0);
+            vec
+        }
+        None => vec![0.0; len],
+    }
+}
+
+/// Zero-filled (height, width) array, reusing pooled storage.
+pub fn take2(dim: (usize, usize)) -> Array2<f32> {
+    Array2::from_shape_vec(dim, take_vec(&[dim.0, dim.1])).expect("pooled buffer matches its shape")
+}
+
+/// Zero-filled (height, width, channels) array, reusing pooled storage.
+pub fn take3(dim: (usize, usize, usize)) -> Array3<f32> {
+    Array3::from_shape_vec(dim, take_vec(&[dim.0, dim.1, dim.2])).expect("pooled buffer matches its shape")
+}
+
+/// Pooled copy of a 2D view (e.g. the alpha plane of an image).
+pub fn copy2(view: ArrayView2<f32>) -> Array2<f32> {
+    let mut array = take2(view.dim());
+    array.assign(&view);
+    array
+}
+
+/// Return an array's storage to the pool.
+///
+/// Arrays that are not in standard layout, or that would exceed the
+/// per-shape limit or the shared byte budget, are simply dropped.
+pub fn give<D: Dimension>(array: Array<f32, D>) {
+    if !array.is_standard_layout() || array.is_empty() {
+        return;
+    }
+    let shape = array.shape().to_vec();
+    let (vec, offset) = array.into_raw_vec_and_offset();
+    if offset.is_some_and(|o| o != 0) {
+        return;
+    }
+    let bytes = vec.capacity() * size_of::<f32>();
+    with_pool(|pool| {
+        let list = pool.free.entry(shape).or_default();
+        if list.len() >= MAX_PER_SHAPE {
+            return;
+        }
+        let reserved = TOTAL_BYTES.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+            (total + bytes <= MAX_POOL_BYTES).then_some(total + bytes)
+        });
+        if reserved.is_ok() {
+            list.push(vec);
+            pool.stats.pooled_bytes += bytes;
+        }
+    });
+}
+
+/// Counters of this thread's pool.
+pub fn stats() -> PoolStats {
+    with_pool(|pool| pool.stats)
+}
+
+/// Bytes held by the pools of all threads.
+pub fn total_bytes() -> usize {
+    TOTAL_BYTES.load(Ordering::Relaxed)
+}
+
+/// Free this thread's pooled buffers, largest first, until it holds at
+/// most `max_bytes`.
+pub fn trim(max_bytes: usize) {
+    with_pool(|pool| pool.trim(max_bytes));
+}
+
+/// Free every pooled buffer of this thread and reset the counters.
+pub fn clear() {
+    with_pool(|pool| {
+        pool.trim(0);
+        pool.stats = PoolStats::default();
+    });
+}
+
+/// Free the pooled buffers of every thread, e.g. once an editing session
+/// ends.
+///
+/// The calling thread's buffers are freed right away; other threads drop
+/// theirs the next time they use the pool. Until then they still count
+/// against [`MAX_POOL_BYTES`].
+pub fn release_all() {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+    trim(0);
+}
+
+/// Free the layer effect buffer pools of all threads.
+///
+/// Call after interactive editing to return the reused temporaries to the
+/// system; later effect calls allocate again as needed.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "release_buffer_pool")]
+pub fn release_buffer_pool_py() {
+    release_all();
+}
+
+/// Temporary array whose storage goes back to the pool when dropped.
+pub struct Pooled<D: Dimension>(Option<Array<f32, D>>);
+
+impl<D: Dimension> Pooled<D> {
+    pub fn new(array: Array<f32, D>) -> Self {
+        Self(Some(array))
+    }
+
+    /// Keep the array instead of returning it to the pool.
+    pub fn into_inner(mut self) -> Array<f32, D> {
+        self.0.take().expect("pooled array present until dropped")
+    }
+}
+
+impl<D: Dimension> Deref for Pooled<D> {
+    type Target = Array<f32, D>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().expect("pooled array present until dropped")
+    }
+}
+
+impl<D: Dimension> DerefMut for Pooled<D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut().expect("pooled array present until dropped")
+    }
+}
+
+impl<D: Dimension> Drop for Pooled<D> {
+    fn drop(&mut self) {
+        if let Some(array) = self.0.take() {
+            give(array);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{mpsc, Mutex};
+
+    // The budget and `release_all` are process-wide; keep pool tests apart
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_pool_reuses_storage_by_shape() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        clear();
+        let mut a = take2((8, 16));
+        a.fill(3.0);
+        let ptr = a.as_ptr();
+        give(a);
+        assert_eq!(stats().pooled_bytes, 8 * 16 * 4);
+
+        // Same shape: same storage, zeroed again
+        let b = Pooled::new(take2((8, 16)));
+        assert_eq!(b.as_ptr(), ptr);
+        assert!(b.iter().all(|&v| v == 0.0));
+        // Other shape: fresh allocation
+        let c = take3((8, 16, 1));
+        assert_eq!(c.dim(), (8, 16, 1));
+        drop(b);
+        assert_eq!(stats(), PoolStats { hits: 1, misses: 2, pooled_bytes: 8 * 16 * 4 });
+
+        // Bounded per shape
+        (0..MAX_PER_SHAPE + 2).for_each(|_| give(Array2::<f32>::zeros((2, 2))));
+        assert_eq!(stats().pooled_bytes, 8 * 16 * 4 + MAX_PER_SHAPE * 16);
+        clear();
+    }
+
+    #[test]
+    fn test_repeated_effect_reuses_temporaries() {
+        use crate::layer_effects::drop_shadow::{render_drop_shadow_f32, DropShadowParams};
+
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        clear();
+        let image = Array3::from_shape_fn((40, 50, 4), |(y, x, c)| {
+            let inside = (10..30).contains(&y) && (12..38).contains(&x);
+            if inside && c == 3 { 1.0 } else if inside { 0.5 } else { 0.0 }
+        });
+        let params = DropShadowParams { blur_radius: 3.0, ..Default::default() };
+        let first = render_drop_shadow_f32(image.view(), &params, 0);
+        let misses = stats().misses;
+        // Slider drag: same layer, same size, again
+        let second = render_drop_shadow_f32(image.view(), &params, 0);
+        let after = stats();
+        assert_eq!(first, second);
+        assert!(after.hits >= 3, "{after:?}");
+        assert_eq!(after.misses, misses, "every temporary came from the pool: {after:?}");
+        clear();
+    }
+
+    #[test]
+    fn test_trim_frees_largest_first() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        clear();
+        give(Array2::<f32>::zeros((4, 4)));
+        give(Array2::<f32>::zeros((16, 16)));
+        give(Array3::<f32>::zeros((16, 16, 4)));
+        assert_eq!(stats().pooled_bytes, (16 + 256 + 1024) * 4);
+
+        trim(300 * 4);
+        assert_eq!(stats().pooled_bytes, (16 + 256) * 4);
+        // The small buffer is still served from the pool
+        let hits = stats().hits;
+        drop(Pooled::new(take2((4, 4))));
+        assert_eq!(stats().hits, hits + 1);
+        trim(0);
+        assert_eq!(stats().pooled_bytes, 0);
+        clear();
+    }
+
+    #[test]
+    fn test_budget_is_shared_across_threads() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        clear();
+        // Half the budget held by another thread (zeroed pages are never touched)
+        let half = MAX_POOL_BYTES / 2 / size_of::<f32>();
+        let (to_worker, from_main) = mpsc::channel::<()>();
+        let (to_main, from_worker) = mpsc::channel();
+        let worker = std::thread::spawn(move || {
+            give(Array2::<f32>::zeros((1, half)));
+            to_main.send(stats().pooled_bytes).unwrap();
+            from_main.recv().unwrap();
+        });
+        assert_eq!(from_worker.recv().unwrap(), half * size_of::<f32>());
+
+        // Only slightly more than half is left for this thread: rejected
+        give(Array2::<f32>::zeros((1, half + 1)));
+        assert_eq!(stats().pooled_bytes, 0);
+
+        // The exiting thread hands its share back
+        to_worker.send(()).unwrap();
+        worker.join().unwrap();
+        give(Array2::<f32>::zeros((1, half + 1)));
+        assert_eq!(stats().pooled_bytes, (half + 1) * size_of::<f32>());
+        clear();
+    }
+
+    #[test]
+    fn test_release_all_reaches_other_threads() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        clear();
+        let (to_worker, from_main) = mpsc::channel::<()>();
+        let (to_main, from_worker) = mpsc::channel();
+        let worker = std::thread::spawn(move || {
+            give(Array2::<f32>::zeros((32, 32)));
+            to_main.send(stats().pooled_bytes).unwrap();
+            from_main.recv().unwrap();
+            to_main.send(stats().pooled_bytes).unwrap();
+        });
+        assert_eq!(from_worker.recv().unwrap(), 32 * 32 * 4);
+
+        give(Array2::<f32>::zeros((8, 8)));
+        release_all();
+        assert_eq!(stats().pooled_bytes, 0);
+        to_worker.send(()).unwrap();
+        assert_eq!(from_worker.recv().unwrap(), 0);
+        worker.join().unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+/**
+ * Panorama projections - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - projection.rs (Rust implementation)
+ * - projection.py (Python wrapper)
+ *
+ * Provides: equirectToCubemap, cubemapToEquirect, reprojectEquirect, littlePlanet
+ * (u8 data; Float32Array data uses the *_f32 WASM functions)
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+/** Cube face order of equirectToCubemap / cubemapToEquirect. */
+export const CUBE_FACES = ['+x', '-x', '+y', '-y', '+z', '-z'];
+
+const isF32 = (data) => data instanceof Float32Array;
+
+function wrap(result, f32, width, height, channels) {
+    const data = f32 ? new Float32Array(result.buffer) : new Uint8ClampedArray(result.buffer);
+    return { data, width, height, channels };
+}
+
+// ============================================================================
+// Cube Maps
+// ============================================================================
+
+/**
+ * Split an equirectangular panorama into six cube faces.
+ * @param {Object} imageData - {data: Uint8ClampedArray|Float32Array, width, height, channels}
+ * @param {number} faceSize - Face edge length (default width / 4)
+ * @returns {Object} - {data, faceSize, channels}: six faces stacked in CUBE_FACES order
+ */
+export function equirectToCubemap(imageData, faceSize = 0) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const size = faceSize || Math.max(1, Math.floor(width / 4));
+    const f32 = isF32(data);
+    const result = f32
+        ? wasm.equirect_to_cubemap_f32_wasm(data, width, height, channels, size)
+        : wasm.equirect_to_cubemap_wasm(new Uint8Array(data.buffer), width, height, channels, size);
+    const out = f32 ? new Float32Array(result.buffer) : new Uint8ClampedArray(result.buffer);
+    return { data: out, faceSize: size, channels };
+}
+
+/**
+ * Merge six stacked cube faces into an equirectangular panorama.
+ * @param {Object} cubemap - {data, faceSize, channels} as returned by equirectToCubemap
+ * @param {number} outWidth - Panorama width (default 4 * faceSize)
+ * @param {number} outHeight - Panorama height (default 2 * faceSize)
+ * @returns {Object} - Image data {data, width, height, channels}
+ */
+export function cubemapToEquirect(cubemap, outWidth = 0, outHeight = 0) {
+    const { data, faceSize, channels } = cubemap;
+    const width = outWidth || 4 * faceSize;
+    const height = outHeight || 2 * faceSize;
+    const f32 = isF32(data);
+    const result = f32
+        ? wasm.cubemap_to_equirect_f32_wasm(data, faceSize, channels, width, height)
+        : wasm.cubemap_to_equirect_wasm(new Uint8Array(data.buffer), faceSize, channels, width, height);
+    return wrap(result, f32, width, height, channels);
+}
+
+// ============================================================================
+// Perspective Views
+// ============================================================================
+
+/**
+ * Render a perspective view of an equirectangular panorama.
+ * @param {Object} imageData - {data: Uint8ClampedArray|Float32Array, width, height, channels}
+ * @param {Object} opts - {yaw: 0, pitch: 0, roll: 0, fov: 90 (degrees), outWidth, outHeight}
+ * @returns {Object} - Image data {data, width, height, channels}
+ */
+export function reprojectEquirect(imageData, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const fov = opts.fov ?? 90;
+    const outWidth = opts.outWidth ?? Math.max(1, Math.round(width * fov / 360));
+    const outHeight = opts.outHeight ?? Math.max(1, Math.floor(outWidth * 3 / 4));
+    const options = Object.assign(new wasm.ReprojectOptions(), {
+        yaw: opts.yaw ?? 0,
+        pitch: opts.pitch ?? 0,
+        roll: opts.roll ?? 0,
+        fov,
+        out_width: outWidth,
+        out_height: outHeight,
+    });
+    const f32 = isF32(data);
+    const result = f32
+        ? wasm.reproject_equirect_f32_wasm(data, width, height, channels, options)
+        : wasm.reproject_equirect_wasm(new Uint8Array(data.buffer), width, height, channels, options);
+    options.free();
+    return wrap(result, f32, outWidth, outHeight, channels);
+}
+
+// ============================================================================
+// Little Planet
+// ============================================================================
+
+/**
+ * Render a "little planet" (stereographic view of the ground).
+ * @param {Object} imageData - {data: Uint8ClampedArray|Float32Array, width, height, channels}
+ * @param {Object} opts - {fov: 240 (degrees), rotation: 0 (degrees, clockwise), outSize: height}
+ * @returns {Object} - Square image data {data, width, height, channels}
+ */
+export function littlePlanet(imageData, opts = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const size = opts.outSize ?? height;
+    const fov = opts.fov ?? 240;
+    const rotation = opts.rotation ?? 0;
+    const f32 = isF32(data);
+    const result = f32
+        ? wasm.little_planet_f32_wasm(data, width, height, channels, fov, rotation, size)
+        : wasm.little_planet_wasm(new Uint8Array(data.buffer), width, height, channels, fov, rotation, size);
+    return wrap(result, f32, size, size, channels);
+}
+
+export default {
+    initWasm,
+    CUBE_FACES,
+    equirectToCubemap,
+    cubemapToEquirect,
+    reprojectEquirect,
+    littlePlanet
+};
@@ -0,0 +1,205 @@
+"""Panorama projections with Rust backend.
+
+This module converts 360° equirectangular panoramas (2:1, longitude along
+x, latitude along y):
+- To and from the six faces of a cube map
+- To ordinary perspective views looking in any direction
+- To a "little planet" (stereographic projection of the ground)
+
+Angles are in degrees: positive yaw looks right, positive pitch looks up,
+roll turns the view about its own axis. All sampling is bilinear and the
+panorama wraps horizontally.
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels. Cube maps are stacked
+as (6, size, size, C) in the order +x (right), -x (left), +y (up),
+-y (down), +z (front), -z (back).
+
+Co-located with:
+- projection.rs (Rust implementation)
+- projection.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.projection import equirect_to_cubemap, reproject_equirect
+
+    faces = equirect_to_cubemap(pano)            # (6, W/4, W/4, C)
+    view = reproject_equirect(pano, yaw=90, pitch=20, fov=75)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+CUBE_FACES = ('+x', '-x', '+y', '-y', '+z', '-z')
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+def _validate_faces(faces: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate a stacked cube map."""
+    if faces.ndim != 4 or faces.shape[0] != 6 or faces.shape[1] != faces.shape[2] \
+            or faces.shape[3] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected faces (6, S, S, 1|3|4), got shape {faces.shape}")
+    if faces.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {faces.dtype}")
+
+
+# ============================================================================
+# Cube Maps
+# ============================================================================
+
+def equirect_to_cubemap(image: np.ndarray, face_size: int = None) -> np.ndarray:
+    """Split an equirectangular panorama into six cube faces (u8).
+
+    Args:
+        image: uint8 equirectangular array with 1, 3, or 4 channels (H, W, C)
+        face_size: Face edge length in pixels (default W / 4, keeps resolution)
+
+    Returns:
+        uint8 faces (6, face_size, face_size, C) in CUBE_FACES order
+    """
+    _validate_image(image, np.uint8, "equirect_to_cubemap")
+    return imagestag_rust.equirect_to_cubemap(image, face_size)
+
+
+def equirect_to_cubemap_f32(image: np.ndarray, face_size: int = None) -> np.ndarray:
+    """Split an equirectangular panorama into six cube faces (f32).
+
+    Args:
+        image: float32 equirectangular array with 1, 3, or 4 channels (H, W, C)
+        face_size: Face edge length in pixels (default W / 4)
+
+    Returns:
+        float32 faces (6, face_size, face_size, C) in CUBE_FACES order
+    """
+    _validate_image(image, np.float32, "equirect_to_cubemap_f32")
+    return imagestag_rust.equirect_to_cubemap_f32(image, face_size)
+
+
+def cubemap_to_equirect(faces: np.ndarray, out_w: int = None, out_h: int = None) -> np.ndarray:
+    """Merge six cube faces into an equirectangular panorama (u8).
+
+    Args:
+        faces: uint8 faces (6, S, S, C) in CUBE_FACES order
+        out_w: Panorama width (default 4 * S)
+        out_h: Panorama height (default 2 * S)
+
+    Returns:
+        uint8 equirectangular array (out_h, out_w, C)
+    """
+    _validate_faces(faces, np.uint8, "cubemap_to_equirect")
+    return imagestag_rust.cubemap_to_equirect(faces, out_w, out_h)
+
+
+def cubemap_to_equirect_f32(faces: np.ndarray, out_w: int = None, out_h: int = None) -> np.ndarray:
+    """Merge six cube faces into an equirectangular panorama (f32).
+
+    Args:
+        faces: float32 faces (6, S, S, C) in CUBE_FACES order
+        out_w: Panorama width (default 4 * S)
+        out_h: Panorama height (default 2 * S)
+
+    Returns:
+        float32 equirectangular array (out_h, out_w, C)
+    """
+    _validate_faces(faces, np.float32, "cubemap_to_equirect_f32")
+    return imagestag_rust.cubemap_to_equirect_f32(faces, out_w, out_h)
+
+
+# ============================================================================
+# Perspective Views
+# ============================================================================
+
+def reproject_equirect(image: np.ndarray, yaw: float = 0.0, pitch: float = 0.0, roll: float = 0.0,
+                       fov: float = 90.0, out_w: int = None, out_h: int = None) -> np.ndarray:
+    """Render a perspective view of an equirectangular panorama (u8).
+
+    Args:
+        image: uint8 equirectangular array with 1, 3, or 4 channels (H, W, C)
+        yaw: Degrees to the right of the panorama center
+        pitch: Degrees above the horizon
+        roll: Degrees of rotation about the viewing axis
+        fov: Horizontal field of view in degrees (1-179)
+        out_w: View width (default: the panorama's resolution, W * fov / 360)
+        out_h: View height (default 3/4 of out_w)
+
+    Returns:
+        uint8 perspective array (out_h, out_w, C)
+    """
+    _validate_image(image, np.uint8, "reproject_equirect")
+    return imagestag_rust.reproject_equirect(image, yaw, pitch, roll, fov, out_w, out_h)
+
+
+def reproject_equirect_f32(image: np.ndarray, yaw: float = 0.0, pitch: float = 0.0, roll: float = 0.0,
+                           fov: float = 90.0, out_w: int = None, out_h: int = None) -> np.ndarray:
+    """Render a perspective view of an equirectangular panorama (f32).
+
+    Args:
+        image: float32 equirectangular array with 1, 3, or 4 channels (H, W, C)
+        yaw, pitch, roll: View direction in degrees
+        fov: Horizontal field of view in degrees (1-179)
+        out_w: View width (default W * fov / 360)
+        out_h: View height (default 3/4 of out_w)
+
+    Returns:
+        float32 perspective array (out_h, out_w, C)
+    """
+    _validate_image(image, np.float32, "reproject_equirect_f32")
+    return imagestag_rust.reproject_equirect_f32(image, yaw, pitch, roll, fov, out_w, out_h)
+
+
+# ============================================================================
+# Little Planet
+# ============================================================================
+
+def little_planet(equirect_image: np.ndarray, fov: float = 240.0, rotation: float = 0.0,
+                  out_size: int = None) -> np.ndarray:
+    """Render a "little planet": the ground in the center, sky around it (u8).
+
+    Stereographic projection centered on the nadir. The panorama's front is
+    at the bottom of the planet for rotation 0.
+
+    Args:
+        equirect_image: uint8 equirectangular array with 1, 3, or 4 channels (H, W, C)
+        fov: Field of view across the output in degrees (1-359; larger = smaller planet)
+        rotation: Clockwise spin of the planet in degrees
+        out_size: Edge length of the square output (default H)
+
+    Returns:
+        uint8 array (out_size, out_size, C)
+    """
+    _validate_image(equirect_image, np.uint8, "little_planet")
+    return imagestag_rust.little_planet(equirect_image, fov, rotation, out_size)
+
+
+def little_planet_f32(equirect_image: np.ndarray, fov: float = 240.0, rotation: float = 0.0,
+                      out_size: int = None) -> np.ndarray:
+    """Render a "little planet": the ground in the center, sky around it (f32).
+
+    Args:
+        equirect_image: float32 equirectangular array with 1, 3, or 4 channels (H, W, C)
+        fov: Field of view across the output in degrees (1-359)
+        rotation: Clockwise spin of the planet in degrees
+        out_size: Edge length of the square output (default H)
+
+    Returns:
+        float32 array (out_size, out_size, C)
+    """
+    _validate_image(equirect_image, np.float32, "little_planet_f32")
+    return imagestag_rust.little_planet_f32(equirect_image, fov, rotation, out_size)
+
+
+__all__ = [
+    'CUBE_FACES',
+    'equirect_to_cubemap', 'equirect_to_cubemap_f32',
+    'cubemap_to_equirect', 'cubemap_to_equirect_f32',
+    'reproject_equirect', 'reproject_equirect_f32',
+    'little_planet', 'little_planet_f32',
+]
@@ -0,0 +1,406 @@
+//! Panorama projections: equirectangular, cube map, perspective views.
+//!
+//! 360° photos are stored as equirectangular images (longitude along x,
+//! latitude along y, 2:1 aspect). This module converts them to and from
+//! the six faces of a cube map, which most 3D engines and some editors
+//! expect, and renders ordinary perspective views looking in any direction.
+//! [`little_planet_f32`] renders the "tiny planet" effect: a stereographic
+//! projection centered on the nadir, with the sky wrapped around it.
+//!
+//! ## Conventions
+//!
+//! - World axes: x right, y up, z forward. The equirectangular center looks
+//!   along +z; longitude grows to the right, latitude upward.
+//! - Angles are in degrees. Positive yaw looks right, positive pitch looks
+//!   up, roll turns the view about its own axis.
+//! - Cube faces are stacked as (6, size, size, channels) in the order
+//!   +X (right), -X (left), +Y (up), -Y (down), +Z (front), -Z (back), each
+//!   seen from the inside with up pointing up (the up and down faces have
+//!   the front face at their bottom and top edge respectively).
+//!
+//! All samples are bilinear; the panorama wraps horizontally.
+//!
+//! ## Supported Formats
+//!
+//! All functions support 1, 3, or 4 channel images in both u8 and f32
+//! formats. RGBA is sampled premultiplied, so transparent pixels don't bleed.
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use ndarray::{Array3, Array4, ArrayView3, ArrayView4, Axis};
+use rayon::prelude::*;
+
+/// Cube face order of [`equirect_to_cubemap_f32`] and [`cubemap_to_equirect_f32`].
+pub const CUBE_FACES: [&str; 6] = ["+x", "-x", "+y", "-y", "+z", "-z"];
+
+// ============================================================================
+// Sampling
+// ============================================================================
+
+/// Bilinear sample at pixel position (fy, fx) into `out`.
+///
+/// `wrap_x` wraps columns (equirectangular seam); everything else clamps
+/// to the edge. RGBA is interpolated premultiplied.
+fn sample_bilinear(input: &ArrayView3<f32>, fy: f32, fx: f32, wrap_x: bool, out: &mut [f32]) {
+    let (height, width, channels) = input.dim();
+    let (y0, x0) = (fy.floor(), fx.floor());
+    let (ty, tx) = (fy - y0, fx - x0);
+    let col = |x: isize| {
+        if wrap_x {
+            x.rem_euclid(width as isize) as usize
+        } else {
+            x.clamp(0, width as isize - 1) as usize
+        }
+    };
+    let row = |y: isize| y.clamp(0, height as isize - 1) as usize;
+
+    let mut sum = [0.0f32; 4];
+    for (sy, wy) in [(row(y0 as isize), 1.0 - ty), (row(y0 as isize + 1), ty)] {
+        for (sx, wx) in [(col(x0 as isize), 1.0 - tx), (col(x0 as isize + 1), tx)] {
+            let w = wy * wx;
+            if channels == 4 {
+                let a = input[[sy, sx, 3]];
+                for c in 0..3 {
+                    sum[c] += w * a * input[[sy, sx, c]];
+                }
+                sum[3] += w * a;
+            } else {
+                for c in 0..channels {
+                    sum[c] += w * input[[sy, sx, c]];
+                }
+            }
+        }
+    }
+    if channels == 4 {
+        let inv = if sum[3] > 0.0 { 1.0 / sum[3] } else { 0.0 };
+        for c in 0..3 {
+            out[c] = sum[c] * inv;
+        }
+        out[3] = sum[3];
+    } else {
+        out.copy_from_slice(&sum[..channels]);
+    }
+}
+
+/// Sample an equirectangular image in direction `dir` (need not be normalized).
+fn sample_equirect(input: &ArrayView3<f32>, dir: [f32; 3], out: &mut [f32]) {
+    let (height, width, _) = input.dim();
+    let [x, y, z] = dir;
+    let lon = x.atan2(z);
+    let lat = y.atan2(x.hypot(z));
+    let fx = (lon + PI) / TAU * width as f32 - 0.5;
+    let fy = (FRAC_PI_2 - lat) / PI * height as f32 - 0.5;
+    sample_bilinear(input, fy, fx, true, out);
+}
+
+/// Direction through face coordinates (a, b) in [-1, 1], a to the right
+/// and b downward in the face image.
+#[inline]
+fn face_direction(face: usize, a: f32, b: f32) -> [f32; 3] {
+    match face {
+        0 => [1.0, -b, -a],
+        1 => [-1.0, -b, a],
+        2 => [a, 1.0, b],
+        3 => [a, -1.0, -b],
+        4 => [a, -b, 1.0],
+        _ => [-a, -b, -1.0],
+    }
+}
+
+/// Face and face coordinates (a, b) hit by direction `dir`.
+#[inline]
+fn direction_face([x, y, z]: [f32; 3]) -> (usize, f32, f32) {
+    let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+    if ax >= ay && ax >= az {
+        if x > 0.0 { (0, -z / ax, -y / ax) } else { (1, z / ax, -y / ax) }
+    } else if ay >= az {
+        if y > 0.0 { (2, x / ay, z / ay) } else { (3, x / ay, -z / ay) }
+    } else if z > 0.0 {
+        (4, x / az, -y / az)
+    } else {
+        (5, -x / az, -y / az)
+    }
+}
+
+/// Rotate a camera-space direction by roll (about z), pitch (about x) and
+/// yaw (about y), all in radians.
+#[inline]
+fn rotate_view([x, y, z]: [f32; 3], yaw: f32, pitch: f32, roll: f32) -> [f32; 3] {
+    let (sr, cr) = roll.sin_cos();
+    let (x, y) = (x * cr - y * sr, x * sr + y * cr);
+    let (sp, cp) = pitch.sin_cos();
+    let (y, z) = (y * cp + z * sp, z * cp - y * sp);
+    let (sy, cy) = yaw.sin_cos();
+    [x * cy + z * sy, y, z * cy - x * sy]
+}
+
+/// Fill a (height, width, channels) image from `dir(y, x)`, rows in parallel.
+fn render_f32<F>(input: &ArrayView3<f32>, height: usize, width: usize, dir: F) -> Array3<f32>
+where
+    F: Fn(usize, usize) -> [f32; 3] + Sync,
+{
+    let channels = input.dim().2;
+    let mut output = vec![0.0f32; height * width * channels];
+    if width > 0 {
+        output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+            for (x, px) in row.chunks_exact_mut(channels).enumerate() {
+                sample_equirect(input, dir(y, x), px);
+            }
+        });
+    }
+    Array3::from_shape_vec((height, width, channels), output).expect("output matches requested shape")
+}
+
+fn to_f32<D: ndarray::Dimension>(input: ndarray::ArrayView<u8, D>) -> ndarray::Array<f32, D> {
+    input.mapv(|v| v as f32 / 255.0)
+}
+
+fn to_u8<D: ndarray::Dimension>(input: ndarray::Array<f32, D>) -> ndarray::Array<u8, D> {
+    input.mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Cube Maps
+// ============================================================================
+
+/// Split an equirectangular panorama into six cube faces - f32 version.
+///
+/// # Arguments
+/// * `input` - Equirectangular image with 1, 3, or 4 channels (height, width, channels)
+/// * `face_size` - Edge length of each face in pixels (width / 4 keeps the resolution)
+///
+/// # Returns
+/// Faces (6, face_size, face_size, channels) in [`CUBE_FACES`] order
+pub fn equirect_to_cubemap_f32(input: ArrayView3<f32>, face_size: usize) -> Array4<f32> {
+    let channels = input.dim().2;
+    let mut faces = Array4::zeros((6, face_size, face_size, channels));
+    if input.is_empty() {
+        return faces;
+    }
+    let scale = 2.0 / face_size as f32;
+    for (face, mut out) in faces.axis_iter_mut(Axis(0)).enumerate() {
+        let rendered = render_f32(&input, face_size, face_size, |y, x| {
+            face_direction(face, (x as f32 + 0.5) * scale - 1.0, (y as f32 + 0.5) * scale - 1.0)
+        });
+        out.assign(&rendered);
+    }
+    faces
+}
+
+/// Split an equirectangular panorama into six cube faces - u8 version.
+pub fn equirect_to_cubemap_u8(input: ArrayView3<u8>, face_size: usize) -> Array4<u8> {
+    to_u8(equirect_to_cubemap_f32(to_f32(input).view(), face_size))
+}
+
+/// Merge six cube faces into an equirectangular panorama - f32 version.
+///
+/// # Arguments
+/// * `faces` - (6, size, size, channels) in [`CUBE_FACES`] order
+/// * `out_width`, `out_height` - Panorama size (usually 4 × size by 2 × size)
+///
+/// # Returns
+/// Equirectangular image (out_height, out_width, channels)
+pub fn cubemap_to_equirect_f32(faces: ArrayView4<f32>, out_width: usize, out_height: usize) -> Array3<f32> {
+    let (count, size, size_x, channels) = faces.dim();
+    assert!(count == 6 && size == size_x, "faces must be (6, size, size, channels)");
+    let mut output = vec![0.0f32; out_height * out_width * channels];
+    if out_width > 0 && size > 0 {
+        output.par_chunks_mut(out_width * channels).enumerate().for_each(|(y, row)| {
+            let lat = FRAC_PI_2 - (y as f32 + 0.5) / out_height as f32 * PI;
+            for (x, px) in row.chunks_exact_mut(channels).enumerate() {
+                let lon = (x as f32 + 0.5) / out_width as f32 * TAU - PI;
+                let dir = [lat.cos() * lon.sin(), lat.sin(), lat.cos() * lon.cos()];
+                let (face, a, b) = direction_face(dir);
+                let half = size as f32 * 0.5;
+                let face_view = faces.index_axis(Axis(0), face);
+                sample_bilinear(&face_view, (b + 1.0) * half - 0.5, (a + 1.0) * half - 0.5, false, px);
+            }
+        });
+    }
+    Array3::from_shape_vec((out_height, out_width, channels), output).expect("output matches requested shape")
+}
+
+/// Merge six cube faces into an equirectangular panorama - u8 version.
+pub fn cubemap_to_equirect_u8(faces: ArrayView4<u8>, out_width: usize, out_height: usize) -> Array3<u8> {
+    to_u8(cubemap_to_equirect_f32(to_f32(faces).view(), out_width, out_height))
+}
+
+// ============================================================================
+// Perspective Views
+// ============================================================================
+
+/// Viewing direction and field of view for [`reproject_equirect_f32`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewParams {
+    /// Degrees to the right of the panorama center
+    pub yaw: f32,
+    /// Degrees above the horizon
+    pub pitch: f32,
+    /// Degrees of rotation about the viewing axis
+    pub roll: f32,
+    /// Horizontal field of view in degrees (clamped to 1-179)
+    pub fov: f32,
+}
+
+impl Default for ViewParams {
+    fn default() -> Self {
+        Self { yaw: 0.0, pitch: 0.0, roll: 0.0, fov: 90.0 }
+    }
+}
+
+/// Render a rectilinear (pinhole) view of an equirectangular panorama - f32 version.
+///
+/// # Arguments
+/// * `input` - Equirectangular image with 1, 3, or 4 channels (height, width, channels)
+/// * `view` - Direction and horizontal field of view
+/// * `out_width`, `out_height` - Size of the rendered view
+///
+/// # Returns
+/// Perspective image (out_height, out_width, channels)
+pub fn reproject_equirect_f32(input: ArrayView3<f32>, view: &ViewParams, out_width: usize, out_height: usize) -> Array3<f32> {
+    if input.is_empty() {
+        return Array3::zeros((out_height, out_width, input.dim().2));
+    }
+    let focal = out_width as f32 * 0.5 / (view.fov.clamp(1.0, 179.0).to_radians() * 0.5).tan();
+    let (yaw, pitch, roll) = (view.yaw.to_radians(), view.pitch.to_radians(), view.roll.to_radians());
+    let (cx, cy) = (out_width as f32 * 0.5, out_height as f32 * 0.5);
+    render_f32(&input, out_height, out_width, |y, x| {
+        rotate_view([x as f32 + 0.5 - cx, cy - (y as f32 + 0.5), focal], yaw, pitch, roll)
+    })
+}
+
+/// Render a rectilinear view of an equirectangular panorama - u8 version.
+pub fn reproject_equirect_u8(input: ArrayView3<u8>, view: &ViewParams, out_width: usize, out_height: usize) -> Array3<u8> {
+    to_u8(reproject_equirect_f32(to_f32(input).view(), view, out_width, out_height))
+}
+
+// ============================================================================
+// Little Planet
+// ============================================================================
+
+/// Largest little planet field of view; 360° would put the zenith at infinity.
+const MAX_PLANET_FOV: f32 = 359.0;
+
+/// Render a "little planet" (stereographic view of the nadir) - f32 version.
+///
+/// The ground directly below the camera lands in the center and the horizon
+/// becomes a circle, with the sky around it. The panorama's front (yaw 0)
+/// is at the bottom of the planet for `rotation` 0.
+///
+/// # Arguments
+/// * `input` - Equirectangular image with 1, 3, or 4 channels (height, width, channels)
+/// * `fov` - Field of view across the output width in degrees (e.g. 240; 1-359)
+/// * `rotation` - Spin of the planet in degrees (clockwise)
+/// * `out_size` - Edge length of the square output
+///
+/// # Returns
+/// Square image (out_size, out_size, channels)
+pub fn little_planet_f32(input: ArrayView3<f32>, fov: f32, rotation: f32, out_size: usize) -> Array3<f32> {
+    if input.is_empty() {
+        return Array3::zeros((out_size, out_size, input.dim().2));
+    }
+    // Stereographic: angle from the nadir θ = 2 atan(k r) at distance r from
+    // the center; the edge midpoints (r = 1) see fov / 2.
+    let k = (fov.clamp(1.0, MAX_PLANET_FOV).to_radians() / 4.0).tan();
+    let rotation = rotation.to_radians();
+    let half = out_size as f32 * 0.5;
+    render_f32(&input, out_size, out_size, |y, x| {
+        let (px, py) = ((x as f32 + 0.5 - half) / half, (y as f32 + 0.5 - half) / half);
+        let theta = 2.0 * (px.hypot(py) * k).atan();
+        let phi = py.atan2(px) - rotation;
+        let (st, ct) = theta.sin_cos();
+        [st * phi.cos(), -ct, st * phi.sin()]
+    })
+}
+
+/// Render a "little planet" (stereographic view of the nadir) - u8 version.
+pub fn little_planet_u8(input: ArrayView3<u8>, fov: f32, rotation: f32, out_size: usize) -> Array3<u8> {
+    to_u8(little_planet_f32(to_f32(input).view(), fov, rotation, out_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smooth panorama: color encodes the direction, so any sample can be checked.
+    fn direction_panorama(height: usize, width: usize) -> Array3<f32> {
+        Array3::from_shape_fn((height, width, 3), |(y, x, c)| {
+            let lat = FRAC_PI_2 - (y as f32 + 0.5) / height as f32 * PI;
+            let lon = (x as f32 + 0.5) / width as f32 * TAU - PI;
+            let d = [lat.cos() * lon.sin(), lat.sin(), lat.cos() * lon.cos()];
+            0.5 + 0.5 * d[c]
+        })
+    }
+
+    #[test]
+    fn test_face_mapping_roundtrip() {
+        for face in 0..6 {
+            for (a, b) in [(0.0, 0.0), (0.5, -0.25), (-0.9, 0.8)] {
+                let (f, ra, rb) = direction_face(face_direction(face, a, b));
+                assert_eq!(f, face);
+                assert!((ra - a).abs() < 1e-6 && (rb - b).abs() < 1e-6, "face {}", face);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cubemap_roundtrip_and_views() {
+        let pano = direction_panorama(64, 128);
+        let faces = equirect_to_cubemap_f32(pano.view(), 32);
+        // Face centers look along their axis: +x is right, +y up, -z back
+        let center = |f: usize| faces.slice(ndarray::s![f, 15..17, 15..17, ..]).mean_axis(Axis(0)).unwrap().mean_axis(Axis(0)).unwrap();
+        assert!((center(0)[0] - 1.0).abs() < 0.01);
+        assert!((center(2)[1] - 1.0).abs() < 0.01);
+        assert!(center(5)[2] < 0.01);
+
+        let back = cubemap_to_equirect_f32(faces.view(), 128, 64);
+        let err = (&back - &pano).mapv(f32::abs);
+        assert!(err.iter().cloned().fold(0.0, f32::max) < 0.03);
+
+        // Looking 90° right and 30° up sees the direction (cos 30°, sin 30°, 0)
+        let view = ViewParams { yaw: 90.0, pitch: 30.0, ..Default::default() };
+        let persp = reproject_equirect_f32(pano.view(), &view, 16, 16);
+        let c = persp.slice(ndarray::s![7..9, 7..9, ..]).mean_axis(Axis(0)).unwrap().mean_axis(Axis(0)).unwrap();
+        let expected = [0.5 + 0.5 * 30f32.to_radians().cos(), 0.75, 0.5];
+        for i in 0..3 {
+            assert!((c[i] - expected[i]).abs() < 0.02, "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn test_little_planet_puts_ground_in_center() {
+        let pano = direction_panorama(64, 128);
+        let planet = little_planet_f32(pano.view(), 240.0, 0.0, 33);
+        // Center looks straight down, corners (beyond 120° from the nadir) into the sky
+        assert!(planet[[16, 16, 1]] < 0.01);
+        assert!(planet[[0, 0, 1]] > 0.7);
+        // Front (+z) at the bottom edge, back at the top
+        assert!(planet[[32, 16, 2]] > planet[[0, 16, 2]] + 0.5);
+    }
+
+    #[test]
+    fn test_empty_panorama_gives_blank_output() {
+        for (height, width) in [(0, 0), (0, 8), (4, 0)] {
+            let empty = Array3::<f32>::zeros((height, width, 4));
+            let faces = equirect_to_cubemap_f32(empty.view(), 5);
+            assert_eq!(faces.dim(), (6, 5, 5, 4));
+            assert!(faces.iter().all(|&v| v == 0.0));
+            let persp = reproject_equirect_f32(empty.view(), &ViewParams::default(), 7, 3);
+            assert_eq!(persp.dim(), (3, 7, 4));
+            assert!(persp.iter().all(|&v| v == 0.0));
+        }
+        let empty = Array3::<u8>::zeros((0, 0, 3));
+        assert_eq!(equirect_to_cubemap_u8(empty.view(), 2).dim(), (6, 2, 2, 3));
+        assert_eq!(reproject_equirect_u8(empty.view(), &ViewParams::default(), 4, 2).dim(), (2, 4, 3));
+    }
+
+    #[test]
+    fn test_little_planet_of_empty_panorama() {
+        let empty = Array3::<f32>::zeros((0, 0, 4));
+        let planet = little_planet_f32(empty.view(), 240.0, 0.0, 9);
+        assert_eq!(planet.dim(), (9, 9, 4));
+        assert!(planet.iter().all(|&v| v == 0.0));
+        let empty = Array3::<u8>::zeros((0, 0, 1));
+        assert_eq!(little_planet_u8(empty.view(), 240.0, 0.0, 3).dim(), (3, 3, 1));
+    }
+}
@@ -0,0 +1,60 @@
+/**
+ * Bayer RAW ingestion - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - raw.rs (Rust implementation)
+ * - raw.py (Python wrapper)
+ *
+ * Provides: rawToLinear, demosaic
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Levels and White Balance
+// ============================================================================
+
+/**
+ * Normalize raw sensor counts to linear light and white balance them.
+ * @param {Object} mosaic - {data: Uint16Array, width, height}
+ * @param {Object} opts - {pattern: 'rggb', blackLevel: 0, whiteLevel: 65535, wbGains: [r, g, b]}
+ * @returns {Object} - Linear mosaic {data: Float32Array, width, height}
+ */
+export function rawToLinear(mosaic, opts = {}) {
+    const { data, width, height } = mosaic;
+    const [r, g, b] = opts.wbGains ?? [1, 1, 1];
+    const options = Object.assign(new wasm.RawOptions(), {
+        black_level: opts.blackLevel ?? 0,
+        white_level: opts.whiteLevel ?? 65535,
+        wb_r: r,
+        wb_g: g,
+        wb_b: b,
+    });
+    const result = wasm.raw_to_linear_wasm(data, width, height, opts.pattern ?? 'rggb', options);
+    options.free();
+    return { data: new Float32Array(result.buffer), width, height };
+}
+
+// ============================================================================
+// Demosaicing
+// ============================================================================
+
+/**
+ * Interpolate the missing colors of a linear Bayer mosaic.
+ * @param {Object} mosaic - {data: Float32Array, width, height}
+ * @param {Object} opts - {pattern: 'rggb', algorithm: 'bilinear'|'malvar'}
+ * @returns {Object} - Linear RGB image data {data: Float32Array, width, height, channels: 3}
+ */
+export function demosaic(mosaic, opts = {}) {
+    const { data, width, height } = mosaic;
+    const result = wasm.demosaic_wasm(data, width, height, opts.pattern ?? 'rggb', opts.algorithm ?? 'malvar');
+    return { data: new Float32Array(result.buffer), width, height, channels: 3 };
+}
+
+export default {
+    initWasm,
+    rawToLinear,
+    demosaic
+};
@@ -0,0 +1,85 @@
+"""Bayer RAW ingestion with Rust backend.
+
+This module turns raw sensor mosaics into linear float32 RGB:
+- Black/white level normalization and white balance on the mosaic
+- Demosaicing: bilinear or Malvar-He-Cutler (gradient-corrected) interpolation
+
+Reading camera RAW containers is out of scope; pass the sensor data
+(e.g. from rawpy's ``raw_image_visible``) with its levels and CFA pattern.
+
+## Supported Formats
+
+Mosaics are 2D arrays (H, W): uint16 raw counts for ``raw_to_linear``,
+float32 linear values for ``demosaic``. ``demosaic`` returns linear camera
+RGB float32 (H, W, 3), not clipped at 1.0.
+
+Co-located with:
+- raw.rs (Rust implementation)
+- raw.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.raw import raw_to_linear, demosaic
+
+    linear = raw_to_linear(sensor, "rggb", black_level=512, white_level=16383,
+                           wb_gains=(2.1, 1.0, 1.6))
+    rgb = demosaic(linear, "rggb")
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_mosaic(bayer: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate mosaic shape and dtype."""
+    if bayer.ndim != 2:
+        raise ValueError(f"{name}: expected mosaic (H, W), got shape {bayer.shape}")
+    if bayer.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {bayer.dtype}")
+
+
+# ============================================================================
+# Levels and White Balance
+# ============================================================================
+
+def raw_to_linear(bayer: np.ndarray, pattern: str = "rggb", black_level: float = 0.0,
+                  white_level: float = 65535.0, wb_gains: tuple = (1.0, 1.0, 1.0)) -> np.ndarray:
+    """Normalize raw sensor counts to linear light and white balance them.
+
+    Args:
+        bayer: uint16 mosaic (H, W)
+        pattern: CFA layout from the top-left photosite: "rggb", "bggr", "grbg" or "gbrg"
+        black_level: Raw value of black
+        white_level: Raw value of sensor saturation
+        wb_gains: White balance gains (r, g, b)
+
+    Returns:
+        float32 mosaic (H, W), 0.0 = black, 1.0 = white level (times gain)
+    """
+    _validate_mosaic(bayer, np.uint16, "raw_to_linear")
+    gains = tuple(float(v) for v in wb_gains)
+    return imagestag_rust.raw_to_linear(bayer, pattern, black_level, white_level, gains)
+
+
+# ============================================================================
+# Demosaicing
+# ============================================================================
+
+def demosaic(bayer: np.ndarray, pattern: str = "rggb", algorithm: str = "malvar") -> np.ndarray:
+    """Interpolate the missing colors of a Bayer mosaic.
+
+    Args:
+        bayer: float32 linear mosaic (H, W), e.g. from raw_to_linear
+        pattern: CFA layout: "rggb", "bggr", "grbg" or "gbrg"
+        algorithm: "bilinear" (fast, soft) or "malvar" (sharper)
+
+    Returns:
+        float32 linear RGB (H, W, 3)
+    """
+    _validate_mosaic(bayer, np.float32, "demosaic")
+    return imagestag_rust.demosaic(bayer, pattern, algorithm)
+
+
+__all__ = [
+    'raw_to_linear',
+    'demosaic',
+]
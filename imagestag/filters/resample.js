@@ -0,0 +1,99 @@
+/**
+ * Thumbnail generation - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - resample.rs (Rust implementation)
+ * - resample.py (Python wrapper)
+ *
+ * Downscales with a Lanczos-3 filter in linear light, avoiding the dark,
+ * aliased thumbnails of a naive canvas resize.
+ */
+
+import { initSync } from '../wasm/imagestag_rust.js';
+import * as wasm from '../wasm/imagestag_rust.js';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+let wasmInitialized = false;
+
+/**
+ * Initialize WASM module (required before using filter functions).
+ */
+export async function initWasm() {
+    if (wasmInitialized) return;
+    const __filename = fileURLToPath(import.meta.url);
+    const __dirname = path.dirname(__filename);
+    const wasmPath = path.join(__dirname, '..', 'wasm', 'imagestag_rust_bg.wasm');
+    const wasmBuffer = fs.readFileSync(wasmPath);
+    initSync(wasmBuffer);
+    wasmInitialized = true;
+}
+
+/**
+ * Compute the thumbnail size fitting into maxWidth x maxHeight (never enlarged).
+ * @returns {{width: number, height: number}}
+ */
+export function thumbnailSize(width, height, maxWidth, maxHeight) {
+    const [w, h] = wasm.thumbnail_size_wasm(width, height, maxWidth, maxHeight);
+    return { width: w, height: h };
+}
+
+/**
+ * Create a thumbnail (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number} maxWidth - Maximum thumbnail width
+ * @param {number} maxHeight - Maximum thumbnail height
+ * @param {boolean} srgbAware - Average in linear light (default true)
+ * @returns {Object} - Thumbnail image data {data, width, height, channels}
+ */
+export function thumbnail(imageData, maxWidth, maxHeight, srgbAware = true) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const size = thumbnailSize(width, height, maxWidth, maxHeight);
+
+    const result = wasm.thumbnail_wasm(
+        new Uint8Array(data.buffer),
+        width,
+        height,
+        channels,
+        maxWidth,
+        maxHeight,
+        srgbAware
+    );
+
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: size.width,
+        height: size.height,
+        channels
+    };
+}
+
+/**
+ * Create a thumbnail (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @returns {Object} - Thumbnail image data {data, width, height, channels}
+ */
+export function thumbnailF32(imageData, maxWidth, maxHeight, srgbAware = true) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const size = thumbnailSize(width, height, maxWidth, maxHeight);
+
+    const result = wasm.thumbnail_f32_wasm(
+        data,
+        width,
+        height,
+        channels,
+        maxWidth,
+        maxHeight,
+        srgbAware
+    );
+
+    return {
+        data: new Float32Array(result.buffer),
+        width: size.width,
+        height: size.height,
+        channels
+    };
+}
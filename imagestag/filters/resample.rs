@@ -0,0 +1,296 @@
+//! High-quality downscaling, thumbnail generation and resizing.
+//!
+//! Resamples with a separable Lanczos-3 filter whose support grows with the
+//! scale factor, so every source pixel contributes (area-correct, no
+//! aliasing). Averaging happens in linear light when requested: averaging
+//! sRGB-encoded values darkens fine detail and high-contrast edges, which is
+//! why naive u8 resizes look dark and muddy.
+//!
+//! ## Supported Formats
+//!
+//! All functions support 1, 3, or 4 channel images in both u8 and f32 formats.
+//! RGBA images are filtered with premultiplied alpha so transparent pixels
+//! do not bleed their color into the result.
+
+use std::sync::OnceLock;
+
+use ndarray::{Array3, ArrayView3};
+
+use super::fmath;
+
+/// Lanczos lobes.
+const LANCZOS_A: f32 = 3.0;
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        1.0
+    } else {
+        let px = std::f32::consts::PI * x;
+        fmath::sin(px) / px
+    }
+}
+
+fn lanczos(x: f32) -> f32 {
+    if x.abs() >= LANCZOS_A {
+        0.0
+    } else {
+        sinc(x) * sinc(x / LANCZOS_A)
+    }
+}
+
+/// Convert an sRGB-encoded value (0-1) to linear light.
+pub fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        fmath::powf((v + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Convert a linear light value (0-1) to sRGB encoding.
+pub fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * fmath::powf(v, 1.0 / 2.4) - 0.055
+    }
+}
+
+/// u8 sRGB <-> linear light tables shared by the linear-light filters.
+struct SrgbTables {
+    /// Linear value of every sRGB code
+    decode: [f32; 256],
+    /// Linear value halfway between neighboring codes, for exact rounding
+    midpoints: [f32; 255],
+}
+
+fn srgb_tables() -> &'static SrgbTables {
+    static TABLES: OnceLock<SrgbTables> = OnceLock::new();
+    TABLES.get_or_init(|| SrgbTables {
+        decode: std::array::from_fn(|i| srgb_to_linear(i as f32 / 255.0)),
+        midpoints: std::array::from_fn(|i| srgb_to_linear((i as f32 + 0.5) / 255.0)),
+    })
+}
+
+/// Convert an sRGB-encoded u8 to linear light (0-1) via lookup table.
+#[inline]
+pub fn srgb_u8_to_linear(v: u8) -> f32 {
+    srgb_tables().decode[v as usize]
+}
+
+/// Convert linear light (0-1) to the nearest sRGB-encoded u8.
+///
+/// Exact inverse of [`srgb_u8_to_linear`]: a binary search over the table
+/// instead of a `powf` per value.
+#[inline]
+pub fn linear_to_srgb_u8(v: f32) -> u8 {
+    srgb_tables().midpoints.partition_point(|&m| m < v) as u8
+}
+
+/// Compute the thumbnail size that fits into `max_width` x `max_height`
+/// while keeping the aspect ratio. Images are never enlarged.
+///
+/// # Returns
+/// (width, height), each at least 1
+pub fn thumbnail_size(width: usize, height: usize, max_width: usize, max_height: usize) -> (usize, usize) {
+    if width == 0 || height == 0 {
+        return (width, height);
+    }
+    let scale = (max_width as f64 / width as f64).min(max_height as f64 / height as f64).min(1.0);
+    let out_w = ((width as f64 * scale).round() as usize).clamp(1, width);
+    let out_h = ((height as f64 * scale).round() as usize).clamp(1, height);
+    (out_w, out_h)
+}
+
+/// Filter taps of one output sample: first source index and normalized weights.
+struct Taps {
+    start: usize,
+    weights: Vec<f32>,
+}
+
+fn compute_taps(src: usize, dst: usize) -> Vec<Taps> {
+    let scale = src as f32 / dst as f32;
+    let filter_scale = scale.max(1.0);
+    let support = LANCZOS_A * filter_scale;
+
+    (0..dst)
+        .map(|i| {
+            let center = (i as f32 + 0.5) * scale;
+            let start = (center - support).floor().max(0.0) as usize;
+            let end = ((center + support).ceil() as usize).min(src);
+            let mut weights: Vec<f32> = (start..end)
+                .map(|j| lanczos((j as f32 + 0.5 - center) / filter_scale))
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            if sum.abs() > 1e-8 {
+                weights.iter_mut().for_each(|w| *w /= sum);
+            }
+            Taps { start, weights }
+        })
+        .collect()
+}
+
+/// Resample a working buffer (f32, any channels) to a new size.
+fn resample(input: &Array3<f32>, out_w: usize, out_h: usize) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+
+    // Horizontal pass
+    let taps_x = compute_taps(width, out_w);
+    let mut horizontal = Array3::<f32>::zeros((height, out_w, channels));
+    for y in 0..height {
+        for (x, taps) in taps_x.iter().enumerate() {
+            for c in 0..channels {
+                let mut acc = 0.0;
+                for (k, w) in taps.weights.iter().enumerate() {
+                    acc += input[[y, taps.start + k, c]] * w;
+                }
+                horizontal[[y, x, c]] = acc;
+            }
+        }
+    }
+
+    // Vertical pass
+    let taps_y = compute_taps(height, out_h);
+    let mut output = Array3::<f32>::zeros((out_h, out_w, channels));
+    for (y, taps) in taps_y.iter().enumerate() {
+        for x in 0..out_w {
+            for c in 0..channels {
+                let mut acc = 0.0;
+                for (k, w) in taps.weights.iter().enumerate() {
+                    acc += horizontal[[taps.start + k, x, c]] * w;
+                }
+                output[[y, x, c]] = acc;
+            }
+        }
+    }
+    output
+}
+
+/// Shared implementation on normalized f32 values.
+fn thumbnail_impl(input: Array3<f32>, max_width: usize, max_height: usize, srgb_aware: bool) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let (out_w, out_h) = thumbnail_size(width, height, max_width, max_height);
+    if (out_w, out_h) == (width, height) {
+        return input;
+    }
+    resample_image(input, out_w, out_h, srgb_aware)
+}
+
+/// Resample normalized f32 values with premultiplied alpha.
+fn resample_image(input: Array3<f32>, out_w: usize, out_h: usize, srgb_aware: bool) -> Array3<f32> {
+    let channels = input.dim().2;
+    let has_alpha = channels == 4;
+    let color_channels = if has_alpha { 3 } else { channels };
+
+    // To linear light, premultiplied
+    let mut work = input;
+    for mut pixel in work.lanes_mut(ndarray::Axis(2)) {
+        let alpha = if has_alpha { pixel[3] } else { 1.0 };
+        for c in 0..color_channels {
+            let v = if srgb_aware { srgb_to_linear(pixel[c]) } else { pixel[c] };
+            pixel[c] = v * alpha;
+        }
+    }
+
+    let mut result = resample(&work, out_w, out_h);
+
+    // Back to straight alpha and sRGB encoding
+    for mut pixel in result.lanes_mut(ndarray::Axis(2)) {
+        let alpha = if has_alpha { pixel[3].clamp(0.0, 1.0) } else { 1.0 };
+        if has_alpha {
+            pixel[3] = alpha;
+        }
+        for c in 0..color_channels {
+            let v = if alpha > 0.0 { (pixel[c] / alpha).clamp(0.0, 1.0) } else { 0.0 };
+            pixel[c] = if srgb_aware { linear_to_srgb(v) } else { v };
+        }
+    }
+    result
+}
+
+/// Create a thumbnail of a u8 image.
+///
+/// # Arguments
+/// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+/// * `max_width` - Maximum thumbnail width
+/// * `max_height` - Maximum thumbnail height
+/// * `srgb_aware` - Average in linear light (recommended for photos)
+///
+/// # Returns
+/// Downscaled image fitting into max_width x max_height (aspect ratio kept,
+/// never enlarged)
+pub fn thumbnail_u8(image: ArrayView3<u8>, max_width: usize, max_height: usize, srgb_aware: bool) -> Array3<u8> {
+    let (height, width, _) = image.dim();
+    if thumbnail_size(width, height, max_width, max_height) == (width, height) {
+        return image.to_owned();
+    }
+    let input = image.mapv(|v| v as f32 / 255.0);
+    thumbnail_impl(input, max_width, max_height, srgb_aware).mapv(|v| (v * 255.0 + 0.5) as u8)
+}
+
+/// Create a thumbnail of an f32 image (0.0-1.0).
+///
+/// Same as thumbnail_u8 but for f32 images.
+pub fn thumbnail_f32(image: ArrayView3<f32>, max_width: usize, max_height: usize, srgb_aware: bool) -> Array3<f32> {
+    thumbnail_impl(image.to_owned(), max_width, max_height, srgb_aware)
+}
+
+/// Resize an f32 image (0.0-1.0) to an exact size.
+///
+/// Unlike [`thumbnail_f32`] this enlarges as well (Lanczos-3 interpolation)
+/// and does not keep the aspect ratio.
+///
+/// # Arguments
+/// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+/// * `width` - Output width (at least 1)
+/// * `height` - Output height (at least 1)
+/// * `srgb_linearize` - Resample in linear light
+pub fn resize_f32(image: ArrayView3<f32>, width: usize, height: usize, srgb_linearize: bool) -> Array3<f32> {
+    let (width, height) = (width.max(1), height.max(1));
+    if image.dim().0 == height && image.dim().1 == width {
+        return image.to_owned();
+    }
+    resample_image(image.to_owned(), width, height, srgb_linearize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_size() {
+        assert_eq!(thumbnail_size(400, 200, 100, 100), (100, 50));
+        assert_eq!(thumbnail_size(200, 400, 100, 100), (50, 100));
+        assert_eq!(thumbnail_size(50, 20, 100, 100), (50, 20));
+        assert_eq!(thumbnail_size(1000, 1, 10, 10), (10, 1));
+    }
+
+    #[test]
+    fn test_checkerboard_keeps_brightness_in_linear_light() {
+        // Black/white checkerboard averages to 50% linear light = sRGB ~188
+        let image = Array3::from_shape_fn((64, 64, 3), |(y, x, _)| if (x + y) % 2 == 0 { 255u8 } else { 0 });
+        let linear = thumbnail_u8(image.view(), 8, 8, true);
+        let naive = thumbnail_u8(image.view(), 8, 8, false);
+        assert_eq!(linear.dim(), (8, 8, 3));
+        assert!(linear.iter().all(|&v| (v as i32 - 188).abs() <= 2), "{:?}", linear[[4, 4, 0]]);
+        assert!(naive.iter().all(|&v| (v as i32 - 128).abs() <= 2));
+    }
+
+    #[test]
+    fn test_transparent_pixels_do_not_bleed() {
+        // Red opaque half, green fully transparent half
+        let image = Array3::from_shape_fn((16, 16, 4), |(_, x, c)| match (x < 8, c) {
+            (true, 0) | (true, 3) => 1.0,
+            (false, 1) => 1.0,
+            _ => 0.0,
+        });
+        let result = thumbnail_f32(image.view(), 4, 4, true);
+        for y in 0..4 {
+            for x in 0..4 {
+                if result[[y, x, 3]] > 0.01 {
+                    assert!(result[[y, x, 1]] < 0.01, "green bled into ({}, {})", x, y);
+                }
+            }
+        }
+    }
+}
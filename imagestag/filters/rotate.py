@@ -9,6 +9,9 @@ All functions support 1, 3, or 4 channel images in both u8 and f32:
 - RGB: (H, W, 3)
 - RGBA: (H, W, 4)
 
+A stack of animation frames (N, H, W, C) is accepted as well; frames are
+processed in parallel and the result is stacked the same way.
+
 ## Rotation Direction
 
 All rotations are clockwise (CW):
@@ -46,7 +49,7 @@ def rotate_90_cw(image: np.ndarray) -> np.ndarray:
     Returns:
         Rotated uint8 array (W, H, C) - note dimensions are swapped
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.uint8:
         raise ValueError(f"Expected uint8, got {image.dtype}")
@@ -62,7 +65,7 @@ def rotate_180(image: np.ndarray) -> np.ndarray:
     Returns:
         Rotated uint8 array (H, W, C) - same dimensions
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.uint8:
         raise ValueError(f"Expected uint8, got {image.dtype}")
@@ -78,7 +81,7 @@ def rotate_270_cw(image: np.ndarray) -> np.ndarray:
     Returns:
         Rotated uint8 array (W, H, C) - note dimensions are swapped
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.uint8:
         raise ValueError(f"Expected uint8, got {image.dtype}")
@@ -98,7 +101,7 @@ def rotate(image: np.ndarray, degrees: int) -> np.ndarray:
     Raises:
         ValueError: If degrees is not 90, 180, or 270.
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.uint8:
         raise ValueError(f"Expected uint8, got {image.dtype}")
@@ -116,7 +119,7 @@ def flip_horizontal(image: np.ndarray) -> np.ndarray:
     Returns:
         Flipped uint8 array (H, W, C) - same dimensions
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.uint8:
         raise ValueError(f"Expected uint8, got {image.dtype}")
@@ -132,7 +135,7 @@ def flip_vertical(image: np.ndarray) -> np.ndarray:
     Returns:
         Flipped uint8 array (H, W, C) - same dimensions
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.uint8:
         raise ValueError(f"Expected uint8, got {image.dtype}")
@@ -152,7 +155,7 @@ def rotate_90_cw_f32(image: np.ndarray) -> np.ndarray:
     Returns:
         Rotated float32 array (W, H, C)
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.float32:
         raise ValueError(f"Expected float32, got {image.dtype}")
@@ -168,7 +171,7 @@ def rotate_180_f32(image: np.ndarray) -> np.ndarray:
     Returns:
         Rotated float32 array (H, W, C)
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.float32:
         raise ValueError(f"Expected float32, got {image.dtype}")
@@ -184,7 +187,7 @@ def rotate_270_cw_f32(image: np.ndarray) -> np.ndarray:
     Returns:
         Rotated float32 array (W, H, C)
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.float32:
         raise ValueError(f"Expected float32, got {image.dtype}")
@@ -201,7 +204,7 @@ def rotate_f32(image: np.ndarray, degrees: int) -> np.ndarray:
     Returns:
         Rotated float32 array. For 90/270, dimensions are swapped.
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.float32:
         raise ValueError(f"Expected float32, got {image.dtype}")
@@ -219,7 +222,7 @@ def flip_horizontal_f32(image: np.ndarray) -> np.ndarray:
     Returns:
         Flipped float32 array (H, W, C)
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.float32:
         raise ValueError(f"Expected float32, got {image.dtype}")
@@ -235,8 +238,42 @@ def flip_vertical_f32(image: np.ndarray) -> np.ndarray:
     Returns:
         Flipped float32 array (H, W, C)
     """
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+    if image.ndim not in (3, 4) or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected (H, W, C) with C in [1, 3, 4], got {image.shape}")
     if image.dtype != np.float32:
         raise ValueError(f"Expected float32, got {image.dtype}")
     return imagestag_rust.flip_vertical_f32(image)
+
+
+# ============================================================================
+# Strided Buffers
+# ============================================================================
+
+def transform_strided(data, width: int, height: int, channels: int, stride: int,
+                      transform: str = "rotate_90") -> np.ndarray:
+    """Rotate or flip a buffer with padded rows without compacting it first.
+
+    Video capture frame buffers often have a row pitch larger than
+    ``width * channels``; this reads them directly.
+
+    Args:
+        data: bytes, bytearray, memoryview or uint8/uint16 array
+        width: Image width in pixels
+        height: Image height in pixels
+        channels: Samples per pixel
+        stride: Samples between row starts (the pitch in bytes for uint8,
+            pitch / 2 for uint16)
+        transform: "rotate_90", "rotate_180", "rotate_270",
+            "flip_horizontal" or "flip_vertical"
+
+    Returns:
+        Compact array (H, W, C) of the input dtype; 90/270 rotations
+        swap width and height
+    """
+    if isinstance(data, np.ndarray):
+        if data.dtype not in (np.uint8, np.uint16):
+            raise ValueError(f"Expected uint8 or uint16, got {data.dtype}")
+        data = np.ascontiguousarray(data).reshape(-1)
+    elif not isinstance(data, bytes):
+        data = np.frombuffer(data, dtype=np.uint8)
+    return imagestag_rust.transform_strided(data, width, height, channels, stride, transform)
@@ -15,8 +15,14 @@
 //! - 90° CW: (x, y) -> (H - 1 - y, x)
 //! - 180°: (x, y) -> (W - 1 - x, H - 1 - y)
 //! - 270° CW (90° CCW): (x, y) -> (y, W - 1 - x)
+//!
+//! ## Strided Buffers
+//!
+//! [`transform_strided`] rotates or flips a raw buffer with padded rows -
+//! such as video capture frames with a pitch larger than the row size - for
+//! any sample type (u8, u16, ...) without compacting it first.
 
-use ndarray::{Array3, ArrayView3};
+use ndarray::{s, Array3, ArrayView3, ShapeBuilder};
 
 /// Rotate image 90 degrees clockwise (u8).
 ///
@@ -259,6 +265,81 @@ pub fn rotate_f32(image: ArrayView3<f32>, degrees: u32) -> Array3<f32> {
     }
 }
 
+// ============================================================================
+// Strided Buffers
+// ============================================================================
+
+/// Lossless transform applied by [`transform_strided`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StridedTransform {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl StridedTransform {
+    /// Parse a transform name ("rotate_90", "rotate_180", "rotate_270",
+    /// "flip_horizontal", "flip_vertical"); plain "90", "180" and "270"
+    /// work too.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rotate_90" | "90" => Some(Self::Rotate90),
+            "rotate_180" | "180" => Some(Self::Rotate180),
+            "rotate_270" | "270" => Some(Self::Rotate270),
+            "flip_horizontal" | "horizontal" => Some(Self::FlipHorizontal),
+            "flip_vertical" | "vertical" => Some(Self::FlipVertical),
+            _ => None,
+        }
+    }
+}
+
+/// View a buffer with padded rows as an image.
+///
+/// `stride` is the distance between row starts in samples (the pitch in
+/// bytes divided by the sample size). Returns `None` if the stride is
+/// shorter than a row or the buffer is too small.
+pub fn strided_view<T>(data: &[T], width: usize, height: usize, channels: usize, stride: usize) -> Option<ArrayView3<'_, T>> {
+    let row = width * channels;
+    if stride < row || (height > 0 && data.len() < (height - 1) * stride + row) {
+        return None;
+    }
+    let data = &data[..(height * stride).min(data.len())];
+    ArrayView3::from_shape((height, width, channels).strides((stride, channels, 1)), data).ok()
+}
+
+/// Rotate or flip a strided buffer.
+///
+/// # Arguments
+/// * `data` - Samples, `stride` per row; the last row may be unpadded
+/// * `width`, `height`, `channels` - Image size
+/// * `stride` - Samples between row starts (>= width * channels)
+/// * `transform` - Rotation or flip
+///
+/// # Returns
+/// Compact image (rotations by 90/270 swap width and height), or `None`
+/// if the buffer doesn't match the size
+pub fn transform_strided<T: Copy>(
+    data: &[T],
+    width: usize,
+    height: usize,
+    channels: usize,
+    stride: usize,
+    transform: StridedTransform,
+) -> Option<Array3<T>> {
+    let view = strided_view(data, width, height, channels, stride)?;
+    let transformed = match transform {
+        // Transpose then mirror: (x, y) -> (H - 1 - y, x)
+        StridedTransform::Rotate90 => view.permuted_axes([1, 0, 2]).slice_move(s![.., ..;-1, ..]),
+        StridedTransform::Rotate180 => view.slice_move(s![..;-1, ..;-1, ..]),
+        StridedTransform::Rotate270 => view.permuted_axes([1, 0, 2]).slice_move(s![..;-1, .., ..]),
+        StridedTransform::FlipHorizontal => view.slice_move(s![.., ..;-1, ..]),
+        StridedTransform::FlipVertical => view.slice_move(s![..;-1, .., ..]),
+    };
+    Some(transformed.as_standard_layout().into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,4 +450,36 @@ mod tests {
         let rotated = rotate_90_cw_f32(image.view());
         assert_eq!(rotated.shape(), &[100, 100, 3]);
     }
+
+    #[test]
+    fn test_transform_strided_matches_compact() {
+        // 2x3 RGB image with rows padded to 11 samples, last row unpadded
+        let image = Array3::from_shape_fn((2, 3, 3), |(y, x, c)| (y * 100 + x * 10 + c) as u8);
+        let mut data = Vec::new();
+        for y in 0..2 {
+            data.extend(image.slice(s![y, .., ..]).iter());
+            if y == 0 {
+                data.extend([0xEE; 2]);
+            }
+        }
+        let cases = [
+            (StridedTransform::Rotate90, rotate_90_cw_u8(image.view())),
+            (StridedTransform::Rotate180, rotate_180_u8(image.view())),
+            (StridedTransform::Rotate270, rotate_270_cw_u8(image.view())),
+            (StridedTransform::FlipHorizontal, flip_horizontal_u8(image.view())),
+            (StridedTransform::FlipVertical, flip_vertical_u8(image.view())),
+        ];
+        for (transform, expected) in cases {
+            assert_eq!(transform_strided(&data, 3, 2, 3, 11, transform), Some(expected), "{:?}", transform);
+        }
+
+        // u16 works the same way
+        let wide: Vec<u16> = vec![1, 2, 0, 3, 4];
+        let rotated = transform_strided(&wide, 2, 2, 1, 3, StridedTransform::Rotate90).unwrap();
+        assert_eq!(rotated.into_raw_vec_and_offset().0, vec![3, 1, 4, 2]);
+
+        assert_eq!(transform_strided(&data, 3, 2, 3, 8, StridedTransform::Rotate90), None);
+        assert_eq!(transform_strided(&data[..15], 3, 2, 3, 11, StridedTransform::Rotate90), None);
+        assert_eq!(StridedTransform::from_name("270"), Some(StridedTransform::Rotate270));
+    }
 }
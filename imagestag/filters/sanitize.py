@@ -0,0 +1,114 @@
+"""NaN / out-of-range policy for float32 filters.
+
+float32 filters expect finite values in 0.0-1.0 and don't check their
+input. NaN, infinities or out-of-range values propagate differently per
+filter (point operations keep them local, blurs spread them, statistics
+based filters such as auto levels shift the whole image). This module
+makes the behavior explicit per call.
+
+Modes:
+- 'clamp': NaN becomes 0.0, everything else is clamped to 0.0-1.0
+- 'propagate': values pass unchanged (the filters' own behavior)
+- 'error': ValueError on NaN, infinity or values outside 0.0-1.0
+
+validate_image / validate_image_f32 are the gate for test pipelines: they
+check channel count and shape, count NaN, infinite and out-of-range
+samples and optionally repair them ('clamp' or neighbor 'median').
+
+Co-located with:
+- sanitize.rs (Rust implementation)
+
+Usage:
+    from imagestag.filters.sanitize import sanitize_f32, apply_f32
+    from imagestag.filters.levels_curves import auto_levels_f32
+
+    clean = sanitize_f32(image, mode="clamp")
+    result = apply_f32(auto_levels_f32, image, 0.01, mode="error")
+    fixed, report = validate_image_f32(image, repair="median", channels=4)
+"""
+from typing import Callable
+
+import numpy as np
+
+import imagestag_rust
+
+MODES = ("clamp", "propagate", "error")
+REPAIRS = ("report", "reject", "clamp", "median")
+
+
+def sanitize_f32(image: np.ndarray, mode: str = "clamp") -> np.ndarray:
+    """Apply a NaN / out-of-range policy to a float32 image.
+
+    Args:
+        image: float32 array (H, W, C)
+        mode: 'clamp', 'propagate' or 'error'
+
+    Returns:
+        float32 array with the same shape
+
+    Raises:
+        ValueError: In 'error' mode when the image holds invalid values, or
+            for an unknown mode
+    """
+    if image.ndim != 3 or image.dtype != np.float32:
+        raise ValueError(f"sanitize_f32: expected float32 (H, W, C), got {image.dtype} {image.shape}")
+    if mode not in MODES:
+        raise ValueError(f"Mode must be one of {MODES}, got {mode}")
+    return imagestag_rust.sanitize_f32(np.ascontiguousarray(image), mode)
+
+
+def apply_f32(filter_fn: Callable[..., np.ndarray], image: np.ndarray, *args,
+              mode: str = "clamp", **kwargs) -> np.ndarray:
+    """Run a float32 filter on sanitized input.
+
+    Args:
+        filter_fn: Any *_f32 filter, called as filter_fn(image, *args, **kwargs)
+        image: float32 array (H, W, C)
+        mode: 'clamp', 'propagate' or 'error'
+
+    Returns:
+        The filter result
+    """
+    return filter_fn(sanitize_f32(image, mode), *args, **kwargs)
+
+
+def validate_image_f32(image: np.ndarray, repair: str = "report", channels: int | None = None,
+                       shape: tuple[int, int] | None = None) -> tuple[np.ndarray, dict]:
+    """Validate a float32 image and optionally repair invalid samples.
+
+    Args:
+        image: float32 array (H, W, C)
+        repair: 'report' (count only), 'reject' (ValueError on invalid
+            samples), 'clamp' or 'median' (NaN / inf replaced by the median
+            of the valid 3x3 neighbors, the rest clamped)
+        channels: Required channel count (None = 1, 3 or 4)
+        shape: Required (height, width)
+
+    Returns:
+        (image, report) with report keys 'nan', 'infinite', 'out_of_range'
+        and 'repaired'
+
+    Raises:
+        ValueError: On a channel or shape mismatch, or invalid samples in
+            'reject' mode
+    """
+    if image.ndim != 3 or image.dtype != np.float32:
+        raise ValueError(f"validate_image_f32: expected float32 (H, W, C), got {image.dtype} {image.shape}")
+    if repair not in REPAIRS:
+        raise ValueError(f"Repair must be one of {REPAIRS}, got {repair}")
+    return imagestag_rust.validate_image_f32(np.ascontiguousarray(image), repair, channels, shape)
+
+
+def validate_image(image: np.ndarray, channels: int | None = None,
+                   shape: tuple[int, int] | None = None) -> dict:
+    """Check the channel count and shape of a uint8 image.
+
+    Raises:
+        ValueError: On a channel or shape mismatch
+    """
+    if image.ndim != 3 or image.dtype != np.uint8:
+        raise ValueError(f"validate_image: expected uint8 (H, W, C), got {image.dtype} {image.shape}")
+    return imagestag_rust.validate_image(np.ascontiguousarray(image), channels, shape)
+
+
+__all__ = ['sanitize_f32', 'apply_f32', 'validate_image', 'validate_image_f32', 'MODES', 'REPAIRS']
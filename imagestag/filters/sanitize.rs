@@ -0,0 +1,470 @@
+//! Input sanitization for f32 filters: NaN and out-of-range policy.
+//!
+//! All f32 filters assume finite values in 0.0-1.0. Other values are not
+//! rejected by the filters themselves; what happens to them depends on the
+//! filter:
+//! - **Point operations** (brightness, invert, levels, ...) map NaN to NaN or
+//!   to the lowest table entry and clamp out-of-range results
+//! - **Neighborhood operations** (blur, sharpen, edges, morphology, ...)
+//!   spread a NaN over the whole kernel footprint
+//! - **Statistics based** (auto levels, match color, Otsu, ...) can see every
+//!   output pixel shift because of a single bad input value
+//!
+//! None of them panic. To get a defined result, run the input through
+//! [`sanitize_f32`] (or [`apply_f32`]) with the [`SanitizeMode`] of choice.
+//!
+//! ## Modes
+//!
+//! | Mode | NaN | +/-inf, < 0.0, > 1.0 |
+//! |------|-----|----------------------|
+//! | `Clamp` | 0.0 | clamped to 0.0-1.0 |
+//! | `Propagate` | unchanged | unchanged (no copy, legacy behavior) |
+//! | `Error` | [`SanitizeError`] | [`SanitizeError`] |
+//!
+//! ## Validation
+//!
+//! [`validate_image_f32`] and [`validate_image_u8`] are the gate test
+//! pipelines run before golden comparisons: they check the channel count
+//! and shape against a [`ValidationPolicy`], count NaN, infinite and
+//! out-of-range samples separately and optionally repair them by clamping
+//! or with the median of the valid neighbors.
+
+use std::fmt;
+
+use ndarray::{Array3, ArrayView3, CowArray, Ix3};
+
+/// How f32 filter input outside finite 0.0-1.0 is treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SanitizeMode {
+    /// Replace NaN by 0.0 and clamp everything else to 0.0-1.0
+    Clamp,
+    /// Pass values through unchanged
+    #[default]
+    Propagate,
+    /// Refuse input containing NaN, infinity or values outside 0.0-1.0
+    Error,
+}
+
+impl SanitizeMode {
+    /// Parse a mode name ("clamp", "propagate", "error").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "clamp" => Some(SanitizeMode::Clamp),
+            "propagate" | "none" => Some(SanitizeMode::Propagate),
+            "error" | "raise" => Some(SanitizeMode::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Input rejected by [`SanitizeMode::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SanitizeError {
+    /// Number of NaN values
+    pub nan: usize,
+    /// Number of infinite or out-of-range values
+    pub out_of_range: usize,
+}
+
+impl fmt::Display for SanitizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "f32 image contains {} NaN and {} out-of-range values (expected 0.0-1.0)",
+            self.nan, self.out_of_range
+        )
+    }
+}
+
+impl std::error::Error for SanitizeError {}
+
+/// Count NaN and infinite / out-of-range values.
+fn count_invalid(input: &ArrayView3<f32>) -> SanitizeError {
+    let mut counts = SanitizeError { nan: 0, out_of_range: 0 };
+    for &v in input.iter() {
+        if v.is_nan() {
+            counts.nan += 1;
+        } else if !(0.0..=1.0).contains(&v) {
+            counts.out_of_range += 1;
+        }
+    }
+    counts
+}
+
+/// Apply a [`SanitizeMode`] to f32 filter input.
+///
+/// Valid input is never copied.
+///
+/// # Returns
+/// The input (borrowed when unchanged), or [`SanitizeError`] in `Error` mode
+pub fn sanitize_f32(input: ArrayView3<'_, f32>, mode: SanitizeMode) -> Result<CowArray<'_, f32, Ix3>, SanitizeError> {
+    if mode == SanitizeMode::Propagate {
+        return Ok(CowArray::from(input));
+    }
+    let counts = count_invalid(&input);
+    if counts.nan == 0 && counts.out_of_range == 0 {
+        return Ok(CowArray::from(input));
+    }
+    match mode {
+        SanitizeMode::Error => Err(counts),
+        _ => Ok(CowArray::from(input.mapv(|v| if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) }))),
+    }
+}
+
+/// Run an f32 filter on sanitized input.
+///
+/// ```ignore
+/// let blurred = apply_f32(image.view(), SanitizeMode::Clamp, |img| gaussian_blur_wasm_f32(img, 2.0))?;
+/// ```
+pub fn apply_f32<F>(input: ArrayView3<f32>, mode: SanitizeMode, filter: F) -> Result<Array3<f32>, SanitizeError>
+where
+    F: FnOnce(ArrayView3<f32>) -> Array3<f32>,
+{
+    let clean = sanitize_f32(input, mode)?;
+    Ok(filter(clean.view()))
+}
+
+/// What [`validate_image_f32`] does with invalid samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepairMode {
+    /// Count them and leave the image unchanged
+    #[default]
+    Report,
+    /// Fail with [`ValidationError::InvalidValues`]
+    Reject,
+    /// Replace NaN by 0.0 and clamp everything else to 0.0-1.0
+    Clamp,
+    /// Replace NaN and infinities by the median of the valid 3x3 neighbors
+    /// in the same channel (0.0 without any), clamp finite out-of-range values
+    Median,
+}
+
+impl RepairMode {
+    /// Parse a repair name ("report", "reject", "clamp", "median").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "report" | "none" => Some(RepairMode::Report),
+            "reject" | "error" | "raise" => Some(RepairMode::Reject),
+            "clamp" => Some(RepairMode::Clamp),
+            "median" => Some(RepairMode::Median),
+            _ => None,
+        }
+    }
+}
+
+/// Invariants checked by [`validate_image_f32`] and [`validate_image_u8`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// Required channel count; `None` accepts 1, 3 or 4
+    pub channels: Option<usize>,
+    /// Required (height, width); `None` accepts any non-empty size
+    pub shape: Option<(usize, usize)>,
+    /// Treatment of NaN, infinite and out-of-range samples (f32 only)
+    pub repair: RepairMode,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self { channels: None, shape: None, repair: RepairMode::Report }
+    }
+}
+
+/// Sample counts found by a validation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Number of NaN samples
+    pub nan: usize,
+    /// Number of +/-inf samples
+    pub infinite: usize,
+    /// Number of finite samples outside 0.0-1.0
+    pub out_of_range: usize,
+    /// Number of samples changed by the repair
+    pub repaired: usize,
+}
+
+impl ValidationReport {
+    /// Whether every sample was finite and in range.
+    pub fn is_clean(&self) -> bool {
+        self.nan == 0 && self.infinite == 0 && self.out_of_range == 0
+    }
+}
+
+/// Image rejected by a [`ValidationPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Channel count not allowed by the policy
+    Channels { expected: Option<usize>, actual: usize },
+    /// Height and width differ from the policy, or the image is empty
+    Shape { expected: Option<(usize, usize)>, actual: (usize, usize) },
+    /// Invalid samples under [`RepairMode::Reject`]
+    InvalidValues(ValidationReport),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Channels { expected: Some(expected), actual } => {
+                write!(f, "expected {} channels, got {}", expected, actual)
+            }
+            ValidationError::Channels { expected: None, actual } => write!(f, "expected 1, 3 or 4 channels, got {}", actual),
+            ValidationError::Shape { expected: Some((h, w)), actual: (ah, aw) } => {
+                write!(f, "expected {}x{} (height x width), got {}x{}", h, w, ah, aw)
+            }
+            ValidationError::Shape { expected: None, actual: (ah, aw) } => write!(f, "expected a non-empty image, got {}x{}", ah, aw),
+            ValidationError::InvalidValues(r) => write!(
+                f,
+                "f32 image contains {} NaN, {} infinite and {} out-of-range values (expected 0.0-1.0)",
+                r.nan, r.infinite, r.out_of_range
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Check channel count and shape against the policy.
+fn check_invariants(dim: (usize, usize, usize), policy: &ValidationPolicy) -> Result<(), ValidationError> {
+    let (height, width, channels) = dim;
+    let channels_ok = match policy.channels {
+        Some(expected) => channels == expected,
+        None => matches!(channels, 1 | 3 | 4),
+    };
+    if !channels_ok {
+        return Err(ValidationError::Channels { expected: policy.channels, actual: channels });
+    }
+    let shape_ok = match policy.shape {
+        Some(expected) => (height, width) == expected,
+        None => height > 0 && width > 0,
+    };
+    if !shape_ok {
+        return Err(ValidationError::Shape { expected: policy.shape, actual: (height, width) });
+    }
+    Ok(())
+}
+
+/// Median of the finite in-range 3x3 neighbors of a sample, 0.0 without any.
+fn neighbor_median(input: &ArrayView3<f32>, y: usize, x: usize, c: usize) -> f32 {
+    let (height, width, _) = input.dim();
+    let mut values = [0.0f32; 8];
+    let mut count = 0;
+    for ny in y.saturating_sub(1)..(y + 2).min(height) {
+        for nx in x.saturating_sub(1)..(x + 2).min(width) {
+            let v = input[[ny, nx, c]];
+            if (ny, nx) != (y, x) && (0.0..=1.0).contains(&v) {
+                values[count] = v;
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    let values = &mut values[..count];
+    values.sort_by(f32::total_cmp);
+    if count % 2 == 1 {
+        values[count / 2]
+    } else {
+        (values[count / 2 - 1] + values[count / 2]) / 2.0
+    }
+}
+
+/// Validate an f32 image and optionally repair invalid samples.
+///
+/// Clean images and images under [`RepairMode::Report`] are never copied.
+///
+/// # Returns
+/// The (possibly repaired) image and the counts found in the input, or a
+/// [`ValidationError`] when an invariant does not hold
+pub fn validate_image_f32<'a>(
+    input: ArrayView3<'a, f32>,
+    policy: &ValidationPolicy,
+) -> Result<(CowArray<'a, f32, Ix3>, ValidationReport), ValidationError> {
+    check_invariants(input.dim(), policy)?;
+    let mut report = ValidationReport::default();
+    for &v in input.iter() {
+        if v.is_nan() {
+            report.nan += 1;
+        } else if v.is_infinite() {
+            report.infinite += 1;
+        } else if !(0.0..=1.0).contains(&v) {
+            report.out_of_range += 1;
+        }
+    }
+    if report.is_clean() {
+        return Ok((CowArray::from(input), report));
+    }
+    let repaired = match policy.repair {
+        RepairMode::Report => return Ok((CowArray::from(input), report)),
+        RepairMode::Reject => return Err(ValidationError::InvalidValues(report)),
+        RepairMode::Clamp => input.mapv(|v| if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) }),
+        // Neighbors are read from the input, so repairs never feed each other
+        RepairMode::Median => Array3::from_shape_fn(input.dim(), |(y, x, c)| {
+            let v = input[[y, x, c]];
+            if !v.is_finite() {
+                neighbor_median(&input, y, x, c)
+            } else {
+                v.clamp(0.0, 1.0)
+            }
+        }),
+    };
+    report.repaired = report.nan + report.infinite + report.out_of_range;
+    Ok((CowArray::from(repaired), report))
+}
+
+/// Check the channel count and shape of a u8 image.
+///
+/// u8 samples are always valid, so the report only confirms the counts are zero.
+pub fn validate_image_u8(input: ArrayView3<u8>, policy: &ValidationPolicy) -> Result<ValidationReport, ValidationError> {
+    check_invariants(input.dim(), policy)?;
+    Ok(ValidationReport::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::{
+        blur_wasm, color_adjust, color_science, edge, glitch, grayscale, levels_curves, material, morphology, noise, sharpen,
+        stylize,
+    };
+
+    type Filter = (&'static str, fn(ArrayView3<f32>) -> Array3<f32>);
+
+    /// Every f32 image filter of the pure Rust modules with typical parameters.
+    fn filters() -> Vec<Filter> {
+        vec![
+            ("grayscale", |i| grayscale::grayscale_f32(i)),
+            ("brightness", |i| color_adjust::brightness_f32(i, 0.2)),
+            ("contrast", |i| color_adjust::contrast_f32(i, 0.3)),
+            ("saturation", |i| color_adjust::saturation_f32(i, 0.5)),
+            ("gamma", |i| color_adjust::gamma_f32(i, 1.8)),
+            ("exposure", |i| color_adjust::exposure_f32(i, 0.5, 0.0, 1.0)),
+            ("invert", |i| color_adjust::invert_f32(i)),
+            ("equalize_histogram", |i| color_adjust::equalize_histogram_f32(i)),
+            ("hue_shift", |i| color_science::hue_shift_f32(i, 90.0)),
+            ("vibrance", |i| color_science::vibrance_f32(i, 0.5)),
+            ("sepia", |i| color_science::sepia_f32(i, 1.0)),
+            ("temperature", |i| color_science::temperature_f32(i, 0.3)),
+            ("split_tone", |i| color_science::split_tone_f32(i, [0.2, 0.3, 0.6], [0.9, 0.7, 0.3], 0.0)),
+            ("levels", |i| levels_curves::levels_f32(i, 0.1, 0.9, 0.0, 1.0, 1.2)),
+            ("curves", |i| levels_curves::curves_f32(i, &[(0.0, 0.0), (0.5, 0.6), (1.0, 1.0)], Default::default(), false)),
+            ("auto_levels", |i| levels_curves::auto_levels_f32(i, 0.01)),
+            ("auto_contrast", |i| levels_curves::auto_contrast_f32(i, 0.01)),
+            ("auto_tone", |i| levels_curves::auto_tone_f32(i, 0.01, true)),
+            ("auto_color", |i| levels_curves::auto_color_f32(i, 0.01, true)),
+            ("posterize", |i| stylize::posterize_f32(i, 4)),
+            ("solarize", |i| stylize::solarize_f32(i, 0.5)),
+            ("threshold", |i| stylize::threshold_f32(i, 0.5)),
+            ("adaptive_threshold", |i| stylize::adaptive_threshold_f32(i, 5, 0.02, Default::default())),
+            ("emboss", |i| stylize::emboss_f32(i, 135.0, 1.0)),
+            ("pixelate", |i| stylize::pixelate_f32(i, 3)),
+            ("vignette", |i| stylize::vignette_f32(i, 0.5)),
+            ("pencil_sketch", |i| stylize::pencil_sketch_f32(i, 2.0, 0.05)),
+            ("xdog", |i| stylize::xdog_f32(i, 1.0, 1.6, 20.0, 0.1, 10.0)),
+            ("sharpen", |i| sharpen::sharpen_f32(i, 1.0)),
+            ("unsharp_mask", |i| sharpen::unsharp_mask_f32(i, 1.0, 1.5, 0.0)),
+            ("high_pass", |i| sharpen::high_pass_f32(i, 2.0)),
+            ("clarity", |i| sharpen::clarity_f32(i, 0.5, 3.0)),
+            ("texture", |i| sharpen::texture_f32(i, 0.5)),
+            ("motion_blur", |i| sharpen::motion_blur_f32(i, 30.0, 4.0, Default::default())),
+            ("sobel", |i| edge::sobel_f32(i, "both", 3)),
+            ("sobel_per_channel", |i| edge::sobel_per_channel_f32(i, "both", 3)),
+            ("laplacian", |i| edge::laplacian_f32(i, 3)),
+            ("find_edges", |i| edge::find_edges_f32(i, 1.0, 0.1, 0.2)),
+            ("dog", |i| edge::dog_f32(i, 1.0, 1.6)),
+            ("add_noise", |i| noise::add_noise_f32(i, 0.1, true, false, 1)),
+            ("film_grain", |i| noise::film_grain_f32(i, 0.3, 1.5, 0.5, 0.0, 1)),
+            ("median", |i| noise::median_f32(i, 1)),
+            ("denoise", |i| noise::denoise_f32(i, 0.5)),
+            ("dilate", |i| morphology::dilate_f32(i, 1.0)),
+            ("erode", |i| morphology::erode_f32(i, 1.0)),
+            ("gradient", |i| morphology::gradient_f32(i, 1.0)),
+            ("rgb_split", |i| glitch::rgb_split_f32(i, 2, 0)),
+            ("scanlines", |i| glitch::scanlines_f32(i, 3, 1, 0.3)),
+            ("block_shift", |i| glitch::block_shift_f32(i, 2, 3, 0.5, 1)),
+            ("wave_distort", |i| glitch::wave_distort_f32(i, 2.0, 8.0, 0.0, 0.5, 1)),
+            ("height_to_normal", |i| material::height_to_normal_f32(i, 2.0, false)),
+            ("gaussian_blur", |i| blur_wasm::gaussian_blur_wasm_f32(i, 1.5)),
+            ("box_blur", |i| blur_wasm::box_blur_wasm_f32(i, 2)),
+        ]
+    }
+
+    /// RGBA image with NaN, infinities and out-of-range values mixed in.
+    fn dirty() -> Array3<f32> {
+        let bad = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, -0.5, 1.5];
+        Array3::from_shape_fn((8, 8, 4), |(y, x, c)| {
+            let i = y * 8 + x;
+            if c < 3 && i % 7 == 3 {
+                bad[(i / 7 + c) % bad.len()]
+            } else {
+                ((y * 13 + x * 7 + c * 5) % 11) as f32 / 10.0
+            }
+        })
+    }
+
+    #[test]
+    fn test_modes() {
+        let img = dirty();
+        let clean = sanitize_f32(img.view(), SanitizeMode::Clamp).unwrap();
+        assert!(clean.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert_eq!(clean[[0, 3, 0]], 0.0);
+
+        let err = sanitize_f32(img.view(), SanitizeMode::Error).unwrap_err();
+        assert_eq!(err.nan + err.out_of_range, 27);
+        // Valid input is passed as a view
+        assert!(sanitize_f32(clean.view(), SanitizeMode::Error).unwrap().is_view());
+
+        let passed = sanitize_f32(img.view(), SanitizeMode::Propagate).unwrap();
+        assert!(passed[[0, 3, 0]].is_nan());
+        assert_eq!(SanitizeMode::from_name("Raise"), Some(SanitizeMode::Error));
+        assert_eq!(SanitizeMode::from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_validate_counts_and_repairs() {
+        let img = dirty();
+        let policy = ValidationPolicy { channels: Some(4), shape: Some((8, 8)), ..Default::default() };
+        let (same, report) = validate_image_f32(img.view(), &policy).unwrap();
+        assert!(same.is_view());
+        assert_eq!(report.nan + report.infinite + report.out_of_range, 27);
+        assert!(report.nan > 0 && report.infinite > 0 && report.out_of_range > 0);
+        assert_eq!(report.repaired, 0);
+
+        let reject = ValidationPolicy { repair: RepairMode::Reject, ..policy };
+        assert_eq!(validate_image_f32(img.view(), &reject).unwrap_err(), ValidationError::InvalidValues(report));
+
+        // Median repair takes the valid neighbors of the same channel
+        let mut flat = Array3::<f32>::from_elem((3, 3, 1), 0.25);
+        flat[[0, 0, 0]] = 0.75;
+        flat[[1, 1, 0]] = f32::NAN;
+        flat[[2, 2, 0]] = 1.5;
+        let median = ValidationPolicy { repair: RepairMode::Median, ..Default::default() };
+        let (fixed, report) = validate_image_f32(flat.view(), &median).unwrap();
+        assert_eq!(fixed[[1, 1, 0]], 0.25);
+        assert_eq!(fixed[[2, 2, 0]], 1.0);
+        assert_eq!(report.repaired, 2);
+        let (fixed, _) = validate_image_f32(img.view(), &median).unwrap();
+        assert!(fixed.iter().all(|v| (0.0..=1.0).contains(v)));
+
+        // Shape invariants
+        assert!(matches!(validate_image_f32(img.view(), &ValidationPolicy { channels: Some(3), ..policy }), Err(ValidationError::Channels { .. })));
+        assert!(matches!(validate_image_u8(Array3::<u8>::zeros((0, 4, 3)).view(), &Default::default()), Err(ValidationError::Shape { .. })));
+        assert_eq!(validate_image_u8(Array3::<u8>::zeros((2, 4, 3)).view(), &Default::default()), Ok(ValidationReport::default()));
+        assert_eq!(RepairMode::from_name("Median"), Some(RepairMode::Median));
+    }
+
+    #[test]
+    fn test_every_f32_filter_with_dirty_input() {
+        let img = dirty();
+        for (name, filter) in filters() {
+            // Propagate: must not panic, whatever comes out
+            let _ = filter(img.view());
+
+            // Clamp: finite input gives finite output in range
+            let result = apply_f32(img.view(), SanitizeMode::Clamp, filter).unwrap();
+            assert!(result.iter().all(|v| v.is_finite() && (-1e-5..=1.0 + 1e-5).contains(v)), "{name}");
+
+            // Error: the filter never runs
+            assert!(apply_f32(img.view(), SanitizeMode::Error, filter).is_err(), "{name}");
+        }
+    }
+}
@@ -0,0 +1,36 @@
+/**
+ * Seamless textures - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - seamless.rs (Rust implementation)
+ * - seamless.py (Python wrapper)
+ *
+ * Provides: make_seamless
+ */
+
+import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Make Seamless
+// ============================================================================
+
+/**
+ * Make an image tile without visible seams (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {method: 'offset_blend'|'frequency', blend_width: px (0 = auto)}
+ * @returns {Object} - Filtered image data
+ */
+export const make_seamless = createU8Filter(
+    wasm.make_seamless_wasm,
+    (opts) => [opts.method ?? 'offset_blend', opts.blend_width ?? 0]
+);
+
+/**
+ * Make an image tile without visible seams (f32).
+ */
+export const make_seamless_f32 = createF32Filter(
+    wasm.make_seamless_f32_wasm,
+    (opts) => [opts.method ?? 'offset_blend', opts.blend_width ?? 0]
+);
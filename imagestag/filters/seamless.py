@@ -0,0 +1,78 @@
+"""Seamless (tileable) textures with Rust backend.
+
+Removes the seams an image shows when tiled, for use as a pattern fill or
+pattern overlay:
+- 'offset_blend': blend in the half-offset image near the borders, keep
+  the original in the middle (fast, local)
+- 'frequency': periodic plus smooth decomposition - subtract the smooth
+  component carrying the border mismatch, texture detail stays untouched
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels.
+
+Co-located with:
+- seamless.rs (Rust implementation)
+- seamless.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.seamless import make_seamless
+
+    tile = make_seamless(brick_photo)                       # offset blend
+    tile = make_seamless(grass_photo, method='frequency')
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Make Seamless
+# ============================================================================
+
+def make_seamless(image: np.ndarray, method: str = 'offset_blend',
+                  blend_width: int = 0) -> np.ndarray:
+    """Make an image tile without visible seams (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        method: 'offset_blend' or 'frequency'
+        blend_width: Blend zone in pixels for offset blend, at most half the
+            shorter side (0 = a quarter of the shorter side)
+
+    Returns:
+        uint8 array with the same shape
+    """
+    _validate_image(image, np.uint8, "make_seamless")
+    return imagestag_rust.make_seamless(image, method, blend_width)
+
+
+def make_seamless_f32(image: np.ndarray, method: str = 'offset_blend',
+                      blend_width: int = 0) -> np.ndarray:
+    """Make an image tile without visible seams (f32).
+
+    Frequency results may slightly leave 0.0-1.0 in color channels.
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        method: 'offset_blend' or 'frequency'
+        blend_width: Blend zone in pixels for offset blend (0 = auto)
+
+    Returns:
+        float32 array with the same shape
+    """
+    _validate_image(image, np.float32, "make_seamless_f32")
+    return imagestag_rust.make_seamless_f32(image, method, blend_width)
+
+
+__all__ = [
+    'make_seamless', 'make_seamless_f32',
+]
@@ -5,7 +5,7 @@
  * - sharpen.rs (Rust implementation)
  * - sharpen.py (Python wrapper)
  *
- * Provides: sharpen, unsharp_mask, high_pass, motion_blur
+ * Provides: sharpen, unsharp_mask, high_pass, clarity, texture, smart_sharpen, motion_blur, spin_blur
  */
 
 import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
@@ -39,24 +39,29 @@ export const sharpen_f32 = createF32Filter(
 // Unsharp Mask
 // ============================================================================
 
+const unsharpMaskOptions = (opts) => [Object.assign(new wasm.UnsharpMaskOptions(), {
+    amount: opts.amount ?? 1.0,
+    radius: opts.radius ?? 2.0,
+    threshold: (opts.threshold ?? 0) / 255.0,
+    edge_mask: opts.edgeMask ?? 0.0,
+    shadow_amount: opts.shadowAmount ?? 1.0,
+    highlight_amount: opts.highlightAmount ?? 1.0,
+})];
+
 /**
  * Apply unsharp mask (u8).
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {amount, radius, threshold}
+ * @param {Object} options - {amount, radius, threshold (0-255), edgeMask, shadowAmount, highlightAmount}
+ *   - edgeMask: 0.0-1.0, fades sharpening out on very strong edges (halos) and flat areas (noise)
+ *   - shadowAmount / highlightAmount: amount multipliers towards black / white (1.0 = unchanged)
  * @returns {Object} - Sharpened image data
  */
-export const unsharp_mask = createU8Filter(
-    wasm.unsharp_mask_wasm,
-    (opts) => [opts.amount ?? 1.0, opts.radius ?? 2.0, opts.threshold ?? 0]
-);
+export const unsharp_mask = createU8Filter(wasm.unsharp_mask_params_wasm, unsharpMaskOptions);
 
 /**
- * Apply unsharp mask (f32).
+ * Apply unsharp mask (f32). `threshold` is given in 0-255 like the u8 version.
  */
-export const unsharp_mask_f32 = createF32Filter(
-    wasm.unsharp_mask_f32_wasm,
-    (opts) => [opts.amount ?? 1.0, opts.radius ?? 2.0, (opts.threshold ?? 0) / 255.0]
-);
+export const unsharp_mask_f32 = createF32Filter(wasm.unsharp_mask_params_f32_wasm, unsharpMaskOptions);
 
 // ============================================================================
 // High Pass
@@ -81,6 +86,76 @@ export const high_pass_f32 = createF32Filter(
     (opts) => [opts.radius ?? 3.0]
 );
 
+// ============================================================================
+// Clarity
+// ============================================================================
+
+/**
+ * Apply clarity / midtone local contrast (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amount: number (-1.0 to 1.0), radius: number}
+ * @returns {Object} - Adjusted image data
+ */
+export const clarity = createU8Filter(
+    wasm.clarity_wasm,
+    (opts) => [opts.amount ?? 0.5, opts.radius ?? 20.0]
+);
+
+/**
+ * Apply clarity (f32).
+ */
+export const clarity_f32 = createF32Filter(
+    wasm.clarity_f32_wasm,
+    (opts) => [opts.amount ?? 0.5, opts.radius ?? 20.0]
+);
+
+// ============================================================================
+// Texture
+// ============================================================================
+
+/**
+ * Boost or smooth medium-frequency detail with edge protection (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amount: number (-1.0 to 1.0)}
+ * @returns {Object} - Adjusted image data
+ */
+export const texture = createU8Filter(
+    wasm.texture_wasm,
+    (opts) => [opts.amount ?? 0.5]
+);
+
+/**
+ * Boost or smooth medium-frequency detail (f32).
+ */
+export const texture_f32 = createF32Filter(
+    wasm.texture_f32_wasm,
+    (opts) => [opts.amount ?? 0.5]
+);
+
+// ============================================================================
+// Smart Sharpen
+// ============================================================================
+
+const smartSharpenArgs = (opts) => [opts.mode ?? 'gaussian', Object.assign(new wasm.SmartSharpenOptions(), {
+    amount: opts.amount ?? 1.0,
+    radius: opts.radius ?? 1.0,
+    angle: opts.angle ?? 0.0,
+})];
+
+/**
+ * Remove gaussian, lens or motion blur (u8).
+ * Lens and motion use Richardson-Lucy deconvolution.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {radius, amount, mode: 'gaussian'|'lens'|'motion', angle}
+ * @returns {Object} - Sharpened image data
+ */
+export const smart_sharpen = createU8Filter(wasm.smart_sharpen_wasm, smartSharpenArgs);
+
+/**
+ * Remove gaussian, lens or motion blur (f32).
+ */
+export const smart_sharpen_f32 = createF32Filter(wasm.smart_sharpen_f32_wasm, smartSharpenArgs);
+
 // ============================================================================
 // Motion Blur
 // ============================================================================
@@ -88,12 +163,12 @@ export const high_pass_f32 = createF32Filter(
 /**
  * Apply motion blur (u8).
  * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
- * @param {Object} options - {angle, distance}
+ * @param {Object} options - {angle, distance, borderMode: 'reflect'|'mirror'|'nearest'|'wrap'|'constant'}
  * @returns {Object} - Blurred image data
  */
 export const motion_blur = createU8Filter(
     wasm.motion_blur_wasm,
-    (opts) => [opts.angle ?? 45.0, opts.distance ?? 10.0]
+    (opts) => [opts.angle ?? 45.0, opts.distance ?? 10.0, opts.borderMode ?? 'reflect']
 );
 
 /**
@@ -101,13 +176,40 @@ export const motion_blur = createU8Filter(
  */
 export const motion_blur_f32 = createF32Filter(
     wasm.motion_blur_f32_wasm,
-    (opts) => [opts.angle ?? 45.0, opts.distance ?? 10.0]
+    (opts) => [opts.angle ?? 45.0, opts.distance ?? 10.0, opts.borderMode ?? 'reflect']
 );
 
+// ============================================================================
+// Spin Blur
+// ============================================================================
+
+const spinBlurArgs = (opts) => [Object.assign(new wasm.SpinBlurOptions(), {
+    angle: opts.angle ?? 10.0,
+    center_x: opts.centerX ?? 0.5,
+    center_y: opts.centerY ?? 0.5,
+}), opts.borderMode ?? 'reflect'];
+
+/**
+ * Blur along circles around a point (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {angle, centerX, centerY (0-1), borderMode}
+ * @returns {Object} - Blurred image data
+ */
+export const spin_blur = createU8Filter(wasm.spin_blur_wasm, spinBlurArgs);
+
+/**
+ * Blur along circles around a point (f32).
+ */
+export const spin_blur_f32 = createF32Filter(wasm.spin_blur_f32_wasm, spinBlurArgs);
+
 export default {
     initWasm,
     sharpen, sharpen_f32,
     unsharp_mask, unsharp_mask_f32,
     high_pass, high_pass_f32,
-    motion_blur, motion_blur_f32
+    clarity, clarity_f32,
+    texture, texture_f32,
+    smart_sharpen, smart_sharpen_f32,
+    motion_blur, motion_blur_f32,
+    spin_blur, spin_blur_f32
 };
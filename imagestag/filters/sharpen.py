@@ -4,7 +4,11 @@ This module provides sharpening and related spatial filters:
 - Sharpen
 - Unsharp Mask
 - High Pass
+- Clarity
+- Texture
+- Smart Sharpen (blur removal by deconvolution)
 - Motion Blur
+- Spin Blur
 
 ## Supported Formats
 
@@ -87,7 +91,9 @@ def sharpen_f32(image: np.ndarray, amount: float = 1.0) -> np.ndarray:
 # ============================================================================
 
 def unsharp_mask(image: np.ndarray, amount: float = 1.0,
-                 radius: float = 1.0, threshold: int = 0) -> np.ndarray:
+                 radius: float = 1.0, threshold: int = 0,
+                 edge_mask: float = 0.0, shadow_amount: float = 1.0,
+                 highlight_amount: float = 1.0) -> np.ndarray:
     """Apply unsharp mask sharpening (u8).
 
     Subtracts a blurred version to enhance edges.
@@ -97,16 +103,25 @@ def unsharp_mask(image: np.ndarray, amount: float = 1.0,
         amount: Sharpening amount (0.0-5.0), 1.0 = 100%
         radius: Blur radius in pixels (0.1-500.0)
         threshold: Minimum difference to sharpen (0-255)
+        edge_mask: Edge mask strength (0.0-1.0). Fades sharpening out on
+            very strong edges, which would get halos, and in flat areas,
+            where only noise would be sharpened
+        shadow_amount: Amount multiplier towards black (1.0 = unchanged,
+            0.0 = no sharpening in the deepest shadows)
+        highlight_amount: Amount multiplier towards white
 
     Returns:
         Sharpened uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "unsharp_mask")
-    return imagestag_rust.unsharp_mask(image, amount, radius, threshold)
+    return imagestag_rust.unsharp_mask(image, amount, radius, threshold,
+                                       edge_mask, shadow_amount, highlight_amount)
 
 
 def unsharp_mask_f32(image: np.ndarray, amount: float = 1.0,
-                     radius: float = 1.0, threshold: float = 0.0) -> np.ndarray:
+                     radius: float = 1.0, threshold: float = 0.0,
+                     edge_mask: float = 0.0, shadow_amount: float = 1.0,
+                     highlight_amount: float = 1.0) -> np.ndarray:
     """Apply unsharp mask sharpening (f32).
 
     Args:
@@ -114,12 +129,16 @@ def unsharp_mask_f32(image: np.ndarray, amount: float = 1.0,
         amount: Sharpening amount (0.0-5.0), 1.0 = 100%
         radius: Blur radius in pixels (0.1-500.0)
         threshold: Minimum difference to sharpen (0.0-1.0)
+        edge_mask: Edge mask strength (0.0-1.0), see ``unsharp_mask``
+        shadow_amount: Amount multiplier towards black (1.0 = unchanged)
+        highlight_amount: Amount multiplier towards white (1.0 = unchanged)
 
     Returns:
         Sharpened float32 array with same channel count
     """
     _validate_image(image, np.float32, "unsharp_mask_f32")
-    return imagestag_rust.unsharp_mask_f32(image, amount, radius, threshold)
+    return imagestag_rust.unsharp_mask_f32(image, amount, radius, threshold,
+                                           edge_mask, shadow_amount, highlight_amount)
 
 
 # ============================================================================
@@ -157,47 +176,216 @@ def high_pass_f32(image: np.ndarray, radius: float = 10.0) -> np.ndarray:
     return imagestag_rust.high_pass_f32(image, radius)
 
 
+# ============================================================================
+# Clarity
+# ============================================================================
+
+def clarity(image: np.ndarray, amount: float = 0.5, radius: float = 20.0) -> np.ndarray:
+    """Apply clarity / midtone local contrast (u8).
+
+    Large-radius unsharp mask on the luminance, faded out in shadows and
+    highlights. Negative amounts soften local contrast.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amount: Strength (-1.0 to 1.0, like Lightroom's -100 to 100)
+        radius: Blur radius of the contrast mask in pixels (typically 10-50)
+
+    Returns:
+        Adjusted uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "clarity")
+    return imagestag_rust.clarity(image, amount, radius)
+
+
+def clarity_f32(image: np.ndarray, amount: float = 0.5, radius: float = 20.0) -> np.ndarray:
+    """Apply clarity / midtone local contrast (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        amount: Strength (-1.0 to 1.0)
+        radius: Blur radius of the contrast mask in pixels
+
+    Returns:
+        Adjusted float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "clarity_f32")
+    return imagestag_rust.clarity_f32(image, amount, radius)
+
+
+# ============================================================================
+# Texture
+# ============================================================================
+
+def texture(image: np.ndarray, amount: float = 0.5) -> np.ndarray:
+    """Boost or smooth medium-frequency detail (u8).
+
+    Works on a band-pass (difference of blurs) of the luminance and fades
+    out at strong edges, so negative amounts smooth skin while outlines
+    stay crisp. Complements clarity, which works on larger structures.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        amount: Strength (-1.0 = remove medium detail, 1.0 = double it)
+
+    Returns:
+        Adjusted uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "texture")
+    return imagestag_rust.texture(image, amount)
+
+
+def texture_f32(image: np.ndarray, amount: float = 0.5) -> np.ndarray:
+    """Boost or smooth medium-frequency detail (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        amount: Strength (-1.0 to 1.0)
+
+    Returns:
+        Adjusted float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "texture_f32")
+    return imagestag_rust.texture_f32(image, amount)
+
+
+# ============================================================================
+# Smart Sharpen
+# ============================================================================
+
+def smart_sharpen(image: np.ndarray, radius: float = 1.0, amount: float = 1.0,
+                  mode: str = "gaussian", angle: float = 0.0) -> np.ndarray:
+    """Remove a known blur (u8).
+
+    ``"gaussian"`` sharpens with an unsharp mask. ``"lens"`` and ``"motion"``
+    run a few Richardson-Lucy deconvolution iterations with a disk or line
+    point spread function, which restores detail with fewer halos.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        radius: Blur radius in pixels. Sigma for gaussian, disk radius for
+            lens, half the streak length for motion
+        amount: Strength (0.0 = none, 1.0 = full restoration)
+        mode: Blur to remove: "gaussian", "lens" or "motion"
+        angle: Motion direction in degrees (motion mode only)
+
+    Returns:
+        Sharpened uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "smart_sharpen")
+    return imagestag_rust.smart_sharpen(image, radius, amount, mode, angle)
+
+
+def smart_sharpen_f32(image: np.ndarray, radius: float = 1.0, amount: float = 1.0,
+                      mode: str = "gaussian", angle: float = 0.0) -> np.ndarray:
+    """Remove a known blur (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        radius: Blur radius in pixels
+        amount: Strength (0.0 = none, 1.0 = full restoration)
+        mode: Blur to remove: "gaussian", "lens" or "motion"
+        angle: Motion direction in degrees (motion mode only)
+
+    Returns:
+        Sharpened float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "smart_sharpen_f32")
+    return imagestag_rust.smart_sharpen_f32(image, radius, amount, mode, angle)
+
+
 # ============================================================================
 # Motion Blur
 # ============================================================================
 
 def motion_blur(image: np.ndarray, angle: float = 0.0,
-                distance: float = 10.0) -> np.ndarray:
+                distance: float = 10.0, border_mode: str = "reflect") -> np.ndarray:
     """Apply motion blur (u8).
 
-    Simulates camera motion during exposure.
+    Simulates camera motion during exposure. Samples along the blur line
+    are read with bilinear interpolation, so fractional distances and
+    diagonal angles blur smoothly.
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         angle: Motion direction in degrees (0-360)
         distance: Blur distance in pixels (1-1000)
+        border_mode: Pixels beyond the edge: "reflect", "mirror",
+            "nearest" (streaks the edge pixel), "wrap" or "constant" (zero)
 
     Returns:
         Motion-blurred uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "motion_blur")
-    return imagestag_rust.motion_blur(image, angle, distance)
+    return imagestag_rust.motion_blur(image, angle, distance, border_mode)
 
 
 def motion_blur_f32(image: np.ndarray, angle: float = 0.0,
-                    distance: float = 10.0) -> np.ndarray:
+                    distance: float = 10.0, border_mode: str = "reflect") -> np.ndarray:
     """Apply motion blur (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         angle: Motion direction in degrees (0-360)
         distance: Blur distance in pixels (1-1000)
+        border_mode: Pixels beyond the edge, see ``motion_blur``
 
     Returns:
         Motion-blurred float32 array with same channel count
     """
     _validate_image(image, np.float32, "motion_blur_f32")
-    return imagestag_rust.motion_blur_f32(image, angle, distance)
+    return imagestag_rust.motion_blur_f32(image, angle, distance, border_mode)
+
+
+# ============================================================================
+# Spin Blur
+# ============================================================================
+
+def spin_blur(image: np.ndarray, angle: float = 10.0, center_x: float = 0.5,
+              center_y: float = 0.5, border_mode: str = "reflect") -> np.ndarray:
+    """Blur along circles around a point, as if the image spun (u8).
+
+    The blur grows with the distance from the center, which stays sharp.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        angle: Rotation swept by the blur in degrees (0-360)
+        center_x: Center of rotation as a fraction of the width
+        center_y: Center of rotation as a fraction of the height
+        border_mode: Pixels beyond the edge, see ``motion_blur``
+
+    Returns:
+        Spin-blurred uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "spin_blur")
+    return imagestag_rust.spin_blur(image, angle, center_x, center_y, border_mode)
+
+
+def spin_blur_f32(image: np.ndarray, angle: float = 10.0, center_x: float = 0.5,
+                  center_y: float = 0.5, border_mode: str = "reflect") -> np.ndarray:
+    """Blur along circles around a point (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        angle: Rotation swept by the blur in degrees (0-360)
+        center_x: Center of rotation as a fraction of the width
+        center_y: Center of rotation as a fraction of the height
+        border_mode: Pixels beyond the edge, see ``motion_blur``
+
+    Returns:
+        Spin-blurred float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "spin_blur_f32")
+    return imagestag_rust.spin_blur_f32(image, angle, center_x, center_y, border_mode)
 
 
 __all__ = [
     'sharpen', 'sharpen_f32',
     'unsharp_mask', 'unsharp_mask_f32',
     'high_pass', 'high_pass_f32',
+    'clarity', 'clarity_f32',
+    'texture', 'texture_f32',
+    'smart_sharpen', 'smart_sharpen_f32',
     'motion_blur', 'motion_blur_f32',
+    'spin_blur', 'spin_blur_f32',
 ]
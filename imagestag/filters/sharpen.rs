@@ -1,4 +1,5 @@
-//! Sharpen filters: Sharpen, Unsharp Mask, High Pass.
+//! Sharpen filters: Sharpen, Unsharp Mask, High Pass, Clarity, Texture, Smart Sharpen,
+//! plus the directional Motion Blur and Spin Blur.
 //!
 //! These filters enhance or extract edge detail.
 //! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
@@ -18,7 +19,13 @@
 //! - Opaque pixels adjacent to transparent regions remain unchanged
 //! - No color bleeding from undefined RGB values in transparent pixels
 
-use ndarray::{Array3, ArrayView3};
+use ndarray::{Array2, Array3, ArrayView3};
+use rayon::prelude::*;
+
+use crate::filters::convolve::BorderMode;
+use crate::filters::fmath;
+use crate::filters::edge::sobel_gradients_f32;
+use crate::filters::grayscale::{LUMA_B, LUMA_G, LUMA_R};
 
 // ============================================================================
 // Sharpen
@@ -38,7 +45,8 @@ const ALPHA_THRESHOLD: f32 = 0.001;
 ///
 /// # Returns
 /// Sharpened image with same channel count
-pub fn sharpen_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+pub fn sharpen_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, amount: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -157,7 +165,8 @@ pub fn sharpen_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
 ///
 /// # Returns
 /// Sharpened image with same channel count
-pub fn sharpen_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+pub fn sharpen_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, amount: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -275,7 +284,7 @@ fn gaussian_kernel_1d(sigma: f32) -> Vec<f32> {
     let mut kernel: Vec<f32> = (0..kernel_size)
         .map(|i| {
             let x = i as f32 - half as f32;
-            (-x * x / (2.0 * sigma * sigma)).exp()
+            fmath::exp(-x * x / (2.0 * sigma * sigma))
         })
         .collect();
 
@@ -490,12 +499,13 @@ fn gaussian_blur_internal_f32(input: ArrayView3<f32>, sigma: f32) -> Array3<f32>
 ///
 /// # Returns
 /// Sharpened image with same channel count
-pub fn unsharp_mask_u8(
-    input: ArrayView3<u8>,
+pub fn unsharp_mask_u8<'a>(
+    input: impl Into<ArrayView3<'a, u8>>,
     amount: f32,
     radius: f32,
     threshold: u8,
 ) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -539,12 +549,13 @@ pub fn unsharp_mask_u8(
 ///
 /// # Returns
 /// Sharpened image with same channel count
-pub fn unsharp_mask_f32(
-    input: ArrayView3<f32>,
+pub fn unsharp_mask_f32<'a>(
+    input: impl Into<ArrayView3<'a, f32>>,
     amount: f32,
     radius: f32,
     threshold: f32,
 ) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -576,6 +587,124 @@ pub fn unsharp_mask_f32(
     output
 }
 
+/// Sobel gradient (unit step = 1.0) above which the edge mask starts to
+/// protect an edge from halos, and where protection is complete.
+const USM_STRONG_EDGE: (f32, f32) = (0.25, 0.6);
+/// Sobel gradient below which an area counts as flat (noise only), and
+/// where full sharpening is reached again.
+const USM_FLAT_AREA: (f32, f32) = (0.02, 0.08);
+
+/// Unsharp mask settings including the edge mask and tonal split.
+///
+/// The defaults are a plain unsharp mask (amount 1.0, radius 1.0).
+#[derive(Clone, Copy, Debug)]
+pub struct UnsharpMaskParams {
+    /// Sharpening amount (0.0-5.0, 1.0 = 100%)
+    pub amount: f32,
+    /// Blur radius for the mask (sigma, typically 0.5-3.0)
+    pub radius: f32,
+    /// Minimum difference to sharpen (0.0-1.0)
+    pub threshold: f32,
+    /// Edge mask strength (0.0 = off, 1.0 = full): fades sharpening out on
+    /// very strong edges, where it would cause halos, and in flat areas,
+    /// where it would only sharpen noise
+    pub edge_mask: f32,
+    /// Amount multiplier for the shadows (1.0 = same as the midtones)
+    pub shadow_amount: f32,
+    /// Amount multiplier for the highlights (1.0 = same as the midtones)
+    pub highlight_amount: f32,
+}
+
+impl Default for UnsharpMaskParams {
+    fn default() -> Self {
+        Self { amount: 1.0, radius: 1.0, threshold: 0.0, edge_mask: 0.0, shadow_amount: 1.0, highlight_amount: 1.0 }
+    }
+}
+
+impl UnsharpMaskParams {
+    /// Amount multiplier for a pixel of luminance `luma` (0.0-1.0).
+    ///
+    /// Midtones get the full amount; towards black and white it blends
+    /// smoothly to `shadow_amount` and `highlight_amount`.
+    fn tonal_factor(&self, luma: f32) -> f32 {
+        if luma < 0.5 {
+            let t = smoothstep(0.0, 0.5, luma);
+            self.shadow_amount + (1.0 - self.shadow_amount) * t
+        } else {
+            let t = smoothstep(0.5, 1.0, luma);
+            1.0 + (self.highlight_amount - 1.0) * t
+        }
+    }
+
+    /// Edge mask weight (0.0-1.0) for a Sobel gradient magnitude.
+    fn edge_weight(&self, gradient: f32) -> f32 {
+        let strong = 1.0 - smoothstep(USM_STRONG_EDGE.0, USM_STRONG_EDGE.1, gradient);
+        let textured = smoothstep(USM_FLAT_AREA.0, USM_FLAT_AREA.1, gradient);
+        1.0 - self.edge_mask.clamp(0.0, 1.0) * (1.0 - strong * textured)
+    }
+}
+
+#[inline]
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Apply unsharp mask with edge mask and shadow / highlight amounts - f32 version.
+///
+/// Same sharpening as [`unsharp_mask_f32`], with the amount scaled per
+/// pixel by the edge mask (from the Sobel gradient of the luminance) and by
+/// the tonal split. With default settings apart from amount, radius and
+/// threshold the result equals [`unsharp_mask_f32`].
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `params` - Amount, radius, threshold, edge mask and tonal split
+///
+/// # Returns
+/// Sharpened image with same channel count
+pub fn unsharp_mask_params_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, params: &UnsharpMaskParams) -> Array3<f32> {
+    let input = input.into();
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+
+    let blurred = gaussian_blur_internal_f32(input, params.radius);
+    let luma = luminance_f32(input);
+    let gradient = (params.edge_mask > 0.0).then(|| {
+        // 3x3 Sobel sums to 4 for a unit step
+        let (gx, gy) = sobel_gradients_f32(input, 3);
+        ndarray::Zip::from(&gx).and(&gy).map_collect(|&gx, &gy| (gx * gx + gy * gy).sqrt() / 4.0)
+    });
+
+    let color_channels = if channels == 4 { 3 } else { channels };
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut amount = params.amount * params.tonal_factor(luma[[y, x]].clamp(0.0, 1.0));
+            if let Some(gradient) = &gradient {
+                amount *= params.edge_weight(gradient[[y, x]]);
+            }
+            for c in 0..color_channels {
+                let orig = input[[y, x, c]];
+                let diff = orig - blurred[[y, x, c]];
+                let sharpened = if diff.abs() > params.threshold { orig + diff * amount } else { orig };
+                output[[y, x, c]] = sharpened.clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    output
+}
+
+/// Apply unsharp mask with edge mask and shadow / highlight amounts - u8 version.
+///
+/// `params.threshold` is given in 0.0-1.0 like for the f32 version.
+pub fn unsharp_mask_params_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, params: &UnsharpMaskParams) -> Array3<u8> {
+    let input = input.into();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    unsharp_mask_params_f32(input_f32.view(), params).mapv(|v| (v * 255.0).round() as u8)
+}
+
 // ============================================================================
 // High Pass
 // ============================================================================
@@ -591,7 +720,8 @@ pub fn unsharp_mask_f32(
 ///
 /// # Returns
 /// High-pass filtered image with same channel count
-pub fn high_pass_u8(input: ArrayView3<u8>, radius: f32) -> Array3<u8> {
+pub fn high_pass_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, radius: f32) -> Array3<u8> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
@@ -627,7 +757,8 @@ pub fn high_pass_u8(input: ArrayView3<u8>, radius: f32) -> Array3<u8> {
 ///
 /// # Returns
 /// High-pass filtered image with same channel count
-pub fn high_pass_f32(input: ArrayView3<f32>, radius: f32) -> Array3<f32> {
+pub fn high_pass_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, radius: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
@@ -654,180 +785,466 @@ pub fn high_pass_f32(input: ArrayView3<f32>, radius: f32) -> Array3<f32> {
 }
 
 // ============================================================================
-// Motion Blur
+// Clarity
 // ============================================================================
 
-/// Apply motion blur - u8 version.
+/// BT.709 luminance of the color channels (the single channel for grayscale).
+fn luminance_f32(input: ArrayView3<f32>) -> Array2<f32> {
+    let (height, width, channels) = input.dim();
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        if channels >= 3 {
+            LUMA_R * input[[y, x, 0]] + LUMA_G * input[[y, x, 1]] + LUMA_B * input[[y, x, 2]]
+        } else {
+            input[[y, x, 0]]
+        }
+    })
+}
+
+/// Gaussian blur of a luminance plane, alpha-aware for RGBA input.
+fn blur_luminance_f32(input: ArrayView3<f32>, luma: &Array2<f32>, sigma: f32) -> Array2<f32> {
+    let (height, width, channels) = input.dim();
+    // Carry alpha along so transparent pixels don't bleed into the blur
+    let plane = if channels == 4 {
+        Array3::from_shape_fn((height, width, 4), |(y, x, c)| if c == 3 { input[[y, x, 3]] } else { luma[[y, x]] })
+    } else {
+        luma.clone().insert_axis(ndarray::Axis(2))
+    };
+    gaussian_blur_internal_f32(plane.view(), sigma).index_axis_move(ndarray::Axis(2), 0)
+}
+
+/// Apply clarity (midtone local contrast) - f32 version.
 ///
-/// Creates directional blur simulating camera or object motion.
-/// For RGBA images, uses premultiplied alpha to prevent transparent pixels
-/// from bleeding into the result.
+/// Unsharp mask on the luminance with a large radius, faded out towards
+/// shadows and highlights so they don't clip. The luminance change is added
+/// to every color channel, keeping the colors. Negative amounts soften local
+/// contrast instead.
 ///
 /// # Arguments
-/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
-/// * `angle` - Direction of motion in degrees (0 = horizontal right)
-/// * `distance` - Blur distance in pixels
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amount` - Strength (-1.0 to 1.0, Lightroom's -100 to 100)
+/// * `radius` - Blur radius of the contrast mask (sigma, typically 10-50)
 ///
 /// # Returns
-/// Motion-blurred image with same channel count
-pub fn motion_blur_u8(input: ArrayView3<u8>, angle: f32, distance: f32) -> Array3<u8> {
+/// Image with same channel count, alpha preserved
+pub fn clarity_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, amount: f32, radius: f32) -> Array3<f32> {
+    let input = input.into();
     let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
+    let mut output = input.to_owned();
+    let luma = luminance_f32(input);
+    let blurred = blur_luminance_f32(input, &luma, radius);
 
-    let angle_rad = angle.to_radians();
-    let dx = angle_rad.cos();
-    let dy = angle_rad.sin();
+    let color_channels = if channels == 4 { 3 } else { channels };
 
-    let steps = (distance.abs().ceil() as usize).max(1);
-    let step_weight = 1.0 / steps as f32;
-    let has_alpha = channels == 4;
+    for y in 0..height {
+        for x in 0..width {
+            let l = luma[[y, x]].clamp(0.0, 1.0);
+            let midtones = 4.0 * l * (1.0 - l);
+            let delta = amount * (l - blurred[[y, x]]) * midtones;
+            for c in 0..color_channels {
+                output[[y, x, c]] = (input[[y, x, c]] + delta).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    output
+}
+
+/// Apply clarity - u8 version.
+pub fn clarity_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, amount: f32, radius: f32) -> Array3<u8> {
+    let input = input.into();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    clarity_f32(input_f32.view(), amount, radius).mapv(|v| (v * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Texture
+// ============================================================================
+
+/// Band-pass of the texture filter: fine detail is kept out (sigma 1),
+/// larger shapes too (sigma 4), leaving pores, fabric and foliage.
+const TEXTURE_SIGMA_FINE: f32 = 1.0;
+const TEXTURE_SIGMA_COARSE: f32 = 4.0;
+/// Local contrast at which edge protection halves the effect.
+const TEXTURE_EDGE_CONTRAST: f32 = 0.12;
+
+/// Apply texture (medium-frequency detail) enhancement - f32 version.
+///
+/// Boosts or smooths the band between two Gaussian blurs of the luminance
+/// (difference of blurs). Where the local contrast is high - real edges -
+/// the effect fades out, so negative amounts smooth skin without softening
+/// outlines.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `amount` - Strength (-1.0 = remove medium detail, 0.0 = none, 1.0 = double it)
+///
+/// # Returns
+/// Image with same channel count, alpha preserved
+pub fn texture_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, amount: f32) -> Array3<f32> {
+    let input = input.into();
+    let (height, width, channels) = input.dim();
+    let mut output = input.to_owned();
+    let luma = luminance_f32(input);
+    let fine = blur_luminance_f32(input, &luma, TEXTURE_SIGMA_FINE);
+    let coarse = blur_luminance_f32(input, &luma, TEXTURE_SIGMA_COARSE);
+
+    let color_channels = if channels == 4 { 3 } else { channels };
 
     for y in 0..height {
         for x in 0..width {
-            if has_alpha {
-                let mut sum_rgb = [0.0f32; 3];
-                let mut sum_alpha = 0.0f32;
+            let band = fine[[y, x]] - coarse[[y, x]];
+            let contrast = (luma[[y, x]] - coarse[[y, x]]).abs() / TEXTURE_EDGE_CONTRAST;
+            let protect = 1.0 / (1.0 + contrast * contrast);
+            let delta = amount * band * protect;
+            for c in 0..color_channels {
+                output[[y, x, c]] = (input[[y, x, c]] + delta).clamp(0.0, 1.0);
+            }
+        }
+    }
 
-                for i in 0..steps {
-                    let t = i as f32 - (steps as f32 - 1.0) / 2.0;
-                    let sx = (x as f32 + dx * t).round() as isize;
-                    let sy = (y as f32 + dy * t).round() as isize;
+    output
+}
 
-                    let sx = sx.clamp(0, width as isize - 1) as usize;
-                    let sy = sy.clamp(0, height as isize - 1) as usize;
+/// Apply texture enhancement - u8 version.
+pub fn texture_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, amount: f32) -> Array3<u8> {
+    let input = input.into();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    texture_f32(input_f32.view(), amount).mapv(|v| (v * 255.0).round() as u8)
+}
 
-                    let a = input[[sy, sx, 3]] as f32 / 255.0;
-                    sum_alpha += a * step_weight;
-                    for c in 0..3 {
-                        // Premultiplied: RGB * alpha
-                        sum_rgb[c] += input[[sy, sx, c]] as f32 * a * step_weight;
-                    }
-                }
+// ============================================================================
+// Smart Sharpen
+// ============================================================================
 
-                // Unpremultiply
-                let final_alpha = sum_alpha.clamp(0.0, 1.0);
-                output[[y, x, 3]] = (final_alpha * 255.0) as u8;
+/// Richardson-Lucy iterations of the lens and motion modes. A few are
+/// enough for sharpening; more start to amplify noise and ring.
+pub const SMART_SHARPEN_ITERATIONS: usize = 10;
+
+/// Floor for the re-blurred estimate in the Richardson-Lucy ratio.
+const RL_EPSILON: f32 = 1e-4;
+
+/// Which blur smart sharpen removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SmartSharpenMode {
+    /// Gaussian blur, removed with an unsharp mask
+    #[default]
+    Gaussian,
+    /// Lens (defocus) blur, a disk PSF
+    Lens,
+    /// Motion blur, a line PSF along `angle`
+    Motion,
+}
 
-                if final_alpha > 0.001 {
-                    for c in 0..3 {
-                        let unpremultiplied = sum_rgb[c] / final_alpha;
-                        output[[y, x, c]] = unpremultiplied.clamp(0.0, 255.0) as u8;
-                    }
-                } else {
-                    for c in 0..3 {
-                        output[[y, x, c]] = 0;
-                    }
-                }
-            } else {
-                let mut sum = vec![0.0f32; channels];
+impl SmartSharpenMode {
+    /// Parse a mode name ("gaussian", "lens", "motion").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "gaussian" | "gauss" => Some(Self::Gaussian),
+            "lens" | "defocus" => Some(Self::Lens),
+            "motion" => Some(Self::Motion),
+            _ => None,
+        }
+    }
+}
 
-                for i in 0..steps {
-                    let t = i as f32 - (steps as f32 - 1.0) / 2.0;
-                    let sx = (x as f32 + dx * t).round() as isize;
-                    let sy = (y as f32 + dy * t).round() as isize;
+/// Parameters of [`smart_sharpen_f32`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmartSharpenParams {
+    /// Strength (0.0 = none, 1.0 = full deconvolution / 100% unsharp mask)
+    pub amount: f32,
+    /// Blur radius in pixels (sigma for gaussian, disk radius for lens,
+    /// half the streak length for motion)
+    pub radius: f32,
+    /// Blur to remove
+    pub mode: SmartSharpenMode,
+    /// Direction of the motion in degrees (0 = horizontal), motion mode only
+    pub angle: f32,
+}
 
-                    let sx = sx.clamp(0, width as isize - 1) as usize;
-                    let sy = sy.clamp(0, height as isize - 1) as usize;
+impl Default for SmartSharpenParams {
+    fn default() -> Self {
+        Self { amount: 1.0, radius: 1.0, mode: SmartSharpenMode::Gaussian, angle: 0.0 }
+    }
+}
 
-                    for c in 0..channels {
-                        sum[c] += input[[sy, sx, c]] as f32 * step_weight;
+/// Point spread function as (dy, dx, weight) taps, weights summing to 1.
+fn smart_sharpen_psf(params: &SmartSharpenParams) -> Vec<(isize, isize, f32)> {
+    let radius = params.radius.max(0.0);
+    let mut taps = Vec::new();
+    match params.mode {
+        SmartSharpenMode::Lens => {
+            let r = radius.ceil() as isize;
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    if ((dy * dy + dx * dx) as f32) <= radius * radius {
+                        taps.push((dy, dx, 1.0));
                     }
                 }
-
-                for c in 0..channels {
-                    output[[y, x, c]] = sum[c].clamp(0.0, 255.0) as u8;
-                }
             }
         }
+        SmartSharpenMode::Motion => {
+            // Same sampling as motion_blur with distance 2 * radius
+            let (dy, dx) = params.angle.to_radians().sin_cos();
+            let steps = ((2.0 * radius).ceil() as usize).max(1);
+            for i in 0..steps {
+                let t = i as f32 - (steps as f32 - 1.0) / 2.0;
+                taps.push(((dy * t).round() as isize, (dx * t).round() as isize, 1.0));
+            }
+        }
+        SmartSharpenMode::Gaussian => taps.push((0, 0, 1.0)),
     }
+    let weight = 1.0 / taps.len() as f32;
+    taps.iter_mut().for_each(|tap| tap.2 = weight);
+    taps
+}
 
-    output
+/// Convolve a plane with PSF taps, edges clamped. `flip` mirrors the PSF
+/// (correlation), as the Richardson-Lucy update needs.
+fn convolve_psf(plane: &Array2<f32>, psf: &[(isize, isize, f32)], flip: bool) -> Array2<f32> {
+    let (height, width) = plane.dim();
+    let sign = if flip { -1 } else { 1 };
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        psf.iter()
+            .map(|&(dy, dx, w)| {
+                let sy = (y as isize - sign * dy).clamp(0, height as isize - 1) as usize;
+                let sx = (x as isize - sign * dx).clamp(0, width as isize - 1) as usize;
+                w * plane[[sy, sx]]
+            })
+            .sum()
+    })
 }
 
-/// Apply motion blur - f32 version.
+/// Richardson-Lucy deconvolution of one plane, starting from the observation.
+fn richardson_lucy(observed: &Array2<f32>, psf: &[(isize, isize, f32)], iterations: usize) -> Array2<f32> {
+    let mut estimate = observed.mapv(|v| v.max(RL_EPSILON));
+    for _ in 0..iterations {
+        let reblurred = convolve_psf(&estimate, psf, false);
+        let mut ratio = observed.clone();
+        ratio.zip_mut_with(&reblurred, |r, &b| *r /= b.max(RL_EPSILON));
+        estimate *= &convolve_psf(&ratio, psf, true);
+    }
+    estimate
+}
+
+/// Apply smart sharpen - f32 version.
 ///
-/// For RGBA images, uses premultiplied alpha to prevent transparent pixels
-/// from bleeding into the result.
+/// Removes a known blur instead of only raising edge contrast. Gaussian
+/// mode is an unsharp mask with `radius` as sigma; lens and motion mode run
+/// [`SMART_SHARPEN_ITERATIONS`] Richardson-Lucy deconvolution steps with a
+/// disk or line point spread function. `amount` blends between the input
+/// and the restored image.
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
-/// * `angle` - Direction of motion in degrees (0 = horizontal right)
-/// * `distance` - Blur distance in pixels
+/// * `params` - Amount, radius, mode and motion angle
 ///
 /// # Returns
-/// Motion-blurred image with same channel count
-pub fn motion_blur_f32(input: ArrayView3<f32>, angle: f32, distance: f32) -> Array3<f32> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
-
-    let angle_rad = angle.to_radians();
-    let dx = angle_rad.cos();
-    let dy = angle_rad.sin();
-
-    let steps = (distance.abs().ceil() as usize).max(1);
-    let step_weight = 1.0 / steps as f32;
-    let has_alpha = channels == 4;
+/// Sharpened image with same channel count, alpha preserved
+pub fn smart_sharpen_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, params: &SmartSharpenParams) -> Array3<f32> {
+    let input = input.into();
+    if params.mode == SmartSharpenMode::Gaussian {
+        return unsharp_mask_f32(input, params.amount, params.radius, 0.0);
+    }
+    let channels = input.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let psf = smart_sharpen_psf(params);
+    let mut output = input.to_owned();
+
+    for c in 0..color_channels {
+        let observed = input.index_axis(ndarray::Axis(2), c).to_owned();
+        let restored = richardson_lucy(&observed, &psf, SMART_SHARPEN_ITERATIONS);
+        let mut plane = output.index_axis_mut(ndarray::Axis(2), c);
+        plane.zip_mut_with(&restored, |v, &r| *v = (*v + params.amount * (r - *v)).clamp(0.0, 1.0));
+    }
+    output
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            if has_alpha {
-                let mut sum_rgb = [0.0f32; 3];
-                let mut sum_alpha = 0.0f32;
+/// Apply smart sharpen - u8 version.
+pub fn smart_sharpen_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, params: &SmartSharpenParams) -> Array3<u8> {
+    let input = input.into();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    smart_sharpen_f32(input_f32.view(), params).mapv(|v| (v * 255.0).round() as u8)
+}
 
-                for i in 0..steps {
-                    let t = i as f32 - (steps as f32 - 1.0) / 2.0;
-                    let sx = (x as f32 + dx * t).round() as isize;
-                    let sy = (y as f32 + dy * t).round() as isize;
+// ============================================================================
+// Motion Blur & Spin Blur
+// ============================================================================
 
-                    let sx = sx.clamp(0, width as isize - 1) as usize;
-                    let sy = sy.clamp(0, height as isize - 1) as usize;
+/// Upper bound of samples per pixel along a blur path.
+const MAX_PATH_SAMPLES: usize = 512;
 
-                    let a = input[[sy, sx, 3]];
-                    sum_alpha += a * step_weight;
-                    for c in 0..3 {
-                        // Premultiplied: RGB * alpha
-                        sum_rgb[c] += input[[sy, sx, c]] * a * step_weight;
-                    }
+/// Add a bilinear sample at (fy, fx) to `sum`, weighted by `weight`.
+///
+/// Color is premultiplied by alpha for RGBA (alpha summed in `sum[3]`), so
+/// transparent pixels don't bleed. Taps outside the image follow `border`;
+/// a constant border contributes nothing.
+#[inline]
+fn accumulate_bilinear(input: &ArrayView3<f32>, fy: f32, fx: f32, weight: f32, border: BorderMode, sum: &mut [f32; 4]) {
+    let (height, width, channels) = input.dim();
+    let (y0, x0) = (fy.floor(), fx.floor());
+    let (ty, tx) = (fy - y0, fx - x0);
+    for (dy, wy) in [(0, 1.0 - ty), (1, ty)] {
+        let Some(sy) = border.index(y0 as isize + dy, height) else {
+            continue;
+        };
+        for (dx, wx) in [(0, 1.0 - tx), (1, tx)] {
+            let w = weight * wy * wx;
+            let Some(sx) = border.index(x0 as isize + dx, width).filter(|_| w > 0.0) else {
+                continue;
+            };
+            if channels == 4 {
+                let a = input[[sy, sx, 3]];
+                for c in 0..3 {
+                    sum[c] += w * a * input[[sy, sx, c]];
                 }
+                sum[3] += w * a;
+            } else {
+                for c in 0..channels {
+                    sum[c] += w * input[[sy, sx, c]];
+                }
+            }
+        }
+    }
+}
 
-                // Unpremultiply
-                let final_alpha = sum_alpha.clamp(0.0, 1.0);
-                output[[y, x, 3]] = final_alpha;
-
-                if final_alpha > 0.001 {
+/// Average `path(y, x)` sample positions per pixel, rows in parallel.
+///
+/// `path` returns the number of samples and a function giving sample `i`'s
+/// (fy, fx) position; all samples weigh the same.
+fn blur_along_path<P, S>(input: ArrayView3<f32>, border: BorderMode, path: P) -> Array3<f32>
+where
+    P: Fn(usize, usize) -> (usize, S) + Sync,
+    S: Fn(usize) -> (f32, f32),
+{
+    let (height, width, channels) = input.dim();
+    let mut output = vec![0.0f32; height * width * channels];
+    if width > 0 {
+        output.par_chunks_mut(width * channels).enumerate().for_each(|(y, row)| {
+            for x in 0..width {
+                let (count, position) = path(y, x);
+                let weight = 1.0 / count as f32;
+                let mut sum = [0.0f32; 4];
+                for i in 0..count {
+                    let (fy, fx) = position(i);
+                    accumulate_bilinear(&input, fy, fx, weight, border, &mut sum);
+                }
+                let pixel = &mut row[x * channels..(x + 1) * channels];
+                if channels == 4 {
+                    let alpha = sum[3].clamp(0.0, 1.0);
+                    pixel[3] = alpha;
                     for c in 0..3 {
-                        let unpremultiplied = sum_rgb[c] / final_alpha;
-                        output[[y, x, c]] = unpremultiplied.clamp(0.0, 1.0);
+                        pixel[c] = if alpha > ALPHA_THRESHOLD { (sum[c] / alpha).clamp(0.0, 1.0) } else { 0.0 };
                     }
                 } else {
-                    for c in 0..3 {
-                        output[[y, x, c]] = 0.0;
+                    for c in 0..channels {
+                        pixel[c] = sum[c].clamp(0.0, 1.0);
                     }
                 }
-            } else {
-                let mut sum = vec![0.0f32; channels];
+            }
+        });
+    }
+    Array3::from_shape_vec((height, width, channels), output).expect("output matches input shape")
+}
 
-                for i in 0..steps {
-                    let t = i as f32 - (steps as f32 - 1.0) / 2.0;
-                    let sx = (x as f32 + dx * t).round() as isize;
-                    let sy = (y as f32 + dy * t).round() as isize;
+/// Apply motion blur - f32 version.
+///
+/// Averages bilinear samples spread evenly over a line of `distance` pixels
+/// centered on each pixel, so fractional distances and any angle blur
+/// smoothly. For RGBA images, uses premultiplied alpha to prevent
+/// transparent pixels from bleeding into the result.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `angle` - Direction of motion in degrees (0 = horizontal right)
+/// * `distance` - Blur distance in pixels
+/// * `border` - Handling of samples beyond the image edge (reflect avoids edge streaks)
+///
+/// # Returns
+/// Motion-blurred image with same channel count
+pub fn motion_blur_f32<'a>(
+    input: impl Into<ArrayView3<'a, f32>>,
+    angle: f32,
+    distance: f32,
+    border: BorderMode,
+) -> Array3<f32> {
+    let (dy, dx) = angle.to_radians().sin_cos();
+    let distance = distance.abs();
+    let count = (distance.ceil() as usize).clamp(1, MAX_PATH_SAMPLES);
+    blur_along_path(input.into(), border, |y, x| {
+        let position = move |i: usize| {
+            let t = ((i as f32 + 0.5) / count as f32 - 0.5) * distance;
+            (y as f32 + dy * t, x as f32 + dx * t)
+        };
+        (count, position)
+    })
+}
 
-                    let sx = sx.clamp(0, width as isize - 1) as usize;
-                    let sy = sy.clamp(0, height as isize - 1) as usize;
+/// Apply motion blur - u8 version.
+pub fn motion_blur_u8<'a>(
+    input: impl Into<ArrayView3<'a, u8>>,
+    angle: f32,
+    distance: f32,
+    border: BorderMode,
+) -> Array3<u8> {
+    let input = input.into();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    motion_blur_f32(input_f32.view(), angle, distance, border).mapv(|v| (v * 255.0).round() as u8)
+}
 
-                    for c in 0..channels {
-                        sum[c] += input[[sy, sx, c]] * step_weight;
-                    }
-                }
+/// Parameters of [`spin_blur_f32`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpinBlurParams {
+    /// Rotation swept by the blur in degrees
+    pub angle: f32,
+    /// Center of rotation as a fraction of the width (0.5 = middle)
+    pub center_x: f32,
+    /// Center of rotation as a fraction of the height (0.5 = middle)
+    pub center_y: f32,
+    /// Handling of samples beyond the image edge
+    pub border: BorderMode,
+}
 
-                for c in 0..channels {
-                    output[[y, x, c]] = sum[c].clamp(0.0, 1.0);
-                }
-            }
-        }
+impl Default for SpinBlurParams {
+    fn default() -> Self {
+        Self { angle: 10.0, center_x: 0.5, center_y: 0.5, border: BorderMode::Reflect }
     }
+}
 
-    output
+/// Apply spin (radial rotation) blur around a point - f32 version.
+///
+/// Each pixel averages bilinear samples along the arc it would travel when
+/// the image turns by `angle` degrees around the center, half to each side.
+/// The blur grows with the distance from the center, which stays sharp.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `params` - Sweep angle, center and border mode
+///
+/// # Returns
+/// Spin-blurred image with same channel count
+pub fn spin_blur_f32<'a>(input: impl Into<ArrayView3<'a, f32>>, params: &SpinBlurParams) -> Array3<f32> {
+    let input = input.into();
+    let (height, width, _) = input.dim();
+    let cx = params.center_x * width as f32 - 0.5;
+    let cy = params.center_y * height as f32 - 0.5;
+    let sweep = params.angle.abs().to_radians();
+    blur_along_path(input, params.border, |y, x| {
+        let (ry, rx) = (y as f32 - cy, x as f32 - cx);
+        let arc = (rx * rx + ry * ry).sqrt() * sweep;
+        let count = (arc.ceil() as usize).clamp(1, MAX_PATH_SAMPLES);
+        let position = move |i: usize| {
+            let (sin, cos) = (((i as f32 + 0.5) / count as f32 - 0.5) * sweep).sin_cos();
+            (cy + rx * sin + ry * cos, cx + rx * cos - ry * sin)
+        };
+        (count, position)
+    })
+}
+
+/// Apply spin blur - u8 version.
+pub fn spin_blur_u8<'a>(input: impl Into<ArrayView3<'a, u8>>, params: &SpinBlurParams) -> Array3<u8> {
+    let input = input.into();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    spin_blur_f32(input_f32.view(), params).mapv(|v| (v * 255.0).round() as u8)
 }
 
 #[cfg(test)]
@@ -889,6 +1306,65 @@ mod tests {
         assert!((result[[1, 1, 0]] as i32 - 130).abs() <= 5);
     }
 
+    #[test]
+    fn test_unsharp_mask_params_edge_mask_and_tonal_split() {
+        // Hard black/white step, soft 0.4/0.6 step and faint noise in one row
+        let img = Array3::from_shape_fn((9, 40, 3), |(y, x, _)| match x {
+            0..=9 => 0.0,
+            10..=19 => 1.0,
+            20..=24 => 0.4,
+            25..=29 => 0.6,
+            _ => 0.5 + if (x + y) % 2 == 0 { 0.005 } else { -0.005 },
+        });
+        let plain = UnsharpMaskParams { amount: 1.5, ..Default::default() };
+        assert_eq!(
+            unsharp_mask_params_f32(img.view(), &plain),
+            unsharp_mask_f32(img.view(), 1.5, 1.0, 0.0)
+        );
+
+        let masked = unsharp_mask_params_f32(img.view(), &UnsharpMaskParams { edge_mask: 1.0, ..plain });
+        let plain = unsharp_mask_params_f32(img.view(), &plain);
+        let change = |result: &Array3<f32>, x: usize| (result[[4, x, 0]] - img[[4, x, 0]]).abs();
+        // The soft edge is still sharpened, the noise isn't, and the hard edge
+        // keeps none of the halo
+        assert!(change(&masked, 24) > 0.5 * change(&plain, 24));
+        assert!(change(&masked, 34) < 0.1 * change(&plain, 34));
+        assert!(change(&plain, 21) > 0.01 && change(&masked, 21) < 0.5 * change(&plain, 21));
+
+        let no_shadows = UnsharpMaskParams { amount: 1.5, shadow_amount: 0.0, ..Default::default() };
+        let result = unsharp_mask_params_f32(img.view(), &no_shadows);
+        // The dark side of the soft edge (luma 0.4) is close to the midtones
+        // and keeps most of the sharpening
+        assert!(change(&result, 24) < change(&plain, 24) && change(&result, 24) > 0.5 * change(&plain, 24));
+    }
+
+    #[test]
+    fn test_smart_sharpen_deconvolution_restores_blur() {
+        // A bar blurred by the PSF of each mode gets closer to the original
+        let original = Array3::from_shape_fn((24, 24, 4), |(y, x, c)| match c {
+            3 => 0.8,
+            _ => if (9..15).contains(&x) && (4..20).contains(&y) { 0.9 } else { 0.1 },
+        });
+        for mode in [SmartSharpenMode::Lens, SmartSharpenMode::Motion] {
+            let params = SmartSharpenParams { radius: 2.0, mode, ..Default::default() };
+            let psf = smart_sharpen_psf(&params);
+            let mut blurred = original.clone();
+            for c in 0..3 {
+                let plane = original.index_axis(ndarray::Axis(2), c).to_owned();
+                blurred.index_axis_mut(ndarray::Axis(2), c).assign(&convolve_psf(&plane, &psf, false));
+            }
+            let restored = smart_sharpen_f32(blurred.view(), &params);
+            let error = |img: &Array3<f32>| img.iter().zip(original.iter()).map(|(a, b)| (a - b).abs()).sum::<f32>();
+            assert!(error(&restored) < 0.8 * error(&blurred), "{:?}", mode);
+            assert!(restored.index_axis(ndarray::Axis(2), 3).iter().all(|&a| a == 0.8));
+
+            let none = smart_sharpen_f32(blurred.view(), &SmartSharpenParams { amount: 0.0, ..params });
+            assert_eq!(none, blurred);
+        }
+        assert_eq!(SmartSharpenMode::from_name("Motion"), Some(SmartSharpenMode::Motion));
+        assert_eq!(SmartSharpenMode::from_name("box"), None);
+    }
+
     #[test]
     fn test_high_pass_u8_flat_is_gray() {
         let mut img = Array3::<u8>::zeros((5, 5, 4));
@@ -932,10 +1408,86 @@ mod tests {
             img[[y, 2, 3]] = 255;
         }
 
-        let result = motion_blur_u8(img.view(), 0.0, 3.0);
+        let result = motion_blur_u8(img.view(), 0.0, 3.0, BorderMode::Reflect);
 
         // Blur should spread horizontally
         assert!(result[[1, 1, 0]] > 0); // Left of center has some white
         assert!(result[[1, 3, 0]] > 0); // Right of center has some white
     }
+
+    #[test]
+    fn test_motion_blur_subpixel_and_border() {
+        // A fractional distance blurs partially instead of rounding to whole pixels
+        let mut line = Array3::<f32>::zeros((1, 9, 1));
+        line[[0, 4, 0]] = 1.0;
+        let short = motion_blur_f32(line.view(), 0.0, 1.5, BorderMode::Reflect);
+        assert!(short[[0, 4, 0]] > 0.4 && short[[0, 4, 0]] < 0.8);
+        assert!(short[[0, 3, 0]] > 0.1 && (short[[0, 3, 0]] - short[[0, 5, 0]]).abs() < 1e-5);
+
+        // A bright right edge column: clamping streaks it inwards, wrapping
+        // pulls in the dark left side instead
+        let edge = Array3::from_shape_fn((1, 10, 1), |(_, x, _)| if x == 9 { 1.0 } else { 0.0 });
+        let nearest = motion_blur_f32(edge.view(), 0.0, 6.0, BorderMode::Nearest);
+        let wrap = motion_blur_f32(edge.view(), 0.0, 6.0, BorderMode::Wrap);
+        assert!(nearest[[0, 9, 0]] > 0.5 && wrap[[0, 9, 0]] < 0.25);
+        let zero = motion_blur_f32(edge.view(), 0.0, 6.0, BorderMode::Constant);
+        assert!(zero[[0, 9, 0]] < wrap[[0, 9, 0]] + 1e-5);
+    }
+
+    #[test]
+    fn test_spin_blur_keeps_center_and_blurs_rim() {
+        let img = Array3::from_shape_fn((21, 21, 4), |(y, x, c)| match c {
+            3 => 1.0,
+            _ => if (x / 3 + y / 3) % 2 == 0 { 1.0 } else { 0.0 },
+        });
+        let params = SpinBlurParams { angle: 30.0, ..Default::default() };
+        let result = spin_blur_f32(img.view(), &params);
+        assert_eq!(result[[10, 10, 0]], img[[10, 10, 0]]);
+        let changed = |y: usize, x: usize| (result[[y, x, 0]] - img[[y, x, 0]]).abs();
+        assert!(changed(1, 1) > 0.1 || changed(1, 2) > 0.1);
+        assert!(result.index_axis(ndarray::Axis(2), 3).iter().all(|&a| (a - 1.0).abs() < 1e-5));
+        let none = spin_blur_f32(img.view(), &SpinBlurParams { angle: 0.0, ..params });
+        assert!(none.iter().zip(img.iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_clarity_boosts_midtone_local_contrast() {
+        // Soft vertical step between two midtones, plus a black column
+        let img = Array3::from_shape_fn((8, 24, 3), |(_, x, _)| match x {
+            0 => 0.0,
+            1..=11 => 0.4,
+            _ => 0.6,
+        });
+        let result = clarity_f32(img.view(), 1.0, 4.0);
+        assert!(result[[4, 11, 0]] < 0.4);
+        assert!(result[[4, 12, 0]] > 0.6);
+        // Black has no midtone weight
+        assert_eq!(result[[4, 0, 0]], 0.0);
+
+        let softened = clarity_f32(img.view(), -1.0, 4.0);
+        assert!(softened[[4, 11, 0]] > 0.4);
+    }
+
+    #[test]
+    fn test_texture_smooths_detail_but_keeps_edges() {
+        // Fine ripple (period 6) on the left half, hard step on the right
+        let img = Array3::from_shape_fn((8, 40, 1), |(_, x, _)| {
+            if x < 20 {
+                0.5 + 0.04 * (x as f32 * std::f32::consts::TAU / 6.0).sin()
+            } else if x < 30 {
+                0.2
+            } else {
+                0.8
+            }
+        });
+        let ripple = |a: &Array3<f32>| (6..14).map(|x| (a[[4, x, 0]] - 0.5).abs()).fold(0.0f32, f32::max);
+
+        let smoothed = texture_f32(img.view(), -1.0);
+        assert!(ripple(&smoothed) < ripple(&img) * 0.6);
+        assert!((smoothed[[4, 29, 0]] - 0.2).abs() < 0.03);
+        assert!((smoothed[[4, 30, 0]] - 0.8).abs() < 0.03);
+
+        let boosted = texture_f32(img.view(), 1.0);
+        assert!(ripple(&boosted) > ripple(&img));
+    }
 }
@@ -0,0 +1,119 @@
+/**
+ * Auto-straightening - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - straighten.rs (Rust implementation)
+ * - straighten.py (Python wrapper)
+ *
+ * Estimates the tilt of scans and photos with a Hough transform, rotates
+ * with bicubic sampling and crops to the largest inscribed rectangle.
+ * Angles are in degrees, positive = clockwise.
+ */
+
+import { initSync } from '../wasm/imagestag_rust.js';
+import * as wasm from '../wasm/imagestag_rust.js';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+let wasmInitialized = false;
+
+/**
+ * Initialize WASM module (required before using filter functions).
+ */
+export async function initWasm() {
+    if (wasmInitialized) return;
+    const __filename = fileURLToPath(import.meta.url);
+    const __dirname = path.dirname(__filename);
+    const wasmPath = path.join(__dirname, '..', 'wasm', 'imagestag_rust_bg.wasm');
+    const wasmBuffer = fs.readFileSync(wasmPath);
+    initSync(wasmBuffer);
+    wasmInitialized = true;
+}
+
+/**
+ * Estimate the dominant skew angle (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number} maxAngle - Largest skew considered, in degrees (default 15)
+ * @returns {number} - Skew angle in degrees (positive = clockwise)
+ */
+export function estimateSkewAngle(imageData, maxAngle = 15) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    return wasm.estimate_skew_angle_wasm(new Uint8Array(data.buffer), width, height, channels, maxAngle);
+}
+
+/**
+ * Estimate the dominant skew angle (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @returns {number} - Skew angle in degrees (positive = clockwise)
+ */
+export function estimateSkewAngleF32(imageData, maxAngle = 15) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    return wasm.estimate_skew_angle_f32_wasm(data, width, height, channels, maxAngle);
+}
+
+/**
+ * Rotate by an arbitrary angle and crop away the empty corners (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number} degrees - Rotation angle (positive = clockwise)
+ * @returns {Object} - Cropped image data {data, width, height, channels}
+ */
+export function rotateCrop(imageData, degrees) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const [w, h] = wasm.rotate_crop_size_wasm(width, height, degrees);
+
+    const result = wasm.rotate_crop_wasm(new Uint8Array(data.buffer), width, height, channels, degrees);
+
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: w,
+        height: h,
+        channels
+    };
+}
+
+/**
+ * Rotate by an arbitrary angle and crop away the empty corners (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @returns {Object} - Cropped image data {data, width, height, channels}
+ */
+export function rotateCropF32(imageData, degrees) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const [w, h] = wasm.rotate_crop_size_wasm(width, height, degrees);
+
+    const result = wasm.rotate_crop_f32_wasm(data, width, height, channels, degrees);
+
+    return {
+        data: new Float32Array(result.buffer),
+        width: w,
+        height: h,
+        channels
+    };
+}
+
+/**
+ * Straighten a tilted scan or photo (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number} maxAngle - Largest skew considered, in degrees (default 15)
+ * @returns {Object} - {image: {data, width, height, channels}, angle}
+ */
+export function autoStraighten(imageData, maxAngle = 15) {
+    const angle = estimateSkewAngle(imageData, maxAngle);
+    const image = angle === 0 ? imageData : rotateCrop(imageData, -angle);
+    return { image, angle };
+}
+
+/**
+ * Straighten a tilted scan or photo (f32).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}
+ * @returns {Object} - {image: {data, width, height, channels}, angle}
+ */
+export function autoStraightenF32(imageData, maxAngle = 15) {
+    const angle = estimateSkewAngleF32(imageData, maxAngle);
+    const image = angle === 0 ? imageData : rotateCropF32(imageData, -angle);
+    return { image, angle };
+}
@@ -0,0 +1,416 @@
+//! Automatic straightening of tilted scans and photos.
+//!
+//! Estimates the dominant tilt with a Hough transform restricted to
+//! near-horizontal and near-vertical lines, rotates with bicubic sampling
+//! and crops to the largest axis-aligned rectangle inside the rotated
+//! image, so no empty corners remain.
+//!
+//! ## Supported Formats
+//!
+//! All functions support 1, 3, or 4 channel images in both u8 and f32 formats.
+//! RGBA images are sampled with premultiplied alpha so transparent pixels
+//! do not bleed their color into the result.
+//!
+//! ## Angle Convention
+//!
+//! Angles are in degrees, positive = clockwise (like `rotate`). A skew of
+//! +2° means horizontal lines descend 2° to the right; straightening
+//! rotates the image by -2°.
+
+use ndarray::{s, Array3, ArrayView3};
+use rayon::prelude::*;
+
+use super::edge::blurred_luminance_f32;
+
+/// Angular resolution of the Hough accumulator in degrees.
+const ANGLE_STEP: f32 = 0.1;
+
+/// Pixels vote if their gradient reaches this fraction of the strongest edge.
+const EDGE_FRACTION: f32 = 0.3;
+
+// ============================================================================
+// Skew Estimation
+// ============================================================================
+
+/// Edge pixel relative to the image center and whether it lies on a
+/// near-horizontal line (gradient mostly vertical).
+struct EdgePoint {
+    x: f32,
+    y: f32,
+    horizontal: bool,
+}
+
+fn edge_points(image: ArrayView3<f32>) -> Vec<EdgePoint> {
+    let lum = blurred_luminance_f32(image, 1.0);
+    let (height, width, _) = lum.dim();
+    if width < 3 || height < 3 {
+        return Vec::new();
+    }
+
+    let at = |y: usize, x: usize| lum[[y, x, 0]];
+    let mut gradients = Vec::with_capacity((width - 2) * (height - 2));
+    let mut max_mag = 0.0f32;
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let gx = (at(y - 1, x + 1) + 2.0 * at(y, x + 1) + at(y + 1, x + 1))
+                - (at(y - 1, x - 1) + 2.0 * at(y, x - 1) + at(y + 1, x - 1));
+            let gy = (at(y + 1, x - 1) + 2.0 * at(y + 1, x) + at(y + 1, x + 1))
+                - (at(y - 1, x - 1) + 2.0 * at(y - 1, x) + at(y - 1, x + 1));
+            let mag = gx.hypot(gy);
+            max_mag = max_mag.max(mag);
+            gradients.push((x, y, gx, gy, mag));
+        }
+    }
+    if max_mag < 1e-3 {
+        return Vec::new();
+    }
+
+    let threshold = max_mag * EDGE_FRACTION;
+    let (cx, cy) = ((width - 1) as f32 / 2.0, (height - 1) as f32 / 2.0);
+    gradients
+        .into_iter()
+        .filter(|&(_, _, _, _, mag)| mag >= threshold)
+        .map(|(x, y, gx, gy, _)| EdgePoint {
+            x: x as f32 - cx,
+            y: y as f32 - cy,
+            horizontal: gy.abs() > gx.abs(),
+        })
+        .collect()
+}
+
+/// Estimate the dominant skew angle of an image - f32 version.
+///
+/// Edge pixels vote into a Hough accumulator for every candidate angle in
+/// `[-max_angle, max_angle]`: near-horizontal edges for lines with that
+/// slope, near-vertical edges for lines perpendicular to it. The angle
+/// whose accumulator is most concentrated (largest sum of squared votes)
+/// wins and is refined to sub-step precision.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `max_angle` - Largest skew considered, in degrees (e.g. 15.0)
+///
+/// # Returns
+/// Skew angle in degrees (positive = clockwise), 0.0 if no edges are found
+pub fn estimate_skew_angle_f32(image: ArrayView3<f32>, max_angle: f32) -> f32 {
+    let points = edge_points(image);
+    let steps = (max_angle.abs() / ANGLE_STEP).round() as i32;
+    if points.is_empty() || steps == 0 {
+        return 0.0;
+    }
+
+    let (height, width, _) = image.dim();
+    let half_diag = ((width * width + height * height) as f32).sqrt() / 2.0;
+    let bins = (2.0 * half_diag).ceil() as usize + 2;
+
+    let scores: Vec<f64> = (-steps..=steps)
+        .into_par_iter()
+        .map(|i| {
+            let (sin, cos) = (i as f32 * ANGLE_STEP).to_radians().sin_cos();
+            let mut horizontal = vec![0u32; bins];
+            let mut vertical = vec![0u32; bins];
+            for p in &points {
+                // Normal of a line at the skew angle is (-sin, cos), of its perpendicular (cos, sin)
+                if p.horizontal {
+                    horizontal[(-p.x * sin + p.y * cos + half_diag).round() as usize] += 1;
+                } else {
+                    vertical[(p.x * cos + p.y * sin + half_diag).round() as usize] += 1;
+                }
+            }
+            horizontal.iter().chain(vertical.iter()).map(|&v| (v as f64) * (v as f64)).sum()
+        })
+        .collect();
+
+    let best = scores
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(steps as usize);
+
+    // Parabolic refinement between neighboring angles
+    let mut offset = 0.0;
+    if best > 0 && best + 1 < scores.len() {
+        let (l, c, r) = (scores[best - 1], scores[best], scores[best + 1]);
+        let denom = l - 2.0 * c + r;
+        if denom < 0.0 {
+            offset = (0.5 * (l - r) / denom).clamp(-0.5, 0.5) as f32;
+        }
+    }
+    ((best as i32 - steps) as f32 + offset) * ANGLE_STEP
+}
+
+/// Estimate the dominant skew angle of an image - u8 version.
+pub fn estimate_skew_angle_u8(image: ArrayView3<u8>, max_angle: f32) -> f32 {
+    let input = image.mapv(|v| v as f32 / 255.0);
+    estimate_skew_angle_f32(input.view(), max_angle)
+}
+
+// ============================================================================
+// Bicubic Rotation
+// ============================================================================
+
+/// Catmull-Rom cubic weight (a = -0.5).
+fn cubic(t: f32) -> f32 {
+    let t = t.abs();
+    if t < 1.0 {
+        (1.5 * t - 2.5) * t * t + 1.0
+    } else if t < 2.0 {
+        ((-0.5 * t + 2.5) * t - 4.0) * t + 2.0
+    } else {
+        0.0
+    }
+}
+
+/// Resample an image through an inverse mapping with bicubic sampling.
+///
+/// `map(x, y)` returns the source position of output pixel (x, y); pixels
+/// mapping outside the source become 0 (transparent for RGBA). RGBA is
+/// sampled premultiplied. Shared with the perspective warp.
+pub(crate) fn warp_bicubic<F>(input: ArrayView3<f32>, out_w: usize, out_h: usize, map: F) -> Array3<f32>
+where
+    F: Fn(f32, f32) -> (f32, f32) + Sync,
+{
+    let (height, width, channels) = input.dim();
+    let has_alpha = channels == 4;
+    let color_channels = if has_alpha { 3 } else { channels };
+    if out_w == 0 || out_h == 0 || width == 0 || height == 0 {
+        return Array3::zeros((out_h, out_w, channels));
+    }
+
+    let mut work = input.to_owned();
+    if has_alpha {
+        for mut pixel in work.lanes_mut(ndarray::Axis(2)) {
+            let alpha = pixel[3];
+            for c in 0..3 {
+                pixel[c] *= alpha;
+            }
+        }
+    }
+
+    let mut output = vec![0.0f32; out_h * out_w * channels];
+    output.par_chunks_mut(out_w * channels).enumerate().for_each(|(y, row)| {
+        for x in 0..out_w {
+            let (sx, sy) = map(x as f32, y as f32);
+            if !(sx >= -0.5 && sy >= -0.5 && sx <= width as f32 - 0.5 && sy <= height as f32 - 0.5) {
+                continue;
+            }
+
+            let (x0, y0) = (sx.floor() as isize, sy.floor() as isize);
+            let (fx, fy) = (sx - x0 as f32, sy - y0 as f32);
+            let wx = [cubic(fx + 1.0), cubic(fx), cubic(1.0 - fx), cubic(2.0 - fx)];
+            let wy = [cubic(fy + 1.0), cubic(fy), cubic(1.0 - fy), cubic(2.0 - fy)];
+            let pixel = &mut row[x * channels..(x + 1) * channels];
+            for (j, wy) in wy.iter().enumerate() {
+                let py = (y0 - 1 + j as isize).clamp(0, height as isize - 1) as usize;
+                for (i, wx) in wx.iter().enumerate() {
+                    let px = (x0 - 1 + i as isize).clamp(0, width as isize - 1) as usize;
+                    let weight = wx * wy;
+                    for (c, out) in pixel.iter_mut().enumerate() {
+                        *out += weight * work[[py, px, c]];
+                    }
+                }
+            }
+        }
+    });
+
+    let mut result = Array3::from_shape_vec((out_h, out_w, channels), output).unwrap();
+    for mut pixel in result.lanes_mut(ndarray::Axis(2)) {
+        let alpha = if has_alpha { pixel[3].clamp(0.0, 1.0) } else { 1.0 };
+        if has_alpha {
+            pixel[3] = alpha;
+        }
+        for c in 0..color_channels {
+            pixel[c] = if alpha > 0.0 { (pixel[c] / alpha).clamp(0.0, 1.0) } else { 0.0 };
+        }
+    }
+    result
+}
+
+fn rotate_impl(input: ArrayView3<f32>, degrees: f32) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (cx, cy) = ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0);
+    // Inverse mapping: rotate the output position back by -degrees
+    warp_bicubic(input, width, height, |x, y| {
+        let (dx, dy) = (x - cx, y - cy);
+        (cx + dx * cos + dy * sin, cy - dx * sin + dy * cos)
+    })
+}
+
+/// Rotate an image by an arbitrary angle with bicubic sampling - f32 version.
+///
+/// Rotates around the image center on a canvas of the same size. Areas
+/// outside the source become 0 (transparent for RGBA).
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `degrees` - Rotation angle in degrees (positive = clockwise)
+///
+/// # Returns
+/// Rotated image with the same dimensions
+pub fn rotate_bicubic_f32(image: ArrayView3<f32>, degrees: f32) -> Array3<f32> {
+    rotate_impl(image, degrees)
+}
+
+/// Rotate an image by an arbitrary angle with bicubic sampling - u8 version.
+pub fn rotate_bicubic_u8(image: ArrayView3<u8>, degrees: f32) -> Array3<u8> {
+    let input = image.mapv(|v| v as f32 / 255.0);
+    rotate_impl(input.view(), degrees).mapv(|v| (v * 255.0 + 0.5) as u8)
+}
+
+// ============================================================================
+// Rotate and Crop
+// ============================================================================
+
+/// Size of the largest axis-aligned rectangle inside a `width` x `height`
+/// image rotated by `degrees`, centered on the image.
+///
+/// # Returns
+/// (width, height), each at least 1 and at most the original size
+pub fn inscribed_size(width: usize, height: usize, degrees: f32) -> (usize, usize) {
+    if width == 0 || height == 0 {
+        return (width, height);
+    }
+    let (w, h) = (width as f64, height as f64);
+    let angle = (degrees as f64).to_radians();
+    let (sin, cos) = (angle.sin().abs(), angle.cos().abs());
+    let (long, short) = if w >= h { (w, h) } else { (h, w) };
+
+    let (crop_w, crop_h) = if short <= 2.0 * sin * cos * long || (sin - cos).abs() < 1e-10 {
+        // Half constrained: two crop corners touch the longer side
+        let x = 0.5 * short;
+        if w >= h { (x / sin, x / cos) } else { (x / cos, x / sin) }
+    } else {
+        // Fully constrained: all four crop corners touch the rotated sides
+        let cos_2a = cos * cos - sin * sin;
+        ((w * cos - h * sin) / cos_2a, (h * cos - w * sin) / cos_2a)
+    };
+    let fit = |v: f64, max: usize| ((v + 1e-6).floor() as usize).clamp(1, max);
+    (fit(crop_w, width), fit(crop_h, height))
+}
+
+fn crop_center<T: Clone>(image: Array3<T>, crop_w: usize, crop_h: usize) -> Array3<T> {
+    let (height, width, _) = image.dim();
+    let (x0, y0) = ((width - crop_w) / 2, (height - crop_h) / 2);
+    image.slice(s![y0..y0 + crop_h, x0..x0 + crop_w, ..]).to_owned()
+}
+
+/// Rotate by an arbitrary angle and crop away the empty corners - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `degrees` - Rotation angle in degrees (positive = clockwise)
+///
+/// # Returns
+/// Rotated image cropped to `inscribed_size(width, height, degrees)`
+pub fn rotate_crop_f32(image: ArrayView3<f32>, degrees: f32) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let (crop_w, crop_h) = inscribed_size(width, height, degrees);
+    crop_center(rotate_bicubic_f32(image, degrees), crop_w, crop_h)
+}
+
+/// Rotate by an arbitrary angle and crop away the empty corners - u8 version.
+pub fn rotate_crop_u8(image: ArrayView3<u8>, degrees: f32) -> Array3<u8> {
+    let (height, width, _) = image.dim();
+    let (crop_w, crop_h) = inscribed_size(width, height, degrees);
+    crop_center(rotate_bicubic_u8(image, degrees), crop_w, crop_h)
+}
+
+// ============================================================================
+// Auto-Straighten
+// ============================================================================
+
+/// Straighten a tilted scan or photo in one call - f32 version.
+///
+/// Estimates the skew with `estimate_skew_angle_f32`, rotates it away and
+/// crops to the largest inscribed rectangle.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `max_angle` - Largest skew considered, in degrees (e.g. 15.0)
+///
+/// # Returns
+/// (straightened image, detected skew angle in degrees)
+pub fn auto_straighten_f32(image: ArrayView3<f32>, max_angle: f32) -> (Array3<f32>, f32) {
+    let angle = estimate_skew_angle_f32(image, max_angle);
+    if angle == 0.0 {
+        return (image.to_owned(), angle);
+    }
+    (rotate_crop_f32(image, -angle), angle)
+}
+
+/// Straighten a tilted scan or photo in one call - u8 version.
+pub fn auto_straighten_u8(image: ArrayView3<u8>, max_angle: f32) -> (Array3<u8>, f32) {
+    let angle = estimate_skew_angle_u8(image, max_angle);
+    if angle == 0.0 {
+        return (image.to_owned(), angle);
+    }
+    (rotate_crop_u8(image, -angle), angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Horizontal stripes tilted clockwise by `degrees`.
+    fn tilted_stripes(width: usize, height: usize, degrees: f32) -> Array3<f32> {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+        Array3::from_shape_fn((height, width, 3), |(y, x, _)| {
+            let d = -(x as f32 - cx) * sin + (y as f32 - cy) * cos;
+            if d.rem_euclid(16.0) < 4.0 { 0.0 } else { 1.0 }
+        })
+    }
+
+    #[test]
+    fn test_estimate_skew_angle() {
+        for angle in [4.0, -2.5, 0.0] {
+            let image = tilted_stripes(160, 120, angle);
+            let estimate = estimate_skew_angle_f32(image.view(), 10.0);
+            assert!((estimate - angle).abs() < 0.3, "expected {angle}, got {estimate}");
+        }
+        assert_eq!(estimate_skew_angle_f32(Array3::zeros((20, 20, 1)).view(), 10.0), 0.0);
+    }
+
+    #[test]
+    fn test_rotate_bicubic_identity_and_alpha() {
+        let image = Array3::from_shape_fn((9, 11, 4), |(y, x, c)| if c == 3 { 1.0 } else { ((x + 2 * y + c) % 7) as f32 / 6.0 });
+        let rotated = rotate_bicubic_f32(image.view(), 0.0);
+        assert!(rotated.iter().zip(image.iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+
+        // Corners fall outside the source at 45° and become transparent
+        let rotated = rotate_bicubic_f32(image.view(), 45.0);
+        assert_eq!(rotated[[0, 0, 3]], 0.0);
+        assert!(rotated[[4, 5, 3]] > 0.99);
+    }
+
+    #[test]
+    fn test_inscribed_size() {
+        assert_eq!(inscribed_size(200, 100, 0.0), (200, 100));
+        let (w, h) = inscribed_size(200, 100, 5.0);
+        assert!(w < 200 && h < 100 && w > 170 && h > 80);
+        assert_eq!(inscribed_size(200, 100, 5.0), inscribed_size(200, 100, -5.0));
+
+        // The crop leaves no empty corners
+        let opaque = Array3::from_elem((60, 90, 4), 1.0f32);
+        let cropped = rotate_crop_f32(opaque.view(), 7.0);
+        assert!(cropped.iter().all(|&v| v > 0.99));
+    }
+
+    #[test]
+    fn test_auto_straighten() {
+        let image = tilted_stripes(160, 120, 3.0);
+        let (straight, angle) = auto_straighten_f32(image.view(), 10.0);
+        assert!((angle - 3.0).abs() < 0.3);
+        assert_eq!(straight.dim(), {
+            let (w, h) = inscribed_size(160, 120, -angle);
+            (h, w, 3)
+        });
+        assert!(estimate_skew_angle_f32(straight.view(), 10.0).abs() < 0.3);
+
+        let u8_image = image.mapv(|v| (v * 255.0) as u8);
+        let (_, angle_u8) = auto_straighten_u8(u8_image.view(), 10.0);
+        assert!((angle_u8 - angle).abs() < 0.2);
+    }
+}
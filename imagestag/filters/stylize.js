@@ -5,7 +5,8 @@
  * - stylize.rs (Rust implementation)
  * - stylize.py (Python wrapper)
  *
- * Provides: posterize, solarize, threshold, emboss
+ * Provides: posterize, solarize, threshold, threshold_otsu, adaptive_threshold,
+ * emboss, xdog, pixelate, vignette
  */
 
 import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
@@ -85,6 +86,46 @@ export const threshold_f32 = createF32Filter(
     (opts) => [(opts.threshold ?? 128) / 255.0]
 );
 
+/**
+ * Compute the Otsu threshold of the luminance (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @returns {number} - Threshold (0-255) for `threshold`
+ */
+export function threshold_otsu(imageData) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    return wasm.threshold_otsu_wasm(new Uint8Array(data.buffer), width, height, channels);
+}
+
+/**
+ * Compute the Otsu threshold of the luminance (f32).
+ * @returns {number} - Threshold (0.0-1.0) for `threshold_f32`
+ */
+export function threshold_otsu_f32(imageData) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    return wasm.threshold_otsu_f32_wasm(data, width, height, channels);
+}
+
+/**
+ * Binarize against the local mean (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {blockSize, c (0-255 units), method: 'mean'|'gaussian'}
+ * @returns {Object} - Thresholded image data
+ */
+export const adaptive_threshold = createU8Filter(
+    wasm.adaptive_threshold_wasm,
+    (opts) => [opts.blockSize ?? 11, opts.c ?? 5, opts.method ?? 'mean']
+);
+
+/**
+ * Binarize against the local mean (f32). `c` is in 0.0-1.0 units.
+ */
+export const adaptive_threshold_f32 = createF32Filter(
+    wasm.adaptive_threshold_f32_wasm,
+    (opts) => [opts.blockSize ?? 11, opts.c ?? 0.02, opts.method ?? 'mean']
+);
+
 // ============================================================================
 // Emboss
 // ============================================================================
@@ -109,10 +150,98 @@ export const emboss_f32 = createF32Filter(
     (opts) => [opts.angle ?? 45.0, opts.depth ?? 1.0]
 );
 
+// ============================================================================
+// XDoG
+// ============================================================================
+
+const xdogOptions = (opts) => [Object.assign(new wasm.XdogOptions(), {
+    sigma: opts.sigma ?? 1.0,
+    k: opts.k ?? 1.6,
+    p: opts.p ?? 20.0,
+    epsilon: opts.epsilon ?? 0.1,
+    phi: opts.phi ?? 10.0,
+})];
+
+/**
+ * Apply XDoG line-art / sketch effect (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {sigma, k, p, epsilon, phi}
+ * @returns {Object} - Grayscale sketch image data
+ */
+export const xdog = createU8Filter(wasm.xdog_wasm, xdogOptions);
+
+/**
+ * Apply XDoG line-art / sketch effect (f32).
+ */
+export const xdog_f32 = createF32Filter(wasm.xdog_f32_wasm, xdogOptions);
+
+// ============================================================================
+// Pixelate
+// ============================================================================
+
+/**
+ * Pixelate with square, circle or hexagon cells (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {blockSize, shape: 'square'|'circle'|'hexagon',
+ *   mask: Uint8Array (width * height, 255 = pixelated) or null for everything}
+ * @returns {Object} - Pixelated image data
+ */
+export const pixelate = createU8Filter(
+    wasm.pixelate_shaped_wasm,
+    (opts) => [opts.blockSize ?? 8, opts.shape ?? 'square', opts.mask ?? new Uint8Array(0)]
+);
+
+/**
+ * Pixelate with square, circle or hexagon cells (f32). `mask` is a Float32Array.
+ */
+export const pixelate_f32 = createF32Filter(
+    wasm.pixelate_shaped_f32_wasm,
+    (opts) => [opts.blockSize ?? 8, opts.shape ?? 'square', opts.mask ?? new Float32Array(0)]
+);
+
+// ============================================================================
+// Vignette
+// ============================================================================
+
+const vignetteOptions = (opts, colorScale) => {
+    const color = opts.color ?? null;
+    return [Object.assign(new wasm.VignetteOptions(), {
+        amount: opts.amount ?? 0.5,
+        center_x: opts.centerX ?? 0.5,
+        center_y: opts.centerY ?? 0.5,
+        midpoint: opts.midpoint ?? 0.0,
+        roundness: opts.roundness ?? 1.0,
+        feather: opts.feather ?? 1.0,
+        colored: color !== null,
+        color_r: color ? color[0] / colorScale : 0,
+        color_g: color ? color[1] / colorScale : 0,
+        color_b: color ? color[2] / colorScale : 0,
+    })];
+};
+
+/**
+ * Apply vignette (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {amount, centerX, centerY, midpoint, roundness, feather,
+ *   color: [r, g, b] (0-255) or null to darken}
+ * @returns {Object} - Vignetted image data
+ */
+export const vignette = createU8Filter(wasm.vignette_params_wasm, (opts) => vignetteOptions(opts, 255));
+
+/**
+ * Apply vignette (f32). `color` is 0.0-1.0.
+ */
+export const vignette_f32 = createF32Filter(wasm.vignette_params_f32_wasm, (opts) => vignetteOptions(opts, 1));
+
 export default {
     initWasm,
     posterize, posterize_f32,
     solarize, solarize_f32,
     threshold, threshold_f32,
-    emboss, emboss_f32
+    threshold_otsu, threshold_otsu_f32,
+    adaptive_threshold, adaptive_threshold_f32,
+    emboss, emboss_f32,
+    xdog, xdog_f32,
+    pixelate, pixelate_f32,
+    vignette, vignette_f32
 };
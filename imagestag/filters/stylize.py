@@ -37,9 +37,13 @@ import numpy as np
 import imagestag_rust
 
 
-def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
-    """Validate image shape and dtype."""
-    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str, frames: bool = False) -> None:
+    """Validate image shape and dtype.
+
+    With ``frames``, an (N, H, W, C) stack of animation frames is accepted too.
+    """
+    ndims = (3, 4) if frames else (3,)
+    if image.ndim not in ndims or image.shape[-1] not in (1, 3, 4):
         raise ValueError(f"Expected image (H, W, 1|3|4), got shape {image.shape}")
     if image.dtype != expected_dtype:
         raise ValueError(f"Expected {expected_dtype} dtype, got {image.dtype}")
@@ -61,7 +65,7 @@ def posterize(image: np.ndarray, levels: int = 4) -> np.ndarray:
     Returns:
         Posterized uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "posterize")
+    _validate_image(image, np.uint8, "posterize", frames=True)
     return imagestag_rust.posterize(image, levels)
 
 
@@ -75,7 +79,7 @@ def posterize_f32(image: np.ndarray, levels: int = 4) -> np.ndarray:
     Returns:
         Posterized float32 array with same channel count
     """
-    _validate_image(image, np.float32, "posterize_f32")
+    _validate_image(image, np.float32, "posterize_f32", frames=True)
     return imagestag_rust.posterize_f32(image, levels)
 
 
@@ -95,7 +99,7 @@ def solarize(image: np.ndarray, threshold: int = 128) -> np.ndarray:
     Returns:
         Solarized uint8 array with same channel count
     """
-    _validate_image(image, np.uint8, "solarize")
+    _validate_image(image, np.uint8, "solarize", frames=True)
     return imagestag_rust.solarize(image, threshold)
 
 
@@ -109,7 +113,7 @@ def solarize_f32(image: np.ndarray, threshold: float = 0.5) -> np.ndarray:
     Returns:
         Solarized float32 array with same channel count
     """
-    _validate_image(image, np.float32, "solarize_f32")
+    _validate_image(image, np.float32, "solarize_f32", frames=True)
     return imagestag_rust.solarize_f32(image, threshold)
 
 
@@ -129,7 +133,7 @@ def threshold(image: np.ndarray, threshold_val: int = 128) -> np.ndarray:
     Returns:
         Thresholded uint8 array (black or white)
     """
-    _validate_image(image, np.uint8, "threshold")
+    _validate_image(image, np.uint8, "threshold", frames=True)
     return imagestag_rust.threshold(image, threshold_val)
 
 
@@ -143,10 +147,76 @@ def threshold_f32(image: np.ndarray, threshold_val: float = 0.5) -> np.ndarray:
     Returns:
         Thresholded float32 array (0.0 or 1.0)
     """
-    _validate_image(image, np.float32, "threshold_f32")
+    _validate_image(image, np.float32, "threshold_f32", frames=True)
     return imagestag_rust.threshold_f32(image, threshold_val)
 
 
+def threshold_otsu(image: np.ndarray) -> int:
+    """Compute the Otsu threshold of the luminance (u8).
+
+    Picks the value that best splits the histogram into dark and bright
+    classes. Fully transparent pixels are ignored.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+
+    Returns:
+        Threshold (0-255) to pass to threshold()
+    """
+    _validate_image(image, np.uint8, "threshold_otsu")
+    return imagestag_rust.threshold_otsu(image)
+
+
+def threshold_otsu_f32(image: np.ndarray) -> float:
+    """Compute the Otsu threshold of the luminance (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+
+    Returns:
+        Threshold (0.0-1.0) to pass to threshold_f32()
+    """
+    _validate_image(image, np.float32, "threshold_otsu_f32")
+    return imagestag_rust.threshold_otsu_f32(image)
+
+
+def adaptive_threshold(image: np.ndarray, block_size: int = 11, c: float = 5.0,
+                       method: str = "mean") -> np.ndarray:
+    """Binarize against the local mean, e.g. for documents (u8).
+
+    A pixel becomes white when its luminance is above the mean of its
+    block_size x block_size neighborhood minus c.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        block_size: Neighborhood size in pixels (odd, >= 3)
+        c: Constant subtracted from the local mean (0-255 units)
+        method: 'mean' or 'gaussian'
+
+    Returns:
+        Thresholded uint8 array (black or white)
+    """
+    _validate_image(image, np.uint8, "adaptive_threshold")
+    return imagestag_rust.adaptive_threshold(image, block_size, c, method)
+
+
+def adaptive_threshold_f32(image: np.ndarray, block_size: int = 11, c: float = 0.02,
+                           method: str = "mean") -> np.ndarray:
+    """Binarize against the local mean (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        block_size: Neighborhood size in pixels (odd, >= 3)
+        c: Constant subtracted from the local mean (0.0-1.0 units)
+        method: 'mean' or 'gaussian'
+
+    Returns:
+        Thresholded float32 array (0.0 or 1.0)
+    """
+    _validate_image(image, np.float32, "adaptive_threshold_f32")
+    return imagestag_rust.adaptive_threshold_f32(image, block_size, c, method)
+
+
 # ============================================================================
 # Emboss
 # ============================================================================
@@ -187,72 +257,122 @@ def emboss_f32(image: np.ndarray, angle: float = 135.0, depth: float = 1.0) -> n
 # Pixelate
 # ============================================================================
 
-def pixelate(image: np.ndarray, block_size: int = 8) -> np.ndarray:
+def pixelate(
+    image: np.ndarray,
+    block_size: int = 8,
+    shape: str = "square",
+    mask: np.ndarray | None = None,
+) -> np.ndarray:
     """Apply pixelation effect (u8).
 
-    Reduces resolution by averaging pixels within blocks.
+    Reduces resolution by averaging pixels within cells. Colors are
+    alpha-weighted so transparent pixels don't darken a cell.
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
-        block_size: Size of pixel blocks (2-256)
+        block_size: Size of pixel cells (2-256)
+        shape: 'square', 'circle' (dots with black gaps) or 'hexagon'
+        mask: Optional uint8 (H, W) or (H, W, 1) mask restricting the effect,
+            e.g. to censor a face (0 = untouched, 255 = pixelated)
 
     Returns:
         Pixelated uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "pixelate")
-    return imagestag_rust.pixelate(image, block_size)
+    if mask is not None:
+        mask = np.ascontiguousarray(mask.reshape(mask.shape[:2]), dtype=np.uint8)
+    return imagestag_rust.pixelate(image, block_size, shape, mask)
 
 
-def pixelate_f32(image: np.ndarray, block_size: int = 8) -> np.ndarray:
+def pixelate_f32(
+    image: np.ndarray,
+    block_size: int = 8,
+    shape: str = "square",
+    mask: np.ndarray | None = None,
+) -> np.ndarray:
     """Apply pixelation effect (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
-        block_size: Size of pixel blocks (2-256)
+        block_size: Size of pixel cells (2-256)
+        shape: 'square', 'circle' or 'hexagon'
+        mask: Optional float32 (H, W) or (H, W, 1) mask, 0.0-1.0
 
     Returns:
         Pixelated float32 array with same channel count
     """
     _validate_image(image, np.float32, "pixelate_f32")
-    return imagestag_rust.pixelate_f32(image, block_size)
+    if mask is not None:
+        mask = np.ascontiguousarray(mask.reshape(mask.shape[:2]), dtype=np.float32)
+    return imagestag_rust.pixelate_f32(image, block_size, shape, mask)
 
 
 # ============================================================================
 # Vignette
 # ============================================================================
 
-def vignette(image: np.ndarray, amount: float = 0.5) -> np.ndarray:
-    """Apply vignette effect - darken edges (u8).
+def vignette(
+    image: np.ndarray,
+    amount: float = 0.5,
+    center: tuple[float, float] = (0.5, 0.5),
+    midpoint: float = 0.0,
+    roundness: float = 1.0,
+    feather: float = 1.0,
+    color: tuple[int, int, int] | None = None,
+) -> np.ndarray:
+    """Apply vignette effect - darken (or tint) edges (u8).
 
     Args:
         image: uint8 array with 1, 3, or 4 channels (H, W, C)
         amount: Vignette strength (0.0 = none, 1.0+ = strong)
+        center: Vignette center as fractions of (width, height)
+        midpoint: Normalized distance where the falloff starts (0.0 = center,
+            1.0 = corners)
+        roundness: -1.0 = rounded rectangle, 0.0 = ellipse following the
+            frame, 1.0 = circle
+        feather: Width of the falloff (small = hard edge)
+        color: (R, G, B) 0-255 to fade towards, e.g. white; None darkens
 
     Returns:
         Vignetted uint8 array with same channel count
     """
     _validate_image(image, np.uint8, "vignette")
-    return imagestag_rust.vignette(image, amount)
+    return imagestag_rust.vignette(image, amount, center, midpoint, roundness, feather, color)
 
 
-def vignette_f32(image: np.ndarray, amount: float = 0.5) -> np.ndarray:
-    """Apply vignette effect - darken edges (f32).
+def vignette_f32(
+    image: np.ndarray,
+    amount: float = 0.5,
+    center: tuple[float, float] = (0.5, 0.5),
+    midpoint: float = 0.0,
+    roundness: float = 1.0,
+    feather: float = 1.0,
+    color: tuple[float, float, float] | None = None,
+) -> np.ndarray:
+    """Apply vignette effect - darken (or tint) edges (f32).
 
     Args:
         image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
         amount: Vignette strength (0.0 = none, 1.0+ = strong)
+        center: Vignette center as fractions of (width, height)
+        midpoint: Normalized distance where the falloff starts
+        roundness: -1.0 = rounded rectangle, 0.0 = frame ellipse, 1.0 = circle
+        feather: Width of the falloff (small = hard edge)
+        color: (R, G, B) 0.0-1.0 to fade towards; None darkens
 
     Returns:
         Vignetted float32 array with same channel count
     """
     _validate_image(image, np.float32, "vignette_f32")
-    return imagestag_rust.vignette_f32(image, amount)
+    return imagestag_rust.vignette_f32(image, amount, center, midpoint, roundness, feather, color)
 
 
 __all__ = [
     'posterize', 'posterize_f32',
     'solarize', 'solarize_f32',
     'threshold', 'threshold_f32',
+    'threshold_otsu', 'threshold_otsu_f32',
+    'adaptive_threshold', 'adaptive_threshold_f32',
     'emboss', 'emboss_f32',
     'pixelate', 'pixelate_f32',
     'vignette', 'vignette_f32',
@@ -1,4 +1,5 @@
-//! Stylize filters: Posterize, Solarize, Threshold, Emboss, Pixelate, Vignette.
+//! Stylize filters: Posterize, Solarize, Threshold (global, Otsu, adaptive),
+//! Emboss, Pixelate, Vignette, Pencil Sketch, XDoG.
 //!
 //! These are artistic effect filters.
 //! All filters support both u8 (0-255) and f32 (0.0-1.0) modes.
@@ -10,10 +11,15 @@
 //! - **RGB**: (height, width, 3) - processes all 3 channels
 //! - **RGBA**: (height, width, 4) - processes RGB, preserves alpha
 
-use ndarray::{Array3, ArrayView3};
+use std::collections::HashMap;
 
-use super::blur_wasm::gaussian_blur_wasm_u8;
-use super::grayscale::grayscale_u8;
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3};
+
+use super::blur_wasm::{gaussian_blur_wasm_f32, gaussian_blur_wasm_u8};
+use super::core::box_filter_via_integral_f32;
+use super::fmath;
+use super::edge::blurred_luminance_f32;
+use super::grayscale::{grayscale_to_single_channel_f32, grayscale_u8, LumaStandard, LUMA_B, LUMA_G, LUMA_R};
 
 // ============================================================================
 // Posterize
@@ -169,14 +175,14 @@ pub fn solarize_f32(input: ArrayView3<f32>, threshold: f32) -> Array3<f32> {
 /// # Returns
 /// Thresholded image with same channel count (black and white)
 pub fn threshold_u8(input: ArrayView3<u8>, threshold: u8) -> Array3<u8> {
+    threshold_u8_with_luma(input, threshold, LumaStandard::default())
+}
+
+/// Apply binary threshold with the given luma standard - u8 version.
+pub fn threshold_u8_with_luma(input: ArrayView3<u8>, threshold: u8, luma: LumaStandard) -> Array3<u8> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
 
-    // BT.709 luminosity coefficients
-    const LUMA_R: f32 = 0.2126;
-    const LUMA_G: f32 = 0.7152;
-    const LUMA_B: f32 = 0.0722;
-
     for y in 0..height {
         for x in 0..width {
             let lum = if channels == 1 {
@@ -187,7 +193,7 @@ pub fn threshold_u8(input: ArrayView3<u8>, threshold: u8) -> Array3<u8> {
                 let r = input[[y, x, 0]] as f32;
                 let g = input[[y, x, 1]] as f32;
                 let b = input[[y, x, 2]] as f32;
-                (LUMA_R * r + LUMA_G * g + LUMA_B * b) as u8
+                luma.luma(r, g, b) as u8
             };
 
             let v = if lum >= threshold { 255 } else { 0 };
@@ -217,13 +223,14 @@ pub fn threshold_u8(input: ArrayView3<u8>, threshold: u8) -> Array3<u8> {
 /// # Returns
 /// Thresholded image with same channel count (black and white)
 pub fn threshold_f32(input: ArrayView3<f32>, threshold: f32) -> Array3<f32> {
+    threshold_f32_with_luma(input, threshold, LumaStandard::default())
+}
+
+/// Apply binary threshold with the given luma standard - f32 version.
+pub fn threshold_f32_with_luma(input: ArrayView3<f32>, threshold: f32, luma: LumaStandard) -> Array3<f32> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
 
-    const LUMA_R: f32 = 0.2126;
-    const LUMA_G: f32 = 0.7152;
-    const LUMA_B: f32 = 0.0722;
-
     for y in 0..height {
         for x in 0..width {
             let lum = if channels == 1 {
@@ -234,7 +241,7 @@ pub fn threshold_f32(input: ArrayView3<f32>, threshold: f32) -> Array3<f32> {
                 let r = input[[y, x, 0]];
                 let g = input[[y, x, 1]];
                 let b = input[[y, x, 2]];
-                LUMA_R * r + LUMA_G * g + LUMA_B * b
+                luma.luma(r, g, b)
             };
 
             let v = if lum >= threshold { 1.0 } else { 0.0 };
@@ -251,31 +258,179 @@ pub fn threshold_f32(input: ArrayView3<f32>, threshold: f32) -> Array3<f32> {
     output
 }
 
-// ============================================================================
-// Emboss
-// ============================================================================
+/// Luminance plane of an image, values 0.0-1.0.
+fn luminance_plane_f32(input: ArrayView3<f32>) -> Array2<f32> {
+    grayscale_to_single_channel_f32(input).index_axis_move(ndarray::Axis(2), 0)
+}
 
-/// Apply emboss effect - u8 version.
+/// Otsu threshold of a 256-bin histogram: the first bin of the bright class.
+pub(crate) fn otsu_from_histogram(histogram: &[f64; 256]) -> usize {
+    let total: f64 = histogram.iter().sum();
+    let sum_all: f64 = histogram.iter().enumerate().map(|(i, &n)| i as f64 * n).sum();
+    let (mut weight_dark, mut sum_dark) = (0.0, 0.0);
+    let (mut best, mut best_variance) = (0, -1.0);
+
+    for (t, &n) in histogram.iter().enumerate() {
+        weight_dark += n;
+        sum_dark += t as f64 * n;
+        let weight_bright = total - weight_dark;
+        if weight_dark == 0.0 || weight_bright == 0.0 {
+            continue;
+        }
+        let mean_dark = sum_dark / weight_dark;
+        let mean_bright = (sum_all - sum_dark) / weight_bright;
+        let variance = weight_dark * weight_bright * (mean_dark - mean_bright).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best = t + 1;
+        }
+    }
+    best.min(255)
+}
+
+/// Compute the Otsu threshold of the luminance (u8 version).
 ///
-/// Creates a 3D raised effect using directional convolution.
-/// Matches skimage behavior: converts to grayscale first, then applies emboss.
-/// Output is grayscale (same value for all RGB channels).
+/// Picks the threshold that best separates the luminance histogram into
+/// two classes (maximum between-class variance). Fully transparent pixels
+/// are ignored.
+///
+/// # Returns
+/// Threshold to pass to [`threshold_u8`]: pixels >= it are white
+pub fn threshold_otsu_u8(input: ArrayView3<u8>) -> u8 {
+    let (height, width, channels) = input.dim();
+    let mut histogram = [0.0f64; 256];
+    for y in 0..height {
+        for x in 0..width {
+            if channels == 4 && input[[y, x, 3]] == 0 {
+                continue;
+            }
+            let lum = if channels == 1 {
+                input[[y, x, 0]]
+            } else {
+                let [r, g, b] = [0, 1, 2].map(|c| input[[y, x, c]] as f32);
+                (LUMA_R * r + LUMA_G * g + LUMA_B * b) as u8
+            };
+            histogram[lum as usize] += 1.0;
+        }
+    }
+    otsu_from_histogram(&histogram) as u8
+}
+
+/// Compute the Otsu threshold of the luminance (f32 version).
+///
+/// Uses a 256-bin histogram, so the result is a multiple of 1/256.
+///
+/// # Returns
+/// Threshold (0.0-1.0) to pass to [`threshold_f32`]
+pub fn threshold_otsu_f32(input: ArrayView3<f32>) -> f32 {
+    let (height, width, channels) = input.dim();
+    let lum = luminance_plane_f32(input);
+    let mut histogram = [0.0f64; 256];
+    for y in 0..height {
+        for x in 0..width {
+            if channels == 4 && input[[y, x, 3]] <= 0.0 {
+                continue;
+            }
+            let bin = (lum[[y, x]].clamp(0.0, 1.0) * 256.0) as usize;
+            histogram[bin.min(255)] += 1.0;
+        }
+    }
+    otsu_from_histogram(&histogram) as f32 / 256.0
+}
+
+/// How [`adaptive_threshold_f32`] computes the local threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AdaptiveMethod {
+    /// Plain mean of the block
+    #[default]
+    Mean,
+    /// Gaussian-weighted mean of the block
+    Gaussian,
+}
+
+impl AdaptiveMethod {
+    /// Parse a method name. Unknown names map to mean.
+    pub fn parse(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "gaussian" | "gauss" => AdaptiveMethod::Gaussian,
+            _ => AdaptiveMethod::Mean,
+        }
+    }
+}
+
+/// Binarize against the local neighborhood mean (f32 version).
+///
+/// A pixel becomes white when its luminance is above the mean of the
+/// surrounding `block_size` x `block_size` block minus `c`, which handles
+/// uneven lighting (scanned documents, photos of pages).
 ///
 /// # Arguments
-/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
-/// * `angle` - Light source angle in degrees (0-360)
-/// * `depth` - Effect strength (0.0-10.0)
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `block_size` - Neighborhood size in pixels (forced odd, minimum 3)
+/// * `c` - Constant subtracted from the local mean (e.g. 0.02)
+/// * `method` - Plain or Gaussian-weighted mean
 ///
 /// # Returns
-/// Embossed image with same channel count (grayscale values in RGB)
-pub fn emboss_u8(input: ArrayView3<u8>, angle: f32, depth: f32) -> Array3<u8> {
+/// Binary image with same channel count, alpha preserved
+pub fn adaptive_threshold_f32(input: ArrayView3<f32>, block_size: u32, c: f32, method: AdaptiveMethod) -> Array3<f32> {
     let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
+    let block_size = (block_size as usize).max(3) | 1;
+    let lum = luminance_plane_f32(input);
+    let local = match method {
+        AdaptiveMethod::Mean => {
+            let mean = box_filter_via_integral_f32(lum.view().insert_axis(ndarray::Axis(2)), block_size / 2);
+            mean.index_axis_move(ndarray::Axis(2), 0)
+        }
+        AdaptiveMethod::Gaussian => {
+            // Same sigma as OpenCV picks for a kernel of this size
+            let sigma = 0.3 * ((block_size as f32 - 1.0) * 0.5 - 1.0) + 0.8;
+            let blurred = gaussian_blur_wasm_f32(lum.view().insert_axis(ndarray::Axis(2)), sigma);
+            blurred.index_axis_move(ndarray::Axis(2), 0)
+        }
+    };
+
+    let mut output = input.to_owned();
+    let color_channels = if channels == 4 { 3 } else { channels };
+    for y in 0..height {
+        for x in 0..width {
+            let v = if lum[[y, x]] > local[[y, x]] - c { 1.0 } else { 0.0 };
+            for ch in 0..color_channels {
+                output[[y, x, ch]] = v;
+            }
+        }
+    }
+    output
+}
 
-    // BT.709 luminosity coefficients for grayscale conversion
-    const LUMA_R: f32 = 0.2126;
-    const LUMA_G: f32 = 0.7152;
-    const LUMA_B: f32 = 0.0722;
+/// Binarize against the local neighborhood mean (u8 version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `block_size` - Neighborhood size in pixels (forced odd, minimum 3)
+/// * `c` - Constant subtracted from the local mean, in 0-255 units (e.g. 5)
+/// * `method` - Plain or Gaussian-weighted mean
+///
+/// # Returns
+/// Binary image (0 or 255) with same channel count, alpha preserved
+pub fn adaptive_threshold_u8(input: ArrayView3<u8>, block_size: u32, c: f32, method: AdaptiveMethod) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let result = adaptive_threshold_f32(input_f32.view(), block_size, c / 255.0, method);
+    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Emboss
+// ============================================================================
+
+/// Emboss response (0-255 scale, before the +128 offset) of a grayscale plane.
+///
+/// `gray` is premultiplied by alpha so transparent pixels have a defined
+/// value. With `alpha`, the silhouette acts as geometry: where the alpha
+/// edge is stronger than the luminance edge it drives the relief, like the
+/// alpha gradient in the Sobel filters. Borders use reflect padding
+/// (scipy mode='reflect', which repeats the edge pixel for a 3x3 kernel).
+fn emboss_response(gray: &Array2<f32>, alpha: Option<&Array2<f32>>, angle: f32, depth: f32) -> Array2<f32> {
+    let (height, width) = gray.dim();
 
     // Calculate kernel based on angle (matching skimage)
     // Note: scipy.ndimage.convolve flips the kernel (true convolution)
@@ -293,113 +448,93 @@ pub fn emboss_u8(input: ArrayView3<u8>, angle: f32, depth: f32) -> Array3<u8> {
         [-depth * dx, -depth, -depth * dy],
     ];
 
-    // First, convert to grayscale
-    let mut gray = vec![vec![0.0f32; width]; height];
-    for y in 0..height {
-        for x in 0..width {
-            gray[y][x] = if channels == 1 {
-                input[[y, x, 0]] as f32
-            } else {
-                LUMA_R * input[[y, x, 0]] as f32
-                    + LUMA_G * input[[y, x, 1]] as f32
-                    + LUMA_B * input[[y, x, 2]] as f32
-            };
-        }
-    }
-
-    // Apply emboss kernel to grayscale
-    for y in 1..height.saturating_sub(1) {
-        for x in 1..width.saturating_sub(1) {
-            let mut sum = 0.0f32;
-
-            for ky in 0..3 {
-                for kx in 0..3 {
-                    let py = y + ky - 1;
-                    let px = x + kx - 1;
-                    sum += gray[py][px] * kernel[ky][kx];
-                }
-            }
-
-            // Add 128 to center the result around middle gray
-            let v = (sum + 128.0).clamp(0.0, 255.0) as u8;
-
-            // Output grayscale value to all color channels
-            let color_channels = if channels == 4 { 3 } else { channels };
-            for c in 0..color_channels {
-                output[[y, x, c]] = v;
-            }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
+    // Kernel response minus the center pixel: the edge part only
+    let edge = |plane: &Array2<f32>, y: usize, x: usize| {
+        let mut sum = 0.0f32;
+        for (ky, row) in kernel.iter().enumerate() {
+            for (kx, &k) in row.iter().enumerate() {
+                let py = (y + ky).saturating_sub(1).min(height - 1);
+                let px = (x + kx).saturating_sub(1).min(width - 1);
+                sum += plane[[py, px]] * k;
             }
         }
-    }
+        sum - plane[[y, x]]
+    };
+
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let gray_edge = edge(gray, y, x);
+        let relief = match alpha {
+            Some(alpha) => {
+                let alpha_edge = edge(alpha, y, x);
+                if alpha_edge.abs() > gray_edge.abs() { alpha_edge } else { gray_edge }
+            }
+            None => gray_edge,
+        };
+        gray[[y, x]] + relief
+    })
+}
 
-    // Handle edges - output grayscale of original at edges
-    for x in 0..width {
-        let v0 = if channels == 1 {
-            input[[0, x, 0]]
+/// Alpha-premultiplied luminance (0-255 scale) and alpha plane of an image.
+fn emboss_planes<T, F>(input: ArrayView3<T>, luma: LumaStandard, to_255: F) -> (Array2<f32>, Option<Array2<f32>>)
+where
+    T: Copy,
+    F: Fn(T) -> f32,
+{
+    let (height, width, channels) = input.dim();
+    let alpha = (channels == 4).then(|| Array2::from_shape_fn((height, width), |(y, x)| to_255(input[[y, x, 3]])));
+    let gray = Array2::from_shape_fn((height, width), |(y, x)| {
+        let lum = if channels == 1 {
+            to_255(input[[y, x, 0]])
         } else {
-            (LUMA_R * input[[0, x, 0]] as f32
-                + LUMA_G * input[[0, x, 1]] as f32
-                + LUMA_B * input[[0, x, 2]] as f32) as u8
+            luma.luma(to_255(input[[y, x, 0]]), to_255(input[[y, x, 1]]), to_255(input[[y, x, 2]]))
         };
-        let color_channels = if channels == 4 { 3 } else { channels };
-        for c in 0..color_channels {
-            output[[0, x, c]] = v0;
-        }
-        if channels == 4 {
-            output[[0, x, 3]] = input[[0, x, 3]];
+        match &alpha {
+            Some(alpha) => lum * alpha[[y, x]] / 255.0,
+            None => lum,
         }
+    });
+    (gray, alpha)
+}
 
-        if height > 1 {
-            let v_last = if channels == 1 {
-                input[[height - 1, x, 0]]
-            } else {
-                (LUMA_R * input[[height - 1, x, 0]] as f32
-                    + LUMA_G * input[[height - 1, x, 1]] as f32
-                    + LUMA_B * input[[height - 1, x, 2]] as f32) as u8
-            };
-            for c in 0..color_channels {
-                output[[height - 1, x, c]] = v_last;
-            }
-            if channels == 4 {
-                output[[height - 1, x, 3]] = input[[height - 1, x, 3]];
-            }
-        }
+/// Apply emboss effect - u8 version.
+///
+/// Creates a 3D raised effect using directional convolution.
+/// Matches skimage behavior: converts to grayscale first, then applies emboss.
+/// Output is grayscale (same value for all RGB channels). Borders use
+/// reflect padding; with alpha, the layer outline is embossed as well.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `angle` - Light source angle in degrees (0-360)
+/// * `depth` - Effect strength (0.0-10.0)
+///
+/// # Returns
+/// Embossed image with same channel count (grayscale values in RGB)
+pub fn emboss_u8(input: ArrayView3<u8>, angle: f32, depth: f32) -> Array3<u8> {
+    emboss_u8_with_luma(input, angle, depth, LumaStandard::default())
+}
+
+/// Apply emboss effect with the given luma standard - u8 version.
+pub fn emboss_u8_with_luma(input: ArrayView3<u8>, angle: f32, depth: f32, luma: LumaStandard) -> Array3<u8> {
+    let (height, width, channels) = input.dim();
+    let mut output = Array3::<u8>::zeros((height, width, channels));
+    if height == 0 || width == 0 {
+        return output;
     }
-    for y in 0..height {
-        let v0 = if channels == 1 {
-            input[[y, 0, 0]]
-        } else {
-            (LUMA_R * input[[y, 0, 0]] as f32
-                + LUMA_G * input[[y, 0, 1]] as f32
-                + LUMA_B * input[[y, 0, 2]] as f32) as u8
-        };
-        let color_channels = if channels == 4 { 3 } else { channels };
+    let (gray, alpha) = emboss_planes(input, luma, |v| v as f32);
+    let response = emboss_response(&gray, alpha.as_ref(), angle, depth);
+    let color_channels = if channels == 4 { 3 } else { channels };
+
+    for ((y, x), &sum) in response.indexed_iter() {
+        // Add 128 to center the result around middle gray
+        let v = (sum + 128.0).clamp(0.0, 255.0) as u8;
         for c in 0..color_channels {
-            output[[y, 0, c]] = v0;
+            output[[y, x, c]] = v;
         }
         if channels == 4 {
-            output[[y, 0, 3]] = input[[y, 0, 3]];
-        }
-
-        if width > 1 {
-            let v_last = if channels == 1 {
-                input[[y, width - 1, 0]]
-            } else {
-                (LUMA_R * input[[y, width - 1, 0]] as f32
-                    + LUMA_G * input[[y, width - 1, 1]] as f32
-                    + LUMA_B * input[[y, width - 1, 2]] as f32) as u8
-            };
-            for c in 0..color_channels {
-                output[[y, width - 1, c]] = v_last;
-            }
-            if channels == 4 {
-                output[[y, width - 1, 3]] = input[[y, width - 1, 3]];
-            }
+            output[[y, x, 3]] = input[[y, x, 3]];
         }
     }
-
     output
 }
 
@@ -416,138 +551,185 @@ pub fn emboss_u8(input: ArrayView3<u8>, angle: f32, depth: f32) -> Array3<u8> {
 /// # Returns
 /// Embossed image with same channel count (grayscale values in RGB)
 pub fn emboss_f32(input: ArrayView3<f32>, angle: f32, depth: f32) -> Array3<f32> {
+    emboss_f32_with_luma(input, angle, depth, LumaStandard::default())
+}
+
+/// Apply emboss effect with the given luma standard - f32 version.
+pub fn emboss_f32_with_luma(input: ArrayView3<f32>, angle: f32, depth: f32, luma: LumaStandard) -> Array3<f32> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
+    if height == 0 || width == 0 {
+        return output;
+    }
+    // Kernel expects 0-255 scale for skimage compat
+    let (gray, alpha) = emboss_planes(input, luma, |v| v * 255.0);
+    let response = emboss_response(&gray, alpha.as_ref(), angle, depth);
+    let color_channels = if channels == 4 { 3 } else { channels };
 
-    // BT.709 luminosity coefficients
-    const LUMA_R: f32 = 0.2126;
-    const LUMA_G: f32 = 0.7152;
-    const LUMA_B: f32 = 0.0722;
+    for ((y, x), &sum) in response.indexed_iter() {
+        // Add 128 and convert back to 0-1 range
+        let v = ((sum + 128.0) / 255.0).clamp(0.0, 1.0);
+        for c in 0..color_channels {
+            output[[y, x, c]] = v;
+        }
+        if channels == 4 {
+            output[[y, x, 3]] = input[[y, x, 3]];
+        }
+    }
+    output
+}
 
-    // Calculate kernel based on angle (matching skimage)
-    // Note: scipy.ndimage.convolve flips the kernel (true convolution)
-    // So we pre-flip it here to match skimage's result
-    let rad = angle.to_radians();
-    let dx = rad.cos();
-    let dy = rad.sin();
+// ============================================================================
+// Pixelate
+// ============================================================================
 
-    // Emboss kernel - flipped to match scipy's convolve behavior
-    // Original skimage kernel: [[-dy*d, -d, -dx*d], [-1, 1, 1], [dx*d, d, dy*d]]
-    // Flipped (180 rotation): [[dy*d, d, dx*d], [1, 1, -1], [-dx*d, -d, -dy*d]]
-    let kernel: [[f32; 3]; 3] = [
-        [depth * dy, depth, depth * dx],
-        [1.0, 1.0, -1.0],
-        [-depth * dx, -depth, -depth * dy],
-    ];
+/// Cell shape of the pixelate mosaic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PixelateShape {
+    /// Square blocks on a grid
+    #[default]
+    Square,
+    /// Round dots on a square grid, black between the dots
+    Circle,
+    /// Hexagonal cells (pointy top) without gaps
+    Hexagon,
+}
 
-    // First, convert to grayscale (0-1 range)
-    let mut gray = vec![vec![0.0f32; width]; height];
-    for y in 0..height {
-        for x in 0..width {
-            gray[y][x] = if channels == 1 {
-                input[[y, x, 0]]
-            } else {
-                LUMA_R * input[[y, x, 0]] + LUMA_G * input[[y, x, 1]] + LUMA_B * input[[y, x, 2]]
-            };
+impl PixelateShape {
+    /// Parse a shape name. Unknown names map to square.
+    pub fn parse(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "circle" | "round" | "dots" => PixelateShape::Circle,
+            "hexagon" | "hex" => PixelateShape::Hexagon,
+            _ => PixelateShape::Square,
         }
     }
 
-    // Apply emboss kernel to grayscale
-    for y in 1..height.saturating_sub(1) {
-        for x in 1..width.saturating_sub(1) {
-            let mut sum = 0.0f32;
-
-            for ky in 0..3 {
-                for kx in 0..3 {
-                    let py = y + ky - 1;
-                    let px = x + kx - 1;
-                    // gray is in 0-1 range, kernel expects 0-255 scale for skimage compat
-                    sum += gray[py][px] * 255.0 * kernel[ky][kx];
-                }
-            }
-
-            // Add 128 and convert back to 0-1 range
-            let v = ((sum + 128.0) / 255.0).clamp(0.0, 1.0);
-
-            // Output grayscale value to all color channels
-            let color_channels = if channels == 4 { 3 } else { channels };
-            for c in 0..color_channels {
-                output[[y, x, c]] = v;
+    /// Cell index of pixel (x, y), `None` for the gaps between circles.
+    fn cell(self, x: usize, y: usize, block_size: usize) -> Option<(i64, i64)> {
+        let bs = block_size as f32;
+        match self {
+            PixelateShape::Square => Some(((y / block_size) as i64, (x / block_size) as i64)),
+            PixelateShape::Circle => {
+                let (row, col) = (y / block_size, x / block_size);
+                let dx = x as f32 + 0.5 - (col as f32 + 0.5) * bs;
+                let dy = y as f32 + 0.5 - (row as f32 + 0.5) * bs;
+                (dx * dx + dy * dy <= bs * bs / 4.0).then_some((row as i64, col as i64))
             }
-            if channels == 4 {
-                output[[y, x, 3]] = input[[y, x, 3]];
+            PixelateShape::Hexagon => {
+                // Axial coordinates of a pointy-top grid whose cells are block_size wide
+                let size = bs / 3f32.sqrt();
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let q = (3f32.sqrt() / 3.0 * px - py / 3.0) / size;
+                let r = (2.0 / 3.0 * py) / size;
+                let (mut rq, mut rr, rs) = (q.round(), r.round(), (-q - r).round());
+                let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs + q + r).abs());
+                if dq > dr && dq > ds {
+                    rq = -rr - rs;
+                } else if dr > ds {
+                    rr = -rq - rs;
+                }
+                Some((rr as i64, rq as i64))
             }
         }
     }
+}
 
-    // Handle edges - output grayscale of original at edges
-    for x in 0..width {
-        let v0 = if channels == 1 {
-            input[[0, x, 0]]
-        } else {
-            LUMA_R * input[[0, x, 0]] + LUMA_G * input[[0, x, 1]] + LUMA_B * input[[0, x, 2]]
-        };
-        let color_channels = if channels == 4 { 3 } else { channels };
-        for c in 0..color_channels {
-            output[[0, x, c]] = v0;
-        }
-        if channels == 4 {
-            output[[0, x, 3]] = input[[0, x, 3]];
-        }
+/// Apply a shaped, optionally masked pixelation (f32 version).
+///
+/// Each cell is filled with its average color. Colors are weighted by alpha
+/// so transparent pixels don't darken a cell; alpha itself is the plain mean.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `block_size` - Cell size in pixels (minimum 1)
+/// * `shape` - Cell shape
+/// * `mask` - Optional (height, width) mask, 0.0 = untouched, 1.0 = pixelated.
+///   Cells still average all of their pixels, masked or not.
+///
+/// # Returns
+/// Pixelated image with same channel count
+pub fn pixelate_shaped_f32(
+    input: ArrayView3<f32>,
+    block_size: u32,
+    shape: PixelateShape,
+    mask: Option<ArrayView2<f32>>,
+) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let block_size = (block_size as usize).max(1);
+    let color_channels = if channels == 4 { 3 } else { channels };
 
-        if height > 1 {
-            let v_last = if channels == 1 {
-                input[[height - 1, x, 0]]
-            } else {
-                LUMA_R * input[[height - 1, x, 0]]
-                    + LUMA_G * input[[height - 1, x, 1]]
-                    + LUMA_B * input[[height - 1, x, 2]]
-            };
-            for c in 0..color_channels {
-                output[[height - 1, x, c]] = v_last;
-            }
-            if channels == 4 {
-                output[[height - 1, x, 3]] = input[[height - 1, x, 3]];
+    // Per pixel cell index into the accumulators
+    let mut index = HashMap::new();
+    let mut cells = Array2::<Option<usize>>::from_elem((height, width), None);
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(key) = shape.cell(x, y, block_size) {
+                let next = index.len();
+                cells[[y, x]] = Some(*index.entry(key).or_insert(next));
             }
         }
     }
-    for y in 0..height {
-        let v0 = if channels == 1 {
-            input[[y, 0, 0]]
-        } else {
-            LUMA_R * input[[y, 0, 0]] + LUMA_G * input[[y, 0, 1]] + LUMA_B * input[[y, 0, 2]]
-        };
-        let color_channels = if channels == 4 { 3 } else { channels };
-        for c in 0..color_channels {
-            output[[y, 0, c]] = v0;
-        }
-        if channels == 4 {
-            output[[y, 0, 3]] = input[[y, 0, 3]];
+
+    // Accumulate alpha-weighted color, weight, plain color and pixel count
+    let mut weighted = vec![[0.0f32; 4]; index.len()];
+    let mut plain = vec![[0.0f32; 4]; index.len()];
+    let mut weights = vec![0.0f32; index.len()];
+    let mut counts = vec![0.0f32; index.len()];
+    for ((y, x), cell) in cells.indexed_iter() {
+        let Some(cell) = *cell else { continue };
+        let alpha = if channels == 4 { input[[y, x, 3]] } else { 1.0 };
+        for c in 0..channels {
+            let v = input[[y, x, c]];
+            weighted[cell][c] += v * alpha;
+            plain[cell][c] += v;
         }
+        weights[cell] += alpha;
+        counts[cell] += 1.0;
+    }
 
-        if width > 1 {
-            let v_last = if channels == 1 {
-                input[[y, width - 1, 0]]
-            } else {
-                LUMA_R * input[[y, width - 1, 0]]
-                    + LUMA_G * input[[y, width - 1, 1]]
-                    + LUMA_B * input[[y, width - 1, 2]]
+    let mut output = input.to_owned();
+    for ((y, x), cell) in cells.indexed_iter() {
+        let amount = mask.map_or(1.0, |m| m[[y, x]].clamp(0.0, 1.0));
+        if amount <= 0.0 {
+            continue;
+        }
+        for c in 0..channels {
+            let value = match *cell {
+                // Gap between circles: black, keeping the alpha
+                None if c < color_channels => 0.0,
+                None => input[[y, x, c]],
+                Some(cell) if c < color_channels && weights[cell] > 0.0 => weighted[cell][c] / weights[cell],
+                Some(cell) => plain[cell][c] / counts[cell],
             };
-            for c in 0..color_channels {
-                output[[y, width - 1, c]] = v_last;
-            }
-            if channels == 4 {
-                output[[y, width - 1, 3]] = input[[y, width - 1, 3]];
-            }
+            let v = input[[y, x, c]];
+            output[[y, x, c]] = v + (value - v) * amount;
         }
     }
-
     output
 }
 
-// ============================================================================
-// Pixelate
-// ============================================================================
+/// Apply a shaped, optionally masked pixelation (u8 version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `block_size` - Cell size in pixels (minimum 1)
+/// * `shape` - Cell shape
+/// * `mask` - Optional (height, width) mask, 0 = untouched, 255 = pixelated
+///
+/// # Returns
+/// Pixelated image with same channel count
+pub fn pixelate_shaped_u8(
+    input: ArrayView3<u8>,
+    block_size: u32,
+    shape: PixelateShape,
+    mask: Option<ArrayView2<u8>>,
+) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let mask_f32 = mask.map(|m| m.mapv(|v| v as f32 / 255.0));
+    let result = pixelate_shaped_f32(input_f32.view(), block_size, shape, mask_f32.as_ref().map(|m| m.view()));
+    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
 
 /// Apply pixelation effect (u8 version).
 ///
@@ -560,59 +742,7 @@ pub fn emboss_f32(input: ArrayView3<f32>, angle: f32, depth: f32) -> Array3<f32>
 /// # Returns
 /// Pixelated image with same channel count
 pub fn pixelate_u8(input: ArrayView3<u8>, block_size: u32) -> Array3<u8> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<u8>::zeros((height, width, channels));
-    let block_size = (block_size as usize).max(1);
-    let color_channels = if channels == 4 { 3 } else { channels };
-
-    // Process each block
-    let mut by = 0;
-    while by < height {
-        let bh = block_size.min(height - by);
-        let mut bx = 0;
-        while bx < width {
-            let bw = block_size.min(width - bx);
-            let pixel_count = (bh * bw) as f32;
-
-            // Compute average color for this block
-            for c in 0..color_channels {
-                let mut sum = 0u32;
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        sum += input[[y, x, c]] as u32;
-                    }
-                }
-                let avg = (sum as f32 / pixel_count).round() as u8;
-
-                // Fill block with average
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        output[[y, x, c]] = avg;
-                    }
-                }
-            }
-
-            // Average alpha too if present
-            if channels == 4 {
-                let mut sum = 0u32;
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        sum += input[[y, x, 3]] as u32;
-                    }
-                }
-                let avg = (sum as f32 / pixel_count).round() as u8;
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        output[[y, x, 3]] = avg;
-                    }
-                }
-            }
-
-            bx += block_size;
-        }
-        by += block_size;
-    }
-    output
+    pixelate_shaped_u8(input, block_size, PixelateShape::Square, None)
 }
 
 /// Apply pixelation effect (f32 version).
@@ -624,92 +754,101 @@ pub fn pixelate_u8(input: ArrayView3<u8>, block_size: u32) -> Array3<u8> {
 /// # Returns
 /// Pixelated image with same channel count
 pub fn pixelate_f32(input: ArrayView3<f32>, block_size: u32) -> Array3<f32> {
-    let (height, width, channels) = input.dim();
-    let mut output = Array3::<f32>::zeros((height, width, channels));
-    let block_size = (block_size as usize).max(1);
-    let color_channels = if channels == 4 { 3 } else { channels };
-
-    let mut by = 0;
-    while by < height {
-        let bh = block_size.min(height - by);
-        let mut bx = 0;
-        while bx < width {
-            let bw = block_size.min(width - bx);
-            let pixel_count = (bh * bw) as f32;
-
-            for c in 0..color_channels {
-                let mut sum = 0.0f32;
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        sum += input[[y, x, c]];
-                    }
-                }
-                let avg = sum / pixel_count;
+    pixelate_shaped_f32(input, block_size, PixelateShape::Square, None)
+}
 
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        output[[y, x, c]] = avg;
-                    }
-                }
-            }
+// ============================================================================
+// Vignette
+// ============================================================================
 
-            if channels == 4 {
-                let mut sum = 0.0f32;
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        sum += input[[y, x, 3]];
-                    }
-                }
-                let avg = sum / pixel_count;
-                for y in by..by + bh {
-                    for x in bx..bx + bw {
-                        output[[y, x, 3]] = avg;
-                    }
-                }
-            }
+/// Shape and color of a vignette.
+///
+/// The defaults reproduce the classic vignette: a circular, quadratic
+/// darkening from the image center to the corners.
+#[derive(Clone, Copy, Debug)]
+pub struct VignetteParams {
+    /// Strength: 0.0 = none, 1.0 = full effect at the edges
+    pub amount: f32,
+    /// Center as a fraction of the width (0.5 = middle)
+    pub center_x: f32,
+    /// Center as a fraction of the height (0.5 = middle)
+    pub center_y: f32,
+    /// Normalized distance where the falloff starts (0.0 = at the center)
+    pub midpoint: f32,
+    /// -1.0 = rounded rectangle, 0.0 = ellipse following the frame, 1.0 = circle
+    pub roundness: f32,
+    /// Width of the falloff as a fraction of the center-to-corner distance
+    /// (small = hard edge)
+    pub feather: f32,
+    /// Fade towards this color (R, G, B), 0.0-1.0, instead of darkening
+    pub color: Option<[f32; 3]>,
+}
 
-            bx += block_size;
+impl Default for VignetteParams {
+    fn default() -> Self {
+        Self {
+            amount: 0.5,
+            center_x: 0.5,
+            center_y: 0.5,
+            midpoint: 0.0,
+            roundness: 1.0,
+            feather: 1.0,
+            color: None,
         }
-        by += block_size;
     }
-    output
 }
 
-// ============================================================================
-// Vignette
-// ============================================================================
+impl VignetteParams {
+    /// Vignette strength (0.0-1.0) at pixel (x, y) of a width x height image.
+    fn strength(&self, x: usize, y: usize, width: usize, height: usize) -> f32 {
+        let (half_w, half_h) = (width as f32 / 2.0, height as f32 / 2.0);
+        let dx = (x as f32 - self.center_x * width as f32).abs();
+        let dy = (y as f32 - self.center_y * height as f32).abs();
+
+        // Axes: the frame ellipse passes through the corners, the circle has
+        // the half-diagonal as radius; negative roundness squares the ellipse
+        let roundness = self.roundness.clamp(-1.0, 1.0);
+        let half_diagonal = (half_w * half_w + half_h * half_h).sqrt().max(1e-6);
+        let t = roundness.max(0.0);
+        let ax = (half_w * std::f32::consts::SQRT_2 * (1.0 - t) + half_diagonal * t).max(1e-6);
+        let ay = (half_h * std::f32::consts::SQRT_2 * (1.0 - t) + half_diagonal * t).max(1e-6);
+        let distance = if roundness < 0.0 {
+            let p = 2.0 - 6.0 * roundness;
+            fmath::powf(fmath::powf(dx / ax, p) + fmath::powf(dy / ay, p), 1.0 / p)
+        } else {
+            ((dx / ax).powi(2) + (dy / ay).powi(2)).sqrt()
+        };
 
-/// Apply vignette effect (u8 version).
-///
-/// Darkens the edges of the image using a radial falloff from the center.
+        let falloff = ((distance - self.midpoint) / self.feather.max(1e-3)).clamp(0.0, 1.0);
+        self.amount * falloff * falloff
+    }
+}
+
+/// Apply a shaped, optionally colored vignette (u8 version).
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
-/// * `amount` - Vignette strength: 0.0 = none, 1.0 = strong darkening at edges
+/// * `params` - Vignette shape and color
 ///
 /// # Returns
 /// Vignetted image with same channel count
-pub fn vignette_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+pub fn vignette_params_u8(input: ArrayView3<u8>, params: &VignetteParams) -> Array3<u8> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<u8>::zeros((height, width, channels));
     let color_channels = if channels == 4 { 3 } else { channels };
-
-    let cx = width as f32 / 2.0;
-    let cy = height as f32 / 2.0;
-    let max_dist = (cx * cx + cy * cy).sqrt();
+    let color = params.color.map(|c| c.map(|v| v * 255.0));
 
     for y in 0..height {
         for x in 0..width {
-            let dx = x as f32 - cx;
-            let dy = y as f32 - cy;
-            let dist = (dx * dx + dy * dy).sqrt();
-            let norm_dist = dist / max_dist;
-            let factor = 1.0 - amount * norm_dist * norm_dist;
-            let factor = factor.clamp(0.0, 1.0);
+            let strength = params.strength(x, y, width, height);
 
             for c in 0..color_channels {
-                output[[y, x, c]] = (input[[y, x, c]] as f32 * factor)
-                    .clamp(0.0, 255.0) as u8;
+                let v = input[[y, x, c]] as f32;
+                let result = match color {
+                    Some(color) => v + (color[c.min(2)] - v) * strength.clamp(0.0, 1.0),
+                    None => v * (1.0 - strength).clamp(0.0, 1.0),
+                };
+                output[[y, x, c]] = result.clamp(0.0, 255.0) as u8;
             }
             if channels == 4 {
                 output[[y, x, 3]] = input[[y, x, 3]];
@@ -719,33 +858,30 @@ pub fn vignette_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
     output
 }
 
-/// Apply vignette effect (f32 version).
+/// Apply a shaped, optionally colored vignette (f32 version).
 ///
 /// # Arguments
 /// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
-/// * `amount` - Vignette strength: 0.0 = none, 1.0 = strong darkening at edges
+/// * `params` - Vignette shape and color
 ///
 /// # Returns
 /// Vignetted image with same channel count
-pub fn vignette_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+pub fn vignette_params_f32(input: ArrayView3<f32>, params: &VignetteParams) -> Array3<f32> {
     let (height, width, channels) = input.dim();
     let mut output = Array3::<f32>::zeros((height, width, channels));
     let color_channels = if channels == 4 { 3 } else { channels };
 
-    let cx = width as f32 / 2.0;
-    let cy = height as f32 / 2.0;
-    let max_dist = (cx * cx + cy * cy).sqrt();
-
     for y in 0..height {
         for x in 0..width {
-            let dx = x as f32 - cx;
-            let dy = y as f32 - cy;
-            let dist = (dx * dx + dy * dy).sqrt();
-            let norm_dist = dist / max_dist;
-            let factor = (1.0 - amount * norm_dist * norm_dist).clamp(0.0, 1.0);
+            let strength = params.strength(x, y, width, height);
 
             for c in 0..color_channels {
-                output[[y, x, c]] = (input[[y, x, c]] * factor).clamp(0.0, 1.0);
+                let v = input[[y, x, c]];
+                let result = match params.color {
+                    Some(color) => v + (color[c.min(2)] - v) * strength.clamp(0.0, 1.0),
+                    None => v * (1.0 - strength).clamp(0.0, 1.0),
+                };
+                output[[y, x, c]] = result.clamp(0.0, 1.0);
             }
             if channels == 4 {
                 output[[y, x, 3]] = input[[y, x, 3]];
@@ -755,6 +891,32 @@ pub fn vignette_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
     output
 }
 
+/// Apply vignette effect (u8 version).
+///
+/// Darkens the edges of the image using a radial falloff from the center.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `amount` - Vignette strength: 0.0 = none, 1.0 = strong darkening at edges
+///
+/// # Returns
+/// Vignetted image with same channel count
+pub fn vignette_u8(input: ArrayView3<u8>, amount: f32) -> Array3<u8> {
+    vignette_params_u8(input, &VignetteParams { amount, ..Default::default() })
+}
+
+/// Apply vignette effect (f32 version).
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `amount` - Vignette strength: 0.0 = none, 1.0 = strong darkening at edges
+///
+/// # Returns
+/// Vignetted image with same channel count
+pub fn vignette_f32(input: ArrayView3<f32>, amount: f32) -> Array3<f32> {
+    vignette_params_f32(input, &VignetteParams { amount, ..Default::default() })
+}
+
 // ============================================================================
 // Pencil Sketch
 // ============================================================================
@@ -854,7 +1016,7 @@ pub fn pencil_sketch_f32(
             let lum = if channels == 1 {
                 input[[y, x, 0]]
             } else {
-                0.2125 * input[[y, x, 0]] + 0.7154 * input[[y, x, 1]] + 0.0721 * input[[y, x, 2]]
+                LUMA_R * input[[y, x, 0]] + LUMA_G * input[[y, x, 1]] + LUMA_B * input[[y, x, 2]]
             };
             for c in 0..color_ch {
                 gray[[y, x, c]] = lum;
@@ -880,7 +1042,7 @@ pub fn pencil_sketch_f32(
 
     // 3. Gaussian blur
     let sigma = sigma_s * 0.2;
-    let blurred = super::blur_wasm::gaussian_blur_wasm_f32(inverted.view(), sigma);
+    let blurred = gaussian_blur_wasm_f32(inverted.view(), sigma);
 
     // 4. Color dodge blend + 5. shade factor
     let mut output = Array3::<f32>::zeros((height, width, channels));
@@ -902,6 +1064,65 @@ pub fn pencil_sketch_f32(
     output
 }
 
+// ============================================================================
+// XDoG (eXtended Difference of Gaussians)
+// ============================================================================
+
+/// XDoG line-art / sketch effect - f32 version.
+///
+/// Winnemöller's extended DoG: the sharpened luminance
+/// `S = (1 + p) * G(sigma) - p * G(k * sigma)` is passed through a soft
+/// threshold, `1` where `S >= epsilon` and `1 + tanh(phi * (S - epsilon))`
+/// below. Large `phi` gives crisp black lines on white, small `phi` a soft
+/// charcoal look.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `sigma` - Base blur sigma (line scale, e.g. 1.0)
+/// * `k` - Ratio of the wide to the narrow sigma (typically 1.6)
+/// * `p` - Edge sharpening strength (e.g. 20)
+/// * `epsilon` - Threshold on the sharpened luminance (e.g. 0.1)
+/// * `phi` - Steepness of the soft ramp below the threshold (e.g. 10)
+///
+/// # Returns
+/// Grayscale image with same channel count (alpha preserved)
+pub fn xdog_f32(input: ArrayView3<f32>, sigma: f32, k: f32, p: f32, epsilon: f32, phi: f32) -> Array3<f32> {
+    let (height, width, channels) = input.dim();
+    let narrow = blurred_luminance_f32(input, sigma);
+    let wide = blurred_luminance_f32(input, sigma * k);
+
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = Array3::<f32>::zeros((height, width, channels));
+    for y in 0..height {
+        for x in 0..width {
+            let s = (1.0 + p) * narrow[[y, x, 0]] - p * wide[[y, x, 0]];
+            let value = if s >= epsilon { 1.0 } else { 1.0 + (phi * (s - epsilon)).tanh() };
+            for c in 0..color_channels {
+                output[[y, x, c]] = value.clamp(0.0, 1.0);
+            }
+            if channels == 4 {
+                output[[y, x, 3]] = input[[y, x, 3]];
+            }
+        }
+    }
+    output
+}
+
+/// XDoG line-art / sketch effect - u8 version.
+///
+/// Same algorithm as xdog_f32; `epsilon` stays on the 0.0-1.0 scale.
+///
+/// # Returns
+/// Grayscale image with same channel count (alpha preserved)
+pub fn xdog_u8(input: ArrayView3<u8>, sigma: f32, k: f32, p: f32, epsilon: f32, phi: f32) -> Array3<u8> {
+    let result = xdog_f32(input.mapv(|v| v as f32 / 255.0).view(), sigma, k, p, epsilon, phi);
+    let mut output = result.mapv(|v| (v * 255.0).round() as u8);
+    if input.dim().2 == 4 {
+        output.index_axis_mut(ndarray::Axis(2), 3).assign(&input.index_axis(ndarray::Axis(2), 3));
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1004,6 +1225,59 @@ mod tests {
         assert_eq!(result[[1, 1, 3]], 200);
     }
 
+    #[test]
+    fn test_emboss_borders_and_alpha_outline() {
+        // Flat opaque image: no frame at the border
+        let flat = Array3::<f32>::from_elem((5, 5, 3), 0.25);
+        let result = emboss_f32(flat.view(), 135.0, 1.0);
+        assert!(result.iter().all(|&v| (v - result[[2, 2, 0]]).abs() < 1e-6));
+
+        // Black square on a transparent background with garbage RGB: only the
+        // outline is embossed, the garbage doesn't show
+        let img = Array3::from_shape_fn((9, 9, 4), |(y, x, c)| {
+            let inside = (3..6).contains(&y) && (3..6).contains(&x);
+            match (inside, c) {
+                (true, 3) => 255,
+                (true, _) => 0,
+                (false, 3) => 0,
+                (false, _) => ((x * 37 + y * 91 + c * 53) % 256) as u8,
+            }
+        });
+        let result = emboss_u8(img.view(), 135.0, 1.0);
+        assert_eq!(result[[0, 0, 0]], 128);
+        assert_eq!(result[[8, 1, 0]], 128);
+        assert_eq!(result[[4, 4, 0]], 128);
+        assert!(result[[3, 4, 0]] != 128 || result[[5, 4, 0]] != 128);
+        assert_eq!(result[[4, 4, 3]], 255);
+    }
+
+    #[test]
+    fn test_threshold_otsu_and_adaptive() {
+        // Two clusters around 40 and 200
+        let img = Array3::from_shape_fn((4, 8, 1), |(y, x, _)| if x < 4 { 38 + y as u8 } else { 198 + y as u8 });
+        let t = threshold_otsu_u8(img.view());
+        assert!(t > 41 && t <= 198, "threshold {t}");
+        let result = threshold_u8(img.view(), t);
+        assert!(result.iter().take(4).all(|&v| v == 0));
+        assert!(result.iter().skip(4).take(4).all(|&v| v == 255));
+        let t = threshold_otsu_f32(img.mapv(|v| v as f32 / 255.0).view());
+        assert!(t > 0.16 && t < 0.78);
+
+        // Dark line on a strong left-to-right gradient: a global threshold
+        // can't separate it, the local mean can
+        let mut page = Array3::from_shape_fn((9, 40, 3), |(_, x, _)| 0.1 + 0.8 * x as f32 / 39.0);
+        for x in 0..40 {
+            for c in 0..3 {
+                page[[4, x, c]] -= 0.08;
+            }
+        }
+        for method in [AdaptiveMethod::Mean, AdaptiveMethod::Gaussian] {
+            let result = adaptive_threshold_f32(page.view(), 7, 0.02, method);
+            assert!((2..38).all(|x| result[[4, x, 0]] == 0.0 && result[[1, x, 0]] == 1.0));
+        }
+        assert_eq!(AdaptiveMethod::parse("gaussian"), AdaptiveMethod::Gaussian);
+    }
+
     // Pixelate tests
 
     #[test]
@@ -1042,6 +1316,35 @@ mod tests {
         assert!((result[[0, 1, 0]] - 0.7).abs() < 0.001);
     }
 
+    #[test]
+    fn test_pixelate_shapes_mask_and_alpha_weighting() {
+        // Left half opaque red, right half transparent black
+        let img = Array3::from_shape_fn((8, 8, 4), |(_, x, c)| match c {
+            0 | 3 if x < 4 => 1.0,
+            _ => 0.0,
+        });
+
+        // Transparent pixels don't darken the block
+        let result = pixelate_shaped_f32(img.view(), 8, PixelateShape::Square, None);
+        assert!((result[[0, 7, 0]] - 1.0).abs() < 1e-6);
+        assert!((result[[0, 7, 3]] - 0.5).abs() < 1e-6);
+
+        // Mask limits the effect to the top half
+        let mask = Array2::from_shape_fn((8, 8), |(y, _)| if y < 4 { 1.0 } else { 0.0 });
+        let result = pixelate_shaped_f32(img.view(), 8, PixelateShape::Square, Some(mask.view()));
+        assert_eq!(result[[0, 7, 3]], 0.5);
+        assert_eq!(result[[7, 7, 3]], 0.0);
+
+        // Circles leave black corners, hexagons cover everything
+        let flat = Array3::<f32>::from_elem((8, 8, 3), 0.6);
+        let result = pixelate_shaped_f32(flat.view(), 8, PixelateShape::Circle, None);
+        assert_eq!(result[[0, 0, 0]], 0.0);
+        assert!((result[[4, 4, 0]] - 0.6).abs() < 1e-6);
+        let result = pixelate_shaped_f32(flat.view(), 4, PixelateShape::Hexagon, None);
+        assert!(result.iter().all(|&v| (v - 0.6).abs() < 1e-6));
+        assert_eq!(PixelateShape::parse("hex"), PixelateShape::Hexagon);
+    }
+
     // Vignette tests
 
     #[test]
@@ -1079,6 +1382,28 @@ mod tests {
         assert!((result[[2, 2, 0]] - 0.8).abs() < 0.001);
     }
 
+    #[test]
+    fn test_vignette_params_shape_and_color() {
+        let img = Array3::<f32>::from_elem((20, 40, 3), 0.5);
+
+        // Off-center: the darkening follows the center
+        let params = VignetteParams { amount: 1.0, center_x: 0.25, ..Default::default() };
+        let result = vignette_params_f32(img.view(), &params);
+        assert!(result[[10, 10, 0]] > result[[10, 30, 0]]);
+
+        // Midpoint keeps the inner area untouched, a small feather makes a hard edge
+        let params = VignetteParams { amount: 1.0, midpoint: 0.6, feather: 0.05, ..Default::default() };
+        let result = vignette_params_f32(img.view(), &params);
+        assert_eq!(result[[10, 14, 0]], 0.5);
+        assert!(result[[0, 0, 0]] < 0.01);
+
+        // White vignette brightens towards the corners
+        let params = VignetteParams { amount: 1.0, color: Some([1.0, 1.0, 1.0]), ..Default::default() };
+        let result = vignette_params_f32(img.view(), &params);
+        assert!(result[[0, 0, 0]] > 0.9);
+        assert!((result[[10, 20, 0]] - 0.5).abs() < 1e-6);
+    }
+
     // Pencil sketch tests
 
     #[test]
@@ -1153,4 +1478,18 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_xdog_draws_lines_at_edges() {
+        let img = Array3::from_shape_fn((12, 12, 3), |(_, x, _)| if x >= 6 { 0.9f32 } else { 0.5 });
+        let result = xdog_f32(img.view(), 1.0, 1.6, 20.0, 0.1, 10.0);
+        // Flat areas stay white, the dark side of the edge gets a line
+        assert_eq!(result[[6, 0, 0]], 1.0);
+        assert_eq!(result[[6, 11, 0]], 1.0);
+        assert!(result[[6, 5, 0]] < 0.5);
+
+        let rgba = Array3::from_elem((4, 4, 4), 200u8);
+        let out = xdog_u8(rgba.view(), 1.0, 1.6, 20.0, 0.1, 10.0);
+        assert!(out.iter().all(|&v| v == 255 || v == 200));
+    }
 }
@@ -0,0 +1,177 @@
+/**
+ * Patch-based synthesis - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - synthesis.rs (Rust implementation)
+ * - synthesis.py (Python wrapper)
+ *
+ * Provides: fill_region_patchmatch, synthesize_texture, patchmatch_nnf, seamless_clone
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Region Fill
+// ============================================================================
+
+/**
+ * Fill the masked region with texture from the rest of the image (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Uint8Array} mask - width * height, >= 128 marks the pixels to replace
+ * @param {Object} options - {patchSize: 7, seed: 0}
+ * @returns {Object} - Filled image data
+ */
+export function fill_region_patchmatch(imageData, mask, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.fill_region_patchmatch_wasm(
+        new Uint8Array(data.buffer), width, height, channels,
+        new Uint8Array(mask), options.patchSize ?? 7, options.seed ?? 0
+    );
+    return { data: new Uint8ClampedArray(result.buffer), width, height, channels };
+}
+
+/**
+ * Fill the masked region with texture from the rest of the image (f32, mask >= 0.5).
+ */
+export function fill_region_patchmatch_f32(imageData, mask, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+    const result = wasm.fill_region_patchmatch_f32_wasm(
+        data, width, height, channels, new Float32Array(mask), options.patchSize ?? 7, options.seed ?? 0
+    );
+    return { data: new Float32Array(result.buffer), width, height, channels };
+}
+
+// ============================================================================
+// Texture Synthesis
+// ============================================================================
+
+function textureOptions(outWidth, outHeight, opts) {
+    return Object.assign(new wasm.TextureSynthesisOptions(), {
+        out_width: outWidth,
+        out_height: outHeight,
+        patch_size: opts.patchSize ?? 7,
+        seed: opts.seed ?? 0,
+    });
+}
+
+/**
+ * Synthesize a texture of any size from a sample (u8).
+ * @param {Object} sample - {data: Uint8ClampedArray, width, height, channels}
+ * @param {number} outWidth - Width of the result
+ * @param {number} outHeight - Height of the result
+ * @param {Object} options - {patchSize: 7, seed: 0}
+ * @returns {Object} - Texture image data
+ */
+export function synthesize_texture(sample, outWidth, outHeight, options = {}) {
+    const { data, width, height } = sample;
+    const channels = sample.channels || 4;
+    const opts = textureOptions(outWidth, outHeight, options);
+    const result = wasm.synthesize_texture_wasm(new Uint8Array(data.buffer), width, height, channels, opts);
+    opts.free();
+    return { data: new Uint8ClampedArray(result.buffer), width: outWidth, height: outHeight, channels };
+}
+
+/**
+ * Synthesize a texture of any size from a sample (f32).
+ */
+export function synthesize_texture_f32(sample, outWidth, outHeight, options = {}) {
+    const { data, width, height } = sample;
+    const channels = sample.channels || 4;
+    const opts = textureOptions(outWidth, outHeight, options);
+    const result = wasm.synthesize_texture_f32_wasm(data, width, height, channels, opts);
+    opts.free();
+    return { data: new Float32Array(result.buffer), width: outWidth, height: outHeight, channels };
+}
+
+// ============================================================================
+// Nearest-Neighbor Field
+// ============================================================================
+
+function patchMatchOptions(source, opts) {
+    return Object.assign(new wasm.PatchMatchOptions(), {
+        source_width: source.width,
+        source_height: source.height,
+        patch_size: opts.patchSize ?? 7,
+        iterations: opts.iterations ?? 5,
+        seed: opts.seed ?? 0,
+    });
+}
+
+/**
+ * PatchMatch nearest-neighbor field from target to source patches (u8).
+ * @param {Object} target - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} source - Image with the same channel count
+ * @param {Object} options - {patchSize: 7, iterations: 5, seed: 0}
+ * @returns {Float32Array} - Per target pixel: source x, source y, distance
+ */
+export function patchmatch_nnf(target, source, options = {}) {
+    const channels = target.channels || 4;
+    const opts = patchMatchOptions(source, options);
+    const result = wasm.patchmatch_nnf_wasm(
+        new Uint8Array(target.data.buffer), target.width, target.height, channels,
+        new Uint8Array(source.data.buffer), opts
+    );
+    opts.free();
+    return result;
+}
+
+/**
+ * PatchMatch nearest-neighbor field from target to source patches (f32).
+ */
+export function patchmatch_nnf_f32(target, source, options = {}) {
+    const channels = target.channels || 4;
+    const opts = patchMatchOptions(source, options);
+    const result = wasm.patchmatch_nnf_f32_wasm(target.data, target.width, target.height, channels, source.data, opts);
+    opts.free();
+    return result;
+}
+
+// ============================================================================
+// Seamless Cloning
+// ============================================================================
+
+function cloneOptions(src, opts) {
+    return Object.assign(new wasm.SeamlessCloneOptions(), {
+        src_width: src.width,
+        src_height: src.height,
+        x: opts.x ?? 0,
+        y: opts.y ?? 0,
+        mode: opts.mode ?? 'normal',
+    });
+}
+
+/**
+ * Paste the masked part of src into dst with Poisson blending (u8).
+ * @param {Object} src - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} dst - Image with the same channel count
+ * @param {Uint8Array} mask - src.width * src.height, >= 128 marks the pixels to clone
+ * @param {Object} options - {x: 0, y: 0, mode: 'normal' | 'mixed'}
+ * @returns {Object} - Destination image data with the blended region
+ */
+export function seamless_clone(src, dst, mask, options = {}) {
+    const channels = dst.channels || 4;
+    const opts = cloneOptions(src, options);
+    const result = wasm.seamless_clone_wasm(
+        new Uint8Array(src.data.buffer), new Uint8Array(dst.data.buffer), dst.width, dst.height, channels,
+        new Uint8Array(mask), opts
+    );
+    opts.free();
+    return { data: new Uint8ClampedArray(result.buffer), width: dst.width, height: dst.height, channels };
+}
+
+/**
+ * Paste the masked part of src into dst with Poisson blending (f32, mask >= 0.5).
+ */
+export function seamless_clone_f32(src, dst, mask, options = {}) {
+    const channels = dst.channels || 4;
+    const opts = cloneOptions(src, options);
+    const result = wasm.seamless_clone_f32_wasm(
+        src.data, dst.data, dst.width, dst.height, channels, new Float32Array(mask), opts
+    );
+    opts.free();
+    return { data: new Float32Array(result.buffer), width: dst.width, height: dst.height, channels };
+}
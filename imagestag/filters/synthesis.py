@@ -0,0 +1,225 @@
+"""Patch-based synthesis with Rust backend.
+
+- PatchMatch nearest-neighbor field: for every patch of a target image,
+  the most similar patch of a source image
+- Region fill: replace a masked region with texture from the rest of the
+  image (content-aware fill, healing)
+- Texture synthesis: grow a texture sample to any size
+- Seamless cloning: paste a masked region into another image with Poisson
+  blending so it adopts the target's lighting
+
+Fill and texture synthesis alternate PatchMatch search and patch voting,
+coarse to fine over an image pyramid. Results are deterministic for a
+given seed.
+
+## Supported Formats
+
+uint8 or float32 arrays with 1, 3, or 4 channels. Alpha is synthesized
+like any other channel.
+
+Co-located with:
+- synthesis.rs (Rust implementation)
+- synthesis.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.synthesis import fill_region_patchmatch, synthesize_texture
+
+    cleaned = fill_region_patchmatch(photo, object_mask)
+    grass = synthesize_texture(grass_sample, 1024, 1024, patch_size=9)
+    composite = seamless_clone(plane, sky, plane_mask, position=(120, 40))
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Region Fill
+# ============================================================================
+
+def fill_region_patchmatch(image: np.ndarray, mask: np.ndarray, patch_size: int = 7,
+                           seed: int = 0) -> np.ndarray:
+    """Fill the masked region with texture from the rest of the image (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        mask: uint8 array (H, W), >= 128 marks the pixels to replace
+        patch_size: Edge length of the matched patches (odd)
+        seed: Seed of the random search
+
+    Returns:
+        uint8 array with the same shape; unmasked pixels are unchanged
+    """
+    _validate_image(image, np.uint8, "fill_region_patchmatch")
+    return imagestag_rust.fill_region_patchmatch(image, mask.astype(np.uint8, copy=False),
+                                                 patch_size, seed)
+
+
+def fill_region_patchmatch_f32(image: np.ndarray, mask: np.ndarray, patch_size: int = 7,
+                               seed: int = 0) -> np.ndarray:
+    """Fill the masked region with texture from the rest of the image (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C)
+        mask: float32 array (H, W), >= 0.5 marks the pixels to replace
+        patch_size: Edge length of the matched patches (odd)
+        seed: Seed of the random search
+
+    Returns:
+        float32 array with the same shape; unmasked pixels are unchanged
+    """
+    _validate_image(image, np.float32, "fill_region_patchmatch_f32")
+    return imagestag_rust.fill_region_patchmatch_f32(image, mask.astype(np.float32, copy=False),
+                                                     patch_size, seed)
+
+
+# ============================================================================
+# Texture Synthesis
+# ============================================================================
+
+def synthesize_texture(sample: np.ndarray, out_width: int, out_height: int,
+                       patch_size: int = 7, seed: int = 0) -> np.ndarray:
+    """Synthesize a texture of any size from a sample (u8).
+
+    Larger patches keep larger structures of the sample intact. A sample
+    smaller than one patch is tiled instead.
+
+    Args:
+        sample: uint8 array with 1, 3, or 4 channels (H, W, C)
+        out_width: Width of the result
+        out_height: Height of the result
+        patch_size: Edge length of the matched patches (odd)
+        seed: Seed of the random initialization and search
+
+    Returns:
+        uint8 array (out_height, out_width, C)
+    """
+    _validate_image(sample, np.uint8, "synthesize_texture")
+    return imagestag_rust.synthesize_texture(sample, out_width, out_height, patch_size, seed)
+
+
+def synthesize_texture_f32(sample: np.ndarray, out_width: int, out_height: int,
+                           patch_size: int = 7, seed: int = 0) -> np.ndarray:
+    """Synthesize a texture of any size from a sample (f32).
+
+    Args:
+        sample: float32 array with 1, 3, or 4 channels (H, W, C)
+        out_width: Width of the result
+        out_height: Height of the result
+        patch_size: Edge length of the matched patches (odd)
+        seed: Seed of the random initialization and search
+
+    Returns:
+        float32 array (out_height, out_width, C)
+    """
+    _validate_image(sample, np.float32, "synthesize_texture_f32")
+    return imagestag_rust.synthesize_texture_f32(sample, out_width, out_height, patch_size, seed)
+
+
+# ============================================================================
+# Nearest-Neighbor Field
+# ============================================================================
+
+def patchmatch_nnf(target: np.ndarray, source: np.ndarray, patch_size: int = 7,
+                   iterations: int = 5, seed: int = 0) -> tuple[np.ndarray, np.ndarray]:
+    """PatchMatch nearest-neighbor field from target to source patches (u8).
+
+    Args:
+        target: uint8 array with 1, 3, or 4 channels (H, W, C)
+        source: uint8 array with the same channel count
+        patch_size: Edge length of the compared patches (odd)
+        iterations: Propagation / random search passes
+        seed: Seed of the random initialization and search
+
+    Returns:
+        (matches, distances): uint32 (H, W, 2) source patch center (x, y)
+        per target pixel, float32 (H, W) sum of squared differences on
+        0.0-1.0 values
+    """
+    _validate_image(target, np.uint8, "patchmatch_nnf")
+    _validate_image(source, np.uint8, "patchmatch_nnf")
+    return imagestag_rust.patchmatch_nnf(target, source, patch_size, iterations, seed)
+
+
+def patchmatch_nnf_f32(target: np.ndarray, source: np.ndarray, patch_size: int = 7,
+                       iterations: int = 5, seed: int = 0) -> tuple[np.ndarray, np.ndarray]:
+    """PatchMatch nearest-neighbor field from target to source patches (f32).
+
+    Args:
+        target: float32 array with 1, 3, or 4 channels (H, W, C)
+        source: float32 array with the same channel count
+        patch_size: Edge length of the compared patches (odd)
+        iterations: Propagation / random search passes
+        seed: Seed of the random initialization and search
+
+    Returns:
+        (matches, distances) as for ``patchmatch_nnf``
+    """
+    _validate_image(target, np.float32, "patchmatch_nnf_f32")
+    _validate_image(source, np.float32, "patchmatch_nnf_f32")
+    return imagestag_rust.patchmatch_nnf_f32(target, source, patch_size, iterations, seed)
+
+
+# ============================================================================
+# Seamless Cloning
+# ============================================================================
+
+def seamless_clone(src: np.ndarray, dst: np.ndarray, mask: np.ndarray,
+                   position: tuple[int, int] = (0, 0), mode: str = 'normal') -> np.ndarray:
+    """Paste the masked part of src into dst with Poisson blending (u8).
+
+    Inside the region the result keeps the gradients of the source while
+    its border matches the destination, so the pasted object adopts the
+    destination's lighting and color cast.
+
+    Args:
+        src: uint8 array with 1, 3, or 4 channels (h, w, C)
+        dst: uint8 array with the same channel count (H, W, C)
+        mask: uint8 array (h, w) over src, >= 128 marks the pixels to clone
+        position: (x, y) of the source's top-left corner in dst; may be negative
+        mode: 'normal' (source gradients) or 'mixed' (stronger of source
+            and destination gradient, keeps destination texture)
+
+    Returns:
+        uint8 array shaped like dst. Alpha is taken from dst unchanged.
+    """
+    _validate_image(src, np.uint8, "seamless_clone")
+    _validate_image(dst, np.uint8, "seamless_clone")
+    return imagestag_rust.seamless_clone(src, dst, mask.astype(np.uint8, copy=False),
+                                         tuple(position), mode)
+
+
+def seamless_clone_f32(src: np.ndarray, dst: np.ndarray, mask: np.ndarray,
+                       position: tuple[int, int] = (0, 0), mode: str = 'normal') -> np.ndarray:
+    """Paste the masked part of src into dst with Poisson blending (f32).
+
+    Args:
+        src: float32 array with 1, 3, or 4 channels (h, w, C)
+        dst: float32 array with the same channel count (H, W, C)
+        mask: float32 array (h, w) over src, >= 0.5 marks the pixels to clone
+        position: (x, y) of the source's top-left corner in dst
+        mode: 'normal' or 'mixed'
+
+    Returns:
+        float32 array shaped like dst
+    """
+    _validate_image(src, np.float32, "seamless_clone_f32")
+    _validate_image(dst, np.float32, "seamless_clone_f32")
+    return imagestag_rust.seamless_clone_f32(src, dst, mask.astype(np.float32, copy=False),
+                                             tuple(position), mode)
+
+
+__all__ = [
+    'fill_region_patchmatch', 'fill_region_patchmatch_f32',
+    'synthesize_texture', 'synthesize_texture_f32',
+    'patchmatch_nnf', 'patchmatch_nnf_f32',
+    'seamless_clone', 'seamless_clone_f32',
+]
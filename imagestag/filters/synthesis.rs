@@ -0,0 +1,875 @@
+//! Patch-based synthesis with PatchMatch.
+//!
+//! [`patchmatch_nnf_f32`] finds, for every patch of a target image, the most
+//! similar patch of a source image (Barnes et al., "PatchMatch"): random
+//! guesses are improved by passing good matches on to neighbors and by
+//! random search around the current best.
+//!
+//! [`fill_region_patchmatch_f32`] replaces a masked region with texture
+//! copied from the rest of the image (Wexler et al., "Space-Time
+//! Completion"): every patch touching the hole is matched to its most
+//! similar patch in the known area, the matches vote on the hole pixels,
+//! and the two steps alternate, coarse to fine over an image pyramid so
+//! large structures settle before detail. [`synthesize_texture_f32`] runs
+//! the same loop with an empty canvas as target and a sample as source.
+//!
+//! [`seamless_clone_f32`] pastes a masked region into another image by
+//! solving a Poisson equation (Pérez et al., "Poisson Image Editing"), so
+//! the pasted object keeps its detail but adopts the target's lighting.
+//!
+//! ## Supported Formats
+//!
+//! All functions support 1, 3, or 4 channel images in both u8 and f32 formats.
+//! Alpha is synthesized like any other channel.
+
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3};
+use rayon::prelude::*;
+
+use crate::filters::noise::hash_uniform;
+
+/// PatchMatch propagation / random search passes per EM iteration.
+const PM_ITERATIONS: usize = 4;
+/// Match-and-vote iterations per pyramid level.
+const EM_ITERATIONS: usize = 3;
+/// Rows handled by one thread during propagation.
+const BAND_ROWS: usize = 16;
+
+/// One pyramid level: image estimate and the pixels still to synthesize.
+struct Level {
+    image: Array3<f32>,
+    hole: Array2<bool>,
+}
+
+/// Nearest-neighbor field entry: source patch center and its distance.
+#[derive(Debug, Clone, Copy)]
+struct Match {
+    y: u32,
+    x: u32,
+    distance: f32,
+}
+
+/// Half-resolution level; a coarse pixel is a hole if any of its children is.
+fn downsample(level: &Level) -> Level {
+    let (height, width, channels) = level.image.dim();
+    let (ch, cw) = (height.div_ceil(2), width.div_ceil(2));
+    let mut image = Array3::<f32>::zeros((ch, cw, channels));
+    let mut hole = Array2::from_elem((ch, cw), false);
+    for cy in 0..ch {
+        for cx in 0..cw {
+            let children: Vec<(usize, usize)> = (2 * cy..(2 * cy + 2).min(height))
+                .flat_map(|y| (2 * cx..(2 * cx + 2).min(width)).map(move |x| (y, x)))
+                .collect();
+            if children.iter().any(|&p| level.hole[p]) {
+                hole[[cy, cx]] = true;
+                continue;
+            }
+            for c in 0..channels {
+                let sum: f32 = children.iter().map(|&(y, x)| level.image[[y, x, c]]).sum();
+                image[[cy, cx, c]] = sum / children.len() as f32;
+            }
+        }
+    }
+    Level { image, hole }
+}
+
+/// Fill the hole by repeatedly averaging already known 8-neighbors.
+fn onion_peel(level: &mut Level) {
+    let (height, width, channels) = level.image.dim();
+    let mut known = level.hole.mapv(|h| !h);
+    loop {
+        let mut frontier = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if known[[y, x]] {
+                    continue;
+                }
+                let mut sum = vec![0.0; channels];
+                let mut count = 0;
+                for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                        if known[[ny, nx]] {
+                            count += 1;
+                            for (c, s) in sum.iter_mut().enumerate() {
+                                *s += level.image[[ny, nx, c]];
+                            }
+                        }
+                    }
+                }
+                if count > 0 {
+                    frontier.push((y, x, sum.into_iter().map(|s| s / count as f32).collect::<Vec<f32>>()));
+                }
+            }
+        }
+        if frontier.is_empty() {
+            break;
+        }
+        for (y, x, value) in frontier {
+            for (c, v) in value.into_iter().enumerate() {
+                level.image[[y, x, c]] = v;
+            }
+            known[[y, x]] = true;
+        }
+    }
+}
+
+/// Number of hole pixels in every window of radius `r`, via a summed-area table.
+fn window_counts(hole: &Array2<bool>, r: usize) -> Array2<u32> {
+    let (height, width) = hole.dim();
+    let mut sat = Array2::<u32>::zeros((height + 1, width + 1));
+    for y in 0..height {
+        for x in 0..width {
+            sat[[y + 1, x + 1]] = hole[[y, x]] as u32 + sat[[y, x + 1]] + sat[[y + 1, x]] - sat[[y, x]];
+        }
+    }
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let (y0, x0) = (y.saturating_sub(r), x.saturating_sub(r));
+        let (y1, x1) = ((y + r + 1).min(height), (x + r + 1).min(width));
+        sat[[y1, x1]] + sat[[y0, x0]] - sat[[y0, x1]] - sat[[y1, x0]]
+    })
+}
+
+/// Image patches are copied from, and the pixels that must not be copied.
+struct Source {
+    image: Array3<f32>,
+    hole: Array2<bool>,
+}
+
+/// Search state of one level: which patches may be copied and which need a match.
+struct Field {
+    r: usize,
+    /// Source patch fully inside the source image and free of hole pixels
+    valid: Array2<bool>,
+    sources: Vec<(u32, u32)>,
+    /// Target patch overlaps the target hole
+    target: Array2<bool>,
+}
+
+impl Field {
+    fn new(source_hole: &Array2<bool>, target_hole: &Array2<bool>, r: usize) -> Self {
+        let (height, width) = source_hole.dim();
+        let counts = window_counts(source_hole, r);
+        let valid = Array2::from_shape_fn((height, width), |(y, x)| {
+            y >= r && x >= r && y + r < height && x + r < width && counts[[y, x]] == 0
+        });
+        let sources = valid.indexed_iter().filter(|(_, &v)| v).map(|((y, x), _)| (y as u32, x as u32)).collect();
+        let target = window_counts(target_hole, r).mapv(|n| n > 0);
+        Field { r, valid, sources, target }
+    }
+
+    fn random_source(&self, y: usize, x: usize, k: u32, seed: u64) -> (u32, u32) {
+        let i = (hash_uniform(x as i64, y as i64, k, seed) * self.sources.len() as f32) as usize;
+        self.sources[i.min(self.sources.len() - 1)]
+    }
+
+    /// Field of random matches for a target of `width` x `height`.
+    fn random_nnf(&self, height: usize, width: usize, seed: u64) -> Vec<Match> {
+        (0..height * width)
+            .map(|i| {
+                let (sy, sx) = self.random_source(i / width, i % width, 0, seed);
+                Match { y: sy, x: sx, distance: f32::INFINITY }
+            })
+            .collect()
+    }
+}
+
+/// Sum of squared differences between the target patch at (ty, tx) and the
+/// source patch at (sy, sx); stops early once above `limit`. Target pixels
+/// outside the image are skipped, source patches always lie inside.
+fn patch_distance(
+    target: &Array3<f32>,
+    source: &Array3<f32>,
+    r: usize,
+    (ty, tx): (usize, usize),
+    (sy, sx): (usize, usize),
+    limit: f32,
+) -> f32 {
+    let (height, width, channels) = target.dim();
+    let mut sum = 0.0;
+    for dy in 0..=2 * r {
+        let y = ty + dy;
+        if y < r || y - r >= height {
+            continue;
+        }
+        for dx in 0..=2 * r {
+            let x = tx + dx;
+            if x < r || x - r >= width {
+                continue;
+            }
+            for c in 0..channels {
+                let d = target[[y - r, x - r, c]] - source[[sy + dy - r, sx + dx - r, c]];
+                sum += d * d;
+            }
+        }
+        if sum > limit {
+            return sum;
+        }
+    }
+    sum
+}
+
+/// Recompute the distance of every target match (stale after the target changed).
+fn update_distances(target: &Array3<f32>, source: &Array3<f32>, field: &Field, nnf: &mut [Match]) {
+    let width = target.dim().1;
+    nnf.par_iter_mut().enumerate().for_each(|(i, m)| {
+        let (y, x) = (i / width, i % width);
+        if field.target[[y, x]] {
+            m.distance = patch_distance(target, source, field.r, (y, x), (m.y as usize, m.x as usize), f32::INFINITY);
+        }
+    });
+}
+
+/// One PatchMatch pass: propagate good matches from scan-order neighbors,
+/// then search randomly around the current match at shrinking radii.
+fn patchmatch_pass(target: &Array3<f32>, source: &Array3<f32>, field: &Field, nnf: &mut [Match], reverse: bool, seed: u64) {
+    let width = target.dim().1;
+    let (source_height, source_width, _) = source.dim();
+    let r = field.r;
+    let max_radius = source_height.max(source_width) as f32;
+    nnf.par_chunks_mut(width * BAND_ROWS).enumerate().for_each(|(band, rows)| {
+        let y0 = band * BAND_ROWS;
+        let band_height = rows.len() / width;
+        let step: isize = if reverse { -1 } else { 1 };
+        for i in 0..band_height * width {
+            let i = if reverse { band_height * width - 1 - i } else { i };
+            let (ly, x) = (i / width, i % width);
+            let y = y0 + ly;
+            if !field.target[[y, x]] {
+                continue;
+            }
+            let mut best = rows[i];
+            let try_candidate = |sy: isize, sx: isize, best: &mut Match| {
+                if sy < 0 || sx < 0 || sy as usize >= source_height || sx as usize >= source_width {
+                    return;
+                }
+                let (sy, sx) = (sy as usize, sx as usize);
+                if !field.valid[[sy, sx]] || (sy as u32 == best.y && sx as u32 == best.x) {
+                    return;
+                }
+                let d = patch_distance(target, source, r, (y, x), (sy, sx), best.distance);
+                if d < best.distance {
+                    *best = Match { y: sy as u32, x: sx as u32, distance: d };
+                }
+            };
+
+            // Propagation from the previous pixel in scan order (row and column)
+            let px = x as isize - step;
+            if px >= 0 && (px as usize) < width {
+                let n = rows[ly * width + px as usize];
+                if field.target[[y, px as usize]] {
+                    try_candidate(n.y as isize, n.x as isize + step, &mut best);
+                }
+            }
+            let py = ly as isize - step;
+            if py >= 0 && (py as usize) < band_height {
+                let n = rows[py as usize * width + x];
+                if field.target[[y0 + py as usize, x]] {
+                    try_candidate(n.y as isize + step, n.x as isize, &mut best);
+                }
+            }
+
+            // Random search
+            let mut radius = max_radius;
+            let mut k = 0;
+            while radius >= 1.0 {
+                let ry = (hash_uniform(x as i64, y as i64, 2 * k, seed) * 2.0 - 1.0) * radius;
+                let rx = (hash_uniform(x as i64, y as i64, 2 * k + 1, seed) * 2.0 - 1.0) * radius;
+                try_candidate(best.y as isize + ry as isize, best.x as isize + rx as isize, &mut best);
+                radius *= 0.5;
+                k += 1;
+            }
+            rows[i] = best;
+        }
+    });
+}
+
+/// Recompute the hole pixels as the average of all overlapping matched
+/// patches, each weighted by exp(-distance / median distance) so that
+/// poorly fitting patches don't blur seams (Wexler et al.).
+fn vote(level: &mut Level, source: &Source, field: &Field, nnf: &[Match]) {
+    let (height, width, channels) = level.image.dim();
+    let r = field.r as isize;
+    let hole = &level.hole;
+    let mut distances: Vec<f32> = nnf
+        .iter()
+        .enumerate()
+        .filter(|&(i, m)| field.target[[i / width, i % width]] && m.distance.is_finite())
+        .map(|(_, m)| m.distance)
+        .collect();
+    let scale = if distances.is_empty() {
+        f32::INFINITY
+    } else {
+        let mid = distances.len() / 2;
+        *distances.select_nth_unstable_by(mid, f32::total_cmp).1 + 1e-6
+    };
+    let values: Vec<Vec<(usize, Vec<f32>)>> = (0..height)
+        .into_par_iter()
+        .map(|y| {
+            (0..width)
+                .filter(|&x| hole[[y, x]])
+                .filter_map(|x| {
+                    let mut sum = vec![0.0; channels];
+                    let mut total = 0.0;
+                    for dy in -r..=r {
+                        for dx in -r..=r {
+                            let (qy, qx) = (y as isize + dy, x as isize + dx);
+                            if qy < 0 || qx < 0 || qy as usize >= height || qx as usize >= width {
+                                continue;
+                            }
+                            if !field.target[[qy as usize, qx as usize]] {
+                                continue;
+                            }
+                            let m = nnf[qy as usize * width + qx as usize];
+                            let (sy, sx) = ((m.y as isize - dy) as usize, (m.x as isize - dx) as usize);
+                            let weight = if m.distance.is_finite() { (-m.distance / scale).exp().max(1e-6) } else { 1.0 };
+                            for (c, s) in sum.iter_mut().enumerate() {
+                                *s += weight * source.image[[sy, sx, c]];
+                            }
+                            total += weight;
+                        }
+                    }
+                    (total > 0.0).then(|| (x, sum.into_iter().map(|s| s / total).collect()))
+                })
+                .collect()
+        })
+        .collect();
+    for (y, row) in values.into_iter().enumerate() {
+        for (x, value) in row {
+            for (c, v) in value.into_iter().enumerate() {
+                level.image[[y, x, c]] = v;
+            }
+        }
+    }
+}
+
+/// Alternate PatchMatch and voting on one level, starting from `nnf`.
+fn synthesize_level(level: &mut Level, source: &Source, field: &Field, nnf: &mut [Match], seed: u64) {
+    for em in 0..EM_ITERATIONS {
+        update_distances(&level.image, &source.image, field, nnf);
+        for pass in 0..PM_ITERATIONS {
+            let pass_seed = seed ^ ((em * PM_ITERATIONS + pass) as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            patchmatch_pass(&level.image, &source.image, field, nnf, pass % 2 == 1, pass_seed);
+        }
+        vote(level, source, field, nnf);
+    }
+}
+
+/// Run match-and-vote coarse to fine. `targets` and `sources` are pyramids
+/// (finest first) of equal depth; `init` prepares the coarsest target.
+fn synthesize_pyramid<F>(mut targets: Vec<Level>, mut sources: Vec<Source>, r: usize, seed: u64, init: F) -> Array3<f32>
+where
+    F: FnOnce(&mut Level, &Source, &Field, &[Match]),
+{
+    let mut level = targets.pop().expect("pyramid has a level");
+    let source = sources.pop().expect("pyramid has a level");
+    let (height, width, _) = level.image.dim();
+    let field = Field::new(&source.hole, &level.hole, r);
+    let mut nnf = field.random_nnf(height, width, seed);
+    init(&mut level, &source, &field, &nnf);
+    synthesize_level(&mut level, &source, &field, &mut nnf, seed);
+
+    let mut previous = (level, nnf);
+    while let (Some(mut level), Some(source)) = (targets.pop(), sources.pop()) {
+        let (coarse, coarse_nnf) = &previous;
+        let (height, width, channels) = level.image.dim();
+        let (source_height, source_width, _) = source.image.dim();
+        let coarse_width = coarse.image.dim().1;
+        for y in 0..height {
+            for x in 0..width {
+                if level.hole[[y, x]] {
+                    for c in 0..channels {
+                        level.image[[y, x, c]] = coarse.image[[y / 2, x / 2, c]];
+                    }
+                }
+            }
+        }
+        let field = Field::new(&source.hole, &level.hole, r);
+        let mut nnf: Vec<Match> = (0..height * width)
+            .map(|i| {
+                let (y, x) = (i / width, i % width);
+                let m = coarse_nnf[(y / 2) * coarse_width + x / 2];
+                let sy = (2 * m.y as usize + y % 2).min(source_height - 1);
+                let sx = (2 * m.x as usize + x % 2).min(source_width - 1);
+                let (sy, sx) = if field.valid[[sy, sx]] { (sy as u32, sx as u32) } else { field.random_source(y, x, 0, seed) };
+                Match { y: sy, x: sx, distance: f32::INFINITY }
+            })
+            .collect();
+        synthesize_level(&mut level, &source, &field, &mut nnf, seed);
+        previous = (level, nnf);
+    }
+    previous.0.image
+}
+
+fn patch_radius(patch_size: usize) -> usize {
+    (patch_size.max(3) - 1) / 2
+}
+
+// ============================================================================
+// Nearest-Neighbor Field
+// ============================================================================
+
+/// PatchMatch result: the most similar source patch for every target pixel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearestNeighborField {
+    /// Center of the matched source patch, (height, width, 2) as (x, y)
+    pub matches: Array3<u32>,
+    /// Sum of squared differences of each match (infinite if the source is
+    /// smaller than one patch)
+    pub distances: Array2<f32>,
+}
+
+/// Find the most similar source patch for every target patch - f32 version.
+///
+/// Randomized PatchMatch search: alternating propagation and random search
+/// passes, each in O(target pixels). Source patches lie fully inside the
+/// source; target patches may reach over the border.
+///
+/// # Arguments
+/// * `target` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `source` - Image with the same channel count
+/// * `patch_size` - Edge length of the compared patches (odd)
+/// * `iterations` - Number of passes (5 is usually converged)
+/// * `seed` - Seed of the random initialization and search
+///
+/// # Panics
+/// If the channel counts differ.
+pub fn patchmatch_nnf_f32(
+    target: ArrayView3<f32>,
+    source: ArrayView3<f32>,
+    patch_size: usize,
+    iterations: usize,
+    seed: u64,
+) -> NearestNeighborField {
+    let (height, width, channels) = target.dim();
+    assert_eq!(channels, source.dim().2, "target and source need the same channel count");
+    let r = patch_radius(patch_size);
+    let (source_height, source_width, _) = source.dim();
+    let field = Field::new(&Array2::from_elem((source_height, source_width), false), &Array2::from_elem((height, width), true), r);
+    if field.sources.is_empty() {
+        return NearestNeighborField {
+            matches: Array3::zeros((height, width, 2)),
+            distances: Array2::from_elem((height, width), f32::INFINITY),
+        };
+    }
+    let (target, source) = (target.to_owned(), source.to_owned());
+    let mut nnf = field.random_nnf(height, width, seed);
+    update_distances(&target, &source, &field, &mut nnf);
+    for pass in 0..iterations {
+        let pass_seed = seed ^ (pass as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        patchmatch_pass(&target, &source, &field, &mut nnf, pass % 2 == 1, pass_seed);
+    }
+    NearestNeighborField {
+        matches: Array3::from_shape_fn((height, width, 2), |(y, x, k)| {
+            let m = nnf[y * width + x];
+            if k == 0 { m.x } else { m.y }
+        }),
+        distances: Array2::from_shape_fn((height, width), |(y, x)| nnf[y * width + x].distance),
+    }
+}
+
+/// Find the most similar source patch for every target patch - u8 version.
+///
+/// Distances are measured on values scaled to 0.0-1.0.
+pub fn patchmatch_nnf_u8(
+    target: ArrayView3<u8>,
+    source: ArrayView3<u8>,
+    patch_size: usize,
+    iterations: usize,
+    seed: u64,
+) -> NearestNeighborField {
+    let target_f32 = target.mapv(|v| v as f32 / 255.0);
+    let source_f32 = source.mapv(|v| v as f32 / 255.0);
+    patchmatch_nnf_f32(target_f32.view(), source_f32.view(), patch_size, iterations, seed)
+}
+
+// ============================================================================
+// Region Fill
+// ============================================================================
+
+/// Fill a masked region with texture from the rest of the image - f32 version.
+///
+/// Backs content-aware canvas extension and healing.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `mask` - (height, width) mask, values >= 0.5 mark the pixels to replace
+/// * `patch_size` - Edge length of the matched patches (odd, 7 is a good default)
+/// * `seed` - Seed of the random search; equal seeds give equal results
+///
+/// # Returns
+/// Image with the masked pixels synthesized; unmasked pixels are unchanged.
+/// If no patch fits entirely outside the mask the input is returned as is.
+pub fn fill_region_patchmatch_f32(image: ArrayView3<f32>, mask: ArrayView2<f32>, patch_size: usize, seed: u64) -> Array3<f32> {
+    let r = patch_radius(patch_size);
+    let mut levels = vec![Level { image: image.to_owned(), hole: mask.mapv(|m| m >= 0.5) }];
+    let hole = &levels[0].hole;
+    if !hole.iter().any(|&h| h) || Field::new(hole, hole, r).sources.is_empty() {
+        return image.to_owned();
+    }
+    // Pyramid down while the coarser level still has source patches
+    while levels.len() < 8 {
+        let last = levels.last().expect("pyramid has a level");
+        let (height, width, _) = last.image.dim();
+        if height.min(width) < 8 * (2 * r + 1) {
+            break;
+        }
+        let coarse = downsample(last);
+        if Field::new(&coarse.hole, &coarse.hole, r).sources.is_empty() {
+            break;
+        }
+        levels.push(coarse);
+    }
+    let sources = levels.iter().map(|level| Source { image: level.image.clone(), hole: level.hole.clone() }).collect();
+    synthesize_pyramid(levels, sources, r, seed, |level, _, _, _| onion_peel(level))
+}
+
+/// Fill a masked region with texture from the rest of the image - u8 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `mask` - (height, width) mask, values >= 128 mark the pixels to replace
+/// * `patch_size` - Edge length of the matched patches (odd, 7 is a good default)
+/// * `seed` - Seed of the random search
+pub fn fill_region_patchmatch_u8(image: ArrayView3<u8>, mask: ArrayView2<u8>, patch_size: usize, seed: u64) -> Array3<u8> {
+    let image_f32 = image.mapv(|v| v as f32 / 255.0);
+    let mask_f32 = mask.mapv(|v| v as f32 / 255.0);
+    fill_region_patchmatch_f32(image_f32.view(), mask_f32.view(), patch_size, seed)
+        .mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Texture Synthesis
+// ============================================================================
+
+/// Synthesize a texture of any size from a sample - f32 version.
+///
+/// The output starts as a patchwork of random sample patches and is then
+/// refined coarse to fine so every patch resembles some patch of the
+/// sample (Kwatra et al., "Texture Optimization"). Works best on
+/// stochastic or regular textures; large objects get broken up.
+///
+/// # Arguments
+/// * `sample` - Texture sample with 1, 3, or 4 channels (height, width, channels)
+/// * `out_width`, `out_height` - Size of the result
+/// * `patch_size` - Edge length of the matched patches (odd); larger keeps bigger structures
+/// * `seed` - Seed of the random initialization and search
+///
+/// # Returns
+/// Texture of (out_height, out_width, channels). A sample smaller than one
+/// patch is tiled instead.
+pub fn synthesize_texture_f32(sample: ArrayView3<f32>, out_width: usize, out_height: usize, patch_size: usize, seed: u64) -> Array3<f32> {
+    let (height, width, channels) = sample.dim();
+    let r = patch_radius(patch_size);
+    if height < 2 * r + 1 || width < 2 * r + 1 || out_width == 0 || out_height == 0 {
+        if height == 0 || width == 0 {
+            return Array3::zeros((out_height, out_width, channels));
+        }
+        return Array3::from_shape_fn((out_height, out_width, channels), |(y, x, c)| sample[[y % height, x % width, c]]);
+    }
+    let mut sources = vec![Source { image: sample.to_owned(), hole: Array2::from_elem((height, width), false) }];
+    let mut targets = vec![Level { image: Array3::zeros((out_height, out_width, channels)), hole: Array2::from_elem((out_height, out_width), true) }];
+    while sources.len() < 8 {
+        let (source, target) = (sources.last().expect("pyramid has a level"), targets.last().expect("pyramid has a level"));
+        let (sh, sw, _) = source.image.dim();
+        let (th, tw, _) = target.image.dim();
+        if sh.min(sw) < 8 * (2 * r + 1) || th.min(tw) < 4 * (2 * r + 1) {
+            break;
+        }
+        let coarse = downsample(&Level { image: source.image.clone(), hole: source.hole.clone() });
+        let (ch, cw) = (th.div_ceil(2), tw.div_ceil(2));
+        sources.push(Source { image: coarse.image, hole: coarse.hole });
+        targets.push(Level { image: Array3::zeros((ch, cw, channels)), hole: Array2::from_elem((ch, cw), true) });
+    }
+    synthesize_pyramid(targets, sources, r, seed, vote)
+}
+
+/// Synthesize a texture of any size from a sample - u8 version.
+pub fn synthesize_texture_u8(sample: ArrayView3<u8>, out_width: usize, out_height: usize, patch_size: usize, seed: u64) -> Array3<u8> {
+    let sample_f32 = sample.mapv(|v| v as f32 / 255.0);
+    synthesize_texture_f32(sample_f32.view(), out_width, out_height, patch_size, seed)
+        .mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+// ============================================================================
+// Seamless Cloning
+// ============================================================================
+
+/// Guidance field used by [`seamless_clone_f32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CloneMode {
+    /// Source gradients only: the pasted object keeps its own texture
+    #[default]
+    Normal,
+    /// Per pixel pair the stronger of source and destination gradient, so
+    /// destination texture shows through flat parts of the source
+    Mixed,
+}
+
+impl CloneMode {
+    /// Parse a mode name ("normal" or "mixed").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "normal" => Some(Self::Normal),
+            "mixed" => Some(Self::Mixed),
+            _ => None,
+        }
+    }
+}
+
+/// Conjugate-gradient iterations per channel before giving up on the tolerance.
+const CLONE_MAX_ITERATIONS: usize = 2000;
+/// Target RMS residual of the Poisson system.
+const CLONE_TOLERANCE: f64 = 1e-5;
+
+/// Neighbor of an unknown pixel in the Poisson system.
+#[derive(Clone, Copy)]
+enum CloneNeighbor {
+    /// Another unknown, by index
+    Inside(usize),
+    /// Destination pixel on the region border, fixed
+    Border(usize, usize),
+}
+
+/// Solve `A x = b` for the clone region with conjugate gradients, where
+/// `A` is the region's graph Laplacian plus its border terms.
+fn solve_clone_channel(neighbors: &[Vec<CloneNeighbor>], b: &[f64], mut x: Vec<f64>) -> Vec<f64> {
+    let apply = |v: &[f64]| -> Vec<f64> {
+        neighbors
+            .iter()
+            .enumerate()
+            .map(|(i, ns)| {
+                let inside = ns.iter().filter_map(|n| match n {
+                    CloneNeighbor::Inside(j) => Some(v[*j]),
+                    CloneNeighbor::Border(..) => None,
+                });
+                ns.len() as f64 * v[i] - inside.sum::<f64>()
+            })
+            .collect()
+    };
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+    let ax = apply(&x);
+    let mut r: Vec<f64> = b.iter().zip(&ax).map(|(b, a)| b - a).collect();
+    let mut p = r.clone();
+    let mut rr = dot(&r, &r);
+    let limit = CLONE_TOLERANCE * CLONE_TOLERANCE * b.len() as f64;
+    for _ in 0..CLONE_MAX_ITERATIONS {
+        if rr <= limit {
+            break;
+        }
+        let ap = apply(&p);
+        let pap = dot(&p, &ap);
+        if pap <= 0.0 {
+            break;
+        }
+        let alpha = rr / pap;
+        x.iter_mut().zip(&p).for_each(|(x, p)| *x += alpha * p);
+        r.iter_mut().zip(&ap).for_each(|(r, ap)| *r -= alpha * ap);
+        let rr_next = dot(&r, &r);
+        let beta = rr_next / rr;
+        p.iter_mut().zip(&r).for_each(|(p, r)| *p = r + beta * *p);
+        rr = rr_next;
+    }
+    x
+}
+
+/// Paste a masked source region into a destination with Poisson blending - f32 version.
+///
+/// Poisson image editing (Pérez et al., 2003): inside the region the
+/// result keeps the gradients of the source (or, in [`CloneMode::Mixed`],
+/// the stronger of source and destination gradient) while its border
+/// matches the destination exactly, so the pasted object takes on the
+/// target's lighting and color cast.
+///
+/// # Arguments
+/// * `src` - Source image (src_height, src_width, channels)
+/// * `dst` - Destination image with the same channel count
+/// * `mask` - (src_height, src_width) mask, values >= 0.5 mark the pixels to clone
+/// * `position` - (x, y) of the source's top-left corner in `dst`; may be negative
+/// * `mode` - Guidance field, see [`CloneMode`]
+///
+/// # Returns
+/// Copy of `dst` with the blended region. Masked pixels falling outside
+/// `dst` are dropped. The alpha channel of 4 channel images is taken from
+/// `dst` unchanged.
+pub fn seamless_clone_f32(src: ArrayView3<f32>, dst: ArrayView3<f32>, mask: ArrayView2<f32>, position: (i64, i64), mode: CloneMode) -> Array3<f32> {
+    let (sh, sw, channels) = src.dim();
+    let (dh, dw, dst_channels) = dst.dim();
+    assert_eq!(channels, dst_channels, "source and destination channel counts differ");
+    assert_eq!(mask.dim(), (sh, sw), "mask size must match the source");
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let (px, py) = position;
+
+    // Destination pixel -> source pixel, for every pixel of the region
+    let to_src = |x: usize, y: usize| -> Option<(usize, usize)> {
+        let (sx, sy) = (x as i64 - px, y as i64 - py);
+        (sx >= 0 && sy >= 0 && (sx as usize) < sw && (sy as usize) < sh).then_some((sx as usize, sy as usize))
+    };
+    let in_region = |x: usize, y: usize| to_src(x, y).is_some_and(|(sx, sy)| mask[[sy, sx]] >= 0.5);
+
+    let mut index = Array2::from_elem((dh, dw), usize::MAX);
+    let mut pixels = Vec::new();
+    for y in 0..dh {
+        for x in 0..dw {
+            if in_region(x, y) {
+                index[[y, x]] = pixels.len();
+                pixels.push((x, y));
+            }
+        }
+    }
+    let mut result = dst.to_owned();
+    if pixels.is_empty() {
+        return result;
+    }
+
+    let neighbors: Vec<Vec<CloneNeighbor>> = pixels
+        .iter()
+        .map(|&(x, y)| {
+            let candidates = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+            candidates
+                .into_iter()
+                .filter(|&(nx, ny)| nx < dw && ny < dh)
+                .map(|(nx, ny)| match index[[ny, nx]] {
+                    usize::MAX => CloneNeighbor::Border(nx, ny),
+                    j => CloneNeighbor::Inside(j),
+                })
+                .collect()
+        })
+        .collect();
+
+    let solved: Vec<Vec<f64>> = (0..color_channels)
+        .into_par_iter()
+        .map(|c| {
+            let src_at = |x: usize, y: usize| to_src(x, y).map(|(sx, sy)| src[[sy, sx, c]] as f64);
+            let dst_at = |x: usize, y: usize| dst[[y, x, c]] as f64;
+            let guidance = |(x, y): (usize, usize), (nx, ny): (usize, usize)| -> f64 {
+                let d = dst_at(x, y) - dst_at(nx, ny);
+                // Source pixels cut off by the source border carry no gradient
+                let s = match (src_at(x, y), src_at(nx, ny)) {
+                    (Some(a), Some(b)) => a - b,
+                    _ => 0.0,
+                };
+                match mode {
+                    CloneMode::Normal => s,
+                    CloneMode::Mixed if d.abs() > s.abs() => d,
+                    CloneMode::Mixed => s,
+                }
+            };
+            let b: Vec<f64> = pixels
+                .iter()
+                .zip(&neighbors)
+                .map(|(&p, ns)| {
+                    ns.iter()
+                        .map(|n| match *n {
+                            CloneNeighbor::Inside(j) => guidance(p, pixels[j]),
+                            CloneNeighbor::Border(nx, ny) => guidance(p, (nx, ny)) + dst_at(nx, ny),
+                        })
+                        .sum()
+                })
+                .collect();
+            // Start from the source shifted by the mean border offset
+            let (offset_sum, offset_count) = pixels
+                .iter()
+                .zip(&neighbors)
+                .flat_map(|(&(x, y), ns)| {
+                    ns.iter().filter_map(move |n| match *n {
+                        CloneNeighbor::Border(nx, ny) => Some((dst_at(nx, ny) - src_at(x, y).unwrap_or(0.0), 1usize)),
+                        CloneNeighbor::Inside(_) => None,
+                    })
+                })
+                .fold((0.0, 0), |(s, n), (d, k)| (s + d, n + k));
+            let offset = if offset_count > 0 { offset_sum / offset_count as f64 } else { 0.0 };
+            let x0 = pixels.iter().map(|&(x, y)| src_at(x, y).unwrap_or(0.0) + offset).collect();
+            solve_clone_channel(&neighbors, &b, x0)
+        })
+        .collect();
+
+    for (i, &(x, y)) in pixels.iter().enumerate() {
+        for (c, values) in solved.iter().enumerate() {
+            result[[y, x, c]] = values[i].clamp(0.0, 1.0) as f32;
+        }
+    }
+    result
+}
+
+/// Paste a masked source region into a destination with Poisson blending - u8 version.
+///
+/// # Arguments
+/// * `src` - Source image (src_height, src_width, channels)
+/// * `dst` - Destination image with the same channel count
+/// * `mask` - (src_height, src_width) mask, values >= 128 mark the pixels to clone
+/// * `position` - (x, y) of the source's top-left corner in `dst`
+/// * `mode` - Guidance field, see [`CloneMode`]
+pub fn seamless_clone_u8(src: ArrayView3<u8>, dst: ArrayView3<u8>, mask: ArrayView2<u8>, position: (i64, i64), mode: CloneMode) -> Array3<u8> {
+    let src_f32 = src.mapv(|v| v as f32 / 255.0);
+    let dst_f32 = dst.mapv(|v| v as f32 / 255.0);
+    let mask_f32 = mask.mapv(|v| v as f32 / 255.0);
+    seamless_clone_f32(src_f32.view(), dst_f32.view(), mask_f32.view(), position, mode)
+        .mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_continues_stripes() {
+        let stripes = |x: usize| if (x / 4).is_multiple_of(2) { 0.2 } else { 0.8 };
+        let truth = Array3::from_shape_fn((64, 64, 1), |(_, x, _)| stripes(x));
+        let mut damaged = truth.clone();
+        let mask = Array2::from_shape_fn((64, 64), |(y, x)| if (24..40).contains(&y) && (24..40).contains(&x) { 1.0 } else { 0.0 });
+        damaged.indexed_iter_mut().filter(|((y, x, _), _)| mask[[*y, *x]] > 0.0).for_each(|(_, v)| *v = 0.0);
+
+        let filled = fill_region_patchmatch_f32(damaged.view(), mask.view(), 7, 1);
+        let error: f32 = filled.iter().zip(truth.iter()).map(|(a, b)| (a - b).abs()).sum::<f32>() / (16.0 * 16.0);
+        assert!(error < 0.05, "mean error {error}");
+        assert_eq!(filled[[0, 0, 0]], truth[[0, 0, 0]]);
+
+        // Nothing to copy from: unchanged
+        let full = Array2::<f32>::ones((64, 64));
+        assert_eq!(fill_region_patchmatch_f32(damaged.view(), full.view(), 7, 1), damaged);
+    }
+
+    #[test]
+    fn test_nnf_finds_shifted_crop_and_texture_keeps_values() {
+        let source = Array3::from_shape_fn((40, 40, 3), |(y, x, c)| hash_uniform(x as i64, y as i64, c as u32, 7));
+        let target = source.slice(ndarray::s![5..35, 3..33, ..]).to_owned();
+        let nnf = patchmatch_nnf_f32(target.view(), source.view(), 5, 5, 3);
+        assert_eq!(nnf.matches.dim(), (30, 30, 2));
+        let exact = (2..28)
+            .flat_map(|y| (2..28).map(move |x| (y, x)))
+            .filter(|&(y, x)| nnf.matches[[y, x, 0]] == x as u32 + 3 && nnf.matches[[y, x, 1]] == y as u32 + 5)
+            .count();
+        assert!(exact > 26 * 26 * 95 / 100, "{exact} exact matches");
+
+        let stripes = Array3::from_shape_fn((32, 32, 1), |(_, x, _)| if (x / 4).is_multiple_of(2) { 0.2 } else { 0.8 });
+        let texture = synthesize_texture_f32(stripes.view(), 48, 40, 7, 1);
+        assert_eq!(texture.dim(), (40, 48, 1));
+        let off: f32 = texture.iter().map(|v| (v - 0.2).abs().min((v - 0.8).abs())).sum::<f32>() / texture.len() as f32;
+        assert!(off < 0.05, "mean distance to stripe values {off}");
+    }
+
+    #[test]
+    fn test_seamless_clone_matches_target_lighting() {
+        // Source: a bright blob on a bright background; destination: dark and flat
+        let blob = |x: usize, y: usize| 0.7 + 0.2 * (-((x as f32 - 10.0).powi(2) + (y as f32 - 10.0).powi(2)) / 20.0).exp();
+        let src = Array3::from_shape_fn((20, 20, 3), |(y, x, _)| blob(x, y));
+        let dst = Array3::from_elem((40, 40, 3), 0.2f32);
+        let mask = Array2::from_shape_fn((20, 20), |(y, x)| if (2..18).contains(&y) && (2..18).contains(&x) { 1.0 } else { 0.0 });
+
+        let cloned = seamless_clone_f32(src.view(), dst.view(), mask.view(), (10, 10), CloneMode::Normal);
+        assert_eq!(cloned.dim(), dst.dim());
+        assert_eq!(cloned[[0, 0, 0]], 0.2);
+        // The blob survives, the background brightness follows the destination
+        let (center, corner) = (cloned[[20, 20, 1]], cloned[[13, 13, 1]]);
+        assert!(center - corner > 0.15, "center {center} corner {corner}");
+        assert!((corner - 0.2).abs() < 0.03, "corner {corner}");
+
+        // Mixed mode keeps strong destination edges under a flat source
+        let flat = Array3::from_elem((20, 20, 1), 0.5f32);
+        let edge = Array3::from_shape_fn((20, 20, 1), |(_, x, _)| if x < 10 { 0.1 } else { 0.9 });
+        let inner = Array2::from_shape_fn((20, 20), |(y, x)| if (3..17).contains(&y) && (3..17).contains(&x) { 1.0 } else { 0.0 });
+        let mixed = seamless_clone_f32(flat.view(), edge.view(), inner.view(), (0, 0), CloneMode::Mixed);
+        assert!(mixed[[10, 10, 0]] - mixed[[10, 9, 0]] > 0.5);
+        let normal = seamless_clone_f32(flat.view(), edge.view(), inner.view(), (0, 0), CloneMode::Normal);
+        assert!(normal[[10, 10, 0]] - normal[[10, 9, 0]] < 0.2);
+    }
+}
@@ -0,0 +1,47 @@
+/**
+ * Rayon thread pool for the threaded WASM build.
+ *
+ * Co-located with:
+ * - rust/src/wasm_threads.rs (Rust side, `wasm-threads` feature)
+ *
+ * Spawns one module worker per rayon thread. Every worker instantiates the
+ * same compiled module on the shared memory and then runs its thread, so
+ * blurs and morphology filters use all cores.
+ *
+ * Usage (inside a worker, after the module was initialized):
+ *   import { initThreadPool } from '/imgstag/filters/thread_pool.js';
+ *   await initThreadPool(wasmModule, wasmMemory, navigator.hardwareConcurrency);
+ *
+ * Requires a cross-origin isolated page (COOP/COEP) for SharedArrayBuffer.
+ */
+
+import * as wasm from '../wasm/imagestag_rust.js';
+
+/**
+ * Whether this environment can run the threaded build.
+ * @returns {boolean}
+ */
+export function threadsSupported() {
+    return typeof SharedArrayBuffer !== 'undefined' && globalThis.crossOriginIsolated === true;
+}
+
+/**
+ * Start the rayon pool. Call once per module instance.
+ * @param {WebAssembly.Module} wasmModule - Compiled module of the threaded build
+ * @param {WebAssembly.Memory} memory - Shared memory of the initialized module
+ * @param {number} numThreads - Number of pool threads
+ * @returns {Promise<void>} Resolves once every worker has started
+ */
+export async function initThreadPool(wasmModule, memory, numThreads = navigator.hardwareConcurrency) {
+    const workerUrl = new URL('./thread_pool_worker.js', import.meta.url);
+    const started = [];
+    wasm.buildThreadPool(numThreads, (thread) => {
+        const worker = new Worker(workerUrl, { type: 'module' });
+        started.push(new Promise((resolve, reject) => {
+            worker.addEventListener('message', resolve, { once: true });
+            worker.addEventListener('error', reject, { once: true });
+        }));
+        worker.postMessage({ module: wasmModule, memory, thread });
+    });
+    await Promise.all(started);
+}
@@ -0,0 +1,12 @@
+/**
+ * Worker entry point for one rayon thread (see thread_pool.js).
+ */
+
+import { initSync, startWorker } from '../wasm/imagestag_rust.js';
+
+self.addEventListener('message', ({ data }) => {
+    initSync({ module: data.module, memory: data.memory });
+    self.postMessage('started');
+    // Runs until the pool shuts down
+    startWorker(data.thread);
+}, { once: true });
@@ -0,0 +1,50 @@
+/**
+ * Tone mapping - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - tonemap.rs (Rust implementation)
+ * - tonemap.py (Python wrapper)
+ *
+ * Provides: tonemap
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Tone Mapping
+// ============================================================================
+
+/**
+ * Compress linear HDR values into 0.0-1.0 (f32 only).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels}, values may exceed 1.0
+ * @param {Object} options - {operator: 'reinhard'|'filmic'|'aces', exposure (stops), whitePoint}
+ * @returns {Object} - Tone mapped image data {data: Float32Array, width, height, channels}
+ */
+export function tonemap(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+
+    const result = wasm.tonemap_wasm(
+        data,
+        width,
+        height,
+        channels,
+        options.operator ?? 'reinhard',
+        options.exposure ?? 0.0,
+        options.whitePoint ?? 11.2
+    );
+
+    return {
+        data: new Float32Array(result.buffer),
+        width,
+        height,
+        channels
+    };
+}
+
+export default {
+    initWasm,
+    tonemap
+};
@@ -0,0 +1,64 @@
+"""Tone mapping with Rust backend.
+
+This module compresses linear HDR float32 data (values above 1.0) into
+display range with a smooth highlight shoulder instead of clipping:
+- Reinhard (extended, with white point)
+- Filmic (John Hable's Uncharted 2 curve)
+- ACES (Krzysztof Narkowicz's fit of the reference rendering)
+
+Every operator maps `white_point` exactly to 1.0. The result is still
+linear; apply the display transfer function (e.g. sRGB) afterwards.
+
+## Supported Formats
+
+float32 arrays with 1, 3, or 4 channels. Color channels are processed
+independently, alpha is preserved. There is no uint8 version, since 8-bit
+images can't hold values above 1.0.
+
+Co-located with:
+- tonemap.rs (Rust implementation)
+- tonemap.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.tonemap import tonemap
+
+    display = tonemap(hdr, operator="aces", exposure=-0.5)
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Tone Mapping
+# ============================================================================
+
+def tonemap(image: np.ndarray, operator: str = "reinhard", exposure: float = 0.0,
+            white_point: float = 11.2) -> np.ndarray:
+    """Compress linear HDR values into 0.0-1.0 (f32 only).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), linear
+            values >= 0.0 (may exceed 1.0)
+        operator: "reinhard", "filmic" (alias "hable") or "aces"
+        exposure: Exposure adjustment in stops, applied before the curve
+        white_point: Linear value (after exposure) that maps to 1.0
+
+    Returns:
+        float32 array with same channel count, values 0.0-1.0 (linear)
+    """
+    _validate_image(image, np.float32, "tonemap")
+    return imagestag_rust.tonemap(image, operator, exposure, white_point)
+
+
+__all__ = [
+    'tonemap',
+]
@@ -0,0 +1,177 @@
+//! Tone mapping: Reinhard, Filmic (Hable), ACES.
+//!
+//! HDR renders, merged brackets and linear EXR data hold values far above
+//! 1.0. Tone mapping compresses that range into 0.0-1.0 with a smooth
+//! shoulder instead of clipping, so highlights keep their gradation. All
+//! operators are applied per channel on linear values and normalized so
+//! that `white_point` maps exactly to 1.0. The output is still linear; apply
+//! the display transfer function afterwards, e.g. sRGB with
+//! [`encode_transfer_f32`](super::colorspace::encode_transfer_f32).
+//!
+//! Unlike the other filters these only have an f32 version: u8 input can't
+//! hold values above 1.0, so there is nothing to compress.
+//!
+//! ## Supported Formats
+//!
+//! All filters accept images with 1, 3, or 4 channels:
+//! - **Grayscale**: (height, width, 1) - processes the single channel
+//! - **RGB**: (height, width, 3) - processes each channel on its own
+//! - **RGBA**: (height, width, 4) - processes RGB, preserves alpha
+
+use ndarray::{Array3, ArrayView3};
+
+/// Pre-scale of the Hable curve, as in its reference implementation.
+const FILMIC_EXPOSURE_BIAS: f32 = 2.0;
+
+/// Pre-scale of the ACES fit (Narkowicz), so mid gray lands where the
+/// reference transform puts it.
+const ACES_INPUT_SCALE: f32 = 0.6;
+
+/// Smallest white point, keeps the normalization finite.
+const MIN_WHITE_POINT: f32 = 1e-3;
+
+/// Tone mapping curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TonemapOperator {
+    /// Extended Reinhard `x (1 + x / w²) / (1 + x)`: soft, keeps midtones
+    #[default]
+    Reinhard,
+    /// John Hable's filmic curve (Uncharted 2): toe and shoulder like film
+    Filmic,
+    /// Krzysztof Narkowicz's fit of the ACES reference rendering: punchy
+    /// contrast, saturated highlights roll off quickly
+    Aces,
+}
+
+impl TonemapOperator {
+    /// Parse an operator name ("reinhard", "filmic" / "hable", "aces").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "reinhard" => Some(TonemapOperator::Reinhard),
+            "filmic" | "hable" => Some(TonemapOperator::Filmic),
+            "aces" => Some(TonemapOperator::Aces),
+            _ => None,
+        }
+    }
+}
+
+/// Tone mapping parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TonemapParams {
+    /// Curve to apply
+    pub operator: TonemapOperator,
+    /// Exposure adjustment in stops applied before the curve
+    pub exposure: f32,
+    /// Linear input value (after exposure) that maps to 1.0
+    pub white_point: f32,
+}
+
+impl Default for TonemapParams {
+    fn default() -> Self {
+        // 11.2 is the linear white of Hable's reference curve
+        Self { operator: TonemapOperator::Reinhard, exposure: 0.0, white_point: 11.2 }
+    }
+}
+
+/// Hable's rational curve with the Uncharted 2 constants.
+#[inline]
+fn hable(x: f32) -> f32 {
+    const A: f32 = 0.15; // shoulder strength
+    const B: f32 = 0.50; // linear strength
+    const C: f32 = 0.10; // linear angle
+    const D: f32 = 0.20; // toe strength
+    const E: f32 = 0.02; // toe numerator
+    const F: f32 = 0.30; // toe denominator
+    (x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F) - E / F
+}
+
+/// Narkowicz's ACES fit.
+#[inline]
+fn aces(x: f32) -> f32 {
+    (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)
+}
+
+/// Map one linear value (already exposed) to 0.0-1.0.
+#[inline]
+fn tonemap_value(x: f32, operator: TonemapOperator, white: f32) -> f32 {
+    let x = x.max(0.0);
+    if x == 0.0 {
+        return 0.0;
+    }
+    let mapped = match operator {
+        TonemapOperator::Reinhard => x * (1.0 + x / (white * white)) / (1.0 + x),
+        TonemapOperator::Filmic => hable(x * FILMIC_EXPOSURE_BIAS) / hable(white * FILMIC_EXPOSURE_BIAS),
+        TonemapOperator::Aces => aces(x * ACES_INPUT_SCALE) / aces(white * ACES_INPUT_SCALE),
+    };
+    mapped.clamp(0.0, 1.0)
+}
+
+/// Tone map HDR values into display range - f32 only.
+///
+/// Negative and NaN values map to 0.0; values at or above the white point
+/// map to 1.0.
+///
+/// # Arguments
+/// * `input` - Image with 1, 3, or 4 channels (height, width, channels), linear values >= 0.0
+/// * `params` - Operator, exposure and white point
+///
+/// # Returns
+/// Image with same channel count, values 0.0-1.0 (linear), alpha preserved
+pub fn tonemap_f32(input: ArrayView3<f32>, params: &TonemapParams) -> Array3<f32> {
+    let channels = input.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let gain = 2.0f32.powf(params.exposure);
+    let white = params.white_point.max(MIN_WHITE_POINT);
+
+    let mut output = input.to_owned();
+    for mut pixel in output.lanes_mut(ndarray::Axis(2)) {
+        for c in 0..color_channels {
+            pixel[c] = tonemap_value(pixel[c] * gain, params.operator, white);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPERATORS: [TonemapOperator; 3] = [TonemapOperator::Reinhard, TonemapOperator::Filmic, TonemapOperator::Aces];
+
+    #[test]
+    fn test_curves_are_monotonic_and_hit_white() {
+        for operator in OPERATORS {
+            let white = 6.0;
+            let mut last = tonemap_value(0.0, operator, white);
+            assert!(last.abs() < 1e-3, "{operator:?} black -> {last}");
+            for i in 1..=120 {
+                let v = tonemap_value(i as f32 * 0.05, operator, white);
+                assert!(v >= last - 1e-6, "{operator:?} not monotonic at {i}");
+                last = v;
+            }
+            assert!((tonemap_value(white, operator, white) - 1.0).abs() < 1e-5);
+            assert_eq!(tonemap_value(50.0, operator, white), 1.0);
+            assert_eq!(tonemap_value(f32::NAN, operator, white), 0.0);
+        }
+        // Extended Reinhard reduces to x / (1 + x) for a far white point
+        assert!((tonemap_value(1.0, TonemapOperator::Reinhard, 1e6) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_tonemap_exposure_and_alpha() {
+        let image = Array3::from_shape_fn((2, 3, 4), |(y, x, c)| if c == 3 { 0.4 } else { (y * 3 + x) as f32 * 2.0 });
+        let params = TonemapParams { operator: TonemapOperator::Filmic, ..Default::default() };
+        let result = tonemap_f32(image.view(), &params);
+        assert!(result.iter().all(|&v| (0.0..=1.0).contains(&v)));
+        assert!(result.lanes(ndarray::Axis(2)).into_iter().all(|p| p[3] == 0.4));
+
+        // One stop of exposure equals doubling the input
+        let brighter = tonemap_f32(image.view(), &TonemapParams { exposure: 1.0, ..params });
+        let doubled = tonemap_f32(image.mapv(|v| v * 2.0).view(), &params);
+        for ((y, x, c), &v) in brighter.indexed_iter() {
+            if c < 3 {
+                assert!((v - doubled[[y, x, c]]).abs() < 1e-6);
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/**
+ * Wavelet filters - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - wavelet.rs (Rust implementation)
+ * - wavelet.py (Python wrapper)
+ *
+ * Provides: wavelet_sharpen, wavelet_denoise
+ */
+
+import { initWasm, createU8Filter, createF32Filter, wasm } from './core.js';
+
+export { initWasm };
+
+// ============================================================================
+// Wavelet Sharpen
+// ============================================================================
+
+/**
+ * Sharpen or soften each wavelet detail layer (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {layerGains: number[]} gain per layer, fine to coarse
+ * @returns {Object} - Sharpened image data
+ */
+export const wavelet_sharpen = createU8Filter(
+    wasm.wavelet_sharpen_wasm,
+    (opts) => [new Float32Array(opts.layerGains ?? [1.8, 1.4, 1.0])]
+);
+
+/**
+ * Sharpen or soften each wavelet detail layer (f32).
+ */
+export const wavelet_sharpen_f32 = createF32Filter(
+    wasm.wavelet_sharpen_f32_wasm,
+    (opts) => [new Float32Array(opts.layerGains ?? [1.8, 1.4, 1.0])]
+);
+
+// ============================================================================
+// Wavelet Denoise
+// ============================================================================
+
+/**
+ * Soft-threshold each wavelet detail layer (u8).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels}
+ * @param {Object} options - {thresholds: number[]} 0-255 per layer, fine to coarse
+ * @returns {Object} - Denoised image data
+ */
+export const wavelet_denoise = createU8Filter(
+    wasm.wavelet_denoise_wasm,
+    (opts) => [new Float32Array(opts.thresholds ?? [8, 4, 2])]
+);
+
+/**
+ * Soft-threshold each wavelet detail layer (f32).
+ * @param {Object} options - {thresholds: number[]} given in 0-255, like the u8 version
+ */
+export const wavelet_denoise_f32 = createF32Filter(
+    wasm.wavelet_denoise_f32_wasm,
+    (opts) => [new Float32Array((opts.thresholds ?? [8, 4, 2]).map((t) => t / 255.0))]
+);
+
+export default {
+    initWasm,
+    wavelet_sharpen, wavelet_sharpen_f32,
+    wavelet_denoise, wavelet_denoise_f32
+};
@@ -0,0 +1,110 @@
+"""Wavelet filters with Rust backend.
+
+This module splits images into detail layers with the à trous wavelet
+transform and adjusts each layer on its own:
+- Wavelet sharpen (gain per layer, RegiStax-style)
+- Wavelet denoise (soft threshold per layer)
+
+Layer 0 holds the finest detail (1-2 px), each further layer structures
+twice as large. Layers beyond the given gains / thresholds are left as is.
+
+## Supported Formats
+
+All filters accept numpy arrays with 1, 3, or 4 channels (uint8 or float32).
+Color channels are processed independently, alpha is preserved.
+
+Co-located with:
+- wavelet.rs (Rust implementation)
+- wavelet.js (JavaScript wrapper)
+
+Usage:
+    from imagestag.filters.wavelet import wavelet_sharpen, wavelet_denoise
+
+    sharpened = wavelet_sharpen(image, layer_gains=[1.8, 1.4, 1.0])
+    cleaned = wavelet_denoise(scan, thresholds=[8, 4, 2])
+"""
+import numpy as np
+
+import imagestag_rust
+
+
+def _validate_image(image: np.ndarray, expected_dtype: type, name: str) -> None:
+    """Validate image shape and dtype."""
+    if image.ndim != 3 or image.shape[2] not in (1, 3, 4):
+        raise ValueError(f"{name}: expected image (H, W, 1|3|4), got shape {image.shape}")
+    if image.dtype != expected_dtype:
+        raise ValueError(f"{name}: expected {expected_dtype} dtype, got {image.dtype}")
+
+
+# ============================================================================
+# Wavelet Sharpen
+# ============================================================================
+
+def wavelet_sharpen(image: np.ndarray, layer_gains: list = (1.8, 1.4, 1.0)) -> np.ndarray:
+    """Sharpen or soften each wavelet detail layer (u8).
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        layer_gains: Gain per detail layer, fine to coarse. 1.0 keeps a
+            layer, above 1.0 sharpens it, below softens it, 0.0 removes it
+
+    Returns:
+        Sharpened uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "wavelet_sharpen")
+    return imagestag_rust.wavelet_sharpen(image, list(layer_gains))
+
+
+def wavelet_sharpen_f32(image: np.ndarray, layer_gains: list = (1.8, 1.4, 1.0)) -> np.ndarray:
+    """Sharpen or soften each wavelet detail layer (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        layer_gains: Gain per detail layer, fine to coarse
+
+    Returns:
+        Sharpened float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "wavelet_sharpen_f32")
+    return imagestag_rust.wavelet_sharpen_f32(image, list(layer_gains))
+
+
+# ============================================================================
+# Wavelet Denoise
+# ============================================================================
+
+def wavelet_denoise(image: np.ndarray, thresholds: list = (8.0, 4.0, 2.0)) -> np.ndarray:
+    """Remove noise by soft-thresholding each wavelet detail layer (u8).
+
+    Detail below a layer's threshold is dropped, stronger detail shrinks by
+    it, so edges survive while grain and sensor noise go.
+
+    Args:
+        image: uint8 array with 1, 3, or 4 channels (H, W, C)
+        thresholds: Threshold per detail layer, fine to coarse (0-255)
+
+    Returns:
+        Denoised uint8 array with same channel count
+    """
+    _validate_image(image, np.uint8, "wavelet_denoise")
+    return imagestag_rust.wavelet_denoise(image, list(thresholds))
+
+
+def wavelet_denoise_f32(image: np.ndarray, thresholds: list = (0.03, 0.015, 0.008)) -> np.ndarray:
+    """Remove noise by soft-thresholding each wavelet detail layer (f32).
+
+    Args:
+        image: float32 array with 1, 3, or 4 channels (H, W, C), values 0.0-1.0
+        thresholds: Threshold per detail layer, fine to coarse (0.0-1.0)
+
+    Returns:
+        Denoised float32 array with same channel count
+    """
+    _validate_image(image, np.float32, "wavelet_denoise_f32")
+    return imagestag_rust.wavelet_denoise_f32(image, list(thresholds))
+
+
+__all__ = [
+    'wavelet_sharpen', 'wavelet_sharpen_f32',
+    'wavelet_denoise', 'wavelet_denoise_f32',
+]
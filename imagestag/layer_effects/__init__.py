@@ -12,6 +12,8 @@ Example:
 Supported Effects:
     - DropShadow: Shadow cast behind the layer
     - InnerShadow: Shadow inside the layer edges
+    - cast_shadow: Perspective ground shadow with distance-dependent blur
+    - LongShadow: Flat-design silhouette extruded away from the light
     - OuterGlow: Glow radiating outward from layer edges
     - InnerGlow: Glow radiating inward from layer edges
     - BevelEmboss: 3D raised/sunken appearance
@@ -31,6 +33,8 @@ All effects support:
 from .base import LayerEffect, PixelFormat, Expansion, EffectResult
 from .drop_shadow import DropShadow
 from .inner_shadow import InnerShadow
+from .cast_shadow import cast_shadow
+from .long_shadow import LongShadow
 from .outer_glow import OuterGlow
 from .inner_glow import InnerGlow
 from .bevel_emboss import BevelEmboss, BevelStyle
@@ -49,6 +53,7 @@ __all__ = [
     # Effects
     "DropShadow",
     "InnerShadow",
+    "LongShadow",
     "OuterGlow",
     "InnerGlow",
     "BevelEmboss",
@@ -57,6 +62,8 @@ __all__ = [
     "ColorOverlay",
     "GradientOverlay",
     "PatternOverlay",
+    # Functions
+    "cast_shadow",
     # Constants
     "BevelStyle",
     "StrokePosition",
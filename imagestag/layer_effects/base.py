@@ -77,6 +77,36 @@ class EffectResult:
     offset_x: int = 0  # X offset of output relative to input origin
     offset_y: int = 0  # Y offset of output relative to input origin
 
+    @property
+    def bounds(self) -> Tuple[int, int, int, int]:
+        """Output placement as (offset_x, offset_y, width, height) relative to the input origin."""
+        return (self.offset_x, self.offset_y, self.image.shape[1], self.image.shape[0])
+
+    def clipped(self, width: int, height: int) -> "EffectResult":
+        """
+        Crop the output back to the original layer bounds.
+
+        Args:
+            width: Width of the input image
+            height: Height of the input image
+
+        Returns:
+            EffectResult of size (height, width) with zero offsets. Areas the
+            output does not cover are transparent.
+        """
+        out = np.zeros((height, width) + self.image.shape[2:], dtype=self.image.dtype)
+        src_h, src_w = self.image.shape[:2]
+        # Overlap of the output with the layer, in layer coordinates
+        x0, y0 = max(self.offset_x, 0), max(self.offset_y, 0)
+        x1 = min(self.offset_x + src_w, width)
+        y1 = min(self.offset_y + src_h, height)
+        if x1 > x0 and y1 > y0:
+            out[y0:y1, x0:x1] = self.image[
+                y0 - self.offset_y:y1 - self.offset_y,
+                x0 - self.offset_x:x1 - self.offset_x,
+            ]
+        return EffectResult(image=out, offset_x=0, offset_y=0)
+
 
 class LayerEffect(BaseModel):
     """
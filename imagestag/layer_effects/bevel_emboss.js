@@ -23,8 +23,17 @@ export { initWasm };
  * @param {number} [options.highlight_opacity=0.75] - Highlight opacity (0.0-1.0)
  * @param {Array<number>} [options.shadow_color=[0,0,0]] - Shadow color [r, g, b]
  * @param {number} [options.shadow_opacity=0.75] - Shadow opacity (0.0-1.0)
- * @param {string} [options.style='inner_bevel'] - Style: 'outer_bevel', 'inner_bevel', 'emboss', 'pillow_emboss'
- * @returns {Object} - Result (may have expanded canvas for outer_bevel)
+ * @param {string} [options.style='inner_bevel'] - Style: 'outer_bevel', 'inner_bevel', 'emboss', 'pillow_emboss', 'stroke_emboss'
+ * @param {string} [options.technique='smooth'] - Technique: 'smooth', 'chisel_hard', 'chisel_soft'
+ * @param {number} [options.size=depth] - Bevel width in pixels
+ * @param {number} [options.soften=0] - Extra blur of the height map in pixels
+ * @param {string} [options.direction='up'] - 'up' (raised) or 'down' (sunken)
+ * @param {Array<Array<number>>} [options.gloss_contour=[]] - Gloss contour points [[x, y], ...] (0.0-1.0)
+ * @param {Object} [options.texture] - Grayscale texture {data: Uint8Array, width, height}, tiled
+ * @param {number} [options.texture_scale=1] - Texture scale factor
+ * @param {number} [options.texture_depth=1] - Texture relief in pixels (negative inverts)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
+ * @returns {Object} - Result (expanded canvas for every style except inner_bevel)
  */
 export function bevel_emboss(imageData, options = {}) {
     const { data, width, height } = imageData;
@@ -42,6 +51,14 @@ export function bevel_emboss(imageData, options = {}) {
     const shadow_color = options.shadow_color ?? [0, 0, 0];
     const shadow_opacity = options.shadow_opacity ?? 0.75;
     const style = options.style ?? 'inner_bevel';
+    const technique = options.technique ?? 'smooth';
+    const size = options.size ?? depth;
+    const soften = options.soften ?? 0;
+    const direction = options.direction ?? 'up';
+    const gloss_contour = new Float32Array((options.gloss_contour ?? []).flat());
+    const texture = options.texture ?? null;
+    const texture_scale = options.texture_scale ?? 1;
+    const texture_depth = options.texture_depth ?? 1;
 
     const result = wasm.bevel_emboss_rgba_wasm(
         new Uint8Array(data.buffer),
@@ -58,12 +75,23 @@ export function bevel_emboss(imageData, options = {}) {
         shadow_color[1],
         shadow_color[2],
         shadow_opacity,
-        style
+        style,
+        technique,
+        size,
+        soften,
+        direction,
+        gloss_contour,
+        texture ? new Uint8Array(texture.data) : new Uint8Array(0),
+        texture ? texture.width : 0,
+        texture ? texture.height : 0,
+        texture_scale,
+        texture_depth,
+        options.quality ?? 'exact'
     );
 
-    // Calculate dimensions based on style
-    const is_outer = style === 'outer_bevel';
-    const expand = is_outer ? Math.ceil(depth) + 2 : 0;
+    // Calculate dimensions based on style (every style but inner bevel draws outside)
+    const is_outer = style !== 'inner_bevel';
+    const expand = is_outer ? Math.ceil(size + soften) + 2 : 0;
     const new_width = width + expand * 2;
     const new_height = height + expand * 2;
 
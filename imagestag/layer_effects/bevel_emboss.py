@@ -19,7 +19,7 @@ This is a visual approximation that captures the general "bevel" look but will n
 match the Rust output pixel-for-pixel. For 100% fidelity, render via Rust.
 """
 
-from typing import Tuple, Union, Dict, Any, Optional, ClassVar
+from typing import List, Tuple, Union, Dict, Any, Optional, ClassVar
 import math
 import numpy as np
 
@@ -41,12 +41,17 @@ class BevelStyle:
     INNER_BEVEL = "innerBevel"
     EMBOSS = "emboss"
     PILLOW_EMBOSS = "pillowEmboss"
+    STROKE_EMBOSS = "strokeEmboss"
+
+    # Styles whose shading extends outside the layer bounds
+    OUTSIDE_STYLES = ("outerBevel", "emboss", "pillowEmboss", "strokeEmboss")
 
     # Style conversion: Python snake_case to JS camelCase
     _TO_JS = {
         "outer_bevel": "outerBevel",
         "inner_bevel": "innerBevel",
         "pillow_emboss": "pillowEmboss",
+        "stroke_emboss": "strokeEmboss",
     }
     _FROM_JS = {v: k for k, v in _TO_JS.items()}
 
@@ -78,16 +83,24 @@ class BevelEmboss(LayerEffect):
 
     # Effect-specific fields
     style: str = Field(default="innerBevel")
+    technique: str = Field(default="smooth")  # 'smooth', 'chisel_hard', 'chisel_soft'
     depth: float = Field(default=3.0)
     direction: str = Field(default="up")  # 'up' or 'down'
     size: float = Field(default=5.0)
-    soften: float = Field(default=0.0, ge=0.0, le=1.0)
+    soften: float = Field(default=0.0, ge=0.0)
+    gloss_contour: Optional[List[Tuple[float, float]]] = Field(default=None, alias='glossContour')
     angle: float = Field(default=120.0)
     altitude: float = Field(default=30.0)
     highlight_color: str = Field(default='#FFFFFF', alias='highlightColor')
     highlight_opacity: float = Field(default=0.75, alias='highlightOpacity', ge=0.0, le=1.0)
     shadow_color: str = Field(default='#000000', alias='shadowColor')
     shadow_opacity: float = Field(default=0.75, alias='shadowOpacity', ge=0.0, le=1.0)
+    # Texture sub-effect: grayscale pattern (H, W) or image (H, W, C), tiled
+    texture: Optional[Any] = Field(default=None)  # numpy array at runtime
+    texture_scale: float = Field(default=1.0, alias='textureScale', gt=0.0)
+    texture_depth: float = Field(default=1.0, alias='textureDepth')
+    # Blur accuracy: 'exact', or 'fast' for interactive previews
+    quality: str = Field(default='exact')
 
     # Internal: parsed RGB tuples (not serialized)
     _highlight_rgb: Optional[Tuple[int, int, int]] = None
@@ -162,9 +175,9 @@ class BevelEmboss(LayerEffect):
         return self._shadow_rgb
 
     def get_expansion(self) -> Expansion:
-        """Calculate expansion needed for outer bevel."""
-        if self.style == BevelStyle.OUTER_BEVEL or self.style == "outer_bevel":
-            expand = int(self.size) + 2
+        """Calculate expansion needed for styles drawing outside the layer."""
+        if BevelStyle.to_js(self.style) in BevelStyle.OUTSIDE_STYLES:
+            expand = math.ceil(self.size + self.soften) + 2
             return Expansion(left=expand, top=expand, right=expand, bottom=expand)
         return Expansion()
 
@@ -185,6 +198,20 @@ class BevelEmboss(LayerEffect):
             return np.concatenate([image, alpha], axis=2)
         return image
 
+    def _texture_gray(self, as_float: bool) -> np.ndarray:
+        """Convert the texture to a 2D grayscale array matching the image dtype."""
+        tex = np.asarray(self.texture)
+        if tex.ndim == 3:
+            if tex.shape[2] >= 3:
+                tex = tex[..., 0] * 0.299 + tex[..., 1] * 0.587 + tex[..., 2] * 0.114
+            else:
+                tex = tex[..., 0]
+        if np.issubdtype(tex.dtype, np.floating) and not as_float:
+            return np.ascontiguousarray(np.clip(tex * 255.0, 0, 255).astype(np.uint8))
+        if as_float and not np.issubdtype(tex.dtype, np.floating):
+            return np.ascontiguousarray(tex.astype(np.float32) / 255.0)
+        return np.ascontiguousarray(tex.astype(np.float32 if as_float else np.uint8))
+
     def apply(self, image: np.ndarray, format: Union[PixelFormat, str, None] = None) -> EffectResult:
         """
         Apply bevel and emboss to image.
@@ -213,22 +240,34 @@ class BevelEmboss(LayerEffect):
 
         highlight = self.highlight_rgb
         shadow = self.shadow_rgb
+        options = dict(
+            style=rust_style,
+            technique=self.technique,
+            size=float(self.size),
+            soften=float(self.soften),
+            direction=self.direction,
+            gloss_contour=self.gloss_contour,
+            quality=self.quality,
+        )
+        if self.texture is not None:
+            options.update(
+                texture=self._texture_gray(fmt.is_float),
+                texture_scale=float(self.texture_scale),
+                texture_depth=float(self.texture_depth),
+            )
 
-        # Currently only u8 version exists
         if fmt.is_float:
-            image_u8 = (image * 255).astype(np.uint8)
-            result = imagestag_rust.bevel_emboss_rgba(
-                image_u8,
+            result = imagestag_rust.bevel_emboss_rgba_f32(
+                image.astype(np.float32),
                 float(self.depth),
                 float(self.angle),
                 float(self.altitude),
-                highlight,
+                tuple(c / 255.0 for c in highlight),
                 float(self.highlight_opacity),
-                shadow,
+                tuple(c / 255.0 for c in shadow),
                 float(self.shadow_opacity),
-                rust_style,
+                **options,
             )
-            result = result.astype(np.float32) / 255.0
         else:
             result = imagestag_rust.bevel_emboss_rgba(
                 image.astype(np.uint8),
@@ -239,10 +278,10 @@ class BevelEmboss(LayerEffect):
                 float(self.highlight_opacity),
                 shadow,
                 float(self.shadow_opacity),
-                rust_style,
+                **options,
             )
 
-        # Handle expansion for outer bevel
+        # Handle expansion for styles drawing outside the layer
         expansion = self.get_expansion()
         offset = -expansion.left if expansion.left > 0 else 0
 
@@ -2,283 +2,727 @@
 //!
 //! Creates a 3D raised or sunken appearance using highlights and shadows.
 //!
+//! The effect builds a height map from the layer's alpha channel, derives
+//! surface normals from it and shades them with a directional light:
+//!
+//! - **Style** decides where the height ramp sits relative to the layer edge
+//!   (inner bevel, outer bevel, emboss, pillow emboss, stroke emboss)
+//! - **Technique** decides the ramp profile: `smooth` (blurred, rounded),
+//!   `chisel_hard` (exact distance transform, sharp ridges) or `chisel_soft`
+//!   (distance transform with slightly rounded ridges)
+//! - **Size** is the width of the ramp in pixels, **soften** blurs the
+//!   resulting height map independently of the size
+//! - **Altitude** tilts the light: flat areas receive `sin(altitude)`
+//!   light; faces brighter than that get highlights, darker ones shadows
+//! - **Gloss contour** remaps the shading response (PCHIP curve)
+//! - **Texture** tiles a grayscale pattern over the layer and adds it to the
+//!   height map as fine relief
+//!
 //! Co-located with:
 //! - bevel_emboss.py (Python wrapper)
 //! - bevel_emboss.js (JavaScript wrapper)
 
 use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::filters::core::{blur_alpha_f32, expand_canvas_f32};
+#[cfg(feature = "python")]
+use crate::filters::blur::parse_blur_quality;
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{blur_alpha_quality_f32, expand_canvas_f32, extract_alpha_f32, signed_distance_alpha};
+use crate::filters::levels_curves::pchip_interpolate;
+
+/// Bevel style: where the bevel ramp sits relative to the layer edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BevelStyle {
+    /// Ramp inside the layer, shading drawn on the layer
+    InnerBevel,
+    /// Ramp outside the layer, shading drawn behind the layer
+    OuterBevel,
+    /// Ramp centered on the edge, shading on both sides
+    Emboss,
+    /// Layer raised and surroundings sunk, shading on both sides
+    PillowEmboss,
+    /// Ramp across an outside stroke band of width `size`
+    StrokeEmboss,
+}
 
-/// Apply bevel and emboss effect to RGBA image.
-///
-/// Creates a 3D raised or sunken appearance using highlights and shadows.
-///
-/// # Arguments
-/// * `image` - Source RGBA image
-/// * `depth` - Depth of the bevel effect in pixels
-/// * `angle` - Light source angle in degrees (0 = right, 90 = top)
-/// * `altitude` - Light altitude in degrees (0-90)
-/// * `highlight_color` - Highlight color (R, G, B)
-/// * `highlight_opacity` - Highlight opacity (0.0-1.0)
-/// * `shadow_color` - Shadow color (R, G, B)
-/// * `shadow_opacity` - Shadow opacity (0.0-1.0)
-/// * `style` - "outer_bevel", "inner_bevel", "emboss", "pillow_emboss"
-#[pyfunction]
-#[pyo3(signature = (image, depth=3.0, angle=120.0, altitude=30.0, highlight_color=(255, 255, 255), highlight_opacity=0.75, shadow_color=(0, 0, 0), shadow_opacity=0.75, style="inner_bevel"))]
-pub fn bevel_emboss_rgba<'py>(
-    py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
-    depth: f32,
-    angle: f32,
-    altitude: f32,
-    highlight_color: (u8, u8, u8),
-    highlight_opacity: f32,
-    shadow_color: (u8, u8, u8),
-    shadow_opacity: f32,
-    style: &str,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
+impl BevelStyle {
+    /// Parse a style name (snake_case or camelCase). Unknown names map to inner bevel.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "outer_bevel" | "outerBevel" => BevelStyle::OuterBevel,
+            "emboss" => BevelStyle::Emboss,
+            "pillow_emboss" | "pillowEmboss" => BevelStyle::PillowEmboss,
+            "stroke_emboss" | "strokeEmboss" => BevelStyle::StrokeEmboss,
+            _ => BevelStyle::InnerBevel,
+        }
+    }
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
+    /// Whether the shading extends outside the layer (requires canvas expansion).
+    pub fn draws_outside(self) -> bool {
+        !matches!(self, BevelStyle::InnerBevel)
+    }
+
+    /// Whether the shading is drawn on top of the layer's own pixels.
+    pub fn draws_inside(self) -> bool {
+        !matches!(self, BevelStyle::OuterBevel | BevelStyle::StrokeEmboss)
+    }
+}
+
+/// Bevel technique: profile of the height ramp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BevelTechnique {
+    /// Rounded profile from a blurred ramp
+    Smooth,
+    /// Linear ramp on the exact distance transform (crisp ridges)
+    ChiselHard,
+    /// Linear distance ramp with lightly rounded ridges
+    ChiselSoft,
+}
+
+impl BevelTechnique {
+    /// Parse a technique name (snake_case or camelCase). Unknown names map to smooth.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "chisel_hard" | "chiselHard" => BevelTechnique::ChiselHard,
+            "chisel_soft" | "chiselSoft" => BevelTechnique::ChiselSoft,
+            _ => BevelTechnique::Smooth,
         }
     }
+}
 
-    // Expand canvas for outer effects
-    let is_outer = style == "outer_bevel";
-    let expand = if is_outer { (depth.ceil() as usize) + 2 } else { 0 };
-    let expanded = if expand > 0 {
-        expand_canvas_f32(&input_f32, expand)
-    } else {
-        input_f32.clone()
-    };
+/// Texture sub-effect: a grayscale pattern embossed into the layer surface.
+#[derive(Debug, Clone)]
+pub struct BevelTexture {
+    /// Grayscale pattern (height, width), 0.0-1.0 (white = raised). Tiled.
+    pub pattern: Array2<f32>,
+    /// Pattern scale factor (1.0 = one pattern pixel per image pixel)
+    pub scale: f32,
+    /// Relief height of the pattern in pixels; negative values invert it
+    pub depth: f32,
+}
 
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+impl BevelTexture {
+    /// Sample the tiled pattern with bilinear filtering at image coordinates.
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (ph, pw) = self.pattern.dim();
+        if ph == 0 || pw == 0 {
+            return 0.0;
+        }
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        let px = (x / scale).rem_euclid(pw as f32);
+        let py = (y / scale).rem_euclid(ph as f32);
+
+        let x0 = (px.floor() as usize) % pw;
+        let y0 = (py.floor() as usize) % ph;
+        let x1 = (x0 + 1) % pw;
+        let y1 = (y0 + 1) % ph;
+        let fx = px - px.floor();
+        let fy = py - py.floor();
+
+        let top = self.pattern[[y0, x0]] * (1.0 - fx) + self.pattern[[y0, x1]] * fx;
+        let bottom = self.pattern[[y1, x0]] * (1.0 - fx) + self.pattern[[y1, x1]] * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
+/// Parameters for the bevel & emboss effect.
+#[derive(Debug, Clone)]
+pub struct BevelEmbossParams {
+    /// Slope multiplier (1.0 ≈ 45° walls for a chisel ramp)
+    pub depth: f32,
+    /// Width of the bevel ramp in pixels
+    pub size: f32,
+    /// Additional blur of the height map in pixels
+    pub soften: f32,
+    /// Light source angle in degrees (0 = right, 90 = top)
+    pub angle: f32,
+    /// Light altitude in degrees (0 = horizon, 90 = straight above)
+    pub altitude: f32,
+    /// `true` = raised ("up"), `false` = sunken ("down")
+    pub direction_up: bool,
+    /// Highlight color (R, G, B), 0.0-1.0
+    pub highlight_color: [f32; 3],
+    /// Highlight opacity (0.0-1.0)
+    pub highlight_opacity: f32,
+    /// Shadow color (R, G, B), 0.0-1.0
+    pub shadow_color: [f32; 3],
+    /// Shadow opacity (0.0-1.0)
+    pub shadow_opacity: f32,
+    pub style: BevelStyle,
+    pub technique: BevelTechnique,
+    /// Gloss contour control points (input, output), 0.0-1.0. Empty = linear.
+    pub gloss_contour: Vec<(f32, f32)>,
+    /// Optional texture relief, applied inside the layer
+    pub texture: Option<BevelTexture>,
+    /// Accuracy of the height map blurs; `Fast` for interactive previews
+    pub quality: BlurQuality,
+}
+
+impl Default for BevelEmbossParams {
+    fn default() -> Self {
+        Self {
+            depth: 3.0,
+            size: 3.0,
+            soften: 0.0,
+            angle: 120.0,
+            altitude: 30.0,
+            direction_up: true,
+            highlight_color: [1.0, 1.0, 1.0],
+            highlight_opacity: 0.75,
+            shadow_color: [0.0, 0.0, 0.0],
+            shadow_opacity: 0.75,
+            style: BevelStyle::InnerBevel,
+            technique: BevelTechnique::Smooth,
+            gloss_contour: Vec::new(),
+            texture: None,
+            quality: BlurQuality::Exact,
         }
     }
+}
 
-    // Calculate light direction from angle
-    let angle_rad = angle.to_radians();
-    let dx = angle_rad.cos();
-    let dy = -angle_rad.sin(); // Negative because Y increases downward
-
-    // Create bump map by computing outward surface normal from alpha gradient
-    // For proper bevel lighting, normal should point OUTWARD from the shape
-    // (toward decreasing alpha, not increasing)
-    let mut bump_x = Array2::<f32>::zeros((new_h, new_w));
-    let mut bump_y = Array2::<f32>::zeros((new_h, new_w));
-
-    for y in 1..new_h - 1 {
-        for x in 1..new_w - 1 {
-            // Negate gradient to get outward-facing normal
-            bump_x[[y, x]] = (alpha[[y, x - 1]] - alpha[[y, x + 1]]) / 2.0;
-            bump_y[[y, x]] = (alpha[[y - 1, x]] - alpha[[y + 1, x]]) / 2.0;
+impl BevelEmbossParams {
+    /// Pixels added on each side of the canvas for this configuration.
+    pub fn expansion(&self) -> usize {
+        if self.style.draws_outside() {
+            (self.size.max(0.0) + self.soften.max(0.0)).ceil() as usize + 2
+        } else {
+            0
         }
     }
+}
+
+/// Clamp a normalized ramp position to 0.0-1.0.
+#[inline]
+fn ramp(t: f32) -> f32 {
+    t.clamp(0.0, 1.0)
+}
+
+/// Build the normalized height map (0.0-1.0) for the given style and technique.
+fn build_height_map(alpha: &Array2<f32>, params: &BevelEmbossParams) -> Array2<f32> {
+    let size = params.size.max(0.5);
+    let sdf = signed_distance_alpha(alpha);
+
+    let mut height = sdf.mapv(|d| match params.style {
+        BevelStyle::InnerBevel => ramp(d / size),
+        BevelStyle::OuterBevel => ramp((d + size) / size),
+        BevelStyle::Emboss => ramp((d + size * 0.5) / size),
+        BevelStyle::PillowEmboss => ramp(d.abs() / size),
+        // Ridge along the middle of the stroke band [-size, 0]
+        BevelStyle::StrokeEmboss => {
+            if d > 0.0 {
+                0.0
+            } else {
+                ramp((d + size).min(-d) / (size * 0.5))
+            }
+        }
+    });
 
-    // Blur the bump map for smoother effect
-    let bump_x_blur = blur_alpha_f32(&bump_x, depth * 0.5);
-    let bump_y_blur = blur_alpha_f32(&bump_y, depth * 0.5);
+    height = match params.technique {
+        BevelTechnique::Smooth => blur_alpha_quality_f32(&height, size / 3.0, params.quality),
+        BevelTechnique::ChiselSoft => blur_alpha_quality_f32(&height, (size / 12.0).max(0.5), params.quality),
+        BevelTechnique::ChiselHard => height,
+    };
 
-    // Calculate lighting contribution
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
+    if params.soften > 0.0 {
+        height = blur_alpha_quality_f32(&height, params.soften, params.quality);
+    }
+
+    height
+}
+
+/// Compute the highlight and shadow coverage of the bevel.
+///
+/// Both masks have the opacities applied but are not yet clipped to the
+/// inside or outside of the layer; see [`BevelStyle::draws_outside`].
+///
+/// # Arguments
+/// * `alpha` - Layer alpha, already expanded by [`BevelEmbossParams::expansion`]
+/// * `params` - Effect parameters
+/// * `origin` - Offset of the original layer inside `alpha` (texture anchor)
+///
+/// # Returns
+/// `(highlight, shadow)` alpha masks, same size as `alpha`
+pub fn bevel_emboss_shading(
+    alpha: &Array2<f32>,
+    params: &BevelEmbossParams,
+    origin: usize,
+) -> (Array2<f32>, Array2<f32>) {
+    let (new_h, new_w) = alpha.dim();
+    let height = build_height_map(alpha, params);
+
+    // Texture relief in pixels, anchored at the original layer origin and
+    // masked by the layer alpha so it stays on the layer surface
+    let texture_height = params.texture.as_ref().map(|texture| {
+        let offset = origin as f32;
+        Array2::from_shape_fn((new_h, new_w), |(y, x)| {
+            let a = alpha[[y, x]];
+            if a <= 0.0 {
+                0.0
+            } else {
+                texture.sample(x as f32 - offset, y as f32 - offset) * texture.depth * a
+            }
+        })
+    });
+
+    // Light vector in image coordinates (y grows downward)
+    let angle_rad = params.angle.to_radians();
+    let altitude_rad = params.altitude.clamp(0.0, 90.0).to_radians();
+    let light = [
+        altitude_rad.cos() * angle_rad.cos(),
+        -altitude_rad.cos() * angle_rad.sin(),
+        altitude_rad.sin(),
+    ];
+
+    let contour = |v: f32| -> f32 {
+        if params.gloss_contour.len() < 2 {
+            v
+        } else {
+            pchip_interpolate(&params.gloss_contour, v).clamp(0.0, 1.0)
+        }
+    };
+    let flat = contour(light[2]);
+
+    // Height in pixels, signed by direction
+    let slope = params.depth * params.size.max(0.5) * if params.direction_up { 1.0 } else { -1.0 };
+
+    let mut highlight = Array2::<f32>::zeros((new_h, new_w));
+    let mut shadow = Array2::<f32>::zeros((new_h, new_w));
 
-    // Copy original first
     for y in 0..new_h {
         for x in 0..new_w {
-            for c in 0..4 {
-                result[[y, x, c]] = expanded[[y, x, c]];
+            // Central differences with clamped borders
+            let xl = x.saturating_sub(1);
+            let xr = (x + 1).min(new_w - 1);
+            let yt = y.saturating_sub(1);
+            let yb = (y + 1).min(new_h - 1);
+            let mut gx = (height[[y, xr]] - height[[y, xl]]) / (xr - xl).max(1) as f32 * slope;
+            let mut gy = (height[[yb, x]] - height[[yt, x]]) / (yb - yt).max(1) as f32 * slope;
+            if let Some(tex) = &texture_height {
+                gx += (tex[[y, xr]] - tex[[y, xl]]) / (xr - xl).max(1) as f32;
+                gy += (tex[[yb, x]] - tex[[yt, x]]) / (yb - yt).max(1) as f32;
+            }
+            if gx == 0.0 && gy == 0.0 {
+                continue;
+            }
+
+            let inv_len = 1.0 / (gx * gx + gy * gy + 1.0).sqrt();
+            let shade = (-gx * light[0] - gy * light[1] + light[2]) * inv_len;
+            let v = contour(shade.clamp(0.0, 1.0));
+
+            if v > flat {
+                let amount = if flat < 1.0 { (v - flat) / (1.0 - flat) } else { 0.0 };
+                highlight[[y, x]] = (amount * params.highlight_opacity).clamp(0.0, 1.0);
+            } else {
+                let amount = if flat > 0.0 { (flat - v) / flat } else { 0.0 };
+                shadow[[y, x]] = (amount * params.shadow_opacity).clamp(0.0, 1.0);
             }
         }
     }
 
-    let hl_r = highlight_color.0 as f32 / 255.0;
-    let hl_g = highlight_color.1 as f32 / 255.0;
-    let hl_b = highlight_color.2 as f32 / 255.0;
-    let sh_r = shadow_color.0 as f32 / 255.0;
-    let sh_g = shadow_color.1 as f32 / 255.0;
-    let sh_b = shadow_color.2 as f32 / 255.0;
+    (highlight, shadow)
+}
+
+/// Apply bevel and emboss to an RGBA f32 image.
+///
+/// This is the shared implementation used by the Python and WASM bindings.
+///
+/// # Arguments
+/// * `input` - Source RGBA image (height, width, 4), values 0.0-1.0
+/// * `params` - Effect parameters
+///
+/// # Returns
+/// RGBA image, expanded by [`BevelEmbossParams::expansion`] on each side
+pub fn bevel_emboss_f32(input: &Array3<f32>, params: &BevelEmbossParams) -> Array3<f32> {
+    let expand = params.expansion();
+    let expanded = if expand > 0 {
+        expand_canvas_f32(input, expand)
+    } else {
+        input.clone()
+    };
+    let (new_h, new_w, _) = expanded.dim();
+
+    let alpha = extract_alpha_f32(&expanded);
+    let (highlight, shadow) = bevel_emboss_shading(&alpha, params, expand);
+
+    let draws_inside = params.style.draws_inside();
+    let draws_outside = params.style.draws_outside();
+
+    let mut result = expanded.clone();
 
     for y in 0..new_h {
         for x in 0..new_w {
-            let orig_a = expanded[[y, x, 3]];
-            if orig_a <= 0.0 && !is_outer {
+            let (color, amount) = if highlight[[y, x]] > 0.0 {
+                (params.highlight_color, highlight[[y, x]])
+            } else {
+                (params.shadow_color, shadow[[y, x]])
+            };
+            if amount <= 0.0 {
                 continue;
             }
 
-            // Compute lighting intensity
-            let bx = bump_x_blur[[y, x]] * depth;
-            let by = bump_y_blur[[y, x]] * depth;
-            let intensity = bx * dx + by * dy;
-
-            if intensity > 0.0 {
-                // Highlight
-                let hl_a = intensity.min(1.0) * highlight_opacity * orig_a;
-                if hl_a > 0.0 {
-                    let src_a = hl_a;
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (hl_r * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (hl_g * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (hl_b * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
+            // Inside part is composited over the layer, outside part behind it
+            let a = alpha[[y, x]];
+            let inside_a = if draws_inside { amount * a } else { 0.0 };
+            let outside_a = if draws_outside { amount * (1.0 - a) } else { 0.0 };
+
+            let mut px = [
+                result[[y, x, 0]],
+                result[[y, x, 1]],
+                result[[y, x, 2]],
+                result[[y, x, 3]],
+            ];
+
+            if outside_a > 0.0 {
+                // layer OVER shading
+                let dst_a = px[3];
+                let out_a = dst_a + outside_a * (1.0 - dst_a);
+                if out_a > 0.0 {
+                    for c in 0..3 {
+                        px[c] = (px[c] * dst_a + color[c] * outside_a * (1.0 - dst_a)) / out_a;
                     }
+                    px[3] = out_a;
                 }
-            } else if intensity < 0.0 {
-                // Shadow
-                let sh_a = (-intensity).min(1.0) * shadow_opacity * orig_a;
-                if sh_a > 0.0 {
-                    let src_a = sh_a;
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (sh_r * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (sh_g * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (sh_b * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
+            }
+
+            if inside_a > 0.0 {
+                // shading OVER layer
+                let dst_a = px[3];
+                let out_a = inside_a + dst_a * (1.0 - inside_a);
+                if out_a > 0.0 {
+                    for c in 0..3 {
+                        px[c] = (color[c] * inside_a + px[c] * dst_a * (1.0 - inside_a)) / out_a;
                     }
+                    px[3] = out_a;
                 }
             }
+
+            for c in 0..4 {
+                result[[y, x, c]] = px[c];
+            }
         }
     }
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    result
 }
 
-/// Apply bevel and emboss effect to f32 RGBA image.
+/// Build parameters from the binding-level arguments.
+#[cfg(feature = "python")]
+fn params_from_args(
+    depth: f32,
+    angle: f32,
+    altitude: f32,
+    highlight_color: [f32; 3],
+    highlight_opacity: f32,
+    shadow_color: [f32; 3],
+    shadow_opacity: f32,
+    style: &str,
+    technique: &str,
+    size: Option<f32>,
+    soften: f32,
+    direction: &str,
+    gloss_contour: Option<Vec<(f32, f32)>>,
+    quality: &str,
+) -> PyResult<BevelEmbossParams> {
+    Ok(BevelEmbossParams {
+        depth,
+        size: size.unwrap_or(depth),
+        soften,
+        angle,
+        altitude,
+        direction_up: direction != "down",
+        highlight_color,
+        highlight_opacity,
+        shadow_color,
+        shadow_opacity,
+        style: BevelStyle::parse(style),
+        technique: BevelTechnique::parse(technique),
+        gloss_contour: gloss_contour.unwrap_or_default(),
+        texture: None,
+        quality: parse_blur_quality(quality)?,
+    })
+}
+
+/// Apply bevel and emboss effect to RGBA image.
+///
+/// Creates a 3D raised or sunken appearance using highlights and shadows.
+///
+/// # Arguments
+/// * `image` - Source RGBA image
+/// * `depth` - Slope strength of the bevel (1.0 ≈ 45° walls)
+/// * `angle` - Light source angle in degrees (0 = right, 90 = top)
+/// * `altitude` - Light altitude in degrees (0-90)
+/// * `highlight_color` - Highlight color (R, G, B)
+/// * `highlight_opacity` - Highlight opacity (0.0-1.0)
+/// * `shadow_color` - Shadow color (R, G, B)
+/// * `shadow_opacity` - Shadow opacity (0.0-1.0)
+/// * `style` - "outer_bevel", "inner_bevel", "emboss", "pillow_emboss", "stroke_emboss"
+/// * `technique` - "smooth", "chisel_hard", "chisel_soft"
+/// * `size` - Bevel width in pixels (defaults to `depth`)
+/// * `soften` - Extra blur of the height map in pixels
+/// * `direction` - "up" (raised) or "down" (sunken)
+/// * `gloss_contour` - Optional list of (input, output) curve points, 0.0-1.0
+/// * `texture` - Optional grayscale pattern (height, width), tiled over the layer
+/// * `texture_scale` - Pattern scale factor
+/// * `texture_depth` - Pattern relief in pixels (negative inverts)
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, depth=3.0, angle=120.0, altitude=30.0, highlight_color=(1.0, 1.0, 1.0), highlight_opacity=0.75, shadow_color=(0.0, 0.0, 0.0), shadow_opacity=0.75, style="inner_bevel"))]
-#[allow(unused_variables)]
-pub fn bevel_emboss_rgba_f32<'py>(
+#[pyo3(signature = (image, depth=3.0, angle=120.0, altitude=30.0, highlight_color=(255, 255, 255), highlight_opacity=0.75, shadow_color=(0, 0, 0), shadow_opacity=0.75, style="inner_bevel", technique="smooth", size=None, soften=0.0, direction="up", gloss_contour=None, texture=None, texture_scale=1.0, texture_depth=1.0, quality="exact"))]
+pub fn bevel_emboss_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, u8>,
     depth: f32,
     angle: f32,
     altitude: f32,
-    highlight_color: (f32, f32, f32),
+    highlight_color: (u8, u8, u8),
     highlight_opacity: f32,
-    shadow_color: (f32, f32, f32),
+    shadow_color: (u8, u8, u8),
     shadow_opacity: f32,
     style: &str,
-) -> Bound<'py, PyArray3<f32>> {
+    technique: &str,
+    size: Option<f32>,
+    soften: f32,
+    direction: &str,
+    gloss_contour: Option<Vec<(f32, f32)>>,
+    texture: Option<PyHostArray2<'py, u8>>,
+    texture_scale: f32,
+    texture_depth: f32,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
     let input = image.as_array();
     let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
 
-    // Clone input
+    // Convert to f32
     let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
     for y in 0..height {
         for x in 0..width {
             for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
+                input_f32[[y, x, c]] = input[[y, x, c]] as f32 / 255.0;
             }
         }
     }
 
-    // Expand canvas for outer effects
-    let is_outer = style == "outer_bevel";
-    let expand = if is_outer { (depth.ceil() as usize) + 2 } else { 0 };
-    let expanded = if expand > 0 {
-        expand_canvas_f32(&input_f32, expand)
-    } else {
-        input_f32
-    };
+    let mut params = params_from_args(
+        depth,
+        angle,
+        altitude,
+        [
+            highlight_color.0 as f32 / 255.0,
+            highlight_color.1 as f32 / 255.0,
+            highlight_color.2 as f32 / 255.0,
+        ],
+        highlight_opacity,
+        [
+            shadow_color.0 as f32 / 255.0,
+            shadow_color.1 as f32 / 255.0,
+            shadow_color.2 as f32 / 255.0,
+        ],
+        shadow_opacity,
+        style,
+        technique,
+        size,
+        soften,
+        direction,
+        gloss_contour,
+        quality,
+    )?;
+    params.texture = texture.map(|t| BevelTexture {
+        pattern: t.as_array().mapv(|v| v as f32 / 255.0),
+        scale: texture_scale,
+        depth: texture_depth,
+    });
+
+    let result = bevel_emboss_f32(&input_f32, &params);
+    Ok(result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py))
+}
 
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+/// Apply bevel and emboss effect to f32 RGBA image.
+///
+/// Same parameters as `bevel_emboss_rgba`, with colors as 0.0-1.0 floats.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, depth=3.0, angle=120.0, altitude=30.0, highlight_color=(1.0, 1.0, 1.0), highlight_opacity=0.75, shadow_color=(0.0, 0.0, 0.0), shadow_opacity=0.75, style="inner_bevel", technique="smooth", size=None, soften=0.0, direction="up", gloss_contour=None, texture=None, texture_scale=1.0, texture_depth=1.0, quality="exact"))]
+pub fn bevel_emboss_rgba_f32<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    depth: f32,
+    angle: f32,
+    altitude: f32,
+    highlight_color: (f32, f32, f32),
+    highlight_opacity: f32,
+    shadow_color: (f32, f32, f32),
+    shadow_opacity: f32,
+    style: &str,
+    technique: &str,
+    size: Option<f32>,
+    soften: f32,
+    direction: &str,
+    gloss_contour: Option<Vec<(f32, f32)>>,
+    texture: Option<PyHostArray2<'py, f32>>,
+    texture_scale: f32,
+    texture_depth: f32,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let input = image.as_array().to_owned();
+
+    let mut params = params_from_args(
+        depth,
+        angle,
+        altitude,
+        [highlight_color.0, highlight_color.1, highlight_color.2],
+        highlight_opacity,
+        [shadow_color.0, shadow_color.1, shadow_color.2],
+        shadow_opacity,
+        style,
+        technique,
+        size,
+        soften,
+        direction,
+        gloss_contour,
+        quality,
+    )?;
+    params.texture = texture.map(|t| BevelTexture {
+        pattern: t.as_array().to_owned(),
+        scale: texture_scale,
+        depth: texture_depth,
+    });
+
+    Ok(bevel_emboss_f32(&input, &params).into_pyarray(py))
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: usize, inset: usize) -> Array3<f32> {
+        let mut img = Array3::<f32>::zeros((size, size, 4));
+        for y in inset..size - inset {
+            for x in inset..size - inset {
+                img[[y, x, 0]] = 0.5;
+                img[[y, x, 1]] = 0.5;
+                img[[y, x, 2]] = 0.5;
+                img[[y, x, 3]] = 1.0;
+            }
         }
+        img
     }
 
-    // Calculate light direction
-    let angle_rad = angle.to_radians();
-    let dx = angle_rad.cos();
-    let dy = -angle_rad.sin();
-
-    // Create bump map
-    let mut bump_x = Array2::<f32>::zeros((new_h, new_w));
-    let mut bump_y = Array2::<f32>::zeros((new_h, new_w));
-
-    for y in 1..new_h - 1 {
-        for x in 1..new_w - 1 {
-            bump_x[[y, x]] = (alpha[[y, x + 1]] - alpha[[y, x - 1]]) / 2.0;
-            bump_y[[y, x]] = (alpha[[y + 1, x]] - alpha[[y - 1, x]]) / 2.0;
-        }
+    #[test]
+    fn test_inner_bevel_lights_top_left() {
+        let img = square(32, 6);
+        let params = BevelEmbossParams {
+            size: 4.0,
+            depth: 1.0,
+            technique: BevelTechnique::ChiselHard,
+            ..Default::default()
+        };
+        let out = bevel_emboss_f32(&img, &params);
+        assert_eq!(out.dim(), (32, 32, 4));
+
+        // Light from the upper left: left edge brighter, right edge darker
+        assert!(out[[16, 7, 0]] > 0.5);
+        assert!(out[[16, 24, 0]] < 0.5);
+        // Flat center and transparent surroundings untouched
+        assert!((out[[16, 16, 0]] - 0.5).abs() < 1e-4);
+        assert_eq!(out[[0, 0, 3]], 0.0);
     }
 
-    let bump_x_blur = blur_alpha_f32(&bump_x, depth * 0.5);
-    let bump_y_blur = blur_alpha_f32(&bump_y, depth * 0.5);
+    #[test]
+    fn test_direction_down_inverts_lighting() {
+        let img = square(32, 6);
+        let params = BevelEmbossParams {
+            size: 4.0,
+            depth: 1.0,
+            direction_up: false,
+            technique: BevelTechnique::ChiselHard,
+            ..Default::default()
+        };
+        let out = bevel_emboss_f32(&img, &params);
+        assert!(out[[16, 7, 0]] < 0.5);
+        assert!(out[[16, 24, 0]] > 0.5);
+    }
 
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
+    #[test]
+    fn test_outer_bevel_expands_and_draws_outside() {
+        let img = square(24, 4);
+        let params = BevelEmbossParams {
+            size: 3.0,
+            soften: 1.0,
+            style: BevelStyle::OuterBevel,
+            ..Default::default()
+        };
+        let expand = params.expansion();
+        assert_eq!(expand, 6);
+        let out = bevel_emboss_f32(&img, &params);
+        assert_eq!(out.dim(), (24 + 2 * expand, 24 + 2 * expand, 4));
+
+        // Shading appears just outside the layer, layer pixels stay opaque
+        let cy = out.dim().0 / 2;
+        let left_outside = out[[cy, expand + 3, 3]];
+        assert!(left_outside > 0.0);
+        assert_eq!(out[[cy, cy, 3]], 1.0);
+    }
 
-    for y in 0..new_h {
-        for x in 0..new_w {
-            for c in 0..4 {
-                result[[y, x, c]] = expanded[[y, x, c]];
+    #[test]
+    fn test_altitude_90_has_no_highlights() {
+        let img = square(32, 6);
+        let params = BevelEmbossParams {
+            size: 4.0,
+            altitude: 90.0,
+            highlight_color: [1.0, 0.0, 0.0],
+            technique: BevelTechnique::ChiselHard,
+            ..Default::default()
+        };
+        let out = bevel_emboss_f32(&img, &params);
+        for y in 0..32 {
+            for x in 0..32 {
+                // Red would only grow through the highlight
+                assert!(out[[y, x, 0]] <= 0.5 + 1e-4);
             }
         }
     }
 
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let orig_a = expanded[[y, x, 3]];
-            if orig_a <= 0.0 && !is_outer {
-                continue;
-            }
+    #[test]
+    fn test_texture_adds_relief_inside_layer() {
+        let img = square(32, 4);
+        // Vertical stripes, 4 pixels wide
+        let pattern = Array2::from_shape_fn((8, 8), |(_, x)| if x < 4 { 1.0 } else { 0.0 });
+        let params = BevelEmbossParams {
+            size: 2.0,
+            technique: BevelTechnique::ChiselHard,
+            texture: Some(BevelTexture { pattern, scale: 1.0, depth: 4.0 }),
+            ..Default::default()
+        };
+        let out = bevel_emboss_f32(&img, &params);
+
+        // The flat center now carries stripe edges, still only on the layer
+        let row: Vec<f32> = (10..22).map(|x| out[[16, x, 0]]).collect();
+        assert!(row.iter().any(|&v| v > 0.55));
+        assert!(row.iter().any(|&v| v < 0.45));
+        assert_eq!(out[[1, 1, 3]], 0.0);
+    }
 
-            let bx = bump_x_blur[[y, x]] * depth;
-            let by = bump_y_blur[[y, x]] * depth;
-            let intensity = bx * dx + by * dy;
-
-            if intensity > 0.0 {
-                let hl_a = intensity.min(1.0) * highlight_opacity * orig_a;
-                if hl_a > 0.0 {
-                    let src_a = hl_a;
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (highlight_color.0 * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (highlight_color.1 * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (highlight_color.2 * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            } else if intensity < 0.0 {
-                let sh_a = (-intensity).min(1.0) * shadow_opacity * orig_a;
-                if sh_a > 0.0 {
-                    let src_a = sh_a;
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (shadow_color.0 * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (shadow_color.1 * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (shadow_color.2 * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_texture_sampling_tiles() {
+        let pattern = Array2::from_shape_fn((2, 2), |(y, x)| (y * 2 + x) as f32 / 3.0);
+        let texture = BevelTexture { pattern, scale: 1.0, depth: 1.0 };
+        assert!((texture.sample(1.0, 1.0) - 1.0).abs() < 1e-6);
+        assert!((texture.sample(3.0, -1.0) - 1.0).abs() < 1e-6);
+        assert!((texture.sample(0.5, 0.0) - 1.0 / 6.0).abs() < 1e-6);
     }
 
-    result.into_pyarray(py)
+    #[test]
+    fn test_style_and_technique_parsing() {
+        assert_eq!(BevelStyle::parse("pillowEmboss"), BevelStyle::PillowEmboss);
+        assert_eq!(BevelStyle::parse("stroke_emboss"), BevelStyle::StrokeEmboss);
+        assert_eq!(BevelStyle::parse("unknown"), BevelStyle::InnerBevel);
+        assert_eq!(BevelTechnique::parse("chiselSoft"), BevelTechnique::ChiselSoft);
+        assert_eq!(BevelTechnique::parse("smooth"), BevelTechnique::Smooth);
+    }
 }
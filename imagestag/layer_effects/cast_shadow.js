@@ -0,0 +1,69 @@
+/**
+ * Cast Shadow effect - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - cast_shadow.rs (Rust implementation)
+ * - cast_shadow.py (Python wrapper)
+ *
+ * Projects the alpha onto the ground behind the object with perspective
+ * and distance-dependent blur.
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+/**
+ * Apply cast shadow effect to RGBA image.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels: 4}
+ * @param {Object} options - Cast shadow options
+ * @param {number} [options.angle=30] - Shadow direction in degrees, clockwise from straight back
+ * @param {number} [options.length=0.6] - Shadow length relative to the object height
+ * @param {number} [options.perspective_squash=0.6] - Ground foreshortening (0.0 = none, 1.0 = flat)
+ * @param {number} [options.blur_near=1] - Blur sigma at the contact line
+ * @param {number} [options.blur_far=8] - Blur sigma at the shadow tip
+ * @param {Array<number>} [options.color=[0,0,0]] - Shadow color [r, g, b] (0-255)
+ * @param {number} [options.opacity=0.6] - Shadow opacity (0.0-1.0)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
+ * @returns {Object} - Result with expanded canvas {data, width, height, channels, offset_x, offset_y}
+ */
+export function cast_shadow(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+
+    if (channels !== 4) {
+        throw new Error('Cast shadow requires RGBA images (4 channels)');
+    }
+
+    const color = options.color ?? [0, 0, 0];
+    const opts = Object.assign(new wasm.CastShadowOptions(), {
+        angle: options.angle ?? 30,
+        length: options.length ?? 0.6,
+        perspective_squash: options.perspective_squash ?? 0.6,
+        blur_near: options.blur_near ?? 1,
+        blur_far: options.blur_far ?? 8,
+        color_r: color[0],
+        color_g: color[1],
+        color_b: color[2],
+        opacity: options.opacity ?? 0.6,
+        fast_blur: options.quality === 'fast',
+    });
+
+    const result = wasm.cast_shadow_rgba_wasm(new Uint8Array(data.buffer), width, height, opts);
+    const expand = wasm.cast_shadow_expansion_wasm(width, height, opts);
+    opts.free();
+
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: width + expand * 2,
+        height: height + expand * 2,
+        channels: 4,
+        offset_x: -expand,
+        offset_y: -expand
+    };
+}
+
+export default {
+    initWasm,
+    cast_shadow
+};
@@ -0,0 +1,73 @@
+"""
+Cast Shadow effect.
+
+Simulates the shadow an upright object throws onto the ground by:
+1. Finding the ground line (bottom of the layer content)
+2. Projecting the alpha onto the ground - sheared along the shadow
+   direction, squashed vertically and narrowed towards the far end
+3. Blurring with a sigma growing from blur_near to blur_far with distance
+4. Compositing original on top
+
+Unlike DropShadow's flat offset this reads as a ground shadow, e.g. for
+product mockups. The canvas expansion depends on the layer size, so this is
+a plain function rather than a LayerEffect.
+
+Example:
+    >>> from imagestag.layer_effects import cast_shadow
+    >>> result = cast_shadow(image, angle=30, length=0.6, perspective_squash=0.6)
+    >>> output_image = result.image
+    >>> offset_x, offset_y = result.offset_x, result.offset_y
+"""
+
+import numpy as np
+
+from .base import EffectResult
+
+# Import Rust implementation
+try:
+    import imagestag_rust
+    HAS_RUST = True
+except ImportError:
+    HAS_RUST = False
+
+
+def cast_shadow(image: np.ndarray, angle: float = 30.0, length: float = 0.6,
+                perspective_squash: float = 0.6, blur_near: float = 1.0,
+                blur_far: float = 8.0, opacity: float = 0.6,
+                quality: str = 'exact') -> EffectResult:
+    """
+    Add a perspective ground shadow behind the layer content.
+
+    Args:
+        image: RGB/RGBA image as uint8 or float32 array (H, W, 3|4)
+        angle: Direction the shadow falls in degrees, clockwise from straight
+            back (0 = behind the object, 90 = right, 180 = towards the viewer)
+        length: Shadow length relative to the object height
+        perspective_squash: Ground foreshortening (0.0 = none, 1.0 = flat)
+        blur_near: Blur sigma at the contact line
+        blur_far: Blur sigma at the shadow tip
+        opacity: Shadow opacity (0.0-1.0)
+        quality: Blur accuracy: 'exact', or 'fast' for interactive previews
+
+    Returns:
+        EffectResult with the expanded RGBA image and its offset
+    """
+    if not HAS_RUST:
+        raise RuntimeError("Rust extension not available. Install imagestag with Rust support.")
+
+    if image.ndim != 3 or image.shape[2] not in (3, 4):
+        raise ValueError(f"cast_shadow: expected image (H, W, 3|4), got shape {image.shape}")
+
+    if image.shape[2] == 3:
+        alpha = np.full((*image.shape[:2], 1), 255 if image.dtype == np.uint8 else 1.0, dtype=image.dtype)
+        image = np.concatenate([image, alpha], axis=2)
+
+    params = (float(angle), float(length), float(perspective_squash), float(blur_near), float(blur_far))
+    if image.dtype == np.uint8:
+        result = imagestag_rust.cast_shadow_rgba(image, *params, float(opacity), quality)
+    else:
+        result = imagestag_rust.cast_shadow_rgba_f32(image.astype(np.float32), *params, float(opacity), quality)
+
+    height, width = image.shape[:2]
+    expand = imagestag_rust.cast_shadow_expansion(width, height, *params)
+    return EffectResult(image=result, offset_x=-expand, offset_y=-expand)
@@ -0,0 +1,283 @@
+//! Cast shadow filter for layer effects.
+//!
+//! Simulates the shadow an upright object throws onto the ground it stands on:
+//! 1. Finding the ground line (bottom of the layer content)
+//! 2. Projecting the alpha onto the ground - sheared along the shadow
+//!    direction, squashed vertically and narrowed towards the far end
+//! 3. Blurring with a sigma growing from `blur_near` at the contact line to
+//!    `blur_far` at the tip
+//! 4. Colorizing with shadow color
+//! 5. Compositing original on top
+//!
+//! Unlike the drop shadow's flat offset, this reads as a ground shadow in
+//! product mockups.
+
+use ndarray::{Array2, Array3, ArrayView3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::filters::blur::parse_blur_quality;
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{blur_alpha_quality_f32, expand_canvas_view_f32, sample_alpha_bilinear};
+use crate::filters::pool::{self, Pooled};
+
+/// Number of blur levels interpolated between `blur_near` and `blur_far`.
+const BLUR_LEVELS: usize = 5;
+
+/// Parameters for the cast shadow effect.
+#[derive(Debug, Clone)]
+pub struct CastShadowParams {
+    /// Direction the shadow falls in degrees, clockwise from straight back
+    /// (0 = behind the object, 90 = to the right, 180 = towards the viewer)
+    pub angle: f32,
+    /// Shadow length relative to the object height (1.0 = as long as tall)
+    pub length: f32,
+    /// Ground plane foreshortening (0.0 = none, 1.0 = flat). Compresses the
+    /// shadow vertically and narrows its far end.
+    pub perspective_squash: f32,
+    /// Blur sigma at the contact line
+    pub blur_near: f32,
+    /// Blur sigma at the shadow tip
+    pub blur_far: f32,
+    /// Shadow color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Shadow opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Blur accuracy; `Fast` for interactive previews
+    pub quality: BlurQuality,
+}
+
+impl Default for CastShadowParams {
+    fn default() -> Self {
+        Self {
+            angle: 30.0,
+            length: 0.6,
+            perspective_squash: 0.6,
+            blur_near: 1.0,
+            blur_far: 8.0,
+            color: [0.0, 0.0, 0.0],
+            opacity: 0.6,
+            quality: BlurQuality::Exact,
+        }
+    }
+}
+
+impl CastShadowParams {
+    /// Ground displacement per pixel of object height (dx, dy).
+    fn displacement(&self) -> (f32, f32) {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let squash = 1.0 - self.perspective_squash.clamp(0.0, 0.95);
+        let dy = -cos * self.length * squash;
+        // A sideways shadow still needs some depth to sample from
+        let min_dy = 0.05 * self.length.max(0.01);
+        let dy = if dy.abs() < min_dy { -min_dy } else { dy };
+        (sin * self.length, dy)
+    }
+
+    /// Pixels required on each side of a layer of the given size for the
+    /// shadow overflow (the content may be as tall as the layer).
+    pub fn expansion(&self, width: usize, height: usize) -> usize {
+        let (dx, dy) = self.displacement();
+        let span = height.max(width) as f32;
+        let shift = (dx.abs() * span).max(dy.abs() * span);
+        let blur = self.blur_near.max(self.blur_far);
+        shift.ceil() as usize + (blur * 3.0).ceil() as usize + 2
+    }
+}
+
+/// Compute the cast shadow coverage from a layer alpha mask.
+///
+/// The mask must already contain room for the shadow overflow
+/// (see [`CastShadowParams::expansion`]).
+///
+/// # Returns
+/// Shadow alpha (0.0-1.0) with the opacity applied, same size as `alpha`
+pub fn cast_shadow_mask(alpha: &Array2<f32>, params: &CastShadowParams) -> Array2<f32> {
+    let (height, width) = alpha.dim();
+    let mut shadow = Array2::<f32>::zeros((height, width));
+
+    // Content bounds: the bottom row is the ground line
+    let rows: Vec<usize> = (0..height).filter(|&y| alpha.row(y).iter().any(|&a| a > 0.0)).collect();
+    let (Some(&top), Some(&bottom)) = (rows.first(), rows.last()) else {
+        return shadow;
+    };
+    let cols: Vec<usize> = (0..width).filter(|&x| alpha.column(x).iter().any(|&a| a > 0.0)).collect();
+    let center_x = (cols[0] + cols[cols.len() - 1] + 1) as f32 / 2.0;
+    let ground = (bottom + 1) as f32;
+    let object_height = (bottom + 1 - top) as f32;
+
+    let (dx, dy) = params.displacement();
+    let narrowing = params.perspective_squash.clamp(0.0, 1.0) * 0.5;
+
+    // Anti-alias rows that get compressed onto fewer output rows
+    let source = if dy.abs() < 1.0 { blur_alpha_quality_f32(alpha, 0.5 / dy.abs(), params.quality) } else { alpha.clone() };
+
+    // Inverse map every output pixel to the object pixel casting it
+    let height_at = |y: usize| ((y as f32 + 0.5) - ground) / dy;
+    for y in 0..height {
+        let h = height_at(y);
+        if !(0.0..=object_height).contains(&h) {
+            continue;
+        }
+        let scale = 1.0 - narrowing * h / object_height;
+        for x in 0..width {
+            let sx = center_x + ((x as f32 + 0.5) - h * dx - center_x) / scale;
+            shadow[[y, x]] = sample_alpha_bilinear(&source, sx, ground - h);
+        }
+    }
+
+    // Distance-dependent blur: interpolate between a few fixed sigmas
+    let levels: Vec<Array2<f32>> = (0..BLUR_LEVELS)
+        .map(|i| {
+            let t = i as f32 / (BLUR_LEVELS - 1) as f32;
+            blur_alpha_quality_f32(&shadow, params.blur_near + (params.blur_far - params.blur_near) * t, params.quality)
+        })
+        .collect();
+
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let t = (height_at(y) / object_height).clamp(0.0, 1.0);
+        let position = t * (BLUR_LEVELS - 1) as f32;
+        let index = (position.floor() as usize).min(BLUR_LEVELS - 2);
+        let frac = position - index as f32;
+        let value = levels[index][[y, x]] * (1.0 - frac) + levels[index + 1][[y, x]] * frac;
+        value * params.opacity
+    })
+}
+
+/// Apply the cast shadow to an RGBA f32 image.
+///
+/// # Returns
+/// RGBA image with the shadow behind the original, expanded by
+/// [`CastShadowParams::expansion`] on every side
+pub fn cast_shadow_rgba_f32(image: ArrayView3<f32>, params: &CastShadowParams) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let expanded = Pooled::new(expand_canvas_view_f32(image, params.expansion(width, height)));
+    let alpha = Pooled::new(pool::copy2(expanded.index_axis(ndarray::Axis(2), 3)));
+    let shadow = cast_shadow_mask(&alpha, params);
+
+    // Composite original over the shadow (Porter-Duff "over")
+    let mut result = Array3::<f32>::zeros(expanded.dim());
+    for ((y, x), &shadow_a) in shadow.indexed_iter() {
+        let src_a = expanded[[y, x, 3]];
+        let out_a = src_a + shadow_a * (1.0 - src_a);
+        if out_a <= 0.0 {
+            continue;
+        }
+        for c in 0..3 {
+            result[[y, x, c]] = (expanded[[y, x, c]] * src_a + params.color[c] * shadow_a * (1.0 - src_a)) / out_a;
+        }
+        result[[y, x, 3]] = out_a;
+    }
+    result
+}
+
+/// Apply the cast shadow to an RGBA u8 image.
+pub fn cast_shadow_rgba_u8(image: ArrayView3<u8>, params: &CastShadowParams) -> Array3<u8> {
+    let input_f32 = image.mapv(|v| v as f32 / 255.0);
+    cast_shadow_rgba_f32(input_f32.view(), params).mapv(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Apply cast shadow effect to RGBA image.
+///
+/// # Arguments
+/// * `image` - Source RGBA image (height, width, 4) as u8
+/// * `angle` - Shadow direction in degrees, clockwise from straight back
+/// * `length` - Shadow length relative to the object height
+/// * `perspective_squash` - Ground foreshortening (0.0 = none, 1.0 = flat)
+/// * `blur_near` - Blur sigma at the contact line
+/// * `blur_far` - Blur sigma at the shadow tip
+/// * `opacity` - Shadow opacity (0.0-1.0)
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+///
+/// # Returns
+/// RGBA image with black cast shadow, expanded on every side
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (image, angle=30.0, length=0.6, perspective_squash=0.6, blur_near=1.0, blur_far=8.0, opacity=0.6, quality="exact"))]
+pub fn cast_shadow_rgba<'py>(
+    image: PyHostArray3<'py, u8>,
+    angle: f32,
+    length: f32,
+    perspective_squash: f32,
+    blur_near: f32,
+    blur_far: f32,
+    opacity: f32,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let params = CastShadowParams { angle, length, perspective_squash, blur_near, blur_far, opacity, quality: parse_blur_quality(quality)?, ..Default::default() };
+    Ok(cast_shadow_rgba_u8(image.as_array(), &params).into_pyarray(image.py()))
+}
+
+/// Apply cast shadow effect to f32 RGBA image.
+///
+/// Same as cast_shadow_rgba but for f32 input/output (0.0-1.0 range).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(name = "cast_shadow_rgba_f32", signature = (image, angle=30.0, length=0.6, perspective_squash=0.6, blur_near=1.0, blur_far=8.0, opacity=0.6, quality="exact"))]
+pub fn cast_shadow_rgba_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    angle: f32,
+    length: f32,
+    perspective_squash: f32,
+    blur_near: f32,
+    blur_far: f32,
+    opacity: f32,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let params = CastShadowParams { angle, length, perspective_squash, blur_near, blur_far, opacity, quality: parse_blur_quality(quality)?, ..Default::default() };
+    Ok(cast_shadow_rgba_f32(image.as_array(), &params).into_pyarray(image.py()))
+}
+
+/// Pixels `cast_shadow_rgba` adds on each side of a layer of the given size.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (width, height, angle=30.0, length=0.6, perspective_squash=0.6, blur_near=1.0, blur_far=8.0))]
+pub fn cast_shadow_expansion(
+    width: usize,
+    height: usize,
+    angle: f32,
+    length: f32,
+    perspective_squash: f32,
+    blur_near: f32,
+    blur_far: f32,
+) -> usize {
+    let params = CastShadowParams { angle, length, perspective_squash, blur_near, blur_far, ..Default::default() };
+    params.expansion(width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cast_shadow_falls_behind_and_blurs_with_distance() {
+        // 10x40 pole standing on row 59 of a 40x80 canvas
+        let alpha = Array2::from_shape_fn((80, 40), |(y, x)| if (20..60).contains(&y) && (15..25).contains(&x) { 1.0 } else { 0.0 });
+        let params = CastShadowParams { angle: 0.0, length: 1.0, perspective_squash: 0.5, blur_near: 0.5, blur_far: 3.0, opacity: 1.0, ..Default::default() };
+        let shadow = cast_shadow_mask(&alpha, &params);
+
+        // Straight back and half as tall: rows 40..60, nothing in front
+        assert!(shadow[[55, 20]] > 0.9);
+        assert!(shadow[[45, 20]] > 0.5);
+        assert!(shadow[[30, 20]] < 0.05);
+        assert!(shadow[[65, 20]] < 0.05);
+        // Near end stays crisp, far end is soft
+        let edge = |y: usize| shadow[[y, 12]];
+        assert!(edge(57) < 0.05);
+        assert!(edge(43) > 0.05);
+
+        // Leaning right shifts the tip sideways
+        let params = CastShadowParams { angle: 45.0, ..params };
+        let shadow = cast_shadow_mask(&alpha, &params);
+        let tip_row = 50;
+        let weighted: f32 = (0..40).map(|x| shadow[[tip_row, x]] * x as f32).sum::<f32>() / shadow.row(tip_row).sum();
+        assert!(weighted > 25.0, "centroid {weighted}");
+    }
+}
@@ -18,6 +18,11 @@ export { initWasm };
  * @param {Object} options - Color overlay options
  * @param {Array<number>} [options.color=[255,0,0]] - Overlay color [r, g, b] (0-255)
  * @param {number} [options.opacity=1.0] - Opacity (0.0-1.0)
+ * @param {string} [options.blendMode='normal'] - Blend mode: 'normal', 'multiply', 'screen', 'overlay',
+ *     'darken', 'lighten', 'color_dodge', 'color_burn', 'linear_dodge', 'linear_burn', 'soft_light',
+ *     'hard_light', 'difference', 'exclusion'
+ * @param {boolean} [options.preserveLuminosity=false] - Tint like the 'Color' blend mode: hue and
+ *     saturation from the color, luminosity from the layer (ignores blendMode)
  * @returns {Object} - Result with same dimensions {data, width, height, channels}
  */
 export function color_overlay(imageData, options = {}) {
@@ -29,17 +34,17 @@ export function color_overlay(imageData, options = {}) {
     }
 
     const color = options.color ?? [255, 0, 0];
-    const opacity = options.opacity ?? 1.0;
-
-    const result = wasm.color_overlay_rgba_wasm(
-        new Uint8Array(data.buffer),
-        width,
-        height,
-        color[0],
-        color[1],
-        color[2],
-        opacity
-    );
+    const opts = Object.assign(new wasm.ColorOverlayOptions(), {
+        color_r: color[0],
+        color_g: color[1],
+        color_b: color[2],
+        opacity: options.opacity ?? 1.0,
+        blend_mode: options.blendMode ?? 'normal',
+        preserve_luminosity: options.preserveLuminosity ?? false,
+    });
+
+    const result = wasm.color_overlay_rgba_options_wasm(new Uint8Array(data.buffer), width, height, opts);
+    opts.free();
 
     return {
         data: new Uint8ClampedArray(result.buffer),
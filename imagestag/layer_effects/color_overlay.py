@@ -25,11 +25,15 @@ class ColorOverlay(LayerEffect):
     Color overlay effect.
 
     Replaces all colors with a solid color while preserving alpha.
-    The opacity controls how much of the original color shows through.
+    The opacity controls how much of the original color shows through,
+    blend_mode ('multiply', 'screen', 'overlay', ...) how the color combines
+    with the layer content. With preserve_luminosity the color only sets hue
+    and saturation and the layer keeps its luminosity (Photoshop's "Color"
+    blend mode), which tints icons without flattening their shading.
 
     Example:
         >>> from imagestag.layer_effects import ColorOverlay
-        >>> effect = ColorOverlay(color='#FF0000', opacity=1.0)
+        >>> effect = ColorOverlay(color='#FF0000', opacity=1.0, blend_mode='multiply')
         >>> result = effect.apply(image)
     """
 
@@ -38,6 +42,7 @@ class ColorOverlay(LayerEffect):
 
     # Effect-specific fields
     color: str = Field(default='#FF0000')  # Hex string for JS compatibility
+    preserve_luminosity: bool = Field(default=False)
 
     # Internal: parsed RGB tuple (not serialized)
     _color_rgb: Optional[Tuple[int, int, int]] = None
@@ -134,12 +139,16 @@ class ColorOverlay(LayerEffect):
                 image.astype(np.float32),
                 color_f32,
                 float(self.opacity),
+                self.blend_mode,
+                self.preserve_luminosity,
             )
         else:
             result = imagestag_rust.color_overlay_rgba(
                 image.astype(np.uint8),
                 color,
                 float(self.opacity),
+                self.blend_mode,
+                self.preserve_luminosity,
             )
 
         return EffectResult(
@@ -170,6 +179,14 @@ class ColorOverlay(LayerEffect):
         if not self.enabled:
             return None
 
+        if self.preserve_luminosity:
+            # Color blend of the flood with the source, clipped to its alpha
+            return f'''<filter id="{filter_id}" x="0%" y="0%" width="100%" height="100%">
+  <feFlood flood-color="{self.color}" flood-opacity="{self.opacity}" result="color"/>
+  <feBlend in="color" in2="SourceGraphic" mode="color" result="tinted"/>
+  <feComposite in="tinted" in2="SourceAlpha" operator="in"/>
+</filter>'''
+
         # Color overlay: blend solid color over source with given opacity
         # 1. Create color flood with effect opacity
         # 2. Clip to source alpha
@@ -184,4 +201,7 @@ class ColorOverlay(LayerEffect):
 </filter>'''
 
     def __repr__(self) -> str:
-        return f"ColorOverlay(color={self.color}, opacity={self.opacity})"
+        return (
+            f"ColorOverlay(color={self.color}, opacity={self.opacity}, blend_mode={self.blend_mode}, "
+            f"preserve_luminosity={self.preserve_luminosity})"
+        )
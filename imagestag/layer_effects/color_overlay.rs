@@ -1,15 +1,216 @@
 //! Color Overlay layer effect.
 //!
-//! Replaces all colors with a solid color while preserving alpha.
+//! Replaces all colors with a solid color while preserving alpha. With
+//! `preserve_luminosity` the overlay acts like the "Color" blend mode:
+//! hue and saturation come from the overlay, luminosity from the layer,
+//! so tinted icons keep their shading.
+//!
+//! Also home of the blend mode helpers shared by the gradient and pattern
+//! overlays ([`blend_channel`], [`overlay_f32`]).
 //!
 //! Co-located with:
 //! - color_overlay.py (Python wrapper)
 //! - color_overlay.js (JavaScript wrapper)
 
-use ndarray::Array3;
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array3, ArrayView3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Blend modes accepted by the overlay effects (color, gradient, pattern).
+pub const BLEND_MODES: [&str; 14] = [
+    "normal",
+    "multiply",
+    "screen",
+    "overlay",
+    "darken",
+    "lighten",
+    "color_dodge",
+    "color_burn",
+    "linear_dodge",
+    "linear_burn",
+    "soft_light",
+    "hard_light",
+    "difference",
+    "exclusion",
+];
+
+/// Blend a single channel with one of the overlay blend modes.
+///
+/// `base` is the layer content, `top` the effect color. Unknown modes
+/// fall back to "normal".
+#[inline]
+pub fn blend_channel(mode: &str, base: f32, top: f32) -> f32 {
+    match mode {
+        "multiply" => base * top,
+        "screen" => 1.0 - (1.0 - base) * (1.0 - top),
+        "overlay" => {
+            if base < 0.5 {
+                2.0 * base * top
+            } else {
+                1.0 - 2.0 * (1.0 - base) * (1.0 - top)
+            }
+        }
+        "darken" => base.min(top),
+        "lighten" => base.max(top),
+        "color_dodge" => {
+            if base <= 0.0 {
+                0.0
+            } else if top >= 1.0 {
+                1.0
+            } else {
+                (base / (1.0 - top)).min(1.0)
+            }
+        }
+        "color_burn" => {
+            if base >= 1.0 {
+                1.0
+            } else if top <= 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - base) / top).min(1.0)
+            }
+        }
+        "linear_dodge" | "add" => (base + top).min(1.0),
+        "linear_burn" => (base + top - 1.0).max(0.0),
+        "soft_light" => {
+            // W3C compositing formula
+            if top <= 0.5 {
+                base - (1.0 - 2.0 * top) * base * (1.0 - base)
+            } else {
+                let d = if base <= 0.25 { ((16.0 * base - 12.0) * base + 4.0) * base } else { base.sqrt() };
+                base + (2.0 * top - 1.0) * (d - base)
+            }
+        }
+        "hard_light" => blend_channel("overlay", top, base),
+        "difference" => (base - top).abs(),
+        "exclusion" => base + top - 2.0 * base * top,
+        _ => top,
+    }
+}
+
+/// Composite an effect fill over the layer content, preserving alpha.
+///
+/// `fill(y, x)` returns the effect color and its strength (opacity times
+/// coverage) at a pixel. Fully transparent pixels are returned unchanged.
+///
+/// # Returns
+/// RGBA image (height, width, 4), values 0.0-1.0
+pub fn overlay_f32<F>(input: ArrayView3<f32>, blend_mode: &str, fill: F) -> Array3<f32>
+where
+    F: Fn(usize, usize) -> ([f32; 3], f32),
+{
+    overlay_pixels_f32(input, fill, |base, top| [0, 1, 2].map(|c| blend_channel(blend_mode, base[c], top[c])))
+}
+
+/// [`overlay_f32`] with a blend function over whole RGB pixels.
+fn overlay_pixels_f32<F, B>(input: ArrayView3<f32>, fill: F, blend: B) -> Array3<f32>
+where
+    F: Fn(usize, usize) -> ([f32; 3], f32),
+    B: Fn([f32; 3], [f32; 3]) -> [f32; 3],
+{
+    let (height, width, _) = input.dim();
+    let mut result = input.to_owned();
+    for y in 0..height {
+        for x in 0..width {
+            if input[[y, x, 3]] <= 0.0 {
+                continue;
+            }
+            let (color, amount) = fill(y, x);
+            let amount = amount.clamp(0.0, 1.0);
+            if amount <= 0.0 {
+                continue;
+            }
+            let base = [0, 1, 2].map(|c| input[[y, x, c]]);
+            let blended = blend(base, color);
+            for c in 0..3 {
+                result[[y, x, c]] = (base[c] + (blended[c] - base[c]) * amount).clamp(0.0, 1.0);
+            }
+        }
+    }
+    result
+}
+
+/// Luminosity as defined for the non-separable blend modes (W3C compositing).
+#[inline]
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+/// "Color" blend mode: hue and saturation of `top`, luminosity of `base`.
+#[inline]
+pub(crate) fn blend_color(base: [f32; 3], top: [f32; 3]) -> [f32; 3] {
+    let target = lum(base);
+    let d = target - lum(top);
+    let c = top.map(|v| v + d);
+    // ClipColor: pull out-of-gamut results towards the gray of equal luminosity
+    let l = lum(c);
+    let (n, x) = (c[0].min(c[1]).min(c[2]), c[0].max(c[1]).max(c[2]));
+    c.map(|v| {
+        let mut v = v;
+        if n < 0.0 {
+            v = l + (v - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            v = l + (v - l) * (1.0 - l) / (x - l);
+        }
+        v
+    })
+}
+
+/// u8 variant of [`overlay_f32`]; untouched pixels keep their exact values.
+pub fn overlay_u8<F>(input: ArrayView3<u8>, blend_mode: &str, fill: F) -> Array3<u8>
+where
+    F: Fn(usize, usize) -> ([f32; 3], f32),
+{
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    overlay_f32(input_f32.view(), blend_mode, fill).mapv(|v| (v * 255.0).round() as u8)
+}
+
+/// Parameters for the color overlay effect.
+#[derive(Debug, Clone)]
+pub struct ColorOverlayParams {
+    /// Overlay color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Overlay opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Blend mode, one of [`BLEND_MODES`]
+    pub blend_mode: String,
+    /// Keep the layer's luminosity and take only hue and saturation from
+    /// the color ("Color" blend mode); `blend_mode` is ignored
+    pub preserve_luminosity: bool,
+}
+
+impl Default for ColorOverlayParams {
+    fn default() -> Self {
+        Self {
+            color: [1.0, 0.0, 0.0],
+            opacity: 1.0,
+            blend_mode: "normal".to_string(),
+            preserve_luminosity: false,
+        }
+    }
+}
+
+/// Apply color overlay to an RGBA f32 image.
+pub fn color_overlay_f32(input: ArrayView3<f32>, params: &ColorOverlayParams) -> Array3<f32> {
+    let fill = |_, _| (params.color, params.opacity);
+    if params.preserve_luminosity {
+        overlay_pixels_f32(input, fill, blend_color)
+    } else {
+        overlay_f32(input, &params.blend_mode, fill)
+    }
+}
+
+/// Apply color overlay to an RGBA u8 image.
+pub fn color_overlay_u8(input: ArrayView3<u8>, params: &ColorOverlayParams) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    color_overlay_f32(input_f32.view(), params).mapv(|v| (v * 255.0).round() as u8)
+}
+
 /// Apply color overlay effect to RGBA image.
 ///
 /// Replaces all colors with a solid color while preserving alpha.
@@ -18,79 +219,93 @@ use pyo3::prelude::*;
 /// * `image` - Source RGBA image
 /// * `color` - Overlay color (R, G, B)
 /// * `opacity` - Overlay opacity (0.0-1.0)
+/// * `blend_mode` - Blend mode, one of [`BLEND_MODES`]
+/// * `preserve_luminosity` - Tint like the "Color" blend mode (ignores `blend_mode`)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, color=(255, 0, 0), opacity=1.0))]
+#[pyo3(signature = (image, color=(255, 0, 0), opacity=1.0, blend_mode="normal", preserve_luminosity=false))]
 pub fn color_overlay_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     color: (u8, u8, u8),
     opacity: f32,
+    blend_mode: &str,
+    preserve_luminosity: bool,
 ) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    let mut result = Array3::<u8>::zeros((height, width, 4));
-
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input[[y, x, 3]] as f32 / 255.0;
-            if orig_a <= 0.0 {
-                continue;
-            }
-
-            // Blend overlay color with original based on opacity
-            let blend_a = opacity;
-            let orig_r = input[[y, x, 0]] as f32 / 255.0;
-            let orig_g = input[[y, x, 1]] as f32 / 255.0;
-            let orig_b = input[[y, x, 2]] as f32 / 255.0;
-            let overlay_r = color.0 as f32 / 255.0;
-            let overlay_g = color.1 as f32 / 255.0;
-            let overlay_b = color.2 as f32 / 255.0;
-
-            // Linear blend between original and overlay color
-            let final_r = orig_r * (1.0 - blend_a) + overlay_r * blend_a;
-            let final_g = orig_g * (1.0 - blend_a) + overlay_g * blend_a;
-            let final_b = orig_b * (1.0 - blend_a) + overlay_b * blend_a;
-
-            result[[y, x, 0]] = (final_r * 255.0) as u8;
-            result[[y, x, 1]] = (final_g * 255.0) as u8;
-            result[[y, x, 2]] = (final_b * 255.0) as u8;
-            result[[y, x, 3]] = input[[y, x, 3]]; // Preserve alpha
-        }
-    }
-
-    result.into_pyarray(py)
+    let params = ColorOverlayParams {
+        color: [color.0 as f32 / 255.0, color.1 as f32 / 255.0, color.2 as f32 / 255.0],
+        opacity,
+        blend_mode: blend_mode.to_string(),
+        preserve_luminosity,
+    };
+    color_overlay_u8(image.as_array(), &params).into_pyarray(py)
 }
 
 /// Apply color overlay effect to f32 RGBA image.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, color=(1.0, 0.0, 0.0), opacity=1.0))]
+#[pyo3(signature = (image, color=(1.0, 0.0, 0.0), opacity=1.0, blend_mode="normal", preserve_luminosity=false))]
 pub fn color_overlay_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     color: (f32, f32, f32),
     opacity: f32,
+    blend_mode: &str,
+    preserve_luminosity: bool,
 ) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
+    let params = ColorOverlayParams {
+        color: [color.0, color.1, color.2],
+        opacity,
+        blend_mode: blend_mode.to_string(),
+        preserve_luminosity,
+    };
+    color_overlay_f32(image.as_array(), &params).into_pyarray(py)
+}
 
-    let mut result = Array3::<f32>::zeros((height, width, 4));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input[[y, x, 3]];
-            if orig_a <= 0.0 {
-                continue;
-            }
+    #[test]
+    fn test_blend_modes_and_transparent_pixels() {
+        let mut image = Array3::<f32>::zeros((1, 3, 4));
+        image[[0, 0, 0]] = 0.5;
+        image[[0, 0, 3]] = 1.0;
+        image[[0, 1, 0]] = 0.5;
+        image[[0, 1, 3]] = 0.5;
+        // Transparent pixel with color data must survive untouched
+        image[[0, 2, 0]] = 0.3;
+
+        let multiply = ColorOverlayParams { color: [0.5, 0.5, 0.5], blend_mode: "multiply".into(), ..Default::default() };
+        let result = color_overlay_f32(image.view(), &multiply);
+        assert!((result[[0, 0, 0]] - 0.25).abs() < 1e-6);
+        assert!((result[[0, 1, 0]] - 0.25).abs() < 1e-6);
+        assert_eq!(result[[0, 1, 3]], 0.5);
+        assert_eq!(result[[0, 2, 0]], 0.3);
 
-            let blend_a = opacity;
+        // Half opacity mixes the blended and the original color
+        let half = ColorOverlayParams { opacity: 0.5, ..multiply };
+        assert!((color_overlay_f32(image.view(), &half)[[0, 0, 0]] - 0.375).abs() < 1e-6);
 
-            result[[y, x, 0]] = input[[y, x, 0]] * (1.0 - blend_a) + color.0 * blend_a;
-            result[[y, x, 1]] = input[[y, x, 1]] * (1.0 - blend_a) + color.1 * blend_a;
-            result[[y, x, 2]] = input[[y, x, 2]] * (1.0 - blend_a) + color.2 * blend_a;
-            result[[y, x, 3]] = orig_a;
+        for mode in BLEND_MODES {
+            for (base, top) in [(0.0, 0.0), (0.2, 0.7), (1.0, 1.0), (0.8, 0.0)] {
+                let v = blend_channel(mode, base, top);
+                assert!((0.0..=1.0).contains(&v), "{mode}");
+            }
         }
-    }
+        assert_eq!(blend_channel("screen", 0.5, 0.5), 0.75);
+        assert_eq!(blend_channel("unknown", 0.2, 0.7), 0.7);
 
-    result.into_pyarray(py)
+        // Colorize: a gray ramp tinted red keeps its luminosity
+        let ramp = Array3::from_shape_fn((1, 3, 4), |(_, x, c)| if c == 3 { 1.0 } else { [0.2, 0.5, 0.9][x] });
+        let tint = ColorOverlayParams { color: [1.0, 0.0, 0.0], preserve_luminosity: true, ..Default::default() };
+        let tinted = color_overlay_f32(ramp.view(), &tint);
+        for x in 0..3 {
+            let pixel = [0, 1, 2].map(|c| tinted[[0, x, c]]);
+            assert!((lum(pixel) - lum([ramp[[0, x, 0]]; 3])).abs() < 1e-5);
+            assert!(pixel[0] > pixel[1] && (pixel[1] - pixel[2]).abs() < 1e-6);
+        }
+        // Bright pixels are clipped towards white instead of a flat red
+        assert!(tinted[[0, 2, 1]] > 0.8 && tinted[[0, 2, 0]] <= 1.0);
+    }
 }
@@ -21,6 +21,8 @@ export { initWasm };
  * @param {number} [options.blur_radius=10] - Shadow blur radius
  * @param {Array<number>} [options.color=[0,0,0]] - Shadow color [r, g, b] (0-255)
  * @param {number} [options.opacity=0.75] - Shadow opacity (0.0-1.0)
+ * @param {boolean} [options.knockout=false] - Hide the shadow under the layer's own pixels
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
  * @returns {Object} - Result with expanded canvas {data, width, height, channels, offset_x, offset_y}
  */
 export function drop_shadow(imageData, options = {}) {
@@ -36,6 +38,7 @@ export function drop_shadow(imageData, options = {}) {
     const blur_radius = options.blur_radius ?? 10;
     const color = options.color ?? [0, 0, 0];
     const opacity = options.opacity ?? 0.75;
+    const knockout = options.knockout ?? false;
 
     const result = wasm.drop_shadow_rgba_wasm(
         new Uint8Array(data.buffer),
@@ -47,13 +50,13 @@ export function drop_shadow(imageData, options = {}) {
         color[0],
         color[1],
         color[2],
-        opacity
+        opacity,
+        knockout,
+        options.quality ?? 'exact'
     );
 
     // Calculate expanded dimensions
-    const blur_expand = Math.ceil(blur_radius * 3);
-    const offset_expand = Math.ceil(Math.max(Math.abs(offset_x), Math.abs(offset_y)));
-    const required_expand = blur_expand + offset_expand + 2;
+    const required_expand = wasm.drop_shadow_expansion_wasm(offset_x, offset_y, blur_radius);
 
     const new_width = width + required_expand * 2;
     const new_height = height + required_expand * 2;
@@ -50,6 +50,10 @@ class DropShadow(LayerEffect):
     color: str = Field(default='#000000')  # Hex string for JS compatibility
     color_opacity: float = Field(default=0.75, alias='colorOpacity', ge=0.0, le=1.0)
     spread: float = Field(default=0.0)
+    # Hide the shadow under the layer's own pixels (Photoshop "Layer Knocks Out Drop Shadow")
+    layer_knocks_out: bool = Field(default=False, alias='layerKnocksOut')
+    # Blur accuracy: 'exact', or 'fast' for interactive previews
+    quality: str = Field(default='exact')
 
     # Internal: parsed RGB tuple (not serialized)
     _color_rgb: Optional[Tuple[int, int, int]] = None
@@ -174,6 +178,8 @@ class DropShadow(LayerEffect):
                 color_f32,
                 float(self.color_opacity),
                 expand,
+                bool(self.layer_knocks_out),
+                quality=self.quality,
             )
         else:
             result = imagestag_rust.drop_shadow_rgba(
@@ -184,6 +190,8 @@ class DropShadow(LayerEffect):
                 color,
                 float(self.color_opacity),
                 expand,
+                bool(self.layer_knocks_out),
+                quality=self.quality,
             )
 
         # The result is expanded, offset is negative of expansion
@@ -248,6 +256,7 @@ class DropShadow(LayerEffect):
                 color_f32,
                 float(self.color_opacity),
                 expand,
+                quality=self.quality,
             )
         else:
             result = imagestag_rust.drop_shadow_only_rgba(
@@ -258,6 +267,7 @@ class DropShadow(LayerEffect):
                 color,
                 float(self.color_opacity),
                 expand,
+                quality=self.quality,
             )
 
         return EffectResult(
@@ -5,15 +5,154 @@
 //! 2. Blurring it with Gaussian kernel
 //! 3. Offsetting the shadow
 //! 4. Colorizing with shadow color
-//! 5. Compositing original on top
+//! 5. Optionally knocking the shadow out under the layer
+//! 6. Compositing original on top
 //!
 //! Anti-aliasing is preserved through all operations.
 
-use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::filters::core::{blur_alpha_f32, expand_canvas_f32};
+#[cfg(feature = "python")]
+use crate::filters::blur::parse_blur_quality;
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{blend_over_f32, blur_alpha_quality_f32, expand_canvas_view_f32, image_f32_to_u8, image_u8_to_f32, CanvasPolicy};
+use crate::filters::pool::{self, Pooled};
+
+
+/// Parameters for the drop shadow effect.
+#[derive(Debug, Clone)]
+pub struct DropShadowParams {
+    /// Horizontal shadow offset (positive = right)
+    pub offset_x: f32,
+    /// Vertical shadow offset (positive = down)
+    pub offset_y: f32,
+    /// Shadow blur radius (sigma for Gaussian)
+    pub blur_radius: f32,
+    /// Shadow color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Shadow opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Layer knocks out drop shadow: remove the shadow under the layer's own
+    /// pixels so it does not show through semi-transparent areas
+    pub knockout: bool,
+    /// Blur accuracy; `Fast` for interactive previews
+    pub quality: BlurQuality,
+    /// Output canvas; ignored inside a layer style, which has its own
+    pub canvas: CanvasPolicy,
+}
+
+impl Default for DropShadowParams {
+    fn default() -> Self {
+        Self {
+            offset_x: 4.0,
+            offset_y: 4.0,
+            blur_radius: 5.0,
+            color: [0.0, 0.0, 0.0],
+            opacity: 0.75,
+            knockout: false,
+            quality: BlurQuality::Exact,
+            canvas: CanvasPolicy::Grow,
+        }
+    }
+}
+
+impl DropShadowParams {
+    /// Pixels required on each side of the layer for the shadow overflow.
+    pub fn expansion(&self) -> usize {
+        let blur_expand = (self.blur_radius * 3.0).ceil() as usize;
+        let offset_expand = self.offset_x.abs().max(self.offset_y.abs()).ceil() as usize;
+        blur_expand + offset_expand + 2
+    }
+}
+
+/// Compute the drop shadow coverage from a layer alpha mask.
+///
+/// The mask must already contain room for the shadow overflow
+/// (see [`DropShadowParams::expansion`]).
+///
+/// # Returns
+/// Shadow alpha (0.0-1.0) with the opacity applied, same size as `alpha`
+pub fn drop_shadow_mask(alpha: &Array2<f32>, params: &DropShadowParams) -> Array2<f32> {
+    let (height, width) = alpha.dim();
+    let blurred = Pooled::new(blur_alpha_quality_f32(alpha, params.blur_radius, params.quality));
+    let ox = params.offset_x.round() as isize;
+    let oy = params.offset_y.round() as isize;
+
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let sx = (x as isize - ox).clamp(0, width as isize - 1) as usize;
+        let sy = (y as isize - oy).clamp(0, height as isize - 1) as usize;
+        let knockout = if params.knockout { 1.0 - alpha[[y, x]] } else { 1.0 };
+        blurred[[sy, sx]] * params.opacity * knockout
+    })
+}
+
+/// Render the drop shadow behind an RGBA f32 layer.
+///
+/// # Arguments
+/// * `image` - Source RGBA image (height, width, 4)
+/// * `params` - Shadow parameters
+/// * `expand` - Pixels to add on each side (0 = [`DropShadowParams::expansion`])
+///
+/// # Returns
+/// RGBA image with the shadow behind the original, on the canvas chosen by
+/// [`DropShadowParams::canvas`]
+pub fn render_drop_shadow_f32(image: ArrayView3<f32>, params: &DropShadowParams, expand: usize) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let expand = if expand > 0 { expand } else { params.expansion() };
+    let expanded = Pooled::new(expand_canvas_view_f32(image, expand));
+    let alpha = Pooled::new(pool::copy2(expanded.index_axis(Axis(2), 3)));
+    let shadow = drop_shadow_mask(&alpha, params);
+
+    let mut result = Array3::<f32>::zeros(expanded.dim());
+    let [r, g, b] = params.color;
+    for ((y, x), &shadow_a) in shadow.indexed_iter() {
+        let mut pixel = [r, g, b, shadow_a];
+        let src = |c: usize| expanded[[y, x, c]];
+        blend_over_f32(&mut pixel, src(0), src(1), src(2), src(3));
+        for (c, &v) in pixel.iter().enumerate() {
+            result[[y, x, c]] = v;
+        }
+    }
+    params.canvas.apply(result, (height, width), expand)
+}
+
+/// Render the drop shadow behind an RGBA u8 layer.
+pub fn render_drop_shadow_u8(image: ArrayView3<u8>, params: &DropShadowParams, expand: usize) -> Array3<u8> {
+    image_f32_to_u8(&render_drop_shadow_f32(image_u8_to_f32(image).view(), params, expand))
+}
+
+/// Render only the shadow layer of an RGBA f32 image, without the original
+/// composited on top.
+///
+/// # Arguments
+/// Same as [`render_drop_shadow_f32`]
+pub fn render_drop_shadow_only_f32(image: ArrayView3<f32>, params: &DropShadowParams, expand: usize) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let expand = if expand > 0 { expand } else { params.expansion() };
+    let expanded = Pooled::new(expand_canvas_view_f32(image, expand));
+    let alpha = Pooled::new(pool::copy2(expanded.index_axis(Axis(2), 3)));
+    let shadow = drop_shadow_mask(&alpha, params);
+
+    let [r, g, b] = params.color;
+    let result = Array3::from_shape_fn(expanded.dim(), |(y, x, c)| match c {
+        0 => r,
+        1 => g,
+        2 => b,
+        _ => shadow[[y, x]],
+    });
+    params.canvas.apply(result, (height, width), expand)
+}
+
+/// Render only the shadow layer of an RGBA u8 image.
+pub fn render_drop_shadow_only_u8(image: ArrayView3<u8>, params: &DropShadowParams, expand: usize) -> Array3<u8> {
+    image_f32_to_u8(&render_drop_shadow_only_f32(image_u8_to_f32(image).view(), params, expand))
+}
 
 /// Apply drop shadow effect to RGBA image.
 ///
@@ -25,213 +164,73 @@ use crate::filters::core::{blur_alpha_f32, expand_canvas_f32};
 /// * `color` - Shadow color as (R, G, B) tuple (0-255)
 /// * `opacity` - Shadow opacity (0.0-1.0)
 /// * `expand` - Extra pixels to add around image for shadow overflow
+/// * `knockout` - Layer knocks out drop shadow (hidden under semi-transparent pixels)
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+/// * `canvas` - Output canvas: "grow" (fit the shadow) or "clip" (input size)
+/// * `canvas_size` - Explicit output (width, height); overrides `canvas`
+/// * `anchor` - Layer position in an explicit canvas ("center", "top_left", ...)
 ///
 /// # Returns
 /// RGBA image with drop shadow, potentially larger than input
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0, 0, 0), opacity=0.75, expand=0))]
+#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0, 0, 0), opacity=0.75, expand=0, knockout=false, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn drop_shadow_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     offset_x: f32,
     offset_y: f32,
     blur_radius: f32,
     color: (u8, u8, u8),
     opacity: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32 for processing
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Calculate required expansion based on offset and blur
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        let blur_expand = (blur_radius * 3.0).ceil() as usize;
-        let offset_expand = offset_x.abs().max(offset_y.abs()).ceil() as usize;
-        blur_expand + offset_expand + 2
-    };
-
-    // Expand canvas if needed
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32.clone()
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha channel
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Blur the alpha channel
-    let blurred_alpha = blur_alpha_f32(&alpha, blur_radius);
-
-    // Create result with shadow
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    let shadow_r = color.0 as f32 / 255.0;
-    let shadow_g = color.1 as f32 / 255.0;
-    let shadow_b = color.2 as f32 / 255.0;
-
-    // Draw shadow (offset and colorized)
-    let ox = offset_x.round() as isize;
-    let oy = offset_y.round() as isize;
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            // Sample shadow alpha from offset position
-            let sx = (x as isize - ox).clamp(0, new_w as isize - 1) as usize;
-            let sy = (y as isize - oy).clamp(0, new_h as isize - 1) as usize;
-
-            let shadow_a = blurred_alpha[[sy, sx]] * opacity;
-
-            result[[y, x, 0]] = shadow_r;
-            result[[y, x, 1]] = shadow_g;
-            result[[y, x, 2]] = shadow_b;
-            result[[y, x, 3]] = shadow_a;
-        }
-    }
-
-    // Composite original image on top using Porter-Duff "over"
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let src_a = expanded[[y, x, 3]];
-            if src_a <= 0.0 {
-                continue;
-            }
-
-            let dst_a = result[[y, x, 3]];
-            let out_a = src_a + dst_a * (1.0 - src_a);
-
-            if out_a > 0.0 {
-                result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 3]] = out_a;
-            }
-        }
-    }
-
-    // Convert back to u8
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    knockout: bool,
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = DropShadowParams { offset_x, offset_y, blur_radius, color, opacity, knockout, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    Ok(render_drop_shadow_u8(image.as_array(), &params, expand).into_pyarray(py))
 }
 
 /// Apply drop shadow effect to f32 RGBA image.
 ///
 /// Same as drop_shadow_rgba but for f32 input/output (0.0-1.0 range).
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0.0, 0.0, 0.0), opacity=0.75, expand=0))]
+#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0.0, 0.0, 0.0), opacity=0.75, expand=0, knockout=false, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn drop_shadow_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     offset_x: f32,
     offset_y: f32,
     blur_radius: f32,
     color: (f32, f32, f32),
     opacity: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Clone input for processing
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    // Calculate required expansion
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        let blur_expand = (blur_radius * 3.0).ceil() as usize;
-        let offset_expand = offset_x.abs().max(offset_y.abs()).ceil() as usize;
-        blur_expand + offset_expand + 2
-    };
-
-    // Expand canvas if needed
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha channel
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Blur the alpha channel
-    let blurred_alpha = blur_alpha_f32(&alpha, blur_radius);
-
-    // Create result with shadow
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    // Draw shadow (offset and colorized)
-    let ox = offset_x.round() as isize;
-    let oy = offset_y.round() as isize;
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let sx = (x as isize - ox).clamp(0, new_w as isize - 1) as usize;
-            let sy = (y as isize - oy).clamp(0, new_h as isize - 1) as usize;
-
-            let shadow_a = blurred_alpha[[sy, sx]] * opacity;
-
-            result[[y, x, 0]] = color.0;
-            result[[y, x, 1]] = color.1;
-            result[[y, x, 2]] = color.2;
-            result[[y, x, 3]] = shadow_a;
-        }
-    }
-
-    // Composite original image on top
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let src_a = expanded[[y, x, 3]];
-            if src_a <= 0.0 {
-                continue;
-            }
-
-            let dst_a = result[[y, x, 3]];
-            let out_a = src_a + dst_a * (1.0 - src_a);
-
-            if out_a > 0.0 {
-                result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 3]] = out_a;
-            }
-        }
-    }
+    knockout: bool,
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = DropShadowParams { offset_x, offset_y, blur_radius, color: [color.0, color.1, color.2], opacity, knockout, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    Ok(render_drop_shadow_f32(image.as_array(), &params, expand).into_pyarray(py))
+}
 
-    result.into_pyarray(py)
+/// Pixels `drop_shadow_rgba` adds on each side when `expand` is 0.
+///
+/// The shadowed image starts at (-expansion, -expansion) relative to the
+/// original layer origin.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (offset_x=4.0, offset_y=4.0, blur_radius=5.0))]
+pub fn drop_shadow_expansion(offset_x: f32, offset_y: f32, blur_radius: f32) -> usize {
+    DropShadowParams { offset_x, offset_y, blur_radius, ..Default::default() }.expansion()
 }
 
 /// Get shadow-only layer (no compositing with original).
@@ -248,176 +247,74 @@ pub fn drop_shadow_rgba_f32<'py>(
 /// * `color` - Shadow color as (R, G, B) tuple (0-255)
 /// * `opacity` - Shadow opacity (0.0-1.0)
 /// * `expand` - Extra pixels to add around image for shadow overflow
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+/// * `canvas`, `canvas_size`, `anchor` - Output canvas, as for `drop_shadow_rgba`
 ///
 /// # Returns
 /// RGBA image with ONLY the shadow (original NOT composited on top)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0, 0, 0), opacity=0.75, expand=0))]
+#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0, 0, 0), opacity=0.75, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn drop_shadow_only_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     offset_x: f32,
     offset_y: f32,
     blur_radius: f32,
     color: (u8, u8, u8),
     opacity: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32 for processing
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Calculate required expansion
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        let blur_expand = (blur_radius * 3.0).ceil() as usize;
-        let offset_expand = offset_x.abs().max(offset_y.abs()).ceil() as usize;
-        blur_expand + offset_expand + 2
-    };
-
-    // Expand canvas if needed
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32.clone()
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha channel
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Blur the alpha channel
-    let blurred_alpha = blur_alpha_f32(&alpha, blur_radius);
-
-    // Create shadow-only result (no compositing with original)
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    let shadow_r = color.0 as f32 / 255.0;
-    let shadow_g = color.1 as f32 / 255.0;
-    let shadow_b = color.2 as f32 / 255.0;
-
-    // Draw shadow (offset and colorized) - this is the FULL shadow including under original
-    let ox = offset_x.round() as isize;
-    let oy = offset_y.round() as isize;
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            // Sample shadow alpha from offset position
-            let sx = (x as isize - ox).clamp(0, new_w as isize - 1) as usize;
-            let sy = (y as isize - oy).clamp(0, new_h as isize - 1) as usize;
-
-            let shadow_a = blurred_alpha[[sy, sx]] * opacity;
-
-            result[[y, x, 0]] = shadow_r;
-            result[[y, x, 1]] = shadow_g;
-            result[[y, x, 2]] = shadow_b;
-            result[[y, x, 3]] = shadow_a;
-        }
-    }
-
-    // NOTE: No compositing step - return shadow layer only
-    // The caller can composite vector content on top
-
-    // Convert back to u8
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = DropShadowParams { offset_x, offset_y, blur_radius, color, opacity, knockout: false, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    Ok(render_drop_shadow_only_u8(image.as_array(), &params, expand).into_pyarray(py))
 }
 
 /// Get shadow-only layer for f32 RGBA image (no compositing with original).
 ///
 /// Same as drop_shadow_only_rgba but for f32 input/output (0.0-1.0 range).
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0.0, 0.0, 0.0), opacity=0.75, expand=0))]
+#[pyo3(signature = (image, offset_x=4.0, offset_y=4.0, blur_radius=5.0, color=(0.0, 0.0, 0.0), opacity=0.75, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn drop_shadow_only_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     offset_x: f32,
     offset_y: f32,
     blur_radius: f32,
     color: (f32, f32, f32),
     opacity: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Clone input for processing
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    // Calculate required expansion
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        let blur_expand = (blur_radius * 3.0).ceil() as usize;
-        let offset_expand = offset_x.abs().max(offset_y.abs()).ceil() as usize;
-        blur_expand + offset_expand + 2
-    };
-
-    // Expand canvas if needed
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha channel
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Blur the alpha channel
-    let blurred_alpha = blur_alpha_f32(&alpha, blur_radius);
-
-    // Create shadow-only result (no compositing with original)
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    // Draw shadow (offset and colorized) - this is the FULL shadow including under original
-    let ox = offset_x.round() as isize;
-    let oy = offset_y.round() as isize;
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let sx = (x as isize - ox).clamp(0, new_w as isize - 1) as usize;
-            let sy = (y as isize - oy).clamp(0, new_h as isize - 1) as usize;
-
-            let shadow_a = blurred_alpha[[sy, sx]] * opacity;
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = DropShadowParams { offset_x, offset_y, blur_radius, color: [color.0, color.1, color.2], opacity, knockout: false, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    Ok(render_drop_shadow_only_f32(image.as_array(), &params, expand).into_pyarray(py))
+}
 
-            result[[y, x, 0]] = color.0;
-            result[[y, x, 1]] = color.1;
-            result[[y, x, 2]] = color.2;
-            result[[y, x, 3]] = shadow_a;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_effects::test_util::{assert_fits_padding, assert_layer_on_top, full_layer};
+
+    #[test]
+    fn test_expansion_matches_output_padding() {
+        let image = full_layer(0.6);
+        let cases = [(0.0, 0.0, 0.0), (4.0, 4.0, 5.0), (-6.0, 3.0, 2.0), (2.5, -7.5, 1.0), (0.0, -9.0, 0.0), (12.0, 0.0, 3.5)];
+        for (offset_x, offset_y, blur_radius) in cases {
+            let params = DropShadowParams { offset_x, offset_y, blur_radius, ..Default::default() };
+            let e = params.expansion();
+            assert_fits_padding(&render_drop_shadow_f32(image.view(), &params, 0), e, &format!("{params:?}"));
+            assert_fits_padding(&render_drop_shadow_only_f32(image.view(), &params, 0), e, &format!("{params:?}"));
+            assert_layer_on_top(&render_drop_shadow_f32(image.view(), &params, 0), &image, e);
         }
     }
-
-    // NOTE: No compositing step - return shadow layer only
-
-    result.into_pyarray(py)
 }
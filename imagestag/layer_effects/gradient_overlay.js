@@ -25,6 +25,9 @@ export { initWasm };
  * @param {number} [options.offsetY=0.0] - Vertical center offset (-1.0 to 1.0)
  * @param {boolean} [options.reverse=false] - Reverse the gradient
  * @param {number} [options.opacity=1.0] - Opacity (0.0-1.0)
+ * @param {string} [options.blendMode='normal'] - Blend mode: 'normal', 'multiply', 'screen', 'overlay',
+ *     'darken', 'lighten', 'color_dodge', 'color_burn', 'linear_dodge', 'linear_burn', 'soft_light',
+ *     'hard_light', 'difference', 'exclusion'
  * @returns {Object} - Result with same dimensions {data, width, height, channels}
  */
 export function gradient_overlay(imageData, options = {}) {
@@ -48,6 +51,7 @@ export function gradient_overlay(imageData, options = {}) {
     const offsetY = options.offsetY ?? 0.0;
     const reverse = options.reverse ?? false;
     const opacity = options.opacity ?? 1.0;
+    const blendMode = options.blendMode ?? 'normal';
 
     // Flatten stops array: [pos, r, g, b, pos, r, g, b, ...]
     const stopsFlat = new Float32Array(stops.length * 4);
@@ -70,7 +74,8 @@ export function gradient_overlay(imageData, options = {}) {
         offsetX,
         offsetY,
         reverse,
-        opacity
+        opacity,
+        blendMode
     );
 
     return {
@@ -3,10 +3,16 @@
 //! Fills the layer with a gradient while preserving the alpha channel.
 //! Supports 5 gradient styles: linear, radial, angle, reflected, and diamond.
 
-use ndarray::Array3;
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array3, ArrayView3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+use crate::layer_effects::color_overlay::{overlay_f32, overlay_u8};
+
 /// Gradient stop definition: position (0.0-1.0) and color (RGB).
 #[derive(Clone, Debug)]
 pub struct GradientStop {
@@ -62,24 +68,11 @@ pub fn interpolate_gradient(stops: &[GradientStop], t: f32) -> (f32, f32, f32) {
 
 /// Calculate gradient position t based on style.
 ///
-/// Returns t in range [0.0, 1.0] for the given pixel position.
-///
-/// Parameters:
-/// - `scale_x`, `scale_y`: Scale factors (1.0 = 100%). Separate X/Y for non-uniform scaling.
-/// - `offset_x`, `offset_y`: Center offset as fraction (-1.0 to 1.0). 0.0 = center.
-pub fn calculate_gradient_t(
-    x: usize,
-    y: usize,
-    width: usize,
-    height: usize,
-    style: &str,
-    angle: f32,
-    scale_x: f32,
-    scale_y: f32,
-    offset_x: f32,
-    offset_y: f32,
-    reverse: bool,
-) -> f32 {
+/// Returns t in range [0.0, 1.0] for the given pixel position. Only the
+/// geometry of `params` is used: style, angle, scale, offset and reverse.
+pub fn calculate_gradient_t(x: usize, y: usize, width: usize, height: usize, params: &GradientOverlayParams) -> f32 {
+    let GradientOverlayParams { ref style, angle, scale_x, scale_y, offset_x, offset_y, reverse, .. } = *params;
+
     // Center with offset applied
     let cx = width as f32 / 2.0 + offset_x * width as f32 / 2.0;
     let cy = height as f32 / 2.0 + offset_y * height as f32 / 2.0;
@@ -93,7 +86,7 @@ pub fn calculate_gradient_t(
     let half_w = width as f32 / 2.0;
     let half_h = height as f32 / 2.0;
 
-    let mut t = match style {
+    let mut t = match style.as_str() {
         "linear" => {
             // Linear gradient along angle with separate scaleX/Y
             let angle_rad = angle.to_radians();
@@ -178,12 +171,107 @@ pub fn calculate_gradient_t(
     t
 }
 
+/// Parameters for the gradient overlay effect.
+#[derive(Debug, Clone)]
+pub struct GradientOverlayParams {
+    /// Color stops, sorted by position. Empty = black to white.
+    pub stops: Vec<GradientStop>,
+    /// Gradient style: "linear", "radial", "angle", "reflected", "diamond"
+    pub style: String,
+    /// Angle in degrees (for linear/reflected styles)
+    pub angle: f32,
+    /// Horizontal scale factor (1.0 = 100%)
+    pub scale_x: f32,
+    /// Vertical scale factor (1.0 = 100%)
+    pub scale_y: f32,
+    /// Horizontal center offset (-1.0 to 1.0, 0.0 = center)
+    pub offset_x: f32,
+    /// Vertical center offset (-1.0 to 1.0, 0.0 = center)
+    pub offset_y: f32,
+    /// Whether to reverse the gradient direction
+    pub reverse: bool,
+    /// Effect opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+    pub blend_mode: String,
+}
+
+impl Default for GradientOverlayParams {
+    fn default() -> Self {
+        Self {
+            stops: Vec::new(),
+            style: "linear".to_string(),
+            angle: 90.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            reverse: false,
+            opacity: 1.0,
+            blend_mode: "normal".to_string(),
+        }
+    }
+}
+
+/// Render the gradient colors for a layer of the given size.
+///
+/// # Returns
+/// RGB buffer (height, width, 3), values 0.0-1.0
+pub fn gradient_fill(width: usize, height: usize, params: &GradientOverlayParams) -> Array3<f32> {
+    let mut stops = params.stops.clone();
+    stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
+    if stops.is_empty() {
+        stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
+        stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
+    }
+
+    let mut buf = Array3::<f32>::zeros((height, width, 3));
+    for y in 0..height {
+        for x in 0..width {
+            let t = calculate_gradient_t(x, y, width, height, params);
+            let (r, g, b) = interpolate_gradient(&stops, t);
+            buf[[y, x, 0]] = r;
+            buf[[y, x, 1]] = g;
+            buf[[y, x, 2]] = b;
+        }
+    }
+    buf
+}
+
+/// Parse gradient stops from a flat array [pos, r, g, b, pos, r, g, b, ...].
+///
+/// Colors are divided by `color_scale` (255.0 for 0-255 input, 1.0 for 0.0-1.0).
+/// A trailing incomplete stop is ignored.
+pub fn parse_stops(flat: &[f32], color_scale: f32) -> Vec<GradientStop> {
+    flat.chunks_exact(4)
+        .map(|c| GradientStop { position: c[0], r: c[1] / color_scale, g: c[2] / color_scale, b: c[3] / color_scale })
+        .collect()
+}
+
+/// Apply gradient overlay to an RGBA f32 image.
+pub fn gradient_overlay_f32(input: ArrayView3<f32>, params: &GradientOverlayParams) -> Array3<f32> {
+    let (height, width, _) = input.dim();
+    let gradient = gradient_fill(width, height, params);
+    overlay_f32(input, &params.blend_mode, |y, x| {
+        ([gradient[[y, x, 0]], gradient[[y, x, 1]], gradient[[y, x, 2]]], params.opacity)
+    })
+}
+
+/// Apply gradient overlay to an RGBA u8 image.
+pub fn gradient_overlay_u8(input: ArrayView3<u8>, params: &GradientOverlayParams) -> Array3<u8> {
+    let (height, width, _) = input.dim();
+    let gradient = gradient_fill(width, height, params);
+    overlay_u8(input, &params.blend_mode, |y, x| {
+        ([gradient[[y, x, 0]], gradient[[y, x, 1]], gradient[[y, x, 2]]], params.opacity)
+    })
+}
+
 /// Apply gradient overlay to RGBA u8 image.
 ///
 /// # Arguments
 /// * `image` - Source RGBA image
 /// * `stops` - Gradient color stops as flat array: [pos, r, g, b, pos, r, g, b, ...]
-///             where pos is 0.0-1.0 and r,g,b are 0-255
+///   where pos is 0.0-1.0 and r,g,b are 0-255
 /// * `style` - Gradient style: "linear", "radial", "angle", "reflected", "diamond"
 /// * `angle` - Angle in degrees (for linear/reflected styles)
 /// * `scale_x` - Horizontal scale factor (1.0 = 100%)
@@ -192,12 +280,13 @@ pub fn calculate_gradient_t(
 /// * `offset_y` - Vertical center offset (-1.0 to 1.0, 0.0 = center)
 /// * `reverse` - Whether to reverse the gradient direction
 /// * `opacity` - Effect opacity (0.0-1.0)
-/// * `blend_mode` - Blend mode: "normal", "multiply", "screen", "overlay"
+/// * `blend_mode` - Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(signature = (image, stops, style="linear", angle=90.0, scale_x=1.0, scale_y=1.0, offset_x=0.0, offset_y=0.0, reverse=false, opacity=1.0, blend_mode="normal"))]
 pub fn gradient_overlay_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     stops: Vec<f32>,
     style: &str,
     angle: f32,
@@ -209,131 +298,31 @@ pub fn gradient_overlay_rgba<'py>(
     opacity: f32,
     blend_mode: &str,
 ) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Parse gradient stops from flat array [pos, r, g, b, pos, r, g, b, ...]
-    let mut gradient_stops: Vec<GradientStop> = Vec::new();
-    for chunk in stops.chunks(4) {
-        if chunk.len() == 4 {
-            gradient_stops.push(GradientStop {
-                position: chunk[0],
-                r: chunk[1] / 255.0,
-                g: chunk[2] / 255.0,
-                b: chunk[3] / 255.0,
-            });
-        }
-    }
-
-    // Sort stops by position
-    gradient_stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Default gradient if no stops provided
-    if gradient_stops.is_empty() {
-        gradient_stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
-        gradient_stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
-    }
-
-    // Step 1: Generate gradient buffer
-    let mut gradient_buf = Array3::<f32>::zeros((height, width, 3));
-    for y in 0..height {
-        for x in 0..width {
-            let t = calculate_gradient_t(x, y, width, height, style, angle, scale_x, scale_y, offset_x, offset_y, reverse);
-            let (r, g, b) = interpolate_gradient(&gradient_stops, t);
-            gradient_buf[[y, x, 0]] = r;
-            gradient_buf[[y, x, 1]] = g;
-            gradient_buf[[y, x, 2]] = b;
-        }
-    }
-
-    // Step 2: Blend with source using optimized per-mode loops
-    let mut result = Array3::<u8>::zeros((height, width, 4));
-
-    match blend_mode {
-        "multiply" => {
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a == 0 { continue; }
-                    let orig_r = input[[y, x, 0]] as f32 / 255.0;
-                    let orig_g = input[[y, x, 1]] as f32 / 255.0;
-                    let orig_b = input[[y, x, 2]] as f32 / 255.0;
-                    let mr = orig_r * gradient_buf[[y, x, 0]];
-                    let mg = orig_g * gradient_buf[[y, x, 1]];
-                    let mb = orig_b * gradient_buf[[y, x, 2]];
-                    result[[y, x, 0]] = ((orig_r * (1.0 - opacity) + mr * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 1]] = ((orig_g * (1.0 - opacity) + mg * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 2]] = ((orig_b * (1.0 - opacity) + mb * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-        "screen" => {
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a == 0 { continue; }
-                    let orig_r = input[[y, x, 0]] as f32 / 255.0;
-                    let orig_g = input[[y, x, 1]] as f32 / 255.0;
-                    let orig_b = input[[y, x, 2]] as f32 / 255.0;
-                    let sr = 1.0 - (1.0 - orig_r) * (1.0 - gradient_buf[[y, x, 0]]);
-                    let sg = 1.0 - (1.0 - orig_g) * (1.0 - gradient_buf[[y, x, 1]]);
-                    let sb = 1.0 - (1.0 - orig_b) * (1.0 - gradient_buf[[y, x, 2]]);
-                    result[[y, x, 0]] = ((orig_r * (1.0 - opacity) + sr * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 1]] = ((orig_g * (1.0 - opacity) + sg * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 2]] = ((orig_b * (1.0 - opacity) + sb * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-        "overlay" => {
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a == 0 { continue; }
-                    let orig_r = input[[y, x, 0]] as f32 / 255.0;
-                    let orig_g = input[[y, x, 1]] as f32 / 255.0;
-                    let orig_b = input[[y, x, 2]] as f32 / 255.0;
-                    let or = if orig_r < 0.5 { 2.0 * orig_r * gradient_buf[[y, x, 0]] } else { 1.0 - 2.0 * (1.0 - orig_r) * (1.0 - gradient_buf[[y, x, 0]]) };
-                    let og = if orig_g < 0.5 { 2.0 * orig_g * gradient_buf[[y, x, 1]] } else { 1.0 - 2.0 * (1.0 - orig_g) * (1.0 - gradient_buf[[y, x, 1]]) };
-                    let ob = if orig_b < 0.5 { 2.0 * orig_b * gradient_buf[[y, x, 2]] } else { 1.0 - 2.0 * (1.0 - orig_b) * (1.0 - gradient_buf[[y, x, 2]]) };
-                    result[[y, x, 0]] = ((orig_r * (1.0 - opacity) + or * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 1]] = ((orig_g * (1.0 - opacity) + og * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 2]] = ((orig_b * (1.0 - opacity) + ob * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-        _ => {
-            // "normal" - simple alpha blend with gradient
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a == 0 { continue; }
-                    let orig_r = input[[y, x, 0]] as f32 / 255.0;
-                    let orig_g = input[[y, x, 1]] as f32 / 255.0;
-                    let orig_b = input[[y, x, 2]] as f32 / 255.0;
-                    result[[y, x, 0]] = ((orig_r * (1.0 - opacity) + gradient_buf[[y, x, 0]] * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 1]] = ((orig_g * (1.0 - opacity) + gradient_buf[[y, x, 1]] * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 2]] = ((orig_b * (1.0 - opacity) + gradient_buf[[y, x, 2]] * opacity) * 255.0).clamp(0.0, 255.0) as u8;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-    }
-
-    result.into_pyarray(py)
+    let params = GradientOverlayParams {
+        stops: parse_stops(&stops, 255.0),
+        style: style.to_string(),
+        angle,
+        scale_x,
+        scale_y,
+        offset_x,
+        offset_y,
+        reverse,
+        opacity,
+        blend_mode: blend_mode.to_string(),
+    };
+    gradient_overlay_u8(image.as_array(), &params).into_pyarray(py)
 }
 
 /// Apply gradient overlay to RGBA f32 image.
 ///
 /// Same as gradient_overlay_rgba but for f32 images (0.0-1.0 range).
 /// Stops format: [pos, r, g, b, pos, r, g, b, ...] where all values are 0.0-1.0
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(signature = (image, stops, style="linear", angle=90.0, scale_x=1.0, scale_y=1.0, offset_x=0.0, offset_y=0.0, reverse=false, opacity=1.0, blend_mode="normal"))]
 pub fn gradient_overlay_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     stops: Vec<f32>,
     style: &str,
     angle: f32,
@@ -345,115 +334,17 @@ pub fn gradient_overlay_rgba_f32<'py>(
     opacity: f32,
     blend_mode: &str,
 ) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Parse gradient stops (all values already 0.0-1.0 for f32)
-    let mut gradient_stops: Vec<GradientStop> = Vec::new();
-    for chunk in stops.chunks(4) {
-        if chunk.len() == 4 {
-            gradient_stops.push(GradientStop {
-                position: chunk[0],
-                r: chunk[1],
-                g: chunk[2],
-                b: chunk[3],
-            });
-        }
-    }
-
-    // Sort stops by position
-    gradient_stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Default gradient if no stops provided
-    if gradient_stops.is_empty() {
-        gradient_stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
-        gradient_stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
-    }
-
-    // Step 1: Generate gradient buffer
-    let mut gradient_buf = Array3::<f32>::zeros((height, width, 3));
-    for y in 0..height {
-        for x in 0..width {
-            let t = calculate_gradient_t(x, y, width, height, style, angle, scale_x, scale_y, offset_x, offset_y, reverse);
-            let (r, g, b) = interpolate_gradient(&gradient_stops, t);
-            gradient_buf[[y, x, 0]] = r;
-            gradient_buf[[y, x, 1]] = g;
-            gradient_buf[[y, x, 2]] = b;
-        }
-    }
-
-    // Step 2: Blend with source using optimized per-mode loops
-    let mut result = Array3::<f32>::zeros((height, width, 4));
-
-    match blend_mode {
-        "multiply" => {
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a <= 0.0 { continue; }
-                    let orig_r = input[[y, x, 0]];
-                    let orig_g = input[[y, x, 1]];
-                    let orig_b = input[[y, x, 2]];
-                    let mr = orig_r * gradient_buf[[y, x, 0]];
-                    let mg = orig_g * gradient_buf[[y, x, 1]];
-                    let mb = orig_b * gradient_buf[[y, x, 2]];
-                    result[[y, x, 0]] = orig_r * (1.0 - opacity) + mr * opacity;
-                    result[[y, x, 1]] = orig_g * (1.0 - opacity) + mg * opacity;
-                    result[[y, x, 2]] = orig_b * (1.0 - opacity) + mb * opacity;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-        "screen" => {
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a <= 0.0 { continue; }
-                    let orig_r = input[[y, x, 0]];
-                    let orig_g = input[[y, x, 1]];
-                    let orig_b = input[[y, x, 2]];
-                    let sr = 1.0 - (1.0 - orig_r) * (1.0 - gradient_buf[[y, x, 0]]);
-                    let sg = 1.0 - (1.0 - orig_g) * (1.0 - gradient_buf[[y, x, 1]]);
-                    let sb = 1.0 - (1.0 - orig_b) * (1.0 - gradient_buf[[y, x, 2]]);
-                    result[[y, x, 0]] = orig_r * (1.0 - opacity) + sr * opacity;
-                    result[[y, x, 1]] = orig_g * (1.0 - opacity) + sg * opacity;
-                    result[[y, x, 2]] = orig_b * (1.0 - opacity) + sb * opacity;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-        "overlay" => {
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a <= 0.0 { continue; }
-                    let orig_r = input[[y, x, 0]];
-                    let orig_g = input[[y, x, 1]];
-                    let orig_b = input[[y, x, 2]];
-                    let or = if orig_r < 0.5 { 2.0 * orig_r * gradient_buf[[y, x, 0]] } else { 1.0 - 2.0 * (1.0 - orig_r) * (1.0 - gradient_buf[[y, x, 0]]) };
-                    let og = if orig_g < 0.5 { 2.0 * orig_g * gradient_buf[[y, x, 1]] } else { 1.0 - 2.0 * (1.0 - orig_g) * (1.0 - gradient_buf[[y, x, 1]]) };
-                    let ob = if orig_b < 0.5 { 2.0 * orig_b * gradient_buf[[y, x, 2]] } else { 1.0 - 2.0 * (1.0 - orig_b) * (1.0 - gradient_buf[[y, x, 2]]) };
-                    result[[y, x, 0]] = orig_r * (1.0 - opacity) + or * opacity;
-                    result[[y, x, 1]] = orig_g * (1.0 - opacity) + og * opacity;
-                    result[[y, x, 2]] = orig_b * (1.0 - opacity) + ob * opacity;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-        _ => {
-            // "normal" - simple alpha blend with gradient
-            for y in 0..height {
-                for x in 0..width {
-                    let orig_a = input[[y, x, 3]];
-                    if orig_a <= 0.0 { continue; }
-                    result[[y, x, 0]] = input[[y, x, 0]] * (1.0 - opacity) + gradient_buf[[y, x, 0]] * opacity;
-                    result[[y, x, 1]] = input[[y, x, 1]] * (1.0 - opacity) + gradient_buf[[y, x, 1]] * opacity;
-                    result[[y, x, 2]] = input[[y, x, 2]] * (1.0 - opacity) + gradient_buf[[y, x, 2]] * opacity;
-                    result[[y, x, 3]] = orig_a;
-                }
-            }
-        }
-    }
-
-    result.into_pyarray(py)
+    let params = GradientOverlayParams {
+        stops: parse_stops(&stops, 1.0),
+        style: style.to_string(),
+        angle,
+        scale_x,
+        scale_y,
+        offset_x,
+        offset_y,
+        reverse,
+        opacity,
+        blend_mode: blend_mode.to_string(),
+    };
+    gradient_overlay_f32(image.as_array(), &params).into_pyarray(py)
 }
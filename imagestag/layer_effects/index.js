@@ -55,6 +55,8 @@ export function isInitialized() {
  * @param {number} [options.blur_radius=10]
  * @param {number[]} [options.color=[0,0,0]]
  * @param {number} [options.opacity=0.75]
+ * @param {boolean} [options.knockout=false]
+ * @param {string} [options.quality='exact'] - 'fast' for interactive previews
  * @returns {Object} Expanded result with offset_x, offset_y
  */
 function drop_shadow(imageData, options = {}) {
@@ -67,10 +69,10 @@ function drop_shadow(imageData, options = {}) {
 
     const result = wasm.drop_shadow_rgba_wasm(
         toU8(data), width, height,
-        ox, oy, blur, c[0], c[1], c[2], opacity
+        ox, oy, blur, c[0], c[1], c[2], opacity, options.knockout ?? false, options.quality ?? 'exact'
     );
 
-    const expand = Math.ceil(blur * 3) + Math.ceil(Math.max(Math.abs(ox), Math.abs(oy))) + 2;
+    const expand = wasm.drop_shadow_expansion_wasm(ox, oy, blur);
     return {
         data: new Uint8ClampedArray(result.buffer),
         width: width + expand * 2,
@@ -95,11 +97,87 @@ function inner_shadow(imageData, options = {}) {
 
     const result = wasm.inner_shadow_rgba_wasm(
         toU8(data), width, height,
-        ox, oy, blur, choke, c[0], c[1], c[2], opacity
+        ox, oy, blur, choke, c[0], c[1], c[2], opacity, options.quality ?? 'exact'
     );
     return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4, offset_x: 0, offset_y: 0 };
 }
 
+/**
+ * Cast Shadow — perspective ground shadow behind the object.
+ * @param {Object} imageData
+ * @param {Object} options
+ * @param {number} [options.angle=30]
+ * @param {number} [options.length=0.6]
+ * @param {number} [options.perspective_squash=0.6]
+ * @param {number} [options.blur_near=1]
+ * @param {number} [options.blur_far=8]
+ * @param {number[]} [options.color=[0,0,0]]
+ * @param {number} [options.opacity=0.6]
+ * @param {string} [options.quality='exact']
+ * @returns {Object} Expanded result with offset_x, offset_y
+ */
+function cast_shadow(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const c = options.color ?? [0, 0, 0];
+    const opts = Object.assign(new wasm.CastShadowOptions(), {
+        angle: options.angle ?? 30,
+        length: options.length ?? 0.6,
+        perspective_squash: options.perspective_squash ?? 0.6,
+        blur_near: options.blur_near ?? 1,
+        blur_far: options.blur_far ?? 8,
+        color_r: c[0], color_g: c[1], color_b: c[2],
+        opacity: options.opacity ?? 0.6,
+        fast_blur: options.quality === 'fast',
+    });
+
+    const result = wasm.cast_shadow_rgba_wasm(toU8(data), width, height, opts);
+    const expand = wasm.cast_shadow_expansion_wasm(width, height, opts);
+    opts.free();
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: width + expand * 2,
+        height: height + expand * 2,
+        channels: 4,
+        offset_x: -expand,
+        offset_y: -expand,
+    };
+}
+
+/**
+ * Long Shadow — silhouette extruded away from the light.
+ * @param {Object} imageData
+ * @param {Object} options
+ * @param {number} [options.angle=135]
+ * @param {number} [options.length=50]
+ * @param {number[]} [options.color=[0,0,0]]
+ * @param {number} [options.fade=0]
+ * @returns {Object} Expanded result with offset_x, offset_y
+ */
+function long_shadow(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const length = options.length ?? 50;
+    const c = options.color ?? [0, 0, 0];
+    const opts = Object.assign(new wasm.LongShadowOptions(), {
+        angle: options.angle ?? 135,
+        length,
+        color_r: c[0], color_g: c[1], color_b: c[2],
+        fade: options.fade ?? 0,
+    });
+
+    const result = wasm.long_shadow_rgba_wasm(toU8(data), width, height, opts);
+    opts.free();
+
+    const expand = Math.ceil(Math.max(length, 0)) + 1;
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: width + expand * 2,
+        height: height + expand * 2,
+        channels: 4,
+        offset_x: -expand,
+        offset_y: -expand,
+    };
+}
+
 /**
  * Outer Glow — glow radiating outward.
  */
@@ -110,12 +188,18 @@ function outer_glow(imageData, options = {}) {
     const opacity = options.opacity ?? 0.75;
     const spread = options.spread ?? 0;
 
-    const result = wasm.outer_glow_rgba_wasm(
-        toU8(data), width, height,
-        radius, c[0], c[1], c[2], opacity, spread
-    );
-
-    const expand = Math.ceil(radius * 3) + spread + 2;
+    // Gradient stops [[pos, r, g, b], ...] replace the solid color
+    const result = options.gradient
+        ? wasm.outer_glow_gradient_rgba_wasm(
+            toU8(data), width, height,
+            new Float32Array(options.gradient.flat()), radius, opacity, spread
+        )
+        : wasm.outer_glow_rgba_wasm(
+            toU8(data), width, height,
+            radius, c[0], c[1], c[2], opacity, spread, options.quality ?? 'exact'
+        );
+
+    const expand = wasm.outer_glow_expansion_wasm(radius, spread);
     return {
         data: new Uint8ClampedArray(result.buffer),
         width: width + expand * 2,
@@ -138,7 +222,7 @@ function inner_glow(imageData, options = {}) {
 
     const result = wasm.inner_glow_rgba_wasm(
         toU8(data), width, height,
-        radius, c[0], c[1], c[2], opacity, choke
+        radius, c[0], c[1], c[2], opacity, choke, options.quality ?? 'exact'
     );
     return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4, offset_x: 0, offset_y: 0 };
 }
@@ -173,16 +257,20 @@ function bevel_emboss(imageData, options = {}) {
 function satin(imageData, options = {}) {
     const { data, width, height } = imageData;
     const c = options.color ?? [0, 0, 0];
-    const opacity = options.opacity ?? 0.5;
-    const angle = options.angle ?? 19;
-    const distance = options.distance ?? 11;
-    const size = options.size ?? 14;
-    const invert = options.invert ?? false;
-
-    const result = wasm.satin_rgba_wasm(
-        toU8(data), width, height,
-        c[0], c[1], c[2], opacity, angle, distance, size, invert
-    );
+    const opts = Object.assign(new wasm.SatinOptions(), {
+        color_r: c[0], color_g: c[1], color_b: c[2],
+        opacity: options.opacity ?? 0.5,
+        angle: options.angle ?? 19,
+        distance: options.distance ?? 11,
+        size: options.size ?? 14,
+        invert: options.invert ?? false,
+        contour: new Float32Array((options.contour ?? []).flat()),
+        blend_mode: options.blendMode ?? 'normal',
+        fast_blur: options.quality === 'fast',
+    });
+
+    const result = wasm.satin_rgba_options_wasm(toU8(data), width, height, opts);
+    opts.free();
     return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4, offset_x: 0, offset_y: 0 };
 }
 
@@ -192,12 +280,14 @@ function satin(imageData, options = {}) {
 function color_overlay(imageData, options = {}) {
     const { data, width, height } = imageData;
     const c = options.color ?? [255, 0, 0];
-    const opacity = options.opacity ?? 1.0;
-
-    const result = wasm.color_overlay_rgba_wasm(
-        toU8(data), width, height,
-        c[0], c[1], c[2], opacity
-    );
+    const opts = Object.assign(new wasm.ColorOverlayOptions(), {
+        color_r: c[0], color_g: c[1], color_b: c[2],
+        opacity: options.opacity ?? 1.0,
+        blend_mode: options.blendMode ?? 'normal',
+    });
+
+    const result = wasm.color_overlay_rgba_options_wasm(toU8(data), width, height, opts);
+    opts.free();
     return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4, offset_x: 0, offset_y: 0 };
 }
 
@@ -215,6 +305,7 @@ function gradient_overlay(imageData, options = {}) {
     const scale = options.scale ?? 1.0;
     const reverse = options.reverse ?? false;
     const opacity = options.opacity ?? 1.0;
+    const blendMode = options.blendMode ?? 'normal';
 
     // Flatten stops: [position, r, g, b, ...]
     const flat = new Float32Array(stops.length * 4);
@@ -227,7 +318,7 @@ function gradient_overlay(imageData, options = {}) {
 
     const result = wasm.gradient_overlay_rgba_wasm(
         toU8(data), width, height,
-        flat, style, angle, scale, reverse, opacity
+        flat, style, angle, scale, scale, 0, 0, reverse, opacity, blendMode
     );
     return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4, offset_x: 0, offset_y: 0 };
 }
@@ -243,12 +334,13 @@ function pattern_overlay(imageData, options = {}) {
     const ox = options.offset_x ?? 0;
     const oy = options.offset_y ?? 0;
     const opacity = options.opacity ?? 1.0;
+    const blendMode = options.blendMode ?? 'normal';
 
     const result = wasm.pattern_overlay_rgba_wasm(
         toU8(data), width, height,
         toU8(pattern.data),
         pattern.width, pattern.height,
-        scale, ox, oy, opacity
+        scale, ox, oy, opacity, blendMode
     );
     return { data: new Uint8ClampedArray(result.buffer), width, height, channels: 4, offset_x: 0, offset_y: 0 };
 }
@@ -269,9 +361,7 @@ function stroke(imageData, options = {}) {
     );
 
     // Expansion depends on position
-    let expand = 0;
-    if (position === 'outside') expand = Math.ceil(strokeWidth);
-    else if (position === 'center') expand = Math.ceil(strokeWidth / 2);
+    const expand = wasm.stroke_expansion_wasm(strokeWidth, position);
 
     return {
         data: new Uint8ClampedArray(result.buffer),
@@ -290,6 +380,8 @@ function stroke(imageData, options = {}) {
 export const effects = {
     drop_shadow,
     inner_shadow,
+    cast_shadow,
+    long_shadow,
     outer_glow,
     inner_glow,
     bevel_emboss,
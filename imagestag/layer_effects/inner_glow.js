@@ -20,6 +20,7 @@ export { initWasm };
  * @param {Array<number>} [options.color=[255,255,0]] - Glow color [r, g, b] (0-255)
  * @param {number} [options.opacity=0.75] - Glow opacity (0.0-1.0)
  * @param {number} [options.choke=0] - Choke amount (0.0-1.0)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
  * @returns {Object} - Result with same dimensions {data, width, height, channels}
  */
 export function inner_glow(imageData, options = {}) {
@@ -44,7 +45,8 @@ export function inner_glow(imageData, options = {}) {
         color[1],
         color[2],
         opacity,
-        choke
+        choke,
+        options.quality ?? 'exact'
     );
 
     return {
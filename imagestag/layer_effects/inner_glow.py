@@ -46,6 +46,8 @@ class InnerGlow(LayerEffect):
     color_opacity: float = Field(default=0.75, alias='colorOpacity', ge=0.0, le=1.0)
     choke: float = Field(default=0.0, ge=0.0, le=1.0)
     source: str = Field(default="edge")  # 'edge' or 'center'
+    # Blur accuracy: 'exact', or 'fast' for interactive previews
+    quality: str = Field(default='exact')
 
     # Internal: parsed RGB tuple (not serialized)
     _color_rgb: Optional[Tuple[int, int, int]] = None
@@ -157,6 +159,7 @@ class InnerGlow(LayerEffect):
                 color,
                 float(self.color_opacity),
                 float(self.choke),
+                quality=self.quality,
             )
             result = result.astype(np.float32) / 255.0
         else:
@@ -166,6 +169,7 @@ class InnerGlow(LayerEffect):
                 color,
                 float(self.color_opacity),
                 float(self.choke),
+                quality=self.quality,
             )
 
         return EffectResult(
@@ -215,6 +219,7 @@ class InnerGlow(LayerEffect):
                 color,
                 float(self.color_opacity),
                 float(self.choke),
+                quality=self.quality,
             )
             result = result.astype(np.float32) / 255.0
         else:
@@ -224,6 +229,7 @@ class InnerGlow(LayerEffect):
                 color,
                 float(self.color_opacity),
                 float(self.choke),
+                quality=self.quality,
             )
 
         return EffectResult(
@@ -6,11 +6,116 @@
 //! - inner_glow.py (Python wrapper)
 //! - inner_glow.js (JavaScript wrapper)
 
-use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::filters::core::{blur_alpha_f32, erode_alpha};
+#[cfg(feature = "python")]
+use crate::filters::blur::parse_blur_quality;
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{blur_alpha_quality_f32, erode_alpha, image_f32_to_u8, image_u8_to_f32};
+use crate::filters::pool::{self, Pooled};
+
+
+/// Parameters for the inner glow effect.
+#[derive(Debug, Clone)]
+pub struct InnerGlowParams {
+    /// Glow blur radius
+    pub radius: f32,
+    /// Glow color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Glow opacity (0.0-1.0)
+    pub opacity: f32,
+    /// How much to contract the glow (0.0-1.0)
+    pub choke: f32,
+    /// Blur accuracy; `Fast` for interactive previews
+    pub quality: BlurQuality,
+}
+
+impl Default for InnerGlowParams {
+    fn default() -> Self {
+        Self {
+            radius: 10.0,
+            color: [1.0, 1.0, 0.0],
+            opacity: 0.75,
+            choke: 0.0,
+            quality: BlurQuality::Exact,
+        }
+    }
+}
+
+/// Compute the inner glow coverage from a layer alpha mask.
+///
+/// The glow is strongest at the edges and fades toward the center. It is
+/// composited with screen blending.
+///
+/// # Returns
+/// Glow alpha (0.0-1.0) with the opacity applied, clipped to `alpha`
+pub fn inner_glow_mask(alpha: &Array2<f32>, params: &InnerGlowParams) -> Array2<f32> {
+    let choke_radius = params.radius * params.choke;
+    let sigma = params.radius * (1.0 - params.choke * 0.5);
+    let blurred = Pooled::new(if choke_radius > 0.0 {
+        blur_alpha_quality_f32(&erode_alpha(alpha, choke_radius), sigma, params.quality)
+    } else {
+        blur_alpha_quality_f32(alpha, sigma, params.quality)
+    });
+
+    let mut mask = alpha - &*blurred;
+    mask.zip_mut_with(alpha, |m, &a| *m = m.max(0.0) * a * params.opacity);
+    mask
+}
+
+/// Render the inner glow onto an RGBA f32 layer (screen blending).
+///
+/// # Returns
+/// RGBA image with the glow inside the layer, same size as `image`
+pub fn render_inner_glow_f32(image: ArrayView3<f32>, params: &InnerGlowParams) -> Array3<f32> {
+    let alpha = Pooled::new(pool::copy2(image.index_axis(Axis(2), 3)));
+    let glow = inner_glow_mask(&alpha, params);
+
+    let mut result = image.to_owned();
+    for ((y, x), &glow_a) in glow.indexed_iter() {
+        if alpha[[y, x]] <= 0.0 || glow_a <= 0.0 {
+            continue;
+        }
+        for (c, &color) in params.color.iter().enumerate() {
+            // Screen blend: 1 - (1-a)(1-b)
+            result[[y, x, c]] = 1.0 - (1.0 - result[[y, x, c]]) * (1.0 - color * glow_a);
+        }
+    }
+    result
+}
+
+/// Render the inner glow onto an RGBA u8 layer.
+pub fn render_inner_glow_u8(image: ArrayView3<u8>, params: &InnerGlowParams) -> Array3<u8> {
+    image_f32_to_u8(&render_inner_glow_f32(image_u8_to_f32(image).view(), params))
+}
+
+/// Render only the inner glow of an RGBA f32 layer, clipped to its alpha.
+pub fn render_inner_glow_only_f32(image: ArrayView3<f32>, params: &InnerGlowParams) -> Array3<f32> {
+    let alpha = Pooled::new(pool::copy2(image.index_axis(Axis(2), 3)));
+    let glow = inner_glow_mask(&alpha, params);
+
+    let mut result = Array3::<f32>::zeros(image.dim());
+    let [r, g, b] = params.color;
+    for ((y, x), &glow_a) in glow.indexed_iter() {
+        if alpha[[y, x]] > 0.0 && glow_a > 0.0 {
+            for (c, v) in [r, g, b, glow_a].into_iter().enumerate() {
+                result[[y, x, c]] = v;
+            }
+        }
+    }
+    result
+}
+
+/// Render only the inner glow of an RGBA u8 layer.
+pub fn render_inner_glow_only_u8(image: ArrayView3<u8>, params: &InnerGlowParams) -> Array3<u8> {
+    image_f32_to_u8(&render_inner_glow_only_f32(image_u8_to_f32(image).view(), params))
+}
 
 /// Apply inner glow effect to RGBA image.
 ///
@@ -22,172 +127,41 @@ use crate::filters::core::{blur_alpha_f32, erode_alpha};
 /// * `color` - Glow color (R, G, B)
 /// * `opacity` - Glow opacity (0.0-1.0)
 /// * `choke` - How much to contract the glow (0.0-1.0)
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, choke=0.0))]
+#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, choke=0.0, quality="exact"))]
 pub fn inner_glow_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     radius: f32,
     color: (u8, u8, u8),
     opacity: f32,
     choke: f32,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
-
-    // Create inner glow mask: erode alpha, blur, subtract from original
-    let choke_radius = radius * choke;
-    let eroded = if choke_radius > 0.0 {
-        erode_alpha(&alpha, choke_radius)
-    } else {
-        alpha.clone()
-    };
-
-    // Blur the eroded alpha
-    let blurred = blur_alpha_f32(&eroded, radius * (1.0 - choke * 0.5));
-
-    // Inner glow = original alpha - blurred (inverted from edge)
-    let mut glow_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            // Glow is strongest at edges, fading toward center
-            let edge_dist = alpha[[y, x]] - blurred[[y, x]];
-            glow_mask[[y, x]] = edge_dist.max(0.0) * alpha[[y, x]];
-        }
-    }
-
-    // Compose result
-    let mut result = Array3::<f32>::zeros((height, width, 4));
-
-    // Copy original
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                result[[y, x, c]] = input_f32[[y, x, c]];
-            }
-        }
-    }
-
-    let glow_r = color.0 as f32 / 255.0;
-    let glow_g = color.1 as f32 / 255.0;
-    let glow_b = color.2 as f32 / 255.0;
-
-    // Composite glow using "screen" blending (additive-like)
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input_f32[[y, x, 3]];
-            if orig_a <= 0.0 {
-                continue;
-            }
-
-            let glow_a = glow_mask[[y, x]] * opacity;
-            if glow_a > 0.0 {
-                // Screen blend: 1 - (1-a)(1-b)
-                result[[y, x, 0]] = 1.0 - (1.0 - result[[y, x, 0]]) * (1.0 - glow_r * glow_a);
-                result[[y, x, 1]] = 1.0 - (1.0 - result[[y, x, 1]]) * (1.0 - glow_g * glow_a);
-                result[[y, x, 2]] = 1.0 - (1.0 - result[[y, x, 2]]) * (1.0 - glow_b * glow_a);
-            }
-        }
-    }
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = InnerGlowParams { radius, color, opacity, choke, quality };
+    Ok(render_inner_glow_u8(image.as_array(), &params).into_pyarray(py))
 }
 
 /// Apply inner glow effect to f32 RGBA image.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, choke=0.0))]
+#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, choke=0.0, quality="exact"))]
 pub fn inner_glow_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     radius: f32,
     color: (f32, f32, f32),
     opacity: f32,
     choke: f32,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Clone input
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
-
-    let choke_radius = radius * choke;
-    let eroded = if choke_radius > 0.0 {
-        erode_alpha(&alpha, choke_radius)
-    } else {
-        alpha.clone()
-    };
-
-    let blurred = blur_alpha_f32(&eroded, radius * (1.0 - choke * 0.5));
-
-    let mut glow_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let edge_dist = alpha[[y, x]] - blurred[[y, x]];
-            glow_mask[[y, x]] = edge_dist.max(0.0) * alpha[[y, x]];
-        }
-    }
-
-    let mut result = Array3::<f32>::zeros((height, width, 4));
-
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                result[[y, x, c]] = input_f32[[y, x, c]];
-            }
-        }
-    }
-
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input_f32[[y, x, 3]];
-            if orig_a <= 0.0 {
-                continue;
-            }
-
-            let glow_a = glow_mask[[y, x]] * opacity;
-            if glow_a > 0.0 {
-                result[[y, x, 0]] = 1.0 - (1.0 - result[[y, x, 0]]) * (1.0 - color.0 * glow_a);
-                result[[y, x, 1]] = 1.0 - (1.0 - result[[y, x, 1]]) * (1.0 - color.1 * glow_a);
-                result[[y, x, 2]] = 1.0 - (1.0 - result[[y, x, 2]]) * (1.0 - color.2 * glow_a);
-            }
-        }
-    }
-
-    result.into_pyarray(py)
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = InnerGlowParams { radius, color: [color.0, color.1, color.2], opacity, choke, quality };
+    Ok(render_inner_glow_f32(image.as_array(), &params).into_pyarray(py))
 }
 
 /// Get inner glow-only layer (no original content composited).
@@ -201,141 +175,38 @@ pub fn inner_glow_rgba_f32<'py>(
 ///
 /// # Returns
 /// RGBA image with ONLY the inner glow (original NOT composited)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, choke=0.0))]
+#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, choke=0.0, quality="exact"))]
 pub fn inner_glow_only_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     radius: f32,
     color: (u8, u8, u8),
     opacity: f32,
     choke: f32,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
-
-    // Create inner glow mask
-    let choke_radius = radius * choke;
-    let eroded = if choke_radius > 0.0 {
-        erode_alpha(&alpha, choke_radius)
-    } else {
-        alpha.clone()
-    };
-
-    let blurred = blur_alpha_f32(&eroded, radius * (1.0 - choke * 0.5));
-
-    let mut glow_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let edge_dist = alpha[[y, x]] - blurred[[y, x]];
-            glow_mask[[y, x]] = edge_dist.max(0.0) * alpha[[y, x]];
-        }
-    }
-
-    // Create glow-only result (no original composited)
-    let mut result = Array3::<f32>::zeros((height, width, 4));
-
-    let glow_r = color.0 as f32 / 255.0;
-    let glow_g = color.1 as f32 / 255.0;
-    let glow_b = color.2 as f32 / 255.0;
-
-    for y in 0..height {
-        for x in 0..width {
-            let glow_a = glow_mask[[y, x]] * opacity;
-            // Only show glow where original has alpha (inside the shape)
-            if alpha[[y, x]] > 0.0 && glow_a > 0.0 {
-                result[[y, x, 0]] = glow_r;
-                result[[y, x, 1]] = glow_g;
-                result[[y, x, 2]] = glow_b;
-                result[[y, x, 3]] = glow_a;
-            }
-        }
-    }
-
-    // NOTE: No compositing step - return glow layer only
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = InnerGlowParams { radius, color, opacity, choke, quality };
+    Ok(render_inner_glow_only_u8(image.as_array(), &params).into_pyarray(py))
 }
 
 /// Get inner glow-only layer for f32 RGBA image.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, choke=0.0))]
+#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, choke=0.0, quality="exact"))]
 pub fn inner_glow_only_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     radius: f32,
     color: (f32, f32, f32),
     opacity: f32,
     choke: f32,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
-
-    let choke_radius = radius * choke;
-    let eroded = if choke_radius > 0.0 {
-        erode_alpha(&alpha, choke_radius)
-    } else {
-        alpha.clone()
-    };
-
-    let blurred = blur_alpha_f32(&eroded, radius * (1.0 - choke * 0.5));
-
-    let mut glow_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let edge_dist = alpha[[y, x]] - blurred[[y, x]];
-            glow_mask[[y, x]] = edge_dist.max(0.0) * alpha[[y, x]];
-        }
-    }
-
-    let mut result = Array3::<f32>::zeros((height, width, 4));
-
-    for y in 0..height {
-        for x in 0..width {
-            let glow_a = glow_mask[[y, x]] * opacity;
-            if alpha[[y, x]] > 0.0 && glow_a > 0.0 {
-                result[[y, x, 0]] = color.0;
-                result[[y, x, 1]] = color.1;
-                result[[y, x, 2]] = color.2;
-                result[[y, x, 3]] = glow_a;
-            }
-        }
-    }
-
-    result.into_pyarray(py)
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = InnerGlowParams { radius, color: [color.0, color.1, color.2], opacity, choke, quality };
+    Ok(render_inner_glow_only_f32(image.as_array(), &params).into_pyarray(py))
 }
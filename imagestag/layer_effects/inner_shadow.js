@@ -22,6 +22,7 @@ export { initWasm };
  * @param {number} [options.choke=0] - Choke amount (0.0-1.0)
  * @param {Array<number>} [options.color=[0,0,0]] - Shadow color [r, g, b] (0-255)
  * @param {number} [options.opacity=0.75] - Shadow opacity (0.0-1.0)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
  * @returns {Object} - Result with same dimensions {data, width, height, channels}
  */
 export function inner_shadow(imageData, options = {}) {
@@ -50,7 +51,8 @@ export function inner_shadow(imageData, options = {}) {
         color[0],
         color[1],
         color[2],
-        opacity
+        opacity,
+        options.quality ?? 'exact'
     );
 
     return {
@@ -48,6 +48,8 @@ class InnerShadow(LayerEffect):
     choke: float = Field(default=0.0, ge=0.0, le=1.0)
     color: str = Field(default='#000000')  # Hex string for JS compatibility
     color_opacity: float = Field(default=0.75, alias='colorOpacity', ge=0.0, le=1.0)
+    # Blur accuracy: 'exact', or 'fast' for interactive previews
+    quality: str = Field(default='exact')
 
     # Internal: parsed RGB tuple (not serialized)
     _color_rgb: Optional[Tuple[int, int, int]] = None
@@ -155,6 +157,7 @@ class InnerShadow(LayerEffect):
                 float(self.choke),
                 color_f32,
                 float(self.color_opacity),
+                quality=self.quality,
             )
         else:
             result = imagestag_rust.inner_shadow_rgba(
@@ -165,6 +168,7 @@ class InnerShadow(LayerEffect):
                 float(self.choke),
                 color,
                 float(self.color_opacity),
+                quality=self.quality,
             )
 
         return EffectResult(
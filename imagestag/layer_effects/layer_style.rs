@@ -0,0 +1,689 @@
+//! Combined layer style renderer.
+//!
+//! Renders all effects of a layer together, the way Photoshop stacks them.
+//! Bottom to top:
+//!
+//! 1. Drop shadow
+//! 2. Outer glow
+//! 3. Outside part of the bevel (outer bevel, emboss, pillow/stroke emboss)
+//! 4. Layer content, followed by the interior effects pattern overlay,
+//!    gradient overlay, color overlay, satin and inner glow
+//! 5. Inner shadow
+//! 6. Stroke
+//! 7. Inside part of the bevel
+//!
+//! Layer-level options:
+//! - **Fill opacity** fades the layer content but not its effects
+//! - **Blend interior effects as group** makes the fill opacity also fade the
+//!   interior effects (overlays, satin, inner glow)
+//! - **Knockout** punches the layer's shape through the effects behind it
+//!   (and everything below it) down to the group backdrop (shallow) or the
+//!   background layer (deep). The amount follows the fill opacity: 0% fill
+//!   knocks out completely.
+//! - **Layer mask hides effects**: when off, the mask reshapes the layer
+//!   before the effects are computed (effects follow the masked edge); when
+//!   on, effects are computed from the unmasked layer and the mask then hides
+//!   them together with the content
+//! - **Scale** multiplies every pixel-based effect parameter, so a style
+//!   authored at 72 dpi renders the same on a 2x or 3x export
+//! - **Canvas** picks the output canvas: grown to fit every effect, clipped
+//!   to the layer, or an explicit size with the layer anchored in it. The
+//!   canvas policies of the individual effect parameters are ignored.
+
+use ndarray::{Array2, Array3};
+
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{blend_over_f32, expand_canvas_f32, extract_alpha_f32, CanvasPolicy};
+use crate::layer_effects::bevel_emboss::{bevel_emboss_shading, BevelEmbossParams};
+use crate::layer_effects::color_overlay::{blend_channel, blend_color, ColorOverlayParams};
+use crate::layer_effects::drop_shadow::{drop_shadow_mask, DropShadowParams};
+use crate::layer_effects::gradient_overlay::{gradient_fill, GradientOverlayParams};
+use crate::layer_effects::inner_glow::{inner_glow_mask, InnerGlowParams};
+use crate::layer_effects::inner_shadow::{inner_shadow_mask, InnerShadowParams};
+use crate::layer_effects::outer_glow::{outer_glow_colors, outer_glow_mask, OuterGlowParams};
+use crate::layer_effects::pattern_overlay::{pattern_fill, PatternOverlayParams};
+use crate::layer_effects::satin::{satin_mask, SatinParams};
+use crate::layer_effects::stroke::{stroke_mask, StrokeParams};
+
+/// Knockout mode of a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Knockout {
+    /// No knockout
+    #[default]
+    None,
+    /// Knock out to the backdrop of the enclosing group
+    Shallow,
+    /// Knock out to the background layer
+    Deep,
+}
+
+impl Knockout {
+    /// Parse a knockout name ("none", "shallow", "deep"). Unknown names map to none.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "shallow" => Knockout::Shallow,
+            "deep" => Knockout::Deep,
+            _ => Knockout::None,
+        }
+    }
+}
+
+/// All effects of a layer plus the layer-level blending options.
+///
+/// Effects set to `None` are disabled.
+#[derive(Debug, Clone)]
+pub struct LayerStyle {
+    pub drop_shadow: Option<DropShadowParams>,
+    pub inner_shadow: Option<InnerShadowParams>,
+    pub outer_glow: Option<OuterGlowParams>,
+    pub inner_glow: Option<InnerGlowParams>,
+    pub bevel_emboss: Option<BevelEmbossParams>,
+    pub satin: Option<SatinParams>,
+    pub color_overlay: Option<ColorOverlayParams>,
+    pub gradient_overlay: Option<GradientOverlayParams>,
+    pub pattern_overlay: Option<PatternOverlayParams>,
+    pub stroke: Option<StrokeParams>,
+    /// Layer opacity (0.0-1.0), fades content and effects
+    pub opacity: f32,
+    /// Fill opacity (0.0-1.0), fades the content only
+    pub fill_opacity: f32,
+    pub knockout: Knockout,
+    /// Fade interior effects together with the content by the fill opacity
+    pub blend_interior_effects: bool,
+    /// Apply the layer mask after the effects instead of before
+    pub layer_mask_hides_effects: bool,
+    /// Resolution factor for sizes, distances and blur radii
+    /// (2.0 = render a style defined at 72 dpi for a 144 dpi export)
+    pub scale: f32,
+    /// Output canvas of [`apply_layer_style`]
+    pub canvas: CanvasPolicy,
+}
+
+impl Default for LayerStyle {
+    fn default() -> Self {
+        Self {
+            drop_shadow: None,
+            inner_shadow: None,
+            outer_glow: None,
+            inner_glow: None,
+            bevel_emboss: None,
+            satin: None,
+            color_overlay: None,
+            gradient_overlay: None,
+            pattern_overlay: None,
+            stroke: None,
+            opacity: 1.0,
+            fill_opacity: 1.0,
+            knockout: Knockout::None,
+            blend_interior_effects: false,
+            layer_mask_hides_effects: false,
+            scale: 1.0,
+            canvas: CanvasPolicy::Grow,
+        }
+    }
+}
+
+impl LayerStyle {
+    /// Pixels added on each side of the canvas to fit every enabled effect.
+    pub fn expansion(&self) -> usize {
+        if self.scale != 1.0 {
+            return self.scaled().expansion();
+        }
+        [
+            self.drop_shadow.as_ref().map(|p| p.expansion()),
+            self.outer_glow.as_ref().map(|p| p.expansion()),
+            self.bevel_emboss.as_ref().map(|p| p.expansion()),
+            self.stroke.as_ref().map(|p| p.expansion()),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0)
+    }
+
+    /// Copy of the style with [`LayerStyle::scale`] baked into the pixel-based
+    /// parameters (and `scale` reset to 1.0). Angles, opacities, colors and
+    /// relative values (spread, choke, gradient scale) are unchanged.
+    pub fn scaled(&self) -> LayerStyle {
+        let f = self.scale;
+        let mut style = self.clone();
+        style.scale = 1.0;
+        if f == 1.0 {
+            return style;
+        }
+        if let Some(p) = &mut style.drop_shadow {
+            p.offset_x *= f;
+            p.offset_y *= f;
+            p.blur_radius *= f;
+        }
+        if let Some(p) = &mut style.inner_shadow {
+            p.offset_x *= f;
+            p.offset_y *= f;
+            p.blur_radius *= f;
+        }
+        if let Some(p) = &mut style.outer_glow {
+            p.radius *= f;
+        }
+        if let Some(p) = &mut style.inner_glow {
+            p.radius *= f;
+        }
+        if let Some(p) = &mut style.bevel_emboss {
+            p.size *= f;
+            p.soften *= f;
+            if let Some(texture) = &mut p.texture {
+                texture.scale *= f;
+                texture.depth *= f;
+            }
+        }
+        if let Some(p) = &mut style.satin {
+            p.distance *= f;
+            p.size *= f;
+        }
+        if let Some(p) = &mut style.pattern_overlay {
+            p.scale *= f;
+            p.offset_x = (p.offset_x as f32 * f).round() as i32;
+            p.offset_y = (p.offset_y as f32 * f).round() as i32;
+        }
+        if let Some(p) = &mut style.stroke {
+            p.width *= f;
+        }
+        style
+    }
+
+    /// Copy of the style with every blurred effect (shadows, glows, satin
+    /// and bevel) set to `quality`, e.g. [`BlurQuality::Fast`] while a
+    /// slider is dragged.
+    pub fn with_quality(&self, quality: BlurQuality) -> LayerStyle {
+        let mut style = self.clone();
+        if let Some(p) = &mut style.drop_shadow {
+            p.quality = quality;
+        }
+        if let Some(p) = &mut style.inner_shadow {
+            p.quality = quality;
+        }
+        if let Some(p) = &mut style.outer_glow {
+            p.quality = quality;
+        }
+        if let Some(p) = &mut style.inner_glow {
+            p.quality = quality;
+        }
+        if let Some(p) = &mut style.satin {
+            p.quality = quality;
+        }
+        if let Some(p) = &mut style.bevel_emboss {
+            p.quality = quality;
+        }
+        style
+    }
+}
+
+/// Optional per-call inputs of [`apply_layer_style`].
+///
+/// All images have the size of the (unexpanded) layer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LayerStyleInputs<'a> {
+    /// Layer mask (height, width), 0.0-1.0. The area outside the layer
+    /// bounds is treated as revealed.
+    pub mask: Option<&'a Array2<f32>>,
+    /// RGBA composite below the enclosing group (shallow knockout target)
+    pub group_backdrop: Option<&'a Array3<f32>>,
+    /// RGBA background layer (deep knockout target)
+    pub background: Option<&'a Array3<f32>>,
+}
+
+#[inline]
+fn pixel(image: &Array3<f32>, y: usize, x: usize) -> [f32; 4] {
+    [image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]], image[[y, x, 3]]]
+}
+
+#[inline]
+fn set_pixel(image: &mut Array3<f32>, y: usize, x: usize, px: [f32; 4]) {
+    for (c, v) in px.into_iter().enumerate() {
+        image[[y, x, c]] = v;
+    }
+}
+
+/// Composite a colored coverage mask over an image.
+///
+/// `color` receives the current pixel and returns the effect color, which
+/// lets overlays apply their blend mode against the layer content.
+fn composite_mask<F>(target: &mut Array3<f32>, mask: &Array2<f32>, color: F)
+where
+    F: Fn(usize, usize, &[f32; 4]) -> [f32; 3],
+{
+    let (height, width) = mask.dim();
+    for y in 0..height {
+        for x in 0..width {
+            let a = mask[[y, x]];
+            if a <= 0.0 {
+                continue;
+            }
+            let mut px = pixel(target, y, x);
+            let c = color(y, x, &px);
+            blend_over_f32(&mut px, c[0], c[1], c[2], a.min(1.0));
+            set_pixel(target, y, x, px);
+        }
+    }
+}
+
+/// Place a layer-sized image into the expanded canvas (transparent elsewhere).
+fn place(image: &Array3<f32>, height: usize, width: usize, offset: usize) -> Array3<f32> {
+    let (src_h, src_w, channels) = image.dim();
+    let mut out = Array3::<f32>::zeros((height, width, channels));
+    for y in 0..src_h.min(height - offset) {
+        for x in 0..src_w.min(width - offset) {
+            for c in 0..channels {
+                out[[y + offset, x + offset, c]] = image[[y, x, c]];
+            }
+        }
+    }
+    out
+}
+
+/// Render a layer with its complete layer style.
+///
+/// # Arguments
+/// * `input` - Layer RGBA image (height, width, 4), values 0.0-1.0
+/// * `style` - Effects and blending options
+/// * `inputs` - Optional layer mask and knockout backdrops
+///
+/// # Returns
+/// Styled RGBA layer on the canvas chosen by [`LayerStyle::canvas`]; by
+/// default expanded by [`LayerStyle::expansion`] on each side.
+/// With knockout enabled the knocked-out area carries the backdrop pixels,
+/// so the result replaces whatever lies below it when composited.
+pub fn apply_layer_style(input: &Array3<f32>, style: &LayerStyle, inputs: &LayerStyleInputs) -> Array3<f32> {
+    let scaled;
+    let style = if style.scale != 1.0 {
+        scaled = style.scaled();
+        &scaled
+    } else {
+        style
+    };
+    let expand = style.expansion();
+    let layer = if expand > 0 {
+        expand_canvas_f32(input, expand)
+    } else {
+        input.clone()
+    };
+    let (height, width, _) = layer.dim();
+    let (src_h, src_w, _) = input.dim();
+
+    // Layer mask on the expanded canvas, revealed outside the layer bounds
+    let mask = inputs.mask.map(|m| {
+        Array2::from_shape_fn((height, width), |(y, x)| {
+            if y >= expand && x >= expand && y - expand < src_h && x - expand < src_w {
+                m[[y - expand, x - expand]].clamp(0.0, 1.0)
+            } else {
+                1.0
+            }
+        })
+    });
+
+    // Shape the effects are computed from
+    let mut shape = extract_alpha_f32(&layer);
+    if let Some(m) = &mask {
+        if !style.layer_mask_hides_effects {
+            shape *= m;
+        }
+    }
+
+    let fill = style.fill_opacity.clamp(0.0, 1.0);
+
+    // ------------------------------------------------------------------
+    // Layer content and interior effects
+    // ------------------------------------------------------------------
+    let mut interior = layer.clone();
+    let content_scale = if style.blend_interior_effects { 1.0 } else { fill };
+    for y in 0..height {
+        for x in 0..width {
+            interior[[y, x, 3]] = shape[[y, x]] * content_scale;
+        }
+    }
+
+    if let Some(p) = &style.pattern_overlay {
+        let pattern = place(&pattern_fill(src_w, src_h, p), height, width, expand);
+        let mut amount = &shape * p.opacity.clamp(0.0, 1.0);
+        for y in 0..height {
+            for x in 0..width {
+                amount[[y, x]] *= pattern[[y, x, 3]];
+            }
+        }
+        composite_mask(&mut interior, &amount, |y, x, px| {
+            [0, 1, 2].map(|c| blend_channel(&p.blend_mode, px[c], pattern[[y, x, c]]))
+        });
+    }
+
+    if let Some(p) = &style.gradient_overlay {
+        let gradient = place(&gradient_fill(src_w, src_h, p), height, width, expand);
+        let amount = &shape * p.opacity.clamp(0.0, 1.0);
+        composite_mask(&mut interior, &amount, |y, x, px| {
+            [0, 1, 2].map(|c| blend_channel(&p.blend_mode, px[c], gradient[[y, x, c]]))
+        });
+    }
+
+    if let Some(p) = &style.color_overlay {
+        let amount = &shape * p.opacity.clamp(0.0, 1.0);
+        composite_mask(&mut interior, &amount, |_, _, px| {
+            if p.preserve_luminosity {
+                blend_color([px[0], px[1], px[2]], p.color)
+            } else {
+                [0, 1, 2].map(|c| blend_channel(&p.blend_mode, px[c], p.color[c]))
+            }
+        });
+    }
+
+    if let Some(p) = &style.satin {
+        composite_mask(&mut interior, &satin_mask(&shape, p), |_, _, px| {
+            [0, 1, 2].map(|c| blend_channel(&p.blend_mode, px[c], p.color[c]))
+        });
+    }
+
+    if let Some(p) = &style.inner_glow {
+        // Screen blend of the glow color
+        composite_mask(&mut interior, &inner_glow_mask(&shape, p), |_, _, px| {
+            [0, 1, 2].map(|c| blend_channel("screen", px[c], p.color[c]))
+        });
+    }
+
+    if style.blend_interior_effects {
+        interior.index_axis_mut(ndarray::Axis(2), 3).mapv_inplace(|a| a * fill);
+    }
+
+    // ------------------------------------------------------------------
+    // Effects on top of the interior, unaffected by fill opacity
+    // ------------------------------------------------------------------
+    if let Some(p) = &style.inner_shadow {
+        composite_mask(&mut interior, &inner_shadow_mask(&shape, p), |_, _, _| p.color);
+    }
+
+    if let Some(p) = &style.stroke {
+        composite_mask(&mut interior, &stroke_mask(&shape, p), |_, _, _| p.color);
+    }
+
+    let bevel = style
+        .bevel_emboss
+        .as_ref()
+        .map(|p| (p, bevel_emboss_shading(&shape, p, expand)));
+
+    if let Some((p, (highlight, shadow))) = &bevel {
+        if p.style.draws_inside() {
+            let hl = highlight * &shape;
+            let sh = shadow * &shape;
+            composite_mask(&mut interior, &sh, |_, _, _| p.shadow_color);
+            composite_mask(&mut interior, &hl, |_, _, _| p.highlight_color);
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // Effects behind the layer
+    // ------------------------------------------------------------------
+    let mut behind = Array3::<f32>::zeros((height, width, 4));
+
+    if let Some(p) = &style.drop_shadow {
+        composite_mask(&mut behind, &drop_shadow_mask(&shape, p), |_, _, _| p.color);
+    }
+
+    if let Some(p) = &style.outer_glow {
+        let colors = outer_glow_colors(&shape, p);
+        composite_mask(&mut behind, &outer_glow_mask(&shape, p), |y, x, _| {
+            [colors[[y, x, 0]], colors[[y, x, 1]], colors[[y, x, 2]]]
+        });
+    }
+
+    if let Some((p, (highlight, shadow))) = &bevel {
+        if p.style.draws_outside() {
+            let outside = shape.mapv(|a| 1.0 - a);
+            let hl = highlight * &outside;
+            let sh = shadow * &outside;
+            composite_mask(&mut behind, &sh, |_, _, _| p.shadow_color);
+            composite_mask(&mut behind, &hl, |_, _, _| p.highlight_color);
+        }
+    }
+
+    // Knockout replaces everything behind the shape with the backdrop
+    let backdrop = match style.knockout {
+        Knockout::None => None,
+        Knockout::Shallow => Some(inputs.group_backdrop),
+        Knockout::Deep => Some(inputs.background),
+    };
+    if let Some(backdrop) = backdrop {
+        let backdrop = backdrop.map(|b| place(b, height, width, expand));
+        for y in 0..height {
+            for x in 0..width {
+                let knock = shape[[y, x]] * (1.0 - fill);
+                if knock <= 0.0 {
+                    continue;
+                }
+                let dst = pixel(&behind, y, x);
+                let src = backdrop.as_ref().map_or([0.0; 4], |b| pixel(b, y, x));
+                let out_a = dst[3] * (1.0 - knock) + src[3] * knock;
+                let mut out = [0.0, 0.0, 0.0, out_a];
+                if out_a > 0.0 {
+                    for c in 0..3 {
+                        out[c] = (dst[c] * dst[3] * (1.0 - knock) + src[c] * src[3] * knock) / out_a;
+                    }
+                }
+                set_pixel(&mut behind, y, x, out);
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // Interior OVER behind, then mask and layer opacity
+    // ------------------------------------------------------------------
+    let opacity = style.opacity.clamp(0.0, 1.0);
+    let mut result = behind;
+    for y in 0..height {
+        for x in 0..width {
+            let src = pixel(&interior, y, x);
+            let mut px = pixel(&result, y, x);
+            blend_over_f32(&mut px, src[0], src[1], src[2], src[3]);
+            if style.layer_mask_hides_effects {
+                if let Some(m) = &mask {
+                    px[3] *= m[[y, x]];
+                }
+            }
+            px[3] *= opacity;
+            set_pixel(&mut result, y, x, px);
+        }
+    }
+
+    style.canvas.apply(result, (src_h, src_w), expand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_effects::gradient_overlay::GradientStop;
+
+    fn square(size: usize, inset: usize) -> Array3<f32> {
+        let mut img = Array3::<f32>::zeros((size, size, 4));
+        for y in inset..size - inset {
+            for x in inset..size - inset {
+                img[[y, x, 0]] = 1.0;
+                img[[y, x, 3]] = 1.0;
+            }
+        }
+        img
+    }
+
+    fn shadow_style() -> LayerStyle {
+        LayerStyle {
+            drop_shadow: Some(DropShadowParams {
+                offset_x: 0.0,
+                offset_y: 0.0,
+                blur_radius: 2.0,
+                color: [0.0, 0.0, 1.0],
+                opacity: 1.0,
+                knockout: false,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_empty_style_is_identity() {
+        let img = square(16, 4);
+        let out = apply_layer_style(&img, &LayerStyle::default(), &LayerStyleInputs::default());
+        assert_eq!(out, img);
+    }
+
+    #[test]
+    fn test_fill_opacity_keeps_effects() {
+        let img = square(24, 8);
+        let style = LayerStyle {
+            fill_opacity: 0.0,
+            color_overlay: Some(ColorOverlayParams { color: [0.0, 1.0, 0.0], ..Default::default() }),
+            ..Default::default()
+        };
+        let out = apply_layer_style(&img, &style, &LayerStyleInputs::default());
+        // Overlay still fully visible although the fill is hidden
+        assert!((out[[12, 12, 1]] - 1.0).abs() < 1e-5);
+        assert!((out[[12, 12, 3]] - 1.0).abs() < 1e-5);
+
+        let grouped = LayerStyle { blend_interior_effects: true, ..style };
+        let out = apply_layer_style(&img, &grouped, &LayerStyleInputs::default());
+        assert!(out[[12, 12, 3]] < 1e-5);
+    }
+
+    #[test]
+    fn test_knockout_reveals_backdrop() {
+        let img = square(24, 8);
+        let style = LayerStyle { fill_opacity: 0.0, ..shadow_style() };
+        let expand = style.expansion();
+        let center = 12 + expand;
+
+        // Without knockout the shadow shows through the transparent fill
+        let out = apply_layer_style(&img, &style, &LayerStyleInputs::default());
+        assert!(out[[center, center, 2]] > 0.9 && out[[center, center, 3]] > 0.9);
+
+        // Shallow knockout replaces it with the group backdrop
+        let mut backdrop = Array3::<f32>::zeros((24, 24, 4));
+        backdrop.index_axis_mut(ndarray::Axis(2), 1).fill(1.0);
+        backdrop.index_axis_mut(ndarray::Axis(2), 3).fill(1.0);
+        let knocked = LayerStyle { knockout: Knockout::Shallow, ..style.clone() };
+        let inputs = LayerStyleInputs { group_backdrop: Some(&backdrop), ..Default::default() };
+        let out = apply_layer_style(&img, &knocked, &inputs);
+        assert!((out[[center, center, 1]] - 1.0).abs() < 1e-5);
+        assert!(out[[center, center, 2]] < 1e-5);
+
+        // Deep knockout without a background punches to transparency
+        let deep = LayerStyle { knockout: Knockout::Deep, ..style };
+        let out = apply_layer_style(&img, &deep, &inputs);
+        assert!(out[[center, center, 3]] < 1e-5);
+    }
+
+    #[test]
+    fn test_layer_knocks_out_drop_shadow() {
+        let img = square(24, 8);
+        let mut style = LayerStyle { fill_opacity: 0.0, ..shadow_style() };
+        style.drop_shadow.as_mut().unwrap().knockout = true;
+        let expand = style.expansion();
+        let center = 12 + expand;
+
+        let out = apply_layer_style(&img, &style, &LayerStyleInputs::default());
+        assert!(out[[center, center, 3]] < 1e-5);
+        // Outside the layer the shadow is untouched
+        let edge = 16 + expand;
+        assert!(out[[center, edge, 3]] > 0.1);
+    }
+
+    #[test]
+    fn test_layer_mask_hides_effects() {
+        let img = square(24, 4);
+        // Mask hides the left half of the layer
+        let mask = Array2::from_shape_fn((24, 24), |(_, x)| if x < 12 { 0.0 } else { 1.0 });
+        let inputs = LayerStyleInputs { mask: Some(&mask), ..Default::default() };
+        let style = shadow_style();
+        let expand = style.expansion();
+        let y = 12 + expand;
+        // Just left of the mask edge, inside the original layer
+        let x = 10 + expand;
+
+        // Effects follow the masked shape: shadow spills over the mask edge
+        let out = apply_layer_style(&img, &style, &inputs);
+        assert!(out[[y, x, 3]] > 0.05);
+        assert!(out[[y, x, 0]] < 1e-5);
+
+        // Mask hides the effects as well
+        let hiding = LayerStyle { layer_mask_hides_effects: true, ..style };
+        let out = apply_layer_style(&img, &hiding, &inputs);
+        assert_eq!(out[[y, x, 3]], 0.0);
+        // The unmasked edge still casts its shadow outside the layer bounds
+        assert!(out[[y, 21 + expand, 3]] > 0.0);
+    }
+
+    #[test]
+    fn test_outer_glow_gradient_follows_distance() {
+        let img = square(24, 8);
+        let stops = vec![
+            GradientStop { position: 0.0, r: 1.0, g: 1.0, b: 1.0 },
+            GradientStop { position: 1.0, r: 0.0, g: 0.0, b: 1.0 },
+        ];
+        let glow = OuterGlowParams { radius: 3.0, opacity: 1.0, gradient: stops, ..Default::default() };
+        let style = LayerStyle { outer_glow: Some(glow), ..Default::default() };
+        let expand = style.expansion();
+        let out = apply_layer_style(&img, &style, &LayerStyleInputs::default());
+
+        // White core next to the edge, blue further out
+        let y = 12 + expand;
+        let near = out.slice(ndarray::s![y, expand + 7, ..]).to_owned();
+        let far = out.slice(ndarray::s![y, expand + 3, ..]).to_owned();
+        assert!(near[3] > 0.1 && far[3] > 0.01);
+        assert!(near[0] > 0.7, "near {near:?}");
+        assert!(far[0] < near[0] && far[2] > 0.9, "far {far:?}");
+    }
+
+    #[test]
+    fn test_scale_multiplies_pixel_parameters() {
+        let img = square(24, 8);
+        let mut style = shadow_style();
+        style.drop_shadow.as_mut().unwrap().offset_x = 2.0;
+        style.stroke = Some(StrokeParams { width: 1.5, ..Default::default() });
+
+        let doubled = LayerStyle { scale: 2.0, ..style.clone() };
+        let mut manual = style.clone();
+        manual.drop_shadow.as_mut().unwrap().offset_x = 4.0;
+        manual.drop_shadow.as_mut().unwrap().blur_radius = 4.0;
+        manual.stroke.as_mut().unwrap().width = 3.0;
+
+        assert_eq!(doubled.expansion(), manual.expansion());
+        let inputs = LayerStyleInputs::default();
+        assert_eq!(apply_layer_style(&img, &doubled, &inputs), apply_layer_style(&img, &manual, &inputs));
+        assert_eq!(doubled.scaled().drop_shadow.unwrap().opacity, 1.0);
+    }
+
+    #[test]
+    fn test_canvas_policy_crops_grown_result() {
+        let img = square(24, 8);
+        let style = shadow_style();
+        let expand = style.expansion();
+        let inputs = LayerStyleInputs::default();
+        let grown = apply_layer_style(&img, &style, &inputs);
+
+        let clipped = LayerStyle { canvas: CanvasPolicy::ClipToInput, ..style.clone() };
+        let out = apply_layer_style(&img, &clipped, &inputs);
+        assert_eq!(out, grown.slice(ndarray::s![expand..expand + 24, expand..expand + 24, ..]));
+
+        let explicit = LayerStyle {
+            canvas: CanvasPolicy::Explicit { width: 30, height: 20, anchor: crate::filters::core::CanvasAnchor::TopLeft },
+            ..style
+        };
+        let out = apply_layer_style(&img, &explicit, &inputs);
+        assert_eq!(out.dim(), (20, 30, 4));
+        assert_eq!(out.slice(ndarray::s![.., ..24, ..]), grown.slice(ndarray::s![expand..expand + 20, expand..expand + 24, ..]));
+    }
+
+    #[test]
+    fn test_expansion_is_max_of_effects() {
+        let style = LayerStyle {
+            stroke: Some(StrokeParams { width: 3.0, ..Default::default() }),
+            outer_glow: Some(OuterGlowParams { radius: 4.0, ..Default::default() }),
+            ..Default::default()
+        };
+        assert_eq!(style.expansion(), 14);
+        assert_eq!(Knockout::parse("deep"), Knockout::Deep);
+    }
+}
@@ -0,0 +1,61 @@
+/**
+ * Long Shadow layer effect - JavaScript WASM wrapper.
+ *
+ * Co-located with:
+ * - long_shadow.rs (Rust implementation)
+ * - long_shadow.py (Python wrapper)
+ *
+ * Extrudes the alpha silhouette away from the light (flat-design long shadow).
+ */
+
+import { initWasm, wasm } from './core.js';
+
+export { initWasm };
+
+/**
+ * Apply long shadow effect to RGBA image.
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels: 4}
+ * @param {Object} options - Long shadow options
+ * @param {number} [options.angle=135] - Light angle in degrees (135 = shadow down-right)
+ * @param {number} [options.length=50] - Extrusion length in pixels
+ * @param {Array<number>} [options.color=[0,0,0]] - Shadow color [r, g, b] (0-255)
+ * @param {number} [options.fade=0] - Fade towards the tip (0 = solid, 1 = transparent tip)
+ * @returns {Object} - Result with expanded canvas {data, width, height, channels, offset_x, offset_y}
+ */
+export function long_shadow(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+
+    if (channels !== 4) {
+        throw new Error('Long shadow requires RGBA images (4 channels)');
+    }
+
+    const length = options.length ?? 50;
+    const color = options.color ?? [0, 0, 0];
+    const opts = Object.assign(new wasm.LongShadowOptions(), {
+        angle: options.angle ?? 135,
+        length,
+        color_r: color[0],
+        color_g: color[1],
+        color_b: color[2],
+        fade: options.fade ?? 0,
+    });
+
+    const result = wasm.long_shadow_rgba_wasm(new Uint8Array(data.buffer), width, height, opts);
+    opts.free();
+
+    const expand = Math.ceil(Math.max(length, 0)) + 1;
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: width + expand * 2,
+        height: height + expand * 2,
+        channels: 4,
+        offset_x: -expand,
+        offset_y: -expand
+    };
+}
+
+export default {
+    initWasm,
+    long_shadow
+};
@@ -20,6 +20,7 @@ export { initWasm };
  * @param {Array<number>} [options.color=[255,255,0]] - Glow color [r, g, b] (0-255)
  * @param {number} [options.opacity=0.75] - Glow opacity (0.0-1.0)
  * @param {number} [options.spread=0] - Spread amount (0.0-1.0)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
  * @returns {Object} - Result with expanded canvas {data, width, height, channels, offset_x, offset_y}
  */
 export function outer_glow(imageData, options = {}) {
@@ -44,11 +45,12 @@ export function outer_glow(imageData, options = {}) {
         color[1],
         color[2],
         opacity,
-        spread
+        spread,
+        options.quality ?? 'exact'
     );
 
     // Calculate expanded dimensions
-    const required_expand = Math.ceil(radius * 3) + 2;
+    const required_expand = wasm.outer_glow_expansion_wasm(radius, spread);
     const new_width = width + required_expand * 2;
     const new_height = height + required_expand * 2;
 
@@ -62,7 +64,53 @@ export function outer_glow(imageData, options = {}) {
     };
 }
 
+/**
+ * Apply outer glow with gradient colors (neon-style multi-color glow).
+ * @param {Object} imageData - {data: Uint8ClampedArray, width, height, channels: 4}
+ * @param {Object} options - Outer glow options
+ * @param {Array<Array<number>>} options.gradient - Stops [[pos, r, g, b], ...] with colors 0-255;
+ *     position 0 is the layer edge, 1 the outer reach of the glow
+ * @param {number} [options.radius=10] - Glow radius
+ * @param {number} [options.opacity=0.75] - Glow opacity (0.0-1.0)
+ * @param {number} [options.spread=0] - Spread amount (0.0-1.0)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
+ * @returns {Object} - Result with expanded canvas {data, width, height, channels, offset_x, offset_y}
+ */
+export function outer_glow_gradient(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    const channels = imageData.channels || 4;
+
+    if (channels !== 4) {
+        throw new Error('Outer glow requires RGBA images (4 channels)');
+    }
+
+    const radius = options.radius ?? 10;
+    const spread = options.spread ?? 0;
+    const stops = new Float32Array((options.gradient ?? []).flat());
+
+    const result = wasm.outer_glow_gradient_rgba_wasm(
+        new Uint8Array(data.buffer),
+        width,
+        height,
+        stops,
+        radius,
+        options.opacity ?? 0.75,
+        spread
+    );
+
+    const required_expand = wasm.outer_glow_expansion_wasm(radius, spread);
+    return {
+        data: new Uint8ClampedArray(result.buffer),
+        width: width + required_expand * 2,
+        height: height + required_expand * 2,
+        channels: 4,
+        offset_x: -required_expand,
+        offset_y: -required_expand
+    };
+}
+
 export default {
     initWasm,
-    outer_glow
+    outer_glow,
+    outer_glow_gradient
 };
@@ -5,13 +5,15 @@ Creates a glow effect outside the shape edges by:
 1. Extracting the alpha channel
 2. Optionally spreading (dilating) the alpha
 3. Blurring the alpha
-4. Colorizing with glow color
+4. Colorizing with glow color (or gradient stops mapped across the glow's
+   distance falloff, Photoshop's "gradient glow")
 5. Compositing original on top
 
 SVG Export: 90% fidelity via composite filter chain.
 """
 
-from typing import Tuple, Union, Dict, Any, Optional, ClassVar
+import math
+from typing import Tuple, Union, Dict, Any, Optional, ClassVar, List
 import numpy as np
 
 from pydantic import Field, model_validator
@@ -35,6 +37,12 @@ class OuterGlow(LayerEffect):
         >>> from imagestag.layer_effects import OuterGlow
         >>> effect = OuterGlow(blur=15, color='#FFFF00')
         >>> result = effect.apply(image)
+
+        >>> # Neon: white core fading to magenta
+        >>> neon = OuterGlow(blur=8, gradient=[
+        ...     {"position": 0.0, "color": "#FFFFFF"},
+        ...     {"position": 1.0, "color": "#FF00FF"},
+        ... ])
     """
 
     effect_type: ClassVar[str] = "outerGlow"
@@ -45,6 +53,11 @@ class OuterGlow(LayerEffect):
     color: str = Field(default='#FFFF00')  # Hex string for JS compatibility
     color_opacity: float = Field(default=0.75, alias='colorOpacity', ge=0.0, le=1.0)
     spread: float = Field(default=0.0)
+    # Optional gradient replacing `color`, from the layer edge (0.0) to the
+    # outer reach of the glow (1.0): [{"position": 0.0, "color": "#RRGGBB"}, ...]
+    gradient: Optional[List[Dict[str, Any]]] = Field(default=None)
+    # Blur accuracy: 'exact', or 'fast' for interactive previews
+    quality: str = Field(default='exact')
 
     # Internal: parsed RGB tuple (not serialized)
     _color_rgb: Optional[Tuple[int, int, int]] = None
@@ -70,6 +83,17 @@ class OuterGlow(LayerEffect):
             if isinstance(color, (list, tuple)):
                 r, g, b = color[:3]
                 data['color'] = f'#{int(r):02X}{int(g):02X}{int(b):02X}'
+
+            # Convert tuple gradient stops (position, r, g, b) to dicts
+            gradient = data.get('gradient')
+            if gradient:
+                normalized = []
+                for stop in gradient:
+                    if isinstance(stop, (list, tuple)):
+                        pos, r, g, b = stop[:4]
+                        stop = {"position": float(pos), "color": f'#{int(r):02X}{int(g):02X}{int(b):02X}'}
+                    normalized.append(stop)
+                data['gradient'] = normalized
         return data
 
     def model_post_init(self, __context: Any) -> None:
@@ -96,6 +120,15 @@ class OuterGlow(LayerEffect):
             self._color_rgb = self._hex_to_rgb(self.color)
         return self._color_rgb
 
+    def _gradient_flat(self, is_float: bool) -> List[float]:
+        """Convert gradient stops to flat [pos, r, g, b, ...] for Rust."""
+        scale = 255.0 if is_float else 1.0
+        flat = []
+        for stop in self.gradient or []:
+            r, g, b = self._hex_to_rgb(stop.get('color', '#000000'))
+            flat.extend([float(stop.get('position', 0.0)), r / scale, g / scale, b / scale])
+        return flat
+
     # Legacy property for backwards compatibility
     @property
     def radius(self) -> float:
@@ -103,7 +136,8 @@ class OuterGlow(LayerEffect):
 
     def get_expansion(self) -> Expansion:
         """Calculate expansion needed for the glow."""
-        expand = int(self.blur * 3) + 2 + abs(int(self.spread))
+        # Spread dilates by blur * spread before the ~3 sigma blur
+        expand = math.ceil(self.blur * (3 + max(self.spread, 0.0))) + 2
         return Expansion(left=expand, top=expand, right=expand, bottom=expand)
 
     def _resolve_format(self, image: np.ndarray, format: Union[PixelFormat, str, None]) -> PixelFormat:
@@ -151,6 +185,29 @@ class OuterGlow(LayerEffect):
 
         color = self.color_rgb
 
+        if self.gradient:
+            if fmt.is_float:
+                result = imagestag_rust.outer_glow_gradient_rgba_f32(
+                    image.astype(np.float32),
+                    self._gradient_flat(True),
+                    float(self.blur),
+                    float(self.color_opacity),
+                    float(self.spread),
+                    expand,
+                    quality=self.quality,
+                )
+            else:
+                result = imagestag_rust.outer_glow_gradient_rgba(
+                    image.astype(np.uint8),
+                    self._gradient_flat(False),
+                    float(self.blur),
+                    float(self.color_opacity),
+                    float(self.spread),
+                    expand,
+                    quality=self.quality,
+                )
+            return EffectResult(image=result, offset_x=-expand, offset_y=-expand)
+
         # Currently only u8 version exists
         if fmt.is_float:
             # Convert to u8, apply, convert back
@@ -162,6 +219,7 @@ class OuterGlow(LayerEffect):
                 float(self.color_opacity),
                 float(self.spread),
                 expand,
+                quality=self.quality,
             )
             result = result.astype(np.float32) / 255.0
         else:
@@ -172,6 +230,7 @@ class OuterGlow(LayerEffect):
                 float(self.color_opacity),
                 float(self.spread),
                 expand,
+                quality=self.quality,
             )
 
         return EffectResult(
@@ -228,6 +287,7 @@ class OuterGlow(LayerEffect):
                 float(self.color_opacity),
                 float(self.spread),
                 expand,
+                quality=self.quality,
             )
             result = result.astype(np.float32) / 255.0
         else:
@@ -238,6 +298,7 @@ class OuterGlow(LayerEffect):
                 float(self.color_opacity),
                 float(self.spread),
                 expand,
+                quality=self.quality,
             )
 
         return EffectResult(
@@ -252,8 +313,11 @@ class OuterGlow(LayerEffect):
 
     @property
     def svg_fidelity(self) -> int:
-        """Outer glow has 90% fidelity via composite filter chain."""
-        return 90
+        """Outer glow has 90% fidelity via composite filter chain.
+
+        Gradient glows are approximated with their edge color.
+        """
+        return 60 if self.gradient else 90
 
     def to_svg_filter(self, filter_id: str, scale: float = 1.0) -> Optional[str]:
         """
@@ -284,12 +348,17 @@ class OuterGlow(LayerEffect):
             spread_elem = f'  <feMorphology operator="dilate" radius="{spread_radius:.2f}" in="SourceAlpha" result="spread"/>\n'
             blur_input = "spread"
 
+        # SVG has no distance-mapped gradient; use the stop at the edge
+        flood_color = self.color
+        if self.gradient:
+            flood_color = min(self.gradient, key=lambda stop: stop.get('position', 0.0)).get('color', self.color)
+
         # primitiveUnits="userSpaceOnUse" ensures values are in viewBox units
         return f'''<filter id="{filter_id}" x="-50%" y="-50%" width="200%" height="200%" primitiveUnits="userSpaceOnUse">
 {spread_elem}  <feGaussianBlur stdDeviation="{svg_blur:.2f}" in="{blur_input}" result="blurred"/>
   <!-- Subtract original alpha from blurred to get outer-only glow -->
   <feComposite in="blurred" in2="SourceAlpha" operator="out" result="outerOnly"/>
-  <feFlood flood-color="{self.color}" flood-opacity="{self.color_opacity}" result="color"/>
+  <feFlood flood-color="{flood_color}" flood-opacity="{self.color_opacity}" result="color"/>
   <feComposite in="color" in2="outerOnly" operator="in" result="glow"/>
   <feMerge>
     <feMergeNode in="glow"/>
@@ -1,16 +1,186 @@
 //! Outer Glow layer effect.
 //!
-//! Creates a glow effect outside the shape edges.
+//! Creates a glow effect outside the shape edges, in a single color or with
+//! gradient stops mapped across the glow's distance falloff (Photoshop's
+//! gradient glow).
 //!
 //! Co-located with:
 //! - outer_glow.py (Python wrapper)
 //! - outer_glow.js (JavaScript wrapper)
 
-use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array2, Array3, ArrayView3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::filters::core::{blur_alpha_f32, dilate_alpha, expand_canvas_f32};
+#[cfg(feature = "python")]
+use crate::filters::blur::parse_blur_quality;
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{
+    blend_over_f32, blur_alpha_quality_f32, dilate_alpha, expand_canvas_view_f32, image_f32_to_u8, image_u8_to_f32, signed_distance_alpha, CanvasPolicy,
+};
+use crate::filters::pool::{self, Pooled};
+#[cfg(feature = "python")]
+use crate::layer_effects::gradient_overlay::parse_stops;
+use crate::layer_effects::gradient_overlay::{interpolate_gradient, GradientStop};
+
+
+/// Parameters for the outer glow effect.
+#[derive(Debug, Clone)]
+pub struct OuterGlowParams {
+    /// Glow blur radius
+    pub radius: f32,
+    /// Glow color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Glow opacity (0.0-1.0)
+    pub opacity: f32,
+    /// How much to expand the glow before blur (0.0-1.0)
+    pub spread: f32,
+    /// Color stops mapped from the layer edge (0.0) to the glow's outer
+    /// reach (1.0). Empty = solid `color`.
+    pub gradient: Vec<GradientStop>,
+    /// Blur accuracy; `Fast` for interactive previews
+    pub quality: BlurQuality,
+    /// Output canvas; ignored inside a layer style, which has its own
+    pub canvas: CanvasPolicy,
+}
+
+impl Default for OuterGlowParams {
+    fn default() -> Self {
+        Self {
+            radius: 10.0,
+            color: [1.0, 1.0, 0.0],
+            opacity: 0.75,
+            spread: 0.0,
+            gradient: Vec::new(),
+            quality: BlurQuality::Exact,
+            canvas: CanvasPolicy::Grow,
+        }
+    }
+}
+
+impl OuterGlowParams {
+    /// Pixels required on each side of the layer for the glow.
+    ///
+    /// The spread dilates the mask by `radius * spread` before the blur, so
+    /// it pushes the glow out on top of the blur's own 3-sigma reach. A
+    /// negative spread doesn't dilate and doesn't shrink the padding.
+    pub fn expansion(&self) -> usize {
+        (self.radius * (3.0 + self.spread.max(0.0))).ceil() as usize + 2
+    }
+
+    /// Distance outside the layer edge at which the gradient reaches 1.0.
+    fn gradient_reach(&self) -> f32 {
+        (self.radius * (2.0 + self.spread)).max(1.0)
+    }
+}
+
+/// Compute the full outer glow coverage from a layer alpha mask.
+///
+/// The glow is not cut out under the layer; it is meant to be drawn behind it.
+///
+/// # Returns
+/// Glow alpha (0.0-1.0) with the opacity applied, same size as `alpha`
+pub fn outer_glow_mask(alpha: &Array2<f32>, params: &OuterGlowParams) -> Array2<f32> {
+    let spread_radius = params.radius * params.spread;
+    let mut glow = if spread_radius > 0.0 {
+        blur_alpha_quality_f32(&dilate_alpha(alpha, spread_radius), params.radius, params.quality)
+    } else {
+        blur_alpha_quality_f32(alpha, params.radius, params.quality)
+    };
+    glow.mapv_inplace(|v| v * params.opacity);
+    glow
+}
+
+/// Compute the glow color of every pixel (H, W, 3).
+///
+/// With gradient stops the color follows the distance from the layer edge;
+/// otherwise every pixel gets the solid `color`.
+pub fn outer_glow_colors(alpha: &Array2<f32>, params: &OuterGlowParams) -> Array3<f32> {
+    let (height, width) = alpha.dim();
+    if params.gradient.is_empty() {
+        return Array3::from_shape_fn((height, width, 3), |(_, _, c)| params.color[c]);
+    }
+    let mut stops = params.gradient.clone();
+    stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+    let reach = params.gradient_reach();
+    let sdf = signed_distance_alpha(alpha);
+    let mut colors = Array3::<f32>::zeros((height, width, 3));
+    for ((y, x), &d) in sdf.indexed_iter() {
+        let (r, g, b) = interpolate_gradient(&stops, -d / reach);
+        colors[[y, x, 0]] = r;
+        colors[[y, x, 1]] = g;
+        colors[[y, x, 2]] = b;
+    }
+    colors
+}
+
+/// Render the outer glow behind an RGBA f32 layer.
+///
+/// The glow is cut out under the layer and the original composited on top.
+/// With gradient stops the glow color follows the distance from the edge.
+///
+/// # Arguments
+/// * `image` - Source RGBA image (height, width, 4)
+/// * `params` - Glow parameters
+/// * `expand` - Pixels to add on each side (0 = [`OuterGlowParams::expansion`])
+///
+/// # Returns
+/// RGBA image on the canvas chosen by [`OuterGlowParams::canvas`]
+pub fn render_outer_glow_f32(image: ArrayView3<f32>, params: &OuterGlowParams, expand: usize) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let expand = if expand > 0 { expand } else { params.expansion() };
+    let expanded = Pooled::new(expand_canvas_view_f32(image, expand));
+    let alpha = Pooled::new(pool::copy2(expanded.index_axis(ndarray::Axis(2), 3)));
+    let glow = outer_glow_mask(&alpha, params);
+    let colors = outer_glow_colors(&alpha, params);
+
+    let mut result = Array3::<f32>::zeros(expanded.dim());
+    for ((y, x), &glow_a) in glow.indexed_iter() {
+        let glow_a = (glow_a - alpha[[y, x]] * params.opacity).max(0.0);
+        let mut pixel = if glow_a > 0.0 {
+            [colors[[y, x, 0]], colors[[y, x, 1]], colors[[y, x, 2]], glow_a]
+        } else {
+            [0.0; 4]
+        };
+        let src = |c: usize| expanded[[y, x, c]];
+        blend_over_f32(&mut pixel, src(0), src(1), src(2), src(3));
+        for (c, &v) in pixel.iter().enumerate() {
+            result[[y, x, c]] = v;
+        }
+    }
+    params.canvas.apply(result, (height, width), expand)
+}
+
+/// Render the outer glow behind an RGBA u8 layer.
+pub fn render_outer_glow_u8(image: ArrayView3<u8>, params: &OuterGlowParams, expand: usize) -> Array3<u8> {
+    image_f32_to_u8(&render_outer_glow_f32(image_u8_to_f32(image).view(), params, expand))
+}
+
+/// Render only the glow of an RGBA f32 layer, including the area under the
+/// layer and without the original composited on top.
+///
+/// # Arguments
+/// Same as [`render_outer_glow_f32`]
+pub fn render_outer_glow_only_f32(image: ArrayView3<f32>, params: &OuterGlowParams, expand: usize) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let expand = if expand > 0 { expand } else { params.expansion() };
+    let expanded = Pooled::new(expand_canvas_view_f32(image, expand));
+    let alpha = Pooled::new(pool::copy2(expanded.index_axis(ndarray::Axis(2), 3)));
+    let glow = outer_glow_mask(&alpha, params);
+    let colors = outer_glow_colors(&alpha, params);
+
+    let result = Array3::from_shape_fn(expanded.dim(), |(y, x, c)| if c < 3 { colors[[y, x, c]] } else { glow[[y, x]] });
+    params.canvas.apply(result, (height, width), expand)
+}
+
+/// Render only the glow of an RGBA u8 layer.
+pub fn render_outer_glow_only_u8(image: ArrayView3<u8>, params: &OuterGlowParams, expand: usize) -> Array3<u8> {
+    image_f32_to_u8(&render_outer_glow_only_f32(image_u8_to_f32(image).view(), params, expand))
+}
 
 /// Apply outer glow effect to RGBA image.
 ///
@@ -23,188 +193,60 @@ use crate::filters::core::{blur_alpha_f32, dilate_alpha, expand_canvas_f32};
 /// * `opacity` - Glow opacity (0.0-1.0)
 /// * `spread` - How much to expand the glow before blur (0.0-1.0)
 /// * `expand` - Extra pixels to add around image
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+/// * `canvas` - Output canvas: "grow" (fit the glow) or "clip" (input size)
+/// * `canvas_size` - Explicit output (width, height); overrides `canvas`
+/// * `anchor` - Layer position in an explicit canvas ("center", "top_left", ...)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, spread=0.0, expand=0))]
+#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, spread=0.0, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn outer_glow_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     radius: f32,
     color: (u8, u8, u8),
     opacity: f32,
     spread: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Calculate expansion
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        (radius * 3.0).ceil() as usize + 2
-    };
-
-    let expanded = expand_canvas_f32(&input_f32, required_expand);
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Apply spread (dilate before blur)
-    let spread_radius = radius * spread;
-    let spread_alpha = if spread_radius > 0.0 {
-        dilate_alpha(&alpha, spread_radius)
-    } else {
-        alpha.clone()
-    };
-
-    // Blur the alpha
-    let blurred = blur_alpha_f32(&spread_alpha, radius);
-
-    // Create result with glow
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    let glow_r = color.0 as f32 / 255.0;
-    let glow_g = color.1 as f32 / 255.0;
-    let glow_b = color.2 as f32 / 255.0;
-
-    // Draw glow first (outside only)
-    for y in 0..new_h {
-        for x in 0..new_w {
-            // Glow is the blurred alpha minus original (outside only)
-            let glow_a = (blurred[[y, x]] - alpha[[y, x]]).max(0.0) * opacity;
-            if glow_a > 0.0 {
-                result[[y, x, 0]] = glow_r;
-                result[[y, x, 1]] = glow_g;
-                result[[y, x, 2]] = glow_b;
-                result[[y, x, 3]] = glow_a;
-            }
-        }
-    }
-
-    // Composite original on top
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let src_a = expanded[[y, x, 3]];
-            if src_a <= 0.0 {
-                continue;
-            }
-
-            let dst_a = result[[y, x, 3]];
-            let out_a = src_a + dst_a * (1.0 - src_a);
-
-            if out_a > 0.0 {
-                result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 3]] = out_a;
-            }
-        }
-    }
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, color, opacity, spread, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor), ..Default::default() };
+    Ok(render_outer_glow_u8(image.as_array(), &params, expand).into_pyarray(py))
 }
 
 /// Apply outer glow effect to f32 RGBA image.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, spread=0.0, expand=0))]
+#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, spread=0.0, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn outer_glow_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     radius: f32,
     color: (f32, f32, f32),
     opacity: f32,
     spread: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Clone input
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        (radius * 3.0).ceil() as usize + 2
-    };
-
-    let expanded = expand_canvas_f32(&input_f32, required_expand);
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    let spread_radius = radius * spread;
-    let spread_alpha = if spread_radius > 0.0 {
-        dilate_alpha(&alpha, spread_radius)
-    } else {
-        alpha.clone()
-    };
-
-    let blurred = blur_alpha_f32(&spread_alpha, radius);
-
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let glow_a = (blurred[[y, x]] - alpha[[y, x]]).max(0.0) * opacity;
-            if glow_a > 0.0 {
-                result[[y, x, 0]] = color.0;
-                result[[y, x, 1]] = color.1;
-                result[[y, x, 2]] = color.2;
-                result[[y, x, 3]] = glow_a;
-            }
-        }
-    }
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let src_a = expanded[[y, x, 3]];
-            if src_a <= 0.0 {
-                continue;
-            }
-
-            let dst_a = result[[y, x, 3]];
-            let out_a = src_a + dst_a * (1.0 - src_a);
-
-            if out_a > 0.0 {
-                result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 3]] = out_a;
-            }
-        }
-    }
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, color: [color.0, color.1, color.2], opacity, spread, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor), ..Default::default() };
+    Ok(render_outer_glow_f32(image.as_array(), &params, expand).into_pyarray(py))
+}
 
-    result.into_pyarray(py)
+/// Pixels `outer_glow_rgba` adds on each side when `expand` is 0.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (radius=10.0, spread=0.0))]
+pub fn outer_glow_expansion(radius: f32, spread: f32) -> usize {
+    OuterGlowParams { radius, spread, ..Default::default() }.expansion()
 }
 
 /// Get glow-only layer (no compositing with original, full glow including under object).
@@ -218,149 +260,128 @@ pub fn outer_glow_rgba_f32<'py>(
 ///
 /// # Returns
 /// RGBA image with ONLY the glow (original NOT composited on top, full glow area)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, spread=0.0, expand=0))]
+#[pyo3(signature = (image, radius=10.0, color=(255, 255, 0), opacity=0.75, spread=0.0, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn outer_glow_only_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     radius: f32,
     color: (u8, u8, u8),
     opacity: f32,
     spread: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Calculate expansion
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        (radius * 3.0).ceil() as usize + 2
-    };
-
-    let expanded = expand_canvas_f32(&input_f32, required_expand);
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Apply spread (dilate before blur)
-    let spread_radius = radius * spread;
-    let spread_alpha = if spread_radius > 0.0 {
-        dilate_alpha(&alpha, spread_radius)
-    } else {
-        alpha.clone()
-    };
-
-    // Blur the alpha - this is the FULL glow
-    let blurred = blur_alpha_f32(&spread_alpha, radius);
-
-    // Create glow-only result (no compositing with original)
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    let glow_r = color.0 as f32 / 255.0;
-    let glow_g = color.1 as f32 / 255.0;
-    let glow_b = color.2 as f32 / 255.0;
-
-    // Draw FULL glow (not subtracting original alpha)
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let glow_a = blurred[[y, x]] * opacity;
-            result[[y, x, 0]] = glow_r;
-            result[[y, x, 1]] = glow_g;
-            result[[y, x, 2]] = glow_b;
-            result[[y, x, 3]] = glow_a;
-        }
-    }
-
-    // NOTE: No compositing step - return glow layer only
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, color, opacity, spread, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor), ..Default::default() };
+    Ok(render_outer_glow_only_u8(image.as_array(), &params, expand).into_pyarray(py))
 }
 
 /// Get glow-only layer for f32 RGBA image (no compositing with original).
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, spread=0.0, expand=0))]
+#[pyo3(signature = (image, radius=10.0, color=(1.0, 1.0, 0.0), opacity=0.75, spread=0.0, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
 pub fn outer_glow_only_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     radius: f32,
     color: (f32, f32, f32),
     opacity: f32,
     spread: f32,
     expand: usize,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Clone input
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, color: [color.0, color.1, color.2], opacity, spread, quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor), ..Default::default() };
+    Ok(render_outer_glow_only_f32(image.as_array(), &params, expand).into_pyarray(py))
+}
 
-    let required_expand = if expand > 0 {
-        expand
-    } else {
-        (radius * 3.0).ceil() as usize + 2
-    };
+/// Apply outer glow with gradient colors to RGBA image.
+///
+/// The gradient runs from the layer edge (position 0.0) to the outer reach
+/// of the glow (1.0), e.g. white core fading to a colored neon halo.
+///
+/// # Arguments
+/// * `image` - Source RGBA image (height, width, 4) as u8
+/// * `stops` - Gradient stops as flat array: [pos, r, g, b, ...] with colors 0-255
+/// * `radius` - Glow blur radius
+/// * `opacity` - Glow opacity (0.0-1.0)
+/// * `spread` - How much to expand the glow before blur (0.0-1.0)
+/// * `expand` - Extra pixels to add around image (0 = automatic)
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+/// * `canvas`, `canvas_size`, `anchor` - Output canvas, as for `outer_glow_rgba`
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, stops, radius=10.0, opacity=0.75, spread=0.0, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
+pub fn outer_glow_gradient_rgba<'py>(
+    image: PyHostArray3<'py, u8>,
+    stops: Vec<f32>,
+    radius: f32,
+    opacity: f32,
+    spread: f32,
+    expand: usize,
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, opacity, spread, gradient: parse_stops(&stops, 255.0), quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor), ..Default::default() };
+    Ok(render_outer_glow_u8(image.as_array(), &params, expand).into_pyarray(image.py()))
+}
 
-    let expanded = expand_canvas_f32(&input_f32, required_expand);
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+/// Apply outer glow with gradient colors to f32 RGBA image.
+///
+/// Same as outer_glow_gradient_rgba with stop colors in 0.0-1.0.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, stops, radius=10.0, opacity=0.75, spread=0.0, expand=0, quality="exact", canvas="grow", canvas_size=None, anchor="center"))]
+pub fn outer_glow_gradient_rgba_f32<'py>(
+    image: PyHostArray3<'py, f32>,
+    stops: Vec<f32>,
+    radius: f32,
+    opacity: f32,
+    spread: f32,
+    expand: usize,
+    quality: &str,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, opacity, spread, gradient: parse_stops(&stops, 1.0), quality, canvas: CanvasPolicy::parse(canvas, canvas_size, anchor), ..Default::default() };
+    Ok(render_outer_glow_f32(image.as_array(), &params, expand).into_pyarray(image.py()))
+}
 
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_effects::test_util::{assert_fits_padding, assert_layer_on_top, full_layer};
+
+    #[test]
+    fn test_expansion_matches_output_padding() {
+        let image = full_layer(0.3);
+        for (radius, spread) in [(1.0, 0.0), (4.0, 0.0), (2.5, 0.5), (3.0, 1.0), (6.0, 0.25), (8.0, 1.0), (5.0, -0.5)] {
+            let params = OuterGlowParams { radius, spread, opacity: 1.0, ..Default::default() };
+            let e = params.expansion();
+            assert_fits_padding(&render_outer_glow_f32(image.view(), &params, 0), e, &format!("{params:?}"));
+            assert_fits_padding(&render_outer_glow_only_f32(image.view(), &params, 0), e, &format!("{params:?}"));
+            assert_layer_on_top(&render_outer_glow_f32(image.view(), &params, 0), &image, e);
         }
     }
 
-    let spread_radius = radius * spread;
-    let spread_alpha = if spread_radius > 0.0 {
-        dilate_alpha(&alpha, spread_radius)
-    } else {
-        alpha.clone()
-    };
-
-    // Blur the alpha - this is the FULL glow
-    let blurred = blur_alpha_f32(&spread_alpha, radius);
-
-    // Create glow-only result (no compositing with original)
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    // Draw FULL glow (not subtracting original alpha)
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let glow_a = blurred[[y, x]] * opacity;
-            result[[y, x, 0]] = color.0;
-            result[[y, x, 1]] = color.1;
-            result[[y, x, 2]] = color.2;
-            result[[y, x, 3]] = glow_a;
-        }
+    #[test]
+    fn test_negative_spread_pads_like_no_spread() {
+        let params = |spread| OuterGlowParams { radius: 5.0, spread, ..Default::default() };
+        assert_eq!(params(-0.5).expansion(), params(0.0).expansion());
+        assert_eq!(params(-4.0).expansion(), params(0.0).expansion());
     }
-
-    // NOTE: No compositing step - return glow layer only
-
-    result.into_pyarray(py)
 }
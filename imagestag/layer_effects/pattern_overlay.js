@@ -21,6 +21,9 @@ export { initWasm };
  * @param {number} [options.offset_x=0] - Horizontal offset
  * @param {number} [options.offset_y=0] - Vertical offset
  * @param {number} [options.opacity=1.0] - Opacity (0.0-1.0)
+ * @param {string} [options.blendMode='normal'] - Blend mode: 'normal', 'multiply', 'screen', 'overlay',
+ *     'darken', 'lighten', 'color_dodge', 'color_burn', 'linear_dodge', 'linear_burn', 'soft_light',
+ *     'hard_light', 'difference', 'exclusion'
  * @returns {Object} - Result with same dimensions {data, width, height, channels}
  */
 export function pattern_overlay(imageData, options = {}) {
@@ -40,6 +43,7 @@ export function pattern_overlay(imageData, options = {}) {
     const offset_x = options.offset_x ?? 0;
     const offset_y = options.offset_y ?? 0;
     const opacity = options.opacity ?? 1.0;
+    const blendMode = options.blendMode ?? 'normal';
 
     const result = wasm.pattern_overlay_rgba_wasm(
         new Uint8Array(data.buffer),
@@ -51,7 +55,8 @@ export function pattern_overlay(imageData, options = {}) {
         scale,
         offset_x,
         offset_y,
-        opacity
+        opacity,
+        blendMode
     );
 
     return {
@@ -0,0 +1,1278 @@
+//! Photoshop layer style import.
+//!
+//! Converts the layer effects stored in a PSD layer record into a
+//! [`LayerStyle`], so imported styles can be rendered with
+//! [`apply_layer_style`](crate::layer_effects::layer_style::apply_layer_style).
+//!
+//! Supported tagged blocks (payload only, without signature, key and length):
+//! - `lfx2` - Object-based effects (Photoshop 6+), an action descriptor
+//! - `lrFX` - Legacy effects (Photoshop 5): shadows, glows, bevel, solid fill
+//! - `iOpa` - Fill opacity
+//! - `knko` - Knockout
+//! - `infx` - Blend interior effects as group
+//! - `lmgm` - Layer mask hides effects
+//!
+//! Style libraries (`.asl` preset files) are read with [`parse_asl`].
+//!
+//! Photoshop effect sizes span the whole soft edge while the effect cores
+//! take Gaussian sigmas; blur sizes are converted with [`PSD_SIZE_TO_SIGMA`].
+//! The "Scale Effects" factor of the layer is applied to all pixel sizes.
+//!
+//! Pattern overlays and bevel textures reference pattern data stored
+//! elsewhere in the file and are not imported.
+
+use std::fmt;
+
+#[cfg(feature = "python")]
+use ndarray::Array3;
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::filters::core::CanvasPolicy;
+
+use crate::filters::color_science::hsv_to_rgb;
+use crate::layer_effects::bevel_emboss::{BevelEmbossParams, BevelStyle, BevelTechnique};
+use crate::layer_effects::color_overlay::ColorOverlayParams;
+use crate::layer_effects::drop_shadow::DropShadowParams;
+use crate::layer_effects::gradient_overlay::{GradientOverlayParams, GradientStop};
+use crate::layer_effects::inner_glow::InnerGlowParams;
+use crate::layer_effects::inner_shadow::InnerShadowParams;
+#[cfg(feature = "python")]
+use crate::layer_effects::layer_style::{apply_layer_style, LayerStyleInputs};
+use crate::layer_effects::layer_style::{Knockout, LayerStyle};
+use crate::layer_effects::outer_glow::OuterGlowParams;
+use crate::layer_effects::satin::SatinParams;
+use crate::layer_effects::stroke::{StrokeParams, StrokePosition};
+
+/// Factor converting a Photoshop effect size in pixels to a Gaussian sigma.
+///
+/// The Gaussian falls off to nearly zero at 3 sigma, which is where
+/// Photoshop's soft edge ends.
+pub const PSD_SIZE_TO_SIGMA: f32 = 1.0 / 3.0;
+
+/// Maximum nesting depth of descriptors and lists.
+const MAX_DEPTH: usize = 32;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Error raised while decoding PSD effect data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PsdStyleError {
+    /// Data ended in the middle of a structure
+    UnexpectedEof,
+    /// Unsupported block or descriptor version
+    UnsupportedVersion(u32),
+    /// Unknown descriptor value type (OSType)
+    UnknownType(String),
+    /// Effect record without a valid '8BIM' signature
+    BadSignature(String),
+    /// Descriptors nested deeper than supported
+    TooDeep,
+}
+
+impl fmt::Display for PsdStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PsdStyleError::UnexpectedEof => write!(f, "unexpected end of effect data"),
+            PsdStyleError::UnsupportedVersion(v) => write!(f, "unsupported effect data version {}", v),
+            PsdStyleError::UnknownType(t) => write!(f, "unknown descriptor value type '{}'", t),
+            PsdStyleError::BadSignature(s) => write!(f, "bad effect signature '{}'", s),
+            PsdStyleError::TooDeep => write!(f, "descriptor nesting too deep"),
+        }
+    }
+}
+
+impl std::error::Error for PsdStyleError {}
+
+// ============================================================================
+// Action Descriptors
+// ============================================================================
+
+/// Value of a descriptor item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DescriptorValue {
+    Descriptor(Descriptor),
+    List(Vec<DescriptorValue>),
+    Double(f64),
+    /// Number with unit ("#Pxl", "#Prc", "#Ang", ...)
+    UnitFloat { unit: String, value: f64 },
+    Text(String),
+    Enum { type_id: String, value: String },
+    Integer(i32),
+    LargeInteger(i64),
+    Bool(bool),
+    Class(String),
+    Raw(Vec<u8>),
+}
+
+impl DescriptorValue {
+    /// Numeric value of doubles, unit floats and integers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DescriptorValue::Double(v) => Some(*v),
+            DescriptorValue::UnitFloat { value, .. } => Some(*value),
+            DescriptorValue::Integer(v) => Some(*v as f64),
+            DescriptorValue::LargeInteger(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_descriptor(&self) -> Option<&Descriptor> {
+        match self {
+            DescriptorValue::Descriptor(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+/// Photoshop action descriptor: a class id plus keyed values.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Descriptor {
+    pub name: String,
+    pub class_id: String,
+    pub items: Vec<(String, DescriptorValue)>,
+}
+
+impl Descriptor {
+    /// Look up an item by key (four-character codes keep their trailing spaces).
+    pub fn get(&self, key: &str) -> Option<&DescriptorValue> {
+        self.items.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn number(&self, key: &str) -> Option<f32> {
+        self.get(key).and_then(DescriptorValue::as_f64).map(|v| v as f32)
+    }
+
+    pub fn flag(&self, key: &str) -> Option<bool> {
+        match self.get(key) {
+            Some(DescriptorValue::Bool(b)) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn child(&self, key: &str) -> Option<&Descriptor> {
+        self.get(key).and_then(DescriptorValue::as_descriptor)
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(DescriptorValue::Text(t)) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Enumeration value with trailing padding spaces removed.
+    pub fn enum_value(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(DescriptorValue::Enum { value, .. }) => Some(value.trim_end()),
+            _ => None,
+        }
+    }
+
+    /// Descriptors contained in a list item.
+    fn descriptors(&self, key: &str) -> Vec<&Descriptor> {
+        match self.get(key) {
+            Some(DescriptorValue::List(items)) => items.iter().filter_map(DescriptorValue::as_descriptor).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Big-endian reader over PSD data.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PsdStyleError> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.data.len())
+            .ok_or(PsdStyleError::UnexpectedEof)?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Skip up to `n` bytes (stops at the end of the data).
+    fn skip(&mut self, n: usize) {
+        self.pos = self.pos.saturating_add(n).min(self.data.len());
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PsdStyleError> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, PsdStyleError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, PsdStyleError> {
+        Ok(u16::from_be_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, PsdStyleError> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, PsdStyleError> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, PsdStyleError> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, PsdStyleError> {
+        Ok(f64::from_be_bytes(self.array()?))
+    }
+
+    fn four_cc(&mut self) -> Result<String, PsdStyleError> {
+        Ok(String::from_utf8_lossy(self.take(4)?).into_owned())
+    }
+
+    /// Descriptor key: length-prefixed string, length 0 = four-character code.
+    fn key(&mut self) -> Result<String, PsdStyleError> {
+        let len = self.u32()? as usize;
+        let len = if len == 0 { 4 } else { len };
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    /// Unicode string: character count followed by UTF-16BE code units.
+    fn unicode(&mut self) -> Result<String, PsdStyleError> {
+        let count = self.u32()? as usize;
+        let bytes = self.take(count.checked_mul(2).ok_or(PsdStyleError::UnexpectedEof)?)?;
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+        Ok(String::from_utf16_lossy(&units).trim_end_matches('\0').to_string())
+    }
+
+    /// Legacy color structure: color space followed by four 16-bit components.
+    fn color(&mut self) -> Result<[f32; 3], PsdStyleError> {
+        let space = self.u16()?;
+        let mut c = [0f32; 4];
+        for v in c.iter_mut() {
+            *v = self.u16()? as f32 / 65535.0;
+        }
+        Ok(match space {
+            // RGB
+            0 => [c[0], c[1], c[2]],
+            // HSB
+            1 => {
+                let (r, g, b) = hsv_to_rgb(c[0], c[1], c[2]);
+                [r, g, b]
+            }
+            // CMYK, 0 = full ink
+            2 => [c[0] * c[3], c[1] * c[3], c[2] * c[3]],
+            // Grayscale, 0-10000 ink
+            8 => {
+                let v = 1.0 - (c[0] * 65535.0 / 10000.0).min(1.0);
+                [v, v, v]
+            }
+            _ => [0.0, 0.0, 0.0],
+        })
+    }
+}
+
+/// Parse an action descriptor (without the leading descriptor version).
+pub fn parse_descriptor(data: &[u8]) -> Result<Descriptor, PsdStyleError> {
+    read_descriptor(&mut Reader::new(data), 0)
+}
+
+/// Read a descriptor preceded by its version (always 16).
+fn read_versioned_descriptor(r: &mut Reader) -> Result<Descriptor, PsdStyleError> {
+    let version = r.u32()?;
+    if version != 16 {
+        return Err(PsdStyleError::UnsupportedVersion(version));
+    }
+    read_descriptor(r, 0)
+}
+
+fn read_descriptor(r: &mut Reader, depth: usize) -> Result<Descriptor, PsdStyleError> {
+    if depth > MAX_DEPTH {
+        return Err(PsdStyleError::TooDeep);
+    }
+    let name = r.unicode()?;
+    let class_id = r.key()?;
+    let count = r.u32()?;
+    let mut items = Vec::new();
+    for _ in 0..count {
+        let key = r.key()?;
+        let ostype = r.four_cc()?;
+        items.push((key, read_value(r, &ostype, depth)?));
+    }
+    Ok(Descriptor { name, class_id, items })
+}
+
+fn read_value(r: &mut Reader, ostype: &str, depth: usize) -> Result<DescriptorValue, PsdStyleError> {
+    if depth > MAX_DEPTH {
+        return Err(PsdStyleError::TooDeep);
+    }
+    Ok(match ostype {
+        "Objc" | "GlbO" => DescriptorValue::Descriptor(read_descriptor(r, depth + 1)?),
+        "VlLs" => {
+            let count = r.u32()?;
+            let mut items = Vec::new();
+            for _ in 0..count {
+                let item_type = r.four_cc()?;
+                items.push(read_value(r, &item_type, depth + 1)?);
+            }
+            DescriptorValue::List(items)
+        }
+        "doub" => DescriptorValue::Double(r.f64()?),
+        "UntF" => {
+            let unit = r.four_cc()?;
+            DescriptorValue::UnitFloat { unit, value: r.f64()? }
+        }
+        "UnFl" => {
+            let unit = r.four_cc()?;
+            let count = r.u32()?;
+            let mut items = Vec::new();
+            for _ in 0..count {
+                items.push(DescriptorValue::UnitFloat { unit: unit.clone(), value: r.f64()? });
+            }
+            DescriptorValue::List(items)
+        }
+        "TEXT" => DescriptorValue::Text(r.unicode()?),
+        "enum" => {
+            let type_id = r.key()?;
+            DescriptorValue::Enum { type_id, value: r.key()? }
+        }
+        "long" => DescriptorValue::Integer(r.i32()?),
+        "comp" => DescriptorValue::LargeInteger(r.i64()?),
+        "bool" => DescriptorValue::Bool(r.u8()? != 0),
+        "type" | "GlbC" => {
+            r.unicode()?;
+            DescriptorValue::Class(r.key()?)
+        }
+        "tdta" | "alis" => {
+            let len = r.u32()? as usize;
+            DescriptorValue::Raw(r.take(len)?.to_vec())
+        }
+        other => return Err(PsdStyleError::UnknownType(other.to_string())),
+    })
+}
+
+// ============================================================================
+// Effect Conversion
+// ============================================================================
+
+/// Document-wide light used by effects with "Use Global Light" enabled.
+///
+/// Stored in the PSD image resources 1037 (angle) and 1049 (altitude).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalLight {
+    /// Light angle in degrees
+    pub angle: f32,
+    /// Light altitude in degrees
+    pub altitude: f32,
+}
+
+impl Default for GlobalLight {
+    fn default() -> Self {
+        Self { angle: 120.0, altitude: 30.0 }
+    }
+}
+
+/// Offset of a shadow cast away from a light at `angle` degrees.
+fn shadow_offset(angle: f32, distance: f32) -> (f32, f32) {
+    let rad = angle.to_radians();
+    (-rad.cos() * distance, rad.sin() * distance)
+}
+
+/// Convert a color descriptor (RGBC, HSBC, Grsc, CMYC) to RGB 0.0-1.0.
+fn descriptor_color(color: &Descriptor) -> Option<[f32; 3]> {
+    let rgb = match color.class_id.as_str() {
+        "RGBC" => match (color.number("redFloat"), color.number("greenFloat"), color.number("blueFloat")) {
+            (Some(r), Some(g), Some(b)) => [r, g, b],
+            _ => [
+                color.number("Rd  ")? / 255.0,
+                color.number("Grn ")? / 255.0,
+                color.number("Bl  ")? / 255.0,
+            ],
+        },
+        "HSBC" => {
+            let (r, g, b) = hsv_to_rgb(
+                (color.number("H   ")? / 360.0).rem_euclid(1.0),
+                color.number("Strt")? / 100.0,
+                color.number("Brgh")? / 100.0,
+            );
+            [r, g, b]
+        }
+        "Grsc" => {
+            let v = 1.0 - color.number("Gry ")? / 100.0;
+            [v, v, v]
+        }
+        "CMYC" => {
+            let k = 1.0 - color.number("Blck")? / 100.0;
+            [
+                (1.0 - color.number("Cyn ")? / 100.0) * k,
+                (1.0 - color.number("Mgnt")? / 100.0) * k,
+                (1.0 - color.number("Ylw ")? / 100.0) * k,
+            ]
+        }
+        _ => return None,
+    };
+    Some(rgb.map(|v| v.clamp(0.0, 1.0)))
+}
+
+fn effect_color(effect: &Descriptor, key: &str, default: [f32; 3]) -> [f32; 3] {
+    effect.child(key).and_then(descriptor_color).unwrap_or(default)
+}
+
+fn effect_opacity(effect: &Descriptor) -> f32 {
+    (effect.number("Opct").unwrap_or(100.0) / 100.0).clamp(0.0, 1.0)
+}
+
+fn effect_angle(effect: &Descriptor, light: &GlobalLight) -> f32 {
+    if effect.flag("uglg").unwrap_or(false) {
+        light.angle
+    } else {
+        effect.number("lagl").unwrap_or(light.angle)
+    }
+}
+
+fn blend_mode_name(effect: &Descriptor) -> String {
+    match effect.enum_value("Md  ") {
+        Some("Mltp") => "multiply",
+        Some("Scrn") => "screen",
+        Some("Ovrl") => "overlay",
+        Some("Drkn") => "darken",
+        Some("Lghn") => "lighten",
+        Some("CDdg") => "color_dodge",
+        Some("CBrn") => "color_burn",
+        Some("linearDodge") => "linear_dodge",
+        Some("linearBurn") => "linear_burn",
+        Some("SftL") => "soft_light",
+        Some("HrdL") => "hard_light",
+        Some("Dfrn") => "difference",
+        Some("Xclu") => "exclusion",
+        _ => "normal",
+    }
+    .to_string()
+}
+
+/// Blend mode name from a legacy `lrFX` blend mode key.
+fn legacy_blend_mode_name(key: &str) -> String {
+    match key {
+        "mul " => "multiply",
+        "scrn" => "screen",
+        "over" => "overlay",
+        "dark" => "darken",
+        "lite" => "lighten",
+        "div " => "color_dodge",
+        "idiv" => "color_burn",
+        "lddg" => "linear_dodge",
+        "lbrn" => "linear_burn",
+        "sLit" => "soft_light",
+        "hLit" => "hard_light",
+        "diff" => "difference",
+        "smud" => "exclusion",
+        _ => "normal",
+    }
+    .to_string()
+}
+
+/// First enabled instance of an effect.
+///
+/// Newer files store every effect type as a list (`multi_key`) in addition
+/// to the single descriptor (`key`).
+fn enabled_effect<'d>(fx: &'d Descriptor, key: &str, multi_key: &str) -> Option<&'d Descriptor> {
+    fx.descriptors(multi_key)
+        .into_iter()
+        .chain(fx.child(key))
+        .find(|d| d.flag("enab").unwrap_or(true) && d.flag("present").unwrap_or(true))
+}
+
+/// Contour curve points (0.0-1.0) from a `ShpC` descriptor.
+fn contour_points(contour: &Descriptor) -> Vec<(f32, f32)> {
+    contour
+        .descriptors("Crv ")
+        .into_iter()
+        .filter_map(|p| Some((p.number("Hrzn")? / 255.0, p.number("Vrtc")? / 255.0)))
+        .collect()
+}
+
+fn gradient_stops(gradient: &Descriptor) -> Vec<GradientStop> {
+    gradient
+        .descriptors("Clrs")
+        .into_iter()
+        .map(|stop| {
+            let [r, g, b] = match stop.enum_value("Type") {
+                Some("FrgC") => [0.0, 0.0, 0.0],
+                Some("BckC") => [1.0, 1.0, 1.0],
+                _ => effect_color(stop, "Clr ", [0.0, 0.0, 0.0]),
+            };
+            GradientStop {
+                position: (stop.number("Lctn").unwrap_or(0.0) / 4096.0).clamp(0.0, 1.0),
+                r,
+                g,
+                b,
+            }
+        })
+        .collect()
+}
+
+fn clear_effects(style: &mut LayerStyle) {
+    style.drop_shadow = None;
+    style.inner_shadow = None;
+    style.outer_glow = None;
+    style.inner_glow = None;
+    style.bevel_emboss = None;
+    style.satin = None;
+    style.color_overlay = None;
+    style.gradient_overlay = None;
+    style.pattern_overlay = None;
+    style.stroke = None;
+}
+
+/// Replace the effects of `style` with those of an `lfx2` effects descriptor.
+fn apply_effects_descriptor(style: &mut LayerStyle, fx: &Descriptor, light: &GlobalLight) {
+    clear_effects(style);
+    if fx.flag("masterFXSwitch") == Some(false) {
+        return;
+    }
+
+    let scale = fx.number("Scl ").unwrap_or(100.0) / 100.0;
+    let px = |effect: &Descriptor, key: &str| effect.number(key).unwrap_or(0.0) * scale;
+    let percent = |effect: &Descriptor, key: &str| (effect.number(key).unwrap_or(0.0) / 100.0).clamp(0.0, 1.0);
+
+    if let Some(d) = enabled_effect(fx, "DrSh", "dropShadowMulti") {
+        let (offset_x, offset_y) = shadow_offset(effect_angle(d, light), px(d, "Dstn"));
+        style.drop_shadow = Some(DropShadowParams {
+            offset_x,
+            offset_y,
+            blur_radius: px(d, "blur") * PSD_SIZE_TO_SIGMA,
+            color: effect_color(d, "Clr ", [0.0, 0.0, 0.0]),
+            opacity: effect_opacity(d),
+            knockout: d.flag("layerConceals").unwrap_or(true),
+            ..Default::default()
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "IrSh", "innerShadowMulti") {
+        let (offset_x, offset_y) = shadow_offset(effect_angle(d, light), px(d, "Dstn"));
+        style.inner_shadow = Some(InnerShadowParams {
+            offset_x,
+            offset_y,
+            blur_radius: px(d, "blur") * PSD_SIZE_TO_SIGMA,
+            choke: percent(d, "Ckmt"),
+            color: effect_color(d, "Clr ", [0.0, 0.0, 0.0]),
+            opacity: effect_opacity(d),
+            ..Default::default()
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "OrGl", "outerGlowMulti") {
+        style.outer_glow = Some(OuterGlowParams {
+            radius: px(d, "blur") * PSD_SIZE_TO_SIGMA,
+            color: effect_color(d, "Clr ", OuterGlowParams::default().color),
+            opacity: effect_opacity(d),
+            spread: percent(d, "Ckmt"),
+            gradient: d.child("Grad").map(gradient_stops).unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "IrGl", "innerGlowMulti") {
+        style.inner_glow = Some(InnerGlowParams {
+            radius: px(d, "blur") * PSD_SIZE_TO_SIGMA,
+            color: effect_color(d, "Clr ", InnerGlowParams::default().color),
+            opacity: effect_opacity(d),
+            choke: percent(d, "Ckmt"),
+            ..Default::default()
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "ebbl", "bevelEmbossMulti") {
+        let use_global = d.flag("uglg").unwrap_or(false);
+        style.bevel_emboss = Some(BevelEmbossParams {
+            depth: d.number("srgR").unwrap_or(100.0) / 100.0,
+            size: px(d, "blur"),
+            soften: px(d, "Sftn") * PSD_SIZE_TO_SIGMA,
+            angle: effect_angle(d, light),
+            altitude: if use_global { light.altitude } else { d.number("Lald").unwrap_or(light.altitude) },
+            direction_up: d.enum_value("bvlD") != Some("Out"),
+            highlight_color: effect_color(d, "hglC", [1.0, 1.0, 1.0]),
+            highlight_opacity: percent(d, "hglO"),
+            shadow_color: effect_color(d, "sdwC", [0.0, 0.0, 0.0]),
+            shadow_opacity: percent(d, "sdwO"),
+            style: match d.enum_value("bvlS") {
+                Some("OtrB") => BevelStyle::OuterBevel,
+                Some("Embs") => BevelStyle::Emboss,
+                Some("PlEb") => BevelStyle::PillowEmboss,
+                Some("strokeEmboss") => BevelStyle::StrokeEmboss,
+                _ => BevelStyle::InnerBevel,
+            },
+            technique: match d.enum_value("bvlT") {
+                Some("PrBL") => BevelTechnique::ChiselHard,
+                Some("Slmt") => BevelTechnique::ChiselSoft,
+                _ => BevelTechnique::Smooth,
+            },
+            gloss_contour: d.child("TrnS").map(contour_points).unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "ChFX", "satinMulti") {
+        style.satin = Some(SatinParams {
+            color: effect_color(d, "Clr ", [0.0, 0.0, 0.0]),
+            opacity: effect_opacity(d),
+            angle: d.number("lagl").unwrap_or(19.0),
+            distance: px(d, "Dstn"),
+            size: px(d, "blur") * PSD_SIZE_TO_SIGMA,
+            invert: d.flag("Invr").unwrap_or(false),
+            contour: d.child("MpgS").map(contour_points).unwrap_or_default(),
+            blend_mode: blend_mode_name(d),
+            ..Default::default()
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "SoFi", "solidFillMulti") {
+        style.color_overlay = Some(ColorOverlayParams {
+            color: effect_color(d, "Clr ", [0.0, 0.0, 0.0]),
+            opacity: effect_opacity(d),
+            blend_mode: blend_mode_name(d),
+            // The "Color" blend mode tints while keeping luminosity
+            preserve_luminosity: d.enum_value("Md  ") == Some("Clr "),
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "GrFl", "gradientFillMulti") {
+        let offset = d.child("Ofst");
+        let offset_axis = |key: &str| offset.and_then(|o| o.number(key)).unwrap_or(0.0) / 100.0;
+        let gradient_scale = d.number("Scl ").unwrap_or(100.0) / 100.0;
+        style.gradient_overlay = Some(GradientOverlayParams {
+            stops: d.child("Grad").map(gradient_stops).unwrap_or_default(),
+            style: match d.enum_value("Type") {
+                Some("Rdl") => "radial",
+                Some("Angl") => "angle",
+                Some("Rflc") => "reflected",
+                Some("Dmnd") => "diamond",
+                _ => "linear",
+            }
+            .to_string(),
+            angle: d.number("Angl").unwrap_or(90.0),
+            scale_x: gradient_scale,
+            scale_y: gradient_scale,
+            offset_x: offset_axis("Hrzn"),
+            offset_y: offset_axis("Vrtc"),
+            reverse: d.flag("Rvrs").unwrap_or(false),
+            opacity: effect_opacity(d),
+            blend_mode: blend_mode_name(d),
+        });
+    }
+
+    if let Some(d) = enabled_effect(fx, "FrFX", "frameFXMulti") {
+        style.stroke = Some(StrokeParams {
+            width: px(d, "Sz  "),
+            color: effect_color(d, "Clr ", [0.0, 0.0, 0.0]),
+            opacity: effect_opacity(d),
+            position: match d.enum_value("Styl") {
+                Some("InsF") => StrokePosition::Inside,
+                Some("CtrF") => StrokePosition::Center,
+                _ => StrokePosition::Outside,
+            },
+            ..Default::default()
+        });
+    }
+}
+
+/// Convert an `lfx2` effects descriptor into a layer style.
+///
+/// Blending options (fill opacity, knockout, ...) keep their defaults; they
+/// are stored in separate tagged blocks, see [`apply_tagged_block`].
+pub fn layer_style_from_descriptor(fx: &Descriptor, light: &GlobalLight) -> LayerStyle {
+    let mut style = LayerStyle::default();
+    apply_effects_descriptor(&mut style, fx, light);
+    style
+}
+
+fn read_lfx2(style: &mut LayerStyle, data: &[u8], light: &GlobalLight) -> Result<(), PsdStyleError> {
+    let mut r = Reader::new(data);
+    let version = r.u32()?;
+    if version != 0 {
+        return Err(PsdStyleError::UnsupportedVersion(version));
+    }
+    let fx = read_versioned_descriptor(&mut r)?;
+    apply_effects_descriptor(style, &fx, light);
+    Ok(())
+}
+
+fn read_lrfx(style: &mut LayerStyle, data: &[u8], light: &GlobalLight) -> Result<(), PsdStyleError> {
+    let mut r = Reader::new(data);
+    let version = r.u16()?;
+    if version != 0 {
+        return Err(PsdStyleError::UnsupportedVersion(version as u32));
+    }
+    let count = r.u16()?;
+    clear_effects(style);
+
+    let mut visible = true;
+    for _ in 0..count {
+        let signature = r.four_cc()?;
+        if signature != "8BIM" {
+            return Err(PsdStyleError::BadSignature(signature));
+        }
+        let key = r.four_cc()?;
+        let size = r.u32()? as usize;
+        let mut e = Reader::new(r.take(size)?);
+
+        match key.as_str() {
+            "cmnS" => {
+                e.u32()?;
+                visible = e.u8()? != 0;
+            }
+            "dsdw" | "isdw" => {
+                e.u32()?;
+                let blur = e.u32()? as f32;
+                e.u32()?; // intensity
+                let angle = e.i32()? as f32;
+                let distance = e.i32()? as f32;
+                let color = e.color()?;
+                e.take(8)?; // blend mode
+                let enabled = e.u8()? != 0;
+                let angle = if e.u8()? != 0 { light.angle } else { angle };
+                let opacity = e.u8()? as f32 / 255.0;
+                if !enabled {
+                    continue;
+                }
+                let (offset_x, offset_y) = shadow_offset(angle, distance);
+                let blur_radius = blur * PSD_SIZE_TO_SIGMA;
+                if key == "dsdw" {
+                    // Legacy blocks have no knockout flag; Photoshop's default is on
+                    style.drop_shadow = Some(DropShadowParams {
+                        offset_x,
+                        offset_y,
+                        blur_radius,
+                        color,
+                        opacity,
+                        knockout: true,
+                        ..Default::default()
+                    });
+                } else {
+                    style.inner_shadow = Some(InnerShadowParams {
+                        offset_x,
+                        offset_y,
+                        blur_radius,
+                        choke: 0.0,
+                        color,
+                        opacity,
+                        ..Default::default()
+                    });
+                }
+            }
+            "oglw" | "iglw" => {
+                e.u32()?;
+                let radius = e.u32()? as f32 * PSD_SIZE_TO_SIGMA;
+                e.u32()?; // intensity
+                let color = e.color()?;
+                e.take(8)?; // blend mode
+                let enabled = e.u8()? != 0;
+                let opacity = e.u8()? as f32 / 255.0;
+                if !enabled {
+                    continue;
+                }
+                if key == "oglw" {
+                    style.outer_glow = Some(OuterGlowParams { radius, color, opacity, ..Default::default() });
+                } else {
+                    style.inner_glow = Some(InnerGlowParams { radius, color, opacity, ..Default::default() });
+                }
+            }
+            "bevl" => {
+                e.u32()?;
+                let angle = e.i32()? as f32;
+                let strength = e.u32()? as f32;
+                let size = (e.u32()? as f32).max(1.0);
+                e.take(16)?; // highlight and shadow blend modes
+                let highlight_color = e.color()?;
+                let shadow_color = e.color()?;
+                let bevel_style = e.u8()?;
+                let highlight_opacity = e.u8()? as f32 / 255.0;
+                let shadow_opacity = e.u8()? as f32 / 255.0;
+                let enabled = e.u8()? != 0;
+                let angle = if e.u8()? != 0 { light.angle } else { angle };
+                let direction_up = e.u8()? == 0;
+                if !enabled {
+                    continue;
+                }
+                style.bevel_emboss = Some(BevelEmbossParams {
+                    // Legacy strength is the bevel height in pixels
+                    depth: strength / size,
+                    size,
+                    angle,
+                    altitude: light.altitude,
+                    direction_up,
+                    highlight_color,
+                    highlight_opacity,
+                    shadow_color,
+                    shadow_opacity,
+                    style: match bevel_style {
+                        1 => BevelStyle::OuterBevel,
+                        3 => BevelStyle::Emboss,
+                        4 => BevelStyle::PillowEmboss,
+                        5 => BevelStyle::StrokeEmboss,
+                        _ => BevelStyle::InnerBevel,
+                    },
+                    ..BevelEmbossParams::default()
+                });
+            }
+            "sofi" => {
+                e.u32()?;
+                e.take(4)?; // blend mode signature
+                let key = e.four_cc()?;
+                let blend_mode = legacy_blend_mode_name(&key);
+                let color = e.color()?;
+                let opacity = e.u8()? as f32 / 255.0;
+                if e.u8()? != 0 {
+                    let preserve_luminosity = key == "colr";
+                    style.color_overlay = Some(ColorOverlayParams { color, opacity, blend_mode, preserve_luminosity });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !visible {
+        clear_effects(style);
+    }
+    Ok(())
+}
+
+/// Parse an `lfx2` (object-based effects) block into a layer style.
+pub fn parse_lfx2(data: &[u8], light: &GlobalLight) -> Result<LayerStyle, PsdStyleError> {
+    let mut style = LayerStyle::default();
+    read_lfx2(&mut style, data, light)?;
+    Ok(style)
+}
+
+/// Parse an `lrFX` (legacy effects) block into a layer style.
+pub fn parse_lrfx(data: &[u8], light: &GlobalLight) -> Result<LayerStyle, PsdStyleError> {
+    let mut style = LayerStyle::default();
+    read_lrfx(&mut style, data, light)?;
+    Ok(style)
+}
+
+/// Apply one tagged block of a PSD layer record to a layer style.
+///
+/// Effect blocks (`lfx2`, `lrFX`) replace all effects of the style; files
+/// that contain both should pass `lrFX` first so `lfx2` wins. Blocks that
+/// do not affect the layer style are ignored.
+///
+/// # Arguments
+/// * `style` - Style to update
+/// * `key` - Four-character block key
+/// * `data` - Block payload
+/// * `light` - Document global light
+pub fn apply_tagged_block(
+    style: &mut LayerStyle,
+    key: &str,
+    data: &[u8],
+    light: &GlobalLight,
+) -> Result<(), PsdStyleError> {
+    let mut r = Reader::new(data);
+    match key {
+        "lfx2" => read_lfx2(style, data, light)?,
+        "lrFX" => read_lrfx(style, data, light)?,
+        "iOpa" => style.fill_opacity = r.u8()? as f32 / 255.0,
+        "knko" => {
+            style.knockout = match r.u8()? {
+                0 => Knockout::None,
+                2 => Knockout::Deep,
+                _ => Knockout::Shallow,
+            }
+        }
+        "infx" => style.blend_interior_effects = r.u8()? != 0,
+        "lmgm" => style.layer_mask_hides_effects = r.u8()? != 0,
+        _ => {}
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Style Libraries
+// ============================================================================
+
+/// Layer style preset from a style library.
+#[derive(Debug, Clone)]
+pub struct NamedLayerStyle {
+    /// Preset name as shown in the Styles panel
+    pub name: String,
+    /// Preset identifier (UUID)
+    pub id: String,
+    pub style: LayerStyle,
+}
+
+/// Parse an Adobe Style Library (.asl) file into its presets.
+///
+/// Each preset keeps its effects plus the layer and fill opacity of its
+/// blending options. Patterns embedded in the library are skipped, so
+/// pattern overlays and bevel textures are not imported.
+///
+/// # Arguments
+/// * `data` - Contents of the .asl file
+/// * `light` - Global light used by effects with "Use Global Light" enabled
+pub fn parse_asl(data: &[u8], light: &GlobalLight) -> Result<Vec<NamedLayerStyle>, PsdStyleError> {
+    let mut r = Reader::new(data);
+    let version = r.u16()?;
+    if version != 2 {
+        return Err(PsdStyleError::UnsupportedVersion(version as u32));
+    }
+    let signature = r.four_cc()?;
+    if signature != "8BSL" {
+        return Err(PsdStyleError::BadSignature(signature));
+    }
+    r.u16()?; // pattern section version
+    let pattern_len = r.u32()? as usize;
+    r.take(pattern_len)?;
+
+    let count = r.u32()?;
+    let mut presets = Vec::new();
+    for _ in 0..count {
+        let len = r.u32()? as usize;
+        let mut block = Reader::new(r.take(len)?);
+        r.skip((4 - len % 4) % 4);
+
+        let info = read_versioned_descriptor(&mut block)?;
+        let body = read_versioned_descriptor(&mut block)?;
+
+        let mut style = LayerStyle::default();
+        if let Some(fx) = body.child("Lefx") {
+            apply_effects_descriptor(&mut style, fx, light);
+        }
+        if let Some(blend) = body.child("blendOptions") {
+            if let Some(opacity) = blend.number("Opct") {
+                style.opacity = (opacity / 100.0).clamp(0.0, 1.0);
+            }
+            if let Some(fill) = blend.number("fillOpacity") {
+                style.fill_opacity = (fill / 100.0).clamp(0.0, 1.0);
+            }
+        }
+
+        presets.push(NamedLayerStyle {
+            name: info.text("Nm  ").unwrap_or_default().to_string(),
+            id: info.text("Idnt").unwrap_or_default().to_string(),
+            style,
+        });
+    }
+    Ok(presets)
+}
+
+/// Build a layer style from the tagged blocks of a PSD layer record.
+#[cfg(feature = "python")]
+pub(crate) fn style_from_blocks(
+    blocks: &[(String, Vec<u8>)],
+    opacity: f32,
+    global_angle: f32,
+    global_altitude: f32,
+) -> PyResult<LayerStyle> {
+    let light = GlobalLight { angle: global_angle, altitude: global_altitude };
+    let mut style = LayerStyle { opacity, ..LayerStyle::default() };
+    // Legacy effects first so object-based effects take precedence
+    let ordered = blocks.iter().filter(|(k, _)| k == "lrFX").chain(blocks.iter().filter(|(k, _)| k != "lrFX"));
+    for (key, data) in ordered {
+        apply_tagged_block(&mut style, key, data, &light).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    }
+    Ok(style)
+}
+
+/// Render a layer with the layer style stored in PSD tagged blocks (u8).
+///
+/// # Arguments
+/// * `image` - Layer RGBA image
+/// * `blocks` - Tagged blocks of the layer record as (key, payload) pairs
+/// * `opacity` - Layer opacity from the layer record (0.0-1.0)
+/// * `global_angle` - Document global light angle in degrees
+/// * `global_altitude` - Document global light altitude in degrees
+/// * `canvas` - Output canvas: "grow" (fit the effects) or "clip" (input size)
+/// * `canvas_size` - Explicit output (width, height); overrides `canvas`
+/// * `anchor` - Layer position in an explicit canvas ("center", "top_left", ...)
+///
+/// # Returns
+/// RGBA image on the chosen canvas, by default expanded to fit the effects
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, blocks, opacity=1.0, global_angle=120.0, global_altitude=30.0, canvas="grow", canvas_size=None, anchor="center"))]
+pub fn psd_layer_style_rgba<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    blocks: Vec<(String, Vec<u8>)>,
+    opacity: f32,
+    global_angle: f32,
+    global_altitude: f32,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let mut style = style_from_blocks(&blocks, opacity, global_angle, global_altitude)?;
+    style.canvas = CanvasPolicy::parse(canvas, canvas_size, anchor);
+    let input: Array3<f32> = image.as_array().mapv(|v| v as f32 / 255.0);
+    let result = apply_layer_style(&input, &style, &LayerStyleInputs::default());
+    Ok(result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py))
+}
+
+/// Render a layer with the layer style stored in PSD tagged blocks (f32).
+///
+/// Same as psd_layer_style_rgba but for f32 images (0.0-1.0 range).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, blocks, opacity=1.0, global_angle=120.0, global_altitude=30.0, canvas="grow", canvas_size=None, anchor="center"))]
+pub fn psd_layer_style_rgba_f32<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    blocks: Vec<(String, Vec<u8>)>,
+    opacity: f32,
+    global_angle: f32,
+    global_altitude: f32,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let mut style = style_from_blocks(&blocks, opacity, global_angle, global_altitude)?;
+    style.canvas = CanvasPolicy::parse(canvas, canvas_size, anchor);
+    let input = image.as_array().to_owned();
+    Ok(apply_layer_style(&input, &style, &LayerStyleInputs::default()).into_pyarray(py))
+}
+
+#[cfg(feature = "python")]
+fn asl_preset(data: &[u8], index: usize, global_angle: f32, global_altitude: f32) -> PyResult<LayerStyle> {
+    let light = GlobalLight { angle: global_angle, altitude: global_altitude };
+    let mut presets = parse_asl(data, &light).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    if index >= presets.len() {
+        return Err(PyValueError::new_err(format!(
+            "style index {} out of range ({} styles)",
+            index,
+            presets.len()
+        )));
+    }
+    Ok(presets.swap_remove(index).style)
+}
+
+/// List the preset names of an Adobe Style Library (.asl) file.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn asl_style_names(data: Vec<u8>) -> PyResult<Vec<String>> {
+    let presets = parse_asl(&data, &GlobalLight::default()).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(presets.into_iter().map(|p| p.name).collect())
+}
+
+/// Render a layer with a preset from an Adobe Style Library (u8).
+///
+/// # Arguments
+/// * `image` - Layer RGBA image
+/// * `data` - Contents of the .asl file
+/// * `index` - Preset index, in the order of `asl_style_names`
+/// * `global_angle` - Global light angle in degrees
+/// * `global_altitude` - Global light altitude in degrees
+/// * `canvas`, `canvas_size`, `anchor` - Output canvas, as for `psd_layer_style_rgba`
+///
+/// # Returns
+/// RGBA image on the chosen canvas, by default expanded to fit the effects
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, data, index, global_angle=120.0, global_altitude=30.0, canvas="grow", canvas_size=None, anchor="center"))]
+pub fn asl_layer_style_rgba<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    data: Vec<u8>,
+    index: usize,
+    global_angle: f32,
+    global_altitude: f32,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let mut style = asl_preset(&data, index, global_angle, global_altitude)?;
+    style.canvas = CanvasPolicy::parse(canvas, canvas_size, anchor);
+    let input: Array3<f32> = image.as_array().mapv(|v| v as f32 / 255.0);
+    let result = apply_layer_style(&input, &style, &LayerStyleInputs::default());
+    Ok(result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py))
+}
+
+/// Render a layer with a preset from an Adobe Style Library (f32).
+///
+/// Same as asl_layer_style_rgba but for f32 images (0.0-1.0 range).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, data, index, global_angle=120.0, global_altitude=30.0, canvas="grow", canvas_size=None, anchor="center"))]
+pub fn asl_layer_style_rgba_f32<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    data: Vec<u8>,
+    index: usize,
+    global_angle: f32,
+    global_altitude: f32,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let mut style = asl_preset(&data, index, global_angle, global_altitude)?;
+    style.canvas = CanvasPolicy::parse(canvas, canvas_size, anchor);
+    let input = image.as_array().to_owned();
+    Ok(apply_layer_style(&input, &style, &LayerStyleInputs::default()).into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal descriptor writer for building test data.
+    struct Writer(Vec<u8>);
+
+    impl Writer {
+        fn new() -> Self {
+            Writer(Vec::new())
+        }
+        fn u32(&mut self, v: u32) -> &mut Self {
+            self.0.extend_from_slice(&v.to_be_bytes());
+            self
+        }
+        fn raw(&mut self, bytes: &[u8]) -> &mut Self {
+            self.0.extend_from_slice(bytes);
+            self
+        }
+        fn key(&mut self, key: &str) -> &mut Self {
+            if key.len() == 4 {
+                self.u32(0).raw(key.as_bytes())
+            } else {
+                self.u32(key.len() as u32).raw(key.as_bytes())
+            }
+        }
+        fn begin(&mut self, class_id: &str, count: u32) -> &mut Self {
+            self.u32(0).key(class_id).u32(count)
+        }
+        fn object(&mut self, key: &str, class_id: &str, count: u32) -> &mut Self {
+            self.key(key).raw(b"Objc").begin(class_id, count)
+        }
+        fn unit(&mut self, key: &str, unit: &str, v: f64) -> &mut Self {
+            self.key(key).raw(b"UntF").raw(unit.as_bytes()).raw(&v.to_be_bytes())
+        }
+        fn double(&mut self, key: &str, v: f64) -> &mut Self {
+            self.key(key).raw(b"doub").raw(&v.to_be_bytes())
+        }
+        fn boolean(&mut self, key: &str, v: bool) -> &mut Self {
+            self.key(key).raw(b"bool").raw(&[v as u8])
+        }
+        fn text(&mut self, key: &str, v: &str) -> &mut Self {
+            self.key(key).raw(b"TEXT").u32(v.len() as u32);
+            for unit in v.encode_utf16() {
+                self.raw(&unit.to_be_bytes());
+            }
+            self
+        }
+        fn rgb(&mut self, key: &str, r: f64, g: f64, b: f64) -> &mut Self {
+            self.object(key, "RGBC", 3).double("Rd  ", r).double("Grn ", g).double("Bl  ", b)
+        }
+    }
+
+    fn lfx2_with_drop_shadow(enabled: bool, master: bool) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.u32(0).u32(16).begin("null", 3);
+        w.unit("Scl ", "#Prc", 100.0);
+        w.boolean("masterFXSwitch", master);
+        w.object("DrSh", "DrSh", 6);
+        w.boolean("enab", enabled);
+        w.rgb("Clr ", 255.0, 0.0, 0.0);
+        w.unit("Opct", "#Prc", 50.0);
+        w.unit("lagl", "#Ang", 90.0);
+        w.unit("Dstn", "#Pxl", 10.0);
+        w.unit("blur", "#Pxl", 6.0);
+        w.0
+    }
+
+    #[test]
+    fn test_lfx2_drop_shadow() {
+        let style = parse_lfx2(&lfx2_with_drop_shadow(true, true), &GlobalLight::default()).unwrap();
+        let shadow = style.drop_shadow.expect("drop shadow imported");
+        // Light from the top casts the shadow downward
+        assert!(shadow.offset_x.abs() < 1e-4);
+        assert!((shadow.offset_y - 10.0).abs() < 1e-4);
+        assert!((shadow.blur_radius - 2.0).abs() < 1e-4);
+        assert_eq!(shadow.color, [1.0, 0.0, 0.0]);
+        assert!((shadow.opacity - 0.5).abs() < 1e-6);
+        assert!(style.inner_shadow.is_none());
+    }
+
+    #[test]
+    fn test_lfx2_disabled_effects() {
+        let light = GlobalLight::default();
+        assert!(parse_lfx2(&lfx2_with_drop_shadow(false, true), &light).unwrap().drop_shadow.is_none());
+        assert!(parse_lfx2(&lfx2_with_drop_shadow(true, false), &light).unwrap().drop_shadow.is_none());
+    }
+
+    #[test]
+    fn test_truncated_data() {
+        let data = lfx2_with_drop_shadow(true, true);
+        let result = parse_lfx2(&data[..data.len() - 3], &GlobalLight::default());
+        assert_eq!(result.unwrap_err(), PsdStyleError::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_lrfx_solid_fill() {
+        let mut w = Writer::new();
+        w.raw(&0u16.to_be_bytes()).raw(&1u16.to_be_bytes());
+        w.raw(b"8BIMsofi").u32(34).u32(2).raw(b"8BIMnorm");
+        // RGB color: space, then 16-bit components
+        w.raw(&[0, 0, 0, 0, 0xFF, 0xFF, 0, 0, 0, 0]);
+        w.raw(&[255, 1]).raw(&[0; 10]);
+        let style = parse_lrfx(&w.0, &GlobalLight::default()).unwrap();
+        let overlay = style.color_overlay.expect("solid fill imported");
+        assert_eq!(overlay.color, [0.0, 1.0, 0.0]);
+        assert!((overlay.opacity - 1.0).abs() < 1e-6);
+        assert_eq!(overlay.blend_mode, "normal");
+    }
+
+    #[test]
+    fn test_blending_option_blocks() {
+        let light = GlobalLight::default();
+        let mut style = LayerStyle::default();
+        apply_tagged_block(&mut style, "iOpa", &[0], &light).unwrap();
+        apply_tagged_block(&mut style, "knko", &[2, 0, 0, 0], &light).unwrap();
+        apply_tagged_block(&mut style, "lmgm", &[1, 0, 0, 0], &light).unwrap();
+        apply_tagged_block(&mut style, "luni", &[1, 2, 3], &light).unwrap();
+        assert_eq!(style.fill_opacity, 0.0);
+        assert_eq!(style.knockout, Knockout::Deep);
+        assert!(style.layer_mask_hides_effects);
+        assert!(!style.blend_interior_effects);
+    }
+
+    #[test]
+    fn test_asl_presets() {
+        let mut block = Writer::new();
+        block.u32(16).begin("null", 2).text("Nm  ", "Red Shadow").text("Idnt", "1234");
+        block.u32(16).begin("Styl", 2);
+        block.object("blendOptions", "blendOptions", 1).unit("fillOpacity", "#Prc", 40.0);
+        block.object("Lefx", "Lefx", 1).object("DrSh", "DrSh", 2);
+        block.boolean("enab", true).rgb("Clr ", 255.0, 0.0, 0.0);
+
+        let mut w = Writer::new();
+        w.raw(&2u16.to_be_bytes()).raw(b"8BSL").raw(&3u16.to_be_bytes()).u32(0);
+        w.u32(1).u32(block.0.len() as u32).raw(&block.0);
+        w.raw(&vec![0; (4 - block.0.len() % 4) % 4]);
+
+        let presets = parse_asl(&w.0, &GlobalLight::default()).unwrap();
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].name, "Red Shadow");
+        assert_eq!(presets[0].id, "1234");
+        assert!((presets[0].style.fill_opacity - 0.4).abs() < 1e-6);
+        assert_eq!(presets[0].style.drop_shadow.as_ref().unwrap().color, [1.0, 0.0, 0.0]);
+
+        assert!(matches!(parse_asl(b"\x00\x02XXXX", &GlobalLight::default()), Err(PsdStyleError::BadSignature(_))));
+    }
+}
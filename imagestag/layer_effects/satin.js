@@ -23,6 +23,9 @@ export { initWasm };
  * @param {number} [options.distance=11] - Distance in pixels
  * @param {number} [options.size=14] - Size (blur amount)
  * @param {boolean} [options.invert=false] - Invert the effect
+ * @param {Array<Array<number>>} [options.contour=[]] - Contour points [[x, y], ...] (0.0-1.0), empty = linear
+ * @param {string} [options.blendMode='normal'] - Blend mode (see color_overlay)
+ * @param {string} [options.quality='exact'] - Blur accuracy; 'fast' for interactive previews
  * @returns {Object} - Result with same dimensions {data, width, height, channels}
  */
 export function satin(imageData, options = {}) {
@@ -33,26 +36,9 @@ export function satin(imageData, options = {}) {
         throw new Error('Satin requires RGBA images (4 channels)');
     }
 
-    const color = options.color ?? [0, 0, 0];
-    const opacity = options.opacity ?? 0.5;
-    const angle = options.angle ?? 19;
-    const distance = options.distance ?? 11;
-    const size = options.size ?? 14;
-    const invert = options.invert ?? false;
-
-    const result = wasm.satin_rgba_wasm(
-        new Uint8Array(data.buffer),
-        width,
-        height,
-        color[0],
-        color[1],
-        color[2],
-        opacity,
-        angle,
-        distance,
-        size,
-        invert
-    );
+    const opts = satinOptions(options);
+    const result = wasm.satin_rgba_options_wasm(new Uint8Array(data.buffer), width, height, opts);
+    opts.free();
 
     return {
         data: new Uint8ClampedArray(result.buffer),
@@ -62,7 +48,44 @@ export function satin(imageData, options = {}) {
     };
 }
 
+/**
+ * Apply satin effect to a float32 RGBA image (values 0.0-1.0).
+ * @param {Object} imageData - {data: Float32Array, width, height, channels: 4}
+ * @param {Object} options - Same options as satin()
+ * @returns {Object} - Result {data: Float32Array, width, height, channels}
+ */
+export function satin_f32(imageData, options = {}) {
+    const { data, width, height } = imageData;
+    if ((imageData.channels || 4) !== 4) {
+        throw new Error('Satin requires RGBA images (4 channels)');
+    }
+
+    const opts = satinOptions(options);
+    const result = wasm.satin_rgba_f32_wasm(data, width, height, opts);
+    opts.free();
+
+    return { data: result, width, height, channels: 4 };
+}
+
+function satinOptions(options) {
+    const color = options.color ?? [0, 0, 0];
+    return Object.assign(new wasm.SatinOptions(), {
+        color_r: color[0],
+        color_g: color[1],
+        color_b: color[2],
+        opacity: options.opacity ?? 0.5,
+        angle: options.angle ?? 19,
+        distance: options.distance ?? 11,
+        size: options.size ?? 14,
+        invert: options.invert ?? false,
+        contour: new Float32Array((options.contour ?? []).flat()),
+        blend_mode: options.blendMode ?? 'normal',
+        fast_blur: options.quality === 'fast',
+    });
+}
+
 export default {
     initWasm,
-    satin
+    satin,
+    satin_f32
 };
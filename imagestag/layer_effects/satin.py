@@ -7,7 +7,7 @@ of the layer alpha channel.
 SVG Export: 0% fidelity (no SVG equivalent).
 """
 
-from typing import Tuple, Union, Dict, Any, Optional, ClassVar
+from typing import List, Tuple, Union, Dict, Any, Optional, ClassVar
 import numpy as np
 
 from pydantic import Field, model_validator
@@ -48,6 +48,10 @@ class Satin(LayerEffect):
     distance: float = Field(default=11.0)
     size: float = Field(default=14.0)
     invert: bool = Field(default=False)
+    # Contour (input, output) points 0.0-1.0 shaping the satin falloff, None = linear
+    contour: Optional[List[Tuple[float, float]]] = Field(default=None)
+    # Blur accuracy: 'exact', or 'fast' for interactive previews
+    quality: str = Field(default='exact')
 
     # Internal: parsed RGB tuple (not serialized)
     _color_rgb: Optional[Tuple[int, int, int]] = None
@@ -155,6 +159,9 @@ class Satin(LayerEffect):
                 float(self.distance),
                 float(self.size),
                 bool(self.invert),
+                self.contour,
+                self.blend_mode,
+                self.quality,
             )
         else:
             result = imagestag_rust.satin_rgba(
@@ -165,6 +172,9 @@ class Satin(LayerEffect):
                 float(self.distance),
                 float(self.size),
                 bool(self.invert),
+                self.contour,
+                self.blend_mode,
+                self.quality,
             )
 
         return EffectResult(
@@ -190,5 +200,5 @@ class Satin(LayerEffect):
         return (
             f"Satin(color={self.color}, colorOpacity={self.color_opacity}, "
             f"angle={self.angle}, distance={self.distance}, "
-            f"size={self.size}, invert={self.invert})"
+            f"size={self.size}, invert={self.invert}, blend_mode={self.blend_mode})"
         )
@@ -2,19 +2,131 @@
 //!
 //! Creates a silky, satiny interior shading by compositing shifted and blurred
 //! copies of the layer alpha channel.
+//!
+//! Python (`satin_rgba`, `satin_rgba_f32`) and WASM (`satin_rgba_wasm`,
+//! `satin_rgba_f32_wasm`) all render through [`satin_f32`].
 
-use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array2, Array3, ArrayView3};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::filters::core::blur_alpha_f32;
+#[cfg(feature = "python")]
+use crate::filters::blur::parse_blur_quality;
+use crate::filters::blur_wasm::BlurQuality;
+use crate::filters::core::{blend_over_f32, blur_alpha_quality_f32};
+use crate::filters::levels_curves::pchip_interpolate;
+use crate::layer_effects::color_overlay::blend_channel;
+
+/// Parameters for the satin effect.
+#[derive(Debug, Clone)]
+pub struct SatinParams {
+    /// Satin color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Effect opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Direction angle in degrees
+    pub angle: f32,
+    /// Offset distance in pixels
+    pub distance: f32,
+    /// Blur radius
+    pub size: f32,
+    /// Whether to invert the effect
+    pub invert: bool,
+    /// Contour control points (input, output), 0.0-1.0. Empty = linear.
+    pub contour: Vec<(f32, f32)>,
+    /// Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+    pub blend_mode: String,
+    /// Blur accuracy; `Fast` for interactive previews
+    pub quality: BlurQuality,
+}
+
+impl Default for SatinParams {
+    fn default() -> Self {
+        Self {
+            color: [0.0, 0.0, 0.0],
+            opacity: 0.5,
+            angle: 19.0,
+            distance: 11.0,
+            size: 14.0,
+            invert: false,
+            contour: Vec::new(),
+            blend_mode: "normal".to_string(),
+            quality: BlurQuality::Exact,
+        }
+    }
+}
+
+/// Compute the satin coverage from a layer alpha mask.
+///
+/// # Returns
+/// Satin alpha (0.0-1.0) with the opacity applied, clipped to `alpha`
+pub fn satin_mask(alpha: &Array2<f32>, params: &SatinParams) -> Array2<f32> {
+    let (height, width) = alpha.dim();
+
+    let angle_rad = params.angle.to_radians();
+    let dx = (angle_rad.cos() * params.distance).round() as isize;
+    let dy = (-angle_rad.sin() * params.distance).round() as isize;
+
+    let shifted = |sign: isize| {
+        Array2::from_shape_fn((height, width), |(y, x)| {
+            let sx = (x as isize + sign * dx).clamp(0, width as isize - 1) as usize;
+            let sy = (y as isize + sign * dy).clamp(0, height as isize - 1) as usize;
+            alpha[[sy, sx]]
+        })
+    };
+    let blurred_a = blur_alpha_quality_f32(&shifted(1), params.size, params.quality);
+    let blurred_b = blur_alpha_quality_f32(&shifted(-1), params.size, params.quality);
+
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let diff = (blurred_a[[y, x]] - blurred_b[[y, x]]).abs().min(1.0);
+        let shaped = if params.contour.len() < 2 { diff } else { pchip_interpolate(&params.contour, diff).clamp(0.0, 1.0) };
+        let mask_val = if params.invert { 1.0 - shaped } else { shaped };
+        mask_val * alpha[[y, x]] * params.opacity
+    })
+}
+
+/// Apply satin to an RGBA f32 image.
+///
+/// The satin color is blended with the layer content using the blend mode,
+/// then composited over it with the satin coverage.
+pub fn satin_f32(input: ArrayView3<f32>, params: &SatinParams) -> Array3<f32> {
+    let alpha = input.index_axis(ndarray::Axis(2), 3).to_owned();
+    let mask = satin_mask(&alpha, params);
+    let (height, width) = alpha.dim();
+    let mut result = input.to_owned();
+    for y in 0..height {
+        for x in 0..width {
+            let satin_a = mask[[y, x]];
+            if alpha[[y, x]] <= 0.0 || satin_a <= 0.0 {
+                continue;
+            }
+            let mut px = [result[[y, x, 0]], result[[y, x, 1]], result[[y, x, 2]], result[[y, x, 3]]];
+            let color = [0, 1, 2].map(|c| blend_channel(&params.blend_mode, px[c], params.color[c]));
+            blend_over_f32(&mut px, color[0], color[1], color[2], satin_a.min(1.0));
+            for (c, v) in px.into_iter().enumerate() {
+                result[[y, x, c]] = v;
+            }
+        }
+    }
+    result
+}
+
+/// Apply satin to an RGBA u8 image.
+pub fn satin_u8(input: ArrayView3<u8>, params: &SatinParams) -> Array3<u8> {
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    satin_f32(input_f32.view(), params).mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8)
+}
 
 /// Apply satin effect to RGBA u8 image.
 ///
 /// Creates silky interior shading by:
 /// 1. Creating two offset copies of the alpha (at +angle and -angle)
 /// 2. Blurring both copies
-/// 3. Computing the absolute difference
+/// 3. Computing the absolute difference, shaped by the contour
 /// 4. Optionally inverting the result
 /// 5. Masking with original alpha (only inside the shape)
 ///
@@ -26,210 +138,122 @@ use crate::filters::core::blur_alpha_f32;
 /// * `distance` - Offset distance in pixels
 /// * `size` - Blur radius
 /// * `invert` - Whether to invert the effect
+/// * `contour` - Optional list of (input, output) curve points, 0.0-1.0
+/// * `blend_mode` - Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+/// * `quality` - Blur accuracy: "exact" or "fast" (for interactive previews)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, color=(0, 0, 0), opacity=0.5, angle=19.0, distance=11.0, size=14.0, invert=false))]
+#[pyo3(signature = (image, color=(0, 0, 0), opacity=0.5, angle=19.0, distance=11.0, size=14.0, invert=false, contour=None, blend_mode="normal", quality="exact"))]
 pub fn satin_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     color: (u8, u8, u8),
     opacity: f32,
     angle: f32,
     distance: f32,
     size: f32,
     invert: bool,
-) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
-
-    // Calculate offset direction
-    let angle_rad = angle.to_radians();
-    let dx = (angle_rad.cos() * distance).round() as isize;
-    let dy = (-angle_rad.sin() * distance).round() as isize;
-
-    // Create offset copy A (positive direction)
-    let mut offset_a = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let sx = (x as isize + dx).clamp(0, width as isize - 1) as usize;
-            let sy = (y as isize + dy).clamp(0, height as isize - 1) as usize;
-            offset_a[[y, x]] = alpha[[sy, sx]];
-        }
-    }
-
-    // Create offset copy B (negative direction)
-    let mut offset_b = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let sx = (x as isize - dx).clamp(0, width as isize - 1) as usize;
-            let sy = (y as isize - dy).clamp(0, height as isize - 1) as usize;
-            offset_b[[y, x]] = alpha[[sy, sx]];
-        }
-    }
-
-    // Blur both copies
-    let blurred_a = blur_alpha_f32(&offset_a, size);
-    let blurred_b = blur_alpha_f32(&offset_b, size);
-
-    // Compute satin mask: absolute difference
-    let mut satin_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let diff = (blurred_a[[y, x]] - blurred_b[[y, x]]).abs();
-            // Apply inversion if requested
-            let mask_val = if invert { 1.0 - diff } else { diff };
-            // Mask with original alpha (only inside the shape)
-            satin_mask[[y, x]] = mask_val * alpha[[y, x]];
-        }
-    }
-
-    // Create result - start with original
-    let mut result = input_f32.clone();
-
-    let satin_r = color.0 as f32 / 255.0;
-    let satin_g = color.1 as f32 / 255.0;
-    let satin_b = color.2 as f32 / 255.0;
-
-    // Apply satin effect using multiply-like blending
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = alpha[[y, x]];
-            if orig_a <= 0.0 {
-                continue;
-            }
-
-            let satin_a = satin_mask[[y, x]] * opacity;
-            if satin_a > 0.0 {
-                // Blend satin color over original
-                let out_a = satin_a + result[[y, x, 3]] * (1.0 - satin_a);
-                if out_a > 0.0 {
-                    result[[y, x, 0]] = (satin_r * satin_a + result[[y, x, 0]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 1]] = (satin_g * satin_a + result[[y, x, 1]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 2]] = (satin_b * satin_a + result[[y, x, 2]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 3]] = out_a;
-                }
-            }
-        }
-    }
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    contour: Option<Vec<(f32, f32)>>,
+    blend_mode: &str,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let params = SatinParams {
+        color: [color.0 as f32 / 255.0, color.1 as f32 / 255.0, color.2 as f32 / 255.0],
+        opacity,
+        angle,
+        distance,
+        size,
+        invert,
+        contour: contour.unwrap_or_default(),
+        blend_mode: blend_mode.to_string(),
+        quality: parse_blur_quality(quality)?,
+    };
+    Ok(satin_u8(image.as_array(), &params).into_pyarray(py))
 }
 
 /// Apply satin effect to RGBA f32 image.
 ///
 /// Same as satin_rgba but for f32 images (0.0-1.0 range).
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, color=(0.0, 0.0, 0.0), opacity=0.5, angle=19.0, distance=11.0, size=14.0, invert=false))]
+#[pyo3(signature = (image, color=(0.0, 0.0, 0.0), opacity=0.5, angle=19.0, distance=11.0, size=14.0, invert=false, contour=None, blend_mode="normal", quality="exact"))]
 pub fn satin_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     color: (f32, f32, f32),
     opacity: f32,
     angle: f32,
     distance: f32,
     size: f32,
     invert: bool,
-) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-
-    // Clone input
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
+    contour: Option<Vec<(f32, f32)>>,
+    blend_mode: &str,
+    quality: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let params = SatinParams {
+        color: [color.0, color.1, color.2],
+        opacity,
+        angle,
+        distance,
+        size,
+        invert,
+        contour: contour.unwrap_or_default(),
+        blend_mode: blend_mode.to_string(),
+        quality: parse_blur_quality(quality)?,
+    };
+    Ok(satin_f32(image.as_array(), &params).into_pyarray(py))
+}
 
-    // Calculate offset direction
-    let angle_rad = angle.to_radians();
-    let dx = (angle_rad.cos() * distance).round() as isize;
-    let dy = (-angle_rad.sin() * distance).round() as isize;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Create offset copy A (positive direction)
-    let mut offset_a = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let sx = (x as isize + dx).clamp(0, width as isize - 1) as usize;
-            let sy = (y as isize + dy).clamp(0, height as isize - 1) as usize;
-            offset_a[[y, x]] = alpha[[sy, sx]];
-        }
+    /// Opaque 24x24 disc on a transparent canvas.
+    fn disc() -> Array3<f32> {
+        Array3::from_shape_fn((24, 24, 4), |(y, x, c)| {
+            let inside = (x as f32 - 11.5).hypot(y as f32 - 11.5) < 9.0;
+            match (inside, c) {
+                (false, _) => 0.0,
+                (true, 3) => 1.0,
+                (true, _) => 0.8,
+            }
+        })
     }
 
-    // Create offset copy B (negative direction)
-    let mut offset_b = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let sx = (x as isize - dx).clamp(0, width as isize - 1) as usize;
-            let sy = (y as isize - dy).clamp(0, height as isize - 1) as usize;
-            offset_b[[y, x]] = alpha[[sy, sx]];
-        }
-    }
+    #[test]
+    fn test_u8_f32_parity_contour_and_blend_mode() {
+        let image = disc();
+        let params = SatinParams { color: [0.2, 0.1, 0.6], opacity: 0.8, distance: 4.0, size: 3.0, ..Default::default() };
+        let f32_result = satin_f32(image.view(), &params);
+        let u8_result = satin_u8(image.mapv(|v| (v * 255.0).round() as u8).view(), &params);
+        let max_diff = f32_result.iter().zip(u8_result.iter()).map(|(a, &b)| (a * 255.0 - b as f32).abs()).fold(0.0, f32::max);
+        assert!(max_diff <= 1.5, "u8 and f32 differ by {max_diff}");
+        // Transparent pixels untouched
+        assert_eq!(f32_result[[0, 0, 3]], 0.0);
 
-    // Blur both copies
-    let blurred_a = blur_alpha_f32(&offset_a, size);
-    let blurred_b = blur_alpha_f32(&offset_b, size);
+        // Identity contour matches the linear default
+        let identity = SatinParams { contour: vec![(0.0, 0.0), (1.0, 1.0)], ..params.clone() };
+        let with_identity = satin_f32(image.view(), &identity);
+        assert!(with_identity.iter().zip(f32_result.iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+        // A flat contour removes the effect entirely
+        let flat = SatinParams { contour: vec![(0.0, 0.0), (1.0, 0.0)], ..params.clone() };
+        assert_eq!(satin_f32(image.view(), &flat), image);
 
-    // Compute satin mask
-    let mut satin_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let diff = (blurred_a[[y, x]] - blurred_b[[y, x]]).abs();
-            let mask_val = if invert { 1.0 - diff } else { diff };
-            satin_mask[[y, x]] = mask_val * alpha[[y, x]];
-        }
+        // White multiply leaves the content unchanged, white normal does not
+        let white = SatinParams { color: [1.0, 1.0, 1.0], blend_mode: "multiply".into(), ..params.clone() };
+        assert!(satin_f32(image.view(), &white).iter().zip(image.iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+        let white_normal = SatinParams { blend_mode: "normal".into(), ..white };
+        assert!(satin_f32(image.view(), &white_normal).iter().zip(image.iter()).any(|(a, b)| (a - b).abs() > 0.01));
     }
 
-    // Create result
-    let mut result = input_f32.clone();
-
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = alpha[[y, x]];
-            if orig_a <= 0.0 {
-                continue;
-            }
-
-            let satin_a = satin_mask[[y, x]] * opacity;
-            if satin_a > 0.0 {
-                let out_a = satin_a + result[[y, x, 3]] * (1.0 - satin_a);
-                if out_a > 0.0 {
-                    result[[y, x, 0]] = (color.0 * satin_a + result[[y, x, 0]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 1]] = (color.1 * satin_a + result[[y, x, 1]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 2]] = (color.2 * satin_a + result[[y, x, 2]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 3]] = out_a;
-                }
-            }
-        }
+    #[test]
+    fn test_fast_quality_tracks_exact() {
+        let image = disc();
+        let exact = SatinParams { distance: 4.0, size: 3.0, ..Default::default() };
+        let fast = SatinParams { quality: BlurQuality::Fast, ..exact.clone() };
+        let (a, b) = (satin_mask(&image.index_axis(ndarray::Axis(2), 3).to_owned(), &exact), satin_mask(&image.index_axis(ndarray::Axis(2), 3).to_owned(), &fast));
+        let max_diff = a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).fold(0.0, f32::max);
+        assert!(max_diff < 0.1, "fast satin deviates by {max_diff}");
+        assert_ne!(a, b);
     }
-
-    result.into_pyarray(py)
 }
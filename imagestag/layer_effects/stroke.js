@@ -48,8 +48,7 @@ export function stroke(imageData, options = {}) {
     );
 
     // Calculate dimensions based on position
-    const is_inside = position === 'inside';
-    const required_expand = is_inside ? 0 : Math.ceil(stroke_width) + 2;
+    const required_expand = wasm.stroke_expansion_wasm(stroke_width, position);
     const new_width = width + required_expand * 2;
     const new_height = height + required_expand * 2;
 
@@ -58,8 +57,8 @@ export function stroke(imageData, options = {}) {
         width: new_width,
         height: new_height,
         channels: 4,
-        offset_x: is_inside ? 0 : -required_expand,
-        offset_y: is_inside ? 0 : -required_expand
+        offset_x: -required_expand,
+        offset_y: -required_expand
     };
 }
 
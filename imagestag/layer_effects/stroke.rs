@@ -8,14 +8,18 @@
 //!
 //! Supports inside, outside, and center stroke positions.
 
-use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::filters::core::{dilate_alpha, erode_alpha, expand_canvas_f32};
+use crate::filters::core::{blend_over_f32, dilate_alpha, erode_alpha, expand_canvas_f32, image_f32_to_u8, image_u8_to_f32, CanvasPolicy};
 
 /// Stroke position relative to the shape edge.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StrokePosition {
     Outside,
     Inside,
@@ -23,7 +27,8 @@ pub enum StrokePosition {
 }
 
 impl StrokePosition {
-    fn from_str(s: &str) -> Self {
+    /// Parse a position name. Unknown names map to outside.
+    pub fn parse(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "inside" => StrokePosition::Inside,
             "center" => StrokePosition::Center,
@@ -32,6 +37,137 @@ impl StrokePosition {
     }
 }
 
+/// Parameters for the stroke effect.
+#[derive(Debug, Clone)]
+pub struct StrokeParams {
+    /// Stroke width in pixels
+    pub width: f32,
+    /// Stroke color (R, G, B), 0.0-1.0
+    pub color: [f32; 3],
+    /// Stroke opacity (0.0-1.0)
+    pub opacity: f32,
+    pub position: StrokePosition,
+    /// Output canvas; ignored inside a layer style, which has its own
+    pub canvas: CanvasPolicy,
+}
+
+impl Default for StrokeParams {
+    fn default() -> Self {
+        Self {
+            width: 2.0,
+            color: [0.0, 0.0, 0.0],
+            opacity: 1.0,
+            position: StrokePosition::Outside,
+            canvas: CanvasPolicy::Grow,
+        }
+    }
+}
+
+impl StrokeParams {
+    /// Pixels required on each side of the layer for the stroke.
+    pub fn expansion(&self) -> usize {
+        match self.position {
+            StrokePosition::Outside | StrokePosition::Center => self.width.ceil() as usize + 2,
+            StrokePosition::Inside => 0,
+        }
+    }
+}
+
+/// Compute the stroke coverage from a layer alpha mask.
+///
+/// # Returns
+/// Stroke alpha (0.0-1.0) with the opacity applied, same size as `alpha`
+pub fn stroke_mask(alpha: &Array2<f32>, params: &StrokeParams) -> Array2<f32> {
+    let (outer, inner) = match params.position {
+        StrokePosition::Outside => (dilate_alpha(alpha, params.width), alpha.clone()),
+        StrokePosition::Inside => (alpha.clone(), erode_alpha(alpha, params.width)),
+        StrokePosition::Center => {
+            let half_width = params.width / 2.0;
+            (dilate_alpha(alpha, half_width), erode_alpha(alpha, half_width))
+        }
+    };
+
+    let mut mask = outer - &inner;
+    mask.mapv_inplace(|v| v.max(0.0) * params.opacity);
+    mask
+}
+
+/// Expand the layer for the stroke and compute the stroke coverage.
+///
+/// Also returns the expansion used, for [`StrokeParams::canvas`].
+fn expanded_with_stroke(image: ArrayView3<f32>, params: &StrokeParams, expand: usize) -> (Array3<f32>, Array2<f32>, usize) {
+    let expand = if expand > 0 { expand } else { params.expansion() };
+    let expanded = expand_canvas_f32(&image.to_owned(), expand);
+    let alpha = expanded.index_axis(Axis(2), 3).to_owned();
+    let mask = stroke_mask(&alpha, params);
+    (expanded, mask, expand)
+}
+
+/// Render the stroke around an RGBA f32 layer.
+///
+/// Outside and center strokes are drawn behind the layer, inside strokes on
+/// top of it.
+///
+/// # Arguments
+/// * `image` - Source RGBA image (height, width, 4)
+/// * `params` - Stroke parameters
+/// * `expand` - Pixels to add on each side (0 = [`StrokeParams::expansion`])
+///
+/// # Returns
+/// RGBA image on the canvas chosen by [`StrokeParams::canvas`]
+pub fn render_stroke_f32(image: ArrayView3<f32>, params: &StrokeParams, expand: usize) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let (expanded, mask, expand) = expanded_with_stroke(image, params, expand);
+
+    let mut result = Array3::<f32>::zeros(expanded.dim());
+    let [r, g, b] = params.color;
+    for ((y, x), &stroke_a) in mask.indexed_iter() {
+        let src = |c: usize| expanded[[y, x, c]];
+        let mut pixel = if stroke_a > 0.0 { [r, g, b, stroke_a] } else { [0.0; 4] };
+        match params.position {
+            StrokePosition::Outside | StrokePosition::Center => {
+                blend_over_f32(&mut pixel, src(0), src(1), src(2), src(3));
+            }
+            StrokePosition::Inside if src(3) > 0.0 => {
+                pixel = [src(0), src(1), src(2), src(3)];
+                blend_over_f32(&mut pixel, r, g, b, stroke_a);
+            }
+            StrokePosition::Inside => {}
+        }
+        for (c, &v) in pixel.iter().enumerate() {
+            result[[y, x, c]] = v;
+        }
+    }
+    params.canvas.apply(result, (height, width), expand)
+}
+
+/// Render the stroke around an RGBA u8 layer.
+pub fn render_stroke_u8(image: ArrayView3<u8>, params: &StrokeParams, expand: usize) -> Array3<u8> {
+    image_f32_to_u8(&render_stroke_f32(image_u8_to_f32(image).view(), params, expand))
+}
+
+/// Render only the stroke of an RGBA f32 layer, without the original.
+///
+/// # Arguments
+/// Same as [`render_stroke_f32`]
+pub fn render_stroke_only_f32(image: ArrayView3<f32>, params: &StrokeParams, expand: usize) -> Array3<f32> {
+    let (height, width, _) = image.dim();
+    let (expanded, mask, expand) = expanded_with_stroke(image, params, expand);
+    let [r, g, b] = params.color;
+    let result = Array3::from_shape_fn(expanded.dim(), |(y, x, c)| match c {
+        0 => r,
+        1 => g,
+        2 => b,
+        _ => mask[[y, x]],
+    });
+    params.canvas.apply(result, (height, width), expand)
+}
+
+/// Render only the stroke of an RGBA u8 layer.
+pub fn render_stroke_only_u8(image: ArrayView3<u8>, params: &StrokeParams, expand: usize) -> Array3<u8> {
+    image_f32_to_u8(&render_stroke_only_f32(image_u8_to_f32(image).view(), params, expand))
+}
+
 /// Apply stroke/outline effect to RGBA image.
 ///
 /// # Arguments
@@ -41,329 +177,58 @@ impl StrokePosition {
 /// * `opacity` - Stroke opacity (0.0-1.0)
 /// * `position` - Stroke position: "outside", "inside", or "center"
 /// * `expand` - Extra pixels to add around image for stroke overflow
+/// * `canvas` - Output canvas: "grow" (fit the stroke) or "clip" (input size)
+/// * `canvas_size` - Explicit output (width, height); overrides `canvas`
+/// * `anchor` - Layer position in an explicit canvas ("center", "top_left", ...)
 ///
 /// # Returns
 /// RGBA image with stroke effect
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, width=2.0, color=(0, 0, 0), opacity=1.0, position="outside", expand=0))]
+#[pyo3(signature = (image, width=2.0, color=(0, 0, 0), opacity=1.0, position="outside", expand=0, canvas="grow", canvas_size=None, anchor="center"))]
 pub fn stroke_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     width: f32,
     color: (u8, u8, u8),
     opacity: f32,
     position: &str,
     expand: usize,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
 ) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, img_width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-    let pos = StrokePosition::from_str(position);
-
-    // Convert to f32 for processing
-    let mut input_f32 = Array3::<f32>::zeros((height, img_width, 4));
-    for y in 0..height {
-        for x in 0..img_width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Calculate expansion needed for outside stroke
-    let required_expand = match pos {
-        StrokePosition::Outside | StrokePosition::Center => {
-            if expand > 0 {
-                expand
-            } else {
-                (width.ceil() as usize) + 2
-            }
-        }
-        StrokePosition::Inside => expand,
-    };
-
-    // Expand canvas if needed
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32.clone()
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha channel
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Create stroke mask based on position
-    let stroke_mask = match pos {
-        StrokePosition::Outside => {
-            // Dilate - original = stroke area
-            let dilated = dilate_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - alpha[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Inside => {
-            // Original - eroded = stroke area
-            let eroded = erode_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (alpha[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Center => {
-            // Dilate half - erode half
-            let half_width = width / 2.0;
-            let dilated = dilate_alpha(&alpha, half_width);
-            let eroded = erode_alpha(&alpha, half_width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-    };
-
-    // Create result
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    let stroke_r = color.0 as f32 / 255.0;
-    let stroke_g = color.1 as f32 / 255.0;
-    let stroke_b = color.2 as f32 / 255.0;
-
-    // Draw stroke first (behind original for outside)
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let stroke_a = stroke_mask[[y, x]] * opacity;
-            if stroke_a > 0.0 {
-                result[[y, x, 0]] = stroke_r;
-                result[[y, x, 1]] = stroke_g;
-                result[[y, x, 2]] = stroke_b;
-                result[[y, x, 3]] = stroke_a;
-            }
-        }
-    }
-
-    // Composite original on top (for outside/center stroke)
-    // For inside stroke, we keep original visible
-    match pos {
-        StrokePosition::Outside | StrokePosition::Center => {
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    let src_a = expanded[[y, x, 3]];
-                    if src_a <= 0.0 {
-                        continue;
-                    }
-
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            }
-        }
-        StrokePosition::Inside => {
-            // For inside stroke, composite stroke over original
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    let orig_a = expanded[[y, x, 3]];
-                    if orig_a <= 0.0 {
-                        continue;
-                    }
-
-                    let stroke_a = result[[y, x, 3]];
-                    if stroke_a <= 0.0 {
-                        // No stroke here, just copy original
-                        result[[y, x, 0]] = expanded[[y, x, 0]];
-                        result[[y, x, 1]] = expanded[[y, x, 1]];
-                        result[[y, x, 2]] = expanded[[y, x, 2]];
-                        result[[y, x, 3]] = orig_a;
-                    } else {
-                        // Composite stroke over original (stroke is on top for inside)
-                        let out_a = stroke_a + orig_a * (1.0 - stroke_a);
-                        if out_a > 0.0 {
-                            result[[y, x, 0]] = (result[[y, x, 0]] * stroke_a + expanded[[y, x, 0]] * orig_a * (1.0 - stroke_a)) / out_a;
-                            result[[y, x, 1]] = (result[[y, x, 1]] * stroke_a + expanded[[y, x, 1]] * orig_a * (1.0 - stroke_a)) / out_a;
-                            result[[y, x, 2]] = (result[[y, x, 2]] * stroke_a + expanded[[y, x, 2]] * orig_a * (1.0 - stroke_a)) / out_a;
-                            result[[y, x, 3]] = out_a;
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Convert back to u8
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let params = StrokeParams { width, color, opacity, position: StrokePosition::parse(position), canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    render_stroke_u8(image.as_array(), &params, expand).into_pyarray(py)
 }
 
 /// Apply stroke/outline effect to f32 RGBA image.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, width=2.0, color=(0.0, 0.0, 0.0), opacity=1.0, position="outside", expand=0))]
+#[pyo3(signature = (image, width=2.0, color=(0.0, 0.0, 0.0), opacity=1.0, position="outside", expand=0, canvas="grow", canvas_size=None, anchor="center"))]
 pub fn stroke_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     width: f32,
     color: (f32, f32, f32),
     opacity: f32,
     position: &str,
     expand: usize,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
 ) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, img_width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-    let pos = StrokePosition::from_str(position);
-
-    // Clone input
-    let mut input_f32 = Array3::<f32>::zeros((height, img_width, 4));
-    for y in 0..height {
-        for x in 0..img_width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    // Calculate expansion
-    let required_expand = match pos {
-        StrokePosition::Outside | StrokePosition::Center => {
-            if expand > 0 { expand } else { (width.ceil() as usize) + 2 }
-        }
-        StrokePosition::Inside => expand,
-    };
-
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Create stroke mask
-    let stroke_mask = match pos {
-        StrokePosition::Outside => {
-            let dilated = dilate_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - alpha[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Inside => {
-            let eroded = erode_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (alpha[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Center => {
-            let half_width = width / 2.0;
-            let dilated = dilate_alpha(&alpha, half_width);
-            let eroded = erode_alpha(&alpha, half_width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-    };
-
-    // Create result and apply stroke
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let stroke_a = stroke_mask[[y, x]] * opacity;
-            if stroke_a > 0.0 {
-                result[[y, x, 0]] = color.0;
-                result[[y, x, 1]] = color.1;
-                result[[y, x, 2]] = color.2;
-                result[[y, x, 3]] = stroke_a;
-            }
-        }
-    }
-
-    // Composite original
-    match pos {
-        StrokePosition::Outside | StrokePosition::Center => {
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    let src_a = expanded[[y, x, 3]];
-                    if src_a <= 0.0 { continue; }
-
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            }
-        }
-        StrokePosition::Inside => {
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    let orig_a = expanded[[y, x, 3]];
-                    if orig_a <= 0.0 { continue; }
-
-                    let stroke_a = result[[y, x, 3]];
-                    if stroke_a <= 0.0 {
-                        result[[y, x, 0]] = expanded[[y, x, 0]];
-                        result[[y, x, 1]] = expanded[[y, x, 1]];
-                        result[[y, x, 2]] = expanded[[y, x, 2]];
-                        result[[y, x, 3]] = orig_a;
-                    } else {
-                        let out_a = stroke_a + orig_a * (1.0 - stroke_a);
-                        if out_a > 0.0 {
-                            result[[y, x, 0]] = (result[[y, x, 0]] * stroke_a + expanded[[y, x, 0]] * orig_a * (1.0 - stroke_a)) / out_a;
-                            result[[y, x, 1]] = (result[[y, x, 1]] * stroke_a + expanded[[y, x, 1]] * orig_a * (1.0 - stroke_a)) / out_a;
-                            result[[y, x, 2]] = (result[[y, x, 2]] * stroke_a + expanded[[y, x, 2]] * orig_a * (1.0 - stroke_a)) / out_a;
-                            result[[y, x, 3]] = out_a;
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let params = StrokeParams { width, color: [color.0, color.1, color.2], opacity, position: StrokePosition::parse(position), canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    render_stroke_f32(image.as_array(), &params, expand).into_pyarray(py)
+}
 
-    result.into_pyarray(py)
+/// Pixels `stroke_rgba` adds on each side when `expand` is 0 (none for inside strokes).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (width=2.0, position="outside"))]
+pub fn stroke_expansion(width: f32, position: &str) -> usize {
+    StrokeParams { width, position: StrokePosition::parse(position), ..Default::default() }.expansion()
 }
 
 /// Get stroke-only layer (no original content composited).
@@ -376,207 +241,73 @@ pub fn stroke_rgba_f32<'py>(
 ///
 /// # Returns
 /// RGBA image with ONLY the stroke (original NOT composited)
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, width=2.0, color=(0, 0, 0), opacity=1.0, position="outside", expand=0))]
+#[pyo3(signature = (image, width=2.0, color=(0, 0, 0), opacity=1.0, position="outside", expand=0, canvas="grow", canvas_size=None, anchor="center"))]
 pub fn stroke_only_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     width: f32,
     color: (u8, u8, u8),
     opacity: f32,
     position: &str,
     expand: usize,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
 ) -> Bound<'py, PyArray3<u8>> {
-    let input = image.as_array();
-    let (height, img_width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-    let pos = StrokePosition::from_str(position);
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, img_width, 4));
-    for y in 0..height {
-        for x in 0..img_width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
-
-    // Calculate expansion
-    let required_expand = match pos {
-        StrokePosition::Outside | StrokePosition::Center => {
-            if expand > 0 { expand } else { (width.ceil() as usize) + 2 }
-        }
-        StrokePosition::Inside => expand,
-    };
-
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32.clone()
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
-
-    // Create stroke mask
-    let stroke_mask = match pos {
-        StrokePosition::Outside => {
-            let dilated = dilate_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - alpha[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Inside => {
-            let eroded = erode_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (alpha[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Center => {
-            let half_width = width / 2.0;
-            let dilated = dilate_alpha(&alpha, half_width);
-            let eroded = erode_alpha(&alpha, half_width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-    };
-
-    // Create stroke-only result (no original composited)
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-
-    let stroke_r = color.0 as f32 / 255.0;
-    let stroke_g = color.1 as f32 / 255.0;
-    let stroke_b = color.2 as f32 / 255.0;
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let stroke_a = stroke_mask[[y, x]] * opacity;
-            result[[y, x, 0]] = stroke_r;
-            result[[y, x, 1]] = stroke_g;
-            result[[y, x, 2]] = stroke_b;
-            result[[y, x, 3]] = stroke_a;
-        }
-    }
-
-    // NOTE: No compositing step - return stroke layer only
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_pyarray(py)
+    let color = [color.0, color.1, color.2].map(|v| v as f32 / 255.0);
+    let params = StrokeParams { width, color, opacity, position: StrokePosition::parse(position), canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    render_stroke_only_u8(image.as_array(), &params, expand).into_pyarray(py)
 }
 
 /// Get stroke-only layer for f32 RGBA image.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (image, width=2.0, color=(0.0, 0.0, 0.0), opacity=1.0, position="outside", expand=0))]
+#[pyo3(signature = (image, width=2.0, color=(0.0, 0.0, 0.0), opacity=1.0, position="outside", expand=0, canvas="grow", canvas_size=None, anchor="center"))]
 pub fn stroke_only_rgba_f32<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, f32>,
+    image: PyHostArray3<'py, f32>,
     width: f32,
     color: (f32, f32, f32),
     opacity: f32,
     position: &str,
     expand: usize,
+    canvas: &str,
+    canvas_size: Option<(usize, usize)>,
+    anchor: &str,
 ) -> Bound<'py, PyArray3<f32>> {
-    let input = image.as_array();
-    let (height, img_width, _) = (input.shape()[0], input.shape()[1], input.shape()[2]);
-    let pos = StrokePosition::from_str(position);
-
-    let mut input_f32 = Array3::<f32>::zeros((height, img_width, 4));
-    for y in 0..height {
-        for x in 0..img_width {
-            for c in 0..4 {
-                input_f32[[y, x, c]] = input[[y, x, c]];
-            }
-        }
-    }
-
-    let required_expand = match pos {
-        StrokePosition::Outside | StrokePosition::Center => {
-            if expand > 0 { expand } else { (width.ceil() as usize) + 2 }
-        }
-        StrokePosition::Inside => expand,
-    };
-
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32
-    };
-
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
-
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
+    let params = StrokeParams { width, color: [color.0, color.1, color.2], opacity, position: StrokePosition::parse(position), canvas: CanvasPolicy::parse(canvas, canvas_size, anchor) };
+    render_stroke_only_f32(image.as_array(), &params, expand).into_pyarray(py)
+}
 
-    let stroke_mask = match pos {
-        StrokePosition::Outside => {
-            let dilated = dilate_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - alpha[[y, x]]).max(0.0);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_effects::test_util::{assert_fits_padding, assert_layer_on_top, full_layer, LAYER_HEIGHT, LAYER_WIDTH};
+
+    #[test]
+    fn test_expansion_matches_output_padding() {
+        let image = full_layer(0.8);
+        for width in [2.5, 3.0, 4.5, 7.0] {
+            for position in [StrokePosition::Outside, StrokePosition::Center] {
+                let params = StrokeParams { width, position, color: [1.0, 0.0, 0.0], ..Default::default() };
+                let e = params.expansion();
+                for result in [render_stroke_f32(image.view(), &params, 0), render_stroke_only_f32(image.view(), &params, 0)] {
+                    assert_fits_padding(&result, e, &format!("{params:?}"));
+                    // The stroke is drawn on the padding right next to the layer
+                    assert!(result[[e - 1, e + 10, 3]] > 0.0, "{params:?}");
                 }
+                // Outside and center strokes sit behind the opaque layer
+                assert_layer_on_top(&render_stroke_f32(image.view(), &params, 0), &image, e);
             }
-            mask
-        }
-        StrokePosition::Inside => {
-            let eroded = erode_alpha(&alpha, width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (alpha[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        StrokePosition::Center => {
-            let half_width = width / 2.0;
-            let dilated = dilate_alpha(&alpha, half_width);
-            let eroded = erode_alpha(&alpha, half_width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-    };
-
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
 
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let stroke_a = stroke_mask[[y, x]] * opacity;
-            result[[y, x, 0]] = color.0;
-            result[[y, x, 1]] = color.1;
-            result[[y, x, 2]] = color.2;
-            result[[y, x, 3]] = stroke_a;
+            // Inside strokes need no padding
+            let params = StrokeParams { width, position: StrokePosition::Inside, ..Default::default() };
+            assert_eq!(params.expansion(), 0);
+            for result in [render_stroke_f32(image.view(), &params, 0), render_stroke_only_f32(image.view(), &params, 0)] {
+                assert_eq!(result.dim(), (LAYER_HEIGHT, LAYER_WIDTH, 4), "{params:?}");
+            }
         }
     }
-
-    result.into_pyarray(py)
 }
@@ -201,6 +201,7 @@ function createF32Filter(wasmFn, paramMapper = (p) => Object.values(p)) {
 // Parameter mappers for filters with complex params
 const paramMappers = {
     simple: (p) => [],
+    invert: (p) => [p.channels ?? 'rgb'],
     amount: (p) => [p.amount ?? 0],
     gamma: (p) => [p.gamma_value ?? 1.0],
     exposure: (p) => [p.exposure_val ?? 0, p.offset ?? 0, p.gamma_val ?? 1.0],
@@ -221,6 +222,7 @@ const paramMappers = {
         p.out_black ?? 0,
         p.out_white ?? 255,
         p.gamma ?? 1.0,
+        p.channel ?? 'rgb',
     ],
     levels_f32: (p) => [
         (p.in_black ?? 0) / 255.0,
@@ -228,17 +230,19 @@ const paramMappers = {
         (p.out_black ?? 0) / 255.0,
         (p.out_white ?? 255) / 255.0,
         p.gamma ?? 1.0,
+        p.channel ?? 'rgb',
     ],
     curves: (p) => {
         const points = p.points ?? [[0, 0], [1, 1]];
-        return [new Float32Array(points.flat())];
+        return [new Float32Array(points.flat()), p.interpolation ?? 'pchip', p.luminosity_only ? 'luminosity' : (p.channel ?? 'rgb')];
     },
+    auto_levels: (p) => [p.clip_percent ?? 0.01, p.channel ?? 'rgb'],
     clip_percent: (p) => [p.clip_percent ?? 0.01],
     sharpen: (p) => [p.amount ?? 1.0],
     unsharp_mask_u8: (p) => [p.amount ?? 1.0, p.radius ?? 2.0, p.threshold ?? 0],
     unsharp_mask_f32: (p) => [p.amount ?? 1.0, p.radius ?? 2.0, (p.threshold ?? 0) / 255.0],
     high_pass: (p) => [p.radius ?? 3.0],
-    motion_blur: (p) => [p.angle ?? 45.0, p.distance ?? 10.0],
+    motion_blur: (p) => [p.angle ?? 45.0, p.distance ?? 10.0, p.border_mode ?? 'reflect'],
     sobel: (p) => [p.direction ?? 'both'],
     laplacian: (p) => [p.kernel_size ?? 3],
     add_noise: (p) => [
@@ -253,11 +257,12 @@ const paramMappers = {
     sepia: (p) => [p.intensity ?? 1.0],
     temperature: (p) => [p.amount ?? 0],
     channel_mixer: (p) => [p.r_src ?? 0, p.g_src ?? 1, p.b_src ?? 2],
-    equalize: (p) => [],
+    equalize: (p) => [p.mode ?? 'per_channel', []],
+    vibrance: (p) => [p.amount ?? 0, p.protect_skin ?? 0],
     pixelate: (p) => [p.block_size ?? 8],
     vignette: (p) => [p.amount ?? 0.5],
-    gaussian_blur: (p) => [p.sigma ?? 1.0],
-    box_blur: (p) => [p.radius ?? 1],
+    gaussian_blur: (p) => [p.sigma ?? 1.0, p.srgb_linearize ?? false, p.quality ?? 'exact'],
+    box_blur: (p) => [p.radius ?? 1, p.srgb_linearize ?? false],
 };
 
 // u8 filter implementations
@@ -268,9 +273,9 @@ export const FILTER_IMPLEMENTATIONS = {
     saturation: createU8Filter(wasm.saturation_wasm, paramMappers.amount),
     gamma: createU8Filter(wasm.gamma_wasm, paramMappers.gamma),
     exposure: createU8Filter(wasm.exposure_wasm, paramMappers.exposure),
-    invert: createU8Filter(wasm.invert_wasm, paramMappers.simple),
+    invert: createU8Filter(wasm.invert_wasm, paramMappers.invert),
     hue_shift: createU8Filter(wasm.hue_shift_wasm, (p) => [p.degrees ?? 0]),
-    vibrance: createU8Filter(wasm.vibrance_wasm, paramMappers.amount),
+    vibrance: createU8Filter(wasm.vibrance_wasm, paramMappers.vibrance),
     color_balance: createU8Filter(wasm.color_balance_wasm, paramMappers.color_balance),
     posterize: createU8Filter(wasm.posterize_wasm, paramMappers.posterize),
     solarize: createU8Filter(wasm.solarize_wasm, paramMappers.solarize_u8),
@@ -278,7 +283,7 @@ export const FILTER_IMPLEMENTATIONS = {
     emboss: createU8Filter(wasm.emboss_wasm, paramMappers.emboss),
     levels: createU8Filter(wasm.levels_wasm, paramMappers.levels_u8),
     curves: createU8Filter(wasm.curves_wasm, paramMappers.curves),
-    auto_levels: createU8Filter(wasm.auto_levels_wasm, paramMappers.clip_percent),
+    auto_levels: createU8Filter(wasm.auto_levels_wasm, paramMappers.auto_levels),
     sharpen: createU8Filter(wasm.sharpen_wasm, paramMappers.sharpen),
     unsharp_mask: createU8Filter(wasm.unsharp_mask_wasm, paramMappers.unsharp_mask_u8),
     high_pass: createU8Filter(wasm.high_pass_wasm, paramMappers.high_pass),
@@ -314,9 +319,9 @@ export const FILTER_IMPLEMENTATIONS_F32 = {
     saturation_f32: createF32Filter(wasm.saturation_f32_wasm, paramMappers.amount),
     gamma_f32: createF32Filter(wasm.gamma_f32_wasm, paramMappers.gamma),
     exposure_f32: createF32Filter(wasm.exposure_f32_wasm, paramMappers.exposure),
-    invert_f32: createF32Filter(wasm.invert_f32_wasm, paramMappers.simple),
+    invert_f32: createF32Filter(wasm.invert_f32_wasm, paramMappers.invert),
     hue_shift_f32: createF32Filter(wasm.hue_shift_f32_wasm, (p) => [p.degrees ?? 0]),
-    vibrance_f32: createF32Filter(wasm.vibrance_f32_wasm, paramMappers.amount),
+    vibrance_f32: createF32Filter(wasm.vibrance_f32_wasm, paramMappers.vibrance),
     color_balance_f32: createF32Filter(wasm.color_balance_f32_wasm, paramMappers.color_balance),
     posterize_f32: createF32Filter(wasm.posterize_f32_wasm, paramMappers.posterize),
     solarize_f32: createF32Filter(wasm.solarize_f32_wasm, paramMappers.solarize_f32),
@@ -324,7 +329,7 @@ export const FILTER_IMPLEMENTATIONS_F32 = {
     emboss_f32: createF32Filter(wasm.emboss_f32_wasm, paramMappers.emboss),
     levels_f32: createF32Filter(wasm.levels_f32_wasm, paramMappers.levels_f32),
     curves_f32: createF32Filter(wasm.curves_f32_wasm, paramMappers.curves),
-    auto_levels_f32: createF32Filter(wasm.auto_levels_f32_wasm, paramMappers.clip_percent),
+    auto_levels_f32: createF32Filter(wasm.auto_levels_f32_wasm, paramMappers.auto_levels),
     sharpen_f32: createF32Filter(wasm.sharpen_f32_wasm, paramMappers.sharpen),
     unsharp_mask_f32: createF32Filter(wasm.unsharp_mask_f32_wasm, paramMappers.unsharp_mask_f32),
     high_pass_f32: createF32Filter(wasm.high_pass_f32_wasm, paramMappers.high_pass),
@@ -110,6 +110,8 @@ export const LAYER_EFFECT_CATALOG = [
             distance: 11.0,
             size: 14.0,
             invert: false,
+            contour: [[0.0, 0.0], [0.5, 1.0], [1.0, 0.0]],
+            blend_mode: 'multiply',
         },
         inputs: ['deer'],
     },
@@ -187,7 +189,9 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
             color[0],
             color[1],
             color[2],
-            params.opacity ?? 0.75
+            params.opacity ?? 0.75,
+            params.knockout ?? false,
+            params.quality ?? 'exact'
         );
 
         // Note: drop_shadow expands canvas, need to compute new dimensions
@@ -220,7 +224,8 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
             color[0],
             color[1],
             color[2],
-            params.opacity ?? 0.75
+            params.opacity ?? 0.75,
+            params.quality ?? 'exact'
         );
 
         return {
@@ -244,7 +249,8 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
             color[1],
             color[2],
             params.opacity ?? 0.75,
-            params.spread ?? 0.0
+            params.spread ?? 0.0,
+            params.quality ?? 'exact'
         );
 
         // outer_glow expands canvas
@@ -273,7 +279,8 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
             color[1],
             color[2],
             params.opacity ?? 0.75,
-            params.choke ?? 0.0
+            params.choke ?? 0.0,
+            params.quality ?? 'exact'
         );
 
         return {
@@ -323,21 +330,9 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
 
     satin: (imageData, params = {}) => {
         const { data, width, height } = imageData;
-        const color = params.color || [0, 0, 0];
-
-        const result = wasm.satin_rgba_wasm(
-            new Uint8Array(data.buffer),
-            width,
-            height,
-            color[0],
-            color[1],
-            color[2],
-            params.opacity ?? 0.5,
-            params.angle ?? 19.0,
-            params.distance ?? 11.0,
-            params.size ?? 14.0,
-            params.invert ?? false
-        );
+        const opts = satinOptions(params);
+        const result = wasm.satin_rgba_options_wasm(new Uint8Array(data.buffer), width, height, opts);
+        opts.free();
 
         return {
             data: new Uint8ClampedArray(result.buffer),
@@ -351,15 +346,15 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
         const { data, width, height } = imageData;
         const color = params.color || [255, 0, 0];
 
-        const result = wasm.color_overlay_rgba_wasm(
-            new Uint8Array(data.buffer),
-            width,
-            height,
-            color[0],
-            color[1],
-            color[2],
-            params.opacity ?? 1.0
-        );
+        const opts = Object.assign(new wasm.ColorOverlayOptions(), {
+            color_r: color[0],
+            color_g: color[1],
+            color_b: color[2],
+            opacity: params.opacity ?? 1.0,
+            blend_mode: params.blend_mode || 'normal',
+        });
+        const result = wasm.color_overlay_rgba_options_wasm(new Uint8Array(data.buffer), width, height, opts);
+        opts.free();
 
         return {
             data: new Uint8ClampedArray(result.buffer),
@@ -381,8 +376,12 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
             params.style || 'linear',
             params.angle ?? 90.0,
             params.scale ?? 1.0,
+            params.scale ?? 1.0,
+            0.0,
+            0.0,
             params.reverse ?? false,
-            params.opacity ?? 1.0
+            params.opacity ?? 1.0,
+            params.blend_mode || 'normal'
         );
 
         return {
@@ -409,7 +408,8 @@ export const LAYER_EFFECT_IMPLEMENTATIONS = {
             params.scale ?? 1.0,
             params.offset_x ?? 0,
             params.offset_y ?? 0,
-            params.opacity ?? 1.0
+            params.opacity ?? 1.0,
+            params.blend_mode || 'normal'
         );
 
         return {
@@ -463,6 +463,31 @@ for (const [name, fn] of Object.entries(LAYER_EFFECT_IMPLEMENTATIONS)) {
     };
 }
 
+// Satin has a native f32 path: u8 -> f32 -> process -> 12bit
+LAYER_EFFECT_IMPLEMENTATIONS_F32.satin_f32 = (imageData, params = {}) => {
+    const input = convertU8ToF32(imageData);
+    const opts = satinOptions(params);
+    const result = wasm.satin_rgba_f32_wasm(input.data, input.width, input.height, opts);
+    opts.free();
+    return convertF32To12bit({ data: result, width: input.width, height: input.height, channels: 4 });
+};
+
+function satinOptions(params) {
+    const color = params.color || [0, 0, 0];
+    return Object.assign(new wasm.SatinOptions(), {
+        color_r: color[0],
+        color_g: color[1],
+        color_b: color[2],
+        opacity: params.opacity ?? 0.5,
+        angle: params.angle ?? 19.0,
+        distance: params.distance ?? 11.0,
+        size: params.size ?? 14.0,
+        invert: params.invert ?? false,
+        contour: new Float32Array((params.contour || []).flat()),
+        blend_mode: params.blend_mode || 'normal',
+    });
+}
+
 /**
  * Register all layer effects with the parity test runner.
  */
@@ -132,6 +132,8 @@ LAYER_EFFECT_CATALOG: list[dict[str, Any]] = [
             "distance": 11.0,
             "size": 14.0,
             "invert": False,
+            "contour": [(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)],
+            "blend_mode": "multiply",
         },
     },
 
@@ -0,0 +1,171 @@
+"""Golden-file conformance check for the Python layer effect bindings.
+
+Renders every layer effect through ``imagestag_rust`` with the canonical
+parameter set and compares the result per pixel against the reference PNGs
+in ``rust/tests/golden/layer_effects/``. The same references are checked
+against the WASM bindings by ``rust/tests/layer_effect_golden.rs``, so a
+pass on both sides means the Python and JavaScript paths agree.
+
+- ``<effect>.png`` - 8-bit RGBA, compared against the u8 binding
+- ``<effect>_f32.png`` - 16-bit RGBA, compared against the f32 binding
+
+Effects without an f32 reference are only checked in u8.
+
+Usage:
+    python -m imagestag.parity.layer_effect_golden
+
+Exits with status 1 if any effect differs by more than the tolerance.
+The parameters below must be kept in sync with
+``rust/tests/layer_effect_golden.rs``.
+"""
+import sys
+from pathlib import Path
+from typing import Callable
+
+import numpy as np
+import imagestag_rust
+
+GOLDEN_DIR = Path(__file__).resolve().parents[2] / "rust" / "tests" / "golden" / "layer_effects"
+
+# Allowed per-channel difference (u8 levels / 0.0-1.0 scale)
+TOLERANCE_U8 = 2
+TOLERANCE_F32 = 2.0 / 255.0
+
+GRADIENT_STOPS = [0.0, 255.0, 200.0, 0.0, 0.5, 0.0, 160.0, 255.0, 1.0, 120.0, 0.0, 160.0]
+SATIN_CONTOUR = [(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)]
+
+
+def _checker_pattern() -> np.ndarray:
+    """4x4 RGBA checkerboard with a half-transparent cell."""
+    pattern = np.zeros((4, 4, 4), dtype=np.uint8)
+    for y in range(4):
+        for x in range(4):
+            dark = (x // 2 + y // 2) % 2
+            pattern[y, x, :3] = (20, 20, 20) if dark else (230, 60, 40)
+            pattern[y, x, 3] = 128 if x == 0 and y == 0 else 255
+    return pattern
+
+
+def _f32_stops(stops: list) -> list:
+    """Rescale flat [pos, r, g, b, ...] stops from 0-255 to 0.0-1.0."""
+    return [v if i % 4 == 0 else v / 255.0 for i, v in enumerate(stops)]
+
+
+PATTERN = _checker_pattern()
+
+# name -> (u8 renderer, f32 renderer); f32 renderers receive 0.0-1.0 input
+CASES: dict[str, tuple[Callable[[np.ndarray], np.ndarray], Callable[[np.ndarray], np.ndarray]]] = {
+    "drop_shadow": (
+        lambda img: imagestag_rust.drop_shadow_rgba(img, 3.0, 3.0, 2.0, (0, 0, 40), 0.75),
+        lambda img: imagestag_rust.drop_shadow_rgba_f32(img, 3.0, 3.0, 2.0, (0.0, 0.0, 40 / 255), 0.75),
+    ),
+    "inner_shadow": (
+        lambda img: imagestag_rust.inner_shadow_rgba(img, 2.0, 2.0, 2.0, 0.0, (0, 0, 0), 0.75),
+        lambda img: imagestag_rust.inner_shadow_rgba_f32(img, 2.0, 2.0, 2.0, 0.0, (0.0, 0.0, 0.0), 0.75),
+    ),
+    "outer_glow": (
+        lambda img: imagestag_rust.outer_glow_rgba(img, 4.0, (255, 220, 0), 0.75, 0.0),
+        lambda img: imagestag_rust.outer_glow_rgba_f32(img, 4.0, (1.0, 220 / 255, 0.0), 0.75, 0.0),
+    ),
+    "inner_glow": (
+        lambda img: imagestag_rust.inner_glow_rgba(img, 4.0, (255, 255, 190), 0.75, 0.0),
+        lambda img: imagestag_rust.inner_glow_rgba_f32(img, 4.0, (1.0, 1.0, 190 / 255), 0.75, 0.0),
+    ),
+    "bevel_emboss": (
+        lambda img: imagestag_rust.bevel_emboss_rgba(img, depth=3.0, size=4.0),
+        lambda img: imagestag_rust.bevel_emboss_rgba_f32(img, depth=3.0, size=4.0),
+    ),
+    "satin": (
+        lambda img: imagestag_rust.satin_rgba(
+            img, (40, 0, 90), 0.7, 19.0, 4.0, 3.0, False, SATIN_CONTOUR, "multiply"),
+        lambda img: imagestag_rust.satin_rgba_f32(
+            img, (40 / 255, 0.0, 90 / 255), 0.7, 19.0, 4.0, 3.0, False, SATIN_CONTOUR, "multiply"),
+    ),
+    "color_overlay": (
+        lambda img: imagestag_rust.color_overlay_rgba(img, (255, 64, 0), 0.7, "multiply"),
+        lambda img: imagestag_rust.color_overlay_rgba_f32(img, (1.0, 64 / 255, 0.0), 0.7, "multiply"),
+    ),
+    "gradient_overlay": (
+        lambda img: imagestag_rust.gradient_overlay_rgba(
+            img, GRADIENT_STOPS, "radial", opacity=0.8, blend_mode="screen"),
+        lambda img: imagestag_rust.gradient_overlay_rgba_f32(
+            img, _f32_stops(GRADIENT_STOPS), "radial", opacity=0.8, blend_mode="screen"),
+    ),
+    "pattern_overlay": (
+        lambda img: imagestag_rust.pattern_overlay_rgba(img, PATTERN, 1.5, 1, 0, 0.9, "overlay"),
+        lambda img: imagestag_rust.pattern_overlay_rgba_f32(
+            img, PATTERN.astype(np.float32) / 255.0, 1.5, 1, 0, 0.9, "overlay"),
+    ),
+    "stroke": (
+        lambda img: imagestag_rust.stroke_rgba(img, 2.0, (255, 0, 0), 1.0, "outside"),
+        lambda img: imagestag_rust.stroke_rgba_f32(img, 2.0, (1.0, 0.0, 0.0), 1.0, "outside"),
+    ),
+    "cast_shadow": (
+        lambda img: imagestag_rust.cast_shadow_rgba(img),
+        lambda img: imagestag_rust.cast_shadow_rgba_f32(img),
+    ),
+    "long_shadow": (
+        lambda img: imagestag_rust.long_shadow_rgba(img, 135.0, 8.0, (26, 26, 51), 0.5),
+        lambda img: imagestag_rust.long_shadow_rgba_f32(img, 135.0, 8.0, (0.1, 0.1, 0.2), 0.5),
+    ),
+}
+
+
+def _load_u8(path: Path) -> np.ndarray:
+    image, _ = imagestag_rust.decode_image(path.read_bytes())
+    return image
+
+
+def _load_f32(path: Path) -> np.ndarray:
+    image, _ = imagestag_rust.decode_image_u16(path.read_bytes())
+    return image.astype(np.float32) / 65535.0
+
+
+def _compare(label: str, rendered: np.ndarray, reference: np.ndarray, tolerance: float) -> str | None:
+    """Return a failure message, or None if within tolerance."""
+    if rendered.shape != reference.shape:
+        return f"{label}: shape {rendered.shape} != reference {reference.shape}"
+    diff = float(np.abs(rendered.astype(np.float64) - reference.astype(np.float64)).max())
+    if diff > tolerance:
+        return f"{label}: max difference {diff:.5f} > {tolerance:.5f}"
+    return None
+
+
+def run(golden_dir: Path = GOLDEN_DIR) -> list[str]:
+    """Check every effect against its references.
+
+    Returns:
+        List of failure messages (empty if everything matches)
+    """
+    source = _load_u8(golden_dir / "input.png")
+    source_f32 = source.astype(np.float32) / 255.0
+    failures: list[str] = []
+
+    for name, (render_u8, render_f32) in CASES.items():
+        failure = _compare(f"{name} (u8)", render_u8(source), _load_u8(golden_dir / f"{name}.png"), TOLERANCE_U8)
+        if failure:
+            failures.append(failure)
+
+        reference_f32 = golden_dir / f"{name}_f32.png"
+        if reference_f32.exists():
+            failure = _compare(f"{name} (f32)", render_f32(source_f32), _load_f32(reference_f32), TOLERANCE_F32)
+            if failure:
+                failures.append(failure)
+
+        status = "FAIL" if any(f.startswith(f"{name} ") for f in failures) else "ok"
+        print(f"{name:<20} {status}")
+
+    return failures
+
+
+def main(argv: list[str]) -> int:
+    golden_dir = Path(argv[1]) if len(argv) > 1 else GOLDEN_DIR
+    failures = run(golden_dir)
+    for failure in failures:
+        print(failure, file=sys.stderr)
+    print(f"{len(CASES) - len({f.split(' ')[0] for f in failures})}/{len(CASES)} effects match")
+    return 1 if failures else 0
+
+
+if __name__ == "__main__":
+    sys.exit(main(sys.argv))
@@ -0,0 +1,113 @@
+//! Performance benchmarks for the hot kernels.
+//!
+//! Covers blur, median, Sobel, layer effects and flood fill at 512², 2048²
+//! and 4096² for u8 and f32. Criterion keeps the last run in
+//! `target/criterion` and reports changes against it, so run the suite
+//! before and after a performance rewrite:
+//!
+//! ```bash
+//! cargo bench --no-default-features --bench filters
+//! cargo bench --no-default-features --bench filters -- median/u8/512   # one case
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ndarray::Array3;
+
+use imagestag_rust::filters::blur_wasm::{box_blur_wasm_f32, box_blur_wasm_u8, gaussian_blur_wasm_f32, gaussian_blur_wasm_u8};
+use imagestag_rust::filters::edge::{sobel_f32, sobel_u8};
+use imagestag_rust::filters::noise::{median_f32, median_u8};
+use imagestag_rust::layer_effects::drop_shadow::{render_drop_shadow_f32, render_drop_shadow_u8, DropShadowParams};
+use imagestag_rust::layer_effects::stroke::{render_stroke_f32, render_stroke_u8, StrokeParams, StrokePosition};
+use imagestag_rust::selection::magic_wand_select;
+
+const SIZES: [usize; 3] = [512, 2048, 4096];
+
+/// RGBA test image: textured disc on a transparent background.
+fn test_image(size: usize) -> Array3<u8> {
+    let center = size as f32 / 2.0;
+    Array3::from_shape_fn((size, size, 4), |(y, x, c)| {
+        let inside = (x as f32 + 0.5 - center).hypot(y as f32 + 0.5 - center) < size as f32 * 0.4;
+        match (inside, c) {
+            (false, _) => 0,
+            (true, 3) => 255,
+            (true, _) => ((x * 7 + y * 13 + c * 61) % 256) as u8,
+        }
+    })
+}
+
+fn to_f32(image: &Array3<u8>) -> Array3<f32> {
+    image.mapv(|v| v as f32 / 255.0)
+}
+
+/// Benchmark `u8` and `f32` variants of a kernel at every size.
+fn bench_pair(
+    c: &mut Criterion,
+    name: &str,
+    run_u8: impl Fn(&Array3<u8>) -> Array3<u8>,
+    run_f32: impl Fn(&Array3<f32>) -> Array3<f32>,
+) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    for size in SIZES {
+        let image = test_image(size);
+        let image_f32 = to_f32(&image);
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_with_input(BenchmarkId::new("u8", size), &image, |b, image| b.iter(|| run_u8(image)));
+        group.bench_with_input(BenchmarkId::new("f32", size), &image_f32, |b, image| b.iter(|| run_f32(image)));
+    }
+    group.finish();
+}
+
+fn blur(c: &mut Criterion) {
+    bench_pair(
+        c,
+        "gaussian_blur",
+        |image| gaussian_blur_wasm_u8(image.view(), 4.0),
+        |image| gaussian_blur_wasm_f32(image.view(), 4.0),
+    );
+    bench_pair(c, "box_blur", |image| box_blur_wasm_u8(image.view(), 4), |image| box_blur_wasm_f32(image.view(), 4));
+}
+
+fn median(c: &mut Criterion) {
+    bench_pair(c, "median", |image| median_u8(image.view(), 3), |image| median_f32(image.view(), 3));
+}
+
+fn sobel(c: &mut Criterion) {
+    bench_pair(c, "sobel", |image| sobel_u8(image.view(), "both", 3), |image| sobel_f32(image.view(), "both", 3));
+}
+
+fn layer_effects(c: &mut Criterion) {
+    let shadow = DropShadowParams { offset_x: 8.0, offset_y: 8.0, blur_radius: 10.0, ..Default::default() };
+    bench_pair(
+        c,
+        "drop_shadow",
+        |image| render_drop_shadow_u8(image.view(), &shadow, shadow.expansion()),
+        |image| render_drop_shadow_f32(image.view(), &shadow, shadow.expansion()),
+    );
+    let stroke = StrokeParams { width: 4.0, color: [0.0, 0.0, 0.0], opacity: 1.0, position: StrokePosition::Outside, ..Default::default() };
+    bench_pair(
+        c,
+        "stroke",
+        |image| render_stroke_u8(image.view(), &stroke, stroke.expansion()),
+        |image| render_stroke_f32(image.view(), &stroke, stroke.expansion()),
+    );
+}
+
+fn flood_fill(c: &mut Criterion) {
+    // Magic wand works on RGBA u8; start in the transparent corner, which
+    // is connected around the whole disc
+    let mut group = c.benchmark_group("flood_fill");
+    group.sample_size(10);
+    for size in SIZES {
+        let image = test_image(size);
+        let data = image.as_slice().expect("standard layout");
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_with_input(BenchmarkId::new("u8", size), &data, |b, data| {
+            b.iter(|| magic_wand_select(data, size, size, 0, 0, 32, true))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, blur, median, sobel, layer_effects, flood_fill);
+criterion_main!(benches);
@@ -0,0 +1,78 @@
+//! Using imagestag_rust as a plain Rust dependency.
+//!
+//! Builds an image in memory and runs filters and layer effects on it
+//! without the Python or WASM bindings:
+//!
+//! ```bash
+//! cargo run --no-default-features --example pure_rust
+//! ```
+
+use ndarray::Array3;
+
+use imagestag_rust::filters::basic::premultiply_alpha_u8;
+use imagestag_rust::filters::blur::gaussian_blur_rgba_u8;
+use imagestag_rust::filters::color_adjust::brightness_u8;
+use imagestag_rust::filters::gradient_generator::generate_gradient_u8;
+use imagestag_rust::filters::sharpen::sharpen_u8;
+use imagestag_rust::layer_effects::drop_shadow::{render_drop_shadow_u8, DropShadowParams};
+use imagestag_rust::layer_effects::gradient_overlay::{gradient_overlay_u8, parse_stops, GradientOverlayParams};
+use imagestag_rust::layer_effects::stroke::{render_stroke_u8, StrokeParams, StrokePosition};
+use imagestag_rust::{ChannelLayout, ColorSpace, Image};
+
+/// Opaque orange disc on a transparent 64x64 canvas.
+fn disc() -> Array3<u8> {
+    let size = 64;
+    let center = size as f32 / 2.0;
+    Array3::from_shape_fn((size, size, 4), |(y, x, c)| {
+        let inside = (x as f32 + 0.5 - center).hypot(y as f32 + 0.5 - center) < 20.0;
+        match (inside, c) {
+            (false, _) => 0,
+            (true, 0) => 240,
+            (true, 1) => 140,
+            (true, 2) => 20,
+            (true, _) => 255,
+        }
+    })
+}
+
+fn describe(name: &str, image: &Array3<u8>) {
+    let (height, width, channels) = image.dim();
+    if channels == 4 {
+        let visible = image.lanes(ndarray::Axis(2)).into_iter().filter(|p| p[3] > 0).count();
+        println!("{name:<18} {width}x{height}x{channels}, {visible} visible pixels");
+    } else {
+        println!("{name:<18} {width}x{height}x{channels}");
+    }
+}
+
+fn main() {
+    let image = disc();
+    describe("input", &image);
+
+    // Filters
+    describe("sharpen", &sharpen_u8(image.view(), 1.0));
+    describe("gaussian blur", &gaussian_blur_rgba_u8(image.view(), 3.0));
+    describe("premultiplied", &premultiply_alpha_u8(image.view()));
+
+    // Layer effects
+    let shadow = DropShadowParams { offset_x: 3.0, offset_y: 3.0, blur_radius: 2.0, ..Default::default() };
+    describe("drop shadow", &render_drop_shadow_u8(image.view(), &shadow, shadow.expansion()));
+
+    let stroke = StrokeParams { width: 2.0, color: [0.0, 0.0, 0.0], opacity: 1.0, position: StrokePosition::Outside, ..Default::default() };
+    describe("stroke", &render_stroke_u8(image.view(), &stroke, stroke.expansion()));
+
+    let gradient = GradientOverlayParams {
+        stops: parse_stops(&[0.0, 255.0, 200.0, 0.0, 1.0, 120.0, 0.0, 160.0], 255.0),
+        style: "radial".to_string(),
+        ..Default::default()
+    };
+    describe("gradient overlay", &gradient_overlay_u8(image.view(), &gradient));
+    describe("gradient image", &generate_gradient_u8(64, 32, 3, &gradient));
+
+    // Typed images: layout and color space travel with the pixels
+    let typed = Image::try_from(image).expect("disc is RGBA").with_color_space(ColorSpace::Srgb);
+    assert_eq!(typed.layout(), ChannelLayout::Rgba);
+    describe("typed brightness", &brightness_u8(&typed, 0.2));
+    let sharpened = typed.map(|view| sharpen_u8(view, 1.0)).expect("sharpen keeps RGBA");
+    describe("typed sharpen", sharpened.as_array());
+}
@@ -0,0 +1,129 @@
+#ifndef IMAGESTAG_H
+#define IMAGESTAG_H
+
+/* Generated by cbindgen from rust/src/capi.rs - do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result code of every C API call.
+typedef enum IsStatus {
+  IS_STATUS_OK = 0,
+  // Null pointer, zero size or invalid UTF-8
+  IS_STATUS_INVALID_ARGUMENT = 1,
+  // Filter name is not known
+  IS_STATUS_UNKNOWN_FILTER = 2,
+  // Parameters, pipeline or recipe could not be parsed
+  IS_STATUS_INVALID_PARAMETERS = 3,
+  // The filter panicked; the library state is unaffected
+  IS_STATUS_INTERNAL_ERROR = 4,
+} IsStatus;
+
+// Image owned by the library (u8).
+typedef struct IsImageU8 {
+  uint8_t *data;
+  size_t width;
+  size_t height;
+  size_t channels;
+} IsImageU8;
+
+// Image owned by the library (f32).
+typedef struct IsImageF32 {
+  float *data;
+  size_t width;
+  size_t height;
+  size_t channels;
+} IsImageF32;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Version of the C ABI, bumped on incompatible changes (currently 1).
+uint32_t imagestag_abi_version(void);
+
+// Message of the last failed call on this thread (empty if none).
+//
+// The pointer stays valid until the next failing call on the same thread.
+const char *imagestag_last_error(void);
+
+// JSON description of every filter; release with [`imagestag_string_free`].
+char *imagestag_filter_registry(void);
+
+// Release a string returned by the library.
+//
+// # Safety
+// `text` must be null or come from [`imagestag_filter_registry`], and is
+// invalid afterwards.
+void imagestag_string_free(char *text);
+
+// Apply one filter by name (u8).
+//
+// # Safety
+// `data` must point to `width * height * channels` bytes, `name` and
+// `params_json` to NUL-terminated strings (`params_json` may be null for
+// defaults) and `out` to writable memory. On success `out` holds a new
+// image to release with [`imagestag_image_u8_free`].
+enum IsStatus imagestag_apply_filter_u8(const uint8_t *data,
+                                        size_t width,
+                                        size_t height,
+                                        size_t channels,
+                                        const char *name,
+                                        const char *params_json,
+                                        struct IsImageU8 *out);
+
+// Apply one filter by name (f32).
+//
+// # Safety
+// As [`imagestag_apply_filter_u8`], with `width * height * channels` floats.
+enum IsStatus imagestag_apply_filter_f32(const float *data,
+                                         size_t width,
+                                         size_t height,
+                                         size_t channels,
+                                         const char *name,
+                                         const char *params_json,
+                                         struct IsImageF32 *out);
+
+// Apply a pipeline string ("brightness 0.2 | sharpen 0.5") or recipe JSON (u8).
+//
+// # Safety
+// As [`imagestag_apply_filter_u8`]; `pipeline` must be a NUL-terminated string.
+enum IsStatus imagestag_apply_pipeline_u8(const uint8_t *data,
+                                          size_t width,
+                                          size_t height,
+                                          size_t channels,
+                                          const char *pipeline,
+                                          struct IsImageU8 *out);
+
+// Apply a pipeline string or recipe JSON (f32).
+//
+// # Safety
+// As [`imagestag_apply_pipeline_u8`], with `width * height * channels` floats.
+enum IsStatus imagestag_apply_pipeline_f32(const float *data,
+                                           size_t width,
+                                           size_t height,
+                                           size_t channels,
+                                           const char *pipeline,
+                                           struct IsImageF32 *out);
+
+// Release the pixels of an image returned by the library.
+//
+// # Safety
+// `image` must be null or hold an image from a `_u8` call that was not
+// freed yet; its `data` is set to null.
+void imagestag_image_u8_free(struct IsImageU8 *image);
+
+// Release the pixels of an f32 image returned by the library.
+//
+// # Safety
+// As [`imagestag_image_u8_free`] for `_f32` results.
+void imagestag_image_f32_free(struct IsImageF32 *image);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* IMAGESTAG_H */
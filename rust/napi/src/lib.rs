@@ -0,0 +1,178 @@
+//! Native Node.js bindings (N-API) for ImageStag.
+//!
+//! Exposes the batch filter set (every filter of the `registry`) to Node.js
+//! servers without the WASM copy into linear memory, with the full rayon
+//! thread pool. Images are `Buffer`s (u8) or `Float32Array`s (0.0-1.0) in
+//! row-major (height, width, channels) order, as in the WASM API.
+//!
+//! Filters take the same parameters as `apply_filter` / `batch_filter` in
+//! Python: a filter name with a JSON parameter object, or a pipeline string
+//! ("brightness 0.2 | sharpen 0.5") / recipe JSON. The `*Async` variants run
+//! on the libuv thread pool and return a Promise.
+//!
+//! ## Build
+//!
+//! ```bash
+//! cd rust/napi && npm install && npm run build
+//! ```
+
+use imagestag_rust::batch::{
+    apply_filter_f32 as filter_f32, apply_filter_u8 as filter_u8, apply_pipeline_f32 as pipeline_f32,
+    apply_pipeline_u8 as pipeline_u8, parse_pipeline_or_recipe, registry_json, BatchError, FilterSpec,
+};
+use napi::bindgen_prelude::*;
+use napi::{Env, Task};
+use napi_derive::napi;
+use ndarray::{Array3, ArrayView3};
+use rayon::prelude::*;
+
+fn invalid_arg(message: impl ToString) -> Error {
+    Error::new(Status::InvalidArg, message.to_string())
+}
+
+fn batch_error(e: BatchError) -> Error {
+    invalid_arg(e)
+}
+
+fn view<T>(data: &[T], width: u32, height: u32, channels: u32) -> Result<ArrayView3<'_, T>> {
+    ArrayView3::from_shape((height as usize, width as usize, channels as usize), data)
+        .map_err(|_| invalid_arg("data length does not match width * height * channels"))
+}
+
+fn parse_pipeline(pipeline: &str) -> Result<Vec<FilterSpec>> {
+    parse_pipeline_or_recipe(pipeline).map_err(batch_error)
+}
+
+/// Image returned by pipelines that may change the size.
+#[napi(object)]
+pub struct FilteredImage {
+    pub data: Buffer,
+    pub width: u32,
+    pub height: u32,
+    pub channels: u32,
+}
+
+/// Image returned by f32 pipelines.
+#[napi(object)]
+pub struct FilteredImageF32 {
+    pub data: Float32Array,
+    pub width: u32,
+    pub height: u32,
+    pub channels: u32,
+}
+
+fn to_image(result: Array3<u8>) -> FilteredImage {
+    let (height, width, channels) = result.dim();
+    FilteredImage {
+        data: result.into_raw_vec_and_offset().0.into(),
+        width: width as u32,
+        height: height as u32,
+        channels: channels as u32,
+    }
+}
+
+fn to_image_f32(result: Array3<f32>) -> FilteredImageF32 {
+    let (height, width, channels) = result.dim();
+    FilteredImageF32 {
+        data: Float32Array::new(result.into_raw_vec_and_offset().0),
+        width: width as u32,
+        height: height as u32,
+        channels: channels as u32,
+    }
+}
+
+/// Describe every filter available to `applyFilter` and pipelines.
+///
+/// # Returns
+/// JSON array of `{"name", "description", "params": [...]}`
+#[napi]
+pub fn filter_registry() -> String {
+    registry_json()
+}
+
+/// Apply one filter by name (u8).
+#[napi]
+pub fn apply_filter(data: Buffer, width: u32, height: u32, channels: u32, name: String, params_json: String) -> Result<FilteredImage> {
+    let input = view(&data, width, height, channels)?;
+    Ok(to_image(filter_u8(&name, input, &params_json).map_err(batch_error)?))
+}
+
+/// Apply one filter by name (f32).
+#[napi]
+pub fn apply_filter_f32(
+    data: Float32Array,
+    width: u32,
+    height: u32,
+    channels: u32,
+    name: String,
+    params_json: String,
+) -> Result<FilteredImageF32> {
+    let input = view(&data, width, height, channels)?;
+    Ok(to_image_f32(filter_f32(&name, input, &params_json).map_err(batch_error)?))
+}
+
+/// Apply a pipeline string or recipe JSON (u8).
+#[napi]
+pub fn apply_pipeline(data: Buffer, width: u32, height: u32, channels: u32, pipeline: String) -> Result<FilteredImage> {
+    let steps = parse_pipeline(&pipeline)?;
+    Ok(to_image(pipeline_u8(view(&data, width, height, channels)?, &steps)))
+}
+
+/// Apply a pipeline string or recipe JSON (f32).
+#[napi]
+pub fn apply_pipeline_f32(
+    data: Float32Array,
+    width: u32,
+    height: u32,
+    channels: u32,
+    pipeline: String,
+) -> Result<FilteredImageF32> {
+    let steps = parse_pipeline(&pipeline)?;
+    Ok(to_image_f32(pipeline_f32(view(&data, width, height, channels)?, &steps)))
+}
+
+/// Background job of `applyPipelineAsync` / `applyPipelineBatchAsync`.
+pub struct PipelineTask {
+    images: Vec<Vec<u8>>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    steps: Vec<FilterSpec>,
+}
+
+impl Task for PipelineTask {
+    type Output = Vec<Array3<u8>>;
+    type JsValue = Vec<FilteredImage>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let (width, height, channels) = (self.width, self.height, self.channels);
+        let views = self.images.iter().map(|data| view(data, width, height, channels)).collect::<Result<Vec<_>>>()?;
+        Ok(views.par_iter().map(|image| pipeline_u8(*image, &self.steps)).collect())
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into_iter().map(to_image).collect())
+    }
+}
+
+/// Apply a pipeline to many same-sized images in parallel (u8).
+///
+/// Runs off the event loop; resolves to one image per input.
+#[napi]
+pub fn apply_pipeline_batch_async(
+    images: Vec<Buffer>,
+    width: u32,
+    height: u32,
+    channels: u32,
+    pipeline: String,
+) -> Result<AsyncTask<PipelineTask>> {
+    let steps = parse_pipeline(&pipeline)?;
+    let images = images.iter().map(|data| data.to_vec()).collect();
+    Ok(AsyncTask::new(PipelineTask { images, width, height, channels, steps }))
+}
+
+/// Apply a pipeline off the event loop (u8); resolves to a one-element array.
+#[napi]
+pub fn apply_pipeline_async(data: Buffer, width: u32, height: u32, channels: u32, pipeline: String) -> Result<AsyncTask<PipelineTask>> {
+    apply_pipeline_batch_async(vec![data], width, height, channels, pipeline)
+}
@@ -0,0 +1,204 @@
+//! WCAG 2.x contrast of text color against image regions.
+//!
+//! [`contrast_ratio_map_f32`] measures how legible text of a given color
+//! would be on top of every pixel or block of an image, for the UI's text
+//! legibility check. The contrast ratio is (L1 + 0.05) / (L2 + 0.05) of the
+//! relative luminances of the lighter and darker color and ranges from 1
+//! (same luminance) to 21 (black on white).
+//!
+//! | Level | Normal text | Large text (18pt, or 14pt bold) |
+//! |-------|-------------|---------------------------------|
+//! | AA    | 4.5         | 3.0                             |
+//! | AAA   | 7.0         | 4.5                             |
+//!
+//! Alpha is ignored; flatten layered images before measuring.
+
+use ndarray::{Array2, ArrayView3};
+use rayon::prelude::*;
+
+use crate::filters::resample::srgb_to_linear;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Per-cell pass level in [`ContrastMap::levels`]: below AA.
+pub const WCAG_FAIL: u8 = 0;
+/// Per-cell pass level in [`ContrastMap::levels`]: meets AA, not AAA.
+pub const WCAG_AA: u8 = 1;
+/// Per-cell pass level in [`ContrastMap::levels`]: meets AAA.
+pub const WCAG_AAA: u8 = 2;
+
+/// Contrast ratios and WCAG pass levels per pixel or block.
+#[derive(Debug, Clone)]
+pub struct ContrastMap {
+    /// Contrast ratio (1-21) of the text color against each cell
+    pub ratios: Array2<f32>,
+    /// [`WCAG_FAIL`], [`WCAG_AA`] or [`WCAG_AAA`] per cell
+    pub levels: Array2<u8>,
+}
+
+/// WCAG relative luminance of an sRGB color (components 0.0-1.0).
+pub fn relative_luminance(rgb: [f32; 3]) -> f32 {
+    let [r, g, b] = rgb.map(|v| srgb_to_linear(v.clamp(0.0, 1.0)));
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio between two sRGB colors (components 0.0-1.0).
+pub fn contrast_ratio(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Pass level of a contrast ratio.
+///
+/// # Arguments
+/// * `ratio` - Contrast ratio (1-21)
+/// * `large_text` - Use the lower thresholds for large text
+pub fn wcag_level(ratio: f32, large_text: bool) -> u8 {
+    let (aa, aaa) = if large_text { (3.0, 4.5) } else { (4.5, 7.0) };
+    if ratio >= aaa {
+        WCAG_AAA
+    } else if ratio >= aa {
+        WCAG_AA
+    } else {
+        WCAG_FAIL
+    }
+}
+
+/// Contrast of a text color against an image, per pixel or block - f32 version.
+///
+/// A block takes the lowest ratio of its pixels, so a cell only passes when
+/// text is legible over all of it.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `text_color` - Text color (R, G, B), 0.0-1.0
+/// * `block_size` - Edge length of a cell in pixels (1 = per pixel); edge blocks may be smaller
+/// * `large_text` - Grade with the large text thresholds
+///
+/// # Returns
+/// Ratios and levels of shape (ceil(height / block_size), ceil(width / block_size))
+pub fn contrast_ratio_map_f32(image: ArrayView3<f32>, text_color: [f32; 3], block_size: usize, large_text: bool) -> ContrastMap {
+    let (height, width, channels) = image.dim();
+    let block = block_size.max(1);
+    let (rows, cols) = (height.div_ceil(block), width.div_ceil(block));
+    let text = relative_luminance(text_color);
+    let ratio_at = |y: usize, x: usize| {
+        let background = if channels >= 3 {
+            relative_luminance([image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]])
+        } else {
+            srgb_to_linear(image[[y, x, 0]].clamp(0.0, 1.0))
+        };
+        (text.max(background) + 0.05) / (text.min(background) + 0.05)
+    };
+
+    let ratios: Vec<f32> = (0..rows * cols)
+        .into_par_iter()
+        .map(|i| {
+            let (y0, x0) = (i / cols * block, i % cols * block);
+            let (y1, x1) = ((y0 + block).min(height), (x0 + block).min(width));
+            (y0..y1).flat_map(|y| (x0..x1).map(move |x| (y, x))).map(|(y, x)| ratio_at(y, x)).fold(f32::INFINITY, f32::min)
+        })
+        .collect();
+    let ratios = Array2::from_shape_vec((rows, cols), ratios).expect("map matches block grid");
+    let levels = ratios.mapv(|r| wcag_level(r, large_text));
+    ContrastMap { ratios, levels }
+}
+
+/// Contrast of a text color against an image, per pixel or block - u8 version.
+///
+/// # Arguments
+/// * `text_color` - Text color (R, G, B), 0-255
+pub fn contrast_ratio_map_u8(image: ArrayView3<u8>, text_color: [u8; 3], block_size: usize, large_text: bool) -> ContrastMap {
+    let image_f32 = image.mapv(|v| v as f32 / 255.0);
+    contrast_ratio_map_f32(image_f32.view(), text_color.map(|v| v as f32 / 255.0), block_size, large_text)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+type ContrastArrays<'py> = (Bound<'py, PyArray2<f32>>, Bound<'py, PyArray2<u8>>);
+
+/// Contrast of a text color against a uint8 image, per pixel or block.
+///
+/// # Arguments
+/// * `text_color` - (R, G, B), 0-255
+/// * `block_size` - Cell size in pixels (1 = per pixel)
+/// * `large_text` - Use the large text thresholds (AA 3.0, AAA 4.5)
+///
+/// # Returns
+/// Tuple of (ratios float32, levels uint8 with 0 = fail, 1 = AA, 2 = AAA)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "contrast_ratio_map", signature = (image, text_color, block_size=1, large_text=false))]
+pub fn contrast_ratio_map_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    text_color: (u8, u8, u8),
+    block_size: usize,
+    large_text: bool,
+) -> ContrastArrays<'py> {
+    let (r, g, b) = text_color;
+    let map = contrast_ratio_map_u8(image.as_array(), [r, g, b], block_size, large_text);
+    (map.ratios.into_pyarray(image.py()), map.levels.into_pyarray(image.py()))
+}
+
+/// Contrast of a text color against a float32 image, per pixel or block.
+///
+/// # Arguments
+/// * `text_color` - (R, G, B), 0.0-1.0
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "contrast_ratio_map_f32", signature = (image, text_color, block_size=1, large_text=false))]
+pub fn contrast_ratio_map_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    text_color: (f32, f32, f32),
+    block_size: usize,
+    large_text: bool,
+) -> ContrastArrays<'py> {
+    let (r, g, b) = text_color;
+    let map = contrast_ratio_map_f32(image.as_array(), [r, g, b], block_size, large_text);
+    (map.ratios.into_pyarray(image.py()), map.levels.into_pyarray(image.py()))
+}
+
+/// WCAG contrast ratio between two (R, G, B) colors, 0-255.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "contrast_ratio")]
+pub fn contrast_ratio_py(a: (u8, u8, u8), b: (u8, u8, u8)) -> f32 {
+    let to_f32 = |c: (u8, u8, u8)| [c.0, c.1, c.2].map(|v| v as f32 / 255.0);
+    contrast_ratio(to_f32(a), to_f32(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+
+    #[test]
+    fn test_wcag_ratios_and_levels() {
+        assert!((contrast_ratio([0.0; 3], [1.0; 3]) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio([0.5; 3], [0.5; 3]) - 1.0).abs() < 1e-6);
+        // #777777 on white is the classic 4.48:1 near miss
+        let gray = 0x77 as f32 / 255.0;
+        assert!((contrast_ratio([gray; 3], [1.0; 3]) - 4.48).abs() < 0.01);
+        assert_eq!(wcag_level(4.48, false), WCAG_FAIL);
+        assert_eq!(wcag_level(4.48, true), WCAG_AA);
+
+        // White text: passes over the dark left half, fails over the light right half
+        let image = Array3::from_shape_fn((4, 8, 3), |(y, x, _)| if x < 4 { 0.0 } else if y == 0 && x == 7 { 0.3 } else { 0.9 });
+        let map = contrast_ratio_map_f32(image.view(), [1.0; 3], 4, false);
+        assert_eq!(map.ratios.dim(), (1, 2));
+        assert_eq!(map.levels[[0, 0]], WCAG_AAA);
+        assert_eq!(map.levels[[0, 1]], WCAG_FAIL);
+        // Per pixel, the darker pixel in the light half still passes
+        let pixels = contrast_ratio_map_u8(image.mapv(|v| (v * 255.0) as u8).view(), [255; 3], 1, false);
+        assert_eq!(pixels.levels.dim(), (4, 8));
+        assert!(pixels.levels[[0, 7]] >= WCAG_AA && pixels.levels[[1, 7]] == WCAG_FAIL);
+    }
+}
@@ -0,0 +1,604 @@
+//! Corner detection, ORB descriptors and descriptor matching.
+//!
+//! - **Harris**: Corner response `det(M) - k * trace(M)^2` of the Gaussian
+//!   weighted structure tensor
+//! - **FAST-9**: A pixel is a corner if 9 contiguous pixels of the
+//!   16-pixel Bresenham circle around it are all brighter or all darker by
+//!   more than a threshold
+//! - **ORB**: FAST keypoints ranked by Harris response, oriented by the
+//!   intensity centroid, described by 256 rotated binary intensity tests
+//!   (rBRIEF) on a smoothed image. Single scale, no pyramid.
+//!
+//! Detection works on luminance; u8 images are converted to 0.0-1.0, so
+//! FAST thresholds of the u8 variants are given in 0-255 units.
+
+use std::sync::OnceLock;
+
+use ndarray::{Array2, Array3, ArrayView3};
+use rayon::prelude::*;
+
+use crate::filters::blur_wasm::gaussian_blur_wasm_f32;
+use crate::filters::edge::blurred_luminance_f32;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Detected feature point.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Keypoint {
+    pub x: f32,
+    pub y: f32,
+    /// Detector score (Harris response or FAST score)
+    pub response: f32,
+    /// Orientation in radians (ORB only, 0 otherwise)
+    pub angle: f32,
+}
+
+/// 256-bit binary descriptor.
+pub type Descriptor = [u8; 32];
+
+/// Keypoints with one descriptor each.
+#[derive(Debug, Clone, Default)]
+pub struct OrbFeatures {
+    pub keypoints: Vec<Keypoint>,
+    pub descriptors: Vec<Descriptor>,
+}
+
+/// Match between `query[query]` and `train[train]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureMatch {
+    pub query: usize,
+    pub train: usize,
+    /// Hamming distance (0-256)
+    pub distance: u32,
+}
+
+/// Radius of the ORB orientation and descriptor patch.
+const PATCH_RADIUS: usize = 15;
+
+fn luminance(image: ArrayView3<f32>) -> Array2<f32> {
+    blurred_luminance_f32(image, 0.0).index_axis_move(ndarray::Axis(2), 0)
+}
+
+/// Keep local maxima of `score` (3x3) above zero, strongest first.
+fn non_max_suppression(score: &Array2<f32>, border: usize, max_points: usize) -> Vec<Keypoint> {
+    let (height, width) = score.dim();
+    let mut points = Vec::new();
+    for y in border.max(1)..height.saturating_sub(border.max(1)) {
+        for x in border.max(1)..width.saturating_sub(border.max(1)) {
+            let s = score[[y, x]];
+            if s <= 0.0 {
+                continue;
+            }
+            // Ties are broken towards the first pixel in scan order
+            let is_max = (0..3).all(|j| {
+                (0..3).all(|i| {
+                    let n = score[[y + j - 1, x + i - 1]];
+                    n < s || (n == s && (j, i) >= (1, 1))
+                })
+            });
+            if is_max {
+                points.push(Keypoint { x: x as f32, y: y as f32, response: s, angle: 0.0 });
+            }
+        }
+    }
+    points.sort_by(|a, b| b.response.total_cmp(&a.response));
+    if max_points > 0 {
+        points.truncate(max_points);
+    }
+    points
+}
+
+// ============================================================================
+// Harris
+// ============================================================================
+
+fn harris_response(gray: &Array2<f32>, k: f32) -> Array2<f32> {
+    let (height, width) = gray.dim();
+    let at = |y: isize, x: isize| gray[[y.clamp(0, height as isize - 1) as usize, x.clamp(0, width as isize - 1) as usize]];
+
+    let mut tensor = Array3::<f32>::zeros((height, width, 3));
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let ix = (at(y - 1, x + 1) + 2.0 * at(y, x + 1) + at(y + 1, x + 1)
+                - at(y - 1, x - 1) - 2.0 * at(y, x - 1) - at(y + 1, x - 1))
+                / 8.0;
+            let iy = (at(y + 1, x - 1) + 2.0 * at(y + 1, x) + at(y + 1, x + 1)
+                - at(y - 1, x - 1) - 2.0 * at(y - 1, x) - at(y - 1, x + 1))
+                / 8.0;
+            let (yu, xu) = (y as usize, x as usize);
+            tensor[[yu, xu, 0]] = ix * ix;
+            tensor[[yu, xu, 1]] = iy * iy;
+            tensor[[yu, xu, 2]] = ix * iy;
+        }
+    }
+    let tensor = gaussian_blur_wasm_f32(tensor.view(), 1.0);
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let (a, b, c) = (tensor[[y, x, 0]], tensor[[y, x, 1]], tensor[[y, x, 2]]);
+        a * b - c * c - k * (a + b) * (a + b)
+    })
+}
+
+/// Detect Harris corners - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `k` - Harris sensitivity (typically 0.04-0.06)
+/// * `threshold` - Minimum response relative to the strongest corner (e.g. 0.01)
+/// * `max_corners` - Keep at most this many corners (0 = all)
+///
+/// # Returns
+/// Corners sorted by response, strongest first
+pub fn harris_corners_f32(image: ArrayView3<f32>, k: f32, threshold: f32, max_corners: usize) -> Vec<Keypoint> {
+    let (height, width, _) = image.dim();
+    if width < 3 || height < 3 {
+        return Vec::new();
+    }
+    let mut response = harris_response(&luminance(image), k);
+    let max = response.iter().cloned().fold(0.0f32, f32::max);
+    let min_response = (max * threshold).max(f32::MIN_POSITIVE);
+    response.mapv_inplace(|r| if r >= min_response { r } else { 0.0 });
+    non_max_suppression(&response, 1, max_corners)
+}
+
+/// Detect Harris corners - u8 version.
+pub fn harris_corners_u8(image: ArrayView3<u8>, k: f32, threshold: f32, max_corners: usize) -> Vec<Keypoint> {
+    harris_corners_f32(image.mapv(|v| v as f32 / 255.0).view(), k, threshold, max_corners)
+}
+
+// ============================================================================
+// FAST
+// ============================================================================
+
+/// Bresenham circle of radius 3, clockwise from the top.
+const CIRCLE: [(isize, isize); 16] = [
+    (0, -3), (1, -3), (2, -2), (3, -1), (3, 0), (3, 1), (2, 2), (1, 3),
+    (0, 3), (-1, 3), (-2, 2), (-3, 1), (-3, 0), (-3, -1), (-2, -2), (-1, -3),
+];
+
+/// FAST-9 score of a pixel: summed contrast beyond the threshold of the
+/// qualifying side, or 0 if it is not a corner.
+fn fast_score(gray: &Array2<f32>, y: usize, x: usize, threshold: f32) -> f32 {
+    let center = gray[[y, x]];
+    let ring: [f32; 16] = std::array::from_fn(|i| {
+        let (dx, dy) = CIRCLE[i];
+        gray[[(y as isize + dy) as usize, (x as isize + dx) as usize]]
+    });
+    let class = |v: f32| {
+        if v > center + threshold {
+            1
+        } else if v < center - threshold {
+            -1
+        } else {
+            0
+        }
+    };
+
+    // A 9-arc always covers at least two of the four compass pixels
+    let compass = [0, 4, 8, 12].map(|i| class(ring[i]));
+    let bright = compass.iter().filter(|&&c| c == 1).count() >= 2;
+    let dark = compass.iter().filter(|&&c| c == -1).count() >= 2;
+    if !bright && !dark {
+        return 0.0;
+    }
+
+    let mut best = 0.0f32;
+    for sign in [1, -1] {
+        let mut run = 0;
+        for i in 0..16 + 8 {
+            if class(ring[i % 16]) == sign {
+                run += 1;
+                if run >= 9 {
+                    let score: f32 = ring
+                        .iter()
+                        .filter(|&&v| class(v) == sign)
+                        .map(|&v| (v - center).abs() - threshold)
+                        .sum();
+                    best = best.max(score);
+                    break;
+                }
+            } else {
+                run = 0;
+            }
+        }
+    }
+    best
+}
+
+fn fast_score_map(gray: &Array2<f32>, threshold: f32) -> Array2<f32> {
+    let (height, width) = gray.dim();
+    let mut score = Array2::<f32>::zeros((height, width));
+    if width < 7 || height < 7 {
+        return score;
+    }
+    score
+        .as_slice_mut()
+        .expect("owned array is contiguous")
+        .par_chunks_mut(width)
+        .enumerate()
+        .filter(|(y, _)| (3..height - 3).contains(y))
+        .for_each(|(y, row)| {
+            for (x, s) in row.iter_mut().enumerate().take(width - 3).skip(3) {
+                *s = fast_score(gray, y, x, threshold);
+            }
+        });
+    score
+}
+
+/// Detect FAST-9 corners - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `threshold` - Minimum brightness difference to the center (e.g. 0.08)
+/// * `nonmax` - Keep only local maxima of the corner score
+/// * `max_corners` - Keep at most this many corners (0 = all)
+///
+/// # Returns
+/// Corners sorted by score, strongest first
+pub fn fast_corners_f32(image: ArrayView3<f32>, threshold: f32, nonmax: bool, max_corners: usize) -> Vec<Keypoint> {
+    let score = fast_score_map(&luminance(image), threshold);
+    if nonmax {
+        return non_max_suppression(&score, 3, max_corners);
+    }
+    let mut points: Vec<Keypoint> = score
+        .indexed_iter()
+        .filter(|(_, &s)| s > 0.0)
+        .map(|((y, x), &s)| Keypoint { x: x as f32, y: y as f32, response: s, angle: 0.0 })
+        .collect();
+    points.sort_by(|a, b| b.response.total_cmp(&a.response));
+    if max_corners > 0 {
+        points.truncate(max_corners);
+    }
+    points
+}
+
+/// Detect FAST-9 corners - u8 version (`threshold` in 0-255 units).
+pub fn fast_corners_u8(image: ArrayView3<u8>, threshold: f32, nonmax: bool, max_corners: usize) -> Vec<Keypoint> {
+    fast_corners_f32(image.mapv(|v| v as f32 / 255.0).view(), threshold / 255.0, nonmax, max_corners)
+        .into_iter()
+        .map(|k| Keypoint { response: k.response * 255.0, ..k })
+        .collect()
+}
+
+// ============================================================================
+// ORB
+// ============================================================================
+
+/// rBRIEF sampling pattern: 256 point pairs (x1, y1, x2, y2) drawn from an
+/// isotropic Gaussian (sigma = patch size / 5) with a fixed seed.
+fn brief_pattern() -> &'static [[i8; 4]; 256] {
+    static PATTERN: OnceLock<[[i8; 4]; 256]> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        let mut state = 0x2545_f491u32;
+        let mut uniform = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state as f32 + 1.0) / (u32::MAX as f32 + 2.0)
+        };
+        let sigma = (2 * PATCH_RADIUS + 1) as f32 / 5.0;
+        let limit = PATCH_RADIUS as f32;
+        std::array::from_fn(|_| {
+            std::array::from_fn(|_| {
+                // Box-Muller
+                let (u1, u2) = (uniform(), uniform());
+                let g = (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos();
+                (g * sigma).round().clamp(-limit, limit) as i8
+            })
+        })
+    })
+}
+
+/// Orientation of the intensity centroid in a circular patch.
+fn centroid_angle(gray: &Array2<f32>, x: usize, y: usize) -> f32 {
+    let r = PATCH_RADIUS as isize;
+    let (mut m10, mut m01) = (0.0f32, 0.0f32);
+    for dy in -r..=r {
+        for dx in -r..=r {
+            if dx * dx + dy * dy > r * r {
+                continue;
+            }
+            let v = gray[[(y as isize + dy) as usize, (x as isize + dx) as usize]];
+            m10 += dx as f32 * v;
+            m01 += dy as f32 * v;
+        }
+    }
+    m01.atan2(m10)
+}
+
+fn describe(smooth: &Array2<f32>, keypoint: &Keypoint) -> Descriptor {
+    let (height, width) = smooth.dim();
+    let (sin, cos) = keypoint.angle.sin_cos();
+    let sample = |px: i8, py: i8| {
+        let (px, py) = (px as f32, py as f32);
+        let x = (keypoint.x + px * cos - py * sin).round().clamp(0.0, width as f32 - 1.0);
+        let y = (keypoint.y + px * sin + py * cos).round().clamp(0.0, height as f32 - 1.0);
+        smooth[[y as usize, x as usize]]
+    };
+
+    let mut descriptor = [0u8; 32];
+    for (bit, pair) in brief_pattern().iter().enumerate() {
+        if sample(pair[0], pair[1]) < sample(pair[2], pair[3]) {
+            descriptor[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+    descriptor
+}
+
+/// Detect ORB features - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `max_features` - Keep the best keypoints by Harris response (0 = all)
+/// * `fast_threshold` - FAST brightness threshold (e.g. 0.08)
+///
+/// # Returns
+/// Oriented keypoints (response = Harris score) and their descriptors
+pub fn orb_f32(image: ArrayView3<f32>, max_features: usize, fast_threshold: f32) -> OrbFeatures {
+    let gray = luminance(image);
+    let (height, width) = gray.dim();
+    if width <= 2 * PATCH_RADIUS + 2 || height <= 2 * PATCH_RADIUS + 2 {
+        return OrbFeatures::default();
+    }
+
+    let harris = harris_response(&gray, 0.04);
+    let mut keypoints = non_max_suppression(&fast_score_map(&gray, fast_threshold), PATCH_RADIUS + 1, 0);
+    for keypoint in &mut keypoints {
+        keypoint.response = harris[[keypoint.y as usize, keypoint.x as usize]];
+    }
+    keypoints.sort_by(|a, b| b.response.total_cmp(&a.response));
+    if max_features > 0 {
+        keypoints.truncate(max_features);
+    }
+
+    let smooth = gaussian_blur_wasm_f32(gray.view().insert_axis(ndarray::Axis(2)), 2.0).index_axis_move(ndarray::Axis(2), 0);
+    let descriptors = keypoints
+        .par_iter_mut()
+        .map(|keypoint| {
+            keypoint.angle = centroid_angle(&gray, keypoint.x as usize, keypoint.y as usize);
+            describe(&smooth, keypoint)
+        })
+        .collect();
+    OrbFeatures { keypoints, descriptors }
+}
+
+/// Detect ORB features - u8 version (`fast_threshold` in 0-255 units).
+pub fn orb_u8(image: ArrayView3<u8>, max_features: usize, fast_threshold: f32) -> OrbFeatures {
+    orb_f32(image.mapv(|v| v as f32 / 255.0).view(), max_features, fast_threshold / 255.0)
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// Hamming distance between two descriptors.
+#[inline]
+pub fn hamming(a: &Descriptor, b: &Descriptor) -> u32 {
+    a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+/// Closest descriptor in `candidates` as (index, distance).
+fn nearest(descriptor: &Descriptor, candidates: &[Descriptor]) -> Option<(usize, u32)> {
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i, hamming(descriptor, c)))
+        .min_by_key(|&(i, d)| (d, i))
+}
+
+/// Brute-force match descriptors by Hamming distance.
+///
+/// # Arguments
+/// * `query` - Descriptors of the first image
+/// * `train` - Descriptors of the second image
+/// * `max_distance` - Reject matches farther apart (e.g. 64)
+/// * `cross_check` - Keep only mutual nearest neighbors
+///
+/// # Returns
+/// Matches sorted by distance, at most one per query descriptor
+pub fn match_descriptors(query: &[Descriptor], train: &[Descriptor], max_distance: u32, cross_check: bool) -> Vec<FeatureMatch> {
+    let backward: Vec<Option<usize>> = if cross_check {
+        train.par_iter().map(|d| nearest(d, query).map(|(i, _)| i)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut matches: Vec<FeatureMatch> = query
+        .par_iter()
+        .enumerate()
+        .filter_map(|(q, d)| {
+            let (t, distance) = nearest(d, train)?;
+            let mutual = !cross_check || backward[t] == Some(q);
+            (mutual && distance <= max_distance).then_some(FeatureMatch { query: q, train: t, distance })
+        })
+        .collect();
+    matches.sort_by_key(|m| (m.distance, m.query));
+    matches
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Keypoints as (x, y, response, angle) tuples.
+#[cfg(feature = "python")]
+type PyKeypoints = Vec<(f32, f32, f32, f32)>;
+
+#[cfg(feature = "python")]
+fn to_py_keypoints(keypoints: &[Keypoint]) -> PyKeypoints {
+    keypoints.iter().map(|k| (k.x, k.y, k.response, k.angle)).collect()
+}
+
+#[cfg(feature = "python")]
+fn to_py_orb(py: Python<'_>, features: OrbFeatures) -> (PyKeypoints, Bound<'_, PyArray2<u8>>) {
+    let flat: Vec<u8> = features.descriptors.iter().flatten().copied().collect();
+    let descriptors = Array2::from_shape_vec((features.descriptors.len(), 32), flat).expect("32 bytes per descriptor");
+    (to_py_keypoints(&features.keypoints), descriptors.into_pyarray(py))
+}
+
+#[cfg(feature = "python")]
+fn from_py_descriptors(array: &PyReadonlyArray2<'_, u8>) -> PyResult<Vec<Descriptor>> {
+    let view = array.as_array();
+    if view.ncols() != 32 {
+        return Err(PyValueError::new_err(format!("expected (N, 32) descriptors, got {:?}", view.shape())));
+    }
+    Ok(view.outer_iter().map(|row| std::array::from_fn(|i| row[i])).collect())
+}
+
+/// Detect Harris corners in a uint8 image.
+///
+/// # Returns
+/// List of (x, y, response, angle) tuples, strongest first
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "harris_corners", signature = (image, k=0.04, threshold=0.01, max_corners=500))]
+pub fn harris_corners_py(image: PyHostArray3<'_, u8>, k: f32, threshold: f32, max_corners: usize) -> PyKeypoints {
+    to_py_keypoints(&harris_corners_u8(image.as_array(), k, threshold, max_corners))
+}
+
+/// Detect Harris corners in a float32 image.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "harris_corners_f32", signature = (image, k=0.04, threshold=0.01, max_corners=500))]
+pub fn harris_corners_f32_py(image: PyHostArray3<'_, f32>, k: f32, threshold: f32, max_corners: usize) -> PyKeypoints {
+    to_py_keypoints(&harris_corners_f32(image.as_array(), k, threshold, max_corners))
+}
+
+/// Detect FAST-9 corners in a uint8 image (threshold in 0-255 units).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "fast_corners", signature = (image, threshold=20.0, nonmax=true, max_corners=0))]
+pub fn fast_corners_py(image: PyHostArray3<'_, u8>, threshold: f32, nonmax: bool, max_corners: usize) -> PyKeypoints {
+    to_py_keypoints(&fast_corners_u8(image.as_array(), threshold, nonmax, max_corners))
+}
+
+/// Detect FAST-9 corners in a float32 image (threshold in 0.0-1.0 units).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "fast_corners_f32", signature = (image, threshold=0.08, nonmax=true, max_corners=0))]
+pub fn fast_corners_f32_py(image: PyHostArray3<'_, f32>, threshold: f32, nonmax: bool, max_corners: usize) -> PyKeypoints {
+    to_py_keypoints(&fast_corners_f32(image.as_array(), threshold, nonmax, max_corners))
+}
+
+/// Detect ORB features in a uint8 image.
+///
+/// # Returns
+/// Tuple of (keypoints, descriptors) with (x, y, response, angle) keypoint
+/// tuples and a (N, 32) uint8 descriptor array
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "orb_features", signature = (image, max_features=500, fast_threshold=20.0))]
+pub fn orb_features_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    max_features: usize,
+    fast_threshold: f32,
+) -> (PyKeypoints, Bound<'py, PyArray2<u8>>) {
+    to_py_orb(image.py(), orb_u8(image.as_array(), max_features, fast_threshold))
+}
+
+/// Detect ORB features in a float32 image.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "orb_features_f32", signature = (image, max_features=500, fast_threshold=0.08))]
+pub fn orb_features_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    max_features: usize,
+    fast_threshold: f32,
+) -> (PyKeypoints, Bound<'py, PyArray2<u8>>) {
+    to_py_orb(image.py(), orb_f32(image.as_array(), max_features, fast_threshold))
+}
+
+/// Brute-force match (N, 32) uint8 descriptor arrays by Hamming distance.
+///
+/// # Returns
+/// List of (query_index, train_index, distance) tuples, best first
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "match_descriptors", signature = (query, train, max_distance=64, cross_check=true))]
+pub fn match_descriptors_py(
+    query: PyHostArray2<'_, u8>,
+    train: PyHostArray2<'_, u8>,
+    max_distance: u32,
+    cross_check: bool,
+) -> PyResult<Vec<(usize, usize, u32)>> {
+    let (query, train) = (from_py_descriptors(&query)?, from_py_descriptors(&train)?);
+    let matches = match_descriptors(&query, &train, max_distance, cross_check);
+    Ok(matches.iter().map(|m| (m.query, m.train, m.distance)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dark image with a bright axis-aligned square from (20, 15) to (44, 39).
+    fn square() -> Array3<f32> {
+        Array3::from_shape_fn((60, 70, 1), |(y, x, _)| if (20..45).contains(&x) && (15..40).contains(&y) { 0.9 } else { 0.1 })
+    }
+
+    /// Random 4x4 blocks, shifted by (dx, dy).
+    fn texture(dx: usize, dy: usize) -> Array3<f32> {
+        Array3::from_shape_fn((96, 128, 3), |(y, x, c)| {
+            let (bx, by) = ((x + dx) / 4, (y + dy) / 4);
+            let h = (bx as u32).wrapping_mul(0x9E37_79B1) ^ (by as u32).wrapping_mul(0x85EB_CA77) ^ (c as u32 * 0x1234);
+            (h.wrapping_mul(0x27D4_EB2F) >> 24) as f32 / 255.0
+        })
+    }
+
+    fn near(points: &[Keypoint], x: f32, y: f32, tolerance: f32) -> bool {
+        points.iter().any(|p| (p.x - x).abs() <= tolerance && (p.y - y).abs() <= tolerance)
+    }
+
+    #[test]
+    fn test_harris_and_fast_find_square_corners() {
+        let image = square();
+        let harris = harris_corners_f32(image.view(), 0.04, 0.1, 0);
+        let fast = fast_corners_f32(image.view(), 0.2, true, 0);
+        for (x, y) in [(20.0, 15.0), (44.0, 15.0), (20.0, 39.0), (44.0, 39.0)] {
+            assert!(near(&harris, x, y, 2.0), "harris misses ({x}, {y}): {harris:?}");
+            assert!(near(&fast, x, y, 2.0), "fast misses ({x}, {y}): {fast:?}");
+        }
+        // Straight edges are not corners
+        assert!(!near(&harris, 32.0, 15.0, 3.0) && !near(&fast, 32.0, 15.0, 3.0));
+        assert!(fast_corners_f32(Array3::from_elem((20, 20, 1), 0.5).view(), 0.1, true, 0).is_empty());
+    }
+
+    #[test]
+    fn test_orb_matches_shifted_image() {
+        let (a, b) = (texture(0, 0), texture(7, 5));
+        let fa = orb_f32(a.view(), 200, 0.08);
+        let fb = orb_f32(b.view(), 200, 0.08);
+        assert_eq!(fa.keypoints.len(), fa.descriptors.len());
+        assert!(fa.keypoints.len() > 20);
+
+        let matches = match_descriptors(&fa.descriptors, &fb.descriptors, 64, true);
+        assert!(matches.len() > 10);
+        let consistent = matches
+            .iter()
+            .filter(|m| {
+                let (p, q) = (fa.keypoints[m.query], fb.keypoints[m.train]);
+                (p.x - q.x - 7.0).abs() <= 1.0 && (p.y - q.y - 5.0).abs() <= 1.0
+            })
+            .count();
+        assert!(consistent * 10 >= matches.len() * 8, "{consistent} of {} matches consistent", matches.len());
+    }
+
+    #[test]
+    fn test_hamming_and_cross_check() {
+        let (zero, ones) = ([0u8; 32], [0xFFu8; 32]);
+        let mut one_bit = zero;
+        one_bit[5] = 0b100;
+        assert_eq!(hamming(&zero, &ones), 256);
+        assert_eq!(hamming(&zero, &one_bit), 1);
+
+        // Both queries prefer train 0; cross-check keeps only the mutual pair
+        let matches = match_descriptors(&[zero, one_bit], &[zero, ones], 64, true);
+        assert_eq!(matches, vec![FeatureMatch { query: 0, train: 0, distance: 0 }]);
+        assert_eq!(match_descriptors(&[zero, one_bit], &[zero, ones], 64, false).len(), 2);
+    }
+}
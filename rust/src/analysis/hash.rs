@@ -0,0 +1,221 @@
+//! Perceptual hashes for duplicate and near-duplicate detection.
+//!
+//! Each hash reduces the luma to a tiny thumbnail and keeps one bit per
+//! cell, so resizing, recompression and mild color or brightness changes
+//! flip few bits while different images differ in about half of them.
+//! Compare hashes with [`hamming_distance`]; distances up to about 10 of 64
+//! usually mean the same picture.
+//!
+//! - [`ahash_f32`]: 8x8 average hash, bit = cell brighter than the mean
+//! - [`dhash_f32`]: 9x8 difference hash, bit = cell brighter than its left
+//!   neighbor; robust to global brightness and contrast
+//! - [`phash_f32`]: DCT hash, bit = low-frequency coefficient above their
+//!   median; the most robust of the three
+//!
+//! Bits are row-major with the first cell in the most significant bit,
+//! the same bit layout as the Python `imagehash` package. Alpha is ignored.
+
+use ndarray::{Array2, ArrayView3};
+
+use crate::filters::grayscale::LumaStandard;
+
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Luma thumbnail of (rows, cols) cells, each the mean of the pixels it covers.
+fn luma_thumbnail(image: &ArrayView3<f32>, rows: usize, cols: usize) -> Array2<f32> {
+    let (height, width, channels) = image.dim();
+    let luma = LumaStandard::default();
+    let value = |y: usize, x: usize| {
+        if channels >= 3 {
+            luma.luma(image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]])
+        } else {
+            image[[y, x, 0]]
+        }
+    };
+    // Cell ranges; images smaller than the thumbnail repeat pixels
+    let span = |i: usize, cells: usize, size: usize| {
+        let start = (i * size / cells).min(size - 1);
+        (start, ((i + 1) * size / cells).max(start + 1))
+    };
+    Array2::from_shape_fn((rows, cols), |(r, c)| {
+        let (y0, y1) = span(r, rows, height);
+        let (x0, x1) = span(c, cols, width);
+        let sum: f32 = (y0..y1).flat_map(|y| (x0..x1).map(move |x| (y, x))).map(|(y, x)| value(y, x)).sum();
+        sum / ((y1 - y0) * (x1 - x0)) as f32
+    })
+}
+
+/// Pack booleans into a u64, first value in the most significant bit.
+fn pack_bits(bits: impl Iterator<Item = bool>) -> u64 {
+    bits.fold(0u64, |hash, bit| (hash << 1) | bit as u64)
+}
+
+/// 1D DCT-II (unnormalized) of every row.
+fn dct_rows(plane: &Array2<f32>) -> Array2<f32> {
+    let (rows, n) = plane.dim();
+    let cosines = Array2::from_shape_fn((n, n), |(k, i)| {
+        (std::f64::consts::PI * k as f64 * (2 * i + 1) as f64 / (2 * n) as f64).cos() as f32
+    });
+    Array2::from_shape_fn((rows, n), |(r, k)| (0..n).map(|i| plane[[r, i]] * cosines[[k, i]]).sum())
+}
+
+/// Average hash - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+///
+/// # Returns
+/// 64-bit hash; an empty image hashes to 0
+pub fn ahash_f32(image: ArrayView3<f32>) -> u64 {
+    let (height, width, _) = image.dim();
+    if width == 0 || height == 0 {
+        return 0;
+    }
+    let thumb = luma_thumbnail(&image, 8, 8);
+    let mean = thumb.mean().unwrap_or(0.0);
+    pack_bits(thumb.iter().map(|&v| v > mean))
+}
+
+/// Difference hash - f32 version.
+///
+/// # Returns
+/// 64-bit hash; an empty image hashes to 0
+pub fn dhash_f32(image: ArrayView3<f32>) -> u64 {
+    let (height, width, _) = image.dim();
+    if width == 0 || height == 0 {
+        return 0;
+    }
+    let thumb = luma_thumbnail(&image, 8, 9);
+    pack_bits((0..8).flat_map(|r| (0..8).map(move |c| (r, c))).map(|(r, c)| thumb[[r, c + 1]] > thumb[[r, c]]))
+}
+
+/// Perceptual (DCT) hash - f32 version.
+///
+/// The luma is reduced to 32x32, transformed with a 2D DCT-II, and the 8x8
+/// lowest frequencies are compared to their median.
+///
+/// # Returns
+/// 64-bit hash; an empty image hashes to 0
+pub fn phash_f32(image: ArrayView3<f32>) -> u64 {
+    let (height, width, _) = image.dim();
+    if width == 0 || height == 0 {
+        return 0;
+    }
+    let thumb = luma_thumbnail(&image, 32, 32);
+    let dct = dct_rows(&dct_rows(&thumb).reversed_axes()).reversed_axes();
+    let low: Vec<f32> = (0..8).flat_map(|r| (0..8).map(move |c| (r, c))).map(|p| dct[p]).collect();
+    let mut sorted = low.clone();
+    sorted.sort_by(f32::total_cmp);
+    let median = (sorted[31] + sorted[32]) / 2.0;
+    pack_bits(low.iter().map(|&v| v > median))
+}
+
+/// Average hash - u8 version.
+pub fn ahash_u8(image: ArrayView3<u8>) -> u64 {
+    ahash_f32(image.mapv(|v| v as f32).view())
+}
+
+/// Difference hash - u8 version.
+pub fn dhash_u8(image: ArrayView3<u8>) -> u64 {
+    dhash_f32(image.mapv(|v| v as f32).view())
+}
+
+/// Perceptual (DCT) hash - u8 version.
+pub fn phash_u8(image: ArrayView3<u8>) -> u64 {
+    phash_f32(image.mapv(|v| v as f32).view())
+}
+
+/// Number of differing bits between two hashes (0 = identical, 64 = inverted).
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Average hash of a uint8 image as a 64-bit integer.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "ahash")]
+pub fn ahash_py(image: PyHostArray3<'_, u8>) -> u64 {
+    ahash_u8(image.as_array())
+}
+
+/// Average hash of a float32 image as a 64-bit integer.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "ahash_f32")]
+pub fn ahash_f32_py(image: PyHostArray3<'_, f32>) -> u64 {
+    ahash_f32(image.as_array())
+}
+
+/// Difference hash of a uint8 image as a 64-bit integer.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "dhash")]
+pub fn dhash_py(image: PyHostArray3<'_, u8>) -> u64 {
+    dhash_u8(image.as_array())
+}
+
+/// Difference hash of a float32 image as a 64-bit integer.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "dhash_f32")]
+pub fn dhash_f32_py(image: PyHostArray3<'_, f32>) -> u64 {
+    dhash_f32(image.as_array())
+}
+
+/// Perceptual (DCT) hash of a uint8 image as a 64-bit integer.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "phash")]
+pub fn phash_py(image: PyHostArray3<'_, u8>) -> u64 {
+    phash_u8(image.as_array())
+}
+
+/// Perceptual (DCT) hash of a float32 image as a 64-bit integer.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "phash_f32")]
+pub fn phash_f32_py(image: PyHostArray3<'_, f32>) -> u64 {
+    phash_f32(image.as_array())
+}
+
+/// Number of differing bits between two 64-bit hashes.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "hamming_distance")]
+pub fn hamming_distance_py(a: u64, b: u64) -> u32 {
+    hamming_distance(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+
+    #[test]
+    fn test_hashes_survive_edits_and_separate_images() {
+        let scene = |y: usize, x: usize, w: usize| {
+            let (u, v) = (x as f32 / w as f32, y as f32 / w as f32);
+            0.5 + 0.3 * (7.0 * u).sin() * (5.0 * v).cos() + 0.2 * u
+        };
+        let image = Array3::from_shape_fn((128, 128, 3), |(y, x, _)| scene(y, x, 128));
+        // Downscaled and brightened copy
+        let edited = Array3::from_shape_fn((64, 64, 3), |(y, x, _)| scene(y, x, 64) * 0.9 + 0.08);
+        // Unrelated image
+        let other = Array3::from_shape_fn((128, 128, 3), |(y, x, _)| if (x / 16 + y / 32).is_multiple_of(2) { 0.9 } else { 0.1 });
+
+        for hash in [ahash_f32, dhash_f32, phash_f32] {
+            let (a, b, c) = (hash(image.view()), hash(edited.view()), hash(other.view()));
+            assert!(hamming_distance(a, b) <= 6, "edit distance {}", hamming_distance(a, b));
+            assert!(hamming_distance(a, c) >= 16, "unrelated distance {}", hamming_distance(a, c));
+        }
+        assert_eq!(hamming_distance(0, u64::MAX), 64);
+        assert_eq!(ahash_f32(Array3::<f32>::zeros((0, 0, 3)).view()), 0);
+    }
+}
@@ -0,0 +1,56 @@
+//! Image analysis tools that produce measurements instead of images.
+//!
+//! - **Contrast**: WCAG contrast ratio of a text color against image
+//!   pixels or blocks with AA / AAA pass levels
+//! - **Features**: Harris and FAST corner detection, ORB descriptors and
+//!   brute-force Hamming matching for alignment and panorama seeding
+//! - **Palette**: k-means dominant colors with weights for theme
+//!   extraction, and mapping images onto a palette
+//! - **Flow**: dense optical flow (Farnebäck or Lucas-Kanade), warping
+//!   along the flow and in-between frame interpolation
+//! - **Hash**: average, difference and DCT perceptual hashes with Hamming
+//!   distance for duplicate and near-duplicate detection
+//! - **Motion**: cleaned binary masks of regions that changed between
+//!   frames for auto-masking moving subjects
+//! - **Noise**: noise sigma estimation to parameterize auto-denoise
+//! - **Sharpness**: variance-of-Laplacian blur score and per-block focus
+//!   heatmaps
+//! - **Stats**: alpha-weighted average color of a rectangle or mask,
+//!   eyedropper sampling (point, square or round window), and histograms
+//!   and channel statistics restricted to a selection
+//! - **Skin**: soft skin tone masks to restrict retouching filters to skin
+//! - **Superpixels**: SLIC label maps for selection snapping and
+//!   region-based filters
+
+pub mod contrast;
+pub mod features;
+pub mod flow;
+pub mod hash;
+pub mod motion;
+pub mod noise;
+pub mod palette;
+pub mod sharpness;
+pub mod skin;
+pub mod stats;
+pub mod superpixels;
+
+pub use contrast::{contrast_ratio, contrast_ratio_map_f32, contrast_ratio_map_u8, relative_luminance, wcag_level, ContrastMap};
+pub use features::{
+    fast_corners_f32, fast_corners_u8, harris_corners_f32, harris_corners_u8, match_descriptors, orb_f32, orb_u8,
+    Descriptor, FeatureMatch, Keypoint, OrbFeatures,
+};
+pub use flow::{
+    interpolate_frame_f32, interpolate_frame_u8, optical_flow_f32, optical_flow_u8, warp_by_flow_f32, warp_by_flow_u8,
+    FlowMethod,
+};
+pub use hash::{ahash_f32, ahash_u8, dhash_f32, dhash_u8, hamming_distance, phash_f32, phash_u8};
+pub use motion::{motion_mask_f32, motion_mask_u8};
+pub use noise::{estimate_noise_sigma_f32, estimate_noise_sigma_u8, NoiseEstimate};
+pub use palette::{extract_palette, posterize_to_palette, PaletteColor};
+pub use sharpness::{estimate_blur_f32, estimate_blur_u8, sharpness_map_f32, sharpness_map_u8};
+pub use skin::{skin_mask_f32, skin_mask_u8};
+pub use stats::{
+    average_color_f32, average_color_u8, histogram_masked_f32, histogram_masked_u8, sample_color_f32, sample_color_u8,
+    stats_masked_f32, stats_masked_u8, ChannelStats, SampleMode,
+};
+pub use superpixels::{slic_f32, slic_u8};
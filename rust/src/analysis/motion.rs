@@ -0,0 +1,161 @@
+//! Motion masks from frame differences.
+//!
+//! [`motion_mask_f32`] marks where two frames of a burst or video differ:
+//! the largest per-channel difference is blurred to suppress sensor noise
+//! and thresholded, morphological opening removes speckles and closing
+//! fills pinholes, and connected regions smaller than `min_area` are
+//! dropped. The result auto-masks moving subjects against a static
+//! background.
+
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+
+use crate::filters::blur_wasm::gaussian_blur_wasm_f32;
+use crate::filters::morphology::{close_u8, open_u8};
+use crate::selection::components::remove_small_components;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Radius of the opening that removes isolated changed pixels.
+const OPEN_RADIUS: f32 = 1.0;
+/// Radius of the closing that fills small holes in moving areas.
+const CLOSE_RADIUS: f32 = 2.0;
+
+/// Binary mask of changed regions between two frames - f32 version.
+///
+/// # Arguments
+/// * `frame_a`, `frame_b` - Frames of the same shape, values 0.0-1.0
+/// * `threshold` - Smallest difference (0.0-1.0) of any channel that
+///   counts as motion, after blurring
+/// * `blur` - Gaussian sigma applied to the difference (0 = none)
+/// * `min_area` - Smallest changed region to keep, in pixels
+///
+/// # Returns
+/// Mask (height, width), 255 = changed, 0 = static
+///
+/// # Panics
+/// If the frames differ in shape.
+pub fn motion_mask_f32(frame_a: ArrayView3<f32>, frame_b: ArrayView3<f32>, threshold: f32, blur: f32, min_area: usize) -> Array2<u8> {
+    assert_eq!(frame_a.dim(), frame_b.dim(), "frames must have the same shape");
+    let (height, width, channels) = frame_a.dim();
+    if height == 0 || width == 0 {
+        return Array2::zeros((height, width));
+    }
+    let difference = Array3::from_shape_fn((height, width, 1), |(y, x, _)| {
+        (0..channels).map(|c| (frame_a[[y, x, c]] - frame_b[[y, x, c]]).abs()).fold(0.0, f32::max)
+    });
+    let difference = gaussian_blur_wasm_f32(difference.view(), blur);
+    let binary = difference.mapv(|d| if d >= threshold { 255u8 } else { 0 });
+
+    let cleaned = close_u8(open_u8(binary.view(), OPEN_RADIUS).view(), CLOSE_RADIUS);
+    let flat = cleaned.remove_axis(Axis(2)).into_raw_vec_and_offset().0;
+    let kept = remove_small_components(&flat, width, height, 128, min_area);
+    Array2::from_shape_vec((height, width), kept).unwrap()
+}
+
+/// Binary mask of changed regions between two frames - u8 version.
+pub fn motion_mask_u8(frame_a: ArrayView3<u8>, frame_b: ArrayView3<u8>, threshold: f32, blur: f32, min_area: usize) -> Array2<u8> {
+    let a = frame_a.mapv(|v| v as f32 / 255.0);
+    let b = frame_b.mapv(|v| v as f32 / 255.0);
+    motion_mask_f32(a.view(), b.view(), threshold, blur, min_area)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+fn check_frames(a: (usize, usize, usize), b: (usize, usize, usize)) -> PyResult<()> {
+    if a != b {
+        return Err(PyValueError::new_err(format!("frames must have the same shape, got {:?} and {:?}", a, b)));
+    }
+    Ok(())
+}
+
+/// Binary mask of regions that changed between two uint8 frames.
+///
+/// # Arguments
+/// * `threshold` - Smallest channel difference (0.0-1.0) counted as motion
+/// * `blur` - Gaussian sigma applied to the difference
+/// * `min_area` - Smallest changed region to keep, in pixels
+///
+/// # Returns
+/// (H, W) uint8 mask, 255 = changed
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "motion_mask", signature = (frame_a, frame_b, threshold=0.1, blur=1.5, min_area=64))]
+pub fn motion_mask_py<'py>(
+    py: Python<'py>,
+    frame_a: PyHostArray3<'py, u8>,
+    frame_b: PyHostArray3<'py, u8>,
+    threshold: f32,
+    blur: f32,
+    min_area: usize,
+) -> PyResult<Bound<'py, PyArray2<u8>>> {
+    let (a, b) = (frame_a.as_array(), frame_b.as_array());
+    check_frames(a.dim(), b.dim())?;
+    Ok(motion_mask_u8(a, b, threshold, blur, min_area).into_pyarray(py))
+}
+
+/// Binary mask of regions that changed between two float32 frames.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "motion_mask_f32", signature = (frame_a, frame_b, threshold=0.1, blur=1.5, min_area=64))]
+pub fn motion_mask_f32_py<'py>(
+    py: Python<'py>,
+    frame_a: PyHostArray3<'py, f32>,
+    frame_b: PyHostArray3<'py, f32>,
+    threshold: f32,
+    blur: f32,
+    min_area: usize,
+) -> PyResult<Bound<'py, PyArray2<u8>>> {
+    let (a, b) = (frame_a.as_array(), frame_b.as_array());
+    check_frames(a.dim(), b.dim())?;
+    Ok(motion_mask_f32(a, b, threshold, blur, min_area).into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_subject_masked_noise_ignored() {
+        // Static gray background with faint noise; a bright 12x12 square
+        // moves from (10, 10) to (30, 10); one hot pixel flickers.
+        let frame = |sx: usize, hot: bool| {
+            Array3::from_shape_fn((40, 60, 3), |(y, x, c)| {
+                let noise = ((x * 7 + y * 13 + c * 3) % 5) as f32 * 0.004;
+                if (sx..sx + 12).contains(&x) && (10..22).contains(&y) {
+                    0.9
+                } else if hot && (x, y) == (50, 35) {
+                    1.0
+                } else {
+                    0.4 + noise
+                }
+            })
+        };
+        let (a, b) = (frame(10, false), frame(30, true));
+        let mask = motion_mask_f32(a.view(), b.view(), 0.1, 1.0, 20);
+        assert_eq!(mask.dim(), (40, 60));
+        assert_eq!(mask[[15, 15]], 255);
+        assert_eq!(mask[[15, 35]], 255);
+        assert_eq!(mask[[35, 50]], 0);
+        assert_eq!(mask[[30, 5]], 0);
+    }
+
+    #[test]
+    fn test_empty_frames_give_empty_mask() {
+        for (height, width) in [(0, 0), (0, 5), (5, 0)] {
+            let empty = Array3::<u8>::zeros((height, width, 3));
+            assert_eq!(motion_mask_u8(empty.view(), empty.view(), 0.1, 1.5, 64).dim(), (height, width));
+            let empty = Array3::<f32>::zeros((height, width, 1));
+            assert_eq!(motion_mask_f32(empty.view(), empty.view(), 0.1, 0.0, 0).dim(), (height, width));
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Dominant color palettes.
+//!
+//! [`extract_palette`] clusters the colors of an image with k-means and
+//! returns the cluster centers together with the share of pixels each one
+//! covers, e.g. for deriving a UI theme from a photo. Colors are first
+//! binned into a 15-bit histogram, so the cost of an iteration depends on
+//! the number of distinct colors rather than the image size. Centers start
+//! with k-means++ seeding driven by a position hash, so a given seed always
+//! gives the same palette.
+//!
+//! [`posterize_to_palette`] maps an image onto any palette, optionally with
+//! Floyd-Steinberg dithering; the GIF quantizer uses the same mapping.
+
+use ndarray::{Array2, Array3, ArrayView3, Axis};
+
+use crate::filters::noise::hash_uniform;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Alpha below which a pixel counts as transparent.
+pub(crate) const ALPHA_THRESHOLD: u8 = 128;
+
+/// Dominant color and the share of opaque pixels it covers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaletteColor {
+    /// Color (R, G, B)
+    pub color: [u8; 3],
+    /// Fraction of opaque pixels 0.0-1.0; all weights sum to 1.0
+    pub weight: f32,
+}
+
+// ============================================================================
+// K-Means Palette
+// ============================================================================
+
+#[inline]
+fn distance_sq(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]) * (a[i] - b[i])).sum()
+}
+
+/// Mean color and pixel count of every occupied 15-bit histogram bin.
+fn color_bins(image: ArrayView3<u8>) -> Vec<([f32; 3], f32)> {
+    let channels = image.shape()[2];
+    let mut histogram = vec![([0u64; 3], 0u64); 1 << 15];
+    for pixel in image.lanes(Axis(2)) {
+        if channels == 4 && pixel[3] < ALPHA_THRESHOLD {
+            continue;
+        }
+        let rgb = if channels >= 3 { [pixel[0], pixel[1], pixel[2]] } else { [pixel[0]; 3] };
+        let key = ((rgb[0] as usize >> 3) << 10) | ((rgb[1] as usize >> 3) << 5) | (rgb[2] as usize >> 3);
+        let (sum, count) = &mut histogram[key];
+        for (s, &v) in sum.iter_mut().zip(&rgb) {
+            *s += v as u64;
+        }
+        *count += 1;
+    }
+    histogram
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .map(|(sum, count)| (sum.map(|s| s as f32 / count as f32), count as f32))
+        .collect()
+}
+
+/// Index of the bin picked with probability proportional to `weights`.
+fn weighted_pick(weights: &[f32], draw: usize, seed: u64) -> usize {
+    let total: f32 = weights.iter().sum();
+    let mut target = hash_uniform(draw as i64, 0, 0, seed) * total;
+    for (i, &w) in weights.iter().enumerate() {
+        if target < w {
+            return i;
+        }
+        target -= w;
+    }
+    weights.iter().rposition(|&w| w > 0.0).unwrap_or(0)
+}
+
+/// k-means++ seeding: each further center is drawn with probability
+/// proportional to pixel count times squared distance to the nearest center.
+fn seed_centers(bins: &[([f32; 3], f32)], k: usize, seed: u64) -> Vec<[f32; 3]> {
+    let counts: Vec<f32> = bins.iter().map(|&(_, count)| count).collect();
+    let mut centers = vec![bins[weighted_pick(&counts, 0, seed)].0];
+    let mut nearest: Vec<f32> = bins.iter().map(|(color, _)| distance_sq(color, &centers[0])).collect();
+    while centers.len() < k {
+        let weights: Vec<f32> = bins.iter().zip(&nearest).map(|(&(_, count), &d)| count * d).collect();
+        if weights.iter().all(|&w| w == 0.0) {
+            break;
+        }
+        let center = bins[weighted_pick(&weights, centers.len(), seed)].0;
+        for (d, (color, _)) in nearest.iter_mut().zip(bins) {
+            *d = d.min(distance_sq(color, &center));
+        }
+        centers.push(center);
+    }
+    centers
+}
+
+/// Extract the dominant colors of an image with k-means clustering.
+///
+/// Pixels with alpha below 128 are ignored. Fewer than `k` colors are
+/// returned if the image has fewer distinct colors.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `k` - Number of colors (1-256)
+/// * `max_iterations` - Upper bound for k-means iterations; stops earlier
+///   once no color changes its cluster
+/// * `seed` - Seed for the initial centers
+///
+/// # Returns
+/// Colors sorted by weight, largest first; empty if no pixel is opaque
+pub fn extract_palette(image: ArrayView3<u8>, k: usize, max_iterations: usize, seed: u64) -> Vec<PaletteColor> {
+    let bins = color_bins(image);
+    if bins.is_empty() {
+        return Vec::new();
+    }
+    let mut centers = seed_centers(&bins, k.clamp(1, 256).min(bins.len()), seed);
+    let mut assignment = vec![usize::MAX; bins.len()];
+
+    for _ in 0..max_iterations.max(1) {
+        let mut changed = false;
+        for ((color, _), a) in bins.iter().zip(assignment.iter_mut()) {
+            let best = nearest_center(&centers, color);
+            changed |= *a != best;
+            *a = best;
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0f64; 3], 0.0f64); centers.len()];
+        for (&(color, count), &a) in bins.iter().zip(&assignment) {
+            let (sum, total) = &mut sums[a];
+            for (s, v) in sum.iter_mut().zip(color) {
+                *s += (v * count) as f64;
+            }
+            *total += count as f64;
+        }
+        for (center, (sum, total)) in centers.iter_mut().zip(&sums) {
+            if *total > 0.0 {
+                *center = sum.map(|s| (s / total) as f32);
+            }
+        }
+    }
+
+    let mut counts = vec![0.0f32; centers.len()];
+    for (&(_, count), &a) in bins.iter().zip(&assignment) {
+        counts[a] += count;
+    }
+    let total: f32 = counts.iter().sum();
+    let mut palette: Vec<PaletteColor> = centers
+        .iter()
+        .zip(&counts)
+        .filter(|&(_, &count)| count > 0.0)
+        .map(|(center, &count)| PaletteColor {
+            color: center.map(|v| v.round().clamp(0.0, 255.0) as u8),
+            weight: count / total,
+        })
+        .collect();
+    palette.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    palette
+}
+
+fn nearest_center(centers: &[[f32; 3]], color: &[f32; 3]) -> usize {
+    let mut best = 0;
+    let mut best_dist = f32::MAX;
+    for (i, center) in centers.iter().enumerate() {
+        let dist = distance_sq(center, color);
+        if dist < best_dist {
+            best_dist = dist;
+            best = i;
+        }
+    }
+    best
+}
+
+// ============================================================================
+// Palette Mapping
+// ============================================================================
+
+fn nearest(palette: &[[u8; 3]], rgb: [f32; 3]) -> usize {
+    let mut best = 0;
+    let mut best_dist = f32::MAX;
+    for (i, color) in palette.iter().enumerate() {
+        let dr = color[0] as f32 - rgb[0];
+        let dg = color[1] as f32 - rgb[1];
+        let db = color[2] as f32 - rgb[2];
+        let dist = dr * dr + dg * dg + db * db;
+        if dist < best_dist {
+            best_dist = dist;
+            best = i;
+        }
+    }
+    best
+}
+
+/// Map an image to palette indices, optionally with error diffusion.
+///
+/// Pixels with alpha below 128 get `transparent` if set.
+pub(crate) fn palette_indices(image: ArrayView3<u8>, palette: &[[u8; 3]], transparent: Option<u8>, dither: bool) -> Array2<u8> {
+    let (height, width, channels) = image.dim();
+    let mut indices = Array2::<u8>::zeros((height, width));
+    if palette.is_empty() {
+        return indices;
+    }
+
+    // Error rows for the current and the next scanline (one pixel of padding per side)
+    let mut error = vec![[0.0f32; 3]; width + 2];
+    let mut next_error = vec![[0.0f32; 3]; width + 2];
+
+    for y in 0..height {
+        for x in 0..width {
+            if channels == 4 && image[[y, x, 3]] < ALPHA_THRESHOLD {
+                if let Some(t) = transparent {
+                    indices[[y, x]] = t;
+                    continue;
+                }
+            }
+            let source = if channels >= 3 {
+                [image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]]
+            } else {
+                [image[[y, x, 0]]; 3]
+            };
+            let rgb = [0, 1, 2].map(|c| (source[c] as f32 + error[x + 1][c]).clamp(0.0, 255.0));
+            let index = nearest(palette, rgb);
+            indices[[y, x]] = index as u8;
+
+            if dither {
+                let color = palette[index];
+                for c in 0..3 {
+                    let err = rgb[c] - color[c] as f32;
+                    error[x + 2][c] += err * 7.0 / 16.0;
+                    next_error[x][c] += err * 3.0 / 16.0;
+                    next_error[x + 1][c] += err * 5.0 / 16.0;
+                    next_error[x + 2][c] += err * 1.0 / 16.0;
+                }
+            }
+        }
+        std::mem::swap(&mut error, &mut next_error);
+        next_error.iter_mut().for_each(|e| *e = [0.0; 3]);
+    }
+    indices
+}
+
+/// Replace every pixel with its nearest palette color.
+///
+/// Grayscale images get the luminance of the chosen color; alpha is
+/// preserved. An empty palette returns the image unchanged.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `palette` - Palette colors (R, G, B), at most 256 are used
+/// * `dither` - Apply Floyd-Steinberg dithering
+///
+/// # Returns
+/// Image with same channel count, colors limited to the palette
+pub fn posterize_to_palette(image: ArrayView3<u8>, palette: &[[u8; 3]], dither: bool) -> Array3<u8> {
+    if palette.is_empty() {
+        return image.to_owned();
+    }
+    let palette = &palette[..palette.len().min(256)];
+    let indices = palette_indices(image, palette, None, dither);
+    let mut output = image.to_owned();
+    let channels = image.dim().2;
+    for ((y, x), &index) in indices.indexed_iter() {
+        let [r, g, b] = palette[index as usize];
+        if channels >= 3 {
+            output[[y, x, 0]] = r;
+            output[[y, x, 1]] = g;
+            output[[y, x, 2]] = b;
+        } else {
+            output[[y, x, 0]] = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32).round() as u8;
+        }
+    }
+    output
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Extract the dominant colors of a uint8 image.
+///
+/// # Returns
+/// Tuple of (colors (n, 3) uint8, weights (n,) float32), largest weight first
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "extract_palette", signature = (image, k=8, max_iterations=20, seed=0))]
+pub fn extract_palette_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    k: usize,
+    max_iterations: usize,
+    seed: u64,
+) -> (Bound<'py, PyArray2<u8>>, Bound<'py, PyArray1<f32>>) {
+    let py = image.py();
+    let palette = extract_palette(image.as_array(), k, max_iterations, seed);
+    let colors = Array2::from_shape_fn((palette.len(), 3), |(i, c)| palette[i].color[c]);
+    let weights: Vec<f32> = palette.iter().map(|p| p.weight).collect();
+    (colors.into_pyarray(py), PyArray1::from_vec(py, weights))
+}
+
+/// Map a uint8 image onto a (n, 3) uint8 palette.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "posterize_to_palette", signature = (image, palette, dither=false))]
+pub fn posterize_to_palette_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    palette: PyHostArray2<'py, u8>,
+    dither: bool,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let palette = palette.as_array();
+    if palette.ncols() != 3 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "palette must have shape (n, 3), got {:?}",
+            palette.shape()
+        )));
+    }
+    let colors: Vec<[u8; 3]> = palette.rows().into_iter().map(|row| [row[0], row[1], row[2]]).collect();
+    Ok(posterize_to_palette(image.as_array(), &colors, dither).into_pyarray(image.py()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_palette_finds_dominant_colors() {
+        // 3/4 red, 1/4 blue with slight noise, plus a transparent green stripe
+        let image = Array3::from_shape_fn((16, 16, 4), |(y, x, c)| {
+            let jitter = ((x * 7 + y * 3) % 5) as u8;
+            match (c, y) {
+                (3, 0) => 0,
+                (3, _) => 255,
+                (_, 0) => [0, 255, 0][c],
+                _ if x < 12 => [220 + jitter, 20, 30][c],
+                _ => [10, 40 + jitter, 200][c],
+            }
+        });
+        let palette = extract_palette(image.view(), 2, 20, 7);
+        assert_eq!(palette.len(), 2);
+        assert!(palette[0].color[0] > 200 && palette[0].color[2] < 50);
+        assert!(palette[1].color[2] > 180 && palette[1].color[0] < 30);
+        assert!((palette[0].weight - 0.75).abs() < 1e-3);
+        assert!((palette[0].weight + palette[1].weight - 1.0).abs() < 1e-5);
+
+        // Deterministic per seed, capped by the number of distinct colors
+        assert_eq!(extract_palette(image.view(), 2, 20, 7), palette);
+        let flat = Array3::from_elem((4, 4, 3), 90u8);
+        assert_eq!(extract_palette(flat.view(), 5, 20, 0), vec![PaletteColor { color: [90; 3], weight: 1.0 }]);
+        assert!(extract_palette(Array3::<u8>::zeros((0, 0, 3)).view(), 4, 10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_posterize_to_palette() {
+        let image = Array3::from_shape_fn((8, 32, 4), |(_, x, c)| if c == 3 { 77 } else { (x * 8) as u8 });
+        let palette = [[0, 0, 0], [255, 255, 255]];
+        let result = posterize_to_palette(image.view(), &palette, false);
+        for ((y, x, c), &v) in result.indexed_iter() {
+            let expected = if c == 3 { 77 } else if x * 8 < 128 { 0 } else { 255 };
+            assert_eq!(v, expected, "at {y},{x},{c}");
+        }
+
+        // Dithering keeps the average of a flat gray
+        let gray = Array3::from_elem((16, 16, 1), 100u8);
+        let dithered = posterize_to_palette(gray.view(), &palette, true);
+        let mean = dithered.iter().map(|&v| v as f32).sum::<f32>() / dithered.len() as f32;
+        assert!((mean - 100.0).abs() < 8.0);
+        assert!(dithered.iter().all(|&v| v == 0 || v == 255));
+    }
+}
@@ -0,0 +1,189 @@
+//! Focus measures: blur estimation and sharpness heatmaps.
+//!
+//! Both use the variance of the Laplacian of the luma (Pech-Pacheco et
+//! al.): in-focus detail produces strong second derivatives, defocus blur
+//! flattens them. [`estimate_blur_f32`] gives one score for the whole image
+//! so uploads can be flagged as out of focus; [`sharpness_map_f32`] scores
+//! blocks so the UI can show where the focus lies and focus stacking can
+//! pick the sharpest frame per region.
+//!
+//! Scores are not normalized: they grow with contrast and depend on the
+//! value scale (u8 versions work in 0-255, so the customary threshold of
+//! about 100 for "blurry" applies).
+
+use ndarray::{Array2, ArrayView3};
+use rayon::prelude::*;
+
+use crate::filters::grayscale::LumaStandard;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Luma plane and per-pixel opacity (alpha > 0).
+fn luma_plane(image: &ArrayView3<f32>) -> (Array2<f32>, Array2<bool>) {
+    let (height, width, channels) = image.dim();
+    let luma = LumaStandard::default();
+    let plane = Array2::from_shape_fn((height, width), |(y, x)| {
+        if channels >= 3 {
+            luma.luma(image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]])
+        } else {
+            image[[y, x, 0]]
+        }
+    });
+    let opaque = Array2::from_shape_fn((height, width), |(y, x)| channels != 4 || image[[y, x, 3]] > 0.0);
+    (plane, opaque)
+}
+
+/// 4-neighbor Laplacian with edge clamping.
+fn laplacian(plane: &Array2<f32>) -> Array2<f32> {
+    let (height, width) = plane.dim();
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let up = plane[[y.saturating_sub(1), x]];
+        let down = plane[[(y + 1).min(height - 1), x]];
+        let left = plane[[y, x.saturating_sub(1)]];
+        let right = plane[[y, (x + 1).min(width - 1)]];
+        up + down + left + right - 4.0 * plane[[y, x]]
+    })
+}
+
+/// Variance of the Laplacian over the opaque pixels of a region.
+fn variance(values: impl Iterator<Item = f32>) -> f32 {
+    let (mut n, mut sum, mut sum_sq) = (0.0f64, 0.0f64, 0.0f64);
+    for v in values {
+        n += 1.0;
+        sum += v as f64;
+        sum_sq += (v as f64) * (v as f64);
+    }
+    if n == 0.0 {
+        return 0.0;
+    }
+    let mean = sum / n;
+    (sum_sq / n - mean * mean).max(0.0) as f32
+}
+
+/// Estimate how sharp an image is - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+///
+/// # Returns
+/// Variance of the Laplacian of the luma; lower means blurrier
+pub fn estimate_blur_f32(image: ArrayView3<f32>) -> f32 {
+    let (height, width, _) = image.dim();
+    if width == 0 || height == 0 {
+        return 0.0;
+    }
+    let (plane, opaque) = luma_plane(&image);
+    let response = laplacian(&plane);
+    variance(response.iter().zip(opaque.iter()).filter(|(_, &o)| o).map(|(&v, _)| v))
+}
+
+/// Estimate how sharp an image is - u8 version.
+///
+/// # Returns
+/// Variance of the Laplacian in 0-255 units; below about 100 usually means blurry
+pub fn estimate_blur_u8(image: ArrayView3<u8>) -> f32 {
+    let image_f32 = image.mapv(|v| v as f32);
+    estimate_blur_f32(image_f32.view())
+}
+
+/// Sharpness heatmap: variance of the Laplacian per block - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `block_size` - Edge length of a block in pixels; edge blocks may be smaller
+///
+/// # Returns
+/// Map (ceil(height / block_size), ceil(width / block_size)); higher = sharper
+pub fn sharpness_map_f32(image: ArrayView3<f32>, block_size: usize) -> Array2<f32> {
+    let (height, width, _) = image.dim();
+    let block = block_size.max(1);
+    let (rows, cols) = (height.div_ceil(block), width.div_ceil(block));
+    if rows == 0 || cols == 0 {
+        return Array2::zeros((rows, cols));
+    }
+    let (plane, opaque) = luma_plane(&image);
+    let response = laplacian(&plane);
+
+    let values: Vec<f32> = (0..rows * cols)
+        .into_par_iter()
+        .map(|i| {
+            let (by, bx) = (i / cols, i % cols);
+            let (y0, x0) = (by * block, bx * block);
+            let (y1, x1) = ((y0 + block).min(height), (x0 + block).min(width));
+            variance((y0..y1).flat_map(|y| (x0..x1).map(move |x| (y, x))).filter(|&p| opaque[p]).map(|p| response[p]))
+        })
+        .collect();
+    Array2::from_shape_vec((rows, cols), values).expect("map matches block grid")
+}
+
+/// Sharpness heatmap: variance of the Laplacian per block - u8 version (0-255 units).
+pub fn sharpness_map_u8(image: ArrayView3<u8>, block_size: usize) -> Array2<f32> {
+    let image_f32 = image.mapv(|v| v as f32);
+    sharpness_map_f32(image_f32.view(), block_size)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Estimate how sharp a uint8 image is (variance of the Laplacian, 0-255 units).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "estimate_blur")]
+pub fn estimate_blur_py(image: PyHostArray3<'_, u8>) -> f32 {
+    estimate_blur_u8(image.as_array())
+}
+
+/// Estimate how sharp a float32 image is (variance of the Laplacian, 0.0-1.0 units).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "estimate_blur_f32")]
+pub fn estimate_blur_f32_py(image: PyHostArray3<'_, f32>) -> f32 {
+    estimate_blur_f32(image.as_array())
+}
+
+/// Sharpness heatmap of a uint8 image, one value per block.
+///
+/// # Returns
+/// (ceil(H / block_size), ceil(W / block_size)) float32 map, higher = sharper
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "sharpness_map", signature = (image, block_size=32))]
+pub fn sharpness_map_py<'py>(image: PyHostArray3<'py, u8>, block_size: usize) -> Bound<'py, PyArray2<f32>> {
+    sharpness_map_u8(image.as_array(), block_size).into_pyarray(image.py())
+}
+
+/// Sharpness heatmap of a float32 image, one value per block.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "sharpness_map_f32", signature = (image, block_size=32))]
+pub fn sharpness_map_f32_py<'py>(image: PyHostArray3<'py, f32>, block_size: usize) -> Bound<'py, PyArray2<f32>> {
+    sharpness_map_f32(image.as_array(), block_size).into_pyarray(image.py())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::blur_wasm::gaussian_blur_wasm_f32;
+    use ndarray::Array3;
+
+    #[test]
+    fn test_blur_lowers_score_and_map_finds_focus() {
+        let checker = |y: usize, x: usize| ((x / 2 + y / 2) % 2) as f32;
+        let sharp = Array3::from_shape_fn((64, 64, 3), |(y, x, _)| checker(y, x));
+        let blurred = gaussian_blur_wasm_f32(sharp.view(), 2.0);
+        assert!(estimate_blur_f32(sharp.view()) > 10.0 * estimate_blur_f32(blurred.view()));
+        assert_eq!(estimate_blur_f32(Array3::<f32>::from_elem((8, 8, 1), 0.5).view()), 0.0);
+
+        // Sharp left half, blurred right half
+        let mixed = Array3::from_shape_fn((64, 70, 1), |(y, x, _)| if x < 32 { sharp[[y, x, 0]] } else { blurred[[y, x.min(63), 0]] });
+        let map = sharpness_map_f32(mixed.view(), 16);
+        assert_eq!(map.dim(), (4, 5));
+        assert!(map[[1, 0]] > 10.0 * map[[1, 3]]);
+    }
+}
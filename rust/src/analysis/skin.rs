@@ -0,0 +1,183 @@
+//! Skin tone masks.
+//!
+//! [`skin_mask_f32`] rates every pixel by how likely its color is skin,
+//! without any learned model: the chroma must fall into the skin ellipse
+//! of the CbCr plane (Hsu, Abdel-Mottaleb and Jain), gated by hue,
+//! saturation and brightness in HSV to reject grays, deep shadows and
+//! saturated oranges. Each test fades out instead of cutting off, and a
+//! Gaussian blur removes speckles, so the mask can restrict smoothing or
+//! clarity filters to skin without visible seams.
+
+use ndarray::{Array2, ArrayView3, Axis};
+
+use crate::filters::blur_wasm::gaussian_blur_wasm_f32;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Skin ellipse in the CbCr plane (0-255 scale): center, rotation,
+/// offset of the ellipse center in the rotated frame and semi-axes.
+const CB_CENTER: f32 = 109.38;
+const CR_CENTER: f32 = 152.02;
+const ELLIPSE_THETA: f32 = 2.53;
+const ELLIPSE_OFFSET: (f32, f32) = (1.60, 2.41);
+const ELLIPSE_AXES: (f32, f32) = (25.39, 14.03);
+
+/// Normalized ellipse distance at which membership reaches zero (1.0 is the ellipse edge).
+const ELLIPSE_FALLOFF: f32 = 2.0;
+
+#[inline]
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Membership of the CbCr ellipse: 1.0 inside, fading to 0.0 at `ELLIPSE_FALLOFF`.
+fn chroma_membership(r: f32, g: f32, b: f32) -> f32 {
+    let cb = 128.0 + 255.0 * (-0.168736 * r - 0.331264 * g + 0.5 * b);
+    let cr = 128.0 + 255.0 * (0.5 * r - 0.418688 * g - 0.081312 * b);
+    let (sin, cos) = ELLIPSE_THETA.sin_cos();
+    let (dx, dy) = (cb - CB_CENTER, cr - CR_CENTER);
+    let u = cos * dx + sin * dy - ELLIPSE_OFFSET.0;
+    let v = -sin * dx + cos * dy - ELLIPSE_OFFSET.1;
+    let d = (u / ELLIPSE_AXES.0).powi(2) + (v / ELLIPSE_AXES.1).powi(2);
+    1.0 - smoothstep(1.0, ELLIPSE_FALLOFF, d)
+}
+
+/// HSV plausibility: reddish-yellow hue, moderate saturation, not too dark.
+fn hsv_membership(r: f32, g: f32, b: f32) -> f32 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    if delta <= 0.0 {
+        return 0.0;
+    }
+    let hue = if max == r {
+        60.0 * ((g - b) / delta)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    // Hue in -180..180 so the band around red is contiguous
+    let hue = if hue > 180.0 { hue - 360.0 } else { hue };
+    let hue_weight = smoothstep(-25.0, -10.0, hue) * (1.0 - smoothstep(45.0, 60.0, hue));
+    let saturation = delta / max;
+    let saturation_weight = smoothstep(0.08, 0.2, saturation) * (1.0 - smoothstep(0.8, 0.95, saturation));
+    hue_weight * saturation_weight * smoothstep(0.1, 0.2, max)
+}
+
+/// Soft mask of likely skin tones - f32 version.
+///
+/// Grayscale images carry no chroma and give an all-zero mask. Alpha
+/// scales the result, so transparent pixels never count as skin.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `smoothing` - Gaussian sigma applied to the mask in pixels (0 = none)
+///
+/// # Returns
+/// Mask (height, width), 1.0 = skin, 0.0 = not skin
+pub fn skin_mask_f32(image: ArrayView3<f32>, smoothing: f32) -> Array2<f32> {
+    let (height, width, channels) = image.dim();
+    if channels < 3 {
+        return Array2::zeros((height, width));
+    }
+    let mask = Array2::from_shape_fn((height, width), |(y, x)| {
+        let (r, g, b) = (image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]);
+        let alpha = if channels == 4 { image[[y, x, 3]] } else { 1.0 };
+        chroma_membership(r, g, b) * hsv_membership(r, g, b) * alpha
+    });
+    if smoothing <= 0.0 {
+        return mask;
+    }
+    gaussian_blur_wasm_f32(mask.insert_axis(Axis(2)).view(), smoothing).remove_axis(Axis(2))
+}
+
+/// Soft mask of likely skin tones - u8 version.
+///
+/// # Returns
+/// Mask (height, width), 255 = skin, 0 = not skin
+pub fn skin_mask_u8(image: ArrayView3<u8>, smoothing: f32) -> Array2<u8> {
+    let image_f32 = image.mapv(|v| v as f32 / 255.0);
+    skin_mask_f32(image_f32.view(), smoothing).mapv(|v| (v * 255.0).round() as u8)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Soft mask of likely skin tones for a uint8 image.
+///
+/// # Returns
+/// (H, W) uint8 mask, 255 = skin
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "skin_mask", signature = (image, smoothing=2.0))]
+pub fn skin_mask_py<'py>(image: PyHostArray3<'py, u8>, smoothing: f32) -> Bound<'py, PyArray2<u8>> {
+    skin_mask_u8(image.as_array(), smoothing).into_pyarray(image.py())
+}
+
+/// Soft mask of likely skin tones for a float32 image.
+///
+/// # Returns
+/// (H, W) float32 mask, 1.0 = skin
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "skin_mask_f32", signature = (image, smoothing=2.0))]
+pub fn skin_mask_f32_py<'py>(image: PyHostArray3<'py, f32>, smoothing: f32) -> Bound<'py, PyArray2<f32>> {
+    skin_mask_f32(image.as_array(), smoothing).into_pyarray(image.py())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+
+    #[test]
+    fn test_skin_tones_pass_and_other_colors_fail() {
+        let colors: [([u8; 3], bool); 8] = [
+            ([224, 172, 140], true),  // light skin
+            ([198, 134, 66], true),   // medium skin
+            ([141, 85, 36], true),    // dark skin
+            ([128, 128, 128], false), // gray
+            ([40, 80, 200], false),   // blue
+            ([60, 160, 60], false),   // green
+            ([20, 12, 8], false),     // near black
+            ([250, 250, 245], false), // near white
+        ];
+        let image = Array3::from_shape_fn((1, colors.len(), 3), |(_, x, c)| colors[x].0[c]);
+        let mask = skin_mask_u8(image.view(), 0.0);
+        for (x, (color, skin)) in colors.iter().enumerate() {
+            let value = mask[[0, x]];
+            assert!(if *skin { value > 200 } else { value < 50 }, "{color:?} -> {value}");
+        }
+    }
+
+    #[test]
+    fn test_smoothing_and_alpha() {
+        // Skin patch with a transparent column and a stray skin pixel on blue
+        let image = Array3::from_shape_fn((20, 20, 4), |(y, x, c)| {
+            let skin = x < 10 || (y, x) == (15, 15);
+            match c {
+                3 => if x == 2 { 0.0 } else { 1.0 },
+                _ if skin => [0.88, 0.67, 0.55][c],
+                _ => [0.15, 0.3, 0.8][c],
+            }
+        });
+        let sharp = skin_mask_f32(image.view(), 0.0);
+        assert_eq!(sharp[[5, 2]], 0.0);
+        assert!(sharp[[5, 5]] > 0.9 && sharp[[15, 15]] > 0.9);
+
+        // Smoothing softens the boundary and fades the isolated pixel
+        let smooth = skin_mask_f32(image.view(), 2.0);
+        assert!(smooth[[5, 6]] > 0.9);
+        assert!(smooth[[5, 10]] > 0.05 && smooth[[5, 10]] < 0.5);
+        assert!(smooth[[15, 15]] < 0.2);
+        assert!(skin_mask_f32(Array3::<f32>::zeros((4, 4, 1)).view(), 1.0).iter().all(|&v| v == 0.0));
+    }
+}
@@ -0,0 +1,527 @@
+//! Color statistics of image regions.
+//!
+//! [`average_color_f32`] averages a rectangle and/or a soft mask, and
+//! [`sample_color_f32`] is the eyedropper: a single pixel or the average of
+//! a small window around it. Both weight colors by alpha, so transparent
+//! pixels don't pull the result towards their (meaningless) RGB values and
+//! the Python and WASM frontends pick identical colors.
+//!
+//! [`histogram_masked_u8`] and [`stats_masked_u8`] restrict histograms and
+//! channel statistics to a selection, so the levels dialog shows only the
+//! pixels it is about to change.
+
+use ndarray::{Array2, ArrayView2, ArrayView3};
+
+use crate::batch::region::Rect;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
+use std::collections::HashMap;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Window of [`sample_color_f32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SampleMode {
+    /// The pixel under the cursor only
+    #[default]
+    Point,
+    /// Average of the (2 * radius + 1)^2 square; radius 1 = 3x3, 2 = 5x5
+    Square,
+    /// Average of the pixels within `radius` of the center
+    Circle,
+}
+
+impl SampleMode {
+    /// Parse a sample mode name ("point", "square", "circle", "3x3", "5x5").
+    ///
+    /// "3x3" and "5x5" map to [`SampleMode::Square`]; pass radius 1 or 2.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "point" | "pixel" => Some(Self::Point),
+            "square" | "3x3" | "5x5" => Some(Self::Square),
+            "circle" | "round" => Some(Self::Circle),
+            _ => None,
+        }
+    }
+}
+
+/// Alpha-weighted mean of (y, x, weight) samples.
+///
+/// Color channels are weighted by weight * alpha, alpha by weight alone.
+/// Returns `None` when the weights sum to zero; fully transparent samples
+/// give color 0 with alpha 0.
+fn weighted_mean<T: Copy + Into<f32>>(
+    image: &ArrayView3<T>,
+    alpha_max: f32,
+    samples: impl Iterator<Item = (usize, usize, f32)>,
+) -> Option<Vec<f32>> {
+    let channels = image.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut sums = [0.0f64; 4];
+    let (mut color_weight, mut weight) = (0.0f64, 0.0f64);
+    for (y, x, w) in samples {
+        let alpha = if channels == 4 { image[[y, x, 3]].into() / alpha_max } else { 1.0 };
+        let wc = (w * alpha) as f64;
+        for (c, sum) in sums.iter_mut().enumerate().take(color_channels) {
+            *sum += image[[y, x, c]].into() as f64 * wc;
+        }
+        sums[3] += (alpha * w) as f64;
+        color_weight += wc;
+        weight += w as f64;
+    }
+    if weight <= 0.0 {
+        return None;
+    }
+    let mut mean: Vec<f32> = (0..color_channels)
+        .map(|c| if color_weight > 0.0 { (sums[c] / color_weight) as f32 } else { 0.0 })
+        .collect();
+    if channels == 4 {
+        mean.push((sums[3] / weight) as f32 * alpha_max);
+    }
+    Some(mean)
+}
+
+/// Samples of a rectangle, optionally weighted by a mask.
+fn region_samples<'a>(
+    rect: Rect,
+    mask: Option<ArrayView2<'a, u8>>,
+) -> impl Iterator<Item = (usize, usize, f32)> + 'a {
+    (rect.y..rect.y + rect.height)
+        .flat_map(move |y| (rect.x..rect.x + rect.width).map(move |x| (y, x)))
+        .map(move |(y, x)| (y, x, mask.as_ref().map_or(1.0, |m| m[[y, x]] as f32 / 255.0)))
+}
+
+/// Samples of the eyedropper window around (x, y), clipped to the image.
+fn window_samples(width: usize, height: usize, x: usize, y: usize, radius: usize, mode: SampleMode) -> Vec<(usize, usize, f32)> {
+    let r = if mode == SampleMode::Point { 0 } else { radius };
+    let rect = Rect::new(x.saturating_sub(r), y.saturating_sub(r), 2 * r + 1, 2 * r + 1).clip(width, height);
+    let Some(rect) = rect else { return Vec::new() };
+    let limit = (r * r) as f32 + 0.5;
+    region_samples(rect, None)
+        .filter(|&(sy, sx, _)| {
+            let (dx, dy) = (sx as f32 - x as f32, sy as f32 - y as f32);
+            mode != SampleMode::Circle || dx * dx + dy * dy <= limit
+        })
+        .collect()
+}
+
+/// Alpha-weighted average color of a region - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `rect` - Region to average (`None` = whole image), clipped to the image
+/// * `mask` - Optional (height, width) selection, values scale each pixel's weight
+///
+/// # Returns
+/// Mean per channel; with RGBA the alpha is the mean coverage. `None` if the
+/// region is empty or the mask selects nothing in it.
+pub fn average_color_f32(image: ArrayView3<f32>, rect: Option<Rect>, mask: Option<ArrayView2<u8>>) -> Option<Vec<f32>> {
+    let (height, width, _) = image.dim();
+    let rect = rect.unwrap_or(Rect::new(0, 0, width, height)).clip(width, height)?;
+    weighted_mean(&image, 1.0, region_samples(rect, mask))
+}
+
+/// Alpha-weighted average color of a region - u8 version.
+pub fn average_color_u8(image: ArrayView3<u8>, rect: Option<Rect>, mask: Option<ArrayView2<u8>>) -> Option<Vec<u8>> {
+    let (height, width, _) = image.dim();
+    let rect = rect.unwrap_or(Rect::new(0, 0, width, height)).clip(width, height)?;
+    let mean = weighted_mean(&image, 255.0, region_samples(rect, mask))?;
+    Some(mean.iter().map(|v| v.round().clamp(0.0, 255.0) as u8).collect())
+}
+
+/// Eyedropper color at a pixel - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `x`, `y` - Pixel under the cursor
+/// * `radius` - Window radius for [`SampleMode::Square`] and [`SampleMode::Circle`]
+/// * `mode` - Sampling window
+///
+/// # Returns
+/// Alpha-weighted mean per channel, `None` if (x, y) lies outside the image
+pub fn sample_color_f32(image: ArrayView3<f32>, x: usize, y: usize, radius: usize, mode: SampleMode) -> Option<Vec<f32>> {
+    let (height, width, _) = image.dim();
+    if x >= width || y >= height {
+        return None;
+    }
+    weighted_mean(&image, 1.0, window_samples(width, height, x, y, radius, mode).into_iter())
+}
+
+/// Eyedropper color at a pixel - u8 version.
+pub fn sample_color_u8(image: ArrayView3<u8>, x: usize, y: usize, radius: usize, mode: SampleMode) -> Option<Vec<u8>> {
+    let (height, width, _) = image.dim();
+    if x >= width || y >= height {
+        return None;
+    }
+    let mean = weighted_mean(&image, 255.0, window_samples(width, height, x, y, radius, mode).into_iter())?;
+    Some(mean.iter().map(|v| v.round().clamp(0.0, 255.0) as u8).collect())
+}
+
+// ============================================================================
+// Masked Histograms and Statistics
+// ============================================================================
+
+/// Mask value from which a pixel counts as selected.
+const SELECTED: u8 = 128;
+
+/// Statistics of one channel over the selected pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChannelStats {
+    /// Number of pixels included
+    pub count: usize,
+    /// Mean value
+    pub mean: f32,
+    /// Population standard deviation
+    pub std_dev: f32,
+    /// Middle value (mean of the two middle values for even counts)
+    pub median: f32,
+    /// Smallest value
+    pub min: f32,
+    /// Largest value
+    pub max: f32,
+}
+
+/// Whether pixel (y, x) is selected and not fully transparent.
+fn included<T: Copy + Into<f32>>(image: &ArrayView3<T>, mask: Option<&ArrayView2<u8>>, y: usize, x: usize) -> bool {
+    mask.is_none_or(|m| m[[y, x]] >= SELECTED) && (image.dim().2 != 4 || image[[y, x, 3]].into() > 0.0)
+}
+
+/// Number of color channels (alpha excluded).
+fn color_channels(channels: usize) -> usize {
+    if channels == 4 { 3 } else { channels }
+}
+
+/// Per-channel histogram of the selected pixels - u8 version.
+///
+/// Pixels count when the mask is >= 128; fully transparent pixels are
+/// skipped and alpha gets no histogram.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `mask` - Optional (height, width) selection
+/// * `bins` - Number of bins 1-256; bin = value * bins / 256
+///
+/// # Returns
+/// Counts of shape (color channels, bins)
+pub fn histogram_masked_u8(image: ArrayView3<u8>, mask: Option<ArrayView2<u8>>, bins: usize) -> Array2<u32> {
+    let (height, width, channels) = image.dim();
+    let bins = bins.clamp(1, 256);
+    let mut histogram = Array2::<u32>::zeros((color_channels(channels), bins));
+    for y in 0..height {
+        for x in 0..width {
+            if included(&image, mask.as_ref(), y, x) {
+                for c in 0..color_channels(channels) {
+                    histogram[[c, image[[y, x, c]] as usize * bins / 256]] += 1;
+                }
+            }
+        }
+    }
+    histogram
+}
+
+/// Per-channel histogram of the selected pixels - f32 version.
+///
+/// # Arguments
+/// * `bins` - Number of bins (>= 1) over 0.0-1.0; values outside are clamped
+pub fn histogram_masked_f32(image: ArrayView3<f32>, mask: Option<ArrayView2<u8>>, bins: usize) -> Array2<u32> {
+    let (height, width, channels) = image.dim();
+    let bins = bins.max(1);
+    let mut histogram = Array2::<u32>::zeros((color_channels(channels), bins));
+    for y in 0..height {
+        for x in 0..width {
+            if included(&image, mask.as_ref(), y, x) {
+                for c in 0..color_channels(channels) {
+                    let bin = (image[[y, x, c]].clamp(0.0, 1.0) * bins as f32) as usize;
+                    histogram[[c, bin.min(bins - 1)]] += 1;
+                }
+            }
+        }
+    }
+    histogram
+}
+
+/// Statistics of one channel's values (sorted in place).
+fn channel_stats(values: &mut [f32]) -> ChannelStats {
+    if values.is_empty() {
+        return ChannelStats::default();
+    }
+    let n = values.len();
+    let mean = (values.iter().map(|&v| v as f64).sum::<f64>() / n as f64) as f32;
+    let variance = values.iter().map(|&v| ((v - mean) as f64).powi(2)).sum::<f64>() / n as f64;
+    values.sort_unstable_by(f32::total_cmp);
+    let median = if n % 2 == 1 { values[n / 2] } else { (values[n / 2 - 1] + values[n / 2]) / 2.0 };
+    ChannelStats { count: n, mean, std_dev: variance.sqrt() as f32, median, min: values[0], max: values[n - 1] }
+}
+
+/// Mean, standard deviation, median and range per channel of the selected
+/// pixels - f32 version.
+///
+/// Selection and transparency rules as in [`histogram_masked_u8`].
+///
+/// # Returns
+/// One entry per color channel; all zero when nothing is selected
+pub fn stats_masked_f32(image: ArrayView3<f32>, mask: Option<ArrayView2<u8>>) -> Vec<ChannelStats> {
+    let (height, width, channels) = image.dim();
+    let pixels: Vec<(usize, usize)> =
+        (0..height).flat_map(|y| (0..width).map(move |x| (y, x))).filter(|&(y, x)| included(&image, mask.as_ref(), y, x)).collect();
+    (0..color_channels(channels))
+        .map(|c| channel_stats(&mut pixels.iter().map(|&(y, x)| image[[y, x, c]]).collect::<Vec<_>>()))
+        .collect()
+}
+
+/// Mean, standard deviation, median and range per channel of the selected
+/// pixels - u8 version, in 0-255 units.
+///
+/// Computed from the 256-bin histogram, so the cost doesn't grow with the
+/// selection beyond one pass.
+pub fn stats_masked_u8(image: ArrayView3<u8>, mask: Option<ArrayView2<u8>>) -> Vec<ChannelStats> {
+    let histogram = histogram_masked_u8(image, mask, 256);
+    histogram
+        .outer_iter()
+        .map(|counts| {
+            let n: u64 = counts.iter().map(|&c| c as u64).sum();
+            if n == 0 {
+                return ChannelStats::default();
+            }
+            let sum: f64 = counts.iter().enumerate().map(|(v, &c)| v as f64 * c as f64).sum();
+            let mean = sum / n as f64;
+            let variance = counts.iter().enumerate().map(|(v, &c)| (v as f64 - mean).powi(2) * c as f64).sum::<f64>() / n as f64;
+            // Value at a 0-based rank in the sorted selection
+            let at_rank = |rank: u64| {
+                let mut seen = 0u64;
+                counts.iter().position(|&c| {
+                    seen += c as u64;
+                    seen > rank
+                })
+                .unwrap_or(255) as f32
+            };
+            let median = if n % 2 == 1 { at_rank(n / 2) } else { (at_rank(n / 2 - 1) + at_rank(n / 2)) / 2.0 };
+            ChannelStats {
+                count: n as usize,
+                mean: mean as f32,
+                std_dev: variance.sqrt() as f32,
+                median,
+                min: counts.iter().position(|&c| c > 0).unwrap_or(0) as f32,
+                max: counts.iter().rposition(|&c| c > 0).unwrap_or(0) as f32,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+type RectTuple = (usize, usize, usize, usize);
+
+/// Mask view, rejecting masks that don't match the image size.
+#[cfg(feature = "python")]
+fn mask_view<'a>(mask: &'a Option<PyHostArray2<'_, u8>>, height: usize, width: usize) -> PyResult<Option<ArrayView2<'a, u8>>> {
+    let view = mask.as_ref().map(|m| m.as_array());
+    if let Some(v) = &view {
+        if v.dim() != (height, width) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "mask shape {:?} does not match image size ({}, {})",
+                v.dim(), height, width
+            )));
+        }
+    }
+    Ok(view)
+}
+
+#[cfg(feature = "python")]
+fn parse_sample_mode(name: &str) -> PyResult<SampleMode> {
+    SampleMode::from_name(name)
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown sample mode '{}'", name)))
+}
+
+/// Alpha-weighted average color of a uint8 image region.
+///
+/// # Arguments
+/// * `mask` - Optional (height, width) uint8 selection weighting each pixel
+/// * `rect` - Optional (x, y, width, height) region
+///
+/// # Returns
+/// Mean per channel, or None if nothing is selected
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "average_color", signature = (image, mask=None, rect=None))]
+pub fn average_color_py(
+    image: PyHostArray3<'_, u8>,
+    mask: Option<PyHostArray2<'_, u8>>,
+    rect: Option<RectTuple>,
+) -> PyResult<Option<Vec<u8>>> {
+    let image = image.as_array();
+    let mask = mask_view(&mask, image.dim().0, image.dim().1)?;
+    let rect = rect.map(|(x, y, w, h)| Rect::new(x, y, w, h));
+    Ok(average_color_u8(image, rect, mask))
+}
+
+/// Alpha-weighted average color of a float32 image region.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "average_color_f32", signature = (image, mask=None, rect=None))]
+pub fn average_color_f32_py(
+    image: PyHostArray3<'_, f32>,
+    mask: Option<PyHostArray2<'_, u8>>,
+    rect: Option<RectTuple>,
+) -> PyResult<Option<Vec<f32>>> {
+    let image = image.as_array();
+    let mask = mask_view(&mask, image.dim().0, image.dim().1)?;
+    let rect = rect.map(|(x, y, w, h)| Rect::new(x, y, w, h));
+    Ok(average_color_f32(image, rect, mask))
+}
+
+/// Eyedropper color of a uint8 image.
+///
+/// # Arguments
+/// * `radius` - Window radius (1 = 3x3, 2 = 5x5)
+/// * `mode` - "point", "square" or "circle"
+///
+/// # Returns
+/// Mean per channel, or None outside the image
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "sample_color", signature = (image, x, y, radius=1, mode="point"))]
+pub fn sample_color_py(image: PyHostArray3<'_, u8>, x: usize, y: usize, radius: usize, mode: &str) -> PyResult<Option<Vec<u8>>> {
+    Ok(sample_color_u8(image.as_array(), x, y, radius, parse_sample_mode(mode)?))
+}
+
+/// Eyedropper color of a float32 image.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "sample_color_f32", signature = (image, x, y, radius=1, mode="point"))]
+pub fn sample_color_f32_py(image: PyHostArray3<'_, f32>, x: usize, y: usize, radius: usize, mode: &str) -> PyResult<Option<Vec<f32>>> {
+    Ok(sample_color_f32(image.as_array(), x, y, radius, parse_sample_mode(mode)?))
+}
+
+/// Channel statistics as a Python dict.
+#[cfg(feature = "python")]
+fn stats_dict(stats: &ChannelStats) -> HashMap<&'static str, f64> {
+    HashMap::from([
+        ("count", stats.count as f64),
+        ("mean", stats.mean as f64),
+        ("std", stats.std_dev as f64),
+        ("median", stats.median as f64),
+        ("min", stats.min as f64),
+        ("max", stats.max as f64),
+    ])
+}
+
+/// Per-channel histogram of the selected pixels of a uint8 image.
+///
+/// # Arguments
+/// * `mask` - Optional (height, width) uint8 selection (>= 128 = selected)
+/// * `bins` - Number of bins 1-256
+///
+/// # Returns
+/// uint32 counts (color channels, bins); transparent pixels and alpha are excluded
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "histogram_masked", signature = (image, mask=None, bins=256))]
+pub fn histogram_masked_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    mask: Option<PyHostArray2<'py, u8>>,
+    bins: usize,
+) -> PyResult<Bound<'py, PyArray2<u32>>> {
+    let view = image.as_array();
+    let mask_view = mask_view(&mask, view.dim().0, view.dim().1)?;
+    Ok(histogram_masked_u8(view, mask_view, bins).into_pyarray(image.py()))
+}
+
+/// Per-channel histogram over 0.0-1.0 of the selected pixels of a float32 image.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "histogram_masked_f32", signature = (image, mask=None, bins=256))]
+pub fn histogram_masked_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    mask: Option<PyHostArray2<'py, u8>>,
+    bins: usize,
+) -> PyResult<Bound<'py, PyArray2<u32>>> {
+    let view = image.as_array();
+    let mask_view = mask_view(&mask, view.dim().0, view.dim().1)?;
+    Ok(histogram_masked_f32(view, mask_view, bins).into_pyarray(image.py()))
+}
+
+/// Statistics per channel of the selected pixels of a uint8 image.
+///
+/// # Returns
+/// List of dicts with count, mean, std, median, min and max (0-255 units)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "stats_masked", signature = (image, mask=None))]
+pub fn stats_masked_py(image: PyHostArray3<'_, u8>, mask: Option<PyHostArray2<'_, u8>>) -> PyResult<Vec<HashMap<&'static str, f64>>> {
+    let view = image.as_array();
+    let mask_view = mask_view(&mask, view.dim().0, view.dim().1)?;
+    Ok(stats_masked_u8(view, mask_view).iter().map(stats_dict).collect())
+}
+
+/// Statistics per channel of the selected pixels of a float32 image.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "stats_masked_f32", signature = (image, mask=None))]
+pub fn stats_masked_f32_py(image: PyHostArray3<'_, f32>, mask: Option<PyHostArray2<'_, u8>>) -> PyResult<Vec<HashMap<&'static str, f64>>> {
+    let view = image.as_array();
+    let mask_view = mask_view(&mask, view.dim().0, view.dim().1)?;
+    Ok(stats_masked_f32(view, mask_view).iter().map(stats_dict).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{Array2, Array3};
+
+    #[test]
+    fn test_alpha_weighted_average_and_sampling() {
+        // Left half opaque red, right half transparent "green" garbage
+        let image = Array3::from_shape_fn((4, 4, 4), |(_, x, c)| match (x < 2, c) {
+            (true, 0) | (true, 3) => 255u8,
+            (false, 1) => 255,
+            _ => 0,
+        });
+        assert_eq!(average_color_u8(image.view(), None, None), Some(vec![255, 0, 0, 128]));
+        // A 3x3 eyedropper at the border ignores the transparent pixels
+        assert_eq!(sample_color_u8(image.view(), 2, 1, 1, SampleMode::Square), Some(vec![255, 0, 0, 85]));
+        assert_eq!(sample_color_u8(image.view(), 3, 1, 0, SampleMode::Point), Some(vec![0, 0, 0, 0]));
+        assert_eq!(sample_color_u8(image.view(), 4, 1, 1, SampleMode::Point), None);
+
+        // Rect and mask select the same two pixels
+        let gradient = Array3::from_shape_fn((4, 4, 3), |(y, x, _)| (x + 4 * y) as f32);
+        let mut mask = Array2::<u8>::zeros((4, 4));
+        mask[[1, 1]] = 255;
+        mask[[1, 2]] = 255;
+        let by_mask = average_color_f32(gradient.view(), None, Some(mask.view())).unwrap();
+        let by_rect = average_color_f32(gradient.view(), Some(Rect::new(1, 1, 2, 1)), None).unwrap();
+        assert_eq!(by_mask, vec![5.5; 3]);
+        assert_eq!(by_rect, by_mask);
+        assert_eq!(average_color_f32(gradient.view(), None, Some(Array2::zeros((4, 4)).view())), None);
+        // The circle of radius 1 is the plus-shaped neighborhood
+        assert_eq!(sample_color_f32(gradient.view(), 1, 1, 1, SampleMode::Circle), Some(vec![5.0; 3]));
+    }
+
+    #[test]
+    fn test_masked_histogram_and_stats() {
+        // Values 0, 16, ..., 240 in every channel, top half selected
+        let image = Array3::from_shape_fn((4, 4, 3), |(y, x, _)| (x + 4 * y) as u8 * 16);
+        let mask = Array2::from_shape_fn((4, 4), |(y, _)| if y < 2 { 255u8 } else { 0 });
+        let histogram = histogram_masked_u8(image.view(), Some(mask.view()), 16);
+        assert_eq!(histogram.dim(), (3, 16));
+        assert_eq!(histogram.row(0).iter().take(8).collect::<Vec<_>>(), vec![&1; 8]);
+        assert_eq!(histogram.row(0).iter().skip(8).sum::<u32>(), 0);
+
+        let stats = stats_masked_u8(image.view(), Some(mask.view()));
+        assert_eq!(stats.len(), 3);
+        assert_eq!((stats[0].count, stats[0].min, stats[0].max), (8, 0.0, 112.0));
+        assert_eq!((stats[0].mean, stats[0].median), (56.0, 56.0));
+        // The f32 path agrees
+        let stats_f32 = stats_masked_f32(image.mapv(|v| v as f32).view(), Some(mask.view()));
+        assert_eq!(stats_f32[1].median, 56.0);
+        assert!((stats_f32[1].std_dev - stats[1].std_dev).abs() < 1e-3);
+        assert_eq!(histogram_masked_f32(image.mapv(|v| v as f32 / 255.0).view(), None, 4).row(2).sum(), 16);
+    }
+}
@@ -0,0 +1,283 @@
+//! SLIC superpixel segmentation.
+//!
+//! Simple Linear Iterative Clustering (Achanta et al.) groups pixels into
+//! compact regions of similar color: cluster centers start on a regular
+//! grid, and each pixel joins the nearest center within twice the grid
+//! step, where distance mixes CIE Lab color difference and spatial distance
+//! weighted by `compactness`. After a few iterations, fragments smaller than
+//! a quarter superpixel are merged into a neighbor so every label is one
+//! connected region.
+//!
+//! The label map backs "snap selection to superpixels" and region-based
+//! filters that work per superpixel instead of per pixel.
+
+use ndarray::{Array2, ArrayView3};
+
+use crate::filters::color_science::rgb_to_lab;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// k-means iterations; SLIC converges in about ten.
+const SLIC_ITERATIONS: usize = 10;
+
+/// Cluster center: Lab color and position.
+#[derive(Debug, Clone, Copy)]
+struct Center {
+    color: [f32; 3],
+    y: f32,
+    x: f32,
+}
+
+/// Lab color per pixel (L only for grayscale, alpha ignored).
+fn lab_features(image: ArrayView3<f32>) -> Array2<[f32; 3]> {
+    let (height, width, channels) = image.dim();
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        if channels >= 3 {
+            let (l, a, b) = rgb_to_lab(image[[y, x, 0]], image[[y, x, 1]], image[[y, x, 2]]);
+            [l, a, b]
+        } else {
+            [image[[y, x, 0]] * 100.0, 0.0, 0.0]
+        }
+    })
+}
+
+#[inline]
+fn color_distance_sq(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]) * (a[i] - b[i])).sum()
+}
+
+/// Grid of initial centers, each moved to the lowest color gradient in its
+/// 3x3 neighborhood so it doesn't start on an edge.
+fn initial_centers(features: &Array2<[f32; 3]>, step: f32) -> Vec<Center> {
+    let (height, width) = features.dim();
+    let rows = ((height as f32 / step).round() as usize).max(1);
+    let cols = ((width as f32 / step).round() as usize).max(1);
+    let gradient = |y: usize, x: usize| {
+        let (x0, x1) = (x.saturating_sub(1), (x + 1).min(width - 1));
+        let (y0, y1) = (y.saturating_sub(1), (y + 1).min(height - 1));
+        color_distance_sq(&features[[y, x1]], &features[[y, x0]])
+            + color_distance_sq(&features[[y1, x]], &features[[y0, x]])
+    };
+
+    let mut centers = Vec::with_capacity(rows * cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            let cy = ((r as f32 + 0.5) * height as f32 / rows as f32) as usize;
+            let cx = ((c as f32 + 0.5) * width as f32 / cols as f32) as usize;
+            let (mut by, mut bx) = (cy, cx);
+            for y in cy.saturating_sub(1)..=(cy + 1).min(height - 1) {
+                for x in cx.saturating_sub(1)..=(cx + 1).min(width - 1) {
+                    if gradient(y, x) < gradient(by, bx) {
+                        (by, bx) = (y, x);
+                    }
+                }
+            }
+            centers.push(Center { color: features[[by, bx]], y: by as f32, x: bx as f32 });
+        }
+    }
+    centers
+}
+
+/// Relabel connected components (4-connected) to 0..K, merging components
+/// smaller than `min_size` into the previously labeled neighbor.
+fn enforce_connectivity(labels: &Array2<usize>, min_size: usize) -> Array2<u32> {
+    let (height, width) = labels.dim();
+    let mut output = Array2::<u32>::from_elem((height, width), u32::MAX);
+    let mut next = 0u32;
+    let mut component = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            if output[[y, x]] != u32::MAX {
+                continue;
+            }
+            // Label of an already relabeled neighbor, the merge target
+            let adjacent = [(y > 0).then(|| (y - 1, x)), (x > 0).then(|| (y, x - 1))]
+                .into_iter()
+                .flatten()
+                .map(|p| output[p])
+                .find(|&l| l != u32::MAX);
+
+            let label = labels[[y, x]];
+            component.clear();
+            component.push((y, x));
+            output[[y, x]] = next;
+            let mut i = 0;
+            while i < component.len() {
+                let (cy, cx) = component[i];
+                i += 1;
+                let neighbors = [
+                    (cy > 0).then(|| (cy - 1, cx)),
+                    (cy + 1 < height).then(|| (cy + 1, cx)),
+                    (cx > 0).then(|| (cy, cx - 1)),
+                    (cx + 1 < width).then(|| (cy, cx + 1)),
+                ];
+                for p in neighbors.into_iter().flatten() {
+                    if output[p] == u32::MAX && labels[p] == label {
+                        output[p] = next;
+                        component.push(p);
+                    }
+                }
+            }
+
+            match adjacent {
+                Some(target) if component.len() < min_size => {
+                    for &p in &component {
+                        output[p] = target;
+                    }
+                }
+                _ => next += 1,
+            }
+        }
+    }
+    output
+}
+
+/// Segment an image into SLIC superpixels - f32 version.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels), values 0.0-1.0
+/// * `n_segments` - Approximate number of superpixels
+/// * `compactness` - Weight of spatial against color distance (typically
+///   1-40; 10 balances both, higher gives more regular, grid-like cells)
+///
+/// # Returns
+/// Label map (height, width) with labels 0..K, each a connected region
+pub fn slic_f32(image: ArrayView3<f32>, n_segments: usize, compactness: f32) -> Array2<u32> {
+    let (height, width, _) = image.dim();
+    let n = height * width;
+    if n == 0 {
+        return Array2::zeros((height, width));
+    }
+    let features = lab_features(image);
+    let step = (n as f32 / n_segments.clamp(1, n) as f32).sqrt().max(1.0);
+    let mut centers = initial_centers(&features, step);
+    let spatial_weight = (compactness / step).powi(2);
+    let reach = step.ceil() as isize;
+
+    let mut labels = Array2::<usize>::zeros((height, width));
+    for _ in 0..SLIC_ITERATIONS {
+        let mut best = Array2::<f32>::from_elem((height, width), f32::INFINITY);
+        for (k, center) in centers.iter().enumerate() {
+            let (cy, cx) = (center.y.round() as isize, center.x.round() as isize);
+            let y0 = (cy - reach).max(0) as usize;
+            let y1 = ((cy + reach) as usize).min(height - 1);
+            let x0 = (cx - reach).max(0) as usize;
+            let x1 = ((cx + reach) as usize).min(width - 1);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    let dy = y as f32 - center.y;
+                    let dx = x as f32 - center.x;
+                    let d = color_distance_sq(&features[[y, x]], &center.color) + spatial_weight * (dy * dy + dx * dx);
+                    if d < best[[y, x]] {
+                        best[[y, x]] = d;
+                        labels[[y, x]] = k;
+                    }
+                }
+            }
+        }
+
+        // Move every center to the mean of its pixels
+        let mut sums = vec![[0.0f64; 6]; centers.len()];
+        for ((y, x), &k) in labels.indexed_iter() {
+            let f = features[[y, x]];
+            let s = &mut sums[k];
+            s[0] += f[0] as f64;
+            s[1] += f[1] as f64;
+            s[2] += f[2] as f64;
+            s[3] += y as f64;
+            s[4] += x as f64;
+            s[5] += 1.0;
+        }
+        for (center, s) in centers.iter_mut().zip(&sums) {
+            if s[5] > 0.0 {
+                let m = |i: usize| (s[i] / s[5]) as f32;
+                *center = Center { color: [m(0), m(1), m(2)], y: m(3), x: m(4) };
+            }
+        }
+    }
+
+    let min_size = ((step * step) / 4.0) as usize;
+    enforce_connectivity(&labels, min_size)
+}
+
+/// Segment an image into SLIC superpixels - u8 version.
+pub fn slic_u8(image: ArrayView3<u8>, n_segments: usize, compactness: f32) -> Array2<u32> {
+    let image_f32 = image.mapv(|v| v as f32 / 255.0);
+    slic_f32(image_f32.view(), n_segments, compactness)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Segment a uint8 image into SLIC superpixels.
+///
+/// # Returns
+/// (H, W) uint32 label map with labels 0..K
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "slic", signature = (image, n_segments=100, compactness=10.0))]
+pub fn slic_py<'py>(image: PyHostArray3<'py, u8>, n_segments: usize, compactness: f32) -> Bound<'py, PyArray2<u32>> {
+    slic_u8(image.as_array(), n_segments, compactness).into_pyarray(image.py())
+}
+
+/// Segment a float32 image into SLIC superpixels.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "slic_f32", signature = (image, n_segments=100, compactness=10.0))]
+pub fn slic_f32_py<'py>(image: PyHostArray3<'py, f32>, n_segments: usize, compactness: f32) -> Bound<'py, PyArray2<u32>> {
+    slic_f32(image.as_array(), n_segments, compactness).into_pyarray(image.py())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+
+    /// Whether every label forms one 4-connected region.
+    fn all_connected(labels: &Array2<u32>) -> bool {
+        let count = labels.iter().max().map_or(0, |&m| m as usize + 1);
+        let mut seen = vec![false; count];
+        let relabeled = enforce_connectivity(&labels.mapv(|l| l as usize), 0);
+        for (&a, &b) in labels.iter().zip(relabeled.iter()) {
+            seen[a as usize] = true;
+            if a != b {
+                return false;
+            }
+        }
+        seen.iter().all(|&s| s)
+    }
+
+    #[test]
+    fn test_superpixels_follow_color_edges() {
+        // Red left half, blue right half with the edge off the grid (x = 23)
+        let image = Array3::from_shape_fn((40, 48, 3), |(_, x, c)| match (x < 23, c) {
+            (true, 0) | (false, 2) => 0.8,
+            _ => 0.1,
+        });
+        let labels = slic_f32(image.view(), 16, 10.0);
+        let count = *labels.iter().max().unwrap() as usize + 1;
+        assert!((8..=24).contains(&count), "{count} superpixels");
+        assert!(all_connected(&labels));
+        // No superpixel straddles the color edge
+        for l in 0..count as u32 {
+            let sides: Vec<bool> = labels.indexed_iter().filter(|(_, &v)| v == l).map(|((_, x), _)| x < 23).collect();
+            assert!(sides.iter().all(|&s| s == sides[0]), "label {l} crosses the edge");
+        }
+    }
+
+    #[test]
+    fn test_flat_image_gives_compact_grid() {
+        let image = Array3::<u8>::from_elem((30, 30, 4), 128);
+        let labels = slic_u8(image.view(), 9, 10.0);
+        assert_eq!(*labels.iter().max().unwrap(), 8);
+        assert!(all_connected(&labels));
+        assert_eq!(slic_u8(Array3::<u8>::zeros((0, 5, 3)).view(), 4, 10.0).dim(), (0, 5));
+    }
+}
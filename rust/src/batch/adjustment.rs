@@ -0,0 +1,178 @@
+//! Adjustment layer previews.
+//!
+//! A non-destructive adjustment layer filters everything below it and mixes
+//! the result back over that backdrop through the layer mask, opacity and
+//! blend mode. [`preview_adjustment_u8`] does all of it in one call, so the
+//! editor doesn't need a filtered copy and a separate blend per redraw:
+//!
+//! ```text
+//! let step = RecipeStep::from_json(r#"{"filter": "saturation", "params": {"amount": -0.5}}"#)?;
+//! let preview = preview_adjustment_u8(backdrop, &step, Some(mask), 0.4, "normal")?;
+//! ```
+//!
+//! The adjustment is one [`recipe`](super::recipe) step, so filter names and
+//! parameters are those of the [`registry`](super::registry). A disabled
+//! step returns the backdrop unchanged. Size-changing steps (thumbnail) are
+//! rejected.
+
+use ndarray::{Array3, ArrayView2, ArrayView3};
+
+use super::recipe::RecipeStep;
+use super::{BatchError, FilterSpec};
+use crate::filters::compositing::{blend_images_f32, blend_images_u8};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Check that the step keeps the image size and the mask fits the backdrop.
+fn check_adjustment(step: &RecipeStep, size: (usize, usize), mask: Option<(usize, usize)>) -> Result<(), BatchError> {
+    if matches!(step.filter, FilterSpec::Thumbnail { .. }) {
+        return Err(BatchError::InvalidArgument(format!(
+            "'{}' changes the image size and cannot be an adjustment layer",
+            step.filter.name()
+        )));
+    }
+    match mask {
+        Some(mask) if mask != size => Err(BatchError::InvalidArgument(format!(
+            "mask is {}x{}, backdrop is {}x{}",
+            mask.1, mask.0, size.1, size.0
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Composite an adjustment over a u8 backdrop.
+///
+/// # Arguments
+/// * `backdrop` - Image (H, W, C) below the adjustment layer
+/// * `step` - Adjustment to apply
+/// * `mask` - Optional layer mask (H, W), 0 = backdrop, 255 = adjusted
+/// * `opacity` - Layer opacity (0.0-1.0)
+/// * `blend_mode` - Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+///
+/// # Returns
+/// The backdrop with the adjustment mixed in
+pub fn preview_adjustment_u8(
+    backdrop: ArrayView3<u8>,
+    step: &RecipeStep,
+    mask: Option<ArrayView2<u8>>,
+    opacity: f32,
+    blend_mode: &str,
+) -> Result<Array3<u8>, BatchError> {
+    let (height, width, _) = backdrop.dim();
+    check_adjustment(step, (height, width), mask.as_ref().map(|m| m.dim()))?;
+    if !step.enabled || opacity <= 0.0 {
+        return Ok(backdrop.to_owned());
+    }
+    let adjusted = step.filter.apply_u8(backdrop);
+    Ok(blend_images_u8(backdrop, adjusted.view(), mask, opacity, blend_mode))
+}
+
+/// Composite an adjustment over an f32 backdrop (0.0-1.0).
+///
+/// Same as preview_adjustment_u8 with a mask of 0.0 = backdrop, 1.0 = adjusted.
+pub fn preview_adjustment_f32(
+    backdrop: ArrayView3<f32>,
+    step: &RecipeStep,
+    mask: Option<ArrayView2<f32>>,
+    opacity: f32,
+    blend_mode: &str,
+) -> Result<Array3<f32>, BatchError> {
+    let (height, width, _) = backdrop.dim();
+    check_adjustment(step, (height, width), mask.as_ref().map(|m| m.dim()))?;
+    if !step.enabled || opacity <= 0.0 {
+        return Ok(backdrop.to_owned());
+    }
+    let adjusted = step.filter.apply_f32(backdrop);
+    Ok(blend_images_f32(backdrop, adjusted.view(), mask, opacity, blend_mode))
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Composite an adjustment layer over a uint8 backdrop.
+///
+/// # Arguments
+/// * `backdrop` - Image (H, W, C) with 1, 3 or 4 channels
+/// * `step_json` - Recipe step, e.g. `{"filter": "brightness", "params": {"amount": 0.2}}`
+/// * `mask` - Optional (H, W) layer mask, 0 = backdrop, 255 = adjusted
+/// * `opacity` - Layer opacity (0.0-1.0)
+/// * `blend_mode` - Overlay blend mode ("normal", "multiply", ...)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "preview_adjustment", signature = (backdrop, step_json, mask=None, opacity=1.0, blend_mode="normal"))]
+pub fn preview_adjustment_py<'py>(
+    py: Python<'py>,
+    backdrop: PyHostArray3<'py, u8>,
+    step_json: &str,
+    mask: Option<PyHostArray2<'py, u8>>,
+    opacity: f32,
+    blend_mode: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let step = RecipeStep::from_json(step_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let mask = mask.as_ref().map(|m| m.as_array());
+    let result = preview_adjustment_u8(backdrop.as_array(), &step, mask, opacity, blend_mode)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Composite an adjustment layer over a float32 backdrop.
+///
+/// Same as preview_adjustment but for f32 images and masks.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "preview_adjustment_f32", signature = (backdrop, step_json, mask=None, opacity=1.0, blend_mode="normal"))]
+pub fn preview_adjustment_f32_py<'py>(
+    py: Python<'py>,
+    backdrop: PyHostArray3<'py, f32>,
+    step_json: &str,
+    mask: Option<PyHostArray2<'py, f32>>,
+    opacity: f32,
+    blend_mode: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let step = RecipeStep::from_json(step_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let mask = mask.as_ref().map(|m| m.as_array());
+    let result = preview_adjustment_f32(backdrop.as_array(), &step, mask, opacity, blend_mode)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(result.into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array2;
+
+    #[test]
+    fn test_preview_matches_filter_and_blend() {
+        let backdrop = Array3::from_shape_fn((6, 8, 4), |(y, x, c)| (y * 30 + x * 20 + c * 10) as u8);
+        let mask = Array2::from_shape_fn((6, 8), |(_, x)| if x < 4 { 0 } else { 255 });
+        let step = RecipeStep::from_json(r#"{"filter": "invert"}"#).unwrap();
+        let preview = preview_adjustment_u8(backdrop.view(), &step, Some(mask.view()), 0.5, "normal").unwrap();
+        let inverted = FilterSpec::Invert.apply_u8(backdrop.view());
+        assert_eq!(preview, blend_images_u8(backdrop.view(), inverted.view(), Some(mask.view()), 0.5, "normal"));
+        assert_eq!(preview[[2, 1, 0]], backdrop[[2, 1, 0]]);
+
+        let disabled = RecipeStep::from_json(r#"{"filter": "invert", "enabled": false}"#).unwrap();
+        assert_eq!(preview_adjustment_u8(backdrop.view(), &disabled, None, 1.0, "normal").unwrap(), backdrop);
+    }
+
+    #[test]
+    fn test_rejects_resize_and_mask_mismatch() {
+        let backdrop = Array3::<f32>::zeros((4, 4, 3));
+        let thumbnail = RecipeStep::from_json(r#"{"filter": "thumbnail", "params": {"max_width": 2}}"#).unwrap();
+        assert!(matches!(
+            preview_adjustment_f32(backdrop.view(), &thumbnail, None, 1.0, "normal"),
+            Err(BatchError::InvalidArgument(_))
+        ));
+        let step = RecipeStep::from_json(r#"{"filter": "blur"}"#).unwrap();
+        let mask = Array2::<f32>::ones((3, 4));
+        assert!(preview_adjustment_f32(backdrop.view(), &step, Some(mask.view()), 1.0, "normal").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! CBOR (RFC 8949) encoding of [`JsonValue`] documents.
+//!
+//! Recipes are stored either as JSON text or as this compact binary form;
+//! both carry the same document. Integral numbers are written as CBOR
+//! integers, all others as 64-bit floats. Decoding accepts the same subset
+//! plus 16/32-bit floats; byte strings, tags and indefinite lengths are
+//! rejected.
+
+use super::json::{JsonValue, MAX_DEPTH};
+use super::BatchError;
+
+/// Largest integer written as a CBOR integer (exactly representable in f64).
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+fn write_header(out: &mut Vec<u8>, major: u8, len: u64) {
+    let major = major << 5;
+    if len < 24 {
+        out.push(major | len as u8);
+    } else if len <= u8::MAX as u64 {
+        out.extend_from_slice(&[major | 24, len as u8]);
+    } else if len <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(len as u16).to_be_bytes());
+    } else if len <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&len.to_be_bytes());
+    }
+}
+
+fn write_value(out: &mut Vec<u8>, value: &JsonValue) {
+    match value {
+        JsonValue::Null => out.push(0xf6),
+        JsonValue::Bool(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+        JsonValue::Number(n) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            if *n >= 0.0 {
+                write_header(out, 0, *n as u64);
+            } else {
+                write_header(out, 1, (-1.0 - n) as u64);
+            }
+        }
+        JsonValue::Number(n) => {
+            out.push(0xfb);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        JsonValue::String(s) => {
+            write_header(out, 3, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        JsonValue::Array(items) => {
+            write_header(out, 4, items.len() as u64);
+            items.iter().for_each(|item| write_value(out, item));
+        }
+        JsonValue::Object(members) => {
+            write_header(out, 5, members.len() as u64);
+            for (key, value) in members {
+                write_header(out, 3, key.len() as u64);
+                out.extend_from_slice(key.as_bytes());
+                write_value(out, value);
+            }
+        }
+    }
+}
+
+/// Encode a document as CBOR.
+pub fn to_cbor(value: &JsonValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_value(&mut out, value);
+    out
+}
+
+/// Decode a CBOR document.
+pub fn from_cbor(bytes: &[u8]) -> Result<JsonValue, BatchError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let value = reader.value(0)?;
+    if reader.pos != bytes.len() {
+        return Err(reader.error("trailing bytes"));
+    }
+    Ok(value)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, msg: &str) -> BatchError {
+        BatchError::InvalidCbor(format!("{} at offset {}", msg, self.pos))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], BatchError> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or_else(|| self.error("unexpected end of input"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Argument of an initial byte (length, count or integer value).
+    fn argument(&mut self, info: u8) -> Result<u64, BatchError> {
+        Ok(match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            _ => return Err(self.error("indefinite or reserved length")),
+        })
+    }
+
+    fn text(&mut self, info: u8) -> Result<String, BatchError> {
+        let len = self.argument(info)?;
+        let len = usize::try_from(len).map_err(|_| self.error("string too long"))?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    fn value(&mut self, depth: usize) -> Result<JsonValue, BatchError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        match major {
+            0 => Ok(JsonValue::Number(self.argument(info)? as f64)),
+            1 => Ok(JsonValue::Number(-1.0 - self.argument(info)? as f64)),
+            3 => Ok(JsonValue::String(self.text(info)?)),
+            4 => {
+                let count = self.argument(info)?;
+                let mut items = Vec::new();
+                for _ in 0..count {
+                    items.push(self.value(depth + 1)?);
+                }
+                Ok(JsonValue::Array(items))
+            }
+            5 => {
+                let count = self.argument(info)?;
+                let mut members = Vec::new();
+                for _ in 0..count {
+                    let key_initial = self.take(1)?[0];
+                    if key_initial >> 5 != 3 {
+                        return Err(self.error("map key is not a string"));
+                    }
+                    let key = self.text(key_initial & 0x1f)?;
+                    members.push((key, self.value(depth + 1)?));
+                }
+                Ok(JsonValue::Object(members))
+            }
+            7 => match info {
+                20 => Ok(JsonValue::Bool(false)),
+                21 => Ok(JsonValue::Bool(true)),
+                22 => Ok(JsonValue::Null),
+                25 => Ok(JsonValue::Number(half_to_f64(u16::from_be_bytes(self.take(2)?.try_into().unwrap())))),
+                26 => Ok(JsonValue::Number(f32::from_be_bytes(self.take(4)?.try_into().unwrap()) as f64)),
+                27 => Ok(JsonValue::Number(f64::from_be_bytes(self.take(8)?.try_into().unwrap()))),
+                _ => Err(self.error("unsupported simple value")),
+            },
+            _ => Err(self.error("unsupported major type")),
+        }
+    }
+}
+
+/// IEEE 754 half precision to f64.
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        e => (1.0 + mantissa / 1024.0) * 2f64.powi(e - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let value = JsonValue::parse(
+            r#"{"version":1,"steps":[{"filter":"blur","params":{"sigma":1.5},"enabled":false}],"n":[-1,-500,70000,null]}"#,
+        )
+        .unwrap();
+        assert_eq!(from_cbor(&to_cbor(&value)).unwrap(), value);
+    }
+
+    #[test]
+    fn test_known_encodings() {
+        // RFC 8949 Appendix A examples
+        assert_eq!(to_cbor(&JsonValue::Number(100.0)), [0x18, 0x64]);
+        assert_eq!(to_cbor(&JsonValue::Number(-1000.0)), [0x39, 0x03, 0xe7]);
+        assert_eq!(to_cbor(&JsonValue::Number(1.1)), [0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]);
+        assert_eq!(to_cbor(&JsonValue::parse(r#"{"a":[true]}"#).unwrap()), [0xa1, 0x61, 0x61, 0x81, 0xf5]);
+        assert_eq!(from_cbor(&[0xf9, 0x3e, 0x00]).unwrap(), JsonValue::Number(1.5));
+        assert_eq!(from_cbor(&[0xfa, 0x47, 0xc3, 0x50, 0x00]).unwrap(), JsonValue::Number(100000.0));
+    }
+
+    #[test]
+    fn test_errors() {
+        for bad in [&[][..], &[0x82, 0x01], &[0x9f, 0xff], &[0x41, 0x00], &[0xa1, 0x01, 0x01], &[0x01, 0x01]] {
+            assert!(matches!(from_cbor(bad), Err(BatchError::InvalidCbor(_))), "{:?}", bad);
+        }
+        // Huge declared lengths fail on the missing data, not on allocation
+        assert!(from_cbor(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(from_cbor(&vec![0x81; 1000]).is_err());
+    }
+}
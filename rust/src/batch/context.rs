@@ -0,0 +1,284 @@
+//! Reusable per-stream filter state for video and animation previews.
+//!
+//! A [`FilterContext`] prepares a pipeline once (lookup tables for point
+//! operations, Gaussian kernels) and keeps its frame and scratch buffers
+//! between calls. As long as consecutive frames share their dimensions,
+//! LUT and blur steps run without allocating.
+
+use ndarray::{Array3, ArrayView3, Zip};
+
+use super::spec::FilterSpec;
+use crate::filters::blur_wasm::{gaussian_blur_into_u8, gaussian_kernel};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3, PyArrayMethods, PyReadwriteArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// A pipeline step with its frame-independent data precomputed.
+#[derive(Debug, Clone)]
+pub(crate) enum PreparedStep {
+    /// Per-channel point operation as a 256-entry table (alpha untouched)
+    Lut(Box<[u8; 256]>),
+    /// Gaussian blur with its kernel
+    Gaussian(Vec<f32>),
+    /// Any other filter, applied through its allocating kernel
+    Generic(FilterSpec),
+}
+
+impl PreparedStep {
+    pub(crate) fn new(spec: &FilterSpec) -> Self {
+        match *spec {
+            FilterSpec::Brightness { .. }
+            | FilterSpec::Contrast { .. }
+            | FilterSpec::Gamma { .. }
+            | FilterSpec::Exposure { .. }
+            | FilterSpec::Invert
+            | FilterSpec::Posterize { .. }
+            | FilterSpec::Solarize { .. } => {
+                // Single-channel ramp through the kernel yields the mapping
+                let ramp = Array3::from_shape_fn((1, 256, 1), |(_, x, _)| x as u8);
+                let mapped = spec.apply_u8(ramp.view());
+                let mut lut = Box::new([0u8; 256]);
+                lut.iter_mut().zip(mapped.iter()).for_each(|(dst, &src)| *dst = src);
+                PreparedStep::Lut(lut)
+            }
+            FilterSpec::Blur { sigma } if sigma > 0.0 => PreparedStep::Gaussian(gaussian_kernel(sigma)),
+            _ => PreparedStep::Generic(spec.clone()),
+        }
+    }
+
+    /// Run the step from `input` into `output`.
+    ///
+    /// `output` and `scratch` are reallocated only if their shape doesn't
+    /// fit; `output` takes the result's shape.
+    fn run(&self, input: ArrayView3<u8>, output: &mut Array3<u8>, scratch: &mut Array3<f32>) {
+        let dim = input.dim();
+        if output.dim() != dim {
+            *output = Array3::zeros(dim);
+        }
+        match self {
+            PreparedStep::Lut(lut) => {
+                let color_channels = if dim.2 == 4 { 3 } else { dim.2 };
+                Zip::indexed(output).and(input).for_each(|(_, _, c), dst, &src| {
+                    *dst = if c < color_channels { lut[src as usize] } else { src };
+                });
+            }
+            PreparedStep::Gaussian(kernel) => {
+                if scratch.dim() != dim {
+                    *scratch = Array3::zeros(dim);
+                }
+                gaussian_blur_into_u8(input, kernel, scratch, output.view_mut());
+            }
+            PreparedStep::Generic(spec) => {
+                let result = spec.apply_u8(input);
+                if result.dim() == dim {
+                    output.assign(&result);
+                } else {
+                    *output = result;
+                }
+            }
+        }
+    }
+
+    /// Run the step on `image`, leaving the result in `image`.
+    ///
+    /// Lookup tables rewrite the samples directly; other steps write into
+    /// `back`, which is then swapped with `image`, so the storage of `image`
+    /// may change.
+    pub(crate) fn run_in_place(&self, image: &mut Array3<u8>, back: &mut Array3<u8>, scratch: &mut Array3<f32>) {
+        match self {
+            PreparedStep::Lut(lut) => {
+                let color_channels = if image.dim().2 == 4 { 3 } else { image.dim().2 };
+                Zip::indexed(image).for_each(|(_, _, c), v| {
+                    if c < color_channels {
+                        *v = lut[*v as usize];
+                    }
+                });
+            }
+            _ => {
+                self.run(image.view(), back, scratch);
+                std::mem::swap(image, back);
+            }
+        }
+    }
+}
+
+/// Pipeline state reused across frames of identical dimensions.
+#[derive(Debug, Clone)]
+pub struct FilterContext {
+    pipeline: Vec<FilterSpec>,
+    steps: Vec<PreparedStep>,
+    /// Current result (front) and the destination of the next step (back)
+    front: Array3<u8>,
+    back: Array3<u8>,
+    /// Horizontal blur pass temporaries
+    scratch: Array3<f32>,
+}
+
+impl FilterContext {
+    /// Prepare a pipeline for repeated use.
+    pub fn new(pipeline: Vec<FilterSpec>) -> Self {
+        let steps = pipeline.iter().map(PreparedStep::new).collect();
+        FilterContext {
+            pipeline,
+            steps,
+            front: Array3::zeros((0, 0, 0)),
+            back: Array3::zeros((0, 0, 0)),
+            scratch: Array3::zeros((0, 0, 0)),
+        }
+    }
+
+    /// The pipeline this context runs.
+    pub fn pipeline(&self) -> &[FilterSpec] {
+        &self.pipeline
+    }
+
+    /// Filter one u8 frame.
+    ///
+    /// Buffers are (re)allocated only when the frame shape changes or a step
+    /// changes the image size.
+    ///
+    /// # Returns
+    /// View of the result, valid until the next call
+    pub fn process_u8(&mut self, frame: ArrayView3<u8>) -> ArrayView3<'_, u8> {
+        if self.front.dim() != frame.dim() {
+            self.front = Array3::zeros(frame.dim());
+        }
+        self.front.assign(&frame);
+
+        for step in &self.steps {
+            step.run(self.front.view(), &mut self.back, &mut self.scratch);
+            std::mem::swap(&mut self.front, &mut self.back);
+        }
+        self.front.view()
+    }
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Reusable filter pipeline for video frames.
+///
+/// Prepares lookup tables and blur kernels once and reuses scratch buffers
+/// while frames keep the same shape.
+///
+/// # Arguments
+/// * `spec` - Pipeline string (e.g. "brightness 0.1 | blur 2") or list of steps
+#[cfg(feature = "python")]
+#[pyclass(name = "FilterContext")]
+pub struct PyFilterContext {
+    inner: FilterContext,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyFilterContext {
+    #[new]
+    fn new(spec: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyFilterContext { inner: FilterContext::new(super::parse_py_spec(spec)?) })
+    }
+
+    /// Filter one uint8 frame (H, W, C).
+    ///
+    /// # Arguments
+    /// * `frame` - Input frame
+    /// * `out` - Optional preallocated destination with the result's shape
+    ///
+    /// # Returns
+    /// `out` if given, otherwise a new array
+    #[pyo3(signature = (frame, out=None))]
+    fn process<'py>(
+        &mut self,
+        py: Python<'py>,
+        frame: PyHostArray3<'py, u8>,
+        out: Option<Bound<'py, PyArray3<u8>>>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let result = self.inner.process_u8(frame.as_array());
+        match out {
+            Some(out) => {
+                {
+                    let mut target: PyReadwriteArray3<u8> = out.readwrite();
+                    let mut target = target.as_array_mut();
+                    if target.dim() != result.dim() {
+                        return Err(PyValueError::new_err(format!(
+                            "out has shape {:?}, result has {:?}",
+                            target.dim(),
+                            result.dim()
+                        )));
+                    }
+                    target.assign(&result);
+                }
+                Ok(out)
+            }
+            None => Ok(result.to_owned().into_pyarray(py)),
+        }
+    }
+
+    /// Number of steps in the pipeline.
+    fn __len__(&self) -> usize {
+        self.inner.pipeline().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::{apply_pipeline_u8, parse_pipeline};
+
+    fn frame(seed: usize) -> Array3<u8> {
+        Array3::from_shape_fn((12, 9, 4), |(y, x, c)| ((y * 37 + x * 11 + c * 53 + seed * 7) % 256) as u8)
+    }
+
+    #[test]
+    fn test_matches_direct_pipeline() {
+        let specs = [
+            "brightness 0.2",
+            "contrast -0.4",
+            "gamma 1.8",
+            "exposure 0.5 0.02 1.2",
+            "invert",
+            "posterize 5",
+            "solarize 100",
+            "blur 1.5",
+            "sharpen 0.5 | thumbnail 4",
+        ];
+        for text in specs {
+            let pipeline = parse_pipeline(text).unwrap();
+            let mut context = FilterContext::new(pipeline.clone());
+            for seed in 0..3 {
+                let input = frame(seed);
+                assert_eq!(context.process_u8(input.view()), apply_pipeline_u8(input.view(), &pipeline).view(), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_in_place_matches_spec() {
+        for text in ["brightness 0.2", "invert", "blur 1.5", "sharpen 0.5", "thumbnail 4"] {
+            let spec = FilterSpec::parse(text).unwrap();
+            let step = PreparedStep::new(&spec);
+            let (mut image, mut back, mut scratch) = (frame(0), Array3::zeros((0, 0, 0)), Array3::zeros((0, 0, 0)));
+            step.run_in_place(&mut image, &mut back, &mut scratch);
+            assert_eq!(image, spec.apply_u8(frame(0).view()), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_reuses_buffers_for_same_shape() {
+        let mut context = FilterContext::new(parse_pipeline("blur 2 | brightness 0.1").unwrap());
+        context.process_u8(frame(0).view());
+        let buffers = (context.front.as_ptr(), context.back.as_ptr(), context.scratch.as_ptr());
+        context.process_u8(frame(1).view());
+        assert_eq!((context.front.as_ptr(), context.back.as_ptr(), context.scratch.as_ptr()), buffers);
+
+        // A new shape reallocates
+        let small = Array3::<u8>::zeros((3, 3, 1));
+        assert_eq!(context.process_u8(small.view()).dim(), (3, 3, 1));
+    }
+}
@@ -0,0 +1,393 @@
+//! Tiled export rendering of a layered document.
+//!
+//! [`render_export`] flattens a layer stack - pixel layers with optional
+//! layer styles, and adjustment layers holding a filter pipeline or recipe -
+//! at export resolution. The output is split into tiles that are composited
+//! in parallel, each reading only the layer pixels it covers plus the
+//! kernel margin of the adjustments above, so a 16k×16k export never holds
+//! a full-size intermediate per layer or adjustment.
+//!
+//! Pixel layers are scaled and styled once (at their own bounds, not the
+//! document size) before tiling; layers outside the exported region are
+//! skipped. Adjustments that depend on the whole image (equalize, auto
+//! levels, vignette, pixelate) turn the export into a single tile.
+//! Adjustment parameters are in document pixels and scaled with the export
+//! like layer styles, so [`render_preview`] renders a fast proxy of the
+//! same document that matches the full-size export.
+
+use ndarray::{s, Array3, ArrayView3};
+use rayon::prelude::*;
+
+use super::proxy::{scale_pipeline, RenderMode};
+use super::region::{pipeline_margin, Rect};
+use super::{apply_pipeline_f32, BatchError, FilterSpec};
+use crate::filters::compositing::blend_images_f32;
+use crate::filters::resample::resize_f32;
+use crate::layer_effects::color_overlay::blend_channel;
+use crate::layer_effects::layer_style::{apply_layer_style, LayerStyle, LayerStyleInputs};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Default edge length of export tiles in pixels.
+pub const DEFAULT_TILE_SIZE: usize = 512;
+
+/// Content of an export layer.
+#[derive(Debug, Clone)]
+pub enum ExportContent<'a> {
+    /// RGBA image (0.0-1.0) at document resolution, top-left at (x, y)
+    Pixels { image: ArrayView3<'a, f32>, x: i64, y: i64, style: Option<Box<LayerStyle>> },
+    /// Filter pipeline applied to everything below
+    Adjustment(Vec<FilterSpec>),
+}
+
+/// One layer of the document, bottom to top.
+#[derive(Debug, Clone)]
+pub struct ExportLayer<'a> {
+    pub content: ExportContent<'a>,
+    /// Layer opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Blend mode, one of [`BLEND_MODES`](crate::layer_effects::color_overlay::BLEND_MODES)
+    pub blend_mode: String,
+}
+
+/// Pixel layer scaled and styled for export.
+struct PreparedPixels {
+    image: Array3<f32>,
+    x: i64,
+    y: i64,
+}
+
+/// Layer ready for tiling.
+enum Prepared<'a> {
+    /// Pixels with the layer's opacity and blend mode
+    Pixels(PreparedPixels, f32, &'a str),
+    Adjustment(&'a [FilterSpec], f32, &'a str),
+    Skipped,
+}
+
+/// Scale and style a pixel layer; `None` if it doesn't reach `reach`.
+fn prepare_pixels(image: ArrayView3<f32>, x: i64, y: i64, style: Option<&LayerStyle>, scale: f32, reach: Rect) -> Option<PreparedPixels> {
+    let (height, width, _) = image.dim();
+    let (sx, sy) = ((x as f32 * scale).round() as i64, (y as f32 * scale).round() as i64);
+    let (sw, sh) = ((width as f32 * scale).round() as usize, (height as f32 * scale).round() as usize);
+    let style = style.map(|s| LayerStyle { scale: s.scale * scale, ..s.clone() });
+    // Placement and size of the styled layer on its canvas policy
+    let ((ox, oy), (out_h, out_w)) = style.as_ref().map_or(((0, 0), (sh, sw)), |s| {
+        let expand = s.expansion();
+        (s.canvas.layer_origin((sh, sw), expand), s.canvas.output_size((sh, sw), expand))
+    });
+    let (left, top) = (sx - ox as i64, sy - oy as i64);
+    let reaches = left < (reach.x + reach.width) as i64
+        && top < (reach.y + reach.height) as i64
+        && left + out_w as i64 > reach.x as i64
+        && top + out_h as i64 > reach.y as i64;
+    if !reaches || sw == 0 || sh == 0 {
+        return None;
+    }
+    let scaled = if scale == 1.0 { image.to_owned() } else { resize_f32(image, sw, sh, false) };
+    let image = match &style {
+        Some(style) => apply_layer_style(&scaled, style, &LayerStyleInputs::default()),
+        None => scaled,
+    };
+    Some(PreparedPixels { image, x: left, y: top })
+}
+
+/// Composite the part of a layer covering `area` over the tile buffer.
+fn composite_pixels(tile: &mut Array3<f32>, area: Rect, layer: &PreparedPixels, opacity: f32, blend_mode: &str) {
+    let (height, width, channels) = layer.image.dim();
+    let opacity = opacity.clamp(0.0, 1.0);
+    let y0 = (area.y as i64).max(layer.y);
+    let y1 = ((area.y + area.height) as i64).min(layer.y + height as i64);
+    let x0 = (area.x as i64).max(layer.x);
+    let x1 = ((area.x + area.width) as i64).min(layer.x + width as i64);
+    for y in y0..y1 {
+        let (ty, ly) = ((y - area.y as i64) as usize, (y - layer.y) as usize);
+        for x in x0..x1 {
+            let (tx, lx) = ((x - area.x as i64) as usize, (x - layer.x) as usize);
+            let src_alpha = if channels == 4 { layer.image[[ly, lx, 3]] } else { 1.0 } * opacity;
+            if src_alpha <= 0.0 {
+                continue;
+            }
+            let dst_alpha = tile[[ty, tx, 3]];
+            let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+            for c in 0..3 {
+                let src = layer.image[[ly, lx, c.min(channels - 1)]];
+                let dst = tile[[ty, tx, c]];
+                // The blend mode only applies where there is a backdrop
+                let mixed = src * (1.0 - dst_alpha) + blend_channel(blend_mode, dst, src) * dst_alpha;
+                tile[[ty, tx, c]] = (mixed * src_alpha + dst * dst_alpha * (1.0 - src_alpha)) / out_alpha;
+            }
+            tile[[ty, tx, 3]] = out_alpha;
+        }
+    }
+}
+
+/// Render a layered document at export resolution.
+///
+/// # Arguments
+/// * `layers` - Layers bottom to top
+/// * `size` - Document (width, height) at scale 1
+/// * `scale` - Export resolution factor (2.0 = twice the document size)
+/// * `region` - Area to render in export pixels, `None` for the whole document
+/// * `tile_size` - Edge length of the tiles rendered in parallel
+///
+/// # Returns
+/// Flattened RGBA image (0.0-1.0) of the region
+pub fn render_export(
+    layers: &[ExportLayer],
+    size: (usize, usize),
+    scale: f32,
+    region: Option<Rect>,
+    tile_size: usize,
+) -> Result<Array3<f32>, BatchError> {
+    if !(scale > 0.0 && scale.is_finite()) {
+        return Err(BatchError::InvalidArgument(format!("scale must be positive, got {}", scale)));
+    }
+    let export_w = (size.0 as f32 * scale).round() as usize;
+    let export_h = (size.1 as f32 * scale).round() as usize;
+    let region = match region {
+        Some(r) => r.clip(export_w, export_h),
+        None => Rect::new(0, 0, export_w, export_h).clip(export_w, export_h),
+    };
+    let Some(region) = region else {
+        return Ok(Array3::zeros((0, 0, 4)));
+    };
+
+    // Adjustments at export size, in layer order
+    let pipelines: Vec<Vec<FilterSpec>> = layers
+        .iter()
+        .map(|layer| match &layer.content {
+            ExportContent::Adjustment(pipeline) if scale != 1.0 => scale_pipeline(pipeline, scale),
+            ExportContent::Adjustment(pipeline) => pipeline.clone(),
+            ExportContent::Pixels { .. } => Vec::new(),
+        })
+        .collect();
+
+    // Every tile is composited with the margin all adjustments together read
+    let mut margin = Some(0usize);
+    for pipeline in &pipelines {
+        margin = margin.zip(pipeline_margin(pipeline)?).map(|(a, b)| a.saturating_add(b));
+    }
+
+    // Tiles read the region plus that margin; whole-image adjustments read everything
+    let full = Rect::new(0, 0, export_w, export_h);
+    let reach = margin.map_or(full, |m| region.expand(m, export_w, export_h));
+
+    let prepared: Vec<Prepared> = layers
+        .par_iter()
+        .zip(&pipelines)
+        .map(|(layer, pipeline)| match &layer.content {
+            ExportContent::Pixels { image, x, y, style } => prepare_pixels(*image, *x, *y, style.as_deref(), scale, reach)
+                .map_or(Prepared::Skipped, |p| Prepared::Pixels(p, layer.opacity, &layer.blend_mode)),
+            ExportContent::Adjustment(_) => Prepared::Adjustment(pipeline, layer.opacity, &layer.blend_mode),
+        })
+        .collect();
+
+    let tiles: Vec<Rect> = match margin {
+        Some(_) => {
+            let tile = tile_size.max(1);
+            (region.y..region.y + region.height)
+                .step_by(tile)
+                .flat_map(|y| (region.x..region.x + region.width).step_by(tile).map(move |x| Rect::new(x, y, tile, tile)))
+                .filter_map(|t| t.clip(region.x + region.width, region.y + region.height))
+                .collect()
+        }
+        None => vec![region],
+    };
+
+    let rendered: Vec<(Rect, Array3<f32>)> = tiles
+        .par_iter()
+        .map(|tile| {
+            let area = margin.map_or(full, |m| tile.expand(m, export_w, export_h));
+            let mut buffer = Array3::<f32>::zeros((area.height, area.width, 4));
+            for item in &prepared {
+                match item {
+                    Prepared::Pixels(pixels, opacity, blend_mode) => composite_pixels(&mut buffer, area, pixels, *opacity, blend_mode),
+                    Prepared::Adjustment(pipeline, opacity, blend_mode) if *opacity > 0.0 => {
+                        let adjusted = apply_pipeline_f32(buffer.view(), pipeline);
+                        buffer = blend_images_f32(buffer.view(), adjusted.view(), None, *opacity, blend_mode);
+                    }
+                    _ => {}
+                }
+            }
+            let (dy, dx) = (tile.y - area.y, tile.x - area.x);
+            (*tile, buffer.slice(s![dy..dy + tile.height, dx..dx + tile.width, ..]).to_owned())
+        })
+        .collect();
+
+    let mut output = Array3::<f32>::zeros((region.height, region.width, 4));
+    for (tile, patch) in rendered {
+        let (y, x) = (tile.y - region.y, tile.x - region.x);
+        output.slice_mut(s![y..y + tile.height, x..x + tile.width, ..]).assign(&patch);
+    }
+    Ok(output)
+}
+
+/// Render a layered document in preview or full-resolution mode.
+///
+/// A preview renders the document downsampled to fit the preview size,
+/// with layer styles and adjustment parameters scaled to match; the full
+/// render of the same layers is [`render_export`] at scale 1.
+///
+/// # Returns
+/// (image, scale) where scale is the proxy factor (1.0 at full resolution)
+pub fn render_preview(
+    layers: &[ExportLayer],
+    size: (usize, usize),
+    mode: RenderMode,
+    tile_size: usize,
+) -> Result<(Array3<f32>, f32), BatchError> {
+    let scale = mode.scale(size.0, size.1);
+    Ok((render_export(layers, size, scale, None, tile_size)?, scale))
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// PSD tagged blocks of one layer as (key, payload) pairs.
+#[cfg(feature = "python")]
+type TaggedBlocks = Vec<(String, Vec<u8>)>;
+
+/// Read an optional layer field, `None` if the key is missing or None.
+#[cfg(feature = "python")]
+fn field<'py>(layer: &Bound<'py, PyAny>, key: &str) -> Option<Bound<'py, PyAny>> {
+    layer.get_item(key).ok().filter(|value| !value.is_none())
+}
+
+/// Render a layered document at export resolution, tile by tile in parallel.
+///
+/// # Arguments
+/// * `layers` - Layers bottom to top as dicts: pixel layers
+///   `{"image": uint8 (H, W, 4), "x": 0, "y": 0, "opacity": 1.0, "blend_mode": "normal"}`,
+///   adjustment layers `{"adjustment": pipeline or recipe JSON, "opacity", "blend_mode"}`
+/// * `effects` - Optional list parallel to `layers` with each layer's style as
+///   PSD tagged blocks ((key, payload) pairs, e.g. `lfx2`), or None
+/// * `scale` - Export resolution factor
+/// * `region` - Optional (x, y, width, height) in export pixels
+/// * `size` - Document (width, height); defaults to the extent of the pixel layers
+/// * `preview_size` - Render a proxy fitting this size instead of `scale`
+///   (`region` is ignored)
+///
+/// # Returns
+/// Flattened uint8 RGBA image of the region
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "render_export", signature = (layers, effects=None, scale=1.0, region=None, size=None, preview_size=None))]
+pub fn render_export_py<'py>(
+    py: Python<'py>,
+    layers: Vec<Bound<'py, PyAny>>,
+    effects: Option<Vec<Option<TaggedBlocks>>>,
+    scale: f32,
+    region: Option<(usize, usize, usize, usize)>,
+    size: Option<(usize, usize)>,
+    preview_size: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    use crate::layer_effects::psd_style::style_from_blocks;
+
+    let to_err = |e: BatchError| PyValueError::new_err(e.to_string());
+    let effects = effects.unwrap_or_default();
+    if !effects.is_empty() && effects.len() != layers.len() {
+        return Err(PyValueError::new_err(format!("{} effects for {} layers", effects.len(), layers.len())));
+    }
+
+    // Convert the pixel layers first so the views below can borrow them
+    let images: Vec<Option<Array3<f32>>> = layers
+        .iter()
+        .map(|layer| {
+            let Some(image) = field(layer, "image") else { return Ok(None) };
+            let image = image.extract::<PyHostArray3<u8>>()?.as_array().mapv(|v| v as f32 / 255.0);
+            if image.dim().2 != 4 {
+                return Err(PyValueError::new_err("layer images must be RGBA (H, W, 4)"));
+            }
+            Ok(Some(image))
+        })
+        .collect::<PyResult<_>>()?;
+
+    let mut export_layers = Vec::with_capacity(layers.len());
+    let mut extent = (0usize, 0usize);
+    for (i, (layer, image)) in layers.iter().zip(&images).enumerate() {
+        let opacity: f32 = field(layer, "opacity").map_or(Ok(1.0), |v| v.extract())?;
+        let blend_mode: String = field(layer, "blend_mode").map_or(Ok("normal".to_string()), |v| v.extract())?;
+        let content = match image {
+            Some(image) => {
+                let x: i64 = field(layer, "x").map_or(Ok(0), |v| v.extract())?;
+                let y: i64 = field(layer, "y").map_or(Ok(0), |v| v.extract())?;
+                let (height, width, _) = image.dim();
+                extent = (extent.0.max((x + width as i64).max(0) as usize), extent.1.max((y + height as i64).max(0) as usize));
+                let style = match effects.get(i) {
+                    Some(Some(blocks)) => Some(Box::new(style_from_blocks(blocks, 1.0, 120.0, 30.0)?)),
+                    _ => None,
+                };
+                ExportContent::Pixels { image: image.view(), x, y, style }
+            }
+            None => {
+                let spec: String = field(layer, "adjustment")
+                    .ok_or_else(|| PyValueError::new_err(format!("layer {} has neither 'image' nor 'adjustment'", i)))?
+                    .extract()?;
+                ExportContent::Adjustment(super::parse_pipeline_or_recipe(&spec).map_err(to_err)?)
+            }
+        };
+        export_layers.push(ExportLayer { content, opacity, blend_mode });
+    }
+
+    let size = size.unwrap_or(extent);
+    let result = match preview_size {
+        Some(max_size) => render_preview(&export_layers, size, RenderMode::Preview { max_size }, DEFAULT_TILE_SIZE).map(|(image, _)| image),
+        None => render_export(&export_layers, size, scale, region.map(|(x, y, w, h)| Rect::new(x, y, w, h)), DEFAULT_TILE_SIZE),
+    }
+    .map_err(to_err)?;
+    Ok(result.mapv(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8).into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(image: &Array3<f32>, x: i64, y: i64) -> ExportLayer<'_> {
+        ExportLayer {
+            content: ExportContent::Pixels { image: image.view(), x, y, style: None },
+            opacity: 1.0,
+            blend_mode: "normal".into(),
+        }
+    }
+
+    #[test]
+    fn test_tiles_match_single_pass() {
+        let background = Array3::from_shape_fn((40, 50, 4), |(y, x, c)| if c == 3 { 1.0 } else { ((x * 5 + y * 3 + c * 40) % 256) as f32 / 255.0 });
+        let square = Array3::from_shape_fn((10, 10, 4), |(_, _, c)| [1.0, 0.0, 0.0, 0.5][c]);
+        let mut multiply = pixels(&square, 30, -4);
+        multiply.blend_mode = "multiply".into();
+        let layers = vec![
+            pixels(&background, 0, 0),
+            pixels(&square, 5, 5),
+            ExportLayer { content: ExportContent::Adjustment(super::super::parse_pipeline("blur 1.5").unwrap()), opacity: 0.8, blend_mode: "normal".into() },
+            multiply,
+        ];
+        let single = render_export(&layers, (50, 40), 1.0, None, 1000).unwrap();
+        let tiled = render_export(&layers, (50, 40), 1.0, None, 7).unwrap();
+        assert_eq!(single.dim(), (40, 50, 4));
+        assert!(single.iter().zip(tiled.iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+
+        // A region is the same crop of the full render
+        let region = render_export(&layers, (50, 40), 1.0, Some(Rect::new(20, 10, 15, 12)), 4).unwrap();
+        assert!(region.iter().zip(single.slice(s![10..22, 20..35, ..]).iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+
+        // Multiply over the backdrop, plain color where the layer sticks out
+        let top = render_export(&layers[3..], (50, 40), 1.0, None, 16).unwrap();
+        assert_eq!(top[[0, 32, 0]], 1.0);
+        assert_eq!(top[[0, 32, 3]], 0.5);
+
+        // Scaled export doubles the size
+        let scaled = render_export(&layers[..2], (50, 40), 2.0, None, 32).unwrap();
+        assert_eq!(scaled.dim(), (80, 100, 4));
+        assert!(render_export(&layers, (50, 40), 0.0, None, 32).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Dry-run planning of filter jobs.
+//!
+//! [`plan_pipeline`] walks a pipeline (or a recipe's steps) without
+//! touching any pixels and reports the size of every intermediate image,
+//! the memory of the buffers alive at once and a rough cost, so a server
+//! can reject or queue a job before allocating gigabytes. Layer styles
+//! render on a canvas grown by [`LayerStyle::expansion`] on every side and
+//! then cut to their [`LayerStyle::canvas`];
+//! [`OperationPlan::then_layer_style`] accounts for both.
+//!
+//! Costs are in sample operations (roughly one multiply-add on one channel
+//! of one pixel). They rank jobs and scale with the input, but are not a
+//! time prediction for any particular machine.
+
+use std::fmt::Write as _;
+
+use super::json::json_string;
+use super::FilterSpec;
+use crate::filters::blur::RECURSIVE_MIN_SIGMA;
+use crate::filters::resample::thumbnail_size;
+use crate::layer_effects::layer_style::LayerStyle;
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyDict, PyList};
+
+/// Taps per output sample of a Lanczos-3 resample on one axis at 1:1.
+const LANCZOS_TAPS: f64 = 6.0;
+/// Window of the non-local means denoiser: 21x21 search, 7x7 patches with
+/// running sums.
+const DENOISE_COST: f64 = 21.0 * 21.0 * 3.0;
+
+/// One planned step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepPlan {
+    /// Filter name, or "layer_style"
+    pub name: &'static str,
+    /// Output shape (height, width, channels)
+    pub shape: (usize, usize, usize),
+    /// Bytes of the output buffer
+    pub output_bytes: u64,
+    /// Bytes of temporary buffers the step allocates besides its output
+    pub scratch_bytes: u64,
+    /// Estimated sample operations
+    pub cost: f64,
+}
+
+/// Expected shapes, memory and cost of running a job.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationPlan {
+    /// Input shape (height, width, channels)
+    pub input: (usize, usize, usize),
+    /// Bytes per sample: 1 for u8 images, 4 for f32
+    pub sample_bytes: usize,
+    /// Steps in application order
+    pub steps: Vec<StepPlan>,
+}
+
+/// Bytes of an image of the given shape.
+fn buffer_bytes(shape: (usize, usize, usize), sample_bytes: usize) -> u64 {
+    shape.0 as u64 * shape.1 as u64 * shape.2 as u64 * sample_bytes as u64
+}
+
+/// Operations per sample of a separable blur reaching `radius` pixels.
+fn separable_cost(radius: f32) -> f64 {
+    2.0 * (2.0 * radius.max(0.0).ceil() as f64 + 1.0)
+}
+
+/// Operations per output sample of one step.
+fn sample_cost(spec: &FilterSpec, input: (usize, usize), output: (usize, usize)) -> f64 {
+    let gaussian = |sigma: f32| {
+        if sigma <= 0.0 {
+            0.0
+        } else if sigma >= RECURSIVE_MIN_SIGMA {
+            // Recursive filter: fixed work per sample, forward and backward on both axes
+            16.0
+        } else {
+            separable_cost(3.0 * sigma)
+        }
+    };
+    match *spec {
+        FilterSpec::Blur { sigma } => gaussian(sigma),
+        FilterSpec::HighPass { radius } => gaussian(radius) + 1.0,
+        FilterSpec::BoxBlur { .. } => 4.0,
+        // Huang's running histogram updates one column per row step
+        FilterSpec::Median { radius } => separable_cost(radius as f32),
+        FilterSpec::Dilate { radius } | FilterSpec::Erode { radius } => separable_cost(radius),
+        FilterSpec::Denoise { .. } => DENOISE_COST,
+        FilterSpec::Sharpen { .. } => 9.0,
+        FilterSpec::HueShift { .. } | FilterSpec::Vibrance { .. } | FilterSpec::Temperature { .. } => 4.0,
+        FilterSpec::Equalize | FilterSpec::AutoLevels { .. } | FilterSpec::Pixelate { .. } | FilterSpec::Vignette { .. } => 2.0,
+        FilterSpec::Thumbnail { .. } => {
+            // Lanczos taps grow with the reduction ratio on each axis
+            let ratio = |from: usize, to: usize| (from as f64 / to.max(1) as f64).max(1.0);
+            LANCZOS_TAPS * (ratio(input.1, output.1) + ratio(input.0, output.0))
+        }
+        _ => 1.0,
+    }
+}
+
+impl FilterSpec {
+    /// Output (width, height) of this step for a `width` x `height` input.
+    ///
+    /// Only thumbnail changes the size; every other step keeps it.
+    pub fn output_size(&self, width: usize, height: usize) -> (usize, usize) {
+        match *self {
+            FilterSpec::Thumbnail { max_width, max_height, .. } => thumbnail_size(width, height, max_width, max_height),
+            _ => (width, height),
+        }
+    }
+}
+
+impl OperationPlan {
+    /// Empty plan for an input of shape (height, width, channels).
+    pub fn new(input: (usize, usize, usize), sample_bytes: usize) -> Self {
+        Self { input, sample_bytes, steps: Vec::new() }
+    }
+
+    /// Shape (height, width, channels) of the final result.
+    pub fn output(&self) -> (usize, usize, usize) {
+        self.steps.last().map_or(self.input, |step| step.shape)
+    }
+
+    /// Bytes of the final result.
+    pub fn output_bytes(&self) -> u64 {
+        self.steps.last().map_or(buffer_bytes(self.input, self.sample_bytes), |step| step.output_bytes)
+    }
+
+    /// Largest memory alive at once: the caller's input plus, for the worst
+    /// step, its input copy, output and scratch buffers.
+    pub fn peak_bytes(&self) -> u64 {
+        let input = buffer_bytes(self.input, self.sample_bytes);
+        let mut previous = input;
+        let mut worst = input;
+        for step in &self.steps {
+            worst = worst.max(previous + step.output_bytes + step.scratch_bytes);
+            previous = step.output_bytes;
+        }
+        input + worst
+    }
+
+    /// Estimated sample operations of the whole job.
+    pub fn cost(&self) -> f64 {
+        self.steps.iter().map(|step| step.cost).sum()
+    }
+
+    /// Append the steps of a pipeline.
+    pub fn then_pipeline(mut self, pipeline: &[FilterSpec]) -> Self {
+        for spec in pipeline {
+            let (height, width, channels) = self.output();
+            let (out_w, out_h) = spec.output_size(width, height);
+            let shape = (out_h, out_w, channels);
+            let per_sample = sample_cost(spec, (height, width), (out_h, out_w));
+            self.steps.push(StepPlan {
+                name: spec.name(),
+                shape,
+                output_bytes: buffer_bytes(shape, self.sample_bytes),
+                scratch_bytes: 0,
+                cost: per_sample * (out_h * out_w * channels) as f64,
+            });
+        }
+        self
+    }
+
+    /// Append rendering a layer style on the current result.
+    ///
+    /// The styled layer is RGBA f32 on the style's canvas. Effects render
+    /// on a canvas grown by [`LayerStyle::expansion`] on every side, with
+    /// one f32 mask per enabled effect as scratch.
+    pub fn then_layer_style(mut self, style: &LayerStyle) -> Self {
+        let (height, width, _) = self.output();
+        let expand = style.expansion();
+        let grown = (height + 2 * expand, width + 2 * expand);
+        let (out_h, out_w) = style.canvas.output_size((height, width), expand);
+        let shape = (out_h, out_w, 4);
+        let pixels = (grown.0 * grown.1) as f64;
+
+        // Blurred effects reach about as far as the canvas grows
+        let blurred = [
+            style.drop_shadow.is_some(),
+            style.inner_shadow.is_some(),
+            style.outer_glow.is_some(),
+            style.inner_glow.is_some(),
+            style.bevel_emboss.is_some(),
+            style.satin.is_some(),
+            style.stroke.is_some(),
+        ];
+        let overlays = [style.color_overlay.is_some(), style.gradient_overlay.is_some(), style.pattern_overlay.is_some()];
+        let blurred = blurred.iter().filter(|&&on| on).count();
+        let overlays = overlays.iter().filter(|&&on| on).count();
+        let effect_cost = blurred as f64 * separable_cost(expand as f32) + overlays as f64 * 4.0;
+
+        self.steps.push(StepPlan {
+            name: "layer_style",
+            shape,
+            output_bytes: buffer_bytes(shape, 4),
+            scratch_bytes: (blurred + overlays) as u64 * buffer_bytes((grown.0, grown.1, 1), 4),
+            cost: pixels * (4.0 + effect_cost),
+        });
+        self
+    }
+
+    /// Plan as JSON: `{"input": [h, w, c], "output": [h, w, c],
+    /// "output_bytes", "peak_bytes", "cost", "steps": [{"name", "shape",
+    /// "output_bytes", "scratch_bytes", "cost"}]}`.
+    pub fn to_json(&self) -> String {
+        let shape = |(h, w, c): (usize, usize, usize)| format!("[{},{},{}]", h, w, c);
+        let mut out = format!(
+            r#"{{"input":{},"output":{},"output_bytes":{},"peak_bytes":{},"cost":{},"steps":["#,
+            shape(self.input),
+            shape(self.output()),
+            self.output_bytes(),
+            self.peak_bytes(),
+            self.cost().round(),
+        );
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                r#"{{"name":{},"shape":{},"output_bytes":{},"scratch_bytes":{},"cost":{}}}"#,
+                json_string(step.name),
+                shape(step.shape),
+                step.output_bytes,
+                step.scratch_bytes,
+                step.cost.round(),
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// Plan a pipeline on an input of shape (height, width, channels).
+///
+/// # Arguments
+/// * `pipeline` - Steps in application order (e.g. from
+///   [`parse_pipeline_or_recipe`](super::parse_pipeline_or_recipe))
+/// * `input` - Input shape (height, width, channels)
+/// * `sample_bytes` - 1 for u8 images, 4 for f32
+pub fn plan_pipeline(pipeline: &[FilterSpec], input: (usize, usize, usize), sample_bytes: usize) -> OperationPlan {
+    OperationPlan::new(input, sample_bytes).then_pipeline(pipeline)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Plan a filter job without running it.
+///
+/// # Arguments
+/// * `spec` - Pipeline string, recipe JSON or list of steps, or None for
+///   no filter steps
+/// * `input_shape` - (height, width) or (height, width, channels)
+/// * `dtype` - "uint8" or "float32"
+/// * `effects` - Optional PSD tagged blocks ((key, payload) pairs, e.g.
+///   `lfx2`) of a layer style rendered after the pipeline
+///
+/// # Returns
+/// Dict with `input`, `output` (shape tuples), `output_bytes`,
+/// `peak_bytes`, `cost` and `steps` (list of dicts with `name`, `shape`,
+/// `output_bytes`, `scratch_bytes`, `cost`)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "plan_operation", signature = (spec, input_shape, dtype="uint8", effects=None))]
+pub fn plan_operation_py<'py>(
+    py: Python<'py>,
+    spec: Option<&Bound<'py, PyAny>>,
+    input_shape: Vec<usize>,
+    dtype: &str,
+    effects: Option<Vec<(String, Vec<u8>)>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    use crate::layer_effects::psd_style::style_from_blocks;
+
+    let input = match input_shape[..] {
+        [height, width] => (height, width, 1),
+        [height, width, channels] => (height, width, channels),
+        _ => return Err(PyValueError::new_err(format!("input_shape needs 2 or 3 values, got {}", input_shape.len()))),
+    };
+    let sample_bytes = match dtype {
+        "uint8" | "u8" => 1,
+        "float32" | "f32" => 4,
+        _ => return Err(PyValueError::new_err(format!("Unknown dtype '{}'. Use 'uint8' or 'float32'", dtype))),
+    };
+    let pipeline = match spec {
+        Some(spec) if !spec.is_none() => super::parse_py_spec(spec)?,
+        _ => Vec::new(),
+    };
+    let mut plan = plan_pipeline(&pipeline, input, sample_bytes);
+    if let Some(blocks) = effects {
+        plan = plan.then_layer_style(&style_from_blocks(&blocks, 1.0, 120.0, 30.0)?);
+    }
+
+    let steps = PyList::empty(py);
+    for step in &plan.steps {
+        let entry = PyDict::new(py);
+        entry.set_item("name", step.name)?;
+        entry.set_item("shape", step.shape)?;
+        entry.set_item("output_bytes", step.output_bytes)?;
+        entry.set_item("scratch_bytes", step.scratch_bytes)?;
+        entry.set_item("cost", step.cost)?;
+        steps.append(entry)?;
+    }
+    let result = PyDict::new(py);
+    result.set_item("input", plan.input)?;
+    result.set_item("output", plan.output())?;
+    result.set_item("output_bytes", plan.output_bytes())?;
+    result.set_item("peak_bytes", plan.peak_bytes())?;
+    result.set_item("cost", plan.cost())?;
+    result.set_item("steps", steps)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::parse_pipeline;
+    use crate::filters::core::CanvasPolicy;
+    use crate::layer_effects::drop_shadow::DropShadowParams;
+
+    #[test]
+    fn test_plan_shapes_memory_and_cost() {
+        let pipeline = parse_pipeline("blur 2 | thumbnail 100 | invert").unwrap();
+        let plan = plan_pipeline(&pipeline, (400, 800, 4), 1);
+        assert_eq!(plan.steps.iter().map(|s| s.shape).collect::<Vec<_>>(), vec![(400, 800, 4), (50, 100, 4), (50, 100, 4)]);
+        assert_eq!(plan.output(), (50, 100, 4));
+        assert_eq!(plan.output_bytes(), 50 * 100 * 4);
+        // Caller's input, plus the blur's input copy and output
+        assert_eq!(plan.peak_bytes(), 3 * 400 * 800 * 4);
+        assert!(plan.steps[0].cost > 10.0 * plan.steps[2].cost);
+        // Costs scale with the image
+        let larger = plan_pipeline(&pipeline, (800, 1600, 4), 4);
+        assert!(larger.cost() > 3.0 * plan.cost());
+        assert_eq!(larger.peak_bytes(), 4 * 3 * 800 * 1600 * 4);
+
+        // A drop shadow grows the canvas on every side
+        let style = LayerStyle { drop_shadow: Some(DropShadowParams::default()), ..Default::default() };
+        let expand = style.expansion();
+        let styled = plan.clone().then_layer_style(&style);
+        assert_eq!(styled.output(), (50 + 2 * expand, 100 + 2 * expand, 4));
+        assert!(styled.steps[3].scratch_bytes > 0);
+        let clipped = plan.clone().then_layer_style(&LayerStyle { canvas: CanvasPolicy::ClipToInput, ..style.clone() });
+        assert_eq!(clipped.output(), (50, 100, 4));
+        assert_eq!(clipped.steps[3].scratch_bytes, styled.steps[3].scratch_bytes);
+
+        let json = styled.to_json();
+        assert!(json.starts_with(r#"{"input":[400,800,4],"output":["#), "{json}");
+        assert!(json.contains(r#""name":"layer_style""#));
+        assert!(super::super::json::JsonValue::parse(&json).is_ok());
+    }
+}
@@ -0,0 +1,161 @@
+//! Preview-resolution proxy rendering.
+//!
+//! A [`RenderMode::Preview`] render downsamples the input to fit a preview
+//! size first and runs the pipeline with every size-dependent parameter
+//! scaled by the same factor (see [`FilterSpec::scaled`]), so a blur of
+//! sigma 20 on a 8000 px photo looks the same in a 1000 px preview as in
+//! the final render. [`RenderMode::Full`] runs the identical pipeline on
+//! the original pixels when the full-resolution result is requested.
+
+use ndarray::{Array3, ArrayView3};
+
+use super::{apply_pipeline_f32, apply_pipeline_u8, FilterSpec};
+use crate::filters::resample::{thumbnail_f32, thumbnail_size, thumbnail_u8};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Resolution a pipeline or document is rendered at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Downsampled to fit `max_size` x `max_size` (never enlarged)
+    Preview { max_size: usize },
+    /// Original resolution
+    Full,
+}
+
+impl RenderMode {
+    /// Preview mode for `max_size`, or full resolution when `full_resolution` is set.
+    pub fn new(max_size: usize, full_resolution: bool) -> Self {
+        if full_resolution {
+            RenderMode::Full
+        } else {
+            RenderMode::Preview { max_size }
+        }
+    }
+
+    /// Size of a `width` x `height` image rendered in this mode.
+    pub fn size(&self, width: usize, height: usize) -> (usize, usize) {
+        match *self {
+            RenderMode::Preview { max_size } => thumbnail_size(width, height, max_size.max(1), max_size.max(1)),
+            RenderMode::Full => (width, height),
+        }
+    }
+
+    /// Factor applied to the size and to size-dependent parameters.
+    pub fn scale(&self, width: usize, height: usize) -> f32 {
+        let (out_w, out_h) = self.size(width, height);
+        // The longer side determines the rounding least
+        if width >= height && width > 0 {
+            out_w as f32 / width as f32
+        } else if height > 0 {
+            out_h as f32 / height as f32
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Scale the size-dependent parameters of every step.
+pub fn scale_pipeline(pipeline: &[FilterSpec], factor: f32) -> Vec<FilterSpec> {
+    pipeline.iter().map(|step| step.scaled(factor)).collect()
+}
+
+/// Render a pipeline on a u8 image in the given mode.
+///
+/// # Returns
+/// (result, scale) where scale is the proxy factor (1.0 at full resolution)
+pub fn render_proxy_u8(image: ArrayView3<u8>, pipeline: &[FilterSpec], mode: RenderMode) -> (Array3<u8>, f32) {
+    let (height, width, _) = image.dim();
+    let scale = mode.scale(width, height);
+    if scale >= 1.0 {
+        return (apply_pipeline_u8(image, pipeline), 1.0);
+    }
+    let (out_w, out_h) = mode.size(width, height);
+    let proxy = thumbnail_u8(image, out_w, out_h, true);
+    (apply_pipeline_u8(proxy.view(), &scale_pipeline(pipeline, scale)), scale)
+}
+
+/// Render a pipeline on an f32 image (0.0-1.0) in the given mode.
+///
+/// Same as render_proxy_u8 but for f32 images.
+pub fn render_proxy_f32(image: ArrayView3<f32>, pipeline: &[FilterSpec], mode: RenderMode) -> (Array3<f32>, f32) {
+    let (height, width, _) = image.dim();
+    let scale = mode.scale(width, height);
+    if scale >= 1.0 {
+        return (apply_pipeline_f32(image, pipeline), 1.0);
+    }
+    let (out_w, out_h) = mode.size(width, height);
+    let proxy = thumbnail_f32(image, out_w, out_h, true);
+    (apply_pipeline_f32(proxy.view(), &scale_pipeline(pipeline, scale)), scale)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Render a filter pipeline as a fast preview-resolution proxy.
+///
+/// # Arguments
+/// * `image` - Input uint8 image (H, W, C)
+/// * `spec` - Pipeline string or recipe JSON, parameters in full-resolution pixels
+/// * `max_size` - Longest side of the preview
+/// * `full_resolution` - Render the original size with the same parameters instead
+///
+/// # Returns
+/// Tuple (image, scale) with the proxy factor (1.0 at full resolution)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "render_proxy", signature = (image, spec, max_size=1024, full_resolution=false))]
+pub fn render_proxy_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    spec: &str,
+    max_size: usize,
+    full_resolution: bool,
+) -> PyResult<(Bound<'py, PyArray3<u8>>, f32)> {
+    let pipeline = super::parse_pipeline_or_recipe(spec).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let (result, scale) = render_proxy_u8(image.as_array(), &pipeline, RenderMode::new(max_size, full_resolution));
+    Ok((result.into_pyarray(py), scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::parse_pipeline;
+
+    #[test]
+    fn test_proxy_matches_full_render() {
+        // Two flat halves with a hard edge in the middle
+        let image = Array3::from_shape_fn((200, 400, 3), |(_, x, _)| if x < 200 { 40u8 } else { 220 });
+        let pipeline = parse_pipeline("blur 8 | thumbnail 300").unwrap();
+        assert_eq!(pipeline[0].scaled(0.25), FilterSpec::Blur { sigma: 2.0 });
+
+        let mode = RenderMode::Preview { max_size: 100 };
+        assert_eq!(mode.size(400, 200), (100, 50));
+        let (proxy, scale) = render_proxy_u8(image.view(), &pipeline, mode);
+        assert_eq!(scale, 0.25);
+        assert_eq!(proxy.dim(), (38, 75, 3));
+
+        let (full, scale) = render_proxy_u8(image.view(), &pipeline, RenderMode::new(100, true));
+        assert_eq!(scale, 1.0);
+        assert_eq!(full.dim(), (150, 300, 3));
+        // The edge is just as soft relative to the image width
+        let soft = |image: &Array3<u8>, y: usize| {
+            let row = image.slice(ndarray::s![y, .., 0]);
+            row.iter().filter(|&&v| v > 60 && v < 200).count() as f32 / row.len() as f32
+        };
+        let (proxy_soft, full_soft) = (soft(&proxy, 19), soft(&full, 75));
+        assert!((proxy_soft - full_soft).abs() < 0.03, "{} vs {}", proxy_soft, full_soft);
+
+        // Images already within the preview size are rendered as they are
+        let (small, scale) = render_proxy_u8(image.view(), &pipeline, RenderMode::Preview { max_size: 1000 });
+        assert_eq!((small.dim(), scale), (full.dim(), 1.0));
+    }
+}
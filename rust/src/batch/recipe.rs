@@ -0,0 +1,318 @@
+//! Filter recipes: saved, serializable lists of filter steps.
+//!
+//! A recipe is an adjustment stack that can be stored with a document,
+//! edited step by step (steps can be disabled without losing their
+//! parameters) and replayed on any number of images:
+//!
+//! ```text
+//! {"version": 1, "steps": [
+//!     {"filter": "brightness", "params": {"amount": 0.2}},
+//!     {"filter": "blur", "params": {"sigma": 1.5}, "enabled": false}
+//! ]}
+//! ```
+//!
+//! Filter names and parameters are those of the [`registry`](super::registry);
+//! omitted parameters take their defaults and `enabled` defaults to true.
+//! The same document can be stored as JSON text or as CBOR.
+
+use ndarray::{Array3, ArrayView3};
+
+use super::cbor::{from_cbor, to_cbor};
+use super::json::JsonValue;
+use super::{apply_pipeline_f32, apply_pipeline_u8, parse_pipeline, BatchError, FilterSpec};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+
+/// Format version written by [`Recipe::to_json`] and [`Recipe::to_cbor`].
+pub const RECIPE_VERSION: u32 = 1;
+
+/// One step of a recipe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipeStep {
+    pub filter: FilterSpec,
+    /// Disabled steps are kept but skipped when the recipe is applied
+    pub enabled: bool,
+}
+
+impl RecipeStep {
+    /// Read one step object, e.g. `{"filter": "brightness", "params": {"amount": 0.2}}`.
+    pub fn from_json(text: &str) -> Result<Self, BatchError> {
+        Recipe::step_from_value(&JsonValue::parse(text)?)
+    }
+}
+
+/// Ordered list of filter steps.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Recipe {
+    pub steps: Vec<RecipeStep>,
+}
+
+fn invalid(msg: String) -> BatchError {
+    BatchError::InvalidRecipe(msg)
+}
+
+impl Recipe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recipe with every step of a parsed pipeline enabled.
+    pub fn from_pipeline(pipeline: &[FilterSpec]) -> Self {
+        Self { steps: pipeline.iter().map(|filter| RecipeStep { filter: filter.clone(), enabled: true }).collect() }
+    }
+
+    /// Append an enabled step.
+    pub fn push(&mut self, filter: FilterSpec) -> &mut Self {
+        self.steps.push(RecipeStep { filter, enabled: true });
+        self
+    }
+
+    /// Enabled steps in application order.
+    pub fn pipeline(&self) -> Vec<FilterSpec> {
+        self.steps.iter().filter(|step| step.enabled).map(|step| step.filter.clone()).collect()
+    }
+
+    /// Apply the enabled steps to a u8 image.
+    pub fn apply_u8(&self, image: ArrayView3<u8>) -> Array3<u8> {
+        apply_pipeline_u8(image, &self.pipeline())
+    }
+
+    /// Apply the enabled steps to an f32 image (0.0-1.0).
+    pub fn apply_f32(&self, image: ArrayView3<f32>) -> Array3<f32> {
+        apply_pipeline_f32(image, &self.pipeline())
+    }
+
+    /// Recipe as a document with every parameter written out.
+    pub fn to_value(&self) -> JsonValue {
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                JsonValue::Object(vec![
+                    ("filter".to_string(), JsonValue::String(step.filter.name().to_string())),
+                    ("params".to_string(), step.filter.to_params()),
+                    ("enabled".to_string(), JsonValue::Bool(step.enabled)),
+                ])
+            })
+            .collect();
+        JsonValue::Object(vec![
+            ("version".to_string(), JsonValue::Number(RECIPE_VERSION as f64)),
+            ("steps".to_string(), JsonValue::Array(steps)),
+        ])
+    }
+
+    /// Read and validate a recipe document.
+    ///
+    /// Errors name the offending step, e.g. `step 2: unknown filter 'glow'`.
+    pub fn from_value(value: &JsonValue) -> Result<Self, BatchError> {
+        let JsonValue::Object(members) = value else {
+            return Err(invalid("recipe must be an object".into()));
+        };
+        for (key, _) in members {
+            if !matches!(key.as_str(), "version" | "steps") {
+                return Err(invalid(format!("unknown key '{}'", key)));
+            }
+        }
+        match value.get("version") {
+            Some(JsonValue::Number(v)) if *v >= 1.0 && *v <= RECIPE_VERSION as f64 && v.fract() == 0.0 => {}
+            Some(other) => return Err(invalid(format!("unsupported version {}", other.to_json()))),
+            None => return Err(invalid("missing 'version'".into())),
+        }
+        let Some(JsonValue::Array(steps)) = value.get("steps") else {
+            return Err(invalid("'steps' must be an array".into()));
+        };
+        let steps = steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| Self::step_from_value(step).map_err(|e| invalid(format!("step {}: {}", i + 1, e))))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { steps })
+    }
+
+    fn step_from_value(value: &JsonValue) -> Result<RecipeStep, BatchError> {
+        let JsonValue::Object(members) = value else {
+            return Err(invalid("step must be an object".into()));
+        };
+        for (key, _) in members {
+            if !matches!(key.as_str(), "filter" | "params" | "enabled") {
+                return Err(invalid(format!("unknown key '{}'", key)));
+            }
+        }
+        let Some(JsonValue::String(name)) = value.get("filter") else {
+            return Err(invalid("'filter' must be a string".into()));
+        };
+        let enabled = match value.get("enabled") {
+            None => true,
+            Some(JsonValue::Bool(enabled)) => *enabled,
+            Some(other) => return Err(invalid(format!("'enabled' must be a bool, got {}", other.to_json()))),
+        };
+        let filter = FilterSpec::from_params(name, value.get("params").unwrap_or(&JsonValue::Null))?;
+        Ok(RecipeStep { filter, enabled })
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_value().to_json()
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, BatchError> {
+        Self::from_value(&JsonValue::parse(text)?)
+    }
+
+    pub fn to_cbor(&self) -> Vec<u8> {
+        to_cbor(&self.to_value())
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, BatchError> {
+        Self::from_value(&from_cbor(bytes)?)
+    }
+}
+
+/// Apply a recipe to a u8 image.
+pub fn apply_recipe_u8(image: ArrayView3<u8>, recipe: &Recipe) -> Array3<u8> {
+    recipe.apply_u8(image)
+}
+
+/// Apply a recipe to an f32 image (0.0-1.0).
+pub fn apply_recipe_f32(image: ArrayView3<f32>, recipe: &Recipe) -> Array3<f32> {
+    recipe.apply_f32(image)
+}
+
+/// Parse either a recipe (JSON object) or a pipeline string.
+///
+/// # Returns
+/// The enabled steps in application order
+pub fn parse_pipeline_or_recipe(text: &str) -> Result<Vec<FilterSpec>, BatchError> {
+    if text.trim_start().starts_with('{') {
+        Ok(Recipe::from_json(text)?.pipeline())
+    } else {
+        parse_pipeline(text)
+    }
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Read a recipe given as JSON text or CBOR bytes.
+#[cfg(feature = "python")]
+fn extract_recipe(recipe: &Bound<'_, PyAny>) -> PyResult<Recipe> {
+    let result = match recipe.extract::<String>() {
+        Ok(text) => Recipe::from_json(&text),
+        Err(_) => Recipe::from_cbor(recipe.extract::<&[u8]>()?),
+    };
+    result.map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Apply a filter recipe to a uint8 image.
+///
+/// # Arguments
+/// * `image` - Image (H, W, C) with 1, 3 or 4 channels
+/// * `recipe` - Recipe as JSON text or CBOR bytes
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_recipe")]
+pub fn apply_recipe_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    recipe: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    Ok(extract_recipe(recipe)?.apply_u8(image.as_array()).into_pyarray(py))
+}
+
+/// Apply a filter recipe to a float32 image.
+///
+/// Same as apply_recipe but for f32 images.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_recipe_f32")]
+pub fn apply_recipe_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    recipe: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    Ok(extract_recipe(recipe)?.apply_f32(image.as_array()).into_pyarray(py))
+}
+
+/// Validate a recipe and return it as normalized JSON.
+///
+/// All parameters are written out with their defaults filled in.
+/// Raises ValueError naming the first invalid step.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "validate_recipe")]
+pub fn validate_recipe_py(recipe: &Bound<'_, PyAny>) -> PyResult<String> {
+    Ok(extract_recipe(recipe)?.to_json())
+}
+
+/// Convert a recipe (JSON text or CBOR bytes) to CBOR bytes.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "recipe_to_cbor")]
+pub fn recipe_to_cbor_py<'py>(py: Python<'py>, recipe: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    Ok(PyBytes::new(py, &extract_recipe(recipe)?.to_cbor()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Recipe {
+        let mut recipe = Recipe::new();
+        recipe.push(FilterSpec::Brightness { amount: 0.2 }).push(FilterSpec::Blur { sigma: 1.5 });
+        recipe.push(FilterSpec::Thumbnail { max_width: 8, max_height: 4, srgb_aware: false });
+        recipe.steps[1].enabled = false;
+        recipe
+    }
+
+    #[test]
+    fn test_json_and_cbor_roundtrip() {
+        let recipe = sample();
+        let json = recipe.to_json();
+        assert!(json.starts_with(r#"{"version":1,"steps":[{"filter":"brightness","params":{"amount":0.2},"enabled":true}"#));
+        assert_eq!(Recipe::from_json(&json).unwrap(), recipe);
+        assert_eq!(Recipe::from_cbor(&recipe.to_cbor()).unwrap(), recipe);
+        assert!(recipe.to_cbor().len() < json.len());
+    }
+
+    #[test]
+    fn test_defaults_and_disabled_steps() {
+        let recipe = Recipe::from_json(r#"{"version":1,"steps":[{"filter":"sharpen"},{"filter":"invert","enabled":false}]}"#);
+        let recipe = recipe.unwrap();
+        assert_eq!(recipe.steps[0].filter, FilterSpec::Sharpen { amount: 1.0 });
+        assert_eq!(recipe.pipeline(), vec![FilterSpec::Sharpen { amount: 1.0 }]);
+
+        let image = Array3::<u8>::from_shape_fn((16, 16, 3), |(y, x, c)| (y * 13 + x * 7 + c * 50) as u8);
+        let expected = apply_pipeline_u8(image.view(), &parse_pipeline("brightness 0.2 | thumbnail 8 4 false").unwrap());
+        assert_eq!(apply_recipe_u8(image.view(), &sample()), expected);
+    }
+
+    #[test]
+    fn test_validation_names_step() {
+        let err = |text: &str| match Recipe::from_json(text) {
+            Err(BatchError::InvalidRecipe(msg)) => msg,
+            other => panic!("expected InvalidRecipe for {}, got {:?}", text, other),
+        };
+        assert!(err(r#"{"version":1,"steps":[{"filter":"blur"},{"filter":"glow"}]}"#).starts_with("step 2: unknown filter"));
+        assert!(err(r#"{"version":1,"steps":[{"filter":"blur","params":{"sigma":-1}}]}"#).starts_with("step 1: invalid argument"));
+        assert!(err(r#"{"version":1,"steps":[{"filter":"blur","opacity":1}]}"#).contains("unknown key 'opacity'"));
+        assert!(err(r#"{"version":2,"steps":[]}"#).contains("unsupported version"));
+        assert!(err(r#"{"steps":[]}"#).contains("missing 'version'"));
+        assert!(matches!(Recipe::from_json("{"), Err(BatchError::InvalidJson(_))));
+    }
+
+    #[test]
+    fn test_parse_pipeline_or_recipe() {
+        let from_recipe = parse_pipeline_or_recipe(r#" {"version":1,"steps":[{"filter":"gamma","params":{"gamma":2}}]}"#);
+        assert_eq!(from_recipe.unwrap(), vec![FilterSpec::Gamma { gamma: 2.0 }]);
+        assert_eq!(parse_pipeline_or_recipe("gamma 2").unwrap(), vec![FilterSpec::Gamma { gamma: 2.0 }]);
+    }
+}
@@ -0,0 +1,296 @@
+//! Dirty-region filtering for live previews.
+//!
+//! Recomputes a filter only inside one or more rectangles of a large image,
+//! reading just the rectangle plus the kernel margin of the pipeline, and
+//! writes the results back into the full buffer. Pixels inside the
+//! rectangles match filtering the whole image; pixels outside are left
+//! untouched.
+//!
+//! Filters that depend on the whole image (histogram equalization, auto
+//! levels, vignette, pixelate) fall back to filtering the full image once
+//! and copying the rectangles out of it. Size-changing steps (thumbnail)
+//! are rejected.
+
+use ndarray::{s, Array3, ArrayView3, ArrayViewMut3};
+use rayon::prelude::*;
+
+use super::{apply_pipeline_f32, apply_pipeline_u8, BatchError, FilterSpec};
+use crate::filters::blur_wasm::gaussian_kernel;
+
+#[cfg(feature = "python")]
+use numpy::PyReadwriteArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Pixel rectangle in image coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect { x, y, width, height }
+    }
+
+    /// Intersection with a `width` x `height` image, `None` if empty.
+    pub fn clip(&self, width: usize, height: usize) -> Option<Rect> {
+        let x0 = self.x.min(width);
+        let y0 = self.y.min(height);
+        let x1 = self.x.saturating_add(self.width).min(width);
+        let y1 = self.y.saturating_add(self.height).min(height);
+        (x1 > x0 && y1 > y0).then(|| Rect::new(x0, y0, x1 - x0, y1 - y0))
+    }
+
+    /// Grow by `margin` on every side, staying inside a `width` x `height` image.
+    pub fn expand(&self, margin: usize, width: usize, height: usize) -> Rect {
+        let x0 = self.x.saturating_sub(margin);
+        let y0 = self.y.saturating_sub(margin);
+        let x1 = self.x.saturating_add(self.width).saturating_add(margin).min(width);
+        let y1 = self.y.saturating_add(self.height).saturating_add(margin).min(height);
+        Rect::new(x0, y0, x1.saturating_sub(x0), y1.saturating_sub(y0))
+    }
+}
+
+impl FilterSpec {
+    /// Distance in pixels a step reads around each output pixel.
+    ///
+    /// # Returns
+    /// `Some(0)` for per-pixel steps, `None` if the result depends on the
+    /// whole image (or its size)
+    pub fn margin(&self) -> Option<usize> {
+        match *self {
+            FilterSpec::Sharpen { .. } => Some(1),
+            // high_pass blurs with the same kernel size as blur
+            FilterSpec::HighPass { radius: sigma } | FilterSpec::Blur { sigma } => Some(gaussian_kernel(sigma).len() / 2),
+            FilterSpec::BoxBlur { radius } => Some(radius as usize),
+            FilterSpec::Median { radius } => Some(radius.min(21) as usize),
+            // 21x21 search window of 7x7 patches
+            FilterSpec::Denoise { .. } => Some(10 + 3),
+            FilterSpec::Dilate { radius } | FilterSpec::Erode { radius } => Some(radius.max(0.0).ceil() as usize),
+            FilterSpec::Equalize
+            | FilterSpec::AutoLevels { .. }
+            | FilterSpec::Vignette { .. }
+            | FilterSpec::Pixelate { .. }
+            | FilterSpec::Thumbnail { .. } => None,
+            _ => Some(0),
+        }
+    }
+}
+
+/// Combined margin of a pipeline, `None` if any step needs the whole image.
+pub(crate) fn pipeline_margin(pipeline: &[FilterSpec]) -> Result<Option<usize>, BatchError> {
+    let mut total = Some(0usize);
+    for step in pipeline {
+        if matches!(step, FilterSpec::Thumbnail { .. }) {
+            return Err(BatchError::InvalidArgument(format!(
+                "'{}' changes the image size and cannot be applied to a region",
+                step.name()
+            )));
+        }
+        total = total.zip(step.margin()).map(|(a, b)| a.saturating_add(b));
+    }
+    Ok(total)
+}
+
+fn apply_in_rects<T: Copy + Send + Sync>(
+    mut image: ArrayViewMut3<T>,
+    rects: &[Rect],
+    pipeline: &[FilterSpec],
+    apply: impl Fn(ArrayView3<T>) -> Array3<T> + Sync,
+) -> Result<(), BatchError> {
+    let margin = pipeline_margin(pipeline)?;
+    let (height, width, _) = image.dim();
+    let rects: Vec<Rect> = rects.iter().filter_map(|r| r.clip(width, height)).collect();
+    if rects.is_empty() {
+        return Ok(());
+    }
+
+    // Every patch reads the unmodified input, so overlapping rects do not
+    // see each other's results
+    let patches: Vec<(Rect, Array3<T>)> = match margin {
+        Some(margin) => {
+            let source = image.view();
+            rects
+                .par_iter()
+                .map(|rect| {
+                    let area = rect.expand(margin, width, height);
+                    let result = apply(source.slice(s![area.y..area.y + area.height, area.x..area.x + area.width, ..]));
+                    let (dy, dx) = (rect.y - area.y, rect.x - area.x);
+                    (*rect, result.slice(s![dy..dy + rect.height, dx..dx + rect.width, ..]).to_owned())
+                })
+                .collect()
+        }
+        None => {
+            let full = apply(image.view());
+            rects.iter().map(|r| (*r, full.slice(s![r.y..r.y + r.height, r.x..r.x + r.width, ..]).to_owned())).collect()
+        }
+    };
+    for (rect, patch) in patches {
+        image.slice_mut(s![rect.y..rect.y + rect.height, rect.x..rect.x + rect.width, ..]).assign(&patch);
+    }
+    Ok(())
+}
+
+/// Apply a pipeline to rectangles of a u8 image in place.
+///
+/// # Arguments
+/// * `image` - Image (H, W, C), modified inside `rects` only
+/// * `rects` - Dirty regions; parts outside the image are ignored
+/// * `pipeline` - Steps applied in order (no size-changing steps)
+pub fn apply_in_rects_u8(image: ArrayViewMut3<u8>, rects: &[Rect], pipeline: &[FilterSpec]) -> Result<(), BatchError> {
+    apply_in_rects(image, rects, pipeline, |patch| apply_pipeline_u8(patch, pipeline))
+}
+
+/// Apply a pipeline to rectangles of an f32 image in place.
+///
+/// Same as apply_in_rects_u8 but for f32 images.
+pub fn apply_in_rects_f32(image: ArrayViewMut3<f32>, rects: &[Rect], pipeline: &[FilterSpec]) -> Result<(), BatchError> {
+    apply_in_rects(image, rects, pipeline, |patch| apply_pipeline_f32(patch, pipeline))
+}
+
+/// Apply a filter by name inside one rectangle of a u8 image.
+///
+/// # Arguments
+/// * `image` - Image (H, W, C), modified inside `rect` only
+/// * `rect` - Dirty region
+/// * `name` - Canonical filter name (see [`super::FILTERS`])
+/// * `params_json` - JSON object of parameters (empty = defaults)
+pub fn apply_in_rect_u8(image: ArrayViewMut3<u8>, rect: Rect, name: &str, params_json: &str) -> Result<(), BatchError> {
+    apply_in_rects_u8(image, &[rect], &[FilterSpec::from_params_json(name, params_json)?])
+}
+
+/// Apply a filter by name inside one rectangle of an f32 image.
+///
+/// Same as apply_in_rect_u8 but for f32 images.
+pub fn apply_in_rect_f32(image: ArrayViewMut3<f32>, rect: Rect, name: &str, params_json: &str) -> Result<(), BatchError> {
+    apply_in_rects_f32(image, &[rect], &[FilterSpec::from_params_json(name, params_json)?])
+}
+
+/// Margin in pixels a filter needs around a dirty region.
+///
+/// # Returns
+/// `None` for unknown filters and filters that need the whole image
+pub fn filter_margin(name: &str, params_json: &str) -> Option<usize> {
+    FilterSpec::from_params_json(name, params_json).ok()?.margin()
+}
+
+/// Rectangles from a flat `[x, y, width, height, ...]` list.
+pub fn rects_from_flat(values: &[u32]) -> Result<Vec<Rect>, BatchError> {
+    if !values.len().is_multiple_of(4) {
+        return Err(BatchError::InvalidArgument(format!(
+            "rect list needs 4 values per rect, got {}",
+            values.len()
+        )));
+    }
+    Ok(values
+        .chunks_exact(4)
+        .map(|r| Rect::new(r[0] as usize, r[1] as usize, r[2] as usize, r[3] as usize))
+        .collect())
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Accept one `(x, y, w, h)` tuple or a list of them.
+#[cfg(feature = "python")]
+fn extract_rects(rects: &Bound<'_, PyAny>) -> PyResult<Vec<Rect>> {
+    let tuples = match rects.extract::<(usize, usize, usize, usize)>() {
+        Ok(rect) => vec![rect],
+        Err(_) => rects.extract::<Vec<(usize, usize, usize, usize)>>()?,
+    };
+    Ok(tuples.into_iter().map(|(x, y, w, h)| Rect::new(x, y, w, h)).collect())
+}
+
+/// Re-filter dirty regions of a uint8 image in place.
+///
+/// Only the regions plus the filter's kernel margin are recomputed, so a
+/// brush preview on a large canvas costs as much as the brush area.
+///
+/// # Arguments
+/// * `image` - Writable C-contiguous array (H, W, C), updated in place
+/// * `rect` - `(x, y, width, height)` or a list of such tuples
+/// * `name` - Filter name from `filter_registry()`
+/// * `params_json` - JSON object of parameters (empty = defaults)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_in_rect", signature = (image, rect, name, params_json="{}"))]
+pub fn apply_in_rect_py(
+    mut image: PyReadwriteArray3<'_, u8>,
+    rect: &Bound<'_, PyAny>,
+    name: &str,
+    params_json: &str,
+) -> PyResult<()> {
+    let spec = FilterSpec::from_params_json(name, params_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    apply_in_rects_u8(image.as_array_mut(), &extract_rects(rect)?, &[spec])
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Re-filter dirty regions of a float32 image in place.
+///
+/// Same as apply_in_rect but for f32 images.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_in_rect_f32", signature = (image, rect, name, params_json="{}"))]
+pub fn apply_in_rect_f32_py(
+    mut image: PyReadwriteArray3<'_, f32>,
+    rect: &Bound<'_, PyAny>,
+    name: &str,
+    params_json: &str,
+) -> PyResult<()> {
+    let spec = FilterSpec::from_params_json(name, params_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    apply_in_rects_f32(image.as_array_mut(), &extract_rects(rect)?, &[spec])
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::parse_pipeline;
+
+    fn noise_image() -> Array3<u8> {
+        Array3::from_shape_fn((40, 50, 4), |(y, x, c)| ((x * 37 + y * 91 + c * 53) % 251) as u8)
+    }
+
+    #[test]
+    fn test_matches_full_image() {
+        let image = noise_image();
+        let rects = [Rect::new(5, 3, 10, 8), Rect::new(12, 6, 20, 30), Rect::new(45, 35, 20, 20)];
+        for text in ["blur 2.5", "sharpen 0.8 | median 2", "dilate 1.5 | box_blur 3", "brightness 0.2", "equalize"] {
+            let pipeline = parse_pipeline(text).unwrap();
+            let full = apply_pipeline_u8(image.view(), &pipeline);
+            let mut partial = image.clone();
+            apply_in_rects_u8(partial.view_mut(), &rects, &pipeline).unwrap();
+            for ((y, x, c), &value) in partial.indexed_iter() {
+                let inside = rects.iter().any(|r| (r.x..r.x + r.width).contains(&x) && (r.y..r.y + r.height).contains(&y));
+                let expected = if inside { full[[y, x, c]] } else { image[[y, x, c]] };
+                assert_eq!(value, expected, "{} at {:?}", text, (y, x, c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rect_helpers_and_errors() {
+        assert_eq!(Rect::new(40, 30, 20, 20).clip(50, 40), Some(Rect::new(40, 30, 10, 10)));
+        assert_eq!(Rect::new(60, 0, 5, 5).clip(50, 40), None);
+        assert_eq!(Rect::new(2, 3, 4, 4).expand(5, 50, 40), Rect::new(0, 0, 11, 12));
+        assert_eq!(filter_margin("blur", r#"{"sigma": 2}"#), Some(6));
+        assert_eq!(filter_margin("auto_levels", ""), None);
+
+        let mut image = noise_image();
+        let thumbnail = parse_pipeline("thumbnail 8").unwrap();
+        assert!(matches!(
+            apply_in_rects_u8(image.view_mut(), &[Rect::new(0, 0, 4, 4)], &thumbnail),
+            Err(BatchError::InvalidArgument(_))
+        ));
+        assert!(rects_from_flat(&[1, 2, 3]).is_err());
+        apply_in_rect_u8(image.view_mut(), Rect::new(0, 0, 0, 10), "invert", "").unwrap();
+        assert_eq!(image, noise_image());
+    }
+}
@@ -0,0 +1,438 @@
+//! Descriptor-driven filter registry.
+//!
+//! Lists every [`FilterSpec`] filter with its parameter schema, so frontends
+//! can build filter dialogs and servers can run saved filter steps by name:
+//!
+//! ```text
+//! let json = registry_json();   // [{"name": "brightness", "params": [...]}, ...]
+//! let out = apply_filter_u8("brightness", image, r#"{"amount": 0.2}"#)?;
+//! ```
+//!
+//! Parameter names, defaults and units are those of the pipeline syntax in
+//! [`spec`](super::spec); the JSON parameters are validated against the
+//! schema (type and range) before the step is built.
+
+use std::fmt::Write as _;
+
+use ndarray::{Array3, ArrayView3};
+
+use super::json::{json_string, JsonValue};
+use super::{BatchError, FilterSpec};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Value type of a filter parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Float,
+    Int,
+    Bool,
+}
+
+impl ParamKind {
+    /// Lowercase type name ("float", "int", "bool").
+    pub fn name(self) -> &'static str {
+        match self {
+            ParamKind::Float => "float",
+            ParamKind::Int => "int",
+            ParamKind::Bool => "bool",
+        }
+    }
+}
+
+/// Schema of one filter parameter.
+///
+/// Bool parameters use 0.0 / 1.0 for `min`, `max` and `default`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSchema {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub min: f64,
+    pub max: f64,
+    pub default: f64,
+}
+
+/// Name, description and parameters of one filter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterDescriptor {
+    /// Canonical name as accepted by [`FilterSpec::parse`]
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Parameters in positional order
+    pub params: &'static [ParamSchema],
+}
+
+const fn float(name: &'static str, min: f64, max: f64, default: f64) -> ParamSchema {
+    ParamSchema { name, kind: ParamKind::Float, min, max, default }
+}
+
+const fn int(name: &'static str, min: f64, max: f64, default: f64) -> ParamSchema {
+    ParamSchema { name, kind: ParamKind::Int, min, max, default }
+}
+
+const fn boolean(name: &'static str, default: bool) -> ParamSchema {
+    ParamSchema { name, kind: ParamKind::Bool, min: 0.0, max: 1.0, default: if default { 1.0 } else { 0.0 } }
+}
+
+const fn filter(name: &'static str, description: &'static str, params: &'static [ParamSchema]) -> FilterDescriptor {
+    FilterDescriptor { name, description, params }
+}
+
+/// Every filter of [`FilterSpec`], in variant order.
+pub static FILTERS: &[FilterDescriptor] = &[
+    filter("brightness", "Shift brightness (-1 black to 1 white)", &[float("amount", -1.0, 1.0, 0.0)]),
+    filter("contrast", "Scale contrast around mid-gray", &[float("amount", -1.0, 1.0, 0.0)]),
+    filter("saturation", "Scale color saturation", &[float("amount", -1.0, 1.0, 0.0)]),
+    filter("gamma", "Gamma correction (< 1 brightens)", &[float("gamma", 0.1, 10.0, 1.0)]),
+    filter(
+        "exposure",
+        "Exposure in stops with black point offset and gamma",
+        &[float("exposure", -10.0, 10.0, 0.0), float("offset", -1.0, 1.0, 0.0), float("gamma", 0.1, 10.0, 1.0)],
+    ),
+    filter("invert", "Invert colors, keeping alpha", &[]),
+    filter("equalize", "Histogram equalization", &[]),
+    filter("grayscale", "Convert to grayscale (BT.709), keeping the channel count", &[]),
+    filter("hue_shift", "Rotate hue in degrees", &[float("degrees", 0.0, 360.0, 0.0)]),
+    filter(
+        "vibrance",
+        "Saturate muted colors more than saturated ones, optionally sparing skin tones",
+        &[float("amount", -1.0, 1.0, 0.0), float("protect_skin", 0.0, 1.0, 0.0)],
+    ),
+    filter("sepia", "Sepia tone", &[float("intensity", 0.0, 1.0, 1.0)]),
+    filter("temperature", "Shift color temperature (-1 cool to 1 warm)", &[float("amount", -1.0, 1.0, 0.0)]),
+    filter("posterize", "Reduce levels per channel", &[int("levels", 2.0, 255.0, 4.0)]),
+    filter("solarize", "Invert values above a threshold (0-255)", &[int("threshold", 0.0, 255.0, 128.0)]),
+    filter("threshold", "Black and white at a threshold (0-255)", &[int("threshold", 0.0, 255.0, 128.0)]),
+    filter("pixelate", "Average square blocks", &[int("block_size", 1.0, 256.0, 8.0)]),
+    filter("vignette", "Darken towards the edges", &[float("amount", 0.0, 1.0, 0.5)]),
+    filter("sharpen", "3x3 sharpen", &[float("amount", 0.0, 10.0, 1.0)]),
+    filter("high_pass", "Keep detail above a blur radius", &[float("radius", 0.1, 100.0, 3.0)]),
+    filter("blur", "Gaussian blur", &[float("sigma", 0.0, 100.0, 1.0)]),
+    filter("box_blur", "Box blur", &[int("radius", 0.0, 100.0, 1.0)]),
+    filter("median", "Median filter", &[int("radius", 1.0, 21.0, 1.0)]),
+    filter("denoise", "Edge-preserving noise reduction", &[float("strength", 0.0, 1.0, 0.5)]),
+    filter("dilate", "Grow bright regions", &[float("radius", 0.0, 50.0, 1.0)]),
+    filter("erode", "Shrink bright regions", &[float("radius", 0.0, 50.0, 1.0)]),
+    filter("auto_levels", "Stretch levels to the full range", &[float("clip_percent", 0.0, 0.5, 0.0)]),
+    filter(
+        "thumbnail",
+        "Lanczos downscale to fit a box (never enlarges)",
+        &[int("max_width", 1.0, 16384.0, 256.0), int("max_height", 1.0, 16384.0, 256.0), boolean("srgb_aware", true)],
+    ),
+];
+
+/// Descriptor of a filter by canonical name (case-insensitive).
+pub fn find_filter(name: &str) -> Option<&'static FilterDescriptor> {
+    FILTERS.iter().find(|descriptor| descriptor.name.eq_ignore_ascii_case(name))
+}
+
+impl ParamSchema {
+    /// Serialize as `{"name", "type", "min", "max", "default"}`.
+    pub fn to_json(&self) -> String {
+        let number = |v: f64| match self.kind {
+            ParamKind::Bool => (v != 0.0).to_string(),
+            _ => v.to_string(),
+        };
+        format!(
+            "{{\"name\":{},\"type\":\"{}\",\"min\":{},\"max\":{},\"default\":{}}}",
+            json_string(self.name),
+            self.kind.name(),
+            number(self.min),
+            number(self.max),
+            number(self.default)
+        )
+    }
+
+    /// Check a JSON value against the schema and format it for the pipeline syntax.
+    fn format_value(&self, filter: &str, value: &JsonValue) -> Result<String, BatchError> {
+        let invalid = |reason: String| {
+            BatchError::InvalidArgument(format!("'{}' parameter '{}' {}", filter, self.name, reason))
+        };
+        match (self.kind, value) {
+            (ParamKind::Bool, JsonValue::Bool(b)) => Ok(b.to_string()),
+            (ParamKind::Float | ParamKind::Int, JsonValue::Number(n)) => {
+                if self.kind == ParamKind::Int && n.fract() != 0.0 {
+                    return Err(invalid(format!("must be an integer, got {}", n)));
+                }
+                if !(self.min..=self.max).contains(n) {
+                    return Err(invalid(format!("must be in {}..={}, got {}", self.min, self.max, n)));
+                }
+                Ok(n.to_string())
+            }
+            (kind, other) => Err(invalid(format!("expects {}, got {}", kind.name(), other.to_json()))),
+        }
+    }
+}
+
+impl FilterDescriptor {
+    /// Serialize as `{"name", "description", "params": [...]}`.
+    pub fn to_json(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(ParamSchema::to_json).collect();
+        format!(
+            "{{\"name\":{},\"description\":{},\"params\":[{}]}}",
+            json_string(self.name),
+            json_string(self.description),
+            params.join(",")
+        )
+    }
+}
+
+/// All filter descriptors as a JSON array.
+pub fn registry_json() -> String {
+    let mut out = String::from("[");
+    for (i, descriptor) in FILTERS.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write!(out, "{}", descriptor.to_json()).unwrap();
+    }
+    out.push(']');
+    out
+}
+
+impl FilterSpec {
+    /// Build a step from a filter name and a JSON parameter object.
+    ///
+    /// Missing parameters take their defaults; unknown names, wrong types and
+    /// out-of-range values are rejected.
+    pub fn from_params(name: &str, params: &JsonValue) -> Result<Self, BatchError> {
+        let descriptor = find_filter(name).ok_or_else(|| BatchError::UnknownFilter(name.to_string()))?;
+        let members = match params {
+            JsonValue::Object(members) => members.as_slice(),
+            JsonValue::Null => &[],
+            other => {
+                return Err(BatchError::InvalidArgument(format!(
+                    "'{}' parameters must be an object, got {}",
+                    name,
+                    other.to_json()
+                )))
+            }
+        };
+        let mut step = descriptor.name.to_string();
+        for (key, value) in members {
+            let schema = descriptor.params.iter().find(|p| p.name == key).ok_or_else(|| {
+                BatchError::InvalidArgument(format!("'{}' has no parameter '{}'", descriptor.name, key))
+            })?;
+            write!(step, " {}={}", key, schema.format_value(descriptor.name, value)?).unwrap();
+        }
+        FilterSpec::parse(&step)
+    }
+
+    /// Same as from_params with the parameters given as JSON text.
+    pub fn from_params_json(name: &str, params_json: &str) -> Result<Self, BatchError> {
+        let params = if params_json.trim().is_empty() { JsonValue::Null } else { JsonValue::parse(params_json)? };
+        FilterSpec::from_params(name, &params)
+    }
+}
+
+/// f32 parameter as the f64 with the same shortest decimal form (0.2, not 0.2000000029...).
+fn shortest(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(value as f64)
+}
+
+impl FilterSpec {
+    /// Canonical name and parameter values in descriptor order.
+    fn name_and_values(&self) -> (&'static str, Vec<f64>) {
+        match *self {
+            FilterSpec::Brightness { amount } => ("brightness", vec![shortest(amount)]),
+            FilterSpec::Contrast { amount } => ("contrast", vec![shortest(amount)]),
+            FilterSpec::Saturation { amount } => ("saturation", vec![shortest(amount)]),
+            FilterSpec::Gamma { gamma } => ("gamma", vec![shortest(gamma)]),
+            FilterSpec::Exposure { exposure, offset, gamma } => {
+                ("exposure", vec![shortest(exposure), shortest(offset), shortest(gamma)])
+            }
+            FilterSpec::Invert => ("invert", vec![]),
+            FilterSpec::Equalize => ("equalize", vec![]),
+            FilterSpec::Grayscale => ("grayscale", vec![]),
+            FilterSpec::HueShift { degrees } => ("hue_shift", vec![shortest(degrees)]),
+            FilterSpec::Vibrance { amount, protect_skin } => ("vibrance", vec![shortest(amount), shortest(protect_skin)]),
+            FilterSpec::Sepia { intensity } => ("sepia", vec![shortest(intensity)]),
+            FilterSpec::Temperature { amount } => ("temperature", vec![shortest(amount)]),
+            FilterSpec::Posterize { levels } => ("posterize", vec![levels as f64]),
+            FilterSpec::Solarize { threshold } => ("solarize", vec![threshold as f64]),
+            FilterSpec::Threshold { threshold } => ("threshold", vec![threshold as f64]),
+            FilterSpec::Pixelate { block_size } => ("pixelate", vec![block_size as f64]),
+            FilterSpec::Vignette { amount } => ("vignette", vec![shortest(amount)]),
+            FilterSpec::Sharpen { amount } => ("sharpen", vec![shortest(amount)]),
+            FilterSpec::HighPass { radius } => ("high_pass", vec![shortest(radius)]),
+            FilterSpec::Blur { sigma } => ("blur", vec![shortest(sigma)]),
+            FilterSpec::BoxBlur { radius } => ("box_blur", vec![radius as f64]),
+            FilterSpec::Median { radius } => ("median", vec![radius as f64]),
+            FilterSpec::Denoise { strength } => ("denoise", vec![shortest(strength)]),
+            FilterSpec::Dilate { radius } => ("dilate", vec![shortest(radius)]),
+            FilterSpec::Erode { radius } => ("erode", vec![shortest(radius)]),
+            FilterSpec::AutoLevels { clip_percent } => ("auto_levels", vec![shortest(clip_percent)]),
+            FilterSpec::Thumbnail { max_width, max_height, srgb_aware } => {
+                ("thumbnail", vec![max_width as f64, max_height as f64, if srgb_aware { 1.0 } else { 0.0 }])
+            }
+        }
+    }
+
+    /// Canonical filter name (see [`FILTERS`]).
+    pub fn name(&self) -> &'static str {
+        self.name_and_values().0
+    }
+
+    /// Parameters as a JSON object; the inverse of [`FilterSpec::from_params`].
+    pub fn to_params(&self) -> JsonValue {
+        let (name, values) = self.name_and_values();
+        let descriptor = find_filter(name).expect("every filter has a descriptor");
+        let members = descriptor
+            .params
+            .iter()
+            .zip(values)
+            .map(|(schema, value)| {
+                let value = match schema.kind {
+                    ParamKind::Bool => JsonValue::Bool(value != 0.0),
+                    _ => JsonValue::Number(value),
+                };
+                (schema.name.to_string(), value)
+            })
+            .collect();
+        JsonValue::Object(members)
+    }
+}
+
+/// Apply a filter by name to a u8 image.
+///
+/// # Arguments
+/// * `name` - Canonical filter name (see [`FILTERS`])
+/// * `params_json` - JSON object of parameters, e.g. `{"amount": 0.2}` (empty = defaults)
+pub fn apply_filter_u8(name: &str, image: ArrayView3<u8>, params_json: &str) -> Result<Array3<u8>, BatchError> {
+    Ok(FilterSpec::from_params_json(name, params_json)?.apply_u8(image))
+}
+
+/// Apply a filter by name to an f32 image (0.0-1.0).
+///
+/// Same as apply_filter_u8 but for f32 images.
+pub fn apply_filter_f32(name: &str, image: ArrayView3<f32>, params_json: &str) -> Result<Array3<f32>, BatchError> {
+    Ok(FilterSpec::from_params_json(name, params_json)?.apply_f32(image))
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Describe every filter available to `apply_filter`.
+///
+/// # Returns
+/// JSON array of `{"name", "description", "params": [{"name", "type", "min", "max", "default"}]}`
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "filter_registry")]
+pub fn filter_registry_py() -> String {
+    registry_json()
+}
+
+/// Apply a filter to a uint8 image by name.
+///
+/// # Arguments
+/// * `image` - Image (H, W, C) with 1, 3 or 4 channels
+/// * `name` - Filter name from `filter_registry()`
+/// * `params_json` - JSON object of parameters (empty = defaults)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_filter", signature = (image, name, params_json="{}"))]
+pub fn apply_filter_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    name: &str,
+    params_json: &str,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let result = apply_filter_u8(name, image.as_array(), params_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Apply a filter to a float32 image by name.
+///
+/// Same as apply_filter but for f32 images.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_filter_f32", signature = (image, name, params_json="{}"))]
+pub fn apply_filter_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    name: &str,
+    params_json: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = apply_filter_f32(name, image.as_array(), params_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(result.into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_parser() {
+        // Spelling out every default must give the same step as omitting them
+        for descriptor in FILTERS {
+            let defaults: Vec<(String, JsonValue)> = descriptor
+                .params
+                .iter()
+                .map(|p| {
+                    let value = match p.kind {
+                        ParamKind::Bool => JsonValue::Bool(p.default != 0.0),
+                        _ => JsonValue::Number(p.default),
+                    };
+                    (p.name.to_string(), value)
+                })
+                .collect();
+            let explicit = FilterSpec::from_params(descriptor.name, &JsonValue::Object(defaults)).unwrap();
+            assert_eq!(explicit, FilterSpec::parse(descriptor.name).unwrap(), "{}", descriptor.name);
+            assert!(descriptor.params.iter().all(|p| p.min <= p.default && p.default <= p.max), "{}", descriptor.name);
+        }
+    }
+
+    #[test]
+    fn test_to_params_roundtrip() {
+        for descriptor in FILTERS {
+            let spec = FilterSpec::parse(descriptor.name).unwrap();
+            assert_eq!(spec.name(), descriptor.name);
+            assert_eq!(FilterSpec::from_params(spec.name(), &spec.to_params()).unwrap(), spec);
+        }
+        let spec = FilterSpec::Exposure { exposure: 0.3, offset: -0.1, gamma: 2.2 };
+        assert_eq!(spec.to_params().to_json(), r#"{"exposure":0.3,"offset":-0.1,"gamma":2.2}"#);
+        assert_eq!(FilterSpec::from_params("exposure", &spec.to_params()).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_apply_by_name() {
+        let image = Array3::<u8>::from_elem((4, 4, 3), 100);
+        let result = apply_filter_u8("Brightness", image.view(), r#"{"amount": 0.2}"#).unwrap();
+        assert_eq!(result, FilterSpec::Brightness { amount: 0.2 }.apply_u8(image.view()));
+        let resized = apply_filter_f32("thumbnail", image.mapv(|v| v as f32 / 255.0).view(), r#"{"max_width": 2}"#);
+        assert_eq!(resized.unwrap().dim(), (2, 2, 3));
+        assert_eq!(apply_filter_u8("invert", image.view(), "").unwrap()[[0, 0, 0]], 155);
+    }
+
+    #[test]
+    fn test_param_validation() {
+        let check = |name: &str, params: &str| FilterSpec::from_params_json(name, params);
+        assert_eq!(check("frobnicate", "{}"), Err(BatchError::UnknownFilter("frobnicate".into())));
+        assert!(matches!(check("brightness", r#"{"amount": 2}"#), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(check("brightness", r#"{"sigma": 1}"#), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(check("posterize", r#"{"levels": 4.5}"#), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(check("thumbnail", r#"{"srgb_aware": 1}"#), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(check("blur", "[1]"), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(check("blur", "{sigma: 1}"), Err(BatchError::InvalidJson(_))));
+    }
+
+    #[test]
+    fn test_registry_json() {
+        let json = JsonValue::parse(&registry_json()).unwrap();
+        let JsonValue::Array(filters) = json else { panic!("registry is not an array") };
+        assert_eq!(filters.len(), FILTERS.len());
+        let thumbnail = filters.iter().find(|f| f.get("name") == Some(&JsonValue::String("thumbnail".into()))).unwrap();
+        let JsonValue::Array(params) = thumbnail.get("params").unwrap() else { panic!("params is not an array") };
+        assert_eq!(params[2].get("type"), Some(&JsonValue::String("bool".into())));
+        assert_eq!(params[2].get("default"), Some(&JsonValue::Bool(true)));
+    }
+}
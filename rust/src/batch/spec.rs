@@ -0,0 +1,373 @@
+//! Filter specs: a parsed, thread-safe description of one filter step.
+//!
+//! Specs use the compact string syntax of the Python filter pipeline:
+//!
+//! ```text
+//! brightness 0.2 | sharpen amount=0.5 | thumbnail 256 256
+//! blur(1.5); grayscale
+//! ```
+//!
+//! Steps are separated by `|` or `;`. Each step is a filter name followed by
+//! positional and/or `key=value` arguments, either space separated or in
+//! parentheses. Parameter names and ranges are those of the Rust kernels
+//! (e.g. `brightness` takes `amount` from -1.0 to 1.0).
+
+use std::fmt;
+
+use ndarray::{Array3, ArrayView3};
+
+use crate::profiling;
+use crate::filters::{
+    blur_wasm, color_adjust, color_science, grayscale, levels_curves, morphology, noise, resample, sharpen, stylize,
+};
+
+/// Error raised while parsing a filter spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchError {
+    /// Filter name is not known
+    UnknownFilter(String),
+    /// Argument is missing a value, malformed, or not accepted by the filter
+    InvalidArgument(String),
+    /// Spec contains no filter steps
+    EmptyPipeline,
+    /// Parameters or recipe are not valid JSON
+    InvalidJson(String),
+    /// Recipe is not valid CBOR
+    InvalidCbor(String),
+    /// Recipe structure or one of its steps is invalid
+    InvalidRecipe(String),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::UnknownFilter(name) => write!(f, "unknown filter '{}'", name),
+            BatchError::InvalidArgument(msg) => write!(f, "invalid argument: {}", msg),
+            BatchError::EmptyPipeline => write!(f, "filter spec contains no steps"),
+            BatchError::InvalidJson(msg) => write!(f, "invalid JSON: {}", msg),
+            BatchError::InvalidCbor(msg) => write!(f, "invalid CBOR: {}", msg),
+            BatchError::InvalidRecipe(msg) => write!(f, "invalid recipe: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/// One filter step with its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterSpec {
+    Brightness { amount: f32 },
+    Contrast { amount: f32 },
+    Saturation { amount: f32 },
+    Gamma { gamma: f32 },
+    Exposure { exposure: f32, offset: f32, gamma: f32 },
+    Invert,
+    Equalize,
+    Grayscale,
+    HueShift { degrees: f32 },
+    Vibrance { amount: f32, protect_skin: f32 },
+    Sepia { intensity: f32 },
+    Temperature { amount: f32 },
+    Posterize { levels: u8 },
+    /// Threshold on the 0-255 scale (scaled to 0.0-1.0 for f32 images)
+    Solarize { threshold: u8 },
+    /// Threshold on the 0-255 scale (scaled to 0.0-1.0 for f32 images)
+    Threshold { threshold: u8 },
+    Pixelate { block_size: u32 },
+    Vignette { amount: f32 },
+    Sharpen { amount: f32 },
+    HighPass { radius: f32 },
+    Blur { sigma: f32 },
+    BoxBlur { radius: u32 },
+    Median { radius: u32 },
+    Denoise { strength: f32 },
+    Dilate { radius: f32 },
+    Erode { radius: f32 },
+    AutoLevels { clip_percent: f32 },
+    Thumbnail { max_width: usize, max_height: usize, srgb_aware: bool },
+}
+
+/// Positional and named arguments of one step.
+struct Args<'a> {
+    filter: &'a str,
+    positional: Vec<&'a str>,
+    named: Vec<(&'a str, &'a str)>,
+    names: &'static [&'static str],
+}
+
+impl<'a> Args<'a> {
+    fn new(filter: &'a str, tokens: &[&'a str], names: &'static [&'static str]) -> Result<Self, BatchError> {
+        let mut positional = Vec::new();
+        let mut named = Vec::new();
+        for token in tokens {
+            match token.split_once('=') {
+                Some((key, value)) => {
+                    if !names.contains(&key) {
+                        return Err(BatchError::InvalidArgument(format!("'{}' has no parameter '{}'", filter, key)));
+                    }
+                    named.push((key, value));
+                }
+                None => positional.push(*token),
+            }
+        }
+        if positional.len() > names.len() {
+            return Err(BatchError::InvalidArgument(format!(
+                "'{}' takes at most {} arguments, got {}",
+                filter,
+                names.len(),
+                positional.len()
+            )));
+        }
+        Ok(Args { filter, positional, named, names })
+    }
+
+    /// Value of parameter `index` (positional or by name), or `default`.
+    fn get<T: std::str::FromStr>(&self, index: usize, default: T) -> Result<T, BatchError> {
+        let name = self.names[index];
+        let raw = self
+            .named
+            .iter()
+            .rev()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+            .or_else(|| self.positional.get(index).copied());
+        match raw {
+            None => Ok(default),
+            Some(text) => text.parse().map_err(|_| {
+                BatchError::InvalidArgument(format!("'{}' parameter '{}' got '{}'", self.filter, name, text))
+            }),
+        }
+    }
+
+    fn get_bool(&self, index: usize, default: bool) -> Result<bool, BatchError> {
+        let text: String = self.get(index, default.to_string())?;
+        match text.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Ok(true),
+            "false" | "0" | "no" => Ok(false),
+            _ => Err(BatchError::InvalidArgument(format!(
+                "'{}' parameter '{}' got '{}'",
+                self.filter, self.names[index], text
+            ))),
+        }
+    }
+}
+
+impl FilterSpec {
+    /// Parse a single step such as `"brightness 0.2"` or `"blur(sigma=2)"`.
+    pub fn parse(step: &str) -> Result<Self, BatchError> {
+        let normalized: String = step
+            .chars()
+            .map(|c| if matches!(c, '(' | ')' | ',') { ' ' } else { c })
+            .collect();
+        let mut tokens = normalized.split_whitespace();
+        let name = tokens.next().ok_or(BatchError::EmptyPipeline)?.to_ascii_lowercase();
+        let tokens: Vec<&str> = tokens.collect();
+        let args = |names: &'static [&'static str]| Args::new(step.trim(), &tokens, names);
+
+        let spec = match name.as_str() {
+            "brightness" => FilterSpec::Brightness { amount: args(&["amount"])?.get(0, 0.0)? },
+            "contrast" => FilterSpec::Contrast { amount: args(&["amount"])?.get(0, 0.0)? },
+            "saturation" => FilterSpec::Saturation { amount: args(&["amount"])?.get(0, 0.0)? },
+            "gamma" => FilterSpec::Gamma { gamma: args(&["gamma"])?.get(0, 1.0)? },
+            "exposure" => {
+                let a = args(&["exposure", "offset", "gamma"])?;
+                FilterSpec::Exposure { exposure: a.get(0, 0.0)?, offset: a.get(1, 0.0)?, gamma: a.get(2, 1.0)? }
+            }
+            "invert" => {
+                args(&[])?;
+                FilterSpec::Invert
+            }
+            "equalize" | "equalize_histogram" => {
+                args(&[])?;
+                FilterSpec::Equalize
+            }
+            "grayscale" | "gray" | "grey" => {
+                args(&[])?;
+                FilterSpec::Grayscale
+            }
+            "hue_shift" | "hue" => FilterSpec::HueShift { degrees: args(&["degrees"])?.get(0, 0.0)? },
+            "vibrance" => {
+                let a = args(&["amount", "protect_skin"])?;
+                FilterSpec::Vibrance { amount: a.get(0, 0.0)?, protect_skin: a.get(1, 0.0)? }
+            }
+            "sepia" => FilterSpec::Sepia { intensity: args(&["intensity"])?.get(0, 1.0)? },
+            "temperature" => FilterSpec::Temperature { amount: args(&["amount"])?.get(0, 0.0)? },
+            "posterize" => FilterSpec::Posterize { levels: args(&["levels"])?.get(0, 4)? },
+            "solarize" => FilterSpec::Solarize { threshold: args(&["threshold"])?.get(0, 128)? },
+            "threshold" => FilterSpec::Threshold { threshold: args(&["threshold"])?.get(0, 128)? },
+            "pixelate" => FilterSpec::Pixelate { block_size: args(&["block_size"])?.get(0, 8)? },
+            "vignette" => FilterSpec::Vignette { amount: args(&["amount"])?.get(0, 0.5)? },
+            "sharpen" => FilterSpec::Sharpen { amount: args(&["amount"])?.get(0, 1.0)? },
+            "high_pass" => FilterSpec::HighPass { radius: args(&["radius"])?.get(0, 3.0)? },
+            "blur" | "gaussian" | "gaussian_blur" => FilterSpec::Blur { sigma: args(&["sigma"])?.get(0, 1.0)? },
+            "box_blur" => FilterSpec::BoxBlur { radius: args(&["radius"])?.get(0, 1)? },
+            "median" => FilterSpec::Median { radius: args(&["radius"])?.get(0, 1)? },
+            "denoise" => FilterSpec::Denoise { strength: args(&["strength"])?.get(0, 0.5)? },
+            "dilate" => FilterSpec::Dilate { radius: args(&["radius"])?.get(0, 1.0)? },
+            "erode" => FilterSpec::Erode { radius: args(&["radius"])?.get(0, 1.0)? },
+            "auto_levels" => FilterSpec::AutoLevels { clip_percent: args(&["clip_percent"])?.get(0, 0.0)? },
+            "thumbnail" => {
+                let a = args(&["max_width", "max_height", "srgb_aware"])?;
+                let max_width = a.get(0, 256)?;
+                FilterSpec::Thumbnail { max_width, max_height: a.get(1, max_width)?, srgb_aware: a.get_bool(2, true)? }
+            }
+            _ => return Err(BatchError::UnknownFilter(name)),
+        };
+        Ok(spec)
+    }
+
+    /// Apply this step to a u8 image.
+    pub fn apply_u8(&self, image: ArrayView3<u8>) -> Array3<u8> {
+        profiling::record(|| self.name(), || self.apply_u8_unrecorded(image))
+    }
+
+    fn apply_u8_unrecorded(&self, image: ArrayView3<u8>) -> Array3<u8> {
+        match *self {
+            FilterSpec::Brightness { amount } => color_adjust::brightness_u8(image, amount),
+            FilterSpec::Contrast { amount } => color_adjust::contrast_u8(image, amount),
+            FilterSpec::Saturation { amount } => color_adjust::saturation_u8(image, amount),
+            FilterSpec::Gamma { gamma } => color_adjust::gamma_u8(image, gamma),
+            FilterSpec::Exposure { exposure, offset, gamma } => color_adjust::exposure_u8(image, exposure, offset, gamma),
+            FilterSpec::Invert => color_adjust::invert_u8(image),
+            FilterSpec::Equalize => color_adjust::equalize_histogram_u8(image),
+            FilterSpec::Grayscale => grayscale::grayscale_u8(image),
+            FilterSpec::HueShift { degrees } => color_science::hue_shift_u8(image, degrees),
+            FilterSpec::Vibrance { amount, protect_skin } => color_science::vibrance_protected_u8(image, amount, protect_skin),
+            FilterSpec::Sepia { intensity } => color_science::sepia_u8(image, intensity),
+            FilterSpec::Temperature { amount } => color_science::temperature_u8(image, amount),
+            FilterSpec::Posterize { levels } => stylize::posterize_u8(image, levels),
+            FilterSpec::Solarize { threshold } => stylize::solarize_u8(image, threshold),
+            FilterSpec::Threshold { threshold } => stylize::threshold_u8(image, threshold),
+            FilterSpec::Pixelate { block_size } => stylize::pixelate_u8(image, block_size),
+            FilterSpec::Vignette { amount } => stylize::vignette_u8(image, amount),
+            FilterSpec::Sharpen { amount } => sharpen::sharpen_u8(image, amount),
+            FilterSpec::HighPass { radius } => sharpen::high_pass_u8(image, radius),
+            FilterSpec::Blur { sigma } => blur_wasm::gaussian_blur_wasm_u8(image, sigma),
+            FilterSpec::BoxBlur { radius } => blur_wasm::box_blur_wasm_u8(image, radius),
+            FilterSpec::Median { radius } => noise::median_u8(image, radius),
+            FilterSpec::Denoise { strength } => noise::denoise_u8(image, strength),
+            FilterSpec::Dilate { radius } => morphology::dilate_u8(image, radius),
+            FilterSpec::Erode { radius } => morphology::erode_u8(image, radius),
+            FilterSpec::AutoLevels { clip_percent } => levels_curves::auto_levels_u8(image, clip_percent),
+            FilterSpec::Thumbnail { max_width, max_height, srgb_aware } => {
+                resample::thumbnail_u8(image, max_width, max_height, srgb_aware)
+            }
+        }
+    }
+
+    /// Apply this step to an f32 image (0.0-1.0).
+    pub fn apply_f32(&self, image: ArrayView3<f32>) -> Array3<f32> {
+        profiling::record(|| self.name(), || self.apply_f32_unrecorded(image))
+    }
+
+    fn apply_f32_unrecorded(&self, image: ArrayView3<f32>) -> Array3<f32> {
+        match *self {
+            FilterSpec::Brightness { amount } => color_adjust::brightness_f32(image, amount),
+            FilterSpec::Contrast { amount } => color_adjust::contrast_f32(image, amount),
+            FilterSpec::Saturation { amount } => color_adjust::saturation_f32(image, amount),
+            FilterSpec::Gamma { gamma } => color_adjust::gamma_f32(image, gamma),
+            FilterSpec::Exposure { exposure, offset, gamma } => color_adjust::exposure_f32(image, exposure, offset, gamma),
+            FilterSpec::Invert => color_adjust::invert_f32(image),
+            FilterSpec::Equalize => color_adjust::equalize_histogram_f32(image),
+            FilterSpec::Grayscale => grayscale::grayscale_f32(image),
+            FilterSpec::HueShift { degrees } => color_science::hue_shift_f32(image, degrees),
+            FilterSpec::Vibrance { amount, protect_skin } => color_science::vibrance_protected_f32(image, amount, protect_skin),
+            FilterSpec::Sepia { intensity } => color_science::sepia_f32(image, intensity),
+            FilterSpec::Temperature { amount } => color_science::temperature_f32(image, amount),
+            FilterSpec::Posterize { levels } => stylize::posterize_f32(image, levels),
+            FilterSpec::Solarize { threshold } => stylize::solarize_f32(image, threshold as f32 / 255.0),
+            FilterSpec::Threshold { threshold } => stylize::threshold_f32(image, threshold as f32 / 255.0),
+            FilterSpec::Pixelate { block_size } => stylize::pixelate_f32(image, block_size),
+            FilterSpec::Vignette { amount } => stylize::vignette_f32(image, amount),
+            FilterSpec::Sharpen { amount } => sharpen::sharpen_f32(image, amount),
+            FilterSpec::HighPass { radius } => sharpen::high_pass_f32(image, radius),
+            FilterSpec::Blur { sigma } => blur_wasm::gaussian_blur_wasm_f32(image, sigma),
+            FilterSpec::BoxBlur { radius } => blur_wasm::box_blur_wasm_f32(image, radius),
+            FilterSpec::Median { radius } => noise::median_f32(image, radius),
+            FilterSpec::Denoise { strength } => noise::denoise_f32(image, strength),
+            FilterSpec::Dilate { radius } => morphology::dilate_f32(image, radius),
+            FilterSpec::Erode { radius } => morphology::erode_f32(image, radius),
+            FilterSpec::AutoLevels { clip_percent } => levels_curves::auto_levels_f32(image, clip_percent),
+            FilterSpec::Thumbnail { max_width, max_height, srgb_aware } => {
+                resample::thumbnail_f32(image, max_width, max_height, srgb_aware)
+            }
+        }
+    }
+
+    /// This step with its size-dependent parameters multiplied by `factor`.
+    ///
+    /// Used to render a downsampled proxy that looks like the full-size
+    /// result: blur radii, morphology radii, pixelate blocks and thumbnail
+    /// bounds shrink with the image. Other parameters are unchanged, and
+    /// sharpen's fixed 3x3 kernel cannot follow the scale.
+    pub fn scaled(&self, factor: f32) -> FilterSpec {
+        let scale_px = |v: u32| (v as f32 * factor).round() as u32;
+        match *self {
+            FilterSpec::Pixelate { block_size } => FilterSpec::Pixelate { block_size: scale_px(block_size).max(1) },
+            FilterSpec::HighPass { radius } => FilterSpec::HighPass { radius: radius * factor },
+            FilterSpec::Blur { sigma } => FilterSpec::Blur { sigma: sigma * factor },
+            FilterSpec::BoxBlur { radius } => FilterSpec::BoxBlur { radius: scale_px(radius) },
+            FilterSpec::Median { radius } => FilterSpec::Median { radius: scale_px(radius) },
+            FilterSpec::Dilate { radius } => FilterSpec::Dilate { radius: radius * factor },
+            FilterSpec::Erode { radius } => FilterSpec::Erode { radius: radius * factor },
+            FilterSpec::Thumbnail { max_width, max_height, srgb_aware } => FilterSpec::Thumbnail {
+                max_width: ((max_width as f32 * factor).round() as usize).max(1),
+                max_height: ((max_height as f32 * factor).round() as usize).max(1),
+                srgb_aware,
+            },
+            ref other => other.clone(),
+        }
+    }
+}
+
+/// Parse a pipeline of steps separated by `|` or `;`.
+///
+/// # Returns
+/// Steps in application order (at least one)
+pub fn parse_pipeline(text: &str) -> Result<Vec<FilterSpec>, BatchError> {
+    let steps = text
+        .split(['|', ';'])
+        .filter(|step| !step.trim().is_empty())
+        .map(FilterSpec::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if steps.is_empty() {
+        return Err(BatchError::EmptyPipeline);
+    }
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_syntax_variants() {
+        let steps = parse_pipeline("brightness 0.2 | blur(sigma=1.5); gray").unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                FilterSpec::Brightness { amount: 0.2 },
+                FilterSpec::Blur { sigma: 1.5 },
+                FilterSpec::Grayscale,
+            ]
+        );
+        assert_eq!(
+            FilterSpec::parse("thumbnail 128 srgb_aware=false").unwrap(),
+            FilterSpec::Thumbnail { max_width: 128, max_height: 128, srgb_aware: false }
+        );
+        assert_eq!(FilterSpec::parse("exposure(1, gamma=2.2)").unwrap(), FilterSpec::Exposure {
+            exposure: 1.0,
+            offset: 0.0,
+            gamma: 2.2
+        });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_pipeline("frobnicate 3"), Err(BatchError::UnknownFilter("frobnicate".into())));
+        assert_eq!(parse_pipeline(" | ;"), Err(BatchError::EmptyPipeline));
+        assert!(matches!(parse_pipeline("posterize abc"), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(parse_pipeline("invert 1"), Err(BatchError::InvalidArgument(_))));
+        assert!(matches!(parse_pipeline("blur radius=2"), Err(BatchError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,347 @@
+//! Stable C ABI (`capi` feature).
+//!
+//! Lets C, C++, Swift or Kotlin (via JNI) applications call the same filter
+//! kernels as the Python and JS bindings. Images are passed as raw pointers
+//! to row-major (height, width, channels) buffers, u8 (0-255) or f32
+//! (0.0-1.0). Filters are addressed by name with JSON parameters, or as a
+//! pipeline string / recipe JSON, exactly like `apply_filter` and
+//! `batch_filter` in Python; `imagestag_filter_registry` lists them.
+//!
+//! Results are allocated by the library and must be released with
+//! [`imagestag_image_u8_free`] / [`imagestag_image_f32_free`] /
+//! [`imagestag_string_free`]. Every call returns an [`IsStatus`]; on failure
+//! [`imagestag_last_error`] describes the problem. Panics never unwind into
+//! the caller.
+//!
+//! The header `rust/include/imagestag.h` is generated with cbindgen:
+//!
+//! ```bash
+//! cd rust && cbindgen --config cbindgen.toml --output include/imagestag.h
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use ndarray::{Array3, ArrayView3};
+
+use crate::batch::{apply_filter_f32, apply_filter_u8, apply_pipeline_f32, apply_pipeline_u8, parse_pipeline_or_recipe, registry_json, BatchError};
+
+/// Version of this ABI; bumped on incompatible changes.
+const ABI_VERSION: u32 = 1;
+
+/// Result code of every C API call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsStatus {
+    Ok = 0,
+    /// Null pointer, zero size or invalid UTF-8
+    InvalidArgument = 1,
+    /// Filter name is not known
+    UnknownFilter = 2,
+    /// Parameters, pipeline or recipe could not be parsed
+    InvalidParameters = 3,
+    /// The filter panicked; the library state is unaffected
+    InternalError = 4,
+}
+
+/// Image owned by the library (u8).
+#[repr(C)]
+#[derive(Debug)]
+pub struct IsImageU8 {
+    pub data: *mut u8,
+    pub width: usize,
+    pub height: usize,
+    pub channels: usize,
+}
+
+/// Image owned by the library (f32).
+#[repr(C)]
+#[derive(Debug)]
+pub struct IsImageF32 {
+    pub data: *mut f32,
+    pub width: usize,
+    pub height: usize,
+    pub channels: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+}
+
+fn fail(status: IsStatus, message: &str) -> IsStatus {
+    set_last_error(message);
+    status
+}
+
+fn batch_status(e: BatchError) -> IsStatus {
+    let status = match e {
+        BatchError::UnknownFilter(_) => IsStatus::UnknownFilter,
+        _ => IsStatus::InvalidParameters,
+    };
+    fail(status, &e.to_string())
+}
+
+/// Run `body`, turning a panic into [`IsStatus::InternalError`].
+fn guarded(body: impl FnOnce() -> IsStatus) -> IsStatus {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| fail(IsStatus::InternalError, "filter panicked"))
+}
+
+/// Borrow a C string as UTF-8.
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string.
+unsafe fn str_arg<'a>(text: *const c_char, name: &str) -> Result<&'a str, IsStatus> {
+    if text.is_null() {
+        return Err(fail(IsStatus::InvalidArgument, &format!("{} is null", name)));
+    }
+    CStr::from_ptr(text).to_str().map_err(|_| fail(IsStatus::InvalidArgument, &format!("{} is not UTF-8", name)))
+}
+
+/// Borrow an image buffer.
+///
+/// # Safety
+/// `data` must be null or point to `width * height * channels` readable values.
+unsafe fn image_arg<'a, T>(data: *const T, width: usize, height: usize, channels: usize) -> Result<ArrayView3<'a, T>, IsStatus> {
+    if data.is_null() || width == 0 || height == 0 || channels == 0 {
+        return Err(fail(IsStatus::InvalidArgument, "image is null or empty"));
+    }
+    Ok(ArrayView3::from_shape_ptr((height, width, channels), data))
+}
+
+fn into_raw<T>(image: Array3<T>) -> (*mut T, usize, usize, usize) {
+    let (height, width, channels) = image.dim();
+    let data = Box::into_raw(image.into_raw_vec_and_offset().0.into_boxed_slice()) as *mut T;
+    (data, width, height, channels)
+}
+
+fn store_u8(image: Array3<u8>, out: &mut IsImageU8) -> IsStatus {
+    let (data, width, height, channels) = into_raw(image);
+    *out = IsImageU8 { data, width, height, channels };
+    IsStatus::Ok
+}
+
+fn store_f32(image: Array3<f32>, out: &mut IsImageF32) -> IsStatus {
+    let (data, width, height, channels) = into_raw(image);
+    *out = IsImageF32 { data, width, height, channels };
+    IsStatus::Ok
+}
+
+/// Version of the C ABI, bumped on incompatible changes (currently 1).
+#[no_mangle]
+pub extern "C" fn imagestag_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// Message of the last failed call on this thread (empty if none).
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn imagestag_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+/// JSON description of every filter; release with [`imagestag_string_free`].
+#[no_mangle]
+pub extern "C" fn imagestag_filter_registry() -> *mut c_char {
+    CString::new(registry_json()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Release a string returned by the library.
+///
+/// # Safety
+/// `text` must be null or come from [`imagestag_filter_registry`], and is
+/// invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+/// Apply one filter by name (u8).
+///
+/// # Safety
+/// `data` must point to `width * height * channels` bytes, `name` and
+/// `params_json` to NUL-terminated strings (`params_json` may be null for
+/// defaults) and `out` to writable memory. On success `out` holds a new
+/// image to release with [`imagestag_image_u8_free`].
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_apply_filter_u8(
+    data: *const u8,
+    width: usize,
+    height: usize,
+    channels: usize,
+    name: *const c_char,
+    params_json: *const c_char,
+    out: *mut IsImageU8,
+) -> IsStatus {
+    guarded(|| {
+        let (input, name, out) = match (image_arg(data, width, height, channels), str_arg(name, "name"), out.as_mut()) {
+            (Ok(input), Ok(name), Some(out)) => (input, name, out),
+            (Err(status), ..) | (_, Err(status), _) => return status,
+            (.., None) => return fail(IsStatus::InvalidArgument, "out is null"),
+        };
+        let params = if params_json.is_null() { Ok("{}") } else { str_arg(params_json, "params_json") };
+        match params.map(|params| apply_filter_u8(name, input, params)) {
+            Ok(Ok(result)) => store_u8(result, out),
+            Ok(Err(e)) => batch_status(e),
+            Err(status) => status,
+        }
+    })
+}
+
+/// Apply one filter by name (f32).
+///
+/// # Safety
+/// As [`imagestag_apply_filter_u8`], with `width * height * channels` floats.
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_apply_filter_f32(
+    data: *const f32,
+    width: usize,
+    height: usize,
+    channels: usize,
+    name: *const c_char,
+    params_json: *const c_char,
+    out: *mut IsImageF32,
+) -> IsStatus {
+    guarded(|| {
+        let (input, name, out) = match (image_arg(data, width, height, channels), str_arg(name, "name"), out.as_mut()) {
+            (Ok(input), Ok(name), Some(out)) => (input, name, out),
+            (Err(status), ..) | (_, Err(status), _) => return status,
+            (.., None) => return fail(IsStatus::InvalidArgument, "out is null"),
+        };
+        let params = if params_json.is_null() { Ok("{}") } else { str_arg(params_json, "params_json") };
+        match params.map(|params| apply_filter_f32(name, input, params)) {
+            Ok(Ok(result)) => store_f32(result, out),
+            Ok(Err(e)) => batch_status(e),
+            Err(status) => status,
+        }
+    })
+}
+
+/// Apply a pipeline string ("brightness 0.2 | sharpen 0.5") or recipe JSON (u8).
+///
+/// # Safety
+/// As [`imagestag_apply_filter_u8`]; `pipeline` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_apply_pipeline_u8(
+    data: *const u8,
+    width: usize,
+    height: usize,
+    channels: usize,
+    pipeline: *const c_char,
+    out: *mut IsImageU8,
+) -> IsStatus {
+    guarded(|| {
+        let (input, pipeline, out) = match (image_arg(data, width, height, channels), str_arg(pipeline, "pipeline"), out.as_mut()) {
+            (Ok(input), Ok(pipeline), Some(out)) => (input, pipeline, out),
+            (Err(status), ..) | (_, Err(status), _) => return status,
+            (.., None) => return fail(IsStatus::InvalidArgument, "out is null"),
+        };
+        match parse_pipeline_or_recipe(pipeline) {
+            Ok(steps) => store_u8(apply_pipeline_u8(input, &steps), out),
+            Err(e) => batch_status(e),
+        }
+    })
+}
+
+/// Apply a pipeline string or recipe JSON (f32).
+///
+/// # Safety
+/// As [`imagestag_apply_pipeline_u8`], with `width * height * channels` floats.
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_apply_pipeline_f32(
+    data: *const f32,
+    width: usize,
+    height: usize,
+    channels: usize,
+    pipeline: *const c_char,
+    out: *mut IsImageF32,
+) -> IsStatus {
+    guarded(|| {
+        let (input, pipeline, out) = match (image_arg(data, width, height, channels), str_arg(pipeline, "pipeline"), out.as_mut()) {
+            (Ok(input), Ok(pipeline), Some(out)) => (input, pipeline, out),
+            (Err(status), ..) | (_, Err(status), _) => return status,
+            (.., None) => return fail(IsStatus::InvalidArgument, "out is null"),
+        };
+        match parse_pipeline_or_recipe(pipeline) {
+            Ok(steps) => store_f32(apply_pipeline_f32(input, &steps), out),
+            Err(e) => batch_status(e),
+        }
+    })
+}
+
+/// Release the pixels of an image returned by the library.
+///
+/// # Safety
+/// `image` must be null or hold an image from a `_u8` call that was not
+/// freed yet; its `data` is set to null.
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_image_u8_free(image: *mut IsImageU8) {
+    if let Some(image) = image.as_mut() {
+        if !image.data.is_null() {
+            let len = image.width * image.height * image.channels;
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(image.data, len)));
+            image.data = ptr::null_mut();
+        }
+    }
+}
+
+/// Release the pixels of an f32 image returned by the library.
+///
+/// # Safety
+/// As [`imagestag_image_u8_free`] for `_f32` results.
+#[no_mangle]
+pub unsafe extern "C" fn imagestag_image_f32_free(image: *mut IsImageF32) {
+    if let Some(image) = image.as_mut() {
+        if !image.data.is_null() {
+            let len = image.width * image.height * image.channels;
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(image.data, len)));
+            image.data = ptr::null_mut();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capi_filter_pipeline_and_errors() {
+        let pixels: Vec<u8> = (0..4 * 6 * 3).map(|i| (i * 5) as u8).collect();
+        let mut out = IsImageU8 { data: ptr::null_mut(), width: 0, height: 0, channels: 0 };
+        let name = CString::new("invert").unwrap();
+        let status = unsafe { imagestag_apply_filter_u8(pixels.as_ptr(), 6, 4, 3, name.as_ptr(), ptr::null(), &mut out) };
+        assert_eq!(status, IsStatus::Ok);
+        assert_eq!((out.width, out.height, out.channels), (6, 4, 3));
+        let result = unsafe { std::slice::from_raw_parts(out.data, 72) };
+        assert!(result.iter().zip(&pixels).all(|(r, p)| *r == 255 - p));
+        unsafe { imagestag_image_u8_free(&mut out) };
+        assert!(out.data.is_null());
+
+        let pipeline = CString::new("thumbnail 3").unwrap();
+        let status = unsafe { imagestag_apply_pipeline_u8(pixels.as_ptr(), 6, 4, 3, pipeline.as_ptr(), &mut out) };
+        assert_eq!(status, IsStatus::Ok);
+        assert_eq!(out.width.max(out.height), 3);
+        unsafe { imagestag_image_u8_free(&mut out) };
+
+        let unknown = CString::new("no_such_filter").unwrap();
+        let status = unsafe { imagestag_apply_filter_u8(pixels.as_ptr(), 6, 4, 3, unknown.as_ptr(), ptr::null(), &mut out) };
+        assert_eq!(status, IsStatus::UnknownFilter);
+        let message = unsafe { CStr::from_ptr(imagestag_last_error()) }.to_str().unwrap();
+        assert!(message.contains("no_such_filter"), "{message}");
+        let status = unsafe { imagestag_apply_filter_u8(ptr::null(), 6, 4, 3, name.as_ptr(), ptr::null(), &mut out) };
+        assert_eq!(status, IsStatus::InvalidArgument);
+
+        let registry = imagestag_filter_registry();
+        assert!(unsafe { CStr::from_ptr(registry) }.to_str().unwrap().contains("\"invert\""));
+        unsafe { imagestag_string_free(registry) };
+    }
+}
@@ -0,0 +1,93 @@
+//! Chunked execution of neighborhood filters.
+//!
+//! [`BandJob`] splits an image into horizontal bands and filters one band
+//! per [`BandJob::step`]. Every band is filtered together with `halo` rows
+//! of context above and below, so for filters whose reach does not exceed
+//! the halo the stitched result equals filtering the whole image at once.
+//!
+//! Callers that must stay responsive interleave steps with other work: the
+//! WASM build exposes the jobs so JS can yield to the browser event loop
+//! between bands and report progress (`imagestag/filters/jobs.js`).
+
+use ndarray::{s, Array3, ArrayView3};
+
+/// Filter applied to one band (with its halo rows); must keep the shape.
+pub type BandFilter<T> = Box<dyn Fn(ArrayView3<T>) -> Array3<T>>;
+
+/// An image filtered band by band.
+pub struct BandJob<T> {
+    input: Array3<T>,
+    output: Array3<T>,
+    halo: usize,
+    band_rows: usize,
+    next_row: usize,
+    filter: BandFilter<T>,
+}
+
+impl<T: Copy + Default> BandJob<T> {
+    /// Prepare a job; nothing is filtered until the first [`step`](Self::step).
+    ///
+    /// # Arguments
+    /// * `input` - Image (height, width, channels)
+    /// * `halo` - Context rows above and below each band, at least the filter's reach
+    /// * `band_rows` - Rows finished per step (at least 1)
+    /// * `filter` - Filter run on each band; must return an image of the band's shape
+    pub fn new(input: Array3<T>, halo: usize, band_rows: usize, filter: impl Fn(ArrayView3<T>) -> Array3<T> + 'static) -> Self {
+        let output = Array3::from_elem(input.dim(), T::default());
+        Self { input, output, halo, band_rows: band_rows.max(1), next_row: 0, filter: Box::new(filter) }
+    }
+
+    /// Filter the next band.
+    ///
+    /// # Returns
+    /// `true` once every row is done
+    pub fn step(&mut self) -> bool {
+        let height = self.input.dim().0;
+        if self.next_row < height {
+            let (y0, y1) = (self.next_row, (self.next_row + self.band_rows).min(height));
+            let (top, bottom) = (y0.saturating_sub(self.halo), (y1 + self.halo).min(height));
+            let band = (self.filter)(self.input.slice(s![top..bottom, .., ..]));
+            assert_eq!(band.dim().0, bottom - top, "band filter changed the row count");
+            self.output.slice_mut(s![y0..y1, .., ..]).assign(&band.slice(s![y0 - top..y1 - top, .., ..]));
+            self.next_row = y1;
+        }
+        self.is_done()
+    }
+
+    /// Completed fraction (0.0-1.0).
+    pub fn fraction(&self) -> f32 {
+        let height = self.input.dim().0;
+        if height == 0 {
+            1.0
+        } else {
+            self.next_row as f32 / height as f32
+        }
+    }
+
+    /// Whether every row has been filtered.
+    pub fn is_done(&self) -> bool {
+        self.next_row >= self.input.dim().0
+    }
+
+    /// Run the remaining steps and return the filtered image.
+    pub fn finish(mut self) -> Array3<T> {
+        while !self.step() {}
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::noise::median_u8;
+
+    #[test]
+    fn test_banded_median_matches_whole_image() {
+        let input = Array3::from_shape_fn((37, 23, 3), |(y, x, c)| ((y * 31 + x * 17 + c * 7) % 251) as u8);
+        let mut job = BandJob::new(input.clone(), 2, 8, |band| median_u8(band, 2));
+        assert_eq!(job.fraction(), 0.0);
+        assert!(!job.step());
+        assert!((job.fraction() - 8.0 / 37.0).abs() < 1e-6);
+        assert_eq!(job.finish(), median_u8(input.view(), 2));
+    }
+}
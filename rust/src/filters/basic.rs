@@ -6,7 +6,8 @@
 //! - Alpha premultiplication
 
 use ndarray::{Array2, Array3};
-use numpy::{IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3};
+use numpy::{IntoPyArray, PyArray2, PyArray3};
+use crate::host_array::{PyHostArray2, PyHostArray3};
 use pyo3::prelude::*;
 
 /// Apply binary threshold to grayscale image.
@@ -20,7 +21,7 @@ use pyo3::prelude::*;
 #[pyfunction]
 pub fn threshold_gray<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray2<'py, u8>,
+    image: PyHostArray2<'py, u8>,
     threshold: u8,
 ) -> Bound<'py, PyArray2<u8>> {
     let input = image.as_array();
@@ -43,7 +44,7 @@ pub fn threshold_gray<'py>(
 #[pyfunction]
 pub fn invert_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
 ) -> Bound<'py, PyArray3<u8>> {
     let input = image.as_array();
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
@@ -71,7 +72,7 @@ pub fn invert_rgba<'py>(
 #[pyfunction]
 pub fn premultiply_alpha<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
 ) -> Bound<'py, PyArray3<u8>> {
     let input = image.as_array();
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
@@ -115,7 +116,7 @@ pub fn premultiply_alpha<'py>(
 #[pyfunction]
 pub fn unpremultiply_alpha<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
 ) -> Bound<'py, PyArray3<u8>> {
     let input = image.as_array();
     let (height, width, channels) = (input.shape()[0], input.shape()[1], input.shape()[2]);
@@ -14,7 +14,8 @@
 //! the blur result.
 
 use ndarray::Array3;
-use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+use numpy::{IntoPyArray, PyArray3};
+use crate::host_array::PyHostArray3;
 use pyo3::prelude::*;
 
 use super::core::gaussian_kernel_1d;
@@ -33,7 +34,7 @@ use super::core::gaussian_kernel_1d;
 #[pyfunction]
 pub fn gaussian_blur_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     sigma: f32,
 ) -> Bound<'py, PyArray3<u8>> {
     let input = image.as_array();
@@ -158,7 +159,7 @@ pub fn gaussian_blur_rgba<'py>(
 #[pyfunction]
 pub fn box_blur_rgba<'py>(
     py: Python<'py>,
-    image: PyReadonlyArray3<'py, u8>,
+    image: PyHostArray3<'py, u8>,
     radius: usize,
 ) -> Bound<'py, PyArray3<u8>> {
     let input = image.as_array();
@@ -21,6 +21,16 @@
 //! Channel count is inferred from input array dimensions. Filters process
 //! only the channels that exist, avoiding unnecessary work on unused alpha.
 //!
+//! Filters in [`color_adjust`] and [`sharpen`] take `impl Into<ArrayView3<T>>`
+//! and so also accept a [`crate::Image`], which carries its layout explicitly.
+//!
+//! ## Strided Input
+//!
+//! Filters read their input through an `ArrayView3` by index and don't need
+//! C-contiguous data: NumPy slices (`img[::2, ::2]`), Fortran-order arrays
+//! and negative strides (`img[::-1]`) are processed in place without a copy.
+//! Output is always a new array in standard layout.
+//!
 //! ## Architecture
 //!
 //! All filters follow these principles:
@@ -29,8 +39,19 @@
 //! - **Alpha preservation** - Alpha channel (if present) is always preserved
 //! - **Grayscale handling** - Color-dependent filters (saturation, hue) are no-ops for grayscale
 //! - **Thread-safe** - Use rayon for parallel processing where available
+//!
+//! ## Non-finite Input
+//!
+//! f32 filters expect finite values in 0.0-1.0 and don't check for others.
+//! See [`sanitize`] for what happens otherwise and for the per-call
+//! clamp / propagate / error policy.
+//!
+//! ## Determinism
+//!
+//! The core filter set gives bit-identical results on every target and
+//! thread count when built with the `deterministic` feature; see [`fmath`].
 
-// Cross-platform filter modules (work with both Python and WASM)
+// Cross-platform filter modules (work with Python, WASM and plain Rust)
 // Source files are co-located with Python/JS wrappers in imagestag/filters/
 
 #[path = "../../../imagestag/filters/grayscale.rs"]
@@ -54,6 +75,9 @@ pub mod sharpen;
 #[path = "../../../imagestag/filters/edge.rs"]
 pub mod edge;
 
+#[path = "../../../imagestag/filters/convolve.rs"]
+pub mod convolve;
+
 #[path = "../../../imagestag/filters/noise.rs"]
 pub mod noise;
 
@@ -63,25 +87,85 @@ pub mod morphology;
 #[path = "../../../imagestag/filters/rotate.rs"]
 pub mod rotate;
 
-// Shared core utilities (available for both Python and WASM)
-#[cfg(any(feature = "python", feature = "wasm"))]
+#[path = "../../../imagestag/filters/resample.rs"]
+pub mod resample;
+
+#[path = "../../../imagestag/filters/straighten.rs"]
+pub mod straighten;
+
+#[path = "../../../imagestag/filters/perspective.rs"]
+pub mod perspective;
+
+#[path = "../../../imagestag/filters/matting.rs"]
+pub mod matting;
+
+#[path = "../../../imagestag/filters/glitch.rs"]
+pub mod glitch;
+
+#[path = "../../../imagestag/filters/material.rs"]
+pub mod material;
+
+#[path = "../../../imagestag/filters/wavelet.rs"]
+pub mod wavelet;
+
+#[path = "../../../imagestag/filters/tonemap.rs"]
+pub mod tonemap;
+
+#[path = "../../../imagestag/filters/colorspace.rs"]
+pub mod colorspace;
+
+#[path = "../../../imagestag/filters/compositing.rs"]
+pub mod compositing;
+
+#[path = "../../../imagestag/filters/raw.rs"]
+pub mod raw;
+
+#[path = "../../../imagestag/filters/deband.rs"]
+pub mod deband;
+
+#[path = "../../../imagestag/filters/fft.rs"]
+pub mod fft;
+
+#[path = "../../../imagestag/filters/projection.rs"]
+pub mod projection;
+
+#[path = "../../../imagestag/filters/distort.rs"]
+pub mod distort;
+
+#[path = "../../../imagestag/filters/synthesis.rs"]
+pub mod synthesis;
+
+#[path = "../../../imagestag/filters/canvas.rs"]
+pub mod canvas;
+
+#[path = "../../../imagestag/filters/seamless.rs"]
+pub mod seamless;
+
+#[path = "../../../imagestag/filters/sanitize.rs"]
+pub mod sanitize;
+
+#[path = "../../../imagestag/filters/fmath.rs"]
+pub mod fmath;
+
+// Shared core utilities
 #[path = "../../../imagestag/filters/core.rs"]
 pub mod core;
 
-// WASM-compatible blur (no rayon, single-threaded)
+// Reusable temporaries of layer effects
+#[path = "../../../imagestag/filters/pool.rs"]
+pub mod pool;
+
+// WASM-compatible blur (rayon-parallel with the wasm-threads feature)
 #[path = "../../../imagestag/filters/blur_wasm.rs"]
 pub mod blur_wasm;
 
 // Gradient generator (co-located with Python/JS wrappers)
-#[cfg(feature = "python")]
 #[path = "../../../imagestag/filters/gradient_generator.rs"]
 pub mod gradient_generator;
 
-// Python-only modules (require PyO3/numpy/rayon)
-#[cfg(feature = "python")]
+// Basic pixel operations and u8 blurs (Python bindings gated per function)
 #[path = "../../../imagestag/filters/basic.rs"]
 pub mod basic;
 
-#[cfg(feature = "python")]
 #[path = "../../../imagestag/filters/blur.rs"]
 pub mod blur;
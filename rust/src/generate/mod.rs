@@ -0,0 +1,9 @@
+//! Generators that create images from data instead of filtering pixels.
+//!
+//! - **QR**: QR code encoding (byte mode, versions 1-40, all error
+//!   correction levels) and rendering into RGBA buffers, with optional
+//!   rounded modules and a cleared area for a centered logo
+
+pub mod qr;
+
+pub use qr::{render_qr, QrCode, QrError, QrErrorCorrection, QrStyle};
@@ -0,0 +1,712 @@
+//! QR code encoding and rendering.
+//!
+//! [`QrCode::encode`] follows ISO/IEC 18004: the data is stored in byte mode
+//! in the smallest version (1-40) that fits, Reed-Solomon error correction
+//! is added per block, and of the eight masks the one with the lowest
+//! penalty score is applied. [`render_qr`] draws the symbol into an RGBA
+//! image with a quiet zone, optionally with rounded modules and a cleared
+//! center for a logo.
+
+use std::fmt;
+
+use ndarray::Array3;
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Largest logo area as a fraction of the symbol width; with level High
+/// error correction the cleared modules stay well within what scanners
+/// recover.
+const MAX_LOGO_SIZE: f32 = 0.3;
+
+// Error correction codewords per block, indexed by [level][version]
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+
+// Number of error correction blocks, indexed by [level][version]
+const ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+/// Error raised while encoding or rendering a QR code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QrError {
+    /// Data of this many bytes does not fit into a version 40 symbol
+    DataTooLong(usize),
+    /// Image size (first) is smaller than the symbol plus quiet zone in modules (second)
+    SizeTooSmall(usize, usize),
+}
+
+impl fmt::Display for QrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrError::DataTooLong(len) => write!(f, "data of {} bytes does not fit into a QR code", len),
+            QrError::SizeTooSmall(size, modules) => {
+                write!(f, "image size {} is smaller than the {} modules of the symbol and margin", size, modules)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QrError {}
+
+/// Share of damaged codewords a symbol can recover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QrErrorCorrection {
+    /// About 7%
+    Low,
+    /// About 15%
+    #[default]
+    Medium,
+    /// About 25%
+    Quartile,
+    /// About 30%
+    High,
+}
+
+impl QrErrorCorrection {
+    /// Parse a level name ("low", "medium", "quartile", "high" or "l", "m", "q", "h").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "low" | "l" => Some(QrErrorCorrection::Low),
+            "medium" | "m" => Some(QrErrorCorrection::Medium),
+            "quartile" | "q" => Some(QrErrorCorrection::Quartile),
+            "high" | "h" => Some(QrErrorCorrection::High),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Two-bit level indicator stored in the format information.
+    fn format_bits(self) -> u32 {
+        match self {
+            QrErrorCorrection::Low => 1,
+            QrErrorCorrection::Medium => 0,
+            QrErrorCorrection::Quartile => 3,
+            QrErrorCorrection::High => 2,
+        }
+    }
+}
+
+/// Encoded QR symbol: a square grid of dark and light modules.
+#[derive(Debug, Clone)]
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    modules: Vec<bool>,
+    /// Finder, timing, alignment and format modules, which carry no data
+    function: Vec<bool>,
+}
+
+/// Number of data and error correction bits a version holds.
+fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let count = version / 7 + 2;
+        result -= (25 * count - 10) * count - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+/// Number of data codewords (bytes) a version holds at a level.
+fn data_codewords(version: usize, level: QrErrorCorrection) -> usize {
+    let l = level.index();
+    raw_data_modules(version) / 8 - ECC_CODEWORDS_PER_BLOCK[l][version] as usize * ERROR_CORRECTION_BLOCKS[l][version] as usize
+}
+
+/// Row and column centers of the alignment patterns.
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let size = version * 4 + 17;
+    let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    let mut result: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    result.push(6);
+    result.reverse();
+    result
+}
+
+/// Multiply in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z = 0u32;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+/// Reed-Solomon generator polynomial of a degree, highest coefficient dropped.
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+/// Reed-Solomon error correction codewords of a data block.
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(d, factor);
+        }
+    }
+    result
+}
+
+/// Split data into blocks, append error correction, and interleave.
+fn add_error_correction(data: &[u8], version: usize, level: QrErrorCorrection) -> Vec<u8> {
+    let blocks = ERROR_CORRECTION_BLOCKS[level.index()][version] as usize;
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[level.index()][version] as usize;
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks = blocks - raw_codewords % blocks;
+    let short_len = raw_codewords / blocks;
+    let divisor = reed_solomon_divisor(ecc_len);
+
+    let mut split = Vec::with_capacity(blocks);
+    let mut offset = 0;
+    for i in 0..blocks {
+        let len = short_len - ecc_len + usize::from(i >= short_blocks);
+        let mut block = data[offset..offset + len].to_vec();
+        offset += len;
+        let ecc = reed_solomon_remainder(&block, &divisor);
+        if i < short_blocks {
+            // Placeholder keeps the columns aligned; skipped below
+            block.push(0);
+        }
+        block.extend(ecc);
+        split.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..split[0].len() {
+        for (j, block) in split.iter().enumerate() {
+            if i != short_len - ecc_len || j >= short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+fn append_bits(bits: &mut Vec<bool>, value: u32, len: usize) {
+    bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+}
+
+fn mask_applies(mask: u8, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + x * y % 3).is_multiple_of(2),
+    }
+}
+
+impl QrCode {
+    /// Encode bytes (UTF-8 text or binary) into the smallest fitting symbol.
+    ///
+    /// # Arguments
+    /// * `data` - Payload, stored in byte mode
+    /// * `level` - Error correction level
+    ///
+    /// # Returns
+    /// The symbol, or [`QrError::DataTooLong`] beyond version 40 capacity
+    pub fn encode(data: &[u8], level: QrErrorCorrection) -> Result<Self, QrError> {
+        let count_bits = |version: usize| if version <= 9 { 8 } else { 16 };
+        let version = (1..=40)
+            .find(|&v| 4 + count_bits(v) + data.len() * 8 <= data_codewords(v, level) * 8)
+            .ok_or(QrError::DataTooLong(data.len()))?;
+
+        // Byte mode segment, terminator and pad codewords
+        let capacity = data_codewords(version, level) * 8;
+        let mut bits = Vec::with_capacity(capacity);
+        append_bits(&mut bits, 0b0100, 4);
+        append_bits(&mut bits, data.len() as u32, count_bits(version));
+        for &byte in data {
+            append_bits(&mut bits, byte as u32, 8);
+        }
+        let terminator = (capacity - bits.len()).min(4);
+        append_bits(&mut bits, 0, terminator);
+        let padding = (8 - bits.len() % 8) % 8;
+        append_bits(&mut bits, 0, padding);
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if bits.len() >= capacity {
+                break;
+            }
+            append_bits(&mut bits, pad, 8);
+        }
+        let codewords: Vec<u8> = bits.chunks(8).map(|byte| byte.iter().fold(0u8, |acc, &b| (acc << 1) | b as u8)).collect();
+
+        let size = version * 4 + 17;
+        let mut code = QrCode { version, size, modules: vec![false; size * size], function: vec![false; size * size] };
+        code.draw_function_patterns(level);
+        code.draw_codewords(&add_error_correction(&codewords, version, level));
+
+        // Masking is an involution, so each candidate is undone by reapplying it
+        let mut best = (u32::MAX, 0);
+        for mask in 0..8 {
+            code.apply_mask(mask);
+            code.draw_format_bits(level, mask);
+            best = best.min((code.penalty(), mask));
+            code.apply_mask(mask);
+        }
+        code.apply_mask(best.1);
+        code.draw_format_bits(level, best.1);
+        Ok(code)
+    }
+
+    /// Symbol version (1-40).
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    /// Width and height in modules (17 + 4 * version).
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module at column `x`, row `y` is dark.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, level: QrErrorCorrection) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i.is_multiple_of(2));
+            self.set_function(i, 6, i.is_multiple_of(2));
+        }
+
+        // Finder patterns with their light separators
+        for (cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            for dy in -4isize..=4 {
+                for dx in -4isize..=4 {
+                    let (x, y) = (cx as isize + dx, cy as isize + dy);
+                    if (0..size as isize).contains(&x) && (0..size as isize).contains(&y) {
+                        let dist = dx.abs().max(dy.abs());
+                        self.set_function(x as usize, y as usize, dist != 2 && dist != 4);
+                    }
+                }
+            }
+        }
+
+        // Alignment patterns, except where they would overlap the finders
+        let positions = alignment_positions(self.version);
+        let last = positions.len().saturating_sub(1);
+        for (i, &cy) in positions.iter().enumerate() {
+            for (j, &cx) in positions.iter().enumerate() {
+                if (i == 0 && (j == 0 || j == last)) || (i == last && j == 0) {
+                    continue;
+                }
+                for dy in 0..5 {
+                    for dx in 0..5 {
+                        let dist = (dx as isize - 2).abs().max((dy as isize - 2).abs());
+                        self.set_function(cx + dx - 2, cy + dy - 2, dist != 1);
+                    }
+                }
+            }
+        }
+
+        // Reserve the format area; the real bits are drawn after masking
+        self.draw_format_bits(level, 0);
+
+        if self.version >= 7 {
+            let mut rem = self.version as u32;
+            for _ in 0..12 {
+                rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+            }
+            let bits = (self.version as u32) << 12 | rem;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 == 1;
+                let (a, b) = (size - 11 + i % 3, i / 3);
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, level: QrErrorCorrection, mask: u8) {
+        let data = level.format_bits() << 3 | mask as u32;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = (data << 10 | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+        let size = self.size;
+
+        // Copy around the top-left finder
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Copy split between the other two finders, plus the dark module
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Place codeword bits in the two-column zigzag from the bottom right.
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let size = self.size;
+        let total = data.len() * 8;
+        let mut i = 0;
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vert in 0..size {
+                for column in 0..2 {
+                    let x = right - column;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    if !self.function[y * size + x] && i < total {
+                        self.modules[y * size + x] = (data[i >> 3] >> (7 - (i & 7))) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u8) {
+        let size = self.size;
+        for y in 0..size {
+            for x in 0..size {
+                if !self.function[y * size + x] && mask_applies(mask, x, y) {
+                    self.modules[y * size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// Penalty score of the current mask (lower scans more reliably).
+    fn penalty(&self) -> u32 {
+        let size = self.size;
+        let mut score = 0;
+        for line in 0..size {
+            for horizontal in [true, false] {
+                let line_modules: Vec<bool> =
+                    (0..size).map(|i| if horizontal { self.is_dark(i, line) } else { self.is_dark(line, i) }).collect();
+
+                // Runs of five or more modules of one color
+                let mut run = 1;
+                for i in 1..=size {
+                    if i < size && line_modules[i] == line_modules[i - 1] {
+                        run += 1;
+                    } else {
+                        if run >= 5 {
+                            score += run - 2;
+                        }
+                        run = 1;
+                    }
+                }
+
+                // Finder-like 1:1:3:1:1 patterns next to four light modules;
+                // modules outside the symbol belong to the light quiet zone
+                let light = |range: std::ops::Range<usize>| range.into_iter().all(|k| k >= size || !line_modules[k]);
+                for i in 0..size.saturating_sub(6) {
+                    let finder = [true, false, true, true, true, false, true];
+                    if finder.iter().enumerate().all(|(k, &dark)| line_modules[i + k] == dark)
+                        && (light(i.saturating_sub(4)..i) || light(i + 7..i + 11))
+                    {
+                        score += 40;
+                    }
+                }
+            }
+        }
+
+        // 2x2 blocks of one color
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.is_dark(x, y);
+                if dark == self.is_dark(x + 1, y) && dark == self.is_dark(x, y + 1) && dark == self.is_dark(x + 1, y + 1) {
+                    score += 3;
+                }
+            }
+        }
+
+        // 10 points per 5% the dark share deviates from one half
+        let total = size * size;
+        let dark = self.modules.iter().filter(|&&d| d).count();
+        let k = (dark * 20).abs_diff(total * 10).div_ceil(total).saturating_sub(1);
+        score as u32 + k as u32 * 10
+    }
+
+    /// Clear the data modules in a centered square covering `fraction` of the width.
+    fn knock_out(&mut self, fraction: f32) {
+        let size = self.size;
+        let mut n = (size as f32 * fraction.min(MAX_LOGO_SIZE)).round() as usize;
+        if n == 0 {
+            return;
+        }
+        if !(size - n).is_multiple_of(2) {
+            n += 1;
+        }
+        let start = (size - n) / 2;
+        for y in start..start + n {
+            for x in start..start + n {
+                if !self.function[y * size + x] {
+                    self.modules[y * size + x] = false;
+                }
+            }
+        }
+    }
+
+    fn dark_at(&self, x: isize, y: isize) -> bool {
+        (0..self.size as isize).contains(&x) && (0..self.size as isize).contains(&y) && self.is_dark(x as usize, y as usize)
+    }
+
+    /// Dark coverage (0-1) at a point in module coordinates.
+    ///
+    /// Rounded corners are only cut where both neighbors towards the corner
+    /// are light, so runs of dark modules stay connected.
+    fn coverage(&self, u: f32, v: f32, radius: f32, pixels_per_module: f32) -> f32 {
+        if u < 0.0 || v < 0.0 || u >= self.size as f32 || v >= self.size as f32 {
+            return 0.0;
+        }
+        let (mx, my) = (u as usize, v as usize);
+        if !self.is_dark(mx, my) {
+            return 0.0;
+        }
+        if radius <= 0.0 {
+            return 1.0;
+        }
+        let (fx, fy) = (u - mx as f32, v - my as f32);
+        let (cx, dx) = if fx < 0.5 { (fx, -1) } else { (1.0 - fx, 1) };
+        let (cy, dy) = if fy < 0.5 { (fy, -1) } else { (1.0 - fy, 1) };
+        if cx >= radius || cy >= radius || self.dark_at(mx as isize + dx, my as isize) || self.dark_at(mx as isize, my as isize + dy) {
+            return 1.0;
+        }
+        let outside = ((radius - cx).powi(2) + (radius - cy).powi(2)).sqrt() - radius;
+        (0.5 - outside * pixels_per_module).clamp(0.0, 1.0)
+    }
+}
+
+/// Appearance of a rendered QR code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QrStyle {
+    /// RGBA color of dark modules
+    pub foreground: [u8; 4],
+    /// RGBA color of light modules and the quiet zone
+    pub background: [u8; 4],
+    /// Error correction level; raised to High when `logo_size` > 0
+    pub error_correction: QrErrorCorrection,
+    /// Corner radius of modules as a fraction of the module size
+    /// (0 = squares, 0.5 = dots)
+    pub module_radius: f32,
+    /// Width of the centered logo knockout as a fraction of the symbol
+    /// width (0 = none, at most 0.3). Finder, timing and alignment
+    /// patterns inside it are kept.
+    pub logo_size: f32,
+}
+
+impl Default for QrStyle {
+    fn default() -> Self {
+        Self {
+            foreground: [0, 0, 0, 255],
+            background: [255, 255, 255, 255],
+            error_correction: QrErrorCorrection::Medium,
+            module_radius: 0.0,
+            logo_size: 0.0,
+        }
+    }
+}
+
+/// Render data as a QR code into an RGBA image.
+///
+/// # Arguments
+/// * `data` - Payload (UTF-8 text or binary)
+/// * `size` - Output width and height in pixels
+/// * `margin` - Quiet zone around the symbol in modules (4 per the standard)
+/// * `style` - Colors, error correction, module shape and logo knockout
+///
+/// # Returns
+/// RGBA image (size, size, 4). Modules are spread over the pixels as evenly
+/// as possible; choose `size` as a multiple of `symbol size + 2 * margin`
+/// for identical module widths.
+pub fn render_qr(data: &[u8], size: usize, margin: usize, style: &QrStyle) -> Result<Array3<u8>, QrError> {
+    let knockout = style.logo_size > 0.0;
+    let level = if knockout { QrErrorCorrection::High } else { style.error_correction };
+    let mut code = QrCode::encode(data, level)?;
+    if knockout {
+        code.knock_out(style.logo_size);
+    }
+
+    let modules = code.size() + 2 * margin;
+    if size < modules {
+        return Err(QrError::SizeTooSmall(size, modules));
+    }
+    let scale = size as f32 / modules as f32;
+    let radius = style.module_radius.clamp(0.0, 0.5);
+    let (fg, bg) = (style.foreground, style.background);
+
+    let mut output = vec![0u8; size * size * 4];
+    output.par_chunks_mut(size * 4).enumerate().for_each(|(y, row)| {
+        let v = (y as f32 + 0.5) / scale - margin as f32;
+        for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+            let u = (x as f32 + 0.5) / scale - margin as f32;
+            let t = code.coverage(u, v, radius, scale);
+            for c in 0..4 {
+                pixel[c] = (bg[c] as f32 + (fg[c] as f32 - bg[c] as f32) * t).round() as u8;
+            }
+        }
+    });
+    Ok(Array3::from_shape_vec((size, size, 4), output).expect("buffer matches the image size"))
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+type PyColor = (u8, u8, u8, u8);
+
+/// Render text or bytes as a QR code.
+///
+/// # Arguments
+/// * `data` - str (encoded as UTF-8) or bytes
+/// * `size` - Output width and height in pixels
+/// * `margin` - Quiet zone in modules
+/// * `colors` - (foreground, background) RGBA tuples
+/// * `error_correction` - "low", "medium", "quartile" or "high"
+/// * `module_radius` - Corner radius as a fraction of the module (0-0.5)
+/// * `logo_size` - Centered knockout width as a fraction of the symbol (0-0.3)
+///
+/// # Returns
+/// RGBA uint8 array (size, size, 4)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "render_qr", signature = (data, size=256, margin=4, colors=((0, 0, 0, 255), (255, 255, 255, 255)), error_correction="medium", module_radius=0.0, logo_size=0.0))]
+pub fn render_qr_py<'py>(
+    data: &Bound<'py, PyAny>,
+    size: usize,
+    margin: usize,
+    colors: (PyColor, PyColor),
+    error_correction: &str,
+    module_radius: f32,
+    logo_size: f32,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let bytes = match data.extract::<String>() {
+        Ok(text) => text.into_bytes(),
+        Err(_) => data.extract::<Vec<u8>>()?,
+    };
+    let error_correction = QrErrorCorrection::from_name(error_correction)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown error correction level '{}'", error_correction)))?;
+    let ((fr, fg, fb, fa), (br, bg, bb, ba)) = colors;
+    let style = QrStyle { foreground: [fr, fg, fb, fa], background: [br, bg, bb, ba], error_correction, module_radius, logo_size };
+    let image = render_qr(&bytes, size, margin, &style).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(image.into_pyarray(data.py()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding_matches_standard() {
+        // Reed-Solomon codewords of the "HELLO WORLD" 1-M example
+        let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+        assert_eq!(reed_solomon_remainder(&data, &reed_solomon_divisor(10)), vec![196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+
+        // Capacities in bytes from the standard's tables
+        assert_eq!(data_codewords(1, QrErrorCorrection::Medium), 16);
+        assert_eq!(data_codewords(5, QrErrorCorrection::Quartile), 62);
+        assert_eq!(data_codewords(40, QrErrorCorrection::Low), 2956);
+        assert_eq!(data_codewords(40, QrErrorCorrection::High), 1276);
+        assert_eq!(alignment_positions(32), vec![6, 34, 60, 86, 112, 138]);
+
+        let code = QrCode::encode(b"https://example.com", QrErrorCorrection::Medium).unwrap();
+        assert_eq!((code.version(), code.size()), (2, 25));
+        // Format information (15 bits around the top-left finder) is a valid BCH codeword
+        let read = |i: usize| match i {
+            0..=5 => code.is_dark(8, i),
+            6 => code.is_dark(8, 7),
+            7 => code.is_dark(8, 8),
+            8 => code.is_dark(7, 8),
+            _ => code.is_dark(14 - i, 8),
+        };
+        let format = (0..15).fold(0u32, |acc, i| acc | (read(i) as u32) << i) ^ 0x5412;
+        assert_eq!(format >> 13, 0, "level bits of medium");
+        let mut rem = format >> 10;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        assert_eq!(rem, format & 0x3FF);
+
+        assert_eq!(QrCode::encode(&[0; 3000], QrErrorCorrection::Low).unwrap_err(), QrError::DataTooLong(3000));
+    }
+
+    #[test]
+    fn test_render_quiet_zone_and_finder() {
+        let style = QrStyle { foreground: [10, 20, 30, 255], ..Default::default() };
+        // Version 1: 21 modules + 2 * 4 margin = 29, 5 pixels per module
+        let image = render_qr(b"hi", 145, 4, &style).unwrap();
+        assert_eq!(image.dim(), (145, 145, 4));
+        assert_eq!(image.slice(ndarray::s![2, 2, ..]).to_vec(), vec![255, 255, 255, 255]);
+        // Finder corner at module (0, 0) and its light ring at (1, 1)
+        assert_eq!(image.slice(ndarray::s![22, 22, ..]).to_vec(), vec![10, 20, 30, 255]);
+        assert_eq!(image[[27, 27, 0]], 255);
+        assert_eq!(render_qr(b"hi", 20, 4, &style).unwrap_err(), QrError::SizeTooSmall(20, 29));
+
+        // Rounded modules cut the outer finder corner
+        let rounded = render_qr(b"hi", 145, 4, &QrStyle { module_radius: 0.5, ..style }).unwrap();
+        assert!(rounded[[20, 20, 0]] > 200);
+    }
+}
@@ -0,0 +1,169 @@
+//! CPU implementations of the GPU kernels.
+//!
+//! Each function mirrors the math of the matching WGSL shader so that
+//! `Backend::Auto` can switch between devices without visible differences.
+
+use ndarray::{Array3, ArrayView2, ArrayView3, Zip};
+
+use super::BlendMode;
+
+/// Convolve the color channels with a kernel (edge clamped, alpha kept).
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `kernel` - Kernel (rows, cols), centered at (rows / 2, cols / 2)
+///
+/// # Returns
+/// Convolved image clamped to 0.0-1.0
+pub fn convolve_f32(image: ArrayView3<f32>, kernel: ArrayView2<f32>) -> Array3<f32> {
+    let (height, width, channels) = image.dim();
+    let (kh, kw) = kernel.dim();
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = image.to_owned();
+
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..color_channels {
+                let mut sum = 0.0f32;
+                for ky in 0..kh {
+                    let sy = (y as isize + ky as isize - (kh / 2) as isize).clamp(0, height as isize - 1) as usize;
+                    for kx in 0..kw {
+                        let sx = (x as isize + kx as isize - (kw / 2) as isize).clamp(0, width as isize - 1) as usize;
+                        sum += image[[sy, sx, c]] * kernel[[ky, kx]];
+                    }
+                }
+                output[[y, x, c]] = sum.clamp(0.0, 1.0);
+            }
+        }
+    }
+    output
+}
+
+/// Look up a value in an evenly sampled 0.0-1.0 table with linear interpolation.
+#[inline]
+pub fn lookup(table: &[f32], v: f32) -> f32 {
+    let last = (table.len() - 1) as f32;
+    let pos = v.clamp(0.0, 1.0) * last;
+    let i = pos.floor() as usize;
+    let j = (i + 1).min(table.len() - 1);
+    let t = pos - i as f32;
+    table[i] + (table[j] - table[i]) * t
+}
+
+/// Map the color channels through a lookup table (alpha kept).
+pub fn apply_lut_f32(image: ArrayView3<f32>, table: &[f32]) -> Array3<f32> {
+    let channels = image.dim().2;
+    let color_channels = if channels == 4 { 3 } else { channels };
+    let mut output = image.to_owned();
+    Zip::indexed(&mut output).for_each(|(_, _, c), v| {
+        if c < color_channels {
+            *v = lookup(table, *v);
+        }
+    });
+    output
+}
+
+/// Blend a separable mode on one channel (backdrop `cb`, source `cs`).
+#[inline]
+pub fn blend_channel(mode: BlendMode, cb: f32, cs: f32) -> f32 {
+    match mode {
+        BlendMode::Normal => cs,
+        BlendMode::Multiply => cb * cs,
+        BlendMode::Screen => cb + cs - cb * cs,
+        BlendMode::Overlay => {
+            if cb <= 0.5 {
+                2.0 * cb * cs
+            } else {
+                1.0 - 2.0 * (1.0 - cb) * (1.0 - cs)
+            }
+        }
+        BlendMode::Darken => cb.min(cs),
+        BlendMode::Lighten => cb.max(cs),
+        BlendMode::Add => (cb + cs).min(1.0),
+        BlendMode::Difference => (cb - cs).abs(),
+    }
+}
+
+/// Composite `top` over `base` with a blend mode.
+///
+/// # Arguments
+/// * `base` - Backdrop image, values 0.0-1.0
+/// * `top` - Layer image with the same shape
+/// * `mode` - Blend mode for the color channels
+/// * `opacity` - Layer opacity (0.0-1.0)
+///
+/// # Returns
+/// Composited image with the base's shape
+pub fn blend_f32(base: ArrayView3<f32>, top: ArrayView3<f32>, mode: BlendMode, opacity: f32) -> Array3<f32> {
+    let (height, width, channels) = base.dim();
+    let has_alpha = channels == 4;
+    let color_channels = if has_alpha { 3 } else { channels };
+    let mut output = Array3::<f32>::zeros((height, width, channels));
+
+    for y in 0..height {
+        for x in 0..width {
+            let alpha_b = if has_alpha { base[[y, x, 3]] } else { 1.0 };
+            let alpha_s = if has_alpha { top[[y, x, 3]] } else { 1.0 } * opacity;
+            let alpha = alpha_s + alpha_b * (1.0 - alpha_s);
+            for c in 0..color_channels {
+                let (cb, cs) = (base[[y, x, c]], top[[y, x, c]]);
+                let mixed = (1.0 - alpha_b) * cs + alpha_b * blend_channel(mode, cb, cs);
+                output[[y, x, c]] = if alpha > 0.0 {
+                    ((alpha_s * mixed + alpha_b * (1.0 - alpha_s) * cb) / alpha).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+            }
+            if has_alpha {
+                output[[y, x, 3]] = alpha;
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr2, Array3};
+
+    #[test]
+    fn test_convolve_identity_and_box() {
+        let image = Array3::from_shape_fn((5, 6, 4), |(y, x, c)| if c == 3 { 0.5 } else { (x + y) as f32 / 10.0 });
+        let identity = arr2(&[[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]]);
+        assert_eq!(convolve_f32(image.view(), identity.view()), image);
+
+        let flat = Array3::from_elem((4, 4, 1), 0.4f32);
+        let box3 = arr2(&[[1.0 / 9.0; 3]; 3]);
+        assert!(convolve_f32(flat.view(), box3.view()).iter().all(|&v| (v - 0.4).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_lut_interpolates() {
+        let table = [0.0, 0.5, 1.0];
+        assert!((lookup(&table, 0.25) - 0.25).abs() < 1e-6);
+        let inverted = [1.0, 0.0];
+        let image = Array3::from_elem((1, 1, 4), 0.25f32);
+        let result = apply_lut_f32(image.view(), &inverted);
+        assert!((result[[0, 0, 0]] - 0.75).abs() < 1e-6);
+        assert_eq!(result[[0, 0, 3]], 0.25);
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        let base = Array3::from_elem((1, 1, 4), 0.5f32);
+        let mut top = Array3::from_elem((1, 1, 4), 0.5f32);
+        top[[0, 0, 3]] = 1.0;
+        let mut opaque_base = base.clone();
+        opaque_base[[0, 0, 3]] = 1.0;
+
+        // Opaque top with normal mode replaces the color
+        assert_eq!(blend_f32(base.view(), top.view(), BlendMode::Normal, 1.0)[[0, 0, 0]], 0.5);
+        assert!((blend_f32(opaque_base.view(), top.view(), BlendMode::Multiply, 1.0)[[0, 0, 0]] - 0.25).abs() < 1e-6);
+        assert!((blend_f32(opaque_base.view(), top.view(), BlendMode::Screen, 1.0)[[0, 0, 0]] - 0.75).abs() < 1e-6);
+        // Half opacity multiply mixes halfway
+        let half = blend_f32(opaque_base.view(), top.view(), BlendMode::Multiply, 0.5);
+        assert!((half[[0, 0, 0]] - 0.375).abs() < 1e-6);
+        assert_eq!(half[[0, 0, 3]], 1.0);
+    }
+}
@@ -0,0 +1,353 @@
+//! wgpu device, compute pipelines and dispatch.
+//!
+//! Images are uploaded as `array<vec4<f32>>` storage buffers (one RGBA
+//! pixel per element). Every shader uses the same four bindings: a uniform
+//! [`Params`] block, the source pixels, the destination pixels and an
+//! auxiliary buffer (kernel weights, lookup table or top layer).
+
+use std::sync::OnceLock;
+
+use wgpu::util::DeviceExt;
+
+use super::GpuError;
+
+/// Uniform block shared by all shaders (see `shaders/*.wgsl`).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    width: u32,
+    height: u32,
+    kernel_width: u32,
+    kernel_height: u32,
+    mode: u32,
+    flags: u32,
+    opacity: f32,
+    pad: f32,
+}
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// GPU device with the compiled filter pipelines.
+pub struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    blur: wgpu::ComputePipeline,
+    convolve: wgpu::ComputePipeline,
+    lut: wgpu::ComputePipeline,
+    blend: wgpu::ComputePipeline,
+    max_binding_size: u64,
+    adapter_name: String,
+}
+
+static SHARED: OnceLock<Result<GpuContext, GpuError>> = OnceLock::new();
+
+impl GpuContext {
+    /// Open the default high-performance adapter and compile all shaders.
+    pub fn new() -> Result<Self, GpuError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    /// Process-wide context, created on first use.
+    pub fn shared() -> Result<&'static GpuContext, GpuError> {
+        SHARED.get_or_init(GpuContext::new).as_ref().map_err(Clone::clone)
+    }
+
+    async fn new_async() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| GpuError::Unavailable(e.to_string()))?;
+        let limits = adapter.limits();
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("imagestag"),
+                required_limits: limits.clone(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| GpuError::Unavailable(e.to_string()))?;
+
+        let pipeline = |label: &str, source: &str| {
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+
+        Ok(GpuContext {
+            blur: pipeline("blur", include_str!("shaders/blur.wgsl")),
+            convolve: pipeline("convolve", include_str!("shaders/convolve.wgsl")),
+            lut: pipeline("lut", include_str!("shaders/lut.wgsl")),
+            blend: pipeline("blend", include_str!("shaders/blend.wgsl")),
+            max_binding_size: limits.max_storage_buffer_binding_size as u64,
+            adapter_name: adapter.get_info().name,
+            device,
+            queue,
+        })
+    }
+
+    /// Name of the adapter backing this context.
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    fn storage(&self, label: &str, data: &[f32]) -> wgpu::Buffer {
+        self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(label),
+            contents: bytemuck::cast_slice(data),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        })
+    }
+
+    fn output(&self, len: usize) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("output"),
+            size: (len * 4) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn check_size(&self, len: usize) -> Result<(), GpuError> {
+        if (len * 4) as u64 > self.max_binding_size {
+            return Err(GpuError::TooLarge(len / 4));
+        }
+        Ok(())
+    }
+
+    /// Record one dispatch covering the whole image.
+    fn pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::ComputePipeline,
+        params: Params,
+        buffers: [&wgpu::Buffer; 3],
+    ) {
+        self.dispatch(encoder, pipeline, bytemuck::bytes_of(&params), &buffers, params.width, params.height);
+    }
+
+    /// Record one dispatch with raw uniform bytes and bindings 1.. from `buffers`.
+    fn dispatch(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::ComputePipeline,
+        uniforms: &[u8],
+        buffers: &[&wgpu::Buffer],
+        width: u32,
+        height: u32,
+    ) {
+        let uniform = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("params"),
+            contents: uniforms,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let mut entries = vec![wgpu::BindGroupEntry { binding: 0, resource: uniform.as_entire_binding() }];
+        entries.extend(buffers.iter().enumerate().map(|(i, buffer)| wgpu::BindGroupEntry {
+            binding: i as u32 + 1,
+            resource: buffer.as_entire_binding(),
+        }));
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(width.div_ceil(WORKGROUP_SIZE), height.div_ceil(WORKGROUP_SIZE), 1);
+    }
+
+    /// Submit the encoder, copy `result` back and wait for it.
+    fn finish(&self, mut encoder: wgpu::CommandEncoder, result: &wgpu::Buffer, len: usize) -> Result<Vec<f32>, GpuError> {
+        let size = (len * 4) as u64;
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_buffer_to_buffer(result, 0, &staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |status| {
+            let _ = sender.send(status);
+        });
+        self.device
+            .poll(wgpu::PollType::Wait)
+            .map_err(|e| GpuError::Unavailable(e.to_string()))?;
+        receiver
+            .recv()
+            .map_err(|e| GpuError::Unavailable(e.to_string()))?
+            .map_err(|e| GpuError::Unavailable(e.to_string()))?;
+        let data = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        staging.unmap();
+        Ok(data)
+    }
+
+    fn params(width: usize, height: usize) -> Params {
+        Params { width: width as u32, height: height as u32, ..Default::default() }
+    }
+
+    fn encoder(&self) -> wgpu::CommandEncoder {
+        self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+    }
+
+    /// Two-pass Gaussian blur of RGBA pixels.
+    pub fn gaussian_blur(&self, rgba: &[f32], width: usize, height: usize, kernel: &[f32]) -> Result<Vec<f32>, GpuError> {
+        self.check_size(rgba.len())?;
+        let src = self.storage("src", rgba);
+        let temp = self.output(rgba.len());
+        let dst = self.output(rgba.len());
+        let weights = self.storage("weights", kernel);
+        let params = Params { kernel_width: kernel.len() as u32, ..Self::params(width, height) };
+
+        let mut encoder = self.encoder();
+        self.pass(&mut encoder, &self.blur, Params { mode: 0, ..params }, [&src, &temp, &weights]);
+        self.pass(&mut encoder, &self.blur, Params { mode: 1, ..params }, [&temp, &dst, &weights]);
+        self.finish(encoder, &dst, rgba.len())
+    }
+
+    /// Convolve RGBA pixels with a row-major kernel.
+    pub fn convolve(
+        &self,
+        rgba: &[f32],
+        width: usize,
+        height: usize,
+        kernel: &[f32],
+        kernel_width: usize,
+        kernel_height: usize,
+    ) -> Result<Vec<f32>, GpuError> {
+        self.check_size(rgba.len())?;
+        let src = self.storage("src", rgba);
+        let dst = self.output(rgba.len());
+        let weights = self.storage("weights", kernel);
+        let params = Params {
+            kernel_width: kernel_width as u32,
+            kernel_height: kernel_height as u32,
+            ..Self::params(width, height)
+        };
+
+        let mut encoder = self.encoder();
+        self.pass(&mut encoder, &self.convolve, params, [&src, &dst, &weights]);
+        self.finish(encoder, &dst, rgba.len())
+    }
+
+    /// Map RGBA color channels through a lookup table.
+    pub fn apply_lut(&self, rgba: &[f32], width: usize, height: usize, table: &[f32]) -> Result<Vec<f32>, GpuError> {
+        self.check_size(rgba.len())?;
+        let src = self.storage("src", rgba);
+        let dst = self.output(rgba.len());
+        let lut = self.storage("lut", table);
+        let params = Params { kernel_width: table.len() as u32, ..Self::params(width, height) };
+
+        let mut encoder = self.encoder();
+        self.pass(&mut encoder, &self.lut, params, [&src, &dst, &lut]);
+        self.finish(encoder, &dst, rgba.len())
+    }
+
+    /// Composite RGBA `top` over `base`.
+    pub fn blend(
+        &self,
+        base: &[f32],
+        top: &[f32],
+        width: usize,
+        height: usize,
+        mode: u32,
+        opacity: f32,
+    ) -> Result<Vec<f32>, GpuError> {
+        self.check_size(base.len())?;
+        let src = self.storage("base", base);
+        let layer = self.storage("top", top);
+        let dst = self.output(base.len());
+        let params = Params { mode, opacity, ..Self::params(width, height) };
+
+        let mut encoder = self.encoder();
+        self.pass(&mut encoder, &self.blend, params, [&src, &dst, &layer]);
+        self.finish(encoder, &dst, base.len())
+    }
+
+    /// Run exported WGSL passes (see [`super::wgsl`]) on RGBA pixels.
+    pub fn run_export(
+        &self,
+        export: &super::wgsl::ShaderExport,
+        rgba: &[f32],
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<f32>, GpuError> {
+        self.check_size(rgba.len())?;
+        let mut current = self.storage("src", rgba);
+        let mut encoder = self.encoder();
+        for shader in &export.passes {
+            let module = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(&shader.label),
+                source: wgpu::ShaderSource::Wgsl(shader.wgsl.as_str().into()),
+            });
+            let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(&shader.label),
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+            let dst = self.output(rgba.len());
+            let uniforms = shader.uniform_bytes(width as u32, height as u32);
+            let weights = shader.weights.as_ref().map(|w| self.storage("weights", w));
+            let mut buffers = vec![&current, &dst];
+            buffers.extend(weights.as_ref());
+            self.dispatch(&mut encoder, &pipeline, &uniforms, &buffers, width as u32, height as u32);
+            current = dst;
+        }
+        self.finish(encoder, &current, rgba.len())
+    }
+}
+
+// ============================================================================
+// Shared-context entry points
+// ============================================================================
+
+/// Whether the shared context could be created.
+pub fn available() -> bool {
+    GpuContext::shared().is_ok()
+}
+
+/// Gaussian blur on the shared context.
+pub fn gaussian_blur(rgba: &[f32], width: usize, height: usize, kernel: &[f32]) -> Result<Vec<f32>, GpuError> {
+    GpuContext::shared()?.gaussian_blur(rgba, width, height, kernel)
+}
+
+/// Convolution on the shared context.
+pub fn convolve(
+    rgba: &[f32],
+    width: usize,
+    height: usize,
+    kernel: &[f32],
+    kernel_width: usize,
+    kernel_height: usize,
+) -> Result<Vec<f32>, GpuError> {
+    GpuContext::shared()?.convolve(rgba, width, height, kernel, kernel_width, kernel_height)
+}
+
+/// Lookup table on the shared context.
+pub fn apply_lut(rgba: &[f32], width: usize, height: usize, table: &[f32]) -> Result<Vec<f32>, GpuError> {
+    GpuContext::shared()?.apply_lut(rgba, width, height, table)
+}
+
+/// Blend compositing on the shared context.
+pub fn blend(base: &[f32], top: &[f32], width: usize, height: usize, mode: u32, opacity: f32) -> Result<Vec<f32>, GpuError> {
+    GpuContext::shared()?.blend(base, top, width, height, mode, opacity)
+}
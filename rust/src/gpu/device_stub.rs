@@ -0,0 +1,37 @@
+//! Stand-in for `device` when built without the `gpu` feature.
+//!
+//! Every entry point reports `GpuError::Unavailable`, so `Backend::Auto`
+//! always takes the CPU path and `Backend::Gpu` returns an error.
+
+use super::GpuError;
+
+fn unavailable() -> GpuError {
+    GpuError::Unavailable("built without the gpu feature".into())
+}
+
+pub fn available() -> bool {
+    false
+}
+
+pub fn gaussian_blur(_rgba: &[f32], _width: usize, _height: usize, _kernel: &[f32]) -> Result<Vec<f32>, GpuError> {
+    Err(unavailable())
+}
+
+pub fn convolve(
+    _rgba: &[f32],
+    _width: usize,
+    _height: usize,
+    _kernel: &[f32],
+    _kernel_width: usize,
+    _kernel_height: usize,
+) -> Result<Vec<f32>, GpuError> {
+    Err(unavailable())
+}
+
+pub fn apply_lut(_rgba: &[f32], _width: usize, _height: usize, _table: &[f32]) -> Result<Vec<f32>, GpuError> {
+    Err(unavailable())
+}
+
+pub fn blend(_base: &[f32], _top: &[f32], _width: usize, _height: usize, _mode: u32, _opacity: f32) -> Result<Vec<f32>, GpuError> {
+    Err(unavailable())
+}
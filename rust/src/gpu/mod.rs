@@ -0,0 +1,487 @@
+//! Optional GPU backend for heavy filters.
+//!
+//! Gaussian blur, convolution, levels/curves lookup tables and blend
+//! compositing run as wgpu compute shaders when the crate is built with the
+//! `gpu` feature. Every entry point takes a [`Backend`]:
+//!
+//! - **Cpu**: Always use the CPU implementation
+//! - **Gpu**: Require the GPU (error if unavailable)
+//! - **Auto**: Use the GPU for large images when an adapter is available,
+//!   otherwise (or on any GPU failure) fall back to the CPU
+//!
+//! The CPU paths share the exact math of the shaders, so switching backends
+//! does not change results beyond float rounding. All functions work on f32
+//! images (0.0-1.0) with 1, 3, or 4 channels; pixels are padded to RGBA for
+//! upload.
+//!
+//! [`wgsl`] exports filter pipelines as WGSL compute passes so a WebGPU
+//! frontend can run the same filter math in the browser.
+
+pub mod cpu;
+#[cfg(feature = "gpu")]
+pub mod device;
+#[cfg(not(feature = "gpu"))]
+#[path = "device_stub.rs"]
+pub mod device;
+pub mod wgsl;
+
+use std::fmt;
+
+use ndarray::{Array3, ArrayView2, ArrayView3};
+
+use crate::filters::{blur_wasm, levels_curves};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::{PyHostArray2, PyHostArray3};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Images with at least this many pixels use the GPU under `Backend::Auto`.
+/// Below it, upload and readback cost more than the CPU filter.
+pub const AUTO_MIN_PIXELS: usize = 1 << 20;
+
+/// Entries of the lookup tables built for levels and curves.
+const LUT_SIZE: usize = 4096;
+
+/// Error raised by the GPU backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuError {
+    /// No usable adapter or device (or built without the `gpu` feature)
+    Unavailable(String),
+    /// Image with this many pixels exceeds the device's buffer limits
+    TooLarge(usize),
+    /// Inputs have different shapes
+    ShapeMismatch,
+    /// Kernel or lookup table is empty
+    InvalidKernel,
+}
+
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuError::Unavailable(msg) => write!(f, "GPU unavailable: {}", msg),
+            GpuError::TooLarge(pixels) => write!(f, "image with {} pixels exceeds GPU buffer limits", pixels),
+            GpuError::ShapeMismatch => write!(f, "images have different shapes"),
+            GpuError::InvalidKernel => write!(f, "kernel or lookup table is empty"),
+        }
+    }
+}
+
+impl std::error::Error for GpuError {}
+
+/// Device selection for accelerated filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    Cpu,
+    Gpu,
+    #[default]
+    Auto,
+}
+
+impl Backend {
+    /// Parse "cpu", "gpu" or "auto".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cpu" => Some(Backend::Cpu),
+            "gpu" => Some(Backend::Gpu),
+            "auto" => Some(Backend::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// Separable blend modes supported by `blend_f32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    Add,
+    Difference,
+}
+
+impl BlendMode {
+    /// Parse a mode name ("normal", "multiply", ...).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "normal" => Some(BlendMode::Normal),
+            "multiply" => Some(BlendMode::Multiply),
+            "screen" => Some(BlendMode::Screen),
+            "overlay" => Some(BlendMode::Overlay),
+            "darken" => Some(BlendMode::Darken),
+            "lighten" => Some(BlendMode::Lighten),
+            "add" | "linear_dodge" => Some(BlendMode::Add),
+            "difference" => Some(BlendMode::Difference),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a GPU adapter could be opened.
+pub fn gpu_available() -> bool {
+    device::available()
+}
+
+/// Pad an image to RGBA pixels (gray is replicated, missing alpha is 1.0).
+fn to_rgba(image: ArrayView3<f32>) -> Vec<f32> {
+    let (height, width, channels) = image.dim();
+    let mut rgba = Vec::with_capacity(height * width * 4);
+    for pixel in image.rows() {
+        match channels {
+            1 => rgba.extend_from_slice(&[pixel[0], pixel[0], pixel[0], 1.0]),
+            3 => rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 1.0]),
+            _ => rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], pixel[3]]),
+        }
+    }
+    rgba
+}
+
+/// Drop the padding added by `to_rgba`.
+fn from_rgba(rgba: &[f32], height: usize, width: usize, channels: usize) -> Array3<f32> {
+    Array3::from_shape_fn((height, width, channels), |(y, x, c)| rgba[(y * width + x) * 4 + c])
+}
+
+/// Run `gpu` on the RGBA-padded image if the backend selects the GPU,
+/// otherwise (or on failure under `Auto`) run `cpu`.
+fn dispatch(
+    image: ArrayView3<f32>,
+    backend: Backend,
+    gpu: impl FnOnce(&[f32]) -> Result<Vec<f32>, GpuError>,
+    cpu: impl FnOnce() -> Array3<f32>,
+) -> Result<Array3<f32>, GpuError> {
+    let (height, width, channels) = image.dim();
+    let use_gpu = match backend {
+        Backend::Cpu => false,
+        Backend::Gpu => true,
+        Backend::Auto => height * width >= AUTO_MIN_PIXELS && gpu_available(),
+    };
+    if !use_gpu || image.is_empty() {
+        return Ok(cpu());
+    }
+    match (gpu(&to_rgba(image)), backend) {
+        (Ok(rgba), _) => Ok(from_rgba(&rgba, height, width, channels)),
+        (Err(_), Backend::Auto) => Ok(cpu()),
+        (Err(e), _) => Err(e),
+    }
+}
+
+/// Gaussian blur on the selected backend.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `sigma` - Blur radius (standard deviation)
+/// * `backend` - Device selection
+///
+/// # Returns
+/// Blurred image (RGBA uses premultiplied alpha)
+pub fn gaussian_blur_f32(image: ArrayView3<f32>, sigma: f32, backend: Backend) -> Result<Array3<f32>, GpuError> {
+    if sigma <= 0.0 {
+        return Ok(image.to_owned());
+    }
+    let (height, width, _) = image.dim();
+    let kernel = blur_wasm::gaussian_kernel(sigma);
+    dispatch(
+        image,
+        backend,
+        |rgba| device::gaussian_blur(rgba, width, height, &kernel),
+        || blur_wasm::gaussian_blur_wasm_f32(image, sigma),
+    )
+}
+
+/// Convolve the color channels with a kernel on the selected backend.
+///
+/// Borders are clamped to the edge, alpha is kept and results are clamped
+/// to 0.0-1.0.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `kernel` - Kernel (rows, cols), centered at (rows / 2, cols / 2)
+/// * `backend` - Device selection
+pub fn convolve_f32(image: ArrayView3<f32>, kernel: ArrayView2<f32>, backend: Backend) -> Result<Array3<f32>, GpuError> {
+    if kernel.is_empty() {
+        return Err(GpuError::InvalidKernel);
+    }
+    let (height, width, _) = image.dim();
+    let (kernel_height, kernel_width) = kernel.dim();
+    let weights: Vec<f32> = kernel.iter().copied().collect();
+    dispatch(
+        image,
+        backend,
+        |rgba| device::convolve(rgba, width, height, &weights, kernel_width, kernel_height),
+        || cpu::convolve_f32(image, kernel),
+    )
+}
+
+/// Map the color channels through a lookup table on the selected backend.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `table` - Output values for evenly spaced inputs from 0.0 to 1.0
+///   (linearly interpolated, at least one entry)
+/// * `backend` - Device selection
+pub fn apply_lut_f32(image: ArrayView3<f32>, table: &[f32], backend: Backend) -> Result<Array3<f32>, GpuError> {
+    if table.is_empty() {
+        return Err(GpuError::InvalidKernel);
+    }
+    let (height, width, _) = image.dim();
+    dispatch(
+        image,
+        backend,
+        |rgba| device::apply_lut(rgba, width, height, table),
+        || cpu::apply_lut_f32(image, table),
+    )
+}
+
+/// Sample a per-value f32 filter into a lookup table.
+fn sample_lut(filter: impl Fn(ArrayView3<f32>) -> Array3<f32>) -> Vec<f32> {
+    let ramp = Array3::from_shape_fn((1, LUT_SIZE, 1), |(_, x, _)| x as f32 / (LUT_SIZE - 1) as f32);
+    filter(ramp.view()).iter().copied().collect()
+}
+
+/// Levels adjustment on the selected backend.
+///
+/// Same parameters as `filters::levels_curves::levels_f32`.
+pub fn levels_f32(
+    image: ArrayView3<f32>,
+    in_black: f32,
+    in_white: f32,
+    out_black: f32,
+    out_white: f32,
+    gamma: f32,
+    backend: Backend,
+) -> Result<Array3<f32>, GpuError> {
+    let table = sample_lut(|ramp| levels_curves::levels_f32(ramp, in_black, in_white, out_black, out_white, gamma));
+    apply_lut_f32(image, &table, backend)
+}
+
+/// Curves adjustment on the selected backend.
+///
+/// Same parameters as `filters::levels_curves::curves_f32` with the default
+/// (PCHIP, per-channel) curve.
+pub fn curves_f32(image: ArrayView3<f32>, points: &[(f32, f32)], backend: Backend) -> Result<Array3<f32>, GpuError> {
+    let table = sample_lut(|ramp| levels_curves::curves_f32(ramp, points, Default::default(), false));
+    apply_lut_f32(image, &table, backend)
+}
+
+/// Composite `top` over `base` with a blend mode on the selected backend.
+///
+/// # Arguments
+/// * `base` - Backdrop image with 1, 3, or 4 channels, values 0.0-1.0
+/// * `top` - Layer image with the same shape
+/// * `mode` - Blend mode for the color channels
+/// * `opacity` - Layer opacity (0.0-1.0)
+/// * `backend` - Device selection
+pub fn blend_f32(
+    base: ArrayView3<f32>,
+    top: ArrayView3<f32>,
+    mode: BlendMode,
+    opacity: f32,
+    backend: Backend,
+) -> Result<Array3<f32>, GpuError> {
+    if base.dim() != top.dim() {
+        return Err(GpuError::ShapeMismatch);
+    }
+    let (height, width, _) = base.dim();
+    dispatch(
+        base,
+        backend,
+        |rgba| device::blend(rgba, &to_rgba(top), width, height, mode as u32, opacity),
+        || cpu::blend_f32(base, top, mode, opacity),
+    )
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+fn parse_backend(name: &str) -> PyResult<Backend> {
+    Backend::from_name(name).ok_or_else(|| PyValueError::new_err(format!("unknown backend '{}'", name)))
+}
+
+#[cfg(feature = "python")]
+fn to_py_err(e: GpuError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Whether a GPU adapter is available.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "gpu_available")]
+pub fn gpu_available_py() -> bool {
+    gpu_available()
+}
+
+/// Gaussian blur of a float32 image on the GPU or CPU.
+///
+/// # Arguments
+/// * `image` - float32 array (H, W, C), values 0.0-1.0
+/// * `sigma` - Blur radius
+/// * `backend` - "auto", "gpu" or "cpu"
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "gpu_gaussian_blur_f32", signature = (image, sigma, backend="auto"))]
+pub fn gpu_gaussian_blur_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    sigma: f32,
+    backend: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = gaussian_blur_f32(image.as_array(), sigma, parse_backend(backend)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Convolve a float32 image with a 2D kernel on the GPU or CPU.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "gpu_convolve_f32", signature = (image, kernel, backend="auto"))]
+pub fn gpu_convolve_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    kernel: PyHostArray2<'py, f32>,
+    backend: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = convolve_f32(image.as_array(), kernel.as_array(), parse_backend(backend)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Levels adjustment of a float32 image on the GPU or CPU.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "gpu_levels_f32", signature = (image, in_black=0.0, in_white=1.0, out_black=0.0, out_white=1.0, gamma=1.0, backend="auto"))]
+pub fn gpu_levels_f32_py<'py>(
+    image: PyHostArray3<'py, f32>,
+    in_black: f32,
+    in_white: f32,
+    out_black: f32,
+    out_white: f32,
+    gamma: f32,
+    backend: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let backend = parse_backend(backend)?;
+    let result = levels_f32(image.as_array(), in_black, in_white, out_black, out_white, gamma, backend).map_err(to_py_err)?;
+    Ok(result.into_pyarray(image.py()))
+}
+
+/// Curves adjustment of a float32 image on the GPU or CPU.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "gpu_curves_f32", signature = (image, points, backend="auto"))]
+pub fn gpu_curves_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    points: Vec<(f32, f32)>,
+    backend: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = curves_f32(image.as_array(), &points, parse_backend(backend)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Composite a float32 layer over a base image on the GPU or CPU.
+///
+/// # Arguments
+/// * `base` - float32 array (H, W, C)
+/// * `top` - float32 array with the same shape
+/// * `mode` - "normal", "multiply", "screen", "overlay", "darken", "lighten", "add", "difference"
+/// * `opacity` - Layer opacity (0.0-1.0)
+/// * `backend` - "auto", "gpu" or "cpu"
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "gpu_blend_f32", signature = (base, top, mode="normal", opacity=1.0, backend="auto"))]
+pub fn gpu_blend_f32_py<'py>(
+    py: Python<'py>,
+    base: PyHostArray3<'py, f32>,
+    top: PyHostArray3<'py, f32>,
+    mode: &str,
+    opacity: f32,
+    backend: &str,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let mode = BlendMode::from_name(mode).ok_or_else(|| PyValueError::new_err(format!("unknown blend mode '{}'", mode)))?;
+    let result = blend_f32(base.as_array(), top.as_array(), mode, opacity, parse_backend(backend)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(channels: usize) -> Array3<f32> {
+        Array3::from_shape_fn((24, 31, channels), |(y, x, c)| ((x * 7 + y * 3 + c * 5) % 17) as f32 / 16.0)
+    }
+
+    fn max_diff(a: &Array3<f32>, b: &Array3<f32>) -> f32 {
+        a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_auto_small_image_uses_cpu() {
+        let input = image(4);
+        let auto = gaussian_blur_f32(input.view(), 2.0, Backend::Auto).unwrap();
+        assert_eq!(auto, blur_wasm::gaussian_blur_wasm_f32(input.view(), 2.0));
+        assert_eq!(Backend::from_name("GPU"), Some(Backend::Gpu));
+        assert_eq!(BlendMode::from_name("linear_dodge"), Some(BlendMode::Add));
+    }
+
+    #[test]
+    fn test_levels_lut_matches_direct_filter() {
+        let input = image(3);
+        let via_lut = levels_f32(input.view(), 0.1, 0.9, 0.0, 1.0, 1.4, Backend::Cpu).unwrap();
+        let direct = levels_curves::levels_f32(input.view(), 0.1, 0.9, 0.0, 1.0, 1.4);
+        assert!(max_diff(&via_lut, &direct) < 1e-3);
+    }
+
+    #[test]
+    fn test_shape_and_kernel_errors() {
+        let a = image(4);
+        let b = image(3);
+        assert_eq!(blend_f32(a.view(), b.view(), BlendMode::Normal, 1.0, Backend::Cpu), Err(GpuError::ShapeMismatch));
+        assert_eq!(apply_lut_f32(a.view(), &[], Backend::Cpu), Err(GpuError::InvalidKernel));
+    }
+
+    #[cfg(not(feature = "gpu"))]
+    #[test]
+    fn test_gpu_backend_requires_feature() {
+        let input = image(1);
+        assert!(matches!(gaussian_blur_f32(input.view(), 1.0, Backend::Gpu), Err(GpuError::Unavailable(_))));
+    }
+
+    /// Compares every shader with its CPU counterpart (skipped without an adapter).
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn test_gpu_matches_cpu() {
+        if !gpu_available() {
+            return;
+        }
+        for channels in [1, 3, 4] {
+            let input = image(channels);
+            let blur_gpu = gaussian_blur_f32(input.view(), 1.5, Backend::Gpu).unwrap();
+            let blur_cpu = gaussian_blur_f32(input.view(), 1.5, Backend::Cpu).unwrap();
+            assert!(max_diff(&blur_gpu, &blur_cpu) < 1e-4);
+
+            let kernel = ndarray::arr2(&[[0.0, -1.0, 0.0], [-1.0, 5.0, -1.0], [0.0, -1.0, 0.0]]);
+            let conv_gpu = convolve_f32(input.view(), kernel.view(), Backend::Gpu).unwrap();
+            let conv_cpu = convolve_f32(input.view(), kernel.view(), Backend::Cpu).unwrap();
+            assert!(max_diff(&conv_gpu, &conv_cpu) < 1e-4);
+
+            let points = [(0.0, 0.0), (0.3, 0.5), (1.0, 1.0)];
+            let curves_gpu = curves_f32(input.view(), &points, Backend::Gpu).unwrap();
+            let curves_cpu = curves_f32(input.view(), &points, Backend::Cpu).unwrap();
+            assert!(max_diff(&curves_gpu, &curves_cpu) < 1e-4);
+
+            let top = input.mapv(|v| 1.0 - v);
+            for mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Overlay, BlendMode::Difference] {
+                let blend_gpu = blend_f32(input.view(), top.view(), mode, 0.7, Backend::Gpu).unwrap();
+                let blend_cpu = blend_f32(input.view(), top.view(), mode, 0.7, Backend::Cpu).unwrap();
+                assert!(max_diff(&blend_gpu, &blend_cpu) < 1e-4, "{:?}", mode);
+            }
+        }
+    }
+}
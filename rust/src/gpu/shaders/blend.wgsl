@@ -0,0 +1,53 @@
+// Separable blend modes with W3C source-over compositing.
+//
+// Mode numbers follow gpu::BlendMode; the top layer's alpha is scaled by
+// the opacity parameter.
+
+struct Params {
+    width: u32,
+    height: u32,
+    kernel_width: u32,
+    kernel_height: u32,
+    mode: u32,
+    flags: u32,
+    opacity: f32,
+    pad: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> src: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> dst: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> layer: array<vec4<f32>>;
+
+fn blend(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    switch params.mode {
+        case 1u: { return cb * cs; }
+        case 2u: { return cb + cs - cb * cs; }
+        case 3u: {
+            return select(1.0 - 2.0 * (1.0 - cb) * (1.0 - cs), 2.0 * cb * cs, cb <= vec3<f32>(0.5));
+        }
+        case 4u: { return min(cb, cs); }
+        case 5u: { return max(cb, cs); }
+        case 6u: { return min(cb + cs, vec3<f32>(1.0)); }
+        case 7u: { return abs(cb - cs); }
+        default: { return cs; }
+    }
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+    let index = id.y * params.width + id.x;
+    let b = src[index];
+    let s = layer[index];
+    let alpha_s = s.a * params.opacity;
+    let mixed = (1.0 - b.a) * s.rgb + b.a * blend(b.rgb, s.rgb);
+    let alpha = alpha_s + b.a * (1.0 - alpha_s);
+    var rgb = vec3<f32>(0.0);
+    if (alpha > 0.0) {
+        rgb = clamp((alpha_s * mixed + b.a * (1.0 - alpha_s) * b.rgb) / alpha, vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+    dst[index] = vec4<f32>(rgb, alpha);
+}
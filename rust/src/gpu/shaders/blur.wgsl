@@ -0,0 +1,54 @@
+// Separable Gaussian blur, one direction per dispatch.
+//
+// Pass 0 (horizontal) reads straight RGBA and writes premultiplied sums;
+// pass 1 (vertical) reads those sums and writes straight RGBA, matching
+// filters::blur_wasm::gaussian_blur_wasm_f32.
+
+struct Params {
+    width: u32,
+    height: u32,
+    kernel_width: u32,
+    kernel_height: u32,
+    mode: u32,
+    flags: u32,
+    opacity: f32,
+    pad: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> src: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> dst: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> weights: array<f32>;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+    let w = i32(params.width);
+    let h = i32(params.height);
+    let x = i32(id.x);
+    let y = i32(id.y);
+    let taps = i32(params.kernel_width);
+    let half = taps / 2;
+    var sum = vec4<f32>(0.0);
+
+    if (params.mode == 0u) {
+        for (var k = 0; k < taps; k++) {
+            let sx = clamp(x + k - half, 0, w - 1);
+            let p = src[y * w + sx];
+            sum += vec4<f32>(p.rgb * p.a, p.a) * weights[k];
+        }
+        dst[y * w + x] = sum;
+    } else {
+        for (var k = 0; k < taps; k++) {
+            let sy = clamp(y + k - half, 0, h - 1);
+            sum += src[sy * w + x] * weights[k];
+        }
+        var rgb = vec3<f32>(0.0);
+        if (sum.a > 0.001) {
+            rgb = clamp(sum.rgb / sum.a, vec3<f32>(0.0), vec3<f32>(1.0));
+        }
+        dst[y * w + x] = vec4<f32>(rgb, clamp(sum.a, 0.0, 1.0));
+    }
+}
@@ -0,0 +1,44 @@
+// 2D convolution of the color channels with a user kernel.
+//
+// Borders are clamped to the edge, alpha is passed through and results
+// are clamped to 0.0-1.0 (see gpu::cpu::convolve_f32).
+
+struct Params {
+    width: u32,
+    height: u32,
+    kernel_width: u32,
+    kernel_height: u32,
+    mode: u32,
+    flags: u32,
+    opacity: f32,
+    pad: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> src: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> dst: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> weights: array<f32>;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+    let w = i32(params.width);
+    let h = i32(params.height);
+    let x = i32(id.x);
+    let y = i32(id.y);
+    let kw = i32(params.kernel_width);
+    let kh = i32(params.kernel_height);
+    var sum = vec3<f32>(0.0);
+
+    for (var ky = 0; ky < kh; ky++) {
+        let sy = clamp(y + ky - kh / 2, 0, h - 1);
+        for (var kx = 0; kx < kw; kx++) {
+            let sx = clamp(x + kx - kw / 2, 0, w - 1);
+            sum += src[sy * w + sx].rgb * weights[ky * kw + kx];
+        }
+    }
+    let center = src[y * w + x];
+    dst[y * w + x] = vec4<f32>(clamp(sum, vec3<f32>(0.0), vec3<f32>(1.0)), center.a);
+}
@@ -0,0 +1,38 @@
+// Per-channel lookup table with linear interpolation (levels, curves).
+//
+// The table samples the 0.0-1.0 input range at kernel_width evenly spaced
+// points; alpha is passed through.
+
+struct Params {
+    width: u32,
+    height: u32,
+    kernel_width: u32,
+    kernel_height: u32,
+    mode: u32,
+    flags: u32,
+    opacity: f32,
+    pad: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> src: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> dst: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> table: array<f32>;
+
+fn lookup(v: f32) -> f32 {
+    let last = f32(params.kernel_width - 1u);
+    let pos = clamp(v, 0.0, 1.0) * last;
+    let i = u32(floor(pos));
+    let j = min(i + 1u, params.kernel_width - 1u);
+    return mix(table[i], table[j], pos - f32(i));
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+    let index = id.y * params.width + id.x;
+    let p = src[index];
+    dst[index] = vec4<f32>(lookup(p.r), lookup(p.g), lookup(p.b), p.a);
+}
@@ -0,0 +1,412 @@
+//! WGSL export of filter pipelines for WebGPU frontends.
+//!
+//! Turns a parsed [`FilterSpec`] pipeline into compute passes that a
+//! browser can run with WebGPU, so the frontend previews exactly the math
+//! of the Rust filters:
+//!
+//! - Consecutive point operations (brightness, curves-like adjustments,
+//!   color effects) are fused into a single generated shader
+//! - Gaussian blur becomes the two passes of `shaders/blur.wgsl`, the same
+//!   shader the native GPU backend runs
+//!
+//! ## Pass Interface
+//!
+//! Every pass is dispatched with 16×16 workgroups over the image and uses:
+//!
+//! | Binding | Type | Content |
+//! |---------|------|---------|
+//! | 0 | uniform | Parameter block described by `uniforms` |
+//! | 1 | `array<vec4<f32>>`, read | Source RGBA pixels (0.0-1.0) |
+//! | 2 | `array<vec4<f32>>`, read_write | Destination RGBA pixels |
+//! | 3 | `array<f32>`, read | Kernel weights (only if `weights` is set) |
+//!
+//! The `width` and `height` uniforms are filled in at dispatch time; all
+//! other uniforms carry the filter parameters and may be changed live.
+
+use std::fmt;
+
+use crate::batch::json::json_string;
+use crate::batch::FilterSpec;
+use crate::filters::blur_wasm;
+
+/// Workgroup size of every exported pass (x and y).
+pub const WORKGROUP_SIZE: u32 = 16;
+
+const BLUR_SHADER: &str = include_str!("shaders/blur.wgsl");
+
+/// Error raised when a pipeline cannot be expressed as shaders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportError {
+    /// Filter has no shader implementation
+    Unsupported(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Unsupported(name) => write!(f, "filter '{}' cannot be exported to WGSL", name),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+/// Value of one uniform field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UniformValue {
+    U32(u32),
+    F32(f32),
+}
+
+/// One field of a pass's uniform block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformField {
+    pub name: String,
+    /// Byte offset in the uniform buffer
+    pub offset: usize,
+    pub value: UniformValue,
+}
+
+/// One compute pass of an exported pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderPass {
+    pub label: String,
+    /// WGSL source with entry point `main`
+    pub wgsl: String,
+    pub uniforms: Vec<UniformField>,
+    /// Uniform buffer size in bytes (multiple of 16)
+    pub uniform_size: usize,
+    /// Contents of binding 3, if the pass uses it
+    pub weights: Option<Vec<f32>>,
+}
+
+impl ShaderPass {
+    /// Uniform buffer contents for an image of the given size.
+    pub fn uniform_bytes(&self, width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = vec![0u8; self.uniform_size];
+        for field in &self.uniforms {
+            let raw = match (field.name.as_str(), field.value) {
+                ("width", _) => width.to_le_bytes(),
+                ("height", _) => height.to_le_bytes(),
+                (_, UniformValue::U32(v)) => v.to_le_bytes(),
+                (_, UniformValue::F32(v)) => v.to_le_bytes(),
+            };
+            bytes[field.offset..field.offset + 4].copy_from_slice(&raw);
+        }
+        bytes
+    }
+}
+
+/// Shader passes implementing a pipeline, in dispatch order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ShaderExport {
+    pub passes: Vec<ShaderPass>,
+}
+
+impl ShaderExport {
+    /// Serialize for the JavaScript frontend.
+    ///
+    /// Shape: `{"workgroup_size": 16, "passes": [{"label", "wgsl",
+    /// "uniform_size", "uniforms": [{"name", "offset", "type", "value"}],
+    /// "weights": [..] | null}]}`
+    pub fn to_json(&self) -> String {
+        let passes: Vec<String> = self
+            .passes
+            .iter()
+            .map(|pass| {
+                let uniforms: Vec<String> = pass
+                    .uniforms
+                    .iter()
+                    .map(|field| {
+                        let (kind, value) = match field.value {
+                            UniformValue::U32(v) => ("u32", v.to_string()),
+                            UniformValue::F32(v) => ("f32", format!("{:?}", v)),
+                        };
+                        format!(
+                            "{{\"name\":{},\"offset\":{},\"type\":\"{}\",\"value\":{}}}",
+                            json_string(&field.name),
+                            field.offset,
+                            kind,
+                            value
+                        )
+                    })
+                    .collect();
+                let weights = match &pass.weights {
+                    Some(weights) => {
+                        format!("[{}]", weights.iter().map(|w| format!("{:?}", w)).collect::<Vec<_>>().join(","))
+                    }
+                    None => "null".to_string(),
+                };
+                format!(
+                    "{{\"label\":{},\"wgsl\":{},\"uniform_size\":{},\"uniforms\":[{}],\"weights\":{}}}",
+                    json_string(&pass.label),
+                    json_string(&pass.wgsl),
+                    pass.uniform_size,
+                    uniforms.join(","),
+                    weights
+                )
+            })
+            .collect();
+        format!("{{\"workgroup_size\":{},\"passes\":[{}]}}", WORKGROUP_SIZE, passes.join(","))
+    }
+}
+
+/// WGSL statements of one point operation on `c` (RGBA, alpha untouched)
+/// and its uniform parameters. `u` prefixes the uniform names.
+fn point_op(spec: &FilterSpec, u: &str) -> Option<(String, Vec<(String, f32)>)> {
+    let p = |name: &str| format!("u.{}_{}", u, name);
+    let luma = "vec3<f32>(0.2126, 0.7152, 0.0722)";
+    let (code, params) = match *spec {
+        FilterSpec::Brightness { amount } => (
+            format!("c = vec4<f32>(clamp(c.rgb + {}, vec3<f32>(0.0), vec3<f32>(1.0)), c.a);", p("amount")),
+            vec![("amount", amount)],
+        ),
+        FilterSpec::Contrast { amount } => (
+            format!(
+                "{{ let f = select(1.0 + {a}, 1.0 + {a} * 3.0, {a} >= 0.0);\n        \
+                 c = vec4<f32>(clamp((c.rgb - 0.5) * f + 0.5, vec3<f32>(0.0), vec3<f32>(1.0)), c.a); }}",
+                a = p("amount")
+            ),
+            vec![("amount", amount)],
+        ),
+        FilterSpec::Saturation { amount } => (
+            format!(
+                "{{ let gray = dot({luma}, c.rgb);\n        \
+                 c = vec4<f32>(clamp(gray + (c.rgb - gray) * (1.0 + {a}), vec3<f32>(0.0), vec3<f32>(1.0)), c.a); }}",
+                luma = luma,
+                a = p("amount")
+            ),
+            vec![("amount", amount)],
+        ),
+        FilterSpec::Gamma { gamma } => (
+            format!(
+                "c = vec4<f32>(pow(clamp(c.rgb, vec3<f32>(0.0), vec3<f32>(1.0)), vec3<f32>(1.0 / max({}, 0.001))), c.a);",
+                p("gamma")
+            ),
+            vec![("gamma", gamma)],
+        ),
+        FilterSpec::Exposure { exposure, offset, gamma } => (
+            format!(
+                "{{ let v = clamp(c.rgb * exp2({e}) + {o}, vec3<f32>(0.0), vec3<f32>(1.0));\n        \
+                 c = vec4<f32>(pow(v, vec3<f32>(1.0 / max({g}, 0.001))), c.a); }}",
+                e = p("exposure"),
+                o = p("offset"),
+                g = p("gamma")
+            ),
+            vec![("exposure", exposure), ("offset", offset), ("gamma", gamma)],
+        ),
+        FilterSpec::Invert => ("c = vec4<f32>(1.0 - c.rgb, c.a);".to_string(), vec![]),
+        FilterSpec::Grayscale => (
+            format!("c = vec4<f32>(vec3<f32>(clamp(dot({}, c.rgb), 0.0, 1.0)), c.a);", luma),
+            vec![],
+        ),
+        FilterSpec::Sepia { intensity } => (
+            format!(
+                "{{ let s = min(vec3<f32>(\n            \
+                 dot(vec3<f32>(0.393, 0.769, 0.189), c.rgb),\n            \
+                 dot(vec3<f32>(0.349, 0.686, 0.168), c.rgb),\n            \
+                 dot(vec3<f32>(0.272, 0.534, 0.131), c.rgb)), vec3<f32>(1.0));\n        \
+                 c = vec4<f32>(clamp(c.rgb + (s - c.rgb) * clamp({i}, 0.0, 1.0), vec3<f32>(0.0), vec3<f32>(1.0)), c.a); }}",
+                i = p("intensity")
+            ),
+            vec![("intensity", intensity)],
+        ),
+        FilterSpec::Temperature { amount } => (
+            format!(
+                "{{ let shift = {a} * (30.0 / 255.0);\n        \
+                 c = vec4<f32>(clamp(c.r + shift, 0.0, 1.0), c.g, clamp(c.b - shift, 0.0, 1.0), c.a); }}",
+                a = p("amount")
+            ),
+            vec![("amount", amount)],
+        ),
+        FilterSpec::Posterize { levels } => (
+            format!(
+                "{{ let d = 1.0 / max({l}, 2.0);\n        \
+                 c = vec4<f32>(min(floor(clamp(c.rgb, vec3<f32>(0.0), vec3<f32>(1.0)) / d) * d, vec3<f32>(1.0 - d)), c.a); }}",
+                l = p("levels")
+            ),
+            vec![("levels", levels as f32)],
+        ),
+        FilterSpec::Solarize { threshold } => (
+            format!("c = vec4<f32>(select(c.rgb, 1.0 - c.rgb, c.rgb > vec3<f32>({})), c.a);", p("threshold")),
+            vec![("threshold", threshold as f32 / 255.0)],
+        ),
+        FilterSpec::Threshold { threshold } => (
+            format!("c = vec4<f32>(vec3<f32>(select(0.0, 1.0, dot({}, c.rgb) >= {})), c.a);", luma, p("threshold")),
+            vec![("threshold", threshold as f32 / 255.0)],
+        ),
+        _ => return None,
+    };
+    Some((code, params.into_iter().map(|(name, value)| (format!("{}_{}", u, name), value)).collect()))
+}
+
+/// Generate one fused pass for a run of point operations.
+fn fused_pass(steps: &[&FilterSpec], first_index: usize) -> ShaderPass {
+    let mut body = Vec::new();
+    let mut params = Vec::new();
+    for (i, spec) in steps.iter().enumerate() {
+        let (code, step_params) = point_op(spec, &format!("s{}", first_index + i)).expect("point operation");
+        body.push(format!("    // {:?}\n    {}", spec, code));
+        params.extend(step_params);
+    }
+
+    let mut uniforms = vec![
+        UniformField { name: "width".into(), offset: 0, value: UniformValue::U32(0) },
+        UniformField { name: "height".into(), offset: 4, value: UniformValue::U32(0) },
+    ];
+    let mut fields = vec!["    width: u32,".to_string(), "    height: u32,".to_string()];
+    for (i, (name, value)) in params.iter().enumerate() {
+        uniforms.push(UniformField { name: name.clone(), offset: 8 + 4 * i, value: UniformValue::F32(*value) });
+        fields.push(format!("    {}: f32,", name));
+    }
+    let uniform_size = (8 + 4 * params.len()).div_ceil(16) * 16;
+
+    let wgsl = format!(
+        "// Generated by imagestag: fused point operations.\n\n\
+         struct Uniforms {{\n{fields}\n}}\n\n\
+         @group(0) @binding(0) var<uniform> u: Uniforms;\n\
+         @group(0) @binding(1) var<storage, read> src: array<vec4<f32>>;\n\
+         @group(0) @binding(2) var<storage, read_write> dst: array<vec4<f32>>;\n\n\
+         @compute @workgroup_size({wg}, {wg})\n\
+         fn main(@builtin(global_invocation_id) id: vec3<u32>) {{\n    \
+         if (id.x >= u.width || id.y >= u.height) {{\n        return;\n    }}\n    \
+         let index = id.y * u.width + id.x;\n    \
+         var c = src[index];\n\
+         {body}\n    \
+         dst[index] = c;\n\
+         }}\n",
+        fields = fields.join("\n"),
+        wg = WORKGROUP_SIZE,
+        body = body.join("\n"),
+    );
+    ShaderPass { label: "point".into(), wgsl, uniforms, uniform_size, weights: None }
+}
+
+/// One direction of the shared Gaussian blur shader.
+fn blur_pass(kernel: &[f32], vertical: bool) -> ShaderPass {
+    let field = |name: &str, offset: usize, value: UniformValue| UniformField { name: name.into(), offset, value };
+    ShaderPass {
+        label: if vertical { "blur_vertical" } else { "blur_horizontal" }.into(),
+        wgsl: BLUR_SHADER.to_string(),
+        uniforms: vec![
+            field("width", 0, UniformValue::U32(0)),
+            field("height", 4, UniformValue::U32(0)),
+            field("kernel_width", 8, UniformValue::U32(kernel.len() as u32)),
+            field("kernel_height", 12, UniformValue::U32(1)),
+            field("mode", 16, UniformValue::U32(vertical as u32)),
+            field("flags", 20, UniformValue::U32(0)),
+            field("opacity", 24, UniformValue::F32(1.0)),
+            field("pad", 28, UniformValue::F32(0.0)),
+        ],
+        uniform_size: 32,
+        weights: Some(kernel.to_vec()),
+    }
+}
+
+/// Export a pipeline as WebGPU compute passes.
+///
+/// Supports the point operations brightness, contrast, saturation, gamma,
+/// exposure, invert, grayscale, sepia, temperature, posterize, solarize and
+/// threshold, plus Gaussian blur. Passes process RGBA pixels, matching the
+/// f32 kernels on 4-channel images.
+///
+/// # Returns
+/// Passes in dispatch order (empty for an empty pipeline)
+pub fn export_wgsl(pipeline: &[FilterSpec]) -> Result<ShaderExport, ExportError> {
+    let mut export = ShaderExport::default();
+    let mut run: Vec<&FilterSpec> = Vec::new();
+    let mut run_start = 0;
+
+    for (index, spec) in pipeline.iter().enumerate() {
+        if point_op(spec, "s").is_some() {
+            if run.is_empty() {
+                run_start = index;
+            }
+            run.push(spec);
+            continue;
+        }
+        if !run.is_empty() {
+            export.passes.push(fused_pass(&run, run_start));
+            run.clear();
+        }
+        match *spec {
+            FilterSpec::Blur { sigma } if sigma <= 0.0 => {}
+            FilterSpec::Blur { sigma } => {
+                let kernel = blur_wasm::gaussian_kernel(sigma);
+                export.passes.push(blur_pass(&kernel, false));
+                export.passes.push(blur_pass(&kernel, true));
+            }
+            _ => {
+                let name = format!("{:?}", spec);
+                let name = name.split([' ', '{']).next().unwrap_or_default().to_string();
+                return Err(ExportError::Unsupported(name));
+            }
+        }
+    }
+    if !run.is_empty() {
+        export.passes.push(fused_pass(&run, run_start));
+    }
+    Ok(export)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::parse_pipeline;
+
+    #[test]
+    fn test_fuses_point_ops_around_blur() {
+        let pipeline = parse_pipeline("brightness 0.1 | contrast 0.2 | blur 1.5 | invert").unwrap();
+        let export = export_wgsl(&pipeline).unwrap();
+        let labels: Vec<&str> = export.passes.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, ["point", "blur_horizontal", "blur_vertical", "point"]);
+
+        let first = &export.passes[0];
+        assert!(first.wgsl.contains("s0_amount: f32") && first.wgsl.contains("s1_amount: f32"));
+        assert_eq!(first.uniform_size, 16);
+        let bytes = first.uniform_bytes(640, 480);
+        assert_eq!(&bytes[0..4], &640u32.to_le_bytes());
+        assert_eq!(&bytes[8..12], &0.1f32.to_le_bytes());
+        assert_eq!(export.passes[3].uniforms.len(), 2);
+    }
+
+    #[test]
+    fn test_unsupported_and_json() {
+        let pipeline = parse_pipeline("median 2").unwrap();
+        assert_eq!(export_wgsl(&pipeline), Err(ExportError::Unsupported("Median".into())));
+
+        let json = export_wgsl(&parse_pipeline("gamma 2").unwrap()).unwrap().to_json();
+        assert!(json.starts_with("{\"workgroup_size\":16,\"passes\":[{\"label\":\"point\""));
+        assert!(json.contains("\"name\":\"s0_gamma\",\"offset\":8,\"type\":\"f32\",\"value\":2.0"));
+        assert!(json.contains("\\n") && !json.contains('\n'));
+    }
+
+    /// Runs exported passes on the native device and compares with the CPU filters.
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn test_exported_passes_match_cpu() {
+        use crate::batch::apply_pipeline_f32;
+        use ndarray::Array3;
+
+        let Ok(context) = crate::gpu::device::GpuContext::shared() else {
+            return;
+        };
+        let image = Array3::from_shape_fn((19, 23, 4), |(y, x, c)| {
+            if c == 3 { 1.0 } else { ((x * 7 + y * 3 + c * 5) % 17) as f32 / 16.0 }
+        });
+        for text in [
+            "brightness 0.1 | contrast 0.3 | saturation -0.4 | gamma 1.8",
+            "exposure 0.5 0.02 1.2 | sepia 0.7 | temperature -0.5 | invert",
+            "grayscale | solarize 100 | posterize 5",
+            "threshold 120",
+            "contrast -0.3 | blur 1.5 | brightness 0.05",
+        ] {
+            let pipeline = parse_pipeline(text).unwrap();
+            let export = export_wgsl(&pipeline).unwrap();
+            let gpu = context.run_export(&export, image.as_slice().unwrap(), 23, 19).unwrap();
+            let cpu = apply_pipeline_f32(image.view(), &pipeline);
+            let diff = gpu.iter().zip(cpu.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+            assert!(diff < 1e-4, "{}: {}", text, diff);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Array arguments of the Python bindings.
+//!
+//! Bindings take their images as [`PyHostArray3`] (and the 2D / 4D
+//! aliases) instead of numpy's `PyReadonlyArray`. numpy arrays extract
+//! without a copy; CuPy arrays and other array-protocol objects are first
+//! copied to host memory with [`host_array`], so GPU-resident pipelines can
+//! call any binding without converting by hand. `Vec<PyHostArray3<T>>`
+//! converts every list element the same way.
+
+use std::ops::Deref;
+
+use numpy::ndarray::{Dimension, Ix2, Ix3, Ix4};
+use numpy::{Element, PyReadonlyArray, PyUntypedArray};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+
+/// Bring an array-protocol object into host memory as a numpy array.
+///
+/// numpy arrays, lists and tuples pass through unchanged. Device arrays
+/// exposing `__cuda_array_interface__` (CuPy) are downloaded with their
+/// `get()` method; other objects go through `numpy.from_dlpack` when they
+/// implement `__dlpack__`, and through `numpy.asarray` (`__array__`,
+/// `__array_interface__`, buffer protocol) otherwise.
+pub fn host_array<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    if obj.is_instance_of::<PyUntypedArray>() || obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        return Ok(obj.clone());
+    }
+    if obj.hasattr("__cuda_array_interface__")? && obj.hasattr("get")? {
+        return obj.call_method0("get");
+    }
+    let numpy = obj.py().import("numpy")?;
+    if obj.hasattr("__dlpack__")? {
+        if let Ok(host) = numpy.call_method1("from_dlpack", (obj,)) {
+            return Ok(host);
+        }
+    }
+    numpy.call_method1("asarray", (obj,))
+}
+
+/// Read-only numpy array argument that also accepts device and
+/// array-protocol objects.
+///
+/// Derefs to the underlying `PyReadonlyArray`. A dtype or dimension
+/// mismatch raises the same `TypeError` as numpy's own extraction.
+pub struct PyHostArray<'py, T: Element, D: Dimension>(PyReadonlyArray<'py, T, D>);
+
+/// [`PyHostArray`] of an (H, W) mask or single-channel plane.
+pub type PyHostArray2<'py, T> = PyHostArray<'py, T, Ix2>;
+/// [`PyHostArray`] of an (H, W, C) image.
+pub type PyHostArray3<'py, T> = PyHostArray<'py, T, Ix3>;
+/// [`PyHostArray`] of an (N, H, W, C) frame stack.
+pub type PyHostArray4<'py, T> = PyHostArray<'py, T, Ix4>;
+
+impl<'py, T: Element, D: Dimension> PyHostArray<'py, T, D> {
+    /// The borrowed numpy array.
+    pub fn into_inner(self) -> PyReadonlyArray<'py, T, D> {
+        self.0
+    }
+}
+
+impl<'py, T: Element, D: Dimension> Deref for PyHostArray<'py, T, D> {
+    type Target = PyReadonlyArray<'py, T, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'py, T: Element, D: Dimension> FromPyObject<'py> for PyHostArray<'py, T, D> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // numpy input takes the zero-copy path and keeps numpy's error message
+        if obj.is_instance_of::<PyUntypedArray>() {
+            return obj.extract().map(PyHostArray);
+        }
+        host_array(obj)?.extract().map(PyHostArray)
+    }
+}
+
+/// Copy an array-protocol object (CuPy, DLPack, `__array_interface__`) to a numpy array.
+///
+/// numpy arrays are returned as is. Lets Python wrappers hand device arrays
+/// to code outside the bindings that only takes numpy input.
+#[pyfunction]
+#[pyo3(name = "to_host_array")]
+pub fn to_host_array_py<'py>(array: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    host_array(array)
+}
@@ -0,0 +1,206 @@
+//! Animated GIF and APNG encoding.
+//!
+//! Assembles equally sized frames with per-frame delays into an animation
+//! that loops forever. GIF frames share one palette built by the quantize
+//! module; APNG keeps full color and alpha.
+
+use std::borrow::Cow;
+
+use gif::{DisposalMethod, Frame, Repeat};
+use ndarray::{Array3, ArrayView3};
+use png::{BitDepth, ColorType};
+
+use super::codec::CodecError;
+use super::quantize::{quantize_frames, PaletteOptions};
+
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+
+/// Validate frames and delays and return (width, height, channels).
+fn checked_frames(frames: &[Array3<u8>], delays: &[u32], max_size: usize) -> Result<(usize, usize, usize), CodecError> {
+    let first = frames.first().ok_or_else(|| CodecError::InvalidShape("no frames".into()))?;
+    if delays.len() != frames.len() {
+        return Err(CodecError::InvalidShape(format!("{} frames but {} delays", frames.len(), delays.len())));
+    }
+    let (height, width, channels) = first.dim();
+    if !matches!(channels, 1 | 3 | 4) {
+        return Err(CodecError::InvalidShape(format!("{} channels (expected 1, 3 or 4)", channels)));
+    }
+    if width == 0 || height == 0 || width > max_size || height > max_size {
+        return Err(CodecError::InvalidShape(format!("{}x{}", width, height)));
+    }
+    if let Some(frame) = frames.iter().find(|f| f.dim() != first.dim()) {
+        return Err(CodecError::InvalidShape(format!("frame size {:?} differs from {:?}", frame.dim(), first.dim())));
+    }
+    Ok((width, height, channels))
+}
+
+/// Encode frames as an animated GIF.
+///
+/// All frames are reduced to one shared palette of up to 256 colors; pixels
+/// with alpha below 128 become transparent.
+///
+/// # Arguments
+/// * `frames` - Frames (height, width, 1, 3 or 4 channels), all the same size
+/// * `delays` - Display time of each frame in milliseconds (GIF stores 10 ms steps)
+/// * `options` - Palette size and dithering
+pub fn encode_gif(frames: &[Array3<u8>], delays: &[u32], options: &PaletteOptions) -> Result<Vec<u8>, CodecError> {
+    let (width, height, _) = checked_frames(frames, delays, u16::MAX as usize)?;
+    let views: Vec<ArrayView3<u8>> = frames.iter().map(|f| f.view()).collect();
+    let indexed = quantize_frames(&views, options);
+    let palette: Vec<u8> = indexed[0].palette.iter().flatten().copied().collect();
+
+    let encode_error = |e: gif::EncodingError| CodecError::Encode(e.to_string());
+    let mut encoder = gif::Encoder::new(Vec::new(), width as u16, height as u16, &palette).map_err(encode_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(encode_error)?;
+    for (frame, &delay) in indexed.iter().zip(delays) {
+        let (indices, _) = frame.indices.as_standard_layout().into_owned().into_raw_vec_and_offset();
+        encoder
+            .write_frame(&Frame {
+                width: width as u16,
+                height: height as u16,
+                delay: ((delay + 5) / 10).min(u16::MAX as u32) as u16,
+                transparent: frame.transparent,
+                // Clear transparent areas instead of showing the previous frame through them
+                dispose: if frame.transparent.is_some() { DisposalMethod::Background } else { DisposalMethod::Keep },
+                buffer: Cow::Owned(indices),
+                ..Frame::default()
+            })
+            .map_err(encode_error)?;
+    }
+    encoder.into_inner().map_err(encode_error)
+}
+
+/// Encode frames as an animated PNG (APNG).
+///
+/// # Arguments
+/// * `frames` - Frames (height, width, 1, 3 or 4 channels), all the same size
+/// * `delays` - Display time of each frame in milliseconds
+pub fn encode_apng(frames: &[Array3<u8>], delays: &[u32]) -> Result<Vec<u8>, CodecError> {
+    let (width, height, channels) = checked_frames(frames, delays, u32::MAX as usize)?;
+    let encode_error = |e: png::EncodingError| CodecError::Encode(e.to_string());
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+    encoder.set_color(match channels {
+        1 => ColorType::Grayscale,
+        3 => ColorType::Rgb,
+        _ => ColorType::Rgba,
+    });
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0).map_err(encode_error)?;
+
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    for (frame, &delay) in frames.iter().zip(delays) {
+        // The delay fraction is 16-bit; fall back to 10 ms steps for long frames
+        let (numerator, denominator) = if delay <= u16::MAX as u32 {
+            (delay as u16, 1000)
+        } else {
+            ((delay / 10).min(u16::MAX as u32) as u16, 100)
+        };
+        writer.set_frame_delay(numerator, denominator).map_err(encode_error)?;
+        let data: Vec<u8> = frame.as_standard_layout().iter().copied().collect();
+        writer.write_image_data(&data).map_err(encode_error)?;
+    }
+    writer.finish().map_err(encode_error)?;
+    Ok(out)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Encode uint8 frames as an animated GIF.
+///
+/// # Arguments
+/// * `frames` - List of image arrays (height, width, channels), all the same size
+/// * `delays` - Display time of each frame in milliseconds
+/// * `max_colors` - Palette size 2-256
+/// * `dither` - Apply Floyd-Steinberg dithering
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_gif", signature = (frames, delays, max_colors=256, dither=true))]
+pub fn encode_gif_py<'py>(
+    py: Python<'py>,
+    frames: Vec<PyHostArray3<'py, u8>>,
+    delays: Vec<u32>,
+    max_colors: usize,
+    dither: bool,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let frames: Vec<Array3<u8>> = frames.iter().map(|f| f.as_array().to_owned()).collect();
+    let bytes = encode_gif(&frames, &delays, &PaletteOptions { max_colors, dither })
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Encode uint8 frames as an animated PNG.
+///
+/// # Arguments
+/// * `frames` - List of image arrays (height, width, channels), all the same size
+/// * `delays` - Display time of each frame in milliseconds
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_apng")]
+pub fn encode_apng_py<'py>(py: Python<'py>, frames: Vec<PyHostArray3<'py, u8>>, delays: Vec<u32>) -> PyResult<Bound<'py, PyBytes>> {
+    let frames: Vec<Array3<u8>> = frames.iter().map(|f| f.as_array().to_owned()).collect();
+    let bytes = encode_apng(&frames, &delays).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::codec::{decode_image, ImageFormat};
+
+    fn frames() -> Vec<Array3<u8>> {
+        (0..3)
+            .map(|i| Array3::from_shape_fn((4, 6, 4), |(y, x, c)| if c == 3 { 255 } else { ((x + y + i) * 30 + c * 50) as u8 }))
+            .collect()
+    }
+
+    #[test]
+    fn test_gif_frames() {
+        let bytes = encode_gif(&frames(), &[100, 100, 250], &PaletteOptions::default()).unwrap();
+        assert_eq!(&bytes[..6], b"GIF89a");
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(bytes.as_slice()).unwrap();
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (6, 4));
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, vec![10, 10, 25]);
+    }
+
+    #[test]
+    fn test_apng_first_frame() {
+        let frames = frames();
+        let bytes = encode_apng(&frames, &[40, 40, 40]).unwrap();
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes.as_slice()));
+        let reader = decoder.read_info().unwrap();
+        let animation = reader.info().animation_control.expect("acTL chunk");
+        assert_eq!((animation.num_frames, animation.num_plays), (3, 0));
+
+        // Viewers without APNG support show the first frame
+        let (decoded, meta) = decode_image(&bytes).unwrap();
+        assert_eq!(meta.format, ImageFormat::Png);
+        assert_eq!(decoded, frames[0]);
+    }
+
+    #[test]
+    fn test_invalid_frames() {
+        let mut frames = frames();
+        assert!(matches!(encode_apng(&frames, &[40]), Err(CodecError::InvalidShape(_))));
+        frames[1] = Array3::zeros((2, 2, 4));
+        assert!(matches!(encode_gif(&frames, &[1, 2, 3], &PaletteOptions::default()), Err(CodecError::InvalidShape(_))));
+        assert!(matches!(encode_apng(&[], &[]), Err(CodecError::InvalidShape(_))));
+    }
+}
@@ -0,0 +1,603 @@
+//! PNG, JPEG, WebP, QOI and TIFF codecs.
+
+use std::fmt;
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::codecs::qoi::QoiEncoder;
+use image::codecs::tiff::TiffEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageDecoder, ImageEncoder, ImageReader};
+use ndarray::Array3;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyBytes, PyDict};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Error raised while decoding or encoding an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// File format not recognized or not enabled
+    UnsupportedFormat,
+    /// Image array has an unsupported shape
+    InvalidShape(String),
+    /// Decoder error
+    Decode(String),
+    /// Encoder error
+    Encode(String),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::UnsupportedFormat => write!(f, "unsupported image format"),
+            CodecError::InvalidShape(msg) => write!(f, "invalid image shape: {}", msg),
+            CodecError::Decode(msg) => write!(f, "decode failed: {}", msg),
+            CodecError::Encode(msg) => write!(f, "encode failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Encoded file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    WebP,
+    Qoi,
+    Tiff,
+}
+
+impl ImageFormat {
+    /// Lowercase format name ("png", "jpeg", "webp", "qoi", "tiff").
+    pub fn name(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::WebP => "webp",
+            ImageFormat::Qoi => "qoi",
+            ImageFormat::Tiff => "tiff",
+        }
+    }
+
+    fn from_image(format: image::ImageFormat) -> Result<Self, CodecError> {
+        match format {
+            image::ImageFormat::Png => Ok(ImageFormat::Png),
+            image::ImageFormat::Jpeg => Ok(ImageFormat::Jpeg),
+            image::ImageFormat::WebP => Ok(ImageFormat::WebP),
+            image::ImageFormat::Qoi => Ok(ImageFormat::Qoi),
+            image::ImageFormat::Tiff => Ok(ImageFormat::Tiff),
+            _ => Err(CodecError::UnsupportedFormat),
+        }
+    }
+}
+
+/// Information about a decoded file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageMetadata {
+    pub format: ImageFormat,
+    pub width: usize,
+    pub height: usize,
+    /// Channels of the decoded array (1, 3 or 4)
+    pub channels: usize,
+    /// Bits per channel in the file
+    pub bit_depth: u8,
+    /// Whether the file has an alpha channel
+    pub has_alpha: bool,
+}
+
+/// PNG encoder options.
+#[derive(Debug, Clone)]
+pub struct PngOptions {
+    /// Deflate level 0-9 (0 = uncompressed, 9 = smallest)
+    pub compression: u8,
+    /// ICC profile to embed (iCCP chunk)
+    pub icc_profile: Option<Vec<u8>>,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self { compression: 6, icc_profile: None }
+    }
+}
+
+/// JPEG encoder options.
+#[derive(Debug, Clone)]
+pub struct JpegOptions {
+    /// Quality 1-100
+    pub quality: u8,
+    /// ICC profile to embed (APP2 segments)
+    pub icc_profile: Option<Vec<u8>>,
+}
+
+impl Default for JpegOptions {
+    fn default() -> Self {
+        Self { quality: 90, icc_profile: None }
+    }
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+type Reader<'a> = ImageReader<Cursor<&'a [u8]>>;
+
+/// Open an in-memory file and detect its format.
+fn reader(data: &[u8]) -> Result<(Reader<'_>, ImageFormat), CodecError> {
+    let reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| CodecError::Decode(e.to_string()))?;
+    let format = ImageFormat::from_image(reader.format().ok_or(CodecError::UnsupportedFormat)?)?;
+    Ok((reader, format))
+}
+
+fn metadata_for(format: ImageFormat, width: u32, height: u32, color: ExtendedColorType, channels: usize) -> ImageMetadata {
+    let channel_count = color.channel_count().max(1);
+    ImageMetadata {
+        format,
+        width: width as usize,
+        height: height as usize,
+        channels,
+        bit_depth: (color.bits_per_pixel() / channel_count as u16) as u8,
+        has_alpha: matches!(channel_count, 2 | 4),
+    }
+}
+
+/// Read format, size and color layout from the file header without decoding pixels.
+pub fn read_metadata(data: &[u8]) -> Result<ImageMetadata, CodecError> {
+    let (reader, format) = reader(data)?;
+    let decoder = reader.into_decoder().map_err(|e| CodecError::Decode(e.to_string()))?;
+    let (width, height) = decoder.dimensions();
+    let color = decoder.original_color_type();
+    let channels = match color.channel_count() {
+        1 => 1,
+        3 => 3,
+        _ => 4,
+    };
+    Ok(metadata_for(format, width, height, color, channels))
+}
+
+/// Extract the embedded ICC profile of an image file.
+///
+/// # Returns
+/// Raw profile bytes, or `None` if the file has no profile
+pub fn read_icc_profile(data: &[u8]) -> Result<Option<Vec<u8>>, CodecError> {
+    let (reader, _) = reader(data)?;
+    let mut decoder = reader.into_decoder().map_err(|e| CodecError::Decode(e.to_string()))?;
+    decoder.icc_profile().map_err(|e| CodecError::Decode(e.to_string()))
+}
+
+fn to_array<T>(pixels: Vec<T>, height: usize, width: usize, channels: usize) -> Result<Array3<T>, CodecError> {
+    Array3::from_shape_vec((height, width, channels), pixels).map_err(|e| CodecError::Decode(e.to_string()))
+}
+
+/// Decode an image file to 8 bits per channel.
+///
+/// # Returns
+/// Image array (height, width, channels) with 1, 3 or 4 channels, and the
+/// file metadata
+pub fn decode_image(data: &[u8]) -> Result<(Array3<u8>, ImageMetadata), CodecError> {
+    let (reader, format) = reader(data)?;
+    let image = reader.decode().map_err(|e| CodecError::Decode(e.to_string()))?;
+    let (width, height) = (image.width(), image.height());
+    let color: ExtendedColorType = image.color().into();
+
+    let (pixels, channels) = match image {
+        DynamicImage::ImageLuma8(buf) => (buf.into_raw(), 1),
+        DynamicImage::ImageRgb8(buf) => (buf.into_raw(), 3),
+        DynamicImage::ImageRgba8(buf) => (buf.into_raw(), 4),
+        other => match other.color().channel_count() {
+            1 => (other.to_luma8().into_raw(), 1),
+            3 => (other.to_rgb8().into_raw(), 3),
+            _ => (other.to_rgba8().into_raw(), 4),
+        },
+    };
+
+    let array = to_array(pixels, height as usize, width as usize, channels)?;
+    Ok((array, metadata_for(format, width, height, color, channels)))
+}
+
+/// Decode an image file to 16 bits per channel.
+///
+/// 8-bit files are scaled to the full 16-bit range (x257).
+///
+/// # Returns
+/// Image array (height, width, channels) with 1, 3 or 4 channels, and the
+/// file metadata
+pub fn decode_image_u16(data: &[u8]) -> Result<(Array3<u16>, ImageMetadata), CodecError> {
+    let (reader, format) = reader(data)?;
+    let image = reader.decode().map_err(|e| CodecError::Decode(e.to_string()))?;
+    let (width, height) = (image.width(), image.height());
+    let color: ExtendedColorType = image.color().into();
+
+    let (pixels, channels) = match image {
+        DynamicImage::ImageLuma16(buf) => (buf.into_raw(), 1),
+        DynamicImage::ImageRgb16(buf) => (buf.into_raw(), 3),
+        DynamicImage::ImageRgba16(buf) => (buf.into_raw(), 4),
+        other => match other.color().channel_count() {
+            1 => (other.to_luma16().into_raw(), 1),
+            3 => (other.to_rgb16().into_raw(), 3),
+            _ => (other.to_rgba16().into_raw(), 4),
+        },
+    };
+
+    let array = to_array(pixels, height as usize, width as usize, channels)?;
+    Ok((array, metadata_for(format, width, height, color, channels)))
+}
+
+/// Decode an image file to 8-bit RGBA regardless of its color layout.
+pub fn decode_image_rgba(data: &[u8]) -> Result<(Array3<u8>, ImageMetadata), CodecError> {
+    let (reader, format) = reader(data)?;
+    let image = reader.decode().map_err(|e| CodecError::Decode(e.to_string()))?;
+    let (width, height) = (image.width(), image.height());
+    let color: ExtendedColorType = image.color().into();
+    let array = to_array(image.into_rgba8().into_raw(), height as usize, width as usize, 4)?;
+    Ok((array, metadata_for(format, width, height, color, 4)))
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// Validate the image shape and return (width, height, channels).
+fn checked_size<T>(image: &Array3<T>) -> Result<(u32, u32, usize), CodecError> {
+    let (height, width, channels) = image.dim();
+    if !matches!(channels, 1 | 3 | 4) {
+        return Err(CodecError::InvalidShape(format!("{} channels (expected 1, 3 or 4)", channels)));
+    }
+    if width == 0 || height == 0 || width > u32::MAX as usize || height > u32::MAX as usize {
+        return Err(CodecError::InvalidShape(format!("{}x{}", width, height)));
+    }
+    Ok((width as u32, height as u32, channels))
+}
+
+/// Validate the image shape and return it as row-major bytes.
+fn pixel_data(image: &Array3<u8>) -> Result<(Vec<u8>, u32, u32, ExtendedColorType), CodecError> {
+    let (width, height, channels) = checked_size(image)?;
+    let color = match channels {
+        1 => ExtendedColorType::L8,
+        3 => ExtendedColorType::Rgb8,
+        _ => ExtendedColorType::Rgba8,
+    };
+    let data = image.as_standard_layout().iter().copied().collect();
+    Ok((data, width, height, color))
+}
+
+/// Encode an image as PNG.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels) with 1, 3 or 4 channels
+/// * `options` - Encoder options
+pub fn encode_png(image: &Array3<u8>, options: &PngOptions) -> Result<Vec<u8>, CodecError> {
+    let (data, width, height, color) = pixel_data(image)?;
+    let compression = match options.compression.min(9) {
+        0 => CompressionType::Uncompressed,
+        level => CompressionType::Level(level),
+    };
+    let mut out = Vec::new();
+    let mut encoder = PngEncoder::new_with_quality(&mut out, compression, FilterType::Adaptive);
+    if let Some(profile) = &options.icc_profile {
+        encoder.set_icc_profile(profile.clone()).map_err(|e| CodecError::Encode(e.to_string()))?;
+    }
+    encoder
+        .write_image(&data, width, height, color)
+        .map_err(|e| CodecError::Encode(e.to_string()))?;
+    Ok(out)
+}
+
+/// Encode an image as JPEG.
+///
+/// JPEG has no alpha channel; the alpha of RGBA input is dropped.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels) with 1, 3 or 4 channels
+/// * `options` - Encoder options
+pub fn encode_jpeg(image: &Array3<u8>, options: &JpegOptions) -> Result<Vec<u8>, CodecError> {
+    let (mut data, width, height, mut color) = pixel_data(image)?;
+    if color == ExtendedColorType::Rgba8 {
+        data = data.chunks_exact(4).flat_map(|px| [px[0], px[1], px[2]]).collect();
+        color = ExtendedColorType::Rgb8;
+    }
+    let mut out = Vec::new();
+    let mut encoder = JpegEncoder::new_with_quality(&mut out, options.quality.clamp(1, 100));
+    if let Some(profile) = &options.icc_profile {
+        encoder.set_icc_profile(profile.clone()).map_err(|e| CodecError::Encode(e.to_string()))?;
+    }
+    encoder
+        .write_image(&data, width, height, color)
+        .map_err(|e| CodecError::Encode(e.to_string()))?;
+    Ok(out)
+}
+
+/// Encode an image as lossless WebP.
+///
+/// Grayscale input is stored as RGB.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels) with 1, 3 or 4 channels
+pub fn encode_webp(image: &Array3<u8>) -> Result<Vec<u8>, CodecError> {
+    let (mut data, width, height, mut color) = pixel_data(image)?;
+    if color == ExtendedColorType::L8 {
+        data = data.iter().flat_map(|&v| [v, v, v]).collect();
+        color = ExtendedColorType::Rgb8;
+    }
+    let mut out = Vec::new();
+    WebPEncoder::new_lossless(&mut out)
+        .write_image(&data, width, height, color)
+        .map_err(|e| CodecError::Encode(e.to_string()))?;
+    Ok(out)
+}
+
+/// Encode an image as QOI (lossless, fast; suited for undo snapshots).
+///
+/// Grayscale input is stored as RGB.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels) with 1, 3 or 4 channels
+pub fn encode_qoi(image: &Array3<u8>) -> Result<Vec<u8>, CodecError> {
+    let (mut data, width, height, mut color) = pixel_data(image)?;
+    if color == ExtendedColorType::L8 {
+        data = data.iter().flat_map(|&v| [v, v, v]).collect();
+        color = ExtendedColorType::Rgb8;
+    }
+    let mut out = Vec::new();
+    QoiEncoder::new(&mut out)
+        .write_image(&data, width, height, color)
+        .map_err(|e| CodecError::Encode(e.to_string()))?;
+    Ok(out)
+}
+
+fn write_tiff(data: &[u8], width: u32, height: u32, color: ExtendedColorType) -> Result<Vec<u8>, CodecError> {
+    let mut out = Cursor::new(Vec::new());
+    TiffEncoder::new(&mut out)
+        .write_image(data, width, height, color)
+        .map_err(|e| CodecError::Encode(e.to_string()))?;
+    Ok(out.into_inner())
+}
+
+/// Encode an 8-bit image as uncompressed TIFF.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels) with 1, 3 or 4 channels
+pub fn encode_tiff(image: &Array3<u8>) -> Result<Vec<u8>, CodecError> {
+    let (data, width, height, color) = pixel_data(image)?;
+    write_tiff(&data, width, height, color)
+}
+
+/// Encode a 16-bit image as uncompressed TIFF.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels) with 1, 3 or 4 channels, 0-65535
+pub fn encode_tiff_u16(image: &Array3<u16>) -> Result<Vec<u8>, CodecError> {
+    let (width, height, channels) = checked_size(image)?;
+    let color = match channels {
+        1 => ExtendedColorType::L16,
+        3 => ExtendedColorType::Rgb16,
+        _ => ExtendedColorType::Rgba16,
+    };
+    // The encoder takes samples as native-endian bytes
+    let data: Vec<u8> = image.as_standard_layout().iter().flat_map(|v| v.to_ne_bytes()).collect();
+    write_tiff(&data, width, height, color)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+fn to_py_err(e: CodecError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Metadata as a Python dict.
+#[cfg(feature = "python")]
+fn metadata_dict<'py>(py: Python<'py>, meta: &ImageMetadata) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("format", meta.format.name())?;
+    dict.set_item("width", meta.width)?;
+    dict.set_item("height", meta.height)?;
+    dict.set_item("channels", meta.channels)?;
+    dict.set_item("bit_depth", meta.bit_depth)?;
+    dict.set_item("has_alpha", meta.has_alpha)?;
+    Ok(dict)
+}
+
+/// Decode an image file (PNG, JPEG, WebP, QOI, TIFF) to uint8.
+///
+/// # Arguments
+/// * `data` - Encoded file contents
+///
+/// # Returns
+/// Tuple of (image, metadata): uint8 array (height, width, channels) and a
+/// dict with 'format', 'width', 'height', 'channels', 'bit_depth', 'has_alpha'
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "decode_image")]
+pub fn decode_image_py<'py>(py: Python<'py>, data: Vec<u8>) -> PyResult<(Bound<'py, PyArray3<u8>>, Bound<'py, PyDict>)> {
+    let (image, meta) = decode_image(&data).map_err(to_py_err)?;
+    Ok((image.into_pyarray(py), metadata_dict(py, &meta)?))
+}
+
+/// Decode an image file to uint16 (8-bit files are scaled to 0-65535).
+///
+/// # Returns
+/// Tuple of (image, metadata) as for decode_image
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "decode_image_u16")]
+pub fn decode_image_u16_py<'py>(py: Python<'py>, data: Vec<u8>) -> PyResult<(Bound<'py, PyArray3<u16>>, Bound<'py, PyDict>)> {
+    let (image, meta) = decode_image_u16(&data).map_err(to_py_err)?;
+    Ok((image.into_pyarray(py), metadata_dict(py, &meta)?))
+}
+
+/// Encode a uint8 image (1, 3 or 4 channels) as PNG.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `compression` - Deflate level 0-9
+/// * `icc_profile` - Optional ICC profile to embed
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_png", signature = (image, compression=6, icc_profile=None))]
+pub fn encode_png_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    compression: u8,
+    icc_profile: Option<Vec<u8>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = encode_png(&image.as_array().to_owned(), &PngOptions { compression, icc_profile }).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Encode a uint8 image (1, 3 or 4 channels) as JPEG. Alpha is dropped.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `quality` - Quality 1-100
+/// * `icc_profile` - Optional ICC profile to embed
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_jpeg", signature = (image, quality=90, icc_profile=None))]
+pub fn encode_jpeg_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    quality: u8,
+    icc_profile: Option<Vec<u8>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = encode_jpeg(&image.as_array().to_owned(), &JpegOptions { quality, icc_profile }).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Encode a uint8 image (1, 3 or 4 channels) as lossless WebP.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_webp")]
+pub fn encode_webp_py<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = encode_webp(&image.as_array().to_owned()).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Encode a uint8 image (1, 3 or 4 channels) as QOI.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_qoi")]
+pub fn encode_qoi_py<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = encode_qoi(&image.as_array().to_owned()).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Encode a uint8 image (1, 3 or 4 channels) as TIFF.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_tiff")]
+pub fn encode_tiff_py<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = encode_tiff(&image.as_array().to_owned()).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Encode a uint16 image (1, 3 or 4 channels) as 16-bit TIFF.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encode_tiff_u16")]
+pub fn encode_tiff_u16_py<'py>(py: Python<'py>, image: PyHostArray3<'py, u16>) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = encode_tiff_u16(&image.as_array().to_owned()).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_image(channels: usize) -> Array3<u8> {
+        Array3::from_shape_fn((6, 5, channels), |(y, x, c)| (y * 40 + x * 10 + c * 3) as u8)
+    }
+
+    #[test]
+    fn test_png_roundtrip() {
+        for channels in [1, 3, 4] {
+            let image = test_image(channels);
+            let encoded = encode_png(&image, &PngOptions::default()).unwrap();
+            let (decoded, meta) = decode_image(&encoded).unwrap();
+            assert_eq!(decoded, image);
+            assert_eq!(meta.format, ImageFormat::Png);
+            assert_eq!((meta.width, meta.height, meta.channels), (5, 6, channels));
+            assert_eq!(meta.has_alpha, channels == 4);
+            assert_eq!(meta.bit_depth, 8);
+        }
+    }
+
+    #[test]
+    fn test_webp_lossless_roundtrip() {
+        let image = test_image(4);
+        let (decoded, meta) = decode_image(&encode_webp(&image).unwrap()).unwrap();
+        assert_eq!(meta.format, ImageFormat::WebP);
+        assert_eq!(decoded, image);
+    }
+
+    #[test]
+    fn test_jpeg_drops_alpha() {
+        let image = Array3::from_elem((8, 8, 4), 128u8);
+        let encoded = encode_jpeg(&image, &JpegOptions { quality: 95, ..Default::default() }).unwrap();
+        let meta = read_metadata(&encoded).unwrap();
+        assert_eq!(meta.format, ImageFormat::Jpeg);
+        assert!(!meta.has_alpha);
+        let (decoded, _) = decode_image_rgba(&encoded).unwrap();
+        assert_eq!(decoded.dim(), (8, 8, 4));
+        assert!(decoded.iter().step_by(4).all(|&v| (v as i32 - 128).abs() <= 2));
+    }
+
+    #[test]
+    fn test_icc_profile_embedding() {
+        let image = test_image(3);
+        let profile = vec![7u8; 200];
+        let png = encode_png(&image, &PngOptions { icc_profile: Some(profile.clone()), ..Default::default() }).unwrap();
+        assert_eq!(read_icc_profile(&png).unwrap(), Some(profile.clone()));
+        let jpeg = encode_jpeg(&image, &JpegOptions { icc_profile: Some(profile.clone()), ..Default::default() }).unwrap();
+        assert_eq!(read_icc_profile(&jpeg).unwrap(), Some(profile));
+        assert_eq!(read_icc_profile(&encode_qoi(&image).unwrap()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(decode_image(b"not an image").unwrap_err(), CodecError::UnsupportedFormat);
+        let two_channels = Array3::<u8>::zeros((2, 2, 2));
+        assert!(matches!(encode_png(&two_channels, &PngOptions::default()), Err(CodecError::InvalidShape(_))));
+    }
+
+    #[test]
+    fn test_qoi_roundtrip() {
+        let image = test_image(4);
+        let encoded = encode_qoi(&image).unwrap();
+        let (decoded, meta) = decode_image(&encoded).unwrap();
+        assert_eq!(meta.format, ImageFormat::Qoi);
+        assert_eq!(decoded, image);
+    }
+
+    #[test]
+    fn test_tiff_16_bit_roundtrip() {
+        let image = Array3::from_shape_fn((4, 3, 3), |(y, x, c)| (y * 10000 + x * 1000 + c * 7) as u16);
+        let encoded = encode_tiff_u16(&image).unwrap();
+        let (decoded, meta) = decode_image_u16(&encoded).unwrap();
+        assert_eq!(meta.format, ImageFormat::Tiff);
+        assert_eq!(meta.bit_depth, 16);
+        assert_eq!(decoded, image);
+
+        // 8-bit files are scaled to the 16-bit range
+        let (scaled, _) = decode_image_u16(&encode_tiff(&Array3::from_elem((2, 2, 1), 255u8)).unwrap()).unwrap();
+        assert!(scaled.iter().all(|&v| v == 65535));
+    }
+}
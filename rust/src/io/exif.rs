@@ -0,0 +1,335 @@
+//! EXIF metadata and orientation.
+//!
+//! Reads the EXIF block of JPEG, TIFF, PNG and WebP files and applies the
+//! EXIF orientation tag to decoded pixels, so photos taken with a rotated
+//! camera display upright.
+
+use std::io::Cursor;
+
+use exif::{Exif, In, Tag, Value};
+use ndarray::{Array3, ArrayView3};
+
+use crate::filters::rotate::{
+    flip_horizontal_f32, flip_horizontal_u8, flip_vertical_f32, flip_vertical_u8, rotate_180_f32, rotate_180_u8,
+    rotate_270_cw_f32, rotate_270_cw_u8, rotate_90_cw_f32, rotate_90_cw_u8,
+};
+
+use super::codec::CodecError;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Selected EXIF fields of the primary image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExifData {
+    /// EXIF orientation 1-8 (1 = upright)
+    pub orientation: u16,
+    /// Horizontal and vertical resolution in dots per inch
+    pub dpi: Option<(f32, f32)>,
+    /// Last modification time ("YYYY:MM:DD HH:MM:SS")
+    pub date_time: Option<String>,
+    /// Time the photo was taken
+    pub date_time_original: Option<String>,
+    /// Time the photo was digitized
+    pub date_time_digitized: Option<String>,
+    /// Camera manufacturer
+    pub make: Option<String>,
+    /// Camera model
+    pub model: Option<String>,
+    /// Lens model
+    pub lens_model: Option<String>,
+    /// Software that wrote the file
+    pub software: Option<String>,
+    /// Exposure time in seconds
+    pub exposure_time: Option<f32>,
+    /// Aperture f-number
+    pub f_number: Option<f32>,
+    /// Focal length in millimeters
+    pub focal_length: Option<f32>,
+    /// ISO speed
+    pub iso: Option<u32>,
+}
+
+impl Default for ExifData {
+    fn default() -> Self {
+        Self {
+            orientation: 1,
+            dpi: None,
+            date_time: None,
+            date_time_original: None,
+            date_time_digitized: None,
+            make: None,
+            model: None,
+            lens_model: None,
+            software: None,
+            exposure_time: None,
+            f_number: None,
+            focal_length: None,
+            iso: None,
+        }
+    }
+}
+
+// ============================================================================
+// Reading
+// ============================================================================
+
+fn text(exif: &Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(parts) => {
+            let bytes = parts.first()?;
+            let value = String::from_utf8_lossy(bytes).trim_end_matches('\0').trim().to_string();
+            (!value.is_empty()).then_some(value)
+        }
+        _ => None,
+    }
+}
+
+fn number(exif: &Exif, tag: Tag) -> Option<f32> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(values) => values.first().filter(|r| r.denom != 0).map(|r| r.to_f64() as f32),
+        Value::SRational(values) => values.first().filter(|r| r.denom != 0).map(|r| r.to_f64() as f32),
+        value => value.get_uint(0).map(|v| v as f32),
+    }
+}
+
+fn integer(exif: &Exif, tag: Tag) -> Option<u32> {
+    exif.get_field(tag, In::PRIMARY)?.value.get_uint(0)
+}
+
+/// Read the EXIF metadata of an encoded image.
+///
+/// # Arguments
+/// * `data` - Encoded JPEG, TIFF, PNG or WebP file
+///
+/// # Returns
+/// `None` if the file carries no EXIF block
+pub fn read_exif(data: &[u8]) -> Result<Option<ExifData>, CodecError> {
+    let exif = match exif::Reader::new().read_from_container(&mut Cursor::new(data)) {
+        Ok(exif) => exif,
+        Err(exif::Error::NotFound(_)) => return Ok(None),
+        Err(e) => return Err(CodecError::Decode(e.to_string())),
+    };
+
+    let orientation = integer(&exif, Tag::Orientation)
+        .filter(|o| (1..=8).contains(o))
+        .unwrap_or(1) as u16;
+
+    // ResolutionUnit: 2 = inch (default), 3 = centimeter
+    let per_cm = integer(&exif, Tag::ResolutionUnit) == Some(3);
+    let dpi = match (number(&exif, Tag::XResolution), number(&exif, Tag::YResolution)) {
+        (Some(x), Some(y)) if per_cm => Some((x * 2.54, y * 2.54)),
+        (Some(x), Some(y)) => Some((x, y)),
+        _ => None,
+    };
+
+    Ok(Some(ExifData {
+        orientation,
+        dpi,
+        date_time: text(&exif, Tag::DateTime),
+        date_time_original: text(&exif, Tag::DateTimeOriginal),
+        date_time_digitized: text(&exif, Tag::DateTimeDigitized),
+        make: text(&exif, Tag::Make),
+        model: text(&exif, Tag::Model),
+        lens_model: text(&exif, Tag::LensModel),
+        software: text(&exif, Tag::Software),
+        exposure_time: number(&exif, Tag::ExposureTime),
+        f_number: number(&exif, Tag::FNumber),
+        focal_length: number(&exif, Tag::FocalLength),
+        iso: integer(&exif, Tag::PhotographicSensitivity),
+    }))
+}
+
+// ============================================================================
+// Orientation
+// ============================================================================
+
+/// Rotate and flip an image so it displays upright.
+///
+/// Orientations 5-8 swap width and height. Values outside 1-8 are treated
+/// as 1 (unchanged).
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `orientation` - EXIF orientation tag value
+pub fn apply_exif_orientation(image: ArrayView3<u8>, orientation: u16) -> Array3<u8> {
+    match orientation {
+        2 => flip_horizontal_u8(image),
+        3 => rotate_180_u8(image),
+        4 => flip_vertical_u8(image),
+        5 => rotate_270_cw_u8(flip_horizontal_u8(image).view()),
+        6 => rotate_90_cw_u8(image),
+        7 => rotate_90_cw_u8(flip_horizontal_u8(image).view()),
+        8 => rotate_270_cw_u8(image),
+        _ => image.to_owned(),
+    }
+}
+
+/// Rotate and flip an f32 image so it displays upright.
+///
+/// Same as apply_exif_orientation but for f32 images.
+pub fn apply_exif_orientation_f32(image: ArrayView3<f32>, orientation: u16) -> Array3<f32> {
+    match orientation {
+        2 => flip_horizontal_f32(image),
+        3 => rotate_180_f32(image),
+        4 => flip_vertical_f32(image),
+        5 => rotate_270_cw_f32(flip_horizontal_f32(image).view()),
+        6 => rotate_90_cw_f32(image),
+        7 => rotate_90_cw_f32(flip_horizontal_f32(image).view()),
+        8 => rotate_270_cw_f32(image),
+        _ => image.to_owned(),
+    }
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Read the EXIF metadata of an encoded image.
+///
+/// # Returns
+/// Dict with orientation, dpi, date_time, date_time_original,
+/// date_time_digitized, make, model, lens_model, software, exposure_time,
+/// f_number, focal_length and iso (missing fields are None), or None if
+/// the file has no EXIF block
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "read_exif")]
+pub fn read_exif_py<'py>(py: Python<'py>, data: Vec<u8>) -> PyResult<Option<Bound<'py, PyDict>>> {
+    let Some(exif) = read_exif(&data).map_err(|e| PyValueError::new_err(e.to_string()))? else {
+        return Ok(None);
+    };
+    let dict = PyDict::new(py);
+    dict.set_item("orientation", exif.orientation)?;
+    dict.set_item("dpi", exif.dpi)?;
+    dict.set_item("date_time", exif.date_time)?;
+    dict.set_item("date_time_original", exif.date_time_original)?;
+    dict.set_item("date_time_digitized", exif.date_time_digitized)?;
+    dict.set_item("make", exif.make)?;
+    dict.set_item("model", exif.model)?;
+    dict.set_item("lens_model", exif.lens_model)?;
+    dict.set_item("software", exif.software)?;
+    dict.set_item("exposure_time", exif.exposure_time)?;
+    dict.set_item("f_number", exif.f_number)?;
+    dict.set_item("focal_length", exif.focal_length)?;
+    dict.set_item("iso", exif.iso)?;
+    Ok(Some(dict))
+}
+
+/// Rotate and flip a uint8 image according to its EXIF orientation.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `orientation` - EXIF orientation 1-8
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_exif_orientation")]
+pub fn apply_exif_orientation_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    orientation: u16,
+) -> Bound<'py, PyArray3<u8>> {
+    apply_exif_orientation(image.as_array(), orientation).into_pyarray(py)
+}
+
+/// Rotate and flip a float32 image according to its EXIF orientation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "apply_exif_orientation_f32")]
+pub fn apply_exif_orientation_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    orientation: u16,
+) -> Bound<'py, PyArray3<f32>> {
+    apply_exif_orientation_f32(image.as_array(), orientation).into_pyarray(py)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exif::experimental::Writer;
+    use exif::{Field, Rational};
+
+    /// 2x3 single-channel image with values 0..6 in row-major order.
+    fn sample() -> Array3<u8> {
+        Array3::from_shape_fn((2, 3, 1), |(y, x, _)| (y * 3 + x) as u8)
+    }
+
+    fn pixels(image: &Array3<u8>) -> Vec<u8> {
+        image.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_orientation_mapping() {
+        // 0 1 2
+        // 3 4 5
+        let image = sample();
+        assert_eq!(pixels(&apply_exif_orientation(image.view(), 1)), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(pixels(&apply_exif_orientation(image.view(), 2)), vec![2, 1, 0, 5, 4, 3]);
+        assert_eq!(pixels(&apply_exif_orientation(image.view(), 3)), vec![5, 4, 3, 2, 1, 0]);
+        assert_eq!(pixels(&apply_exif_orientation(image.view(), 4)), vec![3, 4, 5, 0, 1, 2]);
+
+        // Transposing orientations produce 3x2 images
+        let cases = [
+            (5, vec![0, 3, 1, 4, 2, 5]),
+            (6, vec![3, 0, 4, 1, 5, 2]),
+            (7, vec![5, 2, 4, 1, 3, 0]),
+            (8, vec![2, 5, 1, 4, 0, 3]),
+        ];
+        for (orientation, expected) in cases {
+            let result = apply_exif_orientation(image.view(), orientation);
+            assert_eq!(result.dim(), (3, 2, 1), "orientation {}", orientation);
+            assert_eq!(pixels(&result), expected, "orientation {}", orientation);
+        }
+
+        assert_eq!(apply_exif_orientation(image.view(), 0), image);
+    }
+
+    #[test]
+    fn test_read_exif_fields() {
+        let fields = [
+            Field { tag: Tag::Orientation, ifd_num: In::PRIMARY, value: Value::Short(vec![6]) },
+            Field { tag: Tag::XResolution, ifd_num: In::PRIMARY, value: Value::Rational(vec![Rational { num: 118, denom: 1 }]) },
+            Field { tag: Tag::YResolution, ifd_num: In::PRIMARY, value: Value::Rational(vec![Rational { num: 118, denom: 1 }]) },
+            Field { tag: Tag::ResolutionUnit, ifd_num: In::PRIMARY, value: Value::Short(vec![3]) },
+            Field { tag: Tag::Make, ifd_num: In::PRIMARY, value: Value::Ascii(vec![b"Stag".to_vec()]) },
+            Field { tag: Tag::DateTimeOriginal, ifd_num: In::PRIMARY, value: Value::Ascii(vec![b"2024:05:01 12:30:00".to_vec()]) },
+            Field { tag: Tag::FNumber, ifd_num: In::PRIMARY, value: Value::Rational(vec![Rational { num: 28, denom: 10 }]) },
+            Field { tag: Tag::PhotographicSensitivity, ifd_num: In::PRIMARY, value: Value::Short(vec![400]) },
+        ];
+        let mut writer = Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+
+        let exif = read_exif(tiff.get_ref()).unwrap().expect("EXIF block");
+        assert_eq!(exif.orientation, 6);
+        let (dpi_x, dpi_y) = exif.dpi.unwrap();
+        assert!((dpi_x - 299.72).abs() < 0.01 && (dpi_y - 299.72).abs() < 0.01);
+        assert_eq!(exif.make.as_deref(), Some("Stag"));
+        assert_eq!(exif.date_time_original.as_deref(), Some("2024:05:01 12:30:00"));
+        assert!((exif.f_number.unwrap() - 2.8).abs() < 1e-6);
+        assert_eq!(exif.iso, Some(400));
+        assert_eq!(exif.model, None);
+    }
+
+    #[test]
+    fn test_read_exif_missing() {
+        let png = super::super::codec::encode_png(&sample(), &Default::default()).unwrap();
+        assert_eq!(read_exif(&png).unwrap(), None);
+    }
+}
@@ -0,0 +1,884 @@
+//! ICC profile parsing and color conversion.
+//!
+//! Supports the matrix/TRC profile class used by camera and display profiles
+//! (Display P3, Adobe RGB, ProPhoto RGB, ...) and gray TRC profiles. Images
+//! are converted to sRGB after decoding and back to the profile's space on
+//! export, so wide-gamut photos keep their colors in the editor.
+//! [`soft_proof`] previews an sRGB image as it would look after a round trip
+//! through a smaller target gamut, optionally marking the colors it loses.
+//!
+//! LUT-based profiles (A2B0/B2A0 tags) are not supported, which includes
+//! most CMYK print profiles.
+
+use std::fmt;
+
+use ndarray::{Array3, ArrayView3};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyBytes, PyDict};
+
+/// Samples of the tone curve lookup tables.
+const LUT_SIZE: usize = 4096;
+
+/// Distance outside 0-1 (linear) that still counts as in gamut, absorbs LUT error.
+const GAMUT_TOLERANCE: f32 = 1e-3;
+
+/// sRGB primaries adapted to the D50 profile connection space (RGB -> XYZ).
+const SRGB_TO_XYZ_D50: [[f32; 3]; 3] = [
+    [0.436_074_7, 0.385_064_9, 0.143_080_4],
+    [0.222_504_5, 0.716_878_6, 0.060_616_9],
+    [0.013_932_2, 0.097_104_5, 0.714_173_3],
+];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Error raised while parsing or applying an ICC profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IccError {
+    /// Profile ended before a header, tag or value was complete
+    UnexpectedEof,
+    /// Missing "acsp" signature
+    BadSignature,
+    /// Profile class or color space that is not matrix/TRC RGB or gray
+    Unsupported(String),
+    /// Image channel count does not match the profile color space
+    ChannelMismatch(usize),
+}
+
+impl fmt::Display for IccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IccError::UnexpectedEof => write!(f, "unexpected end of ICC profile"),
+            IccError::BadSignature => write!(f, "not an ICC profile"),
+            IccError::Unsupported(msg) => write!(f, "unsupported ICC profile: {}", msg),
+            IccError::ChannelMismatch(channels) => write!(f, "{} channel image does not match the profile", channels),
+        }
+    }
+}
+
+impl std::error::Error for IccError {}
+
+/// Tone reproduction curve mapping encoded values (0-1) to linear light.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToneCurve {
+    /// Y = X^gamma
+    Gamma(f32),
+    /// Sampled curve, evenly spaced over 0-1
+    Table(Vec<f32>),
+    /// ICC parametric curve (function type 0-4) with parameters g, a, b, c, d, e, f
+    Parametric { kind: u16, params: [f32; 7] },
+}
+
+impl ToneCurve {
+    /// Evaluate the curve at `x` (0-1).
+    pub fn eval(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            ToneCurve::Gamma(gamma) => x.powf(*gamma),
+            ToneCurve::Table(table) => match table.len() {
+                0 => x,
+                1 => table[0],
+                n => {
+                    let pos = x * (n - 1) as f32;
+                    let i = (pos as usize).min(n - 2);
+                    let t = pos - i as f32;
+                    table[i] + (table[i + 1] - table[i]) * t
+                }
+            },
+            ToneCurve::Parametric { kind, params } => {
+                let [g, a, b, c, d, e, f] = *params;
+                let pow = |v: f32| v.max(0.0).powf(g);
+                match kind {
+                    0 => x.powf(g),
+                    1 => if a != 0.0 && x >= -b / a { pow(a * x + b) } else { 0.0 },
+                    2 => if a != 0.0 && x >= -b / a { pow(a * x + b) + c } else { c },
+                    3 => if x >= d { pow(a * x + b) } else { c * x },
+                    _ => if x >= d { pow(a * x + b) + e } else { c * x + f },
+                }
+            }
+        }
+    }
+
+    /// Find the encoded value whose linear value is `y`, assuming a
+    /// non-decreasing curve.
+    pub fn invert(&self, y: f32) -> f32 {
+        if let ToneCurve::Gamma(gamma) = self {
+            return if *gamma > 0.0 { y.clamp(0.0, 1.0).powf(1.0 / gamma) } else { y };
+        }
+        let (mut lo, mut hi) = (0.0f32, 1.0f32);
+        for _ in 0..24 {
+            let mid = 0.5 * (lo + hi);
+            if self.eval(mid) < y {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    /// The sRGB transfer curve.
+    pub fn srgb() -> Self {
+        ToneCurve::Parametric {
+            kind: 3,
+            params: [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045, 0.0, 0.0],
+        }
+    }
+}
+
+/// Color space described by a profile.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IccColorSpace {
+    /// RGB with primaries (columns, D50 XYZ) and per-channel tone curves
+    Rgb { to_xyz: [[f32; 3]; 3], curves: [ToneCurve; 3] },
+    /// Grayscale with a single tone curve
+    Gray { curve: ToneCurve },
+}
+
+/// Parsed ICC profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccProfile {
+    /// Profile description ("desc" tag)
+    pub description: Option<String>,
+    pub color_space: IccColorSpace,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+fn slice(data: &[u8], offset: usize, len: usize) -> Result<&[u8], IccError> {
+    data.get(offset..offset.checked_add(len).ok_or(IccError::UnexpectedEof)?).ok_or(IccError::UnexpectedEof)
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Result<u16, IccError> {
+    let b = slice(data, offset, 2)?;
+    Ok(u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32, IccError> {
+    let b = slice(data, offset, 4)?;
+    Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Signed 15.16 fixed point number.
+fn s15f16_at(data: &[u8], offset: usize) -> Result<f32, IccError> {
+    Ok(u32_at(data, offset)? as i32 as f32 / 65536.0)
+}
+
+fn read_xyz(tag: &[u8]) -> Result<[f32; 3], IccError> {
+    if slice(tag, 0, 4)? != b"XYZ " {
+        return Err(IccError::Unsupported("colorant is not an XYZ tag".into()));
+    }
+    Ok([s15f16_at(tag, 8)?, s15f16_at(tag, 12)?, s15f16_at(tag, 16)?])
+}
+
+fn read_curve(tag: &[u8]) -> Result<ToneCurve, IccError> {
+    match slice(tag, 0, 4)? {
+        b"curv" => {
+            let count = u32_at(tag, 8)? as usize;
+            match count {
+                0 => Ok(ToneCurve::Gamma(1.0)),
+                // u8Fixed8 gamma
+                1 => Ok(ToneCurve::Gamma(u16_at(tag, 12)? as f32 / 256.0)),
+                _ => {
+                    let values = slice(tag, 12, count.checked_mul(2).ok_or(IccError::UnexpectedEof)?)?;
+                    Ok(ToneCurve::Table(
+                        values.chunks_exact(2).map(|v| u16::from_be_bytes([v[0], v[1]]) as f32 / 65535.0).collect(),
+                    ))
+                }
+            }
+        }
+        b"para" => {
+            let kind = u16_at(tag, 8)?;
+            let count = match kind {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return Err(IccError::Unsupported(format!("parametric curve type {}", kind))),
+            };
+            let mut params = [0.0; 7];
+            for (i, p) in params.iter_mut().take(count).enumerate() {
+                *p = s15f16_at(tag, 12 + i * 4)?;
+            }
+            Ok(ToneCurve::Parametric { kind, params })
+        }
+        other => Err(IccError::Unsupported(format!("curve type {:?}", String::from_utf8_lossy(other)))),
+    }
+}
+
+fn read_description(tag: &[u8]) -> Option<String> {
+    let text = match tag.get(0..4)? {
+        // ICC v2: ASCII with length
+        b"desc" => {
+            let len = u32_at(tag, 8).ok()? as usize;
+            let bytes = slice(tag, 12, len).ok()?;
+            String::from_utf8_lossy(bytes).trim_end_matches('\0').to_string()
+        }
+        // ICC v4: first record of a multi-localized UTF-16BE string
+        b"mluc" => {
+            if u32_at(tag, 8).ok()? == 0 {
+                return None;
+            }
+            let len = u32_at(tag, 20).ok()? as usize;
+            let offset = u32_at(tag, 24).ok()? as usize;
+            let units: Vec<u16> = slice(tag, offset, len).ok()?.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units).trim_end_matches('\0').to_string()
+        }
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+impl IccProfile {
+    /// Parse an ICC profile.
+    ///
+    /// # Arguments
+    /// * `data` - Raw profile bytes (as embedded in PNG iCCP, JPEG APP2, ...)
+    pub fn parse(data: &[u8]) -> Result<Self, IccError> {
+        if data.len() < 132 {
+            return Err(IccError::UnexpectedEof);
+        }
+        if &data[36..40] != b"acsp" {
+            return Err(IccError::BadSignature);
+        }
+        if &data[20..24] != b"XYZ " {
+            return Err(IccError::Unsupported("Lab connection space".into()));
+        }
+
+        let count = u32_at(data, 128)? as usize;
+        let mut tags = Vec::with_capacity(count.min(256));
+        for i in 0..count {
+            let entry = 132 + i * 12;
+            let signature = slice(data, entry, 4)?;
+            let offset = u32_at(data, entry + 4)? as usize;
+            let size = u32_at(data, entry + 8)? as usize;
+            tags.push((signature, slice(data, offset, size)?));
+        }
+        let tag = |sig: &[u8; 4]| tags.iter().find(|(s, _)| *s == sig).map(|(_, t)| *t);
+        let required = |sig: &[u8; 4]| tag(sig).ok_or_else(|| IccError::Unsupported(format!("missing {} tag", String::from_utf8_lossy(sig))));
+
+        let color_space = match &data[16..20] {
+            b"RGB " => {
+                let [r, g, b] = [read_xyz(required(b"rXYZ")?)?, read_xyz(required(b"gXYZ")?)?, read_xyz(required(b"bXYZ")?)?];
+                IccColorSpace::Rgb {
+                    to_xyz: [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]],
+                    curves: [read_curve(required(b"rTRC")?)?, read_curve(required(b"gTRC")?)?, read_curve(required(b"bTRC")?)?],
+                }
+            }
+            b"GRAY" => IccColorSpace::Gray { curve: read_curve(required(b"kTRC")?)? },
+            other => return Err(IccError::Unsupported(format!("color space {:?}", String::from_utf8_lossy(other)))),
+        };
+
+        Ok(Self {
+            description: tag(b"desc").and_then(read_description),
+            color_space,
+        })
+    }
+
+    /// The sRGB profile.
+    pub fn srgb() -> Self {
+        Self {
+            description: Some("sRGB".into()),
+            color_space: IccColorSpace::Rgb {
+                to_xyz: SRGB_TO_XYZ_D50,
+                curves: [ToneCurve::srgb(), ToneCurve::srgb(), ToneCurve::srgb()],
+            },
+        }
+    }
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+fn mat_mul(a: &[[f32; 3]; 3], b: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+fn mat_inv(m: &[[f32; 3]; 3]) -> Result<[[f32; 3]; 3], IccError> {
+    let [[a, b, c], [d, e, f], [g, h, i]] = *m;
+    let det = a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g);
+    if det.abs() < 1e-9 {
+        return Err(IccError::Unsupported("singular colorant matrix".into()));
+    }
+    Ok([
+        [(e * i - f * h) / det, (c * h - b * i) / det, (b * f - c * e) / det],
+        [(f * g - d * i) / det, (a * i - c * g) / det, (c * d - a * f) / det],
+        [(d * h - e * g) / det, (b * g - a * h) / det, (a * e - b * d) / det],
+    ])
+}
+
+/// Per-pixel transform: decode curves, matrix on linear light, encode curves.
+struct Transform {
+    decode: [Vec<f32>; 3],
+    matrix: Option<[[f32; 3]; 3]>,
+    encode: [Vec<f32>; 3],
+}
+
+fn sample(f: impl Fn(f32) -> f32) -> Vec<f32> {
+    (0..LUT_SIZE).map(|i| f(i as f32 / (LUT_SIZE - 1) as f32)).collect()
+}
+
+fn lookup(lut: &[f32], v: f32) -> f32 {
+    let pos = v.clamp(0.0, 1.0) * (LUT_SIZE - 1) as f32;
+    let i = (pos as usize).min(LUT_SIZE - 2);
+    lut[i] + (lut[i + 1] - lut[i]) * (pos - i as f32)
+}
+
+impl Transform {
+    fn new(profile: &IccProfile, to_srgb: bool) -> Result<Self, IccError> {
+        let srgb = ToneCurve::srgb();
+        let (curves, matrix) = match &profile.color_space {
+            IccColorSpace::Rgb { to_xyz, curves } => {
+                let matrix = if to_srgb {
+                    mat_mul(&mat_inv(&SRGB_TO_XYZ_D50)?, to_xyz)
+                } else {
+                    mat_mul(&mat_inv(to_xyz)?, &SRGB_TO_XYZ_D50)
+                };
+                (curves.clone(), Some(matrix))
+            }
+            // Neutral gray maps to equal linear RGB in the D50-adapted sRGB space
+            IccColorSpace::Gray { curve } => ([curve.clone(), curve.clone(), curve.clone()], None),
+        };
+
+        let profile_luts = |f: &dyn Fn(&ToneCurve, f32) -> f32| [0, 1, 2].map(|c| sample(|v| f(&curves[c], v)));
+        let srgb_luts = |f: &dyn Fn(&ToneCurve, f32) -> f32| [0, 1, 2].map(|_| sample(|v| f(&srgb, v)));
+        let (decode, encode) = if to_srgb {
+            (profile_luts(&|c, v| c.eval(v)), srgb_luts(&|c, v| c.invert(v)))
+        } else {
+            (srgb_luts(&|c, v| c.eval(v)), profile_luts(&|c, v| c.invert(v)))
+        };
+        Ok(Self { decode, matrix, encode })
+    }
+
+    fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let lin = [0, 1, 2].map(|c| lookup(&self.decode[c], rgb[c]));
+        let mapped = match &self.matrix {
+            Some(m) => [0, 1, 2].map(|r| m[r][0] * lin[0] + m[r][1] * lin[1] + m[r][2] * lin[2]),
+            None => lin,
+        };
+        [0, 1, 2].map(|c| lookup(&self.encode[c], mapped[c]))
+    }
+}
+
+/// Run the transform over all color channels; alpha is kept.
+fn convert<T: Copy>(
+    image: ArrayView3<T>,
+    transform: &Transform,
+    rgb_profile: bool,
+    to_f32: impl Fn(T) -> f32,
+    from_f32: impl Fn(f32) -> T,
+) -> Result<Array3<T>, IccError> {
+    let channels = image.shape()[2];
+    let color_channels = match channels {
+        3 | 4 => 3,
+        1 | 2 if !rgb_profile => 1,
+        n => return Err(IccError::ChannelMismatch(n)),
+    };
+
+    let mut result = image.to_owned();
+    for mut pixel in result.lanes_mut(ndarray::Axis(2)) {
+        let rgb = if color_channels == 3 {
+            [to_f32(pixel[0]), to_f32(pixel[1]), to_f32(pixel[2])]
+        } else {
+            [to_f32(pixel[0]); 3]
+        };
+        let out = transform.apply(rgb);
+        for c in 0..color_channels {
+            pixel[c] = from_f32(out[c]);
+        }
+    }
+    Ok(result)
+}
+
+fn is_rgb(profile: &IccProfile) -> bool {
+    matches!(profile.color_space, IccColorSpace::Rgb { .. })
+}
+
+/// Convert an image from a profile's color space to sRGB.
+///
+/// Colors outside the sRGB gamut are clipped.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels); RGB profiles need 3 or
+///   4 channels, gray profiles also accept 1 or 2
+/// * `profile` - Embedded profile of the image
+pub fn convert_to_srgb(image: ArrayView3<u8>, profile: &IccProfile) -> Result<Array3<u8>, IccError> {
+    let transform = Transform::new(profile, true)?;
+    convert(image, &transform, is_rgb(profile), |v| v as f32 / 255.0, |v| (v * 255.0 + 0.5) as u8)
+}
+
+/// Convert an f32 image (0.0-1.0) from a profile's color space to sRGB.
+pub fn convert_to_srgb_f32(image: ArrayView3<f32>, profile: &IccProfile) -> Result<Array3<f32>, IccError> {
+    let transform = Transform::new(profile, true)?;
+    convert(image, &transform, is_rgb(profile), |v| v, |v| v)
+}
+
+/// Convert an sRGB image to a profile's color space (for export with the
+/// profile embedded).
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `profile` - Target profile
+pub fn convert_from_srgb(image: ArrayView3<u8>, profile: &IccProfile) -> Result<Array3<u8>, IccError> {
+    let transform = Transform::new(profile, false)?;
+    convert(image, &transform, is_rgb(profile), |v| v as f32 / 255.0, |v| (v * 255.0 + 0.5) as u8)
+}
+
+/// Convert an sRGB f32 image (0.0-1.0) to a profile's color space.
+pub fn convert_from_srgb_f32(image: ArrayView3<f32>, profile: &IccProfile) -> Result<Array3<f32>, IccError> {
+    let transform = Transform::new(profile, false)?;
+    convert(image, &transform, is_rgb(profile), |v| v, |v| v)
+}
+
+// ============================================================================
+// Soft Proofing
+// ============================================================================
+
+/// How colors outside the target gamut are brought into it.
+///
+/// Matrix/TRC profiles carry no per-intent tables, so only perceptual and
+/// relative colorimetric differ; the other two behave like relative
+/// colorimetric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderingIntent {
+    /// Desaturate out-of-gamut colors toward gray of the same luminance
+    Perceptual,
+    /// Clip out-of-gamut colors per channel
+    #[default]
+    RelativeColorimetric,
+    /// Same as relative colorimetric
+    Saturation,
+    /// Same as relative colorimetric (no paper white simulation)
+    AbsoluteColorimetric,
+}
+
+impl RenderingIntent {
+    /// Parse an intent name ("perceptual", "relative", "saturation", "absolute",
+    /// with or without the "_colorimetric" suffix).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "perceptual" => Some(RenderingIntent::Perceptual),
+            "relative" | "relative_colorimetric" => Some(RenderingIntent::RelativeColorimetric),
+            "saturation" => Some(RenderingIntent::Saturation),
+            "absolute" | "absolute_colorimetric" => Some(RenderingIntent::AbsoluteColorimetric),
+            _ => None,
+        }
+    }
+}
+
+/// sRGB -> target -> sRGB round trip on linear light.
+struct Proof {
+    decode: Vec<f32>,
+    encode: Vec<f32>,
+    /// Linear sRGB -> linear target RGB
+    to_target: [[f32; 3]; 3],
+    /// Linear target RGB -> linear sRGB
+    from_target: [[f32; 3]; 3],
+    /// Gray target: only luminance survives, the matrices are unused
+    gray: bool,
+    /// Luminance weights of the target primaries
+    luminance: [f32; 3],
+    intent: RenderingIntent,
+}
+
+fn mat_apply(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [0, 1, 2].map(|r| m[r][0] * v[0] + m[r][1] * v[1] + m[r][2] * v[2])
+}
+
+impl Proof {
+    fn new(profile: &IccProfile, intent: RenderingIntent) -> Result<Self, IccError> {
+        let srgb = ToneCurve::srgb();
+        let (to_xyz, gray) = match &profile.color_space {
+            IccColorSpace::Rgb { to_xyz, .. } => (*to_xyz, false),
+            IccColorSpace::Gray { .. } => (SRGB_TO_XYZ_D50, true),
+        };
+        Ok(Self {
+            decode: sample(|v| srgb.eval(v)),
+            encode: sample(|v| srgb.invert(v)),
+            to_target: mat_mul(&mat_inv(&to_xyz)?, &SRGB_TO_XYZ_D50),
+            from_target: mat_mul(&mat_inv(&SRGB_TO_XYZ_D50)?, &to_xyz),
+            gray,
+            luminance: to_xyz[1],
+            intent,
+        })
+    }
+
+    /// Proofed sRGB color and whether the color is outside the target gamut.
+    fn apply(&self, rgb: [f32; 3]) -> ([f32; 3], bool) {
+        let lin = rgb.map(|v| lookup(&self.decode, v));
+        if self.gray {
+            let y = (0..3).map(|c| self.luminance[c] * lin[c]).sum::<f32>();
+            return ([lookup(&self.encode, y); 3], false);
+        }
+        let mut target = mat_apply(&self.to_target, lin);
+        let out_of_gamut = target.iter().any(|&v| !(-GAMUT_TOLERANCE..=1.0 + GAMUT_TOLERANCE).contains(&v));
+        if out_of_gamut && self.intent == RenderingIntent::Perceptual {
+            let y = (0..3).map(|c| self.luminance[c] * target[c]).sum::<f32>().clamp(0.0, 1.0);
+            let scale = target
+                .iter()
+                .map(|&v| if v > 1.0 { (1.0 - y) / (v - y) } else if v < 0.0 { y / (y - v) } else { 1.0 })
+                .fold(1.0f32, f32::min)
+                .max(0.0);
+            target = target.map(|v| y + (v - y) * scale);
+        }
+        let lin = mat_apply(&self.from_target, target.map(|v| v.clamp(0.0, 1.0)));
+        (lin.map(|v| lookup(&self.encode, v)), out_of_gamut)
+    }
+}
+
+fn proof<T: Copy>(
+    image: ArrayView3<T>,
+    proof: &Proof,
+    gamut_warning: Option<[T; 3]>,
+    to_f32: impl Fn(T) -> f32,
+    from_f32: impl Fn(f32) -> T,
+) -> Result<Array3<T>, IccError> {
+    let channels = image.shape()[2];
+    if !matches!(channels, 3 | 4) {
+        return Err(IccError::ChannelMismatch(channels));
+    }
+    let mut result = image.to_owned();
+    for mut pixel in result.lanes_mut(ndarray::Axis(2)) {
+        let (rgb, out_of_gamut) = proof.apply([to_f32(pixel[0]), to_f32(pixel[1]), to_f32(pixel[2])]);
+        let rgb = match gamut_warning {
+            Some(warning) if out_of_gamut => warning,
+            _ => rgb.map(&from_f32),
+        };
+        for c in 0..3 {
+            pixel[c] = rgb[c];
+        }
+    }
+    Ok(result)
+}
+
+/// Simulate how an sRGB image looks when reproduced in a profile's gamut.
+///
+/// The colors are converted to the target space, brought into its gamut
+/// according to `intent` and converted back to sRGB for display. Gray
+/// targets reduce the image to its luminance.
+///
+/// # Arguments
+/// * `image` - sRGB image with 3 or 4 channels (height, width, channels); alpha is kept
+/// * `profile` - Target (output device) profile
+/// * `intent` - Gamut mapping of out-of-gamut colors
+/// * `gamut_warning` - Color painted over pixels outside the target gamut, `None` to show the mapped color
+pub fn soft_proof(
+    image: ArrayView3<u8>,
+    profile: &IccProfile,
+    intent: RenderingIntent,
+    gamut_warning: Option<[u8; 3]>,
+) -> Result<Array3<u8>, IccError> {
+    let transform = Proof::new(profile, intent)?;
+    proof(image, &transform, gamut_warning, |v| v as f32 / 255.0, |v| (v * 255.0 + 0.5) as u8)
+}
+
+/// Simulate how an sRGB f32 image (0.0-1.0) looks when reproduced in a profile's gamut.
+pub fn soft_proof_f32(
+    image: ArrayView3<f32>,
+    profile: &IccProfile,
+    intent: RenderingIntent,
+    gamut_warning: Option<[f32; 3]>,
+) -> Result<Array3<f32>, IccError> {
+    let transform = Proof::new(profile, intent)?;
+    proof(image, &transform, gamut_warning, |v| v, |v| v)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+fn parse_py(profile: &[u8]) -> PyResult<IccProfile> {
+    IccProfile::parse(profile).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[cfg(feature = "python")]
+fn to_py_err(e: IccError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Extract the embedded ICC profile of an image file.
+///
+/// # Returns
+/// Profile bytes, or None if the file has no profile
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "read_icc_profile")]
+pub fn read_icc_profile_py<'py>(py: Python<'py>, data: Vec<u8>) -> PyResult<Option<Bound<'py, PyBytes>>> {
+    let profile = super::codec::read_icc_profile(&data).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(profile.map(|p| PyBytes::new(py, &p)))
+}
+
+/// Describe an ICC profile.
+///
+/// # Returns
+/// Dict with description and color_space ("rgb" or "gray")
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn icc_profile_info<'py>(py: Python<'py>, profile: Vec<u8>) -> PyResult<Bound<'py, PyDict>> {
+    let profile = parse_py(&profile)?;
+    let dict = PyDict::new(py);
+    dict.set_item("color_space", if is_rgb(&profile) { "rgb" } else { "gray" })?;
+    dict.set_item("description", profile.description)?;
+    Ok(dict)
+}
+
+/// Convert a uint8 image from its embedded profile to sRGB.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `profile` - ICC profile bytes
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn icc_to_srgb<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>, profile: Vec<u8>) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let result = convert_to_srgb(image.as_array(), &parse_py(&profile)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Convert a float32 image from its embedded profile to sRGB.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn icc_to_srgb_f32<'py>(py: Python<'py>, image: PyHostArray3<'py, f32>, profile: Vec<u8>) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = convert_to_srgb_f32(image.as_array(), &parse_py(&profile)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Convert a uint8 sRGB image to an ICC profile's color space.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn icc_from_srgb<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>, profile: Vec<u8>) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let result = convert_from_srgb(image.as_array(), &parse_py(&profile)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Convert a float32 sRGB image to an ICC profile's color space.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn icc_from_srgb_f32<'py>(py: Python<'py>, image: PyHostArray3<'py, f32>, profile: Vec<u8>) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let result = convert_from_srgb_f32(image.as_array(), &parse_py(&profile)?).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+#[cfg(feature = "python")]
+fn parse_intent_py(intent: &str) -> PyResult<RenderingIntent> {
+    RenderingIntent::from_name(intent).ok_or_else(|| PyValueError::new_err(format!("unknown rendering intent '{}'", intent)))
+}
+
+/// Preview a uint8 sRGB image as reproduced in an ICC profile's gamut.
+///
+/// # Arguments
+/// * `image` - sRGB image (height, width, 3 or 4)
+/// * `profile` - Target ICC profile bytes
+/// * `intent` - "perceptual", "relative_colorimetric", "saturation" or "absolute_colorimetric"
+/// * `gamut_warning_color` - (r, g, b) painted over out-of-gamut pixels, or None
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, profile, intent="relative_colorimetric", gamut_warning_color=None))]
+pub fn soft_proof_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    profile: Vec<u8>,
+    intent: &str,
+    gamut_warning_color: Option<(u8, u8, u8)>,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let warning = gamut_warning_color.map(|(r, g, b)| [r, g, b]);
+    let result = soft_proof(image.as_array(), &parse_py(&profile)?, parse_intent_py(intent)?, warning).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+/// Preview a float32 sRGB image as reproduced in an ICC profile's gamut.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, profile, intent="relative_colorimetric", gamut_warning_color=None))]
+pub fn soft_proof_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    profile: Vec<u8>,
+    intent: &str,
+    gamut_warning_color: Option<(f32, f32, f32)>,
+) -> PyResult<Bound<'py, PyArray3<f32>>> {
+    let warning = gamut_warning_color.map(|(r, g, b)| [r, g, b]);
+    let result = soft_proof_f32(image.as_array(), &parse_py(&profile)?, parse_intent_py(intent)?, warning).map_err(to_py_err)?;
+    Ok(result.into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Display P3 primaries adapted to D50.
+    const P3_TO_XYZ_D50: [[f32; 3]; 3] = [
+        [0.515_1, 0.291_9, 0.157_1],
+        [0.241_2, 0.692_2, 0.066_7],
+        [-0.001_1, 0.041_9, 0.784_1],
+    ];
+
+    fn fixed(v: f32) -> [u8; 4] {
+        ((v * 65536.0).round() as i32).to_be_bytes()
+    }
+
+    /// Build a v4 matrix/TRC profile with a shared para curve.
+    fn rgb_profile(to_xyz: [[f32; 3]; 3], curve: &[u8], description: &str) -> Vec<u8> {
+        let mut tags: Vec<(&[u8; 4], Vec<u8>)> = Vec::new();
+        for (i, sig) in [b"rXYZ", b"gXYZ", b"bXYZ"].into_iter().enumerate() {
+            let mut tag = b"XYZ \0\0\0\0".to_vec();
+            for row in &to_xyz {
+                tag.extend(fixed(row[i]));
+            }
+            tags.push((sig, tag));
+        }
+        for sig in [b"rTRC", b"gTRC", b"bTRC"] {
+            tags.push((sig, curve.to_vec()));
+        }
+        let mut desc = b"desc\0\0\0\0".to_vec();
+        desc.extend((description.len() as u32 + 1).to_be_bytes());
+        desc.extend(description.as_bytes());
+        desc.push(0);
+        tags.push((b"desc", desc));
+
+        let mut data = vec![0u8; 128];
+        data[16..20].copy_from_slice(b"RGB ");
+        data[20..24].copy_from_slice(b"XYZ ");
+        data[36..40].copy_from_slice(b"acsp");
+        data.extend((tags.len() as u32).to_be_bytes());
+        let mut offset = 132 + tags.len() * 12;
+        let mut body = Vec::new();
+        for (sig, tag) in &tags {
+            data.extend(*sig);
+            data.extend((offset as u32).to_be_bytes());
+            data.extend((tag.len() as u32).to_be_bytes());
+            body.extend(tag);
+            while body.len() % 4 != 0 {
+                body.push(0);
+            }
+            offset = 132 + tags.len() * 12 + body.len();
+        }
+        data.extend(body);
+        let size = data.len() as u32;
+        data[0..4].copy_from_slice(&size.to_be_bytes());
+        data
+    }
+
+    fn srgb_curve() -> Vec<u8> {
+        let mut tag = b"para\0\0\0\0\0\x03\0\0".to_vec();
+        for v in [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045] {
+            tag.extend(fixed(v));
+        }
+        tag
+    }
+
+    fn gamma_curve(gamma: f32) -> Vec<u8> {
+        let mut tag = b"curv\0\0\0\0\0\0\0\x01".to_vec();
+        tag.extend(((gamma * 256.0) as u16).to_be_bytes());
+        tag
+    }
+
+    #[test]
+    fn test_parse_rgb_profile() {
+        let profile = IccProfile::parse(&rgb_profile(P3_TO_XYZ_D50, &gamma_curve(2.2), "Wide")).unwrap();
+        assert_eq!(profile.description.as_deref(), Some("Wide"));
+        match profile.color_space {
+            IccColorSpace::Rgb { to_xyz, curves } => {
+                assert!((to_xyz[0][0] - 0.5151).abs() < 1e-4);
+                assert!((to_xyz[2][0] + 0.0011).abs() < 1e-4);
+                assert_eq!(curves[0], ToneCurve::Gamma(563.0 / 256.0));
+            }
+            _ => panic!("expected RGB profile"),
+        }
+    }
+
+    #[test]
+    fn test_srgb_profile_is_identity() {
+        let profile = IccProfile::parse(&rgb_profile(SRGB_TO_XYZ_D50, &srgb_curve(), "sRGB")).unwrap();
+        let image = Array3::from_shape_fn((4, 4, 4), |(y, x, c)| (y * 60 + x * 13 + c * 7) as u8);
+        let result = convert_to_srgb(image.view(), &profile).unwrap();
+        for (a, b) in image.iter().zip(result.iter()) {
+            assert!((*a as i32 - *b as i32).abs() <= 1, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_wide_gamut_roundtrip() {
+        let profile = IccProfile::parse(&rgb_profile(P3_TO_XYZ_D50, &srgb_curve(), "P3")).unwrap();
+        let image = Array3::from_shape_vec((1, 3, 3), vec![255, 0, 0, 40, 180, 90, 128, 128, 128]).unwrap();
+
+        let p3 = convert_from_srgb(image.view(), &profile).unwrap();
+        // Pure sRGB red is inside P3, so its P3 encoding is less saturated
+        assert!(p3[[0, 0, 0]] < 255 && p3[[0, 0, 1]] > 0);
+        // Neutral gray stays neutral
+        assert!((p3[[0, 2, 0]] as i32 - p3[[0, 2, 2]] as i32).abs() <= 1);
+
+        let back = convert_to_srgb(p3.view(), &profile).unwrap();
+        for (a, b) in image.iter().zip(back.iter()) {
+            assert!((*a as i32 - *b as i32).abs() <= 2, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_invalid_profiles() {
+        assert_eq!(IccProfile::parse(&[0u8; 20]), Err(IccError::UnexpectedEof));
+        let mut data = rgb_profile(SRGB_TO_XYZ_D50, &srgb_curve(), "sRGB");
+        assert!(matches!(IccProfile::parse(&data[..200]), Err(IccError::UnexpectedEof)));
+        data[36] = b'x';
+        assert_eq!(IccProfile::parse(&data), Err(IccError::BadSignature));
+
+        let gray = Array3::<u8>::zeros((2, 2, 1));
+        assert_eq!(convert_to_srgb(gray.view(), &IccProfile::srgb()), Err(IccError::ChannelMismatch(1)));
+    }
+
+    #[test]
+    fn test_soft_proof() {
+        // Target gamut with every sRGB primary pulled 30% toward white
+        let mix = [[0.8, 0.1, 0.1], [0.1, 0.8, 0.1], [0.1, 0.1, 0.8]];
+        let narrow = IccProfile {
+            description: None,
+            color_space: IccColorSpace::Rgb { to_xyz: mat_mul(&SRGB_TO_XYZ_D50, &mix), curves: [ToneCurve::srgb(), ToneCurve::srgb(), ToneCurve::srgb()] },
+        };
+        let image = Array3::from_shape_vec((1, 3, 4), vec![255, 0, 0, 200, 128, 128, 128, 255, 140, 120, 110, 255]).unwrap();
+
+        let relative = soft_proof(image.view(), &narrow, RenderingIntent::RelativeColorimetric, None).unwrap();
+        // Saturated red loses chroma, alpha is kept; gray and muted colors survive
+        assert!(relative[[0, 0, 0]] < 255 && relative[[0, 0, 1]] > 0);
+        assert_eq!(relative[[0, 0, 3]], 200);
+        for x in 1..3 {
+            for c in 0..3 {
+                assert!((relative[[0, x, c]] as i32 - image[[0, x, c]] as i32).abs() <= 1);
+            }
+        }
+
+        let perceptual = soft_proof(image.view(), &narrow, RenderingIntent::Perceptual, None).unwrap();
+        assert_ne!(perceptual.slice(ndarray::s![0, 0, ..3]), relative.slice(ndarray::s![0, 0, ..3]));
+
+        let marked = soft_proof(image.view(), &narrow, RenderingIntent::Perceptual, Some([0, 255, 0])).unwrap();
+        assert_eq!(marked.slice(ndarray::s![0, 0, ..]).to_vec(), vec![0, 255, 0, 200]);
+        assert_eq!(marked.slice(ndarray::s![0, 1, ..]), perceptual.slice(ndarray::s![0, 1, ..]));
+
+        // sRGB itself loses nothing; gray targets keep only luminance
+        let same = soft_proof(image.view(), &IccProfile::srgb(), RenderingIntent::Perceptual, Some([0, 255, 0])).unwrap();
+        assert!(same.iter().zip(image.iter()).all(|(a, b)| (*a as i32 - *b as i32).abs() <= 1));
+        let gray = IccProfile { description: None, color_space: IccColorSpace::Gray { curve: ToneCurve::Gamma(2.2) } };
+        let proofed = soft_proof_f32(image.mapv(|v| v as f32 / 255.0).view(), &gray, RenderingIntent::default(), None).unwrap();
+        assert!(proofed.lanes(ndarray::Axis(2)).into_iter().all(|p| p[0] == p[1] && p[1] == p[2]));
+        assert_eq!(RenderingIntent::from_name("Absolute"), Some(RenderingIntent::AbsoluteColorimetric));
+    }
+}
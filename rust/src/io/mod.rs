@@ -0,0 +1,47 @@
+//! Image encoding and decoding (requires the `encode` feature).
+//!
+//! Decodes encoded files into the crate's (height, width, channels) arrays
+//! and encodes arrays back to files, without going through Python imaging
+//! libraries or a browser canvas. Decoding is deterministic across
+//! platforms.
+//!
+//! ## Formats
+//! - **PNG** - Lossless, 8-bit; grayscale, RGB and RGBA
+//! - **JPEG** - Lossy, no alpha (alpha is dropped on encode)
+//! - **WebP** - Decoding of lossy and lossless files, lossless encoding
+//! - **QOI** - Lossless and fast, suited for undo snapshots; RGB and RGBA
+//! - **TIFF** - Uncompressed encoding with 8 or 16 bits per channel
+//! - **GIF / APNG** - Animated encoding of frame sequences ([`encode_gif`], [`encode_apng`])
+//!
+//! ## Channel Layout
+//! Decoded images keep their channel count where the crate supports it:
+//! grayscale decodes to 1 channel, RGB to 3 and anything with alpha to 4
+//! (grayscale + alpha is expanded to RGBA). [`decode_image`] reduces higher
+//! bit depths to 8 bits, [`decode_image_u16`] keeps up to 16 bits; the source
+//! bit depth is reported in [`ImageMetadata`].
+//!
+//! ## Metadata
+//! [`read_exif`] reads orientation, resolution, timestamps and camera fields
+//! from the EXIF block; [`apply_exif_orientation`] rotates decoded pixels
+//! upright using the rotate/flip kernels. [`read_icc_profile`] extracts an
+//! embedded ICC profile; the [`icc`] module converts matrix/TRC profiles to
+//! sRGB and back, and [`PngOptions`]/[`JpegOptions`] embed a profile on export.
+//!
+//! ## Palettes
+//! The [`quantize`] module reduces images to up to 256 colors (median cut
+//! with optional Floyd-Steinberg dithering) for GIF export.
+
+pub mod animation;
+pub mod codec;
+pub mod exif;
+pub mod icc;
+pub mod quantize;
+
+pub use animation::{encode_apng, encode_gif};
+pub use codec::{
+    decode_image, decode_image_rgba, decode_image_u16, encode_jpeg, encode_png, encode_qoi, encode_tiff,
+    encode_tiff_u16, encode_webp, read_icc_profile, read_metadata, CodecError, ImageFormat, ImageMetadata, JpegOptions, PngOptions,
+};
+pub use exif::{apply_exif_orientation, apply_exif_orientation_f32, read_exif, ExifData};
+pub use icc::{convert_from_srgb, convert_from_srgb_f32, convert_to_srgb, convert_to_srgb_f32, IccColorSpace, IccError, IccProfile, ToneCurve};
+pub use quantize::{build_palette, quantize, remap, IndexedImage, PaletteOptions};
@@ -0,0 +1,273 @@
+//! Color quantization and dithering.
+//!
+//! Reduces RGB(A) images to an indexed palette of up to 256 colors for
+//! palette-based formats such as GIF. The palette is built with median cut
+//! over a 15-bit color histogram; pixels are mapped to it with optional
+//! Floyd-Steinberg error diffusion (shared with
+//! [`posterize_to_palette`](crate::analysis::posterize_to_palette)).
+
+use ndarray::{Array2, ArrayView3};
+
+use crate::analysis::palette::{palette_indices, ALPHA_THRESHOLD};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Palette generation options.
+#[derive(Debug, Clone, Copy)]
+pub struct PaletteOptions {
+    /// Maximum palette size 2-256, including the transparent entry
+    pub max_colors: usize,
+    /// Apply Floyd-Steinberg dithering
+    pub dither: bool,
+}
+
+impl Default for PaletteOptions {
+    fn default() -> Self {
+        Self { max_colors: 256, dither: true }
+    }
+}
+
+/// Image reduced to palette indices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedImage {
+    /// Palette colors (R, G, B)
+    pub palette: Vec<[u8; 3]>,
+    /// Palette index per pixel (height, width)
+    pub indices: Array2<u8>,
+    /// Palette index used for transparent pixels, if any
+    pub transparent: Option<u8>,
+}
+
+// ============================================================================
+// Palette
+// ============================================================================
+
+/// Histogram bin: color sums and pixel count.
+#[derive(Clone, Copy)]
+struct Bin {
+    sum: [u64; 3],
+    count: u64,
+}
+
+impl Bin {
+    fn mean(&self) -> [u8; 3] {
+        self.sum.map(|s| ((s + self.count / 2) / self.count) as u8)
+    }
+}
+
+/// Split the bin set with the widest channel range at its weighted median.
+fn median_cut(bins: Vec<Bin>, max_colors: usize) -> Vec<[u8; 3]> {
+    let mut boxes = vec![bins];
+    while boxes.len() < max_colors {
+        // Widest splittable box and its widest channel
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| {
+                let (channel, range) = (0..3)
+                    .map(|c| {
+                        let values = b.iter().map(|bin| bin.mean()[c]);
+                        let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+                        (c, range)
+                    })
+                    .max_by_key(|&(_, range)| range)
+                    .unwrap_or((0, 0));
+                (i, channel, range)
+            })
+            .max_by_key(|&(_, _, range)| range);
+        let Some((index, channel, range)) = widest else { break };
+        if range == 0 {
+            break;
+        }
+
+        let mut bins = boxes.swap_remove(index);
+        bins.sort_by_key(|bin| bin.mean()[channel]);
+        let total: u64 = bins.iter().map(|b| b.count).sum();
+        let mut seen = 0;
+        let mut split = bins.len() - 1;
+        for (i, bin) in bins.iter().enumerate() {
+            seen += bin.count;
+            if seen * 2 >= total {
+                split = i + 1;
+                break;
+            }
+        }
+        let split = split.clamp(1, bins.len() - 1);
+        let upper = bins.split_off(split);
+        boxes.push(bins);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .filter(|b| !b.is_empty())
+        .map(|b| {
+            let merged = b.iter().fold(Bin { sum: [0; 3], count: 0 }, |acc, bin| Bin {
+                sum: [acc.sum[0] + bin.sum[0], acc.sum[1] + bin.sum[1], acc.sum[2] + bin.sum[2]],
+                count: acc.count + bin.count,
+            });
+            merged.mean()
+        })
+        .collect()
+}
+
+/// Build a palette for one or more images.
+///
+/// Pixels with alpha below 128 are ignored. A palette shared by several
+/// images (e.g. all frames of an animation) avoids flicker between frames.
+///
+/// # Arguments
+/// * `images` - Images (height, width, 3 or 4 channels)
+/// * `max_colors` - Maximum number of colors (1-256)
+pub fn build_palette(images: &[ArrayView3<u8>], max_colors: usize) -> Vec<[u8; 3]> {
+    let mut histogram = vec![Bin { sum: [0; 3], count: 0 }; 1 << 15];
+    for image in images {
+        let channels = image.shape()[2];
+        for pixel in image.lanes(ndarray::Axis(2)) {
+            if channels == 4 && pixel[3] < ALPHA_THRESHOLD {
+                continue;
+            }
+            let rgb = if channels >= 3 { [pixel[0], pixel[1], pixel[2]] } else { [pixel[0]; 3] };
+            let key = ((rgb[0] as usize >> 3) << 10) | ((rgb[1] as usize >> 3) << 5) | (rgb[2] as usize >> 3);
+            let bin = &mut histogram[key];
+            for (sum, &v) in bin.sum.iter_mut().zip(&rgb) {
+                *sum += v as u64;
+            }
+            bin.count += 1;
+        }
+    }
+
+    let bins: Vec<Bin> = histogram.into_iter().filter(|b| b.count > 0).collect();
+    if bins.is_empty() {
+        return vec![[0, 0, 0]];
+    }
+    median_cut(bins, max_colors.clamp(1, 256))
+}
+
+// ============================================================================
+// Mapping
+// ============================================================================
+
+/// Map an image to palette indices.
+///
+/// # Arguments
+/// * `image` - Image (height, width, 1, 3 or 4 channels)
+/// * `palette` - Palette colors (at most 256, or 255 if `transparent` is set)
+/// * `transparent` - Index for pixels with alpha below 128
+/// * `dither` - Apply Floyd-Steinberg dithering
+pub fn remap(image: ArrayView3<u8>, palette: &[[u8; 3]], transparent: Option<u8>, dither: bool) -> Array2<u8> {
+    palette_indices(image, palette, transparent, dither)
+}
+
+/// Reduce an image to an indexed palette.
+///
+/// If the image has transparent pixels (alpha below 128), one palette
+/// entry is reserved for them.
+///
+/// # Arguments
+/// * `image` - Image (height, width, 1, 3 or 4 channels)
+/// * `options` - Palette size and dithering
+pub fn quantize(image: ArrayView3<u8>, options: &PaletteOptions) -> IndexedImage {
+    quantize_frames(&[image], options).pop().unwrap_or_else(|| IndexedImage {
+        palette: vec![[0, 0, 0]],
+        indices: Array2::zeros((0, 0)),
+        transparent: None,
+    })
+}
+
+/// Reduce several images to indexed images sharing one palette.
+pub(crate) fn quantize_frames(images: &[ArrayView3<u8>], options: &PaletteOptions) -> Vec<IndexedImage> {
+    let has_transparency = images
+        .iter()
+        .any(|image| image.shape()[2] == 4 && image.lanes(ndarray::Axis(2)).into_iter().any(|p| p[3] < ALPHA_THRESHOLD));
+    let max_colors = options.max_colors.clamp(2, 256);
+    let mut palette = build_palette(images, if has_transparency { max_colors - 1 } else { max_colors });
+    let transparent = has_transparency.then(|| {
+        palette.push([0, 0, 0]);
+        (palette.len() - 1) as u8
+    });
+
+    images
+        .iter()
+        .map(|image| {
+            let indices = remap(*image, &palette[..palette.len() - transparent.is_some() as usize], transparent, options.dither);
+            IndexedImage { palette: palette.clone(), indices, transparent }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Reduce a uint8 image to an indexed palette.
+///
+/// # Arguments
+/// * `image` - Image array (height, width, channels)
+/// * `max_colors` - Maximum palette size 2-256
+/// * `dither` - Apply Floyd-Steinberg dithering
+///
+/// # Returns
+/// Tuple of (indices (height, width), palette (colors, 3), transparent index or None)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (image, max_colors=256, dither=true))]
+pub fn quantize_image<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    max_colors: usize,
+    dither: bool,
+) -> (Bound<'py, PyArray2<u8>>, Bound<'py, PyArray2<u8>>, Option<u8>) {
+    let indexed = quantize(image.as_array(), &PaletteOptions { max_colors, dither });
+    let palette = Array2::from_shape_fn((indexed.palette.len(), 3), |(i, c)| indexed.palette[i][c]);
+    (indexed.indices.into_pyarray(py), palette.into_pyarray(py), indexed.transparent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+
+    #[test]
+    fn test_exact_palette_for_few_colors() {
+        let colors = [[255u8, 0, 0], [0, 255, 0], [0, 0, 255], [40, 40, 40]];
+        let image = Array3::from_shape_fn((4, 8, 3), |(y, _, c)| colors[y][c]);
+        let indexed = quantize(image.view(), &PaletteOptions { max_colors: 8, dither: true });
+        assert_eq!(indexed.palette.len(), 4);
+        assert_eq!(indexed.transparent, None);
+        for ((y, _), &index) in indexed.indices.indexed_iter() {
+            assert_eq!(indexed.palette[index as usize], colors[y]);
+        }
+    }
+
+    #[test]
+    fn test_palette_size_limit_and_dither() {
+        let image = Array3::from_shape_fn((16, 64, 3), |(y, x, c)| (x * 3 + c * 10 + y) as u8);
+        let indexed = quantize(image.view(), &PaletteOptions { max_colors: 4, dither: false });
+        assert_eq!(indexed.palette.len(), 4);
+
+        // Flat gray between black and white: plain mapping snaps to black,
+        // dithering mixes both so the average stays close to the source
+        let gray = Array3::from_elem((16, 16, 3), 100u8);
+        let palette = [[0, 0, 0], [255, 255, 255]];
+        let mean = |indices: &Array2<u8>| indices.iter().map(|&i| palette[i as usize][0] as f32).sum::<f32>() / indices.len() as f32;
+        assert_eq!(mean(&remap(gray.view(), &palette, None, false)), 0.0);
+        assert!((mean(&remap(gray.view(), &palette, None, true)) - 100.0).abs() < 8.0);
+    }
+
+    #[test]
+    fn test_transparent_index() {
+        let image = Array3::from_shape_fn((2, 2, 4), |(y, _, c)| if c == 3 { if y == 0 { 255 } else { 0 } } else { 200 });
+        let indexed = quantize(image.view(), &PaletteOptions::default());
+        let t = indexed.transparent.expect("transparent index");
+        assert_eq!(indexed.indices[[1, 0]], t);
+        assert_ne!(indexed.indices[[0, 0]], t);
+        assert_eq!(indexed.palette[indexed.indices[[0, 0]] as usize], [200, 200, 200]);
+    }
+}
@@ -5,6 +5,10 @@
 //! Rust implementations are co-located with Python and JS wrappers in `imagestag/layer_effects/`.
 //! This mod.rs uses `#[path]` attributes to include them from that location.
 //!
+//! Every effect exposes a binding-independent core (parameter struct plus
+//! coverage mask or renderer); only the `#[pyfunction]` wrappers are gated
+//! behind the `python` feature.
+//!
 //! Each effect has a triplet of files:
 //! - `effect_name.rs` - Rust implementation
 //! - `effect_name.py` - Python wrapper
@@ -13,6 +17,8 @@
 //! ## Shadow Effects
 //! - **Drop Shadow** - Shadow cast behind the layer
 //! - **Inner Shadow** - Shadow inside the layer edges
+//! - **Cast Shadow** - Perspective ground shadow with distance-dependent blur
+//! - **Long Shadow** - Flat-design silhouette extruded along a direction
 //!
 //! ## Glow Effects
 //! - **Outer Glow** - Glow radiating outward from edges
@@ -29,6 +35,12 @@
 //!
 //! ## Stroke
 //! - **Stroke** - Outline around layer content
+//!
+//! ## Layer Style
+//! - **Layer Style** - All effects of a layer rendered together, with
+//!   fill opacity, knockout and layer mask handling
+//! - **PSD Style Import** - Converts Photoshop effect blocks (`lfx2`, `lrFX`)
+//!   into a layer style
 
 // Shadow effects
 #[path = "../../../imagestag/layer_effects/drop_shadow.rs"]
@@ -37,6 +49,12 @@ pub mod drop_shadow;
 #[path = "../../../imagestag/layer_effects/inner_shadow.rs"]
 pub mod inner_shadow;
 
+#[path = "../../../imagestag/layer_effects/cast_shadow.rs"]
+pub mod cast_shadow;
+
+#[path = "../../../imagestag/layer_effects/long_shadow.rs"]
+pub mod long_shadow;
+
 // Glow effects
 #[path = "../../../imagestag/layer_effects/outer_glow.rs"]
 pub mod outer_glow;
@@ -64,3 +82,14 @@ pub mod pattern_overlay;
 // Stroke effect
 #[path = "../../../imagestag/layer_effects/stroke.rs"]
 pub mod stroke;
+
+// Combined layer style renderer
+#[path = "../../../imagestag/layer_effects/layer_style.rs"]
+pub mod layer_style;
+
+// Photoshop layer style import
+#[path = "../../../imagestag/layer_effects/psd_style.rs"]
+pub mod psd_style;
+
+#[cfg(test)]
+mod test_util;
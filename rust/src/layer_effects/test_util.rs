@@ -0,0 +1,33 @@
+//! Fixtures shared by the layer effect tests.
+
+use ndarray::{s, Array3};
+
+/// Layer height of [`full_layer`].
+pub const LAYER_HEIGHT: usize = 16;
+/// Layer width of [`full_layer`].
+pub const LAYER_WIDTH: usize = 20;
+
+/// Opaque layer of `gray` whose content touches every edge.
+pub fn full_layer(gray: f32) -> Array3<f32> {
+    Array3::from_shape_fn((LAYER_HEIGHT, LAYER_WIDTH, 4), |(_, _, c)| if c == 3 { 1.0 } else { gray })
+}
+
+/// Largest alpha on the outermost ring of pixels.
+pub fn max_border_alpha(image: &Array3<f32>) -> f32 {
+    let (height, width, _) = image.dim();
+    let border = (0..width).flat_map(|x| [(0, x), (height - 1, x)]).chain((0..height).flat_map(|y| [(y, 0), (y, width - 1)]));
+    border.map(|(y, x)| image[[y, x, 3]]).fold(0.0, f32::max)
+}
+
+/// Check that `result` is `layer` grown by `expand` on every side with
+/// nothing reaching the canvas edge.
+pub fn assert_fits_padding(result: &Array3<f32>, expand: usize, context: &str) {
+    assert_eq!(result.dim(), (LAYER_HEIGHT + 2 * expand, LAYER_WIDTH + 2 * expand, 4), "{context}");
+    let edge = max_border_alpha(result);
+    assert!(edge < 1.0 / 255.0, "clipped with {context}: edge alpha {edge}");
+}
+
+/// Check that the opaque `layer` sits unchanged at (expand, expand).
+pub fn assert_layer_on_top(result: &Array3<f32>, layer: &Array3<f32>, expand: usize) {
+    assert_eq!(result.slice(s![expand..expand + LAYER_HEIGHT, expand..expand + LAYER_WIDTH, ..]), layer);
+}
@@ -19,42 +19,87 @@
 //! ## Filter Architecture
 //! Filters can produce output images with different dimensions than input,
 //! useful for effects like drop shadows that extend beyond the original bounds.
-
+//!
+//! ## Using from Rust
+//! Every algorithm is plain Rust on `ndarray` arrays and available with
+//! `default-features = false`; the `python` and `wasm` features only add the
+//! PyO3 / wasm-bindgen wrappers. Filters come as `<filter>_u8` / `<filter>_f32`
+//! pairs; layer effects take a `<Effect>Params` struct. [`Image`] wraps an
+//! array with its channel layout and color space; filters that take
+//! `impl Into<ArrayView3<T>>` accept it directly. See `examples/pure_rust.rs`.
+
+pub mod analysis;
+pub mod batch;
+pub mod chunked;
 pub mod filters;
-pub mod selection;
-
+pub mod generate;
+pub mod gpu;
 #[cfg(feature = "python")]
+pub mod host_array;
 pub mod layer_effects;
+pub mod painting;
+pub mod profiling;
+pub mod progress;
+pub mod selection;
+pub mod sprites;
+pub mod typed_image;
+
+pub use typed_image::{ChannelLayout, ColorSpace, Image, ImageError};
+
+#[cfg(feature = "encode")]
+pub mod io;
+
+#[cfg(feature = "capi")]
+pub mod capi;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
+#[cfg(feature = "wasm-threads")]
+pub mod wasm_threads;
+
 // Python bindings (only when python feature is enabled)
 #[cfg(feature = "python")]
 mod python {
-    use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+    use numpy::{IntoPyArray, PyArray2, PyArray3, PyArray4};
+    use crate::host_array::{PyHostArray2, PyHostArray3, PyHostArray4};
     use pyo3::prelude::*;
     use std::collections::HashMap;
 
     // Layer effects (each in its own module)
-    use crate::layer_effects::drop_shadow::{drop_shadow_rgba, drop_shadow_rgba_f32, drop_shadow_only_rgba, drop_shadow_only_rgba_f32};
+    use crate::layer_effects::drop_shadow::{
+        drop_shadow_rgba, drop_shadow_rgba_f32, drop_shadow_only_rgba, drop_shadow_only_rgba_f32, drop_shadow_expansion,
+    };
     use crate::layer_effects::inner_shadow::{inner_shadow_rgba, inner_shadow_rgba_f32};
-    use crate::layer_effects::outer_glow::{outer_glow_rgba, outer_glow_rgba_f32, outer_glow_only_rgba, outer_glow_only_rgba_f32};
+    use crate::layer_effects::cast_shadow::{cast_shadow_rgba, cast_shadow_rgba_f32_py, cast_shadow_expansion};
+    use crate::layer_effects::long_shadow::{long_shadow_rgba, long_shadow_rgba_f32_py};
+    use crate::layer_effects::outer_glow::{
+        outer_glow_rgba, outer_glow_rgba_f32, outer_glow_only_rgba, outer_glow_only_rgba_f32,
+        outer_glow_gradient_rgba, outer_glow_gradient_rgba_f32, outer_glow_expansion,
+    };
     use crate::layer_effects::inner_glow::{inner_glow_rgba, inner_glow_rgba_f32, inner_glow_only_rgba, inner_glow_only_rgba_f32};
     use crate::layer_effects::bevel_emboss::{bevel_emboss_rgba, bevel_emboss_rgba_f32};
     use crate::layer_effects::satin::{satin_rgba, satin_rgba_f32};
     use crate::layer_effects::color_overlay::{color_overlay_rgba, color_overlay_rgba_f32};
     use crate::layer_effects::gradient_overlay::{gradient_overlay_rgba, gradient_overlay_rgba_f32};
     use crate::layer_effects::pattern_overlay::{pattern_overlay_rgba, pattern_overlay_rgba_f32};
-    use crate::filters::gradient_generator::{generate_gradient, generate_gradient_f32};
-    use crate::layer_effects::stroke::{stroke_rgba, stroke_rgba_f32, stroke_only_rgba, stroke_only_rgba_f32};
+    use crate::filters::gradient_generator::{generate_gradient, generate_gradient_f32_py};
+    use crate::layer_effects::stroke::{stroke_rgba, stroke_rgba_f32, stroke_only_rgba, stroke_only_rgba_f32, stroke_expansion};
+    use crate::layer_effects::psd_style::{psd_layer_style_rgba, psd_layer_style_rgba_f32, asl_style_names, asl_layer_style_rgba, asl_layer_style_rgba_f32};
     use crate::filters::blur::{gaussian_blur_rgba, gaussian_blur_rgba_f32, box_blur_rgba, box_blur_rgba_f32};
     use crate::filters::basic::{threshold_gray, invert_rgba, premultiply_alpha, unpremultiply_alpha};
+    use crate::filters::basic::{
+        split_channels_py, split_channels_f32_py, merge_channels_py, merge_channels_f32_py, swap_channels_py,
+        swap_channels_f32_py, set_channel_py, set_channel_f32_py, alpha_from_luminance_py,
+        alpha_from_luminance_f32_py, threshold_alpha_py, threshold_alpha_f32_py, color_to_alpha_py,
+        color_to_alpha_f32_py, image_arithmetic_py, image_arithmetic_f32_py,
+    };
     use crate::filters::grayscale::{
-        grayscale_rgba_u8, grayscale_rgba_f32 as grayscale_f32_impl,
-        grayscale_weighted_u8, grayscale_weighted_f32, GrayscaleWeights,
+        grayscale_weighted_u8, grayscale_weighted_f32, GrayscaleWeights, LumaStandard,
+        grayscale_to_single_channel_weighted_u8, grayscale_to_single_channel_weighted_f32,
         u8_to_f32 as u8_to_f32_impl, f32_to_u8 as f32_to_u8_impl,
         f32_to_u16_12bit as f32_to_12bit_impl, u16_12bit_to_f32 as u12bit_to_f32_impl,
+        pack_12bit as pack_12bit_impl, unpack_12bit as unpack_12bit_impl,
     };
 
     // Cross-platform filters
@@ -64,49 +109,207 @@ mod python {
     use crate::filters::levels_curves;
     use crate::filters::sharpen as sharpen_mod;
     use crate::filters::edge;
+    use crate::filters::convolve::{self as convolve_mod, BorderMode};
+    use crate::filters::core::{box_filter_via_integral_f32 as box_filter_f32_impl, box_filter_via_integral_u8 as box_filter_u8_impl};
     use crate::filters::noise as noise_mod;
     use crate::filters::morphology;
     use crate::filters::rotate as rotate_mod;
+    use crate::filters::resample;
+    use crate::filters::straighten;
+    use crate::filters::perspective::{self, Quad};
+    use crate::filters::matting;
+    use crate::filters::glitch;
+    use crate::filters::material;
+    use crate::filters::wavelet;
+    use crate::filters::tonemap::{tonemap_f32, TonemapOperator, TonemapParams};
+    use crate::filters::colorspace::{decode_transfer_f32, encode_transfer_f32, TransferFunction};
+    use crate::filters::compositing::{self, AnaglyphMode};
+    use crate::filters::raw::{self as raw_mod, BayerPattern, DemosaicAlgorithm, RawParams};
+    use crate::filters::deband::{deband_f32 as deband_f32_impl, deband_u8, DebandParams};
+    use crate::filters::fft::{self as fft_mod, Complex, Notch};
+    use crate::filters::projection::{self, ViewParams};
+    use crate::filters::distort::{self, Interpolation, PolarDirection, WaveType};
+    use crate::filters::canvas::{self, ExtendMethod, TrimMode};
+    use crate::filters::synthesis;
+    use crate::filters::seamless::{self, SeamlessMethod};
+    use crate::filters::sanitize::{self, RepairMode, SanitizeMode, ValidationPolicy, ValidationReport};
 
     // Selection algorithms
     use crate::selection::contour::extract_contours as extract_contours_impl;
-    use crate::selection::magic_wand::magic_wand_select as magic_wand_impl;
+    use crate::selection::distance::{distance_transform as distance_transform_impl, DistanceMetric};
+    use crate::selection::magic_wand::{
+        auto_tolerance as auto_tolerance_impl, magic_wand_select as magic_wand_impl, magic_wand_select_soft as magic_wand_soft_impl,
+    };
+    use crate::selection::watershed::watershed as watershed_impl;
+    use crate::selection::refine::snap_mask_to_edges as snap_mask_to_edges_impl;
+    use crate::selection::components::{label_components as label_components_impl, remove_small_components as remove_small_components_impl};
+    use crate::selection::stats::{label_summaries as label_summaries_impl, mask_summary as mask_summary_impl, MaskSummary};
     use crate::selection::marching_squares::{
         extract_contours_precise as extract_contours_precise_impl,
+        extract_contours_downsampled as extract_contours_downsampled_impl,
         contours_to_svg as contours_to_svg_impl,
         douglas_peucker as douglas_peucker_impl,
         douglas_peucker_closed as douglas_peucker_closed_impl,
         Point as MarchingPoint,
+        Contour as MarchingContour,
+        BezierSegment as MarchingBezier,
+    };
+    use crate::selection::dashes::contour_to_dash_segments as contour_to_dash_segments_impl;
+    use crate::selection::trace::trace_image_to_svg as trace_image_to_svg_impl;
+    use crate::selection::fill::{fill_contours as fill_contours_impl, FillRule};
+    use crate::selection::path_ops::{path_boolean as path_boolean_impl, stroke_path_outline as stroke_path_outline_impl, LineCap, LineJoin, PathOp};
+
+    // Sprite sheets
+    use crate::batch::{batch_filter_py, batch_filter_f32_py, map_py_frames};
+    use crate::host_array::to_host_array_py;
+    use crate::batch::context::PyFilterContext;
+    use crate::batch::registry::{filter_registry_py, apply_filter_py, apply_filter_f32_py};
+    use crate::batch::recipe::{apply_recipe_py, apply_recipe_f32_py, validate_recipe_py, recipe_to_cbor_py};
+    use crate::batch::region::{apply_in_rect_py, apply_in_rect_f32_py};
+    use crate::batch::adjustment::{preview_adjustment_py, preview_adjustment_f32_py};
+    use crate::batch::export::render_export_py;
+    use crate::batch::proxy::render_proxy_py;
+    use crate::batch::plan::plan_operation_py;
+    use crate::profiling::get_last_perf_report_py;
+    use crate::filters::pool::release_buffer_pool_py;
+    use crate::progress::{with_py_progress, CancelledError};
+    use crate::gpu::{
+        gpu_available_py, gpu_gaussian_blur_f32_py, gpu_convolve_f32_py, gpu_levels_f32_py, gpu_curves_f32_py,
+        gpu_blend_f32_py,
+    };
+    use crate::sprites::sheet::{pack_sprites_py, pack_sprites_f32_py, slice_grid_py, slice_grid_f32_py};
+    use crate::painting::symmetry::{replicate_symmetric_py, replicate_symmetric_f32_py};
+    use crate::painting::stroke::interpolate_stroke_py;
+    use crate::painting::bucket::{bucket_fill_py, bucket_fill_layer_py};
+
+    // Feature detection
+    use crate::analysis::features::{
+        harris_corners_py, harris_corners_f32_py, fast_corners_py, fast_corners_f32_py, orb_features_py,
+        orb_features_f32_py, match_descriptors_py,
+    };
+    use crate::analysis::superpixels::{slic_py, slic_f32_py};
+    use crate::analysis::motion::{motion_mask_py, motion_mask_f32_py};
+    use crate::analysis::flow::{
+        optical_flow_py, optical_flow_f32_py, warp_by_flow_py, warp_by_flow_f32_py, interpolate_frame_py, interpolate_frame_f32_py,
     };
+    use crate::analysis::palette::{extract_palette_py, posterize_to_palette_py};
+    use crate::analysis::skin::{skin_mask_py, skin_mask_f32_py};
+    use crate::analysis::noise::{estimate_noise_sigma_py, estimate_noise_sigma_f32_py};
+    use crate::analysis::sharpness::{estimate_blur_py, estimate_blur_f32_py, sharpness_map_py, sharpness_map_f32_py};
+    use crate::analysis::hash::{ahash_py, ahash_f32_py, dhash_py, dhash_f32_py, phash_py, phash_f32_py, hamming_distance_py};
+    use crate::analysis::contrast::{contrast_ratio_py, contrast_ratio_map_py, contrast_ratio_map_f32_py};
+    use crate::analysis::stats::{
+        average_color_py, average_color_f32_py, sample_color_py, sample_color_f32_py,
+        histogram_masked_py, histogram_masked_f32_py, stats_masked_py, stats_masked_f32_py,
+    };
+    use crate::generate::qr::render_qr_py;
+
+    // Image encode/decode
+    #[cfg(feature = "encode")]
+    use crate::io::codec::{decode_image_py, decode_image_u16_py, encode_png_py, encode_jpeg_py, encode_webp_py, encode_qoi_py, encode_tiff_py, encode_tiff_u16_py};
+    #[cfg(feature = "encode")]
+    use crate::io::exif::{read_exif_py, apply_exif_orientation_py, apply_exif_orientation_f32_py};
+    #[cfg(feature = "encode")]
+    use crate::io::icc::{read_icc_profile_py, icc_profile_info, icc_to_srgb, icc_to_srgb_f32, icc_from_srgb, icc_from_srgb_f32, soft_proof_py, soft_proof_f32_py};
+    #[cfg(feature = "encode")]
+    use crate::io::quantize::quantize_image;
+    #[cfg(feature = "encode")]
+    use crate::io::animation::{encode_gif_py, encode_apng_py};
 
     // ========================================================================
     // Grayscale Filter
     // ========================================================================
 
+    /// Parse a luma standard name ("bt601", "bt709", "bt2020").
+    fn parse_luma(name: &str) -> PyResult<LumaStandard> {
+        LumaStandard::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown luma standard '{}'", name)))
+    }
+
     /// Convert RGBA u8 image to grayscale using BT.709 luminosity.
     ///
-    /// Output is RGBA with R=G=B=luminosity, alpha preserved.
+    /// Output is RGBA with R=G=B=luminosity, alpha preserved. `luma`
+    /// selects the coefficients: "bt601", "bt709" or "bt2020".
     #[pyfunction]
+    #[pyo3(signature = (image, luma="bt709"))]
     pub fn grayscale_rgba<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let input = image.as_array();
-        let result = grayscale_rgba_u8(input);
-        result.into_pyarray(py)
+        image: &Bound<'py, PyAny>,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let weights: GrayscaleWeights = parse_luma(luma)?.into();
+        map_py_frames(image, |frame| grayscale_weighted_u8(frame, weights))
     }
 
     /// Convert RGBA f32 image to grayscale using BT.709 luminosity.
     ///
-    /// Input/output values are 0.0-1.0.
+    /// Input/output values are 0.0-1.0. `luma` as for `grayscale_rgba`.
     #[pyfunction]
+    #[pyo3(signature = (image, luma="bt709"))]
     pub fn grayscale_rgba_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let input = image.as_array();
-        let result = grayscale_f32_impl(input);
-        result.into_pyarray(py)
+        image: &Bound<'py, PyAny>,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let weights: GrayscaleWeights = parse_luma(luma)?.into();
+        map_py_frames(image, |frame| grayscale_weighted_f32(frame, weights))
+    }
+
+    /// Luminance as a single channel (H, W, 1) (u8).
+    ///
+    /// Same values as `grayscale_rgba` without the duplicated channels and
+    /// alpha, for masks. `luma` as for `grayscale_rgba`.
+    #[pyfunction]
+    #[pyo3(name = "grayscale_to_single_channel", signature = (image, luma="bt709"))]
+    pub fn grayscale_to_single_channel_py<'py>(
+        image: &Bound<'py, PyAny>,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let weights: GrayscaleWeights = parse_luma(luma)?.into();
+        map_py_frames(image, |frame| grayscale_to_single_channel_weighted_u8(frame, weights))
+    }
+
+    /// Luminance as a single channel (H, W, 1) (f32).
+    #[pyfunction]
+    #[pyo3(name = "grayscale_to_single_channel_f32", signature = (image, luma="bt709"))]
+    pub fn grayscale_to_single_channel_f32_py<'py>(
+        image: &Bound<'py, PyAny>,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let weights: GrayscaleWeights = parse_luma(luma)?.into();
+        map_py_frames(image, |frame| grayscale_to_single_channel_weighted_f32(frame, weights))
+    }
+
+    /// Luminance with custom RGB channel weights as a single channel (H, W, 1) (u8).
+    ///
+    /// Weights are normalized automatically.
+    #[pyfunction]
+    #[pyo3(
+        name = "grayscale_to_single_channel_weighted",
+        signature = (image, r_weight=0.2126, g_weight=0.7152, b_weight=0.0722)
+    )]
+    pub fn grayscale_to_single_channel_weighted_py<'py>(
+        image: &Bound<'py, PyAny>,
+        r_weight: f32,
+        g_weight: f32,
+        b_weight: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let weights = GrayscaleWeights::custom(r_weight, g_weight, b_weight);
+        map_py_frames(image, |frame| grayscale_to_single_channel_weighted_u8(frame, weights))
+    }
+
+    /// Luminance with custom RGB channel weights as a single channel (H, W, 1) (f32).
+    #[pyfunction]
+    #[pyo3(
+        name = "grayscale_to_single_channel_weighted_f32",
+        signature = (image, r_weight=0.2126, g_weight=0.7152, b_weight=0.0722)
+    )]
+    pub fn grayscale_to_single_channel_weighted_f32_py<'py>(
+        image: &Bound<'py, PyAny>,
+        r_weight: f32,
+        g_weight: f32,
+        b_weight: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let weights = GrayscaleWeights::custom(r_weight, g_weight, b_weight);
+        map_py_frames(image, |frame| grayscale_to_single_channel_weighted_f32(frame, weights))
     }
 
     /// Convert image to grayscale with custom RGB channel weights (u8).
@@ -122,16 +325,13 @@ mod python {
     #[pyfunction]
     #[pyo3(signature = (image, r_weight=0.2126, g_weight=0.7152, b_weight=0.0722))]
     pub fn grayscale_weighted<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         r_weight: f32,
         g_weight: f32,
         b_weight: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let input = image.as_array();
+    ) -> PyResult<Bound<'py, PyAny>> {
         let weights = GrayscaleWeights::custom(r_weight, g_weight, b_weight);
-        let result = grayscale_weighted_u8(input, weights);
-        result.into_pyarray(py)
+        map_py_frames(image, |frame| grayscale_weighted_u8(frame, weights))
     }
 
     /// Convert image to grayscale with custom RGB channel weights (f32).
@@ -140,16 +340,13 @@ mod python {
     #[pyfunction]
     #[pyo3(signature = (image, r_weight=0.2126, g_weight=0.7152, b_weight=0.0722))]
     pub fn grayscale_weighted_f32_py<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         r_weight: f32,
         g_weight: f32,
         b_weight: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let input = image.as_array();
+    ) -> PyResult<Bound<'py, PyAny>> {
         let weights = GrayscaleWeights::custom(r_weight, g_weight, b_weight);
-        let result = grayscale_weighted_f32(input, weights);
-        result.into_pyarray(py)
+        map_py_frames(image, |frame| grayscale_weighted_f32(frame, weights))
     }
 
     // ========================================================================
@@ -160,7 +357,7 @@ mod python {
     #[pyfunction]
     pub fn convert_u8_to_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
     ) -> Bound<'py, PyArray3<f32>> {
         let input = image.as_array();
         let result = u8_to_f32_impl(input);
@@ -171,7 +368,7 @@ mod python {
     #[pyfunction]
     pub fn convert_f32_to_u8<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
     ) -> Bound<'py, PyArray3<u8>> {
         let input = image.as_array();
         let result = f32_to_u8_impl(input);
@@ -182,7 +379,7 @@ mod python {
     #[pyfunction]
     pub fn convert_f32_to_12bit<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
     ) -> Bound<'py, PyArray3<u16>> {
         let input = image.as_array();
         let result = f32_to_12bit_impl(input);
@@ -193,137 +390,173 @@ mod python {
     #[pyfunction]
     pub fn convert_12bit_to_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u16>,
+        image: PyHostArray3<'py, u16>,
     ) -> Bound<'py, PyArray3<f32>> {
         let input = image.as_array();
         let result = u12bit_to_f32_impl(input);
         result.into_pyarray(py)
     }
 
+    /// Pack a u16 12-bit image into two-pixels-in-three-bytes (RAW12) bytes
+    #[pyfunction]
+    pub fn pack_12bit<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u16>,
+    ) -> Bound<'py, pyo3::types::PyBytes> {
+        let values: Vec<u16> = image.as_array().iter().copied().collect();
+        pyo3::types::PyBytes::new(py, &pack_12bit_impl(&values))
+    }
+
+    /// Unpack RAW12 bytes into a u16 12-bit image (height, width, channels)
+    #[pyfunction]
+    #[pyo3(signature = (data, width, height, channels=1))]
+    pub fn unpack_12bit<'py>(
+        py: Python<'py>,
+        data: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+    ) -> PyResult<Bound<'py, PyArray3<u16>>> {
+        let count = width * height * channels;
+        let values = unpack_12bit_impl(data, count).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("{} bytes hold fewer than {} 12-bit values", data.len(), count))
+        })?;
+        let image = ndarray::Array3::from_shape_vec((height, width, channels), values)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(image.into_pyarray(py))
+    }
+
     // ========================================================================
     // Color Adjustment Filters
     // ========================================================================
 
+    // Per-pixel filters, grayscale and the 90-degree rotations take an
+    // (H, W, C) image or an (N, H, W, C) frame stack via map_py_frames.
+
     #[pyfunction]
     pub fn brightness<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::brightness_u8(image.as_array(), amount);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::brightness_u8(frame, amount))
     }
 
     #[pyfunction]
     pub fn brightness_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::brightness_f32(image.as_array(), amount);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::brightness_f32(frame, amount))
+    }
+
+    #[pyfunction]
+    pub fn brightness_12bit<'py>(
+        image: &Bound<'py, PyAny>,
+        amount: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::brightness_12bit(frame, amount))
     }
 
     #[pyfunction]
     pub fn contrast<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::contrast_u8(image.as_array(), amount);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::contrast_u8(frame, amount))
     }
 
     #[pyfunction]
     pub fn contrast_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::contrast_f32(image.as_array(), amount);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::contrast_f32(frame, amount))
+    }
+
+    #[pyfunction]
+    pub fn contrast_12bit<'py>(
+        image: &Bound<'py, PyAny>,
+        amount: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::contrast_12bit(frame, amount))
     }
 
     #[pyfunction]
     pub fn saturation<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::saturation_u8(image.as_array(), amount);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::saturation_u8(frame, amount))
     }
 
     #[pyfunction]
     pub fn saturation_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::saturation_f32(image.as_array(), amount);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::saturation_f32(frame, amount))
     }
 
     #[pyfunction]
     pub fn gamma<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         gamma_val: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::gamma_u8(image.as_array(), gamma_val);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::gamma_u8(frame, gamma_val))
     }
 
     #[pyfunction]
     pub fn gamma_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         gamma_val: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::gamma_f32(image.as_array(), gamma_val);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::gamma_f32(frame, gamma_val))
     }
 
     #[pyfunction]
     pub fn exposure<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         exposure_val: f32,
         offset: f32,
         gamma_val: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::exposure_u8(image.as_array(), exposure_val, offset, gamma_val);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::exposure_u8(frame, exposure_val, offset, gamma_val))
     }
 
     #[pyfunction]
     pub fn exposure_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         exposure_val: f32,
         offset: f32,
         gamma_val: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::exposure_f32(image.as_array(), exposure_val, offset, gamma_val);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_adjust::exposure_f32(frame, exposure_val, offset, gamma_val))
+    }
+
+    /// Parse a channel selection ("rgb", "r", "a", "rgba", ...).
+    fn parse_channel_selection(name: &str) -> PyResult<color_adjust::ChannelSelection> {
+        color_adjust::ChannelSelection::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown channel selection '{}'", name)))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, channels="rgb"))]
     pub fn invert<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::invert_u8(image.as_array());
-        result.into_pyarray(py)
+        image: &Bound<'py, PyAny>,
+        channels: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let selection = parse_channel_selection(channels)?;
+        map_py_frames(image, |frame| color_adjust::invert_channels_u8(frame, selection))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, channels="rgb"))]
     pub fn invert_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::invert_f32(image.as_array());
-        result.into_pyarray(py)
+        image: &Bound<'py, PyAny>,
+        channels: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let selection = parse_channel_selection(channels)?;
+        map_py_frames(image, |frame| color_adjust::invert_channels_f32(frame, selection))
     }
 
     // ========================================================================
@@ -332,66 +565,84 @@ mod python {
 
     #[pyfunction]
     pub fn hue_shift<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         degrees: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_science::hue_shift_u8(image.as_array(), degrees);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::hue_shift_u8(frame, degrees))
     }
 
     #[pyfunction]
     pub fn hue_shift_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         degrees: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_science::hue_shift_f32(image.as_array(), degrees);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::hue_shift_f32(frame, degrees))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, hue=0.0, saturation=0.0, lightness=0.0, colorize=false))]
+    pub fn hsl_adjust<'py>(
+        image: &Bound<'py, PyAny>,
+        hue: f32,
+        saturation: f32,
+        lightness: f32,
+        colorize: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let params = color_science::HslAdjustParams { hue, saturation, lightness, colorize };
+        map_py_frames(image, |frame| color_science::hsl_adjust_u8(frame, &params))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, hue=0.0, saturation=0.0, lightness=0.0, colorize=false))]
+    pub fn hsl_adjust_f32<'py>(
+        image: &Bound<'py, PyAny>,
+        hue: f32,
+        saturation: f32,
+        lightness: f32,
+        colorize: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let params = color_science::HslAdjustParams { hue, saturation, lightness, colorize };
+        map_py_frames(image, |frame| color_science::hsl_adjust_f32(frame, &params))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, amount, protect_skin=0.0))]
     pub fn vibrance<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_science::vibrance_u8(image.as_array(), amount);
-        result.into_pyarray(py)
+        protect_skin: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::vibrance_protected_u8(frame, amount, protect_skin))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, amount, protect_skin=0.0))]
     pub fn vibrance_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         amount: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_science::vibrance_f32(image.as_array(), amount);
-        result.into_pyarray(py)
+        protect_skin: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::vibrance_protected_f32(frame, amount, protect_skin))
     }
 
     #[pyfunction]
     pub fn color_balance<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         shadows: [f32; 3],
         midtones: [f32; 3],
         highlights: [f32; 3],
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_science::color_balance_u8(image.as_array(), shadows, midtones, highlights);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::color_balance_u8(frame, shadows, midtones, highlights))
     }
 
     #[pyfunction]
     pub fn color_balance_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         shadows: [f32; 3],
         midtones: [f32; 3],
         highlights: [f32; 3],
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_science::color_balance_f32(image.as_array(), shadows, midtones, highlights);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::color_balance_f32(frame, shadows, midtones, highlights))
     }
 
     // ========================================================================
@@ -400,91 +651,125 @@ mod python {
 
     #[pyfunction]
     pub fn posterize<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         levels: u8,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = stylize::posterize_u8(image.as_array(), levels);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| stylize::posterize_u8(frame, levels))
     }
 
     #[pyfunction]
     pub fn posterize_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         levels: u8,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = stylize::posterize_f32(image.as_array(), levels);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| stylize::posterize_f32(frame, levels))
     }
 
     #[pyfunction]
     pub fn solarize<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         threshold: u8,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = stylize::solarize_u8(image.as_array(), threshold);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| stylize::solarize_u8(frame, threshold))
     }
 
     #[pyfunction]
     pub fn solarize_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         threshold: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = stylize::solarize_f32(image.as_array(), threshold);
-        result.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| stylize::solarize_f32(frame, threshold))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, threshold_val, luma="bt709"))]
     pub fn threshold<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         threshold_val: u8,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = stylize::threshold_u8(image.as_array(), threshold_val);
-        result.into_pyarray(py)
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let luma = parse_luma(luma)?;
+        map_py_frames(image, |frame| stylize::threshold_u8_with_luma(frame, threshold_val, luma))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, threshold_val, luma="bt709"))]
     pub fn threshold_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         threshold_val: f32,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let luma = parse_luma(luma)?;
+        map_py_frames(image, |frame| stylize::threshold_f32_with_luma(frame, threshold_val, luma))
+    }
+
+    /// Otsu threshold of the luminance (u8): pass it to `threshold`.
+    #[pyfunction]
+    pub fn threshold_otsu(image: PyHostArray3<'_, u8>) -> u8 {
+        stylize::threshold_otsu_u8(image.as_array())
+    }
+
+    /// Otsu threshold of the luminance (f32, 0.0-1.0): pass it to `threshold_f32`.
+    #[pyfunction]
+    pub fn threshold_otsu_f32(image: PyHostArray3<'_, f32>) -> f32 {
+        stylize::threshold_otsu_f32(image.as_array())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, block_size=11, c=5.0, method="mean"))]
+    pub fn adaptive_threshold<'py>(
+        image: PyHostArray3<'py, u8>,
+        block_size: u32,
+        c: f32,
+        method: &str,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = stylize::adaptive_threshold_u8(image.as_array(), block_size, c, stylize::AdaptiveMethod::parse(method));
+        result.into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, block_size=11, c=0.02, method="mean"))]
+    pub fn adaptive_threshold_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        block_size: u32,
+        c: f32,
+        method: &str,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = stylize::threshold_f32(image.as_array(), threshold_val);
-        result.into_pyarray(py)
+        let result = stylize::adaptive_threshold_f32(image.as_array(), block_size, c, stylize::AdaptiveMethod::parse(method));
+        result.into_pyarray(image.py())
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, angle, depth, luma="bt709"))]
     pub fn emboss<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         angle: f32,
         depth: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = stylize::emboss_u8(image.as_array(), angle, depth);
-        result.into_pyarray(py)
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let result = stylize::emboss_u8_with_luma(image.as_array(), angle, depth, parse_luma(luma)?);
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, angle, depth, luma="bt709"))]
     pub fn emboss_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         angle: f32,
         depth: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = stylize::emboss_f32(image.as_array(), angle, depth);
-        result.into_pyarray(py)
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let result = stylize::emboss_f32_with_luma(image.as_array(), angle, depth, parse_luma(luma)?);
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
     #[pyo3(signature = (image, sigma_s=60.0, shade_factor=50.0))]
     pub fn pencil_sketch<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         sigma_s: f32,
         shade_factor: f32,
     ) -> Bound<'py, PyArray3<u8>> {
@@ -496,7 +781,7 @@ mod python {
     #[pyo3(signature = (image, sigma_s=60.0, shade_factor=50.0))]
     pub fn pencil_sketch_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         sigma_s: f32,
         shade_factor: f32,
     ) -> Bound<'py, PyArray3<f32>> {
@@ -504,75 +789,237 @@ mod python {
         result.into_pyarray(py)
     }
 
+    #[pyfunction]
+    #[pyo3(signature = (image, sigma=1.0, k=1.6, p=20.0, epsilon=0.1, phi=10.0))]
+    pub fn xdog<'py>(
+        image: PyHostArray3<'py, u8>,
+        sigma: f32,
+        k: f32,
+        p: f32,
+        epsilon: f32,
+        phi: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = stylize::xdog_u8(image.as_array(), sigma, k, p, epsilon, phi);
+        result.into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, sigma=1.0, k=1.6, p=20.0, epsilon=0.1, phi=10.0))]
+    pub fn xdog_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        sigma: f32,
+        k: f32,
+        p: f32,
+        epsilon: f32,
+        phi: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = stylize::xdog_f32(image.as_array(), sigma, k, p, epsilon, phi);
+        result.into_pyarray(image.py())
+    }
+
     // ========================================================================
     // Levels & Curves Filters
     // ========================================================================
 
+    /// Parse a levels channel name ("rgb", "r", "g", "b", "alpha", "luminosity").
+    fn parse_levels_channel(name: &str) -> PyResult<levels_curves::LevelsChannel> {
+        levels_curves::LevelsChannel::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown levels channel '{}'", name)))
+    }
+
     #[pyfunction]
+    #[pyo3(signature = (image, in_black, in_white, out_black, out_white, gamma_val, channel="rgb"))]
     pub fn levels<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: &Bound<'py, PyAny>,
         in_black: u8,
         in_white: u8,
         out_black: u8,
         out_white: u8,
         gamma_val: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = levels_curves::levels_u8(image.as_array(), in_black, in_white, out_black, out_white, gamma_val);
-        result.into_pyarray(py)
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = parse_levels_channel(channel)?;
+        map_py_frames(image, |frame| {
+            levels_curves::levels_channel_u8(frame, in_black, in_white, out_black, out_white, gamma_val, channel)
+        })
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, in_black, in_white, out_black, out_white, gamma_val, channel="rgb"))]
     pub fn levels_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: &Bound<'py, PyAny>,
         in_black: f32,
         in_white: f32,
         out_black: f32,
         out_white: f32,
         gamma_val: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = levels_curves::levels_f32(image.as_array(), in_black, in_white, out_black, out_white, gamma_val);
-        result.into_pyarray(py)
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = parse_levels_channel(channel)?;
+        map_py_frames(image, |frame| {
+            levels_curves::levels_channel_f32(frame, in_black, in_white, out_black, out_white, gamma_val, channel)
+        })
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, in_black, in_white, out_black, out_white, gamma_val, channel="rgb"))]
+    pub fn levels_12bit<'py>(
+        image: &Bound<'py, PyAny>,
+        in_black: u16,
+        in_white: u16,
+        out_black: u16,
+        out_white: u16,
+        gamma_val: f32,
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = parse_levels_channel(channel)?;
+        map_py_frames(image, |frame| {
+            levels_curves::levels_channel_12bit(frame, in_black, in_white, out_black, out_white, gamma_val, channel)
+        })
     }
 
+    /// Apply a tone curve. `luminosity_only=True` is the same as `channel="luminosity"`.
     #[pyfunction]
+    #[pyo3(signature = (image, points, interpolation="pchip", luminosity_only=false, channel="rgb"))]
     pub fn curves<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         points: Vec<(f32, f32)>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = levels_curves::curves_u8(image.as_array(), &points);
-        result.into_pyarray(py)
+        interpolation: &str,
+        luminosity_only: bool,
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let interpolation = levels_curves::CurveInterpolation::parse(interpolation);
+        let channel = if luminosity_only { levels_curves::LevelsChannel::Luminosity } else { parse_levels_channel(channel)? };
+        let result = levels_curves::curves_channel_u8(image.as_array(), &points, interpolation, channel);
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, points, interpolation="pchip", luminosity_only=false, channel="rgb"))]
     pub fn curves_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
+        points: Vec<(f32, f32)>,
+        interpolation: &str,
+        luminosity_only: bool,
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let interpolation = levels_curves::CurveInterpolation::parse(interpolation);
+        let channel = if luminosity_only { levels_curves::LevelsChannel::Luminosity } else { parse_levels_channel(channel)? };
+        let result = levels_curves::curves_channel_f32(image.as_array(), &points, interpolation, channel);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, points, interpolation="pchip", luminosity_only=false, channel="rgb"))]
+    pub fn curves_12bit<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u16>,
         points: Vec<(f32, f32)>,
+        interpolation: &str,
+        luminosity_only: bool,
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u16>>> {
+        let interpolation = levels_curves::CurveInterpolation::parse(interpolation);
+        let channel = if luminosity_only { levels_curves::LevelsChannel::Luminosity } else { parse_levels_channel(channel)? };
+        let result = levels_curves::curves_channel_12bit(image.as_array(), &points, interpolation, channel);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, clip_percent, channel="rgb"))]
+    pub fn auto_levels<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        clip_percent: f32,
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let channel = parse_levels_channel(channel)?;
+        let result = levels_curves::auto_levels_channel_u8(image.as_array(), clip_percent, channel);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, clip_percent, channel="rgb"))]
+    pub fn auto_levels_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        clip_percent: f32,
+        channel: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let channel = parse_levels_channel(channel)?;
+        let result = levels_curves::auto_levels_channel_f32(image.as_array(), clip_percent, channel);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, clip_percent=0.001))]
+    pub fn auto_contrast<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        clip_percent: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = levels_curves::auto_contrast_u8(image.as_array(), clip_percent);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, clip_percent=0.001))]
+    pub fn auto_contrast_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        clip_percent: f32,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = levels_curves::curves_f32(image.as_array(), &points);
+        let result = levels_curves::auto_contrast_f32(image.as_array(), clip_percent);
         result.into_pyarray(py)
     }
 
     #[pyfunction]
-    pub fn auto_levels<'py>(
+    #[pyo3(signature = (image, clip_percent=0.001, snap_neutral=false))]
+    pub fn auto_tone<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         clip_percent: f32,
+        snap_neutral: bool,
     ) -> Bound<'py, PyArray3<u8>> {
-        let result = levels_curves::auto_levels_u8(image.as_array(), clip_percent);
+        let result = levels_curves::auto_tone_u8(image.as_array(), clip_percent, snap_neutral);
         result.into_pyarray(py)
     }
 
     #[pyfunction]
-    pub fn auto_levels_f32<'py>(
+    #[pyo3(signature = (image, clip_percent=0.001, snap_neutral=false))]
+    pub fn auto_tone_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        clip_percent: f32,
+        snap_neutral: bool,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = levels_curves::auto_tone_f32(image.as_array(), clip_percent, snap_neutral);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, clip_percent=0.001, snap_neutral=true))]
+    pub fn auto_color<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, u8>,
         clip_percent: f32,
+        snap_neutral: bool,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = levels_curves::auto_color_u8(image.as_array(), clip_percent, snap_neutral);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, clip_percent=0.001, snap_neutral=true))]
+    pub fn auto_color_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        clip_percent: f32,
+        snap_neutral: bool,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = levels_curves::auto_levels_f32(image.as_array(), clip_percent);
+        let result = levels_curves::auto_color_f32(image.as_array(), clip_percent, snap_neutral);
         result.into_pyarray(py)
     }
 
@@ -583,7 +1030,7 @@ mod python {
     #[pyfunction]
     pub fn sharpen<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         amount: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = sharpen_mod::sharpen_u8(image.as_array(), amount);
@@ -593,41 +1040,65 @@ mod python {
     #[pyfunction]
     pub fn sharpen_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         amount: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = sharpen_mod::sharpen_f32(image.as_array(), amount);
         result.into_pyarray(py)
     }
 
+    /// Unsharp mask. `edge_mask` (0.0-1.0) fades sharpening out on very strong
+    /// edges and in flat areas; `shadow_amount` / `highlight_amount` scale the
+    /// amount towards black and white.
     #[pyfunction]
+    #[pyo3(signature = (image, amount, radius, threshold_val, edge_mask=0.0, shadow_amount=1.0, highlight_amount=1.0))]
     pub fn unsharp_mask<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         amount: f32,
         radius: f32,
         threshold_val: u8,
+        edge_mask: f32,
+        shadow_amount: f32,
+        highlight_amount: f32,
     ) -> Bound<'py, PyArray3<u8>> {
-        let result = sharpen_mod::unsharp_mask_u8(image.as_array(), amount, radius, threshold_val);
-        result.into_pyarray(py)
+        let result = if edge_mask == 0.0 && shadow_amount == 1.0 && highlight_amount == 1.0 {
+            sharpen_mod::unsharp_mask_u8(image.as_array(), amount, radius, threshold_val)
+        } else {
+            let threshold = threshold_val as f32 / 255.0;
+            let params =
+                sharpen_mod::UnsharpMaskParams { amount, radius, threshold, edge_mask, shadow_amount, highlight_amount };
+            sharpen_mod::unsharp_mask_params_u8(image.as_array(), &params)
+        };
+        result.into_pyarray(image.py())
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, amount, radius, threshold_val, edge_mask=0.0, shadow_amount=1.0, highlight_amount=1.0))]
     pub fn unsharp_mask_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         amount: f32,
         radius: f32,
         threshold_val: f32,
+        edge_mask: f32,
+        shadow_amount: f32,
+        highlight_amount: f32,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = sharpen_mod::unsharp_mask_f32(image.as_array(), amount, radius, threshold_val);
-        result.into_pyarray(py)
+        let params = sharpen_mod::UnsharpMaskParams {
+            amount,
+            radius,
+            threshold: threshold_val,
+            edge_mask,
+            shadow_amount,
+            highlight_amount,
+        };
+        let result = sharpen_mod::unsharp_mask_params_f32(image.as_array(), &params);
+        result.into_pyarray(image.py())
     }
 
     #[pyfunction]
     pub fn high_pass<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = sharpen_mod::high_pass_u8(image.as_array(), radius);
@@ -637,7 +1108,7 @@ mod python {
     #[pyfunction]
     pub fn high_pass_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = sharpen_mod::high_pass_f32(image.as_array(), radius);
@@ -645,98 +1116,262 @@ mod python {
     }
 
     #[pyfunction]
-    pub fn motion_blur<'py>(
+    #[pyo3(signature = (image, amount=0.5, radius=20.0))]
+    pub fn clarity<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-        angle: f32,
-        distance: f32,
+        image: PyHostArray3<'py, u8>,
+        amount: f32,
+        radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
-        let result = sharpen_mod::motion_blur_u8(image.as_array(), angle, distance);
+        let result = sharpen_mod::clarity_u8(image.as_array(), amount, radius);
         result.into_pyarray(py)
     }
 
     #[pyfunction]
-    pub fn motion_blur_f32<'py>(
+    #[pyo3(signature = (image, amount=0.5, radius=20.0))]
+    pub fn clarity_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-        angle: f32,
-        distance: f32,
+        image: PyHostArray3<'py, f32>,
+        amount: f32,
+        radius: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = sharpen_mod::clarity_f32(image.as_array(), amount, radius);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=0.5))]
+    pub fn texture<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        amount: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = sharpen_mod::texture_u8(image.as_array(), amount);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=0.5))]
+    pub fn texture_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        amount: f32,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = sharpen_mod::motion_blur_f32(image.as_array(), angle, distance);
+        let result = sharpen_mod::texture_f32(image.as_array(), amount);
         result.into_pyarray(py)
     }
 
+    /// Parse a smart sharpen mode name ("gaussian", "lens", "motion").
+    fn parse_smart_sharpen_mode(name: &str) -> PyResult<sharpen_mod::SmartSharpenMode> {
+        sharpen_mod::SmartSharpenMode::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown smart sharpen mode '{}'", name)))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, radius=1.0, amount=1.0, mode="gaussian", angle=0.0))]
+    pub fn smart_sharpen<'py>(
+        image: PyHostArray3<'py, u8>,
+        radius: f32,
+        amount: f32,
+        mode: &str,
+        angle: f32,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let params = sharpen_mod::SmartSharpenParams { amount, radius, mode: parse_smart_sharpen_mode(mode)?, angle };
+        let result = sharpen_mod::smart_sharpen_u8(image.as_array(), &params);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, radius=1.0, amount=1.0, mode="gaussian", angle=0.0))]
+    pub fn smart_sharpen_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        radius: f32,
+        amount: f32,
+        mode: &str,
+        angle: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let params = sharpen_mod::SmartSharpenParams { amount, radius, mode: parse_smart_sharpen_mode(mode)?, angle };
+        let result = sharpen_mod::smart_sharpen_f32(image.as_array(), &params);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, angle, distance, border_mode="reflect"))]
+    pub fn motion_blur<'py>(
+        image: PyHostArray3<'py, u8>,
+        angle: f32,
+        distance: f32,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let border = parse_border_mode(border_mode)?;
+        let result = sharpen_mod::motion_blur_u8(image.as_array(), angle, distance, border);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, angle, distance, border_mode="reflect"))]
+    pub fn motion_blur_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        angle: f32,
+        distance: f32,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let border = parse_border_mode(border_mode)?;
+        let result = sharpen_mod::motion_blur_f32(image.as_array(), angle, distance, border);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, angle=10.0, center_x=0.5, center_y=0.5, border_mode="reflect"))]
+    pub fn spin_blur<'py>(
+        image: PyHostArray3<'py, u8>,
+        angle: f32,
+        center_x: f32,
+        center_y: f32,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let params = sharpen_mod::SpinBlurParams { angle, center_x, center_y, border: parse_border_mode(border_mode)? };
+        let result = sharpen_mod::spin_blur_u8(image.as_array(), &params);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, angle=10.0, center_x=0.5, center_y=0.5, border_mode="reflect"))]
+    pub fn spin_blur_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        angle: f32,
+        center_x: f32,
+        center_y: f32,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let params = sharpen_mod::SpinBlurParams { angle, center_x, center_y, border: parse_border_mode(border_mode)? };
+        let result = sharpen_mod::spin_blur_f32(image.as_array(), &params);
+        Ok(result.into_pyarray(image.py()))
+    }
+
     // ========================================================================
     // Edge Detection Filters
     // ========================================================================
 
     #[pyfunction]
-    #[pyo3(signature = (image, direction, kernel_size=3))]
+    #[pyo3(signature = (image, direction, kernel_size=3, per_channel=false, luma="bt709"))]
     pub fn sobel<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         direction: &str,
         kernel_size: u8,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = edge::sobel_u8(image.as_array(), direction, kernel_size);
-        result.into_pyarray(py)
+        per_channel: bool,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let luma = parse_luma(luma)?;
+        let result = if per_channel {
+            edge::sobel_per_channel_u8(image.as_array(), direction, kernel_size)
+        } else {
+            edge::sobel_u8_with_luma(image.as_array(), direction, kernel_size, luma)
+        };
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
-    #[pyo3(signature = (image, direction, kernel_size=3))]
+    #[pyo3(signature = (image, direction, kernel_size=3, per_channel=false, luma="bt709"))]
     pub fn sobel_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         direction: &str,
         kernel_size: u8,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = edge::sobel_f32(image.as_array(), direction, kernel_size);
-        result.into_pyarray(py)
+        per_channel: bool,
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let luma = parse_luma(luma)?;
+        let result = if per_channel {
+            edge::sobel_per_channel_f32(image.as_array(), direction, kernel_size)
+        } else {
+            edge::sobel_f32_with_luma(image.as_array(), direction, kernel_size, luma)
+        };
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, kernel_size, luma="bt709"))]
     pub fn laplacian<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         kernel_size: u8,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = edge::laplacian_u8(image.as_array(), kernel_size);
-        result.into_pyarray(py)
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let result = edge::laplacian_u8_with_luma(image.as_array(), kernel_size, parse_luma(luma)?);
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, kernel_size, luma="bt709"))]
     pub fn laplacian_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         kernel_size: u8,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = edge::laplacian_f32(image.as_array(), kernel_size);
-        result.into_pyarray(py)
+        luma: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let result = edge::laplacian_f32_with_luma(image.as_array(), kernel_size, parse_luma(luma)?);
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
-    #[pyo3(signature = (image, sigma=1.0, low_threshold=0.1, high_threshold=0.2))]
+    #[pyo3(signature = (image, sigma=1.0, low_threshold=0.1, high_threshold=0.2, luma="bt709", progress=None))]
     pub fn find_edges<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         sigma: f64,
         low_threshold: f64,
         high_threshold: f64,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = edge::find_edges_u8(image.as_array(), sigma, low_threshold, high_threshold);
-        result.into_pyarray(py)
+        luma: &str,
+        progress: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let luma = parse_luma(luma)?;
+        let result = with_py_progress(progress, |p| {
+            edge::find_edges_u8_with_progress(image.as_array(), sigma, low_threshold, high_threshold, luma, p)
+        })?;
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
-    #[pyo3(signature = (image, sigma=1.0, low_threshold=0.1, high_threshold=0.2))]
+    #[pyo3(signature = (image, sigma=1.0, low_threshold=0.1, high_threshold=0.2, luma="bt709", progress=None))]
     pub fn find_edges_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         sigma: f64,
         low_threshold: f64,
         high_threshold: f64,
+        luma: &str,
+        progress: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let luma = parse_luma(luma)?;
+        let result = with_py_progress(progress, |p| {
+            edge::find_edges_f32_with_progress(image.as_array(), sigma, low_threshold, high_threshold, luma, p)
+        })?;
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, sigma1=1.0, sigma2=1.6))]
+    pub fn dog<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        sigma1: f32,
+        sigma2: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = edge::dog_u8(image.as_array(), sigma1, sigma2);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, sigma1=1.0, sigma2=1.6))]
+    pub fn dog_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        sigma1: f32,
+        sigma2: f32,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = edge::find_edges_f32(image.as_array(), sigma, low_threshold, high_threshold);
+        let result = edge::dog_f32(image.as_array(), sigma1, sigma2);
         result.into_pyarray(py)
     }
 
@@ -744,7 +1379,7 @@ mod python {
     #[pyo3(signature = (image, threshold=128, line_width=2, color_r=0, color_g=255, color_b=0))]
     pub fn draw_contours<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         threshold: u8,
         line_width: u8,
         color_r: u8,
@@ -759,7 +1394,7 @@ mod python {
     #[pyo3(signature = (image, threshold=0.5, line_width=2, color_r=0.0, color_g=1.0, color_b=0.0))]
     pub fn draw_contours_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         threshold: f32,
         line_width: u8,
         color_r: f32,
@@ -770,6 +1405,183 @@ mod python {
         result.into_pyarray(py)
     }
 
+    // ========================================================================
+    // Input Sanitization
+    // ========================================================================
+
+    /// Apply a NaN / out-of-range policy to f32 filter input.
+    ///
+    /// # Arguments
+    /// * `image` - f32 image (H, W, C)
+    /// * `mode` - "clamp" (NaN -> 0, clamp to 0-1), "propagate" (unchanged)
+    ///   or "error" (raise ValueError on NaN, infinity or values outside 0-1)
+    #[pyfunction]
+    #[pyo3(signature = (image, mode="clamp"))]
+    pub fn sanitize_f32<'py>(image: PyHostArray3<'py, f32>, mode: &str) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let mode = SanitizeMode::from_name(mode)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown sanitize mode '{}'", mode)))?;
+        let result = sanitize::sanitize_f32(image.as_array(), mode)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(result.into_owned().into_pyarray(image.py()))
+    }
+
+    fn validation_policy(channels: Option<usize>, shape: Option<(usize, usize)>, repair: &str) -> PyResult<ValidationPolicy> {
+        let repair = RepairMode::from_name(repair)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown repair mode '{}'", repair)))?;
+        Ok(ValidationPolicy { channels, shape, repair })
+    }
+
+    fn validation_report_dict<'py>(py: Python<'py>, report: &ValidationReport) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("nan", report.nan)?;
+        dict.set_item("infinite", report.infinite)?;
+        dict.set_item("out_of_range", report.out_of_range)?;
+        dict.set_item("repaired", report.repaired)?;
+        Ok(dict)
+    }
+
+    /// Validate an f32 image and optionally repair invalid samples.
+    ///
+    /// # Arguments
+    /// * `image` - f32 image (H, W, C)
+    /// * `repair` - "report" (count only), "reject" (raise ValueError),
+    ///   "clamp" (NaN -> 0, clamp to 0-1) or "median" (NaN / inf -> median
+    ///   of the valid 3x3 neighbors, clamp the rest)
+    /// * `channels` - Required channel count (None = 1, 3 or 4)
+    /// * `shape` - Required (height, width) (None = any non-empty)
+    ///
+    /// # Returns
+    /// (image, dict with `nan`, `infinite`, `out_of_range`, `repaired`)
+    #[pyfunction]
+    #[pyo3(signature = (image, repair="report", channels=None, shape=None))]
+    pub fn validate_image_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        repair: &str,
+        channels: Option<usize>,
+        shape: Option<(usize, usize)>,
+    ) -> PyResult<(Bound<'py, PyArray3<f32>>, Bound<'py, pyo3::types::PyDict>)> {
+        let py = image.py();
+        let policy = validation_policy(channels, shape, repair)?;
+        let (result, report) = sanitize::validate_image_f32(image.as_array(), &policy)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok((result.into_owned().into_pyarray(py), validation_report_dict(py, &report)?))
+    }
+
+    /// Check the channel count and shape of a u8 image, raising ValueError on mismatch.
+    ///
+    /// # Returns
+    /// Dict with `nan`, `infinite`, `out_of_range`, `repaired` (all 0 for u8)
+    #[pyfunction]
+    #[pyo3(signature = (image, channels=None, shape=None))]
+    pub fn validate_image<'py>(
+        image: PyHostArray3<'py, u8>,
+        channels: Option<usize>,
+        shape: Option<(usize, usize)>,
+    ) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        let policy = validation_policy(channels, shape, "report")?;
+        let report = sanitize::validate_image_u8(image.as_array(), &policy)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        validation_report_dict(image.py(), &report)
+    }
+
+    // ========================================================================
+    // Convolution
+    // ========================================================================
+
+    fn parse_border_mode(name: &str) -> PyResult<BorderMode> {
+        BorderMode::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown border mode '{}'", name)))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, kernel, normalize=false, border_mode="reflect"))]
+    pub fn convolve2d<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        kernel: PyHostArray2<'py, f32>,
+        normalize: bool,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let border = parse_border_mode(border_mode)?;
+        let result = convolve_mod::convolve2d_u8(image.as_array(), kernel.as_array(), normalize, border);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, kernel, normalize=false, border_mode="reflect"))]
+    pub fn convolve2d_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        kernel: PyHostArray2<'py, f32>,
+        normalize: bool,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let border = parse_border_mode(border_mode)?;
+        let result = convolve_mod::convolve2d_f32(image.as_array(), kernel.as_array(), normalize, border);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, kx, ky, border_mode="reflect"))]
+    pub fn convolve_separable<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        kx: Vec<f32>,
+        ky: Vec<f32>,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let border = parse_border_mode(border_mode)?;
+        let result = convolve_mod::convolve_separable_u8(image.as_array(), &kx, &ky, border);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, kx, ky, border_mode="reflect"))]
+    pub fn convolve_separable_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        kx: Vec<f32>,
+        ky: Vec<f32>,
+        border_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let border = parse_border_mode(border_mode)?;
+        let result = convolve_mod::convolve_separable_f32(image.as_array(), &kx, &ky, border);
+        Ok(result.into_pyarray(py))
+    }
+
+    // ========================================================================
+    // Integral Images
+    // ========================================================================
+
+    /// Summed-area table of a uint8 image.
+    ///
+    /// # Returns
+    /// (H + 1, W + 1, C) float64 table with a zero first row and column;
+    /// the sum over rows y0..y1 and columns x0..x1 is
+    /// `t[y1, x1] - t[y0, x1] - t[y1, x0] + t[y0, x0]`
+    #[pyfunction]
+    pub fn integral_image<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>) -> Bound<'py, PyArray3<f64>> {
+        crate::filters::core::integral_image(image.as_array()).into_pyarray(py)
+    }
+
+    /// Summed-area table of a float32 image.
+    #[pyfunction]
+    pub fn integral_image_f32<'py>(py: Python<'py>, image: PyHostArray3<'py, f32>) -> Bound<'py, PyArray3<f64>> {
+        crate::filters::core::integral_image(image.as_array()).into_pyarray(py)
+    }
+
+    /// Mean of the (2 * radius + 1)^2 window per pixel and channel, in
+    /// constant time per pixel. The window shrinks at the borders.
+    #[pyfunction]
+    pub fn box_filter_via_integral<'py>(py: Python<'py>, image: PyHostArray3<'py, u8>, radius: usize) -> Bound<'py, PyArray3<u8>> {
+        box_filter_u8_impl(image.as_array(), radius).into_pyarray(py)
+    }
+
+    #[pyfunction]
+    pub fn box_filter_via_integral_f32<'py>(py: Python<'py>, image: PyHostArray3<'py, f32>, radius: usize) -> Bound<'py, PyArray3<f32>> {
+        box_filter_f32_impl(image.as_array(), radius).into_pyarray(py)
+    }
+
     // ========================================================================
     // Noise Filters
     // ========================================================================
@@ -777,7 +1589,7 @@ mod python {
     #[pyfunction]
     pub fn add_noise<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         amount: f32,
         gaussian: bool,
         monochrome: bool,
@@ -790,7 +1602,7 @@ mod python {
     #[pyfunction]
     pub fn add_noise_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         amount: f32,
         gaussian: bool,
         monochrome: bool,
@@ -800,44 +1612,159 @@ mod python {
         result.into_pyarray(py)
     }
 
+    /// Position-stable noise; `origin_x`/`origin_y` place a tile in the full image.
+    #[pyfunction]
+    #[pyo3(signature = (image, amount, gaussian, monochrome, seed, origin_x=0, origin_y=0))]
+    pub fn add_noise_hashed<'py>(
+        image: PyHostArray3<'py, u8>,
+        amount: f32,
+        gaussian: bool,
+        monochrome: bool,
+        seed: u64,
+        origin_x: i64,
+        origin_y: i64,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let origin = (origin_x, origin_y);
+        let result = noise_mod::add_noise_hashed_u8(image.as_array(), amount, gaussian, monochrome, seed, origin);
+        result.into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, amount, gaussian, monochrome, seed, origin_x=0, origin_y=0))]
+    pub fn add_noise_hashed_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        amount: f32,
+        gaussian: bool,
+        monochrome: bool,
+        seed: u64,
+        origin_x: i64,
+        origin_y: i64,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let origin = (origin_x, origin_y);
+        let result = noise_mod::add_noise_hashed_f32(image.as_array(), amount, gaussian, monochrome, seed, origin);
+        result.into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, intensity=0.3, size=1.5, roughness=0.5, chroma_amount=0.0, seed=0))]
+    pub fn film_grain<'py>(
+        image: PyHostArray3<'py, u8>,
+        intensity: f32,
+        size: f32,
+        roughness: f32,
+        chroma_amount: f32,
+        seed: u64,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = noise_mod::film_grain_u8(image.as_array(), intensity, size, roughness, chroma_amount, seed);
+        result.into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, intensity=0.3, size=1.5, roughness=0.5, chroma_amount=0.0, seed=0))]
+    pub fn film_grain_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        intensity: f32,
+        size: f32,
+        roughness: f32,
+        chroma_amount: f32,
+        seed: u64,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = noise_mod::film_grain_f32(image.as_array(), intensity, size, roughness, chroma_amount, seed);
+        result.into_pyarray(image.py())
+    }
+
     #[pyfunction]
+    #[pyo3(signature = (image, radius, progress=None))]
     pub fn median<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
+        radius: u32,
+        progress: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let result = with_py_progress(progress, |p| noise_mod::median_u8_with_progress(image.as_array(), radius, p))?;
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, radius, progress=None))]
+    pub fn median_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
         radius: u32,
+        progress: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let result = with_py_progress(progress, |p| noise_mod::median_f32_with_progress(image.as_array(), radius, p))?;
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, threshold=40.0))]
+    pub fn remove_hot_pixels<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        threshold: f32,
     ) -> Bound<'py, PyArray3<u8>> {
-        let result = noise_mod::median_u8(image.as_array(), radius);
+        let result = noise_mod::remove_hot_pixels_u8(image.as_array(), threshold);
         result.into_pyarray(py)
     }
 
     #[pyfunction]
-    pub fn median_f32<'py>(
+    #[pyo3(signature = (image, threshold=40.0 / 255.0))]
+    pub fn remove_hot_pixels_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        threshold: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = noise_mod::remove_hot_pixels_f32(image.as_array(), threshold);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, radius=5, threshold=15.0))]
+    pub fn surface_blur<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        radius: u32,
+        threshold: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = noise_mod::surface_blur_u8(image.as_array(), radius, threshold);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, radius=5, threshold=15.0 / 255.0))]
+    pub fn surface_blur_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: u32,
+        threshold: f32,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = noise_mod::median_f32(image.as_array(), radius);
+        let result = noise_mod::surface_blur_f32(image.as_array(), radius, threshold);
         result.into_pyarray(py)
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, strength, progress=None))]
     pub fn denoise<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         strength: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = noise_mod::denoise_u8(image.as_array(), strength);
-        result.into_pyarray(py)
+        progress: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let result = with_py_progress(progress, |p| noise_mod::denoise_u8_with_progress(image.as_array(), strength, p))?;
+        Ok(result.into_pyarray(py))
     }
 
     #[pyfunction]
+    #[pyo3(signature = (image, strength, progress=None))]
     pub fn denoise_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         strength: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = noise_mod::denoise_f32(image.as_array(), strength);
-        result.into_pyarray(py)
+        progress: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let result = with_py_progress(progress, |p| noise_mod::denoise_f32_with_progress(image.as_array(), strength, p))?;
+        Ok(result.into_pyarray(py))
     }
 
     // ========================================================================
@@ -847,7 +1774,7 @@ mod python {
     #[pyfunction]
     pub fn dilate<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::dilate_u8(image.as_array(), radius);
@@ -857,7 +1784,7 @@ mod python {
     #[pyfunction]
     pub fn dilate_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::dilate_f32(image.as_array(), radius);
@@ -867,7 +1794,7 @@ mod python {
     #[pyfunction]
     pub fn erode<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::erode_u8(image.as_array(), radius);
@@ -877,7 +1804,7 @@ mod python {
     #[pyfunction]
     pub fn erode_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::erode_f32(image.as_array(), radius);
@@ -889,7 +1816,7 @@ mod python {
     #[pyfunction]
     pub fn morphology_open<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::open_u8(image.as_array(), radius);
@@ -899,7 +1826,7 @@ mod python {
     #[pyfunction]
     pub fn morphology_open_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::open_f32(image.as_array(), radius);
@@ -909,7 +1836,7 @@ mod python {
     #[pyfunction]
     pub fn morphology_close<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::close_u8(image.as_array(), radius);
@@ -919,7 +1846,7 @@ mod python {
     #[pyfunction]
     pub fn morphology_close_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::close_f32(image.as_array(), radius);
@@ -929,7 +1856,7 @@ mod python {
     #[pyfunction]
     pub fn morphology_gradient<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::gradient_u8(image.as_array(), radius);
@@ -939,7 +1866,7 @@ mod python {
     #[pyfunction]
     pub fn morphology_gradient_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::gradient_f32(image.as_array(), radius);
@@ -949,7 +1876,7 @@ mod python {
     #[pyfunction]
     pub fn tophat<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::tophat_u8(image.as_array(), radius);
@@ -959,7 +1886,7 @@ mod python {
     #[pyfunction]
     pub fn tophat_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::tophat_f32(image.as_array(), radius);
@@ -969,7 +1896,7 @@ mod python {
     #[pyfunction]
     pub fn blackhat<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         radius: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = morphology::blackhat_u8(image.as_array(), radius);
@@ -979,7 +1906,7 @@ mod python {
     #[pyfunction]
     pub fn blackhat_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         radius: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = morphology::blackhat_f32(image.as_array(), radius);
@@ -993,7 +1920,7 @@ mod python {
     #[pyfunction]
     pub fn sepia<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         intensity: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = color_science::sepia_u8(image.as_array(), intensity);
@@ -1003,7 +1930,7 @@ mod python {
     #[pyfunction]
     pub fn sepia_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         intensity: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = color_science::sepia_f32(image.as_array(), intensity);
@@ -1013,7 +1940,7 @@ mod python {
     #[pyfunction]
     pub fn temperature<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         amount: f32,
     ) -> Bound<'py, PyArray3<u8>> {
         let result = color_science::temperature_u8(image.as_array(), amount);
@@ -1023,17 +1950,37 @@ mod python {
     #[pyfunction]
     pub fn temperature_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         amount: f32,
     ) -> Bound<'py, PyArray3<f32>> {
         let result = color_science::temperature_f32(image.as_array(), amount);
         result.into_pyarray(py)
     }
 
+    #[pyfunction]
+    #[pyo3(signature = (image, kelvin=color_science::NEUTRAL_KELVIN, tint=0.0))]
+    pub fn white_balance_kelvin<'py>(
+        image: &Bound<'py, PyAny>,
+        kelvin: f32,
+        tint: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::white_balance_kelvin_u8(frame, kelvin, tint))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, kelvin=color_science::NEUTRAL_KELVIN, tint=0.0))]
+    pub fn white_balance_kelvin_f32<'py>(
+        image: &Bound<'py, PyAny>,
+        kelvin: f32,
+        tint: f32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| color_science::white_balance_kelvin_f32(frame, kelvin, tint))
+    }
+
     #[pyfunction]
     pub fn channel_mixer<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+        image: PyHostArray3<'py, u8>,
         r_src: u8,
         g_src: u8,
         b_src: u8,
@@ -1045,7 +1992,7 @@ mod python {
     #[pyfunction]
     pub fn channel_mixer_f32<'py>(
         py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+        image: PyHostArray3<'py, f32>,
         r_src: u8,
         g_src: u8,
         b_src: u8,
@@ -1054,196 +2001,2035 @@ mod python {
         result.into_pyarray(py)
     }
 
-    // ========================================================================
-    // Equalize Histogram
-    // ========================================================================
-
     #[pyfunction]
-    pub fn equalize_histogram<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = color_adjust::equalize_histogram_u8(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (source, reference, amount=1.0))]
+    pub fn match_color<'py>(
+        py: Python<'py>,
+        source: PyHostArray3<'py, u8>,
+        reference: PyHostArray3<'py, u8>,
+        amount: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = color_science::match_color_u8(source.as_array(), reference.as_array(), amount);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (source, reference, amount=1.0))]
+    pub fn match_color_f32<'py>(
+        py: Python<'py>,
+        source: PyHostArray3<'py, f32>,
+        reference: PyHostArray3<'py, f32>,
+        amount: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = color_science::match_color_f32(source.as_array(), reference.as_array(), amount);
+        result.into_pyarray(py)
+    }
+
+    /// BlackWhiteParams from (reds, yellows, greens, cyans, blues, magentas).
+    fn black_white_params(weights: Option<[f32; 6]>, tint: Option<[f32; 3]>) -> color_science::BlackWhiteParams {
+        let defaults = color_science::BlackWhiteParams::default();
+        let [reds, yellows, greens, cyans, blues, magentas] = weights.unwrap_or([
+            defaults.reds, defaults.yellows, defaults.greens, defaults.cyans, defaults.blues, defaults.magentas,
+        ]);
+        color_science::BlackWhiteParams { reds, yellows, greens, cyans, blues, magentas, tint }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, weights=None, tint_color=None))]
+    pub fn black_white<'py>(
+        image: &Bound<'py, PyAny>,
+        weights: Option<[f32; 6]>,
+        tint_color: Option<(u8, u8, u8)>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let tint = tint_color.map(|(r, g, b)| [r, g, b].map(|v| v as f32 / 255.0));
+        let params = black_white_params(weights, tint);
+        map_py_frames(image, |frame| color_science::black_white_u8(frame, &params))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, weights=None, tint_color=None))]
+    pub fn black_white_f32<'py>(
+        image: &Bound<'py, PyAny>,
+        weights: Option<[f32; 6]>,
+        tint_color: Option<(f32, f32, f32)>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let params = black_white_params(weights, tint_color.map(|(r, g, b)| [r, g, b]));
+        map_py_frames(image, |frame| color_science::black_white_f32(frame, &params))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, target_color, replacement_color, tolerance=0.2, softness=0.1, preserve_luminosity=false))]
+    pub fn replace_color<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        target_color: (u8, u8, u8),
+        replacement_color: (u8, u8, u8),
+        tolerance: f32,
+        softness: f32,
+        preserve_luminosity: bool,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let target = [target_color.0, target_color.1, target_color.2];
+        let replacement = [replacement_color.0, replacement_color.1, replacement_color.2];
+        let result = color_science::replace_color_u8(image.as_array(), target, replacement, tolerance, softness, preserve_luminosity);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, target_color, replacement_color, tolerance=0.2, softness=0.1, preserve_luminosity=false))]
+    pub fn replace_color_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        target_color: (f32, f32, f32),
+        replacement_color: (f32, f32, f32),
+        tolerance: f32,
+        softness: f32,
+        preserve_luminosity: bool,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let target = [target_color.0, target_color.1, target_color.2];
+        let replacement = [replacement_color.0, replacement_color.1, replacement_color.2];
+        let result = color_science::replace_color_f32(image.as_array(), target, replacement, tolerance, softness, preserve_luminosity);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, shadow_color, highlight_color, balance=0.0))]
+    pub fn split_tone<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        shadow_color: (u8, u8, u8),
+        highlight_color: (u8, u8, u8),
+        balance: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let shadow = [shadow_color.0, shadow_color.1, shadow_color.2];
+        let highlight = [highlight_color.0, highlight_color.1, highlight_color.2];
+        let result = color_science::split_tone_u8(image.as_array(), shadow, highlight, balance);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, shadow_color, highlight_color, balance=0.0))]
+    pub fn split_tone_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        shadow_color: (f32, f32, f32),
+        highlight_color: (f32, f32, f32),
+        balance: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let shadow = [shadow_color.0, shadow_color.1, shadow_color.2];
+        let highlight = [highlight_color.0, highlight_color.1, highlight_color.2];
+        let result = color_science::split_tone_f32(image.as_array(), shadow, highlight, balance);
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, ink_colors, curves=None))]
+    pub fn duotone<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        ink_colors: Vec<(u8, u8, u8)>,
+        curves: Option<Vec<Vec<(f32, f32)>>>,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let inks: Vec<[u8; 3]> = ink_colors.iter().map(|c| [c.0, c.1, c.2]).collect();
+        let result = color_science::duotone_u8(image.as_array(), &inks, &curves.unwrap_or_default());
+        result.into_pyarray(py)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, ink_colors, curves=None))]
+    pub fn duotone_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        ink_colors: Vec<(f32, f32, f32)>,
+        curves: Option<Vec<Vec<(f32, f32)>>>,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let inks: Vec<[f32; 3]> = ink_colors.iter().map(|c| [c.0, c.1, c.2]).collect();
+        let result = color_science::duotone_f32(image.as_array(), &inks, &curves.unwrap_or_default());
+        result.into_pyarray(py)
+    }
+
+    fn parse_deficiency(name: &str) -> PyResult<color_science::ColorVisionDeficiency> {
+        color_science::ColorVisionDeficiency::from_name(name).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("unknown color vision deficiency '{}'", name))
+        })
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, deficiency="deutan", severity=1.0))]
+    pub fn simulate_cvd<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        deficiency: &str,
+        severity: f32,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let deficiency = parse_deficiency(deficiency)?;
+        let result = color_science::simulate_cvd_u8(image.as_array(), deficiency, severity);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, deficiency="deutan", severity=1.0))]
+    pub fn simulate_cvd_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        deficiency: &str,
+        severity: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let deficiency = parse_deficiency(deficiency)?;
+        let result = color_science::simulate_cvd_f32(image.as_array(), deficiency, severity);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, deficiency="deutan", amount=1.0))]
+    pub fn daltonize<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        deficiency: &str,
+        amount: f32,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let deficiency = parse_deficiency(deficiency)?;
+        let result = color_science::daltonize_u8(image.as_array(), deficiency, amount);
+        Ok(result.into_pyarray(py))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, deficiency="deutan", amount=1.0))]
+    pub fn daltonize_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        deficiency: &str,
+        amount: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let deficiency = parse_deficiency(deficiency)?;
+        let result = color_science::daltonize_f32(image.as_array(), deficiency, amount);
+        Ok(result.into_pyarray(py))
+    }
+
+    // ========================================================================
+    // Equalize Histogram
+    // ========================================================================
+
+    fn parse_equalize_mode(name: &str) -> PyResult<color_adjust::EqualizeMode> {
+        color_adjust::EqualizeMode::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown equalize mode '{}'", name)))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, mode="per_channel", mask=None))]
+    pub fn equalize_histogram<'py>(
+        image: PyHostArray3<'py, u8>,
+        mode: &str,
+        mask: Option<PyHostArray2<'py, u8>>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let mode = parse_equalize_mode(mode)?;
+        let image_view = image.as_array();
+        let mask_view = mask.as_ref().map(|m| m.as_array());
+        if mask_view.is_some_and(|m| m.dim() != (image_view.dim().0, image_view.dim().1)) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        let result = color_adjust::equalize_histogram_masked_u8(image_view, mode, mask_view);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, mode="per_channel", mask=None))]
+    pub fn equalize_histogram_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        mode: &str,
+        mask: Option<PyHostArray2<'py, f32>>,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let mode = parse_equalize_mode(mode)?;
+        let image_view = image.as_array();
+        let mask_view = mask.as_ref().map(|m| m.as_array());
+        if mask_view.is_some_and(|m| m.dim() != (image_view.dim().0, image_view.dim().1)) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        let result = color_adjust::equalize_histogram_masked_f32(image_view, mode, mask_view);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    // ========================================================================
+    // Pixelate & Vignette
+    // ========================================================================
+
+    #[pyfunction]
+    #[pyo3(signature = (image, block_size, shape="square", mask=None))]
+    pub fn pixelate<'py>(
+        image: PyHostArray3<'py, u8>,
+        block_size: u32,
+        shape: &str,
+        mask: Option<PyHostArray2<'py, u8>>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let image_view = image.as_array();
+        let mask_view = mask.as_ref().map(|m| m.as_array());
+        if mask_view.is_some_and(|m| m.dim() != (image_view.dim().0, image_view.dim().1)) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        let result = stylize::pixelate_shaped_u8(image_view, block_size, stylize::PixelateShape::parse(shape), mask_view);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, block_size, shape="square", mask=None))]
+    pub fn pixelate_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        block_size: u32,
+        shape: &str,
+        mask: Option<PyHostArray2<'py, f32>>,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let image_view = image.as_array();
+        let mask_view = mask.as_ref().map(|m| m.as_array());
+        if mask_view.is_some_and(|m| m.dim() != (image_view.dim().0, image_view.dim().1)) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        let result = stylize::pixelate_shaped_f32(image_view, block_size, stylize::PixelateShape::parse(shape), mask_view);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=0.5, center=(0.5, 0.5), midpoint=0.0, roundness=1.0, feather=1.0, color=None))]
+    pub fn vignette<'py>(
+        image: PyHostArray3<'py, u8>,
+        amount: f32,
+        center: (f32, f32),
+        midpoint: f32,
+        roundness: f32,
+        feather: f32,
+        color: Option<(u8, u8, u8)>,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let color = color.map(|(r, g, b)| [r, g, b].map(|v| v as f32 / 255.0));
+        let params = stylize::VignetteParams { amount, center_x: center.0, center_y: center.1, midpoint, roundness, feather, color };
+        let result = stylize::vignette_params_u8(image.as_array(), &params);
+        result.into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=0.5, center=(0.5, 0.5), midpoint=0.0, roundness=1.0, feather=1.0, color=None))]
+    pub fn vignette_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        amount: f32,
+        center: (f32, f32),
+        midpoint: f32,
+        roundness: f32,
+        feather: f32,
+        color: Option<(f32, f32, f32)>,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let color = color.map(|(r, g, b)| [r, g, b]);
+        let params = stylize::VignetteParams { amount, center_x: center.0, center_y: center.1, midpoint, roundness, feather, color };
+        let result = stylize::vignette_params_f32(image.as_array(), &params);
+        result.into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Rotation and Mirroring
+    // ========================================================================
+
+    /// Rotate image 90 degrees clockwise (u8).
+    #[pyfunction]
+    pub fn rotate_90_cw<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::rotate_90_cw_u8)
+    }
+
+    /// Rotate image 90 degrees clockwise (f32).
+    #[pyfunction]
+    pub fn rotate_90_cw_f32<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::rotate_90_cw_f32)
+    }
+
+    /// Rotate image 180 degrees (u8).
+    #[pyfunction]
+    pub fn rotate_180<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::rotate_180_u8)
+    }
+
+    /// Rotate image 180 degrees (f32).
+    #[pyfunction]
+    pub fn rotate_180_f32<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::rotate_180_f32)
+    }
+
+    /// Rotate image 270 degrees clockwise (90 counter-clockwise) (u8).
+    #[pyfunction]
+    pub fn rotate_270_cw<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::rotate_270_cw_u8)
+    }
+
+    /// Rotate image 270 degrees clockwise (90 counter-clockwise) (f32).
+    #[pyfunction]
+    pub fn rotate_270_cw_f32<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::rotate_270_cw_f32)
+    }
+
+    /// Rotate image by specified degrees (90, 180, or 270) (u8).
+    #[pyfunction]
+    pub fn rotate<'py>(
+        image: &Bound<'py, PyAny>,
+        degrees: u32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| rotate_mod::rotate_u8(frame, degrees))
+    }
+
+    /// Rotate image by specified degrees (90, 180, or 270) (f32).
+    #[pyfunction]
+    pub fn rotate_f32<'py>(
+        image: &Bound<'py, PyAny>,
+        degrees: u32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, |frame| rotate_mod::rotate_f32(frame, degrees))
+    }
+
+    /// Flip image horizontally (mirror left-right) (u8).
+    #[pyfunction]
+    pub fn flip_horizontal<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::flip_horizontal_u8)
+    }
+
+    /// Flip image horizontally (mirror left-right) (f32).
+    #[pyfunction]
+    pub fn flip_horizontal_f32<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::flip_horizontal_f32)
+    }
+
+    /// Flip image vertically (mirror top-bottom) (u8).
+    #[pyfunction]
+    pub fn flip_vertical<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::flip_vertical_u8)
+    }
+
+    /// Flip image vertically (mirror top-bottom) (f32).
+    #[pyfunction]
+    pub fn flip_vertical_f32<'py>(
+        image: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_py_frames(image, rotate_mod::flip_vertical_f32)
+    }
+
+    /// Rotate or flip a buffer with padded rows (video capture frames).
+    ///
+    /// `data` is a 1-D uint8 or uint16 array or a bytes object, `stride` the
+    /// distance between row starts in samples. Returns a compact
+    /// (H, W, C) array of the same dtype.
+    #[pyfunction]
+    pub fn transform_strided<'py>(
+        data: &Bound<'py, PyAny>,
+        width: usize,
+        height: usize,
+        channels: usize,
+        stride: usize,
+        transform: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = data.py();
+        let op = rotate_mod::StridedTransform::from_name(transform)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown transform '{}'", transform)))?;
+        let mismatch = || {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "buffer doesn't hold {} rows of {}x{} samples with stride {}",
+                height, width, channels, stride
+            ))
+        };
+        if let Ok(samples) = data.extract::<numpy::PyReadonlyArray1<'py, u16>>() {
+            let result = rotate_mod::transform_strided(samples.as_slice()?, width, height, channels, stride, op).ok_or_else(mismatch)?;
+            return Ok(result.into_pyarray(py).into_any());
+        }
+        let result = if let Ok(samples) = data.extract::<numpy::PyReadonlyArray1<'py, u8>>() {
+            rotate_mod::transform_strided(samples.as_slice()?, width, height, channels, stride, op)
+        } else {
+            let bytes: pyo3::pybacked::PyBackedBytes = data.extract()?;
+            rotate_mod::transform_strided(&bytes, width, height, channels, stride, op)
+        };
+        Ok(result.ok_or_else(mismatch)?.into_pyarray(py).into_any())
+    }
+
+    // ========================================================================
+    // Thumbnails
+    // ========================================================================
+
+    /// Create a thumbnail with Lanczos downscaling (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+    /// * `max_width` - Maximum thumbnail width
+    /// * `max_height` - Maximum thumbnail height
+    /// * `srgb_aware` - Average in linear light
+    #[pyfunction]
+    #[pyo3(signature = (image, max_width, max_height, srgb_aware=true))]
+    pub fn thumbnail<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        max_width: usize,
+        max_height: usize,
+        srgb_aware: bool,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = resample::thumbnail_u8(image.as_array(), max_width, max_height, srgb_aware);
+        result.into_pyarray(py)
+    }
+
+    /// Create a thumbnail with Lanczos downscaling (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, max_width, max_height, srgb_aware=true))]
+    pub fn thumbnail_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        max_width: usize,
+        max_height: usize,
+        srgb_aware: bool,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = resample::thumbnail_f32(image.as_array(), max_width, max_height, srgb_aware);
+        result.into_pyarray(py)
+    }
+
+    /// Create thumbnails of several u8 images in parallel.
+    ///
+    /// # Arguments
+    /// * `images` - List of images (H, W, C), sizes may differ
+    #[pyfunction]
+    #[pyo3(signature = (images, max_width, max_height, srgb_aware=true))]
+    pub fn thumbnail_batch<'py>(
+        py: Python<'py>,
+        images: Vec<PyHostArray3<'py, u8>>,
+        max_width: usize,
+        max_height: usize,
+        srgb_aware: bool,
+    ) -> Vec<Bound<'py, PyArray3<u8>>> {
+        use rayon::prelude::*;
+
+        let inputs: Vec<_> = images.iter().map(|image| image.as_array().to_owned()).collect();
+        let results: Vec<_> = inputs
+            .par_iter()
+            .map(|image| resample::thumbnail_u8(image.view(), max_width, max_height, srgb_aware))
+            .collect();
+        results.into_iter().map(|result| result.into_pyarray(py)).collect()
+    }
+
+    // ========================================================================
+    // Straightening
+    // ========================================================================
+
+    /// Estimate the dominant skew angle of an image (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+    /// * `max_angle` - Largest skew considered, in degrees
+    ///
+    /// # Returns
+    /// Skew angle in degrees (positive = clockwise)
+    #[pyfunction]
+    #[pyo3(signature = (image, max_angle=15.0))]
+    pub fn estimate_skew_angle(image: PyHostArray3<'_, u8>, max_angle: f32) -> f32 {
+        straighten::estimate_skew_angle_u8(image.as_array(), max_angle)
+    }
+
+    /// Estimate the dominant skew angle of an image (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, max_angle=15.0))]
+    pub fn estimate_skew_angle_f32(image: PyHostArray3<'_, f32>, max_angle: f32) -> f32 {
+        straighten::estimate_skew_angle_f32(image.as_array(), max_angle)
+    }
+
+    /// Rotate by an arbitrary angle with bicubic sampling (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+    /// * `degrees` - Rotation angle in degrees (positive = clockwise)
+    #[pyfunction]
+    pub fn rotate_bicubic<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        degrees: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = straighten::rotate_bicubic_u8(image.as_array(), degrees);
+        result.into_pyarray(py)
+    }
+
+    /// Rotate by an arbitrary angle with bicubic sampling (f32).
+    #[pyfunction]
+    pub fn rotate_bicubic_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        degrees: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = straighten::rotate_bicubic_f32(image.as_array(), degrees);
+        result.into_pyarray(py)
+    }
+
+    /// Rotate by an arbitrary angle and crop to the largest inscribed rectangle (u8).
+    #[pyfunction]
+    pub fn rotate_crop<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        degrees: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = straighten::rotate_crop_u8(image.as_array(), degrees);
+        result.into_pyarray(py)
+    }
+
+    /// Rotate by an arbitrary angle and crop to the largest inscribed rectangle (f32).
+    #[pyfunction]
+    pub fn rotate_crop_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        degrees: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = straighten::rotate_crop_f32(image.as_array(), degrees);
+        result.into_pyarray(py)
+    }
+
+    /// Straighten a tilted scan or photo (u8).
+    ///
+    /// # Returns
+    /// (straightened image, detected skew angle in degrees)
+    #[pyfunction]
+    #[pyo3(signature = (image, max_angle=15.0))]
+    pub fn auto_straighten<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        max_angle: f32,
+    ) -> (Bound<'py, PyArray3<u8>>, f32) {
+        let (result, angle) = straighten::auto_straighten_u8(image.as_array(), max_angle);
+        (result.into_pyarray(py), angle)
+    }
+
+    /// Straighten a tilted scan or photo (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, max_angle=15.0))]
+    pub fn auto_straighten_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        max_angle: f32,
+    ) -> (Bound<'py, PyArray3<f32>>, f32) {
+        let (result, angle) = straighten::auto_straighten_f32(image.as_array(), max_angle);
+        (result.into_pyarray(py), angle)
+    }
+
+    // ========================================================================
+    // Perspective Rectification
+    // ========================================================================
+
+    fn parse_quad(corners: &[(f32, f32)]) -> PyResult<Quad> {
+        match corners {
+            [a, b, c, d] => Ok([[a.0, a.1], [b.0, b.1], [c.0, c.1], [d.0, d.1]]),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "expected 4 corners, got {}",
+                corners.len()
+            ))),
+        }
+    }
+
+    /// Warp a quadrilateral region to an upright rectangle (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+    /// * `corners` - 4 (x, y) corners: top-left, top-right, bottom-right, bottom-left
+    /// * `out_width` - Output width (0 = longest horizontal side)
+    /// * `out_height` - Output height (0 = longest vertical side)
+    #[pyfunction]
+    #[pyo3(signature = (image, corners, out_width=0, out_height=0))]
+    pub fn rectify_quad<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, u8>,
+        corners: Vec<(f32, f32)>,
+        out_width: usize,
+        out_height: usize,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let quad = parse_quad(&corners)?;
+        let result = perspective::rectify_quad_u8(image.as_array(), &quad, out_width, out_height);
+        Ok(result.into_pyarray(py))
+    }
+
+    /// Warp a quadrilateral region to an upright rectangle (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, corners, out_width=0, out_height=0))]
+    pub fn rectify_quad_f32<'py>(
+        py: Python<'py>,
+        image: PyHostArray3<'py, f32>,
+        corners: Vec<(f32, f32)>,
+        out_width: usize,
+        out_height: usize,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let quad = parse_quad(&corners)?;
+        let result = perspective::rectify_quad_f32(image.as_array(), &quad, out_width, out_height);
+        Ok(result.into_pyarray(py))
+    }
+
+    /// Find the dominant quadrilateral (document, receipt, screen) (u8).
+    ///
+    /// # Returns
+    /// 4 (x, y) corners (top-left, top-right, bottom-right, bottom-left) or None
+    #[pyfunction]
+    pub fn detect_document_quad(image: PyHostArray3<'_, u8>) -> Option<Vec<(f32, f32)>> {
+        perspective::detect_document_quad_u8(image.as_array()).map(|quad| quad.iter().map(|p| (p[0], p[1])).collect())
+    }
+
+    /// Find the dominant quadrilateral (document, receipt, screen) (f32).
+    #[pyfunction]
+    pub fn detect_document_quad_f32(image: PyHostArray3<'_, f32>) -> Option<Vec<(f32, f32)>> {
+        perspective::detect_document_quad_f32(image.as_array()).map(|quad| quad.iter().map(|p| (p[0], p[1])).collect())
+    }
+
+    /// Warp `src_image` onto a quadrilateral of `dst_image` and composite it (u8).
+    ///
+    /// # Arguments
+    /// * `src_image` - Design to place (H, W, C) where C is 1, 3, or 4
+    /// * `dst_image` - Photo to place it on, any size, C is 1, 3, or 4
+    /// * `quad` - 4 (x, y) corners in `dst_image`: top-left, top-right, bottom-right, bottom-left
+    /// * `blend_mode` - Overlay blend mode ("normal", "multiply", ...)
+    /// * `opacity` - Layer opacity (0.0-1.0)
+    #[pyfunction]
+    #[pyo3(signature = (src_image, dst_image, quad, blend_mode="normal", opacity=1.0))]
+    pub fn map_to_quad<'py>(
+        src_image: PyHostArray3<'py, u8>,
+        dst_image: PyHostArray3<'py, u8>,
+        quad: Vec<(f32, f32)>,
+        blend_mode: &str,
+        opacity: f32,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let quad = parse_quad(&quad)?;
+        let result = perspective::map_to_quad_u8(src_image.as_array(), dst_image.as_array(), &quad, blend_mode, opacity);
+        Ok(result.into_pyarray(dst_image.py()))
+    }
+
+    /// Warp `src_image` onto a quadrilateral of `dst_image` and composite it (f32).
+    #[pyfunction]
+    #[pyo3(signature = (src_image, dst_image, quad, blend_mode="normal", opacity=1.0))]
+    pub fn map_to_quad_f32<'py>(
+        src_image: PyHostArray3<'py, f32>,
+        dst_image: PyHostArray3<'py, f32>,
+        quad: Vec<(f32, f32)>,
+        blend_mode: &str,
+        opacity: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let quad = parse_quad(&quad)?;
+        let result = perspective::map_to_quad_f32(src_image.as_array(), dst_image.as_array(), &quad, blend_mode, opacity);
+        Ok(result.into_pyarray(dst_image.py()))
+    }
+
+    // ========================================================================
+    // Matting
+    // ========================================================================
+
+    /// Remove a uniform backdrop (green/blue screen) (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+    /// * `key_color` - Backdrop color (r, g, b) in 0-255
+    /// * `tolerance` - Chroma distance keyed out completely (0.0-1.0)
+    /// * `softness` - Width of the soft alpha ramp (0.0-1.0)
+    /// * `spill_suppression` - Spill removal strength (0.0-1.0)
+    ///
+    /// # Returns
+    /// RGBA image (H, W, 4)
+    #[pyfunction]
+    #[pyo3(signature = (image, key_color=(0, 255, 0), tolerance=0.15, softness=0.1, spill_suppression=1.0))]
+    pub fn chroma_key<'py>(
+        image: PyHostArray3<'py, u8>,
+        key_color: (u8, u8, u8),
+        tolerance: f32,
+        softness: f32,
+        spill_suppression: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let key = [key_color.0, key_color.1, key_color.2];
+        let result = matting::chroma_key_u8(image.as_array(), key, tolerance, softness, spill_suppression);
+        result.into_pyarray(image.py())
+    }
+
+    /// Remove a uniform backdrop (green/blue screen) (f32).
+    ///
+    /// Same as chroma_key with `key_color` in 0.0-1.0.
+    #[pyfunction]
+    #[pyo3(signature = (image, key_color=(0.0, 1.0, 0.0), tolerance=0.15, softness=0.1, spill_suppression=1.0))]
+    pub fn chroma_key_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        key_color: (f32, f32, f32),
+        tolerance: f32,
+        softness: f32,
+        spill_suppression: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let key = [key_color.0, key_color.1, key_color.2];
+        let result = matting::chroma_key_f32(image.as_array(), key, tolerance, softness, spill_suppression);
+        result.into_pyarray(image.py())
+    }
+
+    /// Estimate fractional alpha in the unknown region of a trimap (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Input image (H, W, C) where C is 1, 3, or 4
+    /// * `trimap` - (H, W): 0 = background, 255 = foreground, else unknown
+    /// * `radius` - Guided filter window radius
+    /// * `epsilon` - Guided filter regularization (larger = smoother)
+    ///
+    /// # Returns
+    /// Alpha matte (H, W, 1)
+    #[pyfunction]
+    #[pyo3(signature = (image, trimap, radius=4, epsilon=1e-4))]
+    pub fn alpha_matting<'py>(
+        image: PyHostArray3<'py, u8>,
+        trimap: PyHostArray2<'py, u8>,
+        radius: usize,
+        epsilon: f32,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let (image_view, trimap_view) = (image.as_array(), trimap.as_array());
+        if trimap_view.dim() != (image_view.dim().0, image_view.dim().1) {
+            return Err(pyo3::exceptions::PyValueError::new_err("trimap size must match the image"));
+        }
+        let result = matting::alpha_matting_u8(image_view, trimap_view, radius, epsilon);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    /// Estimate fractional alpha in the unknown region of a trimap (f32).
+    ///
+    /// Same as alpha_matting with a trimap of 0.0 = background, 1.0 = foreground.
+    #[pyfunction]
+    #[pyo3(signature = (image, trimap, radius=4, epsilon=1e-4))]
+    pub fn alpha_matting_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        trimap: PyHostArray2<'py, f32>,
+        radius: usize,
+        epsilon: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let (image_view, trimap_view) = (image.as_array(), trimap.as_array());
+        if trimap_view.dim() != (image_view.dim().0, image_view.dim().1) {
+            return Err(pyo3::exceptions::PyValueError::new_err("trimap size must match the image"));
+        }
+        let result = matting::alpha_matting_f32(image_view, trimap_view, radius, epsilon);
+        Ok(result.into_pyarray(image.py()))
+    }
+
+    // ========================================================================
+    // Glitch
+    // ========================================================================
+
+    /// Shift red and blue channels in opposite directions (u8).
+    #[pyfunction]
+    #[pyo3(signature = (image, shift_x=4, shift_y=0))]
+    pub fn rgb_split<'py>(
+        image: PyHostArray3<'py, u8>,
+        shift_x: i32,
+        shift_y: i32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = glitch::rgb_split_u8(image.as_array(), shift_x, shift_y);
+        result.into_pyarray(image.py())
+    }
+
+    /// Shift red and blue channels in opposite directions (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, shift_x=4, shift_y=0))]
+    pub fn rgb_split_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        shift_x: i32,
+        shift_y: i32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = glitch::rgb_split_f32(image.as_array(), shift_x, shift_y);
+        result.into_pyarray(image.py())
+    }
+
+    /// Darken periodic rows like CRT scanlines (u8).
+    #[pyfunction]
+    #[pyo3(signature = (image, spacing=3, thickness=1, intensity=0.3))]
+    pub fn scanlines<'py>(
+        image: PyHostArray3<'py, u8>,
+        spacing: u32,
+        thickness: u32,
+        intensity: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = glitch::scanlines_u8(image.as_array(), spacing, thickness, intensity);
+        result.into_pyarray(image.py())
+    }
+
+    /// Darken periodic rows like CRT scanlines (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, spacing=3, thickness=1, intensity=0.3))]
+    pub fn scanlines_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        spacing: u32,
+        thickness: u32,
+        intensity: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = glitch::scanlines_f32(image.as_array(), spacing, thickness, intensity);
+        result.into_pyarray(image.py())
+    }
+
+    /// Displace random horizontal bands sideways (u8).
+    #[pyfunction]
+    #[pyo3(signature = (image, block_height=8, max_shift=20, probability=0.2, seed=0))]
+    pub fn block_shift<'py>(
+        image: PyHostArray3<'py, u8>,
+        block_height: u32,
+        max_shift: u32,
+        probability: f32,
+        seed: u64,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = glitch::block_shift_u8(image.as_array(), block_height, max_shift, probability, seed);
+        result.into_pyarray(image.py())
+    }
+
+    /// Displace random horizontal bands sideways (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, block_height=8, max_shift=20, probability=0.2, seed=0))]
+    pub fn block_shift_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        block_height: u32,
+        max_shift: u32,
+        probability: f32,
+        seed: u64,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = glitch::block_shift_f32(image.as_array(), block_height, max_shift, probability, seed);
+        result.into_pyarray(image.py())
+    }
+
+    /// Shift rows along a sine wave plus random jitter (u8).
+    #[pyfunction]
+    #[pyo3(signature = (image, amplitude=4.0, wavelength=64.0, phase=0.0, jitter=0.0, seed=0))]
+    pub fn wave_distort<'py>(
+        image: PyHostArray3<'py, u8>,
+        amplitude: f32,
+        wavelength: f32,
+        phase: f32,
+        jitter: f32,
+        seed: u64,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = glitch::wave_distort_u8(image.as_array(), amplitude, wavelength, phase, jitter, seed);
+        result.into_pyarray(image.py())
+    }
+
+    /// Shift rows along a sine wave plus random jitter (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, amplitude=4.0, wavelength=64.0, phase=0.0, jitter=0.0, seed=0))]
+    pub fn wave_distort_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        amplitude: f32,
+        wavelength: f32,
+        phase: f32,
+        jitter: f32,
+        seed: u64,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = glitch::wave_distort_f32(image.as_array(), amplitude, wavelength, phase, jitter, seed);
+        result.into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Material Maps
+    // ========================================================================
+
+    /// Convert a height map into a tangent-space normal map (u8).
+    ///
+    /// Output is RGB, or RGBA when the input has alpha.
+    #[pyfunction]
+    #[pyo3(signature = (image, strength=2.0, invert_y=false))]
+    pub fn height_to_normal<'py>(
+        image: PyHostArray3<'py, u8>,
+        strength: f32,
+        invert_y: bool,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = material::height_to_normal_u8(image.as_array(), strength, invert_y);
+        result.into_pyarray(image.py())
+    }
+
+    /// Convert a height map into a tangent-space normal map (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, strength=2.0, invert_y=false))]
+    pub fn height_to_normal_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        strength: f32,
+        invert_y: bool,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = material::height_to_normal_f32(image.as_array(), strength, invert_y);
+        result.into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Wavelet Filters
+    // ========================================================================
+
+    /// Scale à trous wavelet detail layers (fine to coarse) by `layer_gains` (u8).
+    #[pyfunction]
+    pub fn wavelet_sharpen<'py>(
+        image: PyHostArray3<'py, u8>,
+        layer_gains: Vec<f32>,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = wavelet::wavelet_sharpen_u8(image.as_array(), &layer_gains);
+        result.into_pyarray(image.py())
+    }
+
+    /// Scale à trous wavelet detail layers by `layer_gains` (f32).
+    #[pyfunction]
+    pub fn wavelet_sharpen_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        layer_gains: Vec<f32>,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = wavelet::wavelet_sharpen_f32(image.as_array(), &layer_gains);
+        result.into_pyarray(image.py())
+    }
+
+    /// Soft-threshold wavelet detail layers; thresholds are 0-255 (u8).
+    #[pyfunction]
+    pub fn wavelet_denoise<'py>(
+        image: PyHostArray3<'py, u8>,
+        thresholds: Vec<f32>,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let result = wavelet::wavelet_denoise_u8(image.as_array(), &thresholds);
+        result.into_pyarray(image.py())
+    }
+
+    /// Soft-threshold wavelet detail layers; thresholds are 0.0-1.0 (f32).
+    #[pyfunction]
+    pub fn wavelet_denoise_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        thresholds: Vec<f32>,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let result = wavelet::wavelet_denoise_f32(image.as_array(), &thresholds);
+        result.into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Tone Mapping
+    // ========================================================================
+
+    /// Compress linear HDR float32 values into 0.0-1.0.
+    ///
+    /// # Arguments
+    /// * `operator` - "reinhard", "filmic" (Hable) or "aces"
+    /// * `exposure` - Exposure in stops before the curve
+    /// * `white_point` - Linear value mapped to 1.0
+    #[pyfunction]
+    #[pyo3(signature = (image, operator="reinhard", exposure=0.0, white_point=11.2))]
+    pub fn tonemap<'py>(
+        image: PyHostArray3<'py, f32>,
+        operator: &str,
+        exposure: f32,
+        white_point: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let operator = TonemapOperator::from_name(operator)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown tone mapping operator '{}'", operator)))?;
+        let params = TonemapParams { operator, exposure, white_point };
+        Ok(tonemap_f32(image.as_array(), &params).into_pyarray(image.py()))
+    }
+
+    // ========================================================================
+    // Transfer Functions
+    // ========================================================================
+
+    /// Parse a transfer curve name ("srgb", "pq", "hlg", "slog3", "vlog", "logc3").
+    fn parse_transfer_function(name: &str) -> PyResult<TransferFunction> {
+        TransferFunction::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown transfer function '{}'", name)))
+    }
+
+    /// Decode a float32 image encoded with `transfer` to linear light (1.0 = reference white).
+    #[pyfunction]
+    #[pyo3(signature = (image, transfer="srgb"))]
+    pub fn decode_transfer<'py>(image: PyHostArray3<'py, f32>, transfer: &str) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let transfer = parse_transfer_function(transfer)?;
+        Ok(decode_transfer_f32(image.as_array(), transfer).into_pyarray(image.py()))
+    }
+
+    /// Encode a linear float32 image with `transfer`.
+    #[pyfunction]
+    #[pyo3(signature = (image, transfer="srgb"))]
+    pub fn encode_transfer<'py>(image: PyHostArray3<'py, f32>, transfer: &str) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let transfer = parse_transfer_function(transfer)?;
+        Ok(encode_transfer_f32(image.as_array(), transfer).into_pyarray(image.py()))
+    }
+
+    // ========================================================================
+    // Compositing
+    // ========================================================================
+
+    /// Check that two images and an optional mask can be blended.
+    fn check_blend_shapes(a: (usize, usize, usize), b: (usize, usize, usize), mask: Option<(usize, usize)>) -> PyResult<()> {
+        if a != b {
+            return Err(pyo3::exceptions::PyValueError::new_err("blended images must have the same shape"));
+        }
+        if mask.is_some_and(|m| m != (a.0, a.1)) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        Ok(())
+    }
+
+    /// Blend `b` over `a` through an optional mask and opacity (u8).
+    ///
+    /// # Arguments
+    /// * `a` - Base image (H, W, C) where C is 1, 3, or 4
+    /// * `b` - Image blended on top, same shape as `a`
+    /// * `mask` - Optional (H, W) mask, 0 = keep `a`, 255 = full blend
+    /// * `opacity` - Global strength (0.0-1.0)
+    /// * `blend_mode` - Overlay blend mode ("normal", "multiply", "screen", ...)
+    #[pyfunction]
+    #[pyo3(signature = (a, b, mask=None, opacity=1.0, blend_mode="normal"))]
+    pub fn blend_images<'py>(
+        a: PyHostArray3<'py, u8>,
+        b: PyHostArray3<'py, u8>,
+        mask: Option<PyHostArray2<'py, u8>>,
+        opacity: f32,
+        blend_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let mask_view = mask.as_ref().map(|m| m.as_array());
+        check_blend_shapes(a.as_array().dim(), b.as_array().dim(), mask_view.as_ref().map(|m| m.dim()))?;
+        let result = compositing::blend_images_u8(a.as_array(), b.as_array(), mask_view, opacity, blend_mode);
+        Ok(result.into_pyarray(a.py()))
+    }
+
+    /// Blend `b` over `a` through an optional mask and opacity (f32).
+    ///
+    /// Same as blend_images with a mask of 0.0 = keep `a`, 1.0 = full blend.
+    #[pyfunction]
+    #[pyo3(signature = (a, b, mask=None, opacity=1.0, blend_mode="normal"))]
+    pub fn blend_images_f32<'py>(
+        a: PyHostArray3<'py, f32>,
+        b: PyHostArray3<'py, f32>,
+        mask: Option<PyHostArray2<'py, f32>>,
+        opacity: f32,
+        blend_mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let mask_view = mask.as_ref().map(|m| m.as_array());
+        check_blend_shapes(a.as_array().dim(), b.as_array().dim(), mask_view.as_ref().map(|m| m.dim()))?;
+        let result = compositing::blend_images_f32(a.as_array(), b.as_array(), mask_view, opacity, blend_mode);
+        Ok(result.into_pyarray(a.py()))
+    }
+
+    /// Flatten an image onto a transparency checkerboard (u8), giving RGB (H, W, 3).
+    ///
+    /// # Arguments
+    /// * `cell_size` - Edge length of a cell in pixels
+    /// * `color_a` - Color of the top-left cell
+    /// * `color_b` - Color of the other cells
+    #[pyfunction]
+    #[pyo3(signature = (image, cell_size=8, color_a=(255, 255, 255), color_b=(204, 204, 204)))]
+    pub fn composite_on_checkerboard<'py>(
+        image: PyHostArray3<'py, u8>,
+        cell_size: usize,
+        color_a: (u8, u8, u8),
+        color_b: (u8, u8, u8),
+    ) -> Bound<'py, PyArray3<u8>> {
+        let (a, b) = ([color_a.0, color_a.1, color_a.2], [color_b.0, color_b.1, color_b.2]);
+        compositing::composite_on_checkerboard_u8(image.as_array(), cell_size, a, b).into_pyarray(image.py())
+    }
+
+    /// Flatten an image onto a transparency checkerboard (f32), giving RGB (H, W, 3).
+    #[pyfunction]
+    #[pyo3(signature = (image, cell_size=8, color_a=(1.0, 1.0, 1.0), color_b=(0.8, 0.8, 0.8)))]
+    pub fn composite_on_checkerboard_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        cell_size: usize,
+        color_a: (f32, f32, f32),
+        color_b: (f32, f32, f32),
+    ) -> Bound<'py, PyArray3<f32>> {
+        let (a, b) = ([color_a.0, color_a.1, color_a.2], [color_b.0, color_b.1, color_b.2]);
+        compositing::composite_on_checkerboard_f32(image.as_array(), cell_size, a, b).into_pyarray(image.py())
+    }
+
+    /// Flatten an image onto a solid color (u8), giving RGB (H, W, 3).
+    #[pyfunction]
+    #[pyo3(signature = (image, rgb=(255, 255, 255)))]
+    pub fn composite_on_color<'py>(image: PyHostArray3<'py, u8>, rgb: (u8, u8, u8)) -> Bound<'py, PyArray3<u8>> {
+        compositing::composite_on_color_u8(image.as_array(), [rgb.0, rgb.1, rgb.2]).into_pyarray(image.py())
+    }
+
+    /// Flatten an image onto a solid color (f32), giving RGB (H, W, 3).
+    #[pyfunction]
+    #[pyo3(signature = (image, rgb=(1.0, 1.0, 1.0)))]
+    pub fn composite_on_color_f32<'py>(image: PyHostArray3<'py, f32>, rgb: (f32, f32, f32)) -> Bound<'py, PyArray3<f32>> {
+        compositing::composite_on_color_f32(image.as_array(), [rgb.0, rgb.1, rgb.2]).into_pyarray(image.py())
+    }
+
+    fn check_stereo_shapes(left: (usize, usize, usize), right: (usize, usize, usize)) -> PyResult<()> {
+        if left != right {
+            return Err(pyo3::exceptions::PyValueError::new_err("stereo views must have the same shape"));
+        }
+        Ok(())
+    }
+
+    fn parse_anaglyph_mode(name: &str) -> PyResult<AnaglyphMode> {
+        AnaglyphMode::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown anaglyph mode '{}'", name)))
+    }
+
+    /// Combine a stereo pair into a red/cyan anaglyph (u8), giving RGB (H, W, 3).
+    ///
+    /// # Arguments
+    /// * `left` - Left-eye view (H, W, C) where C is 1, 3, or 4
+    /// * `right` - Right-eye view, same shape as `left`
+    /// * `mode` - "color" (red_cyan), "gray" or "dubois"
+    #[pyfunction]
+    #[pyo3(signature = (left, right, mode="dubois"))]
+    pub fn make_anaglyph<'py>(
+        left: PyHostArray3<'py, u8>,
+        right: PyHostArray3<'py, u8>,
+        mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        check_stereo_shapes(left.as_array().dim(), right.as_array().dim())?;
+        let result = compositing::make_anaglyph_u8(left.as_array(), right.as_array(), parse_anaglyph_mode(mode)?);
+        Ok(result.into_pyarray(left.py()))
+    }
+
+    /// Combine a stereo pair into a red/cyan anaglyph (f32), giving RGB (H, W, 3).
+    #[pyfunction]
+    #[pyo3(signature = (left, right, mode="dubois"))]
+    pub fn make_anaglyph_f32<'py>(
+        left: PyHostArray3<'py, f32>,
+        right: PyHostArray3<'py, f32>,
+        mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        check_stereo_shapes(left.as_array().dim(), right.as_array().dim())?;
+        let result = compositing::make_anaglyph_f32(left.as_array(), right.as_array(), parse_anaglyph_mode(mode)?);
+        Ok(result.into_pyarray(left.py()))
+    }
+
+    /// Place a stereo pair side by side (u8), giving (H, 2 * W, C).
+    #[pyfunction]
+    pub fn make_sbs<'py>(left: PyHostArray3<'py, u8>, right: PyHostArray3<'py, u8>) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        check_stereo_shapes(left.as_array().dim(), right.as_array().dim())?;
+        Ok(compositing::make_sbs_u8(left.as_array(), right.as_array()).into_pyarray(left.py()))
+    }
+
+    /// Place a stereo pair side by side (f32), giving (H, 2 * W, C).
+    #[pyfunction]
+    pub fn make_sbs_f32<'py>(left: PyHostArray3<'py, f32>, right: PyHostArray3<'py, f32>) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        check_stereo_shapes(left.as_array().dim(), right.as_array().dim())?;
+        Ok(compositing::make_sbs_f32(left.as_array(), right.as_array()).into_pyarray(left.py()))
+    }
+
+    fn check_onion_frames(current: (usize, usize, usize), frames: &[(usize, usize, usize)]) -> PyResult<()> {
+        if let Some(shape) = frames.iter().find(|&&shape| shape != current) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "onion skin frame of shape {:?} differs from the current frame {:?}",
+                shape, current
+            )));
+        }
+        Ok(())
+    }
+
+    /// Preview an animation frame over tinted, fading ghosts of its neighbors (u8).
+    ///
+    /// # Arguments
+    /// * `frames_before` - Preceding frames, nearest first
+    /// * `frames_after` - Following frames, nearest first
+    /// * `current` - Current frame (H, W, C) where C is 1, 3, or 4
+    /// * `tint_before` / `tint_after` - Ghost colors
+    /// * `opacity_falloff` - Opacity of the nearest ghosts and factor per further frame
+    #[pyfunction]
+    #[pyo3(signature = (frames_before, frames_after, current, tint_before=(255, 64, 64), tint_after=(64, 192, 64), opacity_falloff=0.5))]
+    pub fn onion_skin<'py>(
+        frames_before: Vec<PyHostArray3<'py, u8>>,
+        frames_after: Vec<PyHostArray3<'py, u8>>,
+        current: PyHostArray3<'py, u8>,
+        tint_before: (u8, u8, u8),
+        tint_after: (u8, u8, u8),
+        opacity_falloff: f32,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let before: Vec<_> = frames_before.iter().map(|f| f.as_array()).collect();
+        let after: Vec<_> = frames_after.iter().map(|f| f.as_array()).collect();
+        let shapes: Vec<_> = before.iter().chain(&after).map(|f| f.dim()).collect();
+        check_onion_frames(current.as_array().dim(), &shapes)?;
+        let (tb, ta) = ([tint_before.0, tint_before.1, tint_before.2], [tint_after.0, tint_after.1, tint_after.2]);
+        let result = compositing::onion_skin_u8(&before, &after, current.as_array(), tb, ta, opacity_falloff);
+        Ok(result.into_pyarray(current.py()))
+    }
+
+    /// Preview an animation frame over tinted, fading ghosts of its neighbors (f32).
+    #[pyfunction]
+    #[pyo3(signature = (frames_before, frames_after, current, tint_before=(1.0, 0.25, 0.25), tint_after=(0.25, 0.75, 0.25), opacity_falloff=0.5))]
+    pub fn onion_skin_f32<'py>(
+        frames_before: Vec<PyHostArray3<'py, f32>>,
+        frames_after: Vec<PyHostArray3<'py, f32>>,
+        current: PyHostArray3<'py, f32>,
+        tint_before: (f32, f32, f32),
+        tint_after: (f32, f32, f32),
+        opacity_falloff: f32,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let before: Vec<_> = frames_before.iter().map(|f| f.as_array()).collect();
+        let after: Vec<_> = frames_after.iter().map(|f| f.as_array()).collect();
+        let shapes: Vec<_> = before.iter().chain(&after).map(|f| f.dim()).collect();
+        check_onion_frames(current.as_array().dim(), &shapes)?;
+        let (tb, ta) = ([tint_before.0, tint_before.1, tint_before.2], [tint_after.0, tint_after.1, tint_after.2]);
+        let result = compositing::onion_skin_f32(&before, &after, current.as_array(), tb, ta, opacity_falloff);
+        Ok(result.into_pyarray(current.py()))
+    }
+
+    // ========================================================================
+    // RAW
+    // ========================================================================
+
+    fn parse_bayer_pattern(name: &str) -> PyResult<BayerPattern> {
+        BayerPattern::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown Bayer pattern '{}'", name)))
+    }
+
+    /// Normalize a uint16 Bayer mosaic to linear float32 and white balance it.
+    ///
+    /// # Arguments
+    /// * `bayer` - Raw mosaic (H, W)
+    /// * `pattern` - "rggb", "bggr", "grbg" or "gbrg"
+    /// * `black_level` - Raw value of black
+    /// * `white_level` - Raw value of sensor saturation
+    /// * `wb_gains` - (r, g, b) white balance gains
+    ///
+    /// # Returns
+    /// Linear mosaic (H, W), 1.0 = white level
+    #[pyfunction]
+    #[pyo3(signature = (bayer, pattern="rggb", black_level=0.0, white_level=65535.0, wb_gains=(1.0, 1.0, 1.0)))]
+    pub fn raw_to_linear<'py>(
+        bayer: PyHostArray2<'py, u16>,
+        pattern: &str,
+        black_level: f32,
+        white_level: f32,
+        wb_gains: (f32, f32, f32),
+    ) -> PyResult<Bound<'py, PyArray2<f32>>> {
+        let params = RawParams { black_level, white_level, wb_gains: [wb_gains.0, wb_gains.1, wb_gains.2] };
+        let result = raw_mod::raw_to_linear_f32(bayer.as_array(), parse_bayer_pattern(pattern)?, &params);
+        Ok(result.into_pyarray(bayer.py()))
+    }
+
+    /// Interpolate a linear float32 Bayer mosaic (H, W) to RGB (H, W, 3).
+    ///
+    /// # Arguments
+    /// * `pattern` - "rggb", "bggr", "grbg" or "gbrg"
+    /// * `algorithm` - "bilinear" or "malvar" (Malvar-He-Cutler)
+    #[pyfunction]
+    #[pyo3(signature = (bayer, pattern="rggb", algorithm="malvar"))]
+    pub fn demosaic<'py>(
+        bayer: PyHostArray2<'py, f32>,
+        pattern: &str,
+        algorithm: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let algorithm = DemosaicAlgorithm::from_name(algorithm)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown demosaic algorithm '{}'", algorithm)))?;
+        let result = raw_mod::demosaic_f32(bayer.as_array(), parse_bayer_pattern(pattern)?, algorithm);
+        Ok(result.into_pyarray(bayer.py()))
+    }
+
+    // ========================================================================
+    // Deband
+    // ========================================================================
+
+    /// Smooth quantization banding in flat gradients (u8).
+    ///
+    /// # Arguments
+    /// * `threshold` - Largest difference (0-255) still treated as banding
+    /// * `range` - Largest sample distance in pixels
+    /// * `grain` - Dither grain amplitude (0-255), 0 = none
+    #[pyfunction]
+    #[pyo3(signature = (image, threshold=4.0, range=16, grain=6.0))]
+    pub fn deband<'py>(
+        image: PyHostArray3<'py, u8>,
+        threshold: f32,
+        range: u32,
+        grain: f32,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let params = DebandParams { threshold: threshold / 255.0, range, grain: grain / 255.0 };
+        deband_u8(image.as_array(), &params).into_pyarray(image.py())
+    }
+
+    /// Smooth quantization banding in flat gradients (f32, threshold and grain in 0.0-1.0).
+    #[pyfunction]
+    #[pyo3(signature = (image, threshold=4.0 / 255.0, range=16, grain=6.0 / 255.0))]
+    pub fn deband_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        threshold: f32,
+        range: u32,
+        grain: f32,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let params = DebandParams { threshold, range, grain };
+        deband_f32_impl(image.as_array(), &params).into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // FFT
+    // ========================================================================
+
+    /// Forward 2D FFT of luminance (or each color channel with `per_channel`).
+    ///
+    /// # Returns
+    /// complex64 spectrum (H, W, 1 or 3), unshifted and unnormalized like `numpy.fft.fft2`
+    #[pyfunction]
+    #[pyo3(signature = (image, per_channel=false))]
+    pub fn fft_forward<'py>(
+        image: PyHostArray3<'py, f32>,
+        per_channel: bool,
+    ) -> Bound<'py, PyArray3<numpy::Complex32>> {
+        let result = fft_mod::fft_forward_f32(image.as_array(), per_channel);
+        result.mapv(|v| numpy::Complex32::new(v.re, v.im)).into_pyarray(image.py())
+    }
+
+    /// Inverse 2D FFT of a complex64 spectrum (H, W, C) to float32 (H, W, C).
+    #[pyfunction]
+    pub fn fft_inverse<'py>(spectrum: PyHostArray3<'py, numpy::Complex32>) -> Bound<'py, PyArray3<f32>> {
+        let input = spectrum.as_array().mapv(|v| Complex::new(v.re, v.im));
+        fft_mod::fft_inverse_f32(input.view()).into_pyarray(spectrum.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, cutoff=0.25))]
+    pub fn fft_lowpass<'py>(image: PyHostArray3<'py, u8>, cutoff: f32) -> Bound<'py, PyArray3<u8>> {
+        fft_mod::fft_lowpass_u8(image.as_array(), cutoff).into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, cutoff=0.25))]
+    pub fn fft_lowpass_f32<'py>(image: PyHostArray3<'py, f32>, cutoff: f32) -> Bound<'py, PyArray3<f32>> {
+        fft_mod::fft_lowpass_f32(image.as_array(), cutoff).into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, cutoff=0.1))]
+    pub fn fft_highpass<'py>(image: PyHostArray3<'py, u8>, cutoff: f32) -> Bound<'py, PyArray3<u8>> {
+        fft_mod::fft_highpass_u8(image.as_array(), cutoff).into_pyarray(image.py())
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (image, cutoff=0.1))]
+    pub fn fft_highpass_f32<'py>(image: PyHostArray3<'py, f32>, cutoff: f32) -> Bound<'py, PyArray3<f32>> {
+        fft_mod::fft_highpass_f32(image.as_array(), cutoff).into_pyarray(image.py())
+    }
+
+    fn to_notches(notches: Vec<(f32, f32, f32)>) -> Vec<Notch> {
+        notches.into_iter().map(|(u, v, radius)| Notch { u, v, radius }).collect()
+    }
+
+    /// Suppress spectrum peaks given as (u, v, radius) in cycles per image (u8).
+    #[pyfunction]
+    pub fn fft_notch_filter<'py>(image: PyHostArray3<'py, u8>, notches: Vec<(f32, f32, f32)>) -> Bound<'py, PyArray3<u8>> {
+        fft_mod::fft_notch_filter_u8(image.as_array(), &to_notches(notches)).into_pyarray(image.py())
+    }
+
+    /// Suppress spectrum peaks given as (u, v, radius) in cycles per image (f32).
+    #[pyfunction]
+    pub fn fft_notch_filter_f32<'py>(image: PyHostArray3<'py, f32>, notches: Vec<(f32, f32, f32)>) -> Bound<'py, PyArray3<f32>> {
+        fft_mod::fft_notch_filter_f32(image.as_array(), &to_notches(notches)).into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Projection
+    // ========================================================================
+
+    /// Split an equirectangular panorama into six cube faces (u8).
+    ///
+    /// # Arguments
+    /// * `image` - Equirectangular image (H, W, C)
+    /// * `face_size` - Face edge length, default W / 4
+    ///
+    /// # Returns
+    /// Faces (6, face_size, face_size, C) ordered +x, -x, +y, -y, +z, -z
+    #[pyfunction]
+    #[pyo3(signature = (image, face_size=None))]
+    pub fn equirect_to_cubemap<'py>(image: PyHostArray3<'py, u8>, face_size: Option<usize>) -> Bound<'py, PyArray4<u8>> {
+        let face_size = face_size.unwrap_or(image.as_array().dim().1 / 4).max(1);
+        projection::equirect_to_cubemap_u8(image.as_array(), face_size).into_pyarray(image.py())
+    }
+
+    /// Split an equirectangular panorama into six cube faces (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, face_size=None))]
+    pub fn equirect_to_cubemap_f32<'py>(image: PyHostArray3<'py, f32>, face_size: Option<usize>) -> Bound<'py, PyArray4<f32>> {
+        let face_size = face_size.unwrap_or(image.as_array().dim().1 / 4).max(1);
+        projection::equirect_to_cubemap_f32(image.as_array(), face_size).into_pyarray(image.py())
+    }
+
+    fn check_cube_faces(dim: (usize, usize, usize, usize)) -> PyResult<usize> {
+        if dim.0 != 6 || dim.1 != dim.2 {
+            return Err(pyo3::exceptions::PyValueError::new_err("faces must have shape (6, size, size, channels)"));
+        }
+        Ok(dim.1)
+    }
+
+    /// Merge six cube faces (6, S, S, C) into an equirectangular panorama (u8).
+    ///
+    /// Output size defaults to 4S x 2S.
+    #[pyfunction]
+    #[pyo3(signature = (faces, out_width=None, out_height=None))]
+    pub fn cubemap_to_equirect<'py>(
+        faces: PyHostArray4<'py, u8>,
+        out_width: Option<usize>,
+        out_height: Option<usize>,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let size = check_cube_faces(faces.as_array().dim())?;
+        let (w, h) = (out_width.unwrap_or(4 * size), out_height.unwrap_or(2 * size));
+        Ok(projection::cubemap_to_equirect_u8(faces.as_array(), w, h).into_pyarray(faces.py()))
+    }
+
+    /// Merge six cube faces (6, S, S, C) into an equirectangular panorama (f32).
+    #[pyfunction]
+    #[pyo3(signature = (faces, out_width=None, out_height=None))]
+    pub fn cubemap_to_equirect_f32<'py>(
+        faces: PyHostArray4<'py, f32>,
+        out_width: Option<usize>,
+        out_height: Option<usize>,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let size = check_cube_faces(faces.as_array().dim())?;
+        let (w, h) = (out_width.unwrap_or(4 * size), out_height.unwrap_or(2 * size));
+        Ok(projection::cubemap_to_equirect_f32(faces.as_array(), w, h).into_pyarray(faces.py()))
+    }
+
+    /// Default perspective view size: the panorama's resolution at the view
+    /// center, 4:3.
+    fn view_size(pano_width: usize, fov: f32, out_width: Option<usize>, out_height: Option<usize>) -> (usize, usize) {
+        let w = out_width.unwrap_or((pano_width as f32 * fov.clamp(1.0, 179.0) / 360.0).round() as usize).max(1);
+        (w, out_height.unwrap_or(w * 3 / 4).max(1))
+    }
+
+    /// Render a perspective view of an equirectangular panorama (u8).
+    ///
+    /// # Arguments
+    /// * `yaw` - Degrees to the right of the panorama center
+    /// * `pitch` - Degrees above the horizon
+    /// * `roll` - Degrees about the viewing axis
+    /// * `fov` - Horizontal field of view in degrees
+    /// * `out_width`, `out_height` - View size, default matches the panorama resolution at 4:3
+    #[pyfunction]
+    #[pyo3(signature = (image, yaw=0.0, pitch=0.0, roll=0.0, fov=90.0, out_width=None, out_height=None))]
+    pub fn reproject_equirect<'py>(
+        image: PyHostArray3<'py, u8>,
+        yaw: f32,
+        pitch: f32,
+        roll: f32,
+        fov: f32,
+        out_width: Option<usize>,
+        out_height: Option<usize>,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let (w, h) = view_size(image.as_array().dim().1, fov, out_width, out_height);
+        let view = ViewParams { yaw, pitch, roll, fov };
+        projection::reproject_equirect_u8(image.as_array(), &view, w, h).into_pyarray(image.py())
+    }
+
+    /// Render a perspective view of an equirectangular panorama (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, yaw=0.0, pitch=0.0, roll=0.0, fov=90.0, out_width=None, out_height=None))]
+    pub fn reproject_equirect_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        yaw: f32,
+        pitch: f32,
+        roll: f32,
+        fov: f32,
+        out_width: Option<usize>,
+        out_height: Option<usize>,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let (w, h) = view_size(image.as_array().dim().1, fov, out_width, out_height);
+        let view = ViewParams { yaw, pitch, roll, fov };
+        projection::reproject_equirect_f32(image.as_array(), &view, w, h).into_pyarray(image.py())
+    }
+
+    /// Render a "little planet" stereographic view of a panorama (u8).
+    ///
+    /// # Arguments
+    /// * `fov` - Field of view across the output in degrees (1-359)
+    /// * `rotation` - Clockwise spin of the planet in degrees
+    /// * `out_size` - Edge length of the square output, default panorama height
+    #[pyfunction]
+    #[pyo3(signature = (image, fov=240.0, rotation=0.0, out_size=None))]
+    pub fn little_planet<'py>(
+        image: PyHostArray3<'py, u8>,
+        fov: f32,
+        rotation: f32,
+        out_size: Option<usize>,
+    ) -> Bound<'py, PyArray3<u8>> {
+        let out_size = out_size.unwrap_or(image.as_array().dim().0).max(1);
+        projection::little_planet_u8(image.as_array(), fov, rotation, out_size).into_pyarray(image.py())
+    }
+
+    /// Render a "little planet" stereographic view of a panorama (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, fov=240.0, rotation=0.0, out_size=None))]
+    pub fn little_planet_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        fov: f32,
+        rotation: f32,
+        out_size: Option<usize>,
+    ) -> Bound<'py, PyArray3<f32>> {
+        let out_size = out_size.unwrap_or(image.as_array().dim().0).max(1);
+        projection::little_planet_f32(image.as_array(), fov, rotation, out_size).into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Distort
+    // ========================================================================
+
+    fn parse_interpolation(name: &str) -> PyResult<Interpolation> {
+        Interpolation::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown interpolation '{}'", name)))
+    }
+
+    fn parse_polar_direction(name: &str) -> PyResult<PolarDirection> {
+        PolarDirection::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown polar direction '{}'", name)))
+    }
+
+    /// Convert between rectangular and polar coordinates (u8).
+    ///
+    /// # Arguments
+    /// * `direction` - "rect_to_polar" or "polar_to_rect"
+    /// * `interpolation` - "nearest", "bilinear" or "bicubic"
+    #[pyfunction]
+    #[pyo3(signature = (image, direction="rect_to_polar", interpolation="bilinear"))]
+    pub fn polar_coordinates<'py>(
+        image: PyHostArray3<'py, u8>,
+        direction: &str,
+        interpolation: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let direction = parse_polar_direction(direction)?;
+        let interpolation = parse_interpolation(interpolation)?;
+        Ok(distort::polar_coordinates_u8(image.as_array(), direction, interpolation).into_pyarray(image.py()))
+    }
+
+    /// Convert between rectangular and polar coordinates (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, direction="rect_to_polar", interpolation="bilinear"))]
+    pub fn polar_coordinates_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        direction: &str,
+        interpolation: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let direction = parse_polar_direction(direction)?;
+        let interpolation = parse_interpolation(interpolation)?;
+        Ok(distort::polar_coordinates_f32(image.as_array(), direction, interpolation).into_pyarray(image.py()))
+    }
+
+    /// Twirl the image around a center (u8).
+    ///
+    /// # Arguments
+    /// * `angle` - Rotation at the center in degrees (positive = clockwise)
+    /// * `radius` - Extent as a fraction of half the shorter side
+    /// * `center` - (x, y) center as a fraction of width and height
+    #[pyfunction]
+    #[pyo3(signature = (image, angle=50.0, radius=1.0, center=(0.5, 0.5)))]
+    pub fn twirl<'py>(image: PyHostArray3<'py, u8>, angle: f32, radius: f32, center: (f32, f32)) -> Bound<'py, PyArray3<u8>> {
+        distort::twirl_u8(image.as_array(), angle, radius, center).into_pyarray(image.py())
+    }
+
+    /// Twirl the image around a center (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, angle=50.0, radius=1.0, center=(0.5, 0.5)))]
+    pub fn twirl_f32<'py>(image: PyHostArray3<'py, f32>, angle: f32, radius: f32, center: (f32, f32)) -> Bound<'py, PyArray3<f32>> {
+        distort::twirl_f32(image.as_array(), angle, radius, center).into_pyarray(image.py())
+    }
+
+    /// Wrap the image around a sphere (u8). `amount` -1.0 (squeeze) to 1.0 (bulge).
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=1.0))]
+    pub fn spherize<'py>(image: PyHostArray3<'py, u8>, amount: f32) -> Bound<'py, PyArray3<u8>> {
+        distort::spherize_u8(image.as_array(), amount).into_pyarray(image.py())
+    }
+
+    /// Wrap the image around a sphere (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=1.0))]
+    pub fn spherize_f32<'py>(image: PyHostArray3<'py, f32>, amount: f32) -> Bound<'py, PyArray3<f32>> {
+        distort::spherize_f32(image.as_array(), amount).into_pyarray(image.py())
+    }
+
+    /// Squeeze the image toward the center (u8). `amount` -1.0 (push out) to 1.0 (pinch in).
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=0.5))]
+    pub fn pinch<'py>(image: PyHostArray3<'py, u8>, amount: f32) -> Bound<'py, PyArray3<u8>> {
+        distort::pinch_u8(image.as_array(), amount).into_pyarray(image.py())
+    }
+
+    /// Squeeze the image toward the center (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, amount=0.5))]
+    pub fn pinch_f32<'py>(image: PyHostArray3<'py, f32>, amount: f32) -> Bound<'py, PyArray3<f32>> {
+        distort::pinch_f32(image.as_array(), amount).into_pyarray(image.py())
+    }
+
+    fn parse_wave_type(name: &str) -> PyResult<WaveType> {
+        WaveType::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown wave type '{}'", name)))
+    }
+
+    /// Ripple the image with a periodic wave (u8).
+    ///
+    /// # Arguments
+    /// * `amplitude` - Largest displacement in pixels
+    /// * `wavelength` - Wave period in pixels
+    /// * `wave_type` - "sine", "triangle" or "square"
+    #[pyfunction]
+    #[pyo3(signature = (image, amplitude=10.0, wavelength=120.0, wave_type="sine"))]
+    pub fn wave<'py>(
+        image: PyHostArray3<'py, u8>,
+        amplitude: f32,
+        wavelength: f32,
+        wave_type: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let wave_type = parse_wave_type(wave_type)?;
+        Ok(distort::wave_u8(image.as_array(), amplitude, wavelength, wave_type).into_pyarray(image.py()))
+    }
+
+    /// Ripple the image with a periodic wave (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, amplitude=10.0, wavelength=120.0, wave_type="sine"))]
+    pub fn wave_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        amplitude: f32,
+        wavelength: f32,
+        wave_type: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let wave_type = parse_wave_type(wave_type)?;
+        Ok(distort::wave_f32(image.as_array(), amplitude, wavelength, wave_type).into_pyarray(image.py()))
+    }
+
+    /// Repeat one wedge of the image around a center, mirrored alternately (u8).
+    ///
+    /// # Arguments
+    /// * `segments` - Number of wedges
+    /// * `rotation` - Angle of the source wedge in degrees
+    /// * `center` - (x, y) center as a fraction of width and height
+    #[pyfunction]
+    #[pyo3(signature = (image, segments=6, rotation=0.0, center=(0.5, 0.5)))]
+    pub fn kaleidoscope<'py>(image: PyHostArray3<'py, u8>, segments: u32, rotation: f32, center: (f32, f32)) -> Bound<'py, PyArray3<u8>> {
+        distort::kaleidoscope_u8(image.as_array(), segments, rotation, center).into_pyarray(image.py())
+    }
+
+    /// Repeat one wedge of the image around a center, mirrored alternately (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, segments=6, rotation=0.0, center=(0.5, 0.5)))]
+    pub fn kaleidoscope_f32<'py>(image: PyHostArray3<'py, f32>, segments: u32, rotation: f32, center: (f32, f32)) -> Bound<'py, PyArray3<f32>> {
+        distort::kaleidoscope_f32(image.as_array(), segments, rotation, center).into_pyarray(image.py())
+    }
+
+    /// Reflect one side of the image onto the other across a line (u8).
+    ///
+    /// # Arguments
+    /// * `axis_angle` - Angle of the mirror line in degrees (0 = vertical, left copied to right)
+    /// * `offset` - Shift of the line from the center in pixels
+    #[pyfunction]
+    #[pyo3(signature = (image, axis_angle=0.0, offset=0.0))]
+    pub fn mirror<'py>(image: PyHostArray3<'py, u8>, axis_angle: f32, offset: f32) -> Bound<'py, PyArray3<u8>> {
+        distort::mirror_u8(image.as_array(), axis_angle, offset).into_pyarray(image.py())
+    }
+
+    /// Reflect one side of the image onto the other across a line (f32).
+    #[pyfunction]
+    #[pyo3(signature = (image, axis_angle=0.0, offset=0.0))]
+    pub fn mirror_f32<'py>(image: PyHostArray3<'py, f32>, axis_angle: f32, offset: f32) -> Bound<'py, PyArray3<f32>> {
+        distort::mirror_f32(image.as_array(), axis_angle, offset).into_pyarray(image.py())
+    }
+
+    // ========================================================================
+    // Canvas
+    // ========================================================================
+
+    /// Crop rectangle as returned to Python: (x, y, width, height).
+    type RectTuple = (usize, usize, usize, usize);
+
+    fn parse_trim_mode(name: &str) -> PyResult<TrimMode> {
+        TrimMode::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown trim mode '{}'", name)))
+    }
+
+    /// Crop an image to the bounding box of its content (u8).
+    ///
+    /// # Arguments
+    /// * `mode` - "transparent", "top_left" or "bottom_right" (background color source)
+    /// * `tolerance` - Largest per-channel difference (or alpha) counted as background
+    ///
+    /// # Returns
+    /// (cropped image, (x, y, width, height) of the crop in the input)
+    #[pyfunction]
+    #[pyo3(signature = (image, mode="transparent", tolerance=0))]
+    pub fn trim<'py>(
+        image: PyHostArray3<'py, u8>,
+        mode: &str,
+        tolerance: u8,
+    ) -> PyResult<(Bound<'py, PyArray3<u8>>, RectTuple)> {
+        let mode = parse_trim_mode(mode)?;
+        let (cropped, rect) = canvas::trim_u8(image.as_array(), mode, tolerance);
+        Ok((cropped.into_pyarray(image.py()), (rect.x, rect.y, rect.width, rect.height)))
+    }
+
+    /// Crop an image to the bounding box of its content (f32, tolerance 0.0-1.0).
+    #[pyfunction]
+    #[pyo3(signature = (image, mode="transparent", tolerance=0.0))]
+    pub fn trim_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        mode: &str,
+        tolerance: f32,
+    ) -> PyResult<(Bound<'py, PyArray3<f32>>, RectTuple)> {
+        let mode = parse_trim_mode(mode)?;
+        let (cropped, rect) = canvas::trim_f32(image.as_array(), mode, tolerance);
+        Ok((cropped.into_pyarray(image.py()), (rect.x, rect.y, rect.width, rect.height)))
     }
 
+    /// Tight bounding box of the pixels with alpha above `threshold` (u8).
+    ///
+    /// Images without alpha count as fully visible.
+    ///
+    /// # Returns
+    /// (x, y, width, height), or None if no pixel is above the threshold
     #[pyfunction]
-    pub fn equalize_histogram_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = color_adjust::equalize_histogram_f32(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, threshold=0))]
+    pub fn alpha_bbox(image: PyHostArray3<'_, u8>, threshold: u8) -> Option<RectTuple> {
+        canvas::alpha_bbox_u8(image.as_array(), threshold).map(|r| (r.x, r.y, r.width, r.height))
     }
 
-    // ========================================================================
-    // Pixelate & Vignette
-    // ========================================================================
+    /// Tight bounding box of the pixels with alpha above `threshold` (f32, 0.0-1.0).
+    #[pyfunction]
+    #[pyo3(signature = (image, threshold=0.0))]
+    pub fn alpha_bbox_f32(image: PyHostArray3<'_, f32>, threshold: f32) -> Option<RectTuple> {
+        canvas::alpha_bbox_f32(image.as_array(), threshold).map(|r| (r.x, r.y, r.width, r.height))
+    }
 
+    /// Bounding box of every pixel with any opacity (u8), or None if fully transparent.
     #[pyfunction]
-    pub fn pixelate<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-        block_size: u32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = stylize::pixelate_u8(image.as_array(), block_size);
-        result.into_pyarray(py)
+    pub fn opaque_bbox(image: PyHostArray3<'_, u8>) -> Option<RectTuple> {
+        canvas::opaque_bbox_u8(image.as_array()).map(|r| (r.x, r.y, r.width, r.height))
     }
 
+    /// Bounding box of every pixel with any opacity (f32), or None if fully transparent.
     #[pyfunction]
-    pub fn pixelate_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-        block_size: u32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = stylize::pixelate_f32(image.as_array(), block_size);
-        result.into_pyarray(py)
+    pub fn opaque_bbox_f32(image: PyHostArray3<'_, f32>) -> Option<RectTuple> {
+        canvas::opaque_bbox_f32(image.as_array()).map(|r| (r.x, r.y, r.width, r.height))
+    }
+
+    fn parse_extend_method(name: &str) -> PyResult<ExtendMethod> {
+        ExtendMethod::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown extend method '{}'", name)))
     }
 
+    /// Grow the canvas and fill the new border (u8).
+    ///
+    /// # Arguments
+    /// * `left`, `top`, `right`, `bottom` - Pixels added on each side
+    /// * `method` - "mirror", "smear" or "patch_match"
     #[pyfunction]
-    pub fn vignette<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-        amount: f32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = stylize::vignette_u8(image.as_array(), amount);
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, left=0, top=0, right=0, bottom=0, method="mirror"))]
+    pub fn extend_canvas_fill<'py>(
+        image: PyHostArray3<'py, u8>,
+        left: usize,
+        top: usize,
+        right: usize,
+        bottom: usize,
+        method: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let method = parse_extend_method(method)?;
+        Ok(canvas::extend_canvas_fill_u8(image.as_array(), left, top, right, bottom, method).into_pyarray(image.py()))
     }
 
+    /// Grow the canvas and fill the new border (f32).
     #[pyfunction]
-    pub fn vignette_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-        amount: f32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = stylize::vignette_f32(image.as_array(), amount);
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, left=0, top=0, right=0, bottom=0, method="mirror"))]
+    pub fn extend_canvas_fill_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        left: usize,
+        top: usize,
+        right: usize,
+        bottom: usize,
+        method: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let method = parse_extend_method(method)?;
+        Ok(canvas::extend_canvas_fill_f32(image.as_array(), left, top, right, bottom, method).into_pyarray(image.py()))
     }
 
     // ========================================================================
-    // Rotation and Mirroring
+    // Synthesis
     // ========================================================================
 
-    /// Rotate image 90 degrees clockwise (u8).
+    /// Fill the masked region with texture from the rest of the image (u8).
+    ///
+    /// # Arguments
+    /// * `mask` - (H, W) uint8 mask, >= 128 marks the pixels to replace
+    /// * `patch_size` - Edge length of the matched patches (odd)
+    /// * `seed` - Seed of the random search
     #[pyfunction]
-    pub fn rotate_90_cw<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = rotate_mod::rotate_90_cw_u8(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, mask, patch_size=7, seed=0))]
+    pub fn fill_region_patchmatch<'py>(
+        image: PyHostArray3<'py, u8>,
+        mask: PyHostArray2<'py, u8>,
+        patch_size: usize,
+        seed: u64,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let (image_view, mask_view) = (image.as_array(), mask.as_array());
+        if mask_view.dim() != (image_view.dim().0, image_view.dim().1) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        Ok(synthesis::fill_region_patchmatch_u8(image_view, mask_view, patch_size, seed).into_pyarray(image.py()))
     }
 
-    /// Rotate image 90 degrees clockwise (f32).
+    /// Fill the masked region with texture from the rest of the image (f32, mask >= 0.5).
     #[pyfunction]
-    pub fn rotate_90_cw_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = rotate_mod::rotate_90_cw_f32(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, mask, patch_size=7, seed=0))]
+    pub fn fill_region_patchmatch_f32<'py>(
+        image: PyHostArray3<'py, f32>,
+        mask: PyHostArray2<'py, f32>,
+        patch_size: usize,
+        seed: u64,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let (image_view, mask_view) = (image.as_array(), mask.as_array());
+        if mask_view.dim() != (image_view.dim().0, image_view.dim().1) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the image"));
+        }
+        Ok(synthesis::fill_region_patchmatch_f32(image_view, mask_view, patch_size, seed).into_pyarray(image.py()))
     }
 
-    /// Rotate image 180 degrees (u8).
+    /// Synthesize a texture of any size from a sample (u8).
     #[pyfunction]
-    pub fn rotate_180<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
+    #[pyo3(signature = (sample, out_width, out_height, patch_size=7, seed=0))]
+    pub fn synthesize_texture<'py>(
+        sample: PyHostArray3<'py, u8>,
+        out_width: usize,
+        out_height: usize,
+        patch_size: usize,
+        seed: u64,
     ) -> Bound<'py, PyArray3<u8>> {
-        let result = rotate_mod::rotate_180_u8(image.as_array());
-        result.into_pyarray(py)
+        synthesis::synthesize_texture_u8(sample.as_array(), out_width, out_height, patch_size, seed).into_pyarray(sample.py())
     }
 
-    /// Rotate image 180 degrees (f32).
+    /// Synthesize a texture of any size from a sample (f32).
     #[pyfunction]
-    pub fn rotate_180_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
+    #[pyo3(signature = (sample, out_width, out_height, patch_size=7, seed=0))]
+    pub fn synthesize_texture_f32<'py>(
+        sample: PyHostArray3<'py, f32>,
+        out_width: usize,
+        out_height: usize,
+        patch_size: usize,
+        seed: u64,
     ) -> Bound<'py, PyArray3<f32>> {
-        let result = rotate_mod::rotate_180_f32(image.as_array());
-        result.into_pyarray(py)
+        synthesis::synthesize_texture_f32(sample.as_array(), out_width, out_height, patch_size, seed).into_pyarray(sample.py())
     }
 
-    /// Rotate image 270 degrees clockwise (90 counter-clockwise) (u8).
-    #[pyfunction]
-    pub fn rotate_270_cw<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = rotate_mod::rotate_270_cw_u8(image.as_array());
-        result.into_pyarray(py)
+    /// Nearest-neighbor field as returned to Python: ((H, W, 2) uint32 (x, y), (H, W) float32 SSD).
+    type NnfArrays<'py> = (Bound<'py, PyArray3<u32>>, Bound<'py, PyArray2<f32>>);
+
+    fn nnf_to_py<'py>(py: Python<'py>, nnf: synthesis::NearestNeighborField) -> NnfArrays<'py> {
+        (nnf.matches.into_pyarray(py), nnf.distances.into_pyarray(py))
     }
 
-    /// Rotate image 270 degrees clockwise (90 counter-clockwise) (f32).
+    /// PatchMatch nearest-neighbor field from target patches to source patches (u8).
+    ///
+    /// # Returns
+    /// (matches, distances): matched source patch center (x, y) per target
+    /// pixel and the sum of squared differences on 0.0-1.0 values
     #[pyfunction]
-    pub fn rotate_270_cw_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = rotate_mod::rotate_270_cw_f32(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (target, source, patch_size=7, iterations=5, seed=0))]
+    pub fn patchmatch_nnf<'py>(
+        target: PyHostArray3<'py, u8>,
+        source: PyHostArray3<'py, u8>,
+        patch_size: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> PyResult<NnfArrays<'py>> {
+        if target.as_array().dim().2 != source.as_array().dim().2 {
+            return Err(pyo3::exceptions::PyValueError::new_err("target and source need the same channel count"));
+        }
+        let nnf = synthesis::patchmatch_nnf_u8(target.as_array(), source.as_array(), patch_size, iterations, seed);
+        Ok(nnf_to_py(target.py(), nnf))
     }
 
-    /// Rotate image by specified degrees (90, 180, or 270) (u8).
+    /// PatchMatch nearest-neighbor field from target patches to source patches (f32).
     #[pyfunction]
-    pub fn rotate<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-        degrees: u32,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = rotate_mod::rotate_u8(image.as_array(), degrees);
-        result.into_pyarray(py)
+    #[pyo3(signature = (target, source, patch_size=7, iterations=5, seed=0))]
+    pub fn patchmatch_nnf_f32<'py>(
+        target: PyHostArray3<'py, f32>,
+        source: PyHostArray3<'py, f32>,
+        patch_size: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> PyResult<NnfArrays<'py>> {
+        if target.as_array().dim().2 != source.as_array().dim().2 {
+            return Err(pyo3::exceptions::PyValueError::new_err("target and source need the same channel count"));
+        }
+        let nnf = synthesis::patchmatch_nnf_f32(target.as_array(), source.as_array(), patch_size, iterations, seed);
+        Ok(nnf_to_py(target.py(), nnf))
     }
 
-    /// Rotate image by specified degrees (90, 180, or 270) (f32).
-    #[pyfunction]
-    pub fn rotate_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-        degrees: u32,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = rotate_mod::rotate_f32(image.as_array(), degrees);
-        result.into_pyarray(py)
+    fn parse_clone_mode(name: &str) -> PyResult<synthesis::CloneMode> {
+        synthesis::CloneMode::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown clone mode '{}'", name)))
     }
 
-    /// Flip image horizontally (mirror left-right) (u8).
+    fn check_clone_shapes(src: (usize, usize, usize), dst: (usize, usize, usize), mask: (usize, usize)) -> PyResult<()> {
+        if src.2 != dst.2 {
+            return Err(pyo3::exceptions::PyValueError::new_err("src and dst need the same channel count"));
+        }
+        if mask != (src.0, src.1) {
+            return Err(pyo3::exceptions::PyValueError::new_err("mask size must match the source"));
+        }
+        Ok(())
+    }
+
+    /// Paste the masked part of `src` into `dst` with Poisson blending (u8).
+    ///
+    /// # Arguments
+    /// * `mask` - (H, W) uint8 mask over `src`, >= 128 marks the pixels to clone
+    /// * `position` - (x, y) of the source's top-left corner in `dst`
+    /// * `mode` - "normal" (source gradients) or "mixed" (stronger of source and destination gradient)
     #[pyfunction]
-    pub fn flip_horizontal<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = rotate_mod::flip_horizontal_u8(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (src, dst, mask, position=(0, 0), mode="normal"))]
+    pub fn seamless_clone<'py>(
+        src: PyHostArray3<'py, u8>,
+        dst: PyHostArray3<'py, u8>,
+        mask: PyHostArray2<'py, u8>,
+        position: (i64, i64),
+        mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let (src_view, dst_view, mask_view) = (src.as_array(), dst.as_array(), mask.as_array());
+        check_clone_shapes(src_view.dim(), dst_view.dim(), mask_view.dim())?;
+        let mode = parse_clone_mode(mode)?;
+        Ok(synthesis::seamless_clone_u8(src_view, dst_view, mask_view, position, mode).into_pyarray(dst.py()))
     }
 
-    /// Flip image horizontally (mirror left-right) (f32).
+    /// Paste the masked part of `src` into `dst` with Poisson blending (f32, mask >= 0.5).
     #[pyfunction]
-    pub fn flip_horizontal_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = rotate_mod::flip_horizontal_f32(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (src, dst, mask, position=(0, 0), mode="normal"))]
+    pub fn seamless_clone_f32<'py>(
+        src: PyHostArray3<'py, f32>,
+        dst: PyHostArray3<'py, f32>,
+        mask: PyHostArray2<'py, f32>,
+        position: (i64, i64),
+        mode: &str,
+    ) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let (src_view, dst_view, mask_view) = (src.as_array(), dst.as_array(), mask.as_array());
+        check_clone_shapes(src_view.dim(), dst_view.dim(), mask_view.dim())?;
+        let mode = parse_clone_mode(mode)?;
+        Ok(synthesis::seamless_clone_f32(src_view, dst_view, mask_view, position, mode).into_pyarray(dst.py()))
     }
 
-    /// Flip image vertically (mirror top-bottom) (u8).
+    // ========================================================================
+    // Seamless Textures
+    // ========================================================================
+
+    fn parse_seamless_method(name: &str) -> PyResult<SeamlessMethod> {
+        SeamlessMethod::from_name(name)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown seamless method '{}'", name)))
+    }
+
+    /// Make an image tile without visible seams (u8).
+    ///
+    /// # Arguments
+    /// * `method` - "offset_blend" or "frequency"
+    /// * `blend_width` - Blend zone in pixels for offset blend (0 = a quarter of the shorter side)
     #[pyfunction]
-    pub fn flip_vertical<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, u8>,
-    ) -> Bound<'py, PyArray3<u8>> {
-        let result = rotate_mod::flip_vertical_u8(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, method="offset_blend", blend_width=0))]
+    pub fn make_seamless<'py>(image: PyHostArray3<'py, u8>, method: &str, blend_width: usize) -> PyResult<Bound<'py, PyArray3<u8>>> {
+        let method = parse_seamless_method(method)?;
+        let view = image.as_array();
+        let blend_width = if blend_width == 0 { view.dim().0.min(view.dim().1) / 4 } else { blend_width };
+        Ok(seamless::make_seamless_u8(view, method, blend_width).into_pyarray(image.py()))
     }
 
-    /// Flip image vertically (mirror top-bottom) (f32).
+    /// Make an image tile without visible seams (f32).
     #[pyfunction]
-    pub fn flip_vertical_f32<'py>(
-        py: Python<'py>,
-        image: PyReadonlyArray3<'py, f32>,
-    ) -> Bound<'py, PyArray3<f32>> {
-        let result = rotate_mod::flip_vertical_f32(image.as_array());
-        result.into_pyarray(py)
+    #[pyo3(signature = (image, method="offset_blend", blend_width=0))]
+    pub fn make_seamless_f32<'py>(image: PyHostArray3<'py, f32>, method: &str, blend_width: usize) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let method = parse_seamless_method(method)?;
+        let view = image.as_array();
+        let blend_width = if blend_width == 0 { view.dim().0.min(view.dim().1) / 4 } else { blend_width };
+        Ok(seamless::make_seamless_f32(view, method, blend_width).into_pyarray(image.py()))
     }
 
     // ========================================================================
@@ -1301,11 +4087,15 @@ mod python {
     /// * `start_y` - Starting Y coordinate
     /// * `tolerance` - Color tolerance (0-255)
     /// * `contiguous` - If true, only selects connected pixels
+    /// * `auto_tolerance` - If true, `tolerance` is ignored and chosen from
+    ///   the colors around the start point (see `magic_wand_auto_tolerance`)
+    /// * `antialias` - If true, pixels along the edge get fractional coverage
     ///
     /// # Returns
-    /// Selection mask (255 = selected, 0 = not selected)
+    /// Selection mask (255 = selected, 0 = not selected, in between on
+    /// anti-aliased edges)
     #[pyfunction]
-    #[pyo3(signature = (image, width, height, start_x, start_y, tolerance=32, contiguous=true))]
+    #[pyo3(signature = (image, width, height, start_x, start_y, tolerance=32, contiguous=true, auto_tolerance=false, antialias=false))]
     pub fn magic_wand_select(
         image: Vec<u8>,
         width: usize,
@@ -1314,8 +4104,144 @@ mod python {
         start_y: usize,
         tolerance: u8,
         contiguous: bool,
+        auto_tolerance: bool,
+        antialias: bool,
     ) -> Vec<u8> {
-        magic_wand_impl(&image, width, height, start_x, start_y, tolerance, contiguous)
+        let tolerance = if auto_tolerance { auto_tolerance_impl(&image, width, height, start_x, start_y) } else { tolerance };
+        if antialias {
+            magic_wand_soft_impl(&image, width, height, start_x, start_y, tolerance, contiguous)
+        } else {
+            magic_wand_impl(&image, width, height, start_x, start_y, tolerance, contiguous)
+        }
+    }
+
+    /// Magic wand tolerance chosen from the colors around the start point.
+    ///
+    /// Otsu's split of the color distances in a 21x21 window around the
+    /// seed: halfway to the nearest differing region, or the spread of the
+    /// window plus a margin when it holds a single region.
+    ///
+    /// # Returns
+    /// Tolerance (0-255)
+    #[pyfunction]
+    pub fn magic_wand_auto_tolerance(image: Vec<u8>, width: usize, height: usize, start_x: usize, start_y: usize) -> u8 {
+        auto_tolerance_impl(&image, width, height, start_x, start_y)
+    }
+
+    /// Distance of every selected pixel to the nearest unselected one.
+    ///
+    /// # Arguments
+    /// * `mask` - Selection mask (>= 128 = selected, flattened row-major)
+    /// * `width` - Mask width
+    /// * `height` - Mask height
+    /// * `metric` - "euclidean", "manhattan" or "chessboard"
+    ///
+    /// # Returns
+    /// Distance in pixels per pixel (0.0 outside the selection)
+    #[pyfunction]
+    #[pyo3(signature = (mask, width, height, metric="euclidean"))]
+    pub fn distance_transform(mask: Vec<u8>, width: usize, height: usize, metric: &str) -> PyResult<Vec<f32>> {
+        let metric = DistanceMetric::from_name(metric)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown distance metric '{}'", metric)))?;
+        Ok(distance_transform_impl(&mask, width, height, metric))
+    }
+
+    /// Marker-based watershed segmentation.
+    ///
+    /// # Arguments
+    /// * `surface` - Levels to flood (flattened row-major), e.g. a gradient
+    ///   magnitude or a negated distance transform
+    /// * `width` - Surface width
+    /// * `height` - Surface height
+    /// * `markers` - Seed labels (0 = unlabeled, > 0 = region label)
+    /// * `mask` - Optional mask (>= 128 = flooded)
+    ///
+    /// # Returns
+    /// Label per pixel (0 = not reached)
+    #[pyfunction]
+    #[pyo3(signature = (surface, width, height, markers, mask=None))]
+    pub fn watershed(
+        surface: Vec<f32>,
+        width: usize,
+        height: usize,
+        markers: Vec<u32>,
+        mask: Option<Vec<u8>>,
+    ) -> Vec<u32> {
+        watershed_impl(&surface, width, height, &markers, mask.as_deref())
+    }
+
+    /// Move the boundary of a selection to nearby strong image edges.
+    ///
+    /// # Arguments
+    /// * `mask` - Selection mask (>= 128 = selected, flattened row-major)
+    /// * `image` - RGBA image data (4 bytes per pixel, flattened)
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `strength` - How far boundaries may move (0.0-1.0 of 16 pixels)
+    ///
+    /// # Returns
+    /// Refined selection mask (255 = selected, 0 = not selected)
+    #[pyfunction]
+    #[pyo3(signature = (mask, image, width, height, strength=0.5))]
+    pub fn snap_mask_to_edges(mask: Vec<u8>, image: Vec<u8>, width: usize, height: usize, strength: f32) -> Vec<u8> {
+        snap_mask_to_edges_impl(&mask, &image, width, height, strength)
+    }
+
+    /// Label the connected regions of a selection mask.
+    ///
+    /// # Arguments
+    /// * `mask` - Selection mask (flattened row-major)
+    /// * `width` - Mask width
+    /// * `height` - Mask height
+    /// * `threshold` - Values >= threshold count as selected
+    /// * `eight_connected` - Whether diagonal neighbors connect regions
+    ///
+    /// # Returns
+    /// Tuple of (labels per pixel with 0 = background, number of regions)
+    #[pyfunction]
+    #[pyo3(signature = (mask, width, height, threshold=128, eight_connected=true))]
+    pub fn label_components(mask: Vec<u8>, width: usize, height: usize, threshold: u8, eight_connected: bool) -> (Vec<u32>, u32) {
+        label_components_impl(&mask, width, height, threshold, eight_connected)
+    }
+
+    /// Drop connected regions of a mask smaller than `min_area` pixels.
+    #[pyfunction]
+    #[pyo3(signature = (mask, width, height, min_area, threshold=128))]
+    pub fn remove_small_components(mask: Vec<u8>, width: usize, height: usize, min_area: usize, threshold: u8) -> Vec<u8> {
+        remove_small_components_impl(&mask, width, height, threshold, min_area)
+    }
+
+    /// (count, coverage percent, (x, y, width, height) or None, (cx, cy) or None)
+    type SummaryTuple = (usize, f32, Option<(usize, usize, usize, usize)>, Option<(f32, f32)>);
+
+    fn summary_tuple(summary: MaskSummary) -> SummaryTuple {
+        let bounds = summary.bounds.map(|r| (r.x, r.y, r.width, r.height));
+        (summary.count, summary.coverage, bounds, summary.centroid)
+    }
+
+    /// Pixel count, coverage, bounding box and centroid of a selection mask.
+    ///
+    /// # Arguments
+    /// * `mask` - Selection mask (flattened row-major)
+    /// * `width` - Mask width
+    /// * `height` - Mask height
+    /// * `threshold` - Values >= threshold count as selected
+    ///
+    /// # Returns
+    /// Tuple of (count, coverage percent, (x, y, w, h) or None, (cx, cy) or None)
+    #[pyfunction]
+    #[pyo3(signature = (mask, width, height, threshold=128))]
+    pub fn mask_summary(mask: Vec<u8>, width: usize, height: usize, threshold: u8) -> SummaryTuple {
+        summary_tuple(mask_summary_impl(&mask, width, height, threshold))
+    }
+
+    /// Summaries of every region of a label map (e.g. from `watershed`).
+    ///
+    /// # Returns
+    /// List of (label, count, coverage, bbox, centroid) for labels > 0, ascending
+    #[pyfunction]
+    pub fn label_summaries(labels: Vec<u32>, width: usize, height: usize) -> Vec<(u32, SummaryTuple)> {
+        label_summaries_impl(&labels, width, height).into_iter().map(|(label, s)| (label, summary_tuple(s))).collect()
     }
 
     /// Extract precise contours from an alpha mask using Marching Squares.
@@ -1331,6 +4257,8 @@ mod python {
     /// * `simplify_epsilon` - Douglas-Peucker epsilon (0 to skip simplification)
     /// * `fit_beziers` - Whether to fit Bezier curves
     /// * `bezier_smoothness` - Smoothness factor for Bezier fitting (0.1-0.5)
+    /// * `downsample` - Trace at 1/downsample resolution and refine against the full mask (1 = off)
+    /// * `tolerance` - Refinement tolerance in pixels when downsampling
     ///
     /// # Returns
     /// List of contours. Each contour is a dict with:
@@ -1338,8 +4266,10 @@ mod python {
     /// - 'is_closed': bool
     /// - 'beziers': Optional list of Bezier segments, each is ((p0x,p0y), (p1x,p1y), (p2x,p2y), (p3x,p3y))
     #[pyfunction]
-    #[pyo3(signature = (mask, width, height, threshold=0.5, simplify_epsilon=1.0, fit_beziers=false, bezier_smoothness=0.25))]
+    #[pyo3(signature = (mask, width, height, threshold=0.5, simplify_epsilon=1.0, fit_beziers=false, bezier_smoothness=0.25, downsample=1, tolerance=0.25))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_contours_precise(
+        py: Python<'_>,
         mask: Vec<u8>,
         width: usize,
         height: usize,
@@ -1347,12 +4277,14 @@ mod python {
         simplify_epsilon: f32,
         fit_beziers: bool,
         bezier_smoothness: f32,
+        downsample: usize,
+        tolerance: f32,
     ) -> Vec<HashMap<String, PyObject>> {
         use pyo3::types::{PyList, PyTuple, PyBool};
 
-        let contours = extract_contours_precise_impl(
-            &mask, width, height, threshold, simplify_epsilon, fit_beziers, bezier_smoothness
-        );
+        let contours = py.allow_threads(|| extract_contours_downsampled_impl(
+            &mask, width, height, threshold, downsample, tolerance, simplify_epsilon, fit_beziers, bezier_smoothness
+        ));
 
         Python::with_gil(|py| {
             contours.iter().map(|contour| {
@@ -1424,6 +4356,126 @@ mod python {
         contours_to_svg_impl(&contours, width, height, fill_color, stroke_color, stroke_width, background_color)
     }
 
+    /// Trace a uint8 image into a multi-color SVG document.
+    ///
+    /// # Arguments
+    /// * `image` - Image array (height, width, channels)
+    /// * `max_colors` - Maximum number of color layers (1-255)
+    /// * `simplify_epsilon` - Douglas-Peucker epsilon (0 to skip)
+    /// * `fit_beziers` - Whether to fit Bezier curves
+    ///
+    /// # Returns
+    /// Complete SVG document as string, one stacked path per color
+    #[pyfunction]
+    #[pyo3(signature = (image, max_colors=8, simplify_epsilon=1.0, fit_beziers=true))]
+    pub fn trace_image_to_svg(
+        image: PyHostArray3<'_, u8>,
+        max_colors: usize,
+        simplify_epsilon: f32,
+        fit_beziers: bool,
+    ) -> String {
+        trace_image_to_svg_impl(image.as_array(), max_colors, simplify_epsilon, fit_beziers)
+    }
+
+    /// Rasterize contours (including holes) into a mask.
+    ///
+    /// # Arguments
+    /// * `contours` - List of contours, each a list of (x, y) tuples
+    /// * `width` - Mask width
+    /// * `height` - Mask height
+    /// * `fill_rule` - "nonzero" or "evenodd"
+    ///
+    /// # Returns
+    /// Mask (255 = inside), flattened row-major
+    #[pyfunction]
+    #[pyo3(signature = (contours, width, height, fill_rule="nonzero"))]
+    pub fn fill_contours(contours: Vec<Vec<(f32, f32)>>, width: usize, height: usize, fill_rule: &str) -> PyResult<Vec<u8>> {
+        let rule = FillRule::from_name(fill_rule)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown fill rule '{}'", fill_rule)))?;
+        let contours: Vec<MarchingContour> = contours
+            .iter()
+            .map(|points| MarchingContour::new(points.iter().map(|&(x, y)| MarchingPoint { x, y }).collect(), true))
+            .collect();
+        Ok(fill_contours_impl(&contours, width, height, rule))
+    }
+
+    /// Contour given from Python as (points, beziers or None).
+    type PyPath = (Vec<(f32, f32)>, Option<Vec<[(f32, f32); 4]>>);
+
+    fn path_from_py((points, beziers): PyPath) -> MarchingContour {
+        let point = |(x, y): (f32, f32)| MarchingPoint { x, y };
+        let mut contour = MarchingContour::new(points.into_iter().map(point).collect(), true);
+        contour.beziers = beziers.map(|segments| {
+            segments.into_iter().map(|[p0, p1, p2, p3]| MarchingBezier::new(point(p0), point(p1), point(p2), point(p3))).collect()
+        });
+        contour
+    }
+
+    /// Combine two sets of closed contours with a boolean operation.
+    ///
+    /// # Arguments
+    /// * `subject` - Contours as (points, beziers) tuples; beziers is None or a list of
+    ///   ((p0x,p0y), (p1x,p1y), (p2x,p2y), (p3x,p3y)) and replaces the points when given
+    /// * `clip` - Contours of the second operand, same format
+    /// * `op` - "union", "intersect" or "subtract" (subject minus clip)
+    /// * `fill_rule` - "nonzero" or "evenodd"
+    /// * `tolerance` - Bezier flattening tolerance in pixels
+    ///
+    /// # Returns
+    /// Closed result contours as lists of (x, y) tuples (holes reversed)
+    #[pyfunction]
+    #[pyo3(signature = (subject, clip, op="union", fill_rule="nonzero", tolerance=0.1))]
+    pub fn path_boolean(
+        py: Python<'_>,
+        subject: Vec<PyPath>,
+        clip: Vec<PyPath>,
+        op: &str,
+        fill_rule: &str,
+        tolerance: f32,
+    ) -> PyResult<Vec<Vec<(f32, f32)>>> {
+        let op = PathOp::from_name(op)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown path operation '{}'", op)))?;
+        let rule = FillRule::from_name(fill_rule)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown fill rule '{}'", fill_rule)))?;
+        let subject: Vec<MarchingContour> = subject.into_iter().map(path_from_py).collect();
+        let clip: Vec<MarchingContour> = clip.into_iter().map(path_from_py).collect();
+        let result = py.allow_threads(|| path_boolean_impl(&subject, &clip, op, rule, tolerance));
+        Ok(result.iter().map(|c| c.points.iter().map(|p| (p.x, p.y)).collect()).collect())
+    }
+
+    /// Convert a stroked path into the filled outline of its stroke.
+    ///
+    /// # Arguments
+    /// * `path` - (points, beziers) tuple as for `path_boolean`
+    /// * `width` - Stroke width in pixels
+    /// * `cap` - "butt", "round" or "square"
+    /// * `join` - "miter", "round" or "bevel"
+    /// * `miter_limit` - Largest miter length over stroke width before beveling
+    /// * `closed` - Whether the path is closed (joins instead of caps at its ends)
+    ///
+    /// # Returns
+    /// Closed outline contours as lists of (x, y) tuples (holes reversed)
+    #[pyfunction]
+    #[pyo3(signature = (path, width, cap="butt", join="miter", miter_limit=4.0, closed=false))]
+    pub fn stroke_path_outline(
+        py: Python<'_>,
+        path: PyPath,
+        width: f32,
+        cap: &str,
+        join: &str,
+        miter_limit: f32,
+        closed: bool,
+    ) -> PyResult<Vec<Vec<(f32, f32)>>> {
+        let line_cap = LineCap::from_name(cap)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown line cap '{}'", cap)))?;
+        let line_join = LineJoin::from_name(join)
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("unknown line join '{}'", join)))?;
+        let mut contour = path_from_py(path);
+        contour.is_closed = closed;
+        let result = py.allow_threads(|| stroke_path_outline_impl(&contour, width, line_cap, line_join, miter_limit));
+        Ok(result.iter().map(|c| c.points.iter().map(|p| (p.x, p.y)).collect()).collect())
+    }
+
     /// Simplify a polyline using the Douglas-Peucker algorithm.
     ///
     /// # Arguments
@@ -1471,6 +4523,31 @@ mod python {
         simplified.iter().map(|p| (p.x, p.y)).collect()
     }
 
+    /// Split a contour into marching ants dash polylines.
+    ///
+    /// # Arguments
+    /// * `contour` - List of (x, y) tuples
+    /// * `dash_length` - Length of each dash and gap in pixels
+    /// * `phase` - Pattern offset in pixels; advance it to animate
+    /// * `closed` - Whether the contour is a closed polygon
+    ///
+    /// # Returns
+    /// List of dashes, each a list of (x, y) tuples
+    #[pyfunction]
+    #[pyo3(signature = (contour, dash_length=4.0, phase=0.0, closed=true))]
+    pub fn contour_to_dash_segments(
+        contour: Vec<(f32, f32)>,
+        dash_length: f32,
+        phase: f32,
+        closed: bool,
+    ) -> Vec<Vec<(f32, f32)>> {
+        let points = contour.iter().map(|&(x, y)| MarchingPoint { x, y }).collect();
+        contour_to_dash_segments_impl(&MarchingContour::new(points, closed), dash_length, phase)
+            .iter()
+            .map(|dash| dash.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
     /// ImageStag Rust extension module
     #[pymodule]
     pub fn imagestag_rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -1480,23 +4557,53 @@ mod python {
         m.add_function(wrap_pyfunction!(premultiply_alpha, m)?)?;
         m.add_function(wrap_pyfunction!(unpremultiply_alpha, m)?)?;
 
+        // Channel split / merge / swizzle (u8 and f32)
+        m.add_function(wrap_pyfunction!(split_channels_py, m)?)?;
+        m.add_function(wrap_pyfunction!(split_channels_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(merge_channels_py, m)?)?;
+        m.add_function(wrap_pyfunction!(merge_channels_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(swap_channels_py, m)?)?;
+        m.add_function(wrap_pyfunction!(swap_channels_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(set_channel_py, m)?)?;
+        m.add_function(wrap_pyfunction!(set_channel_f32_py, m)?)?;
+
+        // Alpha masks (u8 and f32)
+        m.add_function(wrap_pyfunction!(alpha_from_luminance_py, m)?)?;
+        m.add_function(wrap_pyfunction!(alpha_from_luminance_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(threshold_alpha_py, m)?)?;
+        m.add_function(wrap_pyfunction!(threshold_alpha_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(color_to_alpha_py, m)?)?;
+        m.add_function(wrap_pyfunction!(color_to_alpha_f32_py, m)?)?;
+
+        // Image arithmetic (u8 and f32)
+        m.add_function(wrap_pyfunction!(image_arithmetic_py, m)?)?;
+        m.add_function(wrap_pyfunction!(image_arithmetic_f32_py, m)?)?;
+
         // Grayscale filter (u8 and f32)
         m.add_function(wrap_pyfunction!(grayscale_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(grayscale_rgba_f32, m)?)?;
         m.add_function(wrap_pyfunction!(grayscale_weighted, m)?)?;
         m.add_function(wrap_pyfunction!(grayscale_weighted_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(grayscale_to_single_channel_py, m)?)?;
+        m.add_function(wrap_pyfunction!(grayscale_to_single_channel_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(grayscale_to_single_channel_weighted_py, m)?)?;
+        m.add_function(wrap_pyfunction!(grayscale_to_single_channel_weighted_f32_py, m)?)?;
 
         // Conversion utilities
         m.add_function(wrap_pyfunction!(convert_u8_to_f32, m)?)?;
         m.add_function(wrap_pyfunction!(convert_f32_to_u8, m)?)?;
         m.add_function(wrap_pyfunction!(convert_f32_to_12bit, m)?)?;
         m.add_function(wrap_pyfunction!(convert_12bit_to_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(pack_12bit, m)?)?;
+        m.add_function(wrap_pyfunction!(unpack_12bit, m)?)?;
 
         // Color adjustment filters
         m.add_function(wrap_pyfunction!(brightness, m)?)?;
         m.add_function(wrap_pyfunction!(brightness_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(brightness_12bit, m)?)?;
         m.add_function(wrap_pyfunction!(contrast, m)?)?;
         m.add_function(wrap_pyfunction!(contrast_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(contrast_12bit, m)?)?;
         m.add_function(wrap_pyfunction!(saturation, m)?)?;
         m.add_function(wrap_pyfunction!(saturation_f32, m)?)?;
         m.add_function(wrap_pyfunction!(gamma, m)?)?;
@@ -1509,6 +4616,8 @@ mod python {
         // Color science filters
         m.add_function(wrap_pyfunction!(hue_shift, m)?)?;
         m.add_function(wrap_pyfunction!(hue_shift_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(hsl_adjust, m)?)?;
+        m.add_function(wrap_pyfunction!(hsl_adjust_f32, m)?)?;
         m.add_function(wrap_pyfunction!(vibrance, m)?)?;
         m.add_function(wrap_pyfunction!(vibrance_f32, m)?)?;
         m.add_function(wrap_pyfunction!(color_balance, m)?)?;
@@ -1521,18 +4630,32 @@ mod python {
         m.add_function(wrap_pyfunction!(solarize_f32, m)?)?;
         m.add_function(wrap_pyfunction!(threshold, m)?)?;
         m.add_function(wrap_pyfunction!(threshold_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(threshold_otsu, m)?)?;
+        m.add_function(wrap_pyfunction!(threshold_otsu_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(adaptive_threshold, m)?)?;
+        m.add_function(wrap_pyfunction!(adaptive_threshold_f32, m)?)?;
         m.add_function(wrap_pyfunction!(emboss, m)?)?;
         m.add_function(wrap_pyfunction!(emboss_f32, m)?)?;
         m.add_function(wrap_pyfunction!(pencil_sketch, m)?)?;
         m.add_function(wrap_pyfunction!(pencil_sketch_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(xdog, m)?)?;
+        m.add_function(wrap_pyfunction!(xdog_f32, m)?)?;
 
         // Levels & curves filters
         m.add_function(wrap_pyfunction!(levels, m)?)?;
         m.add_function(wrap_pyfunction!(levels_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(levels_12bit, m)?)?;
         m.add_function(wrap_pyfunction!(curves, m)?)?;
         m.add_function(wrap_pyfunction!(curves_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(curves_12bit, m)?)?;
         m.add_function(wrap_pyfunction!(auto_levels, m)?)?;
         m.add_function(wrap_pyfunction!(auto_levels_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_contrast, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_contrast_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_tone, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_tone_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_color, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_color_f32, m)?)?;
 
         // Sharpen filters
         m.add_function(wrap_pyfunction!(sharpen, m)?)?;
@@ -1541,8 +4664,16 @@ mod python {
         m.add_function(wrap_pyfunction!(unsharp_mask_f32, m)?)?;
         m.add_function(wrap_pyfunction!(high_pass, m)?)?;
         m.add_function(wrap_pyfunction!(high_pass_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(clarity, m)?)?;
+        m.add_function(wrap_pyfunction!(clarity_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(texture, m)?)?;
+        m.add_function(wrap_pyfunction!(texture_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(smart_sharpen, m)?)?;
+        m.add_function(wrap_pyfunction!(smart_sharpen_f32, m)?)?;
         m.add_function(wrap_pyfunction!(motion_blur, m)?)?;
         m.add_function(wrap_pyfunction!(motion_blur_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(spin_blur, m)?)?;
+        m.add_function(wrap_pyfunction!(spin_blur_f32, m)?)?;
 
         // Edge detection filters
         m.add_function(wrap_pyfunction!(sobel, m)?)?;
@@ -1551,14 +4682,39 @@ mod python {
         m.add_function(wrap_pyfunction!(laplacian_f32, m)?)?;
         m.add_function(wrap_pyfunction!(find_edges, m)?)?;
         m.add_function(wrap_pyfunction!(find_edges_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(dog, m)?)?;
+        m.add_function(wrap_pyfunction!(dog_f32, m)?)?;
         m.add_function(wrap_pyfunction!(draw_contours, m)?)?;
         m.add_function(wrap_pyfunction!(draw_contours_f32, m)?)?;
 
+        // Convolution
+        m.add_function(wrap_pyfunction!(sanitize_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(validate_image, m)?)?;
+        m.add_function(wrap_pyfunction!(validate_image_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(convolve2d, m)?)?;
+        m.add_function(wrap_pyfunction!(convolve2d_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(convolve_separable, m)?)?;
+        m.add_function(wrap_pyfunction!(convolve_separable_f32, m)?)?;
+
+        // Integral images
+        m.add_function(wrap_pyfunction!(integral_image, m)?)?;
+        m.add_function(wrap_pyfunction!(integral_image_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(box_filter_via_integral, m)?)?;
+        m.add_function(wrap_pyfunction!(box_filter_via_integral_f32, m)?)?;
+
         // Noise filters
         m.add_function(wrap_pyfunction!(add_noise, m)?)?;
         m.add_function(wrap_pyfunction!(add_noise_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(add_noise_hashed, m)?)?;
+        m.add_function(wrap_pyfunction!(add_noise_hashed_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(film_grain, m)?)?;
+        m.add_function(wrap_pyfunction!(film_grain_f32, m)?)?;
         m.add_function(wrap_pyfunction!(median, m)?)?;
         m.add_function(wrap_pyfunction!(median_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(remove_hot_pixels, m)?)?;
+        m.add_function(wrap_pyfunction!(remove_hot_pixels_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(surface_blur, m)?)?;
+        m.add_function(wrap_pyfunction!(surface_blur_f32, m)?)?;
         m.add_function(wrap_pyfunction!(denoise, m)?)?;
         m.add_function(wrap_pyfunction!(denoise_f32, m)?)?;
 
@@ -1567,8 +4723,24 @@ mod python {
         m.add_function(wrap_pyfunction!(sepia_f32, m)?)?;
         m.add_function(wrap_pyfunction!(temperature, m)?)?;
         m.add_function(wrap_pyfunction!(temperature_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(white_balance_kelvin, m)?)?;
+        m.add_function(wrap_pyfunction!(white_balance_kelvin_f32, m)?)?;
         m.add_function(wrap_pyfunction!(channel_mixer, m)?)?;
         m.add_function(wrap_pyfunction!(channel_mixer_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(match_color, m)?)?;
+        m.add_function(wrap_pyfunction!(match_color_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(black_white, m)?)?;
+        m.add_function(wrap_pyfunction!(black_white_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(replace_color, m)?)?;
+        m.add_function(wrap_pyfunction!(replace_color_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(split_tone, m)?)?;
+        m.add_function(wrap_pyfunction!(split_tone_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(duotone, m)?)?;
+        m.add_function(wrap_pyfunction!(duotone_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(simulate_cvd, m)?)?;
+        m.add_function(wrap_pyfunction!(simulate_cvd_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(daltonize, m)?)?;
+        m.add_function(wrap_pyfunction!(daltonize_f32, m)?)?;
 
         // Color adjustment - new filters
         m.add_function(wrap_pyfunction!(equalize_histogram, m)?)?;
@@ -1609,6 +4781,137 @@ mod python {
         m.add_function(wrap_pyfunction!(flip_horizontal_f32, m)?)?;
         m.add_function(wrap_pyfunction!(flip_vertical, m)?)?;
         m.add_function(wrap_pyfunction!(flip_vertical_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(transform_strided, m)?)?;
+
+        // Thumbnails
+        m.add_function(wrap_pyfunction!(thumbnail, m)?)?;
+        m.add_function(wrap_pyfunction!(thumbnail_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(thumbnail_batch, m)?)?;
+
+        // Straightening
+        m.add_function(wrap_pyfunction!(estimate_skew_angle, m)?)?;
+        m.add_function(wrap_pyfunction!(estimate_skew_angle_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(rotate_bicubic, m)?)?;
+        m.add_function(wrap_pyfunction!(rotate_bicubic_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(rotate_crop, m)?)?;
+        m.add_function(wrap_pyfunction!(rotate_crop_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_straighten, m)?)?;
+        m.add_function(wrap_pyfunction!(auto_straighten_f32, m)?)?;
+
+        // Perspective rectification
+        m.add_function(wrap_pyfunction!(rectify_quad, m)?)?;
+        m.add_function(wrap_pyfunction!(rectify_quad_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(detect_document_quad, m)?)?;
+        m.add_function(wrap_pyfunction!(detect_document_quad_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(map_to_quad, m)?)?;
+        m.add_function(wrap_pyfunction!(map_to_quad_f32, m)?)?;
+
+        // Matting
+        m.add_function(wrap_pyfunction!(chroma_key, m)?)?;
+        m.add_function(wrap_pyfunction!(chroma_key_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(alpha_matting, m)?)?;
+        m.add_function(wrap_pyfunction!(alpha_matting_f32, m)?)?;
+
+        // Glitch
+        m.add_function(wrap_pyfunction!(rgb_split, m)?)?;
+        m.add_function(wrap_pyfunction!(rgb_split_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(scanlines, m)?)?;
+        m.add_function(wrap_pyfunction!(scanlines_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(block_shift, m)?)?;
+        m.add_function(wrap_pyfunction!(block_shift_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(wave_distort, m)?)?;
+        m.add_function(wrap_pyfunction!(wave_distort_f32, m)?)?;
+
+        // Material maps
+        m.add_function(wrap_pyfunction!(height_to_normal, m)?)?;
+        m.add_function(wrap_pyfunction!(height_to_normal_f32, m)?)?;
+
+        // Wavelet filters
+        m.add_function(wrap_pyfunction!(wavelet_sharpen, m)?)?;
+        m.add_function(wrap_pyfunction!(wavelet_sharpen_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(wavelet_denoise, m)?)?;
+        m.add_function(wrap_pyfunction!(wavelet_denoise_f32, m)?)?;
+
+        // Tone mapping
+        m.add_function(wrap_pyfunction!(tonemap, m)?)?;
+
+        // Transfer functions
+        m.add_function(wrap_pyfunction!(decode_transfer, m)?)?;
+        m.add_function(wrap_pyfunction!(encode_transfer, m)?)?;
+
+        // Compositing
+        m.add_function(wrap_pyfunction!(blend_images, m)?)?;
+        m.add_function(wrap_pyfunction!(blend_images_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(composite_on_checkerboard, m)?)?;
+        m.add_function(wrap_pyfunction!(composite_on_checkerboard_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(composite_on_color, m)?)?;
+        m.add_function(wrap_pyfunction!(composite_on_color_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(make_anaglyph, m)?)?;
+        m.add_function(wrap_pyfunction!(make_anaglyph_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(make_sbs, m)?)?;
+        m.add_function(wrap_pyfunction!(make_sbs_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(onion_skin, m)?)?;
+        m.add_function(wrap_pyfunction!(onion_skin_f32, m)?)?;
+
+        // RAW
+        m.add_function(wrap_pyfunction!(raw_to_linear, m)?)?;
+        m.add_function(wrap_pyfunction!(demosaic, m)?)?;
+
+        // Deband
+        m.add_function(wrap_pyfunction!(deband, m)?)?;
+        m.add_function(wrap_pyfunction!(deband_f32, m)?)?;
+
+        // FFT
+        m.add_function(wrap_pyfunction!(fft_forward, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_inverse, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_lowpass, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_lowpass_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_highpass, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_highpass_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_notch_filter, m)?)?;
+        m.add_function(wrap_pyfunction!(fft_notch_filter_f32, m)?)?;
+
+        // Projection
+        m.add_function(wrap_pyfunction!(equirect_to_cubemap, m)?)?;
+        m.add_function(wrap_pyfunction!(equirect_to_cubemap_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(cubemap_to_equirect, m)?)?;
+        m.add_function(wrap_pyfunction!(cubemap_to_equirect_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(reproject_equirect, m)?)?;
+        m.add_function(wrap_pyfunction!(reproject_equirect_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(little_planet, m)?)?;
+        m.add_function(wrap_pyfunction!(little_planet_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(polar_coordinates, m)?)?;
+        m.add_function(wrap_pyfunction!(polar_coordinates_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(twirl, m)?)?;
+        m.add_function(wrap_pyfunction!(twirl_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(spherize, m)?)?;
+        m.add_function(wrap_pyfunction!(spherize_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(pinch, m)?)?;
+        m.add_function(wrap_pyfunction!(pinch_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(wave, m)?)?;
+        m.add_function(wrap_pyfunction!(wave_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(kaleidoscope, m)?)?;
+        m.add_function(wrap_pyfunction!(kaleidoscope_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(mirror, m)?)?;
+        m.add_function(wrap_pyfunction!(mirror_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(trim, m)?)?;
+        m.add_function(wrap_pyfunction!(trim_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(alpha_bbox, m)?)?;
+        m.add_function(wrap_pyfunction!(alpha_bbox_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(opaque_bbox, m)?)?;
+        m.add_function(wrap_pyfunction!(opaque_bbox_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(extend_canvas_fill, m)?)?;
+        m.add_function(wrap_pyfunction!(extend_canvas_fill_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(fill_region_patchmatch, m)?)?;
+        m.add_function(wrap_pyfunction!(fill_region_patchmatch_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(synthesize_texture, m)?)?;
+        m.add_function(wrap_pyfunction!(synthesize_texture_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(patchmatch_nnf, m)?)?;
+        m.add_function(wrap_pyfunction!(patchmatch_nnf_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(seamless_clone, m)?)?;
+        m.add_function(wrap_pyfunction!(seamless_clone_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(make_seamless, m)?)?;
+        m.add_function(wrap_pyfunction!(make_seamless_f32, m)?)?;
 
         // Blur filters
         m.add_function(wrap_pyfunction!(gaussian_blur_rgba, m)?)?;
@@ -1619,14 +4922,23 @@ mod python {
         // Layer effects (each in its own module)
         m.add_function(wrap_pyfunction!(drop_shadow_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(drop_shadow_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(drop_shadow_expansion, m)?)?;
         m.add_function(wrap_pyfunction!(drop_shadow_only_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(drop_shadow_only_rgba_f32, m)?)?;
         m.add_function(wrap_pyfunction!(inner_shadow_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(inner_shadow_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(cast_shadow_rgba, m)?)?;
+        m.add_function(wrap_pyfunction!(cast_shadow_rgba_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(cast_shadow_expansion, m)?)?;
+        m.add_function(wrap_pyfunction!(long_shadow_rgba, m)?)?;
+        m.add_function(wrap_pyfunction!(long_shadow_rgba_f32_py, m)?)?;
         m.add_function(wrap_pyfunction!(outer_glow_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(outer_glow_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(outer_glow_expansion, m)?)?;
         m.add_function(wrap_pyfunction!(outer_glow_only_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(outer_glow_only_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(outer_glow_gradient_rgba, m)?)?;
+        m.add_function(wrap_pyfunction!(outer_glow_gradient_rgba_f32, m)?)?;
         m.add_function(wrap_pyfunction!(inner_glow_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(inner_glow_rgba_f32, m)?)?;
         m.add_function(wrap_pyfunction!(inner_glow_only_rgba, m)?)?;
@@ -1640,21 +4952,185 @@ mod python {
         m.add_function(wrap_pyfunction!(gradient_overlay_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(gradient_overlay_rgba_f32, m)?)?;
         m.add_function(wrap_pyfunction!(generate_gradient, m)?)?;
-        m.add_function(wrap_pyfunction!(generate_gradient_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(generate_gradient_f32_py, m)?)?;
         m.add_function(wrap_pyfunction!(pattern_overlay_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(pattern_overlay_rgba_f32, m)?)?;
         m.add_function(wrap_pyfunction!(stroke_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(stroke_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(stroke_expansion, m)?)?;
         m.add_function(wrap_pyfunction!(stroke_only_rgba, m)?)?;
         m.add_function(wrap_pyfunction!(stroke_only_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(psd_layer_style_rgba, m)?)?;
+        m.add_function(wrap_pyfunction!(psd_layer_style_rgba_f32, m)?)?;
+        m.add_function(wrap_pyfunction!(asl_style_names, m)?)?;
+        m.add_function(wrap_pyfunction!(asl_layer_style_rgba, m)?)?;
+        m.add_function(wrap_pyfunction!(asl_layer_style_rgba_f32, m)?)?;
 
         // Selection algorithms
         m.add_function(wrap_pyfunction!(extract_contours, m)?)?;
         m.add_function(wrap_pyfunction!(magic_wand_select, m)?)?;
+        m.add_function(wrap_pyfunction!(magic_wand_auto_tolerance, m)?)?;
+        m.add_function(wrap_pyfunction!(distance_transform, m)?)?;
+        m.add_function(wrap_pyfunction!(watershed, m)?)?;
+        m.add_function(wrap_pyfunction!(snap_mask_to_edges, m)?)?;
+        m.add_function(wrap_pyfunction!(label_components, m)?)?;
+        m.add_function(wrap_pyfunction!(remove_small_components, m)?)?;
+        m.add_function(wrap_pyfunction!(mask_summary, m)?)?;
+        m.add_function(wrap_pyfunction!(label_summaries, m)?)?;
         m.add_function(wrap_pyfunction!(extract_contours_precise, m)?)?;
         m.add_function(wrap_pyfunction!(contours_to_svg, m)?)?;
+        m.add_function(wrap_pyfunction!(trace_image_to_svg, m)?)?;
+        m.add_function(wrap_pyfunction!(fill_contours, m)?)?;
+        m.add_function(wrap_pyfunction!(path_boolean, m)?)?;
+        m.add_function(wrap_pyfunction!(stroke_path_outline, m)?)?;
         m.add_function(wrap_pyfunction!(douglas_peucker, m)?)?;
         m.add_function(wrap_pyfunction!(douglas_peucker_closed, m)?)?;
+        m.add_function(wrap_pyfunction!(contour_to_dash_segments, m)?)?;
+
+        // Sprite sheets
+        m.add_function(wrap_pyfunction!(pack_sprites_py, m)?)?;
+        m.add_function(wrap_pyfunction!(pack_sprites_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(slice_grid_py, m)?)?;
+        m.add_function(wrap_pyfunction!(slice_grid_f32_py, m)?)?;
+
+        // Painting
+        m.add_function(wrap_pyfunction!(replicate_symmetric_py, m)?)?;
+        m.add_function(wrap_pyfunction!(replicate_symmetric_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(interpolate_stroke_py, m)?)?;
+        m.add_function(wrap_pyfunction!(bucket_fill_py, m)?)?;
+        m.add_function(wrap_pyfunction!(bucket_fill_layer_py, m)?)?;
+
+        // Feature detection
+        m.add_function(wrap_pyfunction!(harris_corners_py, m)?)?;
+        m.add_function(wrap_pyfunction!(harris_corners_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(fast_corners_py, m)?)?;
+        m.add_function(wrap_pyfunction!(fast_corners_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(orb_features_py, m)?)?;
+        m.add_function(wrap_pyfunction!(orb_features_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(match_descriptors_py, m)?)?;
+
+        // Superpixels
+        m.add_function(wrap_pyfunction!(slic_py, m)?)?;
+        m.add_function(wrap_pyfunction!(slic_f32_py, m)?)?;
+
+        // Motion masks
+        m.add_function(wrap_pyfunction!(motion_mask_py, m)?)?;
+        m.add_function(wrap_pyfunction!(motion_mask_f32_py, m)?)?;
+
+        // Optical flow
+        m.add_function(wrap_pyfunction!(optical_flow_py, m)?)?;
+        m.add_function(wrap_pyfunction!(optical_flow_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(warp_by_flow_py, m)?)?;
+        m.add_function(wrap_pyfunction!(warp_by_flow_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(interpolate_frame_py, m)?)?;
+        m.add_function(wrap_pyfunction!(interpolate_frame_f32_py, m)?)?;
+
+        // Palettes
+        m.add_function(wrap_pyfunction!(extract_palette_py, m)?)?;
+        m.add_function(wrap_pyfunction!(posterize_to_palette_py, m)?)?;
+
+        // Skin masks
+        m.add_function(wrap_pyfunction!(skin_mask_py, m)?)?;
+        m.add_function(wrap_pyfunction!(skin_mask_f32_py, m)?)?;
+
+        // Noise estimation
+        m.add_function(wrap_pyfunction!(estimate_noise_sigma_py, m)?)?;
+        m.add_function(wrap_pyfunction!(estimate_noise_sigma_f32_py, m)?)?;
+
+        // Focus measures
+        m.add_function(wrap_pyfunction!(estimate_blur_py, m)?)?;
+        m.add_function(wrap_pyfunction!(estimate_blur_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(sharpness_map_py, m)?)?;
+        m.add_function(wrap_pyfunction!(sharpness_map_f32_py, m)?)?;
+
+        // Perceptual hashes
+        m.add_function(wrap_pyfunction!(ahash_py, m)?)?;
+        m.add_function(wrap_pyfunction!(ahash_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(dhash_py, m)?)?;
+        m.add_function(wrap_pyfunction!(dhash_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(phash_py, m)?)?;
+        m.add_function(wrap_pyfunction!(phash_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(hamming_distance_py, m)?)?;
+
+        // Text contrast
+        m.add_function(wrap_pyfunction!(contrast_ratio_py, m)?)?;
+        m.add_function(wrap_pyfunction!(contrast_ratio_map_py, m)?)?;
+        m.add_function(wrap_pyfunction!(contrast_ratio_map_f32_py, m)?)?;
+
+        // QR codes
+        m.add_function(wrap_pyfunction!(render_qr_py, m)?)?;
+
+        // Color sampling
+        m.add_function(wrap_pyfunction!(average_color_py, m)?)?;
+        m.add_function(wrap_pyfunction!(average_color_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(sample_color_py, m)?)?;
+        m.add_function(wrap_pyfunction!(sample_color_f32_py, m)?)?;
+
+        // Masked histograms and statistics
+        m.add_function(wrap_pyfunction!(histogram_masked_py, m)?)?;
+        m.add_function(wrap_pyfunction!(histogram_masked_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(stats_masked_py, m)?)?;
+        m.add_function(wrap_pyfunction!(stats_masked_f32_py, m)?)?;
+
+        // Batch processing
+        m.add_function(wrap_pyfunction!(batch_filter_py, m)?)?;
+        m.add_function(wrap_pyfunction!(batch_filter_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(to_host_array_py, m)?)?;
+        m.add_function(wrap_pyfunction!(filter_registry_py, m)?)?;
+        m.add_function(wrap_pyfunction!(apply_filter_py, m)?)?;
+        m.add_function(wrap_pyfunction!(apply_filter_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(apply_recipe_py, m)?)?;
+        m.add_function(wrap_pyfunction!(apply_recipe_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(validate_recipe_py, m)?)?;
+        m.add_function(wrap_pyfunction!(recipe_to_cbor_py, m)?)?;
+        m.add_function(wrap_pyfunction!(apply_in_rect_py, m)?)?;
+        m.add_function(wrap_pyfunction!(apply_in_rect_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(preview_adjustment_py, m)?)?;
+        m.add_function(wrap_pyfunction!(preview_adjustment_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(render_export_py, m)?)?;
+        m.add_function(wrap_pyfunction!(render_proxy_py, m)?)?;
+        m.add_function(wrap_pyfunction!(plan_operation_py, m)?)?;
+        m.add_class::<PyFilterContext>()?;
+        m.add("CancelledError", m.py().get_type::<CancelledError>())?;
+        m.add("DETERMINISTIC", crate::filters::fmath::DETERMINISTIC)?;
+        m.add("PROFILING", crate::profiling::PROFILING)?;
+        m.add_function(wrap_pyfunction!(get_last_perf_report_py, m)?)?;
+        m.add_function(wrap_pyfunction!(release_buffer_pool_py, m)?)?;
+
+        // GPU backend (CPU fallback without the gpu feature)
+        m.add_function(wrap_pyfunction!(gpu_available_py, m)?)?;
+        m.add_function(wrap_pyfunction!(gpu_gaussian_blur_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(gpu_convolve_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(gpu_levels_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(gpu_curves_f32_py, m)?)?;
+        m.add_function(wrap_pyfunction!(gpu_blend_f32_py, m)?)?;
+
+        // Image encode/decode
+        #[cfg(feature = "encode")]
+        {
+            m.add_function(wrap_pyfunction!(decode_image_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_png_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_jpeg_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_webp_py, m)?)?;
+            m.add_function(wrap_pyfunction!(decode_image_u16_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_qoi_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_tiff_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_tiff_u16_py, m)?)?;
+            m.add_function(wrap_pyfunction!(read_exif_py, m)?)?;
+            m.add_function(wrap_pyfunction!(apply_exif_orientation_py, m)?)?;
+            m.add_function(wrap_pyfunction!(apply_exif_orientation_f32_py, m)?)?;
+            m.add_function(wrap_pyfunction!(read_icc_profile_py, m)?)?;
+            m.add_function(wrap_pyfunction!(icc_profile_info, m)?)?;
+            m.add_function(wrap_pyfunction!(icc_to_srgb, m)?)?;
+            m.add_function(wrap_pyfunction!(icc_to_srgb_f32, m)?)?;
+            m.add_function(wrap_pyfunction!(icc_from_srgb, m)?)?;
+            m.add_function(wrap_pyfunction!(icc_from_srgb_f32, m)?)?;
+            m.add_function(wrap_pyfunction!(soft_proof_py, m)?)?;
+            m.add_function(wrap_pyfunction!(soft_proof_f32_py, m)?)?;
+            m.add_function(wrap_pyfunction!(quantize_image, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_gif_py, m)?)?;
+            m.add_function(wrap_pyfunction!(encode_apng_py, m)?)?;
+        }
 
         Ok(())
     }
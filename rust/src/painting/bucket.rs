@@ -0,0 +1,308 @@
+//! Paint bucket fill.
+//!
+//! [`bucket_fill`] selects the region around a seed pixel with the magic
+//! wand flood fill (optionally with its anti-aliased edge) and paints it
+//! with a [`FillSource`]: a flat color, a gradient stretched over the
+//! bounds of the filled region, or a pattern tiled from the canvas origin
+//! so neighboring fills line up. [`bucket_fill_layer`] returns the paint
+//! as a separate layer instead of compositing it onto the image.
+
+use ndarray::{Array3, ArrayView3};
+
+use crate::filters::core::blend_over_u8;
+use crate::layer_effects::gradient_overlay::{gradient_fill, GradientOverlayParams};
+use crate::layer_effects::pattern_overlay::{pattern_fill, PatternOverlayParams};
+use crate::selection::magic_wand::{magic_wand_select, magic_wand_select_soft};
+use crate::selection::stats::mask_summary;
+
+#[cfg(feature = "python")]
+use crate::layer_effects::gradient_overlay::parse_stops;
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3, PyReadonlyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// What a bucket fill paints with.
+///
+/// The `opacity` of gradient and pattern parameters scales the paint's
+/// alpha; their blend mode is not used.
+#[derive(Debug, Clone)]
+pub enum FillSource {
+    /// Flat RGBA color
+    Solid([u8; 4]),
+    /// Gradient spanning the bounds of the filled region
+    Gradient(GradientOverlayParams),
+    /// Pattern tiled from the canvas origin
+    Pattern(PatternOverlayParams),
+}
+
+/// Render the paint of a bucket fill as an RGBA layer.
+///
+/// # Arguments
+/// * `image` - RGBA image (height, width, 4)
+/// * `x`, `y` - Seed pixel
+/// * `source` - Color, gradient or pattern to paint
+/// * `tolerance` - Color tolerance of the flood fill (0-255)
+/// * `contiguous` - Fill only pixels connected to the seed
+/// * `antialias` - Give edge pixels fractional coverage
+///
+/// # Returns
+/// RGBA layer (height, width, 4), transparent outside the filled region
+///
+/// # Panics
+/// If the image is not RGBA.
+pub fn bucket_fill_layer(
+    image: ArrayView3<u8>,
+    x: usize,
+    y: usize,
+    source: &FillSource,
+    tolerance: u8,
+    contiguous: bool,
+    antialias: bool,
+) -> Array3<u8> {
+    let (height, width, channels) = image.dim();
+    assert_eq!(channels, 4, "bucket fill needs an RGBA image");
+    let mut layer = Array3::<u8>::zeros((height, width, 4));
+    let pixels = image.as_standard_layout();
+    let data = pixels.as_slice().expect("standard layout");
+    let mask = if antialias {
+        magic_wand_select_soft(data, width, height, x, y, tolerance, contiguous)
+    } else {
+        magic_wand_select(data, width, height, x, y, tolerance, contiguous)
+    };
+    let Some(bounds) = mask_summary(&mask, width, height, 1).bounds else {
+        return layer;
+    };
+
+    // Paint color of a canvas pixel, 0.0-1.0
+    let gradient = match source {
+        FillSource::Gradient(params) => Some(gradient_fill(bounds.width, bounds.height, params)),
+        _ => None,
+    };
+    let pattern = match source {
+        FillSource::Pattern(params) => Some(pattern_fill(width, height, params)),
+        _ => None,
+    };
+    let paint = |px: usize, py: usize| -> [f32; 4] {
+        match source {
+            FillSource::Solid(color) => color.map(|v| v as f32 / 255.0),
+            FillSource::Gradient(params) => {
+                let g = gradient.as_ref().unwrap();
+                let (gx, gy) = (px - bounds.x, py - bounds.y);
+                [g[[gy, gx, 0]], g[[gy, gx, 1]], g[[gy, gx, 2]], params.opacity]
+            }
+            FillSource::Pattern(params) => {
+                let p = pattern.as_ref().unwrap();
+                [p[[py, px, 0]], p[[py, px, 1]], p[[py, px, 2]], p[[py, px, 3]] * params.opacity]
+            }
+        }
+    };
+
+    for py in bounds.y..bounds.y + bounds.height {
+        for px in bounds.x..bounds.x + bounds.width {
+            let coverage = mask[py * width + px];
+            if coverage == 0 {
+                continue;
+            }
+            let color = paint(px, py);
+            for c in 0..3 {
+                layer[[py, px, c]] = (color[c].clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+            layer[[py, px, 3]] = (color[3].clamp(0.0, 1.0) * coverage as f32).round() as u8;
+        }
+    }
+    layer
+}
+
+/// Paint bucket: fill the region around a seed pixel and composite the
+/// paint over the image.
+///
+/// # Arguments
+/// Same as [`bucket_fill_layer`]
+///
+/// # Returns
+/// Filled RGBA image
+pub fn bucket_fill(
+    image: ArrayView3<u8>,
+    x: usize,
+    y: usize,
+    source: &FillSource,
+    tolerance: u8,
+    contiguous: bool,
+    antialias: bool,
+) -> Array3<u8> {
+    let layer = bucket_fill_layer(image, x, y, source, tolerance, contiguous, antialias);
+    let mut output = image.to_owned();
+    for ((py, px, _), _) in layer.indexed_iter().filter(|((_, _, c), &a)| *c == 3 && a > 0) {
+        let mut dst = [0, 1, 2, 3].map(|c| output[[py, px, c]]);
+        blend_over_u8(&mut dst, layer[[py, px, 0]], layer[[py, px, 1]], layer[[py, px, 2]], layer[[py, px, 3]]);
+        for (c, value) in dst.into_iter().enumerate() {
+            output[[py, px, c]] = value;
+        }
+    }
+    output
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Read an optional gradient field, `None` if the key is missing or None.
+#[cfg(feature = "python")]
+fn field<'py>(spec: &Bound<'py, PyAny>, key: &str) -> Option<Bound<'py, PyAny>> {
+    spec.get_item(key).ok().filter(|value| !value.is_none())
+}
+
+/// Parse a Python fill source: an (r, g, b[, a]) color, a uint8 pattern
+/// image, or a gradient dict.
+#[cfg(feature = "python")]
+fn parse_source(source: &Bound<'_, PyAny>) -> PyResult<FillSource> {
+    if let Ok(pattern) = source.extract::<PyHostArray3<u8>>() {
+        return Ok(FillSource::Pattern(PatternOverlayParams {
+            pattern: pattern.as_array().mapv(|v| v as f32 / 255.0),
+            scale: 1.0,
+            offset_x: 0,
+            offset_y: 0,
+            opacity: 1.0,
+            blend_mode: "normal".to_string(),
+        }));
+    }
+    if let Ok(color) = source.extract::<Vec<u8>>() {
+        return match color[..] {
+            [r, g, b] => Ok(FillSource::Solid([r, g, b, 255])),
+            [r, g, b, a] => Ok(FillSource::Solid([r, g, b, a])),
+            _ => Err(PyValueError::new_err(format!("fill color needs 3 or 4 values, got {}", color.len()))),
+        };
+    }
+    let Some(stops) = field(source, "stops") else {
+        return Err(PyValueError::new_err("source must be a color tuple, a uint8 pattern image or a gradient dict with 'stops'"));
+    };
+    let defaults = GradientOverlayParams::default();
+    let number = |key: &str, default: f32| field(source, key).map_or(Ok(default), |v| v.extract::<f32>());
+    Ok(FillSource::Gradient(GradientOverlayParams {
+        stops: parse_stops(&stops.extract::<Vec<f32>>()?, 255.0),
+        style: field(source, "style").map_or(Ok(defaults.style), |v| v.extract::<String>())?,
+        angle: number("angle", defaults.angle)?,
+        scale_x: number("scale_x", defaults.scale_x)?,
+        scale_y: number("scale_y", defaults.scale_y)?,
+        offset_x: number("offset_x", defaults.offset_x)?,
+        offset_y: number("offset_y", defaults.offset_y)?,
+        reverse: field(source, "reverse").map_or(Ok(defaults.reverse), |v| v.extract::<bool>())?,
+        opacity: number("opacity", defaults.opacity)?,
+        blend_mode: defaults.blend_mode,
+    }))
+}
+
+#[cfg(feature = "python")]
+fn check_rgba(image: &PyReadonlyArray3<'_, u8>) -> PyResult<()> {
+    match image.as_array().dim().2 {
+        4 => Ok(()),
+        c => Err(PyValueError::new_err(format!("bucket fill needs an RGBA image, got {} channels", c))),
+    }
+}
+
+/// Paint bucket fill of a uint8 RGBA image.
+///
+/// # Arguments
+/// * `image` - RGBA image (H, W, 4)
+/// * `x`, `y` - Seed pixel
+/// * `source` - (r, g, b[, a]) color, uint8 pattern image tiled from the
+///   canvas origin, or gradient dict `{"stops": [pos, r, g, b, ...],
+///   "style": "linear", "angle": 90.0, "reverse": False, ...}` spanning the
+///   filled region
+/// * `tolerance` - Color tolerance of the flood fill (0-255)
+/// * `contiguous` - Fill only pixels connected to the seed
+/// * `antialias` - Give edge pixels fractional coverage
+///
+/// # Returns
+/// Filled uint8 RGBA image
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "bucket_fill", signature = (image, x, y, source, tolerance=32, contiguous=true, antialias=false))]
+pub fn bucket_fill_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    x: usize,
+    y: usize,
+    source: &Bound<'py, PyAny>,
+    tolerance: u8,
+    contiguous: bool,
+    antialias: bool,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    check_rgba(&image)?;
+    let source = parse_source(source)?;
+    let result = bucket_fill(image.as_array(), x, y, &source, tolerance, contiguous, antialias);
+    Ok(result.into_pyarray(image.py()))
+}
+
+/// Paint of a bucket fill as a separate uint8 RGBA layer.
+///
+/// Same arguments as bucket_fill; the layer is transparent outside the
+/// filled region.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "bucket_fill_layer", signature = (image, x, y, source, tolerance=32, contiguous=true, antialias=false))]
+pub fn bucket_fill_layer_py<'py>(
+    image: PyHostArray3<'py, u8>,
+    x: usize,
+    y: usize,
+    source: &Bound<'py, PyAny>,
+    tolerance: u8,
+    contiguous: bool,
+    antialias: bool,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    check_rgba(&image)?;
+    let source = parse_source(source)?;
+    let result = bucket_fill_layer(image.as_array(), x, y, &source, tolerance, contiguous, antialias);
+    Ok(result.into_pyarray(image.py()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_effects::gradient_overlay::GradientStop;
+
+    #[test]
+    fn test_fill_sources_stay_inside_region() {
+        // White canvas split by a black vertical line at x = 10
+        let image = Array3::from_shape_fn((8, 20, 4), |(_, x, c)| if x == 10 && c < 3 { 0 } else { 255 });
+
+        let red = bucket_fill(image.view(), 2, 2, &FillSource::Solid([255, 0, 0, 255]), 32, true, false);
+        assert_eq!([0, 1, 2, 3].map(|c| red[[4, 9, c]]), [255, 0, 0, 255]);
+        assert_eq!([0, 1, 2].map(|c| red[[4, 10, c]]), [0, 0, 0]);
+        assert_eq!([0, 1, 2].map(|c| red[[4, 15, c]]), [255, 255, 255]);
+
+        // Non-contiguous fill reaches the right half too
+        let everywhere = bucket_fill(image.view(), 2, 2, &FillSource::Solid([255, 0, 0, 255]), 32, false, false);
+        assert_eq!(everywhere[[4, 15, 1]], 0);
+
+        // Horizontal gradient spans the filled region [11, 20), not the canvas
+        let gradient = FillSource::Gradient(GradientOverlayParams {
+            stops: vec![
+                GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 },
+                GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 },
+            ],
+            angle: 0.0,
+            ..Default::default()
+        });
+        let layer = bucket_fill_layer(image.view(), 15, 4, &gradient, 32, true, false);
+        assert_eq!(layer[[4, 5, 3]], 0);
+        assert!(layer[[4, 11, 0]] < 64 && layer[[4, 19, 0]] > 192, "{} .. {}", layer[[4, 11, 0]], layer[[4, 19, 0]]);
+
+        // Pattern tiles from the canvas origin
+        let checker = Array3::from_shape_fn((2, 2, 4), |(y, x, c)| if c < 3 && (x + y) % 2 == 1 { 0.0 } else { 1.0 });
+        let pattern = FillSource::Pattern(PatternOverlayParams {
+            pattern: checker,
+            scale: 1.0,
+            offset_x: 0,
+            offset_y: 0,
+            opacity: 1.0,
+            blend_mode: "normal".to_string(),
+        });
+        let tiled = bucket_fill_layer(image.view(), 15, 4, &pattern, 32, true, false);
+        assert_eq!((tiled[[4, 14, 0]], tiled[[4, 15, 0]], tiled[[4, 15, 3]]), (255, 0, 255));
+    }
+}
@@ -0,0 +1,18 @@
+//! Helpers for brush painting tools.
+//!
+//! - **Symmetry**: Replicates a rendered brush dab into its mirrored and
+//!   rotated instances (mirror axes, radial and mandala symmetry), so a
+//!   symmetry tool renders each dab once and composites all copies
+//! - **Strokes**: Resamples raw tablet input (position, pressure, tilt) into
+//!   evenly spaced dab parameters through a pressure curve, identically for
+//!   the Python tools and the WASM canvas
+//! - **Bucket fill**: Paint bucket over the magic wand flood fill, with
+//!   color, gradient or pattern paint and optional anti-aliasing
+
+pub mod bucket;
+pub mod stroke;
+pub mod symmetry;
+
+pub use bucket::{bucket_fill, bucket_fill_layer, FillSource};
+pub use stroke::{interpolate_stroke, DabParams, PressureCurve, StrokeInterpolator, StrokePoint};
+pub use symmetry::{replicate_symmetric_f32, replicate_symmetric_u8, Dab, SymmetryMode};
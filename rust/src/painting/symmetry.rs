@@ -0,0 +1,286 @@
+//! Symmetric instances of brush dabs.
+//!
+//! Symmetry is taken about the canvas center. Each instance is the dab image
+//! mirrored and/or rotated like its position, so strokes keep their shape
+//! in every copy. Right angles and mirrors are exact pixel rearrangements;
+//! other angles are resampled bicubically onto a canvas large enough for
+//! the rotated dab.
+
+use ndarray::{Array3, ArrayView3};
+
+use crate::filters::rotate::{flip_horizontal_f32, flip_vertical_f32, rotate_f32};
+use crate::filters::straighten::warp_bicubic;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Kind of symmetry of [`replicate_symmetric_f32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymmetryMode {
+    /// Mirror across the vertical axis (1 axis) and also the horizontal
+    /// axis (2 axes)
+    #[default]
+    Mirror,
+    /// N copies rotated in equal steps around the center
+    Radial,
+    /// Radial symmetry where every segment is also mirrored (2N copies)
+    Mandala,
+}
+
+impl SymmetryMode {
+    /// Parse a mode name ("mirror", "radial", "mandala").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "mirror" => Some(SymmetryMode::Mirror),
+            "radial" | "rotational" => Some(SymmetryMode::Radial),
+            "mandala" | "kaleidoscope" => Some(SymmetryMode::Mandala),
+            _ => None,
+        }
+    }
+}
+
+/// A rendered brush dab placed on the canvas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dab<T> {
+    /// Dab pixels (height, width, channels)
+    pub image: Array3<T>,
+    /// Center of the dab in canvas pixels
+    pub x: f32,
+    pub y: f32,
+}
+
+impl<T> Dab<T> {
+    pub fn new(image: Array3<T>, x: f32, y: f32) -> Self {
+        Self { image, x, y }
+    }
+
+    /// Canvas position of the dab's top-left pixel, for compositing.
+    pub fn origin(&self) -> (isize, isize) {
+        let (height, width, _) = self.image.dim();
+        ((self.x - width as f32 / 2.0).round() as isize, (self.y - height as f32 / 2.0).round() as isize)
+    }
+}
+
+/// Rotate a dab image clockwise by `degrees`, growing the canvas to fit.
+fn rotate_dab(image: ArrayView3<f32>, degrees: f32) -> Array3<f32> {
+    let degrees = degrees.rem_euclid(360.0);
+    let right_angle = (degrees / 90.0).round();
+    if (degrees - right_angle * 90.0).abs() < 1e-3 {
+        return match right_angle as u32 % 4 {
+            0 => image.to_owned(),
+            quarter => rotate_f32(image, quarter * 90),
+        };
+    }
+    let (height, width, _) = image.dim();
+    let size = ((width * width + height * height) as f32).sqrt().ceil() as usize;
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (cx, cy) = ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0);
+    let center = (size as f32 - 1.0) / 2.0;
+    // Inverse mapping like `rotate_bicubic_f32`, between different centers
+    warp_bicubic(image, size, size, |x, y| {
+        let (dx, dy) = (x - center, y - center);
+        (cx + dx * cos + dy * sin, cy - dx * sin + dy * cos)
+    })
+}
+
+/// All symmetric instances of a dab - f32 version.
+///
+/// The first instance is the dab itself. Positions are mirrored or rotated
+/// about the canvas center `(width / 2, height / 2)`; rotations are
+/// clockwise in steps of `360 / segments` degrees.
+///
+/// # Arguments
+/// * `dab` - Rendered dab with its center position
+/// * `width`, `height` - Canvas size
+/// * `mode` - Kind of symmetry
+/// * `axes_or_segments` - Mirror axes for `Mirror` (1 = left/right,
+///   2 = also top/bottom), number of segments for `Radial` and `Mandala`
+///
+/// # Returns
+/// Instances ready for compositing at their [`Dab::origin`]
+pub fn replicate_symmetric_f32(
+    dab: &Dab<f32>,
+    width: usize,
+    height: usize,
+    mode: SymmetryMode,
+    axes_or_segments: usize,
+) -> Vec<Dab<f32>> {
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    let (dx, dy) = (dab.x - cx, dab.y - cy);
+    let image = dab.image.view();
+    let mut instances = vec![dab.clone()];
+
+    match mode {
+        SymmetryMode::Mirror => {
+            let mirrored = flip_horizontal_f32(image);
+            if axes_or_segments >= 2 {
+                instances.push(Dab::new(flip_vertical_f32(image), dab.x, cy - dy));
+                instances.push(Dab::new(flip_vertical_f32(mirrored.view()), cx - dx, cy - dy));
+            }
+            instances.insert(1, Dab::new(mirrored, cx - dx, dab.y));
+        }
+        SymmetryMode::Radial | SymmetryMode::Mandala => {
+            let segments = axes_or_segments.max(1);
+            let mirrored = (mode == SymmetryMode::Mandala).then(|| flip_horizontal_f32(image));
+            for k in 0..segments {
+                let degrees = 360.0 * k as f32 / segments as f32;
+                let (sin, cos) = degrees.to_radians().sin_cos();
+                let rotate = |x: f32, y: f32| (cx + x * cos - y * sin, cy + x * sin + y * cos);
+                if k > 0 {
+                    let (x, y) = rotate(dx, dy);
+                    instances.push(Dab::new(rotate_dab(image, degrees), x, y));
+                }
+                if let Some(mirrored) = &mirrored {
+                    let (x, y) = rotate(-dx, dy);
+                    instances.push(Dab::new(rotate_dab(mirrored.view(), degrees), x, y));
+                }
+            }
+        }
+    }
+    instances
+}
+
+/// All symmetric instances of a dab - u8 version.
+pub fn replicate_symmetric_u8(
+    dab: &Dab<u8>,
+    width: usize,
+    height: usize,
+    mode: SymmetryMode,
+    axes_or_segments: usize,
+) -> Vec<Dab<u8>> {
+    let dab_f32 = Dab::new(dab.image.mapv(|v| v as f32 / 255.0), dab.x, dab.y);
+    replicate_symmetric_f32(&dab_f32, width, height, mode, axes_or_segments)
+        .into_iter()
+        .map(|d| Dab::new(d.image.mapv(|v| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8), d.x, d.y))
+        .collect()
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Dab instances as (image, left, top) tuples.
+#[cfg(feature = "python")]
+type PyDabs<'py, T> = Vec<(Bound<'py, PyArray3<T>>, isize, isize)>;
+
+#[cfg(feature = "python")]
+fn parse_mode(name: &str) -> PyResult<SymmetryMode> {
+    SymmetryMode::from_name(name).ok_or_else(|| {
+        PyValueError::new_err(format!("Unknown symmetry mode '{}'. Use 'mirror', 'radial' or 'mandala'", name))
+    })
+}
+
+/// Replicate a uint8 brush dab into its symmetric instances.
+///
+/// # Arguments
+/// * `dab` - Dab pixels (height, width, channels)
+/// * `x`, `y` - Dab center on the canvas
+/// * `width`, `height` - Canvas size; symmetry is about its center
+/// * `mode` - "mirror", "radial" or "mandala"
+/// * `axes_or_segments` - Mirror axes (1 or 2) or number of segments
+///
+/// # Returns
+/// List of (image, left, top) tuples, the original dab first
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "replicate_symmetric", signature = (dab, x, y, width, height, mode="mirror", axes_or_segments=1))]
+pub fn replicate_symmetric_py<'py>(
+    py: Python<'py>,
+    dab: PyHostArray3<'py, u8>,
+    x: f32,
+    y: f32,
+    width: usize,
+    height: usize,
+    mode: &str,
+    axes_or_segments: usize,
+) -> PyResult<PyDabs<'py, u8>> {
+    let dab = Dab::new(dab.as_array().to_owned(), x, y);
+    let instances = replicate_symmetric_u8(&dab, width, height, parse_mode(mode)?, axes_or_segments);
+    Ok(instances.into_iter().map(|d| {
+        let (left, top) = d.origin();
+        (d.image.into_pyarray(py), left, top)
+    }).collect())
+}
+
+/// Replicate a float32 brush dab into its symmetric instances.
+///
+/// Same as replicate_symmetric but for f32 dabs.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "replicate_symmetric_f32", signature = (dab, x, y, width, height, mode="mirror", axes_or_segments=1))]
+pub fn replicate_symmetric_f32_py<'py>(
+    py: Python<'py>,
+    dab: PyHostArray3<'py, f32>,
+    x: f32,
+    y: f32,
+    width: usize,
+    height: usize,
+    mode: &str,
+    axes_or_segments: usize,
+) -> PyResult<PyDabs<'py, f32>> {
+    let dab = Dab::new(dab.as_array().to_owned(), x, y);
+    let instances = replicate_symmetric_f32(&dab, width, height, parse_mode(mode)?, axes_or_segments);
+    Ok(instances.into_iter().map(|d| {
+        let (left, top) = d.origin();
+        (d.image.into_pyarray(py), left, top)
+    }).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrow() -> Dab<f32> {
+        // 4x6 dab, bright top-right corner to track orientation
+        let image = Array3::from_shape_fn((4, 6, 1), |(y, x, _)| if y == 0 && x == 5 { 1.0 } else { 0.25 });
+        Dab::new(image, 30.0, 20.0)
+    }
+
+    #[test]
+    fn test_mirror_axes() {
+        let dab = arrow();
+        let one = replicate_symmetric_f32(&dab, 100, 80, SymmetryMode::Mirror, 1);
+        assert_eq!(one.len(), 2);
+        assert_eq!((one[1].x, one[1].y), (70.0, 20.0));
+        assert_eq!(one[1].image[[0, 0, 0]], 1.0);
+        // Mirrored footprint mirrors the original's: [27, 33) <-> [67, 73)
+        assert_eq!(one[0].origin(), (27, 18));
+        assert_eq!(one[1].origin(), (67, 18));
+
+        let two = replicate_symmetric_f32(&dab, 100, 80, SymmetryMode::Mirror, 2);
+        let centers: Vec<_> = two.iter().map(|d| (d.x, d.y)).collect();
+        assert_eq!(centers, [(30.0, 20.0), (70.0, 20.0), (30.0, 60.0), (70.0, 60.0)]);
+        assert_eq!(two[3].image[[3, 0, 0]], 1.0);
+    }
+
+    #[test]
+    fn test_radial_and_mandala() {
+        let dab = arrow();
+        let four = replicate_symmetric_f32(&dab, 100, 80, SymmetryMode::Radial, 4);
+        assert_eq!(four.len(), 4);
+        // 90 degrees clockwise about (50, 40): (-20, -20) -> (20, -20)
+        assert!((four[1].x - 70.0).abs() < 1e-4 && (four[1].y - 20.0).abs() < 1e-4);
+        assert_eq!(four[1].image.dim(), (6, 4, 1));
+        assert_eq!(four[1].image[[5, 3, 0]], 1.0);
+
+        let six = replicate_symmetric_f32(&dab, 100, 80, SymmetryMode::Radial, 6);
+        let grown = six[1].image.dim();
+        assert_eq!((grown.0, grown.1), (8, 8));
+        let mass = |d: &Dab<f32>| d.image.sum();
+        assert!((mass(&six[1]) - mass(&dab)).abs() < 0.05 * mass(&dab));
+        for d in &six {
+            let r = ((d.x - 50.0).powi(2) + (d.y - 40.0).powi(2)).sqrt();
+            assert!((r - 800f32.sqrt()).abs() < 1e-3);
+        }
+
+        let mandala = replicate_symmetric_u8(&Dab::new(dab.image.mapv(|v| (v * 255.0) as u8), 30.0, 20.0), 100, 80, SymmetryMode::Mandala, 3);
+        assert_eq!(mandala.len(), 6);
+        assert_eq!((mandala[1].x, mandala[1].y), (70.0, 20.0));
+    }
+}
@@ -0,0 +1,247 @@
+//! Per-filter timing and allocation tracing (`profiling` feature).
+//!
+//! With the feature enabled every filter step of the batch pipeline
+//! ([`FilterSpec::apply_u8`](crate::batch::FilterSpec::apply_u8) and its f32
+//! twin, so also recipes, `apply_filter` and `FilterContext`) is timed, its
+//! peak heap growth is measured through [`TrackingAllocator`], and a
+//! `tracing` event is emitted at DEBUG level. The records collect until
+//! [`take_report`] (Python: `get_last_perf_report()`) fetches them.
+//!
+//! Without the feature [`record`] just runs the step and the report stays
+//! empty, so the instrumentation costs nothing in release builds.
+//!
+//! The allocator is not installed by this library, since a crate linking
+//! it may bring its own. The final binary installs it:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: imagestag_rust::profiling::TrackingAllocator = imagestag_rust::profiling::TrackingAllocator;
+//! ```
+//!
+//! The `profiling-alloc` feature does this for builds where this crate is
+//! the final artifact (the Python extension). Without an installed
+//! allocator the peak bytes stay 0.
+//!
+//! Peaks are counted per thread, so steps running in parallel (batches of
+//! images) don't see each other's allocations. Allocations a step hands to
+//! rayon workers are not included; its result and the buffers allocated
+//! on its own thread are.
+
+use std::time::Duration;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+
+/// Whether the crate was built with the `profiling` feature.
+pub const PROFILING: bool = cfg!(feature = "profiling");
+
+/// Most records kept when the report is never fetched.
+#[cfg(feature = "profiling")]
+const MAX_RECORDS: usize = 10_000;
+
+/// Timing and memory of one filter step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerfRecord {
+    /// Canonical filter name
+    pub filter: &'static str,
+    /// Wall-clock time of the step
+    pub duration: Duration,
+    /// Peak heap growth during the step in bytes, including the result
+    pub peak_bytes: usize,
+}
+
+#[cfg(feature = "profiling")]
+mod tracking {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use super::PerfRecord;
+
+    thread_local! {
+        // Bytes allocated minus bytes freed on this thread (negative when
+        // the thread frees memory allocated elsewhere) and its high-water mark
+        pub static CURRENT: Cell<isize> = const { Cell::new(0) };
+        pub static PEAK: Cell<isize> = const { Cell::new(0) };
+    }
+    pub static REPORT: Mutex<VecDeque<PerfRecord>> = Mutex::new(VecDeque::new());
+
+    /// System allocator counting heap bytes per thread and their high-water mark.
+    ///
+    /// Install it as `#[global_allocator]` in the final binary to get peak
+    /// bytes in the profiling report.
+    pub struct TrackingAllocator;
+
+    fn grow(bytes: usize) {
+        // try_with: the thread locals are gone while a thread shuts down
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + bytes as isize);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+    }
+
+    fn shrink(bytes: usize) {
+        let _ = CURRENT.try_with(|current| current.set(current.get() - bytes as isize));
+    }
+
+    // SAFETY: every call is forwarded to `System` unchanged; only counters are updated.
+    unsafe impl GlobalAlloc for TrackingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                if new_size >= layout.size() {
+                    grow(new_size - layout.size());
+                } else {
+                    shrink(layout.size() - new_size);
+                }
+            }
+            new_ptr
+        }
+    }
+
+    #[cfg(feature = "profiling-alloc")]
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+}
+
+#[cfg(feature = "profiling")]
+pub use tracking::TrackingAllocator;
+
+/// Run one filter step, recording its time and peak allocation.
+///
+/// `name` is only evaluated when profiling is enabled.
+#[cfg(feature = "profiling")]
+pub fn record<R>(name: impl FnOnce() -> &'static str, step: impl FnOnce() -> R) -> R {
+    use std::cell::Cell;
+    use std::time::Instant;
+    use tracking::{CURRENT, PEAK, REPORT};
+
+    let filter = name();
+    // Measure from the current level; the outer peak is restored afterwards
+    // so nested steps (recipes) don't hide each other's peaks
+    let start_bytes = CURRENT.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(start_bytes));
+    let start = Instant::now();
+    let result = step();
+    let duration = start.elapsed();
+    let step_peak = PEAK.with(|peak| peak.replace(peak.get().max(outer_peak)));
+    let peak_bytes = (step_peak - start_bytes).max(0) as usize;
+    tracing::debug!(filter, micros = duration.as_micros() as u64, peak_bytes, "filter step");
+
+    let mut report = REPORT.lock().unwrap_or_else(|e| e.into_inner());
+    if report.len() >= MAX_RECORDS {
+        report.pop_front();
+    }
+    report.push_back(PerfRecord { filter, duration, peak_bytes });
+    result
+}
+
+/// Run one filter step (profiling disabled: no bookkeeping).
+#[cfg(not(feature = "profiling"))]
+#[inline(always)]
+pub fn record<R>(_name: impl FnOnce() -> &'static str, step: impl FnOnce() -> R) -> R {
+    step()
+}
+
+/// Records of all steps since the previous call, oldest first.
+///
+/// Always empty without the `profiling` feature.
+pub fn take_report() -> Vec<PerfRecord> {
+    #[cfg(feature = "profiling")]
+    {
+        std::mem::take(&mut *tracking::REPORT.lock().unwrap_or_else(|e| e.into_inner())).into()
+    }
+    #[cfg(not(feature = "profiling"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Filter steps recorded since the previous call, oldest first.
+///
+/// # Returns
+/// List of `{"filter": str, "seconds": float, "peak_bytes": int}`; always
+/// empty unless the module was built with the `profiling` feature
+/// (`imagestag_rust.PROFILING`)
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "get_last_perf_report")]
+pub fn get_last_perf_report_py(py: Python<'_>) -> PyResult<Vec<Bound<'_, PyDict>>> {
+    take_report()
+        .into_iter()
+        .map(|record| {
+            let entry = PyDict::new(py);
+            entry.set_item("filter", record.filter)?;
+            entry.set_item("seconds", record.duration.as_secs_f64())?;
+            entry.set_item("peak_bytes", record.peak_bytes)?;
+            Ok(entry)
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "profiling"))]
+mod tests {
+    use super::*;
+    use crate::batch::{apply_pipeline_u8, parse_pipeline};
+    use ndarray::Array3;
+
+    // The test binary is a final artifact, so it may install the allocator
+    #[cfg(not(feature = "profiling-alloc"))]
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+    #[test]
+    fn test_pipeline_steps_are_recorded() {
+        let image = Array3::<u8>::from_elem((64, 64, 3), 100);
+        take_report();
+        apply_pipeline_u8(image.view(), &parse_pipeline("brightness 0.1 | invert").unwrap());
+        let report = take_report();
+        // Other tests may run filters concurrently; look for this pipeline's steps
+        for filter in ["brightness", "invert"] {
+            // Each step allocates at least its 64x64x3 result
+            assert!(report.iter().any(|r| r.filter == filter && r.peak_bytes >= 64 * 64 * 3), "{filter}: {report:?}");
+        }
+    }
+
+    #[test]
+    fn test_parallel_steps_keep_their_own_peaks() {
+        let run = |size: usize| {
+            std::thread::spawn(move || {
+                let image = Array3::<u8>::from_elem((size, size, 3), 100);
+                (0..20).map(|_| record(|| "parallel_probe", || crate::filters::color_adjust::brightness_u8(image.view(), 0.1))).count();
+            })
+        };
+        take_report();
+        let (small, large) = (run(16), run(512));
+        small.join().unwrap();
+        large.join().unwrap();
+        let peaks: Vec<usize> = take_report().iter().filter(|r| r.filter == "parallel_probe").map(|r| r.peak_bytes).collect();
+        // Small steps never report the large thread's 512x512x3 buffers
+        assert!(peaks.iter().filter(|&&p| p < 512 * 512).count() >= 20, "{peaks:?}");
+        assert!(peaks.iter().any(|&p| p >= 512 * 512 * 3), "{peaks:?}");
+    }
+}
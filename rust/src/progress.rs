@@ -0,0 +1,151 @@
+//! Progress reporting and cancellation for long-running operations.
+//!
+//! Expensive kernels (median, NL-means denoise, Canny edges) have
+//! `*_with_progress` variants that take a callback. The kernel calls it on
+//! its own thread with the completed fraction (0.0-1.0) and stops with
+//! [`Cancelled`] as soon as the callback returns `false`.
+//!
+//! [`ProgressHandle`] is a thread-safe callback target: one thread runs the
+//! operation with `&mut |f| handle.report(f)`, others poll
+//! [`ProgressHandle::fraction`] and call [`ProgressHandle::cancel`].
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use std::time::{Duration, Instant};
+
+/// Error returned by an operation that was cancelled through its progress callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Callback for operations run without progress reporting.
+pub fn no_progress(_fraction: f32) -> bool {
+    true
+}
+
+/// Pass `fraction` to `progress`, turning a `false` answer into [`Cancelled`].
+pub fn report(progress: &mut dyn FnMut(f32) -> bool, fraction: f32) -> Result<(), Cancelled> {
+    if progress(fraction.clamp(0.0, 1.0)) {
+        Ok(())
+    } else {
+        Err(Cancelled)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    /// f32 bits of the last reported fraction
+    fraction: AtomicU32,
+    cancelled: AtomicBool,
+}
+
+/// Shared progress and cancel flag of one operation.
+///
+/// Clones refer to the same state.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressHandle {
+    state: Arc<ProgressState>,
+}
+
+impl ProgressHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `fraction`; returns `false` once the operation was cancelled.
+    pub fn report(&self, fraction: f32) -> bool {
+        self.state.fraction.store(fraction.to_bits(), Ordering::Relaxed);
+        !self.is_cancelled()
+    }
+
+    /// Last reported fraction (0.0-1.0).
+    pub fn fraction(&self) -> f32 {
+        f32::from_bits(self.state.fraction.load(Ordering::Relaxed))
+    }
+
+    /// Ask the operation to stop at its next progress report.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+#[cfg(feature = "python")]
+pyo3::create_exception!(
+    imagestag_rust,
+    CancelledError,
+    pyo3::exceptions::PyException,
+    "Raised when a progress callback cancels an operation."
+);
+
+/// Run `op` with an optional Python progress callable.
+///
+/// The callable gets the completed percentage (0-100) each time it
+/// increases by at least one, and at least every 100 ms so it can cancel
+/// phases without steady progress. Returning `False` cancels the operation
+/// with `CancelledError`; an exception raised by the callable cancels it and
+/// is re-raised.
+#[cfg(feature = "python")]
+pub fn with_py_progress<T>(
+    progress: Option<&Bound<'_, PyAny>>,
+    op: impl FnOnce(&mut dyn FnMut(f32) -> bool) -> Result<T, Cancelled>,
+) -> PyResult<T> {
+    let Some(callback) = progress else {
+        return op(&mut no_progress).map_err(|e| CancelledError::new_err(e.to_string()));
+    };
+    let mut last_percent = -1.0f32;
+    let mut last_call = Instant::now();
+    let mut error = None;
+    let result = op(&mut |fraction| {
+        let percent = (fraction * 100.0).floor();
+        if percent <= last_percent && last_call.elapsed() < Duration::from_millis(100) {
+            return true;
+        }
+        last_percent = percent;
+        last_call = Instant::now();
+        match callback.call1((percent,)) {
+            Ok(answer) => !matches!(answer.extract::<bool>(), Ok(false)),
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        }
+    });
+    result.map_err(|e| error.unwrap_or_else(|| CancelledError::new_err(e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_reports_and_cancels() {
+        let handle = ProgressHandle::new();
+        let shared = handle.clone();
+        let mut callback = |f| shared.report(f);
+        assert_eq!(report(&mut callback, 0.25), Ok(()));
+        assert_eq!(handle.fraction(), 0.25);
+        handle.cancel();
+        assert_eq!(report(&mut callback, 1.5), Err(Cancelled));
+        assert_eq!(handle.fraction(), 1.0);
+    }
+}
@@ -0,0 +1,109 @@
+//! Connected component labeling of selection masks.
+//!
+//! [`label_components`] numbers every connected region of a mask, so each
+//! blob can be measured with [`super::label_summaries`] or handled on its
+//! own; [`remove_small_components`] uses it to drop specks from masks
+//! produced by thresholding.
+
+use std::collections::VecDeque;
+
+/// Label the connected regions of a mask.
+///
+/// # Arguments
+/// * `mask` - Selection mask (flattened row-major)
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `threshold` - Values >= threshold count as selected
+/// * `eight_connected` - Whether diagonal neighbors connect regions
+///
+/// # Returns
+/// Label per pixel (0 = background, 1..=N in scanline order of each
+/// region's first pixel) and the number of regions N
+pub fn label_components(mask: &[u8], width: usize, height: usize, threshold: u8, eight_connected: bool) -> (Vec<u32>, u32) {
+    let total = width * height;
+    let mut labels = vec![0u32; total];
+    if mask.len() < total {
+        return (labels, 0);
+    }
+    let offsets: &[(isize, isize)] = if eight_connected {
+        &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)]
+    } else {
+        &[(0, -1), (-1, 0), (1, 0), (0, 1)]
+    };
+
+    let mut count = 0;
+    let mut queue = VecDeque::new();
+    for start in 0..total {
+        if mask[start] < threshold || labels[start] != 0 {
+            continue;
+        }
+        count += 1;
+        labels[start] = count;
+        queue.push_back(start);
+        while let Some(idx) = queue.pop_front() {
+            let (x, y) = ((idx % width) as isize, (idx / width) as isize);
+            for &(dx, dy) in offsets {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || ny < 0 || nx >= width as isize || ny >= height as isize {
+                    continue;
+                }
+                let neighbor = ny as usize * width + nx as usize;
+                if mask[neighbor] >= threshold && labels[neighbor] == 0 {
+                    labels[neighbor] = count;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+    (labels, count)
+}
+
+/// Drop connected regions smaller than `min_area` pixels.
+///
+/// Regions are 8-connected. Kept pixels retain their mask value; removed
+/// ones become 0.
+///
+/// # Arguments
+/// * `mask` - Selection mask (flattened row-major)
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `threshold` - Values >= threshold count as selected
+/// * `min_area` - Smallest region to keep, in pixels
+pub fn remove_small_components(mask: &[u8], width: usize, height: usize, threshold: u8, min_area: usize) -> Vec<u8> {
+    let (labels, count) = label_components(mask, width, height, threshold, true);
+    let mut areas = vec![0usize; count as usize + 1];
+    for &label in &labels {
+        areas[label as usize] += 1;
+    }
+    labels
+        .iter()
+        .zip(mask)
+        .map(|(&label, &value)| if label > 0 && areas[label as usize] >= min_area { value } else { 0 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_and_small_component_removal() {
+        // Two diagonal pixels, a separate 2x2 block and a lone pixel
+        let mask = [
+            255, 0,   0,   0,   0,
+            0,   255, 0,   255, 255,
+            0,   0,   0,   255, 255,
+            200, 0,   0,   0,   0,
+        ];
+        let (labels, count) = label_components(&mask, 5, 4, 128, true);
+        assert_eq!(count, 3);
+        assert_eq!((labels[0], labels[6], labels[8], labels[15]), (1, 1, 2, 3));
+
+        let (_, four_count) = label_components(&mask, 5, 4, 128, false);
+        assert_eq!(four_count, 4);
+
+        let cleaned = remove_small_components(&mask, 5, 4, 128, 3);
+        assert_eq!(cleaned.iter().filter(|&&v| v > 0).count(), 4);
+        assert_eq!((cleaned[0], cleaned[9], cleaned[15]), (0, 255, 0));
+    }
+}
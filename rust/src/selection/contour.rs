@@ -55,7 +55,7 @@ fn is_boundary(mask: &[u8], width: usize, height: usize, x: i32, y: i32) -> bool
 
 /// Extract contours by tracing boundaries.
 fn extract_contours_internal(mask: &[u8], width: usize, height: usize) -> Vec<Vec<(f32, f32)>> {
-    if width == 0 || height == 0 {
+    if width.checked_mul(height).is_none_or(|n| n == 0 || mask.len() < n) {
         return Vec::new();
     }
 
@@ -68,7 +68,9 @@ fn extract_contours_internal(mask: &[u8], width: usize, height: usize) -> Vec<Ve
             if is_boundary(mask, width, height, x, y) && !visited.contains(&(x, y)) {
                 // Trace contour starting from this pixel
                 let contour = trace_boundary(mask, width, height, x, y, &mut visited);
-                if contour.len() >= 3 {
+                // Isolated pixels and 1-pixel-wide lines trace to fewer than
+                // 3 points but are still part of the selection
+                if !contour.is_empty() {
                     contours.push(contour);
                 }
             }
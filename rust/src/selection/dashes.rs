@@ -0,0 +1,123 @@
+//! Dash segmentation of contours for marching ants.
+//!
+//! [`contour_to_dash_segments`] cuts a contour into the "on" pieces of an
+//! even dash pattern at a given animation phase. The frontend uploads the
+//! returned polylines as line geometry and only asks for new ones when the
+//! phase advances, instead of recomputing the dash pattern in JS every frame.
+
+use super::marching_squares::{Contour, Point};
+
+/// Split a contour into dash polylines.
+///
+/// The pattern alternates `dash_length` drawn and `dash_length` skipped
+/// along the arc length. Increasing `phase` moves the dashes forward along
+/// the contour; it repeats every `2 * dash_length`. On closed contours a
+/// dash running over the start point is returned as one polyline.
+///
+/// # Arguments
+/// * `contour` - Contour to dash (closed contours include the closing edge)
+/// * `dash_length` - Length of each dash and gap in pixels (<= 0 draws the contour solid)
+/// * `phase` - Pattern offset in pixels
+///
+/// # Returns
+/// Dash polylines in contour order, each with at least two points
+pub fn contour_to_dash_segments(contour: &Contour, dash_length: f32, phase: f32) -> Vec<Vec<Point>> {
+    let mut points = contour.points.clone();
+    if contour.is_closed && points.len() > 1 {
+        points.push(points[0]);
+    }
+    if points.len() < 2 {
+        return Vec::new();
+    }
+    if dash_length <= 0.0 || !dash_length.is_finite() {
+        return vec![points];
+    }
+
+    let period = 2.0 * dash_length;
+    let mut dashes: Vec<Vec<Point>> = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    let mut traveled = 0.0f32;
+
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let length = a.distance_to(&b);
+        if length <= 0.0 {
+            continue;
+        }
+        let along = (traveled - phase).rem_euclid(period);
+        let mut on = along < dash_length;
+        // Dashes and gaps may also switch exactly at a vertex
+        if on && current.is_empty() {
+            current.push(a);
+        } else if !on && !current.is_empty() {
+            dashes.push(std::mem::take(&mut current));
+        }
+        // Distance from `a` to the next switch between dash and gap
+        let mut next = if on { dash_length - along } else { period - along };
+        while next < length {
+            let t = next / length;
+            let p = Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t);
+            current.push(p);
+            if on {
+                dashes.push(std::mem::take(&mut current));
+            }
+            on = !on;
+            next += dash_length;
+        }
+        if on {
+            current.push(b);
+        }
+        traveled += length;
+    }
+    if current.len() > 1 {
+        dashes.push(current);
+    }
+
+    // Join the dash crossing the start of a closed contour
+    let start = points[0];
+    let crosses_start = dashes.first().is_some_and(|d| d[0] == start) && dashes.last().is_some_and(|d| d[d.len() - 1] == start);
+    if contour.is_closed && crosses_start && dashes.len() > 1 {
+        let first = dashes.remove(0);
+        if let Some(last) = dashes.last_mut() {
+            last.extend(first.into_iter().skip(1));
+        }
+    }
+    dashes.retain(|dash| dash.len() > 1);
+    dashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dash_length(dash: &[Point]) -> f32 {
+        dash.windows(2).map(|p| p[0].distance_to(&p[1])).sum()
+    }
+
+    #[test]
+    fn test_dash_segments() {
+        // 10x10 square, perimeter 40, period 8 divides it evenly
+        let square = Contour::new(
+            vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0), Point::new(10.0, 10.0), Point::new(0.0, 10.0)],
+            true,
+        );
+        let dashes = contour_to_dash_segments(&square, 4.0, 0.0);
+        assert_eq!(dashes.len(), 5);
+        assert!(dashes.iter().all(|d| (dash_length(d) - 4.0).abs() < 1e-4));
+        // The dash starting at 8 turns the corner at (10, 0)
+        assert_eq!(dashes[1], vec![Point::new(8.0, 0.0), Point::new(10.0, 0.0), Point::new(10.0, 2.0)]);
+
+        // Shifted by 6, the dash over the start point is joined into one
+        let shifted = contour_to_dash_segments(&square, 4.0, 6.0);
+        assert_eq!(shifted.len(), 5);
+        assert!(shifted.iter().all(|d| (dash_length(d) - 4.0).abs() < 1e-4));
+        assert_eq!(shifted[4], vec![Point::new(0.0, 2.0), Point::new(0.0, 0.0), Point::new(2.0, 0.0)]);
+
+        // A full period later the pattern is the same
+        assert_eq!(contour_to_dash_segments(&square, 4.0, 8.0), dashes);
+
+        let open = Contour::new(vec![Point::new(0.0, 0.0), Point::new(5.0, 0.0)], false);
+        assert_eq!(contour_to_dash_segments(&open, 2.0, 0.0).len(), 2);
+        assert_eq!(contour_to_dash_segments(&open, 0.0, 0.0), vec![open.points.clone()]);
+    }
+}
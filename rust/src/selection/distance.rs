@@ -0,0 +1,136 @@
+//! Distance transform of selection masks.
+//!
+//! Turns a mask into a grayscale map of each selected pixel's distance to
+//! the nearest unselected one. The map drives feathering and strokes, and
+//! its ridges mark the centers of blobs, where [`super::watershed`] seeds
+//! markers to split touching objects.
+
+use ndarray::Array2;
+
+use crate::filters::core::euclidean_distance_transform;
+
+/// Distance measure of [`distance_transform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceMetric {
+    /// Straight-line distance (exact)
+    #[default]
+    Euclidean,
+    /// Sum of the x and y offsets (4-connected steps)
+    Manhattan,
+    /// Larger of the x and y offsets (8-connected steps)
+    Chessboard,
+}
+
+impl DistanceMetric {
+    /// Parse a metric name ("euclidean", "manhattan", "chessboard").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "euclidean" | "l2" => Some(Self::Euclidean),
+            "manhattan" | "cityblock" | "taxicab" | "l1" => Some(Self::Manhattan),
+            "chessboard" | "chebyshev" => Some(Self::Chessboard),
+            _ => None,
+        }
+    }
+}
+
+/// Compute the distance of every selected pixel to the nearest unselected one.
+///
+/// # Arguments
+/// * `mask` - Selection mask (>= 128 = selected, flattened row-major)
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `metric` - Distance measure
+///
+/// # Returns
+/// Distance in pixels per pixel (0.0 outside the selection). A mask without
+/// unselected pixels yields very large distances.
+pub fn distance_transform(mask: &[u8], width: usize, height: usize, metric: DistanceMetric) -> Vec<f32> {
+    let n = width * height;
+    if mask.len() < n {
+        return vec![0.0; n];
+    }
+    match metric {
+        DistanceMetric::Euclidean => {
+            let inside = Array2::from_shape_fn((height, width), |(y, x)| mask[y * width + x] >= 128);
+            euclidean_distance_transform(&inside).into_raw_vec_and_offset().0
+        }
+        DistanceMetric::Manhattan => chamfer(mask, width, height, false),
+        DistanceMetric::Chessboard => chamfer(mask, width, height, true),
+    }
+}
+
+/// Two-pass chamfer distance with unit steps, exact for the city block
+/// (4 neighbors) and chessboard (8 neighbors) metrics.
+fn chamfer(mask: &[u8], width: usize, height: usize, diagonal: bool) -> Vec<f32> {
+    const FAR: u32 = u32::MAX / 2;
+    let mut dist: Vec<u32> = mask[..width * height].iter().map(|&m| if m >= 128 { FAR } else { 0 }).collect();
+
+    // Forward pass: neighbors above and to the left
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            let mut d = dist[i];
+            if x > 0 {
+                d = d.min(dist[i - 1] + 1);
+            }
+            if y > 0 {
+                d = d.min(dist[i - width] + 1);
+                if diagonal && x > 0 {
+                    d = d.min(dist[i - width - 1] + 1);
+                }
+                if diagonal && x + 1 < width {
+                    d = d.min(dist[i - width + 1] + 1);
+                }
+            }
+            dist[i] = d;
+        }
+    }
+
+    // Backward pass: neighbors below and to the right
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            let i = y * width + x;
+            let mut d = dist[i];
+            if x + 1 < width {
+                d = d.min(dist[i + 1] + 1);
+            }
+            if y + 1 < height {
+                d = d.min(dist[i + width] + 1);
+                if diagonal && x + 1 < width {
+                    d = d.min(dist[i + width + 1] + 1);
+                }
+                if diagonal && x > 0 {
+                    d = d.min(dist[i + width - 1] + 1);
+                }
+            }
+            dist[i] = d;
+        }
+    }
+
+    dist.into_iter().map(|d| d as f32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_on_single_hole() {
+        // 7x7 selected square with one unselected pixel in the middle
+        let mut mask = vec![255u8; 49];
+        mask[3 * 7 + 3] = 0;
+        let at = |d: &[f32], x: usize, y: usize| d[y * 7 + x];
+
+        let euclidean = distance_transform(&mask, 7, 7, DistanceMetric::Euclidean);
+        let manhattan = distance_transform(&mask, 7, 7, DistanceMetric::Manhattan);
+        let chessboard = distance_transform(&mask, 7, 7, DistanceMetric::Chessboard);
+        assert_eq!(at(&euclidean, 3, 3), 0.0);
+        assert!((at(&euclidean, 5, 5) - 8.0f32.sqrt()).abs() < 1e-5);
+        assert_eq!(at(&manhattan, 5, 5), 4.0);
+        assert_eq!(at(&chessboard, 5, 5), 2.0);
+        assert_eq!(at(&manhattan, 0, 3), 3.0);
+
+        assert_eq!(DistanceMetric::from_name("cityblock"), Some(DistanceMetric::Manhattan));
+        assert_eq!(DistanceMetric::from_name("hamming"), None);
+    }
+}
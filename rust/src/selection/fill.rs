@@ -0,0 +1,149 @@
+//! Scanline polygon fill of contours back into a mask.
+//!
+//! [`fill_contours`] is the inverse of contour extraction: selections
+//! edited in vector form, or paths imported from SVG, are rasterized into
+//! a selection mask. Contours are filled together, so holes come out of
+//! the fill rule rather than needing a separate subtract step.
+
+use rayon::prelude::*;
+
+use super::marching_squares::{Contour, Point};
+
+/// Segments per Bezier curve when flattening contours with curves.
+const BEZIER_STEPS: usize = 16;
+
+/// Rule deciding which regions enclosed by contours are inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside where a ray crosses an odd number of edges; nested contours
+    /// alternate between filled and hole regardless of direction
+    EvenOdd,
+    /// Inside where the edge directions don't cancel out; a hole needs
+    /// the opposite winding of its outer contour (the SVG default)
+    #[default]
+    NonZero,
+}
+
+impl FillRule {
+    /// Parse a fill rule name ("evenodd", "nonzero").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "evenodd" => Some(Self::EvenOdd),
+            "nonzero" | "winding" => Some(Self::NonZero),
+            _ => None,
+        }
+    }
+}
+
+/// Polygon vertices of a contour, with Bezier curves flattened.
+fn outline(contour: &Contour) -> Vec<Point> {
+    match &contour.beziers {
+        Some(beziers) if !beziers.is_empty() => beziers
+            .iter()
+            .flat_map(|b| (0..BEZIER_STEPS).map(move |i| b.evaluate(i as f32 / BEZIER_STEPS as f32)))
+            .collect(),
+        _ => contour.points.clone(),
+    }
+}
+
+/// Rasterize contours into a mask.
+///
+/// Every contour is treated as a closed polygon. Pixel (x, y) is inside
+/// when the point (x, y) is, the same convention as
+/// [`marching_squares`](super::marching_squares::marching_squares), so
+/// extracted contours fill back to the mask they came from. For paths in
+/// pixel-edge coordinates (e.g. SVG), subtract 0.5 first.
+///
+/// # Arguments
+/// * `contours` - Outlines including holes
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `fill_rule` - How overlapping and nested contours combine
+///
+/// # Returns
+/// Mask (255 = inside, 0 = outside), flattened row-major
+pub fn fill_contours(contours: &[Contour], width: usize, height: usize, fill_rule: FillRule) -> Vec<u8> {
+    // Non-horizontal edges as (upper, lower, winding direction)
+    let edges: Vec<(Point, Point, i32)> = contours
+        .iter()
+        .map(outline)
+        .flat_map(|points| {
+            let n = points.len();
+            (0..n).map(move |i| (points[i], points[(i + 1) % n])).collect::<Vec<_>>()
+        })
+        .filter(|(a, b)| a.y != b.y)
+        .map(|(a, b)| if a.y < b.y { (a, b, 1) } else { (b, a, -1) })
+        .collect();
+
+    let mut mask = vec![0u8; width * height];
+    if width == 0 {
+        return mask;
+    }
+    mask.par_chunks_mut(width).enumerate().for_each(|(y, row)| {
+        let scan = y as f32;
+        // Half-open spans so a vertex on the scanline counts once
+        let mut crossings: Vec<(f32, i32)> = edges
+            .iter()
+            .filter(|(top, bottom, _)| top.y <= scan && scan < bottom.y)
+            .map(|(top, bottom, dir)| (top.x + (scan - top.y) / (bottom.y - top.y) * (bottom.x - top.x), *dir))
+            .collect();
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            let inside = match fill_rule {
+                FillRule::EvenOdd => winding % 2 != 0,
+                FillRule::NonZero => winding != 0,
+            };
+            if !inside {
+                continue;
+            }
+            let start = pair[0].0.ceil().max(0.0) as usize;
+            let end = (pair[1].0.ceil().max(0.0) as usize).min(width);
+            if start < end {
+                row[start..end].fill(255);
+            }
+        }
+    });
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::marching_squares::marching_squares;
+
+    fn rect(x0: f32, y0: f32, x1: f32, y1: f32, clockwise: bool) -> Contour {
+        let mut points = vec![Point::new(x0, y0), Point::new(x1, y0), Point::new(x1, y1), Point::new(x0, y1)];
+        if !clockwise {
+            points.reverse();
+        }
+        Contour::new(points, true)
+    }
+
+    #[test]
+    fn test_fill_rules_and_round_trip() {
+        let count = |mask: &[u8]| mask.iter().filter(|&&v| v == 255).count();
+        // Outer 8x8 area (pixels 1..=8) with a 4x4 hole (pixels 3..=6), same direction
+        let same = [rect(0.5, 0.5, 8.5, 8.5, true), rect(2.5, 2.5, 6.5, 6.5, true)];
+        assert_eq!(count(&fill_contours(&same, 10, 10, FillRule::EvenOdd)), 64 - 16);
+        assert_eq!(count(&fill_contours(&same, 10, 10, FillRule::NonZero)), 64);
+        // Reversed hole is a hole under both rules
+        let opposite = [rect(0.5, 0.5, 8.5, 8.5, true), rect(2.5, 2.5, 6.5, 6.5, false)];
+        assert_eq!(count(&fill_contours(&opposite, 10, 10, FillRule::NonZero)), 64 - 16);
+        assert_eq!(fill_contours(&opposite, 10, 10, FillRule::EvenOdd)[4 * 10 + 4], 0);
+        assert_eq!(FillRule::from_name("even-odd"), Some(FillRule::EvenOdd));
+
+        // Extracted contours of a ring fill back to the ring
+        let ring: Vec<u8> = (0..24 * 24)
+            .map(|i| {
+                let (dx, dy) = ((i % 24) as f32 - 11.5, (i / 24) as f32 - 11.5);
+                let r = (dx * dx + dy * dy).sqrt();
+                if (4.0..9.0).contains(&r) { 255 } else { 0 }
+            })
+            .collect();
+        let contours = marching_squares(&ring, 24, 24, 0.5);
+        assert_eq!(fill_contours(&contours, 24, 24, FillRule::EvenOdd), ring);
+    }
+}
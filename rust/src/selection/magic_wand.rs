@@ -1,9 +1,21 @@
 //! Magic wand selection using flood fill algorithm.
 //!
 //! Selects contiguous regions of similar color based on tolerance.
+//! [`auto_tolerance`] picks the tolerance from the colors around the seed,
+//! and [`magic_wand_select_soft`] anti-aliases the edge of the selection.
 
 use std::collections::VecDeque;
 
+use crate::filters::stylize::otsu_from_histogram;
+
+/// Half size of the window [`auto_tolerance`] analyzes around the seed.
+const AUTO_WINDOW_RADIUS: usize = 10;
+/// Smallest mean distance between the near and far colors of the window
+/// that counts as a second region; below it the window is one noisy region.
+const AUTO_MIN_CONTRAST: f64 = 32.0;
+/// Headroom over the spread of a window that holds a single region.
+const AUTO_NOISE_MARGIN: u8 = 4;
+
 /// Magic wand selection result with metadata.
 pub struct MagicWandResult {
     /// Selection mask (255 = selected, 0 = not selected)
@@ -53,7 +65,8 @@ pub fn magic_wand_select_detailed(
 ) -> MagicWandResult {
     let mut mask = vec![0u8; width * height];
 
-    if width == 0 || height == 0 || start_x >= width || start_y >= height {
+    let too_short = width.checked_mul(height).and_then(|n| n.checked_mul(4)).is_none_or(|n| image.len() < n);
+    if too_short || width == 0 || height == 0 || start_x >= width || start_y >= height {
         return MagicWandResult {
             mask,
             bounds: None,
@@ -150,6 +163,112 @@ pub fn magic_wand_select_detailed(
     }
 }
 
+/// Perform magic wand selection with an anti-aliased edge.
+///
+/// Selects like [`magic_wand_select`], then gives every unselected pixel
+/// next to the selection a fractional coverage: its color is unmixed
+/// between the seed color and the unselected color farthest from the seed
+/// in its 5x5 neighborhood, so a pixel halfway between the two gets 128.
+/// Pure background colors stay 0, and cutouts lose their jagged edges.
+///
+/// # Arguments
+/// Same as [`magic_wand_select`]
+///
+/// # Returns
+/// Soft selection mask (0-255)
+pub fn magic_wand_select_soft(
+    image: &[u8],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    tolerance: u8,
+    contiguous: bool,
+) -> Vec<u8> {
+    let hard = magic_wand_select(image, width, height, start_x, start_y, tolerance, contiguous);
+    if !hard.contains(&255) {
+        return hard;
+    }
+    let pixel = |i: usize| -> [f32; 4] { std::array::from_fn(|c| image[i * 4 + c] as f32) };
+    let seed = pixel(start_y * width + start_x);
+    let distance_sq = |a: &[f32; 4], b: &[f32; 4]| (0..4).map(|c| (a[c] - b[c]).powi(2)).sum::<f32>();
+    let window = |x: usize, y: usize, radius: usize| {
+        (y.saturating_sub(radius)..(y + radius + 1).min(height))
+            .flat_map(move |ny| (x.saturating_sub(radius)..(x + radius + 1).min(width)).map(move |nx| ny * width + nx))
+    };
+
+    let mut mask = hard.clone();
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if hard[i] != 0 || !window(x, y, 1).any(|j| hard[j] != 0) {
+                continue;
+            }
+            let background = window(x, y, 2)
+                .filter(|&j| hard[j] == 0)
+                .map(pixel)
+                .max_by(|a, b| distance_sq(a, &seed).total_cmp(&distance_sq(b, &seed)))
+                .unwrap_or(seed);
+            let span = distance_sq(&seed, &background);
+            if span < 1.0 {
+                continue;
+            }
+            let color = pixel(i);
+            let coverage = (0..4).map(|c| (color[c] - background[c]) * (seed[c] - background[c])).sum::<f32>() / span;
+            mask[i] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+    mask
+}
+
+/// Choose a magic wand tolerance from the colors around the seed.
+///
+/// Builds a histogram of the color distances (largest channel difference,
+/// as the wand measures them) between the seed and every pixel of a
+/// 21x21 window and splits it with Otsu's method. When the window holds
+/// two regions, the tolerance lies halfway between the farthest "near"
+/// distance and the nearest "far" one, so the wand stops at the edge;
+/// when it holds only one, the tolerance covers its spread plus a margin.
+///
+/// # Arguments
+/// * `image` - RGBA image data (4 bytes per pixel)
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `start_x` - Seed X coordinate
+/// * `start_y` - Seed Y coordinate
+///
+/// # Returns
+/// Tolerance (0-255) for [`magic_wand_select`]
+pub fn auto_tolerance(image: &[u8], width: usize, height: usize, start_x: usize, start_y: usize) -> u8 {
+    let too_short = width.checked_mul(height).and_then(|n| n.checked_mul(4)).is_none_or(|n| image.len() < n);
+    if too_short || start_x >= width || start_y >= height {
+        return 0;
+    }
+    let seed = &image[(start_y * width + start_x) * 4..][..4];
+    let mut histogram = [0.0f64; 256];
+    for y in start_y.saturating_sub(AUTO_WINDOW_RADIUS)..(start_y + AUTO_WINDOW_RADIUS + 1).min(height) {
+        for x in start_x.saturating_sub(AUTO_WINDOW_RADIUS)..(start_x + AUTO_WINDOW_RADIUS + 1).min(width) {
+            let pixel = &image[(y * width + x) * 4..][..4];
+            let distance = pixel.iter().zip(seed).map(|(&a, &b)| a.abs_diff(b)).max().unwrap_or(0);
+            histogram[distance as usize] += 1.0;
+        }
+    }
+
+    let split = otsu_from_histogram(&histogram);
+    let occupied = |range: std::ops::Range<usize>| range.filter(|&d| histogram[d] > 0.0);
+    let spread = occupied(0..256).next_back().unwrap_or(0) as u8;
+    let mean = |range: std::ops::Range<usize>| {
+        let (n, sum) = range.fold((0.0, 0.0), |(n, sum), d| (n + histogram[d], sum + histogram[d] * d as f64));
+        if n > 0.0 { sum / n } else { 0.0 }
+    };
+    if split == 0 || mean(split..256) - mean(0..split) < AUTO_MIN_CONTRAST {
+        return spread.saturating_add(AUTO_NOISE_MARGIN);
+    }
+    let near = occupied(0..split).next_back().unwrap_or(0);
+    let far = occupied(split..256).next().unwrap_or(255);
+    ((near + far) / 2) as u8
+}
+
 /// Check if a color matches the reference color within tolerance.
 #[inline]
 fn color_matches(
@@ -303,6 +422,46 @@ mod tests {
         assert!(selected >= 5);
     }
 
+    #[test]
+    fn test_auto_tolerance() {
+        // Noisy red (values 200-215) left of x = 12, blue right of it
+        let (width, height) = (24, 16);
+        let image: Vec<u8> = (0..width * height)
+            .flat_map(|i| {
+                let noise = ((i * 7) % 16) as u8;
+                if i % width < 12 { [200 + noise, noise, 0, 255] } else { [20, noise, 230, 255] }
+            })
+            .collect();
+        let tolerance = auto_tolerance(&image, width, height, 9, 8);
+        assert!((15..180).contains(&tolerance), "tolerance {tolerance}");
+        let mask = magic_wand_select(&image, width, height, 9, 8, tolerance, true);
+        assert!(mask.iter().enumerate().all(|(i, &v)| (v > 0) == (i % width < 12)));
+
+        // A window of one noisy region gets its full spread
+        let red: Vec<u8> = (0..width * height).flat_map(|i| [200 + ((i * 7) % 16) as u8, 0, 0, 255]).collect();
+        let tolerance = auto_tolerance(&red, width, height, 9, 8);
+        assert!(magic_wand_select(&red, width, height, 9, 8, tolerance, true).iter().all(|&v| v == 255));
+    }
+
+    #[test]
+    fn test_soft_edge() {
+        // Red | half red, half blue | blue
+        let (width, height) = (8, 3);
+        let image: Vec<u8> = (0..width * height)
+            .flat_map(|i| match i % width {
+                0..=3 => [255, 0, 0, 255],
+                4 => [128, 0, 127, 255],
+                _ => [0, 0, 255, 255],
+            })
+            .collect();
+        let soft = magic_wand_select_soft(&image, width, height, 0, 1, 10, true);
+        for row in soft.chunks(width) {
+            assert_eq!(&row[..4], &[255; 4]);
+            assert!((126..=130).contains(&row[4]), "edge coverage {}", row[4]);
+            assert_eq!(&row[5..], &[0; 3]);
+        }
+    }
+
     #[test]
     fn test_non_contiguous() {
         // 5x5 image with checkerboard pattern
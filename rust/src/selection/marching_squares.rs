@@ -4,9 +4,13 @@
 //! - **Marching Squares**: Sub-pixel precision contour extraction
 //! - **Douglas-Peucker**: Polyline simplification
 //! - **Bezier Fitting**: Convert polylines to smooth cubic Bezier curves
+//! - **Downsampled tracing**: Trace huge masks at reduced resolution and
+//!   refine the points against the full-resolution mask
 
 use std::collections::HashMap;
 
+use rayon::prelude::*;
+
 /// A 2D point with sub-pixel precision.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Point {
@@ -153,7 +157,7 @@ pub fn marching_squares(
     height: usize,
     threshold: f32,
 ) -> Vec<Contour> {
-    if width < 2 || height < 2 {
+    if width < 2 || height < 2 || width.checked_mul(height).is_none_or(|n| mask.len() < n) {
         return Vec::new();
     }
 
@@ -340,7 +344,9 @@ pub fn douglas_peucker(points: &[Point], epsilon: f32) -> Vec<Point> {
         }
     }
 
-    if max_dist > epsilon {
+    // max_idx == 0 means every point lies on the chord; with a negative
+    // epsilon splitting there would recurse forever
+    if max_dist > epsilon && max_idx > 0 {
         // Recursively simplify both halves
         let mut left = douglas_peucker(&points[..=max_idx], epsilon);
         let right = douglas_peucker(&points[max_idx..], epsilon);
@@ -535,6 +541,185 @@ pub fn extract_contours_precise(
     contours
 }
 
+/// Box-average a mask by `factor` (partial blocks at the right and bottom
+/// average the pixels they cover).
+fn downsample_mask(mask: &[u8], width: usize, height: usize, factor: usize) -> (Vec<u8>, usize, usize) {
+    let (small_w, small_h) = (width.div_ceil(factor), height.div_ceil(factor));
+    let mut small = vec![0u8; small_w * small_h];
+    for sy in 0..small_h {
+        let rows = sy * factor..((sy + 1) * factor).min(height);
+        for sx in 0..small_w {
+            let cols = sx * factor..((sx + 1) * factor).min(width);
+            let sum: u32 = rows.clone().map(|y| mask[y * width + cols.start..y * width + cols.end].iter().map(|&v| v as u32).sum::<u32>()).sum();
+            let count = (rows.len() * cols.len()) as u32;
+            small[sy * small_w + sx] = ((sum + count / 2) / count) as u8;
+        }
+    }
+    (small, small_w, small_h)
+}
+
+/// Bilinear sample of a mask at (x, y), pixel values at integer positions
+/// (the marching squares convention), clamped at the edges.
+fn sample_mask(mask: &[u8], width: usize, height: usize, x: f32, y: f32) -> f32 {
+    let x = x.clamp(0.0, (width - 1) as f32);
+    let y = y.clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+    let at = |xi: usize, yi: usize| mask[yi * width + xi] as f32;
+    let top = at(x0, y0) * (1.0 - tx) + at(x1, y0) * tx;
+    let bottom = at(x0, y1) * (1.0 - tx) + at(x1, y1) * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
+/// Move a point along `normal` onto the nearest threshold crossing of the
+/// full-resolution mask within `reach` pixels; unchanged if there is none.
+fn snap_to_crossing(mask: &[u8], width: usize, height: usize, threshold: f32, point: Point, normal: (f32, f32), reach: f32) -> Point {
+    const STEP: f32 = 0.5;
+    let at = |t: f32| sample_mask(mask, width, height, point.x + normal.0 * t, point.y + normal.1 * t) - threshold;
+    let steps = (reach / STEP).ceil() as i32;
+    let mut best: Option<f32> = None;
+    // Walk outward in both directions; the first crossing found is the nearest
+    for i in 0..steps {
+        for (a, b) in [(i as f32 * STEP, (i + 1) as f32 * STEP), (-(i as f32) * STEP, -((i + 1) as f32) * STEP)] {
+            let (va, vb) = (at(a), at(b));
+            if (va > 0.0) != (vb > 0.0) {
+                let t = a + (b - a) * va / (va - vb);
+                if best.is_none_or(|bt| t.abs() < bt.abs()) {
+                    best = Some(t);
+                }
+            }
+        }
+        if best.is_some() {
+            break;
+        }
+    }
+    best.map_or(point, |t| Point::new(point.x + normal.0 * t, point.y + normal.1 * t))
+}
+
+/// Unit normal of the segment from `a` to `b`, (0, 0) for a degenerate one.
+fn segment_normal(a: &Point, b: &Point) -> (f32, f32) {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 1e-6 {
+        (0.0, 0.0)
+    } else {
+        (-dy / len, dx / len)
+    }
+}
+
+/// Refine a coarse contour (already in full-resolution coordinates) against
+/// the full mask: snap every point onto the threshold crossing, then split
+/// segments whose snapped midpoint leaves the chord by more than
+/// `tolerance`, down to one-pixel segments.
+fn refine_contour(mask: &[u8], width: usize, height: usize, threshold: f32, contour: &Contour, reach: f32, tolerance: f32) -> Contour {
+    let points = &contour.points;
+    let n = points.len();
+    let neighbor = |i: usize, forward: bool| -> &Point {
+        match (forward, contour.is_closed) {
+            (true, true) => &points[(i + 1) % n],
+            (false, true) => &points[(i + n - 1) % n],
+            (true, false) => &points[(i + 1).min(n - 1)],
+            (false, false) => &points[i.saturating_sub(1)],
+        }
+    };
+    let snapped: Vec<Point> = (0..n)
+        .map(|i| snap_to_crossing(mask, width, height, threshold, points[i], segment_normal(neighbor(i, false), neighbor(i, true)), reach))
+        .collect();
+
+    let mut refined = Vec::with_capacity(n * 2);
+    let segments = if contour.is_closed { n } else { n - 1 };
+    for i in 0..segments {
+        let (a, b) = (snapped[i], snapped[(i + 1) % n]);
+        refined.push(a);
+        subdivide(mask, width, height, threshold, a, b, tolerance, &mut refined);
+    }
+    if !contour.is_closed {
+        refined.push(snapped[n - 1]);
+    }
+    Contour::new(refined, contour.is_closed)
+}
+
+/// Insert snapped midpoints between `a` and `b` (exclusive) where the
+/// mask's edge bends away from the chord.
+#[allow(clippy::too_many_arguments)]
+fn subdivide(mask: &[u8], width: usize, height: usize, threshold: f32, a: Point, b: Point, tolerance: f32, out: &mut Vec<Point>) {
+    if a.distance_to(&b) <= 2.0 {
+        return;
+    }
+    let mid = Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+    let normal = segment_normal(&a, &b);
+    let snapped = snap_to_crossing(mask, width, height, threshold, mid, normal, a.distance_to(&b) / 2.0);
+    if snapped.distance_to(&mid) <= tolerance {
+        return;
+    }
+    subdivide(mask, width, height, threshold, a, snapped, tolerance, out);
+    out.push(snapped);
+    subdivide(mask, width, height, threshold, snapped, b, tolerance, out);
+}
+
+/// Extract contours of a huge mask by tracing a downsampled copy.
+///
+/// Marching squares runs on the mask box-averaged by `factor`, so it
+/// produces about `factor` times fewer points. Every point is then snapped
+/// along the contour normal onto the threshold crossing of the
+/// full-resolution mask, and segments are split where the full-resolution
+/// edge bends away from them by more than `tolerance` pixels. Straight
+/// edges stay sparse, detailed ones keep sub-pixel accuracy. Features
+/// smaller than about `factor` pixels may be lost.
+///
+/// # Arguments
+/// * `mask` - Alpha mask (0-255 values)
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `threshold` - Alpha threshold (0.0-1.0)
+/// * `factor` - Downsampling factor (1 = same as [`extract_contours_precise`])
+/// * `tolerance` - Largest distance in pixels between the refined contour and the mask edge
+/// * `simplify_epsilon` - Douglas-Peucker epsilon (0 to skip simplification)
+/// * `fit_beziers` - Whether to fit Bezier curves
+/// * `bezier_smoothness` - Smoothness factor for Bezier fitting (0.1-0.5)
+///
+/// # Returns
+/// Vector of processed contours in full-resolution coordinates.
+#[allow(clippy::too_many_arguments)]
+pub fn extract_contours_downsampled(
+    mask: &[u8],
+    width: usize,
+    height: usize,
+    threshold: f32,
+    factor: usize,
+    tolerance: f32,
+    simplify_epsilon: f32,
+    fit_beziers: bool,
+    bezier_smoothness: f32,
+) -> Vec<Contour> {
+    if factor <= 1 || width.checked_mul(height).is_none_or(|n| mask.len() < n) {
+        return extract_contours_precise(mask, width, height, threshold, simplify_epsilon, fit_beziers, bezier_smoothness);
+    }
+    let (small, small_w, small_h) = downsample_mask(mask, width, height, factor);
+    let coarse = marching_squares(&small, small_w, small_h, threshold);
+
+    // Center of a block in full-resolution coordinates
+    let scale = factor as f32;
+    let offset = (factor - 1) as f32 / 2.0;
+    let threshold_value = (threshold * 255.0) as u8 as f32;
+    let mut contours: Vec<Contour> = coarse
+        .par_iter()
+        .map(|contour| {
+            let points = contour.points.iter().map(|p| Point::new(p.x * scale + offset, p.y * scale + offset)).collect();
+            let full = Contour::new(points, contour.is_closed);
+            refine_contour(mask, width, height, threshold_value, &full, scale, tolerance.max(0.05))
+        })
+        .collect();
+
+    if simplify_epsilon > 0.0 || fit_beziers {
+        for contour in &mut contours {
+            simplify_contour(contour, simplify_epsilon, fit_beziers, bezier_smoothness);
+        }
+    }
+    contours
+}
+
 /// Convert contours to a complete SVG document.
 ///
 /// # Arguments
@@ -635,6 +820,37 @@ pub fn contours_to_flat(contours: &[Contour]) -> Vec<f32> {
     result
 }
 
+/// Parse contours from the flat format written by [`contours_to_flat`].
+///
+/// Truncated input yields the contours that were complete.
+pub fn contours_from_flat(flat: &[f32]) -> Vec<Contour> {
+    let mut contours = Vec::new();
+    let mut values = flat.iter().copied();
+    let count = values.next().map_or(0, |n| n as usize);
+    let mut take = |n: usize| -> Option<Vec<f32>> {
+        let taken: Vec<f32> = values.by_ref().take(n).collect();
+        (taken.len() == n).then_some(taken)
+    };
+    for _ in 0..count {
+        let Some(header) = take(2) else { break };
+        let Some(coords) = take(header[1] as usize * 2) else { break };
+        let mut contour = Contour::new(coords.chunks_exact(2).map(|c| Point::new(c[0], c[1])).collect(), header[0] != 0.0);
+        let Some(has_beziers) = take(1) else { break };
+        if has_beziers[0] != 0.0 {
+            let Some(len) = take(1) else { break };
+            let Some(coords) = take(len[0] as usize * 8) else { break };
+            contour.beziers = Some(
+                coords
+                    .chunks_exact(8)
+                    .map(|c| BezierSegment::new(Point::new(c[0], c[1]), Point::new(c[2], c[3]), Point::new(c[4], c[5]), Point::new(c[6], c[7])))
+                    .collect(),
+            );
+        }
+        contours.push(contour);
+    }
+    contours
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -678,6 +894,34 @@ mod tests {
         assert!(!contours.is_empty());
     }
 
+    #[test]
+    fn test_downsampled_contours_stay_on_full_resolution_edge() {
+        // Anti-aliased disc of radius 40 around (100.3, 90.7)
+        let (width, height) = (200, 180);
+        let (cx, cy, r) = (100.3f32, 90.7f32, 40.0f32);
+        let mask: Vec<u8> = (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as f32, (i / width) as f32);
+                let d = ((x - cx).powi(2) + (y - cy).powi(2)).sqrt();
+                ((r - d + 0.5).clamp(0.0, 1.0) * 255.0).round() as u8
+            })
+            .collect();
+
+        let full = marching_squares(&mask, width, height, 0.5);
+        let coarse = extract_contours_downsampled(&mask, width, height, 0.5, 8, 0.25, 0.0, false, 0.25);
+        assert_eq!(coarse.len(), 1);
+        assert!(coarse[0].is_closed);
+        assert!(coarse[0].points.len() * 2 < full[0].points.len(), "{} vs {}", coarse[0].points.len(), full[0].points.len());
+        for p in &coarse[0].points {
+            let d = ((p.x - cx).powi(2) + (p.y - cy).powi(2)).sqrt();
+            assert!((d - r).abs() < 0.3, "point {:?} at distance {}", p, d);
+        }
+
+        // Factor 1 is the plain pipeline
+        let same = extract_contours_downsampled(&mask, width, height, 0.5, 1, 0.25, 0.5, false, 0.25);
+        assert_eq!(same[0].points, extract_contours_precise(&mask, width, height, 0.5, 0.5, false, 0.25)[0].points);
+    }
+
     #[test]
     fn test_bezier_evaluate() {
         let bez = BezierSegment::new(
@@ -2,19 +2,47 @@
 //!
 //! This module provides cross-platform selection algorithms:
 //! - **Contour extraction**: Basic boundary tracing for marching ants display
-//! - **Marching squares**: Sub-pixel precision contour extraction with simplification
-//! - **Magic wand**: Flood fill based color/tolerance selection
+//! - **Marching squares**: Sub-pixel precision contour extraction with simplification, optionally
+//!   traced downsampled and refined against the full mask for huge masks
+//! - **Path operations**: Union, intersection and subtraction of closed vector contours, stroke outlines
+//! - **Polygon fill**: Scanline fill of contours back into a mask (even-odd or nonzero)
+//! - **Dashes**: Dash polylines of a contour for animating marching ants
+//! - **Components**: Connected component labeling and removal of small specks
+//! - **Magic wand**: Flood fill based color/tolerance selection, with automatic tolerance and anti-aliased edges
+//! - **Distance transform**: Distance of selected pixels to the selection edge
+//! - **Watershed**: Marker-based segmentation, e.g. to split touching blobs
+//! - **Edge snapping**: Moves rough selection boundaries onto nearby strong image edges
+//! - **Tracing**: Multi-color vectorization of whole images into stacked SVG paths
+//! - **Statistics**: Pixel count, coverage, bounds and centroid of a mask or of every label
 //!
-//! Both are used in Stagforge for selection tools and marching ants visualization.
+//! Contours and the magic wand are used in Stagforge for selection tools and marching ants visualization.
 
+pub mod components;
 pub mod contour;
+pub mod dashes;
+pub mod distance;
+pub mod fill;
 pub mod magic_wand;
 pub mod marching_squares;
+pub mod path_ops;
+pub mod refine;
+pub mod stats;
+pub mod trace;
+pub mod watershed;
 
+pub use components::{label_components, remove_small_components};
 pub use contour::extract_contours;
-pub use magic_wand::magic_wand_select;
+pub use dashes::contour_to_dash_segments;
+pub use distance::{distance_transform, DistanceMetric};
+pub use fill::{fill_contours, FillRule};
+pub use magic_wand::{auto_tolerance, magic_wand_select, magic_wand_select_soft};
 pub use marching_squares::{
-    extract_contours_precise, marching_squares, douglas_peucker, douglas_peucker_closed,
-    fit_bezier_curves, contours_to_svg, contours_to_flat, simplify_contour,
+    extract_contours_precise, extract_contours_downsampled, marching_squares, douglas_peucker, douglas_peucker_closed,
+    fit_bezier_curves, contours_to_svg, contours_to_flat, contours_from_flat, simplify_contour,
     Point, BezierSegment, Contour,
 };
+pub use path_ops::{path_boolean, stroke_path_outline, LineCap, LineJoin, PathOp};
+pub use refine::snap_mask_to_edges;
+pub use stats::{label_summaries, mask_summary, MaskSummary};
+pub use trace::trace_image_to_svg;
+pub use watershed::watershed;
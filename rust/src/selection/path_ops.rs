@@ -0,0 +1,708 @@
+//! Boolean operations on closed vector paths.
+//!
+//! [`path_boolean`] combines two sets of closed contours (union,
+//! intersection, difference) on their geometry, so vector selections and
+//! shape layers can be merged without rasterizing them to masks and tracing
+//! the result again. Bezier segments are flattened adaptively to a given
+//! tolerance and the result is returned as polylines; refit curves with
+//! [`simplify_contour`](super::marching_squares::simplify_contour) if needed.
+//!
+//! All edges are split at their mutual intersections, every piece is kept
+//! when the result is inside on exactly one of its sides, and the kept
+//! pieces are chained into loops. Outer boundaries run clockwise on screen
+//! (y pointing down) and holes counter-clockwise, so the result fills the
+//! same under both fill rules.
+//!
+//! [`stroke_path_outline`] turns a stroked path into the filled outline of
+//! its stroke (caps, joins and miter limit as in SVG), for editable vector
+//! strokes and exact SVG export of stroked selections.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use rayon::prelude::*;
+
+use super::fill::FillRule;
+use super::marching_squares::{Contour, Point};
+
+/// Boolean operation combining a subject and a clip path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathOp {
+    /// Area covered by either path
+    #[default]
+    Union,
+    /// Area covered by both paths
+    Intersect,
+    /// Area of the subject not covered by the clip path
+    Subtract,
+}
+
+impl PathOp {
+    /// Parse an operation name ("union", "intersect", "subtract").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "union" | "add" => Some(Self::Union),
+            "intersect" | "intersection" => Some(Self::Intersect),
+            "subtract" | "difference" => Some(Self::Subtract),
+            _ => None,
+        }
+    }
+
+    fn combine(self, subject: bool, clip: bool) -> bool {
+        match self {
+            Self::Union => subject || clip,
+            Self::Intersect => subject && clip,
+            Self::Subtract => subject && !clip,
+        }
+    }
+}
+
+type P = (f64, f64);
+
+fn sub(a: P, b: P) -> P {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn cross(a: P, b: P) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn dot(a: P, b: P) -> f64 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+fn lerp(a: P, b: P, t: f64) -> P {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+fn distance_to_chord(p: P, a: P, b: P) -> f64 {
+    let ab = sub(b, a);
+    let len = dot(ab, ab).sqrt();
+    if len < 1e-12 {
+        dot(sub(p, a), sub(p, a)).sqrt()
+    } else {
+        cross(ab, sub(p, a)).abs() / len
+    }
+}
+
+/// Append a flattened cubic Bezier (without its start point) so that no
+/// control point lies further than `tolerance` from its chord.
+fn flatten_bezier(c: [P; 4], tolerance: f64, depth: u32, out: &mut Vec<P>) {
+    let flat = distance_to_chord(c[1], c[0], c[3]).max(distance_to_chord(c[2], c[0], c[3])) <= tolerance;
+    if flat || depth >= 16 {
+        out.push(c[3]);
+        return;
+    }
+    // de Casteljau split at t = 0.5
+    let (ab, bc, cd) = (lerp(c[0], c[1], 0.5), lerp(c[1], c[2], 0.5), lerp(c[2], c[3], 0.5));
+    let (abc, bcd) = (lerp(ab, bc, 0.5), lerp(bc, cd, 0.5));
+    let mid = lerp(abc, bcd, 0.5);
+    flatten_bezier([c[0], ab, abc, mid], tolerance, depth + 1, out);
+    flatten_bezier([mid, bcd, cd, c[3]], tolerance, depth + 1, out);
+}
+
+/// Vertices of a contour, with curves flattened and repeated points removed.
+fn polyline(contour: &Contour, tolerance: f64) -> Vec<P> {
+    let p = |pt: &Point| (pt.x as f64, pt.y as f64);
+    let mut points = match &contour.beziers {
+        Some(beziers) if !beziers.is_empty() => {
+            let mut out = vec![p(&beziers[0].p0)];
+            for b in beziers {
+                flatten_bezier([p(&b.p0), p(&b.p1), p(&b.p2), p(&b.p3)], tolerance, 0, &mut out);
+            }
+            out
+        }
+        _ => contour.points.iter().map(p).collect(),
+    };
+    points.dedup();
+    points
+}
+
+/// Closed polygon of a contour, empty when it encloses nothing.
+fn polygon(contour: &Contour, tolerance: f64) -> Vec<P> {
+    let mut points = polyline(contour, tolerance);
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 3 {
+        points.clear();
+    }
+    points
+}
+
+/// Edges of one operand bucketed into horizontal bands for point-in-path queries.
+struct WindingIndex {
+    edges: Vec<(P, P)>,
+    top: f64,
+    band_height: f64,
+    bands: Vec<Vec<usize>>,
+    fill_rule: FillRule,
+}
+
+impl WindingIndex {
+    fn new(polygons: &[Vec<P>], fill_rule: FillRule) -> Self {
+        let edges: Vec<(P, P)> = polygons
+            .iter()
+            .flat_map(|poly| (0..poly.len()).map(move |i| (poly[i], poly[(i + 1) % poly.len()])))
+            .filter(|(a, b)| a.1 != b.1)
+            .collect();
+        let top = edges.iter().map(|(a, b)| a.1.min(b.1)).fold(f64::INFINITY, f64::min);
+        let bottom = edges.iter().map(|(a, b)| a.1.max(b.1)).fold(f64::NEG_INFINITY, f64::max);
+        let count = ((edges.len() as f64).sqrt().ceil() as usize).max(1);
+        let band_height = ((bottom - top) / count as f64).max(1e-9);
+        let mut index = Self { edges, top, band_height, bands: vec![Vec::new(); count], fill_rule };
+        for (i, (a, b)) in index.edges.iter().enumerate() {
+            let (first, last) = (index.band(a.1.min(b.1)), index.band(a.1.max(b.1)));
+            for band in &mut index.bands[first..=last] {
+                band.push(i);
+            }
+        }
+        index
+    }
+
+    fn band(&self, y: f64) -> usize {
+        (((y - self.top) / self.band_height).max(0.0) as usize).min(self.bands.len() - 1)
+    }
+
+    fn contains(&self, p: P) -> bool {
+        if self.edges.is_empty() {
+            return false;
+        }
+        let mut winding = 0;
+        for &i in &self.bands[self.band(p.1)] {
+            let (a, b) = self.edges[i];
+            if a.1 <= p.1 {
+                if b.1 > p.1 && cross(sub(b, a), sub(p, a)) > 0.0 {
+                    winding += 1;
+                }
+            } else if b.1 <= p.1 && cross(sub(b, a), sub(p, a)) < 0.0 {
+                winding -= 1;
+            }
+        }
+        match self.fill_rule {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// Split parameters where edge `j` meets edge `i`, as (edge, t, point).
+fn intersect_edges(i: usize, e: (P, P), j: usize, f: (P, P), out: &mut Vec<(usize, f64, P)>) {
+    const EPS: f64 = 1e-9;
+    let (r, s) = (sub(e.1, e.0), sub(f.1, f.0));
+    let qp = sub(f.0, e.0);
+    let denom = cross(r, s);
+    let (rr, ss) = (dot(r, r), dot(s, s));
+    if denom.abs() > EPS * (rr * ss).sqrt() {
+        let t = cross(qp, s) / denom;
+        let u = cross(qp, r) / denom;
+        if !(-EPS..=1.0 + EPS).contains(&t) || !(-EPS..=1.0 + EPS).contains(&u) {
+            return;
+        }
+        // Prefer an existing endpoint so both edges split at the same vertex
+        let point = if t <= EPS {
+            e.0
+        } else if t >= 1.0 - EPS {
+            e.1
+        } else if u <= EPS {
+            f.0
+        } else if u >= 1.0 - EPS {
+            f.1
+        } else {
+            lerp(e.0, e.1, t)
+        };
+        out.push((i, t.clamp(0.0, 1.0), point));
+        out.push((j, u.clamp(0.0, 1.0), point));
+    } else if rr > 0.0 && cross(qp, r).abs() / rr.sqrt() <= 1e-6 {
+        // Collinear: split each edge at the other's endpoints
+        for (edge, dir, len2, origin, other) in [(i, r, rr, e.0, f), (j, s, ss, f.0, e)] {
+            for p in [other.0, other.1] {
+                let t = dot(sub(p, origin), dir) / len2;
+                if t > EPS && t < 1.0 - EPS {
+                    out.push((edge, t, p));
+                }
+            }
+        }
+    }
+}
+
+/// Combine two sets of closed contours with a boolean operation.
+///
+/// Open contours are treated as closed, as in
+/// [`fill_contours`](super::fill::fill_contours).
+///
+/// # Arguments
+/// * `subject` - Outlines (and holes) of the first operand
+/// * `clip` - Outlines (and holes) of the second operand
+/// * `op` - Union, intersection or subtraction (subject minus clip)
+/// * `fill_rule` - How overlapping and nested contours of each operand combine
+/// * `tolerance` - Largest distance in pixels between a Bezier curve and its flattened polyline
+///
+/// # Returns
+/// Closed polyline contours of the result; outer boundaries clockwise on
+/// screen, holes counter-clockwise
+pub fn path_boolean(subject: &[Contour], clip: &[Contour], op: PathOp, fill_rule: FillRule, tolerance: f32) -> Vec<Contour> {
+    let tolerance = (tolerance as f64).max(1e-3);
+    let flatten = |contours: &[Contour]| -> Vec<Vec<P>> {
+        contours.iter().map(|c| polygon(c, tolerance)).filter(|p| !p.is_empty()).collect()
+    };
+    boolean_polygons(&flatten(subject), &flatten(clip), op, fill_rule)
+}
+
+fn boolean_polygons(subject: &[Vec<P>], clip: &[Vec<P>], op: PathOp, fill_rule: FillRule) -> Vec<Contour> {
+    let edges: Vec<(P, P)> = subject
+        .iter()
+        .chain(clip)
+        .flat_map(|poly| (0..poly.len()).map(move |i| (poly[i], poly[(i + 1) % poly.len()])))
+        .collect();
+
+    // Sweep over x extents to find intersecting edge pairs
+    let min_x = |e: &(P, P)| e.0 .0.min(e.1 .0);
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&a, &b| min_x(&edges[a]).total_cmp(&min_x(&edges[b])));
+    let splits: Vec<(usize, f64, P)> = (0..order.len())
+        .into_par_iter()
+        .flat_map_iter(|k| {
+            let i = order[k];
+            let e = edges[i];
+            let max_x = e.0 .0.max(e.1 .0);
+            let (top, bottom) = (e.0 .1.min(e.1 .1), e.0 .1.max(e.1 .1));
+            let mut found = Vec::new();
+            for &j in order[k + 1..].iter().take_while(|&&j| min_x(&edges[j]) <= max_x) {
+                let f = edges[j];
+                if f.0 .1.max(f.1 .1) >= top && f.0 .1.min(f.1 .1) <= bottom {
+                    intersect_edges(i, e, j, f, &mut found);
+                }
+            }
+            found
+        })
+        .collect();
+    let mut cuts: Vec<Vec<(f64, P)>> = vec![Vec::new(); edges.len()];
+    for (edge, t, p) in splits {
+        cuts[edge].push((t, p));
+    }
+
+    // Shared vertices, merged on a fine grid
+    let mut vertices: Vec<P> = Vec::new();
+    let mut lookup: HashMap<(i64, i64), usize> = HashMap::new();
+    let mut vertex = |p: P| -> usize {
+        let key = ((p.0 * 1e6).round() as i64, (p.1 * 1e6).round() as i64);
+        *lookup.entry(key).or_insert_with(|| {
+            vertices.push(p);
+            vertices.len() - 1
+        })
+    };
+    // Undirected pieces; overlapping edges of both operands collapse into
+    // one. Ordered so the output doesn't depend on hashing.
+    let mut pieces: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for (edge, cut) in edges.iter().zip(&mut cuts) {
+        cut.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let chain: Vec<usize> = std::iter::once(edge.0)
+            .chain(cut.iter().map(|&(_, p)| p))
+            .chain(std::iter::once(edge.1))
+            .map(&mut vertex)
+            .collect();
+        for pair in chain.windows(2) {
+            if pair[0] != pair[1] {
+                pieces.insert((pair[0].min(pair[1]), pair[0].max(pair[1])));
+            }
+        }
+    }
+
+    // Keep pieces with the result inside on exactly one side, directed so
+    // the inside lies on the side of (-dy, dx)
+    let (subject_index, clip_index) = (WindingIndex::new(subject, fill_rule), WindingIndex::new(clip, fill_rule));
+    let inside = |p: P| op.combine(subject_index.contains(p), clip_index.contains(p));
+    let pieces: Vec<(usize, usize)> = pieces.into_iter().collect();
+    let kept: Vec<(usize, usize)> = pieces
+        .par_iter()
+        .filter_map(|&(u, v)| {
+            let (a, b) = (vertices[u], vertices[v]);
+            let d = sub(b, a);
+            let len = dot(d, d).sqrt();
+            let eps = (len * 0.01).min(1e-4);
+            let normal = (-d.1 / len * eps, d.0 / len * eps);
+            let mid = lerp(a, b, 0.5);
+            let left = inside((mid.0 + normal.0, mid.1 + normal.1));
+            let right = inside((mid.0 - normal.0, mid.1 - normal.1));
+            match (left, right) {
+                (true, false) => Some((u, v)),
+                (false, true) => Some((v, u)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    chain_loops(&vertices, &kept)
+}
+
+/// Shape at the open ends of a stroked path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// Stroke ends flush with the endpoint
+    #[default]
+    Butt,
+    /// Half circle around the endpoint
+    Round,
+    /// Stroke extends half its width past the endpoint
+    Square,
+}
+
+impl LineCap {
+    /// Parse a cap name ("butt", "round", "square").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "butt" | "flat" => Some(Self::Butt),
+            "round" => Some(Self::Round),
+            "square" => Some(Self::Square),
+            _ => None,
+        }
+    }
+}
+
+/// Shape at the corners of a stroked path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// Outer edges extended to a point, beveled beyond the miter limit
+    #[default]
+    Miter,
+    /// Circular arc around the corner
+    Round,
+    /// Outer edges connected by a straight line
+    Bevel,
+}
+
+impl LineJoin {
+    /// Parse a join name ("miter", "round", "bevel").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "miter" | "mitre" => Some(Self::Miter),
+            "round" => Some(Self::Round),
+            "bevel" => Some(Self::Bevel),
+            _ => None,
+        }
+    }
+}
+
+/// Flattening tolerance in pixels for curves and round joins of strokes.
+const STROKE_TOLERANCE: f64 = 0.05;
+
+/// Polygon approximating a circle within [`STROKE_TOLERANCE`].
+fn circle(center: P, radius: f64) -> Vec<P> {
+    let steps = if radius > STROKE_TOLERANCE {
+        (std::f64::consts::PI / (1.0 - STROKE_TOLERANCE / radius).acos()).ceil() as usize
+    } else {
+        8
+    }
+    .clamp(8, 512);
+    (0..steps)
+        .map(|i| {
+            let angle = i as f64 / steps as f64 * std::f64::consts::TAU;
+            (center.0 + radius * angle.cos(), center.1 + radius * angle.sin())
+        })
+        .collect()
+}
+
+fn offset(p: P, d: P, scale: f64) -> P {
+    (p.0 + d.0 * scale, p.1 + d.1 * scale)
+}
+
+/// Convert a stroked path into the filled outline of its stroke.
+///
+/// The outline is the union of one rectangle per segment plus the joins
+/// and caps, so self-overlapping strokes come out as a single region. A
+/// closed path yields an outer outline and a hole. Bezier segments are
+/// flattened to 0.05 pixels.
+///
+/// # Arguments
+/// * `path` - Polyline or Bezier path; `is_closed` decides between joins and caps at its ends
+/// * `width` - Stroke width in pixels
+/// * `cap` - Shape of the open ends
+/// * `join` - Shape of the corners
+/// * `miter_limit` - Largest ratio of miter length to stroke width before a miter is beveled (SVG semantics)
+///
+/// # Returns
+/// Closed outline contours, filled with either fill rule
+pub fn stroke_path_outline(path: &Contour, width: f32, cap: LineCap, join: LineJoin, miter_limit: f32) -> Vec<Contour> {
+    let half = width as f64 / 2.0;
+    let mut points = polyline(path, STROKE_TOLERANCE);
+    let closed = path.is_closed && points.len() >= 3;
+    if closed && points.first() == points.last() {
+        points.pop();
+    }
+    if half <= 0.0 || points.is_empty() {
+        return Vec::new();
+    }
+
+    let mut pieces: Vec<Vec<P>> = Vec::new();
+    if points.len() == 1 {
+        // A dot only shows with caps that reach past the endpoint
+        let c = points[0];
+        match cap {
+            LineCap::Butt => {}
+            LineCap::Round => pieces.push(circle(c, half)),
+            LineCap::Square => pieces.push(vec![(c.0 - half, c.1 - half), (c.0 + half, c.1 - half), (c.0 + half, c.1 + half), (c.0 - half, c.1 + half)]),
+        }
+    }
+
+    let n = points.len();
+    let segments = if closed { n } else { n - 1 };
+    // Unit direction and unit normal (-dy, dx) of every segment
+    let frames: Vec<(P, P)> = (0..segments)
+        .map(|i| {
+            let d = sub(points[(i + 1) % n], points[i]);
+            let len = dot(d, d).sqrt();
+            let d = (d.0 / len, d.1 / len);
+            (d, (-d.1, d.0))
+        })
+        .collect();
+    for (i, &(_, normal)) in frames.iter().enumerate() {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        pieces.push(vec![offset(a, normal, half), offset(b, normal, half), offset(b, normal, -half), offset(a, normal, -half)]);
+    }
+
+    let corners = if closed { 0..n } else { 1..n - 1 };
+    for v in corners {
+        let ((d0, n0), (d1, n1)) = (frames[(v + segments - 1) % segments], frames[v % segments]);
+        let turn = cross(d0, d1);
+        if turn.abs() < 1e-9 && dot(d0, d1) > 0.0 {
+            continue;
+        }
+        let vertex = points[v];
+        // The outer side of the corner is opposite to the turn
+        let side = if turn > 0.0 { -half } else { half };
+        let (outer0, outer1) = (offset(vertex, n0, side), offset(vertex, n1, side));
+        match join {
+            LineJoin::Round => pieces.push(circle(vertex, half)),
+            LineJoin::Bevel => pieces.push(vec![vertex, outer0, outer1]),
+            LineJoin::Miter => {
+                let bisector = (n0.0 + n1.0, n0.1 + n1.1);
+                let len = dot(bisector, bisector).sqrt();
+                // Miter length over stroke width is 1 / cos(turn angle / 2)
+                let cos_half = len / 2.0;
+                if cos_half > 1e-9 && 1.0 / cos_half <= miter_limit as f64 {
+                    let tip = offset(vertex, (bisector.0 / len, bisector.1 / len), side / cos_half);
+                    pieces.push(vec![vertex, outer0, tip, outer1]);
+                } else {
+                    pieces.push(vec![vertex, outer0, outer1]);
+                }
+            }
+        }
+    }
+
+    if !closed && n > 1 {
+        let (d_start, n_start) = frames[0];
+        let (d_end, n_end) = frames[segments - 1];
+        for (end, d, normal) in [(points[0], (-d_start.0, -d_start.1), n_start), (points[n - 1], d_end, n_end)] {
+            match cap {
+                LineCap::Butt => {}
+                LineCap::Round => pieces.push(circle(end, half)),
+                LineCap::Square => {
+                    let tip = offset(end, d, half);
+                    pieces.push(vec![offset(end, normal, half), offset(tip, normal, half), offset(tip, normal, -half), offset(end, normal, -half)]);
+                }
+            }
+        }
+    }
+
+    // Uniform winding so the nonzero union merges all pieces
+    for piece in &mut pieces {
+        let m = piece.len();
+        let area: f64 = (0..m).map(|i| cross(piece[i], piece[(i + 1) % m])).sum();
+        if area < 0.0 {
+            piece.reverse();
+        }
+    }
+    pieces.retain(|piece| {
+        let m = piece.len();
+        (0..m).map(|i| cross(piece[i], piece[(i + 1) % m])).sum::<f64>().abs() > 1e-12
+    });
+    boolean_polygons(&pieces, &[], PathOp::Union, FillRule::NonZero)
+}
+
+/// Chain directed boundary pieces into closed loops.
+fn chain_loops(vertices: &[P], pieces: &[(usize, usize)]) -> Vec<Contour> {
+    let mut outgoing: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, &(u, _)) in pieces.iter().enumerate() {
+        outgoing.entry(u).or_default().push(i);
+    }
+    let mut used = vec![false; pieces.len()];
+    let mut contours = Vec::new();
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start = pieces[first].0;
+        let mut loop_points = vec![vertices[start]];
+        let mut current = first;
+        while pieces[current].1 != start {
+            let (from, at) = pieces[current];
+            loop_points.push(vertices[at]);
+            let incoming = sub(vertices[at], vertices[from]);
+            // Where several loops touch, take the sharpest turn towards the
+            // inside so touching loops stay separate
+            let next = outgoing.get(&at).and_then(|candidates| {
+                candidates.iter().copied().filter(|&c| !used[c]).max_by(|&a, &b| {
+                    let turn = |c: usize| {
+                        let out = sub(vertices[pieces[c].1], vertices[at]);
+                        cross(incoming, out).atan2(dot(incoming, out))
+                    };
+                    turn(a).total_cmp(&turn(b))
+                })
+            });
+            let Some(next) = next else { break };
+            used[next] = true;
+            current = next;
+        }
+        let points = drop_collinear(loop_points);
+        if points.len() >= 3 {
+            contours.push(Contour::new(points.iter().map(|&(x, y)| Point::new(x as f32, y as f32)).collect(), true));
+        }
+    }
+    contours
+}
+
+/// Remove vertices that continue their neighbors' line, e.g. split points
+/// on edges that ended up unchanged.
+fn drop_collinear(mut points: Vec<P>) -> Vec<P> {
+    let mut changed = true;
+    while changed && points.len() >= 3 {
+        changed = false;
+        let n = points.len();
+        let keep: Vec<bool> = (0..n)
+            .map(|i| {
+                let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+                let (a, b) = (sub(p, prev), sub(next, p));
+                cross(a, b).abs() > 1e-9 * (dot(a, a) * dot(b, b)).sqrt() || dot(a, b) < 0.0
+            })
+            .collect();
+        if keep.iter().any(|k| !k) {
+            changed = true;
+            let mut i = 0;
+            points.retain(|_| {
+                i += 1;
+                keep[i - 1]
+            });
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::fill::fill_contours;
+    use crate::selection::marching_squares::{contours_from_flat, contours_to_flat, BezierSegment};
+
+    fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Contour {
+        Contour::new(vec![Point::new(x0, y0), Point::new(x1, y0), Point::new(x1, y1), Point::new(x0, y1)], true)
+    }
+
+    fn area(contours: &[Contour]) -> f32 {
+        contours
+            .iter()
+            .map(|c| {
+                let n = c.points.len();
+                (0..n).map(|i| c.points[i].x * c.points[(i + 1) % n].y - c.points[(i + 1) % n].x * c.points[i].y).sum::<f32>() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_boolean_ops_match_mask_ops() {
+        let a = [rect(1.5, 1.5, 20.5, 14.5)];
+        let b = [rect(10.5, 6.5, 30.5, 26.5)];
+        let (w, h) = (32, 32);
+        let mask_a = fill_contours(&a, w, h, FillRule::NonZero);
+        let mask_b = fill_contours(&b, w, h, FillRule::NonZero);
+
+        for (op, expected_area) in [(PathOp::Union, 19.0 * 13.0 + 400.0 - 80.0), (PathOp::Intersect, 80.0), (PathOp::Subtract, 19.0 * 13.0 - 80.0)] {
+            let result = path_boolean(&a, &b, op, FillRule::NonZero, 0.1);
+            assert!((area(&result) - expected_area).abs() < 1e-3, "{:?}: area {}", op, area(&result));
+            let mask = fill_contours(&result, w, h, FillRule::NonZero);
+            for i in 0..w * h {
+                assert_eq!(mask[i] == 255, op.combine(mask_a[i] == 255, mask_b[i] == 255), "{:?} at {}", op, i);
+            }
+        }
+
+        // Subtracting an inner rect leaves an outline and a reversed hole
+        let holed = path_boolean(&a, &[rect(5.0, 5.0, 9.0, 9.0)], PathOp::Subtract, FillRule::NonZero, 0.1);
+        assert_eq!(holed.len(), 2);
+        assert!((area(&holed) - (19.0 * 13.0 - 16.0)).abs() < 1e-3);
+        // Touching rects merge into one outline
+        assert_eq!(path_boolean(&a, &[rect(20.5, 1.5, 25.0, 14.5)], PathOp::Union, FillRule::NonZero, 0.1).len(), 1);
+        assert_eq!(PathOp::from_name("difference"), Some(PathOp::Subtract));
+    }
+
+    #[test]
+    fn test_boolean_output_is_deterministic() {
+        let points = |contours: Vec<Contour>| contours.into_iter().map(|c| c.points).collect::<Vec<_>>();
+        let (a, b) = ([rect(0.0, 0.0, 10.0, 10.0), rect(20.0, 0.0, 30.0, 10.0)], [rect(5.0, 5.0, 25.0, 15.0)]);
+        let first = points(path_boolean(&a, &b, PathOp::Union, FillRule::NonZero, 0.1));
+        for _ in 0..8 {
+            assert_eq!(points(path_boolean(&a, &b, PathOp::Union, FillRule::NonZero, 0.1)), first);
+        }
+    }
+
+    #[test]
+    fn test_bezier_circles_intersect() {
+        // Circle of radius 10 from four Bezier quarter arcs
+        let circle = |cx: f32, cy: f32| {
+            let (r, k) = (10.0, 10.0 * 0.552_284_8);
+            let p = |x: f32, y: f32| Point::new(cx + x, cy + y);
+            let beziers = vec![
+                BezierSegment::new(p(r, 0.0), p(r, k), p(k, r), p(0.0, r)),
+                BezierSegment::new(p(0.0, r), p(-k, r), p(-r, k), p(-r, 0.0)),
+                BezierSegment::new(p(-r, 0.0), p(-r, -k), p(-k, -r), p(0.0, -r)),
+                BezierSegment::new(p(0.0, -r), p(k, -r), p(r, -k), p(r, 0.0)),
+            ];
+            let mut contour = Contour::new(beziers.iter().map(|b| b.p0).collect(), true);
+            contour.beziers = Some(beziers);
+            contour
+        };
+        // Curves survive the flat wasm format
+        let flat = contours_from_flat(&contours_to_flat(&[circle(20.0, 20.0)]));
+        assert_eq!(flat[0].beziers.as_ref().map(|b| b[1].p2), Some(circle(20.0, 20.0).beziers.unwrap()[1].p2));
+
+        let lens = path_boolean(&[circle(20.0, 20.0)], &[circle(30.0, 20.0)], PathOp::Intersect, FillRule::NonZero, 0.01);
+        assert_eq!(lens.len(), 1);
+        // Lens of two unit-distance circles: 2 r^2 acos(d / 2r) - d/2 sqrt(4r^2 - d^2)
+        let expected = 2.0 * 100.0 * 0.5f32.acos() - 5.0 * 300.0f32.sqrt();
+        assert!((area(&lens).abs() - expected).abs() < 0.5, "area {}", area(&lens));
+    }
+
+    #[test]
+    fn test_stroke_outline_caps_and_joins() {
+        let open = |points: &[(f32, f32)]| Contour::new(points.iter().map(|&(x, y)| Point::new(x, y)).collect(), false);
+        let line = open(&[(0.0, 0.0), (10.0, 0.0)]);
+        let stroke_area = |path: &Contour, cap, join, limit| area(&stroke_path_outline(path, 2.0, cap, join, limit));
+        assert!((stroke_area(&line, LineCap::Butt, LineJoin::Miter, 4.0) - 20.0).abs() < 1e-3);
+        assert!((stroke_area(&line, LineCap::Square, LineJoin::Miter, 4.0) - 24.0).abs() < 1e-3);
+        // Round shapes are inscribed polygons, slightly smaller than the circle
+        assert!((stroke_area(&line, LineCap::Round, LineJoin::Miter, 4.0) - (20.0 + std::f32::consts::PI)).abs() < 0.25);
+
+        // Right angle: miter fills the outer corner, bevel half of it, and
+        // a miter limit below sqrt(2) falls back to the bevel
+        let corner = open(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+        assert!((stroke_area(&corner, LineCap::Butt, LineJoin::Miter, 4.0) - 40.0).abs() < 1e-3);
+        assert!((stroke_area(&corner, LineCap::Butt, LineJoin::Bevel, 4.0) - 39.5).abs() < 1e-3);
+        assert!((stroke_area(&corner, LineCap::Butt, LineJoin::Miter, 1.2) - 39.5).abs() < 1e-3);
+        assert!((stroke_area(&corner, LineCap::Butt, LineJoin::Round, 4.0) - (39.0 + std::f32::consts::FRAC_PI_4)).abs() < 0.25);
+
+        // Closed square: outer outline plus a hole
+        let square = Contour::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0), Point::new(10.0, 10.0), Point::new(0.0, 10.0)], true);
+        let ring = stroke_path_outline(&square, 2.0, LineCap::Butt, LineJoin::Miter, 4.0);
+        assert_eq!(ring.len(), 2);
+        assert!((area(&ring) - (144.0 - 64.0)).abs() < 1e-3);
+        assert_eq!(LineJoin::from_name("mitre"), Some(LineJoin::Miter));
+    }
+
+    #[test]
+    fn test_stroke_outline_is_deterministic() {
+        let path = Contour::new(vec![Point::new(0.0, 0.0), Point::new(20.0, 5.0), Point::new(5.0, 15.0), Point::new(25.0, 20.0)], false);
+        let outline = || stroke_path_outline(&path, 3.0, LineCap::Round, LineJoin::Miter, 4.0).into_iter().map(|c| c.points).collect::<Vec<_>>();
+        let first = outline();
+        assert_eq!(outline(), first);
+        assert_eq!(outline(), first);
+    }
+}
@@ -0,0 +1,190 @@
+//! Snapping rough selections to image edges.
+//!
+//! [`snap_mask_to_edges`] lets the boundary of a mask move to the
+//! strongest nearby image edge, like an active contour attracted by the
+//! gradient: pixels deeper than the snap distance inside and outside the
+//! selection become watershed markers, and the band between them is
+//! flooded over the color gradient magnitude. The floods meet on the
+//! highest ridge, i.e. the strongest edge in the band. A faint ridge along
+//! the original boundary, and discarding changes that touch no edge, keep
+//! the boundary in place where the band holds no edge.
+
+use crate::selection::components::label_components;
+use crate::selection::distance::{distance_transform, DistanceMetric};
+use crate::selection::watershed::watershed;
+
+/// Distance in pixels the boundary may move at strength 1.0.
+pub const MAX_SNAP_DISTANCE: f32 = 16.0;
+
+/// Height of the ridge along the original boundary; edges weaker than
+/// this (as a fraction of full contrast) don't attract the boundary.
+const BOUNDARY_BIAS: f32 = 0.1;
+
+/// Largest Sobel gradient magnitude over the RGBA channels, 0.0-1.0.
+fn edge_magnitude(image: &[u8], width: usize, height: usize) -> Vec<f32> {
+    let at = |x: isize, y: isize, c: usize| {
+        let x = x.clamp(0, width as isize - 1) as usize;
+        let y = y.clamp(0, height as isize - 1) as usize;
+        image[(y * width + x) * 4 + c] as f32
+    };
+    let mut magnitude = vec![0.0; width * height];
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let mut strongest: f32 = 0.0;
+            for c in 0..4 {
+                let gx = at(x + 1, y - 1, c) + 2.0 * at(x + 1, y, c) + at(x + 1, y + 1, c)
+                    - at(x - 1, y - 1, c) - 2.0 * at(x - 1, y, c) - at(x - 1, y + 1, c);
+                let gy = at(x - 1, y + 1, c) + 2.0 * at(x, y + 1, c) + at(x + 1, y + 1, c)
+                    - at(x - 1, y - 1, c) - 2.0 * at(x, y - 1, c) - at(x + 1, y - 1, c);
+                strongest = strongest.max((gx * gx + gy * gy).sqrt());
+            }
+            // A full-contrast step gives 4 * 255
+            magnitude[y as usize * width + x as usize] = (strongest / 1020.0).min(1.0);
+        }
+    }
+    magnitude
+}
+
+/// Assign pixels on an edge to the side whose color they match.
+///
+/// A Sobel ridge is two pixels wide, and the floods meeting on it split it
+/// arbitrarily. Each ridge pixel instead takes the label whose off-ridge
+/// pixels in its 5x5 neighborhood are closer in color on average.
+fn settle_ridge_pixels(labels: &mut [u32], edges: &[f32], image: &[u8], width: usize, height: usize) {
+    let flooded = labels.to_vec();
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if edges[i] <= BOUNDARY_BIAS {
+                continue;
+            }
+            let mut sums = [[0.0f32; 4]; 2];
+            let mut counts = [0usize; 2];
+            for ny in y.saturating_sub(2)..(y + 3).min(height) {
+                for nx in x.saturating_sub(2)..(x + 3).min(width) {
+                    let j = ny * width + nx;
+                    if edges[j] > 0.5 * edges[i] || !(1..=2).contains(&flooded[j]) {
+                        continue;
+                    }
+                    let side = flooded[j] as usize - 1;
+                    counts[side] += 1;
+                    for c in 0..4 {
+                        sums[side][c] += image[j * 4 + c] as f32;
+                    }
+                }
+            }
+            if counts[0] == 0 || counts[1] == 0 {
+                continue;
+            }
+            let distance = |side: usize| {
+                (0..4).map(|c| (sums[side][c] / counts[side] as f32 - image[i * 4 + c] as f32).powi(2)).sum::<f32>()
+            };
+            labels[i] = if distance(0) <= distance(1) { 1 } else { 2 };
+        }
+    }
+}
+
+/// Move the boundary of a selection to nearby strong image edges.
+///
+/// # Arguments
+/// * `mask` - Selection mask (>= 128 = selected, flattened row-major)
+/// * `image` - RGBA image data (4 bytes per pixel)
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `strength` - Snap distance as a fraction (0.0-1.0) of
+///   [`MAX_SNAP_DISTANCE`]
+///
+/// # Returns
+/// Refined selection mask (255 = selected, 0 = not selected)
+pub fn snap_mask_to_edges(mask: &[u8], image: &[u8], width: usize, height: usize, strength: f32) -> Vec<u8> {
+    let n = width * height;
+    if mask.len() < n || image.len() < n * 4 || n == 0 {
+        return vec![0; n];
+    }
+    let binary: Vec<u8> = mask[..n].iter().map(|&v| if v >= 128 { 255 } else { 0 }).collect();
+    let radius = strength.clamp(0.0, 1.0) * MAX_SNAP_DISTANCE;
+    if radius < 1.0 {
+        return binary;
+    }
+
+    let inverted: Vec<u8> = binary.iter().map(|&v| 255 - v).collect();
+    let inside = distance_transform(&binary, width, height, DistanceMetric::Euclidean);
+    let outside = distance_transform(&inverted, width, height, DistanceMetric::Euclidean);
+    let edges = edge_magnitude(image, width, height);
+
+    // Marker depth per side, so selections thinner than the snap distance
+    // still keep a core
+    let deepest = |distances: &[f32]| distances.iter().fold(0.0f32, |a, &d| a.max(d - 0.5));
+    let depth = [radius.min(deepest(&inside) - 1.0), radius.min(deepest(&outside) - 1.0)];
+
+    let mut markers = vec![0u32; n];
+    let mut surface = vec![0.0f32; n];
+    for i in 0..n {
+        // Distance from the pixel center to the original boundary
+        let selected = binary[i] > 0;
+        let distance = if selected { inside[i] } else { outside[i] } - 0.5;
+        if distance > depth[usize::from(!selected)] {
+            markers[i] = if selected { 1 } else { 2 };
+        }
+        surface[i] = edges[i] + BOUNDARY_BIAS * (1.0 - distance / radius).max(0.0);
+    }
+    let mut labels = watershed(&surface, width, height, &markers, None);
+    settle_ridge_pixels(&mut labels, &edges, image, width, height);
+    let snapped: Vec<u8> = labels
+        .iter()
+        .zip(&binary)
+        .map(|(&label, &original)| match label {
+            1 => 255,
+            2 => 0,
+            _ => original,
+        })
+        .collect();
+
+    // Floods meeting on the faint boundary ridge alone cut corners; keep
+    // only changes that reach an actual edge
+    let changed: Vec<u8> = snapped.iter().zip(&binary).map(|(a, b)| if a != b { 255 } else { 0 }).collect();
+    let (regions, count) = label_components(&changed, width, height, 128, true);
+    let mut at_edge = vec![false; count as usize + 1];
+    for (&region, &edge) in regions.iter().zip(&edges) {
+        if edge > BOUNDARY_BIAS {
+            at_edge[region as usize] = true;
+        }
+    }
+    regions
+        .iter()
+        .zip(snapped.iter().zip(&binary))
+        .map(|(&region, (&new, &original))| if region > 0 && at_edge[region as usize] { new } else { original })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_snaps_to_nearby_edge_only() {
+        let (width, height) = (48, 48);
+        // Bright square [10, 30) on a dark background
+        let object = |x: usize, y: usize| (10..30).contains(&x) && (10..30).contains(&y);
+        let image: Vec<u8> = (0..width * height)
+            .flat_map(|i| {
+                let v = if object(i % width, i / width) { 220 } else { 30 };
+                [v, v, v, 255]
+            })
+            .collect();
+        // Rough selection [13, 27), 3 px too small on every side
+        let rough: Vec<u8> = (0..width * height)
+            .map(|i| if (13..27).contains(&(i % width)) && (13..27).contains(&(i / width)) { 255 } else { 0 })
+            .collect();
+
+        let snapped = snap_mask_to_edges(&rough, &image, width, height, 0.5);
+        for (i, &value) in snapped.iter().enumerate() {
+            assert_eq!(value > 0, object(i % width, i / width), "pixel ({}, {})", i % width, i / width);
+        }
+
+        // Without edges nearby the selection stays as it was
+        let flat = vec![128u8; width * height * 4];
+        assert_eq!(snap_mask_to_edges(&rough, &flat, width, height, 0.5), rough);
+        assert_eq!(snap_mask_to_edges(&rough, &image, width, height, 0.0), rough);
+    }
+}
@@ -0,0 +1,129 @@
+//! Selection statistics for status bars and QA scripts.
+//!
+//! [`mask_summary`] reports how much of the canvas a selection covers,
+//! where it lies and where its center of mass is, without copying the
+//! mask anywhere; [`label_summaries`] does the same for every region of a
+//! label map such as the output of [`super::watershed`].
+
+use std::collections::BTreeMap;
+
+use crate::batch::region::Rect;
+
+/// Size and position of a selection or labeled region.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MaskSummary {
+    /// Number of selected pixels
+    pub count: usize,
+    /// Selected share of the canvas in percent (0-100)
+    pub coverage: f32,
+    /// Tightest rectangle around the selected pixels, `None` if empty
+    pub bounds: Option<Rect>,
+    /// Mean (x, y) of the selected pixel centers, `None` if empty
+    pub centroid: Option<(f32, f32)>,
+}
+
+/// Running sums of one region.
+#[derive(Debug, Clone, Copy)]
+struct Accumulator {
+    count: usize,
+    sum_x: f64,
+    sum_y: f64,
+    min: (usize, usize),
+    max: (usize, usize),
+}
+
+impl Accumulator {
+    fn new() -> Self {
+        Self { count: 0, sum_x: 0.0, sum_y: 0.0, min: (usize::MAX, usize::MAX), max: (0, 0) }
+    }
+
+    fn add(&mut self, x: usize, y: usize) {
+        self.count += 1;
+        self.sum_x += x as f64;
+        self.sum_y += y as f64;
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    fn summary(&self, total: usize) -> MaskSummary {
+        if self.count == 0 {
+            return MaskSummary::default();
+        }
+        let n = self.count as f64;
+        MaskSummary {
+            count: self.count,
+            coverage: (self.count as f64 * 100.0 / total as f64) as f32,
+            bounds: Some(Rect::new(self.min.0, self.min.1, self.max.0 - self.min.0 + 1, self.max.1 - self.min.1 + 1)),
+            centroid: Some(((self.sum_x / n + 0.5) as f32, (self.sum_y / n + 0.5) as f32)),
+        }
+    }
+}
+
+/// Summarize a selection mask.
+///
+/// # Arguments
+/// * `mask` - Selection mask (flattened row-major)
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `threshold` - Values >= threshold count as selected (128 for soft masks, 1 for any coverage)
+///
+/// # Returns
+/// Pixel count, coverage, bounds and centroid (pixel centers at +0.5)
+pub fn mask_summary(mask: &[u8], width: usize, height: usize, threshold: u8) -> MaskSummary {
+    let total = width * height;
+    let mut acc = Accumulator::new();
+    for (i, &value) in mask.iter().take(total).enumerate() {
+        if value >= threshold {
+            acc.add(i % width, i / width);
+        }
+    }
+    acc.summary(total)
+}
+
+/// Summarize every region of a label map.
+///
+/// # Arguments
+/// * `labels` - Label per pixel (flattened row-major), 0 = background
+/// * `width` - Map width
+/// * `height` - Map height
+///
+/// # Returns
+/// (label, summary) for every label > 0 present, in ascending label order
+pub fn label_summaries(labels: &[u32], width: usize, height: usize) -> Vec<(u32, MaskSummary)> {
+    let total = width * height;
+    let mut regions: BTreeMap<u32, Accumulator> = BTreeMap::new();
+    for (i, &label) in labels.iter().take(total).enumerate() {
+        if label > 0 {
+            regions.entry(label).or_insert_with(Accumulator::new).add(i % width, i / width);
+        }
+    }
+    regions.into_iter().map(|(label, acc)| (label, acc.summary(total))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_and_label_summaries() {
+        // 4x3 mask with a 2x2 block at (1, 1) and a faint pixel at (0, 0)
+        let mut mask = vec![0u8; 12];
+        for i in [5, 6, 9, 10] {
+            mask[i] = 255;
+        }
+        mask[0] = 40;
+        let summary = mask_summary(&mask, 4, 3, 128);
+        assert_eq!(summary.count, 4);
+        assert!((summary.coverage - 100.0 / 3.0).abs() < 1e-4);
+        assert_eq!(summary.bounds, Some(Rect::new(1, 1, 2, 2)));
+        assert_eq!(summary.centroid, Some((2.0, 2.0)));
+        assert_eq!(mask_summary(&mask, 4, 3, 1).bounds, Some(Rect::new(0, 0, 3, 3)));
+        assert_eq!(mask_summary(&[0; 12], 4, 3, 128), MaskSummary::default());
+
+        let labels = vec![0, 2, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0];
+        let regions = label_summaries(&labels, 4, 3);
+        assert_eq!(regions.iter().map(|r| r.0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(regions[1].1.bounds, Some(Rect::new(1, 0, 2, 1)));
+        assert_eq!(regions[0].1.count, 1);
+    }
+}
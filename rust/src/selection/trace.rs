@@ -0,0 +1,138 @@
+//! Multi-color image tracing to SVG.
+//!
+//! [`trace_image_to_svg`] vectorizes a whole image: the colors are reduced
+//! with the k-means palette of [`crate::analysis::extract_palette`], every
+//! palette color becomes a layer traced with marching squares, and the
+//! layers are emitted as stacked SVG paths.
+//!
+//! Layers are drawn from the most to the least frequent color and each
+//! layer's mask also covers all layers drawn above it, so adjacent shapes
+//! overlap instead of leaving hairline gaps between anti-aliased edges.
+//! Holes are kept with `fill-rule="evenodd"`.
+
+use ndarray::ArrayView3;
+
+use super::marching_squares::{marching_squares, simplify_contour, Point};
+use crate::analysis::palette::{extract_palette, palette_indices, ALPHA_THRESHOLD};
+
+/// k-means iterations used to build the trace palette.
+const PALETTE_ITERATIONS: usize = 20;
+
+/// Smoothness passed to Bezier fitting.
+const BEZIER_SMOOTHNESS: f32 = 0.25;
+
+/// Trace an image into a multi-color SVG document.
+///
+/// Pixels with alpha below 128 stay uncovered. Path coordinates use pixel
+/// edges, so a shape covering pixels 0..w spans 0.0..w in the SVG.
+///
+/// # Arguments
+/// * `image` - Image with 1, 3, or 4 channels (height, width, channels)
+/// * `max_colors` - Maximum number of color layers (1-255)
+/// * `simplify_epsilon` - Douglas-Peucker epsilon (0 to keep every marching squares vertex)
+/// * `fit_beziers` - Fit cubic Bezier curves to the simplified outlines
+///
+/// # Returns
+/// Complete SVG document as string
+pub fn trace_image_to_svg(image: ArrayView3<u8>, max_colors: usize, simplify_epsilon: f32, fit_beziers: bool) -> String {
+    let (height, width, channels) = image.dim();
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}px" height="{}px" viewBox="0 0 {} {}">"#,
+        width, height, width, height
+    );
+    svg.push('\n');
+
+    // Layer colors, most frequent first
+    let palette: Vec<[u8; 3]> = extract_palette(image, max_colors.clamp(1, 255), PALETTE_ITERATIONS, 0)
+        .iter()
+        .map(|p| p.color)
+        .collect();
+    let transparent = palette.len() as u8;
+    let has_transparency = channels == 4 && image.lanes(ndarray::Axis(2)).into_iter().any(|p| p[3] < ALPHA_THRESHOLD);
+    let indices = palette_indices(image, &palette, Some(transparent), false);
+
+    // The padded mask closes contours at the image border
+    let (padded_width, padded_height) = (width + 2, height + 2);
+    let mut mask = vec![0u8; padded_width * padded_height];
+
+    for (layer, color) in palette.iter().enumerate() {
+        let fill = format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2]);
+        if layer == 0 && !has_transparency {
+            svg.push_str(&format!(r#"  <rect x="0" y="0" width="{}" height="{}" fill="{}"/>"#, width, height, fill));
+            svg.push('\n');
+            continue;
+        }
+
+        for ((y, x), &index) in indices.indexed_iter() {
+            let covered = index != transparent && index as usize >= layer;
+            // 254 puts the 0.5 threshold (byte 127) exactly halfway between pixels
+            mask[(y + 1) * padded_width + x + 1] = if covered { 254 } else { 0 };
+        }
+
+        let mut path = String::new();
+        for mut contour in marching_squares(&mask, padded_width, padded_height, 0.5) {
+            // Padded pixel centers to unpadded pixel edges
+            let shift = |p: &mut Point| {
+                p.x -= 0.5;
+                p.y -= 0.5;
+            };
+            contour.points.iter_mut().for_each(shift);
+            if simplify_epsilon > 0.0 || fit_beziers {
+                simplify_contour(&mut contour, simplify_epsilon, fit_beziers, BEZIER_SMOOTHNESS);
+            }
+            let data = contour.to_svg_path();
+            if !data.is_empty() {
+                path.push_str(&data);
+                path.push(' ');
+            }
+        }
+        if !path.is_empty() {
+            svg.push_str(&format!(r#"  <path d="{}" fill="{}" fill-rule="evenodd"/>"#, path.trim_end(), fill));
+            svg.push('\n');
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+
+    #[test]
+    fn test_trace_stacks_layers() {
+        // White canvas with a red ring (the hole shows the canvas) and a blue dot
+        let image = Array3::from_shape_fn((32, 32, 3), |(y, x, c)| {
+            let (dx, dy) = (x as f32 - 12.0, y as f32 - 12.0);
+            let r = (dx * dx + dy * dy).sqrt();
+            let color = if (4.0..9.0).contains(&r) {
+                [220, 20, 20]
+            } else if x >= 26 && y >= 26 {
+                [20, 20, 220]
+            } else {
+                [255, 255, 255]
+            };
+            color[c]
+        });
+        let svg = trace_image_to_svg(image.view(), 3, 0.5, false);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="32" height="32" fill="#ffffff"/>"##));
+        assert_eq!(svg.matches("<path").count(), 2);
+        // The red layer has the ring's outer and hole outline plus the
+        // blue square it is stacked under
+        let red = svg.lines().find(|l| l.contains("#dc1414")).expect("red layer");
+        assert_eq!(red.matches('M').count(), 3);
+        // The blue square reaches the image corner
+        let blue = svg.lines().find(|l| l.contains("#1414dc")).expect("blue layer");
+        assert!(blue.contains("32.000"));
+
+        // Transparent pixels are left out
+        let mut rgba = Array3::from_elem((8, 8, 4), 255u8);
+        rgba.slice_mut(ndarray::s![.., ..4, 3]).fill(0);
+        let svg = trace_image_to_svg(rgba.view(), 2, 0.0, true);
+        assert!(!svg.contains("<rect"));
+        assert_eq!(svg.matches("<path").count(), 1);
+    }
+}
@@ -0,0 +1,13 @@
+//! Sprite sheet tools for game-asset workflows.
+//!
+//! - **Packing**: Places sprites into an atlas with a MaxRects packer
+//!   (best short side fit) and returns the placement of each sprite
+//! - **Slicing**: Cuts a sheet laid out on a regular grid into cells
+//!
+//! Both work on u8 and f32 images with any channel count.
+
+pub mod packer;
+pub mod sheet;
+
+pub use packer::{pack_rects, SpriteError, SpriteRect};
+pub use sheet::{pack_sprites, slice_grid};
@@ -0,0 +1,169 @@
+//! MaxRects rectangle packer.
+//!
+//! Keeps a list of maximal free rectangles; each sprite goes into the free
+//! rectangle that leaves the shortest leftover side, and every free
+//! rectangle it overlaps is split around it. Sprites are placed largest
+//! first, which keeps the atlas compact.
+
+use std::fmt;
+
+/// Error raised while packing or slicing sprites.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpriteError {
+    /// Sprite at this index does not fit into the maximum atlas size
+    DoesNotFit(usize),
+    /// Sprites have different channel counts
+    ChannelMismatch,
+    /// Grid with zero cells or more cells than pixels
+    InvalidGrid(String),
+}
+
+impl fmt::Display for SpriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteError::DoesNotFit(index) => write!(f, "sprite {} does not fit into the atlas", index),
+            SpriteError::ChannelMismatch => write!(f, "sprites have different channel counts"),
+            SpriteError::InvalidGrid(msg) => write!(f, "invalid grid: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SpriteError {}
+
+/// Placement of a sprite in the atlas (pixels).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpriteRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl SpriteRect {
+    fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    fn intersects(&self, other: &SpriteRect) -> bool {
+        self.x < other.right() && other.x < self.right() && self.y < other.bottom() && other.y < self.bottom()
+    }
+
+    fn contains(&self, other: &SpriteRect) -> bool {
+        other.x >= self.x && other.y >= self.y && other.right() <= self.right() && other.bottom() <= self.bottom()
+    }
+}
+
+/// Split `free` around `used`, returning the up to four remaining maximal strips.
+fn split_free(free: &SpriteRect, used: &SpriteRect, out: &mut Vec<SpriteRect>) {
+    if used.x > free.x {
+        out.push(SpriteRect { width: used.x - free.x, ..*free });
+    }
+    if used.right() < free.right() {
+        out.push(SpriteRect { x: used.right(), width: free.right() - used.right(), ..*free });
+    }
+    if used.y > free.y {
+        out.push(SpriteRect { height: used.y - free.y, ..*free });
+    }
+    if used.bottom() < free.bottom() {
+        out.push(SpriteRect { y: used.bottom(), height: free.bottom() - used.bottom(), ..*free });
+    }
+}
+
+/// Compute sprite placements without copying pixels.
+///
+/// # Arguments
+/// * `sizes` - (width, height) of each sprite
+/// * `padding` - Empty pixels between neighboring sprites
+/// * `max_size` - Maximum atlas width and height
+///
+/// # Returns
+/// Atlas (width, height) trimmed to the placed sprites, and one rect per
+/// sprite in input order
+pub fn pack_rects(sizes: &[(usize, usize)], padding: usize, max_size: usize) -> Result<((usize, usize), Vec<SpriteRect>), SpriteError> {
+    // Padding is added on the right/bottom of each cell; the bin is enlarged
+    // by the same amount so sprites may touch the atlas edge
+    let bin = max_size + padding;
+    let mut free = vec![SpriteRect { x: 0, y: 0, width: bin, height: bin }];
+    let mut placed = vec![SpriteRect::default(); sizes.len()];
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse((sizes[i].0 * sizes[i].1, sizes[i].0.max(sizes[i].1))));
+
+    for index in order {
+        let (width, height) = sizes[index];
+        if width == 0 || height == 0 {
+            continue;
+        }
+        let (cell_w, cell_h) = (width + padding, height + padding);
+
+        let best = free
+            .iter()
+            .filter(|f| f.width >= cell_w && f.height >= cell_h)
+            .min_by_key(|f| {
+                let (dw, dh) = (f.width - cell_w, f.height - cell_h);
+                (dw.min(dh), dw.max(dh), f.y, f.x)
+            })
+            .copied()
+            .ok_or(SpriteError::DoesNotFit(index))?;
+
+        let cell = SpriteRect { x: best.x, y: best.y, width: cell_w, height: cell_h };
+        let mut next = Vec::with_capacity(free.len() + 4);
+        for f in &free {
+            if f.intersects(&cell) {
+                split_free(f, &cell, &mut next);
+            } else {
+                next.push(*f);
+            }
+        }
+        // Drop free rects contained in others
+        let mut pruned: Vec<SpriteRect> = Vec::with_capacity(next.len());
+        for (i, r) in next.iter().enumerate() {
+            let redundant = next.iter().enumerate().any(|(j, o)| i != j && o.contains(r) && (o != r || j < i));
+            if !redundant {
+                pruned.push(*r);
+            }
+        }
+        free = pruned;
+
+        placed[index] = SpriteRect { x: best.x, y: best.y, width, height };
+    }
+
+    let atlas_w = placed.iter().map(SpriteRect::right).max().unwrap_or(0);
+    let atlas_h = placed.iter().map(SpriteRect::bottom).max().unwrap_or(0);
+    Ok(((atlas_w, atlas_h), placed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_overlap_with_padding() {
+        let sizes = [(30, 20), (10, 40), (25, 25), (5, 5), (40, 10), (12, 18), (8, 30)];
+        let ((w, h), rects) = pack_rects(&sizes, 2, 128).unwrap();
+        for (i, a) in rects.iter().enumerate() {
+            assert_eq!((a.width, a.height), sizes[i]);
+            assert!(a.right() <= w && a.bottom() <= h);
+            // Cells (sprite plus padding on the right/bottom) never overlap
+            let cell = |r: &SpriteRect| SpriteRect { width: r.width + 2, height: r.height + 2, ..*r };
+            for b in rects.iter().skip(i + 1) {
+                assert!(!cell(a).intersects(&cell(b)), "{:?} {:?}", a, b);
+            }
+        }
+        // Everything fits well below the limit
+        assert!(w <= 128 && h <= 128);
+    }
+
+    #[test]
+    fn test_exact_fit_and_overflow() {
+        let ((w, h), rects) = pack_rects(&[(32, 32); 4], 0, 64).unwrap();
+        assert_eq!((w, h), (64, 64));
+        assert_eq!(rects.len(), 4);
+        assert_eq!(pack_rects(&[(32, 32); 5], 0, 64), Err(SpriteError::DoesNotFit(4)));
+        assert_eq!(pack_rects(&[(10, 80)], 0, 64), Err(SpriteError::DoesNotFit(0)));
+    }
+}
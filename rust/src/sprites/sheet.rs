@@ -0,0 +1,193 @@
+//! Atlas assembly and grid slicing.
+
+use ndarray::{s, Array3, ArrayView3};
+
+use super::packer::{pack_rects, SpriteError, SpriteRect};
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3};
+#[cfg(feature = "python")]
+use crate::host_array::PyHostArray3;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Pack sprites into a single atlas image.
+///
+/// Unused atlas pixels are zero (transparent for RGBA).
+///
+/// # Arguments
+/// * `images` - Sprites (height, width, channels), all with the same channel count
+/// * `padding` - Empty pixels between neighboring sprites
+/// * `max_size` - Maximum atlas width and height
+///
+/// # Returns
+/// Atlas image trimmed to the placed sprites, and the placement of each
+/// sprite in input order
+pub fn pack_sprites<T: Copy + Default>(
+    images: &[ArrayView3<T>],
+    padding: usize,
+    max_size: usize,
+) -> Result<(Array3<T>, Vec<SpriteRect>), SpriteError> {
+    let channels = images.first().map_or(4, |image| image.shape()[2]);
+    if images.iter().any(|image| image.shape()[2] != channels) {
+        return Err(SpriteError::ChannelMismatch);
+    }
+
+    let sizes: Vec<(usize, usize)> = images.iter().map(|image| (image.shape()[1], image.shape()[0])).collect();
+    let ((width, height), rects) = pack_rects(&sizes, padding, max_size)?;
+
+    let mut atlas = Array3::from_elem((height, width, channels), T::default());
+    for (image, rect) in images.iter().zip(&rects) {
+        atlas
+            .slice_mut(s![rect.y..rect.y + rect.height, rect.x..rect.x + rect.width, ..])
+            .assign(image);
+    }
+    Ok((atlas, rects))
+}
+
+/// Cut a sprite sheet into a regular grid of cells.
+///
+/// Cell column `c` covers x from `c * width / cols` to `(c + 1) * width / cols`
+/// (rows likewise), so sizes differ by at most one pixel if the sheet is not
+/// evenly divisible.
+///
+/// # Arguments
+/// * `image` - Sheet image (height, width, channels)
+/// * `cols` - Number of columns
+/// * `rows` - Number of rows
+///
+/// # Returns
+/// Cells in row-major order
+pub fn slice_grid<T: Copy>(image: ArrayView3<T>, cols: usize, rows: usize) -> Result<Vec<Array3<T>>, SpriteError> {
+    let (height, width, _) = image.dim();
+    if cols == 0 || rows == 0 || cols > width || rows > height {
+        return Err(SpriteError::InvalidGrid(format!("{}x{} cells for a {}x{} image", cols, rows, width, height)));
+    }
+
+    let mut cells = Vec::with_capacity(cols * rows);
+    for row in 0..rows {
+        let (y0, y1) = (row * height / rows, (row + 1) * height / rows);
+        for col in 0..cols {
+            let (x0, x1) = (col * width / cols, (col + 1) * width / cols);
+            cells.push(image.slice(s![y0..y1, x0..x1, ..]).to_owned());
+        }
+    }
+    Ok(cells)
+}
+
+// ============================================================================
+// Python Bindings
+// ============================================================================
+
+/// Sprite placements as (x, y, width, height) tuples.
+#[cfg(feature = "python")]
+type PyRects = Vec<(usize, usize, usize, usize)>;
+
+#[cfg(feature = "python")]
+fn to_py_err(e: SpriteError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Pack uint8 sprites into an atlas.
+///
+/// # Arguments
+/// * `images` - List of sprite arrays (height, width, channels)
+/// * `padding` - Empty pixels between sprites
+/// * `max_size` - Maximum atlas width and height
+///
+/// # Returns
+/// Tuple of (atlas, rects) with one (x, y, width, height) tuple per sprite
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "pack_sprites", signature = (images, padding=1, max_size=4096))]
+pub fn pack_sprites_py<'py>(
+    py: Python<'py>,
+    images: Vec<PyHostArray3<'py, u8>>,
+    padding: usize,
+    max_size: usize,
+) -> PyResult<(Bound<'py, PyArray3<u8>>, PyRects)> {
+    let views: Vec<ArrayView3<u8>> = images.iter().map(|image| image.as_array()).collect();
+    let (atlas, rects) = pack_sprites(&views, padding, max_size).map_err(to_py_err)?;
+    Ok((atlas.into_pyarray(py), rects.iter().map(|r| (r.x, r.y, r.width, r.height)).collect()))
+}
+
+/// Pack float32 sprites into an atlas.
+///
+/// Same as pack_sprites but for f32 images.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "pack_sprites_f32", signature = (images, padding=1, max_size=4096))]
+pub fn pack_sprites_f32_py<'py>(
+    py: Python<'py>,
+    images: Vec<PyHostArray3<'py, f32>>,
+    padding: usize,
+    max_size: usize,
+) -> PyResult<(Bound<'py, PyArray3<f32>>, PyRects)> {
+    let views: Vec<ArrayView3<f32>> = images.iter().map(|image| image.as_array()).collect();
+    let (atlas, rects) = pack_sprites(&views, padding, max_size).map_err(to_py_err)?;
+    Ok((atlas.into_pyarray(py), rects.iter().map(|r| (r.x, r.y, r.width, r.height)).collect()))
+}
+
+/// Cut a uint8 sprite sheet into a grid of cells (row-major).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "slice_grid")]
+pub fn slice_grid_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, u8>,
+    cols: usize,
+    rows: usize,
+) -> PyResult<Vec<Bound<'py, PyArray3<u8>>>> {
+    let cells = slice_grid(image.as_array(), cols, rows).map_err(to_py_err)?;
+    Ok(cells.into_iter().map(|cell| cell.into_pyarray(py)).collect())
+}
+
+/// Cut a float32 sprite sheet into a grid of cells (row-major).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "slice_grid_f32")]
+pub fn slice_grid_f32_py<'py>(
+    py: Python<'py>,
+    image: PyHostArray3<'py, f32>,
+    cols: usize,
+    rows: usize,
+) -> PyResult<Vec<Bound<'py, PyArray3<f32>>>> {
+    let cells = slice_grid(image.as_array(), cols, rows).map_err(to_py_err)?;
+    Ok(cells.into_iter().map(|cell| cell.into_pyarray(py)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_copies_pixels() {
+        let sprites: Vec<Array3<u8>> = (1..=3u8)
+            .map(|i| Array3::from_elem((4 * i as usize, 6, 4), i * 50))
+            .collect();
+        let views: Vec<ArrayView3<u8>> = sprites.iter().map(|s| s.view()).collect();
+        let (atlas, rects) = pack_sprites(&views, 1, 64).unwrap();
+        for (sprite, rect) in sprites.iter().zip(&rects) {
+            let region = atlas.slice(s![rect.y..rect.y + rect.height, rect.x..rect.x + rect.width, ..]);
+            assert_eq!(region, sprite.view());
+        }
+
+        let gray = Array3::<u8>::zeros((2, 2, 1));
+        let mixed = [sprites[0].view(), gray.view()];
+        assert_eq!(pack_sprites(&mixed, 0, 64).unwrap_err(), SpriteError::ChannelMismatch);
+    }
+
+    #[test]
+    fn test_slice_grid() {
+        let sheet = Array3::from_shape_fn((4, 7, 1), |(y, x, _)| (y * 10 + x) as u8);
+        let cells = slice_grid(sheet.view(), 2, 2).unwrap();
+        assert_eq!(cells.len(), 4);
+        assert_eq!(cells[0].dim(), (2, 3, 1));
+        assert_eq!(cells[1].dim(), (2, 4, 1));
+        assert_eq!(cells[3][[0, 0, 0]], 23);
+        assert!(slice_grid(sheet.view(), 0, 2).is_err());
+        assert!(slice_grid(sheet.view(), 8, 1).is_err());
+    }
+}
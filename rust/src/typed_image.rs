@@ -0,0 +1,304 @@
+//! Typed image container for Rust consumers.
+//!
+//! Filters work on bare `(height, width, channels)` arrays and infer the
+//! layout from the last axis. [`Image<T>`] wraps such an array together with
+//! its [`ChannelLayout`] and [`ColorSpace`], validating the shape once on
+//! construction:
+//!
+//! ```text
+//! let image = Image::from_vec(640, 480, ChannelLayout::Rgba, pixels)?;
+//! let sharpened = image.map(|view| sharpen_u8(view, 0.5))?;
+//! let brighter = brightness_u8(&sharpened, 0.1);  // filters accept &Image directly
+//! ```
+//!
+//! The sample type is checked at compile time: an `Image<f32>` cannot be
+//! passed to a `_u8` filter. Filters that accept `impl Into<ArrayView3<T>>`
+//! take `&Image<T>`, `ArrayView3<T>` and `&Array3<T>` alike.
+
+use std::fmt;
+
+use ndarray::{Array3, ArrayView3, ArrayViewMut3};
+
+use crate::filters::core::{image_f32_to_u8, image_u8_to_f32};
+
+/// Error raised when data does not describe a valid image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    /// Channel count is not 1, 3 or 4
+    UnsupportedChannels(usize),
+    /// Buffer length does not match width * height * channels
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::UnsupportedChannels(n) => write!(f, "unsupported channel count {} (expected 1, 3 or 4)", n),
+            ImageError::LengthMismatch { expected, actual } => {
+                write!(f, "buffer has {} samples, expected {}", actual, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+/// Order and meaning of the channels of a pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelLayout {
+    Gray,
+    Rgb,
+    Rgba,
+}
+
+impl ChannelLayout {
+    /// Number of channels per pixel.
+    pub fn channels(self) -> usize {
+        match self {
+            ChannelLayout::Gray => 1,
+            ChannelLayout::Rgb => 3,
+            ChannelLayout::Rgba => 4,
+        }
+    }
+
+    /// Layout for a channel count, as inferred by the filters.
+    pub fn from_channels(channels: usize) -> Result<Self, ImageError> {
+        match channels {
+            1 => Ok(ChannelLayout::Gray),
+            3 => Ok(ChannelLayout::Rgb),
+            4 => Ok(ChannelLayout::Rgba),
+            n => Err(ImageError::UnsupportedChannels(n)),
+        }
+    }
+
+    /// Whether the last channel is alpha.
+    pub fn has_alpha(self) -> bool {
+        self == ChannelLayout::Rgba
+    }
+}
+
+/// Encoding of the color values.
+///
+/// This is a tag only; filters do not convert between color spaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// sRGB transfer curve (the default for decoded files)
+    #[default]
+    Srgb,
+    /// Linear light with sRGB primaries
+    LinearSrgb,
+}
+
+/// Sample type of an image: `u8` (0-255) or `f32` (0.0-1.0).
+pub trait Sample: Copy + Default + 'static {
+    /// Value of a fully saturated channel (255 or 1.0).
+    const MAX: Self;
+}
+
+impl Sample for u8 {
+    const MAX: Self = 255;
+}
+
+impl Sample for f32 {
+    const MAX: Self = 1.0;
+}
+
+/// Image with a known channel layout and color space.
+///
+/// Pixel data is stored as a standard-layout `(height, width, channels)`
+/// array, so [`Image::as_slice`] always succeeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image<T> {
+    data: Array3<T>,
+    layout: ChannelLayout,
+    color_space: ColorSpace,
+}
+
+impl<T: Sample> Image<T> {
+    /// Create an image filled with zeros (transparent black for RGBA).
+    pub fn new(width: usize, height: usize, layout: ChannelLayout) -> Self {
+        Self::filled(width, height, layout, T::default())
+    }
+
+    /// Create an image with every channel set to `value`.
+    pub fn filled(width: usize, height: usize, layout: ChannelLayout, value: T) -> Self {
+        Self {
+            data: Array3::from_elem((height, width, layout.channels()), value),
+            layout,
+            color_space: ColorSpace::default(),
+        }
+    }
+
+    /// Wrap a `(height, width, channels)` array; the layout is inferred
+    /// from the channel count.
+    pub fn from_array(data: Array3<T>) -> Result<Self, ImageError> {
+        let layout = ChannelLayout::from_channels(data.dim().2)?;
+        let data = if data.is_standard_layout() { data } else { data.as_standard_layout().into_owned() };
+        Ok(Self { data, layout, color_space: ColorSpace::default() })
+    }
+
+    /// Wrap row-major interleaved samples.
+    pub fn from_vec(width: usize, height: usize, layout: ChannelLayout, samples: Vec<T>) -> Result<Self, ImageError> {
+        let expected = width * height * layout.channels();
+        if samples.len() != expected {
+            return Err(ImageError::LengthMismatch { expected, actual: samples.len() });
+        }
+        let data = Array3::from_shape_vec((height, width, layout.channels()), samples)
+            .expect("length checked above");
+        Ok(Self { data, layout, color_space: ColorSpace::default() })
+    }
+
+    /// Copy row-major interleaved samples.
+    pub fn from_slice(width: usize, height: usize, layout: ChannelLayout, samples: &[T]) -> Result<Self, ImageError> {
+        Self::from_vec(width, height, layout, samples.to_vec())
+    }
+
+    /// Tag the image with a color space (does not convert the values).
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.data.dim().1
+    }
+
+    pub fn height(&self) -> usize {
+        self.data.dim().0
+    }
+
+    pub fn layout(&self) -> ChannelLayout {
+        self.layout
+    }
+
+    pub fn channels(&self) -> usize {
+        self.layout.channels()
+    }
+
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    pub fn view(&self) -> ArrayView3<'_, T> {
+        self.data.view()
+    }
+
+    pub fn view_mut(&mut self) -> ArrayViewMut3<'_, T> {
+        self.data.view_mut()
+    }
+
+    pub fn as_array(&self) -> &Array3<T> {
+        &self.data
+    }
+
+    pub fn into_array(self) -> Array3<T> {
+        self.data
+    }
+
+    /// Row-major interleaved samples.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice().expect("image data is in standard layout")
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data.into_raw_vec_and_offset().0
+    }
+
+    /// Run an array filter and wrap its result, keeping the color space.
+    ///
+    /// The layout of the result is inferred from its channel count, so
+    /// filters that change it (e.g. to grayscale) are tagged correctly.
+    pub fn map<U: Sample>(&self, filter: impl FnOnce(ArrayView3<T>) -> Array3<U>) -> Result<Image<U>, ImageError> {
+        Ok(Image::from_array(filter(self.view()))?.with_color_space(self.color_space))
+    }
+}
+
+impl Image<u8> {
+    /// Convert to f32 (0.0-1.0), keeping layout and color space.
+    pub fn to_f32(&self) -> Image<f32> {
+        Image { data: image_u8_to_f32(self.view()), layout: self.layout, color_space: self.color_space }
+    }
+}
+
+impl Image<f32> {
+    /// Convert to u8 (0-255, truncating), keeping layout and color space.
+    pub fn to_u8(&self) -> Image<u8> {
+        Image { data: image_f32_to_u8(&self.data), layout: self.layout, color_space: self.color_space }
+    }
+}
+
+impl<T: Sample> TryFrom<Array3<T>> for Image<T> {
+    type Error = ImageError;
+
+    fn try_from(data: Array3<T>) -> Result<Self, ImageError> {
+        Image::from_array(data)
+    }
+}
+
+impl<T> From<Image<T>> for Array3<T> {
+    fn from(image: Image<T>) -> Self {
+        image.data
+    }
+}
+
+impl<'a, T> From<&'a Image<T>> for ArrayView3<'a, T> {
+    fn from(image: &'a Image<T>) -> Self {
+        image.data.view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::color_adjust::brightness_u8;
+    use ndarray::s;
+
+    #[test]
+    fn test_from_vec_checks_length() {
+        let err = Image::<u8>::from_vec(2, 2, ChannelLayout::Rgb, vec![0; 11]).unwrap_err();
+        assert_eq!(err, ImageError::LengthMismatch { expected: 12, actual: 11 });
+        assert!(Image::<u8>::from_vec(2, 2, ChannelLayout::Rgb, vec![0; 12]).is_ok());
+    }
+
+    #[test]
+    fn test_from_array_infers_layout() {
+        let image = Image::from_array(Array3::<f32>::zeros((3, 5, 1))).unwrap();
+        assert_eq!((image.width(), image.height(), image.layout()), (5, 3, ChannelLayout::Gray));
+        assert_eq!(
+            Image::from_array(Array3::<u8>::zeros((3, 5, 2))).unwrap_err(),
+            ImageError::UnsupportedChannels(2)
+        );
+    }
+
+    #[test]
+    fn test_slice_roundtrip_is_row_major() {
+        let samples: Vec<u8> = (0..24).collect();
+        let image = Image::from_slice(3, 2, ChannelLayout::Rgba, &samples).unwrap();
+        assert_eq!(image.view()[[1, 0, 2]], 14);
+        assert_eq!(image.as_slice(), samples.as_slice());
+
+        // Non-standard layout input is normalized
+        let transposed = Image::from_array(Array3::<u8>::zeros((4, 2, 3)).permuted_axes([1, 0, 2])).unwrap();
+        assert_eq!(transposed.as_slice().len(), 24);
+    }
+
+    #[test]
+    fn test_map_keeps_color_space_and_infers_layout() {
+        let image = Image::<u8>::filled(4, 4, ChannelLayout::Rgba, 200).with_color_space(ColorSpace::LinearSrgb);
+        let gray = image.map(|view| view.slice(s![.., .., 0..1]).to_owned()).unwrap();
+        assert_eq!(gray.layout(), ChannelLayout::Gray);
+        assert_eq!(gray.color_space(), ColorSpace::LinearSrgb);
+
+        // Filters accept &Image directly
+        let brighter = brightness_u8(&image, 0.1);
+        assert_eq!(brighter.dim(), (4, 4, 4));
+    }
+
+    #[test]
+    fn test_u8_f32_conversion() {
+        let image = Image::<u8>::filled(2, 2, ChannelLayout::Rgb, 255);
+        let float = image.to_f32();
+        assert_eq!(float.view()[[0, 0, 0]], <f32 as Sample>::MAX);
+        assert_eq!(float.to_u8(), image);
+    }
+}
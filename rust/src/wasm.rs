@@ -17,24 +17,65 @@
 //!
 //! Both versions use identical Rust implementations.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use wasm_bindgen::prelude::*;
-use ndarray::Array3;
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3};
 
 use crate::filters::grayscale::{
-    grayscale_rgba_u8, grayscale_rgba_f32,
-    u8_to_f32, f32_to_u8, f32_to_u16_12bit, u16_12bit_to_f32,
+    grayscale_rgba_u8, grayscale_rgba_f32, LumaStandard,
+    grayscale_to_single_channel_u8, grayscale_to_single_channel_f32,
+    u8_to_f32, f32_to_u8, f32_to_u16_12bit, u16_12bit_to_f32, pack_12bit, unpack_12bit,
 };
+use crate::filters::basic::{self, ChannelError};
 use crate::filters::color_adjust;
 use crate::filters::color_science;
 use crate::filters::stylize;
 use crate::filters::levels_curves;
 use crate::filters::sharpen;
 use crate::filters::edge;
+use crate::filters::convolve::{self, BorderMode};
 use crate::filters::noise;
 use crate::filters::morphology;
-use crate::filters::blur_wasm;
+use crate::filters::blur_wasm::{self, BlurQuality};
 use crate::filters::rotate;
-use crate::filters::core::{blur_alpha_f32, dilate_alpha, erode_alpha, expand_canvas_f32};
+use crate::filters::resample;
+use crate::filters::straighten;
+use crate::filters::perspective::{self, Quad};
+use crate::filters::matting;
+use crate::filters::glitch;
+use crate::filters::material;
+use crate::filters::wavelet;
+use crate::filters::tonemap::{self, TonemapOperator, TonemapParams};
+use crate::filters::colorspace::{self, TransferFunction};
+use crate::filters::compositing::{self, AnaglyphMode};
+use crate::filters::raw::{self as raw_mod, BayerPattern, DemosaicAlgorithm, RawParams};
+use crate::filters::deband::{self, DebandParams};
+use crate::filters::fft::{self, Complex, Notch};
+use crate::filters::projection::{self, ViewParams};
+use crate::filters::distort::{self, Interpolation, PolarDirection, WaveType};
+use crate::filters::canvas::{self, ExtendMethod, TrimMode};
+use crate::filters::synthesis;
+use crate::filters::seamless::{self, SeamlessMethod};
+use crate::filters::sanitize::{self, SanitizeMode};
+use crate::layer_effects::cast_shadow::{cast_shadow_rgba_u8, CastShadowParams};
+use crate::layer_effects::long_shadow::{long_shadow_rgba_u8, LongShadowParams};
+use crate::layer_effects::color_overlay::{self, ColorOverlayParams};
+use crate::layer_effects::gradient_overlay::{self, GradientOverlayParams};
+use crate::layer_effects::pattern_overlay::{self, PatternOverlayParams};
+use crate::layer_effects::satin::{self, SatinParams};
+use crate::layer_effects::drop_shadow::{render_drop_shadow_u8, DropShadowParams};
+use crate::layer_effects::inner_shadow::{render_inner_shadow_u8, InnerShadowParams};
+use crate::layer_effects::outer_glow::{render_outer_glow_u8, OuterGlowParams};
+use crate::layer_effects::inner_glow::{render_inner_glow_u8, InnerGlowParams};
+use crate::layer_effects::stroke::{render_stroke_u8, StrokeParams, StrokePosition};
+use crate::layer_effects::bevel_emboss::{
+    bevel_emboss_f32, BevelEmbossParams, BevelStyle, BevelTechnique, BevelTexture,
+};
+use crate::batch::context::PreparedStep;
+use crate::batch::region::rects_from_flat;
+use crate::batch::{apply_in_rects_u8, BatchError, FilterSpec};
 
 // ============================================================================
 // Grayscale Filter - u8 (8-bit)
@@ -96,6 +137,55 @@ pub fn grayscale_rgba_f32_wasm(
     result.into_raw_vec_and_offset().0
 }
 
+// ============================================================================
+// Grayscale Single Channel
+// ============================================================================
+
+/// Luminance (BT.709) as a single channel - u8.
+///
+/// # Arguments
+/// * `data` - Flat array of bytes (length = width * height * channels)
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `channels` - Number of channels (1, 3, or 4)
+///
+/// # Returns
+/// Flat array with one luminance value per pixel (length = width * height)
+#[wasm_bindgen]
+pub fn grayscale_to_single_channel_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+) -> Vec<u8> {
+    let input = Array3::from_shape_vec(
+        (height, width, channels),
+        data.to_vec()
+    ).expect("Invalid dimensions");
+
+    let result = grayscale_to_single_channel_u8(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Luminance (BT.709) as a single channel - f32.
+///
+/// Same as `grayscale_to_single_channel_wasm`, values 0.0-1.0.
+#[wasm_bindgen]
+pub fn grayscale_to_single_channel_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+) -> Vec<f32> {
+    let input = Array3::from_shape_vec(
+        (height, width, channels),
+        data.to_vec()
+    ).expect("Invalid dimensions");
+
+    let result = grayscale_to_single_channel_f32(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
 // ============================================================================
 // Conversion Utilities
 // ============================================================================
@@ -168,6 +258,261 @@ pub fn convert_12bit_to_f32_wasm(
     result.into_raw_vec_and_offset().0
 }
 
+/// Pack u16 12-bit values two-in-three-bytes (MIPI CSI-2 RAW12 layout)
+#[wasm_bindgen]
+pub fn pack_12bit_wasm(data: &[u16]) -> Vec<u8> {
+    pack_12bit(data)
+}
+
+/// Unpack `count` u16 12-bit values from a RAW12 buffer
+#[wasm_bindgen]
+pub fn unpack_12bit_wasm(data: &[u8], count: usize) -> Result<Vec<u16>, JsError> {
+    unpack_12bit(data, count).ok_or_else(|| JsError::new(&format!("{} bytes hold fewer than {} 12-bit values", data.len(), count)))
+}
+
+// ============================================================================
+// Channel Operations
+// ============================================================================
+//
+// Planes are passed planar: `count` planes of width * height values, one
+// after the other.
+
+fn channel_js_error(e: ChannelError) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn split_planar<T: Copy>(data: &[T], width: usize, height: usize, channels: usize) -> Vec<T> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    basic::split_channels(input).into_iter().flat_map(|plane| plane.into_raw_vec_and_offset().0).collect()
+}
+
+fn merge_planar<T: Copy>(planes: &[T], width: usize, height: usize, count: usize) -> Result<Vec<T>, JsError> {
+    if planes.len() != width * height * count {
+        return Err(JsError::new("planes must hold width * height * count values"));
+    }
+    let views: Vec<_> = planes
+        .chunks_exact((width * height).max(1))
+        .take(count)
+        .map(|plane| ArrayView2::from_shape((height, width), plane).expect("Invalid dimensions"))
+        .collect();
+    let result = basic::merge_channels(&views).map_err(channel_js_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+fn swap_interleaved<T: Copy>(data: &[T], width: usize, height: usize, channels: usize, order: &str) -> Result<Vec<T>, JsError> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let order = basic::parse_channel_order(order).map_err(channel_js_error)?;
+    let result = basic::swap_channels(input, &order).map_err(channel_js_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+fn set_interleaved<T: Copy>(
+    data: &[T],
+    width: usize,
+    height: usize,
+    channels: usize,
+    index: usize,
+    plane: &[T],
+) -> Result<Vec<T>, JsError> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let plane = ArrayView2::from_shape((height, width), plane).map_err(|_| channel_js_error(ChannelError::ShapeMismatch))?;
+    let result = basic::set_channel(input, index, plane).map_err(channel_js_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Split an image into planar channels (all of channel 0, then channel 1, ...).
+#[wasm_bindgen]
+pub fn split_channels_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    split_planar(data, width, height, channels)
+}
+
+#[wasm_bindgen]
+pub fn split_channels_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    split_planar(data, width, height, channels)
+}
+
+/// Interleave `count` (1-4) planar channels into an image.
+#[wasm_bindgen]
+pub fn merge_channels_wasm(planes: &[u8], width: usize, height: usize, count: usize) -> Result<Vec<u8>, JsError> {
+    merge_planar(planes, width, height, count)
+}
+
+#[wasm_bindgen]
+pub fn merge_channels_f32_wasm(planes: &[f32], width: usize, height: usize, count: usize) -> Result<Vec<f32>, JsError> {
+    merge_planar(planes, width, height, count)
+}
+
+/// Reorder, duplicate or drop channels; `order` such as "bgra" or "rgb".
+#[wasm_bindgen]
+pub fn swap_channels_wasm(data: &[u8], width: usize, height: usize, channels: usize, order: &str) -> Result<Vec<u8>, JsError> {
+    swap_interleaved(data, width, height, channels, order)
+}
+
+#[wasm_bindgen]
+pub fn swap_channels_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    order: &str,
+) -> Result<Vec<f32>, JsError> {
+    swap_interleaved(data, width, height, channels, order)
+}
+
+/// Replace channel `index` with `plane`; `index == channels` appends one.
+#[wasm_bindgen]
+pub fn set_channel_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    index: usize,
+    plane: &[u8],
+) -> Result<Vec<u8>, JsError> {
+    set_interleaved(data, width, height, channels, index, plane)
+}
+
+#[wasm_bindgen]
+pub fn set_channel_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    index: usize,
+    plane: &[f32],
+) -> Result<Vec<f32>, JsError> {
+    set_interleaved(data, width, height, channels, index, plane)
+}
+
+// ============================================================================
+// Alpha Utilities
+// ============================================================================
+//
+// Results are RGBA whatever the input channel count.
+
+/// Use the luminance as alpha; bright = opaque unless `invert`.
+#[wasm_bindgen]
+pub fn alpha_from_luminance_wasm(data: &[u8], width: usize, height: usize, channels: usize, invert: bool) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    basic::alpha_from_luminance_u8(input, invert).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn alpha_from_luminance_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, invert: bool) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    basic::alpha_from_luminance_f32(input, invert).into_raw_vec_and_offset().0
+}
+
+/// Binarize alpha: alpha >= `cutoff` becomes opaque, the rest transparent.
+#[wasm_bindgen]
+pub fn threshold_alpha_wasm(data: &[u8], width: usize, height: usize, channels: usize, cutoff: u8) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    basic::threshold_alpha_u8(input, cutoff).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn threshold_alpha_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, cutoff: f32) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    basic::threshold_alpha_f32(input, cutoff).into_raw_vec_and_offset().0
+}
+
+/// Knock out `color` (`[r, g, b]`), GIMP "Color to Alpha" style.
+#[wasm_bindgen]
+pub fn color_to_alpha_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    color: &[u8],
+    tolerance: u8,
+) -> Result<Vec<u8>, JsError> {
+    let &[r, g, b] = color else {
+        return Err(JsError::new("color must be [r, g, b]"));
+    };
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(basic::color_to_alpha_u8(input, [r, g, b], tolerance).into_raw_vec_and_offset().0)
+}
+
+#[wasm_bindgen]
+pub fn color_to_alpha_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    color: &[f32],
+    tolerance: f32,
+) -> Result<Vec<f32>, JsError> {
+    let &[r, g, b] = color else {
+        return Err(JsError::new("color must be [r, g, b]"));
+    };
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(basic::color_to_alpha_f32(input, [r, g, b], tolerance).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Image Arithmetic
+// ============================================================================
+
+/// Operation and output mapping for `image_arithmetic_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct ArithmeticOptions {
+    /// "add", "subtract", "multiply", "divide", "difference", "min" or "max"
+    pub op: String,
+    /// Divisor of the result
+    pub scale: f32,
+    /// Added after scaling (0-255 units for u8, 0.0-1.0 for f32)
+    pub offset: f32,
+    /// Saturate to the value range; otherwise u8 wraps and f32 keeps out-of-range values
+    pub clamp: bool,
+}
+
+impl Default for ArithmeticOptions {
+    fn default() -> Self {
+        Self { op: "add".to_string(), scale: 1.0, offset: 0.0, clamp: true }
+    }
+}
+
+#[wasm_bindgen]
+impl ArithmeticOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Operands of image arithmetic; `b` has as many channels as `a` or one (mask).
+fn arithmetic_operands<'a, T>(
+    a: &'a [T],
+    b: &'a [T],
+    width: usize,
+    height: usize,
+    channels: usize,
+    op: &str,
+) -> Result<(ArrayView3<'a, T>, ArrayView3<'a, T>, basic::ArithmeticOp), JsError> {
+    let op = basic::ArithmeticOp::from_name(op).ok_or_else(|| JsError::new(&format!("unknown arithmetic operation '{}'", op)))?;
+    let a = ArrayView3::from_shape((height, width, channels), a).expect("Invalid dimensions");
+    let b_channels = b.len() / (width * height).max(1);
+    let b = ArrayView3::from_shape((height, width, b_channels), b).map_err(|_| channel_js_error(ChannelError::ShapeMismatch))?;
+    Ok((a, b, op))
+}
+
+/// Combine two images pixel by pixel (u8).
+#[wasm_bindgen]
+pub fn image_arithmetic_wasm(a: &[u8], b: &[u8], width: usize, height: usize, channels: usize, options: &ArithmeticOptions) -> Result<Vec<u8>, JsError> {
+    let (a, b, op) = arithmetic_operands(a, b, width, height, channels, &options.op)?;
+    let result = basic::image_arithmetic_u8(a, b, op, options.scale, options.offset, options.clamp).map_err(channel_js_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Combine two images pixel by pixel (f32).
+#[wasm_bindgen]
+pub fn image_arithmetic_f32_wasm(a: &[f32], b: &[f32], width: usize, height: usize, channels: usize, options: &ArithmeticOptions) -> Result<Vec<f32>, JsError> {
+    let (a, b, op) = arithmetic_operands(a, b, width, height, channels, &options.op)?;
+    let result = basic::image_arithmetic_f32(a, b, op, options.scale, options.offset, options.clamp).map_err(channel_js_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
 // ============================================================================
 // Color Adjustment Filters
 // ============================================================================
@@ -186,6 +531,13 @@ pub fn brightness_f32_wasm(data: &[f32], width: usize, height: usize, channels:
     result.into_raw_vec_and_offset().0
 }
 
+#[wasm_bindgen]
+pub fn brightness_12bit_wasm(data: &[u16], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u16> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = color_adjust::brightness_12bit(input.view(), amount);
+    result.into_raw_vec_and_offset().0
+}
+
 #[wasm_bindgen]
 pub fn contrast_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
@@ -200,6 +552,13 @@ pub fn contrast_f32_wasm(data: &[f32], width: usize, height: usize, channels: us
     result.into_raw_vec_and_offset().0
 }
 
+#[wasm_bindgen]
+pub fn contrast_12bit_wasm(data: &[u16], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u16> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = color_adjust::contrast_12bit(input.view(), amount);
+    result.into_raw_vec_and_offset().0
+}
+
 #[wasm_bindgen]
 pub fn saturation_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
@@ -242,18 +601,25 @@ pub fn exposure_f32_wasm(data: &[f32], width: usize, height: usize, channels: us
     result.into_raw_vec_and_offset().0
 }
 
+/// Parse a channel selection ("rgb", "r", "a", "rgba", ...).
+fn parse_channel_selection(name: &str) -> Result<color_adjust::ChannelSelection, JsError> {
+    color_adjust::ChannelSelection::from_name(name).ok_or_else(|| JsError::new(&format!("unknown channel selection '{}'", name)))
+}
+
+/// Invert the selected channels (`selection`: "rgb", "r", "a", "rgba", ...).
 #[wasm_bindgen]
-pub fn invert_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+pub fn invert_wasm(data: &[u8], width: usize, height: usize, channels: usize, selection: &str) -> Result<Vec<u8>, JsError> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_adjust::invert_u8(input.view());
-    result.into_raw_vec_and_offset().0
+    let result = color_adjust::invert_channels_u8(input.view(), parse_channel_selection(selection)?);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
+/// Invert the selected channels (f32).
 #[wasm_bindgen]
-pub fn invert_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+pub fn invert_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, selection: &str) -> Result<Vec<f32>, JsError> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_adjust::invert_f32(input.view());
-    result.into_raw_vec_and_offset().0
+    let result = color_adjust::invert_channels_f32(input.view(), parse_channel_selection(selection)?);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 // ============================================================================
@@ -274,17 +640,59 @@ pub fn hue_shift_f32_wasm(data: &[f32], width: usize, height: usize, channels: u
     result.into_raw_vec_and_offset().0
 }
 
+/// Parameters of `hsl_adjust_wasm` / `hsl_adjust_f32_wasm`
+/// (see `color_science::HslAdjustParams`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HslAdjustOptions {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub colorize: bool,
+}
+
+#[wasm_bindgen]
+impl HslAdjustOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<&HslAdjustOptions> for color_science::HslAdjustParams {
+    fn from(o: &HslAdjustOptions) -> Self {
+        Self { hue: o.hue, saturation: o.saturation, lightness: o.lightness, colorize: o.colorize }
+    }
+}
+
+/// Photoshop-style Hue/Saturation (u8).
+#[wasm_bindgen]
+pub fn hsl_adjust_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &HslAdjustOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = color_science::hsl_adjust_u8(input.view(), &options.into());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Photoshop-style Hue/Saturation (f32).
+#[wasm_bindgen]
+pub fn hsl_adjust_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &HslAdjustOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = color_science::hsl_adjust_f32(input.view(), &options.into());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Vibrance (u8); `protect_skin` 0.0-1.0 spares skin tones from the boost.
 #[wasm_bindgen]
-pub fn vibrance_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
+pub fn vibrance_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32, protect_skin: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_science::vibrance_u8(input.view(), amount);
+    let result = color_science::vibrance_protected_u8(input.view(), amount, protect_skin);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn vibrance_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
+pub fn vibrance_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32, protect_skin: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_science::vibrance_f32(input.view(), amount);
+    let result = color_science::vibrance_protected_f32(input.view(), amount, protect_skin);
     result.into_raw_vec_and_offset().0
 }
 
@@ -354,824 +762,3489 @@ pub fn temperature_f32_wasm(data: &[f32], width: usize, height: usize, channels:
     result.into_raw_vec_and_offset().0
 }
 
+/// White balance (u8): `kelvin` is the color temperature of the light
+/// (6504 = neutral), `tint` -1.0 (green) to 1.0 (magenta).
 #[wasm_bindgen]
-pub fn channel_mixer_wasm(data: &[u8], width: usize, height: usize, channels: usize, r_src: u8, g_src: u8, b_src: u8) -> Vec<u8> {
+pub fn white_balance_kelvin_wasm(data: &[u8], width: usize, height: usize, channels: usize, kelvin: f32, tint: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_science::channel_mixer_u8(input.view(), r_src, g_src, b_src);
+    let result = color_science::white_balance_kelvin_u8(input.view(), kelvin, tint);
     result.into_raw_vec_and_offset().0
 }
 
+/// White balance (f32).
 #[wasm_bindgen]
-pub fn channel_mixer_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, r_src: u8, g_src: u8, b_src: u8) -> Vec<f32> {
+pub fn white_balance_kelvin_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, kelvin: f32, tint: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_science::channel_mixer_f32(input.view(), r_src, g_src, b_src);
+    let result = color_science::white_balance_kelvin_f32(input.view(), kelvin, tint);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Equalize Histogram
-// ============================================================================
-
 #[wasm_bindgen]
-pub fn equalize_histogram_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+pub fn channel_mixer_wasm(data: &[u8], width: usize, height: usize, channels: usize, r_src: u8, g_src: u8, b_src: u8) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_adjust::equalize_histogram_u8(input.view());
+    let result = color_science::channel_mixer_u8(input.view(), r_src, g_src, b_src);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn equalize_histogram_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+pub fn channel_mixer_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, r_src: u8, g_src: u8, b_src: u8) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = color_adjust::equalize_histogram_f32(input.view());
+    let result = color_science::channel_mixer_f32(input.view(), r_src, g_src, b_src);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Stylize Filters
-// ============================================================================
-
+/// Match colors to a reference image with the same channel count
+/// (its height follows from the data length).
 #[wasm_bindgen]
-pub fn posterize_wasm(data: &[u8], width: usize, height: usize, channels: usize, levels: u8) -> Vec<u8> {
+pub fn match_color_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    reference: &[u8], reference_width: usize, amount: f32,
+) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::posterize_u8(input.view(), levels);
+    let reference_height = reference.len() / (reference_width * channels).max(1);
+    let reference = Array3::from_shape_vec((reference_height, reference_width, channels), reference.to_vec()).expect("Invalid reference dimensions");
+    let result = color_science::match_color_u8(input.view(), reference.view(), amount);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn posterize_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, levels: u8) -> Vec<f32> {
+pub fn match_color_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    reference: &[f32], reference_width: usize, amount: f32,
+) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::posterize_f32(input.view(), levels);
+    let reference_height = reference.len() / (reference_width * channels).max(1);
+    let reference = Array3::from_shape_vec((reference_height, reference_width, channels), reference.to_vec()).expect("Invalid reference dimensions");
+    let result = color_science::match_color_f32(input.view(), reference.view(), amount);
     result.into_raw_vec_and_offset().0
 }
 
+/// Split a flat `[x0, y0, x1, y1, ...]` array into one curve per entry of `lengths` (points per curve).
+fn split_curves(flat: &[f32], lengths: &[u32]) -> Vec<Vec<(f32, f32)>> {
+    let mut offset = 0;
+    lengths
+        .iter()
+        .map(|&n| {
+            let end = (offset + n as usize * 2).min(flat.len());
+            let curve = flat[offset..end].chunks(2).map(|c| (c[0], c[1])).collect();
+            offset = end;
+            curve
+        })
+        .collect()
+}
+
+/// BlackWhiteParams from [reds, yellows, greens, cyans, blues, magentas]
+/// (empty for Photoshop's defaults) and an optional tint (0.0-1.0).
+fn black_white_params(weights: &[f32], tint: Option<[f32; 3]>) -> color_science::BlackWhiteParams {
+    match *weights {
+        [reds, yellows, greens, cyans, blues, magentas] => {
+            color_science::BlackWhiteParams { reds, yellows, greens, cyans, blues, magentas, tint }
+        }
+        _ => color_science::BlackWhiteParams { tint, ..Default::default() },
+    }
+}
+
+/// Black & White (u8). `weights` has the six hue weights or is empty for
+/// the defaults; `tint` is [r, g, b] (0-255) or empty for none.
 #[wasm_bindgen]
-pub fn solarize_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: u8) -> Vec<u8> {
+pub fn black_white_wasm(data: &[u8], width: usize, height: usize, channels: usize, weights: &[f32], tint: &[u8]) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::solarize_u8(input.view(), threshold);
+    let tint = (tint.len() >= 3).then(|| [tint[0], tint[1], tint[2]].map(|v| v as f32 / 255.0));
+    let result = color_science::black_white_u8(input.view(), &black_white_params(weights, tint));
     result.into_raw_vec_and_offset().0
 }
 
+/// Black & White (f32). `tint` values are 0.0-1.0.
 #[wasm_bindgen]
-pub fn solarize_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32) -> Vec<f32> {
+pub fn black_white_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, weights: &[f32], tint: &[f32]) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::solarize_f32(input.view(), threshold);
+    let tint = (tint.len() >= 3).then(|| [tint[0], tint[1], tint[2]]);
+    let result = color_science::black_white_f32(input.view(), &black_white_params(weights, tint));
     result.into_raw_vec_and_offset().0
 }
 
+/// Options for [`replace_color_wasm`]; distances are Lab Delta E / 100.
 #[wasm_bindgen]
-pub fn threshold_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold_val: u8) -> Vec<u8> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::threshold_u8(input.view(), threshold_val);
-    result.into_raw_vec_and_offset().0
+#[derive(Debug, Clone, Copy)]
+pub struct ReplaceColorOptions {
+    pub tolerance: f32,
+    pub softness: f32,
+    pub preserve_luminosity: bool,
+}
+
+impl Default for ReplaceColorOptions {
+    fn default() -> Self {
+        Self { tolerance: 0.2, softness: 0.1, preserve_luminosity: false }
+    }
 }
 
 #[wasm_bindgen]
-pub fn threshold_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold_val: f32) -> Vec<f32> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::threshold_f32(input.view(), threshold_val);
-    result.into_raw_vec_and_offset().0
+impl ReplaceColorOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
+/// Replace a color throughout the image (u8); colors are [r, g, b] 0-255.
 #[wasm_bindgen]
-pub fn emboss_wasm(data: &[u8], width: usize, height: usize, channels: usize, angle: f32, depth: f32) -> Vec<u8> {
+pub fn replace_color_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    target_color: &[u8], replacement_color: &[u8], options: &ReplaceColorOptions,
+) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::emboss_u8(input.view(), angle, depth);
+    let target = [target_color[0], target_color[1], target_color[2]];
+    let replacement = [replacement_color[0], replacement_color[1], replacement_color[2]];
+    let result = color_science::replace_color_u8(
+        input.view(), target, replacement, options.tolerance, options.softness, options.preserve_luminosity,
+    );
     result.into_raw_vec_and_offset().0
 }
 
+/// Replace a color throughout the image (f32); colors are [r, g, b] 0.0-1.0.
 #[wasm_bindgen]
-pub fn emboss_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, angle: f32, depth: f32) -> Vec<f32> {
+pub fn replace_color_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    target_color: &[f32], replacement_color: &[f32], options: &ReplaceColorOptions,
+) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::emboss_f32(input.view(), angle, depth);
+    let target = [target_color[0], target_color[1], target_color[2]];
+    let replacement = [replacement_color[0], replacement_color[1], replacement_color[2]];
+    let result = color_science::replace_color_f32(
+        input.view(), target, replacement, options.tolerance, options.softness, options.preserve_luminosity,
+    );
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn pencil_sketch_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma_s: f32, shade_factor: f32) -> Vec<u8> {
+pub fn split_tone_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    shadow_color: &[u8], highlight_color: &[u8], balance: f32,
+) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::pencil_sketch_u8(input.view(), sigma_s, shade_factor);
+    let shadow = [shadow_color[0], shadow_color[1], shadow_color[2]];
+    let highlight = [highlight_color[0], highlight_color[1], highlight_color[2]];
+    let result = color_science::split_tone_u8(input.view(), shadow, highlight, balance);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn pencil_sketch_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, sigma_s: f32, shade_factor: f32) -> Vec<f32> {
+pub fn split_tone_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    shadow_color: &[f32], highlight_color: &[f32], balance: f32,
+) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::pencil_sketch_f32(input.view(), sigma_s, shade_factor);
+    let shadow = [shadow_color[0], shadow_color[1], shadow_color[2]];
+    let highlight = [highlight_color[0], highlight_color[1], highlight_color[2]];
+    let result = color_science::split_tone_f32(input.view(), shadow, highlight, balance);
     result.into_raw_vec_and_offset().0
 }
 
+/// Duotone with flat RGB ink colors and concatenated per-ink curves.
 #[wasm_bindgen]
-pub fn pixelate_wasm(data: &[u8], width: usize, height: usize, channels: usize, block_size: u32) -> Vec<u8> {
+pub fn duotone_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    inks_flat: &[u8], curves_flat: &[f32], curve_lengths: &[u32],
+) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::pixelate_u8(input.view(), block_size);
+    let inks: Vec<[u8; 3]> = inks_flat.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect();
+    let result = color_science::duotone_u8(input.view(), &inks, &split_curves(curves_flat, curve_lengths));
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn pixelate_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, block_size: u32) -> Vec<f32> {
+pub fn duotone_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    inks_flat: &[f32], curves_flat: &[f32], curve_lengths: &[u32],
+) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::pixelate_f32(input.view(), block_size);
+    let inks: Vec<[f32; 3]> = inks_flat.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect();
+    let result = color_science::duotone_f32(input.view(), &inks, &split_curves(curves_flat, curve_lengths));
     result.into_raw_vec_and_offset().0
 }
 
-#[wasm_bindgen]
-pub fn vignette_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::vignette_u8(input.view(), amount);
-    result.into_raw_vec_and_offset().0
+fn parse_deficiency(name: &str) -> Result<color_science::ColorVisionDeficiency, JsError> {
+    color_science::ColorVisionDeficiency::from_name(name)
+        .ok_or_else(|| JsError::new(&format!("unknown color vision deficiency '{}'", name)))
 }
 
+/// Simulate a color vision deficiency ("protan", "deutan", "tritan"); severity 0.0-1.0 (u8).
 #[wasm_bindgen]
-pub fn vignette_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
+pub fn simulate_cvd_wasm(data: &[u8], width: usize, height: usize, channels: usize, deficiency: &str, severity: f32) -> Result<Vec<u8>, JsError> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = stylize::vignette_f32(input.view(), amount);
-    result.into_raw_vec_and_offset().0
+    let result = color_science::simulate_cvd_u8(input.view(), parse_deficiency(deficiency)?, severity);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
-// ============================================================================
-// Levels & Curves Filters
-// ============================================================================
-
+/// Simulate a color vision deficiency ("protan", "deutan", "tritan"); severity 0.0-1.0 (f32).
 #[wasm_bindgen]
-pub fn levels_wasm(
-    data: &[u8], width: usize, height: usize, channels: usize,
-    in_black: u8, in_white: u8, out_black: u8, out_white: u8, gamma_val: f32,
-) -> Vec<u8> {
+pub fn simulate_cvd_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, deficiency: &str, severity: f32) -> Result<Vec<f32>, JsError> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = levels_curves::levels_u8(input.view(), in_black, in_white, out_black, out_white, gamma_val);
-    result.into_raw_vec_and_offset().0
+    let result = color_science::simulate_cvd_f32(input.view(), parse_deficiency(deficiency)?, severity);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
+/// Recolor for viewers with a color vision deficiency; amount 0.0-1.0 (u8).
 #[wasm_bindgen]
-pub fn levels_f32_wasm(
-    data: &[f32], width: usize, height: usize, channels: usize,
-    in_black: f32, in_white: f32, out_black: f32, out_white: f32, gamma_val: f32,
-) -> Vec<f32> {
+pub fn daltonize_wasm(data: &[u8], width: usize, height: usize, channels: usize, deficiency: &str, amount: f32) -> Result<Vec<u8>, JsError> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = levels_curves::levels_f32(input.view(), in_black, in_white, out_black, out_white, gamma_val);
-    result.into_raw_vec_and_offset().0
+    let result = color_science::daltonize_u8(input.view(), parse_deficiency(deficiency)?, amount);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
+/// Recolor for viewers with a color vision deficiency; amount 0.0-1.0 (f32).
 #[wasm_bindgen]
-pub fn curves_wasm(data: &[u8], width: usize, height: usize, channels: usize, points_flat: &[f32]) -> Vec<u8> {
+pub fn daltonize_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, deficiency: &str, amount: f32) -> Result<Vec<f32>, JsError> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    // Convert flat array to Vec<(f32, f32)>
-    let points: Vec<(f32, f32)> = points_flat.chunks(2).map(|c| (c[0], c[1])).collect();
-    let result = levels_curves::curves_u8(input.view(), &points);
-    result.into_raw_vec_and_offset().0
+    let result = color_science::daltonize_f32(input.view(), parse_deficiency(deficiency)?, amount);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
-#[wasm_bindgen]
-pub fn curves_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, points_flat: &[f32]) -> Vec<f32> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let points: Vec<(f32, f32)> = points_flat.chunks(2).map(|c| (c[0], c[1])).collect();
-    let result = levels_curves::curves_f32(input.view(), &points);
-    result.into_raw_vec_and_offset().0
+// ============================================================================
+// Equalize Histogram
+// ============================================================================
+
+fn parse_equalize_mode(name: &str) -> Result<color_adjust::EqualizeMode, JsError> {
+    color_adjust::EqualizeMode::from_name(name).ok_or_else(|| JsError::new(&format!("unknown equalize mode '{}'", name)))
 }
 
+/// Equalize histogram (u8). `mode` is "per_channel" or "luminance"; `mask` is
+/// (height * width) with 0 = ignored, 255 = equalized, or empty for none.
 #[wasm_bindgen]
-pub fn auto_levels_wasm(data: &[u8], width: usize, height: usize, channels: usize, clip_percent: f32) -> Vec<u8> {
+pub fn equalize_histogram_wasm(data: &[u8], width: usize, height: usize, channels: usize, mode: &str, mask: &[u8]) -> Result<Vec<u8>, JsError> {
+    let mode = parse_equalize_mode(mode)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = levels_curves::auto_levels_u8(input.view(), clip_percent);
-    result.into_raw_vec_and_offset().0
+    let mask = (!mask.is_empty()).then(|| Array2::from_shape_vec((height, width), mask.to_vec()).expect("Invalid mask dimensions"));
+    let result = color_adjust::equalize_histogram_masked_u8(input.view(), mode, mask.as_ref().map(|m| m.view()));
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
+/// Equalize histogram (f32). `mask` values are 0.0-1.0.
 #[wasm_bindgen]
-pub fn auto_levels_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, clip_percent: f32) -> Vec<f32> {
+pub fn equalize_histogram_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mode: &str, mask: &[f32]) -> Result<Vec<f32>, JsError> {
+    let mode = parse_equalize_mode(mode)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = levels_curves::auto_levels_f32(input.view(), clip_percent);
-    result.into_raw_vec_and_offset().0
+    let mask = (!mask.is_empty()).then(|| Array2::from_shape_vec((height, width), mask.to_vec()).expect("Invalid mask dimensions"));
+    let result = color_adjust::equalize_histogram_masked_f32(input.view(), mode, mask.as_ref().map(|m| m.view()));
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 // ============================================================================
-// Sharpen Filters
+// Stylize Filters
 // ============================================================================
 
 #[wasm_bindgen]
-pub fn sharpen_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
+pub fn posterize_wasm(data: &[u8], width: usize, height: usize, channels: usize, levels: u8) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::sharpen_u8(input.view(), amount);
+    let result = stylize::posterize_u8(input.view(), levels);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn sharpen_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
+pub fn posterize_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, levels: u8) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::sharpen_f32(input.view(), amount);
+    let result = stylize::posterize_f32(input.view(), levels);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn unsharp_mask_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32, radius: f32, threshold_val: u8) -> Vec<u8> {
+pub fn solarize_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: u8) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::unsharp_mask_u8(input.view(), amount, radius, threshold_val);
+    let result = stylize::solarize_u8(input.view(), threshold);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn unsharp_mask_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32, radius: f32, threshold_val: f32) -> Vec<f32> {
+pub fn solarize_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::unsharp_mask_f32(input.view(), amount, radius, threshold_val);
+    let result = stylize::solarize_f32(input.view(), threshold);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn high_pass_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+pub fn threshold_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold_val: u8) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::high_pass_u8(input.view(), radius);
+    let result = stylize::threshold_u8(input.view(), threshold_val);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn high_pass_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn threshold_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold_val: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::high_pass_f32(input.view(), radius);
+    let result = stylize::threshold_f32(input.view(), threshold_val);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn motion_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, angle: f32, distance: f32) -> Vec<u8> {
+pub fn threshold_otsu_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> u8 {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::motion_blur_u8(input.view(), angle, distance);
-    result.into_raw_vec_and_offset().0
+    stylize::threshold_otsu_u8(input.view())
 }
 
 #[wasm_bindgen]
-pub fn motion_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, angle: f32, distance: f32) -> Vec<f32> {
+pub fn threshold_otsu_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> f32 {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = sharpen::motion_blur_f32(input.view(), angle, distance);
-    result.into_raw_vec_and_offset().0
+    stylize::threshold_otsu_f32(input.view())
 }
 
-// ============================================================================
-// Edge Detection Filters
-// ============================================================================
-
 #[wasm_bindgen]
-pub fn sobel_wasm(data: &[u8], width: usize, height: usize, channels: usize, direction: &str, kernel_size: u8) -> Vec<u8> {
+pub fn adaptive_threshold_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    block_size: u32, c: f32, method: &str,
+) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::sobel_u8(input.view(), direction, kernel_size);
+    let result = stylize::adaptive_threshold_u8(input.view(), block_size, c, stylize::AdaptiveMethod::parse(method));
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn sobel_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, direction: &str, kernel_size: u8) -> Vec<f32> {
+pub fn adaptive_threshold_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    block_size: u32, c: f32, method: &str,
+) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::sobel_f32(input.view(), direction, kernel_size);
+    let result = stylize::adaptive_threshold_f32(input.view(), block_size, c, stylize::AdaptiveMethod::parse(method));
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn laplacian_wasm(data: &[u8], width: usize, height: usize, channels: usize, kernel_size: u8) -> Vec<u8> {
+pub fn emboss_wasm(data: &[u8], width: usize, height: usize, channels: usize, angle: f32, depth: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::laplacian_u8(input.view(), kernel_size);
+    let result = stylize::emboss_u8(input.view(), angle, depth);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn laplacian_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, kernel_size: u8) -> Vec<f32> {
+pub fn emboss_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, angle: f32, depth: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::laplacian_f32(input.view(), kernel_size);
+    let result = stylize::emboss_f32(input.view(), angle, depth);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn find_edges_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma: f64, low_threshold: f64, high_threshold: f64) -> Vec<u8> {
+pub fn pencil_sketch_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma_s: f32, shade_factor: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::find_edges_u8(input.view(), sigma, low_threshold, high_threshold);
+    let result = stylize::pencil_sketch_u8(input.view(), sigma_s, shade_factor);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn find_edges_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, sigma: f64, low_threshold: f64, high_threshold: f64) -> Vec<f32> {
+pub fn pencil_sketch_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, sigma_s: f32, shade_factor: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::find_edges_f32(input.view(), sigma, low_threshold, high_threshold);
+    let result = stylize::pencil_sketch_f32(input.view(), sigma_s, shade_factor);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Draw Contours
-// ============================================================================
+/// Parameters of `xdog_wasm` / `xdog_f32_wasm` (see `stylize::xdog_f32`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct XdogOptions {
+    pub sigma: f32,
+    pub k: f32,
+    pub p: f32,
+    pub epsilon: f32,
+    pub phi: f32,
+}
+
+impl Default for XdogOptions {
+    fn default() -> Self {
+        Self { sigma: 1.0, k: 1.6, p: 20.0, epsilon: 0.1, phi: 10.0 }
+    }
+}
 
 #[wasm_bindgen]
-pub fn draw_contours_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: u8, line_width: u8, color_r: u8, color_g: u8, color_b: u8) -> Vec<u8> {
+impl XdogOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn xdog_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &XdogOptions) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::draw_contours_u8(input.view(), threshold, line_width, color_r, color_g, color_b);
+    let o = options;
+    let result = stylize::xdog_u8(input.view(), o.sigma, o.k, o.p, o.epsilon, o.phi);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn draw_contours_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32, line_width: u8, color_r: f32, color_g: f32, color_b: f32) -> Vec<f32> {
+pub fn xdog_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &XdogOptions) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = edge::draw_contours_f32(input.view(), threshold, line_width, color_r, color_g, color_b);
+    let o = options;
+    let result = stylize::xdog_f32(input.view(), o.sigma, o.k, o.p, o.epsilon, o.phi);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Noise Filters
-// ============================================================================
-
 #[wasm_bindgen]
-pub fn add_noise_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32, gaussian: bool, monochrome: bool, seed: u32) -> Vec<u8> {
+pub fn pixelate_wasm(data: &[u8], width: usize, height: usize, channels: usize, block_size: u32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = noise::add_noise_u8(input.view(), amount, gaussian, monochrome, seed as u64);
+    let result = stylize::pixelate_u8(input.view(), block_size);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn add_noise_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32, gaussian: bool, monochrome: bool, seed: u32) -> Vec<f32> {
+pub fn pixelate_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, block_size: u32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = noise::add_noise_f32(input.view(), amount, gaussian, monochrome, seed as u64);
+    let result = stylize::pixelate_f32(input.view(), block_size);
     result.into_raw_vec_and_offset().0
 }
 
+/// Shaped, masked pixelate (u8). `shape` is "square", "circle" or "hexagon";
+/// `mask` is (height * width) with 0 = untouched, 255 = pixelated, or empty
+/// to pixelate everything.
 #[wasm_bindgen]
-pub fn median_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32) -> Vec<u8> {
+pub fn pixelate_shaped_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    block_size: u32, shape: &str, mask: &[u8],
+) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = noise::median_u8(input.view(), radius);
+    let mask = (!mask.is_empty()).then(|| Array2::from_shape_vec((height, width), mask.to_vec()).expect("Invalid mask dimensions"));
+    let result = stylize::pixelate_shaped_u8(input.view(), block_size, stylize::PixelateShape::parse(shape), mask.as_ref().map(|m| m.view()));
     result.into_raw_vec_and_offset().0
 }
 
+/// Shaped, masked pixelate (f32). `mask` values are 0.0-1.0.
 #[wasm_bindgen]
-pub fn median_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: u32) -> Vec<f32> {
+pub fn pixelate_shaped_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    block_size: u32, shape: &str, mask: &[f32],
+) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = noise::median_f32(input.view(), radius);
+    let mask = (!mask.is_empty()).then(|| Array2::from_shape_vec((height, width), mask.to_vec()).expect("Invalid mask dimensions"));
+    let result = stylize::pixelate_shaped_f32(input.view(), block_size, stylize::PixelateShape::parse(shape), mask.as_ref().map(|m| m.view()));
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn denoise_wasm(data: &[u8], width: usize, height: usize, channels: usize, strength: f32) -> Vec<u8> {
+pub fn vignette_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = noise::denoise_u8(input.view(), strength);
+    let result = stylize::vignette_u8(input.view(), amount);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn denoise_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, strength: f32) -> Vec<f32> {
+pub fn vignette_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = noise::denoise_f32(input.view(), strength);
+    let result = stylize::vignette_f32(input.view(), amount);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Morphology Filters
-// ============================================================================
+/// Parameters of `vignette_params_wasm` / `vignette_params_f32_wasm` (see `stylize::VignetteParams`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct VignetteOptions {
+    pub amount: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub midpoint: f32,
+    pub roundness: f32,
+    pub feather: f32,
+    /// Fade towards (color_r, color_g, color_b) instead of darkening
+    pub colored: bool,
+    pub color_r: f32,
+    pub color_g: f32,
+    pub color_b: f32,
+}
+
+impl Default for VignetteOptions {
+    fn default() -> Self {
+        let d = stylize::VignetteParams::default();
+        Self {
+            amount: d.amount,
+            center_x: d.center_x,
+            center_y: d.center_y,
+            midpoint: d.midpoint,
+            roundness: d.roundness,
+            feather: d.feather,
+            colored: false,
+            color_r: 0.0,
+            color_g: 0.0,
+            color_b: 0.0,
+        }
+    }
+}
 
 #[wasm_bindgen]
-pub fn dilate_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::dilate_u8(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+impl VignetteOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<&VignetteOptions> for stylize::VignetteParams {
+    fn from(o: &VignetteOptions) -> Self {
+        Self {
+            amount: o.amount,
+            center_x: o.center_x,
+            center_y: o.center_y,
+            midpoint: o.midpoint,
+            roundness: o.roundness,
+            feather: o.feather,
+            color: o.colored.then_some([o.color_r, o.color_g, o.color_b]),
+        }
+    }
 }
 
 #[wasm_bindgen]
-pub fn dilate_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn vignette_params_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &VignetteOptions) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::dilate_f32(input.view(), radius);
+    let result = stylize::vignette_params_u8(input.view(), &options.into());
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn erode_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+pub fn vignette_params_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &VignetteOptions) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::erode_u8(input.view(), radius);
+    let result = stylize::vignette_params_f32(input.view(), &options.into());
     result.into_raw_vec_and_offset().0
 }
 
+// ============================================================================
+// Levels & Curves Filters
+// ============================================================================
+
+/// Parse a levels channel name ("rgb", "r", "g", "b", "alpha", "luminosity").
+fn parse_levels_channel(name: &str) -> Result<levels_curves::LevelsChannel, JsError> {
+    levels_curves::LevelsChannel::from_name(name).ok_or_else(|| JsError::new(&format!("unknown levels channel '{}'", name)))
+}
+
 #[wasm_bindgen]
-pub fn erode_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn levels_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    in_black: u8, in_white: u8, out_black: u8, out_white: u8, gamma_val: f32, channel: &str,
+) -> Result<Vec<u8>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::erode_f32(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let result = levels_curves::levels_channel_u8(input.view(), in_black, in_white, out_black, out_white, gamma_val, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 #[wasm_bindgen]
-pub fn morphology_open_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+pub fn levels_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    in_black: f32, in_white: f32, out_black: f32, out_white: f32, gamma_val: f32, channel: &str,
+) -> Result<Vec<f32>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::open_u8(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let result = levels_curves::levels_channel_f32(input.view(), in_black, in_white, out_black, out_white, gamma_val, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
+/// Input and output levels of [`levels_12bit_wasm`], all 0-4095.
 #[wasm_bindgen]
-pub fn morphology_open_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::open_f32(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+#[derive(Debug, Clone, Copy)]
+pub struct Levels12bitOptions {
+    pub in_black: u16,
+    pub in_white: u16,
+    pub out_black: u16,
+    pub out_white: u16,
+    pub gamma: f32,
+}
+
+impl Default for Levels12bitOptions {
+    fn default() -> Self {
+        Self { in_black: 0, in_white: 4095, out_black: 0, out_white: 4095, gamma: 1.0 }
+    }
 }
 
 #[wasm_bindgen]
-pub fn morphology_close_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+impl Levels12bitOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn levels_12bit_wasm(
+    data: &[u16], width: usize, height: usize, channels: usize, options: &Levels12bitOptions, channel: &str,
+) -> Result<Vec<u16>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::close_u8(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let o = options;
+    let result = levels_curves::levels_channel_12bit(input.view(), o.in_black, o.in_white, o.out_black, o.out_white, o.gamma, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
+/// `channel` "luminosity" replaces the `luminosity_only` flag of the Python binding.
 #[wasm_bindgen]
-pub fn morphology_close_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn curves_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize,
+    points_flat: &[f32], interpolation: &str, channel: &str,
+) -> Result<Vec<u8>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::close_f32(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    // Convert flat array to Vec<(f32, f32)>
+    let points: Vec<(f32, f32)> = points_flat.chunks(2).map(|c| (c[0], c[1])).collect();
+    let interpolation = levels_curves::CurveInterpolation::parse(interpolation);
+    let result = levels_curves::curves_channel_u8(input.view(), &points, interpolation, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 #[wasm_bindgen]
-pub fn morphology_gradient_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+pub fn curves_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize,
+    points_flat: &[f32], interpolation: &str, channel: &str,
+) -> Result<Vec<f32>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::gradient_u8(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let points: Vec<(f32, f32)> = points_flat.chunks(2).map(|c| (c[0], c[1])).collect();
+    let interpolation = levels_curves::CurveInterpolation::parse(interpolation);
+    let result = levels_curves::curves_channel_f32(input.view(), &points, interpolation, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 #[wasm_bindgen]
-pub fn morphology_gradient_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn curves_12bit_wasm(
+    data: &[u16], width: usize, height: usize, channels: usize,
+    points_flat: &[f32], interpolation: &str, channel: &str,
+) -> Result<Vec<u16>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::gradient_f32(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let points: Vec<(f32, f32)> = points_flat.chunks(2).map(|c| (c[0], c[1])).collect();
+    let interpolation = levels_curves::CurveInterpolation::parse(interpolation);
+    let result = levels_curves::curves_channel_12bit(input.view(), &points, interpolation, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 #[wasm_bindgen]
-pub fn tophat_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+pub fn auto_levels_wasm(
+    data: &[u8], width: usize, height: usize, channels: usize, clip_percent: f32, channel: &str,
+) -> Result<Vec<u8>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::tophat_u8(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let result = levels_curves::auto_levels_channel_u8(input.view(), clip_percent, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 #[wasm_bindgen]
-pub fn tophat_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn auto_levels_f32_wasm(
+    data: &[f32], width: usize, height: usize, channels: usize, clip_percent: f32, channel: &str,
+) -> Result<Vec<f32>, JsError> {
+    let channel = parse_levels_channel(channel)?;
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::tophat_f32(input.view(), radius);
-    result.into_raw_vec_and_offset().0
+    let result = levels_curves::auto_levels_channel_f32(input.view(), clip_percent, channel);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 #[wasm_bindgen]
-pub fn blackhat_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+pub fn auto_contrast_wasm(data: &[u8], width: usize, height: usize, channels: usize, clip_percent: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::blackhat_u8(input.view(), radius);
+    let result = levels_curves::auto_contrast_u8(input.view(), clip_percent);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn blackhat_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+pub fn auto_contrast_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, clip_percent: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = morphology::blackhat_f32(input.view(), radius);
+    let result = levels_curves::auto_contrast_f32(input.view(), clip_percent);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// WASM-compatible Blur (no rayon)
-// ============================================================================
-
 #[wasm_bindgen]
-pub fn gaussian_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma: f32) -> Vec<u8> {
+pub fn auto_tone_wasm(data: &[u8], width: usize, height: usize, channels: usize, clip_percent: f32, snap_neutral: bool) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = blur_wasm::gaussian_blur_wasm_u8(input.view(), sigma);
+    let result = levels_curves::auto_tone_u8(input.view(), clip_percent, snap_neutral);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn gaussian_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, sigma: f32) -> Vec<f32> {
+pub fn auto_tone_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, clip_percent: f32, snap_neutral: bool) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = blur_wasm::gaussian_blur_wasm_f32(input.view(), sigma);
+    let result = levels_curves::auto_tone_f32(input.view(), clip_percent, snap_neutral);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn box_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32) -> Vec<u8> {
+pub fn auto_color_wasm(data: &[u8], width: usize, height: usize, channels: usize, clip_percent: f32, snap_neutral: bool) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = blur_wasm::box_blur_wasm_u8(input.view(), radius);
+    let result = levels_curves::auto_color_u8(input.view(), clip_percent, snap_neutral);
     result.into_raw_vec_and_offset().0
 }
 
 #[wasm_bindgen]
-pub fn box_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: u32) -> Vec<f32> {
+pub fn auto_color_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, clip_percent: f32, snap_neutral: bool) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = blur_wasm::box_blur_wasm_f32(input.view(), radius);
+    let result = levels_curves::auto_color_f32(input.view(), clip_percent, snap_neutral);
     result.into_raw_vec_and_offset().0
 }
 
 // ============================================================================
-// Rotation and Mirroring
+// Sharpen Filters
 // ============================================================================
 
-/// Rotate image 90 degrees clockwise (u8).
-/// Returns flat array with dimensions swapped (W, H instead of H, W).
 #[wasm_bindgen]
-pub fn rotate_90_cw_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+pub fn sharpen_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_90_cw_u8(input.view());
+    let result = sharpen::sharpen_u8(input.view(), amount);
     result.into_raw_vec_and_offset().0
 }
 
-/// Rotate image 90 degrees clockwise (f32).
 #[wasm_bindgen]
-pub fn rotate_90_cw_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+pub fn sharpen_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_90_cw_f32(input.view());
+    let result = sharpen::sharpen_f32(input.view(), amount);
     result.into_raw_vec_and_offset().0
 }
 
-/// Rotate image 180 degrees (u8).
 #[wasm_bindgen]
-pub fn rotate_180_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+pub fn unsharp_mask_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32, radius: f32, threshold_val: u8) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_180_u8(input.view());
+    let result = sharpen::unsharp_mask_u8(input.view(), amount, radius, threshold_val);
     result.into_raw_vec_and_offset().0
 }
 
-/// Rotate image 180 degrees (f32).
 #[wasm_bindgen]
-pub fn rotate_180_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+pub fn unsharp_mask_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32, radius: f32, threshold_val: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_180_f32(input.view());
+    let result = sharpen::unsharp_mask_f32(input.view(), amount, radius, threshold_val);
     result.into_raw_vec_and_offset().0
 }
 
-/// Rotate image 270 degrees clockwise (90 CCW) (u8).
-/// Returns flat array with dimensions swapped (W, H instead of H, W).
+/// Parameters of `unsharp_mask_params_wasm` / `unsharp_mask_params_f32_wasm`
+/// (see `sharpen::UnsharpMaskParams`). `threshold` is 0.0-1.0 for both.
 #[wasm_bindgen]
-pub fn rotate_270_cw_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_270_cw_u8(input.view());
-    result.into_raw_vec_and_offset().0
+#[derive(Debug, Clone, Copy)]
+pub struct UnsharpMaskOptions {
+    pub amount: f32,
+    pub radius: f32,
+    pub threshold: f32,
+    pub edge_mask: f32,
+    pub shadow_amount: f32,
+    pub highlight_amount: f32,
+}
+
+impl Default for UnsharpMaskOptions {
+    fn default() -> Self {
+        let d = sharpen::UnsharpMaskParams::default();
+        Self {
+            amount: d.amount,
+            radius: d.radius,
+            threshold: d.threshold,
+            edge_mask: d.edge_mask,
+            shadow_amount: d.shadow_amount,
+            highlight_amount: d.highlight_amount,
+        }
+    }
 }
 
-/// Rotate image 270 degrees clockwise (90 CCW) (f32).
 #[wasm_bindgen]
-pub fn rotate_270_cw_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
-    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_270_cw_f32(input.view());
-    result.into_raw_vec_and_offset().0
+impl UnsharpMaskOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<&UnsharpMaskOptions> for sharpen::UnsharpMaskParams {
+    fn from(o: &UnsharpMaskOptions) -> Self {
+        Self {
+            amount: o.amount,
+            radius: o.radius,
+            threshold: o.threshold,
+            edge_mask: o.edge_mask,
+            shadow_amount: o.shadow_amount,
+            highlight_amount: o.highlight_amount,
+        }
+    }
 }
 
-/// Rotate image by specified degrees (90, 180, or 270) (u8).
-/// For 90/270, dimensions are swapped in output.
 #[wasm_bindgen]
-pub fn rotate_wasm(data: &[u8], width: usize, height: usize, channels: usize, degrees: u32) -> Vec<u8> {
+pub fn unsharp_mask_params_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &UnsharpMaskOptions) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_u8(input.view(), degrees);
+    let result = sharpen::unsharp_mask_params_u8(input.view(), &options.into());
     result.into_raw_vec_and_offset().0
 }
 
-/// Rotate image by specified degrees (90, 180, or 270) (f32).
 #[wasm_bindgen]
-pub fn rotate_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, degrees: u32) -> Vec<f32> {
+pub fn unsharp_mask_params_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &UnsharpMaskOptions) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::rotate_f32(input.view(), degrees);
+    let result = sharpen::unsharp_mask_params_f32(input.view(), &options.into());
     result.into_raw_vec_and_offset().0
 }
 
-/// Flip image horizontally (mirror left-right) (u8).
 #[wasm_bindgen]
-pub fn flip_horizontal_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+pub fn high_pass_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::flip_horizontal_u8(input.view());
+    let result = sharpen::high_pass_u8(input.view(), radius);
     result.into_raw_vec_and_offset().0
 }
 
-/// Flip image horizontally (mirror left-right) (f32).
 #[wasm_bindgen]
-pub fn flip_horizontal_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+pub fn high_pass_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::flip_horizontal_f32(input.view());
+    let result = sharpen::high_pass_f32(input.view(), radius);
     result.into_raw_vec_and_offset().0
 }
 
-/// Flip image vertically (mirror top-bottom) (u8).
 #[wasm_bindgen]
-pub fn flip_vertical_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+pub fn clarity_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32, radius: f32) -> Vec<u8> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::flip_vertical_u8(input.view());
+    let result = sharpen::clarity_u8(input.view(), amount, radius);
     result.into_raw_vec_and_offset().0
 }
 
-/// Flip image vertically (mirror top-bottom) (f32).
 #[wasm_bindgen]
-pub fn flip_vertical_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+pub fn clarity_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32, radius: f32) -> Vec<f32> {
     let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
-    let result = rotate::flip_vertical_f32(input.view());
+    let result = sharpen::clarity_f32(input.view(), amount, radius);
     result.into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Layer Effects: Satin
-// ============================================================================
+#[wasm_bindgen]
+pub fn texture_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::texture_u8(input.view(), amount);
+    result.into_raw_vec_and_offset().0
+}
 
-/// Apply satin effect to RGBA u8 image.
-///
-/// Creates silky interior shading by compositing shifted, blurred copies
-/// of the alpha channel.
 #[wasm_bindgen]
-pub fn satin_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-    angle: f32,
-    distance: f32,
-    size: f32,
-    invert: bool,
-) -> Vec<u8> {
-    use ndarray::Array2;
+pub fn texture_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::texture_f32(input.view(), amount);
+    result.into_raw_vec_and_offset().0
+}
 
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+/// Smart sharpen settings; the mode is passed by name next to them.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct SmartSharpenOptions {
+    pub amount: f32,
+    pub radius: f32,
+    pub angle: f32,
+}
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
+impl Default for SmartSharpenOptions {
+    fn default() -> Self {
+        let d = sharpen::SmartSharpenParams::default();
+        Self { amount: d.amount, radius: d.radius, angle: d.angle }
     }
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
+#[wasm_bindgen]
+impl SmartSharpenOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
     }
+}
 
-    // Calculate offset direction
-    let angle_rad = angle.to_radians();
-    let dx = (angle_rad.cos() * distance).round() as isize;
-    let dy = (-angle_rad.sin() * distance).round() as isize;
+fn smart_sharpen_params(mode: &str, o: &SmartSharpenOptions) -> Result<sharpen::SmartSharpenParams, JsError> {
+    let mode = sharpen::SmartSharpenMode::from_name(mode)
+        .ok_or_else(|| JsError::new(&format!("unknown smart sharpen mode '{}'", mode)))?;
+    Ok(sharpen::SmartSharpenParams { amount: o.amount, radius: o.radius, mode, angle: o.angle })
+}
 
-    // Create offset copies
-    let mut offset_a = Array2::<f32>::zeros((height, width));
-    let mut offset_b = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let sx_a = (x as isize + dx).clamp(0, width as isize - 1) as usize;
-            let sy_a = (y as isize + dy).clamp(0, height as isize - 1) as usize;
-            offset_a[[y, x]] = alpha[[sy_a, sx_a]];
+#[wasm_bindgen]
+pub fn smart_sharpen_wasm(data: &[u8], width: usize, height: usize, channels: usize, mode: &str, options: &SmartSharpenOptions) -> Result<Vec<u8>, JsError> {
+    let params = smart_sharpen_params(mode, options)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::smart_sharpen_u8(input.view(), &params);
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-            let sx_b = (x as isize - dx).clamp(0, width as isize - 1) as usize;
-            let sy_b = (y as isize - dy).clamp(0, height as isize - 1) as usize;
-            offset_b[[y, x]] = alpha[[sy_b, sx_b]];
-        }
-    }
+#[wasm_bindgen]
+pub fn smart_sharpen_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mode: &str, options: &SmartSharpenOptions) -> Result<Vec<f32>, JsError> {
+    let params = smart_sharpen_params(mode, options)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::smart_sharpen_f32(input.view(), &params);
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    // Blur both copies
-    let blurred_a = blur_alpha_f32(&offset_a, size);
-    let blurred_b = blur_alpha_f32(&offset_b, size);
+#[wasm_bindgen]
+pub fn motion_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, angle: f32, distance: f32, border_mode: &str) -> Result<Vec<u8>, JsError> {
+    let border = parse_border_mode(border_mode)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::motion_blur_u8(input.view(), angle, distance, border);
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    // Compute satin mask
-    let mut satin_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let diff = (blurred_a[[y, x]] - blurred_b[[y, x]]).abs();
-            let mask_val = if invert { 1.0 - diff } else { diff };
-            satin_mask[[y, x]] = mask_val * alpha[[y, x]];
-        }
-    }
+#[wasm_bindgen]
+pub fn motion_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, angle: f32, distance: f32, border_mode: &str) -> Result<Vec<f32>, JsError> {
+    let border = parse_border_mode(border_mode)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::motion_blur_f32(input.view(), angle, distance, border);
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    // Create result
-    let mut result = input_f32.clone();
-    let satin_r = color_r as f32 / 255.0;
-    let satin_g = color_g as f32 / 255.0;
-    let satin_b = color_b as f32 / 255.0;
+/// Spin blur settings; the border mode is passed by name next to them.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct SpinBlurOptions {
+    pub angle: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = alpha[[y, x]];
-            if orig_a <= 0.0 { continue; }
-
-            let satin_a = satin_mask[[y, x]] * opacity;
-            if satin_a > 0.0 {
-                let out_a = satin_a + result[[y, x, 3]] * (1.0 - satin_a);
-                if out_a > 0.0 {
-                    result[[y, x, 0]] = (satin_r * satin_a + result[[y, x, 0]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 1]] = (satin_g * satin_a + result[[y, x, 1]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 2]] = (satin_b * satin_a + result[[y, x, 2]] * result[[y, x, 3]] * (1.0 - satin_a)) / out_a;
-                    result[[y, x, 3]] = out_a;
-                }
-            }
-        }
+impl Default for SpinBlurOptions {
+    fn default() -> Self {
+        let d = sharpen::SpinBlurParams::default();
+        Self { angle: d.angle, center_x: d.center_x, center_y: d.center_y }
+    }
+}
+
+#[wasm_bindgen]
+impl SpinBlurOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
     }
+}
+
+fn spin_blur_params(o: &SpinBlurOptions, border_mode: &str) -> Result<sharpen::SpinBlurParams, JsError> {
+    let border = parse_border_mode(border_mode)?;
+    Ok(sharpen::SpinBlurParams { angle: o.angle, center_x: o.center_x, center_y: o.center_y, border })
+}
+
+#[wasm_bindgen]
+pub fn spin_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &SpinBlurOptions, border_mode: &str) -> Result<Vec<u8>, JsError> {
+    let params = spin_blur_params(options, border_mode)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::spin_blur_u8(input.view(), &params);
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
+#[wasm_bindgen]
+pub fn spin_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &SpinBlurOptions, border_mode: &str) -> Result<Vec<f32>, JsError> {
+    let params = spin_blur_params(options, border_mode)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = sharpen::spin_blur_f32(input.view(), &params);
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
 // ============================================================================
-// Layer Effects: Gradient Overlay
+// Edge Detection Filters
 // ============================================================================
 
-/// Apply gradient overlay to RGBA u8 image.
-///
-/// Gradient stops are passed as flat array: [pos, r, g, b, pos, r, g, b, ...]
-/// Style: "linear", "radial", "angle", "reflected", "diamond"
 #[wasm_bindgen]
-pub fn gradient_overlay_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    stops: &[f32],
-    style: &str,
-    angle: f32,
-    scale_x: f32,
-    scale_y: f32,
-    offset_x: f32,
-    offset_y: f32,
-    reverse: bool,
-    opacity: f32,
-) -> Vec<u8> {
-    // Parse gradient stops
-    struct GradientStop { position: f32, r: f32, g: f32, b: f32 }
-    let mut gradient_stops: Vec<GradientStop> = stops.chunks(4)
-        .filter(|c| c.len() == 4)
-        .map(|c| GradientStop { position: c[0], r: c[1] / 255.0, g: c[2] / 255.0, b: c[3] / 255.0 })
-        .collect();
+pub fn sobel_wasm(data: &[u8], width: usize, height: usize, channels: usize, direction: &str, kernel_size: u8) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::sobel_u8(input.view(), direction, kernel_size);
+    result.into_raw_vec_and_offset().0
+}
 
-    gradient_stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
+#[wasm_bindgen]
+pub fn sobel_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, direction: &str, kernel_size: u8) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::sobel_f32(input.view(), direction, kernel_size);
+    result.into_raw_vec_and_offset().0
+}
 
-    if gradient_stops.is_empty() {
-        gradient_stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
-        gradient_stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
-    }
+#[wasm_bindgen]
+pub fn sobel_per_channel_wasm(data: &[u8], width: usize, height: usize, channels: usize, direction: &str, kernel_size: u8) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::sobel_per_channel_u8(input.view(), direction, kernel_size);
+    result.into_raw_vec_and_offset().0
+}
 
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
-    let mut result = Array3::<u8>::zeros((height, width, 4));
+#[wasm_bindgen]
+pub fn sobel_per_channel_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, direction: &str, kernel_size: u8) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::sobel_per_channel_f32(input.view(), direction, kernel_size);
+    result.into_raw_vec_and_offset().0
+}
 
-    // Center with offset applied
-    let cx = width as f32 / 2.0 + offset_x * width as f32 / 2.0;
-    let cy = height as f32 / 2.0 + offset_y * height as f32 / 2.0;
+#[wasm_bindgen]
+pub fn laplacian_wasm(data: &[u8], width: usize, height: usize, channels: usize, kernel_size: u8) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::laplacian_u8(input.view(), kernel_size);
+    result.into_raw_vec_and_offset().0
+}
 
-    // Effective scale factors (avoid division by zero)
-    let sx = if scale_x.abs() > 0.001 { scale_x } else { 0.001 };
-    let sy = if scale_y.abs() > 0.001 { scale_y } else { 0.001 };
+#[wasm_bindgen]
+pub fn laplacian_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, kernel_size: u8) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::laplacian_f32(input.view(), kernel_size);
+    result.into_raw_vec_and_offset().0
+}
 
-    let half_w = width as f32 / 2.0;
-    let half_h = height as f32 / 2.0;
+#[wasm_bindgen]
+pub fn find_edges_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma: f64, low_threshold: f64, high_threshold: f64) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::find_edges_u8(input.view(), sigma, low_threshold, high_threshold);
+    result.into_raw_vec_and_offset().0
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input[[y, x, 3]];
-            if orig_a == 0 { continue; }
+#[wasm_bindgen]
+pub fn find_edges_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, sigma: f64, low_threshold: f64, high_threshold: f64) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::find_edges_f32(input.view(), sigma, low_threshold, high_threshold);
+    result.into_raw_vec_and_offset().0
+}
 
-            // Calculate gradient t based on style
-            let px = x as f32;
-            let py = y as f32;
-            let mut t = match style {
-                "linear" => {
-                    let angle_rad = angle.to_radians();
-                    let dx = angle_rad.cos();
-                    let dy = -angle_rad.sin();
-                    let rx = (px - cx) / sx;
-                    let ry = (py - cy) / sy;
-                    let proj = rx * dx + ry * dy;
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    (proj / max_dist + 1.0) / 2.0
-                }
-                "radial" => {
-                    let dx = (px - cx) / sx;
-                    let dy = (py - cy) / sy;
-                    let dist = (dx * dx + dy * dy).sqrt();
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    dist / max_dist
-                }
-                "angle" => {
-                    let dx = (px - cx) / sx;
-                    let dy = (py - cy) / sy;
-                    let mut angle_at_pixel = dy.atan2(dx);
-                    angle_at_pixel -= angle.to_radians();
-                    (angle_at_pixel + std::f32::consts::PI) / (2.0 * std::f32::consts::PI)
-                }
-                "reflected" => {
-                    let angle_rad = angle.to_radians();
-                    let dx = angle_rad.cos();
-                    let dy = -angle_rad.sin();
-                    let rx = (px - cx) / sx;
-                    let ry = (py - cy) / sy;
-                    let proj = rx * dx + ry * dy;
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    let linear_t = (proj / max_dist + 1.0) / 2.0;
-                    (2.0 * (linear_t - 0.5)).abs()
-                }
-                "diamond" => {
-                    let dx = ((px - cx) / sx).abs();
-                    let dy = ((py - cy) / sy).abs();
-                    (dx + dy) / (half_w + half_h)
-                }
-                _ => {
-                    let angle_rad = angle.to_radians();
-                    let dx = angle_rad.cos();
-                    let dy = -angle_rad.sin();
-                    let rx = (px - cx) / sx;
-                    let ry = (py - cy) / sy;
-                    let proj = rx * dx + ry * dy;
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    (proj / max_dist + 1.0) / 2.0
-                }
-            };
+#[wasm_bindgen]
+pub fn dog_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma1: f32, sigma2: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::dog_u8(input.view(), sigma1, sigma2);
+    result.into_raw_vec_and_offset().0
+}
 
-            t = t.clamp(0.0, 1.0);
-            if reverse { t = 1.0 - t; }
+#[wasm_bindgen]
+pub fn dog_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, sigma1: f32, sigma2: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::dog_f32(input.view(), sigma1, sigma2);
+    result.into_raw_vec_and_offset().0
+}
 
-            // Interpolate gradient color
+// ============================================================================
+// Input Sanitization
+// ============================================================================
+
+/// Apply a NaN / out-of-range policy ("clamp", "propagate" or "error") to f32
+/// filter input. Works on the flat pixel data, so no shape is needed.
+#[wasm_bindgen]
+pub fn sanitize_f32_wasm(data: &[f32], mode: &str) -> Result<Vec<f32>, JsError> {
+    let mode = SanitizeMode::from_name(mode).ok_or_else(|| JsError::new(&format!("unknown sanitize mode '{}'", mode)))?;
+    let input = ndarray::ArrayView3::from_shape((1, data.len(), 1), data).expect("flat shape");
+    let result = sanitize::sanitize_f32(input, mode).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_owned().into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Convolution
+// ============================================================================
+//
+// Normalize kernels on the JS side if needed (divide by their sum).
+
+fn parse_border_mode(name: &str) -> Result<BorderMode, JsError> {
+    BorderMode::from_name(name).ok_or_else(|| JsError::new(&format!("unknown border mode '{}'", name)))
+}
+
+fn kernel_view(kernel: &[f32], kernel_width: usize) -> Result<ndarray::ArrayView2<'_, f32>, JsError> {
+    if kernel_width == 0 || kernel.is_empty() || !kernel.len().is_multiple_of(kernel_width) {
+        return Err(JsError::new("kernel length must be a multiple of kernel_width"));
+    }
+    Ok(ndarray::ArrayView2::from_shape((kernel.len() / kernel_width, kernel_width), kernel).expect("checked shape"))
+}
+
+/// Convolve with a row-major kernel of `kernel_width` columns.
+#[wasm_bindgen]
+pub fn convolve2d_wasm(data: &[u8], width: usize, height: usize, channels: usize, kernel: &[f32], kernel_width: usize, border_mode: &str) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = convolve::convolve2d_u8(input.view(), kernel_view(kernel, kernel_width)?, false, parse_border_mode(border_mode)?);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+#[wasm_bindgen]
+pub fn convolve2d_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, kernel: &[f32], kernel_width: usize, border_mode: &str) -> Result<Vec<f32>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = convolve::convolve2d_f32(input.view(), kernel_view(kernel, kernel_width)?, false, parse_border_mode(border_mode)?);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+#[wasm_bindgen]
+pub fn convolve_separable_wasm(data: &[u8], width: usize, height: usize, channels: usize, kx: &[f32], ky: &[f32], border_mode: &str) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = convolve::convolve_separable_u8(input.view(), kx, ky, parse_border_mode(border_mode)?);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+#[wasm_bindgen]
+pub fn convolve_separable_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, kx: &[f32], ky: &[f32], border_mode: &str) -> Result<Vec<f32>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = convolve::convolve_separable_f32(input.view(), kx, ky, parse_border_mode(border_mode)?);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Draw Contours
+// ============================================================================
+
+#[wasm_bindgen]
+pub fn draw_contours_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: u8, line_width: u8, color_r: u8, color_g: u8, color_b: u8) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::draw_contours_u8(input.view(), threshold, line_width, color_r, color_g, color_b);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn draw_contours_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32, line_width: u8, color_r: f32, color_g: f32, color_b: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = edge::draw_contours_f32(input.view(), threshold, line_width, color_r, color_g, color_b);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Noise Filters
+// ============================================================================
+
+#[wasm_bindgen]
+pub fn add_noise_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32, gaussian: bool, monochrome: bool, seed: u32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::add_noise_u8(input.view(), amount, gaussian, monochrome, seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn add_noise_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32, gaussian: bool, monochrome: bool, seed: u32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::add_noise_f32(input.view(), amount, gaussian, monochrome, seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Parameters of `add_noise_hashed_wasm` / `add_noise_hashed_f32_wasm`
+/// (see `noise::add_noise_hashed_f32`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct HashedNoiseOptions {
+    pub amount: f32,
+    pub gaussian: bool,
+    pub monochrome: bool,
+    pub seed: u32,
+    /// Position of the tile's top-left pixel in the full image
+    pub origin_x: i32,
+    pub origin_y: i32,
+}
+
+impl Default for HashedNoiseOptions {
+    fn default() -> Self {
+        Self { amount: 0.1, gaussian: true, monochrome: false, seed: 0, origin_x: 0, origin_y: 0 }
+    }
+}
+
+#[wasm_bindgen]
+impl HashedNoiseOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Position-stable noise: tiles rendered with their origin match a full render.
+#[wasm_bindgen]
+pub fn add_noise_hashed_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &HashedNoiseOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let origin = (o.origin_x as i64, o.origin_y as i64);
+    let result = noise::add_noise_hashed_u8(input.view(), o.amount, o.gaussian, o.monochrome, o.seed as u64, origin);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn add_noise_hashed_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &HashedNoiseOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let origin = (o.origin_x as i64, o.origin_y as i64);
+    let result = noise::add_noise_hashed_f32(input.view(), o.amount, o.gaussian, o.monochrome, o.seed as u64, origin);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Parameters of `film_grain_wasm` / `film_grain_f32_wasm` (see `noise::film_grain_f32`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct FilmGrainOptions {
+    pub intensity: f32,
+    pub size: f32,
+    pub roughness: f32,
+    pub chroma_amount: f32,
+    pub seed: u32,
+}
+
+impl Default for FilmGrainOptions {
+    fn default() -> Self {
+        Self { intensity: 0.3, size: 1.5, roughness: 0.5, chroma_amount: 0.0, seed: 0 }
+    }
+}
+
+#[wasm_bindgen]
+impl FilmGrainOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn film_grain_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &FilmGrainOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = noise::film_grain_u8(input.view(), o.intensity, o.size, o.roughness, o.chroma_amount, o.seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn film_grain_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &FilmGrainOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = noise::film_grain_f32(input.view(), o.intensity, o.size, o.roughness, o.chroma_amount, o.seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn median_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::median_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn median_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: u32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::median_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn remove_hot_pixels_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::remove_hot_pixels_u8(input.view(), threshold);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn remove_hot_pixels_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::remove_hot_pixels_f32(input.view(), threshold);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn surface_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32, threshold: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::surface_blur_u8(input.view(), radius, threshold);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn surface_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: u32, threshold: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::surface_blur_f32(input.view(), radius, threshold);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn denoise_wasm(data: &[u8], width: usize, height: usize, channels: usize, strength: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::denoise_u8(input.view(), strength);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn denoise_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, strength: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = noise::denoise_f32(input.view(), strength);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Progress and Cancellation
+// ============================================================================
+//
+// Long filters take a `ProgressHandle` that records the completed fraction
+// and a cancel flag. Run the filter in a worker of the threaded build and
+// poll `fraction()` / call `cancel()` from another thread; a cancelled call
+// throws "operation cancelled".
+
+use crate::progress;
+
+/// Pollable progress and cancel flag for one long-running call.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct ProgressHandle {
+    inner: progress::ProgressHandle,
+}
+
+#[wasm_bindgen]
+impl ProgressHandle {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ProgressHandle {
+        ProgressHandle::default()
+    }
+
+    /// Completed fraction of the running call (0.0-1.0).
+    pub fn fraction(&self) -> f32 {
+        self.inner.fraction()
+    }
+
+    /// Stop the running call at its next progress report.
+    pub fn cancel(&self) {
+        self.inner.cancel()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
+
+fn cancelled_error(e: progress::Cancelled) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Median filter with progress (u8).
+#[wasm_bindgen]
+pub fn median_progress_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    radius: u32,
+    progress: &ProgressHandle,
+) -> Result<Vec<u8>, JsError> {
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).map_err(|e| JsError::new(&e.to_string()))?;
+    let result = noise::median_u8_with_progress(input, radius, &mut |f| progress.inner.report(f)).map_err(cancelled_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Non-local means denoise with progress (u8).
+#[wasm_bindgen]
+pub fn denoise_progress_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    strength: f32,
+    progress: &ProgressHandle,
+) -> Result<Vec<u8>, JsError> {
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).map_err(|e| JsError::new(&e.to_string()))?;
+    let result =
+        noise::denoise_u8_with_progress(input, strength, &mut |f| progress.inner.report(f)).map_err(cancelled_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Canny edge detection with progress (u8).
+///
+/// # Arguments
+/// * `params` - `[sigma, low_threshold, high_threshold]` as in `find_edges_wasm`
+#[wasm_bindgen]
+pub fn find_edges_progress_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    params: &[f64],
+    progress: &ProgressHandle,
+) -> Result<Vec<u8>, JsError> {
+    let &[sigma, low_threshold, high_threshold] = params else {
+        return Err(JsError::new("params must be [sigma, low_threshold, high_threshold]"));
+    };
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).map_err(|e| JsError::new(&e.to_string()))?;
+    let luma = LumaStandard::default();
+    let result = edge::find_edges_u8_with_progress(input, sigma, low_threshold, high_threshold, luma, &mut |f| {
+        progress.inner.report(f)
+    })
+    .map_err(cancelled_error)?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Chunked Jobs
+// ============================================================================
+//
+// For the single-threaded build on the browser main thread: a job filters
+// one band of rows per `step()`, so JS can yield to the event loop between
+// steps and report `fraction()` (see `imagestag/filters/jobs.js`).
+
+use crate::chunked::BandJob;
+
+/// Pixels filtered per `FilterJob::step` (a few milliseconds of work).
+const JOB_BAND_PIXELS: usize = 1 << 16;
+
+/// A u8 filter run band by band.
+#[wasm_bindgen]
+pub struct FilterJob {
+    job: Option<BandJob<u8>>,
+    width: usize,
+    height: usize,
+    channels: usize,
+}
+
+impl FilterJob {
+    fn new(
+        data: &[u8],
+        width: usize,
+        height: usize,
+        channels: usize,
+        halo: usize,
+        filter: impl Fn(ArrayView3<u8>) -> Array3<u8> + 'static,
+    ) -> FilterJob {
+        let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+        let band_rows = JOB_BAND_PIXELS / width.max(1);
+        FilterJob { job: Some(BandJob::new(input, halo, band_rows, filter)), width, height, channels }
+    }
+}
+
+#[wasm_bindgen]
+impl FilterJob {
+    /// Filter the next band; returns `true` once the image is done.
+    pub fn step(&mut self) -> bool {
+        self.job.as_mut().is_none_or(|job| job.step())
+    }
+
+    /// Completed fraction (0.0-1.0).
+    pub fn fraction(&self) -> f32 {
+        self.job.as_ref().map_or(1.0, |job| job.fraction())
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.job.as_ref().is_none_or(|job| job.is_done())
+    }
+
+    /// Finish any remaining bands and return the filtered image.
+    ///
+    /// The job is empty afterwards; a second call throws.
+    pub fn take_result(&mut self) -> Result<Vec<u8>, JsError> {
+        let job = self.job.take().ok_or_else(|| JsError::new("job result already taken"))?;
+        Ok(job.finish().into_raw_vec_and_offset().0)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+}
+
+/// Median filter as a chunked job (u8).
+#[wasm_bindgen]
+pub fn median_job_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32) -> FilterJob {
+    FilterJob::new(data, width, height, channels, radius.min(21) as usize, move |band| noise::median_u8(band, radius))
+}
+
+/// Non-local means denoise as a chunked job (u8).
+#[wasm_bindgen]
+pub fn denoise_job_wasm(data: &[u8], width: usize, height: usize, channels: usize, strength: f32) -> FilterJob {
+    // 21x21 search window of 7x7 patches
+    FilterJob::new(data, width, height, channels, 13, move |band| noise::denoise_u8(band, strength))
+}
+
+/// Surface blur as a chunked job (u8).
+#[wasm_bindgen]
+pub fn surface_blur_job_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32, threshold: f32) -> FilterJob {
+    FilterJob::new(data, width, height, channels, radius.min(100) as usize, move |band| {
+        noise::surface_blur_u8(band, radius, threshold)
+    })
+}
+
+/// Gaussian blur as a chunked job (u8).
+#[wasm_bindgen]
+pub fn gaussian_blur_job_wasm(data: &[u8], width: usize, height: usize, channels: usize, sigma: f32) -> FilterJob {
+    let halo = blur_wasm::gaussian_kernel(sigma).len() / 2;
+    FilterJob::new(data, width, height, channels, halo, move |band| blur_wasm::gaussian_blur_wasm_u8(band, sigma))
+}
+
+// ============================================================================
+// Morphology Filters
+// ============================================================================
+
+#[wasm_bindgen]
+pub fn dilate_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::dilate_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn dilate_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::dilate_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn erode_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::erode_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn erode_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::erode_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn morphology_open_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::open_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn morphology_open_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::open_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn morphology_close_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::close_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn morphology_close_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::close_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn morphology_gradient_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::gradient_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn morphology_gradient_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::gradient_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn tophat_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::tophat_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn tophat_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::tophat_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn blackhat_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::blackhat_u8(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn blackhat_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = morphology::blackhat_f32(input.view(), radius);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// WASM-compatible Blur (parallel with wasm-threads)
+// ============================================================================
+
+fn parse_blur_quality(name: &str) -> Result<BlurQuality, JsError> {
+    BlurQuality::from_name(name).ok_or_else(|| JsError::new(&format!("unknown blur quality '{}'", name)))
+}
+
+/// Gaussian blur; `quality` is "exact" or "fast" (three-box approximation
+/// for interactive previews).
+#[wasm_bindgen]
+pub fn gaussian_blur_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    sigma: f32,
+    srgb_linearize: bool,
+    quality: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let quality = parse_blur_quality(quality)?;
+    let result = if srgb_linearize {
+        blur_wasm::gaussian_blur_linear_u8(input.view(), sigma, quality)
+    } else {
+        blur_wasm::gaussian_blur_quality_u8(input.view(), sigma, quality)
+    };
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+#[wasm_bindgen]
+pub fn gaussian_blur_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    sigma: f32,
+    srgb_linearize: bool,
+    quality: &str,
+) -> Result<Vec<f32>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let quality = parse_blur_quality(quality)?;
+    let result = if srgb_linearize {
+        blur_wasm::gaussian_blur_linear_f32(input.view(), sigma, quality)
+    } else {
+        blur_wasm::gaussian_blur_quality_f32(input.view(), sigma, quality)
+    };
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+#[wasm_bindgen]
+pub fn box_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize, radius: u32, srgb_linearize: bool) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = if srgb_linearize {
+        blur_wasm::box_blur_linear_u8(input.view(), radius)
+    } else {
+        blur_wasm::box_blur_wasm_u8(input.view(), radius)
+    };
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn box_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, radius: u32, srgb_linearize: bool) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = if srgb_linearize {
+        blur_wasm::box_blur_linear_f32(input.view(), radius)
+    } else {
+        blur_wasm::box_blur_wasm_f32(input.view(), radius)
+    };
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Rotation and Mirroring
+// ============================================================================
+
+/// Rotate image 90 degrees clockwise (u8).
+/// Returns flat array with dimensions swapped (W, H instead of H, W).
+#[wasm_bindgen]
+pub fn rotate_90_cw_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_90_cw_u8(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image 90 degrees clockwise (f32).
+#[wasm_bindgen]
+pub fn rotate_90_cw_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_90_cw_f32(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image 180 degrees (u8).
+#[wasm_bindgen]
+pub fn rotate_180_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_180_u8(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image 180 degrees (f32).
+#[wasm_bindgen]
+pub fn rotate_180_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_180_f32(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image 270 degrees clockwise (90 CCW) (u8).
+/// Returns flat array with dimensions swapped (W, H instead of H, W).
+#[wasm_bindgen]
+pub fn rotate_270_cw_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_270_cw_u8(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image 270 degrees clockwise (90 CCW) (f32).
+#[wasm_bindgen]
+pub fn rotate_270_cw_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_270_cw_f32(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image by specified degrees (90, 180, or 270) (u8).
+/// For 90/270, dimensions are swapped in output.
+#[wasm_bindgen]
+pub fn rotate_wasm(data: &[u8], width: usize, height: usize, channels: usize, degrees: u32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_u8(input.view(), degrees);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate image by specified degrees (90, 180, or 270) (f32).
+#[wasm_bindgen]
+pub fn rotate_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, degrees: u32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::rotate_f32(input.view(), degrees);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Flip image horizontally (mirror left-right) (u8).
+#[wasm_bindgen]
+pub fn flip_horizontal_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::flip_horizontal_u8(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Flip image horizontally (mirror left-right) (f32).
+#[wasm_bindgen]
+pub fn flip_horizontal_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::flip_horizontal_f32(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Flip image vertically (mirror top-bottom) (u8).
+#[wasm_bindgen]
+pub fn flip_vertical_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::flip_vertical_u8(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Flip image vertically (mirror top-bottom) (f32).
+#[wasm_bindgen]
+pub fn flip_vertical_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = rotate::flip_vertical_f32(input.view());
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate or flip a u8 buffer with padded rows; `stride` is in bytes.
+///
+/// Returns the compact result; 90/270 rotations swap width and height.
+#[wasm_bindgen]
+pub fn transform_strided_wasm(data: &[u8], width: usize, height: usize, channels: usize, stride: usize, transform: &str) -> Result<Vec<u8>, JsError> {
+    transform_strided_any(data, width, height, channels, stride, transform)
+}
+
+/// Rotate or flip a u16 buffer with padded rows; `stride` is in samples.
+#[wasm_bindgen]
+pub fn transform_strided_u16_wasm(data: &[u16], width: usize, height: usize, channels: usize, stride: usize, transform: &str) -> Result<Vec<u16>, JsError> {
+    transform_strided_any(data, width, height, channels, stride, transform)
+}
+
+fn transform_strided_any<T: Copy>(data: &[T], width: usize, height: usize, channels: usize, stride: usize, transform: &str) -> Result<Vec<T>, JsError> {
+    let op = rotate::StridedTransform::from_name(transform).ok_or_else(|| JsError::new(&format!("unknown transform '{}'", transform)))?;
+    let result = rotate::transform_strided(data, width, height, channels, stride, op)
+        .ok_or_else(|| JsError::new(&format!("buffer doesn't hold {} rows of {}x{} samples with stride {}", height, width, channels, stride)))?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Thumbnails
+// ============================================================================
+
+/// Compute the thumbnail size for an image.
+///
+/// # Returns
+/// [width, height] fitting into max_width x max_height (never enlarged)
+#[wasm_bindgen]
+pub fn thumbnail_size_wasm(width: usize, height: usize, max_width: usize, max_height: usize) -> Vec<u32> {
+    let (w, h) = resample::thumbnail_size(width, height, max_width, max_height);
+    vec![w as u32, h as u32]
+}
+
+/// Create a thumbnail with Lanczos downscaling (u8).
+/// Output dimensions are given by `thumbnail_size_wasm`.
+#[wasm_bindgen]
+pub fn thumbnail_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    max_width: usize,
+    max_height: usize,
+    srgb_aware: bool,
+) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = resample::thumbnail_u8(input.view(), max_width, max_height, srgb_aware);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Create a thumbnail with Lanczos downscaling (f32).
+#[wasm_bindgen]
+pub fn thumbnail_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    max_width: usize,
+    max_height: usize,
+    srgb_aware: bool,
+) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = resample::thumbnail_f32(input.view(), max_width, max_height, srgb_aware);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Straightening
+// ============================================================================
+
+/// Estimate the dominant skew angle in degrees (positive = clockwise) (u8).
+#[wasm_bindgen]
+pub fn estimate_skew_angle_wasm(data: &[u8], width: usize, height: usize, channels: usize, max_angle: f32) -> f32 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    straighten::estimate_skew_angle_u8(input.view(), max_angle)
+}
+
+/// Estimate the dominant skew angle in degrees (positive = clockwise) (f32).
+#[wasm_bindgen]
+pub fn estimate_skew_angle_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, max_angle: f32) -> f32 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    straighten::estimate_skew_angle_f32(input.view(), max_angle)
+}
+
+/// Rotate by an arbitrary angle with bicubic sampling, same canvas size (u8).
+#[wasm_bindgen]
+pub fn rotate_bicubic_wasm(data: &[u8], width: usize, height: usize, channels: usize, degrees: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = straighten::rotate_bicubic_u8(input.view(), degrees);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate by an arbitrary angle with bicubic sampling, same canvas size (f32).
+#[wasm_bindgen]
+pub fn rotate_bicubic_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, degrees: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = straighten::rotate_bicubic_f32(input.view(), degrees);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Compute the output size of `rotate_crop_wasm`.
+///
+/// # Returns
+/// [width, height] of the largest rectangle inside the rotated image
+#[wasm_bindgen]
+pub fn rotate_crop_size_wasm(width: usize, height: usize, degrees: f32) -> Vec<u32> {
+    let (w, h) = straighten::inscribed_size(width, height, degrees);
+    vec![w as u32, h as u32]
+}
+
+/// Rotate by an arbitrary angle and crop away the empty corners (u8).
+/// Output dimensions are given by `rotate_crop_size_wasm`.
+#[wasm_bindgen]
+pub fn rotate_crop_wasm(data: &[u8], width: usize, height: usize, channels: usize, degrees: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = straighten::rotate_crop_u8(input.view(), degrees);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Rotate by an arbitrary angle and crop away the empty corners (f32).
+#[wasm_bindgen]
+pub fn rotate_crop_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, degrees: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = straighten::rotate_crop_f32(input.view(), degrees);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Perspective Rectification
+// ============================================================================
+
+fn quad_from_slice(corners: &[f32]) -> Result<Quad, JsError> {
+    if corners.len() != 8 {
+        return Err(JsError::new(&format!("expected 8 corner coordinates, got {}", corners.len())));
+    }
+    Ok(std::array::from_fn(|i| [corners[2 * i], corners[2 * i + 1]]))
+}
+
+/// Compute the natural output size of a quad (longest opposite sides).
+///
+/// # Returns
+/// [width, height]
+#[wasm_bindgen]
+pub fn quad_size_wasm(corners: &[f32]) -> Result<Vec<u32>, JsError> {
+    let (w, h) = perspective::quad_size(&quad_from_slice(corners)?);
+    Ok(vec![w as u32, h as u32])
+}
+
+/// Warp a quadrilateral region to an upright rectangle (u8).
+///
+/// `corners` holds [x0, y0, ..., x3, y3] ordered top-left, top-right,
+/// bottom-right, bottom-left. An output size of 0 uses `quad_size_wasm`.
+#[wasm_bindgen]
+pub fn rectify_quad_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    corners: &[f32],
+    out_width: usize,
+    out_height: usize,
+) -> Result<Vec<u8>, JsError> {
+    let quad = quad_from_slice(corners)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = perspective::rectify_quad_u8(input.view(), &quad, out_width, out_height);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Warp a quadrilateral region to an upright rectangle (f32).
+#[wasm_bindgen]
+pub fn rectify_quad_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    corners: &[f32],
+    out_width: usize,
+    out_height: usize,
+) -> Result<Vec<f32>, JsError> {
+    let quad = quad_from_slice(corners)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = perspective::rectify_quad_f32(input.view(), &quad, out_width, out_height);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Find the dominant quadrilateral (u8).
+///
+/// # Returns
+/// [x0, y0, ..., x3, y3] (top-left, top-right, bottom-right, bottom-left), empty if none
+#[wasm_bindgen]
+pub fn detect_document_quad_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    perspective::detect_document_quad_u8(input.view()).map(|quad| quad.concat()).unwrap_or_default()
+}
+
+/// Find the dominant quadrilateral (f32).
+#[wasm_bindgen]
+pub fn detect_document_quad_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    perspective::detect_document_quad_f32(input.view()).map(|quad| quad.concat()).unwrap_or_default()
+}
+
+/// Source image size and compositing parameters of `map_to_quad_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct MapToQuadOptions {
+    pub src_width: usize,
+    pub src_height: usize,
+    pub src_channels: usize,
+    pub opacity: f32,
+    /// One of `overlay_blend_modes_wasm()`
+    pub blend_mode: String,
+}
+
+impl Default for MapToQuadOptions {
+    fn default() -> Self {
+        Self { src_width: 0, src_height: 0, src_channels: 4, opacity: 1.0, blend_mode: "normal".into() }
+    }
+}
+
+#[wasm_bindgen]
+impl MapToQuadOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Warp `src` onto a quadrilateral of `dst` and composite it (u8).
+///
+/// `dst` is (height, width, channels); the size of `src` comes from the
+/// options. `corners` holds [x0, y0, ..., x3, y3] in `dst`, ordered
+/// top-left, top-right, bottom-right, bottom-left.
+#[wasm_bindgen]
+pub fn map_to_quad_wasm(
+    src: &[u8],
+    dst: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    corners: &[f32],
+    options: &MapToQuadOptions,
+) -> Result<Vec<u8>, JsError> {
+    let quad = quad_from_slice(corners)?;
+    let src = ArrayView3::from_shape((options.src_height, options.src_width, options.src_channels), src).expect("Invalid source dimensions");
+    let dst = ArrayView3::from_shape((height, width, channels), dst).expect("Invalid dimensions");
+    let result = perspective::map_to_quad_u8(src, dst, &quad, &options.blend_mode, options.opacity);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Warp `src` onto a quadrilateral of `dst` and composite it (f32).
+#[wasm_bindgen]
+pub fn map_to_quad_f32_wasm(
+    src: &[f32],
+    dst: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    corners: &[f32],
+    options: &MapToQuadOptions,
+) -> Result<Vec<f32>, JsError> {
+    let quad = quad_from_slice(corners)?;
+    let src = ArrayView3::from_shape((options.src_height, options.src_width, options.src_channels), src).expect("Invalid source dimensions");
+    let dst = ArrayView3::from_shape((height, width, channels), dst).expect("Invalid dimensions");
+    let result = perspective::map_to_quad_f32(src, dst, &quad, &options.blend_mode, options.opacity);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Matting
+// ============================================================================
+
+/// Parameters of `chroma_key_wasm` / `chroma_key_f32_wasm` (see `matting::chroma_key_f32`).
+///
+/// The key color is given in 0.0-1.0 for both variants.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct ChromaKeyOptions {
+    pub key_r: f32,
+    pub key_g: f32,
+    pub key_b: f32,
+    pub tolerance: f32,
+    pub softness: f32,
+    pub spill_suppression: f32,
+}
+
+impl Default for ChromaKeyOptions {
+    fn default() -> Self {
+        Self { key_r: 0.0, key_g: 1.0, key_b: 0.0, tolerance: 0.15, softness: 0.1, spill_suppression: 1.0 }
+    }
+}
+
+#[wasm_bindgen]
+impl ChromaKeyOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Remove a uniform backdrop (u8). Returns RGBA (4 channels).
+#[wasm_bindgen]
+pub fn chroma_key_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &ChromaKeyOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let key = [o.key_r, o.key_g, o.key_b].map(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8);
+    let result = matting::chroma_key_u8(input.view(), key, o.tolerance, o.softness, o.spill_suppression);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Remove a uniform backdrop (f32). Returns RGBA (4 channels).
+#[wasm_bindgen]
+pub fn chroma_key_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &ChromaKeyOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = matting::chroma_key_f32(input.view(), [o.key_r, o.key_g, o.key_b], o.tolerance, o.softness, o.spill_suppression);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Estimate fractional alpha in the unknown region of a trimap (u8).
+///
+/// `trimap` is (height * width): 0 = background, 255 = foreground, else unknown.
+/// Returns the alpha matte (1 channel).
+#[wasm_bindgen]
+pub fn alpha_matting_wasm(
+    data: &[u8],
+    trimap: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    radius: usize,
+    epsilon: f32,
+) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let trimap = Array2::from_shape_vec((height, width), trimap.to_vec()).expect("Invalid trimap dimensions");
+    let result = matting::alpha_matting_u8(input.view(), trimap.view(), radius, epsilon);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Estimate fractional alpha in the unknown region of a trimap (f32).
+///
+/// `trimap` is (height * width): 0.0 = background, 1.0 = foreground, else unknown.
+/// Returns the alpha matte (1 channel).
+#[wasm_bindgen]
+pub fn alpha_matting_f32_wasm(
+    data: &[f32],
+    trimap: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    radius: usize,
+    epsilon: f32,
+) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let trimap = Array2::from_shape_vec((height, width), trimap.to_vec()).expect("Invalid trimap dimensions");
+    let result = matting::alpha_matting_f32(input.view(), trimap.view(), radius, epsilon);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Glitch
+// ============================================================================
+
+#[wasm_bindgen]
+pub fn rgb_split_wasm(data: &[u8], width: usize, height: usize, channels: usize, shift_x: i32, shift_y: i32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    glitch::rgb_split_u8(input.view(), shift_x, shift_y).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn rgb_split_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, shift_x: i32, shift_y: i32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    glitch::rgb_split_f32(input.view(), shift_x, shift_y).into_raw_vec_and_offset().0
+}
+
+/// Parameters of `scanlines_wasm` / `scanlines_f32_wasm` (see `glitch::scanlines_f32`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct ScanlinesOptions {
+    pub spacing: u32,
+    pub thickness: u32,
+    pub intensity: f32,
+}
+
+impl Default for ScanlinesOptions {
+    fn default() -> Self {
+        Self { spacing: 3, thickness: 1, intensity: 0.3 }
+    }
+}
+
+#[wasm_bindgen]
+impl ScanlinesOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn scanlines_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &ScanlinesOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    glitch::scanlines_u8(input.view(), o.spacing, o.thickness, o.intensity).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn scanlines_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &ScanlinesOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    glitch::scanlines_f32(input.view(), o.spacing, o.thickness, o.intensity).into_raw_vec_and_offset().0
+}
+
+/// Parameters of `block_shift_wasm` / `block_shift_f32_wasm` (see `glitch::block_shift_f32`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct BlockShiftOptions {
+    pub block_height: u32,
+    pub max_shift: u32,
+    pub probability: f32,
+    pub seed: u32,
+}
+
+impl Default for BlockShiftOptions {
+    fn default() -> Self {
+        Self { block_height: 8, max_shift: 20, probability: 0.2, seed: 0 }
+    }
+}
+
+#[wasm_bindgen]
+impl BlockShiftOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn block_shift_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &BlockShiftOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = glitch::block_shift_u8(input.view(), o.block_height, o.max_shift, o.probability, o.seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn block_shift_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &BlockShiftOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = glitch::block_shift_f32(input.view(), o.block_height, o.max_shift, o.probability, o.seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Parameters of `wave_distort_wasm` / `wave_distort_f32_wasm` (see `glitch::wave_distort_f32`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct WaveDistortOptions {
+    pub amplitude: f32,
+    pub wavelength: f32,
+    pub phase: f32,
+    pub jitter: f32,
+    pub seed: u32,
+}
+
+impl Default for WaveDistortOptions {
+    fn default() -> Self {
+        Self { amplitude: 4.0, wavelength: 64.0, phase: 0.0, jitter: 0.0, seed: 0 }
+    }
+}
+
+#[wasm_bindgen]
+impl WaveDistortOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[wasm_bindgen]
+pub fn wave_distort_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &WaveDistortOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = glitch::wave_distort_u8(input.view(), o.amplitude, o.wavelength, o.phase, o.jitter, o.seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn wave_distort_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &WaveDistortOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let result = glitch::wave_distort_f32(input.view(), o.amplitude, o.wavelength, o.phase, o.jitter, o.seed as u64);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Material Maps
+// ============================================================================
+
+/// Height map to tangent-space normal map (u8). The output has 3 channels,
+/// or 4 when `channels` is 4.
+#[wasm_bindgen]
+pub fn height_to_normal_wasm(data: &[u8], width: usize, height: usize, channels: usize, strength: f32, invert_y: bool) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = material::height_to_normal_u8(input.view(), strength, invert_y);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Height map to tangent-space normal map (f32).
+#[wasm_bindgen]
+pub fn height_to_normal_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, strength: f32, invert_y: bool) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = material::height_to_normal_f32(input.view(), strength, invert_y);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Wavelet Filters
+// ============================================================================
+
+/// Wavelet sharpen (u8) with one gain per detail layer, fine to coarse.
+#[wasm_bindgen]
+pub fn wavelet_sharpen_wasm(data: &[u8], width: usize, height: usize, channels: usize, layer_gains: &[f32]) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = wavelet::wavelet_sharpen_u8(input.view(), layer_gains);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Wavelet sharpen (f32).
+#[wasm_bindgen]
+pub fn wavelet_sharpen_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, layer_gains: &[f32]) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = wavelet::wavelet_sharpen_f32(input.view(), layer_gains);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Wavelet denoise (u8) with one threshold (0-255) per detail layer.
+#[wasm_bindgen]
+pub fn wavelet_denoise_wasm(data: &[u8], width: usize, height: usize, channels: usize, thresholds: &[f32]) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = wavelet::wavelet_denoise_u8(input.view(), thresholds);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Wavelet denoise (f32) with one threshold (0.0-1.0) per detail layer.
+#[wasm_bindgen]
+pub fn wavelet_denoise_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, thresholds: &[f32]) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = wavelet::wavelet_denoise_f32(input.view(), thresholds);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Tone Mapping
+// ============================================================================
+
+/// Tone map linear HDR values into 0.0-1.0 (f32 only).
+///
+/// `operator` is "reinhard", "filmic" or "aces".
+#[wasm_bindgen]
+pub fn tonemap_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    operator: &str,
+    exposure: f32,
+    white_point: f32,
+) -> Result<Vec<f32>, JsError> {
+    let operator = TonemapOperator::from_name(operator)
+        .ok_or_else(|| JsError::new(&format!("unknown tone mapping operator '{}'", operator)))?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = tonemap::tonemap_f32(input.view(), &TonemapParams { operator, exposure, white_point });
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Transfer Functions
+// ============================================================================
+
+fn parse_transfer_function(name: &str) -> Result<TransferFunction, JsError> {
+    TransferFunction::from_name(name).ok_or_else(|| JsError::new(&format!("unknown transfer function '{}'", name)))
+}
+
+/// Decode an image encoded with `transfer` ("srgb", "pq", "hlg", "slog3",
+/// "vlog", "logc3") to linear light (f32 only).
+#[wasm_bindgen]
+pub fn decode_transfer_wasm(data: &[f32], width: usize, height: usize, channels: usize, transfer: &str) -> Result<Vec<f32>, JsError> {
+    let transfer = parse_transfer_function(transfer)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    Ok(colorspace::decode_transfer_f32(input.view(), transfer).into_raw_vec_and_offset().0)
+}
+
+/// Encode a linear image with `transfer` (f32 only).
+#[wasm_bindgen]
+pub fn encode_transfer_wasm(data: &[f32], width: usize, height: usize, channels: usize, transfer: &str) -> Result<Vec<f32>, JsError> {
+    let transfer = parse_transfer_function(transfer)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    Ok(colorspace::encode_transfer_f32(input.view(), transfer).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Compositing
+// ============================================================================
+
+/// Parameters of `blend_images_wasm` / `blend_images_f32_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct BlendImagesOptions {
+    pub opacity: f32,
+    /// One of `overlay_blend_modes_wasm()`
+    pub blend_mode: String,
+}
+
+impl Default for BlendImagesOptions {
+    fn default() -> Self {
+        Self { opacity: 1.0, blend_mode: "normal".into() }
+    }
+}
+
+#[wasm_bindgen]
+impl BlendImagesOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Blend `b` over `a` through a mask and opacity (u8).
+///
+/// `mask` is (height * width), 0 = keep `a`, 255 = full blend; empty = no mask.
+#[wasm_bindgen]
+pub fn blend_images_wasm(
+    a: &[u8],
+    b: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    mask: &[u8],
+    options: &BlendImagesOptions,
+) -> Vec<u8> {
+    let a = Array3::from_shape_vec((height, width, channels), a.to_vec()).expect("Invalid dimensions");
+    let b = Array3::from_shape_vec((height, width, channels), b.to_vec()).expect("Invalid dimensions");
+    let mask = (!mask.is_empty()).then(|| Array2::from_shape_vec((height, width), mask.to_vec()).expect("Invalid mask dimensions"));
+    let result = compositing::blend_images_u8(a.view(), b.view(), mask.as_ref().map(|m| m.view()), options.opacity, &options.blend_mode);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Blend `b` over `a` through a mask and opacity (f32).
+///
+/// `mask` is (height * width), 0.0 = keep `a`, 1.0 = full blend; empty = no mask.
+#[wasm_bindgen]
+pub fn blend_images_f32_wasm(
+    a: &[f32],
+    b: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    mask: &[f32],
+    options: &BlendImagesOptions,
+) -> Vec<f32> {
+    let a = Array3::from_shape_vec((height, width, channels), a.to_vec()).expect("Invalid dimensions");
+    let b = Array3::from_shape_vec((height, width, channels), b.to_vec()).expect("Invalid dimensions");
+    let mask = (!mask.is_empty()).then(|| Array2::from_shape_vec((height, width), mask.to_vec()).expect("Invalid mask dimensions"));
+    let result = compositing::blend_images_f32(a.view(), b.view(), mask.as_ref().map(|m| m.view()), options.opacity, &options.blend_mode);
+    result.into_raw_vec_and_offset().0
+}
+
+/// Flatten an image onto a transparency checkerboard (u8), giving RGB.
+///
+/// `color_a` (top-left cell) and `color_b` are `[r, g, b]`.
+#[wasm_bindgen]
+pub fn composite_on_checkerboard_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    cell_size: usize,
+    color_a: &[u8],
+    color_b: &[u8],
+) -> Result<Vec<u8>, JsError> {
+    let (&[ar, ag, ab], &[br, bg, bb]) = (color_a, color_b) else {
+        return Err(JsError::new("colors must be [r, g, b]"));
+    };
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let result = compositing::composite_on_checkerboard_u8(input, cell_size, [ar, ag, ab], [br, bg, bb]);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Flatten an image onto a transparency checkerboard (f32), giving RGB.
+#[wasm_bindgen]
+pub fn composite_on_checkerboard_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    cell_size: usize,
+    color_a: &[f32],
+    color_b: &[f32],
+) -> Result<Vec<f32>, JsError> {
+    let (&[ar, ag, ab], &[br, bg, bb]) = (color_a, color_b) else {
+        return Err(JsError::new("colors must be [r, g, b]"));
+    };
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let result = compositing::composite_on_checkerboard_f32(input, cell_size, [ar, ag, ab], [br, bg, bb]);
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Flatten an image onto a solid `[r, g, b]` color (u8), giving RGB.
+#[wasm_bindgen]
+pub fn composite_on_color_wasm(data: &[u8], width: usize, height: usize, channels: usize, rgb: &[u8]) -> Result<Vec<u8>, JsError> {
+    let &[r, g, b] = rgb else {
+        return Err(JsError::new("color must be [r, g, b]"));
+    };
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(compositing::composite_on_color_u8(input, [r, g, b]).into_raw_vec_and_offset().0)
+}
+
+/// Flatten an image onto a solid `[r, g, b]` color (f32), giving RGB.
+#[wasm_bindgen]
+pub fn composite_on_color_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, rgb: &[f32]) -> Result<Vec<f32>, JsError> {
+    let &[r, g, b] = rgb else {
+        return Err(JsError::new("color must be [r, g, b]"));
+    };
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(compositing::composite_on_color_f32(input, [r, g, b]).into_raw_vec_and_offset().0)
+}
+
+/// Combine a stereo pair (same shape) into a red/cyan anaglyph (u8), giving RGB.
+///
+/// `mode` is "color" (red_cyan), "gray" or "dubois".
+#[wasm_bindgen]
+pub fn make_anaglyph_wasm(left: &[u8], right: &[u8], width: usize, height: usize, channels: usize, mode: &str) -> Result<Vec<u8>, JsError> {
+    let mode = AnaglyphMode::from_name(mode).ok_or_else(|| JsError::new(&format!("unknown anaglyph mode '{}'", mode)))?;
+    let left = ArrayView3::from_shape((height, width, channels), left).expect("Invalid dimensions");
+    let right = ArrayView3::from_shape((height, width, channels), right).expect("Invalid dimensions");
+    Ok(compositing::make_anaglyph_u8(left, right, mode).into_raw_vec_and_offset().0)
+}
+
+/// Combine a stereo pair (same shape) into a red/cyan anaglyph (f32), giving RGB.
+#[wasm_bindgen]
+pub fn make_anaglyph_f32_wasm(left: &[f32], right: &[f32], width: usize, height: usize, channels: usize, mode: &str) -> Result<Vec<f32>, JsError> {
+    let mode = AnaglyphMode::from_name(mode).ok_or_else(|| JsError::new(&format!("unknown anaglyph mode '{}'", mode)))?;
+    let left = ArrayView3::from_shape((height, width, channels), left).expect("Invalid dimensions");
+    let right = ArrayView3::from_shape((height, width, channels), right).expect("Invalid dimensions");
+    Ok(compositing::make_anaglyph_f32(left, right, mode).into_raw_vec_and_offset().0)
+}
+
+/// Place a stereo pair side by side (u8), giving width * 2 columns.
+#[wasm_bindgen]
+pub fn make_sbs_wasm(left: &[u8], right: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let left = ArrayView3::from_shape((height, width, channels), left).expect("Invalid dimensions");
+    let right = ArrayView3::from_shape((height, width, channels), right).expect("Invalid dimensions");
+    compositing::make_sbs_u8(left, right).into_raw_vec_and_offset().0
+}
+
+/// Place a stereo pair side by side (f32), giving width * 2 columns.
+#[wasm_bindgen]
+pub fn make_sbs_f32_wasm(left: &[f32], right: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let left = ArrayView3::from_shape((height, width, channels), left).expect("Invalid dimensions");
+    let right = ArrayView3::from_shape((height, width, channels), right).expect("Invalid dimensions");
+    compositing::make_sbs_f32(left, right).into_raw_vec_and_offset().0
+}
+
+/// Ghost tints and falloff of `onion_skin_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct OnionSkinOptions {
+    pub tint_before_r: u8,
+    pub tint_before_g: u8,
+    pub tint_before_b: u8,
+    pub tint_after_r: u8,
+    pub tint_after_g: u8,
+    pub tint_after_b: u8,
+    /// Opacity of the nearest ghosts and factor per further frame
+    pub opacity_falloff: f32,
+}
+
+impl Default for OnionSkinOptions {
+    fn default() -> Self {
+        Self {
+            tint_before_r: 255,
+            tint_before_g: 64,
+            tint_before_b: 64,
+            tint_after_r: 64,
+            tint_after_g: 192,
+            tint_after_b: 64,
+            opacity_falloff: 0.5,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl OnionSkinOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Views of concatenated frames of the same size.
+fn frame_views<T>(data: &[T], width: usize, height: usize, channels: usize) -> Vec<ArrayView3<'_, T>> {
+    data.chunks_exact((width * height * channels).max(1))
+        .map(|f| ArrayView3::from_shape((height, width, channels), f).expect("Invalid dimensions"))
+        .collect()
+}
+
+/// Preview an animation frame over tinted, fading ghosts of its neighbors (u8).
+///
+/// `before` and `after` hold concatenated frames of the current frame's
+/// size, nearest first.
+#[wasm_bindgen]
+pub fn onion_skin_wasm(
+    current: &[u8],
+    before: &[u8],
+    after: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    options: &OnionSkinOptions,
+) -> Vec<u8> {
+    let frames = |data| frame_views(data, width, height, channels);
+    let current = ArrayView3::from_shape((height, width, channels), current).expect("Invalid dimensions");
+    let o = options;
+    let tint_before = [o.tint_before_r, o.tint_before_g, o.tint_before_b];
+    let tint_after = [o.tint_after_r, o.tint_after_g, o.tint_after_b];
+    let result = compositing::onion_skin_u8(&frames(before), &frames(after), current, tint_before, tint_after, o.opacity_falloff);
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Deband
+// ============================================================================
+
+/// Smooth quantization banding (u8). `threshold` and `grain` are in 0-255.
+#[wasm_bindgen]
+pub fn deband_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: f32, range: u32, grain: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let params = DebandParams { threshold: threshold / 255.0, range, grain: grain / 255.0 };
+    deband::deband_u8(input.view(), &params).into_raw_vec_and_offset().0
+}
+
+/// Smooth quantization banding (f32). `threshold` and `grain` are in 0.0-1.0.
+#[wasm_bindgen]
+pub fn deband_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32, range: u32, grain: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    deband::deband_f32(input.view(), &DebandParams { threshold, range, grain }).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// FFT
+// ============================================================================
+
+/// Forward 2D FFT of luminance (or each color channel with `per_channel`).
+///
+/// Returns the unshifted spectrum as interleaved (re, im) pairs,
+/// height * width * (1 or 3) * 2 values.
+#[wasm_bindgen]
+pub fn fft_forward_wasm(data: &[f32], width: usize, height: usize, channels: usize, per_channel: bool) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    fft::fft_forward_f32(input.view(), per_channel).iter().flat_map(|v| [v.re, v.im]).collect()
+}
+
+/// Inverse 2D FFT of an interleaved (re, im) spectrum with `channels` planes.
+#[wasm_bindgen]
+pub fn fft_inverse_wasm(spectrum: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let values: Vec<Complex> = spectrum.chunks_exact(2).map(|p| Complex::new(p[0], p[1])).collect();
+    let input = Array3::from_shape_vec((height, width, channels), values).expect("Invalid dimensions");
+    fft::fft_inverse_f32(input.view()).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn fft_lowpass_wasm(data: &[u8], width: usize, height: usize, channels: usize, cutoff: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    fft::fft_lowpass_u8(input.view(), cutoff).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn fft_lowpass_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, cutoff: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    fft::fft_lowpass_f32(input.view(), cutoff).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn fft_highpass_wasm(data: &[u8], width: usize, height: usize, channels: usize, cutoff: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    fft::fft_highpass_u8(input.view(), cutoff).into_raw_vec_and_offset().0
+}
+
+#[wasm_bindgen]
+pub fn fft_highpass_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, cutoff: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    fft::fft_highpass_f32(input.view(), cutoff).into_raw_vec_and_offset().0
+}
+
+/// Parse flat (u, v, radius) triples.
+fn parse_notches(notches: &[f32]) -> Result<Vec<Notch>, JsError> {
+    if !notches.len().is_multiple_of(3) {
+        return Err(JsError::new("notches must be (u, v, radius) triples"));
+    }
+    Ok(notches.chunks_exact(3).map(|n| Notch { u: n[0], v: n[1], radius: n[2] }).collect())
+}
+
+/// Suppress spectrum peaks; `notches` holds (u, v, radius) triples in cycles per image.
+#[wasm_bindgen]
+pub fn fft_notch_filter_wasm(data: &[u8], width: usize, height: usize, channels: usize, notches: &[f32]) -> Result<Vec<u8>, JsError> {
+    let notches = parse_notches(notches)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    Ok(fft::fft_notch_filter_u8(input.view(), &notches).into_raw_vec_and_offset().0)
+}
+
+/// Suppress spectrum peaks (f32); `notches` holds (u, v, radius) triples in cycles per image.
+#[wasm_bindgen]
+pub fn fft_notch_filter_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, notches: &[f32]) -> Result<Vec<f32>, JsError> {
+    let notches = parse_notches(notches)?;
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    Ok(fft::fft_notch_filter_f32(input.view(), &notches).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Projection
+// ============================================================================
+
+/// Split an equirectangular panorama into six cube faces (u8).
+///
+/// Returns 6 * face_size * face_size * channels values, faces ordered
+/// +x, -x, +y, -y, +z, -z.
+#[wasm_bindgen]
+pub fn equirect_to_cubemap_wasm(data: &[u8], width: usize, height: usize, channels: usize, face_size: usize) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    projection::equirect_to_cubemap_u8(input, face_size).into_raw_vec_and_offset().0
+}
+
+/// Split an equirectangular panorama into six cube faces (f32).
+#[wasm_bindgen]
+pub fn equirect_to_cubemap_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, face_size: usize) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    projection::equirect_to_cubemap_f32(input, face_size).into_raw_vec_and_offset().0
+}
+
+/// Merge six stacked cube faces into an equirectangular panorama (u8).
+#[wasm_bindgen]
+pub fn cubemap_to_equirect_wasm(faces: &[u8], face_size: usize, channels: usize, out_width: usize, out_height: usize) -> Vec<u8> {
+    let input = ndarray::ArrayView4::from_shape((6, face_size, face_size, channels), faces).expect("Invalid dimensions");
+    projection::cubemap_to_equirect_u8(input, out_width, out_height).into_raw_vec_and_offset().0
+}
+
+/// Merge six stacked cube faces into an equirectangular panorama (f32).
+#[wasm_bindgen]
+pub fn cubemap_to_equirect_f32_wasm(faces: &[f32], face_size: usize, channels: usize, out_width: usize, out_height: usize) -> Vec<f32> {
+    let input = ndarray::ArrayView4::from_shape((6, face_size, face_size, channels), faces).expect("Invalid dimensions");
+    projection::cubemap_to_equirect_f32(input, out_width, out_height).into_raw_vec_and_offset().0
+}
+
+/// View direction, field of view (degrees) and output size for `reproject_equirect_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct ReprojectOptions {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+    pub fov: f32,
+    pub out_width: usize,
+    pub out_height: usize,
+}
+
+impl Default for ReprojectOptions {
+    fn default() -> Self {
+        let d = ViewParams::default();
+        Self { yaw: d.yaw, pitch: d.pitch, roll: d.roll, fov: d.fov, out_width: 640, out_height: 480 }
+    }
+}
+
+#[wasm_bindgen]
+impl ReprojectOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReprojectOptions {
+    fn view(&self) -> ViewParams {
+        ViewParams { yaw: self.yaw, pitch: self.pitch, roll: self.roll, fov: self.fov }
+    }
+}
+
+/// Render a perspective view of an equirectangular panorama (u8).
+#[wasm_bindgen]
+pub fn reproject_equirect_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &ReprojectOptions) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    projection::reproject_equirect_u8(input, &options.view(), options.out_width, options.out_height).into_raw_vec_and_offset().0
+}
+
+/// Render a perspective view of an equirectangular panorama (f32).
+#[wasm_bindgen]
+pub fn reproject_equirect_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &ReprojectOptions) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    projection::reproject_equirect_f32(input, &options.view(), options.out_width, options.out_height).into_raw_vec_and_offset().0
+}
+
+/// Render a "little planet" stereographic view of a panorama (u8), out_size x out_size.
+#[wasm_bindgen]
+pub fn little_planet_wasm(data: &[u8], width: usize, height: usize, channels: usize, fov: f32, rotation: f32, out_size: usize) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    projection::little_planet_u8(input, fov, rotation, out_size).into_raw_vec_and_offset().0
+}
+
+/// Render a "little planet" stereographic view of a panorama (f32), out_size x out_size.
+#[wasm_bindgen]
+pub fn little_planet_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, fov: f32, rotation: f32, out_size: usize) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    projection::little_planet_f32(input, fov, rotation, out_size).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Distort
+// ============================================================================
+
+fn parse_distort_args(direction: &str, interpolation: &str) -> Result<(PolarDirection, Interpolation), JsError> {
+    let direction = PolarDirection::from_name(direction)
+        .ok_or_else(|| JsError::new(&format!("unknown polar direction '{}'", direction)))?;
+    let interpolation = Interpolation::from_name(interpolation)
+        .ok_or_else(|| JsError::new(&format!("unknown interpolation '{}'", interpolation)))?;
+    Ok((direction, interpolation))
+}
+
+/// Convert between rectangular and polar coordinates (u8).
+///
+/// `direction` is "rect_to_polar" or "polar_to_rect"; `interpolation` is
+/// "nearest", "bilinear" or "bicubic".
+#[wasm_bindgen]
+pub fn polar_coordinates_wasm(data: &[u8], width: usize, height: usize, channels: usize, direction: &str, interpolation: &str) -> Result<Vec<u8>, JsError> {
+    let (direction, interpolation) = parse_distort_args(direction, interpolation)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(distort::polar_coordinates_u8(input, direction, interpolation).into_raw_vec_and_offset().0)
+}
+
+/// Convert between rectangular and polar coordinates (f32).
+#[wasm_bindgen]
+pub fn polar_coordinates_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, direction: &str, interpolation: &str) -> Result<Vec<f32>, JsError> {
+    let (direction, interpolation) = parse_distort_args(direction, interpolation)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(distort::polar_coordinates_f32(input, direction, interpolation).into_raw_vec_and_offset().0)
+}
+
+/// Twirl parameters for `twirl_wasm`: angle in degrees, radius and center as fractions.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct TwirlOptions {
+    pub angle: f32,
+    pub radius: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+}
+
+impl Default for TwirlOptions {
+    fn default() -> Self {
+        Self { angle: 50.0, radius: 1.0, center_x: 0.5, center_y: 0.5 }
+    }
+}
+
+#[wasm_bindgen]
+impl TwirlOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Twirl the image around a center (u8).
+#[wasm_bindgen]
+pub fn twirl_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &TwirlOptions) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let center = (options.center_x, options.center_y);
+    distort::twirl_u8(input, options.angle, options.radius, center).into_raw_vec_and_offset().0
+}
+
+/// Twirl the image around a center (f32).
+#[wasm_bindgen]
+pub fn twirl_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &TwirlOptions) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let center = (options.center_x, options.center_y);
+    distort::twirl_f32(input, options.angle, options.radius, center).into_raw_vec_and_offset().0
+}
+
+/// Wrap the image around a sphere (u8). `amount` -1.0 (squeeze) to 1.0 (bulge).
+#[wasm_bindgen]
+pub fn spherize_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    distort::spherize_u8(input, amount).into_raw_vec_and_offset().0
+}
+
+/// Wrap the image around a sphere (f32).
+#[wasm_bindgen]
+pub fn spherize_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    distort::spherize_f32(input, amount).into_raw_vec_and_offset().0
+}
+
+/// Squeeze the image toward the center (u8). `amount` -1.0 (push out) to 1.0 (pinch in).
+#[wasm_bindgen]
+pub fn pinch_wasm(data: &[u8], width: usize, height: usize, channels: usize, amount: f32) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    distort::pinch_u8(input, amount).into_raw_vec_and_offset().0
+}
+
+/// Squeeze the image toward the center (f32).
+#[wasm_bindgen]
+pub fn pinch_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amount: f32) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    distort::pinch_f32(input, amount).into_raw_vec_and_offset().0
+}
+
+/// Ripple the image with a periodic wave (u8). `wave_type` is "sine", "triangle" or "square".
+#[wasm_bindgen]
+pub fn wave_wasm(data: &[u8], width: usize, height: usize, channels: usize, amplitude: f32, wavelength: f32, wave_type: &str) -> Result<Vec<u8>, JsError> {
+    let wave_type = WaveType::from_name(wave_type).ok_or_else(|| JsError::new(&format!("unknown wave type '{}'", wave_type)))?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(distort::wave_u8(input, amplitude, wavelength, wave_type).into_raw_vec_and_offset().0)
+}
+
+/// Ripple the image with a periodic wave (f32).
+#[wasm_bindgen]
+pub fn wave_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, amplitude: f32, wavelength: f32, wave_type: &str) -> Result<Vec<f32>, JsError> {
+    let wave_type = WaveType::from_name(wave_type).ok_or_else(|| JsError::new(&format!("unknown wave type '{}'", wave_type)))?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(distort::wave_f32(input, amplitude, wavelength, wave_type).into_raw_vec_and_offset().0)
+}
+
+/// Kaleidoscope parameters for `kaleidoscope_wasm`: rotation in degrees, center as fractions.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct KaleidoscopeOptions {
+    pub segments: u32,
+    pub rotation: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+}
+
+impl Default for KaleidoscopeOptions {
+    fn default() -> Self {
+        Self { segments: 6, rotation: 0.0, center_x: 0.5, center_y: 0.5 }
+    }
+}
+
+#[wasm_bindgen]
+impl KaleidoscopeOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Repeat one wedge of the image around a center, mirrored alternately (u8).
+#[wasm_bindgen]
+pub fn kaleidoscope_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &KaleidoscopeOptions) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let center = (options.center_x, options.center_y);
+    distort::kaleidoscope_u8(input, options.segments, options.rotation, center).into_raw_vec_and_offset().0
+}
+
+/// Repeat one wedge of the image around a center, mirrored alternately (f32).
+#[wasm_bindgen]
+pub fn kaleidoscope_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &KaleidoscopeOptions) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let center = (options.center_x, options.center_y);
+    distort::kaleidoscope_f32(input, options.segments, options.rotation, center).into_raw_vec_and_offset().0
+}
+
+/// Reflect one side of the image onto the other across a line (u8). `axis_angle` 0 = vertical.
+#[wasm_bindgen]
+pub fn mirror_wasm(data: &[u8], width: usize, height: usize, channels: usize, axis_angle: f32, offset: f32) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    distort::mirror_u8(input, axis_angle, offset).into_raw_vec_and_offset().0
+}
+
+/// Reflect one side of the image onto the other across a line (f32).
+#[wasm_bindgen]
+pub fn mirror_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, axis_angle: f32, offset: f32) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    distort::mirror_f32(input, axis_angle, offset).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Canvas
+// ============================================================================
+
+fn parse_trim_mode(mode: &str) -> Result<TrimMode, JsError> {
+    TrimMode::from_name(mode).ok_or_else(|| JsError::new(&format!("unknown trim mode '{}'", mode)))
+}
+
+/// Bounding box of the non-background content (u8).
+///
+/// `mode` is "transparent", "top_left" or "bottom_right"; `tolerance` is the
+/// largest per-channel difference (or alpha) still counted as background.
+///
+/// # Returns
+/// [x, y, width, height], or an empty array if the image holds only background
+#[wasm_bindgen]
+pub fn content_bounds_wasm(data: &[u8], width: usize, height: usize, channels: usize, mode: &str, tolerance: u8) -> Result<Vec<u32>, JsError> {
+    let mode = parse_trim_mode(mode)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let rect = canvas::content_bounds_u8(input, mode, tolerance);
+    Ok(rect.map(|r| vec![r.x as u32, r.y as u32, r.width as u32, r.height as u32]).unwrap_or_default())
+}
+
+/// Bounding box of the non-background content (f32, tolerance 0.0-1.0).
+#[wasm_bindgen]
+pub fn content_bounds_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mode: &str, tolerance: f32) -> Result<Vec<u32>, JsError> {
+    let mode = parse_trim_mode(mode)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let rect = canvas::content_bounds_f32(input, mode, tolerance);
+    Ok(rect.map(|r| vec![r.x as u32, r.y as u32, r.width as u32, r.height as u32]).unwrap_or_default())
+}
+
+/// Tight bounding box of the pixels with alpha above `threshold` (u8).
+///
+/// Images without alpha count as fully visible; `threshold` 0 gives the
+/// bounds of every pixel with any opacity.
+///
+/// # Returns
+/// [x, y, width, height], or an empty array if no pixel is above the threshold
+#[wasm_bindgen]
+pub fn alpha_bbox_wasm(data: &[u8], width: usize, height: usize, channels: usize, threshold: u8) -> Vec<u32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let rect = canvas::alpha_bbox_u8(input, threshold);
+    rect.map(|r| vec![r.x as u32, r.y as u32, r.width as u32, r.height as u32]).unwrap_or_default()
+}
+
+/// Tight bounding box of the pixels with alpha above `threshold` (f32, 0.0-1.0).
+#[wasm_bindgen]
+pub fn alpha_bbox_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, threshold: f32) -> Vec<u32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let rect = canvas::alpha_bbox_f32(input, threshold);
+    rect.map(|r| vec![r.x as u32, r.y as u32, r.width as u32, r.height as u32]).unwrap_or_default()
+}
+
+/// Canvas extension for `extend_canvas_fill_wasm`: pixels added per side and
+/// the fill method ("mirror", "smear" or "patch_match").
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct ExtendCanvasOptions {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+    pub method: String,
+}
+
+impl Default for ExtendCanvasOptions {
+    fn default() -> Self {
+        Self { left: 0, top: 0, right: 0, bottom: 0, method: "mirror".to_string() }
+    }
+}
+
+#[wasm_bindgen]
+impl ExtendCanvasOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn parse_extend_method(method: &str) -> Result<ExtendMethod, JsError> {
+    ExtendMethod::from_name(method).ok_or_else(|| JsError::new(&format!("unknown extend method '{}'", method)))
+}
+
+/// Grow the canvas and fill the new border (u8).
+///
+/// # Returns
+/// Image of (height + top + bottom) x (width + left + right)
+#[wasm_bindgen]
+pub fn extend_canvas_fill_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &ExtendCanvasOptions) -> Result<Vec<u8>, JsError> {
+    let method = parse_extend_method(&options.method)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let o = options;
+    Ok(canvas::extend_canvas_fill_u8(input, o.left, o.top, o.right, o.bottom, method).into_raw_vec_and_offset().0)
+}
+
+/// Grow the canvas and fill the new border (f32).
+#[wasm_bindgen]
+pub fn extend_canvas_fill_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &ExtendCanvasOptions) -> Result<Vec<f32>, JsError> {
+    let method = parse_extend_method(&options.method)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let o = options;
+    Ok(canvas::extend_canvas_fill_f32(input, o.left, o.top, o.right, o.bottom, method).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Synthesis
+// ============================================================================
+
+/// Fill the masked region with texture from the rest of the image (u8).
+///
+/// `mask` is (height * width), values >= 128 mark the pixels to replace.
+#[wasm_bindgen]
+pub fn fill_region_patchmatch_wasm(data: &[u8], width: usize, height: usize, channels: usize, mask: &[u8], patch_size: usize, seed: u32) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let mask = ArrayView2::from_shape((height, width), mask).expect("Invalid mask dimensions");
+    synthesis::fill_region_patchmatch_u8(input, mask, patch_size, seed as u64).into_raw_vec_and_offset().0
+}
+
+/// Fill the masked region with texture from the rest of the image (f32, mask >= 0.5).
+#[wasm_bindgen]
+pub fn fill_region_patchmatch_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mask: &[f32], patch_size: usize, seed: u32) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let mask = ArrayView2::from_shape((height, width), mask).expect("Invalid mask dimensions");
+    synthesis::fill_region_patchmatch_f32(input, mask, patch_size, seed as u64).into_raw_vec_and_offset().0
+}
+
+/// Output size and search parameters for `synthesize_texture_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct TextureSynthesisOptions {
+    pub out_width: usize,
+    pub out_height: usize,
+    pub patch_size: usize,
+    pub seed: u32,
+}
+
+impl Default for TextureSynthesisOptions {
+    fn default() -> Self {
+        Self { out_width: 256, out_height: 256, patch_size: 7, seed: 0 }
+    }
+}
+
+#[wasm_bindgen]
+impl TextureSynthesisOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Synthesize a texture of `out_width` x `out_height` from a sample (u8).
+#[wasm_bindgen]
+pub fn synthesize_texture_wasm(data: &[u8], width: usize, height: usize, channels: usize, options: &TextureSynthesisOptions) -> Vec<u8> {
+    let sample = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let o = options;
+    synthesis::synthesize_texture_u8(sample, o.out_width, o.out_height, o.patch_size, o.seed as u64).into_raw_vec_and_offset().0
+}
+
+/// Synthesize a texture of `out_width` x `out_height` from a sample (f32).
+#[wasm_bindgen]
+pub fn synthesize_texture_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, options: &TextureSynthesisOptions) -> Vec<f32> {
+    let sample = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let o = options;
+    synthesis::synthesize_texture_f32(sample, o.out_width, o.out_height, o.patch_size, o.seed as u64).into_raw_vec_and_offset().0
+}
+
+/// Source size and search parameters for `patchmatch_nnf_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct PatchMatchOptions {
+    pub source_width: usize,
+    pub source_height: usize,
+    pub patch_size: usize,
+    pub iterations: usize,
+    pub seed: u32,
+}
+
+impl Default for PatchMatchOptions {
+    fn default() -> Self {
+        Self { source_width: 0, source_height: 0, patch_size: 7, iterations: 5, seed: 0 }
+    }
+}
+
+#[wasm_bindgen]
+impl PatchMatchOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn nnf_to_vec(nnf: synthesis::NearestNeighborField) -> Vec<f32> {
+    nnf.distances
+        .indexed_iter()
+        .flat_map(|((y, x), &d)| [nnf.matches[[y, x, 0]] as f32, nnf.matches[[y, x, 1]] as f32, d])
+        .collect()
+}
+
+/// PatchMatch nearest-neighbor field from target to source patches (u8).
+///
+/// # Returns
+/// Per target pixel (row-major) the matched source center x, y and the
+/// sum of squared differences on 0.0-1.0 values
+#[wasm_bindgen]
+pub fn patchmatch_nnf_wasm(target: &[u8], width: usize, height: usize, channels: usize, source: &[u8], options: &PatchMatchOptions) -> Vec<f32> {
+    let target = ArrayView3::from_shape((height, width, channels), target).expect("Invalid dimensions");
+    let source = ArrayView3::from_shape((options.source_height, options.source_width, channels), source).expect("Invalid source dimensions");
+    nnf_to_vec(synthesis::patchmatch_nnf_u8(target, source, options.patch_size, options.iterations, options.seed as u64))
+}
+
+/// PatchMatch nearest-neighbor field from target to source patches (f32).
+#[wasm_bindgen]
+pub fn patchmatch_nnf_f32_wasm(target: &[f32], width: usize, height: usize, channels: usize, source: &[f32], options: &PatchMatchOptions) -> Vec<f32> {
+    let target = ArrayView3::from_shape((height, width, channels), target).expect("Invalid dimensions");
+    let source = ArrayView3::from_shape((options.source_height, options.source_width, channels), source).expect("Invalid source dimensions");
+    nnf_to_vec(synthesis::patchmatch_nnf_f32(target, source, options.patch_size, options.iterations, options.seed as u64))
+}
+
+/// Source size, placement and mode for `seamless_clone_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct SeamlessCloneOptions {
+    pub src_width: usize,
+    pub src_height: usize,
+    /// Position of the source's top-left corner in the destination
+    pub x: i32,
+    pub y: i32,
+    /// "normal" or "mixed"
+    pub mode: String,
+}
+
+impl Default for SeamlessCloneOptions {
+    fn default() -> Self {
+        Self { src_width: 0, src_height: 0, x: 0, y: 0, mode: "normal".into() }
+    }
+}
+
+#[wasm_bindgen]
+impl SeamlessCloneOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn parse_clone_mode(mode: &str) -> Result<synthesis::CloneMode, JsError> {
+    synthesis::CloneMode::from_name(mode).ok_or_else(|| JsError::new(&format!("unknown clone mode '{}'", mode)))
+}
+
+/// Paste the masked part of `src` into `dst` with Poisson blending (u8).
+///
+/// `dst` is (height, width, channels); the size of `src` and of `mask`
+/// (values >= 128 are cloned) comes from the options.
+#[wasm_bindgen]
+pub fn seamless_clone_wasm(src: &[u8], dst: &[u8], width: usize, height: usize, channels: usize, mask: &[u8], options: &SeamlessCloneOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    let mode = parse_clone_mode(&o.mode)?;
+    let src = ArrayView3::from_shape((o.src_height, o.src_width, channels), src).expect("Invalid source dimensions");
+    let dst = ArrayView3::from_shape((height, width, channels), dst).expect("Invalid dimensions");
+    let mask = ArrayView2::from_shape((o.src_height, o.src_width), mask).expect("Invalid mask dimensions");
+    Ok(synthesis::seamless_clone_u8(src, dst, mask, (o.x as i64, o.y as i64), mode).into_raw_vec_and_offset().0)
+}
+
+/// Paste the masked part of `src` into `dst` with Poisson blending (f32, mask >= 0.5).
+#[wasm_bindgen]
+pub fn seamless_clone_f32_wasm(src: &[f32], dst: &[f32], width: usize, height: usize, channels: usize, mask: &[f32], options: &SeamlessCloneOptions) -> Result<Vec<f32>, JsError> {
+    let o = options;
+    let mode = parse_clone_mode(&o.mode)?;
+    let src = ArrayView3::from_shape((o.src_height, o.src_width, channels), src).expect("Invalid source dimensions");
+    let dst = ArrayView3::from_shape((height, width, channels), dst).expect("Invalid dimensions");
+    let mask = ArrayView2::from_shape((o.src_height, o.src_width), mask).expect("Invalid mask dimensions");
+    Ok(synthesis::seamless_clone_f32(src, dst, mask, (o.x as i64, o.y as i64), mode).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Seamless Textures
+// ============================================================================
+
+fn seamless_args(method: &str, blend_width: usize, width: usize, height: usize) -> Result<(SeamlessMethod, usize), JsError> {
+    let method = SeamlessMethod::from_name(method).ok_or_else(|| JsError::new(&format!("unknown seamless method '{}'", method)))?;
+    let blend_width = if blend_width == 0 { width.min(height) / 4 } else { blend_width };
+    Ok((method, blend_width))
+}
+
+/// Make an image tile without visible seams (u8).
+///
+/// `method` is "offset_blend" or "frequency"; `blend_width` 0 uses a quarter
+/// of the shorter side.
+#[wasm_bindgen]
+pub fn make_seamless_wasm(data: &[u8], width: usize, height: usize, channels: usize, method: &str, blend_width: usize) -> Result<Vec<u8>, JsError> {
+    let (method, blend_width) = seamless_args(method, blend_width, width, height)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(seamless::make_seamless_u8(input, method, blend_width).into_raw_vec_and_offset().0)
+}
+
+/// Make an image tile without visible seams (f32).
+#[wasm_bindgen]
+pub fn make_seamless_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, method: &str, blend_width: usize) -> Result<Vec<f32>, JsError> {
+    let (method, blend_width) = seamless_args(method, blend_width, width, height)?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(seamless::make_seamless_f32(input, method, blend_width).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// RAW
+// ============================================================================
+
+fn parse_bayer_pattern(name: &str) -> Result<BayerPattern, JsError> {
+    BayerPattern::from_name(name).ok_or_else(|| JsError::new(&format!("unknown Bayer pattern '{}'", name)))
+}
+
+/// Sensor levels and white balance for `raw_to_linear_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct RawOptions {
+    pub black_level: f32,
+    pub white_level: f32,
+    pub wb_r: f32,
+    pub wb_g: f32,
+    pub wb_b: f32,
+}
+
+impl Default for RawOptions {
+    fn default() -> Self {
+        let d = RawParams::default();
+        Self {
+            black_level: d.black_level,
+            white_level: d.white_level,
+            wb_r: d.wb_gains[0],
+            wb_g: d.wb_gains[1],
+            wb_b: d.wb_gains[2],
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RawOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Normalize a u16 Bayer mosaic (height * width) to linear f32 and white balance it.
+///
+/// `pattern` is "rggb", "bggr", "grbg" or "gbrg".
+#[wasm_bindgen]
+pub fn raw_to_linear_wasm(data: &[u16], width: usize, height: usize, pattern: &str, options: &RawOptions) -> Result<Vec<f32>, JsError> {
+    let pattern = parse_bayer_pattern(pattern)?;
+    let input = ArrayView2::from_shape((height, width), data).expect("Invalid dimensions");
+    let params = RawParams {
+        black_level: options.black_level,
+        white_level: options.white_level,
+        wb_gains: [options.wb_r, options.wb_g, options.wb_b],
+    };
+    Ok(raw_mod::raw_to_linear_f32(input, pattern, &params).into_raw_vec_and_offset().0)
+}
+
+/// Interpolate a linear Bayer mosaic (height * width) to RGB (3 channels).
+///
+/// `algorithm` is "bilinear" or "malvar".
+#[wasm_bindgen]
+pub fn demosaic_wasm(data: &[f32], width: usize, height: usize, pattern: &str, algorithm: &str) -> Result<Vec<f32>, JsError> {
+    let pattern = parse_bayer_pattern(pattern)?;
+    let algorithm = DemosaicAlgorithm::from_name(algorithm)
+        .ok_or_else(|| JsError::new(&format!("unknown demosaic algorithm '{}'", algorithm)))?;
+    let input = ArrayView2::from_shape((height, width), data).expect("Invalid dimensions");
+    Ok(raw_mod::demosaic_f32(input, pattern, algorithm).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Layer Effects: Satin
+// ============================================================================
+
+/// Apply satin effect to RGBA u8 image.
+///
+/// Creates silky interior shading by compositing shifted, blurred copies
+/// of the alpha channel. Contour and blend mode are available through
+/// `satin_rgba_options_wasm`.
+#[wasm_bindgen]
+pub fn satin_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+    angle: f32,
+    distance: f32,
+    size: f32,
+    invert: bool,
+) -> Vec<u8> {
+    let options = SatinOptions { color_r, color_g, color_b, opacity, angle, distance, size, invert, ..Default::default() };
+    satin_rgba_options_wasm(data, width, height, &options)
+}
+
+/// Apply satin effect to RGBA f32 image (same parameters as the u8 version).
+#[wasm_bindgen]
+pub fn satin_rgba_f32_wasm(data: &[f32], width: usize, height: usize, options: &SatinOptions) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    satin::satin_f32(input.view(), &SatinParams::from(options)).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Layer Effects: Gradient Overlay
+// ============================================================================
+
+/// Apply gradient overlay to RGBA u8 image.
+///
+/// Gradient stops are passed as flat array: [pos, r, g, b, pos, r, g, b, ...]
+/// Style: "linear", "radial", "angle", "reflected", "diamond"
+/// Blend mode: one of `overlay_blend_modes_wasm()`
+#[wasm_bindgen]
+pub fn gradient_overlay_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stops: &[f32],
+    style: &str,
+    angle: f32,
+    scale_x: f32,
+    scale_y: f32,
+    offset_x: f32,
+    offset_y: f32,
+    reverse: bool,
+    opacity: f32,
+    blend_mode: &str,
+) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let params = GradientOverlayParams {
+        stops: gradient_overlay::parse_stops(stops, 255.0),
+        style: style.to_string(),
+        angle,
+        scale_x,
+        scale_y,
+        offset_x,
+        offset_y,
+        reverse,
+        opacity,
+        blend_mode: blend_mode.to_string(),
+    };
+    gradient_overlay::gradient_overlay_u8(input.view(), &params).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Filters: Gradient Generator
+// ============================================================================
+
+/// Generate a gradient surface as RGBA u8 image (no input image needed).
+///
+/// Gradient stops: [pos, r, g, b, ...] where r,g,b are 0-255.
+#[wasm_bindgen]
+pub fn generate_gradient_wasm(
+    width: usize,
+    height: usize,
+    stops: &[f32],
+    style: &str,
+    angle: f32,
+    scale_x: f32,
+    scale_y: f32,
+    offset_x: f32,
+    offset_y: f32,
+    reverse: bool,
+) -> Vec<u8> {
+    struct GradientStop { position: f32, r: f32, g: f32, b: f32 }
+
+    let mut gradient_stops: Vec<GradientStop> = stops.chunks(4)
+        .filter(|c| c.len() == 4)
+        .map(|c| GradientStop { position: c[0], r: c[1] / 255.0, g: c[2] / 255.0, b: c[3] / 255.0 })
+        .collect();
+
+    gradient_stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
+
+    if gradient_stops.is_empty() {
+        gradient_stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
+        gradient_stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
+    }
+
+    let mut result = Array3::<u8>::zeros((height, width, 4));
+
+    // Center with offset
+    let cx = width as f32 / 2.0 + offset_x * width as f32 / 2.0;
+    let cy = height as f32 / 2.0 + offset_y * height as f32 / 2.0;
+    let sx = if scale_x.abs() > 0.001 { scale_x } else { 0.001 };
+    let sy = if scale_y.abs() > 0.001 { scale_y } else { 0.001 };
+    let half_w = width as f32 / 2.0;
+    let half_h = height as f32 / 2.0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let px = x as f32;
+            let py = y as f32;
+            let mut t = match style {
+                "linear" => {
+                    let angle_rad = angle.to_radians();
+                    let dx = angle_rad.cos();
+                    let dy = -angle_rad.sin();
+                    let rx = (px - cx) / sx;
+                    let ry = (py - cy) / sy;
+                    let proj = rx * dx + ry * dy;
+                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
+                    (proj / max_dist + 1.0) / 2.0
+                }
+                "radial" => {
+                    let dx = (px - cx) / sx;
+                    let dy = (py - cy) / sy;
+                    let dist = (dx * dx + dy * dy).sqrt();
+                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
+                    dist / max_dist
+                }
+                "angle" => {
+                    let dx = (px - cx) / sx;
+                    let dy = (py - cy) / sy;
+                    let mut angle_at_pixel = dy.atan2(dx);
+                    angle_at_pixel -= angle.to_radians();
+                    (angle_at_pixel + std::f32::consts::PI) / (2.0 * std::f32::consts::PI)
+                }
+                "reflected" => {
+                    let angle_rad = angle.to_radians();
+                    let dx = angle_rad.cos();
+                    let dy = -angle_rad.sin();
+                    let rx = (px - cx) / sx;
+                    let ry = (py - cy) / sy;
+                    let proj = rx * dx + ry * dy;
+                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
+                    let linear_t = (proj / max_dist + 1.0) / 2.0;
+                    (2.0 * (linear_t - 0.5)).abs()
+                }
+                "diamond" => {
+                    let dx = ((px - cx) / sx).abs();
+                    let dy = ((py - cy) / sy).abs();
+                    (dx + dy) / (half_w + half_h)
+                }
+                _ => {
+                    let angle_rad = angle.to_radians();
+                    let dx = angle_rad.cos();
+                    let dy = -angle_rad.sin();
+                    let rx = (px - cx) / sx;
+                    let ry = (py - cy) / sy;
+                    let proj = rx * dx + ry * dy;
+                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
+                    (proj / max_dist + 1.0) / 2.0
+                }
+            };
+
+            t = t.clamp(0.0, 1.0);
+            if reverse { t = 1.0 - t; }
+
+            // Interpolate gradient color
             let (grad_r, grad_g, grad_b) = {
                 let mut prev_idx = 0;
                 let mut next_idx = gradient_stops.len() - 1;
@@ -1189,1123 +4262,3036 @@ pub fn gradient_overlay_rgba_wasm(
                 }
             };
 
-            let orig_r = input[[y, x, 0]] as f32 / 255.0;
-            let orig_g = input[[y, x, 1]] as f32 / 255.0;
-            let orig_b = input[[y, x, 2]] as f32 / 255.0;
+            result[[y, x, 0]] = (grad_r * 255.0).clamp(0.0, 255.0) as u8;
+            result[[y, x, 1]] = (grad_g * 255.0).clamp(0.0, 255.0) as u8;
+            result[[y, x, 2]] = (grad_b * 255.0).clamp(0.0, 255.0) as u8;
+            result[[y, x, 3]] = 255;
+        }
+    }
+
+    result.into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Layer Effects: Pattern Overlay
+// ============================================================================
+
+/// Apply pattern overlay to RGBA u8 image.
+///
+/// Pattern is tiled across the image, scaled and offset as specified.
+/// Uses bilinear interpolation when scale != 1.0 for smooth scaling.
+/// Blend mode: one of `overlay_blend_modes_wasm()`
+#[wasm_bindgen]
+pub fn pattern_overlay_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    pattern_data: &[u8],
+    pattern_width: usize,
+    pattern_height: usize,
+    scale: f32,
+    offset_x: i32,
+    offset_y: i32,
+    opacity: f32,
+    blend_mode: &str,
+) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let pattern = Array3::from_shape_vec((pattern_height, pattern_width, 4), pattern_data.to_vec()).expect("Invalid pattern dimensions");
+    let params = PatternOverlayParams {
+        pattern: pattern.mapv(|v| v as f32 / 255.0),
+        scale,
+        offset_x,
+        offset_y,
+        opacity,
+        blend_mode: blend_mode.to_string(),
+    };
+    pattern_overlay::pattern_overlay_u8(input.view(), &params).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Layer Effects: Drop Shadow
+// ============================================================================
+
+/// Apply drop shadow effect to RGBA u8 image.
+///
+/// Creates a shadow cast behind the layer by blurring and offsetting the alpha.
+/// With `knockout` the shadow is hidden under the layer's own pixels, so it
+/// does not show through semi-transparent areas. The canvas grows by
+/// `drop_shadow_expansion_wasm` pixels on every side.
+///
+/// `quality` is "exact" or "fast" (three-box blur for interactive previews).
+#[wasm_bindgen]
+pub fn drop_shadow_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    offset_x: f32,
+    offset_y: f32,
+    blur_radius: f32,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+    knockout: bool,
+    quality: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = ArrayView3::from_shape((height, width, 4), data).expect("Invalid dimensions");
+    let color = [color_r, color_g, color_b].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = DropShadowParams { offset_x, offset_y, blur_radius, color, opacity, knockout, quality, ..Default::default() };
+    Ok(render_drop_shadow_u8(input, &params, 0).into_raw_vec_and_offset().0)
+}
+
+/// Pixels `drop_shadow_rgba_wasm` adds on each side of the layer.
+///
+/// The result starts at (-expansion, -expansion) relative to the layer origin.
+#[wasm_bindgen]
+pub fn drop_shadow_expansion_wasm(offset_x: f32, offset_y: f32, blur_radius: f32) -> usize {
+    DropShadowParams { offset_x, offset_y, blur_radius, ..Default::default() }.expansion()
+}
+
+// ============================================================================
+// Layer Effects: Cast Shadow
+// ============================================================================
+
+/// Parameters of `cast_shadow_rgba_wasm` (see `layer_effects::cast_shadow`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct CastShadowOptions {
+    pub angle: f32,
+    pub length: f32,
+    pub perspective_squash: f32,
+    pub blur_near: f32,
+    pub blur_far: f32,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    /// Three-box blur approximation for interactive previews
+    pub fast_blur: bool,
+}
+
+impl Default for CastShadowOptions {
+    fn default() -> Self {
+        Self {
+            angle: 30.0,
+            length: 0.6,
+            perspective_squash: 0.6,
+            blur_near: 1.0,
+            blur_far: 8.0,
+            color_r: 0,
+            color_g: 0,
+            color_b: 0,
+            opacity: 0.6,
+            fast_blur: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl CastShadowOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<&CastShadowOptions> for CastShadowParams {
+    fn from(o: &CastShadowOptions) -> Self {
+        Self {
+            angle: o.angle,
+            length: o.length,
+            perspective_squash: o.perspective_squash,
+            blur_near: o.blur_near,
+            blur_far: o.blur_far,
+            color: [o.color_r, o.color_g, o.color_b].map(|v| v as f32 / 255.0),
+            opacity: o.opacity,
+            quality: if o.fast_blur { BlurQuality::Fast } else { BlurQuality::Exact },
+        }
+    }
+}
+
+/// Apply cast shadow effect to RGBA u8 image.
+///
+/// Projects the alpha onto the ground behind the object with perspective and
+/// distance-dependent blur. The canvas grows by `cast_shadow_expansion_wasm`
+/// pixels on every side.
+#[wasm_bindgen]
+pub fn cast_shadow_rgba_wasm(data: &[u8], width: usize, height: usize, options: &CastShadowOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    cast_shadow_rgba_u8(input.view(), &CastShadowParams::from(options)).into_raw_vec_and_offset().0
+}
+
+/// Pixels `cast_shadow_rgba_wasm` adds on each side of a layer.
+#[wasm_bindgen]
+pub fn cast_shadow_expansion_wasm(width: usize, height: usize, options: &CastShadowOptions) -> usize {
+    CastShadowParams::from(options).expansion(width, height)
+}
+
+// ============================================================================
+// Layer Effects: Long Shadow
+// ============================================================================
+
+/// Parameters of `long_shadow_rgba_wasm` (see `layer_effects::long_shadow`).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct LongShadowOptions {
+    pub angle: f32,
+    pub length: f32,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub fade: f32,
+}
+
+impl Default for LongShadowOptions {
+    fn default() -> Self {
+        Self { angle: 135.0, length: 50.0, color_r: 0, color_g: 0, color_b: 0, fade: 0.0 }
+    }
+}
+
+#[wasm_bindgen]
+impl LongShadowOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply long shadow effect to RGBA u8 image.
+///
+/// Extrudes the alpha silhouette away from the light at `angle` degrees.
+/// The canvas grows by `ceil(length) + 1` pixels on every side.
+#[wasm_bindgen]
+pub fn long_shadow_rgba_wasm(data: &[u8], width: usize, height: usize, options: &LongShadowOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let color = [o.color_r, o.color_g, o.color_b].map(|v| v as f32 / 255.0);
+    let params = LongShadowParams { angle: o.angle, length: o.length, color, fade: o.fade };
+    long_shadow_rgba_u8(input.view(), &params).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Layer Effects: Inner Shadow
+// ============================================================================
+
+/// Apply inner shadow effect to RGBA u8 image.
+///
+/// Creates a shadow inside the shape by inverting and blurring alpha.
+///
+/// `quality` is "exact" or "fast" (three-box blur for interactive previews).
+#[wasm_bindgen]
+pub fn inner_shadow_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    offset_x: f32,
+    offset_y: f32,
+    blur_radius: f32,
+    choke: f32,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+    quality: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = ArrayView3::from_shape((height, width, 4), data).expect("Invalid dimensions");
+    let color = [color_r, color_g, color_b].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = InnerShadowParams { offset_x, offset_y, blur_radius, choke, color, opacity, quality };
+    Ok(render_inner_shadow_u8(input, &params).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Layer Effects: Outer Glow
+// ============================================================================
+
+/// Apply outer glow effect to RGBA u8 image.
+///
+/// Creates a glow effect outside the shape edges. The canvas grows by
+/// `outer_glow_expansion_wasm` pixels on every side.
+///
+/// `quality` is "exact" or "fast" (three-box blur for interactive previews).
+#[wasm_bindgen]
+pub fn outer_glow_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    radius: f32,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+    spread: f32,
+    quality: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = ArrayView3::from_shape((height, width, 4), data).expect("Invalid dimensions");
+    let color = [color_r, color_g, color_b].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = OuterGlowParams { radius, color, opacity, spread, quality, ..Default::default() };
+    Ok(render_outer_glow_u8(input, &params, 0).into_raw_vec_and_offset().0)
+}
+
+/// Pixels `outer_glow_rgba_wasm` and `outer_glow_gradient_rgba_wasm` add on
+/// each side of the layer.
+#[wasm_bindgen]
+pub fn outer_glow_expansion_wasm(radius: f32, spread: f32) -> usize {
+    OuterGlowParams { radius, spread, ..Default::default() }.expansion()
+}
+
+/// Apply outer glow with gradient colors to RGBA u8 image.
+///
+/// `stops` is a flat array [pos, r, g, b, ...] with colors 0-255, running
+/// from the layer edge (0.0) to the outer reach of the glow (1.0).
+#[wasm_bindgen]
+pub fn outer_glow_gradient_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stops: &[f32],
+    radius: f32,
+    opacity: f32,
+    spread: f32,
+) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, 4), data).expect("Invalid dimensions");
+    let params = OuterGlowParams { radius, opacity, spread, gradient: gradient_overlay::parse_stops(stops, 255.0), ..Default::default() };
+    render_outer_glow_u8(input, &params, 0).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Layer Effects: Inner Glow
+// ============================================================================
+
+/// Apply inner glow effect to RGBA u8 image.
+///
+/// Creates a glow effect inside the shape edges.
+///
+/// `quality` is "exact" or "fast" (three-box blur for interactive previews).
+#[wasm_bindgen]
+pub fn inner_glow_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    radius: f32,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+    choke: f32,
+    quality: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = ArrayView3::from_shape((height, width, 4), data).expect("Invalid dimensions");
+    let color = [color_r, color_g, color_b].map(|v| v as f32 / 255.0);
+    let quality = parse_blur_quality(quality)?;
+    let params = InnerGlowParams { radius, color, opacity, choke, quality };
+    Ok(render_inner_glow_u8(input, &params).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Layer Effects: Bevel & Emboss
+// ============================================================================
+
+/// Apply bevel and emboss effect to RGBA u8 image.
+///
+/// Creates a 3D raised or sunken appearance using highlights and shadows.
+/// Uses the shared implementation in `layer_effects::bevel_emboss`.
+///
+/// # Arguments
+/// * `style` - "inner_bevel", "outer_bevel", "emboss", "pillow_emboss", "stroke_emboss"
+/// * `technique` - "smooth", "chisel_hard", "chisel_soft"
+/// * `size` - Bevel width in pixels (0 or negative = use `depth`)
+/// * `soften` - Extra blur of the height map in pixels
+/// * `direction` - "up" (raised) or "down" (sunken)
+/// * `gloss_contour_flat` - Gloss contour points [x0, y0, x1, y1, ...] (empty = linear)
+/// * `texture_data` - Grayscale texture pattern (empty = no texture)
+/// * `texture_width`, `texture_height` - Texture pattern dimensions
+/// * `texture_scale` - Pattern scale factor
+/// * `texture_depth` - Pattern relief in pixels (negative inverts)
+/// * `quality` - "exact" or "fast" (three-box blur for interactive previews)
+///
+/// # Returns
+/// RGBA data, expanded by `ceil(size + soften) + 2` on each side for every
+/// style except inner bevel
+#[wasm_bindgen]
+pub fn bevel_emboss_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    depth: f32,
+    angle: f32,
+    altitude: f32,
+    highlight_r: u8,
+    highlight_g: u8,
+    highlight_b: u8,
+    highlight_opacity: f32,
+    shadow_r: u8,
+    shadow_g: u8,
+    shadow_b: u8,
+    shadow_opacity: f32,
+    style: &str,
+    technique: &str,
+    size: f32,
+    soften: f32,
+    direction: &str,
+    gloss_contour_flat: &[f32],
+    texture_data: &[u8],
+    texture_width: usize,
+    texture_height: usize,
+    texture_scale: f32,
+    texture_depth: f32,
+    quality: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+
+    let params = BevelEmbossParams {
+        depth,
+        size: if size > 0.0 { size } else { depth },
+        soften,
+        angle,
+        altitude,
+        direction_up: direction != "down",
+        highlight_color: [
+            highlight_r as f32 / 255.0,
+            highlight_g as f32 / 255.0,
+            highlight_b as f32 / 255.0,
+        ],
+        highlight_opacity,
+        shadow_color: [
+            shadow_r as f32 / 255.0,
+            shadow_g as f32 / 255.0,
+            shadow_b as f32 / 255.0,
+        ],
+        shadow_opacity,
+        style: BevelStyle::parse(style),
+        technique: BevelTechnique::parse(technique),
+        gloss_contour: gloss_contour_flat.chunks(2).map(|c| (c[0], c[1])).collect(),
+        texture: if texture_data.is_empty() {
+            None
+        } else {
+            let pattern = Array2::from_shape_vec((texture_height, texture_width), texture_data.to_vec())
+                .expect("Invalid texture dimensions");
+            Some(BevelTexture {
+                pattern: pattern.mapv(|v| v as f32 / 255.0),
+                scale: texture_scale,
+                depth: texture_depth,
+            })
+        },
+        quality: parse_blur_quality(quality)?,
+    };
+
+    let result = bevel_emboss_f32(&input_f32, &params);
+    Ok(result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0)
+}
+
+// ============================================================================
+// Layer Effects: Stroke
+// ============================================================================
+
+/// Apply stroke/outline effect to RGBA u8 image.
+///
+/// Creates an outline around non-transparent areas. The canvas grows by
+/// `stroke_expansion_wasm` pixels on every side.
+#[wasm_bindgen]
+pub fn stroke_rgba_wasm(
+    data: &[u8],
+    img_width: usize,
+    height: usize,
+    stroke_width: f32,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+    position: &str,
+) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, img_width, 4), data).expect("Invalid dimensions");
+    let color = [color_r, color_g, color_b].map(|v| v as f32 / 255.0);
+    let params = StrokeParams { width: stroke_width, color, opacity, position: StrokePosition::parse(position), ..Default::default() };
+    render_stroke_u8(input, &params, 0).into_raw_vec_and_offset().0
+}
+
+/// Pixels `stroke_rgba_wasm` adds on each side of the layer (none for inside strokes).
+#[wasm_bindgen]
+pub fn stroke_expansion_wasm(stroke_width: f32, position: &str) -> usize {
+    StrokeParams { width: stroke_width, position: StrokePosition::parse(position), ..Default::default() }.expansion()
+}
+
+// ============================================================================
+// Layer Effects: Color Overlay
+// ============================================================================
+
+/// Apply color overlay effect to RGBA u8 image.
+///
+/// Replaces all colors with a solid color while preserving alpha. Use
+/// `color_overlay_rgba_options_wasm` for blend modes other than "normal".
+#[wasm_bindgen]
+pub fn color_overlay_rgba_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    color_r: u8,
+    color_g: u8,
+    color_b: u8,
+    opacity: f32,
+) -> Vec<u8> {
+    let options = ColorOverlayOptions { color_r, color_g, color_b, opacity, ..Default::default() };
+    color_overlay_rgba_options_wasm(data, width, height, &options)
+}
+
+/// Blend modes accepted by the color, gradient and pattern overlays.
+#[wasm_bindgen]
+pub fn overlay_blend_modes_wasm() -> Vec<String> {
+    color_overlay::BLEND_MODES.iter().map(|m| m.to_string()).collect()
+}
+
+// ============================================================================
+// Layer Effects: Options Objects
+// ============================================================================
+//
+// Named-field alternatives to the positional layer effect functions above.
+// Construct with defaults, set the fields that differ and pass the object:
+//
+//   const opts = new DropShadowOptions();
+//   opts.blur_radius = 4;
+//   const out = drop_shadow_rgba_options_wasm(data, width, height, opts);
+//
+// Defaults match the JS wrappers in `imagestag/layer_effects`.
+
+/// Quality name of an options object's `fast_blur` flag.
+fn quality_name(fast_blur: bool) -> &'static str {
+    if fast_blur { "fast" } else { "exact" }
+}
+
+/// Parameters of `drop_shadow_rgba_options_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct DropShadowOptions {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur_radius: f32,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    pub knockout: bool,
+    /// Three-box blur approximation for interactive previews
+    pub fast_blur: bool,
+}
+
+impl Default for DropShadowOptions {
+    fn default() -> Self {
+        Self {
+            offset_x: 5.0,
+            offset_y: 5.0,
+            blur_radius: 10.0,
+            color_r: 0,
+            color_g: 0,
+            color_b: 0,
+            opacity: 0.75,
+            knockout: false,
+            fast_blur: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl DropShadowOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply drop shadow with an options object (see `drop_shadow_rgba_wasm`).
+#[wasm_bindgen]
+pub fn drop_shadow_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &DropShadowOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    drop_shadow_rgba_wasm(
+        data,
+        width,
+        height,
+        o.offset_x,
+        o.offset_y,
+        o.blur_radius,
+        o.color_r,
+        o.color_g,
+        o.color_b,
+        o.opacity,
+        o.knockout,
+        quality_name(o.fast_blur),
+    )
+}
+
+/// Parameters of `inner_shadow_rgba_options_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct InnerShadowOptions {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur_radius: f32,
+    pub choke: f32,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    /// Three-box blur approximation for interactive previews
+    pub fast_blur: bool,
+}
+
+impl Default for InnerShadowOptions {
+    fn default() -> Self {
+        Self {
+            offset_x: 5.0,
+            offset_y: 5.0,
+            blur_radius: 10.0,
+            choke: 0.0,
+            color_r: 0,
+            color_g: 0,
+            color_b: 0,
+            opacity: 0.75,
+            fast_blur: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl InnerShadowOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply inner shadow with an options object (see `inner_shadow_rgba_wasm`).
+#[wasm_bindgen]
+pub fn inner_shadow_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &InnerShadowOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    inner_shadow_rgba_wasm(
+        data,
+        width,
+        height,
+        o.offset_x,
+        o.offset_y,
+        o.blur_radius,
+        o.choke,
+        o.color_r,
+        o.color_g,
+        o.color_b,
+        o.opacity,
+        quality_name(o.fast_blur),
+    )
+}
+
+/// Parameters of `outer_glow_rgba_options_wasm` and `inner_glow_rgba_options_wasm`.
+///
+/// `spread` applies to the outer glow, `choke` to the inner glow.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct GlowOptions {
+    pub radius: f32,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    pub spread: f32,
+    pub choke: f32,
+    /// Three-box blur approximation for interactive previews
+    pub fast_blur: bool,
+}
+
+impl Default for GlowOptions {
+    fn default() -> Self {
+        Self { radius: 10.0, color_r: 255, color_g: 255, color_b: 0, opacity: 0.75, spread: 0.0, choke: 0.0, fast_blur: false }
+    }
+}
+
+#[wasm_bindgen]
+impl GlowOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply outer glow with an options object (see `outer_glow_rgba_wasm`).
+#[wasm_bindgen]
+pub fn outer_glow_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &GlowOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    outer_glow_rgba_wasm(data, width, height, o.radius, o.color_r, o.color_g, o.color_b, o.opacity, o.spread, quality_name(o.fast_blur))
+}
+
+/// Apply inner glow with an options object (see `inner_glow_rgba_wasm`).
+#[wasm_bindgen]
+pub fn inner_glow_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &GlowOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    inner_glow_rgba_wasm(data, width, height, o.radius, o.color_r, o.color_g, o.color_b, o.opacity, o.choke, quality_name(o.fast_blur))
+}
+
+/// Parameters of `satin_rgba_options_wasm` and `satin_rgba_f32_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct SatinOptions {
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    pub angle: f32,
+    pub distance: f32,
+    pub size: f32,
+    pub invert: bool,
+    /// Contour points [x0, y0, x1, y1, ...] (empty = linear)
+    pub contour: Vec<f32>,
+    /// One of `overlay_blend_modes_wasm()`
+    pub blend_mode: String,
+    /// Three-box blur approximation for interactive previews
+    pub fast_blur: bool,
+}
+
+impl Default for SatinOptions {
+    fn default() -> Self {
+        Self {
+            color_r: 0,
+            color_g: 0,
+            color_b: 0,
+            opacity: 0.5,
+            angle: 19.0,
+            distance: 11.0,
+            size: 14.0,
+            invert: false,
+            contour: Vec::new(),
+            blend_mode: "normal".into(),
+            fast_blur: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl SatinOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<&SatinOptions> for SatinParams {
+    fn from(o: &SatinOptions) -> Self {
+        Self {
+            color: [o.color_r as f32 / 255.0, o.color_g as f32 / 255.0, o.color_b as f32 / 255.0],
+            opacity: o.opacity,
+            angle: o.angle,
+            distance: o.distance,
+            size: o.size,
+            invert: o.invert,
+            contour: o.contour.chunks_exact(2).map(|p| (p[0], p[1])).collect(),
+            blend_mode: o.blend_mode.clone(),
+            quality: if o.fast_blur { BlurQuality::Fast } else { BlurQuality::Exact },
+        }
+    }
+}
+
+/// Apply satin with an options object (see `satin_rgba_wasm`).
+#[wasm_bindgen]
+pub fn satin_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &SatinOptions) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    satin::satin_u8(input.view(), &SatinParams::from(options)).into_raw_vec_and_offset().0
+}
+
+/// Parameters of `stroke_rgba_options_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct StrokeOptions {
+    pub stroke_width: f32,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    /// "outside", "inside" or "center"
+    pub position: String,
+}
+
+impl Default for StrokeOptions {
+    fn default() -> Self {
+        Self { stroke_width: 3.0, color_r: 255, color_g: 0, color_b: 0, opacity: 1.0, position: "outside".into() }
+    }
+}
+
+#[wasm_bindgen]
+impl StrokeOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply stroke with an options object (see `stroke_rgba_wasm`).
+#[wasm_bindgen]
+pub fn stroke_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &StrokeOptions) -> Vec<u8> {
+    let o = options;
+    stroke_rgba_wasm(data, width, height, o.stroke_width, o.color_r, o.color_g, o.color_b, o.opacity, &o.position)
+}
+
+/// Parameters of `color_overlay_rgba_options_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct ColorOverlayOptions {
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+    pub opacity: f32,
+    /// One of `overlay_blend_modes_wasm()`
+    pub blend_mode: String,
+    /// Keep the layer's luminosity ("Color" blend mode, ignores `blend_mode`)
+    pub preserve_luminosity: bool,
+}
+
+impl Default for ColorOverlayOptions {
+    fn default() -> Self {
+        Self { color_r: 255, color_g: 0, color_b: 0, opacity: 1.0, blend_mode: "normal".into(), preserve_luminosity: false }
+    }
+}
+
+#[wasm_bindgen]
+impl ColorOverlayOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply color overlay with an options object (see `color_overlay_rgba_wasm`).
+#[wasm_bindgen]
+pub fn color_overlay_rgba_options_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    options: &ColorOverlayOptions,
+) -> Vec<u8> {
+    let o = options;
+    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let params = ColorOverlayParams {
+        color: [o.color_r as f32 / 255.0, o.color_g as f32 / 255.0, o.color_b as f32 / 255.0],
+        opacity: o.opacity,
+        blend_mode: o.blend_mode.clone(),
+        preserve_luminosity: o.preserve_luminosity,
+    };
+    color_overlay::color_overlay_u8(input.view(), &params).into_raw_vec_and_offset().0
+}
+
+/// Parameters of `gradient_overlay_rgba_options_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct GradientOverlayOptions {
+    /// Flat stops [pos, r, g, b, ...]
+    pub stops: Vec<f32>,
+    /// "linear", "radial", "angle", "reflected" or "diamond"
+    pub style: String,
+    pub angle: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub reverse: bool,
+    pub opacity: f32,
+    /// One of `overlay_blend_modes_wasm()`
+    pub blend_mode: String,
+}
+
+impl Default for GradientOverlayOptions {
+    fn default() -> Self {
+        Self {
+            stops: vec![0.0, 0.0, 0.0, 0.0, 1.0, 255.0, 255.0, 255.0],
+            style: "linear".into(),
+            angle: 90.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            reverse: false,
+            opacity: 1.0,
+            blend_mode: "normal".into(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl GradientOverlayOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply gradient overlay with an options object (see `gradient_overlay_rgba_wasm`).
+#[wasm_bindgen]
+pub fn gradient_overlay_rgba_options_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    options: &GradientOverlayOptions,
+) -> Vec<u8> {
+    let o = options;
+    gradient_overlay_rgba_wasm(
+        data, width, height, &o.stops, &o.style, o.angle, o.scale_x, o.scale_y, o.offset_x, o.offset_y, o.reverse, o.opacity,
+        &o.blend_mode,
+    )
+}
+
+/// Parameters of `bevel_emboss_rgba_options_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct BevelEmbossOptions {
+    pub depth: f32,
+    pub angle: f32,
+    pub altitude: f32,
+    pub highlight_r: u8,
+    pub highlight_g: u8,
+    pub highlight_b: u8,
+    pub highlight_opacity: f32,
+    pub shadow_r: u8,
+    pub shadow_g: u8,
+    pub shadow_b: u8,
+    pub shadow_opacity: f32,
+    /// "inner_bevel", "outer_bevel", "emboss", "pillow_emboss" or "stroke_emboss"
+    pub style: String,
+    /// "smooth", "chisel_hard" or "chisel_soft"
+    pub technique: String,
+    /// Bevel width in pixels (0 = use `depth`)
+    pub size: f32,
+    pub soften: f32,
+    /// "up" or "down"
+    pub direction: String,
+    /// Gloss contour points [x0, y0, x1, y1, ...] (empty = linear)
+    pub gloss_contour: Vec<f32>,
+    /// Grayscale texture pattern (empty = no texture)
+    pub texture_data: Vec<u8>,
+    pub texture_width: usize,
+    pub texture_height: usize,
+    pub texture_scale: f32,
+    pub texture_depth: f32,
+    /// Three-box blur approximation for interactive previews
+    pub fast_blur: bool,
+}
+
+impl Default for BevelEmbossOptions {
+    fn default() -> Self {
+        Self {
+            depth: 3.0,
+            angle: 120.0,
+            altitude: 30.0,
+            highlight_r: 255,
+            highlight_g: 255,
+            highlight_b: 255,
+            highlight_opacity: 0.75,
+            shadow_r: 0,
+            shadow_g: 0,
+            shadow_b: 0,
+            shadow_opacity: 0.75,
+            style: "inner_bevel".into(),
+            technique: "smooth".into(),
+            size: 0.0,
+            soften: 0.0,
+            direction: "up".into(),
+            gloss_contour: Vec::new(),
+            texture_data: Vec::new(),
+            texture_width: 0,
+            texture_height: 0,
+            texture_scale: 1.0,
+            texture_depth: 1.0,
+            fast_blur: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl BevelEmbossOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Apply bevel and emboss with an options object (see `bevel_emboss_rgba_wasm`).
+#[wasm_bindgen]
+pub fn bevel_emboss_rgba_options_wasm(data: &[u8], width: usize, height: usize, options: &BevelEmbossOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    bevel_emboss_rgba_wasm(
+        data,
+        width,
+        height,
+        o.depth,
+        o.angle,
+        o.altitude,
+        o.highlight_r,
+        o.highlight_g,
+        o.highlight_b,
+        o.highlight_opacity,
+        o.shadow_r,
+        o.shadow_g,
+        o.shadow_b,
+        o.shadow_opacity,
+        &o.style,
+        &o.technique,
+        o.size,
+        o.soften,
+        &o.direction,
+        &o.gloss_contour,
+        &o.texture_data,
+        o.texture_width,
+        o.texture_height,
+        o.texture_scale,
+        o.texture_depth,
+        quality_name(o.fast_blur),
+    )
+}
+
+// ============================================================================
+// Selection Algorithms
+// ============================================================================
+
+use crate::selection::contour::extract_contours as extract_contours_impl;
+use crate::selection::distance::{distance_transform as distance_transform_impl, DistanceMetric};
+use crate::selection::magic_wand::{
+    auto_tolerance as auto_tolerance_impl, magic_wand_select as magic_wand_impl, magic_wand_select_soft as magic_wand_soft_impl,
+};
+use crate::selection::watershed::watershed as watershed_impl;
+use crate::selection::refine::snap_mask_to_edges as snap_mask_to_edges_impl;
+use crate::selection::components::{label_components as label_components_impl, remove_small_components as remove_small_components_impl};
+use crate::selection::stats::{label_summaries as label_summaries_impl, mask_summary as mask_summary_impl, MaskSummary};
+use crate::selection::marching_squares::{
+    extract_contours_precise as extract_contours_precise_impl,
+    extract_contours_downsampled as extract_contours_downsampled_impl,
+    contours_to_flat,
+    contours_from_flat,
+    douglas_peucker as douglas_peucker_impl,
+    douglas_peucker_closed as douglas_peucker_closed_impl,
+    Point as MarchingPoint,
+    Contour as MarchingContour,
+};
+use crate::selection::dashes::contour_to_dash_segments as contour_to_dash_segments_impl;
+use crate::selection::trace::trace_image_to_svg as trace_image_to_svg_impl;
+use crate::selection::fill::{fill_contours as fill_contours_impl, FillRule};
+use crate::selection::path_ops::{path_boolean as path_boolean_impl, stroke_path_outline as stroke_path_outline_impl, LineCap, LineJoin, PathOp};
+
+/// Extract contours from an alpha mask using Marching Squares.
+///
+/// # Arguments
+/// * `mask` - Alpha mask (0 = unselected, >0 = selected)
+/// * `width` - Mask width
+/// * `height` - Mask height
+///
+/// # Returns
+/// Flat array: [num_contours, len1, x1, y1, x2, y2, ..., len2, ...]
+#[wasm_bindgen]
+pub fn extract_contours_wasm(mask: &[u8], width: usize, height: usize) -> Vec<f32> {
+    extract_contours_impl(mask, width, height)
+}
+
+/// Magic wand selection using flood fill algorithm.
+///
+/// # Arguments
+/// * `image` - RGBA image data (4 bytes per pixel)
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `start_x` - Starting X coordinate
+/// * `start_y` - Starting Y coordinate
+/// * `tolerance` - Color tolerance (0-255)
+/// * `contiguous` - If true, only selects connected pixels
+///
+/// # Returns
+/// Selection mask (255 = selected, 0 = not selected)
+#[wasm_bindgen]
+pub fn magic_wand_select_wasm(
+    image: &[u8],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    tolerance: u8,
+    contiguous: bool,
+) -> Vec<u8> {
+    magic_wand_impl(image, width, height, start_x, start_y, tolerance, contiguous)
+}
+
+/// Magic wand selection with an anti-aliased edge.
+///
+/// # Returns
+/// Soft selection mask (0-255); edge pixels get fractional coverage
+#[wasm_bindgen]
+pub fn magic_wand_select_soft_wasm(
+    image: &[u8],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    tolerance: u8,
+    contiguous: bool,
+) -> Vec<u8> {
+    magic_wand_soft_impl(image, width, height, start_x, start_y, tolerance, contiguous)
+}
+
+/// Magic wand tolerance chosen from the colors around the start point.
+///
+/// # Returns
+/// Tolerance (0-255) for `magic_wand_select_wasm`
+#[wasm_bindgen]
+pub fn magic_wand_auto_tolerance_wasm(image: &[u8], width: usize, height: usize, start_x: usize, start_y: usize) -> u8 {
+    auto_tolerance_impl(image, width, height, start_x, start_y)
+}
+
+/// Distance of every selected pixel to the nearest unselected one.
+///
+/// # Arguments
+/// * `mask` - Selection mask (>= 128 = selected)
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `metric` - "euclidean", "manhattan" or "chessboard"
+///
+/// # Returns
+/// Distance in pixels per pixel (0.0 outside the selection)
+#[wasm_bindgen]
+pub fn distance_transform_wasm(mask: &[u8], width: usize, height: usize, metric: &str) -> Result<Vec<f32>, JsError> {
+    let metric = DistanceMetric::from_name(metric)
+        .ok_or_else(|| JsError::new(&format!("unknown distance metric '{}'", metric)))?;
+    Ok(distance_transform_impl(mask, width, height, metric))
+}
+
+/// Marker-based watershed segmentation.
+///
+/// # Arguments
+/// * `surface` - Levels to flood, e.g. a gradient magnitude or a negated distance transform
+/// * `width` - Surface width
+/// * `height` - Surface height
+/// * `markers` - Seed labels (0 = unlabeled, > 0 = region label)
+/// * `mask` - Mask (>= 128 = flooded); empty to flood everywhere
+///
+/// # Returns
+/// Label per pixel (0 = not reached)
+#[wasm_bindgen]
+pub fn watershed_wasm(surface: &[f32], width: usize, height: usize, markers: &[u32], mask: &[u8]) -> Vec<u32> {
+    let mask = (!mask.is_empty()).then_some(mask);
+    watershed_impl(surface, width, height, markers, mask)
+}
+
+/// Move the boundary of a selection to nearby strong image edges.
+///
+/// # Arguments
+/// * `mask` - Selection mask (>= 128 = selected)
+/// * `image` - RGBA image data (4 bytes per pixel)
+/// * `strength` - How far boundaries may move (0.0-1.0 of 16 pixels)
+///
+/// # Returns
+/// Refined selection mask (255 = selected, 0 = not selected)
+#[wasm_bindgen]
+pub fn snap_mask_to_edges_wasm(mask: &[u8], image: &[u8], width: usize, height: usize, strength: f32) -> Vec<u8> {
+    snap_mask_to_edges_impl(mask, image, width, height, strength)
+}
+
+/// Label the connected regions of a selection mask.
+///
+/// # Arguments
+/// * `threshold` - Values >= threshold count as selected
+/// * `eight_connected` - Whether diagonal neighbors connect regions
+///
+/// # Returns
+/// Label per pixel (0 = background, 1..=N)
+#[wasm_bindgen]
+pub fn label_components_wasm(mask: &[u8], width: usize, height: usize, threshold: u8, eight_connected: bool) -> Vec<u32> {
+    label_components_impl(mask, width, height, threshold, eight_connected).0
+}
+
+/// Drop connected regions of a mask smaller than `min_area` pixels.
+#[wasm_bindgen]
+pub fn remove_small_components_wasm(mask: &[u8], width: usize, height: usize, threshold: u8, min_area: usize) -> Vec<u8> {
+    remove_small_components_impl(mask, width, height, threshold, min_area)
+}
+
+/// [count, coverage, x, y, width, height, cx, cy]; bounds and centroid are 0 when empty.
+fn summary_values(summary: &MaskSummary) -> [f64; 8] {
+    let r = summary.bounds.unwrap_or_default();
+    let (cx, cy) = summary.centroid.unwrap_or_default();
+    [summary.count as f64, summary.coverage as f64, r.x as f64, r.y as f64, r.width as f64, r.height as f64, cx as f64, cy as f64]
+}
+
+/// Pixel count, coverage, bounding box and centroid of a selection mask.
+///
+/// # Arguments
+/// * `mask` - Selection mask
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `threshold` - Values >= threshold count as selected
+///
+/// # Returns
+/// [count, coverage percent, x, y, width, height, cx, cy]
+#[wasm_bindgen]
+pub fn mask_summary_wasm(mask: &[u8], width: usize, height: usize, threshold: u8) -> Vec<f64> {
+    summary_values(&mask_summary_impl(mask, width, height, threshold)).to_vec()
+}
+
+/// Summaries of every region of a label map.
+///
+/// # Returns
+/// Flat [label, count, coverage, x, y, width, height, cx, cy] per label > 0, ascending
+#[wasm_bindgen]
+pub fn label_summaries_wasm(labels: &[u32], width: usize, height: usize) -> Vec<f64> {
+    label_summaries_impl(labels, width, height)
+        .iter()
+        .flat_map(|(label, summary)| std::iter::once(*label as f64).chain(summary_values(summary)))
+        .collect()
+}
+
+// ============================================================================
+// Precise Contour Extraction (Marching Squares + Simplification + Bezier)
+// ============================================================================
+
+/// Extract precise sub-pixel contours from an alpha mask.
+///
+/// Uses Marching Squares algorithm for sub-pixel contour extraction,
+/// Douglas-Peucker for simplification, and optional Bezier curve fitting.
+///
+/// # Arguments
+/// * `mask` - Alpha mask (0-255), flattened row-major
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `threshold` - Alpha threshold (0.0-1.0)
+/// * `simplify_epsilon` - Douglas-Peucker epsilon (0 = no simplification)
+/// * `fit_beziers` - Whether to fit Bezier curves
+/// * `bezier_smoothness` - Bezier smoothness factor (0.1-0.5)
+///
+/// # Returns
+/// Flat array with contour data:
+/// [num_contours,
+///  is_closed_1, num_points_1, x1, y1, x2, y2, ...,
+///  has_beziers_1, (num_beziers, p0x, p0y, p1x, p1y, p2x, p2y, p3x, p3y, ...),
+///  ...]
+#[wasm_bindgen]
+pub fn extract_contours_precise_wasm(
+    mask: &[u8],
+    width: usize,
+    height: usize,
+    threshold: f32,
+    simplify_epsilon: f32,
+    fit_beziers: bool,
+    bezier_smoothness: f32,
+) -> Vec<f32> {
+    let contours = extract_contours_precise_impl(
+        mask,
+        width,
+        height,
+        threshold,
+        simplify_epsilon,
+        fit_beziers,
+        bezier_smoothness,
+    );
+    contours_to_flat(&contours)
+}
+
+/// Extract contours of a huge mask by tracing it downsampled and refining
+/// the points against the full-resolution mask.
+///
+/// # Arguments
+/// * `downsample` - Downsampling factor (1 = same as `extract_contours_precise_wasm`)
+/// * `tolerance` - Largest refinement error in pixels
+///
+/// Other arguments and the returned flat array match
+/// `extract_contours_precise_wasm`.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn extract_contours_downsampled_wasm(
+    mask: &[u8],
+    width: usize,
+    height: usize,
+    threshold: f32,
+    downsample: usize,
+    tolerance: f32,
+    simplify_epsilon: f32,
+    fit_beziers: bool,
+    bezier_smoothness: f32,
+) -> Vec<f32> {
+    let contours = extract_contours_downsampled_impl(
+        mask,
+        width,
+        height,
+        threshold,
+        downsample,
+        tolerance,
+        simplify_epsilon,
+        fit_beziers,
+        bezier_smoothness,
+    );
+    contours_to_flat(&contours)
+}
+
+/// Trace an image into a multi-color SVG document.
+///
+/// # Arguments
+/// * `data` - Image data (RGB or RGBA), flattened row-major
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `channels` - Number of channels (1, 3 or 4)
+/// * `max_colors` - Maximum number of color layers (1-255)
+/// * `simplify_epsilon` - Douglas-Peucker epsilon (0 = no simplification)
+/// * `fit_beziers` - Whether to fit Bezier curves
+///
+/// # Returns
+/// Complete SVG document as string, one stacked path per color
+#[wasm_bindgen]
+pub fn trace_image_to_svg_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    max_colors: usize,
+    simplify_epsilon: f32,
+    fit_beziers: bool,
+) -> String {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    trace_image_to_svg_impl(input, max_colors, simplify_epsilon, fit_beziers)
+}
+
+/// Rasterize contours (including holes) into a mask.
+///
+/// # Arguments
+/// * `contours` - Flat array: [num_contours, len1, x1, y1, x2, y2, ..., len2, ...]
+/// * `width` - Mask width
+/// * `height` - Mask height
+/// * `fill_rule` - "nonzero" or "evenodd"
+///
+/// # Returns
+/// Mask (255 = inside), flattened row-major
+#[wasm_bindgen]
+pub fn fill_contours_wasm(contours: &[f32], width: usize, height: usize, fill_rule: &str) -> Result<Vec<u8>, JsError> {
+    let rule = FillRule::from_name(fill_rule)
+        .ok_or_else(|| JsError::new(&format!("unknown fill rule '{}'", fill_rule)))?;
+    let mut parsed = Vec::new();
+    let mut idx = 1;
+    for _ in 0..contours.first().map_or(0, |&n| n as usize) {
+        let Some(&len) = contours.get(idx) else { break };
+        let len = len as usize;
+        let end = (idx + 1 + len * 2).min(contours.len());
+        let points = contours[idx + 1..end].chunks_exact(2).map(|c| MarchingPoint { x: c[0], y: c[1] }).collect();
+        parsed.push(MarchingContour::new(points, true));
+        idx = end;
+    }
+    Ok(fill_contours_impl(&parsed, width, height, rule))
+}
+
+/// Combine two sets of closed contours with a boolean operation.
+///
+/// # Arguments
+/// * `subject` - Contours in the flat format of `extract_contours_precise_wasm`
+///   (Bezier segments replace the points when present)
+/// * `clip` - Contours of the second operand, same format
+/// * `op` - "union", "intersect" or "subtract" (subject minus clip)
+/// * `fill_rule` - "nonzero" or "evenodd"
+/// * `tolerance` - Bezier flattening tolerance in pixels
+///
+/// # Returns
+/// Closed result polylines in the same flat format (holes reversed)
+#[wasm_bindgen]
+pub fn path_boolean_wasm(subject: &[f32], clip: &[f32], op: &str, fill_rule: &str, tolerance: f32) -> Result<Vec<f32>, JsError> {
+    let path_op = PathOp::from_name(op).ok_or_else(|| JsError::new(&format!("unknown path operation '{}'", op)))?;
+    let rule = FillRule::from_name(fill_rule)
+        .ok_or_else(|| JsError::new(&format!("unknown fill rule '{}'", fill_rule)))?;
+    let result = path_boolean_impl(&contours_from_flat(subject), &contours_from_flat(clip), path_op, rule, tolerance);
+    Ok(contours_to_flat(&result))
+}
+
+/// Convert stroked paths into the filled outlines of their strokes.
+///
+/// # Arguments
+/// * `paths` - Contours in the flat format of `extract_contours_precise_wasm`;
+///   `is_closed` decides between joins and caps at the ends
+/// * `width` - Stroke width in pixels
+/// * `cap` - "butt", "round" or "square"
+/// * `join` - "miter", "round" or "bevel"
+/// * `miter_limit` - Largest miter length over stroke width before beveling
+///
+/// # Returns
+/// Closed outline polylines of all paths in the same flat format
+#[wasm_bindgen]
+pub fn stroke_path_outline_wasm(paths: &[f32], width: f32, cap: &str, join: &str, miter_limit: f32) -> Result<Vec<f32>, JsError> {
+    let line_cap = LineCap::from_name(cap).ok_or_else(|| JsError::new(&format!("unknown line cap '{}'", cap)))?;
+    let line_join = LineJoin::from_name(join).ok_or_else(|| JsError::new(&format!("unknown line join '{}'", join)))?;
+    let outlines: Vec<_> = contours_from_flat(paths)
+        .iter()
+        .flat_map(|path| stroke_path_outline_impl(path, width, line_cap, line_join, miter_limit))
+        .collect();
+    Ok(contours_to_flat(&outlines))
+}
+
+/// Simplify a polyline using the Douglas-Peucker algorithm.
+///
+/// # Arguments
+/// * `points` - Flat array of floats [x1, y1, x2, y2, ...] representing the polyline
+/// * `epsilon` - Maximum distance threshold for simplification (higher = more simplification)
+///
+/// # Returns
+/// Flat array of simplified points [x1, y1, x2, y2, ...]
+#[wasm_bindgen]
+pub fn douglas_peucker_wasm(
+    points: &[f32],
+    epsilon: f32,
+) -> Vec<f32> {
+    // Convert flat array to Point array
+    let pts: Vec<MarchingPoint> = points
+        .chunks(2)
+        .map(|chunk| MarchingPoint { x: chunk[0], y: chunk[1] })
+        .collect();
+
+    let simplified = douglas_peucker_impl(&pts, epsilon);
+
+    // Convert back to flat array
+    simplified.iter().flat_map(|p| vec![p.x, p.y]).collect()
+}
+
+/// Simplify a closed polygon using the Douglas-Peucker algorithm.
+///
+/// This version handles closed polygons by finding the point farthest from
+/// the centroid to use as the starting point, ensuring better results.
+///
+/// # Arguments
+/// * `points` - Flat array of floats [x1, y1, x2, y2, ...] representing the closed polygon
+/// * `epsilon` - Maximum distance threshold for simplification (higher = more simplification)
+///
+/// # Returns
+/// Flat array of simplified points [x1, y1, x2, y2, ...] (closed polygon)
+#[wasm_bindgen]
+pub fn douglas_peucker_closed_wasm(
+    points: &[f32],
+    epsilon: f32,
+) -> Vec<f32> {
+    // Convert flat array to Point array
+    let pts: Vec<MarchingPoint> = points
+        .chunks(2)
+        .map(|chunk| MarchingPoint { x: chunk[0], y: chunk[1] })
+        .collect();
+
+    let simplified = douglas_peucker_closed_impl(&pts, epsilon);
+
+    // Convert back to flat array
+    simplified.iter().flat_map(|p| vec![p.x, p.y]).collect()
+}
+
+/// Split a contour into marching ants dash polylines.
+///
+/// # Arguments
+/// * `points` - Flat array of floats [x1, y1, x2, y2, ...]
+/// * `closed` - Whether the contour is a closed polygon
+/// * `dash_length` - Length of each dash and gap in pixels
+/// * `phase` - Pattern offset in pixels; advance it to animate
+///
+/// # Returns
+/// Flat array: [num_dashes, len1, x1, y1, x2, y2, ..., len2, ...]
+#[wasm_bindgen]
+pub fn contour_to_dash_segments_wasm(points: &[f32], closed: bool, dash_length: f32, phase: f32) -> Vec<f32> {
+    let pts = points.chunks_exact(2).map(|c| MarchingPoint { x: c[0], y: c[1] }).collect();
+    let dashes = contour_to_dash_segments_impl(&MarchingContour::new(pts, closed), dash_length, phase);
+
+    let mut result = vec![dashes.len() as f32];
+    for dash in &dashes {
+        result.push(dash.len() as f32);
+        result.extend(dash.iter().flat_map(|p| [p.x, p.y]));
+    }
+    result
+}
+
+// ============================================================================
+// Sprite Sheets
+// ============================================================================
+
+use crate::sprites::{pack_rects, pack_sprites, slice_grid};
+
+/// Compute sprite placements for an atlas.
+///
+/// # Arguments
+/// * `sizes` - Sprite sizes as [w0, h0, w1, h1, ...]
+/// * `padding` - Empty pixels between neighboring sprites
+/// * `max_size` - Maximum atlas width and height
+///
+/// # Returns
+/// [atlas_width, atlas_height, x0, y0, x1, y1, ...]
+#[wasm_bindgen]
+pub fn pack_sprites_layout_wasm(sizes: &[u32], padding: usize, max_size: usize) -> Result<Vec<u32>, JsError> {
+    let sizes: Vec<(usize, usize)> = sizes.chunks_exact(2).map(|s| (s[0] as usize, s[1] as usize)).collect();
+    let ((width, height), rects) = pack_rects(&sizes, padding, max_size).map_err(|e| JsError::new(&e.to_string()))?;
+    let mut result = vec![width as u32, height as u32];
+    result.extend(rects.iter().flat_map(|r| [r.x as u32, r.y as u32]));
+    Ok(result)
+}
+
+/// Pack sprites into an atlas image (layout as from `pack_sprites_layout_wasm`).
+///
+/// # Arguments
+/// * `data` - Concatenated sprite pixels
+/// * `sizes` - Sprite sizes as [w0, h0, w1, h1, ...]
+/// * `channels` - Channels per pixel (same for all sprites)
+#[wasm_bindgen]
+pub fn pack_sprites_wasm(data: &[u8], sizes: &[u32], channels: usize, padding: usize, max_size: usize) -> Result<Vec<u8>, JsError> {
+    let mut offset = 0;
+    let mut sprites = Vec::with_capacity(sizes.len() / 2);
+    for size in sizes.chunks_exact(2) {
+        let (width, height) = (size[0] as usize, size[1] as usize);
+        let len = width * height * channels;
+        let pixels = data.get(offset..offset + len).expect("Invalid dimensions");
+        sprites.push(Array3::from_shape_vec((height, width, channels), pixels.to_vec()).expect("Invalid dimensions"));
+        offset += len;
+    }
+    let views: Vec<_> = sprites.iter().map(|s| s.view()).collect();
+    let (atlas, _) = pack_sprites(&views, padding, max_size).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(atlas.into_raw_vec_and_offset().0)
+}
+
+/// Cut a sprite sheet into grid cells.
+///
+/// Column `c` spans x from `c * width / cols` to `(c + 1) * width / cols`
+/// (rows likewise).
+///
+/// # Returns
+/// Concatenated cell pixels in row-major cell order
+#[wasm_bindgen]
+pub fn slice_grid_wasm(data: &[u8], width: usize, height: usize, channels: usize, cols: usize, rows: usize) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let cells = slice_grid(input.view(), cols, rows).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(cells.into_iter().flat_map(|cell| cell.into_raw_vec_and_offset().0).collect())
+}
+
+// ============================================================================
+// Painting
+// ============================================================================
+
+use crate::painting::{
+    bucket_fill, bucket_fill_layer, interpolate_stroke, replicate_symmetric_u8, Dab, DabParams, FillSource, PressureCurve,
+    StrokeInterpolator, StrokePoint, SymmetryMode,
+};
+
+/// Placement and symmetry of `replicate_symmetric_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct SymmetryOptions {
+    /// Dab center on the canvas
+    pub x: f32,
+    pub y: f32,
+    /// Canvas size; symmetry is about its center
+    pub canvas_width: usize,
+    pub canvas_height: usize,
+    /// Mirror axes (1 or 2) or number of radial segments
+    pub axes_or_segments: usize,
+}
+
+impl Default for SymmetryOptions {
+    fn default() -> Self {
+        Self { x: 0.0, y: 0.0, canvas_width: 0, canvas_height: 0, axes_or_segments: 1 }
+    }
+}
+
+#[wasm_bindgen]
+impl SymmetryOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Symmetric instances of a dab.
+#[wasm_bindgen]
+pub struct SymmetricDabs {
+    layout: Vec<i32>,
+    pixels: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl SymmetricDabs {
+    /// Flat array [width, height, left, top] per instance, original first.
+    #[wasm_bindgen(getter)]
+    pub fn layout(&self) -> Vec<i32> {
+        self.layout.clone()
+    }
+
+    /// Concatenated instance pixels, in layout order.
+    #[wasm_bindgen(getter)]
+    pub fn pixels(&self) -> Vec<u8> {
+        self.pixels.clone()
+    }
+}
+
+/// Replicate a rendered RGBA dab into its symmetric instances.
+///
+/// # Arguments
+/// * `data` - Dab pixels (RGBA)
+/// * `mode` - "mirror", "radial" or "mandala"
+#[wasm_bindgen]
+pub fn replicate_symmetric_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    mode: &str,
+    options: &SymmetryOptions,
+) -> Result<SymmetricDabs, JsError> {
+    let mode = SymmetryMode::from_name(mode).ok_or_else(|| JsError::new(&format!("unknown symmetry mode '{}'", mode)))?;
+    let image = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let o = options;
+    let instances = replicate_symmetric_u8(&Dab::new(image, o.x, o.y), o.canvas_width, o.canvas_height, mode, o.axes_or_segments);
+    let mut result = SymmetricDabs { layout: Vec::new(), pixels: Vec::new() };
+    for dab in instances {
+        let (h, w, _) = dab.image.dim();
+        let (left, top) = dab.origin();
+        result.layout.extend([w as i32, h as i32, left as i32, top as i32]);
+        result.pixels.extend(dab.image.into_raw_vec_and_offset().0);
+    }
+    Ok(result)
+}
+
+/// Pressure curve from flat control points [in0, out0, in1, out1, ...].
+fn pressure_curve_from_flat(curve: &[f32]) -> PressureCurve {
+    let points: Vec<(f32, f32)> = curve.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    PressureCurve::new(&points)
+}
+
+/// Dabs as a flat array [x, y, pressure, tilt_x, tilt_y, angle] per dab.
+fn dabs_to_flat(dabs: &[DabParams]) -> Vec<f32> {
+    dabs.iter().flat_map(|d| [d.x, d.y, d.pressure, d.tilt_x, d.tilt_y, d.angle]).collect()
+}
+
+/// Resample a whole stroke into evenly spaced dabs.
+///
+/// # Arguments
+/// * `points` - Flat samples [x, y, pressure, tilt_x, tilt_y] per point
+/// * `spacing` - Distance between dabs in pixels
+/// * `curve` - Pressure curve as [in0, out0, in1, out1, ...] (empty = identity)
+///
+/// # Returns
+/// Flat array [x, y, pressure, tilt_x, tilt_y, angle] per dab
+#[wasm_bindgen]
+pub fn interpolate_stroke_wasm(points: &[f32], spacing: f32, curve: &[f32]) -> Vec<f32> {
+    let samples: Vec<StrokePoint> = points
+        .chunks_exact(5)
+        .map(|p| StrokePoint { x: p[0], y: p[1], pressure: p[2], tilt_x: p[3], tilt_y: p[4] })
+        .collect();
+    dabs_to_flat(&interpolate_stroke(&samples, spacing, &pressure_curve_from_flat(curve)))
+}
+
+/// Live stroke resampler for pointer events (see `interpolate_stroke_wasm`).
+#[wasm_bindgen]
+pub struct StrokeSampler {
+    inner: StrokeInterpolator,
+}
+
+#[wasm_bindgen]
+impl StrokeSampler {
+    #[wasm_bindgen(constructor)]
+    pub fn new(spacing: f32, curve: &[f32]) -> StrokeSampler {
+        StrokeSampler { inner: StrokeInterpolator::new(spacing, pressure_curve_from_flat(curve)) }
+    }
+
+    /// Add a pointer sample; returns the completed dabs as
+    /// [x, y, pressure, tilt_x, tilt_y, angle] per dab.
+    pub fn push(&mut self, x: f32, y: f32, pressure: f32, tilt_x: f32, tilt_y: f32) -> Vec<f32> {
+        dabs_to_flat(&self.inner.push(StrokePoint { x, y, pressure, tilt_x, tilt_y }))
+    }
+
+    /// End the stroke; the next sample starts a new one.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Seed and flood fill settings of the bucket fill functions.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct BucketFillOptions {
+    /// Seed pixel
+    pub x: usize,
+    pub y: usize,
+    /// Color tolerance of the flood fill (0-255)
+    pub tolerance: u8,
+    /// Fill only pixels connected to the seed
+    pub contiguous: bool,
+    /// Give edge pixels fractional coverage
+    pub antialias: bool,
+    /// Return the paint as a separate layer instead of the filled image
+    pub as_layer: bool,
+}
+
+impl Default for BucketFillOptions {
+    fn default() -> Self {
+        Self { x: 0, y: 0, tolerance: 32, contiguous: true, antialias: false, as_layer: false }
+    }
+}
+
+#[wasm_bindgen]
+impl BucketFillOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn run_bucket_fill(data: &[u8], width: usize, height: usize, source: &FillSource, o: &BucketFillOptions) -> Vec<u8> {
+    let image = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+    let fill = if o.as_layer { bucket_fill_layer } else { bucket_fill };
+    fill(image.view(), o.x, o.y, source, o.tolerance, o.contiguous, o.antialias).into_raw_vec_and_offset().0
+}
+
+/// Paint bucket with a flat color.
+///
+/// # Arguments
+/// * `data` - RGBA image
+/// * `color` - [r, g, b] or [r, g, b, a] (0-255)
+#[wasm_bindgen]
+pub fn bucket_fill_wasm(data: &[u8], width: usize, height: usize, color: &[u8], options: &BucketFillOptions) -> Result<Vec<u8>, JsError> {
+    let color = match *color {
+        [r, g, b] => [r, g, b, 255],
+        [r, g, b, a] => [r, g, b, a],
+        _ => return Err(JsError::new(&format!("fill color needs 3 or 4 values, got {}", color.len()))),
+    };
+    Ok(run_bucket_fill(data, width, height, &FillSource::Solid(color), options))
+}
+
+/// Paint bucket with a gradient spanning the filled region.
+///
+/// # Arguments
+/// * `data` - RGBA image
+/// * `stops` - Flat gradient stops [pos, r, g, b, ...] (r, g, b 0-255)
+/// * `style` - "linear", "radial", "angle", "reflected" or "diamond"
+/// * `angle` - Angle in degrees (linear and reflected styles)
+#[wasm_bindgen]
+pub fn bucket_fill_gradient_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stops: &[f32],
+    style: &str,
+    angle: f32,
+    options: &BucketFillOptions,
+) -> Vec<u8> {
+    let params = GradientOverlayParams {
+        stops: gradient_overlay::parse_stops(stops, 255.0),
+        style: style.to_string(),
+        angle,
+        ..Default::default()
+    };
+    run_bucket_fill(data, width, height, &FillSource::Gradient(params), options)
+}
+
+/// Paint bucket with an RGBA pattern tiled from the canvas origin.
+#[wasm_bindgen]
+pub fn bucket_fill_pattern_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    pattern: &[u8],
+    pattern_width: usize,
+    pattern_height: usize,
+    options: &BucketFillOptions,
+) -> Vec<u8> {
+    let pattern = Array3::from_shape_vec((pattern_height, pattern_width, 4), pattern.to_vec()).expect("Invalid pattern dimensions");
+    let params = PatternOverlayParams {
+        pattern: pattern.mapv(|v| v as f32 / 255.0),
+        scale: 1.0,
+        offset_x: 0,
+        offset_y: 0,
+        opacity: 1.0,
+        blend_mode: "normal".to_string(),
+    };
+    run_bucket_fill(data, width, height, &FillSource::Pattern(params), options)
+}
+
+// ============================================================================
+// Feature Detection
+// ============================================================================
+
+use crate::analysis::features::{self, Descriptor, Keypoint};
+
+/// Keypoints as a flat array [x0, y0, response0, angle0, x1, ...].
+fn keypoints_to_flat(keypoints: &[Keypoint]) -> Vec<f32> {
+    keypoints.iter().flat_map(|k| [k.x, k.y, k.response, k.angle]).collect()
+}
+
+fn descriptors_from_flat(data: &[u8]) -> Result<Vec<Descriptor>, JsError> {
+    if !data.len().is_multiple_of(32) {
+        return Err(JsError::new(&format!("descriptor data length {} is not a multiple of 32", data.len())));
+    }
+    Ok(data.chunks_exact(32).map(|chunk| std::array::from_fn(|i| chunk[i])).collect())
+}
+
+/// Detect Harris corners (u8).
+///
+/// # Returns
+/// Flat array [x, y, response, angle] per corner, strongest first
+#[wasm_bindgen]
+pub fn harris_corners_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    k: f32,
+    threshold: f32,
+    max_corners: usize,
+) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    keypoints_to_flat(&features::harris_corners_u8(input.view(), k, threshold, max_corners))
+}
+
+/// Detect FAST-9 corners (u8, threshold in 0-255 units).
+///
+/// # Returns
+/// Flat array [x, y, score, angle] per corner, strongest first
+#[wasm_bindgen]
+pub fn fast_corners_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    threshold: f32,
+    nonmax: bool,
+    max_corners: usize,
+) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    keypoints_to_flat(&features::fast_corners_u8(input.view(), threshold, nonmax, max_corners))
+}
+
+/// ORB keypoints and descriptors.
+#[wasm_bindgen]
+pub struct OrbResult {
+    keypoints: Vec<f32>,
+    descriptors: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl OrbResult {
+    /// Flat array [x, y, response, angle] per keypoint.
+    #[wasm_bindgen(getter)]
+    pub fn keypoints(&self) -> Vec<f32> {
+        self.keypoints.clone()
+    }
+
+    /// 32 bytes per keypoint, in keypoint order.
+    #[wasm_bindgen(getter)]
+    pub fn descriptors(&self) -> Vec<u8> {
+        self.descriptors.clone()
+    }
+}
+
+/// Detect ORB features (u8, FAST threshold in 0-255 units).
+#[wasm_bindgen]
+pub fn orb_features_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    max_features: usize,
+    fast_threshold: f32,
+) -> OrbResult {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let result = features::orb_u8(input.view(), max_features, fast_threshold);
+    OrbResult {
+        keypoints: keypoints_to_flat(&result.keypoints),
+        descriptors: result.descriptors.concat(),
+    }
+}
+
+/// Brute-force match descriptors (32 bytes each) by Hamming distance.
+///
+/// # Returns
+/// Flat array [query_index, train_index, distance] per match, best first
+#[wasm_bindgen]
+pub fn match_descriptors_wasm(query: &[u8], train: &[u8], max_distance: u32, cross_check: bool) -> Result<Vec<u32>, JsError> {
+    let (query, train) = (descriptors_from_flat(query)?, descriptors_from_flat(train)?);
+    let matches = features::match_descriptors(&query, &train, max_distance, cross_check);
+    Ok(matches.iter().flat_map(|m| [m.query as u32, m.train as u32, m.distance]).collect())
+}
+
+// ============================================================================
+// Superpixels
+// ============================================================================
+
+use crate::analysis::superpixels;
+
+/// Segment an image into SLIC superpixels (u8).
+///
+/// # Returns
+/// Label per pixel (row-major), labels 0..K, each a connected region
+#[wasm_bindgen]
+pub fn slic_wasm(data: &[u8], width: usize, height: usize, channels: usize, n_segments: usize, compactness: f32) -> Vec<u32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    superpixels::slic_u8(input.view(), n_segments, compactness).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Motion Masks
+// ============================================================================
+
+use crate::analysis::motion;
+
+/// Cleanup settings of `motion_mask_wasm`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct MotionMaskOptions {
+    /// Smallest channel difference (0.0-1.0) counted as motion
+    pub threshold: f32,
+    /// Gaussian sigma applied to the difference
+    pub blur: f32,
+    /// Smallest changed region to keep, in pixels
+    pub min_area: usize,
+}
+
+impl Default for MotionMaskOptions {
+    fn default() -> Self {
+        Self { threshold: 0.1, blur: 1.5, min_area: 64 }
+    }
+}
+
+#[wasm_bindgen]
+impl MotionMaskOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
-            let final_r = orig_r * (1.0 - opacity) + grad_r * opacity;
-            let final_g = orig_g * (1.0 - opacity) + grad_g * opacity;
-            let final_b = orig_b * (1.0 - opacity) + grad_b * opacity;
+/// Binary mask (255 = changed) of regions that differ between two frames (u8).
+#[wasm_bindgen]
+pub fn motion_mask_wasm(
+    frame_a: &[u8],
+    frame_b: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    options: &MotionMaskOptions,
+) -> Vec<u8> {
+    let a = ArrayView3::from_shape((height, width, channels), frame_a).expect("Invalid dimensions");
+    let b = ArrayView3::from_shape((height, width, channels), frame_b).expect("Invalid dimensions");
+    motion::motion_mask_u8(a, b, options.threshold, options.blur, options.min_area).into_raw_vec_and_offset().0
+}
+
+// ============================================================================
+// Optical Flow
+// ============================================================================
+
+use crate::analysis::flow::{self, FlowMethod};
+
+/// Dense optical flow between two frames (u8).
+///
+/// # Arguments
+/// * `method` - "farneback" or "lucas_kanade"
+///
+/// # Returns
+/// Flat (height, width, 2) flow of [dx, dy] per pixel of `prev`
+#[wasm_bindgen]
+pub fn optical_flow_wasm(prev: &[u8], next: &[u8], width: usize, height: usize, channels: usize, method: &str) -> Result<Vec<f32>, JsError> {
+    let method = FlowMethod::from_name(method)
+        .ok_or_else(|| JsError::new(&format!("Unknown flow method '{}'", method)))?;
+    let prev = Array3::from_shape_vec((height, width, channels), prev.to_vec()).expect("Invalid dimensions");
+    let next = Array3::from_shape_vec((height, width, channels), next.to_vec()).expect("Invalid dimensions");
+    Ok(flow::optical_flow_u8(prev.view(), next.view(), method).into_raw_vec_and_offset().0)
+}
 
-            result[[y, x, 0]] = (final_r * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 1]] = (final_g * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 2]] = (final_b * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 3]] = orig_a;
-        }
-    }
+/// Warp an image (u8) along a fraction `t` of a flow field.
+#[wasm_bindgen]
+pub fn warp_by_flow_wasm(data: &[u8], flow_data: &[f32], width: usize, height: usize, channels: usize, t: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let flow = ArrayView3::from_shape((height, width, 2), flow_data).expect("Invalid flow dimensions");
+    flow::warp_by_flow_u8(input.view(), flow, t).into_raw_vec_and_offset().0
+}
 
-    result.into_raw_vec_and_offset().0
+/// In-between frame (u8) at time `t` (0 = prev, 1 = next).
+#[wasm_bindgen]
+pub fn interpolate_frame_wasm(prev: &[u8], next: &[u8], flow_data: &[f32], width: usize, height: usize, channels: usize, t: f32) -> Vec<u8> {
+    let prev = Array3::from_shape_vec((height, width, channels), prev.to_vec()).expect("Invalid dimensions");
+    let next = Array3::from_shape_vec((height, width, channels), next.to_vec()).expect("Invalid dimensions");
+    let flow = ArrayView3::from_shape((height, width, 2), flow_data).expect("Invalid flow dimensions");
+    flow::interpolate_frame_u8(prev.view(), next.view(), flow, t).into_raw_vec_and_offset().0
 }
 
 // ============================================================================
-// Filters: Gradient Generator
+// Palettes
 // ============================================================================
 
-/// Generate a gradient surface as RGBA u8 image (no input image needed).
+use crate::analysis::palette;
+
+/// Extract the dominant colors of an image with k-means (u8).
 ///
-/// Gradient stops: [pos, r, g, b, ...] where r,g,b are 0-255.
+/// # Returns
+/// Flat [r, g, b, weight, ...] per color (colors 0-255, weight 0.0-1.0), largest weight first
 #[wasm_bindgen]
-pub fn generate_gradient_wasm(
-    width: usize,
-    height: usize,
-    stops: &[f32],
-    style: &str,
-    angle: f32,
-    scale_x: f32,
-    scale_y: f32,
-    offset_x: f32,
-    offset_y: f32,
-    reverse: bool,
-) -> Vec<u8> {
-    struct GradientStop { position: f32, r: f32, g: f32, b: f32 }
-
-    let mut gradient_stops: Vec<GradientStop> = stops.chunks(4)
-        .filter(|c| c.len() == 4)
-        .map(|c| GradientStop { position: c[0], r: c[1] / 255.0, g: c[2] / 255.0, b: c[3] / 255.0 })
-        .collect();
+pub fn extract_palette_wasm(data: &[u8], width: usize, height: usize, channels: usize, k: usize, max_iterations: usize, seed: u32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    palette::extract_palette(input.view(), k, max_iterations, seed as u64)
+        .iter()
+        .flat_map(|p| [p.color[0] as f32, p.color[1] as f32, p.color[2] as f32, p.weight])
+        .collect()
+}
 
-    gradient_stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(std::cmp::Ordering::Equal));
+/// Replace every pixel with its nearest palette color (u8).
+///
+/// `colors` is a flat [r, g, b, ...] list.
+#[wasm_bindgen]
+pub fn posterize_to_palette_wasm(data: &[u8], width: usize, height: usize, channels: usize, colors: &[u8], dither: bool) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let colors: Vec<[u8; 3]> = colors.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect();
+    palette::posterize_to_palette(input.view(), &colors, dither).into_raw_vec_and_offset().0
+}
 
-    if gradient_stops.is_empty() {
-        gradient_stops.push(GradientStop { position: 0.0, r: 0.0, g: 0.0, b: 0.0 });
-        gradient_stops.push(GradientStop { position: 1.0, r: 1.0, g: 1.0, b: 1.0 });
-    }
+// ============================================================================
+// Skin Masks
+// ============================================================================
 
-    let mut result = Array3::<u8>::zeros((height, width, 4));
+use crate::analysis::skin;
 
-    // Center with offset
-    let cx = width as f32 / 2.0 + offset_x * width as f32 / 2.0;
-    let cy = height as f32 / 2.0 + offset_y * height as f32 / 2.0;
-    let sx = if scale_x.abs() > 0.001 { scale_x } else { 0.001 };
-    let sy = if scale_y.abs() > 0.001 { scale_y } else { 0.001 };
-    let half_w = width as f32 / 2.0;
-    let half_h = height as f32 / 2.0;
+/// Soft mask of likely skin tones (u8).
+///
+/// # Returns
+/// Mask value per pixel (row-major), 255 = skin
+#[wasm_bindgen]
+pub fn skin_mask_wasm(data: &[u8], width: usize, height: usize, channels: usize, smoothing: f32) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    skin::skin_mask_u8(input.view(), smoothing).into_raw_vec_and_offset().0
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let px = x as f32;
-            let py = y as f32;
-            let mut t = match style {
-                "linear" => {
-                    let angle_rad = angle.to_radians();
-                    let dx = angle_rad.cos();
-                    let dy = -angle_rad.sin();
-                    let rx = (px - cx) / sx;
-                    let ry = (py - cy) / sy;
-                    let proj = rx * dx + ry * dy;
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    (proj / max_dist + 1.0) / 2.0
-                }
-                "radial" => {
-                    let dx = (px - cx) / sx;
-                    let dy = (py - cy) / sy;
-                    let dist = (dx * dx + dy * dy).sqrt();
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    dist / max_dist
-                }
-                "angle" => {
-                    let dx = (px - cx) / sx;
-                    let dy = (py - cy) / sy;
-                    let mut angle_at_pixel = dy.atan2(dx);
-                    angle_at_pixel -= angle.to_radians();
-                    (angle_at_pixel + std::f32::consts::PI) / (2.0 * std::f32::consts::PI)
-                }
-                "reflected" => {
-                    let angle_rad = angle.to_radians();
-                    let dx = angle_rad.cos();
-                    let dy = -angle_rad.sin();
-                    let rx = (px - cx) / sx;
-                    let ry = (py - cy) / sy;
-                    let proj = rx * dx + ry * dy;
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    let linear_t = (proj / max_dist + 1.0) / 2.0;
-                    (2.0 * (linear_t - 0.5)).abs()
-                }
-                "diamond" => {
-                    let dx = ((px - cx) / sx).abs();
-                    let dy = ((py - cy) / sy).abs();
-                    (dx + dy) / (half_w + half_h)
-                }
-                _ => {
-                    let angle_rad = angle.to_radians();
-                    let dx = angle_rad.cos();
-                    let dy = -angle_rad.sin();
-                    let rx = (px - cx) / sx;
-                    let ry = (py - cy) / sy;
-                    let proj = rx * dx + ry * dy;
-                    let max_dist = (half_w * half_w + half_h * half_h).sqrt();
-                    (proj / max_dist + 1.0) / 2.0
-                }
-            };
+/// Soft mask of likely skin tones (f32).
+#[wasm_bindgen]
+pub fn skin_mask_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, smoothing: f32) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    skin::skin_mask_f32(input.view(), smoothing).into_raw_vec_and_offset().0
+}
 
-            t = t.clamp(0.0, 1.0);
-            if reverse { t = 1.0 - t; }
+// ============================================================================
+// Noise Estimation
+// ============================================================================
 
-            // Interpolate gradient color
-            let (grad_r, grad_g, grad_b) = {
-                let mut prev_idx = 0;
-                let mut next_idx = gradient_stops.len() - 1;
-                for (i, stop) in gradient_stops.iter().enumerate() {
-                    if stop.position <= t { prev_idx = i; }
-                    if stop.position >= t && i > prev_idx { next_idx = i; break; }
-                }
-                let prev = &gradient_stops[prev_idx];
-                let next = &gradient_stops[next_idx];
-                if (next.position - prev.position).abs() < 0.0001 {
-                    (prev.r, prev.g, prev.b)
-                } else {
-                    let local_t = ((t - prev.position) / (next.position - prev.position)).clamp(0.0, 1.0);
-                    (prev.r + (next.r - prev.r) * local_t, prev.g + (next.g - prev.g) * local_t, prev.b + (next.b - prev.b) * local_t)
-                }
-            };
+use crate::analysis::noise as noise_estimate;
 
-            result[[y, x, 0]] = (grad_r * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 1]] = (grad_g * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 2]] = (grad_b * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 3]] = 255;
-        }
-    }
+/// Estimate the noise standard deviation of an image (u8).
+///
+/// # Returns
+/// [sigma per color channel..., luminance sigma] in 0-255 code values
+#[wasm_bindgen]
+pub fn estimate_noise_sigma_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let estimate = noise_estimate::estimate_noise_sigma_u8(input.view());
+    estimate.channels.into_iter().chain([estimate.luminance]).collect()
+}
 
-    result.into_raw_vec_and_offset().0
+/// Estimate the noise standard deviation of an image (f32).
+///
+/// # Returns
+/// [sigma per color channel..., luminance sigma] in 0.0-1.0 value units
+#[wasm_bindgen]
+pub fn estimate_noise_sigma_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let estimate = noise_estimate::estimate_noise_sigma_f32(input.view());
+    estimate.channels.into_iter().chain([estimate.luminance]).collect()
 }
 
 // ============================================================================
-// Layer Effects: Pattern Overlay
+// Focus Measures
 // ============================================================================
 
-/// Sample a pixel from a pattern with tiling (modulo wrapping).
-#[inline]
-fn sample_pattern_tiled(
-    pattern: &Array3<f32>,
-    x: isize,
-    y: isize,
-    pattern_w: usize,
-    pattern_h: usize,
-) -> (f32, f32, f32, f32) {
-    let px = ((x % pattern_w as isize) + pattern_w as isize) as usize % pattern_w;
-    let py = ((y % pattern_h as isize) + pattern_h as isize) as usize % pattern_h;
-    (
-        pattern[[py, px, 0]],
-        pattern[[py, px, 1]],
-        pattern[[py, px, 2]],
-        pattern[[py, px, 3]],
-    )
+use crate::analysis::sharpness;
+
+/// Estimate how sharp an image is (u8): variance of the Laplacian, lower = blurrier.
+#[wasm_bindgen]
+pub fn estimate_blur_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> f32 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    sharpness::estimate_blur_u8(input.view())
 }
 
-/// Bilinear interpolation for scaled pattern sampling.
-fn sample_pattern_bilinear(
-    pattern: &Array3<f32>,
-    x: f32,
-    y: f32,
-    pattern_w: usize,
-    pattern_h: usize,
-) -> (f32, f32, f32, f32) {
-    let x0 = x.floor() as isize;
-    let y0 = y.floor() as isize;
-    let x1 = x0 + 1;
-    let y1 = y0 + 1;
+/// Estimate how sharp an image is (f32).
+#[wasm_bindgen]
+pub fn estimate_blur_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> f32 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    sharpness::estimate_blur_f32(input.view())
+}
 
-    let fx = x - x.floor();
-    let fy = y - y.floor();
+/// Sharpness heatmap, one value per block (u8).
+///
+/// # Returns
+/// Row-major map of ceil(height / block_size) x ceil(width / block_size) values
+#[wasm_bindgen]
+pub fn sharpness_map_wasm(data: &[u8], width: usize, height: usize, channels: usize, block_size: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    sharpness::sharpness_map_u8(input.view(), block_size).into_raw_vec_and_offset().0
+}
 
-    let (r00, g00, b00, a00) = sample_pattern_tiled(pattern, x0, y0, pattern_w, pattern_h);
-    let (r10, g10, b10, a10) = sample_pattern_tiled(pattern, x1, y0, pattern_w, pattern_h);
-    let (r01, g01, b01, a01) = sample_pattern_tiled(pattern, x0, y1, pattern_w, pattern_h);
-    let (r11, g11, b11, a11) = sample_pattern_tiled(pattern, x1, y1, pattern_w, pattern_h);
+/// Sharpness heatmap, one value per block (f32).
+#[wasm_bindgen]
+pub fn sharpness_map_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, block_size: usize) -> Vec<f32> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    sharpness::sharpness_map_f32(input.view(), block_size).into_raw_vec_and_offset().0
+}
 
-    // Bilinear interpolation
-    let r = r00 * (1.0 - fx) * (1.0 - fy) + r10 * fx * (1.0 - fy) + r01 * (1.0 - fx) * fy + r11 * fx * fy;
-    let g = g00 * (1.0 - fx) * (1.0 - fy) + g10 * fx * (1.0 - fy) + g01 * (1.0 - fx) * fy + g11 * fx * fy;
-    let b = b00 * (1.0 - fx) * (1.0 - fy) + b10 * fx * (1.0 - fy) + b01 * (1.0 - fx) * fy + b11 * fx * fy;
-    let a = a00 * (1.0 - fx) * (1.0 - fy) + a10 * fx * (1.0 - fy) + a01 * (1.0 - fx) * fy + a11 * fx * fy;
+// ============================================================================
+// Perceptual Hashes
+// ============================================================================
 
-    (r, g, b, a)
-}
+use crate::analysis::hash;
 
-/// Apply pattern overlay to RGBA u8 image.
-///
-/// Pattern is tiled across the image, scaled and offset as specified.
-/// Uses bilinear interpolation when scale != 1.0 for smooth scaling.
+/// Average hash (u8) as a 64-bit integer (BigInt in JS).
 #[wasm_bindgen]
-pub fn pattern_overlay_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    pattern_data: &[u8],
-    pattern_width: usize,
-    pattern_height: usize,
-    scale: f32,
-    offset_x: i32,
-    offset_y: i32,
-    opacity: f32,
-) -> Vec<u8> {
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
-    let pattern = Array3::from_shape_vec((pattern_height, pattern_width, 4), pattern_data.to_vec()).expect("Invalid pattern dimensions");
-
-    // Convert pattern to f32
-    let mut pattern_f32 = Array3::<f32>::zeros((pattern_height, pattern_width, 4));
-    for y in 0..pattern_height {
-        for x in 0..pattern_width {
-            for c in 0..4 {
-                pattern_f32[[y, x, c]] = pattern[[y, x, c]] as f32 / 255.0;
-            }
-        }
-    }
+pub fn ahash_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> u64 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    hash::ahash_u8(input.view())
+}
 
-    let mut result = Array3::<u8>::zeros((height, width, 4));
-    let effective_scale = scale.clamp(0.01, 100.0);
+/// Average hash (f32).
+#[wasm_bindgen]
+pub fn ahash_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> u64 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    hash::ahash_f32(input.view())
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input[[y, x, 3]];
-            if orig_a == 0 { continue; }
-
-            // Calculate pattern coordinates with scale and offset
-            let px = (x as f32 / effective_scale) + offset_x as f32;
-            let py = (y as f32 / effective_scale) + offset_y as f32;
-
-            // Sample pattern (with bilinear interpolation for smooth scaling)
-            let (pat_r, pat_g, pat_b, pat_a) = if effective_scale == 1.0 {
-                sample_pattern_tiled(&pattern_f32, px.round() as isize, py.round() as isize, pattern_width, pattern_height)
-            } else {
-                sample_pattern_bilinear(&pattern_f32, px, py, pattern_width, pattern_height)
-            };
+/// Difference hash (u8) as a 64-bit integer (BigInt in JS).
+#[wasm_bindgen]
+pub fn dhash_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> u64 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    hash::dhash_u8(input.view())
+}
 
-            let blend_a = opacity * pat_a;
-            let orig_r = input[[y, x, 0]] as f32 / 255.0;
-            let orig_g = input[[y, x, 1]] as f32 / 255.0;
-            let orig_b = input[[y, x, 2]] as f32 / 255.0;
+/// Difference hash (f32).
+#[wasm_bindgen]
+pub fn dhash_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> u64 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    hash::dhash_f32(input.view())
+}
 
-            let final_r = orig_r * (1.0 - blend_a) + pat_r * blend_a;
-            let final_g = orig_g * (1.0 - blend_a) + pat_g * blend_a;
-            let final_b = orig_b * (1.0 - blend_a) + pat_b * blend_a;
+/// Perceptual (DCT) hash (u8) as a 64-bit integer (BigInt in JS).
+#[wasm_bindgen]
+pub fn phash_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> u64 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    hash::phash_u8(input.view())
+}
 
-            result[[y, x, 0]] = (final_r * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 1]] = (final_g * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 2]] = (final_b * 255.0).clamp(0.0, 255.0) as u8;
-            result[[y, x, 3]] = orig_a;
-        }
-    }
+/// Perceptual (DCT) hash (f32).
+#[wasm_bindgen]
+pub fn phash_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize) -> u64 {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    hash::phash_f32(input.view())
+}
 
-    result.into_raw_vec_and_offset().0
+/// Number of differing bits between two 64-bit hashes.
+#[wasm_bindgen]
+pub fn hamming_distance_wasm(a: u64, b: u64) -> u32 {
+    hash::hamming_distance(a, b)
 }
 
 // ============================================================================
-// Layer Effects: Drop Shadow
+// Text Contrast
 // ============================================================================
 
-/// Apply drop shadow effect to RGBA u8 image.
+use crate::analysis::contrast;
+
+/// WCAG contrast of a text color against every block (u8).
 ///
-/// Creates a shadow cast behind the layer by blurring and offsetting the alpha.
+/// # Arguments
+/// * `text_color` - Text color [r, g, b], 0-255
+/// * `block_size` - Cell size in pixels (1 = per pixel)
+/// * `large_text` - Use the large text thresholds
+///
+/// # Returns
+/// Row-major ceil(height / block_size) x ceil(width / block_size) ratios
 #[wasm_bindgen]
-pub fn drop_shadow_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    offset_x: f32,
-    offset_y: f32,
-    blur_radius: f32,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-) -> Vec<u8> {
-    use ndarray::Array2;
-
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
-
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
+pub fn contrast_ratio_map_wasm(data: &[u8], width: usize, height: usize, channels: usize, text_color: &[u8], block_size: usize, large_text: bool) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let text = [text_color[0], text_color[1], text_color[2]];
+    contrast::contrast_ratio_map_u8(input, text, block_size, large_text).ratios.into_raw_vec_and_offset().0
+}
 
-    // Calculate expansion
-    let blur_expand = (blur_radius * 3.0).ceil() as usize;
-    let offset_expand = offset_x.abs().max(offset_y.abs()).ceil() as usize;
-    let required_expand = blur_expand + offset_expand + 2;
+/// WCAG contrast of a text color against every block (f32).
+#[wasm_bindgen]
+pub fn contrast_ratio_map_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, text_color: &[f32], block_size: usize, large_text: bool) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let text = [text_color[0], text_color[1], text_color[2]];
+    contrast::contrast_ratio_map_f32(input, text, block_size, large_text).ratios.into_raw_vec_and_offset().0
+}
 
-    // Expand canvas
-    let expanded = expand_canvas_f32(&input_f32, required_expand);
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+/// WCAG pass levels for contrast ratios (0 = fail, 1 = AA, 2 = AAA).
+#[wasm_bindgen]
+pub fn wcag_levels_wasm(ratios: &[f32], large_text: bool) -> Vec<u8> {
+    ratios.iter().map(|&r| contrast::wcag_level(r, large_text)).collect()
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
+// ============================================================================
+// QR Codes
+// ============================================================================
 
-    // Blur alpha
-    let blurred_alpha = blur_alpha_f32(&alpha, blur_radius);
-
-    // Create result with shadow
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-    let shadow_r = color_r as f32 / 255.0;
-    let shadow_g = color_g as f32 / 255.0;
-    let shadow_b = color_b as f32 / 255.0;
-
-    let ox = offset_x.round() as isize;
-    let oy = offset_y.round() as isize;
-
-    // Draw shadow
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let sx = (x as isize - ox).clamp(0, new_w as isize - 1) as usize;
-            let sy = (y as isize - oy).clamp(0, new_h as isize - 1) as usize;
-            let shadow_a = blurred_alpha[[sy, sx]] * opacity;
-            result[[y, x, 0]] = shadow_r;
-            result[[y, x, 1]] = shadow_g;
-            result[[y, x, 2]] = shadow_b;
-            result[[y, x, 3]] = shadow_a;
-        }
-    }
+use crate::generate::qr::{self, QrErrorCorrection, QrStyle};
+
+/// Colors, error correction and module shape for `render_qr_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct QrOptions {
+    pub foreground_r: u8,
+    pub foreground_g: u8,
+    pub foreground_b: u8,
+    pub foreground_a: u8,
+    pub background_r: u8,
+    pub background_g: u8,
+    pub background_b: u8,
+    pub background_a: u8,
+    /// "low", "medium", "quartile" or "high"
+    pub error_correction: String,
+    /// Corner radius as a fraction of the module (0 = squares, 0.5 = dots)
+    pub module_radius: f32,
+    /// Centered logo knockout width as a fraction of the symbol (0 = none)
+    pub logo_size: f32,
+}
 
-    // Composite original on top
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let src_a = expanded[[y, x, 3]];
-            if src_a <= 0.0 { continue; }
-            let dst_a = result[[y, x, 3]];
-            let out_a = src_a + dst_a * (1.0 - src_a);
-            if out_a > 0.0 {
-                result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 3]] = out_a;
-            }
+impl Default for QrOptions {
+    fn default() -> Self {
+        Self {
+            foreground_r: 0,
+            foreground_g: 0,
+            foreground_b: 0,
+            foreground_a: 255,
+            background_r: 255,
+            background_g: 255,
+            background_b: 255,
+            background_a: 255,
+            error_correction: "medium".to_string(),
+            module_radius: 0.0,
+            logo_size: 0.0,
         }
     }
-
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
 }
 
-// ============================================================================
-// Layer Effects: Inner Shadow
-// ============================================================================
+#[wasm_bindgen]
+impl QrOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
-/// Apply inner shadow effect to RGBA u8 image.
+/// Render data (UTF-8 text or binary) as a QR code.
 ///
-/// Creates a shadow inside the shape by inverting and blurring alpha.
+/// # Arguments
+/// * `data` - Payload bytes
+/// * `size` - Output width and height in pixels
+/// * `margin` - Quiet zone in modules
+///
+/// # Returns
+/// RGBA pixels (size x size x 4)
 #[wasm_bindgen]
-pub fn inner_shadow_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    offset_x: f32,
-    offset_y: f32,
-    blur_radius: f32,
-    choke: f32,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-) -> Vec<u8> {
-    use ndarray::Array2;
+pub fn render_qr_wasm(data: &[u8], size: usize, margin: usize, options: &QrOptions) -> Result<Vec<u8>, JsError> {
+    let o = options;
+    let error_correction = QrErrorCorrection::from_name(&o.error_correction)
+        .ok_or_else(|| JsError::new(&format!("unknown error correction level '{}'", o.error_correction)))?;
+    let style = QrStyle {
+        foreground: [o.foreground_r, o.foreground_g, o.foreground_b, o.foreground_a],
+        background: [o.background_r, o.background_g, o.background_b, o.background_a],
+        error_correction,
+        module_radius: o.module_radius,
+        logo_size: o.logo_size,
+    };
+    let image = qr::render_qr(data, size, margin, &style).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(image.into_raw_vec_and_offset().0)
+}
 
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+// ============================================================================
+// Color Sampling
+// ============================================================================
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
+use crate::analysis::stats::{self as color_stats, SampleMode};
+use crate::batch::region::Rect;
+
+/// Eyedropper window for `sample_color_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct SampleOptions {
+    /// Window radius (1 = 3x3, 2 = 5x5)
+    pub radius: usize,
+    /// "point", "square" or "circle"
+    pub mode: String,
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
+impl Default for SampleOptions {
+    fn default() -> Self {
+        Self { radius: 1, mode: "point".to_string() }
     }
+}
 
-    // Invert alpha
-    let mut inverted = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            inverted[[y, x]] = 1.0 - alpha[[y, x]];
-        }
+#[wasm_bindgen]
+impl SampleOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
     }
+}
 
-    // Apply choke
-    let choke_radius = blur_radius * choke;
-    let choked = if choke_radius > 0.0 {
-        dilate_alpha(&inverted, choke_radius)
-    } else {
-        inverted
-    };
-
-    // Blur
-    let blurred = blur_alpha_f32(&choked, blur_radius);
+/// Optional rectangle from [x, y, width, height]; empty = whole image.
+fn sample_rect(rect: &[usize]) -> Option<Rect> {
+    (rect.len() >= 4).then(|| Rect::new(rect[0], rect[1], rect[2], rect[3]))
+}
 
-    // Create result
-    let mut result = input_f32.clone();
-    let shadow_r = color_r as f32 / 255.0;
-    let shadow_g = color_g as f32 / 255.0;
-    let shadow_b = color_b as f32 / 255.0;
+/// Alpha-weighted average color of a region (u8).
+///
+/// # Arguments
+/// * `mask` - Selection (width * height) weighting each pixel; empty = none
+/// * `rect` - [x, y, width, height]; empty = whole image
+///
+/// # Returns
+/// Mean per channel; empty if nothing is selected
+#[wasm_bindgen]
+pub fn average_color_wasm(data: &[u8], width: usize, height: usize, channels: usize, mask: &[u8], rect: &[usize]) -> Vec<u8> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    color_stats::average_color_u8(input, sample_rect(rect), optional_mask(mask, width, height)).unwrap_or_default()
+}
 
-    let ox = offset_x.round() as isize;
-    let oy = offset_y.round() as isize;
+/// Alpha-weighted average color of a region (f32).
+#[wasm_bindgen]
+pub fn average_color_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mask: &[u8], rect: &[usize]) -> Vec<f32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    color_stats::average_color_f32(input, sample_rect(rect), optional_mask(mask, width, height)).unwrap_or_default()
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = alpha[[y, x]];
-            if orig_a <= 0.0 { continue; }
-
-            let sx = (x as isize - ox).clamp(0, width as isize - 1) as usize;
-            let sy = (y as isize - oy).clamp(0, height as isize - 1) as usize;
-            let shadow_a = blurred[[sy, sx]] * opacity * orig_a;
-
-            if shadow_a > 0.0 {
-                let out_a = shadow_a + result[[y, x, 3]] * (1.0 - shadow_a);
-                if out_a > 0.0 {
-                    result[[y, x, 0]] = (shadow_r * shadow_a + result[[y, x, 0]] * result[[y, x, 3]] * (1.0 - shadow_a)) / out_a;
-                    result[[y, x, 1]] = (shadow_g * shadow_a + result[[y, x, 1]] * result[[y, x, 3]] * (1.0 - shadow_a)) / out_a;
-                    result[[y, x, 2]] = (shadow_b * shadow_a + result[[y, x, 2]] * result[[y, x, 3]] * (1.0 - shadow_a)) / out_a;
-                    result[[y, x, 3]] = out_a;
-                }
-            }
-        }
-    }
+/// Eyedropper color at a pixel (u8).
+///
+/// # Returns
+/// Mean per channel; empty outside the image
+#[wasm_bindgen]
+pub fn sample_color_wasm(data: &[u8], width: usize, height: usize, channels: usize, x: usize, y: usize, options: &SampleOptions) -> Result<Vec<u8>, JsError> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let mode = SampleMode::from_name(&options.mode)
+        .ok_or_else(|| JsError::new(&format!("unknown sample mode '{}'", options.mode)))?;
+    Ok(color_stats::sample_color_u8(input, x, y, options.radius, mode).unwrap_or_default())
+}
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
+/// Eyedropper color at a pixel (f32).
+#[wasm_bindgen]
+pub fn sample_color_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, x: usize, y: usize, options: &SampleOptions) -> Result<Vec<f32>, JsError> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let mode = SampleMode::from_name(&options.mode)
+        .ok_or_else(|| JsError::new(&format!("unknown sample mode '{}'", options.mode)))?;
+    Ok(color_stats::sample_color_f32(input, x, y, options.radius, mode).unwrap_or_default())
 }
 
 // ============================================================================
-// Layer Effects: Outer Glow
+// Masked Histograms and Statistics
 // ============================================================================
 
-/// Apply outer glow effect to RGBA u8 image.
+/// Optional mask view; empty = no mask.
+fn optional_mask(mask: &[u8], width: usize, height: usize) -> Option<ArrayView2<'_, u8>> {
+    (!mask.is_empty()).then(|| ArrayView2::from_shape((height, width), mask).expect("Invalid mask dimensions"))
+}
+
+/// [count, mean, std, median, min, max] per channel.
+fn stats_values(stats: &[color_stats::ChannelStats]) -> Vec<f64> {
+    stats
+        .iter()
+        .flat_map(|s| [s.count as f64, s.mean as f64, s.std_dev as f64, s.median as f64, s.min as f64, s.max as f64])
+        .collect()
+}
+
+/// Per-channel histogram of the selected pixels (u8).
 ///
-/// Creates a glow effect outside the shape edges.
+/// # Arguments
+/// * `mask` - Selection (width * height, >= 128 = selected); empty = whole image
+/// * `bins` - Number of bins 1-256
+///
+/// # Returns
+/// Row-major (color channels, bins) counts
 #[wasm_bindgen]
-pub fn outer_glow_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    radius: f32,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-    spread: f32,
-) -> Vec<u8> {
-    use ndarray::Array2;
+pub fn histogram_masked_wasm(data: &[u8], width: usize, height: usize, channels: usize, mask: &[u8], bins: usize) -> Vec<u32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    color_stats::histogram_masked_u8(input, optional_mask(mask, width, height), bins).into_raw_vec_and_offset().0
+}
 
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+/// Per-channel histogram over 0.0-1.0 of the selected pixels (f32).
+#[wasm_bindgen]
+pub fn histogram_masked_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mask: &[u8], bins: usize) -> Vec<u32> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    color_stats::histogram_masked_f32(input, optional_mask(mask, width, height), bins).into_raw_vec_and_offset().0
+}
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
+/// Statistics per channel of the selected pixels (u8).
+///
+/// # Returns
+/// Flat [count, mean, std, median, min, max] per color channel
+#[wasm_bindgen]
+pub fn stats_masked_wasm(data: &[u8], width: usize, height: usize, channels: usize, mask: &[u8]) -> Vec<f64> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    stats_values(&color_stats::stats_masked_u8(input, optional_mask(mask, width, height)))
+}
 
-    // Expand canvas
-    let required_expand = (radius * 3.0).ceil() as usize + 2;
-    let expanded = expand_canvas_f32(&input_f32, required_expand);
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+/// Statistics per channel of the selected pixels (f32).
+#[wasm_bindgen]
+pub fn stats_masked_f32_wasm(data: &[f32], width: usize, height: usize, channels: usize, mask: &[u8]) -> Vec<f64> {
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    stats_values(&color_stats::stats_masked_f32(input, optional_mask(mask, width, height)))
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
+// ============================================================================
+// Filter Context
+// ============================================================================
 
-    // Apply spread (dilate before blur)
-    let spread_radius = radius * spread;
-    let spread_alpha = if spread_radius > 0.0 {
-        dilate_alpha(&alpha, spread_radius)
-    } else {
-        alpha.clone()
-    };
+use crate::batch::{parse_pipeline, parse_pipeline_or_recipe, FilterContext};
 
-    // Blur
-    let blurred = blur_alpha_f32(&spread_alpha, radius);
-
-    // Create result
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-    let glow_r = color_r as f32 / 255.0;
-    let glow_g = color_g as f32 / 255.0;
-    let glow_b = color_b as f32 / 255.0;
-
-    // Draw glow (outside only)
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let glow_a = (blurred[[y, x]] - alpha[[y, x]]).max(0.0) * opacity;
-            if glow_a > 0.0 {
-                result[[y, x, 0]] = glow_r;
-                result[[y, x, 1]] = glow_g;
-                result[[y, x, 2]] = glow_b;
-                result[[y, x, 3]] = glow_a;
-            }
-        }
-    }
+/// Reusable filter pipeline for video frames and animation previews.
+///
+/// Lookup tables, blur kernels and scratch buffers are kept between calls,
+/// so frames of the same size are filtered without reallocating.
+#[wasm_bindgen]
+pub struct FilterContextHandle {
+    context: FilterContext,
+}
 
-    // Composite original on top
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let src_a = expanded[[y, x, 3]];
-            if src_a <= 0.0 { continue; }
-            let dst_a = result[[y, x, 3]];
-            let out_a = src_a + dst_a * (1.0 - src_a);
-            if out_a > 0.0 {
-                result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                result[[y, x, 3]] = out_a;
-            }
-        }
+#[wasm_bindgen]
+impl FilterContextHandle {
+    /// Create a context from a pipeline string (e.g. "brightness 0.1 | blur 2")
+    /// or a recipe JSON.
+    #[wasm_bindgen(constructor)]
+    pub fn new(spec: &str) -> Result<FilterContextHandle, JsError> {
+        let pipeline = parse_pipeline_or_recipe(spec).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(FilterContextHandle { context: FilterContext::new(pipeline) })
     }
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
+    /// Filter one u8 frame.
+    ///
+    /// # Arguments
+    /// * `data` - Flat array of bytes (length = width * height * channels)
+    /// * `width` - Frame width in pixels
+    /// * `height` - Frame height in pixels
+    /// * `channels` - Number of channels (1, 3, or 4)
+    ///
+    /// # Returns
+    /// Flat array with the filtered frame
+    pub fn process(&mut self, data: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+        let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+        self.context.process_u8(input).iter().copied().collect()
+    }
 }
 
 // ============================================================================
-// Layer Effects: Inner Glow
+// Filter Registry
 // ============================================================================
 
-/// Apply inner glow effect to RGBA u8 image.
+use crate::batch::{apply_filter_f32, apply_filter_u8, registry_json};
+
+/// Describe every filter available to `apply_filter_wasm`.
 ///
-/// Creates a glow effect inside the shape edges.
+/// # Returns
+/// JSON array of `{"name", "description", "params": [{"name", "type", "min", "max", "default"}]}`
 #[wasm_bindgen]
-pub fn inner_glow_rgba_wasm(
+pub fn filter_registry_wasm() -> String {
+    registry_json()
+}
+
+/// Plan a pipeline or recipe without running it.
+///
+/// # Arguments
+/// * `spec` - Pipeline string or recipe JSON
+/// * `float32` - Plan for f32 images instead of u8
+///
+/// # Returns
+/// JSON with the input and output shapes, `output_bytes`, `peak_bytes`,
+/// `cost` and the shape, memory and cost of every step
+#[wasm_bindgen]
+pub fn plan_operation_wasm(spec: &str, width: usize, height: usize, channels: usize, float32: bool) -> Result<String, JsError> {
+    let pipeline = parse_pipeline_or_recipe(spec).map_err(|e| JsError::new(&e.to_string()))?;
+    let sample_bytes = if float32 { 4 } else { 1 };
+    Ok(crate::batch::plan_pipeline(&pipeline, (height, width, channels), sample_bytes).to_json())
+}
+
+/// Whether this module was built with the `deterministic` feature, i.e.
+/// the core filters match a deterministic native build bit for bit.
+#[wasm_bindgen]
+pub fn is_deterministic_wasm() -> bool {
+    crate::filters::fmath::DETERMINISTIC
+}
+
+/// Free the buffers layer effects keep for reuse, e.g. when an editing
+/// session ends; later effect calls allocate again as needed.
+#[wasm_bindgen]
+pub fn release_buffer_pool_wasm() {
+    crate::filters::pool::release_all();
+}
+
+/// Apply a filter by name (u8).
+///
+/// # Arguments
+/// * `data` - Flat array of bytes (length = width * height * channels)
+/// * `name` - Filter name from `filter_registry_wasm`
+/// * `params_json` - JSON object of parameters (empty = defaults)
+///
+/// Output has the input size except for thumbnail (see `thumbnail_size_wasm`).
+#[wasm_bindgen]
+pub fn apply_filter_wasm(
     data: &[u8],
     width: usize,
     height: usize,
-    radius: f32,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-    choke: f32,
-) -> Vec<u8> {
-    use ndarray::Array2;
+    channels: usize,
+    name: &str,
+    params_json: &str,
+) -> Result<Vec<u8>, JsError> {
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let result = apply_filter_u8(name, input, params_json).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+/// Apply a filter by name (f32).
+///
+/// Same as apply_filter_wasm but for f32 images (0.0-1.0).
+#[wasm_bindgen]
+pub fn apply_filter_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    name: &str,
+    params_json: &str,
+) -> Result<Vec<f32>, JsError> {
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let result = apply_filter_f32(name, input, params_json).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
+// ============================================================================
+// Filter Recipes
+// ============================================================================
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            alpha[[y, x]] = input_f32[[y, x, 3]];
-        }
-    }
+use crate::batch::Recipe;
 
-    // Erode and blur
-    let choke_radius = radius * choke;
-    let eroded = if choke_radius > 0.0 {
-        erode_alpha(&alpha, choke_radius)
-    } else {
-        alpha.clone()
-    };
-    let blurred = blur_alpha_f32(&eroded, radius * (1.0 - choke * 0.5));
+/// Apply a recipe (JSON) to a u8 image.
+///
+/// Output has the input size unless the recipe contains a thumbnail step.
+#[wasm_bindgen]
+pub fn apply_recipe_wasm(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    recipe_json: &str,
+) -> Result<Vec<u8>, JsError> {
+    let recipe = Recipe::from_json(recipe_json).map_err(|e| JsError::new(&e.to_string()))?;
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(recipe.apply_u8(input).into_raw_vec_and_offset().0)
+}
 
-    // Glow mask
-    let mut glow_mask = Array2::<f32>::zeros((height, width));
-    for y in 0..height {
-        for x in 0..width {
-            let edge_dist = alpha[[y, x]] - blurred[[y, x]];
-            glow_mask[[y, x]] = edge_dist.max(0.0) * alpha[[y, x]];
-        }
-    }
+/// Apply a recipe (JSON) to an f32 image.
+#[wasm_bindgen]
+pub fn apply_recipe_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    recipe_json: &str,
+) -> Result<Vec<f32>, JsError> {
+    let recipe = Recipe::from_json(recipe_json).map_err(|e| JsError::new(&e.to_string()))?;
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    Ok(recipe.apply_f32(input).into_raw_vec_and_offset().0)
+}
 
-    // Compose result
-    let mut result = input_f32.clone();
-    let glow_r = color_r as f32 / 255.0;
-    let glow_g = color_g as f32 / 255.0;
-    let glow_b = color_b as f32 / 255.0;
+/// Validate a recipe and return it as normalized JSON (all parameters written out).
+#[wasm_bindgen]
+pub fn validate_recipe_wasm(recipe_json: &str) -> Result<String, JsError> {
+    Recipe::from_json(recipe_json).map(|recipe| recipe.to_json()).map_err(|e| JsError::new(&e.to_string()))
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = alpha[[y, x]];
-            if orig_a <= 0.0 { continue; }
-            let glow_a = glow_mask[[y, x]] * opacity;
-            if glow_a > 0.0 {
-                // Screen blend
-                result[[y, x, 0]] = 1.0 - (1.0 - result[[y, x, 0]]) * (1.0 - glow_r * glow_a);
-                result[[y, x, 1]] = 1.0 - (1.0 - result[[y, x, 1]]) * (1.0 - glow_g * glow_a);
-                result[[y, x, 2]] = 1.0 - (1.0 - result[[y, x, 2]]) * (1.0 - glow_b * glow_a);
-            }
-        }
-    }
+/// Convert a recipe from JSON to CBOR.
+#[wasm_bindgen]
+pub fn recipe_to_cbor_wasm(recipe_json: &str) -> Result<Vec<u8>, JsError> {
+    Recipe::from_json(recipe_json).map(|recipe| recipe.to_cbor()).map_err(|e| JsError::new(&e.to_string()))
+}
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
+/// Convert a recipe from CBOR to JSON.
+#[wasm_bindgen]
+pub fn recipe_from_cbor_wasm(cbor: &[u8]) -> Result<String, JsError> {
+    Recipe::from_cbor(cbor).map(|recipe| recipe.to_json()).map_err(|e| JsError::new(&e.to_string()))
 }
 
 // ============================================================================
-// Layer Effects: Bevel & Emboss
+// Adjustment Layers
 // ============================================================================
 
-/// Apply bevel and emboss effect to RGBA u8 image.
+use crate::batch::{preview_adjustment_f32, preview_adjustment_u8, RecipeStep};
+
+/// Composite an adjustment layer over a u8 backdrop.
 ///
-/// Creates a 3D raised or sunken appearance using highlights and shadows.
+/// # Arguments
+/// * `step_json` - Recipe step, e.g. `{"filter": "brightness", "params": {"amount": 0.2}}`
+/// * `mask` - Layer mask (height * width), 0 = backdrop, 255 = adjusted; empty = no mask
+/// * `options` - Layer opacity and blend mode
 #[wasm_bindgen]
-pub fn bevel_emboss_rgba_wasm(
+pub fn preview_adjustment_wasm(
     data: &[u8],
     width: usize,
     height: usize,
-    depth: f32,
-    angle: f32,
-    _altitude: f32,
-    highlight_r: u8,
-    highlight_g: u8,
-    highlight_b: u8,
-    highlight_opacity: f32,
-    shadow_r: u8,
-    shadow_g: u8,
-    shadow_b: u8,
-    shadow_opacity: f32,
-    style: &str,
-) -> Vec<u8> {
-    use ndarray::Array2;
+    channels: usize,
+    step_json: &str,
+    mask: &[u8],
+    options: &BlendImagesOptions,
+) -> Result<Vec<u8>, JsError> {
+    let step = RecipeStep::from_json(step_json).map_err(|e| JsError::new(&e.to_string()))?;
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let mask = (!mask.is_empty()).then(|| ndarray::ArrayView2::from_shape((height, width), mask).expect("Invalid mask dimensions"));
+    let result = preview_adjustment_u8(input, &step, mask, options.opacity, &options.blend_mode)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
+/// Composite an adjustment layer over an f32 backdrop.
+///
+/// Same as preview_adjustment_wasm with a mask of 0.0 = backdrop, 1.0 = adjusted.
+#[wasm_bindgen]
+pub fn preview_adjustment_f32_wasm(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    channels: usize,
+    step_json: &str,
+    mask: &[f32],
+    options: &BlendImagesOptions,
+) -> Result<Vec<f32>, JsError> {
+    let step = RecipeStep::from_json(step_json).map_err(|e| JsError::new(&e.to_string()))?;
+    let input = ndarray::ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let mask = (!mask.is_empty()).then(|| ndarray::ArrayView2::from_shape((height, width), mask).expect("Invalid mask dimensions"));
+    let result = preview_adjustment_f32(input, &step, mask, options.opacity, &options.blend_mode)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, width, 4));
-    for y in 0..height {
-        for x in 0..width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
+// ============================================================================
+// Persistent Image Buffers (zero-copy)
+// ============================================================================
+//
+// Images live in WASM memory between calls. JS writes pixels once through a
+// view on `memory.buffer` at `get_view_ptr(id)` and then runs any number of
+// filters in place, without copying across the JS boundary per call.
+//
+// Views must be recreated after every call that may allocate (memory growth
+// detaches the old ArrayBuffer) and after every `process_in_place`, which
+// may move the pixels to the buffer's second storage.
+
+struct ImageBuffer {
+    /// Pixels (height, width, channels), the memory JS views
+    image: Array3<u8>,
+    /// Destination of steps that can't rewrite `image` directly
+    back: Array3<u8>,
+    /// Blur temporaries
+    scratch: Array3<f32>,
+}
 
-    // Expand for outer bevel
-    let is_outer = style == "outer_bevel";
-    let expand = if is_outer { (depth.ceil() as usize) + 2 } else { 0 };
-    let expanded = if expand > 0 {
-        expand_canvas_f32(&input_f32, expand)
-    } else {
-        input_f32.clone()
-    };
+thread_local! {
+    static BUFFERS: RefCell<(u32, HashMap<u32, ImageBuffer>)> = RefCell::new((0, HashMap::new()));
+}
 
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+fn buffer_error(error: BatchError) -> JsError {
+    JsError::new(&error.to_string())
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
+fn with_buffer<T>(id: u32, f: impl FnOnce(&mut ImageBuffer) -> T) -> Result<T, BatchError> {
+    BUFFERS.with(|buffers| {
+        let mut buffers = buffers.borrow_mut();
+        let buffer = buffers.1.get_mut(&id).ok_or_else(|| BatchError::InvalidArgument(format!("unknown buffer {}", id)))?;
+        Ok(f(buffer))
+    })
+}
+
+fn insert_buffer(width: usize, height: usize, channels: usize) -> Result<u32, BatchError> {
+    if !matches!(channels, 1 | 3 | 4) {
+        return Err(BatchError::InvalidArgument("channels must be 1, 3, or 4".to_string()));
     }
+    width.checked_mul(height).and_then(|n| n.checked_mul(channels)).ok_or_else(|| {
+        BatchError::InvalidArgument(format!("buffer of {}x{}x{} is too large", width, height, channels))
+    })?;
+    Ok(BUFFERS.with(|buffers| {
+        let mut buffers = buffers.borrow_mut();
+        buffers.0 += 1;
+        let id = buffers.0;
+        let buffer = ImageBuffer {
+            image: Array3::zeros((height, width, channels)),
+            back: Array3::zeros((0, 0, 0)),
+            scratch: Array3::zeros((0, 0, 0)),
+        };
+        buffers.1.insert(id, buffer);
+        id
+    }))
+}
+
+fn process_buffer(id: u32, spec: &FilterSpec) -> Result<(), BatchError> {
+    let step = PreparedStep::new(spec);
+    with_buffer(id, |buffer| step.run_in_place(&mut buffer.image, &mut buffer.back, &mut buffer.scratch))
+}
+
+/// Allocate a zero-filled u8 image buffer in WASM memory.
+///
+/// # Arguments
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `channels` - Number of channels (1, 3, or 4)
+///
+/// # Returns
+/// Buffer id for the other buffer functions
+#[wasm_bindgen]
+pub fn create_buffer(width: usize, height: usize, channels: usize) -> Result<u32, JsError> {
+    insert_buffer(width, height, channels).map_err(buffer_error)
+}
+
+/// Release a buffer created by `create_buffer`.
+#[wasm_bindgen]
+pub fn free_buffer(id: u32) -> bool {
+    BUFFERS.with(|buffers| buffers.borrow_mut().1.remove(&id).is_some())
+}
 
-    // Light direction
-    let angle_rad = angle.to_radians();
-    let dx = angle_rad.cos();
-    let dy = -angle_rad.sin();
+/// Byte offset of the buffer's pixels in WASM memory.
+#[wasm_bindgen]
+pub fn get_view_ptr(id: u32) -> Result<usize, JsError> {
+    with_buffer(id, |buffer| buffer.image.as_mut_ptr() as usize).map_err(buffer_error)
+}
 
-    // Bump map
-    let mut bump_x = Array2::<f32>::zeros((new_h, new_w));
-    let mut bump_y = Array2::<f32>::zeros((new_h, new_w));
-    for y in 1..new_h - 1 {
-        for x in 1..new_w - 1 {
-            bump_x[[y, x]] = (alpha[[y, x + 1]] - alpha[[y, x - 1]]) / 2.0;
-            bump_y[[y, x]] = (alpha[[y + 1, x]] - alpha[[y - 1, x]]) / 2.0;
-        }
-    }
+/// Current buffer shape as [width, height, channels].
+#[wasm_bindgen]
+pub fn get_buffer_shape(id: u32) -> Result<Vec<usize>, JsError> {
+    with_buffer(id, |buffer| {
+        let (height, width, channels) = buffer.image.dim();
+        vec![width, height, channels]
+    })
+    .map_err(buffer_error)
+}
 
-    let bump_x_blur = blur_alpha_f32(&bump_x, depth * 0.5);
-    let bump_y_blur = blur_alpha_f32(&bump_y, depth * 0.5);
-
-    // Create result
-    let mut result = expanded.clone();
-    let hl_r = highlight_r as f32 / 255.0;
-    let hl_g = highlight_g as f32 / 255.0;
-    let hl_b = highlight_b as f32 / 255.0;
-    let sh_r = shadow_r as f32 / 255.0;
-    let sh_g = shadow_g as f32 / 255.0;
-    let sh_b = shadow_b as f32 / 255.0;
-
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let orig_a = expanded[[y, x, 3]];
-            if orig_a <= 0.0 && !is_outer { continue; }
-
-            let bx = bump_x_blur[[y, x]] * depth;
-            let by = bump_y_blur[[y, x]] * depth;
-            let intensity = bx * dx + by * dy;
-
-            if intensity > 0.0 {
-                let hl_a = intensity.min(1.0) * highlight_opacity * orig_a;
-                if hl_a > 0.0 {
-                    let src_a = hl_a;
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (hl_r * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (hl_g * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (hl_b * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            } else if intensity < 0.0 {
-                let sh_a = (-intensity).min(1.0) * shadow_opacity * orig_a;
-                if sh_a > 0.0 {
-                    let src_a = sh_a;
-                    let dst_a = result[[y, x, 3]];
-                    let out_a = src_a + dst_a * (1.0 - src_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (sh_r * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 1]] = (sh_g * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 2]] = (sh_b * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            }
-        }
-    }
+/// Run a filter on a buffer in place.
+///
+/// Point operations rewrite the pixels directly; blurs and other filters
+/// write into storage kept with the buffer and swap it in, so repeated
+/// calls don't allocate (except inside filters without a buffered kernel).
+///
+/// # Arguments
+/// * `id` - Buffer id
+/// * `filter` - Filter name as in pipeline strings (e.g. "blur")
+/// * `params` - Filter arguments (e.g. "sigma=2" or "2"), may be empty
+///
+/// The pixels may move; query `get_view_ptr` (and, after filters that
+/// resize such as thumbnail, `get_buffer_shape`) again afterwards.
+#[wasm_bindgen]
+pub fn process_in_place(id: u32, filter: &str, params: &str) -> Result<(), JsError> {
+    let spec = FilterSpec::parse(&format!("{} {}", filter, params)).map_err(buffer_error)?;
+    process_buffer(id, &spec).map_err(buffer_error)
+}
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
+/// Re-run a filter on dirty regions of a buffer in place.
+///
+/// Only the regions plus the filter's kernel margin are recomputed, which
+/// keeps live brush previews on large canvases cheap.
+///
+/// # Arguments
+/// * `id` - Buffer id
+/// * `rects` - Flat list of `[x, y, width, height, ...]` regions
+/// * `filter` - Filter name as in pipeline strings (e.g. "blur")
+/// * `params` - Filter arguments (e.g. "sigma=2" or "2"), may be empty
+#[wasm_bindgen]
+pub fn process_rects_in_place(id: u32, rects: &[u32], filter: &str, params: &str) -> Result<(), JsError> {
+    let spec = FilterSpec::parse(&format!("{} {}", filter, params)).map_err(buffer_error)?;
+    let rects = rects_from_flat(rects).map_err(buffer_error)?;
+    with_buffer(id, |buffer| apply_in_rects_u8(buffer.image.view_mut(), &rects, &[spec]))
+        .and_then(|result| result)
+        .map_err(buffer_error)
 }
 
 // ============================================================================
-// Layer Effects: Stroke
+// WebGPU Shader Export
 // ============================================================================
 
-/// Apply stroke/outline effect to RGBA u8 image.
+use crate::gpu::wgsl::export_wgsl;
+
+/// Export a pipeline as WGSL compute passes for WebGPU.
+///
+/// # Arguments
+/// * `spec` - Pipeline string (e.g. "brightness 0.1 | blur 2")
 ///
-/// Creates an outline around non-transparent areas.
+/// # Returns
+/// JSON with the WGSL source, uniform layout and kernel weights of every
+/// pass, in dispatch order
 #[wasm_bindgen]
-pub fn stroke_rgba_wasm(
-    data: &[u8],
-    img_width: usize,
-    height: usize,
-    stroke_width: f32,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-    position: &str,
-) -> Vec<u8> {
-    use ndarray::Array2;
+pub fn export_wgsl_wasm(spec: &str) -> Result<String, JsError> {
+    let pipeline = parse_pipeline(spec).map_err(|e| JsError::new(&e.to_string()))?;
+    let export = export_wgsl(&pipeline).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(export.to_json())
+}
 
-    let input = Array3::from_shape_vec((height, img_width, 4), data.to_vec()).expect("Invalid dimensions");
+// ============================================================================
+// Image Encode/Decode
+// ============================================================================
 
-    // Convert to f32
-    let mut input_f32 = Array3::<f32>::zeros((height, img_width, 4));
-    for y in 0..height {
-        for x in 0..img_width {
-            input_f32[[y, x, 0]] = input[[y, x, 0]] as f32 / 255.0;
-            input_f32[[y, x, 1]] = input[[y, x, 1]] as f32 / 255.0;
-            input_f32[[y, x, 2]] = input[[y, x, 2]] as f32 / 255.0;
-            input_f32[[y, x, 3]] = input[[y, x, 3]] as f32 / 255.0;
-        }
-    }
+#[cfg(feature = "encode")]
+use crate::io::{
+    apply_exif_orientation, decode_image_rgba, decode_image_u16, encode_apng, encode_gif, encode_jpeg, encode_png, encode_qoi,
+    encode_tiff, encode_tiff_u16, encode_webp, read_exif, read_icc_profile, read_metadata, CodecError, JpegOptions,
+    PaletteOptions, PngOptions,
+};
+#[cfg(feature = "encode")]
+use crate::io::icc::{convert_from_srgb, convert_to_srgb, soft_proof, IccProfile, RenderingIntent};
 
-    // Expand for outside stroke
-    let is_inside = position == "inside";
-    let required_expand = if is_inside { 0 } else { (stroke_width.ceil() as usize) + 2 };
-    let expanded = if required_expand > 0 {
-        expand_canvas_f32(&input_f32, required_expand)
-    } else {
-        input_f32.clone()
-    };
+#[cfg(feature = "encode")]
+fn codec_error(e: CodecError) -> JsError {
+    JsError::new(&e.to_string())
+}
 
-    let (new_h, new_w, _) = (expanded.shape()[0], expanded.shape()[1], expanded.shape()[2]);
+/// Read size and color layout of an image file.
+///
+/// # Returns
+/// [width, height, bit_depth, has_alpha (0/1), channels]
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn image_info_wasm(data: &[u8]) -> Result<Vec<u32>, JsError> {
+    let meta = read_metadata(data).map_err(codec_error)?;
+    Ok(vec![meta.width as u32, meta.height as u32, meta.bit_depth as u32, meta.has_alpha as u32, meta.channels as u32])
+}
 
-    // Extract alpha
-    let mut alpha = Array2::<f32>::zeros((new_h, new_w));
-    for y in 0..new_h {
-        for x in 0..new_w {
-            alpha[[y, x]] = expanded[[y, x, 3]];
-        }
-    }
+/// Decode an image file to 8-bit RGBA pixels (use `image_info_wasm` for the size).
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn decode_image_rgba_wasm(data: &[u8]) -> Result<Vec<u8>, JsError> {
+    let (image, _) = decode_image_rgba(data).map_err(codec_error)?;
+    Ok(image.into_raw_vec_and_offset().0)
+}
 
-    // Create stroke mask
-    let stroke_mask = match position {
-        "inside" => {
-            let eroded = erode_alpha(&alpha, stroke_width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (alpha[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        "center" => {
-            let half = stroke_width / 2.0;
-            let dilated = dilate_alpha(&alpha, half);
-            let eroded = erode_alpha(&alpha, half);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - eroded[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-        _ => { // outside
-            let dilated = dilate_alpha(&alpha, stroke_width);
-            let mut mask = Array2::<f32>::zeros((new_h, new_w));
-            for y in 0..new_h {
-                for x in 0..new_w {
-                    mask[[y, x]] = (dilated[[y, x]] - alpha[[y, x]]).max(0.0);
-                }
-            }
-            mask
-        }
-    };
+/// Encode pixels as PNG.
+///
+/// # Arguments
+/// * `data` - Pixel data with 1, 3 or 4 channels
+/// * `compression` - Deflate level 0-9
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn encode_png_wasm(data: &[u8], width: usize, height: usize, channels: usize, compression: u8) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    encode_png(&input, &PngOptions { compression, ..Default::default() }).map_err(codec_error)
+}
 
-    // Create result
-    let mut result = Array3::<f32>::zeros((new_h, new_w, 4));
-    let stroke_r = color_r as f32 / 255.0;
-    let stroke_g = color_g as f32 / 255.0;
-    let stroke_b = color_b as f32 / 255.0;
-
-    // Draw stroke
-    for y in 0..new_h {
-        for x in 0..new_w {
-            let stroke_a = stroke_mask[[y, x]] * opacity;
-            if stroke_a > 0.0 {
-                result[[y, x, 0]] = stroke_r;
-                result[[y, x, 1]] = stroke_g;
-                result[[y, x, 2]] = stroke_b;
-                result[[y, x, 3]] = stroke_a;
-            }
-        }
-    }
+/// Encode pixels as JPEG (alpha is dropped).
+///
+/// # Arguments
+/// * `data` - Pixel data with 1, 3 or 4 channels
+/// * `quality` - Quality 1-100
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn encode_jpeg_wasm(data: &[u8], width: usize, height: usize, channels: usize, quality: u8) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    encode_jpeg(&input, &JpegOptions { quality, ..Default::default() }).map_err(codec_error)
+}
 
-    // Composite original
-    if is_inside {
-        for y in 0..new_h {
-            for x in 0..new_w {
-                let orig_a = expanded[[y, x, 3]];
-                if orig_a <= 0.0 { continue; }
-                let stroke_a = result[[y, x, 3]];
-                if stroke_a <= 0.0 {
-                    result[[y, x, 0]] = expanded[[y, x, 0]];
-                    result[[y, x, 1]] = expanded[[y, x, 1]];
-                    result[[y, x, 2]] = expanded[[y, x, 2]];
-                    result[[y, x, 3]] = orig_a;
-                } else {
-                    let out_a = stroke_a + orig_a * (1.0 - stroke_a);
-                    if out_a > 0.0 {
-                        result[[y, x, 0]] = (result[[y, x, 0]] * stroke_a + expanded[[y, x, 0]] * orig_a * (1.0 - stroke_a)) / out_a;
-                        result[[y, x, 1]] = (result[[y, x, 1]] * stroke_a + expanded[[y, x, 1]] * orig_a * (1.0 - stroke_a)) / out_a;
-                        result[[y, x, 2]] = (result[[y, x, 2]] * stroke_a + expanded[[y, x, 2]] * orig_a * (1.0 - stroke_a)) / out_a;
-                        result[[y, x, 3]] = out_a;
-                    }
-                }
-            }
-        }
-    } else {
-        for y in 0..new_h {
-            for x in 0..new_w {
-                let src_a = expanded[[y, x, 3]];
-                if src_a <= 0.0 { continue; }
-                let dst_a = result[[y, x, 3]];
-                let out_a = src_a + dst_a * (1.0 - src_a);
-                if out_a > 0.0 {
-                    result[[y, x, 0]] = (expanded[[y, x, 0]] * src_a + result[[y, x, 0]] * dst_a * (1.0 - src_a)) / out_a;
-                    result[[y, x, 1]] = (expanded[[y, x, 1]] * src_a + result[[y, x, 1]] * dst_a * (1.0 - src_a)) / out_a;
-                    result[[y, x, 2]] = (expanded[[y, x, 2]] * src_a + result[[y, x, 2]] * dst_a * (1.0 - src_a)) / out_a;
-                    result[[y, x, 3]] = out_a;
-                }
-            }
-        }
-    }
+/// Encode pixels as lossless WebP.
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn encode_webp_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    encode_webp(&input).map_err(codec_error)
+}
 
-    result.mapv(|v| (v.clamp(0.0, 1.0) * 255.0) as u8).into_raw_vec_and_offset().0
+/// Decode an image file to 16-bit pixels with the channel count reported by `image_info_wasm`.
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn decode_image_u16_wasm(data: &[u8]) -> Result<Vec<u16>, JsError> {
+    let (image, _) = decode_image_u16(data).map_err(codec_error)?;
+    Ok(image.into_raw_vec_and_offset().0)
 }
 
-// ============================================================================
-// Layer Effects: Color Overlay
-// ============================================================================
+/// Encode pixels as QOI.
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn encode_qoi_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    encode_qoi(&input).map_err(codec_error)
+}
 
-/// Apply color overlay effect to RGBA u8 image.
-///
-/// Replaces all colors with a solid color while preserving alpha.
+/// Encode 8-bit pixels as TIFF.
+#[cfg(feature = "encode")]
 #[wasm_bindgen]
-pub fn color_overlay_rgba_wasm(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    color_r: u8,
-    color_g: u8,
-    color_b: u8,
-    opacity: f32,
-) -> Vec<u8> {
-    let input = Array3::from_shape_vec((height, width, 4), data.to_vec()).expect("Invalid dimensions");
-    let mut result = Array3::<u8>::zeros((height, width, 4));
+pub fn encode_tiff_wasm(data: &[u8], width: usize, height: usize, channels: usize) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    encode_tiff(&input).map_err(codec_error)
+}
 
-    for y in 0..height {
-        for x in 0..width {
-            let orig_a = input[[y, x, 3]] as f32 / 255.0;
-            if orig_a <= 0.0 { continue; }
-
-            let blend_a = opacity;
-            let orig_r = input[[y, x, 0]] as f32 / 255.0;
-            let orig_g = input[[y, x, 1]] as f32 / 255.0;
-            let orig_b = input[[y, x, 2]] as f32 / 255.0;
-            let overlay_r = color_r as f32 / 255.0;
-            let overlay_g = color_g as f32 / 255.0;
-            let overlay_b = color_b as f32 / 255.0;
-
-            let final_r = orig_r * (1.0 - blend_a) + overlay_r * blend_a;
-            let final_g = orig_g * (1.0 - blend_a) + overlay_g * blend_a;
-            let final_b = orig_b * (1.0 - blend_a) + overlay_b * blend_a;
-
-            result[[y, x, 0]] = (final_r * 255.0) as u8;
-            result[[y, x, 1]] = (final_g * 255.0) as u8;
-            result[[y, x, 2]] = (final_b * 255.0) as u8;
-            result[[y, x, 3]] = input[[y, x, 3]];
-        }
-    }
+/// Encode 16-bit pixels as TIFF.
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn encode_tiff_u16_wasm(data: &[u16], width: usize, height: usize, channels: usize) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    encode_tiff_u16(&input).map_err(codec_error)
+}
 
-    result.into_raw_vec_and_offset().0
+/// Read the EXIF orientation of an image file (1 if the file has no EXIF block).
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn read_exif_orientation_wasm(data: &[u8]) -> Result<u16, JsError> {
+    let exif = read_exif(data).map_err(codec_error)?;
+    Ok(exif.map_or(1, |e| e.orientation))
 }
 
-// ============================================================================
-// Selection Algorithms
-// ============================================================================
+/// Rotate and flip pixels according to an EXIF orientation.
+///
+/// Orientations 5-8 swap width and height of the result.
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn apply_exif_orientation_wasm(data: &[u8], width: usize, height: usize, channels: usize, orientation: u16) -> Vec<u8> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    apply_exif_orientation(input.view(), orientation).into_raw_vec_and_offset().0
+}
 
-use crate::selection::contour::extract_contours as extract_contours_impl;
-use crate::selection::magic_wand::magic_wand_select as magic_wand_impl;
-use crate::selection::marching_squares::{
-    extract_contours_precise as extract_contours_precise_impl,
-    contours_to_flat,
-    douglas_peucker as douglas_peucker_impl,
-    douglas_peucker_closed as douglas_peucker_closed_impl,
-    Point as MarchingPoint,
-};
+/// Extract the embedded ICC profile of an image file (empty if there is none).
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn read_icc_profile_wasm(data: &[u8]) -> Result<Vec<u8>, JsError> {
+    Ok(read_icc_profile(data).map_err(codec_error)?.unwrap_or_default())
+}
 
-/// Extract contours from an alpha mask using Marching Squares.
+/// Convert pixels from an ICC profile's color space to sRGB.
 ///
 /// # Arguments
-/// * `mask` - Alpha mask (0 = unselected, >0 = selected)
-/// * `width` - Mask width
-/// * `height` - Mask height
-///
-/// # Returns
-/// Flat array: [num_contours, len1, x1, y1, x2, y2, ..., len2, ...]
+/// * `data` - Pixel data with 3 or 4 channels (1 or 2 for gray profiles)
+/// * `profile` - ICC profile bytes
+#[cfg(feature = "encode")]
 #[wasm_bindgen]
-pub fn extract_contours_wasm(mask: &[u8], width: usize, height: usize) -> Vec<f32> {
-    extract_contours_impl(mask, width, height)
+pub fn icc_to_srgb_wasm(data: &[u8], width: usize, height: usize, channels: usize, profile: &[u8]) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let profile = IccProfile::parse(profile).map_err(|e| JsError::new(&e.to_string()))?;
+    let result = convert_to_srgb(input.view(), &profile).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
-/// Magic wand selection using flood fill algorithm.
+/// Convert sRGB pixels to an ICC profile's color space.
+#[cfg(feature = "encode")]
+#[wasm_bindgen]
+pub fn icc_from_srgb_wasm(data: &[u8], width: usize, height: usize, channels: usize, profile: &[u8]) -> Result<Vec<u8>, JsError> {
+    let input = Array3::from_shape_vec((height, width, channels), data.to_vec()).expect("Invalid dimensions");
+    let profile = IccProfile::parse(profile).map_err(|e| JsError::new(&e.to_string()))?;
+    let result = convert_from_srgb(input.view(), &profile).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
+}
+
+/// Preview sRGB pixels as reproduced in an ICC profile's gamut.
 ///
 /// # Arguments
-/// * `image` - RGBA image data (4 bytes per pixel)
-/// * `width` - Image width
-/// * `height` - Image height
-/// * `start_x` - Starting X coordinate
-/// * `start_y` - Starting Y coordinate
-/// * `tolerance` - Color tolerance (0-255)
-/// * `contiguous` - If true, only selects connected pixels
-///
-/// # Returns
-/// Selection mask (255 = selected, 0 = not selected)
+/// * `data` - sRGB pixel data with 3 or 4 channels
+/// * `profile` - Target ICC profile bytes
+/// * `intent` - "perceptual", "relative_colorimetric", "saturation" or "absolute_colorimetric"
+/// * `gamut_warning_color` - `[r, g, b]` painted over out-of-gamut pixels, empty for none
+#[cfg(feature = "encode")]
 #[wasm_bindgen]
-pub fn magic_wand_select_wasm(
-    image: &[u8],
+pub fn soft_proof_wasm(
+    data: &[u8],
     width: usize,
     height: usize,
-    start_x: usize,
-    start_y: usize,
-    tolerance: u8,
-    contiguous: bool,
-) -> Vec<u8> {
-    magic_wand_impl(image, width, height, start_x, start_y, tolerance, contiguous)
+    channels: usize,
+    profile: &[u8],
+    intent: &str,
+    gamut_warning_color: &[u8],
+) -> Result<Vec<u8>, JsError> {
+    let warning = match gamut_warning_color {
+        [] => None,
+        &[r, g, b] => Some([r, g, b]),
+        _ => return Err(JsError::new("gamut warning color must be [r, g, b] or empty")),
+    };
+    let intent = RenderingIntent::from_name(intent).ok_or_else(|| JsError::new(&format!("unknown rendering intent '{}'", intent)))?;
+    let input = ArrayView3::from_shape((height, width, channels), data).expect("Invalid dimensions");
+    let profile = IccProfile::parse(profile).map_err(|e| JsError::new(&e.to_string()))?;
+    let result = soft_proof(input, &profile, intent, warning).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(result.into_raw_vec_and_offset().0)
 }
 
-// ============================================================================
-// Precise Contour Extraction (Marching Squares + Simplification + Bezier)
-// ============================================================================
+/// Split concatenated frames into arrays (one frame per delay).
+#[cfg(feature = "encode")]
+fn split_frames(data: &[u8], width: usize, height: usize, channels: usize, count: usize) -> Vec<Array3<u8>> {
+    let frame_size = width * height * channels;
+    assert_eq!(data.len(), frame_size * count, "Invalid dimensions");
+    data.chunks_exact(frame_size.max(1))
+        .map(|frame| Array3::from_shape_vec((height, width, channels), frame.to_vec()).expect("Invalid dimensions"))
+        .collect()
+}
 
-/// Extract precise sub-pixel contours from an alpha mask.
-///
-/// Uses Marching Squares algorithm for sub-pixel contour extraction,
-/// Douglas-Peucker for simplification, and optional Bezier curve fitting.
+/// Encode frames as an animated GIF.
 ///
 /// # Arguments
-/// * `mask` - Alpha mask (0-255), flattened row-major
-/// * `width` - Mask width
-/// * `height` - Mask height
-/// * `threshold` - Alpha threshold (0.0-1.0)
-/// * `simplify_epsilon` - Douglas-Peucker epsilon (0 = no simplification)
-/// * `fit_beziers` - Whether to fit Bezier curves
-/// * `bezier_smoothness` - Bezier smoothness factor (0.1-0.5)
-///
-/// # Returns
-/// Flat array with contour data:
-/// [num_contours,
-///  is_closed_1, num_points_1, x1, y1, x2, y2, ...,
-///  has_beziers_1, (num_beziers, p0x, p0y, p1x, p1y, p2x, p2y, p3x, p3y, ...),
-///  ...]
+/// * `data` - Concatenated frame pixels, all frames the same size
+/// * `delays` - Display time of each frame in milliseconds (one per frame)
+/// * `max_colors` - Palette size 2-256
+/// * `dither` - Apply Floyd-Steinberg dithering
+#[cfg(feature = "encode")]
 #[wasm_bindgen]
-pub fn extract_contours_precise_wasm(
-    mask: &[u8],
+pub fn encode_gif_wasm(
+    data: &[u8],
     width: usize,
     height: usize,
-    threshold: f32,
-    simplify_epsilon: f32,
-    fit_beziers: bool,
-    bezier_smoothness: f32,
-) -> Vec<f32> {
-    let contours = extract_contours_precise_impl(
-        mask,
-        width,
-        height,
-        threshold,
-        simplify_epsilon,
-        fit_beziers,
-        bezier_smoothness,
-    );
-    contours_to_flat(&contours)
+    channels: usize,
+    delays: &[u32],
+    max_colors: usize,
+    dither: bool,
+) -> Result<Vec<u8>, JsError> {
+    let frames = split_frames(data, width, height, channels, delays.len());
+    encode_gif(&frames, delays, &PaletteOptions { max_colors, dither }).map_err(codec_error)
 }
 
-/// Simplify a polyline using the Douglas-Peucker algorithm.
+/// Encode frames as an animated PNG.
 ///
 /// # Arguments
-/// * `points` - Flat array of floats [x1, y1, x2, y2, ...] representing the polyline
-/// * `epsilon` - Maximum distance threshold for simplification (higher = more simplification)
-///
-/// # Returns
-/// Flat array of simplified points [x1, y1, x2, y2, ...]
+/// * `data` - Concatenated frame pixels, all frames the same size
+/// * `delays` - Display time of each frame in milliseconds (one per frame)
+#[cfg(feature = "encode")]
 #[wasm_bindgen]
-pub fn douglas_peucker_wasm(
-    points: &[f32],
-    epsilon: f32,
-) -> Vec<f32> {
-    // Convert flat array to Point array
-    let pts: Vec<MarchingPoint> = points
-        .chunks(2)
-        .map(|chunk| MarchingPoint { x: chunk[0], y: chunk[1] })
-        .collect();
+pub fn encode_apng_wasm(data: &[u8], width: usize, height: usize, channels: usize, delays: &[u32]) -> Result<Vec<u8>, JsError> {
+    let frames = split_frames(data, width, height, channels, delays.len());
+    encode_apng(&frames, delays).map_err(codec_error)
+}
 
-    let simplified = douglas_peucker_impl(&pts, epsilon);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Convert back to flat array
-    simplified.iter().flat_map(|p| vec![p.x, p.y]).collect()
-}
+    fn pixels(id: u32) -> Array3<u8> {
+        with_buffer(id, |buffer| buffer.image.clone()).unwrap()
+    }
 
-/// Simplify a closed polygon using the Douglas-Peucker algorithm.
-///
-/// This version handles closed polygons by finding the point farthest from
-/// the centroid to use as the starting point, ensuring better results.
-///
-/// # Arguments
-/// * `points` - Flat array of floats [x1, y1, x2, y2, ...] representing the closed polygon
-/// * `epsilon` - Maximum distance threshold for simplification (higher = more simplification)
-///
-/// # Returns
-/// Flat array of simplified points [x1, y1, x2, y2, ...] (closed polygon)
-#[wasm_bindgen]
-pub fn douglas_peucker_closed_wasm(
-    points: &[f32],
-    epsilon: f32,
-) -> Vec<f32> {
-    // Convert flat array to Point array
-    let pts: Vec<MarchingPoint> = points
-        .chunks(2)
-        .map(|chunk| MarchingPoint { x: chunk[0], y: chunk[1] })
-        .collect();
+    #[test]
+    fn test_buffer_lifecycle() {
+        let id = create_buffer(5, 3, 4).unwrap();
+        assert_eq!(get_buffer_shape(id).unwrap(), vec![5, 3, 4]);
+        let input = Array3::from_shape_fn((3, 5, 4), |(y, x, c)| ((y * 40 + x * 17 + c * 60) % 256) as u8);
+        with_buffer(id, |buffer| buffer.image.assign(&input)).unwrap();
+        let spec = |text: &str| FilterSpec::parse(text).unwrap();
+
+        // Point operations rewrite the pixels where they are
+        let ptr = get_view_ptr(id).unwrap();
+        process_in_place(id, "invert", "").unwrap();
+        assert_eq!(get_view_ptr(id).unwrap(), ptr);
+        let mut expected = spec("invert").apply_u8(input.view());
+        assert_eq!(pixels(id), expected);
+
+        // Blurs alternate between the two storages without reallocating
+        process_in_place(id, "blur", "sigma=1.5").unwrap();
+        let other = get_view_ptr(id).unwrap();
+        process_in_place(id, "blur", "1.5").unwrap();
+        assert_eq!(get_view_ptr(id).unwrap(), ptr);
+        process_in_place(id, "blur", "1.5").unwrap();
+        assert_eq!(get_view_ptr(id).unwrap(), other);
+        for _ in 0..3 {
+            expected = spec("blur 1.5").apply_u8(expected.view());
+        }
+        assert_eq!(pixels(id), expected);
 
-    let simplified = douglas_peucker_closed_impl(&pts, epsilon);
+        // Resizing filters change the reported shape
+        process_in_place(id, "thumbnail", "2").unwrap();
+        expected = spec("thumbnail 2").apply_u8(expected.view());
+        let (height, width, channels) = expected.dim();
+        assert_eq!(get_buffer_shape(id).unwrap(), vec![width, height, channels]);
+        assert_eq!(pixels(id), expected);
 
-    // Convert back to flat array
-    simplified.iter().flat_map(|p| vec![p.x, p.y]).collect()
+        assert!(free_buffer(id));
+        assert!(!free_buffer(id));
+    }
+
+    #[test]
+    fn test_stale_and_invalid_buffers() {
+        let id = create_buffer(2, 2, 1).unwrap();
+        assert!(free_buffer(id));
+        let unknown = Err(BatchError::InvalidArgument(format!("unknown buffer {}", id)));
+        assert_eq!(with_buffer(id, |_| ()), unknown);
+        assert_eq!(process_buffer(id, &FilterSpec::Invert), unknown);
+        // Ids are not reused, so a stale id never reaches a newer buffer
+        let next = create_buffer(2, 2, 1).unwrap();
+        assert_ne!(next, id);
+        assert!(free_buffer(next));
+
+        assert!(insert_buffer(4, 4, 2).is_err());
+        assert!(insert_buffer(usize::MAX, 2, 4).is_err());
+        assert!(insert_buffer(1 << 40, 1 << 30, 3).is_err());
+    }
 }
@@ -0,0 +1,173 @@
+//! Rayon thread pool for the threaded WASM build (`wasm-threads` feature).
+//!
+//! With this feature the portable blurs and the morphology filters run their
+//! rows on the rayon pool, as they do under Python. Browsers have no native
+//! threads, so the pool is backed by Web Workers that share the module's
+//! memory:
+//!
+//! 1. JS calls [`build_thread_pool`] with a spawner function
+//! 2. For every rayon thread the spawner starts a worker and passes it the
+//!    compiled module, the shared memory and the thread handle
+//! 3. The worker instantiates the module and calls [`start_worker`], which
+//!    runs the rayon thread until the pool shuts down
+//!
+//! `imagestag/filters/thread_pool.js` implements the JS side.
+//!
+//! ## Build Requirements
+//!
+//! - Nightly Rust with `-C target-feature=+atomics,+bulk-memory` and
+//!   `-Z build-std=panic_abort,std`
+//! - Cross-origin isolation (COOP/COEP headers) for `SharedArrayBuffer`
+//! - Filters must be called from a worker: the browser main thread may not
+//!   block while waiting for the pool
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use wasm_bindgen::prelude::*;
+
+/// Rayon threads waiting for their worker, by the handle given to JS.
+static PENDING: Mutex<Option<HashMap<u32, rayon::ThreadBuilder>>> = Mutex::new(None);
+/// Next worker handle.
+static NEXT_HANDLE: AtomicU32 = AtomicU32::new(1);
+/// Threads of the pool started by [`build_thread_pool`] (0 = not started).
+static POOL_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+fn pending() -> std::sync::MutexGuard<'static, Option<HashMap<u32, rayon::ThreadBuilder>>> {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Park a rayon thread until a worker claims it; returns its handle.
+fn register(thread: rayon::ThreadBuilder) -> u32 {
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    pending().get_or_insert_with(HashMap::new).insert(handle, thread);
+    handle
+}
+
+/// Take the rayon thread of `handle`, if it was issued and not yet claimed.
+fn claim(handle: u32) -> Option<rayon::ThreadBuilder> {
+    pending().as_mut().and_then(|threads| threads.remove(&handle))
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// JS function `(thread: number) => void` that starts one pool worker.
+    pub type WorkerSpawner;
+
+    #[wasm_bindgen(method, catch, js_name = call)]
+    fn spawn(this: &WorkerSpawner, context: &JsValue, thread: u32) -> Result<JsValue, JsValue>;
+}
+
+/// Start the global rayon pool on Web Workers.
+///
+/// # Arguments
+/// * `num_threads` - Number of pool threads (usually `navigator.hardwareConcurrency`)
+/// * `spawner` - Function starting a worker for a thread handle
+///
+/// # Returns
+/// Error if the pool is already running or a worker could not be spawned
+#[wasm_bindgen(js_name = buildThreadPool)]
+pub fn build_thread_pool(num_threads: usize, spawner: &WorkerSpawner) -> Result<(), JsError> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .spawn_handler(|thread| {
+            let handle = register(thread);
+            spawner.spawn(&JsValue::NULL, handle).map(|_| ()).map_err(|e| {
+                // The worker was not started, so nobody will claim the handle
+                claim(handle);
+                std::io::Error::other(format!("failed to spawn worker: {:?}", e))
+            })
+        })
+        .build_global();
+    pool.map_err(|e| JsError::new(&e.to_string()))?;
+    POOL_THREADS.store(num_threads, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Run one pool thread (called by each worker after instantiation).
+///
+/// # Arguments
+/// * `thread` - Handle passed to the spawner by [`build_thread_pool`]
+///
+/// # Returns
+/// Error if the handle was not issued or was already started
+#[wasm_bindgen(js_name = startWorker)]
+pub fn start_worker(thread: u32) -> Result<(), JsError> {
+    let builder = claim(thread).ok_or_else(|| JsError::new(&format!("unknown or already started worker handle {}", thread)))?;
+    builder.run();
+    Ok(())
+}
+
+/// Number of threads of the pool started by [`build_thread_pool`], or the
+/// hardware concurrency before that.
+///
+/// Doesn't touch rayon, so calling it first doesn't create a default pool
+/// that would make a later [`build_thread_pool`] fail.
+#[wasm_bindgen(js_name = threadCount)]
+pub fn thread_count() -> usize {
+    match POOL_THREADS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Local pool whose threads are parked in the handle map, like the
+    /// global pool waiting for its workers.
+    fn parked_pool(num_threads: usize) -> (rayon::ThreadPool, Vec<u32>) {
+        let handles = Mutex::new(Vec::new());
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .spawn_handler(|thread| {
+                handles.lock().unwrap().push(register(thread));
+                Ok(())
+            })
+            .build()
+            .unwrap();
+        (pool, handles.into_inner().unwrap())
+    }
+
+    #[test]
+    fn test_handle_lifecycle() {
+        let (pool, handles) = parked_pool(2);
+        assert_eq!(handles.len(), 2);
+        assert_ne!(handles[0], handles[1]);
+
+        // Each "worker" claims its handle once and runs the thread
+        let workers: Vec<_> = handles
+            .iter()
+            .map(|&handle| {
+                let builder = claim(handle).expect("issued handle");
+                std::thread::spawn(move || builder.run())
+            })
+            .collect();
+        assert_eq!(pool.install(|| (0..100).map(|v| v * 2).sum::<i32>()), 9900);
+        drop(pool);
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_handles() {
+        let (pool, handles) = parked_pool(1);
+        let builder = claim(handles[0]).expect("issued handle");
+        // A second claim of the same handle finds nothing
+        assert!(claim(handles[0]).is_none());
+        assert!(claim(u32::MAX).is_none());
+        let worker = std::thread::spawn(move || builder.run());
+        drop(pool);
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn test_thread_count_before_pool() {
+        // No pool started in tests: falls back to the hardware concurrency
+        assert_eq!(POOL_THREADS.load(Ordering::Relaxed), 0);
+        assert_eq!(thread_count(), std::thread::available_parallelism().map_or(1, |n| n.get()));
+    }
+}
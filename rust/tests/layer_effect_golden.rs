@@ -0,0 +1,349 @@
+//! Golden-file conformance tests for the layer effects.
+//!
+//! Every effect is rendered on a canonical input with a canonical parameter
+//! set and compared per pixel against the reference PNGs in
+//! `tests/golden/layer_effects/`:
+//! - `<effect>.png` - 8-bit RGBA, checked against the WASM u8 path
+//! - `<effect>_f32.png` - 16-bit RGBA, checked against the shared f32 core
+//!   (and the WASM f32 path where one exists)
+//!
+//! The Python bindings are checked against the same files by
+//! `python -m imagestag.parity.layer_effect_golden`, so drift between the
+//! Python and WASM implementations shows up as a failure on one side.
+//!
+//! After an intended rendering change, regenerate the references with
+//!
+//! ```text
+//! IMAGESTAG_UPDATE_GOLDEN=1 cargo test --no-default-features --features wasm,encode --test layer_effect_golden
+//! ```
+//!
+//! and review the changed PNGs before committing them.
+
+#![cfg(all(feature = "wasm", feature = "encode"))]
+
+use std::path::PathBuf;
+
+use image::{ImageBuffer, Rgba};
+use ndarray::{Array3, ArrayView3};
+
+use imagestag_rust::io::{decode_image_rgba, decode_image_u16, encode_png, PngOptions};
+use imagestag_rust::layer_effects::bevel_emboss::{bevel_emboss_f32, BevelEmbossParams};
+use imagestag_rust::layer_effects::cast_shadow::{cast_shadow_rgba_f32, CastShadowParams};
+use imagestag_rust::layer_effects::color_overlay::{color_overlay_f32, ColorOverlayParams};
+use imagestag_rust::layer_effects::gradient_overlay::{gradient_overlay_f32, parse_stops, GradientOverlayParams};
+use imagestag_rust::layer_effects::long_shadow::{long_shadow_rgba_f32, LongShadowParams};
+use imagestag_rust::layer_effects::pattern_overlay::{pattern_overlay_f32, PatternOverlayParams};
+use imagestag_rust::layer_effects::satin::{satin_f32, SatinParams};
+use imagestag_rust::wasm;
+
+/// Canonical input size (square, so expanded outputs stay square).
+const SIZE: usize = 40;
+/// Allowed per-channel difference, 8-bit references.
+const TOLERANCE_U8: u8 = 2;
+/// Allowed per-channel difference, 16-bit references (0.0-1.0 scale).
+const TOLERANCE_F32: f32 = 2.0 / 255.0;
+
+const UPDATE_ENV: &str = "IMAGESTAG_UPDATE_GOLDEN";
+
+type RenderU8 = fn(&[u8]) -> Vec<u8>;
+type RenderF32 = fn(ArrayView3<f32>) -> Array3<f32>;
+type RenderFlatF32 = fn(&[f32]) -> Vec<f32>;
+
+/// One effect with its canonical parameters.
+struct Case {
+    name: &'static str,
+    /// WASM u8 binding
+    wasm_u8: RenderU8,
+    /// Binding-independent f32 core, if the effect has one
+    core_f32: Option<RenderF32>,
+    /// WASM f32 binding, if the effect has one
+    wasm_f32: Option<RenderFlatF32>,
+}
+
+/// Anti-aliased disc with a color ramp and a transparent hole.
+fn canonical_input() -> Array3<u8> {
+    Array3::from_shape_fn((SIZE, SIZE, 4), |(y, x, c)| {
+        let (fx, fy) = (x as f32 + 0.5, y as f32 + 0.5);
+        let outer = (14.0 - (fx - 20.0).hypot(fy - 20.0)).clamp(0.0, 1.0);
+        let hole = ((fx - 24.0).hypot(fy - 16.0) - 4.0).clamp(0.0, 1.0);
+        let value = match c {
+            0 => fx / SIZE as f32,
+            1 => fy / SIZE as f32,
+            2 => 0.5,
+            _ => outer * hole,
+        };
+        (value * 255.0).round() as u8
+    })
+}
+
+/// 4x4 RGBA checkerboard with a half-transparent cell.
+fn checker_pattern() -> Array3<u8> {
+    Array3::from_shape_fn((4, 4, 4), |(y, x, c)| match (c, (x / 2 + y / 2) % 2) {
+        (3, _) if x == 0 && y == 0 => 128,
+        (3, _) => 255,
+        (0, 0) => 230,
+        (1, 0) => 60,
+        (_, 0) => 40,
+        (_, _) => 20,
+    })
+}
+
+const GRADIENT_STOPS: [f32; 12] = [0.0, 255.0, 200.0, 0.0, 0.5, 0.0, 160.0, 255.0, 1.0, 120.0, 0.0, 160.0];
+const SATIN_CONTOUR: [f32; 6] = [0.0, 0.0, 0.5, 1.0, 1.0, 0.0];
+
+fn gradient_params() -> GradientOverlayParams {
+    GradientOverlayParams {
+        stops: parse_stops(&GRADIENT_STOPS, 255.0),
+        style: "radial".into(),
+        opacity: 0.8,
+        blend_mode: "screen".into(),
+        ..Default::default()
+    }
+}
+
+fn pattern_params() -> PatternOverlayParams {
+    PatternOverlayParams {
+        pattern: checker_pattern().mapv(|v| v as f32 / 255.0),
+        scale: 1.5,
+        offset_x: 1,
+        offset_y: 0,
+        opacity: 0.9,
+        blend_mode: "overlay".into(),
+    }
+}
+
+fn satin_options() -> wasm::SatinOptions {
+    wasm::SatinOptions {
+        color_r: 40,
+        color_g: 0,
+        color_b: 90,
+        opacity: 0.7,
+        distance: 4.0,
+        size: 3.0,
+        contour: SATIN_CONTOUR.to_vec(),
+        blend_mode: "multiply".into(),
+        ..Default::default()
+    }
+}
+
+fn long_shadow_params() -> LongShadowParams {
+    LongShadowParams { angle: 135.0, length: 8.0, color: [0.1, 0.1, 0.2], fade: 0.5 }
+}
+
+fn long_shadow_options() -> wasm::LongShadowOptions {
+    wasm::LongShadowOptions { angle: 135.0, length: 8.0, color_r: 26, color_g: 26, color_b: 51, fade: 0.5 }
+}
+
+fn bevel_params() -> BevelEmbossParams {
+    BevelEmbossParams { depth: 3.0, size: 4.0, ..Default::default() }
+}
+
+fn bevel_options() -> wasm::BevelEmbossOptions {
+    wasm::BevelEmbossOptions { depth: 3.0, size: 4.0, ..Default::default() }
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            name: "drop_shadow",
+            wasm_u8: |d| wasm::drop_shadow_rgba_wasm(d, SIZE, SIZE, 3.0, 3.0, 2.0, 0, 0, 40, 0.75, false, "exact").unwrap_or_else(|_| panic!("invalid quality")),
+            core_f32: None,
+            wasm_f32: None,
+        },
+        Case {
+            name: "inner_shadow",
+            wasm_u8: |d| wasm::inner_shadow_rgba_wasm(d, SIZE, SIZE, 2.0, 2.0, 2.0, 0.0, 0, 0, 0, 0.75, "exact").unwrap_or_else(|_| panic!("invalid quality")),
+            core_f32: None,
+            wasm_f32: None,
+        },
+        Case {
+            name: "outer_glow",
+            wasm_u8: |d| wasm::outer_glow_rgba_wasm(d, SIZE, SIZE, 4.0, 255, 220, 0, 0.75, 0.0, "exact").unwrap_or_else(|_| panic!("invalid quality")),
+            core_f32: None,
+            wasm_f32: None,
+        },
+        Case {
+            name: "inner_glow",
+            wasm_u8: |d| wasm::inner_glow_rgba_wasm(d, SIZE, SIZE, 4.0, 255, 255, 190, 0.75, 0.0, "exact").unwrap_or_else(|_| panic!("invalid quality")),
+            core_f32: None,
+            wasm_f32: None,
+        },
+        Case {
+            name: "bevel_emboss",
+            wasm_u8: |d| wasm::bevel_emboss_rgba_options_wasm(d, SIZE, SIZE, &bevel_options()).unwrap_or_else(|_| panic!("invalid quality")),
+            core_f32: Some(|i| bevel_emboss_f32(&i.to_owned(), &bevel_params())),
+            wasm_f32: None,
+        },
+        Case {
+            name: "satin",
+            wasm_u8: |d| wasm::satin_rgba_options_wasm(d, SIZE, SIZE, &satin_options()),
+            core_f32: Some(|i| satin_f32(i, &SatinParams::from(&satin_options()))),
+            wasm_f32: Some(|d| wasm::satin_rgba_f32_wasm(d, SIZE, SIZE, &satin_options())),
+        },
+        Case {
+            name: "color_overlay",
+            wasm_u8: |d| {
+                let options = wasm::ColorOverlayOptions {
+                    color_r: 255,
+                    color_g: 64,
+                    color_b: 0,
+                    opacity: 0.7,
+                    blend_mode: "multiply".into(),
+                    preserve_luminosity: false,
+                };
+                wasm::color_overlay_rgba_options_wasm(d, SIZE, SIZE, &options)
+            },
+            core_f32: Some(|i| {
+                let params = ColorOverlayParams {
+                    color: [1.0, 64.0 / 255.0, 0.0],
+                    opacity: 0.7,
+                    blend_mode: "multiply".into(),
+                    preserve_luminosity: false,
+                };
+                color_overlay_f32(i, &params)
+            }),
+            wasm_f32: None,
+        },
+        Case {
+            name: "gradient_overlay",
+            wasm_u8: |d| {
+                let p = gradient_params();
+                wasm::gradient_overlay_rgba_wasm(
+                    d, SIZE, SIZE, &GRADIENT_STOPS, &p.style, p.angle, p.scale_x, p.scale_y, p.offset_x, p.offset_y,
+                    p.reverse, p.opacity, &p.blend_mode,
+                )
+            },
+            core_f32: Some(|i| gradient_overlay_f32(i, &gradient_params())),
+            wasm_f32: None,
+        },
+        Case {
+            name: "pattern_overlay",
+            wasm_u8: |d| {
+                let p = pattern_params();
+                let pattern = checker_pattern().into_raw_vec_and_offset().0;
+                wasm::pattern_overlay_rgba_wasm(d, SIZE, SIZE, &pattern, 4, 4, p.scale, p.offset_x, p.offset_y, p.opacity, &p.blend_mode)
+            },
+            core_f32: Some(|i| pattern_overlay_f32(i, &pattern_params())),
+            wasm_f32: None,
+        },
+        Case {
+            name: "stroke",
+            wasm_u8: |d| wasm::stroke_rgba_wasm(d, SIZE, SIZE, 2.0, 255, 0, 0, 1.0, "outside"),
+            core_f32: None,
+            wasm_f32: None,
+        },
+        Case {
+            name: "cast_shadow",
+            wasm_u8: |d| wasm::cast_shadow_rgba_wasm(d, SIZE, SIZE, &wasm::CastShadowOptions::default()),
+            core_f32: Some(|i| cast_shadow_rgba_f32(i, &CastShadowParams::from(&wasm::CastShadowOptions::default()))),
+            wasm_f32: None,
+        },
+        Case {
+            name: "long_shadow",
+            wasm_u8: |d| wasm::long_shadow_rgba_wasm(d, SIZE, SIZE, &long_shadow_options()),
+            core_f32: Some(|i| long_shadow_rgba_f32(i, &long_shadow_params())),
+            wasm_f32: None,
+        },
+    ]
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/layer_effects")
+}
+
+fn updating() -> bool {
+    std::env::var_os(UPDATE_ENV).is_some()
+}
+
+/// Shape a flat RGBA buffer from a square render.
+fn square(data: Vec<u8>) -> Array3<u8> {
+    let side = ((data.len() / 4) as f64).sqrt() as usize;
+    Array3::from_shape_vec((side, side, 4), data).expect("square RGBA output")
+}
+
+fn write_u8(name: &str, image: &Array3<u8>) {
+    let png = encode_png(image, &PngOptions::default()).expect("encode reference");
+    std::fs::write(golden_dir().join(format!("{name}.png")), png).expect("write reference");
+}
+
+fn write_f32(name: &str, image: &Array3<f32>) {
+    let (height, width, _) = image.dim();
+    let raw: Vec<u16> = image.iter().map(|v| (v.clamp(0.0, 1.0) * 65535.0).round() as u16).collect();
+    let buffer = ImageBuffer::<Rgba<u16>, _>::from_raw(width as u32, height as u32, raw).expect("16-bit buffer");
+    buffer.save(golden_dir().join(format!("{name}.png"))).expect("write reference");
+}
+
+fn read_u8(name: &str) -> Array3<u8> {
+    let path = golden_dir().join(format!("{name}.png"));
+    let data = std::fs::read(&path).unwrap_or_else(|_| panic!("missing reference {}, run with {UPDATE_ENV}=1", path.display()));
+    decode_image_rgba(&data).expect("decode reference").0
+}
+
+fn read_f32(name: &str) -> Array3<f32> {
+    let path = golden_dir().join(format!("{name}.png"));
+    let data = std::fs::read(&path).unwrap_or_else(|_| panic!("missing reference {}, run with {UPDATE_ENV}=1", path.display()));
+    decode_image_u16(&data).expect("decode reference").0.mapv(|v| v as f32 / 65535.0)
+}
+
+/// Largest per-channel difference, or `None` if the shapes differ.
+fn max_diff<A: Copy, B: Copy>(a: &Array3<A>, b: &Array3<B>, diff: impl Fn(A, B) -> f32) -> Option<f32> {
+    (a.dim() == b.dim()).then(|| a.iter().zip(b.iter()).map(|(&x, &y)| diff(x, y)).fold(0.0, f32::max))
+}
+
+fn check(failures: &mut Vec<String>, label: String, diff: Option<f32>, tolerance: f32) {
+    match diff {
+        None => failures.push(format!("{label}: size differs from reference")),
+        Some(d) if d > tolerance => failures.push(format!("{label}: max difference {d} > {tolerance}")),
+        Some(_) => {}
+    }
+}
+
+#[test]
+fn test_layer_effects_match_golden_files() {
+    let input = canonical_input();
+    let input_f32 = input.mapv(|v| v as f32 / 255.0);
+    let data = input.as_slice().expect("contiguous input").to_vec();
+    let data_f32 = input_f32.as_slice().expect("contiguous input").to_vec();
+
+    if updating() {
+        std::fs::create_dir_all(golden_dir()).expect("create golden directory");
+    }
+
+    let mut failures = Vec::new();
+    for case in cases() {
+        let rendered = square((case.wasm_u8)(&data));
+        if updating() {
+            write_u8(case.name, &rendered);
+        }
+        let diff = max_diff(&rendered, &read_u8(case.name), |a, b| a.abs_diff(b) as f32);
+        check(&mut failures, format!("{} (wasm u8)", case.name), diff, TOLERANCE_U8 as f32);
+
+        let f32_name = format!("{}_f32", case.name);
+        if let Some(render) = case.core_f32 {
+            let rendered = render(input_f32.view());
+            if updating() {
+                write_f32(&f32_name, &rendered);
+            }
+            let reference = read_f32(&f32_name);
+            check(&mut failures, format!("{} (core f32)", case.name), max_diff(&rendered, &reference, |a, b| (a - b).abs()), TOLERANCE_F32);
+
+            if let Some(render_wasm) = case.wasm_f32 {
+                let flat = render_wasm(&data_f32);
+                let side = ((flat.len() / 4) as f64).sqrt() as usize;
+                let rendered = Array3::from_shape_vec((side, side, 4), flat).expect("square RGBA output");
+                check(&mut failures, format!("{} (wasm f32)", case.name), max_diff(&rendered, &reference, |a, b| (a - b).abs()), TOLERANCE_F32);
+            }
+        }
+    }
+    assert!(failures.is_empty(), "golden mismatches:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_golden_input_is_canonical() {
+    // The Python runner reads input.png, so it must match the generator
+    if updating() {
+        std::fs::create_dir_all(golden_dir()).expect("create golden directory");
+        write_u8("input", &canonical_input());
+    }
+    assert_eq!(read_u8("input"), canonical_input());
+}
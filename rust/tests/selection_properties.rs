@@ -0,0 +1,180 @@
+//! Property tests for the selection and contour code.
+//!
+//! Feeds random masks, images and parameters to the magic wand, both
+//! contour extractors, Douglas-Peucker and the SVG writer, looking for
+//! panics, out-of-range output and runaway loops. Degenerate inputs
+//! (empty, 1-pixel and 1-row masks, short buffers, negative epsilon) are
+//! generated on purpose.
+//!
+//! ```text
+//! cargo test --no-default-features --test selection_properties
+//! PROPTEST_CASES=5000 cargo test --no-default-features --test selection_properties
+//! ```
+
+use proptest::prelude::*;
+
+use imagestag_rust::selection::magic_wand::magic_wand_select_detailed;
+use imagestag_rust::selection::{
+    contours_to_svg, douglas_peucker, douglas_peucker_closed, extract_contours, extract_contours_precise, Contour,
+    Point,
+};
+
+/// Mask of up to 12x12 pixels, mostly 0/255 with some soft values.
+fn mask() -> impl Strategy<Value = (Vec<u8>, usize, usize)> {
+    (0usize..=12, 0usize..=12).prop_flat_map(|(width, height)| {
+        let value = prop_oneof![4 => Just(0u8), 4 => Just(255u8), 1 => any::<u8>()];
+        (prop::collection::vec(value, width * height), Just(width), Just(height))
+    })
+}
+
+/// RGBA image of up to 12x12 pixels from a small palette, so regions form.
+fn image() -> impl Strategy<Value = (Vec<u8>, usize, usize)> {
+    (0usize..=12, 0usize..=12).prop_flat_map(|(width, height)| {
+        let pixel = prop_oneof![Just([0u8, 0, 0, 255]), Just([255, 255, 255, 255]), Just([0, 0, 0, 0]), any::<[u8; 4]>()];
+        (prop::collection::vec(pixel, width * height), Just(width), Just(height))
+            .prop_map(|(pixels, w, h)| (pixels.concat(), w, h))
+    })
+}
+
+fn polyline() -> impl Strategy<Value = Vec<Point>> {
+    let coord = prop_oneof![-50.0f32..50.0, Just(0.0f32), Just(1.0f32)];
+    prop::collection::vec((coord.clone(), coord).prop_map(|(x, y)| Point::new(x, y)), 0..40)
+}
+
+/// Parse the flat `[count, len, x, y, ...]` output of `extract_contours`.
+fn parse_flat(flat: &[f32]) -> Vec<Vec<(f32, f32)>> {
+    let mut contours = Vec::new();
+    let mut pos = 1;
+    for _ in 0..flat[0] as usize {
+        let len = flat[pos] as usize;
+        let points = flat[pos + 1..pos + 1 + 2 * len].chunks(2).map(|p| (p[0], p[1])).collect();
+        contours.push(points);
+        pos += 1 + 2 * len;
+    }
+    assert_eq!(pos, flat.len(), "trailing data in flat contour list");
+    contours
+}
+
+proptest! {
+    #[test]
+    fn magic_wand_mask_is_consistent(
+        (data, width, height) in image(),
+        start in (0usize..14, 0usize..14),
+        tolerance in any::<u8>(),
+    ) {
+        let (start_x, start_y) = start;
+        let contiguous = magic_wand_select_detailed(&data, width, height, start_x, start_y, tolerance, true);
+        let global = magic_wand_select_detailed(&data, width, height, start_x, start_y, tolerance, false);
+        for result in [&contiguous, &global] {
+            prop_assert_eq!(result.mask.len(), width * height);
+            prop_assert!(result.mask.iter().all(|&v| v == 0 || v == 255));
+            prop_assert_eq!(result.pixel_count, result.mask.iter().filter(|&&v| v == 255).count());
+            if let Some((x, y, w, h)) = result.bounds {
+                prop_assert!(x + w <= width && y + h <= height);
+                for (i, _) in result.mask.iter().enumerate().filter(|(_, &v)| v == 255) {
+                    prop_assert!((x..x + w).contains(&(i % width)) && (y..y + h).contains(&(i / width)));
+                }
+            } else {
+                prop_assert_eq!(result.pixel_count, 0);
+            }
+        }
+        // The start pixel always matches itself; contiguous selects a subset
+        let inside = start_x < width && start_y < height;
+        prop_assert_eq!(contiguous.pixel_count > 0, inside);
+        prop_assert!(contiguous.mask.iter().zip(&global.mask).all(|(&c, &g)| c <= g));
+    }
+
+    #[test]
+    fn magic_wand_rejects_short_buffers((data, width, height) in image(), cut in 1usize..8) {
+        let short = &data[..data.len().saturating_sub(cut)];
+        let result = magic_wand_select_detailed(short, width, height, 0, 0, 10, true);
+        prop_assert_eq!(result.mask.len(), width * height);
+        prop_assert_eq!(result.pixel_count, 0);
+    }
+
+    #[test]
+    fn traced_contours_stay_on_selected_pixels((data, width, height) in mask()) {
+        let flat = extract_contours(&data, width, height);
+        for contour in parse_flat(&flat) {
+            prop_assert!(!contour.is_empty());
+            for (x, y) in contour {
+                // Points are pixel centers of selected pixels
+                let (px, py) = ((x - 0.5) as usize, (y - 0.5) as usize);
+                prop_assert!(px < width && py < height);
+                prop_assert!(data[py * width + px] > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn precise_contours_stay_inside_the_mask(
+        (data, width, height) in mask(),
+        threshold in 0.0f32..1.0,
+        epsilon in prop_oneof![Just(0.0f32), 0.0f32..3.0, -1.0f32..0.0],
+        fit_beziers in any::<bool>(),
+    ) {
+        let raw = extract_contours_precise(&data, width, height, threshold, 0.0, false, 0.25);
+        let simplified = extract_contours_precise(&data, width, height, threshold, epsilon, fit_beziers, 0.25);
+        prop_assert_eq!(raw.len(), simplified.len());
+        for (raw, contour) in raw.iter().zip(&simplified) {
+            prop_assert!(raw.points.len() >= 3);
+            // Closed contours may gain the repeated start point
+            prop_assert!(contour.points.len() <= raw.points.len() + 1);
+            for p in &contour.points {
+                prop_assert!(p.x.is_finite() && p.y.is_finite());
+                prop_assert!(p.x >= 0.0 && p.y >= 0.0 && p.x <= (width - 1) as f32 && p.y <= (height - 1) as f32);
+            }
+            prop_assert_eq!(contour.beziers.is_some(), fit_beziers);
+        }
+        let svg = contours_to_svg(&simplified, width, height, "#000", Some("red"), 1.0, None);
+        prop_assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        prop_assert_eq!(svg.matches("<path").count(), simplified.len());
+    }
+
+    #[test]
+    fn douglas_peucker_keeps_endpoints_and_subset(points in polyline(), epsilon in -2.0f32..5.0) {
+        let simplified = douglas_peucker(&points, epsilon);
+        prop_assert!(simplified.len() <= points.len());
+        prop_assert_eq!(simplified.first(), points.first());
+        prop_assert_eq!(simplified.last(), points.last());
+        prop_assert!(simplified.iter().all(|p| points.contains(p)));
+
+        let closed = douglas_peucker_closed(&points, epsilon);
+        prop_assert!(closed.iter().all(|p| points.contains(p)));
+        if points.len() >= 4 {
+            prop_assert_eq!(closed.first(), closed.last());
+        }
+    }
+
+    #[test]
+    fn svg_writer_handles_arbitrary_contours(
+        contours in prop::collection::vec((polyline(), any::<bool>()), 0..5),
+        width in 0usize..100,
+        height in 0usize..100,
+    ) {
+        let contours: Vec<Contour> = contours.into_iter().map(|(points, closed)| Contour::new(points, closed)).collect();
+        let svg = contours_to_svg(&contours, width, height, "#fff", None, 0.0, Some("black"));
+        prop_assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        prop_assert!(svg.matches("<path").count() <= contours.len());
+    }
+}
+
+#[test]
+fn degenerate_masks() {
+    for (width, height) in [(0, 0), (0, 5), (5, 0), (1, 1), (1, 7), (7, 1)] {
+        for value in [0u8, 255] {
+            let data = vec![value; width * height];
+            let flat = extract_contours(&data, width, height);
+            let traced = parse_flat(&flat);
+            assert_eq!(traced.len(), usize::from(value > 0 && width * height > 0), "{}x{} = {}", width, height, value);
+            // Marching squares needs a 2x2 cell and yields nothing here
+            assert!(extract_contours_precise(&data, width, height, 0.5, 1.0, true, 0.25).is_empty());
+            // Mask shorter than width * height is ignored, not indexed
+            assert_eq!(extract_contours(&data[..data.len().saturating_sub(1)], width, height), [0.0]);
+        }
+    }
+    assert!(douglas_peucker(&[], 1.0).is_empty());
+    assert!(douglas_peucker_closed(&[Point::new(1.0, 1.0)], 1.0).len() == 1);
+    let single = magic_wand_select_detailed(&[10, 20, 30, 255], 1, 1, 0, 0, 0, true);
+    assert_eq!((single.pixel_count, single.bounds), (1, Some((0, 0, 1, 1))));
+}
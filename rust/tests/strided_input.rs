@@ -0,0 +1,96 @@
+//! Filters on non-contiguous input.
+//!
+//! NumPy hands the bindings sliced (`img[::2, ::2]`), Fortran-order and
+//! negatively strided (`img[::-1]`) arrays as strided views. Every kernel
+//! here must give the same result on such a view as on a C-order copy.
+//!
+//! ```text
+//! cargo test --no-default-features --test strided_input
+//! ```
+
+use ndarray::{s, Array3, ArrayView3, Axis, ShapeBuilder};
+
+use imagestag_rust::filters::{basic, color_adjust, color_science, grayscale, levels_curves, rotate, stylize};
+
+fn pattern(height: usize, width: usize, channels: usize) -> Array3<u8> {
+    Array3::from_shape_fn((height, width, channels), |(y, x, c)| ((y * 31 + x * 17 + c * 71) % 256) as u8)
+}
+
+/// Strided views of `image` and of its Fortran-order copy: every second
+/// pixel, Fortran order, flipped on both axes, and transposed.
+fn views<'a, T>(image: &'a Array3<T>, fortran: &'a Array3<T>) -> Vec<(&'static str, ArrayView3<'a, T>)> {
+    let mut flipped = image.view();
+    flipped.invert_axis(Axis(0));
+    flipped.invert_axis(Axis(1));
+    vec![
+        ("sliced", image.slice(s![..;2, ..;2, ..])),
+        ("fortran", fortran.view()),
+        ("negative", flipped),
+        ("transposed", image.view().permuted_axes([1, 0, 2])),
+    ]
+}
+
+fn fortran_copy<T: Copy + Default>(image: &Array3<T>) -> Array3<T> {
+    let mut fortran = Array3::from_elem(image.dim().f(), T::default());
+    fortran.assign(image);
+    fortran
+}
+
+/// Check `filter` on u8 and f32 views of 1, 3 and 4 channel images.
+fn check(
+    name: &str,
+    filter_u8: impl Fn(ArrayView3<u8>) -> Array3<u8>,
+    filter_f32: impl Fn(ArrayView3<f32>) -> Array3<f32>,
+) {
+    for channels in [1, 3, 4] {
+        let image = pattern(9, 7, channels);
+        let image_f32 = image.mapv(|v| v as f32 / 255.0);
+        let (fortran, fortran_f32) = (fortran_copy(&image), fortran_copy(&image_f32));
+        for ((layout, view), (_, view_f32)) in views(&image, &fortran).into_iter().zip(views(&image_f32, &fortran_f32))
+        {
+            assert!(!view.is_standard_layout(), "{} view should be strided", layout);
+            let expected = filter_u8(view.as_standard_layout().view());
+            assert_eq!(filter_u8(view), expected, "{} u8 {} {}ch", name, layout, channels);
+            let expected = filter_f32(view_f32.as_standard_layout().view());
+            assert_eq!(filter_f32(view_f32), expected, "{} f32 {} {}ch", name, layout, channels);
+        }
+    }
+}
+
+#[test]
+fn rotate_and_flip() {
+    check("rotate_90_cw", rotate::rotate_90_cw_u8, rotate::rotate_90_cw_f32);
+    check("rotate_180", rotate::rotate_180_u8, rotate::rotate_180_f32);
+    check("rotate_270_cw", rotate::rotate_270_cw_u8, rotate::rotate_270_cw_f32);
+    check("flip_horizontal", rotate::flip_horizontal_u8, rotate::flip_horizontal_f32);
+    check("flip_vertical", rotate::flip_vertical_u8, rotate::flip_vertical_f32);
+}
+
+#[test]
+fn per_pixel_filters() {
+    check("grayscale", grayscale::grayscale_u8, grayscale::grayscale_f32);
+    check("brightness", |i| color_adjust::brightness_u8(i, 0.2), |i| color_adjust::brightness_f32(i, 0.2));
+    check("contrast", |i| color_adjust::contrast_u8(i, 0.3), |i| color_adjust::contrast_f32(i, 0.3));
+    check("saturation", |i| color_adjust::saturation_u8(i, 0.5), |i| color_adjust::saturation_f32(i, 0.5));
+    check("gamma", |i| color_adjust::gamma_u8(i, 1.8), |i| color_adjust::gamma_f32(i, 1.8));
+    check("invert", |i| color_adjust::invert_u8(i), |i| color_adjust::invert_f32(i));
+    check("hue_shift", |i| color_science::hue_shift_u8(i, 90.0), |i| color_science::hue_shift_f32(i, 90.0));
+    check("sepia", |i| color_science::sepia_u8(i, 0.8), |i| color_science::sepia_f32(i, 0.8));
+    check("posterize", |i| stylize::posterize_u8(i, 4), |i| stylize::posterize_f32(i, 4));
+    check("threshold", |i| stylize::threshold_u8(i, 100), |i| stylize::threshold_f32(i, 0.4));
+    check(
+        "levels",
+        |i| levels_curves::levels_u8(i, 20, 230, 0, 255, 1.2),
+        |i| levels_curves::levels_f32(i, 0.1, 0.9, 0.0, 1.0, 1.2),
+    );
+    check(
+        "alpha_from_luminance",
+        |i| basic::alpha_from_luminance_u8(i, false),
+        |i| basic::alpha_from_luminance_f32(i, false),
+    );
+    check(
+        "swap_channels",
+        |i| basic::swap_channels(i, &[0, 0]).unwrap(),
+        |i| basic::swap_channels(i, &[0, 0]).unwrap(),
+    );
+}
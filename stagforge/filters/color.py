@@ -92,7 +92,7 @@ class HueSaturationFilter(BaseFilter):
     name: ClassVar[str] = "HSL / Color"
     description: ClassVar[str] = "Adjust hue, saturation, lightness, vibrance, and temperature"
     category: ClassVar[str] = "color"
-    VERSION: ClassVar[int] = 2
+    VERSION: ClassVar[int] = 3
 
     hue: int = Field(default=0, ge=-180, le=180,
                      json_schema_extra={"step": 1, "suffix": "\u00b0",
@@ -103,23 +103,25 @@ class HueSaturationFilter(BaseFilter):
     lightness: int = Field(default=0, ge=-100, le=100,
                            json_schema_extra={"step": 1, "suffix": "%",
                                               "display_name": "Lightness"})
+    colorize: bool = Field(default=False,
+                           json_schema_extra={"display_name": "Colorize"})
     vibrance: int = Field(default=0, ge=-100, le=100,
                           json_schema_extra={"step": 1, "suffix": "%",
                                              "display_name": "Vibrance"})
+    protect_skin: int = Field(default=0, ge=0, le=100,
+                              json_schema_extra={"step": 1, "suffix": "%",
+                                                 "display_name": "Protect Skin"})
     temperature: int = Field(default=0, ge=-100, le=100,
                              json_schema_extra={"step": 1,
                                                 "display_name": "Temperature"})
 
     def apply(self, image: np.ndarray) -> np.ndarray:
         result = image
-        if self.hue != 0:
-            result = imagestag_rust.hue_shift(result, float(self.hue))
-        if self.saturation != 0:
-            result = imagestag_rust.saturation(result, self.saturation / 100.0)
-        if self.lightness != 0:
-            result = imagestag_rust.brightness(result, self.lightness / 100.0)
+        if self.hue != 0 or self.saturation != 0 or self.lightness != 0 or self.colorize:
+            result = imagestag_rust.hsl_adjust(result, float(self.hue), self.saturation / 100.0,
+                                               self.lightness / 100.0, self.colorize)
         if self.vibrance != 0:
-            result = imagestag_rust.vibrance(result, self.vibrance / 100.0)
+            result = imagestag_rust.vibrance(result, self.vibrance / 100.0, self.protect_skin / 100.0)
         if self.temperature != 0:
             result = imagestag_rust.temperature(result, self.temperature / 100.0)
         return result
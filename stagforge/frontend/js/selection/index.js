@@ -113,21 +113,30 @@ export function extractContours(mask, width, height) {
  * @param {Object} [options] - Selection options
  * @param {number} [options.tolerance=32] - Color tolerance (0-255)
  * @param {boolean} [options.contiguous=true] - Only select connected pixels
- * @returns {Uint8Array} Selection mask (255 = selected, 0 = not selected)
+ * @param {boolean} [options.autoTolerance=false] - Choose the tolerance from the
+ *   colors around the start point instead of using `tolerance`
+ * @param {boolean} [options.antialias=false] - Give edge pixels fractional coverage
+ * @returns {Uint8Array} Selection mask (255 = selected, 0 = not selected, in
+ *   between on anti-aliased edges)
  */
 export function magicWandSelect(imageData, width, height, startX, startY, options = {}) {
     if (!_initialized) {
         throw new Error('SelectionWASM not initialized. Call initSelection() first.');
     }
 
-    const { tolerance = 32, contiguous = true } = options;
+    const { contiguous = true, autoTolerance = false, antialias = false } = options;
 
     // Convert Uint8ClampedArray to Uint8Array if needed
     const u8 = imageData instanceof Uint8Array ? imageData :
         new Uint8Array(imageData.buffer, imageData.byteOffset, imageData.byteLength);
 
+    const tolerance = autoTolerance
+        ? wasm.magic_wand_auto_tolerance_wasm(u8, width, height, startX, startY)
+        : (options.tolerance ?? 32);
+
     // Call WASM function
-    const mask = wasm.magic_wand_select_wasm(u8, width, height, startX, startY, tolerance, contiguous);
+    const select = antialias ? wasm.magic_wand_select_soft_wasm : wasm.magic_wand_select_wasm;
+    const mask = select(u8, width, height, startX, startY, tolerance, contiguous);
 
     return new Uint8Array(mask);
 }
@@ -0,0 +1,88 @@
+"""
+Tests for EffectResult placement and cropping.
+
+Effects that grow the canvas return their output with negative offsets;
+clipped() must map it back onto the layer bounds pixel for pixel.
+"""
+
+import numpy as np
+import pytest
+
+from imagestag.layer_effects import EffectResult, OuterGlow
+
+
+def make_output(height, width, channels=4):
+    """Output whose every pixel encodes its own (y, x) position."""
+    ys, xs = np.mgrid[0:height, 0:width]
+    image = np.zeros((height, width, channels), dtype=np.uint8)
+    image[..., 0] = ys
+    image[..., 1] = xs
+    image[..., 3] = 255
+    return image
+
+
+class TestClipped:
+    def test_grown_canvas_is_cropped_to_layer(self):
+        # 3px expansion on the left, 2px on top, as after an outer effect
+        output = make_output(14, 16)
+        result = EffectResult(image=output, offset_x=-3, offset_y=-2).clipped(10, 8)
+        assert result.image.shape == (8, 10, 4)
+        assert (result.offset_x, result.offset_y) == (0, 0)
+        np.testing.assert_array_equal(result.image, output[2:10, 3:13])
+
+    def test_symmetric_expansion_round_trip(self):
+        layer = make_output(6, 9)
+        expand = 5
+        grown = np.zeros((6 + 2 * expand, 9 + 2 * expand, 4), dtype=np.uint8)
+        grown[expand:expand + 6, expand:expand + 9] = layer
+        result = EffectResult(image=grown, offset_x=-expand, offset_y=-expand).clipped(9, 6)
+        np.testing.assert_array_equal(result.image, layer)
+
+    def test_output_smaller_than_layer(self):
+        # Output covers only the top-left corner of the layer
+        output = make_output(4, 5)
+        result = EffectResult(image=output, offset_x=-2, offset_y=-1).clipped(10, 8)
+        assert result.image.shape == (8, 10, 4)
+        np.testing.assert_array_equal(result.image[0:3, 0:3], output[1:4, 2:5])
+        assert not result.image[3:, :].any()
+        assert not result.image[:, 3:].any()
+
+    def test_mixed_sign_offsets(self):
+        # Shifted left past the layer edge and down into it
+        output = make_output(8, 12)
+        result = EffectResult(image=output, offset_x=-4, offset_y=3).clipped(10, 8)
+        np.testing.assert_array_equal(result.image[3:8, 0:8], output[0:5, 4:12])
+        assert not result.image[0:3].any()
+        assert not result.image[:, 8:].any()
+
+    def test_no_overlap_is_transparent(self):
+        output = make_output(4, 4)
+        for offset in [(-4, 0), (0, -4), (-10, -10), (10, 0)]:
+            result = EffectResult(image=output, offset_x=offset[0], offset_y=offset[1]).clipped(6, 5)
+            assert result.image.shape == (5, 6, 4)
+            assert not result.image.any()
+
+    def test_keeps_dtype_and_channels(self):
+        output = np.ones((10, 10, 3), dtype=np.float32)
+        result = EffectResult(image=output, offset_x=-1, offset_y=-1).clipped(4, 3)
+        assert result.image.dtype == np.float32
+        assert result.image.shape == (3, 4, 3)
+        assert (result.image == 1.0).all()
+
+
+class TestOuterGlowExpansion:
+    @pytest.mark.parametrize("blur,spread", [(4.0, 0.0), (3.0, 0.5), (8.0, 1.0), (5.0, -0.5)])
+    def test_glow_fits_expanded_canvas(self, blur, spread):
+        pytest.importorskip("imagestag.imagestag_rust")
+        image = np.zeros((16, 20, 4), dtype=np.uint8)
+        image[..., 3] = 255
+        effect = OuterGlow(blur=blur, spread=spread, color_opacity=1.0)
+        result = effect.apply(image)
+        expand = effect.get_expansion().left
+        assert result.image.shape == (16 + 2 * expand, 20 + 2 * expand, 4)
+        assert (result.offset_x, result.offset_y) == (-expand, -expand)
+        border = np.concatenate([
+            result.image[0, :, 3], result.image[-1, :, 3],
+            result.image[:, 0, 3], result.image[:, -1, 3],
+        ])
+        assert border.max() == 0
@@ -152,6 +152,36 @@ class TestGrayscaleFilter:
             grayscale(img)
 
 
+class TestGrayscaleSingleChannel:
+    """Test (H, W, 1) luminance output."""
+
+    def test_matches_grayscale(self):
+        """Values equal the first channel of the RGBA grayscale result."""
+        from imagestag.filters.grayscale import grayscale, grayscale_to_single_channel
+
+        rng = np.random.default_rng(3)
+        img = rng.integers(0, 256, (12, 9, 4), dtype=np.uint8)
+        result = grayscale_to_single_channel(img)
+        assert result.shape == (12, 9, 1)
+        np.testing.assert_array_equal(result[:, :, 0], grayscale(img)[:, :, 0])
+
+    def test_weighted_f32(self):
+        """Custom weights, f32: pure red with red-only weights stays 1.0."""
+        from imagestag.filters.grayscale import grayscale_to_single_channel_weighted_f32
+
+        img = np.zeros((4, 4, 3), dtype=np.float32)
+        img[:, :, 0] = 1.0
+        result = grayscale_to_single_channel_weighted_f32(img, 1.0, 0.0, 0.0)
+        assert result.shape == (4, 4, 1)
+        assert np.allclose(result, 1.0)
+
+    def test_invalid_dtype(self):
+        from imagestag.filters.grayscale import grayscale_to_single_channel_f32
+
+        with pytest.raises(ValueError):
+            grayscale_to_single_channel_f32(np.zeros((4, 4, 4), dtype=np.uint8))
+
+
 class TestGrayscaleRustOnly:
     """Verify grayscale uses Rust backend (no fallback)."""
 
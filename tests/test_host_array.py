@@ -0,0 +1,95 @@
+"""Tests for array-protocol input to the Rust bindings.
+
+Bindings accept CuPy and other non-numpy arrays by copying them to host
+memory first. The fakes below stand in for device arrays so the tests run
+without a GPU.
+"""
+import numpy as np
+import pytest
+
+imagestag_rust = pytest.importorskip("imagestag.imagestag_rust")
+
+
+class FakeDeviceArray:
+    """Mimics a CuPy array: device memory that downloads with get()."""
+
+    def __init__(self, array):
+        self._array = array
+        self.get_calls = 0
+
+    @property
+    def __cuda_array_interface__(self):
+        return {"shape": self._array.shape, "typestr": self._array.dtype.str, "version": 3}
+
+    def get(self):
+        self.get_calls += 1
+        return self._array.copy()
+
+
+class ArrayLike:
+    """Exposes only __array__, like many third-party containers."""
+
+    def __init__(self, array):
+        self._array = array
+
+    def __array__(self, dtype=None, copy=None):
+        return self._array if dtype is None else self._array.astype(dtype)
+
+
+def make_image(seed=0):
+    rng = np.random.default_rng(seed)
+    return rng.integers(0, 256, (12, 10, 4), dtype=np.uint8)
+
+
+class TestSingleArguments:
+    def test_device_array_is_downloaded(self):
+        image = make_image()
+        device = FakeDeviceArray(image)
+        result = imagestag_rust.invert_rgba(device)
+        np.testing.assert_array_equal(result, imagestag_rust.invert_rgba(image))
+        assert device.get_calls == 1
+
+    def test_array_protocol_object(self):
+        image = make_image(1)
+        result = imagestag_rust.invert_rgba(ArrayLike(image))
+        np.testing.assert_array_equal(result, imagestag_rust.invert_rgba(image))
+
+    def test_optional_mask_argument(self):
+        image = make_image(2)
+        mask = np.zeros(image.shape[:2], dtype=np.uint8)
+        mask[2:6, 3:8] = 255
+        expected = imagestag_rust.average_color(image, mask)
+        assert imagestag_rust.average_color(FakeDeviceArray(image), ArrayLike(mask)) == expected
+
+    def test_wrong_dtype_still_rejected(self):
+        image = make_image().astype(np.float32) / 255.0
+        with pytest.raises(TypeError):
+            imagestag_rust.invert_rgba(FakeDeviceArray(image))
+
+    def test_to_host_array(self):
+        image = make_image(3)
+        host = imagestag_rust.to_host_array(FakeDeviceArray(image))
+        assert isinstance(host, np.ndarray)
+        np.testing.assert_array_equal(host, image)
+
+
+class TestListArguments:
+    def test_list_of_device_arrays(self):
+        images = [make_image(seed) for seed in range(3)]
+        expected = imagestag_rust.batch_filter(images, "invert")
+        result = imagestag_rust.batch_filter([FakeDeviceArray(image) for image in images], "invert")
+        assert len(result) == len(expected)
+        for got, want in zip(result, expected):
+            np.testing.assert_array_equal(got, want)
+
+    def test_mixed_list(self):
+        images = [make_image(seed) for seed in range(3)]
+        mixed = [images[0], FakeDeviceArray(images[1]), ArrayLike(images[2])]
+        result = imagestag_rust.batch_filter(mixed, "invert")
+        for got, image in zip(result, images):
+            np.testing.assert_array_equal(got, imagestag_rust.invert_rgba(image))
+
+    def test_stacked_device_array(self):
+        stack = np.stack([make_image(seed) for seed in range(2)])
+        result = imagestag_rust.batch_filter(FakeDeviceArray(stack), "invert")
+        np.testing.assert_array_equal(result, imagestag_rust.batch_filter(stack, "invert"))
@@ -6,6 +6,8 @@ Tests cover:
 - All pixel formats (gray u8/f32, RGB u8/f32, RGBA u8/f32)
 - Dimension changes (90/270 swap dimensions)
 - Pixel position correctness
+- Strided input (slices, Fortran order, negative strides)
+- Animation frame stacks (N, H, W, C)
 """
 import numpy as np
 import pytest
@@ -257,6 +259,96 @@ class TestF32Functions:
         assert result.dtype == np.float32
 
 
+def _strided_views(img):
+    """Non-contiguous views of ``img`` as NumPy hands them out."""
+    return {
+        "sliced": img[::2, ::2],
+        "fortran": np.asfortranarray(img),
+        "negative": img[::-1, ::-1],
+        "channel_reversed": img[:, :, ::-1],
+    }
+
+
+class TestStridedInput:
+    """Views that aren't C-contiguous give the same result as a copy."""
+
+    @pytest.mark.parametrize("func,reference", [
+        (rotate_90_cw, lambda a: np.rot90(a, -1)),
+        (rotate_180, lambda a: np.rot90(a, 2)),
+        (rotate_270_cw, lambda a: np.rot90(a, 1)),
+        (flip_horizontal, np.fliplr),
+        (flip_vertical, np.flipud),
+    ])
+    def test_rotate_and_flip_u8(self, func, reference):
+        img = np.arange(9 * 7 * 4, dtype=np.uint8).reshape(9, 7, 4)
+        for name, view in _strided_views(img).items():
+            assert not view.flags.c_contiguous, name
+            np.testing.assert_array_equal(func(view), reference(view), err_msg=name)
+
+    @pytest.mark.parametrize("func,reference", [
+        (rotate_90_cw_f32, lambda a: np.rot90(a, -1)),
+        (flip_horizontal_f32, np.fliplr),
+        (flip_vertical_f32, np.flipud),
+    ])
+    def test_rotate_and_flip_f32(self, func, reference):
+        img = np.linspace(0, 1, 9 * 7 * 3, dtype=np.float32).reshape(9, 7, 3)
+        for name, view in _strided_views(img).items():
+            np.testing.assert_array_equal(func(view), reference(view), err_msg=name)
+
+    @pytest.mark.parametrize("name,args", [
+        ("grayscale_rgba", ()),
+        ("brightness", (0.2,)),
+        ("contrast", (0.3,)),
+        ("saturation", (0.5,)),
+        ("invert", ()),
+        ("posterize", (4,)),
+        ("threshold", (100,)),
+    ])
+    def test_per_pixel_filters(self, name, args):
+        import imagestag_rust
+        func = getattr(imagestag_rust, name)
+        img = np.arange(9 * 7 * 4, dtype=np.uint8).reshape(9, 7, 4)
+        for layout, view in _strided_views(img).items():
+            expected = func(np.ascontiguousarray(view), *args)
+            np.testing.assert_array_equal(func(view, *args), expected, err_msg=layout)
+
+
+class TestFrameStacks:
+    """(N, H, W, C) stacks give the same frames as per-frame calls."""
+
+    @pytest.mark.parametrize("func", [
+        rotate_90_cw, rotate_180, rotate_270_cw, flip_horizontal, flip_vertical,
+    ])
+    def test_rotate_and_flip(self, func):
+        frames = np.arange(5 * 9 * 7 * 4, dtype=np.uint8).reshape(5, 9, 7, 4)
+        result = func(frames)
+        assert result.shape == (5,) + func(frames[0]).shape
+        for frame, expected in zip(frames, result):
+            np.testing.assert_array_equal(func(frame), expected)
+
+    def test_rotate_f32_by_degrees(self):
+        frames = np.random.default_rng(1).random((3, 6, 4, 3), dtype=np.float32)
+        result = rotate_f32(frames, 270)
+        assert result.shape == (3, 4, 6, 3)
+        np.testing.assert_array_equal(result[2], rotate_f32(frames[2], 270))
+
+    @pytest.mark.parametrize("name,args", [
+        ("grayscale_rgba", ()),
+        ("brightness", (0.2,)),
+        ("hue_shift", (90.0,)),
+        ("posterize", (4,)),
+        ("levels", (20, 230, 0, 255, 1.2)),
+    ])
+    def test_per_pixel_filters(self, name, args):
+        import imagestag_rust
+        func = getattr(imagestag_rust, name)
+        frames = np.arange(4 * 5 * 6 * 4, dtype=np.uint8).reshape(4, 5, 6, 4)
+        result = func(frames, *args)
+        assert result.shape == frames.shape
+        for frame, expected in zip(frames, result):
+            np.testing.assert_array_equal(func(frame, *args), expected)
+
+
 class TestValidation:
     """Test input validation."""
 
@@ -161,6 +161,16 @@ class TestBlurFilters:
         blurred = rust.gaussian_blur_rgba(img, 2.0)
         assert blurred.shape == img.shape
 
+    def test_blur_quality_names(self):
+        """Quality names are case-insensitive and unknown ones are rejected."""
+        img = create_test_image(40, 40, 'square')
+        fast = rust.gaussian_blur_rgba(img, 2.0, quality='Fast')
+        assert fast.shape == img.shape
+        with pytest.raises(ValueError):
+            rust.gaussian_blur_rgba(img, 2.0, quality='fastest')
+        with pytest.raises(ValueError):
+            rust.drop_shadow_rgba(img, quality='bogus')
+
     def test_box_blur_averages_region(self):
         """Box blur should average neighboring pixels."""
         # Use opaque black background so pixels participate in averaging